@@ -10,6 +10,10 @@ from datetime import datetime
 from pathlib import Path
 from typing import Any
 
+from extensions.i18n import Translator
+from extensions.knowledge.class_loader import ClassLoader
+from extensions.knowledge.taxonomy import TAXONOMY, facet_counts, filter_by_tags, hypothesis_tags
+from extensions.static.confidence import confidence_level, meets_confidence
 from llm.unified_client import UnifiedLLMClient
 from llm.local_provider import is_local_url
 
@@ -17,12 +21,19 @@ from llm.local_provider import is_local_url
 class ReportGenerator:
     """Generate professional security audit reports."""
     
-    def __init__(self, project_dir: Path, config: dict, debug: bool = False, include_all: bool = False):
+    messages = Translator()  # English unless a locale is passed
+    
+    def __init__(self, project_dir: Path, config: dict, debug: bool = False, include_all: bool = False,
+                 tags: list[str] | None = None, locale: str | None = None, min_confidence: float | None = None):
         """Initialize report generator."""
         self.project_dir = project_dir
         self.config = config
         self.debug = debug
         self.include_all = include_all  # Flag to include all hypotheses, not just confirmed
+        self.tags = tags or []  # Only report findings carrying one of these taxonomy tags
+        self.min_confidence = min_confidence  # Only report findings at or above this confidence
+        self.messages = Translator(locale)  # Report language (en, zh, es, ja)
+        self.classes = ClassLoader(locale=self.messages.locale)  # Chain-agnostic classes supply shared fix guidance
         
         # Initialize reporting LLM
         self.llm = UnifiedLLMClient(
@@ -37,6 +48,7 @@ class ReportGenerator:
         self.hypothesis_metadata = self._load_hypothesis_metadata()
         self.agent_runs = self._load_agent_runs()
         self.pocs = self._load_pocs()  # Load available PoCs
+        self.chain = self._load_chain()
         # Debug helpers for CLI
         self.last_prompt: str | None = None
         self.last_response: str | None = None
@@ -56,6 +68,33 @@ class ReportGenerator:
         local_cfg = config.get('local', {})
         self._local_max_context = local_cfg.get('max_report_context', 6000)
 
+    def _t(self, key: str, **kwargs) -> str:
+        """Report text in the report's language."""
+        return self.messages.t(f"report.{key}", **kwargs)
+
+    def _severity_label(self, severity: str) -> str:
+        return self.messages.t(f"severity.{severity}", default=severity)
+
+    def _confidence_label(self, finding: dict) -> str:
+        """Confidence with its level and, for detector hits, the precision tier ("72% (medium, dataflow)")."""
+        conf = finding.get('confidence') or 0
+        level = confidence_level(conf)
+        parts = [self.messages.t(f"confidence.{level}", default=level)]
+        precision = (finding.get('properties') or {}).get('precision')
+        if precision:
+            parts.append(self.messages.t(f"precision.{precision}", default=precision))
+        return f"{conf:.0%} ({', '.join(parts)})"
+
+    def _tag_label(self, tag: str) -> str:
+        return self.messages.t(f"tags.{tag}", default=TAXONOMY[tag].label)
+
+    def _language_rule(self) -> str:
+        """Prompt guidance line asking for prose in the report's language (empty for English)."""
+        if self.messages.is_default:
+            return ""
+        return (f"- Write all prose in {self.messages.language}; keep code identifiers, contract and function "
+                "names and JSON keys unchanged\n")
+
     def _detect_local_model(self) -> bool:
         """Detect if we're using a local model via LM Studio."""
         base_url = os.environ.get("OPENAI_BASE_URL") or self.config.get("openai", {}).get("base_url")
@@ -180,6 +219,17 @@ class ReportGenerator:
                 return data.get("metadata", {})
         return {}
     
+    def _load_chain(self) -> str | None:
+        """Chain of the audited project, from project.json."""
+        project_file = self.project_dir / "project.json"
+        if project_file.exists():
+            try:
+                with open(project_file) as f:
+                    return json.load(f).get("chain_id")
+            except Exception:
+                return None
+        return None
+    
     def _load_pocs(self) -> dict[str, dict[str, Any]]:
         """Load available PoCs for hypotheses."""
         pocs = {}
@@ -352,41 +402,48 @@ class ReportGenerator:
         models = self._extract_audit_models()
         
         # Combine junior models if they're duplicates
-        junior_display = ', '.join(models['junior']) if models['junior'] else 'Not specified'
-        senior_display = ', '.join(models['senior']) if models['senior'] else 'Not specified'
+        not_specified = self._t('not_specified')
+        junior_display = ', '.join(models['junior']) if models['junior'] else not_specified
+        senior_display = ', '.join(models['senior']) if models['senior'] else not_specified
         
         table_html = """
             <table style="width: 100%; margin: 20px 0; border-collapse: collapse; background: rgba(26,31,46,0.4); border: 1px solid rgba(136,146,176,0.2); border-radius: 8px; overflow: hidden;">
                 <thead>
                     <tr style="background: rgba(100,181,246,0.1); border-bottom: 1px solid rgba(136,146,176,0.2);">
-                        <th style="padding: 12px 16px; text-align: left; color: #64b5f6; font-weight: 600;">Audit Role</th>
-                        <th style="padding: 12px 16px; text-align: left; color: #64b5f6; font-weight: 600;">Model(s)</th>
+                        <th style="padding: 12px 16px; text-align: left; color: #64b5f6; font-weight: 600;">{}</th>
+                        <th style="padding: 12px 16px; text-align: left; color: #64b5f6; font-weight: 600;">{}</th>
                     </tr>
                 </thead>
                 <tbody>
                     <tr style="border-bottom: 1px solid rgba(136,146,176,0.1);">
-                        <td style="padding: 12px 16px; color: #8892b0;">Junior Auditors</td>
+                        <td style="padding: 12px 16px; color: #8892b0;">{}</td>
                         <td style="padding: 12px 16px; color: #e8ecf1;">{}</td>
                     </tr>
                     <tr style="border-bottom: 1px solid rgba(136,146,176,0.1);">
-                        <td style="padding: 12px 16px; color: #8892b0;">Senior Auditor</td>
+                        <td style="padding: 12px 16px; color: #8892b0;">{}</td>
                         <td style="padding: 12px 16px; color: #e8ecf1;">{}</td>
                     </tr>
                     <tr style="border-bottom: 1px solid rgba(136,146,176,0.1);">
-                        <td style="padding: 12px 16px; color: #8892b0;">Quality Assurance</td>
+                        <td style="padding: 12px 16px; color: #8892b0;">{}</td>
                         <td style="padding: 12px 16px; color: #e8ecf1;">{}</td>
                     </tr>
                     <tr>
-                        <td style="padding: 12px 16px; color: #8892b0;">Report Writing</td>
+                        <td style="padding: 12px 16px; color: #8892b0;">{}</td>
                         <td style="padding: 12px 16px; color: #e8ecf1;">{}</td>
                     </tr>
                 </tbody>
             </table>
         """.format(
+            self._t('audit_role'),
+            self._t('models'),
+            self._t('junior_auditors'),
             junior_display,
+            self._t('senior_auditor'),
             senior_display,
-            models['finalize'] or 'Not specified',
-            models['reporting'] or 'Not specified'
+            self._t('quality_assurance'),
+            models['finalize'] or not_specified,
+            self._t('report_writing'),
+            models['reporting'] or not_specified
         )
         
         return table_html
@@ -522,6 +579,7 @@ class ReportGenerator:
             "  * Use: 'The Hound team identified', 'our analysis revealed', 'we discovered'\n"
             "  * When mentioning specific findings, ONLY use actual model names from ACTUAL_TEAM_MEMBERS\n"
             "  * DO NOT invent human names - only use the exact model names provided\n"
+            f"{self._language_rule()}"
         )
 
         # Try LLM call with fallback for context length errors
@@ -954,10 +1012,18 @@ and systematic vulnerability assessment across all identified attack surfaces.""
                     'senior_model': hyp.get('senior_model'),
                     'supporting_evidence': hyp.get('supporting_evidence', []),
                     'properties': hyp.get('properties', {}),
+                    'tags': hypothesis_tags(hyp),
+                    **self._class_fields(hyp),
                     'qa_comment': hyp.get('qa_comment', '')  # Include QA comment if available
                 }
                 findings.append(finding)
         
+        # Slice by taxonomy tag and confidence before spending LLM calls on descriptions
+        if self.tags:
+            findings = filter_by_tags(findings, self.tags)
+        if self.min_confidence is not None:
+            findings = [f for f in findings if meets_confidence(f, self.min_confidence)]
+        
         # Batch generate professional descriptions for all findings
         if findings:
             self._emit_progress('findings_describe', f'Generating professional descriptions for {len(findings)} findings')
@@ -985,6 +1051,17 @@ and systematic vulnerability assessment across all identified attack surfaces.""
         
         return findings
 
+    def _class_fields(self, hyp: dict) -> dict[str, Any]:
+        """Vulnerability class of a hypothesis and the class's fix guidance for this chain."""
+        vuln_class = self.classes.resolve(hyp.get('vulnerability_type', ''), self.chain)
+        if not vuln_class:
+            return {'vuln_class': None, 'vuln_class_name': None, 'recommendation': ''}
+        return {
+            'vuln_class': vuln_class.id,
+            'vuln_class_name': vuln_class.name,
+            'recommendation': vuln_class.fix_for(self.chain),
+        }
+
     def _emit_progress(self, status: str, message: str):
         """Emit progress to callback if provided."""
         if callable(self._progress_cb):
@@ -1125,7 +1202,7 @@ External dependencies are limited and clearly defined."""
         """Generate HTML format report."""
         
         html = f"""<!DOCTYPE html>
-<html lang="en">
+<html lang="{self.messages.locale}">
 <head>
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
@@ -1464,6 +1541,55 @@ External dependencies are limited and clearly defined."""
             border-left-color: #28a745;
         }}
         
+        .tag-facets {{
+            display: flex;
+            flex-wrap: wrap;
+            align-items: center;
+            gap: 8px;
+            margin-bottom: 25px;
+        }}
+        
+        .tag-facets-label {{
+            color: #8892a6;
+            font-size: 13px;
+            margin-right: 4px;
+        }}
+        
+        .tag-facet {{
+            background: rgba(100, 181, 246, 0.08);
+            border: 1px solid rgba(100, 181, 246, 0.3);
+            color: #c3cfe2;
+            border-radius: 16px;
+            padding: 5px 12px;
+            font-size: 12px;
+            cursor: pointer;
+        }}
+        
+        .tag-facet.active {{
+            background: rgba(100, 181, 246, 0.35);
+            border-color: #64b5f6;
+            color: #ffffff;
+        }}
+        
+        .tag-count {{
+            color: #8892a6;
+            margin-left: 4px;
+        }}
+        
+        .tag-chips {{
+            margin: -4px 0 12px 0;
+        }}
+        
+        .tag-chip {{
+            display: inline-block;
+            padding: 2px 10px;
+            margin-right: 6px;
+            border-radius: 12px;
+            font-size: 11px;
+            color: #64b5f6;
+            background: rgba(100, 181, 246, 0.1);
+        }}
+        
         .severity-badge {{
             display: inline-block;
             padding: 6px 14px;
@@ -1936,40 +2062,40 @@ External dependencies are limited and clearly defined."""
 <body>
     <div class="container">
         <div class="header">
-            <h1>SECURITY AUDIT REPORT</h1>
+            <h1>{self._t('title')}</h1>
             <div class="subtitle">{kwargs.get('application_name', kwargs.get('project_name', 'Application'))}</div>
             <div class="report-meta">
-                <strong>Project:</strong> {kwargs.get('project_name', '')}<br>
-                <strong>Date:</strong> {kwargs['report_date']}<br>
-                <strong>Audit Team:</strong> Hound Security<br>
-                <strong>Lead Auditors:</strong> {', '.join(kwargs['auditors'])}
+                <strong>{self._t('project')}:</strong> {kwargs.get('project_name', '')}<br>
+                <strong>{self._t('date')}:</strong> {kwargs['report_date']}<br>
+                <strong>{self._t('audit_team')}:</strong> {self._t('audit_team_name')}<br>
+                <strong>{self._t('lead_auditors')}:</strong> {', '.join(kwargs['auditors'])}
             </div>
         </div>
         
         <div class="section">
-            <h2>Executive Summary</h2>
+            <h2>{self._t('executive_summary')}</h2>
             {self._format_paragraphs_html(kwargs['executive_summary'])}
             
-            <h3 style="margin-top: 30px; color: #64b5f6;">Audit Team</h3>
+            <h3 style="margin-top: 30px; color: #64b5f6;">{self._t('audit_team')}</h3>
             {self._generate_models_table_html()}
         </div>
         
         <div class="section">
-            <h2>System Overview</h2>
+            <h2>{self._t('system_overview')}</h2>
             {self._format_paragraphs_html(kwargs.get('system_overview', ''))}
         </div>
         
         {self._generate_statistics_section_html(kwargs['findings'])}
         
         <div class="section">
-            <h2>{'All Hypotheses (UNREVIEWED)' if self.include_all else 'Findings'}</h2>
+            <h2>{self._t('all_hypotheses') if self.include_all else self._t('findings')}</h2>
             {self._add_unreviewed_warning_html() if self.include_all else ''}
             {self._format_findings_html(kwargs['findings'])}
         </div>
         
         <div class="footer">
-            <p>© {datetime.now().year} Hound Security Team<br>
-            Report prepared by: {kwargs.get('report_writer', 'Hound Team')}<br>
+            <p>© {datetime.now().year} {self._t('copyright')}<br>
+            {self._t('prepared_by')}: {kwargs.get('report_writer', 'Hound Team')}<br>
         </div>
     </div>
 </body>
@@ -1988,7 +2114,7 @@ External dependencies are limited and clearly defined."""
             return ''
         
         html_parts = ['<div class="poc-section">']
-        html_parts.append('<h4>Proof of Concept</h4>')
+        html_parts.append(f"<h4>{self._t('proof_of_concept')}</h4>")
         
         for poc_file in poc_data['files']:
             name = poc_file['name']
@@ -2058,37 +2184,37 @@ External dependencies are limited and clearly defined."""
         
         html = f'''
         <div class="stats-section">
-            <h2 class="summary-title">Security Analysis Statistics</h2>
+            <h2 class="summary-title">{self._t('statistics')}</h2>
             
             <div class="stats-grid">
                 <div class="stat-card">
                     <div class="stat-number">{total_findings}</div>
-                    <div class="stat-label">Total Findings</div>
+                    <div class="stat-label">{self._t('total_findings')}</div>
                 </div>
                 
                 <div class="stat-card">
                     <div class="stat-number critical">{severity_counts["critical"]}</div>
-                    <div class="stat-label">Critical Issues</div>
+                    <div class="stat-label">{self._t('critical_issues')}</div>
                 </div>
                 
                 <div class="stat-card">
                     <div class="stat-number high">{severity_counts["high"]}</div>
-                    <div class="stat-label">High Severity</div>
+                    <div class="stat-label">{self._t('high_severity')}</div>
                 </div>
                 
                 <div class="stat-card">
                     <div class="stat-number medium">{severity_counts["medium"]}</div>
-                    <div class="stat-label">Medium Severity</div>
+                    <div class="stat-label">{self._t('medium_severity')}</div>
                 </div>
                 
                 <div class="stat-card">
                     <div class="stat-number low">{severity_counts["low"]}</div>
-                    <div class="stat-label">Low Severity</div>
+                    <div class="stat-label">{self._t('low_severity')}</div>
                 </div>
             </div>
             
             <div class="chart-container">
-                <h3 style="text-align: center; color: #81c7f7; margin-bottom: 30px; font-size: 20px;">Severity Distribution</h3>
+                <h3 style="text-align: center; color: #81c7f7; margin-bottom: 30px; font-size: 20px;">{self._t('severity_distribution')}</h3>
                 <div class="chart-wrapper">
                     <div class="pie-chart">
                         {pie_chart_svg}
@@ -2191,7 +2317,7 @@ External dependencies are limited and clearly defined."""
                   font-weight="600" 
                   fill="#8892b0" 
                   text-transform="uppercase" 
-                  letter-spacing="1">Issues</text>
+                  letter-spacing="1">{self._t('issues')}</text>
         </svg>
         '''
         
@@ -2212,7 +2338,7 @@ External dependencies are limited and clearly defined."""
                 items.append(f'''
                 <div class="legend-item">
                     <div class="legend-color" style="background: {colors[sev]};"></div>
-                    <div class="legend-text">{sev.capitalize()}</div>
+                    <div class="legend-text">{self._severity_label(sev).capitalize()}</div>
                     <div class="legend-count">{severity_counts[sev]}</div>
                     <div style="color: #64738c; font-size: 12px;">({percentages[sev]:.1f}%)</div>
                 </div>
@@ -2222,13 +2348,11 @@ External dependencies are limited and clearly defined."""
     
     def _add_unreviewed_warning_html(self) -> str:
         """Add a warning box for unreviewed findings."""
-        return """
+        return f"""
         <div style="background: #fff3cd; border: 2px solid #ffc107; border-radius: 8px; padding: 15px; margin-bottom: 20px;">
-            <h3 style="color: #856404; margin-top: 0;">⚠️ WARNING: Unreviewed Findings</h3>
+            <h3 style="color: #856404; margin-top: 0;">⚠️ {self._t('unreviewed_title')}</h3>
             <p style="color: #856404; margin-bottom: 0;">
-                This report includes ALL hypotheses generated during the analysis, not just confirmed findings.
-                No quality assurance or review process has been performed. These findings may contain false positives
-                and should be independently verified before taking action.
+                {self._t('unreviewed_body')}
             </p>
         </div>
         """
@@ -2237,13 +2361,16 @@ External dependencies are limited and clearly defined."""
         """Format findings into HTML with code samples."""
         if not findings:
             if self.include_all:
-                return '<p><em>No hypotheses were generated during this analysis.</em></p>'
+                return f"<p><em>{self._t('no_hypotheses')}</em></p>"
             else:
-                return '<p><em>No confirmed vulnerabilities were identified during this audit.</em></p>'
+                return f"<p><em>{self._t('no_findings')}</em></p>"
         
-        html_parts = []
+        html_parts = [self._format_tag_facets_html(findings)]
         for finding in findings:
             severity = finding['severity']
+            tags = finding.get('tags', [])
+            tags_html = ''.join(f'<span class="tag-chip">{self._escape_html(self._tag_label(t))}</span>'
+                                for t in tags if t in TAXONOMY)
             
             # Format code samples
             code_html = ''
@@ -2255,11 +2382,20 @@ External dependencies are limited and clearly defined."""
             if finding.get('qa_comment'):
                 qa_comment_html = f'''
                 <div class="qa-comment" style="margin-top: 1em; padding: 15px; background: rgba(70, 130, 180, 0.1); border-left: 3px solid #4682b4; border-radius: 8px;">
-                    <strong style="color: #64b5f6;">QA Review:</strong> 
+                    <strong style="color: #64b5f6;">{self._t('qa_review')}:</strong> 
                     <span style="color: #c3cfe2; line-height: 1.6;">{self._escape_html(finding['qa_comment'])}</span>
                 </div>
                 '''
             
+            # Class and its shared fix guidance
+            class_html = ''
+            if finding.get('vuln_class_name'):
+                class_html = (f'<p><strong>{self._t("class")}:</strong> '
+                              f'{self._escape_html(finding["vuln_class_name"])}</p>')
+            if finding.get('recommendation'):
+                class_html += (f'<p><strong>{self._t("recommendation")}:</strong> '
+                               f'{self._escape_html(finding["recommendation"])}</p>')
+            
             # Check if there's a PoC for this hypothesis
             poc_html = ''
             hypothesis_id = finding.get('id', '')
@@ -2268,13 +2404,16 @@ External dependencies are limited and clearly defined."""
                 poc_html = self._format_poc_html(poc_data)
             
             html_parts.append(f'''
-            <div class="finding {severity}">
-                <span class="severity-badge severity-{severity}">{severity}</span>
+            <div class="finding {severity}" data-tags="{' '.join(tags)}">
+                <span class="severity-badge severity-{severity}">{self._severity_label(severity)}</span>
                 <h3>{finding['title']}</h3>
+                <div class="tag-chips">{tags_html}</div>
                 <div class="vulnerability-description">
                     {self._format_paragraphs_html(finding.get('professional_description', finding['description']))}
                 </div>
-                <p><strong>Affected Components:</strong> {finding.get('affected_description', self._describe_affected_components(finding.get('affected', [])))}</p>
+                <p><strong>{self._t('affected_components')}:</strong> {finding.get('affected_description', self._describe_affected_components(finding.get('affected', [])))}</p>
+                <p><strong>{self._t('confidence')}:</strong> {self._escape_html(self._confidence_label(finding))}</p>
+                {class_html}
                 {qa_comment_html}
                 {code_html}
                 {poc_html}
@@ -2283,6 +2422,47 @@ External dependencies are limited and clearly defined."""
         
         return '\n'.join(html_parts)
     
+    def _format_tag_facets_html(self, findings: list[dict]) -> str:
+        """Tag facet buttons that show only the findings carrying the selected tags."""
+        facets = facet_counts(findings)
+        if len(findings) < 2 or not facets:
+            return ''
+        buttons = ''.join(
+            f'<button class="tag-facet" data-tag="{tag}" title="{self._escape_html(TAXONOMY[tag].description)}">'
+            f'{self._escape_html(self._tag_label(tag))} <span class="tag-count">{count}</span></button>'
+            for tag, count in facets.items()
+        )
+        return f'''
+            <div class="tag-facets">
+                <span class="tag-facets-label">{self._t('filter_by_tag')}</span>
+                {buttons}
+                <button class="tag-facet tag-reset">{self._t('show_all')}</button>
+            </div>
+            <script>
+            (function () {{
+                var selected = new Set();
+                function apply() {{
+                    document.querySelectorAll('.tag-facet[data-tag]').forEach(function (b) {{
+                        b.classList.toggle('active', selected.has(b.dataset.tag));
+                    }});
+                    document.querySelectorAll('.finding[data-tags]').forEach(function (f) {{
+                        var tags = f.dataset.tags.split(' ');
+                        var shown = selected.size === 0 || tags.some(function (t) {{ return selected.has(t); }});
+                        f.style.display = shown ? '' : 'none';
+                    }});
+                }}
+                document.querySelectorAll('.tag-facet').forEach(function (b) {{
+                    b.addEventListener('click', function () {{
+                        if (!b.dataset.tag) {{ selected.clear(); }}
+                        else if (selected.has(b.dataset.tag)) {{ selected.delete(b.dataset.tag); }}
+                        else {{ selected.add(b.dataset.tag); }}
+                        apply();
+                    }});
+                }});
+            }})();
+            </script>
+            '''
+    
     def _describe_affected_components(self, node_refs: list[str]) -> str:
         """Generate human-readable descriptions of affected components."""
         if not node_refs:
@@ -2338,7 +2518,7 @@ Rules for descriptions:
 - Be concise but thorough
 - DO NOT include metadata prefixes like "VULNERABILITY TYPE:", "ROOT CAUSE:", etc.
 - DO NOT mention discovery methods or analysis process
-
+{self._language_rule()}
 Rules for affected components:
 - Convert raw node names like "func_MintingFacet__performMinting" to readable format like "the MintingFacet contract, specifically the performMinting() function"
 - Use proper articles and grammar
@@ -2415,7 +2595,7 @@ Rules:
 - DO NOT repeat the same information multiple times
 - DO NOT mention discovery methods or analysis process
 - Present as a clean, professional finding description
-"""
+{self._language_rule()}"""
 
         try:
             description = self.llm.raw(
@@ -2551,52 +2731,47 @@ Rules:
     def _generate_markdown_report(self, **kwargs) -> str:
         """Generate Markdown format report."""
         
-        md = f"""# SECURITY AUDIT REPORT
+        t = self._t
+        md = f"""# {t('title')}
 
 **{kwargs.get('application_name', kwargs.get('project_name', 'Application'))}**
 
-**Date:** {kwargs['report_date']}  
-**Performed by:** {', '.join(kwargs['auditors'])}
+**{t('date')}:** {kwargs['report_date']}  
+**{t('performed_by')}:** {', '.join(kwargs['auditors'])}
 
 ---
 
-## Executive Summary
+## {t('executive_summary')}
 
 {kwargs['executive_summary']}
 
-## System Overview
+## {t('system_overview')}
 
 {kwargs.get('system_overview', '')}
 
-## Scope & Methodology
+## {t('scope_methodology')}
 
-### Project Information
+### {t('project_information')}
 
-| Field | Value |
+| {t('field')} | {t('value')} |
 |-------|-------|
-| Project Name | {kwargs['project_name']} |
-| Repository | {kwargs['project_source']} |
-| Audit Date | {kwargs['report_date']} |
-| Auditors | {', '.join(kwargs['auditors'])} |
+| {t('project_name')} | {kwargs['project_name']} |
+| {t('repository')} | {kwargs['project_source']} |
+| {t('audit_date')} | {kwargs['report_date']} |
+| {t('auditors')} | {', '.join(kwargs['auditors'])} |
 
-### Methodology
+### {t('methodology')}
 
-The audit employed a comprehensive security assessment methodology including:
+{t('methodology_body')}
 
-- Static code analysis and manual code review
-- Architectural security assessment  
-- Attack surface mapping and threat modeling
-- Vulnerability pattern matching and invariant analysis
-- External dependency and integration review
-
-## Findings
+## {t('findings')}
 
 {self._format_findings_markdown(kwargs['findings'])}
 
 ---
 
-*Generated by Hound Security Analysis Platform*  
-*© {datetime.now().year} - Security Report*
+*{t('generated_by')}*  
+*© {datetime.now().year} - {t('security_report')}*
 """
         
         return md
@@ -2604,20 +2779,30 @@ The audit employed a comprehensive security assessment methodology including:
     def _format_findings_markdown(self, findings: list[dict]) -> str:
         """Format findings for Markdown."""
         if not findings:
-            return '*No confirmed vulnerabilities were identified during this audit.*'
+            return f"*{self._t('no_findings')}*"
         
         md_parts = []
         for finding in findings:
             # Include QA comment if available
             qa_comment = ''
             if finding.get('qa_comment'):
-                qa_comment = f"\n\n**QA Review:** {finding['qa_comment']}"
+                qa_comment = f"\n\n**{self._t('qa_review')}:** {finding['qa_comment']}"
             
-            md_parts.append(f"""### [{finding['severity'].upper()}] {finding['title']}
-
-**Affected:** {finding.get('affected_description', self._describe_affected_components(finding.get('affected', [])))}  
-
-{finding.get('professional_description', finding['description'])}{qa_comment}
+            tags = ', '.join(self._tag_label(t) for t in finding.get('tags', []) if t in TAXONOMY)
+            vuln_class = ''
+            if finding.get('vuln_class_name'):
+                vuln_class = f"**{self._t('class')}:** {finding['vuln_class_name']}  \n"
+            recommendation = ''
+            if finding.get('recommendation'):
+                recommendation = f"\n\n**{self._t('recommendation')}:** {finding['recommendation']}"
+            severity = self._severity_label(finding['severity']).upper()
+            md_parts.append(f"""### [{severity}] {finding['title']}
+
+**{self._t('tags')}:** {tags or self._t('none')}  
+{vuln_class}**{self._t('affected')}:** {finding.get('affected_description', self._describe_affected_components(finding.get('affected', [])))}  
+**{self._t('confidence')}:** {self._confidence_label(finding)}  
+
+{finding.get('professional_description', finding['description'])}{recommendation}{qa_comment}
 
 ---""")
         
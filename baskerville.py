@@ -4,7 +4,39 @@
 Wraps Hound core with additional capabilities:
 - Solodit vulnerability database integration
 - Security knowledge base
-- Bounty workflow for audit contests
+- Bounty workflow for audit contests, with per-finding assignees, reviewers and due dates
+- Program IR exports (inventory, transaction size feasibility, PDA map, privilege paths, Anchor.toml checks,
+  codebase metrics, account/instruction/CPI graphs as DOT, Mermaid or JSON)
+- Report data exports (risk heat-map, per-program risk scores, engagement summaries, signed deliverables,
+  SARIF for code scanning, JSON and Markdown findings reports with PoC snippets and remediation)
+- Agent-based economic simulation and fee/reward math stress tests
+- Account substitution fuzzing of legitimate transactions on LiteSVM
+- Instruction argument boundary fuzzing from IDL types
+- Differential execution of original and patched builds to verify security patches
+- Upgrade safety: account layout changes since a baseline version without a migration, proven by running
+  the new build against accounts in the old layout
+- Coverage-guided exploit search steered by SBF coverage from the execution backend
+- Persistent fuzzing corpora per project, with replay against fixed builds and minimization
+- Managed solana-test-validator with accounts cloned for PoCs
+- Standalone PoC repositories with pinned dependencies, account snapshots and a run script
+- Disclosure-safe PoC copies with placeholder addresses and drain amounts removed
+- Multi-step exploit scenarios compiled into ordered multi-transaction Anchor tests
+- Shared attacker helpers for PoCs (funded keypairs, token accounts, a fake token program)
+- Shared Solana RPC client (providers, caching, rate limiting)
+- Live exploit-attempt monitoring with rules derived from findings, alert routing, event decoding, Geyser plugins
+- Deployment consistency across clusters and chains (builds, authorities, configuration)
+- Precedent from published audit reports with severity calibration
+- Localized knowledge-base and report output (zh, es, ja)
+- Finding confidence from analysis precision, calibrated on the detector benchmark corpus
+- Versioned detector rule ids, with suppressions and baselines migrated across rule renames, splits and merges
+- Reproducibility manifests and `reproduce`
+- Top-level `scan <path>`, `render` and `list-templates` shortcuts (installed as the `baskerville` command)
+- Engagement archives (findings, triage, PoCs, reports, manifest) with export and import
+- Auditor notes from `@baskerville` source comments, kept as an audit trail in findings and reports
+- Hardcoded protocol parameters (fees, LTV, cooldowns, max supply) checked against sane ranges, with a
+  report appendix for client confirmation
+- Multi-tenant server mode: per-tenant storage, API keys with viewer/triager/admin roles, a JSON API
+  with streaming scan progress
 """
 
 import sys
@@ -71,6 +103,48 @@ app.add_typer(kb_app, name="kb")
 bounty_app = typer.Typer(help="Bounty workflow for audit contests")
 app.add_typer(bounty_app, name="bounty")
 
+ir_app = typer.Typer(help="Program IR exports")
+app.add_typer(ir_app, name="ir")
+
+reporting_app = typer.Typer(help="Report data exports")
+app.add_typer(reporting_app, name="reporting")
+
+sim_app = typer.Typer(help="Economic simulations and arithmetic stress tests")
+app.add_typer(sim_app, name="sim")
+
+validator_app = typer.Typer(help="Managed solana-test-validator for PoCs")
+app.add_typer(validator_app, name="validator")
+
+poc_app = typer.Typer(help="Standalone PoC packages")
+app.add_typer(poc_app, name="poc")
+
+rpc_app = typer.Typer(help="Solana RPC providers and response cache")
+app.add_typer(rpc_app, name="rpc")
+
+monitor_app = typer.Typer(help="Live exploit-attempt monitoring")
+app.add_typer(monitor_app, name="monitor")
+
+deployments_app = typer.Typer(help="Deployment consistency across clusters and chains")
+app.add_typer(deployments_app, name="deployments")
+
+precedent_app = typer.Typer(help="Published audit findings as precedent")
+app.add_typer(precedent_app, name="precedent")
+
+server_app = typer.Typer(help="Multi-tenant server mode (tenants, API keys, JSON API)")
+app.add_typer(server_app, name="server")
+
+export_app = typer.Typer(help="Export complete engagements")
+app.add_typer(export_app, name="export")
+
+import_app = typer.Typer(help="Import complete engagements")
+app.add_typer(import_app, name="import")
+
+rules_app = typer.Typer(help="Detector rule ids, suppressions and baselines")
+app.add_typer(rules_app, name="rules")
+
+bundle_app = typer.Typer(help="Content-addressed, signed knowledge bundles")
+app.add_typer(bundle_app, name="bundle")
+
 
 # ─────────────────────────────────────────────────────────────────────────────
 # Solodit Commands
@@ -145,22 +219,34 @@ def solodit_intel(
 @kb_app.command("search")
 def kb_search(
     query: str = typer.Argument(..., help="Search query"),
-    limit: int = typer.Option(20, "--limit", "-l", help="Maximum results per category")
+    limit: int = typer.Option(20, "--limit", "-l", help="Maximum results per category"),
+    tag: list[str] = typer.Option(None, "--tag", "-t", help="Filter by taxonomy tag (can specify multiple)"),
+    all_tags: bool = typer.Option(False, "--all-tags", help="Require every --tag instead of any")
 ):
     """Search the knowledge base."""
     from commands.knowledge import search
-    _invoke_click(search, {'query': query, 'limit': limit})
+    _invoke_click(search, {'query': query, 'limit': limit, 'tags': tuple(tag) if tag else (), 'all_tags': all_tags})
 
 
 @kb_app.command("checklist")
 def kb_checklist(
     category: str = typer.Option(None, "--category", "-c", help="Filter by category"),
     source: str = typer.Option("all", "--source", "-s", help="Filter by source (all, solodit, custom)"),
-    limit: int = typer.Option(20, "--limit", "-l", help="Maximum results")
+    tag: list[str] = typer.Option(None, "--tag", "-t", help="Filter by taxonomy tag (can specify multiple)"),
+    all_tags: bool = typer.Option(False, "--all-tags", help="Require every --tag instead of any"),
+    limit: int = typer.Option(20, "--limit", "-l", help="Maximum results"),
+    lang: str = typer.Option(None, "--lang", help="Language for descriptions and fixes (en, zh, es, ja)")
 ):
     """View security checklists."""
     from commands.knowledge import checklist
-    _invoke_click(checklist, {'category': category, 'source': source, 'limit': limit})
+    _invoke_click(checklist, {
+        'category': category,
+        'source': source,
+        'tags': tuple(tag) if tag else (),
+        'all_tags': all_tags,
+        'limit': limit,
+        'lang': lang
+    })
 
 
 @kb_app.command("categories")
@@ -173,11 +259,13 @@ def kb_categories():
 @kb_app.command("tips")
 def kb_tips(
     category: str = typer.Option(None, "--category", "-c", help="Filter by category"),
-    priority: str = typer.Option(None, "--priority", "-p", help="Filter by priority (high, medium, low)")
+    priority: str = typer.Option(None, "--priority", "-p", help="Filter by priority (high, medium, low)"),
+    tag: list[str] = typer.Option(None, "--tag", "-t", help="Filter by taxonomy tag (can specify multiple)"),
+    lang: str = typer.Option(None, "--lang", help="Language for descriptions and fixes (en, zh, es, ja)")
 ):
     """View auditor tips and heuristics."""
     from commands.knowledge import tips
-    _invoke_click(tips, {'category': category, 'priority': priority})
+    _invoke_click(tips, {'category': category, 'priority': priority, 'tags': tuple(tag) if tag else (), 'lang': lang})
 
 
 @kb_app.command("template")
@@ -190,6 +278,157 @@ def kb_template(
     _invoke_click(template, {'vuln_type': vuln_type, 'list_only': list_only})
 
 
+@kb_app.command("templates")
+def kb_templates(
+    chain: str = typer.Option(None, "--chain", "-c", help="Filter by chain (evm, solana, sui, aptos, cosmwasm)"),
+    severity: str = typer.Option(None, "--severity", help="Filter by severity"),
+    min_severity: str = typer.Option(None, "--min-severity", help="Lowest severity to include"),
+    vuln_class: str = typer.Option(None, "--class", help="Filter by vulnerability class or a vulnerability type of it"),
+    cwe: str = typer.Option(None, "--cwe", help="Filter by CWE id"),
+    swc: str = typer.Option(None, "--swc", help="Filter by SWC id"),
+    harness: str = typer.Option(None, "--harness", help="Filter by test harness (foundry, anchor, sui-move-test, "
+                                "aptos-move-test, cw-multi-test)"),
+    origin: str = typer.Option(None, "--origin", help="Filter by where templates come from (builtin, bundle, user)"),
+    as_json: bool = typer.Option(False, "--json", help="Print template metadata as JSON"),
+    template_dirs: list[str] = typer.Option(None, "--template-dir", help="Also load PoC templates from this root "
+                                            "(repeatable; later wins)")
+):
+    """Query PoC templates by their front matter metadata."""
+    from commands.knowledge import templates
+    _invoke_click(templates, {'chain': chain, 'severity': severity, 'min_severity': min_severity,
+                              'vuln_class': vuln_class, 'cwe': cwe, 'swc': swc, 'harness': harness,
+                              'as_json': as_json, 'origin': origin, 'template_dirs': tuple(template_dirs or ())})
+
+
+@kb_app.command("find")
+def kb_find(
+    query: str = typer.Argument(..., help="Words, \"phrases\" and tag:/chain:/severity: filters"),
+    chain: str = typer.Option(None, "--chain", "-c", help="Filter by chain (evm, solana, sui, aptos, cosmwasm)"),
+    tag: list[str] = typer.Option(None, "--tag", "-t", help="Filter by taxonomy tag (can specify multiple)"),
+    all_tags: bool = typer.Option(False, "--all-tags", help="Require every --tag instead of any"),
+    limit: int = typer.Option(10, "--limit", "-l", help="Maximum results"),
+    as_json: bool = typer.Option(False, "--json", help="Print the matches as JSON"),
+    template_dirs: list[str] = typer.Option(None, "--template-dir", help="Also load PoC templates from this root "
+                                            "(repeatable; later wins)")
+):
+    """Rank PoC templates by how well their titles, descriptions and fixes match the query."""
+    from commands.knowledge import find
+    _invoke_click(find, {'query': query, 'chain': chain, 'tags': tuple(tag) if tag else (), 'all_tags': all_tags,
+                         'limit': limit, 'as_json': as_json, 'template_dirs': tuple(template_dirs or ())})
+
+
+@kb_app.command("render")
+def kb_render(
+    template_id: str = typer.Argument(..., help="Template ID"),
+    assignments: list[str] = typer.Option(None, "--set", "-s", help="Placeholder value as KEY=VALUE (repeatable)"),
+    output: str = typer.Option(None, "--output", "-o", help="Write the rendered test file here"),
+    workspace: str = typer.Option(None, "--workspace", "-w",
+                                  help="Write into this workspace or package, where the template's harness "
+                                  "expects tests"),
+    idl: str = typer.Option(None, "--idl", help="Anchor IDL to fill the program id, instruction and accounts from"),
+    instruction: str = typer.Option(None, "--instruction", "-i",
+                                    help="IDL instruction the PoC calls (default: the only one)"),
+    program_test: bool = typer.Option(False, "--program-test", help="Generate a complete solana-program-test "
+                                      "exploit from the template and --idl instead"),
+    cluster: str = typer.Option(None, "--cluster", help="Solana cluster the PoC targets (feature gates)"),
+    target: str = typer.Option(None, "--target", help="Test harness to render for (e.g. anchor-ts: a Mocha test)"),
+    template_dirs: list[str] = typer.Option(None, "--template-dir", help="Also load PoC templates from this root "
+                                            "(repeatable; later wins)")
+):
+    """Fill in a PoC template's placeholders and emit a test file."""
+    from commands.knowledge import render
+    _invoke_click(render, {'template_id': template_id, 'assignments': tuple(assignments or ()), 'output': output,
+                           'workspace': workspace, 'idl_path': idl, 'instruction': instruction,
+                           'program_test': program_test, 'cluster': cluster, 'target': target,
+                           'template_dirs': tuple(template_dirs or ())})
+
+
+@kb_app.command("classes")
+def kb_classes(
+    chain: str = typer.Option(None, "--chain", help="Show each class's form on a chain (evm, solana, sui)"),
+    lang: str = typer.Option(None, "--lang", help="Language for descriptions and fixes (en, zh, es, ja)")
+):
+    """List chain-agnostic vulnerability classes."""
+    from commands.knowledge import list_classes
+    _invoke_click(list_classes, {'chain': chain, 'lang': lang})
+
+
+@kb_app.command("class")
+def kb_class(
+    name: str = typer.Argument(..., help="Class ID or a vulnerability type it covers"),
+    chain: str = typer.Option(None, "--chain", help="Highlight the form on a chain (evm, solana, sui)"),
+    lang: str = typer.Option(None, "--lang", help="Language for descriptions and fixes (en, zh, es, ja)")
+):
+    """Show a vulnerability class by ID or by any vulnerability type it covers."""
+    from commands.knowledge import show_class
+    _invoke_click(show_class, {'name': name, 'chain': chain, 'lang': lang})
+
+
+@kb_app.command("tags")
+def kb_tags(
+    chain: str = typer.Option(None, "--chain", help="Only count entries for a chain (evm, solana, sui)")
+):
+    """List taxonomy tags with entry counts."""
+    from commands.knowledge import list_tags
+    _invoke_click(list_tags, {'chain': chain})
+
+
+@kb_app.command("browse")
+def kb_browse(
+    tag: list[str] = typer.Option(None, "--tag", "-t", help="Filter by taxonomy tag (can specify multiple)"),
+    all_tags: bool = typer.Option(False, "--all-tags", help="Require every --tag instead of any"),
+    chain: str = typer.Option(None, "--chain", help="Filter by chain (evm, solana, sui)"),
+    limit: int = typer.Option(20, "--limit", "-l", help="Maximum results per category")
+):
+    """Browse checklists, tips and templates by taxonomy tag."""
+    from commands.knowledge import browse
+    _invoke_click(browse, {'tags': tuple(tag) if tag else (), 'all_tags': all_tags, 'chain': chain, 'limit': limit})
+
+
+@kb_app.command("integrations")
+def kb_integrations(
+    integration_id: str = typer.Argument(None, help="Integration ID (pyth, switchboard, ...) to show in full"),
+    chain: str = typer.Option("solana", "--chain", help="Chain to list integrations for")
+):
+    """List third-party integrations and the validation their documentation requires."""
+    from commands.knowledge import integrations
+    _invoke_click(integrations, {'integration_id': integration_id, 'chain': chain})
+
+
+@kb_app.command("programs")
+def kb_programs(
+    query: str = typer.Argument(None, help="Program ID (spl-token, metaplex-token-metadata, ...) or address"),
+    chain: str = typer.Option("solana", "--chain", help="Chain to list programs for")
+):
+    """List known programs and their canonical deployment addresses."""
+    from commands.knowledge import programs
+    _invoke_click(programs, {'query': query, 'chain': chain})
+
+
+@kb_app.command("incidents")
+def kb_incidents(
+    query: str = typer.Argument(None, help="Incident ID (wormhole-2022, the-dao-2016, ...), or a class or "
+                                "vulnerability type"),
+    chain: str = typer.Option(None, "--chain", help="Only list incidents on a chain (evm, solana, sui)"),
+    as_json: bool = typer.Option(False, "--json", help="Print the incidents as JSON")
+):
+    """List real-world exploits, or those of a vulnerability class or type."""
+    from commands.knowledge import incidents
+    _invoke_click(incidents, {'query': query, 'chain': chain, 'as_json': as_json})
+
+
+@kb_app.command("features")
+def kb_features(
+    cluster: str = typer.Option(None, "--cluster", help="Cluster to show feature gate status on (default mainnet)"),
+    provider_spec: str = typer.Option(None, "--provider", help="Read the status from this RPC provider "
+                                      "(URL, cluster or provider)"),
+    as_json: bool = typer.Option(False, "--json", help="Print the gates as JSON")
+):
+    """List runtime feature gates and whether a cluster has them."""
+    from commands.knowledge import features
+    _invoke_click(features, {'cluster': cluster, 'provider_spec': provider_spec, 'as_json': as_json})
+
+
 @kb_app.command("stats")
 def kb_stats():
     """Show knowledge base statistics."""
@@ -200,11 +439,13 @@ def kb_stats():
 @kb_app.command("context")
 def kb_context(
     topic: str = typer.Argument(..., help="Topic to get context for"),
-    protocol: bool = typer.Option(False, "--protocol", "-p", help="Treat topic as protocol type")
+    protocol: bool = typer.Option(False, "--protocol", "-p", help="Treat topic as protocol type"),
+    chain: str = typer.Option(None, "--chain", help="Filter by chain (evm, solana, sui)"),
+    lang: str = typer.Option(None, "--lang", help="Language for descriptions and fixes (en, zh, es, ja)")
 ):
     """Get audit context for a topic (for LLM prompts)."""
     from commands.knowledge import context
-    _invoke_click(context, {'topic': topic, 'protocol': protocol})
+    _invoke_click(context, {'topic': topic, 'protocol': protocol, 'chain': chain, 'lang': lang})
 
 
 # ─────────────────────────────────────────────────────────────────────────────
@@ -275,22 +516,61 @@ def bounty_import(
 @bounty_app.command("review")
 def bounty_review(
     contest_id: str = typer.Argument(..., help="Contest ID"),
-    severity: str = typer.Option(None, "--severity", "-s", help="Filter by severity")
+    severity: str = typer.Option(None, "--severity", "-s", help="Filter by severity"),
+    tag: list[str] = typer.Option(None, "--tag", "-t", help="Filter by taxonomy tag (can specify multiple)"),
+    assignee: str = typer.Option(None, "--assignee", help="Only findings assigned to this auditor"),
+    reviewer: str = typer.Option(None, "--reviewer", help="Only findings this auditor reviews")
 ):
     """Interactive review workflow for findings."""
     from commands.bounty import review
-    _invoke_click(review, {'contest_id': contest_id, 'severity': severity})
+    _invoke_click(review, {'contest_id': contest_id, 'severity': severity, 'tags': tuple(tag) if tag else (),
+                           'assignee': assignee, 'reviewer': reviewer})
+
+
+@bounty_app.command("assign")
+def bounty_assign(
+    contest_id: str = typer.Argument(..., help="Contest ID"),
+    finding_ids: list[str] = typer.Argument(..., help="Finding IDs"),
+    assignee: str = typer.Option(None, "--to", help="Auditor verifying the findings (\"\" unassigns)"),
+    reviewer: str = typer.Option(None, "--reviewer", help="Auditor signing off on the verdict (\"\" clears)"),
+    due: str = typer.Option(None, "--due", help="Due date: YYYY-MM-DD, +Nd (days from today) or none")
+):
+    """Assign findings to an auditor and reviewer, with an optional due date."""
+    from commands.bounty import assign
+    _invoke_click(assign, {'contest_id': contest_id, 'finding_ids': tuple(finding_ids), 'assignee': assignee,
+                           'reviewer': reviewer, 'due': due})
+
+
+@bounty_app.command("findings")
+def bounty_findings(
+    contest_id: str = typer.Argument(..., help="Contest ID"),
+    state: str = typer.Option(None, "--state", "-s", help="Filter by state"),
+    severity: str = typer.Option(None, "--severity", help="Filter by severity"),
+    assignee: str = typer.Option(None, "--assignee", help="Only findings assigned to this auditor"),
+    reviewer: str = typer.Option(None, "--reviewer", help="Only findings this auditor reviews"),
+    unassigned: bool = typer.Option(False, "--unassigned", help="Only findings nobody is assigned to"),
+    overdue: bool = typer.Option(False, "--overdue", help="Only findings still pending past their due date"),
+    as_json: bool = typer.Option(False, "--json", help="Output as JSON")
+):
+    """List a contest's findings with their assignment."""
+    from commands.bounty import list_findings
+    _invoke_click(list_findings, {'contest_id': contest_id, 'state': state, 'severity': severity,
+                                  'assignee': assignee, 'reviewer': reviewer, 'unassigned': unassigned,
+                                  'overdue': overdue, 'as_json': as_json})
 
 
 @bounty_app.command("export")
 def bounty_export(
     contest_id: str = typer.Argument(..., help="Contest ID"),
     output: str = typer.Option(None, "--output", "-o", help="Output directory"),
-    fmt: str = typer.Option("individual", "--format", "-f", help="Export format (individual, report, both)")
+    fmt: str = typer.Option("individual", "--format", "-f", help="Export format (individual, report, both, files)"),
+    assignee: str = typer.Option(None, "--assignee", help="Only findings assigned to this auditor"),
+    reviewer: str = typer.Option(None, "--reviewer", help="Only findings this auditor reviews")
 ):
     """Export findings for platform submission."""
     from commands.bounty import export
-    _invoke_click(export, {'contest_id': contest_id, 'output': output, 'fmt': fmt})
+    _invoke_click(export, {'contest_id': contest_id, 'output': output, 'fmt': fmt, 'assignee': assignee,
+                           'reviewer': reviewer})
 
 
 @bounty_app.command("submit")
@@ -319,6 +599,1137 @@ def bounty_archive(
     _invoke_click(archive, {'contest_id': contest_id})
 
 
+# ─────────────────────────────────────────────────────────────────────────────
+# Program IR Commands
+# ─────────────────────────────────────────────────────────────────────────────
+
+@ir_app.command("inventory")
+def ir_inventory(
+    target: str = typer.Argument(..., help="Project name or path to program sources"),
+    fmt: str = typer.Option("json", "--format", "-f", help="Output format (json, csv)"),
+    output: str = typer.Option(None, "--output", "-o", help="Output file")
+):
+    """Export instructions, accounts, PDAs, authorities and dependencies."""
+    from commands.ir import inventory
+    _invoke_click(inventory, {'target': target, 'fmt': fmt, 'output': output})
+
+
+@ir_app.command("tx-size")
+def ir_tx_size(
+    target: str = typer.Argument(..., help="Project name or path to program sources"),
+    show_all: bool = typer.Option(False, "--all", help="Show every instruction"),
+    cluster: str = typer.Option(None, "--cluster", help="Use this cluster's account lock limit (feature gates)"),
+    as_json: bool = typer.Option(False, "--json", help="Print footprints as JSON")
+):
+    """Flag instructions that need an address lookup table to fit in a transaction."""
+    from commands.ir import tx_size
+    _invoke_click(tx_size, {'target': target, 'show_all': show_all, 'cluster': cluster, 'as_json': as_json})
+
+
+@ir_app.command("pda-map")
+def ir_pda_map(
+    target: str = typer.Argument(..., help="Project name or path to program sources"),
+    as_json: bool = typer.Option(False, "--json", help="Print the map as JSON"),
+    output: str = typer.Option(None, "--output", "-o", help="Write the JSON map to a file")
+):
+    """Map PDA derivations across the workspace and report seed collisions."""
+    from commands.ir import pda_map
+    _invoke_click(pda_map, {'target': target, 'as_json': as_json, 'output': output})
+
+
+@ir_app.command("privilege-paths")
+def ir_privilege_paths(
+    target: str = typer.Argument(..., help="Project name or path to program sources"),
+    queries: list[str] = typer.Option(None, "--query", "-q", help="Query to run (can specify multiple)"),
+    as_json: bool = typer.Option(False, "--json", help="Print the paths as JSON"),
+    save_graph: bool = typer.Option(False, "--save-graph", help="Save the graph to the project's graphs/ directory")
+):
+    """Find privilege-escalation paths from unauthenticated instructions."""
+    from commands.ir import privilege_paths
+    _invoke_click(privilege_paths, {'target': target, 'queries': tuple(queries or ()), 'as_json': as_json,
+                                    'save_graph': save_graph})
+
+
+@ir_app.command("graph")
+def ir_graph(
+    target: str = typer.Argument(..., help="Project name or path to program sources"),
+    fmt: str = typer.Option("dot", "--format", help="Output format: dot, mermaid or json"),
+    output: str = typer.Option(None, "--output", "-o", help="Output file (default: stdout)"),
+    findings: list[str] = typer.Option(None, "--findings", help="Hypotheses JSON to annotate nodes with (repeatable)"),
+    no_findings: bool = typer.Option(False, "--no-findings", help="Export the graph without finding annotations"),
+    min_confidence: str = typer.Option(None, "--min-confidence", help="Minimum confidence: high, medium, low or 0-1")
+):
+    """Export the account/instruction/CPI graph as DOT, Mermaid or JSON."""
+    from commands.ir import graph
+    _invoke_click(graph, {'target': target, 'fmt': fmt, 'output': output, 'findings_files': tuple(findings or ()),
+                          'no_findings': no_findings, 'min_confidence': min_confidence})
+
+
+@ir_app.command("anchor-config")
+def ir_anchor_config(
+    target: str = typer.Argument(..., help="Project name or path to program sources"),
+    cluster: str = typer.Option(None, "--cluster", help="Cluster to resolve program ids for"),
+    as_json: bool = typer.Option(False, "--json", help="Print the configuration and issues as JSON")
+):
+    """Show the workspace's Anchor.toml setup and flag inconsistencies with the programs."""
+    from commands.ir import anchor_config
+    _invoke_click(anchor_config, {'target': target, 'cluster': cluster, 'as_json': as_json})
+
+
+@ir_app.command("metrics")
+def ir_metrics(
+    target: str = typer.Argument(..., help="Project name or path to program sources"),
+    top: int = typer.Option(10, "--top", help="Instructions to list in the review order"),
+    as_json: bool = typer.Option(False, "--json", help="Print the metrics as JSON"),
+    output: str = typer.Option(None, "--output", "-o", help="Output file")
+):
+    """Per-program size and attack-surface statistics, and the order to review instructions in."""
+    from commands.ir import metrics
+    _invoke_click(metrics, {'target': target, 'top': top, 'as_json': as_json, 'output': output})
+
+
+# ─────────────────────────────────────────────────────────────────────────────
+# Report Data Commands
+# ─────────────────────────────────────────────────────────────────────────────
+
+@reporting_app.command("heatmap")
+def reporting_heatmap(
+    project_name: str = typer.Argument(..., help="Project name"),
+    output: str = typer.Option(None, "--output", "-o", help="Output file"),
+    findings_only: bool = typer.Option(False, "--findings-only", help="Only include files that have findings"),
+    top: int = typer.Option(10, "--top", help="Number of hottest files to print"),
+    min_confidence: str = typer.Option(None, "--min-confidence", help="Minimum confidence: high, medium, low or 0-1")
+):
+    """Export per-file/per-module risk scores for treemap visualization."""
+    from commands.reporting import heatmap
+    _invoke_click(heatmap, {'project_name': project_name, 'output': output, 'findings_only': findings_only, 'top': top,
+                            'min_confidence': min_confidence})
+
+
+@reporting_app.command("risk")
+def reporting_risk(
+    project_names: list[str] = typer.Argument(..., help="Projects to score"),
+    model_path: str = typer.Option(None, "--model", help="Risk model YAML"),
+    output: str = typer.Option(None, "--output", "-o", help="Output file"),
+    as_json: bool = typer.Option(False, "--json", help="Print the ranking as JSON"),
+    min_confidence: str = typer.Option(None, "--min-confidence", help="Minimum confidence: high, medium, low or 0-1")
+):
+    """Score and rank programs by aggregate risk."""
+    from commands.reporting import risk
+    _invoke_click(risk, {'project_names': tuple(project_names), 'model_path': model_path, 'output': output,
+                         'as_json': as_json, 'min_confidence': min_confidence})
+
+
+@reporting_app.command("engagement")
+def reporting_engagement(
+    project_name: str = typer.Argument(..., help="Project name"),
+    since: str = typer.Option(None, "--since", help="First day of the engagement window (YYYY-MM-DD)"),
+    until: str = typer.Option(None, "--until", help="Last day of the engagement window (YYYY-MM-DD)"),
+    output: str = typer.Option(None, "--output", "-o", help="Output file"),
+    as_json: bool = typer.Option(False, "--json", help="Write the summary as JSON instead of Markdown"),
+    chain: str = typer.Option(None, "--chain", help="Checklist chain (default: the project's chain)"),
+    no_checklist: bool = typer.Option(False, "--no-checklist", help="Skip checklist coverage")
+):
+    """Summarize an engagement window for the report's methodology section."""
+    from commands.reporting import engagement
+    _invoke_click(engagement, {'project_name': project_name, 'since': since, 'until': until, 'output': output,
+                               'as_json': as_json, 'chain': chain, 'no_checklist': no_checklist})
+
+
+@reporting_app.command("annotations")
+def reporting_annotations(
+    project_name: str = typer.Argument(..., help="Project name"),
+    source: str = typer.Option(None, "--source", help="Source tree to scan (default: the project's source)"),
+    kind: str = typer.Option(None, "--kind", help="Only list annotations of this kind (note, reviewed, todo, ...)"),
+    include_removed: bool = typer.Option(False, "--all", help="Also list annotations since removed from the source"),
+    as_json: bool = typer.Option(False, "--json", help="Print annotations as JSON")
+):
+    """Ingest `@baskerville` notes from source comments and list them with the findings they touch."""
+    from commands.reporting import annotations
+    _invoke_click(annotations, {'project_name': project_name, 'source': source, 'kind': kind,
+                                'include_removed': include_removed, 'as_json': as_json})
+
+
+@reporting_app.command("sarif")
+def reporting_sarif(
+    project_name: str = typer.Argument(..., help="Project name"),
+    output: str = typer.Option(None, "--output", "-o", help="Output file (default: <project>/reports/static.sarif)"),
+    min_confidence: str = typer.Option(None, "--min-confidence", help="Only export hits at or above this confidence")
+):
+    """Export the last static run's hits as SARIF 2.1.0 for code-scanning dashboards."""
+    from commands.reporting import sarif
+    _invoke_click(sarif, {'project_name': project_name, 'output': output, 'min_confidence': min_confidence})
+
+
+@reporting_app.command("findings")
+def reporting_findings(
+    project_name: str = typer.Argument(..., help="Project name"),
+    fmt: str = typer.Option(None, "--format", help="Report format: json, markdown or sarif (default: [output] "
+                            "report_format of the source's baskerville.toml, else markdown)"),
+    output: str = typer.Option(None, "--output", "-o", help="Output file (default: <project>/reports/findings.<ext>)"),
+    min_confidence: str = typer.Option(None, "--min-confidence", help="Only report findings at or above this level"),
+    include_rejected: bool = typer.Option(False, "--all", help="Also report findings rejected during triage"),
+    no_static: bool = typer.Option(False, "--no-static", help="Leave out the last static run's detector hits"),
+    rubric_path: str = typer.Option(None, "--rubric", help="Severity rubric YAML (metrics, defaults, classes, bands)"),
+    reported_severity: bool = typer.Option(False, "--reported-severity",
+                                           help="Keep the severities findings declare instead of scoring them")
+):
+    """Write the project's findings with PoC snippets and remediation as JSON, Markdown or SARIF."""
+    from commands.reporting import findings
+    _invoke_click(findings, {'project_name': project_name, 'fmt': fmt, 'output': output,
+                             'min_confidence': min_confidence, 'include_rejected': include_rejected,
+                             'no_static': no_static, 'rubric_path': rubric_path,
+                             'reported_severity': reported_severity})
+
+
+@reporting_app.command("parameters")
+def reporting_parameters(
+    project_name: str = typer.Argument(..., help="Project name"),
+    status: str = typer.Option(None, "--status", help="Only list this status: ok, out-of-range, unclear"),
+    as_json: bool = typer.Option(False, "--json", help="Print parameters as JSON")
+):
+    """List the protocol parameters the last static run extracted, against their sane ranges."""
+    from commands.reporting import parameters
+    _invoke_click(parameters, {'project_name': project_name, 'status': status, 'as_json': as_json})
+
+
+@reporting_app.command("attack-trees")
+def reporting_attack_trees(
+    project_name: str = typer.Argument(..., help="Project name"),
+    fmt: str = typer.Option("mermaid", "--format", help="Output format: mermaid, markdown or json"),
+    output: str = typer.Option(None, "--output", "-o", help="Output file (default: <project>/reports/attack_trees.*)"),
+    min_confidence: str = typer.Option(None, "--min-confidence", help="Only use findings at or above this level"),
+    confirmed_only: bool = typer.Option(False, "--confirmed-only", help="Only use confirmed findings")
+):
+    """Compose the project's findings into attack trees per attacker goal."""
+    from commands.reporting import attack_trees
+    _invoke_click(attack_trees, {'project_name': project_name, 'fmt': fmt, 'output': output,
+                                 'min_confidence': min_confidence, 'confirmed_only': confirmed_only})
+
+
+@reporting_app.command("keygen")
+def reporting_keygen(
+    output_dir: str = typer.Option(".", "--output-dir", "-o", help="Directory for the key pair")
+):
+    """Generate an ed25519 key pair for signing reports."""
+    from commands.reporting import keygen
+    _invoke_click(keygen, {'output_dir': output_dir})
+
+
+@reporting_app.command("sign")
+def reporting_sign(
+    files: list[str] = typer.Argument(..., help="Report and finding files to sign"),
+    key_path: str = typer.Option(..., "--key", "-k", help="ed25519 private key (PEM)"),
+    source: str = typer.Option(None, "--source", "-s", help="Audited source path or project name"),
+    output: str = typer.Option(None, "--output", "-o", help="Envelope file")
+):
+    """Write a detached signature over report and finding files."""
+    from commands.reporting import sign
+    _invoke_click(sign, {'files': tuple(files), 'key_path': key_path, 'source': source, 'output': output})
+
+
+@reporting_app.command("verify")
+def reporting_verify(
+    envelope: str = typer.Argument(..., help="Signature envelope (.sig.json)"),
+    key_path: str = typer.Option(..., "--key", "-k", help="Signer's ed25519 public key (PEM)"),
+    root: str = typer.Option(None, "--root", help="Directory the signed files are relative to")
+):
+    """Verify a detached signature and the files it covers."""
+    from commands.reporting import verify
+    _invoke_click(verify, {'envelope': envelope, 'key_path': key_path, 'root': root})
+
+
+# ─────────────────────────────────────────────────────────────────────────────
+# Simulation Commands
+# ─────────────────────────────────────────────────────────────────────────────
+
+@sim_app.command("run")
+def sim_run(
+    config_path: str = typer.Argument(..., help="Simulation config (YAML)"),
+    runs: int = typer.Option(None, "--runs", help="Number of seeded runs"),
+    slots: int = typer.Option(None, "--slots", help="Slots per run"),
+    seed: int = typer.Option(None, "--seed", help="First seed"),
+    as_json: bool = typer.Option(False, "--json", help="Print results as JSON"),
+    output: str = typer.Option(None, "--output", "-o", help="Write results to a file"),
+    project_name: str = typer.Option(None, "--project", help="Save results under this project"),
+    import_hypotheses: bool = typer.Option(False, "--import-hypotheses", help="Import findings as hypotheses")
+):
+    """Run agents against a protocol over many slots and report economic exploits."""
+    from commands.simulate import run
+    _invoke_click(run, {'config_path': config_path, 'runs': runs, 'slots': slots, 'seed': seed, 'as_json': as_json,
+                        'output': output, 'project_name': project_name, 'import_hypotheses': import_hypotheses})
+
+
+@sim_app.command("math")
+def sim_math(
+    config_path: str = typer.Argument(..., help="Stress cases (YAML)"),
+    trials: int = typer.Option(None, "--trials", help="Trials per case"),
+    steps: int = typer.Option(None, "--steps", help="Steps per trial"),
+    seed: int = typer.Option(0, "--seed", help="Random seed"),
+    as_json: bool = typer.Option(False, "--json", help="Print reports as JSON"),
+    output: str = typer.Option(None, "--output", "-o", help="Write reports to a file"),
+    project_name: str = typer.Option(None, "--project", help="Save reports under this project"),
+    import_hypotheses: bool = typer.Option(False, "--import-hypotheses", help="Import findings as hypotheses")
+):
+    """Stress fee, reward and interest math with randomized inputs over long horizons."""
+    from commands.simulate import math_stress
+    _invoke_click(math_stress, {'config_path': config_path, 'trials': trials, 'steps': steps, 'seed': seed,
+                                'as_json': as_json, 'output': output, 'project_name': project_name,
+                                'import_hypotheses': import_hypotheses})
+
+
+@sim_app.command("liquidations")
+def sim_liquidations(
+    config_path: str = typer.Argument(..., help="Liquidation sweep config (YAML)"),
+    as_json: bool = typer.Option(False, "--json", help="Print the sweep as JSON"),
+    output: str = typer.Option(None, "--output", "-o", help="Write the sweep to a file"),
+    project_name: str = typer.Option(None, "--project", help="Save the sweep under this project"),
+    import_hypotheses: bool = typer.Option(False, "--import-hypotheses", help="Import findings as hypotheses")
+):
+    """Sweep price drops, oracle latencies and position sizes for unprofitable liquidations and bad debt."""
+    from commands.simulate import liquidations
+    _invoke_click(liquidations, {'config_path': config_path, 'as_json': as_json, 'output': output,
+                                 'project_name': project_name, 'import_hypotheses': import_hypotheses})
+
+
+@sim_app.command("substitute")
+def sim_substitute(
+    config_path: str = typer.Argument(..., help="Transaction config (YAML)"),
+    kinds: list[str] = typer.Option(None, "--kind", help="Substitution kind to try (repeatable)"),
+    as_json: bool = typer.Option(False, "--json", help="Print the substitutions as JSON"),
+    output: str = typer.Option(None, "--output", "-o", help="Write the substitutions to a file"),
+    project_name: str = typer.Option(None, "--project", help="Save the substitutions under this project"),
+    import_hypotheses: bool = typer.Option(False, "--import-hypotheses", help="Import findings as hypotheses")
+):
+    """Replay a legitimate transaction with each account swapped for an attacker lookalike."""
+    from commands.simulate import substitute
+    _invoke_click(substitute, {'config_path': config_path, 'kinds': tuple(kinds or ()), 'as_json': as_json,
+                               'output': output, 'project_name': project_name,
+                               'import_hypotheses': import_hypotheses})
+
+
+@sim_app.command("boundaries")
+def sim_boundaries(
+    config_path: str = typer.Argument(..., help="Instructions config (YAML)"),
+    only: list[str] = typer.Option(None, "--instruction", help="Only fuzz this instruction (repeatable)"),
+    as_json: bool = typer.Option(False, "--json", help="Print the cases as JSON"),
+    output: str = typer.Option(None, "--output", "-o", help="Write the cases to a file"),
+    project_name: str = typer.Option(None, "--project", help="Save the cases under this project"),
+    corpus: str = typer.Option(None, "--corpus", help="Corpus directory to store crashes in (default: the project's)"),
+    import_hypotheses: bool = typer.Option(False, "--import-hypotheses", help="Import findings as hypotheses")
+):
+    """Drive instructions with boundary argument values and classify panics, overflows and unexpected successes."""
+    from commands.simulate import boundaries
+    _invoke_click(boundaries, {'config_path': config_path, 'only': tuple(only or ()), 'as_json': as_json,
+                               'output': output, 'project_name': project_name, 'corpus': corpus,
+                               'import_hypotheses': import_hypotheses})
+
+
+@sim_app.command("diff")
+def sim_diff(
+    config_path: str = typer.Argument(..., help="Transaction corpus and builds (YAML)"),
+    as_json: bool = typer.Option(False, "--json", help="Print the diff as JSON"),
+    output: str = typer.Option(None, "--output", "-o", help="Write the diff to a file"),
+    project_name: str = typer.Option(None, "--project", help="Save the diff under this project")
+):
+    """Run a transaction corpus against the original and the patched build and diff the results."""
+    from commands.simulate import diff
+    _invoke_click(diff, {'config_path': config_path, 'as_json': as_json, 'output': output,
+                         'project_name': project_name})
+
+
+@sim_app.command("migrate")
+def sim_migrate(
+    config_path: str = typer.Argument(..., help="Fixtures in the previous layout and the new build (YAML)"),
+    as_json: bool = typer.Option(False, "--json", help="Print the results as JSON"),
+    output: str = typer.Option(None, "--output", "-o", help="Write the results to a file"),
+    project_name: str = typer.Option(None, "--project", help="Save the results under this project"),
+    import_hypotheses: bool = typer.Option(False, "--import-hypotheses", help="Import findings as hypotheses")
+):
+    """Run the new build against accounts in the previous version's layout and show whether state survives."""
+    from commands.simulate import migrate
+    _invoke_click(migrate, {'config_path': config_path, 'as_json': as_json, 'output': output,
+                            'project_name': project_name, 'import_hypotheses': import_hypotheses})
+
+
+@sim_app.command("search")
+def sim_search(
+    config_path: str = typer.Argument(..., help="Instructions config (YAML)"),
+    iterations: int = typer.Option(None, "--iterations", help="Mutated inputs per instruction (overrides the config)"),
+    seed: int = typer.Option(None, "--seed", help="Mutation seed (overrides the config)"),
+    only: list[str] = typer.Option(None, "--instruction", help="Only search this instruction (repeatable)"),
+    as_json: bool = typer.Option(False, "--json", help="Print the search as JSON"),
+    output: str = typer.Option(None, "--output", "-o", help="Write the search to a file"),
+    project_name: str = typer.Option(None, "--project", help="Save the search under this project"),
+    corpus: str = typer.Option(None, "--corpus", help="Corpus directory to resume from (default: the project's)"),
+    import_hypotheses: bool = typer.Option(False, "--import-hypotheses", help="Import findings as hypotheses")
+):
+    """Search for constraint bypasses and aborts with mutations steered by SBF coverage."""
+    from commands.simulate import search
+    _invoke_click(search, {'config_path': config_path, 'iterations': iterations, 'seed': seed,
+                           'only': tuple(only or ()), 'as_json': as_json, 'output': output,
+                           'project_name': project_name, 'corpus': corpus,
+                           'import_hypotheses': import_hypotheses})
+
+
+@sim_app.command("corpus")
+def sim_corpus(
+    project_name: str = typer.Option(None, "--project", help="Project whose corpus to list"),
+    corpus: str = typer.Option(None, "--corpus", help="Corpus directory (default: the project's)"),
+    as_json: bool = typer.Option(False, "--json", help="Print the counts as JSON")
+):
+    """List the stored fuzzing inputs per instruction."""
+    from commands.simulate import corpus_stats
+    _invoke_click(corpus_stats, {'project_name': project_name, 'corpus': corpus, 'as_json': as_json})
+
+
+@sim_app.command("replay")
+def sim_replay(
+    config_path: str = typer.Argument(..., help="Instructions config (YAML)"),
+    buckets: list[str] = typer.Option(None, "--bucket", help="Only replay this bucket (repeatable)"),
+    project_name: str = typer.Option(None, "--project", help="Project whose corpus to replay"),
+    corpus: str = typer.Option(None, "--corpus", help="Corpus directory (default: the project's)"),
+    as_json: bool = typer.Option(False, "--json", help="Print the replays as JSON")
+):
+    """Run stored crashes and witnesses against the current build and report which still reproduce."""
+    from commands.simulate import replay
+    _invoke_click(replay, {'config_path': config_path, 'buckets': tuple(buckets or ()),
+                           'project_name': project_name, 'corpus': corpus, 'as_json': as_json})
+
+
+@sim_app.command("minimize")
+def sim_minimize(
+    config_path: str = typer.Argument(..., help="Instructions config (YAML)"),
+    project_name: str = typer.Option(None, "--project", help="Project whose corpus to minimize"),
+    corpus: str = typer.Option(None, "--corpus", help="Corpus directory (default: the project's)"),
+    as_json: bool = typer.Option(False, "--json", help="Print the counts as JSON")
+):
+    """Minimize stored crashes and witnesses, and cut the queue to the inputs that keep its coverage."""
+    from commands.simulate import minimize
+    _invoke_click(minimize, {'config_path': config_path, 'project_name': project_name, 'corpus': corpus,
+                             'as_json': as_json})
+
+
+# ─────────────────────────────────────────────────────────────────────────────
+# Test Validator Commands
+# ─────────────────────────────────────────────────────────────────────────────
+
+@validator_app.command("plan")
+def validator_plan(
+    target: str = typer.Argument(..., help="Project name or path to program sources"),
+    pocs: list[str] = typer.Option(None, "--poc", help="PoC source to scan for accounts"),
+    hypothesis: str = typer.Option(None, "--hypothesis", help="Use the PoC imported for this hypothesis"),
+    clones: list[str] = typer.Option(None, "--clone", help="Additional account to clone"),
+    url: str = typer.Option(None, "--url", help="Cluster to clone from"),
+    as_json: bool = typer.Option(False, "--json", help="Print the plan and validator flags as JSON")
+):
+    """Show which accounts and programs the validator would clone or load."""
+    from commands.validator import plan
+    _invoke_click(plan, {'target': target, 'pocs': tuple(pocs or ()), 'hypothesis': hypothesis,
+                         'clones': tuple(clones or ()), 'url': url, 'as_json': as_json})
+
+
+@validator_app.command("run")
+def validator_run(
+    target: str = typer.Argument(..., help="Project name or path to program sources"),
+    pocs: list[str] = typer.Option(None, "--poc", help="PoC source to scan for accounts"),
+    hypothesis: str = typer.Option(None, "--hypothesis", help="Use the PoC imported for this hypothesis"),
+    clones: list[str] = typer.Option(None, "--clone", help="Additional account to clone"),
+    url: str = typer.Option(None, "--url", help="Cluster to clone from"),
+    command: str = typer.Option(None, "--exec", help="Run this command against the validator, then stop it"),
+    timeout: float = typer.Option(60.0, "--timeout", help="Seconds to wait for the validator to become healthy"),
+    binary: str = typer.Option("solana-test-validator", "--binary", help="Validator executable")
+):
+    """Start a validator with the PoC's accounts cloned; stop it on exit, Ctrl-C or SIGTERM."""
+    from commands.validator import run
+    _invoke_click(run, {'target': target, 'pocs': tuple(pocs or ()), 'hypothesis': hypothesis,
+                        'clones': tuple(clones or ()), 'url': url, 'command': command, 'timeout': timeout,
+                        'binary': binary})
+
+
+# ─────────────────────────────────────────────────────────────────────────────
+# PoC Packaging Commands
+# ─────────────────────────────────────────────────────────────────────────────
+
+@poc_app.command("package")
+def poc_package(
+    project: str = typer.Argument(..., help="Project name"),
+    hypothesis: str = typer.Argument(..., help="Hypothesis whose imported PoC to package"),
+    output: str = typer.Option(None, "--output", "-o", help="Package directory"),
+    backend: str = typer.Option(None, "--backend", help="Execution backend (validator, litesvm, native)"),
+    url: str = typer.Option(None, "--url", help="Cluster to snapshot accounts from"),
+    clones: list[str] = typer.Option(None, "--clone", help="Additional account to snapshot"),
+    command: str = typer.Option(None, "--command", help="Command that runs the PoC"),
+    no_pin: bool = typer.Option(False, "--no-pin", help="Do not generate missing lockfiles"),
+    redact: bool = typer.Option(False, "--redact", help="Redact addresses and amounts for disclosure"),
+    archive: bool = typer.Option(False, "--archive", help="Also write a .tar.gz of the package"),
+    force: bool = typer.Option(False, "--force", help="Replace an existing package directory")
+):
+    """Bundle a PoC into a self-contained repository the client can run."""
+    from commands.pocs import package
+    _invoke_click(package, {'project_name': project, 'hypothesis': hypothesis, 'output': output,
+                            'backend': backend, 'url': url, 'clones': tuple(clones or ()), 'command': command,
+                            'no_pin': no_pin, 'redact': redact, 'archive': archive, 'force': force})
+
+
+@poc_app.command("redact")
+def poc_redact(
+    path: str = typer.Argument(..., help="PoC package directory"),
+    output: str = typer.Option(None, "--output", "-o", help="Redacted copy (default: <dir>-redacted)"),
+    keep: list[str] = typer.Option(None, "--keep", help="Address to leave in place"),
+    map_file: str = typer.Option(None, "--map", help="Where to write the private address mapping"),
+    force: bool = typer.Option(False, "--force", help="Replace an existing output directory")
+):
+    """Write a disclosure-safe copy of a PoC package that still runs against its fixtures."""
+    from commands.pocs import redact
+    _invoke_click(redact, {'path': path, 'output': output, 'keep': tuple(keep or ()), 'map_file': map_file,
+                           'force': force})
+
+
+@poc_app.command("scenario")
+def poc_scenario(
+    scenario_file: str = typer.Argument(..., help="Scenario file (YAML or JSON)"),
+    workspace: str = typer.Argument(..., help="Anchor workspace the scenario targets"),
+    output: str = typer.Option(None, "--output", "-o", help="Test file to write (default: <scenario>.ts)")
+):
+    """Compile a multi-step exploit scenario into an ordered Anchor test."""
+    from commands.pocs import scenario
+    _invoke_click(scenario, {'scenario_file': scenario_file, 'workspace': workspace, 'output': output})
+
+
+@poc_app.command("harness")
+def poc_harness(
+    out_dir: str = typer.Argument(..., help="PoC test directory"),
+    fake_token: bool = typer.Option(False, "--fake-token", help="Also write the fake SPL Token program crate"),
+    force: bool = typer.Option(False, "--force", help="Overwrite helper files that were edited")
+):
+    """Write the shared attacker helpers into a PoC test directory."""
+    from commands.pocs import harness
+    _invoke_click(harness, {'out_dir': out_dir, 'fake_token': fake_token, 'force': force})
+
+
+@poc_app.command("run")
+def poc_run(
+    project: str = typer.Argument(..., help="Project name"),
+    hypothesis: str = typer.Argument(None, help="Hypothesis whose imported PoC to run"),
+    run_all: bool = typer.Option(False, "--all", help="Run every imported PoC"),
+    backends: list[str] = typer.Option(None, "--backend", help="Backend to run on (repeatable)"),
+    url: str = typer.Option(None, "--url", help="Cluster the fork backend snapshots"),
+    timeout: float = typer.Option(600.0, "--timeout", help="Seconds before a run is stopped"),
+    as_json: bool = typer.Option(False, "--json", help="Print the runs as JSON")
+):
+    """Run imported PoCs across backends and record the results."""
+    from commands.pocs import run
+    _invoke_click(run, {'project_name': project, 'hypothesis': hypothesis, 'run_all': run_all,
+                        'backends': tuple(backends or ()), 'url': url, 'timeout': timeout, 'as_json': as_json})
+
+
+@poc_app.command("matrix")
+def poc_matrix(
+    project: str = typer.Argument(..., help="Project name"),
+    backends: list[str] = typer.Option(None, "--backend", help="Backend column to show (repeatable)"),
+    as_json: bool = typer.Option(False, "--json", help="Print the last run per hypothesis and backend as JSON")
+):
+    """Show the last recorded run of each PoC on each backend."""
+    from commands.pocs import matrix
+    _invoke_click(matrix, {'project_name': project, 'backends': tuple(backends or ()), 'as_json': as_json})
+
+
+# ─────────────────────────────────────────────────────────────────────────────
+# RPC Commands
+# ─────────────────────────────────────────────────────────────────────────────
+
+@rpc_app.command("call")
+def rpc_call(
+    method: str = typer.Argument(..., help="JSON-RPC method, e.g. getAccountInfo"),
+    params: str = typer.Argument(None, help="Params as a JSON array"),
+    provider_spec: str = typer.Option(None, "--provider", help="URL, cluster or provider (helius, devnet:helius)"),
+    no_cache: bool = typer.Option(False, "--no-cache", help="Bypass the response cache")
+):
+    """Make one JSON-RPC call through the shared client and print the result."""
+    from commands.rpc import call
+    _invoke_click(call, {'method': method, 'params': params, 'provider_spec': provider_spec, 'no_cache': no_cache})
+
+
+@rpc_app.command("provider")
+def rpc_provider(
+    spec: str = typer.Argument(None, help="Provider spec (default: $SOLANA_RPC_URL or mainnet)")
+):
+    """Show how a provider spec resolves (endpoint with credentials masked, limits)."""
+    from commands.rpc import provider
+    _invoke_click(provider, {'spec': spec})
+
+
+@rpc_app.command("cache")
+def rpc_cache(
+    clear: bool = typer.Option(False, "--clear", help="Delete all cached responses")
+):
+    """Show or clear the on-disk response cache."""
+    from commands.rpc import cache
+    _invoke_click(cache, {'clear': clear})
+
+
+# ─────────────────────────────────────────────────────────────────────────────
+# Monitoring Commands
+# ─────────────────────────────────────────────────────────────────────────────
+
+@monitor_app.command("rules")
+def monitor_rules(
+    project_name: str = typer.Argument(..., help="Project whose findings the rules are derived from"),
+    program_name: str = typer.Option(None, "--program", help="Program to watch (default: the only one)"),
+    program_id: str = typer.Option(None, "--program-id", help="Deployed address (default: Anchor.toml, declare_id!)"),
+    min_severity: str = typer.Option("low", "--min-severity", help="Ignore findings below this severity"),
+    output: str = typer.Option(None, "--output", "-o", help="Rules file (default: <project>/monitoring/)"),
+    as_json: bool = typer.Option(False, "--json", help="Print the rules as JSON instead of writing a file"),
+    idl: str = typer.Option(None, "--idl", help="Anchor IDL to decode events with (default: the #[event] structs)"),
+    canaries_path: str = typer.Option(None, "--canaries", help="Canary plan whose created canaries are watched")
+):
+    """Derive monitoring rules for a program from the project's findings."""
+    from commands.monitor import rules
+    _invoke_click(rules, {'project_name': project_name, 'program_name': program_name, 'program_id': program_id,
+                          'min_severity': min_severity, 'output': output, 'as_json': as_json, 'idl': idl,
+                          'canaries_path': canaries_path})
+
+
+@monitor_app.command("canaries")
+def monitor_canaries(
+    project_name: str = typer.Argument(..., help="Project whose findings the canaries bait"),
+    program_name: str = typer.Option(None, "--program", help="Program to plant canaries in (default: the only one)"),
+    program_id: str = typer.Option(None, "--program-id", help="Deployed address (default: Anchor.toml, declare_id!)"),
+    min_severity: str = typer.Option("low", "--min-severity", help="Ignore findings below this severity"),
+    output: str = typer.Option(None, "--output", "-o", help="Canary plan (default: <project>/monitoring/)"),
+    as_json: bool = typer.Option(False, "--json", help="Print the plan as JSON instead of writing a file")
+):
+    """Plan decoy vaults and tripwire PDAs that catch probes of the project's findings."""
+    from commands.monitor import canaries
+    _invoke_click(canaries, {'project_name': project_name, 'program_name': program_name, 'program_id': program_id,
+                             'min_severity': min_severity, 'output': output, 'as_json': as_json})
+
+
+@monitor_app.command("events")
+def monitor_events(
+    project_name: str = typer.Argument(..., help="Project whose program declares the events"),
+    program_name: str = typer.Option(None, "--program", help="Program to read (default: the only one)"),
+    idl: str = typer.Option(None, "--idl", help="Anchor IDL to take the schemas from (default: the #[event] structs)"),
+    as_json: bool = typer.Option(False, "--json", help="Print the schemas as JSON")
+):
+    """Show the event schemas monitoring decodes a program's events with."""
+    from commands.monitor import events
+    _invoke_click(events, {'project_name': project_name, 'program_name': program_name, 'idl': idl,
+                           'as_json': as_json})
+
+
+@monitor_app.command("run")
+def monitor_run(
+    rules_path: str = typer.Argument(..., help="Rules file from `monitor rules`"),
+    provider_spec: str = typer.Option(None, "--provider", help="URL, cluster or provider (helius, devnet:helius)"),
+    source: str = typer.Option("poll", "--source", help="poll, websocket or webhook"),
+    interval: float = typer.Option(2.0, "--interval", help="Polling interval in seconds"),
+    backfill: int = typer.Option(0, "--backfill", help="Also evaluate this many recent transactions (polling)"),
+    host: str = typer.Option("127.0.0.1", "--host", help="Webhook listen address"),
+    port: int = typer.Option(8787, "--port", help="Webhook listen port"),
+    webhook_auth: str = typer.Option(None, "--webhook-auth", envvar="MONITOR_WEBHOOK_AUTH",
+                                     help="Required Authorization header for webhook posts"),
+    alerts_path: str = typer.Option(None, "--alerts", help="Append alerts to this JSONL file"),
+    routing_path: str = typer.Option(None, "--routing", help="Route alerts to Slack, PagerDuty, email or webhooks"),
+    max_events: int = typer.Option(None, "--max-events", help="Stop after this many transactions")
+):
+    """Watch the program's transactions and raise alerts as rules match."""
+    from commands.monitor import run
+    _invoke_click(run, {'rules_path': rules_path, 'provider_spec': provider_spec, 'source': source,
+                        'interval': interval, 'backfill': backfill, 'host': host, 'port': port,
+                        'webhook_auth': webhook_auth, 'alerts_path': alerts_path, 'routing_path': routing_path,
+                        'max_events': max_events})
+
+
+@monitor_app.command("replay")
+def monitor_replay(
+    rules_path: str = typer.Argument(..., help="Rules file from `monitor rules`"),
+    signatures: list[str] = typer.Argument(..., help="Transaction signatures to evaluate"),
+    provider_spec: str = typer.Option(None, "--provider", help="URL, cluster or provider (helius, devnet:helius)"),
+    as_json: bool = typer.Option(False, "--json", help="Print alerts as JSON")
+):
+    """Evaluate the rules against past transactions, e.g. a known exploit, showing the events they emitted."""
+    from commands.monitor import replay
+    _invoke_click(replay, {'rules_path': rules_path, 'signatures': tuple(signatures),
+                           'provider_spec': provider_spec, 'as_json': as_json})
+
+
+@monitor_app.command("geyser")
+def monitor_geyser(
+    rules_path: str = typer.Argument(..., help="Rules file from `monitor rules`"),
+    rule_ids: list[str] = typer.Option(None, "--rule", help="Rule to include (repeatable; default: all)"),
+    output: str = typer.Option(None, "--output", "-o", help="Crate directory (default: <rules file>-geyser)"),
+    name: str = typer.Option(None, "--name", help="Crate name (default: <program>-baskerville-geyser)"),
+    force: bool = typer.Option(False, "--force", help="Overwrite a non-empty output directory")
+):
+    """Generate a Geyser plugin crate that runs the rules inside a validator."""
+    from commands.monitor import geyser
+    _invoke_click(geyser, {'rules_path': rules_path, 'rule_ids': tuple(rule_ids or ()), 'output': output,
+                           'name': name, 'force': force})
+
+@monitor_app.command("test-alert")
+def monitor_test_alert(
+    routing_path: str = typer.Argument(..., help="Routing file"),
+    severity: str = typer.Option("critical", "--severity", help="Severity of the test alert"),
+    rule_id: str = typer.Option("test-alert", "--rule", help="Rule id of the test alert (to exercise routes)")
+):
+    """Send a test alert through the routing file, showing which channels it reached."""
+    from commands.monitor import send_test_alert
+    _invoke_click(send_test_alert, {'routing_path': routing_path, 'severity': severity, 'rule_id': rule_id})
+
+
+# ─────────────────────────────────────────────────────────────────────────────
+# Deployment Commands
+# ─────────────────────────────────────────────────────────────────────────────
+
+@deployments_app.command("check")
+def deployments_check(
+    config_path: str = typer.Argument(..., help="Deployments file (YAML)"),
+    names: list[str] = typer.Option(None, "--deployment", help="Deployment to compare (repeatable; default: all)"),
+    as_json: bool = typer.Option(False, "--json", help="Print snapshots and findings as JSON"),
+    output: str = typer.Option(None, "--output", "-o", help="Write snapshots and findings to a file"),
+    project_name: str = typer.Option(None, "--project", help="Save results under this project"),
+    import_hypotheses: bool = typer.Option(False, "--import-hypotheses", help="Import findings as hypotheses")
+):
+    """Compare program versions, configuration and authorities across deployments and flag divergence."""
+    from commands.deployments import check
+    _invoke_click(check, {'config_path': config_path, 'names': tuple(names or ()), 'as_json': as_json,
+                          'output': output, 'project_name': project_name, 'import_hypotheses': import_hypotheses})
+
+
+@app.command("calibrate")
+def calibrate(
+    corpus: str = typer.Argument(None, help="Labelled corpus (default: tests/fixtures/benchmark/corpus.yaml)"),
+    output: str = typer.Option(None, "--output", "-o", help="Write the calibration here"),
+    as_json: bool = typer.Option(False, "--json", help="Print per-hit labels as JSON")
+):
+    """Measure detector precision on the benchmark corpus and calibrate confidence."""
+    from commands.calibrate import calibrate as calibrate_command
+    _invoke_click(calibrate_command, {'corpus': corpus, 'output': output, 'as_json': as_json})
+
+
+@rules_app.command("list")
+def rules_list(
+    as_json: bool = typer.Option(False, "--json", help="Print the catalog as JSON")
+):
+    """Show the rule set version, every rename, split, merge and retirement, and the rule codes."""
+    from commands.rules import list_rules
+    _invoke_click(list_rules, {'as_json': as_json})
+
+
+@rules_app.command("suppress")
+def rules_suppress(
+    project: str = typer.Argument(..., help="Project name"),
+    rule: str = typer.Argument(..., help="Rule id (<detector>/<vulnerability-type>) or code (SOL-001-MISSING-SIGNER)"),
+    reason: str = typer.Option(..., "--reason", help="Why these hits are not findings"),
+    path: str = typer.Option(None, "--path", help="Only suppress hits in files matching this glob"),
+    created_by: str = typer.Option(None, "--by", help="Who decided")
+):
+    """Hide a rule's hits in later static runs."""
+    from commands.rules import suppress
+    _invoke_click(suppress, {'project_name': project, 'rule': rule, 'reason': reason, 'path': path,
+                             'created_by': created_by})
+
+
+@rules_app.command("baseline")
+def rules_baseline(
+    project: str = typer.Argument(..., help="Project name")
+):
+    """Accept the hits of the last static run as known."""
+    from commands.rules import baseline
+    _invoke_click(baseline, {'project_name': project})
+
+
+@rules_app.command("show")
+def rules_show(
+    project: str = typer.Argument(..., help="Project name"),
+    as_json: bool = typer.Option(False, "--json", help="Print suppressions and baseline as JSON")
+):
+    """List a project's suppressions and baseline."""
+    from commands.rules import show
+    _invoke_click(show, {'project_name': project, 'as_json': as_json})
+
+
+@rules_app.command("migrate")
+def rules_migrate(
+    project: str = typer.Argument(..., help="Project name"),
+    dry_run: bool = typer.Option(False, "--dry-run", help="Report what would change without writing")
+):
+    """Move a project's suppressions and baseline to the current rule set."""
+    from commands.rules import migrate
+    _invoke_click(migrate, {'project_name': project, 'dry_run': dry_run})
+
+
+@bundle_app.command("build")
+def bundle_build(
+    source: str = typer.Argument(..., help="Directory with checklists/, templates/ and tips/"),
+    name: str = typer.Option(..., "--name", "-n", help="Bundle name"),
+    version: str = typer.Option(..., "--version", "-v", help="Bundle version"),
+    description: str = typer.Option("", "--description", "-d", help="What the bundle is for"),
+    key: str = typer.Option(None, "--key", help="ed25519 private key (PEM) to sign the bundle with"),
+    output_dir: str = typer.Option(".", "--output-dir", "-o", help="Directory for the archive")
+):
+    """Pack a directory's checklists, templates and tips into a bundle."""
+    from commands.bundles import build
+    _invoke_click(build, {'source': source, 'name': name, 'version': version, 'description': description,
+                          'key_path': key, 'output_dir': output_dir})
+
+
+@bundle_app.command("add")
+def bundle_add(
+    archive: str = typer.Argument(..., help="Bundle archive (.tar.gz)"),
+    key: str = typer.Option(None, "--key", help="Only add the bundle if this public key (PEM) signed it")
+):
+    """Verify a bundle archive and add it to the local store."""
+    from commands.bundles import add
+    _invoke_click(add, {'archive': archive, 'key_path': key})
+
+
+@bundle_app.command("list")
+def bundle_list(
+    as_json: bool = typer.Option(False, "--json", help="Print the bundles as JSON")
+):
+    """List the bundles in the local store."""
+    from commands.bundles import list_bundles
+    _invoke_click(list_bundles, {'as_json': as_json})
+
+
+@bundle_app.command("verify")
+def bundle_verify(
+    workspace: str = typer.Argument(".", help="Workspace with a baskerville.toml"),
+    as_json: bool = typer.Option(False, "--json", help="Print the resolved bundles as JSON")
+):
+    """Check the bundles a workspace pins are present, intact and signed as pinned."""
+    from commands.bundles import verify
+    _invoke_click(verify, {'workspace': workspace, 'as_json': as_json})
+
+
+@app.command("reproduce")
+def reproduce(
+    report: str = typer.Argument(..., help="Report or export carrying a reproducibility manifest"),
+    output: str = typer.Option(None, "--output", "-o", help="Where to write the reproduced output"),
+    check: bool = typer.Option(False, "--check", help="Only compare the recorded environment with the current one"),
+    strict: bool = typer.Option(False, "--strict", help="Refuse to re-run if versions, rule set or target commit differ")
+):
+    """Re-run the exact configuration recorded in a report's manifest."""
+    from commands.reproduce import reproduce as reproduce_command
+    _invoke_click(reproduce_command, {'report': report, 'output': output, 'check': check, 'strict': strict})
+
+
+@app.command("scan")
+def scan(
+    path: str = typer.Argument(..., help="Workspace to scan (e.g. an Anchor workspace)"),
+    chain: str = typer.Option(None, "--chain", help="Chain of the code (default: detected from Anchor.toml, "
+                              "Move.toml, foundry.toml, ...)"),
+    tools: list[str] = typer.Option(None, "--tool", help="Only run this detector (repeatable)"),
+    min_confidence: str = typer.Option(None, "--min-confidence", help="Drop hits below this confidence"),
+    as_json: bool = typer.Option(False, "--json", help="Print hits as JSON (--format json)"),
+    output_format: str = typer.Option(None, "--format", help="Output format: table or json (default: [output] "
+                                      "format of baskerville.toml, else table)"),
+    output: str = typer.Option(None, "--output", "-o", help="Also save the results to this directory"),
+    sarif_path: str = typer.Option(None, "--sarif", help="Also write the hits as SARIF 2.1.0 (for code scanning)"),
+    baseline: str = typer.Option(None, "--baseline", help="Previous version (directory or git ref) to check "
+                                 "account layout changes against"),
+    update_baseline: bool = typer.Option(False, "--update-baseline",
+                                         help="Accept every current hit in the workspace's .baskerville-baseline.json"),
+    show_suppressed: bool = typer.Option(False, "--show-suppressed",
+                                         help="Also show baselined and inline-suppressed hits"),
+    with_usage: bool = typer.Option(False, "--usage", help="Rank hits by how often their instruction is called "
+                                    "on-chain (Solana, needs RPC)"),
+    provider_spec: str = typer.Option(None, "--provider", help="RPC provider for --usage (URL, cluster or provider)"),
+    usage_sample: int = typer.Option(1000, "--usage-sample", help="Recent transactions to sample per program"),
+    cluster: str = typer.Option(None, "--cluster", help="Cluster whose feature gates the code must work with "
+                                "(default mainnet)"),
+    no_cache: bool = typer.Option(False, "--no-cache", help="Parse and run every detector again instead of reusing "
+                                  ".baskerville/cache"),
+    jobs: int = typer.Option(None, "--jobs", "-j", min=1, help="Worker processes parsing crates and running detectors "
+                             "(default: one per CPU)"),
+    no_plugins: bool = typer.Option(False, "--no-plugins", help="Only run the built-in detectors"),
+    diff_ref: str = typer.Option(None, "--diff", help="Only report hits on lines changed since the merge base "
+                                 "with this git ref (e.g. origin/main)"),
+    diff_context: int = typer.Option(3, "--diff-context", min=0, help="Lines around a change that still count "
+                                     "as touched by it"),
+    github_mode: str = typer.Option(None, "--github", help="Also post the hits to GitHub: checks (check-run "
+                                    "annotations) or review (pull request review comments)"),
+    github_pr: int = typer.Option(None, "--github-pr", min=1, help="Pull request to review (default: the one of "
+                                  "the GitHub Actions event)"),
+    fix: bool = typer.Option(False, "--fix", help="Fix the hits that have a mechanical fix in the source"),
+    dry_run: bool = typer.Option(False, "--dry-run", help="With --fix, print the patch instead of changing the source"),
+    fix_patch: str = typer.Option(None, "--fix-patch", help="Write the fixes to this patch file instead of applying "
+                                  "them"),
+    fail_on: str = typer.Option(None, "--fail-on", help="Exit with status 1 when a hit is this severe or worse: "
+                                "critical, high, medium, low, info or never (default: info with --diff, else never)")
+):
+    """Run the chain's detectors against a workspace and list the hits, without creating a project."""
+    from commands.scan import scan as scan_command
+    _invoke_click(scan_command, {'path': path, 'chain': chain, 'tools': tuple(tools or ()),
+                                 'min_confidence': min_confidence, 'as_json': as_json, 'output': output,
+                                 'sarif_path': sarif_path, 'baseline': baseline, 'update_baseline': update_baseline,
+                                 'show_suppressed': show_suppressed, 'with_usage': with_usage,
+                                 'provider_spec': provider_spec, 'usage_sample': usage_sample, 'cluster': cluster,
+                                 'no_cache': no_cache, 'jobs': jobs, 'no_plugins': no_plugins,
+                                 'output_format': output_format, 'diff_ref': diff_ref,
+                                 'diff_context': diff_context, 'github_mode': github_mode, 'github_pr': github_pr,
+                                 'fix': fix, 'dry_run': dry_run, 'fix_patch': fix_patch, 'fail_on': fail_on})
+
+
+@app.command("render")
+def render(
+    template_id: str = typer.Argument(..., help="Template ID (see `list-templates`)"),
+    variables: list[str] = typer.Option(None, "--var", "--set", "-s", help="Placeholder value as KEY=VALUE "
+                                        "(repeatable)"),
+    output: str = typer.Option(None, "--output", "-o", help="Write the rendered test file here"),
+    workspace: str = typer.Option(None, "--workspace", "-w",
+                                  help="Write into this workspace or package, where the template's harness "
+                                  "expects tests"),
+    idl: str = typer.Option(None, "--idl", help="Anchor IDL to fill the program id, instruction and accounts from"),
+    instruction: str = typer.Option(None, "--instruction", "-i",
+                                    help="IDL instruction the PoC calls (default: the only one)"),
+    program_test: bool = typer.Option(False, "--program-test", help="Generate a complete solana-program-test "
+                                      "exploit from the template and --idl instead"),
+    cluster: str = typer.Option(None, "--cluster", help="Solana cluster the PoC targets (feature gates)"),
+    target: str = typer.Option(None, "--target", help="Test harness to render for (e.g. anchor-ts: a Mocha test)"),
+    template_dirs: list[str] = typer.Option(None, "--template-dir", help="Also load PoC templates from this root "
+                                            "(repeatable; later wins)")
+):
+    """Instantiate a PoC template (same as `kb render`)."""
+    from commands.knowledge import render as render_command
+    _invoke_click(render_command, {'template_id': template_id, 'assignments': tuple(variables or ()),
+                                   'output': output, 'workspace': workspace, 'idl_path': idl,
+                                   'instruction': instruction, 'program_test': program_test,
+                                   'cluster': cluster, 'target': target, 'template_dirs': tuple(template_dirs or ())})
+
+
+@app.command("search")
+def search(
+    query: str = typer.Argument(..., help="Words, \"phrases\" and tag:/chain:/severity: filters"),
+    chain: str = typer.Option(None, "--chain", "-c", help="Filter by chain (evm, solana, sui, aptos, cosmwasm)"),
+    tag: list[str] = typer.Option(None, "--tag", "-t", help="Filter by taxonomy tag (can specify multiple)"),
+    limit: int = typer.Option(10, "--limit", "-l", help="Maximum results"),
+    as_json: bool = typer.Option(False, "--json", help="Print the matches as JSON"),
+    template_dirs: list[str] = typer.Option(None, "--template-dir", help="Also load PoC templates from this root "
+                                            "(repeatable; later wins)")
+):
+    """Find PoC templates by their titles, descriptions and fixes, best match first (same as `kb find`)."""
+    from commands.knowledge import find
+    _invoke_click(find, {'query': query, 'chain': chain, 'tags': tuple(tag) if tag else (), 'all_tags': False,
+                         'limit': limit, 'as_json': as_json, 'template_dirs': tuple(template_dirs or ())})
+
+
+@app.command("list-templates")
+def list_templates(
+    chain: str = typer.Option(None, "--chain", "-c", help="Filter by chain (evm, solana, sui, aptos, cosmwasm)"),
+    severity: str = typer.Option(None, "--severity", help="Filter by severity"),
+    vuln_class: str = typer.Option(None, "--class", help="Filter by vulnerability class or a vulnerability type of it"),
+    harness: str = typer.Option(None, "--harness", help="Filter by test harness"),
+    as_json: bool = typer.Option(False, "--json", help="Print template metadata as JSON"),
+    template_dirs: list[str] = typer.Option(None, "--template-dir", help="Also load PoC templates from this root "
+                                            "(repeatable; later wins)")
+):
+    """Browse the knowledge base's PoC templates (same as `kb templates`)."""
+    from commands.knowledge import templates
+    _invoke_click(templates, {'chain': chain, 'severity': severity, 'min_severity': None, 'vuln_class': vuln_class,
+                              'cwe': None, 'swc': None, 'harness': harness, 'as_json': as_json,
+                              'template_dirs': tuple(template_dirs or ())})
+
+
+# ─────────────────────────────────────────────────────────────────────────────
+# Precedent Commands
+# ─────────────────────────────────────────────────────────────────────────────
+
+@precedent_app.command("import")
+def precedent_import(
+    path: str = typer.Argument(..., help="Report metadata (YAML/JSON) or published Markdown report"),
+    firm: str = typer.Option(None, "--firm", help="Audit firm (required for Markdown reports)"),
+    protocol: str = typer.Option(None, "--protocol", help="Audited protocol (required for Markdown reports)"),
+    chain: str = typer.Option(None, "--chain", help="Chain of the audited code (evm, solana, sui, ...)"),
+    date: str = typer.Option(None, "--date", help="Report date (YYYY-MM-DD)"),
+    url: str = typer.Option(None, "--url", help="Link to the published report")
+):
+    """Import a published report from metadata (YAML/JSON) or Markdown."""
+    from commands.precedent import import_report
+    _invoke_click(import_report, {
+        'path': path,
+        'firm': firm,
+        'protocol': protocol,
+        'chain': chain,
+        'date': date,
+        'url': url
+    })
+
+
+@precedent_app.command("solodit")
+def precedent_solodit(
+    query: str = typer.Argument(..., help="Search keywords"),
+    limit: int = typer.Option(50, "--limit", "-l", help="Maximum findings to import"),
+    chain: str = typer.Option(None, "--chain", help="Chain to record on the imported reports")
+):
+    """Import Solodit search results, one report per firm and protocol."""
+    from commands.precedent import import_solodit
+    _invoke_click(import_solodit, {'query': query, 'limit': limit, 'chain': chain})
+
+
+@precedent_app.command("list")
+def precedent_list():
+    """List imported reports."""
+    from commands.precedent import list_reports
+    _invoke_click(list_reports, {})
+
+
+@precedent_app.command("remove")
+def precedent_remove(
+    key: str = typer.Argument(..., help="Report key (see `precedent list`)")
+):
+    """Remove an imported report by key."""
+    from commands.precedent import remove_report
+    _invoke_click(remove_report, {'key': key})
+
+
+@precedent_app.command("match")
+def precedent_match(
+    project_name: str = typer.Argument(..., help="Project name"),
+    static: bool = typer.Option(False, "--static", help="Match static-analysis hits instead of the hypothesis store"),
+    limit: int = typer.Option(3, "--limit", "-l", help="Matches shown per hypothesis"),
+    min_score: float = typer.Option(0.3, "--min-score", help="Minimum match score (0-1)"),
+    annotate: bool = typer.Option(False, "--annotate", help="Record matches and suggested severity on each hypothesis"),
+    as_json: bool = typer.Option(False, "--json", help="Print matches as JSON")
+):
+    """Match a project's hypotheses or detector hits against published findings."""
+    from commands.precedent import match
+    _invoke_click(match, {
+        'project_name': project_name,
+        'static': static,
+        'limit': limit,
+        'min_score': min_score,
+        'annotate': annotate,
+        'as_json': as_json
+    })
+
+
+# ─────────────────────────────────────────────────────────────────────────────
+# Server Commands
+# ─────────────────────────────────────────────────────────────────────────────
+
+@server_app.command("tenant-add")
+def server_tenant_add(
+    tenant_id: str = typer.Argument(..., help="Tenant id (lowercase letters, digits, '-' and '_')"),
+    name: str = typer.Option(None, "--name", help="Display name (default: the id)"),
+    data_dir: str = typer.Option(None, "--data-dir", help="Server data directory (default: ~/.hound/server)")
+):
+    """Register a tenant and create its storage namespace."""
+    from commands.server import tenant_add
+    _invoke_click(tenant_add, {'tenant_id': tenant_id, 'name': name, 'data_dir': data_dir})
+
+
+@server_app.command("tenants")
+def server_tenants(
+    data_dir: str = typer.Option(None, "--data-dir", help="Server data directory (default: ~/.hound/server)")
+):
+    """List tenants with their key and project counts."""
+    from commands.server import tenants
+    _invoke_click(tenants, {'data_dir': data_dir})
+
+
+@server_app.command("tenant-remove")
+def server_tenant_remove(
+    tenant_id: str = typer.Argument(..., help="Tenant id"),
+    data_dir: str = typer.Option(None, "--data-dir", help="Server data directory (default: ~/.hound/server)")
+):
+    """Unregister a tenant and revoke its keys (its storage is kept)."""
+    from commands.server import tenant_remove
+    _invoke_click(tenant_remove, {'tenant_id': tenant_id, 'data_dir': data_dir})
+
+
+@server_app.command("key-create")
+def server_key_create(
+    tenant_id: str = typer.Argument(..., help="Tenant the key belongs to"),
+    role: str = typer.Option("viewer", "--role", help="viewer, triager or admin"),
+    label: str = typer.Option("", "--label", help="Who or what the key is for"),
+    data_dir: str = typer.Option(None, "--data-dir", help="Server data directory (default: ~/.hound/server)")
+):
+    """Issue an API key for a tenant; the key is shown only once."""
+    from commands.server import key_create
+    _invoke_click(key_create, {'tenant_id': tenant_id, 'role': role, 'label': label, 'data_dir': data_dir})
+
+
+@server_app.command("keys")
+def server_keys(
+    tenant_id: str = typer.Option(None, "--tenant", help="Only this tenant's keys"),
+    as_json: bool = typer.Option(False, "--json", help="Print keys as JSON"),
+    data_dir: str = typer.Option(None, "--data-dir", help="Server data directory (default: ~/.hound/server)")
+):
+    """List API keys (never their secrets)."""
+    from commands.server import keys
+    _invoke_click(keys, {'tenant_id': tenant_id, 'as_json': as_json, 'data_dir': data_dir})
+
+
+@server_app.command("key-revoke")
+def server_key_revoke(
+    key_id: str = typer.Argument(..., help="Key id (see `server keys`)"),
+    data_dir: str = typer.Option(None, "--data-dir", help="Server data directory (default: ~/.hound/server)")
+):
+    """Revoke an API key."""
+    from commands.server import key_revoke
+    _invoke_click(key_revoke, {'key_id': key_id, 'data_dir': data_dir})
+
+
+@server_app.command("serve")
+def server_serve(
+    host: str = typer.Option("127.0.0.1", "--host", help="Listen address"),
+    port: int = typer.Option(8080, "--port", help="Listen port"),
+    data_dir: str = typer.Option(None, "--data-dir", help="Server data directory (default: ~/.hound/server)")
+):
+    """Serve the tenant-scoped JSON API until interrupted."""
+    from commands.server import serve
+    _invoke_click(serve, {'host': host, 'port': port, 'data_dir': data_dir})
+
+
+@server_app.command("scan")
+def server_scan(
+    project_name: str = typer.Argument(..., help="Project name on the server"),
+    url: str = typer.Option("http://127.0.0.1:8080", "--url", help="Server URL"),
+    api_key: str = typer.Option(None, "--key", help="API key (default: $BASKERVILLE_API_KEY)"),
+    tools: list[str] = typer.Option(None, "--tool", help="Only run this detector (repeatable)"),
+    scan_id: str = typer.Option(None, "--attach", help="Follow an existing scan instead of starting one")
+):
+    """Run static analysis on a server-side project, showing its progress as it streams."""
+    from commands.server import scan
+    _invoke_click(scan, {'project_name': project_name, 'url': url, 'api_key': api_key,
+                         'tools': tuple(tools or ()), 'scan_id': scan_id})
+
+
+# ─────────────────────────────────────────────────────────────────────────────
+# Engagement Archive Commands
+# ─────────────────────────────────────────────────────────────────────────────
+
+@export_app.command("engagement")
+def export_engagement(
+    project_name: str = typer.Argument(..., help="Project name"),
+    output: str = typer.Option(None, "--output", "-o", help="Archive file (default: <project>-engagement-<date>.tar.gz)"),
+    include_source: bool = typer.Option(False, "--include-source", help="Also archive the audited source"),
+    no_bounty: bool = typer.Option(False, "--no-bounty", help="Leave out the bounty contests linked to the project")
+):
+    """Write a project's findings, triage, PoCs, reports and manifest to one archive."""
+    from commands.archive import export_cmd
+    _invoke_click(export_cmd, {'project_name': project_name, 'output': output, 'include_source': include_source,
+                               'no_bounty': no_bounty})
+
+
+@import_app.command("engagement")
+def import_engagement(
+    archive: str = typer.Argument(..., help="Engagement archive (.tar.gz)"),
+    name: str = typer.Option(None, "--name", help="Project name (default: the exported project's name)"),
+    source_path: str = typer.Option(None, "--source", help="Audited source on this machine"),
+    force: bool = typer.Option(False, "--force", "-f", help="Replace an existing project and contests"),
+    verify_only: bool = typer.Option(False, "--verify-only", help="Check the archive's checksums without importing")
+):
+    """Recreate a project and its bounty contests from an engagement archive."""
+    from commands.archive import import_cmd
+    _invoke_click(import_cmd, {'archive': archive, 'name': name, 'source_path': source_path, 'force': force,
+                               'verify_only': verify_only})
+
+
 # ─────────────────────────────────────────────────────────────────────────────
 # Baskerville-specific Commands
 # ─────────────────────────────────────────────────────────────────────────────
@@ -326,9 +1737,10 @@ def bounty_archive(
 @app.command()
 def version():
     """Show Baskerville version."""
-    console.print("[bold]Baskerville[/bold] v1.0.0")
+    from extensions.reporting.manifest import BASKERVILLE_VERSION, HOUND_VERSION
+    console.print(f"[bold]Baskerville[/bold] v{BASKERVILLE_VERSION}")
     console.print("Extended security analysis platform")
-    console.print("[dim]Built on Hound v2.0.0[/dim]")
+    console.print(f"[dim]Built on Hound v{HOUND_VERSION}[/dim]")
 
 
 def main():
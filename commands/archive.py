@@ -0,0 +1,109 @@
+"""
+Engagement archive commands.
+
+Usage:
+    ./baskerville.py export engagement <project> [--output FILE] [--include-source] [--no-bounty]
+    ./baskerville.py import engagement <archive> [--name NAME] [--source PATH] [--force] [--verify-only]
+"""
+
+import sys
+from datetime import datetime
+from pathlib import Path
+
+import click
+from rich.console import Console
+
+sys.path.insert(0, str(Path(__file__).parent.parent))
+
+from commands.project import ProjectManager
+from extensions.bounty.storage import BountyStorage
+from extensions.reporting.archive import (
+    ARCHIVE_SUFFIX, ArchiveError, export_engagement, import_engagement, read_archive,
+)
+
+
+console = Console()
+
+
+def get_manager() -> ProjectManager:
+    """Get the project manager (default: ~/.hound/projects)."""
+    return ProjectManager()
+
+
+def get_bounty_storage() -> BountyStorage:
+    """Get the bounty storage (default: ~/.hound/bounty)."""
+    return BountyStorage()
+
+
+def _print_sections(sections: dict) -> None:
+    console.print(f"  Findings:  {sections.get('hypotheses', 0)} ({sections.get('triaged', 0)} triaged)")
+    console.print(f"  PoCs:      {sections.get('pocs', 0)}")
+    console.print(f"  Reports:   {sections.get('reports', 0)}")
+    console.print(f"  Sessions:  {sections.get('sessions', 0)}")
+    console.print(f"  Contests:  {sections.get('contests', 0)} ({sections.get('bounty_findings', 0)} bounty findings)")
+
+
+@click.group("export")
+def export():
+    """Export complete engagements."""
+    pass
+
+
+@export.command("engagement")
+@click.argument("project_name")
+@click.option("--output", "-o", default=None,
+              help=f"Archive file (default: <project>-engagement-<date>{ARCHIVE_SUFFIX})")
+@click.option("--include-source", is_flag=True, help="Also archive the audited source (without build output)")
+@click.option("--no-bounty", is_flag=True, help="Leave out the bounty contests linked to the project")
+def export_cmd(project_name: str, output: str | None, include_source: bool, no_bounty: bool):
+    """Write a project's findings, triage, PoCs, reports and manifest to one archive."""
+    from utils.config_loader import load_config
+
+    project = get_manager().get_project(project_name)
+    if not project:
+        console.print(f"[red]Project '{project_name}' not found[/red]")
+        raise SystemExit(1)
+    out_path = Path(output) if output else Path(
+        f"{project_name}-engagement-{datetime.now().strftime('%Y%m%d')}{ARCHIVE_SUFFIX}")
+    manifest = export_engagement(project, out_path, bounty_dir=get_bounty_storage().base_dir,
+                                 include_source=include_source, include_bounty=not no_bounty, config=load_config())
+
+    _print_sections(manifest["sections"])
+    console.print(f"  Files:     {len(manifest['files'])}" + (" (with source)" if manifest["includes_source"] else ""))
+    console.print(f"[green]Engagement archive written to {out_path}[/green]")
+    console.print("[dim]Sign it for retention with: ./baskerville.py reporting sign "
+                  f"{out_path} --key signing-key.pem[/dim]")
+
+
+@click.group("import")
+def import_():
+    """Import complete engagements."""
+    pass
+
+
+@import_.command("engagement")
+@click.argument("archive", type=click.Path(exists=True, dir_okay=False))
+@click.option("--name", default=None, help="Project name (default: the exported project's name)")
+@click.option("--source", "source_path", default=None, help="Audited source on this machine")
+@click.option("--force", "-f", is_flag=True, help="Replace an existing project and contests of the same names")
+@click.option("--verify-only", is_flag=True, help="Check the archive's checksums without importing")
+def import_cmd(archive: str, name: str | None, source_path: str | None, force: bool, verify_only: bool):
+    """Recreate a project and its bounty contests from an engagement archive."""
+    try:
+        if verify_only:
+            manifest, files = read_archive(Path(archive))
+            project = manifest.get("project", {})
+            console.print(f"[bold]{project.get('name')}[/bold] exported {str(manifest.get('exported_at'))[:19]}")
+            _print_sections(manifest.get("sections", {}))
+            console.print(f"[green]Archive OK: {len(files)} files match their checksums[/green]")
+            return
+        result = import_engagement(Path(archive), get_manager(), get_bounty_storage(), name=name,
+                                   source_path=Path(source_path) if source_path else None, force=force)
+    except ArchiveError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+
+    _print_sections(result["sections"])
+    for warning in result["warnings"]:
+        console.print(f"[yellow]{warning}[/yellow]")
+    console.print(f"[green]Imported project {result['project']} ({result['files']} files) to {result['path']}[/green]")
@@ -10,12 +10,20 @@ Usage:
     ./hound.py bounty link <contest-id> <project>         # Link to Hound project
     ./hound.py bounty import <contest-id>                 # Import findings from project
     ./hound.py bounty review <contest-id>                 # Interactive review workflow
+    ./hound.py bounty review <contest-id> -t oracle       # Review only findings with a taxonomy tag
+    ./hound.py bounty review <contest-id> --assignee alice  # Review only findings assigned to alice
+    ./hound.py bounty assign <contest-id> <finding-id>... --to alice --reviewer bob --due 2026-11-01
+    ./hound.py bounty findings <contest-id> [--assignee] [--reviewer] [--unassigned] [--overdue]
     ./hound.py bounty export <contest-id>                 # Export for submission
+    ./hound.py bounty export <contest-id> -f files        # findings/ directory, one file per finding
+    ./hound.py bounty export <contest-id> --assignee alice  # Export only alice's findings
     ./hound.py bounty stats                               # Show statistics
 """
 
+import json
 import sys
 import asyncio
+from datetime import date, timedelta
 from pathlib import Path
 
 import click
@@ -29,8 +37,9 @@ sys.path.insert(0, str(Path(__file__).parent.parent))
 
 from extensions.bounty import (
     Contest, ContestState, Finding, FindingState, Severity,
-    ContestScraper, BountyStorage, get_formatter, FORMATTERS
+    ContestScraper, BountyStorage, get_formatter, FORMATTERS, FindingFileWriter
 )
+from extensions.knowledge.taxonomy import facet_counts, filter_by_tags, resolve_tags
 
 
 console = Console()
@@ -41,6 +50,39 @@ def get_storage() -> BountyStorage:
     return BountyStorage()
 
 
+def _mark_exported(storage: BountyStorage, finding: Finding) -> None:
+    """Move an accepted or refined finding to EXPORTED and persist it."""
+    if finding.state == FindingState.EXPORTED:
+        return
+    if finding.state == FindingState.ACCEPTED:
+        finding.transition_to(FindingState.REFINED)
+    finding.transition_to(FindingState.EXPORTED)
+    storage.save_finding(finding)
+
+
+def _parse_due(text: str) -> date | None:
+    """Due date from "YYYY-MM-DD", "+3d" (days from today) or "none" (cleared → None)."""
+    text = text.strip().lower()
+    if text in ("", "none"):
+        return None
+    if text.startswith("+") and text.endswith("d") and text[1:-1].isdigit():
+        return date.today() + timedelta(days=int(text[1:-1]))
+    try:
+        return date.fromisoformat(text)
+    except ValueError:
+        raise click.BadParameter(f"invalid due date: {text} (use YYYY-MM-DD, +Nd or none)")
+
+
+def _assignment(finding: Finding) -> str:
+    """One-line assignee/reviewer/due summary."""
+    parts = [f"Assignee: {finding.assignee or 'unassigned'}"]
+    if finding.reviewer:
+        parts.append(f"Reviewer: {finding.reviewer}")
+    if finding.due_date:
+        parts.append(f"Due: {finding.due_date.isoformat()}" + (" (overdue)" if finding.is_overdue else ""))
+    return " | ".join(parts)
+
+
 @click.group("bounty")
 def bounty():
     """Bounty workflow for audit contests."""
@@ -206,6 +248,10 @@ def show(contest_id: str):
         console.print(findings_table)
 
         console.print(f"\n[dim]Accepted: {stats['accepted']} | Rejected: {stats['rejected']} | Pending: {stats['pending_review']}[/dim]")
+        if stats["by_assignee"]:
+            workload = ", ".join(f"{name} ({n})" for name, n in sorted(stats["by_assignee"].items()))
+            console.print(f"[dim]Assigned: {workload} | Unassigned: {stats['unassigned']} | "
+                          f"Overdue: {stats['overdue']}[/dim]")
 
 
 @bounty.command("add")
@@ -341,7 +387,11 @@ def import_findings(contest_id: str, force: bool):
 @bounty.command("review")
 @click.argument("contest_id")
 @click.option("--severity", "-s", type=click.Choice(["critical", "high", "medium", "low"]), help="Filter by severity")
-def review(contest_id: str, severity: str | None):
+@click.option("--tag", "-t", "tags", multiple=True, help="Filter by taxonomy tag (repeatable; any tag matches)")
+@click.option("--assignee", help="Only findings assigned to this auditor")
+@click.option("--reviewer", help="Only findings this auditor reviews")
+def review(contest_id: str, severity: str | None, tags: tuple[str, ...], assignee: str | None = None,
+           reviewer: str | None = None):
     """Interactive review workflow for findings."""
     storage = get_storage()
     contest = storage.load_contest(contest_id)
@@ -351,29 +401,39 @@ def review(contest_id: str, severity: str | None):
         return
 
     # Get findings needing review
-    findings = storage.list_findings(contest_id, state=FindingState.DETECTED)
-    findings += storage.list_findings(contest_id, state=FindingState.TRIAGED)
+    owners = {"assignee": assignee, "reviewer": reviewer}
+    findings = storage.list_findings(contest_id, state=FindingState.DETECTED, **owners)
+    findings += storage.list_findings(contest_id, state=FindingState.TRIAGED, **owners)
 
     if severity:
         findings = [f for f in findings if f.severity.value == severity]
+    try:
+        findings = filter_by_tags(findings, tags)
+    except ValueError as e:
+        console.print(f"[red]{e}[/red]")
+        return
 
     if not findings:
         console.print("[green]No findings pending review.[/green]")
         return
 
     console.print(f"\n[bold]Review Mode: {len(findings)} findings to review[/bold]")
-    console.print("[dim]Commands: (a)ccept, (r)eject, (s)kip, (e)dit, (q)uit[/dim]\n")
+    facets = facet_counts(findings)
+    console.print("[dim]Tags: " + ", ".join(f"{tag} ({n})" for tag, n in facets.items()) + "[/dim]")
+    console.print("[dim]Commands: (a)ccept, (r)eject, (s)kip, (e)dit, (o)wner, (q)uit[/dim]\n")
 
     for i, finding in enumerate(findings, 1):
         console.print(f"\n[bold cyan]Finding {i}/{len(findings)}[/bold cyan]")
         console.print(Panel(
             f"""[bold]{finding.title}[/bold]
 [{finding.severity.value.upper()}] {finding.vulnerability_type or 'Unknown type'}
+[dim]Tags: {', '.join(finding.tags)}[/dim]
 
 {finding.description[:500]}{'...' if len(finding.description) > 500 else ''}
 
 [dim]Location: {finding.file_path or 'Unknown'}[/dim]
 [dim]Confidence: {finding.confidence:.0%}[/dim]
+[dim]{_assignment(finding)}[/dim]
 """,
             title=f"[{finding.severity.value}] {finding.id}",
             border_style="yellow" if finding.severity in [Severity.HIGH, Severity.CRITICAL] else "blue"
@@ -386,7 +446,7 @@ def review(contest_id: str, severity: str | None):
         # Get action
         action = Prompt.ask(
             "Action",
-            choices=["a", "r", "s", "e", "q", "accept", "reject", "skip", "edit", "quit"],
+            choices=["a", "r", "s", "e", "o", "q", "accept", "reject", "skip", "edit", "owner", "quit"],
             default="s"
         )
 
@@ -407,13 +467,32 @@ def review(contest_id: str, severity: str | None):
             new_title = Prompt.ask("Title", default=finding.title)
             new_sev = Prompt.ask("Severity", choices=["critical", "high", "medium", "low", "informational", "gas"], default=finding.severity.value)
             new_desc = Prompt.ask("Description (enter to keep)", default="")
+            new_tags = Prompt.ask("Tags (comma-separated)", default=", ".join(finding.tags))
 
             finding.title = new_title
             finding.severity = Severity(new_sev)
+            try:
+                finding.tags = resolve_tags(t.strip() for t in new_tags.split(",") if t.strip()) or finding.tags
+            except ValueError as e:
+                console.print(f"[yellow]Tags unchanged: {e}[/yellow]")
             if new_desc:
                 finding.description = new_desc
             storage.save_finding(finding)
             console.print("[blue]Updated[/blue]")
+        elif action in ["o", "owner"]:
+            new_assignee = Prompt.ask("Assignee", default=finding.assignee)
+            new_reviewer = Prompt.ask("Reviewer", default=finding.reviewer)
+            new_due = Prompt.ask("Due (YYYY-MM-DD, +Nd or none)",
+                                 default=finding.due_date.isoformat() if finding.due_date else "none")
+            try:
+                due = _parse_due(new_due)
+            except click.BadParameter as e:
+                console.print(f"[yellow]Due date unchanged: {e.message}[/yellow]")
+                due = finding.due_date
+            finding.assign(new_assignee, new_reviewer)
+            finding.due_date = due
+            storage.save_finding(finding)
+            console.print(f"[blue]{_assignment(finding)}[/blue]")
         # skip does nothing
 
     # Update contest state after review
@@ -426,11 +505,99 @@ def review(contest_id: str, severity: str | None):
     console.print("\n[bold]Review session complete.[/bold]")
 
 
+@bounty.command("assign")
+@click.argument("contest_id")
+@click.argument("finding_ids", nargs=-1, required=True)
+@click.option("--to", "assignee", help="Auditor verifying the findings (\"\" unassigns)")
+@click.option("--reviewer", help="Auditor signing off on the verdict (\"\" clears)")
+@click.option("--due", help="Due date: YYYY-MM-DD, +Nd (days from today) or none")
+def assign(contest_id: str, finding_ids: tuple[str, ...], assignee: str | None, reviewer: str | None,
+           due: str | None):
+    """Assign findings to an auditor and reviewer, with an optional due date."""
+    storage = get_storage()
+    if not storage.load_contest(contest_id):
+        console.print(f"[red]Contest not found: {contest_id}[/red]")
+        raise SystemExit(1)
+    if assignee is None and reviewer is None and due is None:
+        console.print("[red]Nothing to set: pass --to, --reviewer and/or --due[/red]")
+        raise SystemExit(1)
+    try:
+        due_date = _parse_due(due) if due is not None else None
+    except click.BadParameter as e:
+        console.print(f"[red]{e.message}[/red]")
+        raise SystemExit(1)
+
+    missing = []
+    for finding_id in finding_ids:
+        finding = storage.load_finding(contest_id, finding_id)
+        if finding is None:
+            missing.append(finding_id)
+            continue
+        finding.assign(assignee, reviewer)
+        if due is not None:
+            finding.due_date = due_date
+        storage.save_finding(finding)
+        console.print(f"  [green]{finding.id}[/green]  {_assignment(finding)}")
+    if missing:
+        console.print(f"[red]Findings not found: {', '.join(missing)}[/red]")
+        raise SystemExit(1)
+
+
+@bounty.command("findings")
+@click.argument("contest_id")
+@click.option("--state", "-s", type=click.Choice([s.value for s in FindingState]), help="Filter by state")
+@click.option("--severity", type=click.Choice([s.value for s in Severity]), help="Filter by severity")
+@click.option("--assignee", help="Only findings assigned to this auditor")
+@click.option("--reviewer", help="Only findings this auditor reviews")
+@click.option("--unassigned", is_flag=True, help="Only findings nobody is assigned to")
+@click.option("--overdue", is_flag=True, help="Only findings still pending past their due date")
+@click.option("--json", "as_json", is_flag=True, help="Output as JSON")
+def list_findings(contest_id: str, state: str | None, severity: str | None, assignee: str | None,
+                  reviewer: str | None, unassigned: bool, overdue: bool, as_json: bool):
+    """List a contest's findings with their assignment."""
+    storage = get_storage()
+    if not storage.load_contest(contest_id):
+        console.print(f"[red]Contest not found: {contest_id}[/red]")
+        raise SystemExit(1)
+    findings = storage.list_findings(
+        contest_id,
+        state=FindingState(state) if state else None,
+        severity=severity,
+        assignee="" if unassigned else assignee,
+        reviewer=reviewer,
+        overdue=overdue,
+    )
+
+    if as_json:
+        keys = ["id", "title", "severity", "state", "assignee", "reviewer", "due_date"]
+        click.echo(json.dumps([{k: f.to_dict()[k] for k in keys} for f in findings], indent=2))
+        return
+    if not findings:
+        console.print("[yellow]No matching findings.[/yellow]")
+        return
+
+    table = Table(show_header=True, header_style="bold")
+    table.add_column("ID")
+    table.add_column("Severity")
+    table.add_column("State")
+    table.add_column("Title")
+    table.add_column("Assignee")
+    table.add_column("Reviewer")
+    table.add_column("Due")
+    for f in findings:
+        due_text = f.due_date.isoformat() if f.due_date else "-"
+        table.add_row(f.id, f.severity.value, f.state.value, f.title[:50], f.assignee or "-", f.reviewer or "-",
+                      f"[red]{due_text}[/red]" if f.is_overdue else due_text)
+    console.print(table)
+
+
 @bounty.command("export")
 @click.argument("contest_id")
 @click.option("--output", "-o", type=click.Path(), help="Output directory")
-@click.option("--format", "-f", "fmt", type=click.Choice(["individual", "report", "both"]), default="individual", help="Export format")
-def export(contest_id: str, output: str | None, fmt: str):
+@click.option("--format", "-f", "fmt", type=click.Choice(["individual", "report", "both", "files"]), default="individual", help="Export format (files: findings/ directory with one Markdown file per finding)")
+@click.option("--assignee", help="Only findings assigned to this auditor")
+@click.option("--reviewer", help="Only findings this auditor reviews")
+def export(contest_id: str, output: str | None, fmt: str, assignee: str | None = None, reviewer: str | None = None):
     """Export findings for platform submission."""
     storage = get_storage()
     contest = storage.load_contest(contest_id)
@@ -442,7 +609,11 @@ def export(contest_id: str, output: str | None, fmt: str):
     # Get exportable findings (accepted or refined)
     findings = storage.get_exportable_findings(contest_id)
     accepted = storage.list_findings(contest_id, state=FindingState.ACCEPTED)
-    findings = list(set(findings + accepted))
+    findings = list({f.id: f for f in findings + accepted}.values())
+    if assignee is not None:
+        findings = [f for f in findings if f.assignee.lower() == assignee.strip().lower()]
+    if reviewer is not None:
+        findings = [f for f in findings if f.reviewer.lower() == reviewer.strip().lower()]
 
     if not findings:
         console.print("[yellow]No findings ready for export.[/yellow]")
@@ -462,6 +633,20 @@ def export(contest_id: str, output: str | None, fmt: str):
 
     console.print(f"\n[bold]Exporting {len(findings)} findings for {contest.platform}[/bold]\n")
 
+    from extensions.reporting.manifest import build_manifest, embed_manifest, project_detectors
+
+    project_dir = Path(contest.project_path) if contest.project_path else None
+    project_file = project_dir / "project.json" if project_dir else None
+    source_path = None
+    if project_file and project_file.exists():
+        source_path = json.loads(project_file.read_text()).get("source_path")
+    manifest = build_manifest(
+        "bounty export",
+        {"contest_id": contest_id, "output": output, "fmt": fmt, "assignee": assignee, "reviewer": reviewer},
+        source=source_path,
+        detectors=project_detectors(project_dir),
+    )
+
     if fmt in ["individual", "both"]:
         # Export individual findings
         for finding in findings:
@@ -471,16 +656,22 @@ def export(contest_id: str, output: str | None, fmt: str):
             filepath.write_text(formatted.to_markdown())
             console.print(f"  [green]Exported:[/green] {filename}")
 
-            # Mark as exported
-            if finding.state != FindingState.EXPORTED:
-                finding.transition_to(FindingState.REFINED)
-                finding.transition_to(FindingState.EXPORTED)
-                storage.save_finding(finding)
+            _mark_exported(storage, finding)
+
+    if fmt == "files":
+        # One Markdown file per finding, for review in the audit repo
+        writer = FindingFileWriter(output_dir, project_dir=contest.project_path or None)
+        for path in writer.write(findings, contest_name=contest.name):
+            console.print(f"  [green]Exported:[/green] findings/{path.name}")
+        index = writer.findings_dir / "README.md"
+        index.write_text(embed_manifest(index.read_text(), manifest, "markdown"))
+        for finding in findings:
+            _mark_exported(storage, finding)
 
     if fmt in ["report", "both"]:
         # Export full report (if formatter supports it)
         if hasattr(formatter, "format_full_report"):
-            report = formatter.format_full_report(contest.name, findings)
+            report = embed_manifest(formatter.format_full_report(contest.name, findings), manifest, "markdown")
             report_path = output_dir / "full_report.md"
             report_path.write_text(report)
             console.print(f"\n  [green]Full report:[/green] {report_path}")
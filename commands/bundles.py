@@ -0,0 +1,108 @@
+"""
+Content-addressed knowledge bundles.
+
+Usage:
+    ./baskerville.py bundle build <dir> --name NAME --version VER [--key KEY] [-o DIR]
+    ./baskerville.py bundle add <archive> [--key PUBKEY]
+    ./baskerville.py bundle list [--json]
+    ./baskerville.py bundle verify [<workspace>] [--json]   # Check baskerville.toml pins
+"""
+
+import json
+import sys
+from pathlib import Path
+
+import click
+from rich.console import Console
+from rich.table import Table
+
+sys.path.insert(0, str(Path(__file__).parent.parent))
+
+from extensions.knowledge.bundles import CONFIG_FILE, BundleError, BundleStore, build_bundle, resolve_bundles
+
+
+console = Console()
+
+
+@click.group("bundle")
+def bundle():
+    """Signed, content-addressed checklist/template/tip bundles."""
+    pass
+
+
+@bundle.command("build")
+@click.argument("source", type=click.Path(exists=True, file_okay=False))
+@click.option("--name", "-n", required=True, help="Bundle name")
+@click.option("--version", "-v", "version", required=True, help="Bundle version")
+@click.option("--description", "-d", default="", help="What the bundle is for")
+@click.option("--key", "key_path", default=None, type=click.Path(exists=True, dir_okay=False),
+              help="ed25519 private key (PEM) to sign the bundle with")
+@click.option("--output-dir", "-o", default=".", help="Directory for the archive")
+def build(source: str, name: str, version: str, description: str, key_path: str | None, output_dir: str):
+    """Pack a directory's checklists/, templates/ and tips/ into a bundle."""
+    try:
+        archive, digest = build_bundle(Path(source), Path(output_dir), name, version, description=description,
+                                       key=Path(key_path) if key_path else None)
+    except BundleError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    console.print(f"[green]Wrote {archive}[/green]{' (signed)' if key_path else ''}")
+    console.print(f"  digest: {digest}")
+    console.print(f"\n[dim]Pin it in {CONFIG_FILE}:[/dim]")
+    click.echo(f'[[bundle]]\nname = "{name}"\ndigest = "{digest}"')
+
+
+@bundle.command("add")
+@click.argument("archive", type=click.Path(exists=True, dir_okay=False))
+@click.option("--key", "key_path", default=None, type=click.Path(exists=True, dir_okay=False),
+              help="Only add the bundle if this public key (PEM) signed it")
+def add(archive: str, key_path: str | None):
+    """Verify a bundle archive and add it to the local store."""
+    try:
+        added = BundleStore().add(Path(archive), public_key=Path(key_path) if key_path else None)
+    except BundleError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    console.print(f"[green]Added {added.name} {added.version}[/green] ({len(added.files)} files)")
+    console.print(f"  digest: {added.digest}")
+    if key_path:
+        console.print(f"  signed by: {added.signer()}")
+
+
+@bundle.command("list")
+@click.option("--json", "as_json", is_flag=True, help="Print the bundles as JSON")
+def list_bundles(as_json: bool):
+    """List the bundles in the local store."""
+    bundles = BundleStore().list()
+    if as_json:
+        click.echo(json.dumps([b.to_dict() for b in bundles], indent=2))
+        return
+    if not bundles:
+        console.print("[yellow]No bundles added[/yellow]")
+        return
+    table = Table(show_header=True, header_style="bold")
+    for column in ("Name", "Version", "Digest", "Files", "Signer"):
+        table.add_column(column)
+    for b in bundles:
+        table.add_row(b.name, b.version, b.digest, str(len(b.files)), b.signer() or "-")
+    console.print(table)
+
+
+@bundle.command("verify")
+@click.argument("workspace", default=".", type=click.Path(exists=True, file_okay=False))
+@click.option("--json", "as_json", is_flag=True, help="Print the resolved bundles as JSON")
+def verify(workspace: str, as_json: bool):
+    """Check the bundles a workspace's baskerville.toml pins are present, intact and signed as pinned."""
+    try:
+        bundles = resolve_bundles(Path(workspace))
+    except BundleError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    if as_json:
+        click.echo(json.dumps([b.to_dict() for b in bundles], indent=2))
+        return
+    if not bundles:
+        console.print(f"[yellow]No bundles pinned in {Path(workspace) / CONFIG_FILE}[/yellow]")
+        return
+    for b in bundles:
+        console.print(f"[green]✓[/green] {b.name} {b.version} {b.digest}")
@@ -0,0 +1,60 @@
+"""
+Measure detector precision on the benchmark corpus and calibrate confidence.
+
+Usage:
+    ./baskerville.py calibrate [<corpus.yaml>] [--output FILE] [--json]
+"""
+
+import json
+import sys
+from pathlib import Path
+
+import click
+from rich.console import Console
+from rich.table import Table
+
+sys.path.insert(0, str(Path(__file__).parent.parent))
+
+from extensions.static.confidence import TIERS, measure, write_calibration
+
+
+console = Console()
+
+
+@click.command("calibrate")
+@click.argument("corpus", required=False)
+@click.option("--output", "-o", default=None,
+              help="Write the calibration here (extensions/static/confidence_calibration.yaml updates the default)")
+@click.option("--json", "as_json", is_flag=True, help="Print per-hit labels as JSON")
+def calibrate(corpus: str | None, output: str | None, as_json: bool):
+    """Run the detectors over a labelled corpus and report precision per detector and tier."""
+    try:
+        calibration, rows = measure(Path(corpus) if corpus else None)
+    except (OSError, ValueError, KeyError) as e:
+        console.print(f"[red]Cannot read corpus: {e}[/red]")
+        raise SystemExit(1)
+
+    if as_json:
+        click.echo(json.dumps({"calibration": calibration.to_dict(), "hits": rows}, indent=2))
+    else:
+        table = Table(title=f"Detector precision ({calibration.corpus})", show_header=True, header_style="bold")
+        table.add_column("Detector")
+        table.add_column("Tier")
+        table.add_column("Hits", justify="right")
+        table.add_column("True positives", justify="right")
+        table.add_column("Precision", justify="right")
+        table.add_column("Confidence", justify="right")
+        for tool, tiers in sorted(calibration.detectors.items()):
+            for tier in sorted(tiers, key=TIERS.index):
+                counts = tiers[tier]
+                table.add_row(tool, tier, str(counts["hits"]), str(counts["true_positives"]),
+                              f"{counts['true_positives'] / counts['hits']:.0%}",
+                              f"{calibration.confidence(tool, tier):.2f}")
+        console.print(table)
+        if not rows:
+            console.print("[yellow]No detector produced a hit on the corpus[/yellow]")
+
+    if output:
+        path = write_calibration(calibration, Path(output))
+        if not as_json:
+            console.print(f"[green]Calibration written to {path}[/green]")
@@ -0,0 +1,144 @@
+"""
+Deployment consistency commands.
+
+Usage:
+    ./baskerville.py deployments check <deployments.yaml> [--deployment NAME ...] [--json] [--output FILE]
+    ./baskerville.py deployments check <deployments.yaml> --project <name> [--import-hypotheses]
+"""
+
+import hashlib
+import json
+import sys
+from datetime import datetime
+from pathlib import Path
+
+import click
+import yaml
+from rich.console import Console
+from rich.table import Table
+
+sys.path.insert(0, str(Path(__file__).parent.parent))
+
+from commands.project import ProjectManager
+
+
+console = Console()
+
+
+def _import_findings(hypotheses: list[dict], hyp_file: Path) -> int:
+    store = json.loads(hyp_file.read_text()) if hyp_file.exists() else {"version": "1.0", "hypotheses": {}}
+    imported = 0
+    for hyp in hypotheses:
+        key = "|".join([hyp["vulnerability_type"], hyp["title"]])
+        hyp_id = f"dep_{hashlib.md5(key.encode()).hexdigest()[:8]}"
+        if hyp_id not in store["hypotheses"]:
+            store["hypotheses"][hyp_id] = {**hyp, "id": hyp_id, "created_at": datetime.now().isoformat(),
+                                           "created_by": "deployment-check"}
+            imported += 1
+    hyp_file.write_text(json.dumps(store, indent=2))
+    return imported
+
+
+def _client(provider_spec: str | None):
+    from extensions.rpc import RpcCache, RpcClient, default_cache_dir
+
+    return RpcClient(provider_spec, cache=RpcCache(default_cache_dir()))
+
+
+@click.group("deployments")
+def deployments():
+    """Consistency of a protocol's deployments across clusters and chains."""
+    pass
+
+
+@deployments.command("check")
+@click.argument("config_path", type=click.Path(exists=True, dir_okay=False))
+@click.option("--deployment", "names", multiple=True, help="Deployment to compare (repeatable; default: all)")
+@click.option("--json", "as_json", is_flag=True, help="Print snapshots and findings as JSON")
+@click.option("--output", "-o", default=None, help="Write snapshots and findings to a file")
+@click.option("--project", "project_name", default=None, help="Save results under this project")
+@click.option("--import-hypotheses", is_flag=True, help="Import findings into the project's hypothesis store")
+def check(config_path: str, names: tuple[str, ...], as_json: bool, output: str | None, project_name: str | None,
+          import_hypotheses: bool):
+    """Compare program versions, configuration and authorities across deployments and flag divergence."""
+    from extensions.deployments import CHECKER_VERSION, check_deployments, load_deployments, take_snapshot
+    from extensions.reporting.manifest import build_manifest
+
+    path = Path(config_path)
+    try:
+        deployment_set = load_deployments(yaml.safe_load(path.read_text()) or {}, base=path.parent)
+    except (OSError, ValueError, TypeError, KeyError, yaml.YAMLError) as e:
+        console.print(f"[red]Invalid deployments file {path}: {e}[/red]")
+        raise SystemExit(1)
+    selected = [d for d in deployment_set.deployments if not names or d.name in names]
+    unknown = set(names) - {d.name for d in deployment_set.deployments}
+    if unknown or len(selected) < 2:
+        console.print(f"[red]Pick at least two deployments (unknown: {', '.join(sorted(unknown)) or 'none'})[/red]")
+        raise SystemExit(1)
+
+    snapshots = []
+    for dep in selected:
+        try:
+            client = _client(dep.provider_spec)
+        except ValueError as e:
+            console.print(f"[red]{dep.name}: {e}[/red]")
+            raise SystemExit(1)
+        snapshots.append(take_snapshot(dep, client, deployment_set))
+    findings = check_deployments(snapshots, deployment_set.builds)
+    hypotheses = [f.to_hypothesis() for f in findings]
+    export = {
+        "tool": "deployment-check",
+        "version": CHECKER_VERSION,
+        "snapshots": [s.to_dict() for s in snapshots],
+        "builds": deployment_set.builds,
+        "findings": [f.to_dict() for f in findings],
+        "manifest": build_manifest(
+            "deployments check",
+            {"config_path": str(path.resolve()), "names": list(names), "as_json": False, "output": output,
+             "project_name": project_name, "import_hypotheses": False},
+            detectors={"deployment-check": CHECKER_VERSION},
+        ),
+    }
+
+    out_path = Path(output) if output else None
+    if project_name:
+        project = ProjectManager().get_project(project_name)
+        if not project:
+            console.print(f"[red]Project '{project_name}' not found[/red]")
+            raise SystemExit(1)
+        project_dir = Path(project["path"])
+        out_path = out_path or project_dir / "deployments" / f"{path.stem}.json"
+        if import_hypotheses and hypotheses:
+            imported = _import_findings(hypotheses, project_dir / "hypotheses.json")
+            console.print(f"[green]Imported {imported} hypotheses to store[/green]")
+    if out_path:
+        out_path.parent.mkdir(parents=True, exist_ok=True)
+        out_path.write_text(json.dumps(export, indent=2))
+    if as_json:
+        click.echo(json.dumps(export, indent=2))
+        return
+
+    table = Table(show_header=True, header_style="bold")
+    for column in ("Deployment", "Program", "Build", "Upgrade authority"):
+        table.add_column(column)
+    for snap in snapshots:
+        for name, state in snap.programs.items():
+            authority = snap.authorities.get(f"{name}.upgrade_authority")
+            holder = f"{authority.kind} {authority.address or ''}".strip() if authority else "-"
+            build = (state.hash or "")[:12] if state.found else f"[red]{state.error}[/red]"
+            if state.found and state.hash and state.hash == deployment_set.builds.get(name):
+                build += " (audited)"
+            table.add_row(snap.deployment + (" *" if snap.production else ""), name, build, holder)
+    console.print(table)
+    for snap in snapshots:
+        for error in snap.errors:
+            console.print(f"[yellow]{snap.deployment}: {error}[/yellow]")
+    styles = {"high": "red", "medium": "yellow", "low": "blue"}
+    for hyp in hypotheses:
+        style = styles.get(hyp["severity"], "white")
+        console.print(f"  [{style}][{hyp['severity'].upper()}][/{style}] {hyp['title']}")
+        console.print(f"    {hyp['description']}")
+    if not hypotheses:
+        console.print("[green]Deployments are consistent[/green]")
+    if out_path:
+        console.print(f"[dim]Results written to {out_path}[/dim]")
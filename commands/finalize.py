@@ -6,6 +6,7 @@ import json
 import random
 import sys
 from dataclasses import dataclass
+from datetime import datetime
 from pathlib import Path
 
 import click
@@ -334,6 +335,9 @@ Be conservative - only confirm if the code clearly shows the vulnerability.
                     def update_status(data):
                         if hid in data["hypotheses"]:
                             data["hypotheses"][hid]["status"] = "confirmed"
+                            data["hypotheses"][hid].setdefault("status_history", []).append(
+                                {"status": "confirmed", "at": datetime.now().isoformat()}
+                            )
                             data["metadata"]["confirmed"] = sum(
                                 1 for h in data["hypotheses"].values() 
                                 if h["status"] == "confirmed"
@@ -0,0 +1,416 @@
+"""
+Program IR CLI commands.
+
+Usage:
+    ./baskerville.py ir inventory <project_or_path> [--format json|csv] [--output FILE]
+    ./baskerville.py ir tx-size <project_or_path> [--all] [--cluster NAME] [--json]
+    ./baskerville.py ir pda-map <project_or_path> [--json] [--output FILE]
+    ./baskerville.py ir privilege-paths <project_or_path> [--query NAME ...] [--json] [--save-graph]
+    ./baskerville.py ir graph <project_or_path> [--format dot|mermaid|json] [--output FILE] [--findings FILE ...]
+    ./baskerville.py ir anchor-config <project_or_path> [--cluster NAME] [--json]
+    ./baskerville.py ir metrics <project_or_path> [--top N] [--json] [--output FILE]
+"""
+
+import sys
+from pathlib import Path
+
+import click
+from rich.console import Console
+from rich.table import Table
+
+sys.path.insert(0, str(Path(__file__).parent.parent))
+
+from extensions.ir import load_programs
+
+
+console = Console()
+
+
+def resolve_source(target: str) -> tuple[Path, Path | None]:
+    """Map a project name or filesystem path to (source path, project dir)."""
+    path = Path(target).expanduser()
+    if path.exists():
+        return path, None
+    from commands.project import ProjectManager
+
+    project = ProjectManager().get_project(target)
+    if not project:
+        console.print(f"[red]'{target}' is neither a path nor a known project[/red]")
+        raise SystemExit(1)
+    return Path(project["source_path"]), Path(project["path"])
+
+
+@click.group("ir")
+def ir():
+    """Program IR exports."""
+    pass
+
+
+@ir.command("inventory")
+@click.argument("target")
+@click.option("--format", "fmt", type=click.Choice(["json", "csv"]), default="json", help="Output format")
+@click.option("--output", "-o", default=None, help="Output file (default: <project>/inventory.<fmt> or stdout)")
+def inventory(target: str, fmt: str, output: str | None):
+    """Export the instruction/account/authority inventory of every program."""
+    from extensions.ir.inventory import build_inventory, inventory_to_csv, write_inventory
+    from extensions.reporting.manifest import build_manifest, write_sidecar
+    from utils.config_loader import load_config
+
+    source, project_dir = resolve_source(target)
+    programs = load_programs(source)
+    if not programs:
+        console.print(f"[yellow]No programs found under {source}[/yellow]")
+        raise SystemExit(1)
+    data = build_inventory(programs, source)
+    data["manifest"] = build_manifest(
+        "ir inventory", {"target": target, "fmt": fmt, "output": output},
+        source=source, config=load_config(),
+        detectors={f"ir-{framework}": None for framework in sorted({p.framework for p in programs})},
+    )
+
+    out_path = Path(output) if output else (project_dir / f"inventory.{fmt}" if project_dir else None)
+    if out_path is None:
+        import json
+
+        click.echo(json.dumps(data, indent=2) if fmt == "json" else inventory_to_csv(data), nl=False)
+        return
+    write_inventory(data, out_path, fmt)
+    if fmt == "csv":
+        write_sidecar(out_path, data["manifest"])
+
+    table = Table(show_header=True, header_style="bold")
+    for column in ("Program", "Framework", "Instructions", "Accounts", "PDAs", "Authorities", "Dependencies"):
+        table.add_column(column)
+    for program in data["programs"]:
+        s = program["summary"]
+        table.add_row(
+            program["name"], program["framework"], str(s["instructions"]), str(s["accounts"]),
+            str(s["pdas"]), str(s["authorities"]), str(s["external_dependencies"]),
+        )
+    console.print(table)
+    console.print(f"[green]Inventory written to {out_path}[/green]")
+
+
+@ir.command("tx-size")
+@click.argument("target")
+@click.option("--all", "show_all", is_flag=True, help="Show every instruction, not only those that need an ALT")
+@click.option("--cluster", default=None,
+              help="Use this cluster's account lock limit, from its feature gates (default: 64 locks)")
+@click.option("--json", "as_json", is_flag=True, help="Print footprints as JSON")
+def tx_size(target: str, show_all: bool, as_json: bool, cluster: str | None = None):
+    """Flag Solana instructions that cannot fit in a legacy transaction."""
+    from extensions.ir.tx_size import MAX_TX_ACCOUNT_LOCKS, PACKET_DATA_SIZE, analyze_transactions
+
+    locks, where = MAX_TX_ACCOUNT_LOCKS, ""
+    if cluster:
+        from extensions.knowledge.feature_loader import ClusterFeatures
+
+        features = ClusterFeatures(cluster)
+        locks, where = features.limits().get("max_tx_account_locks", locks), f" on {features.cluster}"
+    source, _ = resolve_source(target)
+    footprints = analyze_transactions(load_programs(source), max_account_locks=locks)
+    if not footprints:
+        console.print(f"[yellow]No Solana instructions found under {source}[/yellow]")
+        raise SystemExit(1)
+    flagged = [f for f in footprints if f.status != "ok"]
+    if as_json:
+        import json
+
+        click.echo(json.dumps([f.to_dict() for f in (footprints if show_all else flagged)], indent=2))
+        return
+
+    table = Table(show_header=True, header_style="bold")
+    for column in ("Program", "Instruction", "Accounts", "Keys", "Signers", "Data", "Legacy", "v0 + ALT", "Status"):
+        table.add_column(column)
+    styles = {"ok": "green", "needs-alt": "yellow", "infeasible": "red"}
+    for f in footprints if show_all else flagged:
+        data = f"{f.data_bytes}" if f.data_exact else f"{f.data_bytes}+"
+        table.add_row(
+            f.program, f.instruction, str(f.accounts), str(f.unique_keys), str(f.signers), data,
+            str(f.legacy_bytes), str(f.v0_bytes), f"[{styles[f.status]}]{f.status}[/{styles[f.status]}]",
+        )
+    if show_all or flagged:
+        console.print(table)
+    console.print(
+        f"{len(flagged)} of {len(footprints)} instructions exceed the {PACKET_DATA_SIZE}-byte legacy transaction "
+        f"or {locks}-account lock limit{where}"
+    )
+    if any(f.uses_remaining_accounts for f in flagged):
+        console.print("[dim]Instructions reading remaining_accounts may need more than the minimum shown[/dim]")
+
+
+@ir.command("pda-map")
+@click.argument("target")
+@click.option("--json", "as_json", is_flag=True, help="Print the map and conflicts as JSON")
+@click.option("--output", "-o", default=None, help="Write the JSON map to a file")
+def pda_map(target: str, as_json: bool, output: str | None):
+    """Map every PDA derivation in the workspace and report seed collisions."""
+    from extensions.ir.pda_map import build_pda_map, find_conflicts, find_namespace_reuse, seed_constants
+
+    source, _ = resolve_source(target)
+    programs = load_programs(source)
+    constants = {p.name: seed_constants(p) for p in programs if p.chain == "solana"}
+    derivations = build_pda_map(programs, constants)
+    if not derivations:
+        console.print(f"[yellow]No PDA derivations found under {source}[/yellow]")
+        raise SystemExit(1)
+    conflicts = find_conflicts(derivations)
+    reuse = find_namespace_reuse(derivations, constants)
+    data = {
+        "source": str(source),
+        "derivations": [d.to_dict() for d in derivations],
+        "conflicts": [c.to_dict() for c in conflicts],
+        "seed_constants": [c.to_dict() for consts in constants.values() for c in consts.values()],
+        "namespace_reuse": [r.to_dict() for r in reuse],
+    }
+    if as_json or output:
+        import json
+
+        if output:
+            Path(output).write_text(json.dumps(data, indent=2))
+            console.print(f"[green]PDA map written to {output}[/green]")
+        else:
+            click.echo(json.dumps(data, indent=2))
+        return
+
+    table = Table(show_header=True, header_style="bold")
+    for column in ("Owner", "Account type", "Seeds", "Instruction", "Location"):
+        table.add_column(column)
+    for d in derivations:
+        owner = d.owner if d.owner == d.program else f"{d.owner} (from {d.program})"
+        table.add_row(owner, d.account_type, d.schema, d.instruction, f"{d.file}:{d.line}")
+    console.print(table)
+
+    styles = {"collision": "red", "cross-program": "yellow", "prefix": "dim"}
+    for c in conflicts:
+        style = styles[c.kind]
+        console.print(
+            f"[{style}]{c.kind} ({c.severity})[/{style}] {c.a.account_type} [{c.a.schema}] "
+            f"<-> {c.b.account_type} [{c.b.schema}]"
+        )
+    for r in reuse:
+        shared = r.ecosystem if r.kind == "ecosystem" else "workspace"
+        console.print(f'[yellow]namespace ({r.severity})[/yellow] "{r.value}" used by {", ".join(r.programs)} ({shared})')
+    console.print(f"{len(derivations)} derivations, {sum(1 for c in conflicts if c.kind == 'collision')} collisions")
+
+
+@ir.command("privilege-paths")
+@click.argument("target")
+@click.option("--query", "-q", "queries", multiple=True, help="Query to run (repeatable; default: all)")
+@click.option("--json", "as_json", is_flag=True, help="Print the paths as JSON")
+@click.option("--save-graph", is_flag=True, help="Save the graph to the project's graphs/ directory")
+def privilege_paths(target: str, queries: tuple[str, ...], as_json: bool, save_graph: bool):
+    """Find privilege-escalation paths from unauthenticated instructions."""
+    import json
+
+    from extensions.static.privilege_paths import GRAPH_NAME, QUERIES, build_privilege_graph, run_query
+
+    unknown = [q for q in queries if q not in QUERIES]
+    if unknown:
+        console.print(f"[red]Unknown query: {', '.join(unknown)}[/red] (available: {', '.join(QUERIES)})")
+        raise SystemExit(1)
+    source, project_dir = resolve_source(target)
+    graph = build_privilege_graph(load_programs(source))
+    if not graph.of_type("instruction"):
+        console.print(f"[yellow]No Solana instructions found under {source}[/yellow]")
+        raise SystemExit(1)
+    if save_graph:
+        if project_dir is None:
+            console.print("[red]--save-graph needs a project name, not a path[/red]")
+            raise SystemExit(1)
+        graphs_dir = project_dir / "graphs"
+        graphs_dir.mkdir(parents=True, exist_ok=True)
+        graph_file = graphs_dir / f"graph_{GRAPH_NAME}.json"
+        graph_file.write_text(json.dumps(graph.to_dict(), indent=2))
+        index = graphs_dir / "knowledge_graphs.json"
+        if index.exists():
+            meta = json.loads(index.read_text())
+            meta.setdefault("graphs", {})[GRAPH_NAME] = str(graph_file)
+            index.write_text(json.dumps(meta, indent=2))
+        console.print(f"[green]Graph written to {graph_file}[/green]")
+
+    paths = [p for q in (queries or QUERIES) for p in run_query(graph, q)]
+    if as_json:
+        click.echo(json.dumps({"source": str(source), "paths": [p.to_dict() for p in paths]}, indent=2))
+        return
+    for p in paths:
+        console.print(f"[red]{p.query}[/red] ({p.severity}) — {QUERIES[p.query][2]}")
+        for i, hop in enumerate(p.hops, 1):
+            console.print(f"  {i}. {hop.note} [dim]{hop.program}:{hop.file}:{hop.line}[/dim]")
+    if not paths:
+        console.print("[green]No privilege-escalation paths found[/green]")
+    counts = ", ".join(f"{q}: {sum(1 for p in paths if p.query == q)}" for q in (queries or QUERIES))
+    console.print(f"{len(graph.nodes)} nodes, {len(graph.edges)} edges; {counts}")
+
+
+@ir.command("graph")
+@click.argument("target")
+@click.option("--format", "fmt", type=click.Choice(["dot", "mermaid", "json"]), default="dot", help="Output format")
+@click.option("--output", "-o", default=None, help="Output file (default: stdout)")
+@click.option("--findings", "findings_files", multiple=True,
+              help="Hypotheses JSON to annotate nodes with (repeatable; default: the project's hypotheses)")
+@click.option("--no-findings", is_flag=True, help="Export the graph without finding annotations")
+@click.option("--min-confidence", default=None, help="Only annotate findings at or above: high, medium, low or 0-1")
+def graph(target: str, fmt: str, output: str | None, findings_files: tuple[str, ...], no_findings: bool,
+          min_confidence: str | None):
+    """Export the account/instruction/CPI graph as DOT, Mermaid or JSON."""
+    from extensions.static.confidence import meets_confidence, parse_confidence
+    from extensions.static.graph_export import annotate_findings, render_graph
+    from extensions.static.privilege_paths import build_privilege_graph
+
+    try:
+        floor = parse_confidence(min_confidence)
+    except ValueError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    source, project_dir = resolve_source(target)
+    programs = load_programs(source)
+    program_graph = build_privilege_graph(programs)
+    if not program_graph.of_type("instruction"):
+        console.print(f"[yellow]No Solana instructions found under {source}[/yellow]")
+        raise SystemExit(1)
+
+    findings: list[dict] = []
+    if not no_findings:
+        if findings_files:
+            paths = [Path(f) for f in findings_files]
+        elif project_dir is not None:
+            paths = [project_dir / "hypotheses.json", project_dir / "static_analysis" / "static_hypotheses.json"]
+        else:
+            paths = []
+        titles: set[str] = set()
+        for path in paths:
+            for finding in _load_findings(path):
+                if finding.get("title") not in titles and meets_confidence(finding, floor):
+                    titles.add(finding.get("title"))
+                    findings.append(finding)
+    unplaced = annotate_findings(program_graph, programs, findings, source)
+
+    text = render_graph(program_graph, fmt)
+    if not output:
+        click.echo(text, nl=False)
+        return
+    Path(output).write_text(text)
+    annotated = sum(1 for n in program_graph.nodes.values() if n.properties.get("findings"))
+    console.print(f"[green]Graph written to {output}[/green] ({len(program_graph.nodes)} nodes, "
+                  f"{len(program_graph.edges)} edges, {annotated} annotated)")
+    if unplaced:
+        console.print(f"[dim]{len(unplaced)} findings did not point at any node[/dim]")
+
+
+def _load_findings(path: Path) -> list[dict]:
+    """Hypotheses from a hypotheses.json store or a JSON list of hypotheses."""
+    import json
+
+    if not path.exists():
+        return []
+    try:
+        data = json.loads(path.read_text())
+    except ValueError:
+        console.print(f"[yellow]Could not parse {path}, skipping[/yellow]")
+        return []
+    if isinstance(data, dict):
+        items = data.get("hypotheses", {})
+        items = [{"id": key, **h} for key, h in items.items()] if isinstance(items, dict) else items
+    else:
+        items = data
+    return [h for h in items if isinstance(h, dict)]
+
+
+@ir.command("anchor-config")
+@click.argument("target")
+@click.option("--cluster", default=None, help="Cluster to resolve program ids for (default: the provider cluster)")
+@click.option("--json", "as_json", is_flag=True, help="Print the configuration and issues as JSON")
+def anchor_config(target: str, cluster: str | None, as_json: bool):
+    """Show the workspace's Anchor.toml setup and flag inconsistencies with the programs."""
+    from extensions.ir.anchor_config import check_consistency, load_anchor_config
+
+    source, _ = resolve_source(target)
+    try:
+        config = load_anchor_config(source)
+    except ValueError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    if config is None:
+        console.print(f"[yellow]No Anchor.toml found under {source}[/yellow]")
+        raise SystemExit(1)
+    issues = check_consistency(config, load_programs(config.root))
+    if as_json:
+        import json
+
+        click.echo(json.dumps({"config": config.to_dict(), "program_ids": config.program_ids(cluster),
+                               "rpc_url": config.rpc_url(cluster), "issues": [i.to_dict() for i in issues]},
+                              indent=2))
+        return
+
+    console.print(f"{config.path}: cluster {config.cluster} ({config.rpc_url()}), wallet {config.wallet or '-'}")
+    table = Table(show_header=True, header_style="bold")
+    table.add_column("Program")
+    for name in config.clusters:
+        table.add_column(name)
+    for program in sorted({n for ids in config.programs.values() for n in ids}):
+        table.add_row(program, *[config.programs[c].get(program, "-") for c in config.clusters])
+    console.print(table)
+    validator = config.validator
+    if validator.clones or validator.genesis or validator.accounts:
+        console.print(f"Test validator: {len(validator.genesis)} genesis programs, {len(validator.clones)} clones "
+                      f"from {validator.url or 'the provider cluster'}, {len(validator.accounts)} preloaded accounts")
+    styles = {"medium": "yellow", "low": "blue"}
+    for issue in issues:
+        style = styles.get(issue.severity, "red")
+        console.print(f"  [{style}][{issue.severity.upper()}][/{style}] {issue.kind}: {issue.message}")
+    if not issues:
+        console.print("[green]Anchor.toml is consistent with the workspace[/green]")
+
+
+@ir.command("metrics")
+@click.argument("target")
+@click.option("--top", default=10, show_default=True, help="Instructions to list in the review order")
+@click.option("--json", "as_json", is_flag=True, help="Print the metrics as JSON")
+@click.option("--output", "-o", default=None, help="Output file (default: <project>/metrics.json for projects)")
+def metrics(target: str, top: int, as_json: bool, output: str | None):
+    """Per-program size and attack-surface statistics, and the order to review instructions in."""
+    import json
+
+    from extensions.ir.metrics import build_metrics
+    from extensions.reporting.manifest import build_manifest
+    from utils.config_loader import load_config
+
+    source, project_dir = resolve_source(target)
+    programs = load_programs(source)
+    if not programs:
+        console.print(f"[yellow]No programs found under {source}[/yellow]")
+        raise SystemExit(1)
+    data = build_metrics(programs, source, top=top)
+    data["manifest"] = build_manifest(
+        "ir metrics", {"target": target, "top": top, "as_json": as_json, "output": output},
+        source=source, config=load_config(),
+        detectors={f"ir-{framework}": None for framework in sorted({p.framework for p in programs})},
+    )
+    out_path = Path(output) if output else (project_dir / "metrics.json" if project_dir else None)
+    if out_path:
+        out_path.parent.mkdir(parents=True, exist_ok=True)
+        out_path.write_text(json.dumps(data, indent=2))
+    if as_json:
+        click.echo(json.dumps(data, indent=2))
+        return
+
+    table = Table(show_header=True, header_style="bold")
+    for column in ("Program", "Framework", "LoC", "Instructions", "Privileged", "Unchecked accounts", "CPI fan-out",
+                   "Max complexity"):
+        table.add_column(column)
+    for program in data["programs"]:
+        s = program["summary"]
+        table.add_row(
+            program["name"], program["framework"], str(program["loc"]), str(s["instructions"]),
+            str(s["privileged_instructions"]), str(s["unchecked_accounts"]), str(s["cpi_fan_out"]),
+            str(s["max_complexity"]),
+        )
+    console.print(table)
+    if data["review_order"]:
+        console.print("[bold]Review first:[/bold]")
+        for i, entry in enumerate(data["review_order"], 1):
+            console.print(f"  {i}. {entry['program']}.{entry['instruction']} (priority {entry['priority']}) "
+                          f"[dim]{entry['file']}:{entry['line']}[/dim]")
+    if out_path:
+        console.print(f"[green]Metrics written to {out_path}[/green]")
@@ -3,9 +3,28 @@ Knowledge base CLI commands.
 
 Usage:
     ./hound.py kb search <query>           # Search all knowledge
+    ./hound.py kb search <query> -t oracle # Search within a taxonomy tag
     ./hound.py kb checklist [--category]   # View checklists
     ./hound.py kb tips [--category]        # View auditor tips
+    ./hound.py kb tags [--chain]           # Taxonomy tags with entry counts
+    ./hound.py kb browse -t cpi -t oracle  # Browse entries by taxonomy tag
     ./hound.py kb template <vuln-type>     # Get PoC template
+    ./hound.py kb templates -c solana      # Query templates by chain, severity, class, CWE/SWC
+    ./hound.py kb find "reentrancy cpi"    # Rank templates by their titles, descriptions and fixes
+    ./hound.py kb render <id> -s KEY=VAL   # Fill in a PoC template as a test file
+    ./hound.py kb render <id> -w <pkg>     # ... written where its test harness expects it
+    ./hound.py kb render <id> --idl <json> -i <ix>  # ... with the instruction's structs from an Anchor IDL
+    ./hound.py kb render <id> --idl <json> -i <ix> --program-test  # A complete solana-program-test exploit
+    ./hound.py kb render <id> ... --cluster mainnet  # ... with only the feature gates active on mainnet
+    ./hound.py kb render <id> --target anchor-ts  # The template's Mocha test for `anchor test`
+    ./hound.py kb render <id> --template-dir ~/my-pocs  # ... from a private template root too
+    ./hound.py kb classes [--chain]        # Chain-agnostic vulnerability classes
+    ./hound.py kb class <class|vuln-type>  # A class, its per-chain forms and templates
+    ./hound.py kb class <name> --lang zh   # Translated descriptions and fix guidance
+    ./hound.py kb integrations [<id>]      # Validation third-party integrations require
+    ./hound.py kb programs [<id|address>]  # Canonical deployments of common programs
+    ./hound.py kb incidents [<id|class>]   # Real-world exploits, of a class or vulnerability type
+    ./hound.py kb features [--cluster C]   # Runtime feature gates and their status on a cluster
     ./hound.py kb stats                    # Show statistics
 """
 
@@ -20,12 +39,55 @@ from rich.syntax import Syntax
 
 sys.path.insert(0, str(Path(__file__).parent.parent))
 
-from extensions.knowledge import KnowledgeBase, ChecklistLoader, TemplateLoader, TipLoader
+from extensions.i18n import normalize_locale
+from extensions.knowledge import KnowledgeBase, ChecklistLoader, TemplateLoader, TipLoader, user_template_dirs
+from extensions.knowledge.taxonomy import TAXONOMY, facet_counts, filter_by_tags, resolve_tags
 
 
 console = Console()
 
 
+def _resolve(tags: tuple[str, ...]) -> list[str]:
+    """Canonical taxonomy tags, or a usage error naming the valid ones."""
+    try:
+        return resolve_tags(tags)
+    except ValueError as e:
+        raise click.BadParameter(str(e), param_hint="'--tag'")
+
+
+def _locale(lang: str | None) -> str:
+    """Supported locale code, or a usage error naming the supported ones."""
+    try:
+        return normalize_locale(lang)
+    except ValueError as e:
+        raise click.BadParameter(str(e), param_hint="'--lang'")
+
+
+def _template_dirs(template_dirs: tuple[str, ...], workspace: str | None = None) -> list[Path]:
+    """The user's template roots: config.yaml's, BASKERVILLE_TEMPLATE_DIRS, the workspace's, then --template-dir."""
+    from extensions.static.project_config import ConfigError, ProjectConfig
+    from utils.config_loader import load_config
+
+    try:
+        project = ProjectConfig.load(Path(workspace)).template_dirs if workspace else []
+    except ConfigError as e:
+        raise click.BadParameter(str(e), param_hint="'--workspace'")
+    try:
+        return user_template_dirs([Path(d) for d in template_dirs], load_config(), project)
+    except ValueError as e:
+        raise click.BadParameter(str(e), param_hint="'--template-dir'")
+
+
+template_dir_option = click.option(
+    "--template-dir", "template_dirs", multiple=True,
+    help="Also load PoC templates from this root, laid out like the built-in one (repeatable; later wins)")
+
+
+def _print_facets(facets: dict[str, int]) -> None:
+    if facets:
+        console.print("[dim]Tags: " + ", ".join(f"{tag} ({n})" for tag, n in facets.items()) + "[/dim]\n")
+
+
 @click.group("kb")
 def kb():
     """Knowledge base for security auditing."""
@@ -35,12 +97,16 @@ def kb():
 @kb.command("search")
 @click.argument("query")
 @click.option("--limit", "-l", default=20, help="Maximum results per category")
-def search(query: str, limit: int):
+@click.option("--tag", "-t", "tags", multiple=True, help="Filter by taxonomy tag (repeatable)")
+@click.option("--all-tags", is_flag=True, help="Require every --tag instead of any")
+def search(query: str, limit: int, tags: tuple[str, ...], all_tags: bool):
     """Search the knowledge base."""
+    wanted = _resolve(tags)
     console.print(f"\n[bold]Searching knowledge base for: {query}[/bold]\n")
 
     kb = KnowledgeBase()
-    result = kb.query(query)
+    result = kb.query(query, tags=wanted, match_all=all_tags)
+    _print_facets(result.facets())
 
     # Checklists
     if result.checklists:
@@ -48,12 +114,14 @@ def search(query: str, limit: int):
         table = Table(show_header=True, header_style="bold")
         table.add_column("ID", width=15)
         table.add_column("Question", width=50)
+        table.add_column("Tags", width=24)
         table.add_column("Source", width=8)
 
         for item in result.checklists[:limit]:
             table.add_row(
                 item.id,
                 item.question[:50] + ("..." if len(item.question) > 50 else ""),
+                ", ".join(item.taxonomy),
                 item.source,
             )
         console.print(table)
@@ -81,12 +149,17 @@ def search(query: str, limit: int):
 @kb.command("checklist")
 @click.option("--category", "-c", help="Filter by category")
 @click.option("--source", "-s", type=click.Choice(["all", "solodit", "custom"]), default="all", help="Filter by source")
+@click.option("--tag", "-t", "tags", multiple=True, help="Filter by taxonomy tag (repeatable)")
+@click.option("--all-tags", is_flag=True, help="Require every --tag instead of any")
 @click.option("--limit", "-l", default=20, help="Maximum results")
-def checklist(category: str | None, source: str, limit: int):
+@click.option("--lang", help="Language for descriptions and fixes (en, zh, es, ja)")
+def checklist(category: str | None, source: str, tags: tuple[str, ...], all_tags: bool, limit: int,
+              lang: str | None = None):
     """View security checklists."""
+    wanted = _resolve(tags)
     console.print("\n[bold]Security Checklists[/bold]\n")
 
-    loader = ChecklistLoader()
+    loader = ChecklistLoader(locale=_locale(lang))
 
     if category:
         items = loader.get_by_category(category)
@@ -99,6 +172,7 @@ def checklist(category: str | None, source: str, limit: int):
         items = [i for i in items if i.source == "solodit"]
     elif source == "custom":
         items = [i for i in items if i.source == "custom"]
+    items = filter_by_tags(items, wanted, all_tags)
 
     if not items:
         console.print("[yellow]No checklist items found.[/yellow]")
@@ -110,6 +184,7 @@ def checklist(category: str | None, source: str, limit: int):
         return
 
     console.print(f"Found {len(items)} items (showing {min(len(items), limit)})\n")
+    _print_facets(facet_counts(items))
 
     for item in items[:limit]:
         severity_color = {
@@ -119,7 +194,8 @@ def checklist(category: str | None, source: str, limit: int):
             "low": "blue",
         }.get(item.severity, "white")
 
-        console.print(f"[{severity_color}][{item.id}][/{severity_color}] {item.question}")
+        console.print(f"[{severity_color}][{item.id}][/{severity_color}] {item.question} "
+                      f"[dim]({', '.join(item.taxonomy)})[/dim]")
         if item.description:
             console.print(f"    [dim]{item.description[:100]}...[/dim]")
         console.print()
@@ -154,17 +230,21 @@ def categories():
 @kb.command("tips")
 @click.option("--category", "-c", help="Filter by category")
 @click.option("--priority", "-p", type=click.Choice(["high", "medium", "low"]), help="Filter by priority")
-def tips(category: str | None, priority: str | None):
+@click.option("--tag", "-t", "tags", multiple=True, help="Filter by taxonomy tag (repeatable)")
+@click.option("--lang", help="Language for descriptions and fixes (en, zh, es, ja)")
+def tips(category: str | None, priority: str | None, tags: tuple[str, ...], lang: str | None = None):
     """View auditor tips and heuristics."""
+    wanted = _resolve(tags)
     console.print("\n[bold]Auditor Tips & Heuristics[/bold]\n")
 
-    loader = TipLoader()
+    loader = TipLoader(locale=_locale(lang))
     all_tips = loader.get_all()
 
     if category:
         all_tips = loader.get_by_category(category)
     if priority:
         all_tips = [t for t in all_tips if t.priority == priority]
+    all_tips = filter_by_tags(all_tips, wanted)
 
     if not all_tips:
         console.print("[yellow]No tips found.[/yellow]")
@@ -191,9 +271,10 @@ def tips(category: str | None, priority: str | None):
 @kb.command("template")
 @click.argument("vuln_type")
 @click.option("--list", "-l", "list_only", is_flag=True, help="List available templates")
-def template(vuln_type: str, list_only: bool):
+@template_dir_option
+def template(vuln_type: str, list_only: bool, template_dirs: tuple[str, ...] = ()):
     """Get PoC template for a vulnerability type."""
-    loader = TemplateLoader()
+    loader = TemplateLoader(user_dirs=_template_dirs(template_dirs))
 
     if list_only:
         console.print("\n[bold]Available PoC Templates[/bold]\n")
@@ -228,6 +309,529 @@ def template(vuln_type: str, list_only: bool):
     console.print(Panel(syntax, title=f"{template.id}.sol", border_style="dim"))
 
 
+@kb.command("templates")
+@click.option("--chain", "-c", help="Filter by chain (evm, solana, sui, aptos, cosmwasm)")
+@click.option("--severity", help="Filter by severity")
+@click.option("--min-severity", help="Lowest severity to include")
+@click.option("--class", "vuln_class", help="Filter by vulnerability class or a vulnerability type of it")
+@click.option("--cwe", help="Filter by CWE id")
+@click.option("--swc", help="Filter by SWC id")
+@click.option("--harness",
+              help="Filter by test harness (foundry, anchor, sui-move-test, aptos-move-test, cw-multi-test)")
+@click.option("--origin", help="Filter by where templates come from (builtin, bundle, user)")
+@click.option("--json", "as_json", is_flag=True, help="Print template metadata as JSON")
+@template_dir_option
+def templates(chain: str | None, severity: str | None, min_severity: str | None, vuln_class: str | None,
+              cwe: str | None, swc: str | None, harness: str | None, as_json: bool, origin: str | None = None,
+              template_dirs: tuple[str, ...] = ()):
+    """Query PoC templates by their front matter metadata."""
+    from extensions.knowledge import TemplateRegistry
+
+    registry = TemplateRegistry(TemplateLoader(user_dirs=_template_dirs(template_dirs)))
+    try:
+        found = registry.query(chain=chain, severity=severity, min_severity=min_severity, vuln_class=vuln_class,
+                               cwe=cwe, swc=swc, harness=harness, origin=origin)
+    except ValueError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    if as_json:
+        import json
+
+        click.echo(json.dumps(registry.index(found), indent=2))
+        return
+    if not found:
+        console.print("[yellow]No templates match[/yellow]")
+        return
+
+    table = Table(show_header=True, header_style="bold")
+    for column in ("ID", "Chain", "Harness", "Severity", "Class", "CWE/SWC", "Context", "Origin"):
+        table.add_column(column)
+    for t in found:
+        table.add_row(t.id, t.chain, ", ".join([t.harness.id, *t.targets]), t.severity or "-", t.vuln_class or "-",
+                      ", ".join(t.cwe + t.swc) or "-", ", ".join(t.context or t.placeholders) or "-",
+                      t.origin + (f" (overrides {t.shadows[-1]})" if t.shadows else ""))
+    console.print(table)
+
+
+@kb.command("find")
+@click.argument("query")
+@click.option("--chain", "-c", help="Filter by chain (evm, solana, sui, aptos, cosmwasm)")
+@click.option("--tag", "-t", "tags", multiple=True, help="Filter by taxonomy tag (repeatable)")
+@click.option("--all-tags", is_flag=True, help="Require every --tag instead of any")
+@click.option("--limit", "-l", default=10, help="Maximum results")
+@click.option("--json", "as_json", is_flag=True, help="Print the matches as JSON")
+@template_dir_option
+def find(query: str, chain: str | None, tags: tuple[str, ...], all_tags: bool, limit: int, as_json: bool,
+         template_dirs: tuple[str, ...] = ()):
+    """Rank PoC templates by how well their titles, descriptions and fixes match the query.
+
+    The query takes words, "quoted phrases" and tag:, chain: and severity:
+    filters (e.g. `reentrancy cpi chain:solana`).
+    """
+    kb = KnowledgeBase(template_dirs=_template_dirs(template_dirs))
+    try:
+        hits = kb.search(query, chain=chain, tags=_resolve(tags), match_all=all_tags, limit=limit)
+    except ValueError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    if as_json:
+        import json
+
+        click.echo(json.dumps([h.to_dict() for h in hits], indent=2))
+        return
+    if not hits:
+        console.print(f"[yellow]No templates match: {query}[/yellow]")
+        return
+
+    console.print(f"\n[bold]Templates matching: {query}[/bold]\n")
+    for rank, hit in enumerate(hits, 1):
+        t = hit.template
+        console.print(f"{rank:>2}. [bold]{t.id}[/bold] [dim]({t.chain}, {t.severity or '-'}, "
+                      f"score {hit.score:.2f})[/dim] {t.name}")
+        console.print(f"    [dim]{hit.field}:[/dim] {_highlight(hit.snippet, hit.matched)}")
+    console.print("\n[dim]Render one with: kb render <id>[/dim]")
+
+
+def _highlight(text: str, terms: list[str]) -> str:
+    """``text`` with the words matching ``terms`` in bold, for the console."""
+    import re
+
+    from extensions.knowledge.search import tokenize
+
+    def mark(match: re.Match) -> str:
+        text = match.group().replace("[", "\\[")  # Snippets quote code such as #[account(...)]
+        word = tokenize(match.group())
+        hit = word and any(word[0] == t or (len(t) >= 4 and word[0].startswith(t)) for t in terms)
+        return f"[bold yellow]{text}[/bold yellow]" if hit else text
+
+    return re.sub(r"[A-Za-z0-9]+|[^A-Za-z0-9]+", mark, text)
+
+
+def _render_program_test(poc, idl_path: str | None, instruction: str | None, assignments: tuple[str, ...],
+                         output: str | None, workspace: str | None, cluster: str | None = None) -> None:
+    from extensions.knowledge.harness import HARNESSES
+    from extensions.knowledge.idl import load_idl
+    from extensions.knowledge.program_test import DEV_DEPENDENCIES, generate_program_test
+
+    if not idl_path:
+        raise click.BadParameter("needs --idl", param_hint="'--program-test'")
+    if assignments:
+        raise click.BadParameter("program tests take their values from the IDL", param_hint="'--set' / '--var'")
+    try:
+        source = generate_program_test(poc, load_idl(Path(idl_path)), instruction, cluster)
+    except ValueError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    if not (output or workspace):
+        click.echo(source, nl=False)
+        return
+    harness = HARNESSES["solana-program-test"]
+    path = Path(output) if output else Path(workspace) / harness.test_path(poc.id)
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(source)
+    console.print(f"[green]Wrote {path}[/green]")
+    console.print(f"[dim]Add to the program's [dev-dependencies]: {'; '.join(DEV_DEPENDENCIES)}[/dim]")
+    console.print(f"[dim]Run with: {harness.run_command(path.name.removesuffix(harness.suffix))}[/dim]")
+
+
+@kb.command("render")
+@click.argument("template_id")
+@click.option("--set", "--var", "-s", "assignments", multiple=True, help="Placeholder value as KEY=VALUE (repeatable)")
+@click.option("--output", "-o", default=None, help="Write the rendered test file here")
+@click.option("--workspace", "-w", default=None,
+              help="Write into this workspace or package, where the template's harness expects tests")
+@click.option("--idl", "idl_path", default=None, type=click.Path(exists=True, dir_okay=False),
+              help="Anchor IDL (target/idl/<program>.json) to fill the program id, instruction and accounts from")
+@click.option("--instruction", "-i", default=None, help="IDL instruction the PoC calls (default: the only one)")
+@click.option("--program-test", is_flag=True,
+              help="Generate a complete solana-program-test exploit from the template and --idl instead")
+@click.option("--cluster", default=None,
+              help="Solana cluster the PoC targets: warn about feature gates it lacks, deactivate them in "
+                   "program tests")
+@click.option("--target", default=None,
+              help="Test harness to render for, if the template is written for it (e.g. anchor-ts: a Mocha test)")
+@template_dir_option
+def render(template_id: str, assignments: tuple[str, ...], output: str | None, workspace: str | None,
+           idl_path: str | None = None, instruction: str | None = None, program_test: bool = False,
+           cluster: str | None = None, target: str | None = None, template_dirs: tuple[str, ...] = ()):
+    """Fill in a PoC template's placeholders and emit a test file.
+
+    With --idl, the program id, instruction name and its argument and
+    account fields come from the program's Anchor IDL; --set values win.
+    With --program-test, the output is a BanksClient integration test that
+    builds and runs as is, rather than the template's commented-out sketch.
+    With --cluster, the PoC is checked against that cluster's feature gates.
+    With --target, the template's version for another harness is rendered
+    from the same values (anchor-ts: a TypeScript test run by `anchor test`).
+    With --workspace, templates from the bundles pinned in its
+    baskerville.toml are available too, and its [templates] dirs. Templates
+    in the user's own roots (config.yaml's knowledge.template_dirs,
+    BASKERVILLE_TEMPLATE_DIRS, the workspace's and --template-dir) load last
+    and replace any of the same id.
+    """
+    from extensions.knowledge.render import TemplateEngine, TemplateError
+
+    extra_dirs = []
+    if workspace:
+        from extensions.knowledge.bundles import BundleError, resolve_bundles
+
+        try:
+            extra_dirs = [b.root / "templates" for b in resolve_bundles(Path(workspace))]
+        except BundleError as e:
+            console.print(f"[red]{e}[/red]")
+            raise SystemExit(1)
+    loader = TemplateLoader(extra_dirs=extra_dirs, user_dirs=_template_dirs(template_dirs, workspace))
+    poc = loader.get(template_id)
+    if not poc:
+        console.print(f"[red]Unknown template: {template_id}[/red]")
+        console.print("\n[dim]Available templates:[/dim]")
+        for t in loader.list_all():
+            console.print(f"  • {t.id}: {t.name}")
+        raise SystemExit(1)
+
+    context = {}
+    if instruction and not idl_path:
+        raise click.BadParameter("needs --idl", param_hint="'--instruction'")
+    if program_test:
+        if target:
+            raise click.BadParameter("program tests are their own target", param_hint="'--target'")
+        _render_program_test(poc, idl_path, instruction, assignments, output, workspace, cluster)
+        return
+    if target:
+        try:
+            poc = poc.for_target(target)
+        except ValueError as e:
+            console.print(f"[red]{e}[/red]")
+            raise SystemExit(1)
+    if idl_path:
+        from extensions.knowledge.idl import load_idl, template_context
+
+        try:
+            context = template_context(load_idl(Path(idl_path)), instruction, typescript=poc.harness.suffix == ".ts")
+        except ValueError as e:
+            console.print(f"[red]{e}[/red]")
+            raise SystemExit(1)
+    for assignment in assignments:
+        key, sep, value = assignment.partition("=")
+        if not sep or not key.strip():
+            raise click.BadParameter(f"expected KEY=VALUE, got {assignment!r}", param_hint="'--set' / '--var'")
+        context[key.strip().upper()] = value.strip()
+
+    try:
+        engine = TemplateEngine(poc)
+        rendered = engine.render(context)
+    except TemplateError as e:
+        console.print(f"[red]{e}[/red]")
+        if e.missing or e.invalid:
+            console.print("[bold]Placeholders:[/bold]")
+            for p in engine.placeholders:
+                default = f" (default {p.default})" if p.default is not None else ""
+                about = f" — {poc.context[p.name]}" if poc.context.get(p.name) else ""
+                console.print(f"  • {{{{{p.name}}}}}{default}{about}")
+        raise SystemExit(1)
+
+    for problem in engine.harness.problems(rendered):
+        console.print(f"[yellow]Warning: {problem}; the PoC may not build under {engine.harness.label}[/yellow]")
+    if cluster:
+        from extensions.knowledge.feature_loader import ClusterFeatures
+
+        features = ClusterFeatures(cluster)
+        for gate, use in features.unmet(rendered):
+            state = "not active" if not features.is_active(gate) else "active"
+            console.print(f"[yellow]Warning: the PoC uses `{use.group(0)}` ({gate.name}), whose feature gate is "
+                          f"{state} on {features.cluster}[/yellow]")
+    if output or workspace:
+        path = engine.write(Path(output) if output else Path(workspace) / engine.harness.test_path(poc.id), context)
+        console.print(f"[green]Wrote {path}[/green]")
+        name = path.name.removesuffix(engine.harness.suffix)
+        console.print(f"[dim]Run with: {engine.harness.run_command(name)}[/dim]")
+    else:
+        click.echo(rendered)
+
+
+@kb.command("classes")
+@click.option("--chain", help="Show each class's form on a chain (evm, solana, sui)")
+@click.option("--lang", help="Language for descriptions and fixes (en, zh, es, ja)")
+def list_classes(chain: str | None, lang: str | None = None):
+    """List chain-agnostic vulnerability classes."""
+    kb_instance = KnowledgeBase(locale=_locale(lang))
+    console.print("\n[bold]Vulnerability Classes[/bold]\n")
+
+    table = Table(show_header=True, header_style="bold")
+    table.add_column("Class", width=34)
+    table.add_column("Tags", width=24)
+    if chain:
+        table.add_column(f"On {chain}", width=40)
+        table.add_column("Templates", width=24)
+    else:
+        table.add_column("Chains", width=20)
+        table.add_column("Templates", width=6)
+
+    for vuln_class in kb_instance.classes.list_all():
+        if chain:
+            variant = vuln_class.variant(chain)
+            templates = kb_instance.templates.get_by_class(vuln_class.id, chain)
+            table.add_row(vuln_class.id, ", ".join(vuln_class.taxonomy),
+                          variant.name if variant else "[dim]-[/dim]", ", ".join(t.id for t in templates))
+        else:
+            table.add_row(vuln_class.id, ", ".join(vuln_class.taxonomy), ", ".join(vuln_class.variants),
+                          str(len(vuln_class.templates)))
+    console.print(table)
+
+
+@kb.command("class")
+@click.argument("name")
+@click.option("--chain", help="Highlight the form on a chain (evm, solana, sui)")
+@click.option("--lang", help="Language for descriptions and fixes (en, zh, es, ja)")
+def show_class(name: str, chain: str | None, lang: str | None = None):
+    """Show a vulnerability class by ID or by any vulnerability type it covers."""
+    kb_instance = KnowledgeBase(locale=_locale(lang))
+    vuln_class = kb_instance.resolve_class(name, chain)
+    if not vuln_class:
+        console.print(f"[yellow]No vulnerability class covers: {name}[/yellow]")
+        console.print("[dim]List classes with `kb classes`.[/dim]")
+        return
+
+    console.print(f"\n[bold]{vuln_class.name}[/bold] [dim]({vuln_class.id}; {', '.join(vuln_class.taxonomy)})[/dim]\n")
+    console.print(vuln_class.description)
+    if vuln_class.impact:
+        console.print(f"\n[bold]Impact:[/bold] {vuln_class.impact}")
+    console.print(f"[bold]Fix:[/bold] {vuln_class.fix}\n")
+
+    for variant in vuln_class.variants.values():
+        marker = "[green]▶[/green] " if chain and variant.chain == chain.lower() else "  "
+        console.print(f"{marker}[bold cyan]{variant.chain}[/bold cyan]: {variant.name}")
+        if variant.description:
+            console.print(f"    {variant.description}")
+        if variant.fix:
+            console.print(f"    [bold]Fix:[/bold] {variant.fix}")
+        if variant.aliases:
+            console.print(f"    [dim]Types: {', '.join(variant.aliases)}[/dim]")
+        templates = kb_instance.templates.get_by_class(vuln_class.id, variant.chain)
+        if templates:
+            console.print(f"    [dim]Templates: {', '.join(t.id for t in templates)}[/dim]")
+        console.print()
+
+
+@kb.command("tags")
+@click.option("--chain", help="Only count entries for a chain (evm, solana, sui)")
+def list_tags(chain: str | None):
+    """List taxonomy tags with entry counts."""
+    console.print("\n[bold]Taxonomy Tags[/bold]\n")
+
+    result = KnowledgeBase().browse(chain=chain)
+    counts = {
+        "checklists": facet_counts(result.checklists),
+        "tips": facet_counts(result.tips),
+        "templates": facet_counts(result.templates),
+    }
+
+    table = Table(show_header=True, header_style="bold")
+    table.add_column("Tag", width=20)
+    table.add_column("Checklists", width=10)
+    table.add_column("Tips", width=6)
+    table.add_column("Templates", width=9)
+    table.add_column("Description", width=50)
+
+    for tag in TAXONOMY.values():
+        table.add_row(
+            tag.id,
+            *(str(counts[kind].get(tag.id, 0)) for kind in ("checklists", "tips", "templates")),
+            tag.description,
+        )
+    console.print(table)
+
+
+@kb.command("browse")
+@click.option("--tag", "-t", "tags", multiple=True, help="Filter by taxonomy tag (repeatable)")
+@click.option("--all-tags", is_flag=True, help="Require every --tag instead of any")
+@click.option("--chain", help="Filter by chain (evm, solana, sui)")
+@click.option("--limit", "-l", default=20, help="Maximum results per category")
+def browse(tags: tuple[str, ...], all_tags: bool, chain: str | None, limit: int):
+    """Browse checklists, tips and templates by taxonomy tag."""
+    wanted = _resolve(tags)
+    result = KnowledgeBase().browse(wanted, match_all=all_tags, chain=chain)
+    label = (" + " if all_tags else " | ").join(wanted) or "all tags"
+    console.print(f"\n[bold]Knowledge Base: {label}[/bold]\n")
+    _print_facets(result.facets())
+
+    sections = [
+        ("Checklist Items", result.checklists, lambda i: (i.id, i.question)),
+        ("Auditor Tips", result.tips, lambda t: (t.id, t.title)),
+        ("PoC Templates", result.templates, lambda t: (t.id, t.name)),
+    ]
+    for title, entries, row in sections:
+        if not entries:
+            continue
+        console.print(f"[bold cyan]{title} ({len(entries)})[/bold cyan]")
+        for entry in entries[:limit]:
+            entry_id, text = row(entry)
+            console.print(f"  [bold]{entry_id}[/bold] {text} [dim]({', '.join(entry.taxonomy)})[/dim]")
+        console.print()
+
+    if not (result.checklists or result.tips or result.templates):
+        console.print("[yellow]No entries found.[/yellow]")
+
+
+@kb.command("integrations")
+@click.argument("integration_id", required=False)
+@click.option("--chain", default="solana", help="Chain to list integrations for")
+def integrations(integration_id: str | None, chain: str):
+    """List third-party integrations and the validation their documentation requires."""
+    from extensions.knowledge import IntegrationLoader
+
+    loader = IntegrationLoader()
+    if integration_id:
+        found = loader.get(integration_id)
+        if not found:
+            console.print(f"[red]Unknown integration: {integration_id}[/red]")
+            console.print(f"[dim]Available: {', '.join(i.id for i in loader.list_all())}[/dim]")
+            raise SystemExit(1)
+        selected = [found]
+    else:
+        selected = loader.get_by_chain(chain)
+
+    severity_color = {"high": "red", "medium": "yellow", "low": "blue"}
+    for integration in selected:
+        console.print(f"\n[bold]{integration.name}[/bold] [dim]({integration.id}, {integration.category})[/dim]")
+        if integration.docs:
+            console.print(f"  [dim]{integration.docs}[/dim]")
+        for req in integration.requirements:
+            color = severity_color.get(req.severity, "white")
+            console.print(f"  [{color}][{req.id}][/{color}] {req.title}")
+            if integration_id:
+                console.print(f"      [dim]{req.description}[/dim]")
+    if not selected:
+        console.print(f"[yellow]No integrations for chain: {chain}[/yellow]")
+
+
+@kb.command("programs")
+@click.argument("query", required=False)
+@click.option("--chain", default="solana", help="Chain to list programs for")
+def programs(query: str | None, chain: str):
+    """List known programs and their canonical deployment addresses."""
+    from extensions.knowledge import ProgramLoader
+
+    loader = ProgramLoader()
+    if query:
+        found = loader.get(query) or loader.by_address(query)
+        if not found:
+            console.print(f"[red]Unknown program: {query}[/red]")
+            console.print(f"[dim]Available: {', '.join(p.id for p in loader.list_all())}[/dim]")
+            raise SystemExit(1)
+        selected = [found]
+    else:
+        selected = loader.get_by_chain(chain)
+
+    table = Table(show_header=True, header_style="bold")
+    table.add_column("Program")
+    table.add_column("Category")
+    table.add_column("Address")
+    for program in selected:
+        table.add_row(f"{program.name} [dim]({program.id})[/dim]", program.category,
+                      "\n".join(program.addresses))
+    if selected:
+        console.print(table)
+    if query and selected[0].docs:
+        console.print(f"[dim]{selected[0].docs}[/dim]")
+    if not selected:
+        console.print(f"[yellow]No known programs for chain: {chain}[/yellow]")
+
+
+@kb.command("incidents")
+@click.argument("query", required=False)
+@click.option("--chain", default=None, help="Only list incidents on a chain (evm, solana, sui)")
+@click.option("--json", "as_json", is_flag=True, help="Print the incidents as JSON")
+def incidents(query: str | None, chain: str | None, as_json: bool):
+    """List real-world exploits, or those of a vulnerability class or type."""
+    import json
+    from dataclasses import asdict
+
+    kb_instance = KnowledgeBase()
+    found = kb_instance.incidents.get(query) if query else None
+    if found:
+        selected = [found]
+    elif query:
+        selected = kb_instance.related_incidents({"vulnerability_type": query}, chain, limit=None)
+        selected = [i for i in selected if not chain or i.chain == chain.lower()]
+        if not selected:
+            console.print(f"[yellow]No incidents of: {query}[/yellow]")
+            console.print("[dim]List incidents with `kb incidents`.[/dim]")
+            return
+    else:
+        selected = kb_instance.incidents.get_by_chain(chain) if chain else kb_instance.incidents.list_all()
+
+    if as_json:
+        click.echo(json.dumps([asdict(i) for i in selected], indent=2))
+        return
+    if found:
+        console.print(f"\n[bold]{found.name}[/bold] [dim]({found.id}; {found.chain}, {found.date})[/dim]\n")
+        console.print(found.summary)
+        if found.root_cause:
+            console.print(f"\n[bold]Root cause:[/bold] {found.root_cause}")
+        console.print(f"[bold]Loss:[/bold] {found.loss()}")
+        if found.classes:
+            console.print(f"[bold]Classes:[/bold] {', '.join(found.classes)}")
+        for reference in found.references:
+            console.print(f"[dim]{reference}[/dim]")
+        return
+
+    table = Table(show_header=True, header_style="bold")
+    table.add_column("Incident")
+    table.add_column("Date")
+    table.add_column("Chain")
+    table.add_column("Loss", justify="right")
+    table.add_column("Classes")
+    for incident in selected:
+        table.add_row(f"{incident.name} [dim]({incident.id})[/dim]", incident.date, incident.chain,
+                      incident.loss(), ", ".join(incident.classes))
+    console.print(table)
+
+
+@kb.command("features")
+@click.option("--cluster", default=None, help="Cluster to show feature gate status on (default mainnet)")
+@click.option("--provider", "provider_spec", default=None,
+              help="Read the status from this RPC provider's feature accounts (URL, cluster or provider)")
+@click.option("--json", "as_json", is_flag=True, help="Print the gates as JSON")
+def features(cluster: str | None, provider_spec: str | None, as_json: bool):
+    """List the runtime feature gates code can depend on and whether a cluster has them."""
+    from extensions.knowledge.feature_loader import ClusterFeatures, FeatureLoader, fetch_activation
+
+    loader = FeatureLoader()
+    active = None
+    if provider_spec:
+        from extensions.rpc import RpcClient, RpcError
+
+        try:
+            client = RpcClient(provider_spec)
+            active = fetch_activation(client, loader.get_by_chain("solana"))
+        except (ValueError, RpcError) as e:
+            console.print(f"[red]Feature status not read: {e}[/red]")
+            raise SystemExit(1)
+        cluster = cluster or client.provider.cluster or "mainnet"
+    view = ClusterFeatures(cluster or "mainnet", active=active, loader=loader)
+    if as_json:
+        import json
+
+        click.echo(json.dumps({"cluster": view.cluster, "source": view.source, "limits": view.limits(),
+                               "features": [{**g.to_dict(), "active": view.is_active(g)} for g in view.gates]},
+                              indent=2))
+        return
+
+    table = Table(show_header=True, header_style="bold")
+    table.add_column("Feature gate")
+    table.add_column("Category")
+    table.add_column(view.cluster.capitalize())
+    table.add_column("Feature account")
+    for gate in view.gates:
+        state = "[green]active[/green]" if view.is_active(gate) else "[yellow]inactive[/yellow]"
+        table.add_row(f"{gate.name} [dim]({gate.id})[/dim]", gate.category, state, gate.feature)
+    console.print(table)
+    origin = "its feature accounts" if view.source == "rpc" else f"the knowledge base {view.source}"
+    limits = ", ".join(f"{name} {value}" for name, value in view.limits().items())
+    console.print(f"[dim]Status on {view.cluster} from {origin}; limits: {limits}[/dim]")
+
+
 @kb.command("stats")
 def stats():
     """Show knowledge base statistics."""
@@ -268,20 +872,36 @@ def stats():
         "",
     )
 
+    # Classes
+    table.add_row(
+        "Vulnerability Classes",
+        str(stats["classes"]),
+        "",
+    )
+
+    # Incidents
+    table.add_row(
+        "Incidents",
+        str(stats["incidents"]),
+        "",
+    )
+
     console.print(table)
 
 
 @kb.command("context")
 @click.argument("topic")
 @click.option("--protocol", "-p", is_flag=True, help="Treat topic as protocol type (lending, amm, etc.)")
-def context(topic: str, protocol: bool):
+@click.option("--chain", help="Filter by chain (evm, solana, sui)")
+@click.option("--lang", help="Language for descriptions and fixes (en, zh, es, ja)")
+def context(topic: str, protocol: bool, chain: str | None, lang: str | None = None):
     """Get audit context for a topic (for LLM prompts)."""
-    kb_instance = KnowledgeBase()
+    kb_instance = KnowledgeBase(locale=_locale(lang))
 
     if protocol:
-        ctx = kb_instance.get_protocol_context(topic)
+        ctx = kb_instance.get_protocol_context(topic, chain=chain)
     else:
-        ctx = kb_instance.get_audit_context(topic)
+        ctx = kb_instance.get_audit_context(topic, chain=chain)
 
     console.print(f"\n[bold]Audit Context: {topic}[/bold]\n")
     console.print(ctx)
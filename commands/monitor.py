@@ -0,0 +1,376 @@
+"""
+Live monitoring commands.
+
+Usage:
+    ./baskerville.py monitor rules <project> [--program NAME] [--program-id ID] [--min-severity LEVEL] [--output FILE]
+                                   [--idl FILE] [--canaries FILE]
+    ./baskerville.py monitor canaries <project> [--program NAME] [--min-severity LEVEL] [--output FILE] [--json]
+    ./baskerville.py monitor events <project> [--program NAME] [--idl FILE] [--json]
+    ./baskerville.py monitor run <rules.yaml> [--provider SPEC] [--source poll|websocket|webhook] [--alerts FILE]
+                                 [--routing FILE]
+    ./baskerville.py monitor replay <rules.yaml> <signature>... [--provider SPEC] [--json]
+    ./baskerville.py monitor geyser <rules.yaml> [--rule ID]... [--output DIR] [--name CRATE] [--force]
+    ./baskerville.py monitor test-alert <routing.yaml> [--severity LEVEL] [--rule ID]
+"""
+
+import json
+import sys
+from pathlib import Path
+
+import click
+import yaml
+from rich.console import Console
+
+sys.path.insert(0, str(Path(__file__).parent.parent))
+
+from commands.project import ProjectManager
+from extensions.monitor import Alert, JsonlSink, Monitor, RuleSet, TxEvent
+
+
+console = Console()
+
+_SEVERITY_STYLE = {"critical": "bold red", "high": "red", "medium": "yellow", "low": "cyan", "info": "dim"}
+
+
+def _load_ruleset(path: str) -> RuleSet:
+    try:
+        return RuleSet.from_dict(yaml.safe_load(Path(path).read_text()) or {})
+    except (OSError, ValueError, TypeError, yaml.YAMLError) as e:
+        console.print(f"[red]Invalid rules file {path}: {e}[/red]")
+        raise SystemExit(1)
+
+
+def _client(provider_spec: str | None):
+    from extensions.rpc import RpcCache, RpcClient, default_cache_dir
+
+    try:
+        return RpcClient(provider_spec, cache=RpcCache(default_cache_dir()))
+    except ValueError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+
+
+def _load_router(path: str):
+    from extensions.monitor import AlertRouter
+
+    try:
+        return AlertRouter.from_dict(yaml.safe_load(Path(path).read_text()) or {})
+    except (OSError, ValueError, TypeError, yaml.YAMLError) as e:
+        console.print(f"[red]Invalid routing file {path}: {e}[/red]")
+        raise SystemExit(1)
+
+
+def _load_canaries(path: str):
+    from extensions.monitor import CanaryPlan
+
+    try:
+        return CanaryPlan.from_dict(yaml.safe_load(Path(path).read_text()) or {})
+    except (OSError, ValueError, TypeError, yaml.YAMLError) as e:
+        console.print(f"[red]Invalid canary plan {path}: {e}[/red]")
+        raise SystemExit(1)
+
+
+def _mainnet_id(project: dict, program) -> str | None:
+    """The program's mainnet address from Anchor.toml, if it has one."""
+    from extensions.ir.anchor_config import load_anchor_config
+
+    try:
+        anchor = load_anchor_config(Path(project["source_path"]))
+    except ValueError:
+        anchor = None
+    return anchor.program_id(program.name, "mainnet") if anchor else None
+
+
+def _project_program(project_name: str, program_name: str | None):
+    """The project and the Solana program of it to watch."""
+    from extensions.ir import load_programs
+
+    project = ProjectManager().get_project(project_name)
+    if not project:
+        console.print(f"[red]Project '{project_name}' not found[/red]")
+        raise SystemExit(1)
+    programs = [p for p in load_programs(Path(project["source_path"])) if p.chain == "solana"]
+    if program_name:
+        programs = [p for p in programs if p.name == program_name]
+    if len(programs) != 1:
+        names = ", ".join(p.name for p in programs) or "none"
+        console.print(f"[red]Pick one program with --program (found: {names})[/red]")
+        raise SystemExit(1)
+    return project, programs[0]
+
+
+def _event_schemas(program, idl: str | None):
+    """Event schemas from ``idl``, located in the sources where they match, else from the sources."""
+    from extensions.monitor.events import schemas_from_idl, schemas_from_program
+
+    found, types = schemas_from_program(program)
+    if not idl:
+        return found, types
+    try:
+        events, idl_types = schemas_from_idl(Path(idl))
+    except (ValueError, KeyError, TypeError) as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    by_name = {e.name: e for e in found}
+    for event in events:
+        if event.name in by_name:
+            src = by_name[event.name]
+            event.file, event.line, event.emitted_by = src.file, src.line, src.emitted_by
+    return events, idl_types
+
+
+def print_alert(alert: Alert) -> None:
+    style = _SEVERITY_STYLE.get(alert.severity, "")
+    console.print(f"[{style}]{alert.severity.upper()}[/{style}] {alert.title}")
+    console.print(f"  {alert.message}  [dim]{alert.signature} @ {alert.slot}[/dim]")
+
+
+def print_events(event: TxEvent) -> None:
+    for e in event.events:
+        decoded = f"[red]{e.error}[/red]" if e.error else json.dumps(e.fields)
+        console.print(f"[cyan]EVENT[/cyan] {e.name} {decoded}  [dim]{event.signature} @ {event.slot}[/dim]")
+
+
+@click.group("monitor")
+def monitor():
+    """Live exploit-attempt monitoring of deployed programs."""
+    pass
+
+
+@monitor.command("rules")
+@click.argument("project_name")
+@click.option("--program", "program_name", default=None, help="Program to watch (default: the only one)")
+@click.option("--program-id", default=None, help="Deployed address (default: Anchor.toml, then declare_id!)")
+@click.option("--min-severity", default="low", type=click.Choice(["info", "low", "medium", "high", "critical"]),
+              help="Ignore findings below this severity")
+@click.option("--output", "-o", default=None, help="Rules file (default: <project>/monitoring/<program>.rules.yaml)")
+@click.option("--json", "as_json", is_flag=True, help="Print the rules as JSON instead of writing a file")
+@click.option("--idl", default=None, type=click.Path(exists=True, dir_okay=False),
+              help="Anchor IDL to take event schemas from (default: the #[event] structs)")
+@click.option("--canaries", "canaries_path", default=None, type=click.Path(exists=True, dir_okay=False),
+              help="Canary plan whose created canaries get canary-touched rules")
+def rules(project_name: str, program_name: str | None, program_id: str | None, min_severity: str,
+          output: str | None, as_json: bool, idl: str | None, canaries_path: str | None):
+    """Derive monitoring rules for a program from the project's findings."""
+    from extensions.monitor import rules_from_findings
+
+    project, program = _project_program(project_name, program_name)
+    project_dir = Path(project["path"])
+    if program_id is None:
+        program_id = _mainnet_id(project, program)
+    hyp_file = project_dir / "hypotheses.json"
+    hypotheses = json.loads(hyp_file.read_text()).get("hypotheses", {}) if hyp_file.exists() else {}
+    try:
+        ruleset = rules_from_findings(hypotheses, program, program_id=program_id, min_severity=min_severity,
+                                      events=_event_schemas(program, idl))
+    except ValueError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    if canaries_path:
+        plan = _load_canaries(canaries_path)
+        if plan.program != program.name:
+            console.print(f"[red]{canaries_path} plans canaries for {plan.program}, not {program.name}[/red]")
+            raise SystemExit(1)
+        taken = {r.id for r in ruleset.rules}
+        ruleset.rules += [r for r in plan.rules() if r.id not in taken]
+        for canary in plan.pending():
+            click.echo(f"Canary {canary.id} has no address yet; not watched", err=True)
+    if as_json:
+        click.echo(json.dumps(ruleset.to_dict(), indent=2))
+        return
+    out_path = Path(output) if output else project_dir / "monitoring" / f"{program.name}.rules.yaml"
+    out_path.parent.mkdir(parents=True, exist_ok=True)
+    out_path.write_text(yaml.safe_dump(ruleset.to_dict(), sort_keys=False))
+    console.print(f"[green]{len(ruleset.rules)} rules for {program.name} ({ruleset.program_id}) "
+                  f"written to {out_path}[/green]")
+    for rule in ruleset.rules:
+        console.print(f"  {rule.id} [dim]({rule.kind}, {rule.severity})[/dim]")
+    if ruleset.events:
+        console.print(f"  [dim]Decoding {len(ruleset.events)} events: "
+                      f"{', '.join(e.name for e in ruleset.events)}[/dim]")
+
+
+@monitor.command("canaries")
+@click.argument("project_name")
+@click.option("--program", "program_name", default=None, help="Program to plant canaries in (default: the only one)")
+@click.option("--program-id", default=None, help="Deployed address (default: Anchor.toml, then declare_id!)")
+@click.option("--min-severity", default="low", type=click.Choice(["info", "low", "medium", "high", "critical"]),
+              help="Ignore findings below this severity")
+@click.option("--output", "-o", default=None,
+              help="Canary plan (default: <project>/monitoring/<program>.canaries.yaml)")
+@click.option("--json", "as_json", is_flag=True, help="Print the plan as JSON instead of writing a file")
+def canaries(project_name: str, program_name: str | None, program_id: str | None, min_severity: str,
+             output: str | None, as_json: bool):
+    """Plan decoy vaults and tripwire PDAs that catch probes of the project's findings."""
+    from extensions.monitor import plan_canaries
+
+    project, program = _project_program(project_name, program_name)
+    project_dir = Path(project["path"])
+    hyp_file = project_dir / "hypotheses.json"
+    hypotheses = json.loads(hyp_file.read_text()).get("hypotheses", {}) if hyp_file.exists() else {}
+    plan = plan_canaries(hypotheses, program, program_id=program_id or _mainnet_id(project, program),
+                         min_severity=min_severity)
+    out_path = Path(output) if output else project_dir / "monitoring" / f"{program.name}.canaries.yaml"
+    if out_path.exists():
+        plan.merge(_load_canaries(str(out_path)))
+    if as_json:
+        click.echo(json.dumps(plan.to_dict(), indent=2))
+        return
+    if not plan.canaries:
+        console.print(f"[yellow]No finding of {program.name} is pinned to an instruction with state to "
+                      f"plant a canary in[/yellow]")
+        return
+    out_path.parent.mkdir(parents=True, exist_ok=True)
+    out_path.write_text(yaml.safe_dump(plan.to_dict(), sort_keys=False))
+    console.print(f"[green]{len(plan.canaries)} canaries for {program.name} written to {out_path}[/green]")
+    for canary in plan.canaries:
+        state = f"[green]{canary.address}[/green]" if canary.address else "[yellow]not created[/yellow]"
+        console.print(f"  [bold]{canary.id}[/bold] [dim]({canary.kind} {canary.account_type}, "
+                      f"{', '.join(canary.findings)})[/dim] {state}")
+        if not canary.address:
+            for step in canary.steps():
+                console.print(f"    - {step}")
+    if not plan.pending():
+        console.print(f"  Watch them with: monitor rules {project_name} --canaries {out_path}")
+
+
+@monitor.command("events")
+@click.argument("project_name")
+@click.option("--program", "program_name", default=None, help="Program to read (default: the only one)")
+@click.option("--idl", default=None, type=click.Path(exists=True, dir_okay=False),
+              help="Anchor IDL to take the schemas from (default: the #[event] structs)")
+@click.option("--json", "as_json", is_flag=True, help="Print the schemas as JSON")
+def events(project_name: str, program_name: str | None, idl: str | None, as_json: bool):
+    """Show the event schemas monitoring decodes a program's events with."""
+    _, program = _project_program(project_name, program_name)
+    schemas, types = _event_schemas(program, idl)
+    if as_json:
+        click.echo(json.dumps({"events": [e.to_dict() for e in schemas], "types": types}, indent=2))
+        return
+    if not schemas:
+        console.print(f"[yellow]{program.name} declares no events[/yellow]")
+        return
+    for event in schemas:
+        where = f" [dim]{event.file}:{event.line}[/dim]" if event.file else ""
+        console.print(f"[bold]{event.name}[/bold] [dim]{event.discriminator}[/dim]{where}")
+        for f in event.fields:
+            ty = f["type"] if isinstance(f["type"], str) else json.dumps(f["type"])
+            console.print(f"  {f['name']}: {ty}")
+        if event.emitted_by:
+            console.print(f"  [dim]emitted by {', '.join(event.emitted_by)}[/dim]")
+
+
+@monitor.command("run")
+@click.argument("rules_path", type=click.Path(exists=True, dir_okay=False))
+@click.option("--provider", "provider_spec", default=None, help="RPC provider (URL, cluster, helius, triton, ...)")
+@click.option("--source", type=click.Choice(["poll", "websocket", "webhook"]), default="poll",
+              help="How transactions are received")
+@click.option("--interval", default=2.0, help="Polling interval in seconds")
+@click.option("--backfill", default=0, help="Also evaluate this many recent transactions on start (polling)")
+@click.option("--host", default="127.0.0.1", help="Webhook listen address")
+@click.option("--port", default=8787, help="Webhook listen port")
+@click.option("--webhook-auth", default=None, envvar="MONITOR_WEBHOOK_AUTH",
+              help="Required Authorization header for webhook posts")
+@click.option("--alerts", "alerts_path", default=None, help="Append alerts to this JSONL file")
+@click.option("--routing", "routing_path", default=None, type=click.Path(exists=True, dir_okay=False),
+              help="Route alerts to Slack, PagerDuty, email or webhooks")
+@click.option("--max-events", default=None, type=int, help="Stop after this many transactions")
+def run(rules_path: str, provider_spec: str | None, source: str, interval: float, backfill: int, host: str,
+        port: int, webhook_auth: str | None, alerts_path: str | None, routing_path: str | None,
+        max_events: int | None):
+    """Watch the program's transactions and raise alerts as rules match."""
+    from extensions.monitor import PollingSource, WebhookSource, WebsocketSource
+
+    ruleset = _load_ruleset(rules_path)
+    client = _client(provider_spec)
+    sinks = [print_alert]
+    if alerts_path:
+        sinks.append(JsonlSink(Path(alerts_path)))
+    if routing_path:
+        sinks.append(_load_router(routing_path))
+    if source == "poll":
+        feed = PollingSource(client, ruleset.program_id, interval=interval, backfill=backfill)
+    elif source == "websocket":
+        feed = WebsocketSource(client.provider.websocket_url, ruleset.program_id)
+    else:
+        feed = WebhookSource(host, port, auth=webhook_auth)
+    mon = Monitor(ruleset, client, sinks)
+    console.print(f"Watching {ruleset.program or ruleset.program_id} ({len(ruleset.rules)} rules) via {source} "
+                  f"on {client.provider.name}; Ctrl-C to stop")
+    try:
+        mon.run(feed, max_events=max_events)
+    except KeyboardInterrupt:
+        pass
+    finally:
+        feed.close()
+    console.print(f"{mon.processed} transactions, {mon.alerts} alerts, {mon.errors} fetch errors")
+
+
+@monitor.command("replay")
+@click.argument("rules_path", type=click.Path(exists=True, dir_okay=False))
+@click.argument("signatures", nargs=-1, required=True)
+@click.option("--provider", "provider_spec", default=None, help="RPC provider (URL, cluster, helius, triton, ...)")
+@click.option("--json", "as_json", is_flag=True, help="Print alerts as JSON")
+def replay(rules_path: str, signatures: tuple[str, ...], provider_spec: str | None, as_json: bool):
+    """Evaluate the rules against past transactions, e.g. a known exploit, showing the events they emitted."""
+    from extensions.monitor import Observed
+
+    ruleset = _load_ruleset(rules_path)
+    mon = Monitor(ruleset, _client(provider_spec), [] if as_json else [print_alert],
+                  on_event=None if as_json else print_events)
+    alerts = [a for sig in signatures for a in mon.handle(Observed(sig))]
+    if as_json:
+        click.echo(json.dumps([a.to_dict() for a in alerts], indent=2))
+    elif not alerts:
+        console.print("[green]No rule matched[/green]")
+    if mon.errors:
+        click.echo(f"{mon.errors} transactions could not be fetched", err=True)
+
+
+@monitor.command("geyser")
+@click.argument("rules_path", type=click.Path(exists=True, dir_okay=False))
+@click.option("--rule", "rule_ids", multiple=True, help="Rule to include (repeatable; default: all)")
+@click.option("--output", "-o", default=None, help="Crate directory (default: <rules file>-geyser next to it)")
+@click.option("--name", default=None, help="Crate name (default: <program>-baskerville-geyser)")
+@click.option("--force", is_flag=True, help="Overwrite a non-empty output directory")
+def geyser(rules_path: str, rule_ids: tuple[str, ...], output: str | None, name: str | None, force: bool):
+    """Generate a Geyser plugin crate that runs the rules inside a validator."""
+    from extensions.monitor import generate_geyser_plugin, write_geyser_plugin
+
+    ruleset = _load_ruleset(rules_path)
+    rules_file = Path(rules_path)
+    out_dir = Path(output) if output else rules_file.with_name(rules_file.name.split(".")[0] + "-geyser")
+    try:
+        files = generate_geyser_plugin(ruleset, list(rule_ids), name=name, source=rules_file.name)
+        write_geyser_plugin(files, out_dir, force=force)
+    except (ValueError, FileExistsError) as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    selected = list(rule_ids) or [r.id for r in ruleset.rules]
+    console.print(f"[green]Geyser plugin crate written to {out_dir}[/green] ({len(selected)} rules)")
+    console.print(f"  cd {out_dir} && cargo build --release")
+    console.print("  solana-validator ... --geyser-plugin-config config.json")
+
+
+@monitor.command("test-alert")
+@click.argument("routing_path", type=click.Path(exists=True, dir_okay=False))
+@click.option("--severity", default="critical", type=click.Choice(["info", "low", "medium", "high", "critical"]),
+              help="Severity of the test alert")
+@click.option("--rule", "rule_id", default="test-alert", help="Rule id of the test alert (to exercise routes)")
+def send_test_alert(routing_path: str, severity: str, rule_id: str):
+    """Send a test alert through the routing file, showing which channels it reached."""
+    router = _load_router(routing_path)
+    router.on_error = lambda msg: None
+    alert = Alert(rule_id=rule_id, kind="test", severity=severity, title="Baskerville test alert",
+                  message="Test alert; no on-chain activity matched.", signature="test", slot=0, program_id="")
+    deliveries = router(alert)
+    if not deliveries:
+        console.print("[yellow]No route matched the test alert[/yellow]")
+    for d in deliveries:
+        label = "escalation" if d.escalated else "route"
+        if d.ok:
+            console.print(f"[green]✓[/green] {d.channel} [dim]({label})[/dim]")
+        else:
+            console.print(f"[red]✗[/red] {d.channel} [dim]({label})[/dim]: {d.error}")
+    if any(not d.ok for d in deliveries):
+        raise SystemExit(1)
@@ -3,9 +3,10 @@ Proof-of-Concept management for confirmed vulnerabilities
 """
 
 import json
+import re
 import shutil
 import sys
-from dataclasses import dataclass
+from dataclasses import dataclass, field
 from datetime import datetime
 from pathlib import Path
 from typing import Any
@@ -24,6 +25,10 @@ class PoCContext:
     hypothesis: dict[str, Any]
     affected_files: dict[str, str]  # filepath -> content
     manifest_data: dict[str, Any]
+    alt_harnesses: dict[str, str] = field(default_factory=dict)  # instruction -> ALT helper source
+    anchor_config: dict[str, Any] | None = None  # Anchor.toml summary (cluster, program ids, test setup)
+    scenario_harness: Any = None  # ScenarioHarness compiled from a multi-step scenario
+    harness_files: dict[str, str] = field(default_factory=dict)  # shared attacker helpers, by relative path
 
 
 def load_affected_files(hypothesis: dict[str, Any], manifest_data: dict[str, Any]) -> dict[str, str]:
@@ -55,6 +60,57 @@ def load_affected_files(hypothesis: dict[str, Any], manifest_data: dict[str, Any
     return affected_files
 
 
+def load_solana_programs(source_path: str | None) -> list:
+    """IR programs for the project source, or [] if it cannot be loaded"""
+    if not source_path or not Path(source_path).exists():
+        return []
+    try:
+        from extensions.ir import load_programs
+        return [p for p in load_programs(Path(source_path)) if p.chain == 'solana']
+    except Exception:
+        return []
+
+
+def load_anchor_summary(source_path: str | None) -> dict[str, Any] | None:
+    """Anchor.toml summary for the project source, or None without a (valid) one"""
+    if not source_path or not Path(source_path).exists():
+        return None
+    try:
+        from extensions.ir.anchor_config import load_anchor_config
+        config = load_anchor_config(Path(source_path))
+    except (ImportError, ValueError):
+        return None
+    return config.summary() if config else None
+
+
+def load_alt_harnesses(programs: list, hypothesis: dict[str, Any]) -> dict[str, str]:
+    """ALT harnesses for Solana instructions the hypothesis targets that do not fit a legacy transaction"""
+    from extensions.ir.tx_size import alt_harness, instruction_footprint
+    
+    properties = hypothesis.get('properties') or {}
+    text = ' '.join(str(v) for v in (
+        hypothesis.get('title', ''), hypothesis.get('description', ''),
+        properties.get('function', ''), ' '.join(map(str, hypothesis.get('node_refs', []))),
+    ))
+    harnesses = {}
+    for program in programs:
+        for ix in program.instructions:
+            names = {ix.name, ix.handler}
+            if not any(re.search(rf'\b{re.escape(n)}\b', text) for n in names if n):
+                continue
+            if instruction_footprint(program, ix).status != 'ok':
+                harnesses[ix.name] = alt_harness(program, ix)
+    return harnesses
+
+
+def load_harness_files(programs: list, hypothesis: dict[str, Any]) -> dict[str, str]:
+    """Shared attacker helpers for a Solana project, with the fake token program for arbitrary-CPI findings"""
+    if not programs:
+        return {}
+    from extensions.poc.harness import generate_harness, needs_fake_token
+    return generate_harness(fake_token=needs_fake_token(hypothesis))
+
+
 def generate_poc_with_strategist(context: PoCContext, config: dict[str, Any]) -> str:
     """Use strategist model to generate PoC prompt"""
     
@@ -98,6 +154,70 @@ def generate_poc_with_strategist(context: PoCContext, config: dict[str, Any]) ->
         else:
             files_context += content
     
+    # Instructions that only fit in a v0 transaction with an address lookup table
+    alt_context = ""
+    if context.alt_harnesses:
+        alt_context = f"""
+TRANSACTION SIZE CONSTRAINTS:
+These instructions do not fit in a legacy Solana transaction: {', '.join(context.alt_harnesses)}.
+The PoC must send them as v0 transactions with an address lookup table. An ALT helper
+(buildLookupTable / sendV0) is saved next to this prompt; tell the coding agent to copy it
+into the test directory and use it instead of building legacy transactions.
+"""
+    
+    # The project's own Anchor.toml setup, so the PoC runs under `anchor test` unchanged
+    anchor_context = ""
+    if context.anchor_config:
+        cfg = context.anchor_config
+        validator = cfg.get('validator', {})
+        lines = [f"- Cluster: {cfg['cluster']} ({cfg['rpc_url']})"]
+        if cfg.get('wallet'):
+            lines.append(f"- Wallet: {cfg['wallet']}")
+        lines += [f"- Program `{name}`: {address}" for name, address in cfg.get('program_ids', {}).items()]
+        if cfg.get('test_script'):
+            lines.append(f"- Test command (Anchor.toml [scripts] test): {cfg['test_script']}")
+        if validator.get('clones'):
+            lines.append(f"- Accounts cloned from {validator.get('url', 'the cluster')}: {', '.join(validator['clones'])}")
+        if validator.get('genesis'):
+            lines.append("- Programs loaded at genesis: " + ', '.join(g.get('address', '?') for g in validator['genesis']))
+        if validator.get('accounts'):
+            lines.append("- Preloaded accounts: " + ', '.join(a.get('address', '?') for a in validator['accounts']))
+        anchor_context = f"""
+PROJECT CONFIGURATION (from Anchor.toml):
+{chr(10).join(lines)}
+The PoC must use these program ids, this cluster and wallet, and run with the project's test command;
+it must not hard-code other addresses or start its own validator with different settings.
+"""
+    
+    # Multi-transaction exploit compiled from a scenario file
+    scenario_context = ""
+    if context.scenario_harness:
+        harness = context.scenario_harness
+        scenario_context = f"""
+MULTI-STEP SCENARIO:
+The exploit is the scenario `{harness.name}`: {harness.transactions} transactions that must run in order.
+A mocha test compiled from it (setup, each transaction, preconditions and expected state deltas) is saved
+next to this prompt; tell the coding agent to start from it, keep the transaction order and the
+assertions, and only fill in what the scenario could not express.
+"""
+    
+    # Shared attacker helpers, so the PoC does not rebuild keypair and token setup
+    harness_context = ""
+    if context.harness_files:
+        from extensions.poc.harness import FAKE_TOKEN_CRATE, HELPERS, LIBRARY
+        helpers = '\n'.join(f"- {names}: {description}" for names, description in HELPERS.items())
+        fake_token = ""
+        if any(rel.startswith(f"{FAKE_TOKEN_CRATE}/") for rel in context.harness_files):
+            fake_token = (f"\nA fake SPL Token program crate ({FAKE_TOKEN_CRATE}/) is saved too: the PoC "
+                          "should pass it where the Token program is expected\n(deployFakeTokenProgram) and show "
+                          "what the target does with the unchecked CPI (fakeTokenSigners, balances).")
+        harness_context = f"""
+HARNESS LIBRARY:
+A helper module ({LIBRARY}.ts) is saved next to this prompt; tell the coding agent to copy it into the
+test directory and import from it instead of writing its own setup code:
+{helpers}{fake_token}
+"""
+    
     # Create prompt for strategist
     strategist_prompt = f"""
 You are a security expert creating a detailed prompt for a coding agent to generate a proof-of-concept exploit.
@@ -124,6 +244,10 @@ The coding agent should ONLY create a NEW test/exploit file that interacts with
 
 RELEVANT CODE FROM AFFECTED FILES:
 {files_context}
+{alt_context}
+{anchor_context}
+{scenario_context}
+{harness_context}
 
 Your task is to create a comprehensive prompt for a coding agent (like Claude Code) that will:
 1. Generate a NEW proof-of-concept test/exploit file that demonstrates the vulnerability
@@ -155,7 +279,8 @@ Start with the clarification about existing vs new files before any other instru
     return response
 
 
-def make_prompt(project_name: str, hypothesis_id: str | None = None, config: dict[str, Any] | None = None):
+def make_prompt(project_name: str, hypothesis_id: str | None = None, config: dict[str, Any] | None = None,
+                scenario_file: str | None = None):
     """Generate PoC prompts for vulnerabilities"""
     
     # Load project data
@@ -220,6 +345,26 @@ def make_prompt(project_name: str, hypothesis_id: str | None = None, config: dic
                 console.print("[red]No vulnerabilities found suitable for PoC generation[/red]")
                 sys.exit(1)
     
+    # Solana programs for IR-based checks (e.g. transaction size)
+    source_path = None
+    project_file = project_dir / "project.json"
+    if project_file.exists():
+        with open(project_file) as f:
+            source_path = json.load(f).get('source_path')
+    programs = load_solana_programs(source_path)
+    anchor_config = load_anchor_summary(source_path)
+    scenario_harness = None
+    if scenario_file:
+        from extensions.poc.scenario import ScenarioError, compile_scenario, load_scenario
+        try:
+            scenario_harness = compile_scenario(load_scenario(Path(scenario_file)), programs,
+                                                (anchor_config or {}).get('toolchain', {}).get('anchor_version'))
+        except ScenarioError as e:
+            console.print(f"[red]Scenario {scenario_file}: {e}[/red]")
+            sys.exit(1)
+        for warning in scenario_harness.warnings:
+            console.print(f"[yellow]Scenario: {warning}[/yellow]")
+    
     # Create output directory
     output_dir = project_dir / "poc_prompts"
     output_dir.mkdir(exist_ok=True)
@@ -239,8 +384,14 @@ def make_prompt(project_name: str, hypothesis_id: str | None = None, config: dic
             project_name=project_name,
             hypothesis=hypothesis,
             affected_files=affected_files,
-            manifest_data=manifest_data
+            manifest_data=manifest_data,
+            alt_harnesses=load_alt_harnesses(programs, hypothesis),
+            anchor_config=anchor_config,
+            scenario_harness=scenario_harness,
+            harness_files=load_harness_files(programs, hypothesis)
         )
+        if context.alt_harnesses:
+            console.print(f"  [yellow]Needs an address lookup table: {', '.join(context.alt_harnesses)}[/yellow]")
         
         # Generate prompt using strategist
         console.print("  Generating prompt with strategist model...")
@@ -259,6 +410,21 @@ def make_prompt(project_name: str, hypothesis_id: str | None = None, config: dic
                 f.write(prompt)
             
             console.print(f"  [green]✓[/green] Saved to {output_file}")
+            for ix_name, harness in context.alt_harnesses.items():
+                harness_file = output_dir / f"{hid}_{ix_name}_alt.ts"
+                harness_file.write_text(harness)
+                console.print(f"  [green]✓[/green] ALT harness saved to {harness_file}")
+            if scenario_harness:
+                scenario_out = output_dir / f"{hid}_scenario.ts"
+                scenario_out.write_text(scenario_harness.source)
+                console.print(f"  [green]✓[/green] Scenario harness saved to {scenario_out}")
+            if context.harness_files:
+                from extensions.poc.harness import write_harness
+                try:
+                    for path in write_harness(context.harness_files, output_dir):
+                        console.print(f"  [green]✓[/green] Harness helpers saved to {path}")
+                except FileExistsError as e:
+                    console.print(f"  [yellow]Harness helpers not updated: {e}[/yellow]")
             
             # If single hypothesis, also display the prompt
             if len(hypotheses) == 1:
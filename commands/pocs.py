@@ -0,0 +1,318 @@
+"""
+PoC packaging commands.
+
+Usage:
+    ./baskerville.py poc package <project> <hypothesis> [--output DIR] [--backend validator|litesvm|native]
+                                 [--url URL] [--clone ADDR ...] [--command CMD] [--redact] [--archive] [--force]
+    ./baskerville.py poc redact <dir> [--output DIR] [--keep ADDR ...] [--map FILE] [--force]
+    ./baskerville.py poc scenario <scenario.yaml> <workspace> [--output FILE]
+    ./baskerville.py poc harness <test-dir> [--fake-token] [--force]
+    ./baskerville.py poc run <project> [<hypothesis> | --all] [--backend litesvm|test-validator|fork|native ...]
+                             [--url URL] [--timeout SECONDS] [--json]
+    ./baskerville.py poc matrix <project> [--backend B ...] [--json]
+"""
+
+import json
+import shutil
+import sys
+from pathlib import Path
+
+import click
+from rich.console import Console
+from rich.table import Table
+
+sys.path.insert(0, str(Path(__file__).parent.parent))
+
+from extensions.poc.harness import generate_harness, write_harness
+from extensions.poc.matrix import MATRIX_BACKENDS, PocRun, PocRunStore, default_backends, matrix_rows, run_matrix
+from extensions.poc.package import BACKENDS, PackageError, package_poc, poc_destination
+from extensions.poc.redact import Redaction, redact_poc
+from extensions.poc.scenario import ScenarioError, compile_scenario, load_scenario
+
+
+console = Console()
+
+# Matrix cell per run status
+_CELLS = {"passed": "[green]pass[/green]", "failed": "[red]fail[/red]", "error": "[yellow]error[/yellow]",
+          "skipped": "[dim]skip[/dim]"}
+
+
+def _load_poc(project_dir: Path, source: Path, hypothesis: str) -> dict[str, Path]:
+    """Imported PoC files by package destination, placed where they were written in the workspace."""
+    poc_dir = project_dir / "poc" / hypothesis
+    if not poc_dir.is_dir():
+        console.print(f"[red]No imported PoC for hypothesis '{hypothesis}' (run `poc import` first)[/red]")
+        raise SystemExit(1)
+    metadata_file = poc_dir / "metadata.json"
+    originals = {}
+    if metadata_file.exists():
+        metadata = json.loads(metadata_file.read_text())
+        originals = {f["name"]: f.get("original_path") for f in metadata.get("files", [])}
+    pocs = {}
+    for path in sorted(p for p in poc_dir.iterdir() if p.is_file() and p.name != "metadata.json"):
+        original = originals.get(path.name)
+        pocs[poc_destination(Path(original) if original else path, source)] = path
+    return pocs
+
+
+def _write_map(redaction: Redaction, map_file: str | None) -> Path:
+    """Keep the address mapping next to the artifact, never inside it."""
+    path = Path(map_file) if map_file else redaction.root.with_name(f"{redaction.root.name}.redaction-map.json")
+    path.write_text(json.dumps(redaction.redaction_map(), indent=2) + "\n")
+    return path
+
+
+def _print_redaction(redaction: Redaction, map_path: Path):
+    console.print(f"  Redacted: {len(redaction.mapping)} address(es), {redaction.amounts} amount(s), "
+                  f"{len(redaction.files)} file(s) rewritten")
+    console.print(f"  [bold]Redaction map (keep private):[/bold] {map_path}")
+    for warning in redaction.warnings:
+        console.print(f"  [yellow]{warning}[/yellow]")
+
+
+def _project(project_name: str) -> tuple[Path, Path, dict]:
+    """Project directory, workspace and stored hypotheses."""
+    from commands.project import ProjectManager
+
+    project = ProjectManager().get_project(project_name)
+    if not project:
+        console.print(f"[red]Project '{project_name}' not found[/red]")
+        raise SystemExit(1)
+    project_dir = Path(project["path"])
+    hypotheses = {}
+    store_file = project_dir / "hypotheses.json"
+    if store_file.exists():
+        hypotheses = json.loads(store_file.read_text()).get("hypotheses", {})
+    return project_dir, Path(project["source_path"]), hypotheses
+
+
+def _cell(run: PocRun | None) -> str:
+    if run is None:
+        return "[dim]-[/dim]"
+    cell = _CELLS[run.status]
+    if run.status in ("passed", "failed"):
+        cell += f" {run.duration:.1f}s"
+        if run.compute_units is not None:
+            cell += f" {run.compute_units:,} CU"
+    return cell
+
+
+def _print_matrix(runs: dict, hypotheses: dict, backends: list[str] | None = None):
+    columns, rows = matrix_rows(runs, backends)
+    table = Table(title="PoC verification")
+    table.add_column("Hypothesis")
+    for backend in columns:
+        table.add_column(backend)
+    for hypothesis, cells in rows:
+        title = (hypotheses.get(hypothesis) or {}).get("title", "")
+        table.add_row(f"{hypothesis}\n[dim]{title[:60]}[/dim]" if title else hypothesis, *map(_cell, cells))
+    console.print(table)
+    verified = sum(1 for _, cells in rows if any(r is not None and r.status == "passed" for r in cells))
+    console.print(f"Verified on at least one backend: {verified}/{len(rows)} hypotheses")
+
+
+@click.group("poc")
+def poc():
+    """Standalone PoC packages."""
+    pass
+
+
+@poc.command("package")
+@click.argument("project_name")
+@click.argument("hypothesis")
+@click.option("--output", "-o", default=None, help="Package directory (default: <project>/poc_packages/<hypothesis>)")
+@click.option("--backend", type=click.Choice(list(BACKENDS)), default=None,
+              help="Execution backend run.sh targets (default: validator for Solana workspaces, else native)")
+@click.option("--url", default=None,
+              help="Cluster or RPC provider to snapshot accounts from (default: Anchor.toml [test.validator] url)")
+@click.option("--clone", "clones", multiple=True, help="Additional account to snapshot")
+@click.option("--command", default=None, help="Command that runs the PoC (default: inferred from the PoC files)")
+@click.option("--no-pin", is_flag=True, help="Do not generate missing lockfiles")
+@click.option("--redact", is_flag=True, help="Redact addresses and amounts for disclosure (see `poc redact`)")
+@click.option("--archive", is_flag=True, help="Also write a .tar.gz of the package")
+@click.option("--force", is_flag=True, help="Replace an existing package directory")
+def package(project_name: str, hypothesis: str, output: str | None, backend: str | None, url: str | None,
+            clones: tuple[str, ...], command: str | None, no_pin: bool, redact: bool, archive: bool, force: bool):
+    """Bundle a PoC into a self-contained repository the client can run."""
+    from commands.project import ProjectManager
+    from extensions.reporting.manifest import build_manifest
+    from utils.config_loader import load_config
+
+    project = ProjectManager().get_project(project_name)
+    if not project:
+        console.print(f"[red]Project '{project_name}' not found[/red]")
+        raise SystemExit(1)
+    project_dir = Path(project["path"])
+    source = Path(project["source_path"])
+
+    hypotheses = {}
+    store_file = project_dir / "hypotheses.json"
+    if store_file.exists():
+        hypotheses = json.loads(store_file.read_text()).get("hypotheses", {})
+    if hypothesis not in hypotheses:
+        console.print(f"[red]Hypothesis {hypothesis} not found[/red]")
+        raise SystemExit(1)
+
+    out_dir = Path(output) if output else project_dir / "poc_packages" / hypothesis
+    params = {"project_name": project_name, "hypothesis": hypothesis, "backend": backend, "url": url,
+              "clones": list(clones), "command": command}
+    try:
+        result = package_poc(
+            source, _load_poc(project_dir, source, hypothesis), out_dir, hypothesis,
+            hypothesis=hypotheses[hypothesis], backend=backend, url=url, clones=list(clones), command=command,
+            pin=not no_pin, manifest=build_manifest("poc package", params, source=source, config=load_config()),
+            force=force,
+        )
+        redaction = redact_poc(result.root) if redact else None
+    except PackageError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+
+    console.print(f"[green]Packaged {hypothesis} in {result.root}[/green] (backend: {result.backend})")
+    console.print(f"  PoC: {', '.join(result.poc_files)}")
+    console.print(f"  Run: {result.command}")
+    if result.lockfiles:
+        console.print(f"  Lockfiles: {', '.join(result.lockfiles)}")
+    if result.accounts or result.programs:
+        console.print(f"  Fixtures: {len(result.accounts)} account(s), {len(result.programs)} program(s)")
+    for warning in result.warnings:
+        console.print(f"  [yellow]{warning}[/yellow]")
+    for address, reason in result.skipped.items():
+        console.print(f"  [dim]{address}: {reason}[/dim]")
+    if redaction:
+        _print_redaction(redaction, _write_map(redaction, None))
+    if archive:
+        tarball = shutil.make_archive(str(result.root), "gztar", root_dir=result.root.parent,
+                                      base_dir=result.root.name)
+        console.print(f"  Archive: {tarball}")
+
+
+@poc.command("redact")
+@click.argument("path", type=click.Path(exists=True, file_okay=False))
+@click.option("--output", "-o", default=None, help="Redacted copy (default: <dir>-redacted)")
+@click.option("--keep", multiple=True, help="Address to leave in place (e.g. a program ID an SDK hardcodes)")
+@click.option("--map", "map_file", default=None,
+              help="Where to write the private address mapping (default: <output>.redaction-map.json)")
+@click.option("--force", is_flag=True, help="Replace an existing output directory")
+def redact(path: str, output: str | None, keep: tuple[str, ...], map_file: str | None, force: bool):
+    """Write a disclosure-safe copy of a PoC package that still runs against its fixtures."""
+    source = Path(path)
+    out_dir = Path(output) if output else source.with_name(f"{source.name}-redacted")
+    try:
+        redaction = redact_poc(source, out_dir, keep=list(keep), force=force)
+    except PackageError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    console.print(f"[green]Redacted copy in {redaction.root}[/green]")
+    _print_redaction(redaction, _write_map(redaction, map_file))
+
+
+@poc.command("scenario")
+@click.argument("scenario_file", type=click.Path(exists=True, dir_okay=False))
+@click.argument("workspace", type=click.Path(exists=True, file_okay=False))
+@click.option("--output", "-o", default=None, help="Test file to write (default: <scenario>.ts next to the scenario)")
+def scenario(scenario_file: str, workspace: str, output: str | None):
+    """Compile a multi-step exploit scenario into an ordered Anchor test."""
+    from extensions.ir import load_programs
+    from extensions.ir.anchor_config import load_anchor_config
+
+    source = Path(workspace)
+    try:
+        anchor = load_anchor_config(source)
+        harness = compile_scenario(load_scenario(Path(scenario_file)), load_programs(source),
+                                   anchor.toolchain.get("anchor_version") if anchor else None)
+    except (ScenarioError, ValueError) as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    out = Path(output) if output else Path(scenario_file).with_suffix(".ts")
+    out.write_text(harness.source)
+    console.print(f"[green]Compiled {harness.name}[/green]: {harness.transactions} transaction(s) "
+                  f"against {', '.join(harness.programs)} -> {out}")
+    try:
+        for path in write_harness(generate_harness(), out.parent):
+            console.print(f"  Helpers: {path}")
+    except FileExistsError as e:
+        console.print(f"  [yellow]{e}[/yellow]")
+    for warning in harness.warnings:
+        console.print(f"  [yellow]{warning}[/yellow]")
+
+
+@poc.command("harness")
+@click.argument("out_dir", type=click.Path(file_okay=False))
+@click.option("--fake-token", is_flag=True, help="Also write the fake SPL Token program crate (arbitrary CPI PoCs)")
+@click.option("--force", is_flag=True, help="Overwrite helper files that were edited")
+def harness(out_dir: str, fake_token: bool, force: bool):
+    """Write the shared attacker helpers into a PoC test directory."""
+    try:
+        written = write_harness(generate_harness(fake_token=fake_token), Path(out_dir), force=force)
+    except FileExistsError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    if not written:
+        console.print(f"[dim]Helpers in {out_dir} are up to date[/dim]")
+    for path in written:
+        console.print(f"[green]Wrote {path}[/green]")
+
+
+@poc.command("run")
+@click.argument("project_name")
+@click.argument("hypothesis", required=False)
+@click.option("--all", "run_all", is_flag=True, help="Run every imported PoC")
+@click.option("--backend", "backends", multiple=True, type=click.Choice(list(MATRIX_BACKENDS)),
+              help="Backend to run on; repeat for several (default: litesvm, test-validator and fork for "
+                   "Solana workspaces, else native)")
+@click.option("--url", default=None, help="Cluster the fork backend snapshots (default: Anchor.toml url)")
+@click.option("--timeout", default=600.0, show_default=True, help="Seconds before a run is stopped")
+@click.option("--json", "as_json", is_flag=True, help="Print the runs as JSON")
+def run(project_name: str, hypothesis: str | None, run_all: bool, backends: tuple[str, ...], url: str | None,
+        timeout: float, as_json: bool):
+    """Run imported PoCs across backends and record the results."""
+    if bool(hypothesis) == run_all:
+        console.print("[red]Pass a hypothesis or --all[/red]")
+        raise SystemExit(1)
+    project_dir, source, hypotheses = _project(project_name)
+    if run_all:
+        poc_root = project_dir / "poc"
+        ids = sorted(p.name for p in poc_root.iterdir() if p.is_dir()) if poc_root.is_dir() else []
+        if not ids:
+            console.print("[yellow]No imported PoCs (run `poc import` first)[/yellow]")
+            return
+    else:
+        ids = [hypothesis]
+    pocs = {h: _load_poc(project_dir, source, h) for h in ids}
+    selected = list(backends) or default_backends(source)
+
+    def progress(result: PocRun):
+        if not as_json:
+            reason = f" ({result.reason})" if result.reason else ""
+            console.print(f"  {result.hypothesis} on {result.backend}: {_CELLS[result.status]}{reason}")
+
+    runs = run_matrix(source, pocs, selected, hypotheses=hypotheses, on_run=progress, url=url, timeout=timeout)
+    PocRunStore(project_dir / "poc_runs.json").record(runs)
+    if as_json:
+        click.echo(json.dumps([r.to_dict() for r in runs], indent=2))
+        return
+    _print_matrix({(r.hypothesis, r.backend): r for r in runs}, hypotheses, selected)
+
+
+@poc.command("matrix")
+@click.argument("project_name")
+@click.option("--backend", "backends", multiple=True, type=click.Choice(list(MATRIX_BACKENDS)),
+              help="Backend column to show; repeat for several (default: every backend with a run)")
+@click.option("--json", "as_json", is_flag=True, help="Print the last run per hypothesis and backend as JSON")
+def matrix(project_name: str, backends: tuple[str, ...], as_json: bool):
+    """Show the last recorded run of each PoC on each backend."""
+    project_dir, _, hypotheses = _project(project_name)
+    store_file = project_dir / "poc_runs.json"
+    latest = PocRunStore(store_file).latest() if store_file.exists() else {}
+    if as_json:
+        columns, rows = matrix_rows(latest, list(backends) or None)
+        click.echo(json.dumps({"backends": columns, "hypotheses": [
+            {"id": h, "title": (hypotheses.get(h) or {}).get("title"),
+             "runs": {b: r.to_dict() if r else None for b, r in zip(columns, cells)}} for h, cells in rows]},
+            indent=2))
+        return
+    if not latest:
+        console.print("[yellow]No PoC runs recorded (run `poc run --all` first)[/yellow]")
+        return
+    _print_matrix(latest, hypotheses, list(backends) or None)
@@ -0,0 +1,190 @@
+"""
+Precedent commands: published audit reports as precedent for findings.
+
+Usage:
+    ./baskerville.py precedent import <report.yaml|report.json>           # Import report metadata
+    ./baskerville.py precedent import <report.md> --firm F --protocol P   # Import a published Markdown report
+    ./baskerville.py precedent solodit <query> [--limit N] [--chain C]   # Import Solodit search results
+    ./baskerville.py precedent list                                       # List imported reports
+    ./baskerville.py precedent remove <key>                               # Remove an imported report
+    ./baskerville.py precedent match <project> [--static] [--annotate]    # Match hypotheses against precedent
+"""
+
+import json
+import sys
+from pathlib import Path
+
+import click
+from rich.console import Console
+from rich.table import Table
+
+sys.path.insert(0, str(Path(__file__).parent.parent))
+
+from commands.project import ProjectManager
+from extensions.precedent import PrecedentCatalog, PrecedentMatcher, load_report_file, reports_from_solodit
+
+
+console = Console()
+
+_SEVERITY_STYLE = {"critical": "bold red", "high": "red", "medium": "yellow", "low": "cyan", "info": "dim"}
+
+
+@click.group("precedent")
+def precedent():
+    """Published audit findings as precedent for hypotheses and detector hits."""
+    pass
+
+
+@precedent.command("import")
+@click.argument("path", type=click.Path(exists=True, dir_okay=False))
+@click.option("--firm", help="Audit firm (required for Markdown reports)")
+@click.option("--protocol", help="Audited protocol (required for Markdown reports)")
+@click.option("--chain", help="Chain of the audited code (evm, solana, sui, ...)")
+@click.option("--date", help="Report date (YYYY-MM-DD)")
+@click.option("--url", help="Link to the published report")
+def import_report(path: str, firm: str | None, protocol: str | None, chain: str | None, date: str | None,
+                  url: str | None):
+    """Import a published report from metadata (YAML/JSON) or Markdown."""
+    try:
+        report = load_report_file(Path(path), firm=firm, protocol=protocol, chain=chain, date=date, url=url)
+    except (OSError, ValueError, TypeError, json.JSONDecodeError) as e:
+        console.print(f"[red]Cannot import {path}: {e}[/red]")
+        raise SystemExit(1)
+    stored = PrecedentCatalog().add(report)
+    by_severity: dict[str, int] = {}
+    for f in report.findings:
+        by_severity[f.severity] = by_severity.get(f.severity, 0) + 1
+    counts = ", ".join(f"{n} {sev}" for sev, n in by_severity.items())
+    console.print(f"[green]Imported {len(report.findings)} findings ({counts}) from "
+                  f"{report.firm}'s audit of {report.protocol}[/green]")
+    console.print(f"[dim]{stored}[/dim]")
+
+
+@precedent.command("solodit")
+@click.argument("query")
+@click.option("--limit", "-l", default=50, help="Maximum findings to import")
+@click.option("--chain", help="Chain to record on the imported reports")
+def import_solodit(query: str, limit: int, chain: str | None):
+    """Import Solodit search results, one report per firm and protocol."""
+    from extensions.solodit.client import SoloditClientSync
+
+    findings, _ = SoloditClientSync().search_findings(keywords=query, page_size=min(limit, 100))
+    if not findings:
+        console.print("[yellow]No findings found (is SOLODIT_API_KEY set?).[/yellow]")
+        return
+    catalog = PrecedentCatalog()
+    reports = reports_from_solodit(findings[:limit], chain=chain)
+    for report in reports:
+        catalog.add(report)
+    console.print(f"[green]Imported {sum(len(r.findings) for r in reports)} findings from "
+                  f"{len(reports)} reports[/green]")
+
+
+@precedent.command("list")
+def list_reports():
+    """List imported reports."""
+    reports = PrecedentCatalog().reports()
+    if not reports:
+        console.print("[yellow]No reports imported. Use `precedent import` or `precedent solodit`.[/yellow]")
+        return
+
+    table = Table(show_header=True, header_style="bold")
+    table.add_column("Key", width=36)
+    table.add_column("Firm", width=16)
+    table.add_column("Protocol", width=16)
+    table.add_column("Chain", width=8)
+    table.add_column("Findings", width=8)
+    for report in reports:
+        table.add_row(report.key, report.firm, report.protocol, report.chain or "", str(len(report.findings)))
+    console.print(table)
+
+
+@precedent.command("remove")
+@click.argument("key")
+def remove_report(key: str):
+    """Remove an imported report by key."""
+    if not PrecedentCatalog().remove(key):
+        console.print(f"[red]No imported report {key}[/red]")
+        raise SystemExit(1)
+    console.print(f"[green]Removed {key}[/green]")
+
+
+def _load_hypotheses(project_dir: Path, static: bool) -> tuple[dict[str, dict], Path]:
+    """Hypotheses keyed by id, and the file they came from."""
+    if static:
+        path = project_dir / "static_analysis" / "static_hypotheses.json"
+        items = json.loads(path.read_text()) if path.exists() else []
+        return {h.get("id") or f"static_{i}": h for i, h in enumerate(items)}, path
+    path = project_dir / "hypotheses.json"
+    data = json.loads(path.read_text()) if path.exists() else {}
+    return data.get("hypotheses", {}), path
+
+
+@precedent.command("match")
+@click.argument("project_name")
+@click.option("--static", "static", is_flag=True, help="Match static-analysis hits instead of the hypothesis store")
+@click.option("--limit", "-l", default=3, help="Matches shown per hypothesis")
+@click.option("--min-score", default=0.3, type=float, help="Minimum match score (0-1)")
+@click.option("--annotate", is_flag=True, help="Record matches and suggested severity on each hypothesis")
+@click.option("--json", "as_json", is_flag=True, help="Print matches as JSON")
+def match(project_name: str, static: bool, limit: int, min_score: float, annotate: bool, as_json: bool):
+    """Match a project's hypotheses or detector hits against published findings."""
+    project = ProjectManager().get_project(project_name)
+    if not project:
+        console.print(f"[red]Project '{project_name}' not found[/red]")
+        raise SystemExit(1)
+    project_dir = Path(project["path"])
+    hypotheses, source = _load_hypotheses(project_dir, static)
+    if not hypotheses:
+        console.print(f"[yellow]No hypotheses in {source}[/yellow]")
+        return
+
+    catalog = PrecedentCatalog()
+    if not catalog.reports():
+        console.print("[yellow]No reports imported. Use `precedent import` or `precedent solodit`.[/yellow]")
+        return
+    matcher = PrecedentMatcher(catalog, min_score=min_score)
+    chain = project.get("chain_id")
+
+    results = {}
+    for hyp_id, hyp in hypotheses.items():
+        matches = matcher.match(hyp, chain=chain, limit=limit)
+        calibration = matcher.calibrate(matches)
+        results[hyp_id] = (hyp, matches, calibration)
+        if annotate:
+            hyp.setdefault("properties", {})["precedents"] = [m.to_dict() for m in matches]
+            hyp["properties"]["precedent_severity"] = calibration.suggested
+
+    if as_json:
+        click.echo(json.dumps({
+            hyp_id: {"title": hyp.get("title"), "severity": hyp.get("severity"),
+                     "suggested_severity": cal.suggested, "matches": [m.to_dict() for m in matches]}
+            for hyp_id, (hyp, matches, cal) in results.items()
+        }, indent=2))
+    else:
+        matched = 0
+        for hyp_id, (hyp, matches, calibration) in results.items():
+            if not matches:
+                continue
+            matched += 1
+            severity = hyp.get("severity", "medium")
+            style = _SEVERITY_STYLE.get(severity, "white")
+            console.print(f"[{style}][{severity.upper()}][/{style}] [bold]{hyp.get('title', hyp_id)}[/bold]")
+            for m in matches:
+                url = m.finding.url or m.report.url
+                console.print(f"  {m.score:.2f}  This pattern {m.summary}: {m.finding.title}")
+                console.print(f"        [dim]{'; '.join(m.reasons)}{f' — {url}' if url else ''}[/dim]")
+            note = " [yellow](differs)[/yellow]" if calibration.differs_from(severity) else ""
+            console.print(f"  [dim]Precedent severity:[/dim] {calibration.describe()}{note}\n")
+        console.print(f"[dim]{matched}/{len(results)} hypotheses have precedent in "
+                      f"{len(catalog.reports())} imported reports[/dim]")
+
+    if annotate:
+        if static:
+            source.write_text(json.dumps(list(hypotheses.values()), indent=2))
+        else:
+            data = json.loads(source.read_text())
+            data["hypotheses"] = hypotheses
+            source.write_text(json.dumps(data, indent=2))
+        if not as_json:
+            console.print(f"[green]Annotated {source}[/green]")
@@ -24,8 +24,13 @@ console = Console()
 class ProjectManager:
     """Manages Hound projects."""
     
-    def __init__(self):
-        self.projects_dir = Path.home() / ".hound" / "projects"
+    def __init__(self, projects_dir: Path | None = None):
+        """Initialize manager.
+
+        Args:
+            projects_dir: Directory holding the projects. Defaults to ~/.hound/projects
+        """
+        self.projects_dir = Path(projects_dir) if projects_dir else Path.home() / ".hound" / "projects"
         self.projects_dir.mkdir(parents=True, exist_ok=True)
         self.registry_file = self.projects_dir / "registry.json"
         self._ensure_registry()
@@ -700,7 +705,8 @@ def delete(name: str, force: bool):
 @project.command()
 @click.argument('name')
 @click.option('--details', '-d', is_flag=True, help='Show full descriptions without abbreviation')
-def hypotheses(name: str, details: bool = False):
+@click.option('--min-confidence', default=None, help='Only list hypotheses at or above: high, medium, low or 0-1')
+def hypotheses(name: str, details: bool = False, min_confidence: str | None = None):
     """List all hypotheses for a project with confidence ratings."""
     manager = ProjectManager()
     project = manager.get_project(name)
@@ -727,6 +733,14 @@ def hypotheses(name: str, details: bool = False):
         console.print("[yellow]No hypotheses recorded yet.[/yellow]")
         raise click.Exit(0)
     
+    from extensions.static.confidence import meets_confidence, parse_confidence
+    try:
+        floor = parse_confidence(min_confidence)
+    except ValueError as e:
+        console.print(f"[red]{e}[/red]")
+        raise click.Exit(1)
+    hypotheses = {hid: h for hid, h in hypotheses.items() if meets_confidence(h, floor)}
+    
     # Create table
     from rich.table import Table
     
@@ -768,6 +782,9 @@ def hypotheses(name: str, details: bool = False):
             conf_str = f"[yellow]{conf:.0%}[/yellow]"
         else:
             conf_str = f"[red]{conf:.0%}[/red]"
+        precision = (hyp.get("properties") or {}).get("precision")
+        if precision:
+            conf_str += f"\n[dim]{precision}[/dim]"
         
         # Format status with color
         status = hyp.get("status", "proposed")
@@ -781,6 +798,8 @@ def hypotheses(name: str, details: bool = False):
             status_str = "[cyan]+ supported[/cyan]"
         elif status == "refuted":
             status_str = "[magenta]- refuted[/magenta]"
+        elif status == "fixed":
+            status_str = "[blue]✓ fixed[/blue]"
         else:
             status_str = "[dim]○ proposed[/dim]"
         
@@ -860,10 +879,10 @@ def hypotheses(name: str, details: bool = False):
 @project.command(name='set-hypothesis-status')
 @click.argument('project_name')
 @click.argument('hypothesis_id')
-@click.argument('status', type=click.Choice(['proposed', 'confirmed', 'rejected'], case_sensitive=False))
+@click.argument('status', type=click.Choice(['proposed', 'confirmed', 'rejected', 'fixed'], case_sensitive=False))
 @click.option('--force', '-f', is_flag=True, help="Force status change without confirmation")
 def set_hypothesis_status(project_name: str, hypothesis_id: str, status: str, force: bool):
-    """Set the status of a hypothesis to proposed, confirmed, or rejected."""
+    """Set the status of a hypothesis to proposed, confirmed, rejected, or fixed."""
     manager = ProjectManager()
     project = manager.get_project(project_name)
     
@@ -915,8 +934,11 @@ def set_hypothesis_status(project_name: str, hypothesis_id: str, status: str, fo
             console.print("[dim]Status change cancelled.[/dim]")
             return
     
-    # Update status
+    # Update status, keeping when it changed for engagement summaries
     hypothesis["status"] = status.lower()
+    hypothesis.setdefault("status_history", []).append(
+        {"status": status.lower(), "at": datetime.now().isoformat()}
+    )
     
     # Save updated hypotheses
     with open(hypothesis_file, 'w') as f:
@@ -927,7 +949,7 @@ def set_hypothesis_status(project_name: str, hypothesis_id: str, status: str, fo
     console.print(f"[dim]Title: {hypothesis.get('title', 'Unknown')}[/dim]")
     
     # Show summary of status counts
-    status_counts = {'proposed': 0, 'confirmed': 0, 'rejected': 0}
+    status_counts = {'proposed': 0, 'confirmed': 0, 'rejected': 0, 'fixed': 0}
     for h in hypotheses.values():
         h_status = h.get('status', 'proposed')
         if h_status in status_counts:
@@ -936,6 +958,7 @@ def set_hypothesis_status(project_name: str, hypothesis_id: str, status: str, fo
     console.print("\n[bold]Status Summary:[/bold]")
     console.print(f"  [green]Confirmed: {status_counts['confirmed']}[/green]")
     console.print(f"  [red]Rejected: {status_counts['rejected']}[/red]")
+    console.print(f"  [cyan]Fixed: {status_counts['fixed']}[/cyan]")
     console.print(f"  [dim]Proposed: {status_counts['proposed']}[/dim]")
 
 
@@ -12,6 +12,9 @@ from rich.console import Console
 from rich.panel import Panel
 
 from analysis.report_generator import ReportGenerator
+from extensions.i18n import LOCALES, normalize_locale
+from extensions.knowledge.taxonomy import filter_by_tags, resolve_tags
+from extensions.static.confidence import meets_confidence, parse_confidence
 from commands.project import ProjectManager
 
 console = Console()
@@ -26,8 +29,12 @@ console = Console()
 @click.option('--debug', is_flag=True, help="Enable debug mode")
 @click.option('--show-prompt', is_flag=True, help="Show the LLM prompt and response used to generate the report")
 @click.option('--all', 'include_all', is_flag=True, help="Include ALL hypotheses (not just confirmed) - WARNING: No QA performed, may contain false positives")
+@click.option('--tag', 'tags', multiple=True, help="Only report findings with this taxonomy tag (repeatable)")
+@click.option('--lang', 'lang', default=None, help=f"Report language ({', '.join(LOCALES)}; default en)")
+@click.option('--min-confidence', default=None, help="Only report findings at or above: high, medium, low or 0-1")
 def report(project_name: str, output: str | None, format: str, 
-          title: str | None, auditors: str, debug: bool, show_prompt: bool, include_all: bool):
+          title: str | None, auditors: str, debug: bool, show_prompt: bool, include_all: bool,
+          tags: tuple[str, ...] = (), lang: str | None = None, min_confidence: str | None = None):
     """
     Generate a professional security audit report for a project.
     
@@ -46,6 +53,14 @@ def report(project_name: str, output: str | None, format: str,
     
     project_dir = Path(project["path"])
     
+    try:
+        tags = resolve_tags(tags)
+        lang = normalize_locale(lang)
+        confidence_floor = parse_confidence(min_confidence)
+    except ValueError as e:
+        console.print(f"[red]{e}[/red]")
+        raise click.Exit(1)
+    
     # Check for required data
     graphs_dir = project_dir / "graphs"
     if not graphs_dir.exists() or not list(graphs_dir.glob("*.json")):
@@ -101,7 +116,10 @@ def report(project_name: str, output: str | None, format: str,
         project_dir=project_dir,
         config=config,
         debug=debug,
-        include_all=include_all  # Pass the flag to include all hypotheses
+        include_all=include_all,  # Pass the flag to include all hypotheses
+        tags=tags,
+        locale=lang,
+        min_confidence=confidence_floor
     )
     
     # Resolve model names for narrative flavor
@@ -188,6 +206,46 @@ def report(project_name: str, output: str | None, format: str,
                 if generator.last_response:
                     console.print(Panel(generator.last_response, title="Raw Response"))
 
+        # Codebase metrics saved by `ir metrics <project>`
+        from extensions.reporting.metrics import embed_metrics, load_metrics
+        report_data = embed_metrics(report_data, load_metrics(project_dir),
+                                    'markdown' if format == 'markdown' else 'html')
+
+        # Auditor notes ingested from `@baskerville` source comments
+        from extensions.reporting.annotations import embed_annotations, load_annotations
+        report_data = embed_annotations(report_data, load_annotations(project_dir),
+                                        'markdown' if format == 'markdown' else 'html')
+
+        # Attack trees composed from the reported findings
+        from extensions.reporting.attack_trees import build_attack_trees, embed_attack_trees, load_findings
+        tree_findings = [dict(h, id=hid) for hid, h in load_findings(project_dir, not include_all).items()
+                         if meets_confidence(h, confidence_floor)]
+        report_data = embed_attack_trees(report_data, build_attack_trees(filter_by_tags(tree_findings, tags)),
+                                         'markdown' if format == 'markdown' else 'html')
+
+        # Economic-risk section from saved liquidation sweeps
+        from extensions.reporting.economic import embed_economic_risk, load_sweeps
+        report_data = embed_economic_risk(report_data, load_sweeps(project_dir),
+                                          'markdown' if format == 'markdown' else 'html')
+
+        # Protocol parameters from the last static run, for the client to confirm
+        from extensions.reporting.parameters import embed_parameters, load_parameters
+        report_data = embed_parameters(report_data, load_parameters(project_dir),
+                                       'markdown' if format == 'markdown' else 'html')
+
+        # Embed the reproducibility manifest
+        from extensions.reporting.manifest import build_manifest, embed_manifest, project_detectors
+        manifest = build_manifest(
+            "report",
+            {'project_name': project_name, 'output': output, 'format': format, 'title': title,
+             'auditors': auditors, 'debug': False, 'show_prompt': False, 'include_all': include_all,
+             'min_confidence': min_confidence},
+            source=project["source_path"],
+            config=config,
+            detectors=project_detectors(project_dir),
+        )
+        report_data = embed_manifest(report_data, manifest, 'markdown' if format == 'markdown' else 'html')
+
         # Write report
         console.print(f"[bright_cyan]Writing {format.upper()} report...[/bright_cyan]")
         
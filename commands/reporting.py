@@ -0,0 +1,566 @@
+"""
+Report data export commands.
+
+Usage:
+    ./baskerville.py reporting heatmap <project> [--output FILE] [--findings-only] [--min-confidence LEVEL]
+    ./baskerville.py reporting risk <project...> [--model FILE] [--output FILE] [--json] [--min-confidence LEVEL]
+    ./baskerville.py reporting engagement <project> [--since DATE] [--until DATE] [--output FILE] [--json]
+                                           [--chain CHAIN] [--no-checklist]
+    ./baskerville.py reporting annotations <project> [--source PATH] [--kind KIND] [--all] [--json]
+    ./baskerville.py reporting sarif <project> [--output FILE] [--min-confidence LEVEL]
+    ./baskerville.py reporting findings <project> [--format json|markdown|sarif] [--output FILE]
+                                         [--min-confidence LEVEL] [--all] [--no-static]
+                                         [--rubric FILE] [--reported-severity]
+    ./baskerville.py reporting parameters <project> [--status STATUS] [--json]
+    ./baskerville.py reporting attack-trees <project> [--format mermaid|markdown|json] [--output FILE]
+                                             [--min-confidence LEVEL] [--confirmed-only]
+    ./baskerville.py reporting keygen [--output-dir DIR]
+    ./baskerville.py reporting sign <files...> --key KEY [--source PATH_OR_PROJECT] [--output FILE]
+    ./baskerville.py reporting verify <envelope> --key PUBKEY [--root DIR]
+"""
+
+import json
+import sys
+from datetime import datetime
+from pathlib import Path
+
+import click
+from rich.console import Console
+from rich.table import Table
+
+sys.path.insert(0, str(Path(__file__).parent.parent))
+
+from commands.project import ProjectManager
+from extensions.static.confidence import meets_confidence, parse_confidence
+
+
+console = Console()
+
+
+def _load_project(project_name: str) -> tuple[dict, Path]:
+    project = ProjectManager().get_project(project_name)
+    if not project:
+        console.print(f"[red]Project '{project_name}' not found[/red]")
+        raise SystemExit(1)
+    return project, Path(project["path"])
+
+
+def _load_hypotheses(project_dir: Path) -> dict[str, dict]:
+    hyp_file = project_dir / "hypotheses.json"
+    if not hyp_file.exists():
+        return {}
+    with open(hyp_file) as f:
+        return json.load(f).get("hypotheses", {})
+
+
+def _load_static_hypotheses(project_dir: Path) -> list[dict]:
+    path = project_dir / "static_analysis" / "static_hypotheses.json"
+    try:
+        items = json.loads(path.read_text()) if path.exists() else []
+    except ValueError:
+        return []
+    return [h for h in items if isinstance(h, dict)]
+
+
+@click.group("reporting")
+def reporting():
+    """Report data exports."""
+    pass
+
+
+@reporting.command("heatmap")
+@click.argument("project_name")
+@click.option("--output", "-o", default=None, help="Output file (default: <project>/reports/heatmap.json)")
+@click.option("--findings-only", is_flag=True, help="Only include files that have findings")
+@click.option("--top", default=10, help="Number of hottest files to print")
+@click.option("--min-confidence", default=None, help="Only count findings at or above: high, medium, low or 0-1")
+def heatmap(project_name: str, output: str | None, findings_only: bool, top: int, min_confidence: str | None = None):
+    """Export per-file/per-module risk scores for treemap visualization."""
+    from extensions.reporting import build_heatmap
+    from extensions.reporting.manifest import build_manifest, project_detectors
+    from utils.config_loader import load_config
+
+    try:
+        floor = parse_confidence(min_confidence)
+    except ValueError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    project, project_dir = _load_project(project_name)
+    hypotheses = {hid: h for hid, h in _load_hypotheses(project_dir).items() if meets_confidence(h, floor)}
+    if not hypotheses:
+        console.print("[yellow]No hypotheses found. Run an audit first.[/yellow]")
+        raise SystemExit(1)
+
+    source = Path(project["source_path"]) if project.get("source_path") else None
+    data = build_heatmap(hypotheses, source if source and source.exists() else None,
+                         include_unreferenced=not findings_only)
+    data["manifest"] = build_manifest(
+        "reporting heatmap",
+        {"project_name": project_name, "output": output, "findings_only": findings_only, "top": top,
+         "min_confidence": min_confidence},
+        source=source, config=load_config(), detectors=project_detectors(project_dir),
+    )
+
+    out_path = Path(output) if output else project_dir / "reports" / "heatmap.json"
+    out_path.parent.mkdir(parents=True, exist_ok=True)
+    out_path.write_text(json.dumps(data, indent=2))
+
+    table = Table(title="Hottest files", show_header=True, header_style="bold")
+    table.add_column("File")
+    table.add_column("Findings", justify="right")
+    table.add_column("Score", justify="right")
+    table.add_column("Per KLOC", justify="right")
+    table.add_column("Heat", justify="right")
+    for entry in [e for e in data["files"] if e["findings"]][:top]:
+        density = "-" if entry["density"] is None else f"{entry['density']:.1f}"
+        table.add_row(entry["path"], str(entry["findings"]), f"{entry['score']:.2f}", density, f"{entry['heat']:.2f}")
+    console.print(table)
+    if data["unlocated_findings"]:
+        console.print(f"[dim]{data['unlocated_findings']} findings had no file reference and were skipped[/dim]")
+    console.print(f"[green]Heat-map written to {out_path}[/green]")
+
+
+@reporting.command("risk")
+@click.argument("project_names", nargs=-1, required=True)
+@click.option("--model", "model_path", default=None, help="Risk model YAML (weights, severity_weights, scales, grades)")
+@click.option("--output", "-o", default=None, help="Output file (default: <project>/reports/risk.json for one project)")
+@click.option("--json", "as_json", is_flag=True, help="Print the ranking as JSON")
+@click.option("--min-confidence", default=None, help="Only score findings at or above: high, medium, low or 0-1")
+def risk(project_names: tuple[str, ...], model_path: str | None, output: str | None, as_json: bool,
+         min_confidence: str | None = None):
+    """Score and rank programs by aggregate risk (findings, centralization, dependencies, attack surface)."""
+    from extensions.ir import load_programs
+    from extensions.reporting.manifest import build_manifest, project_detectors
+    from extensions.reporting.risk import build_risk_report, load_risk_model, score_project
+    from utils.config_loader import load_config
+
+    try:
+        model = load_risk_model(Path(model_path) if model_path else None)
+        floor = parse_confidence(min_confidence)
+    except ValueError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+
+    scored = []
+    detectors: dict[str, str | None] = {}
+    sources = []
+    project_dir = None
+    for name in project_names:
+        project, project_dir = _load_project(name)
+        source = Path(project["source_path"]) if project.get("source_path") else None
+        if source is None or not source.exists():
+            console.print(f"[yellow]Source of project '{name}' not found, skipping[/yellow]")
+            continue
+        programs = load_programs(source)
+        if not programs:
+            console.print(f"[yellow]No programs found in {source}, skipping '{name}'[/yellow]")
+            continue
+        findings = list(_load_hypotheses(project_dir).values())
+        titles = {f.get("title") for f in findings}
+        findings += [h for h in _load_static_hypotheses(project_dir) if h.get("title") not in titles]
+        findings = [f for f in findings if meets_confidence(f, floor)]
+        scored.append(score_project(programs, findings, source, model, project=name))
+        sources.append(source)
+        detectors.update(project_detectors(project_dir))
+    if not scored:
+        console.print("[red]No programs to score[/red]")
+        raise SystemExit(1)
+
+    data = build_risk_report(scored, model)
+    data["manifest"] = build_manifest(
+        "reporting risk",
+        {"project_names": list(project_names), "model": model_path, "output": output,
+         "min_confidence": min_confidence},
+        source=sources[0] if len(sources) == 1 else None, config=load_config(), detectors=detectors,
+    )
+    if output:
+        out_path: Path | None = Path(output)
+    elif len(project_names) == 1 and project_dir is not None:
+        out_path = project_dir / "reports" / "risk.json"
+    else:
+        out_path = None
+    if out_path is not None:
+        out_path.parent.mkdir(parents=True, exist_ok=True)
+        out_path.write_text(json.dumps(data, indent=2))
+
+    if as_json:
+        click.echo(json.dumps(data["programs"], indent=2))
+        return
+    table = Table(title="Program risk", show_header=True, header_style="bold")
+    table.add_column("#", justify="right")
+    table.add_column("Program")
+    table.add_column("Score", justify="right")
+    table.add_column("Grade")
+    for component in ("Findings", "Centralization", "Dependencies", "Attack surface"):
+        table.add_column(component, justify="right")
+    for i, entry in enumerate(data["programs"], 1):
+        parts = entry["components"]
+        label = entry["program"] if len(scored) == 1 else f"{entry['project']}/{entry['program']}"
+        table.add_row(str(i), label, f"{entry['score']:.1f}", entry["grade"],
+                      *(f"{parts[c]['score']:.0f}" for c in ("findings", "centralization", "dependencies",
+                                                             "attack_surface")))
+    console.print(table)
+    unattributed = sum(p["unattributed_findings"] for p in scored)
+    if unattributed:
+        console.print(f"[dim]{unattributed} findings matched no program and were not scored[/dim]")
+    if out_path is not None:
+        console.print(f"[green]Risk scores written to {out_path}[/green]")
+
+
+def _parse_day(value: str | None, end: bool = False) -> datetime | None:
+    """YYYY-MM-DD or an ISO timestamp; a bare date ending the window includes that whole day."""
+    if not value:
+        return None
+    from extensions.reporting.engagement import parse_time
+
+    moment = parse_time(value)
+    if moment is None:
+        console.print(f"[red]Invalid date '{value}' (use YYYY-MM-DD)[/red]")
+        raise SystemExit(1)
+    if end and len(value) == 10:
+        moment = moment.replace(hour=23, minute=59, second=59, microsecond=999999)
+    return moment
+
+
+@reporting.command("engagement")
+@click.argument("project_name")
+@click.option("--since", default=None, help="First day of the engagement window (YYYY-MM-DD)")
+@click.option("--until", default=None, help="Last day of the engagement window (YYYY-MM-DD, default: now)")
+@click.option("--output", "-o", default=None, help="Output file (default: <project>/reports/engagement.md)")
+@click.option("--json", "as_json", is_flag=True, help="Write the summary as JSON instead of Markdown")
+@click.option("--chain", default=None, help="Checklist chain (default: the project's chain)")
+@click.option("--no-checklist", is_flag=True, help="Skip checklist coverage")
+def engagement(project_name: str, since: str | None, until: str | None, output: str | None, as_json: bool,
+               chain: str | None, no_checklist: bool):
+    """Summarize an engagement window for the report's methodology section."""
+    from extensions.reporting.engagement import build_engagement_summary, render_methodology
+    from extensions.reporting.manifest import build_manifest, embed_manifest, project_detectors
+    from utils.config_loader import load_config
+
+    start, end = _parse_day(since), _parse_day(until, end=True)
+    if start and end and start > end:
+        console.print("[red]--since is after --until[/red]")
+        raise SystemExit(1)
+    project, project_dir = _load_project(project_name)
+    checklist = None
+    if not no_checklist:
+        from extensions.knowledge.checklist_loader import ChecklistLoader
+
+        checklist = ChecklistLoader().get_by_chain(chain or project.get("chain_id") or "evm")
+    summary = build_engagement_summary(project_dir, start, end, checklist=checklist, project=project_name)
+    source = Path(project["source_path"]) if project.get("source_path") else None
+    manifest = build_manifest(
+        "reporting engagement",
+        {"project_name": project_name, "since": since, "until": until, "output": output, "as_json": as_json,
+         "chain": chain, "no_checklist": no_checklist},
+        source=source, config=load_config(), detectors=project_detectors(project_dir),
+    )
+
+    suffix = "json" if as_json else "md"
+    out_path = Path(output) if output else project_dir / "reports" / f"engagement.{suffix}"
+    out_path.parent.mkdir(parents=True, exist_ok=True)
+    if as_json:
+        out_path.write_text(json.dumps({**summary, "manifest": manifest}, indent=2))
+    else:
+        out_path.write_text(embed_manifest(render_methodology(summary), manifest, "markdown"))
+
+    findings, pocs = summary["findings"], summary["pocs"]
+    console.print(f"  Scans:     {summary['scans']['agent_sessions']} agent session(s), "
+                  f"{summary['scans']['static_runs']} static run(s)")
+    console.print(f"  Findings:  {findings['opened']} opened, {findings['confirmed']} confirmed, "
+                  f"{findings['fixed']} fixed")
+    console.print(f"  PoCs:      {pocs['verified']} of {pocs['total']} verified")
+    if summary["checklist"] is not None:
+        console.print(f"  Checklist: {summary['checklist']['covered']} of {summary['checklist']['total']} "
+                      f"items ({summary['checklist']['percent']}%)")
+    console.print(f"[green]Engagement summary written to {out_path}[/green]")
+
+
+@reporting.command("annotations")
+@click.argument("project_name")
+@click.option("--source", default=None, help="Source tree to scan (default: the project's source)")
+@click.option("--kind", default=None, help="Only list annotations of this kind (note, reviewed, todo, ...)")
+@click.option("--all", "include_removed", is_flag=True, help="Also list annotations since removed from the source")
+@click.option("--json", "as_json", is_flag=True, help="Print annotations as JSON")
+def annotations(project_name: str, source: str | None, kind: str | None, include_removed: bool, as_json: bool):
+    """Ingest `@baskerville` notes from source comments and list them with the findings they touch."""
+    from extensions.reporting.annotations import ingest_annotations
+
+    project, project_dir = _load_project(project_name)
+    root = Path(source or project.get("source_path") or "")
+    if not root.exists():
+        console.print(f"[red]Source not found: {root}[/red]")
+        raise SystemExit(1)
+    result = ingest_annotations(project_dir, root)
+    shown = [a for a in result["annotations"]
+             if (include_removed or not a.get("removed_at")) and (kind is None or a["kind"] == kind.lower())]
+    if as_json:
+        click.echo(json.dumps(shown, indent=2))
+        return
+
+    if shown:
+        table = Table(show_header=True, header_style="bold")
+        for column in ("Location", "Kind", "Note", "Author", "Findings"):
+            table.add_column(column)
+        for a in sorted(shown, key=lambda a: (a["file"], a["line"])):
+            note = f"[strike]{a['text']}[/strike] [dim](removed)[/dim]" if a.get("removed_at") else a["text"]
+            table.add_row(f"{a['file']}:{a.get('target_line') or a['line']}", a["kind"], note,
+                          a.get("author") or "-", ", ".join(a.get("findings") or []) or "-")
+        console.print(table)
+    else:
+        console.print("[dim]No annotations[/dim]")
+    console.print(f"[green]{result['added']} new, {result['updated']} moved, {result['removed']} removed; "
+                  f"saved to {project_dir / 'annotations.json'}[/green]")
+
+
+@reporting.command("sarif")
+@click.argument("project_name")
+@click.option("--output", "-o", default=None, help="Output file (default: <project>/reports/static.sarif)")
+@click.option("--min-confidence", default=None, help="Only export hits at or above: high, medium, low or 0-1")
+def sarif(project_name: str, output: str | None, min_confidence: str | None):
+    """Export the last static run's hits as SARIF 2.1.0 for code-scanning dashboards."""
+    from extensions.knowledge.class_loader import ClassLoader
+    from extensions.reporting.sarif import build_sarif, write_sarif
+
+    try:
+        floor = parse_confidence(min_confidence)
+    except ValueError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    project, project_dir = _load_project(project_name)
+    static_dir = project_dir / "static_analysis"
+    if not (static_dir / "static_hypotheses.json").exists():
+        console.print(f"[red]No static results; run `static {project_name}` first[/red]")
+        raise SystemExit(1)
+    hits = [h for h in _load_static_hypotheses(project_dir) if meets_confidence(h, floor)]
+    try:
+        metadata = json.loads((static_dir / "static_metadata.json").read_text())
+    except (OSError, ValueError):
+        metadata = {}
+    source = Path(project["source_path"]) if project.get("source_path") else None
+    chain = metadata.get("chain_id") or project.get("chain_id")
+    log = build_sarif(hits, source, chain, metadata.get("tools"), ClassLoader())
+
+    out_path = write_sarif(log, Path(output) if output else project_dir / "reports" / "static.sarif")
+    console.print(f"[green]{len(hits)} hits across {len(log['runs'][0]['tool']['driver']['rules'])} rules "
+                  f"written to {out_path}[/green]")
+
+
+@reporting.command("findings")
+@click.argument("project_name")
+@click.option("--format", "fmt", type=click.Choice(["json", "markdown", "sarif"]), default=None,
+              help="Report format (default: [output] report_format of the source's baskerville.toml, else markdown)")
+@click.option("--output", "-o", default=None, help="Output file (default: <project>/reports/findings.<suffix>)")
+@click.option("--min-confidence", default=None, help="Only report findings at or above: high, medium, low or 0-1")
+@click.option("--all", "include_rejected", is_flag=True, help="Also report findings rejected during triage")
+@click.option("--no-static", is_flag=True, help="Leave out the last static run's detector hits")
+@click.option("--rubric", "rubric_path", default=None, help="Severity rubric YAML (metrics, defaults, classes, bands)")
+@click.option("--reported-severity", is_flag=True, help="Keep the severities findings declare instead of scoring them")
+def findings(project_name: str, fmt: str | None, output: str | None, min_confidence: str | None,
+             include_rejected: bool, no_static: bool, rubric_path: str | None = None,
+             reported_severity: bool = False):
+    """Write the project's findings with PoC snippets and remediation as JSON, Markdown or SARIF."""
+    from extensions.knowledge.class_loader import ClassLoader
+    from extensions.knowledge.incident_loader import IncidentLoader
+    from extensions.knowledge.template_loader import TemplateLoader, user_template_dirs
+    from extensions.reporting.formatters import build_findings_report, get_formatter
+    from extensions.reporting.manifest import build_manifest, project_detectors
+    from extensions.reporting.scoring import load_severity_rubric
+    from extensions.static.project_config import ConfigError, ProjectConfig
+    from utils.config_loader import load_config
+
+    project, project_dir = _load_project(project_name)
+    source = Path(project["source_path"]) if project.get("source_path") else None
+    try:
+        # The source's baskerville.toml, with the options given overriding it
+        settings = ProjectConfig.load(source) if source and source.exists() else ProjectConfig()
+        floor = parse_confidence(settings.default("min_confidence", min_confidence))
+        rubric = None if reported_severity else load_severity_rubric(Path(rubric_path) if rubric_path else None)
+    except (ConfigError, ValueError) as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    fmt = settings.default("report_format", fmt) or "markdown"
+    items = dict(_load_hypotheses(project_dir))
+    if not no_static:
+        for hit in _load_static_hypotheses(project_dir):
+            items.setdefault(hit.get("id") or f"static_{len(items)}", hit)
+    items = {
+        hid: h for hid, h in items.items()
+        if meets_confidence(h, floor) and (include_rejected or h.get("status") != "rejected")
+    }
+
+    manifest = build_manifest(
+        "reporting findings",
+        {"project_name": project_name, "format": fmt, "output": output, "min_confidence": min_confidence,
+         "all": include_rejected, "no_static": no_static, "rubric": rubric_path,
+         "reported_severity": reported_severity},
+        source=source, config=load_config(), detectors=project_detectors(project_dir),
+    )
+    # Findings match the user's own templates too (knowledge.template_dirs, BASKERVILLE_TEMPLATE_DIRS, [templates])
+    templates = TemplateLoader(user_dirs=user_template_dirs(config=load_config(), project_dirs=settings.template_dirs))
+    report = build_findings_report(items, project_name, project.get("chain_id"), source, templates,
+                                   ClassLoader(), manifest, IncidentLoader(), rubric)
+    formatter = get_formatter(fmt)
+    out_path = Path(output) if output else project_dir / "reports" / f"findings{formatter.suffix}"
+    out_path.parent.mkdir(parents=True, exist_ok=True)
+    out_path.write_text(formatter.format(report))
+
+    counts = ", ".join(f"{n} {severity}" for severity, n in report["counts"].items() if n) or "none"
+    console.print(f"[green]{len(report['findings'])} findings ({counts}) written to {out_path}[/green]")
+
+
+@reporting.command("parameters")
+@click.argument("project_name")
+@click.option("--status", type=click.Choice(["ok", "out-of-range", "unclear"]), default=None,
+              help="Only list parameters with this status")
+@click.option("--json", "as_json", is_flag=True, help="Print parameters as JSON")
+def parameters(project_name: str, status: str | None, as_json: bool):
+    """List the protocol parameters the last static run extracted, against their sane ranges."""
+    from extensions.reporting.parameters import PARAMETER_COLUMNS, load_parameters, parameter_rows
+
+    _, project_dir = _load_project(project_name)
+    if not (project_dir / "static_analysis" / "static_metadata.json").exists():
+        console.print(f"[red]No static results; run `static {project_name}` first[/red]")
+        raise SystemExit(1)
+    found = [p for p in load_parameters(project_dir) if status is None or p.get("status") == status]
+    if as_json:
+        click.echo(json.dumps(found, indent=2))
+        return
+    if not found:
+        console.print("[yellow]No protocol parameters found[/yellow]")
+        return
+
+    table = Table(title="Protocol parameters", show_header=True, header_style="bold")
+    for column in PARAMETER_COLUMNS:
+        table.add_column(column)
+    styles = {"OUT OF RANGE": "red", "unit unclear": "yellow"}
+    for row in parameter_rows(found):
+        table.add_row(*row, style=styles.get(row[-1]))
+    console.print(table)
+
+
+@reporting.command("attack-trees")
+@click.argument("project_name")
+@click.option("--format", "fmt", type=click.Choice(["mermaid", "markdown", "json"]), default="mermaid",
+              help="Output format")
+@click.option("--output", "-o", default=None, help="Output file (default: <project>/reports/attack_trees.<ext>)")
+@click.option("--min-confidence", default=None, help="Only use findings at or above: high, medium, low or 0-1")
+@click.option("--confirmed-only", is_flag=True, help="Only use confirmed findings (default: all but rejected, "
+              "plus static hits)")
+def attack_trees(project_name: str, fmt: str, output: str | None, min_confidence: str | None,
+                 confirmed_only: bool):
+    """Compose the project's findings into attack trees per attacker goal."""
+    from extensions.reporting.attack_trees import build_attack_trees, load_findings, render_attack_trees
+
+    try:
+        floor = parse_confidence(min_confidence)
+    except ValueError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    _, project_dir = _load_project(project_name)
+    items = {hid: h for hid, h in load_findings(project_dir, confirmed_only).items() if meets_confidence(h, floor)}
+    if not items:
+        console.print("[yellow]No findings found. Run an audit or `static` first.[/yellow]")
+        raise SystemExit(1)
+    data = build_attack_trees(items)
+
+    suffix = {"mermaid": ".mmd", "markdown": ".md", "json": ".json"}[fmt]
+    out_path = Path(output) if output else project_dir / "reports" / f"attack_trees{suffix}"
+    out_path.parent.mkdir(parents=True, exist_ok=True)
+    out_path.write_text(render_attack_trees(data, fmt))
+
+    table = Table(title="Attack trees", show_header=True, header_style="bold")
+    table.add_column("Goal")
+    table.add_column("Severity")
+    table.add_column("Paths", justify="right")
+    table.add_column("Chains", justify="right")
+    for tree in data["trees"]:
+        table.add_row(tree["title"], tree["severity"], str(len(tree["branches"])), str(tree["chains"]))
+    if data["trees"]:
+        console.print(table)
+    else:
+        console.print("[yellow]No finding reaches an attacker goal[/yellow]")
+    if data["unplaced"]:
+        console.print(f"[dim]{len(data['unplaced'])} findings reach no goal on their own[/dim]")
+    console.print(f"[green]Attack trees written to {out_path}[/green]")
+
+
+@reporting.command("keygen")
+@click.option("--output-dir", "-o", default=".", help="Directory for signing-key.pem / signing-key.pub.pem")
+def keygen(output_dir: str):
+    """Generate an ed25519 key pair for signing reports."""
+    from extensions.reporting.signing import SigningError, generate_keypair
+
+    out = Path(output_dir)
+    private_path, public_path = out / "signing-key.pem", out / "signing-key.pub.pem"
+    if private_path.exists():
+        console.print(f"[red]{private_path} already exists[/red]")
+        raise SystemExit(1)
+    try:
+        kid = generate_keypair(private_path, public_path)
+    except SigningError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    console.print(f"[green]Key {kid} written[/green]")
+    console.print(f"  private: {private_path} (keep secret)")
+    console.print(f"  public:  {public_path} (give to clients)")
+
+
+@reporting.command("sign")
+@click.argument("files", nargs=-1, required=True)
+@click.option("--key", "-k", "key_path", required=True, help="ed25519 private key (PEM)")
+@click.option("--source", "-s", default=None, help="Audited source path or project name, for the input commit hash")
+@click.option("--output", "-o", default=None, help="Envelope file (default: <file>.sig.json or SIGNATURES.sig.json)")
+def sign(files: tuple[str, ...], key_path: str, source: str | None, output: str | None):
+    """Write a detached signature over report and finding files."""
+    from extensions.reporting.signing import SigningError, read_statement, sign_files
+
+    source_path = None
+    if source:
+        source_path = Path(source).expanduser()
+        if not source_path.exists():
+            project, _ = _load_project(source)
+            source_path = Path(project["source_path"])
+    try:
+        envelope = sign_files([Path(f) for f in files], Path(key_path),
+                              output=Path(output) if output else None, source=source_path)
+    except SigningError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+
+    statement = read_statement(envelope)
+    predicate = statement["predicate"]
+    console.print(f"[green]Signed {len(statement['subject'])} file(s) -> {envelope}[/green]")
+    console.print(f"  rule set: {predicate['ruleset_version']}")
+    if predicate["source"]:
+        dirty = " (dirty)" if predicate["source"]["dirty"] else ""
+        console.print(f"  source:   {predicate['source']['commit']}{dirty}")
+
+
+@reporting.command("verify")
+@click.argument("envelope")
+@click.option("--key", "-k", "key_path", required=True, help="Signer's ed25519 public key (PEM)")
+@click.option("--root", default=None, help="Directory the signed files are relative to (default: envelope's directory)")
+def verify(envelope: str, key_path: str, root: str | None):
+    """Verify a detached signature and the files it covers."""
+    from extensions.reporting.signing import SigningError, verify_envelope
+
+    try:
+        result = verify_envelope(Path(envelope), Path(key_path), Path(root) if root else None)
+    except SigningError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+
+    for name in result.verified_files:
+        console.print(f"  [green]OK[/green]       {name}")
+    for error in result.errors:
+        console.print(f"  [red]FAILED[/red]   {error}")
+    if not result.valid:
+        console.print("[red]Verification failed[/red]")
+        raise SystemExit(1)
+    predicate = result.statement["predicate"]
+    console.print(f"[green]Verified {len(result.verified_files)} file(s)[/green]")
+    console.print(f"  signed:   {predicate.get('created_at')}")
+    console.print(f"  rule set: {predicate.get('ruleset_version')}")
+    if predicate.get("source"):
+        console.print(f"  source:   {predicate['source'].get('commit')}")
@@ -0,0 +1,132 @@
+"""
+Re-run the configuration recorded in an output's reproducibility manifest.
+
+Usage:
+    ./baskerville.py reproduce <report> [--output FILE] [--check] [--strict]
+"""
+
+import importlib
+import os
+import sys
+import tempfile
+from pathlib import Path
+
+import click
+import yaml
+from rich.console import Console
+from rich.table import Table
+
+sys.path.insert(0, str(Path(__file__).parent.parent))
+
+from extensions.reporting.manifest import (
+    build_manifest,
+    compare_manifests,
+    extract_manifest,
+    restore_secrets,
+)
+
+
+console = Console()
+
+# Manifest command name → (module, click command). Commands that change
+# stored state (e.g. bounty export) are deliberately not re-runnable.
+REPRODUCIBLE_COMMANDS = {
+    "report": ("commands.report", "report"),
+    "static": ("commands.static", "static"),
+    "reporting heatmap": ("commands.reporting", "heatmap"),
+    "ir inventory": ("commands.ir", "inventory"),
+    "sim run": ("commands.simulate", "run"),
+    "sim math": ("commands.simulate", "math_stress"),
+    "sim liquidations": ("commands.simulate", "liquidations"),
+}
+
+
+def current_detectors(recorded: dict[str, str | None]) -> dict[str, str | None]:
+    """Versions of the recorded static detectors as installed now."""
+    from extensions.static import StaticAnalysisPipeline
+
+    versions: dict[str, str | None] = {}
+    for chain in ("evm", "solana", "sui"):
+        for name, runner in StaticAnalysisPipeline(chain_id=chain).runners.items():
+            if name in recorded and name not in versions:
+                available, version = runner.is_available()
+                versions[name] = version if available else None
+    # Non-static detectors (IR front-ends) carry no version; keep as recorded
+    for name, version in recorded.items():
+        versions.setdefault(name, version)
+    return versions
+
+
+@click.command("reproduce")
+@click.argument("report", type=click.Path(exists=True, dir_okay=False))
+@click.option("--output", "-o", default=None, help="Where to write the reproduced output (default: the command's default)")
+@click.option("--check", is_flag=True, help="Only compare the recorded environment with the current one")
+@click.option("--strict", is_flag=True, help="Refuse to re-run if versions, rule set or target commit differ")
+def reproduce(report: str, output: str | None, check: bool, strict: bool):
+    """Re-run the exact configuration recorded in REPORT's manifest."""
+    from utils.config_loader import load_config
+
+    manifest = extract_manifest(Path(report))
+    if not manifest:
+        console.print(f"[red]No reproducibility manifest found in {report}[/red]")
+        raise SystemExit(1)
+
+    command = manifest.get("command", {})
+    name, params = command.get("name"), dict(command.get("params") or {})
+    target = manifest.get("target") or {}
+    config = restore_secrets(manifest.get("config") or {}, load_config())
+    current = build_manifest(
+        name, params, source=target.get("path"), config=config,
+        detectors=current_detectors(manifest.get("detectors") or {}),
+    )
+
+    console.print(f"[bold]Manifest:[/bold] {name} ({manifest.get('config_hash', '')[:19]})")
+    diffs = compare_manifests(manifest, current)
+    if diffs:
+        table = Table(title="Environment drift", show_header=True, header_style="bold")
+        table.add_column("Field")
+        table.add_column("Recorded")
+        table.add_column("Current")
+        for field_name, old, new in diffs:
+            table.add_row(field_name, str(old), str(new))
+        console.print(table)
+    else:
+        console.print("[green]Environment matches the manifest[/green]")
+    if target.get("dirty"):
+        console.print("[yellow]The recorded target had uncommitted changes; results may differ[/yellow]")
+
+    if check:
+        raise SystemExit(1 if diffs else 0)
+    if diffs and strict:
+        console.print("[red]Refusing to re-run with --strict[/red]")
+        raise SystemExit(1)
+    if name not in REPRODUCIBLE_COMMANDS:
+        console.print(f"[red]'{name}' outputs cannot be re-run[/red]")
+        raise SystemExit(1)
+
+    module_name, attr = REPRODUCIBLE_COMMANDS[name]
+    cmd = getattr(importlib.import_module(module_name), attr)
+    if "output" in params:
+        params["output"] = output
+
+    console.print(f"[bold]Re-running {name}...[/bold]")
+    ctx = click.Context(cmd)
+    ctx.params = params
+    if not config:
+        cmd.invoke(ctx)
+        return
+
+    # Run under the recorded configuration
+    with tempfile.NamedTemporaryFile("w", suffix=".yaml", delete=False) as f:
+        yaml.safe_dump(config, f)
+        config_file = f.name
+    previous = os.environ.get("HOUND_CONFIG")
+    os.environ["HOUND_CONFIG"] = config_file
+    try:
+        cmd.invoke(ctx)
+    finally:
+        if previous is None:
+            os.environ.pop("HOUND_CONFIG", None)
+        else:
+            os.environ["HOUND_CONFIG"] = previous
+        os.unlink(config_file)
@@ -0,0 +1,82 @@
+"""
+RPC provider commands.
+
+Usage:
+    ./baskerville.py rpc call <method> [params_json] [--provider SPEC] [--no-cache]
+    ./baskerville.py rpc provider [SPEC]
+    ./baskerville.py rpc cache [--clear]
+"""
+
+import json
+import sys
+from pathlib import Path
+
+import click
+from rich.console import Console
+
+sys.path.insert(0, str(Path(__file__).parent.parent))
+
+from extensions.rpc import RpcCache, RpcClient, RpcError, default_cache_dir, resolve_provider
+
+
+console = Console()
+
+
+@click.group("rpc")
+def rpc():
+    """Solana RPC providers and response cache."""
+    pass
+
+
+@rpc.command("call")
+@click.argument("method")
+@click.argument("params", required=False)
+@click.option("--provider", "provider_spec", default=None,
+              help="URL, cluster or provider (helius, triton, devnet:helius); default $SOLANA_RPC_URL or mainnet")
+@click.option("--no-cache", is_flag=True, help="Bypass the response cache")
+def call(method: str, params: str | None, provider_spec: str | None, no_cache: bool):
+    """Make one JSON-RPC call through the shared client and print the result."""
+    try:
+        args = json.loads(params) if params else []
+    except ValueError as e:
+        console.print(f"[red]PARAMS is not valid JSON: {e}[/red]")
+        raise SystemExit(1)
+    if not isinstance(args, list):
+        args = [args]
+    try:
+        client = RpcClient(provider_spec, cache=RpcCache(default_cache_dir()))
+        result = client.call(method, args, cache=not no_cache)
+    except (ValueError, RpcError) as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    click.echo(json.dumps(result, indent=2))
+
+
+@rpc.command("provider")
+@click.argument("spec", required=False)
+def provider(spec: str | None):
+    """Show how a provider spec resolves (endpoint with credentials masked, limits)."""
+    try:
+        resolved = resolve_provider(spec)
+    except ValueError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    click.echo(json.dumps(resolved.to_dict(), indent=2))
+
+
+@rpc.command("cache")
+@click.option("--clear", is_flag=True, help="Delete all cached responses")
+def cache(clear: bool):
+    """Show or clear the on-disk response cache."""
+    store = RpcCache(default_cache_dir())
+    if clear:
+        store.clear()
+        console.print("[green]RPC cache cleared[/green]")
+        return
+    store.cleanup_expired()
+    stats = store.stats()
+    console.print(f"{stats['db_path']}")
+    for method, count in sorted(stats["stored"].items()):
+        console.print(f"  {method}: {count}")
+    if not stats["stored"]:
+        console.print("  [dim](empty)[/dim]")
@@ -0,0 +1,172 @@
+"""
+Rule ids, suppressions and baselines.
+
+Usage:
+    ./baskerville.py rules list [--json]
+    ./baskerville.py rules suppress <project> <rule|code> --reason TEXT [--path GLOB] [--by NAME]
+    ./baskerville.py rules baseline <project>
+    ./baskerville.py rules show <project> [--json]
+    ./baskerville.py rules migrate <project> [--dry-run]
+"""
+
+import json
+import sys
+from dataclasses import asdict
+from pathlib import Path
+
+import click
+from rich.console import Console
+from rich.table import Table
+
+sys.path.insert(0, str(Path(__file__).parent.parent))
+
+from commands.project import ProjectManager
+from extensions.static.rules import RuleCatalogError, load_catalog
+from extensions.static.suppressions import SuppressionError, SuppressionStore
+
+
+console = Console()
+
+
+def _store(project_name: str, migrate: bool = True, write: bool = True) -> SuppressionStore:
+    project = ProjectManager().get_project(project_name)
+    if not project:
+        console.print(f"[red]Project '{project_name}' not found[/red]")
+        raise SystemExit(1)
+    try:
+        store = SuppressionStore.for_project(Path(project["path"]))
+        report = store.load(migrate=migrate, write=write)
+    except (RuleCatalogError, SuppressionError) as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    if report:
+        _print_report(report)
+    return store
+
+
+def _print_report(report) -> None:
+    first, *rest = report.lines()
+    console.print(f"[yellow]{first}[/yellow]")
+    for line in rest:
+        console.print(f"[dim]{line}[/dim]")
+
+
+@click.group("rules")
+def rules():
+    """Rule ids, deprecations, suppressions and baselines."""
+    pass
+
+
+@rules.command("list")
+@click.option("--json", "as_json", is_flag=True, help="Print the catalog as JSON")
+def list_rules(as_json: bool):
+    """Show the rule set version, every rename, split, merge and retirement, and the rule codes."""
+    try:
+        catalog = load_catalog()
+    except RuleCatalogError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    if as_json:
+        click.echo(json.dumps(catalog.to_dict(), indent=2))
+        return
+    console.print(f"[bold]Rule set v{catalog.version}[/bold]")
+    table = Table(show_header=True, header_style="bold")
+    for column in ("Version", "Change", "From", "To", "Reason"):
+        table.add_column(column)
+    for m in catalog.migrations:
+        table.add_row(str(m.version), m.kind, "\n".join(m.old), "\n".join(m.new), m.reason)
+    for d in catalog.deprecated:
+        table.add_row(str(d.version), "retired", d.rule, "\n".join(d.replaced_by) or "-", d.reason)
+    if catalog.migrations or catalog.deprecated:
+        console.print(table)
+    else:
+        console.print("[dim]No rule has been renamed, split, merged or retired[/dim]")
+    if catalog.codes:
+        codes = Table(title="Rule codes", show_header=True, header_style="bold")
+        codes.add_column("Code")
+        codes.add_column("Rule")
+        for code, rule in catalog.codes.items():
+            codes.add_row(code, rule)
+        console.print(codes)
+
+
+@rules.command("suppress")
+@click.argument("project_name")
+@click.argument("rule")
+@click.option("--reason", required=True, help="Why these hits are not findings")
+@click.option("--path", default=None, help="Only suppress hits in files matching this glob")
+@click.option("--by", "created_by", default=None, help="Who decided")
+def suppress(project_name: str, rule: str, reason: str, path: str | None, created_by: str | None):
+    """Hide a rule's hits in later static runs (RULE is a rule id or a stable code)."""
+    store = _store(project_name)
+    if store.catalog.rule_for_code(rule):
+        rule = rule.upper()
+    deprecation = store.catalog.deprecation(rule)
+    if deprecation and deprecation.replaced_by:
+        console.print(f"[yellow]{rule} is deprecated since rule set v{deprecation.version}; "
+                      f"suppressing {', '.join(deprecation.replaced_by)} instead[/yellow]")
+    elif deprecation:
+        console.print(f"[yellow]{rule} was retired in rule set v{deprecation.version} and no longer fires[/yellow]")
+    store.suppress(rule, path, reason, created_by)
+    store.save()
+    console.print(f"[green]Suppressed {rule}{' under ' + path if path else ''}[/green]")
+
+
+@rules.command("baseline")
+@click.argument("project_name")
+def baseline(project_name: str):
+    """Accept the hits of the last static run as known."""
+    store = _store(project_name)
+    static_dir = store.path.parent
+    hits_path = static_dir / "static_hypotheses.json"
+    if not hits_path.exists():
+        console.print(f"[red]No static results in {static_dir}; run `static {project_name}` first[/red]")
+        raise SystemExit(1)
+    hits = json.loads(hits_path.read_text())
+    meta_path = static_dir / "static_metadata.json"
+    metadata = json.loads(meta_path.read_text()) if meta_path.exists() else {}
+    added = store.add_baseline(hits, since=metadata.get("rules_version", 1))
+    store.save()
+    console.print(f"[green]Baselined {added} hits ({len(store.baseline)} in total)[/green]")
+
+
+@rules.command("show")
+@click.argument("project_name")
+@click.option("--json", "as_json", is_flag=True, help="Print suppressions and baseline as JSON")
+def show(project_name: str, as_json: bool):
+    """List a project's suppressions and baseline."""
+    store = _store(project_name)
+    if as_json:
+        click.echo(json.dumps({"rules_version": store.rules_version,
+                               "suppressions": [asdict(s) for s in store.suppressions],
+                               "baseline": [asdict(b) for b in store.baseline]}, indent=2))
+        return
+    if not store.suppressions and not store.baseline:
+        console.print("[dim]No suppressions or baseline[/dim]")
+        return
+    if store.suppressions:
+        table = Table(title="Suppressions", show_header=True, header_style="bold")
+        for column in ("Rule", "Path", "Reason", "By", "Migrated from"):
+            table.add_column(column)
+        for s in store.suppressions:
+            rule = f"{s.rule} [red](retired)[/red]" if store.catalog.retired(s.rule) else s.rule
+            table.add_row(rule, s.path or "*", s.reason, s.created_by or "-", ", ".join(s.migrated_from) or "-")
+        console.print(table)
+    if store.baseline:
+        console.print(f"[bold]Baseline:[/bold] {len(store.baseline)} known hits")
+
+
+@rules.command("migrate")
+@click.argument("project_name")
+@click.option("--dry-run", is_flag=True, help="Report what would change without writing")
+def migrate(project_name: str, dry_run: bool):
+    """Move a project's suppressions and baseline to the current rule set."""
+    store = _store(project_name, migrate=False)
+    if store.rules_version == store.catalog.version:
+        console.print(f"[green]Already on rule set v{store.catalog.version}[/green]")
+        return
+    if dry_run:
+        _print_report(store.migrate())
+        return
+    report = store.load()
+    _print_report(report)
@@ -0,0 +1,384 @@
+"""
+Scan a workspace without creating a project first.
+
+Usage:
+    ./baskerville.py scan <path> [--chain CHAIN] [--tool NAME]... [--min-confidence LEVEL] [--json | --format FMT]
+                                 [--output DIR]
+                                 [--sarif FILE] [--baseline DIR|REF] [--update-baseline] [--show-suppressed]
+                                 [--usage [--provider SPEC] [--usage-sample N]] [--cluster NAME] [--no-cache]
+                                 [--jobs N] [--no-plugins] [--diff REF [--diff-context N]]
+                                 [--github checks|review [--github-pr N]]
+                                 [--fix [--dry-run] | --fix-patch FILE] [--fail-on SEVERITY|never]
+
+Hits accepted in the workspace's .baskerville-baseline.json and hits under a
+`baskerville:allow(<code>)` comment are hidden unless --show-suppressed.
+
+With --diff origin/main, only hits on lines changed since the merge base
+with that ref (committed, uncommitted or in untracked files) are reported,
+so pull requests are gated on the findings they introduce or touch
+(extensions/static/diff_scope.py). Nothing runs when the change touches no
+file in the workspace.
+
+With --fail-on high, the scan exits with status 1 when a reported hit is
+high or worse. A --diff scan fails on any hit in the change unless
+--fail-on says otherwise (--fail-on never to only report).
+
+With --github, the hits are also posted to GitHub as check-run annotations
+or pull request review comments, each with its suggested fix
+(extensions/reporting/github.py). A check run fails when the scan does
+(--fail-on). The token comes from GITHUB_TOKEN and the repository, commit
+and pull request from GitHub Actions' environment.
+
+With --fix, hits with a mechanical fix (an authority that must be a
+Signer, a missing has_one, colliding PDA seeds) are fixed in the source;
+--dry-run prints the patch instead and --fix-patch writes it to a file
+(extensions/static/autofix.py). The rest of each file is left untouched.
+
+With --usage, each deployed Solana program's recent transactions are
+sampled and hits are ranked by severity weighted by how much the
+instruction they are in is actually called.
+
+Solana code is checked against the feature gates of --cluster (default
+mainnet): syscalls and precompiles it calls must be active there.
+
+Parsed programs and detector results are cached under the workspace's
+.baskerville/cache by content hash, so a repeat scan only re-parses the
+crates whose files changed and replays detectors when nothing did. Crates
+are parsed and detectors run in --jobs worker processes (default one per
+CPU). Installed third-party detectors (extensions/static/plugins.py) run
+with the built-in ones unless --no-plugins.
+
+The workspace's baskerville.toml chooses rules, ignored paths, severity
+overrides and output defaults (extensions/static/project_config.py);
+options given on the command line win.
+"""
+
+import json
+import sys
+from pathlib import Path
+from typing import TYPE_CHECKING
+
+import click
+from rich.console import Console
+from rich.table import Table
+
+sys.path.insert(0, str(Path(__file__).parent.parent))
+
+from commands.static import ScanProgressView
+from extensions.static import StaticAnalysisPipeline, confidence_level, parse_confidence
+from extensions.static.cache import CACHE_DIR, ScanCache
+from extensions.static.confidence import meets_confidence
+from extensions.static.diff_scope import DiffError, DiffScope
+from extensions.static.project_config import SCAN_FORMATS, ConfigError, ProjectConfig
+from extensions.static.suppressions import BASELINE_FILE, SuppressionError, WorkspaceBaseline
+
+if TYPE_CHECKING:
+    from extensions.reporting.github import GitHubContext
+
+
+console = Console()
+
+_CHAINS = ("evm", "solana", "sui", "aptos")
+_SEVERITY_ORDER = {"critical": 0, "high": 1, "medium": 2, "low": 3, "info": 4}
+_SEVERITY_COLORS = {"critical": "red", "high": "red", "medium": "yellow", "low": "blue", "info": "dim"}
+FAIL_ON = tuple(_SEVERITY_ORDER) + ("never",)
+
+
+def _failing(hits: list[dict], fail_on: str) -> list[dict]:
+    """The hits at or above the ``fail_on`` severity (none for ``never``)."""
+    if fail_on == "never":
+        return []
+    return [h for h in hits if _SEVERITY_ORDER.get(h.get("severity", "medium"), 2) <= _SEVERITY_ORDER[fail_on]]
+
+
+def _location(hypothesis: dict) -> str:
+    properties = hypothesis.get("properties", {})
+    files = properties.get("source_files") or []
+    lines = properties.get("affected_lines") or []
+    if not files:
+        return "-"
+    return f"{files[0]}:{lines[0]}" if lines else str(files[0])
+
+
+def _post_github(context: "GitHubContext", mode: str, hits: list[dict], source: Path, chain_id: str,
+                 config: ProjectConfig, fail_on: str) -> dict:
+    """Post hits to GitHub, with their templates' suggested fixes; a check run fails like ``--fail-on``."""
+    from extensions.knowledge.class_loader import ClassLoader
+    from extensions.knowledge.template_loader import TemplateLoader, user_template_dirs
+    from extensions.reporting.formatters import build_findings_report
+    from extensions.reporting.github import GitHubClient, GitHubError, repo_prefix
+
+    try:
+        templates = TemplateLoader(user_dirs=user_template_dirs(project_dirs=config.template_dirs))
+        report = build_findings_report(hits, source.name, chain_id, source=source, templates=templates,
+                                       classes=ClassLoader())
+        return GitHubClient(context).post(report, mode, repo_prefix(source), fail_on=fail_on)
+    except (GitHubError, ValueError) as e:
+        console.print(f"[red]GitHub: {e}[/red]")
+        raise SystemExit(1)
+
+
+def _program_usage(source: Path, provider_spec: str | None, sample: int, notes: list[str]) -> dict:
+    """Usage samples of the workspace's deployed Solana programs, by program name."""
+    from extensions.ir import load_programs
+    from extensions.ir.anchor_config import load_anchor_config
+    from extensions.monitor.usage import collect_usage
+    from extensions.rpc import RpcCache, RpcClient, RpcError, default_cache_dir
+
+    try:
+        client = RpcClient(provider_spec, cache=RpcCache(default_cache_dir()))
+    except ValueError as e:
+        notes.append(f"Usage not collected: {e}")
+        return {}
+    try:
+        anchor = load_anchor_config(source)
+    except ValueError:
+        anchor = None
+    usages = {}
+    for program in load_programs(source):
+        address = (anchor.program_id(program.name, "mainnet") if anchor else None) or program.program_id
+        if program.chain != "solana" or not address:
+            continue
+        try:
+            usages[program.name] = collect_usage(client, program, address, limit=sample)
+        except RpcError as e:
+            notes.append(f"Usage of {program.name} not collected: {e}")
+            continue
+        notes.append(f"Sampled {usages[program.name].transactions} transactions of {program.name} ({address})")
+    return usages
+
+
+@click.command("scan")
+@click.argument("path", type=click.Path(exists=True))
+@click.option("--chain", default=None,
+              help="Chain of the code (default: detected from Anchor.toml, Move.toml, foundry.toml, ...)")
+@click.option("--tool", "tools", multiple=True, help="Only run this detector (repeatable)")
+@click.option("--min-confidence", default=None, help="Drop hits below this confidence (high, medium, low or 0-1)")
+@click.option("--json", "as_json", is_flag=True, help="Print hits as JSON (--format json)")
+@click.option("--format", "output_format", type=click.Choice(SCAN_FORMATS), default=None,
+              help="Output format (default: [output] format of baskerville.toml, else table)")
+@click.option("--output", "-o", default=None, help="Also save the results to this directory")
+@click.option("--sarif", "sarif_path", default=None, help="Also write the hits as SARIF 2.1.0 (for code scanning)")
+@click.option("--baseline", default=None,
+              help="Previous version (directory or git ref) to check account layout changes against")
+@click.option("--update-baseline", is_flag=True, help=f"Accept every current hit in the workspace's {BASELINE_FILE}")
+@click.option("--show-suppressed", is_flag=True, help="Also show baselined and inline-suppressed hits")
+@click.option("--usage", "with_usage", is_flag=True,
+              help="Rank hits by how often their instruction is called on-chain (Solana, needs RPC)")
+@click.option("--provider", "provider_spec", default=None,
+              help="RPC provider for --usage (URL, cluster or provider; default $SOLANA_RPC_URL or mainnet)")
+@click.option("--usage-sample", default=1000, show_default=True, help="Recent transactions to sample per program")
+@click.option("--cluster", default=None,
+              help="Cluster whose feature gates the code must work with (default mainnet; localnet has all)")
+@click.option("--no-cache", is_flag=True, help=f"Parse and run every detector again instead of reusing {CACHE_DIR}")
+@click.option("--jobs", "-j", type=click.IntRange(min=1), default=None,
+              help="Worker processes parsing crates and running detectors (default: one per CPU)")
+@click.option("--no-plugins", is_flag=True, help="Only run the built-in detectors")
+@click.option("--diff", "diff_ref", default=None,
+              help="Only report hits on lines changed since the merge base with this git ref (e.g. origin/main)")
+@click.option("--diff-context", type=click.IntRange(min=0), default=3, show_default=True,
+              help="Lines around a change that still count as touched by it")
+@click.option("--github", "github_mode", type=click.Choice(["checks", "review"]), default=None,
+              help="Also post the hits to GitHub as check-run annotations or pull request review comments")
+@click.option("--github-pr", type=click.IntRange(min=1), default=None,
+              help="Pull request to review (default: the one of the GitHub Actions event)")
+@click.option("--fix", "fix", is_flag=True, help="Fix the hits that have a mechanical fix in the source")
+@click.option("--dry-run", is_flag=True, help="With --fix, print the patch instead of changing the source")
+@click.option("--fix-patch", default=None, help="Write the fixes to this patch file instead of applying them")
+@click.option("--fail-on", type=click.Choice(FAIL_ON), default=None,
+              help="Exit with status 1 when a hit is this severe or worse (default: info with --diff, else never)")
+def scan(path: str, chain: str | None, tools: tuple[str, ...], min_confidence: str | None, as_json: bool,
+         output: str | None, sarif_path: str | None = None, baseline: str | None = None,
+         update_baseline: bool = False, show_suppressed: bool = False, with_usage: bool = False,
+         provider_spec: str | None = None, usage_sample: int = 1000, cluster: str | None = None,
+         no_cache: bool = False, jobs: int | None = None, no_plugins: bool = False,
+         output_format: str | None = None, diff_ref: str | None = None, diff_context: int = 3,
+         github_mode: str | None = None, github_pr: int | None = None, fix: bool = False, dry_run: bool = False,
+         fix_patch: str | None = None, fail_on: str | None = None):
+    """Run the chain's detectors against a workspace (e.g. an Anchor workspace) and list the hits."""
+    from analysis.chain_profiles import detect_chain_from_files
+
+    source = Path(path).resolve()
+    chain_id = (chain or detect_chain_from_files(source)).lower()
+    try:
+        config = ProjectConfig.load(source)
+        confidence_floor = parse_confidence(config.default("min_confidence", min_confidence))
+        scope = DiffScope.from_git(source, diff_ref, diff_context) if diff_ref else None
+    except (ConfigError, DiffError, ValueError) as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    # A pull request gate fails on what the change introduces or touches
+    fail_on = fail_on or ("info" if scope is not None else "never")
+    github = None
+    if github_mode:
+        from extensions.reporting.github import GitHubContext, GitHubError
+
+        # Before scanning, so a missing token fails fast
+        try:
+            github = GitHubContext.from_env(pr=github_pr, workspace=source)
+        except GitHubError as e:
+            console.print(f"[red]GitHub: {e}[/red]")
+            raise SystemExit(1)
+
+    # The pipeline falls back to EVM detectors for chains it has none for
+    if chain_id not in _CHAINS:
+        console.print(f"[red]No detectors for chain '{chain_id}' (expected {', '.join(_CHAINS)})[/red]")
+        raise SystemExit(1)
+    as_json = as_json or config.default("format", output_format) == "json"
+    if not sarif_path and config.output.get("sarif"):
+        # Relative to the workspace, like every path in baskerville.toml
+        sarif_path = str(config.root / config.output["sarif"])
+    pipeline = StaticAnalysisPipeline(chain_id=chain_id, state_migration_config={"baseline": baseline},
+                                      feature_gates_config={"cluster": cluster} if cluster else None,
+                                      inline_suppressions=not show_suppressed,
+                                      cache=None if no_cache else ScanCache(source), jobs=jobs,
+                                      plugins=not no_plugins, config=config)
+    unknown = sorted(set(tools) - set(pipeline.runners))
+    if unknown:
+        console.print(f"[red]Unknown detector(s) for {chain_id}: {', '.join(unknown)}[/red]")
+        console.print(f"[dim]Available: {', '.join(pipeline.runners)}[/dim]")
+        raise SystemExit(1)
+
+    if scope is not None and not scope.files:
+        # A required check still gets its (passing) run
+        posted = _post_github(github, github_mode, [], source, chain_id, config, fail_on) if github else None
+        if as_json:
+            click.echo(json.dumps({"path": str(source), "chain": chain_id, "tools": {}, "hypotheses": [],
+                                   "suppressed": 0, "baselined": 0, "usage": None,
+                                   "diff": {**scope.to_dict(), "outside": 0}, "github": posted}, indent=2))
+        else:
+            console.print(f"[green]No changes in {source} since {diff_ref}, nothing to scan[/green]")
+        return
+    if as_json:
+        result = pipeline.run(source, tools=tools or None)
+    else:
+        console.print(f"\n[bold]Scanning {source}[/bold] [dim]({chain_id})[/dim]\n")
+        with ScanProgressView("Running detectors") as view:
+            result = pipeline.run(source, tools=tools or None, progress=lambda event: view.update(event.to_dict()))
+    if confidence_floor is not None:
+        result.hypotheses = [h for h in result.hypotheses if meets_confidence(h, confidence_floor)]
+
+    notes = [f"Plugin {name} not loaded: {error}" for name, error in pipeline.plugin_errors.items()]
+    applied = result.metadata.get("config")
+    if applied and (applied["filtered"] or applied["tools_disabled"] or applied["severity_overridden"]):
+        notes.append(f"{config.path.name}: {len(applied['tools_disabled'])} detectors disabled, {applied['filtered']} "
+                     f"hits filtered, {applied['severity_overridden']} severities overridden")
+    cache = result.metadata.get("cache")
+    if cache:
+        notes.append(f"Cache: {cache['detectors_replayed']} detectors replayed, {cache['programs_parsed']} "
+                     f"programs parsed ({CACHE_DIR}; --no-cache to redo everything)")
+    accepted = WorkspaceBaseline(source if source.is_dir() else source.parent)
+    if update_baseline:
+        # Only what this run found stays accepted
+        accepted.accept(result.hypotheses)
+        notes.append(f"Baselined {len(accepted.entries)} hits in {accepted.save()}")
+    if not show_suppressed:
+        try:
+            accepted.load()
+        except SuppressionError as e:
+            notes.append(f"Baseline not applied: {e}")
+        stale = accepted.stale(result.hypotheses)
+        result.hypotheses, baselined = accepted.apply(result.hypotheses)
+        result.metadata["baselined"] = len(baselined)
+        if result.suppressed or baselined:
+            notes.append(f"{len(result.suppressed)} suppressed inline and {len(baselined)} baselined hits hidden "
+                         f"(--show-suppressed to see them)")
+        if stale:
+            notes.append(f"{len(stale)} baselined findings no longer reported (--update-baseline drops them)")
+    if scope is not None:
+        result.hypotheses, outside = scope.filter(result.hypotheses, source)
+        result.metadata["diff"] = {**scope.to_dict(), "outside": len(outside)}
+        notes.append(f"{len(outside)} hits outside the changes since {diff_ref} hidden "
+                     f"({len(scope.files)} files changed, --diff-context {scope.context})")
+    hits = sorted(result.hypotheses, key=lambda h: _SEVERITY_ORDER.get(h.get("severity", "medium"), 2))
+    usages = {}
+    if with_usage and chain_id != "solana":
+        notes.append("--usage only samples Solana programs")
+    elif with_usage:
+        usages = _program_usage(source, provider_spec, usage_sample, notes)
+    if usages:
+        from extensions.monitor.usage import weight_hits
+
+        hits = weight_hits(hits, usages)
+        result.metadata["usage"] = {name: u.to_dict() for name, u in usages.items()}
+
+    if output:
+        pipeline.save_results(result, Path(output))
+    if sarif_path:
+        from extensions.knowledge.class_loader import ClassLoader
+        from extensions.reporting.sarif import build_sarif, write_sarif
+
+        write_sarif(build_sarif(hits, source, chain_id, result.metadata["tools"], ClassLoader()), Path(sarif_path))
+    patch = None
+    if fix or dry_run or fix_patch:
+        from extensions.static.autofix import FixError, apply_fixes, plan_fixes, render_patch
+
+        fixes, unfixed = plan_fixes(hits, source)
+        try:
+            patch = render_patch(fixes, source)
+            changed = apply_fixes(fixes, source) if fix and not (dry_run or fix_patch) else []
+        except (FixError, OSError) as e:
+            console.print(f"[red]Cannot fix: {e}[/red]")
+            raise SystemExit(1)
+        if fix_patch:
+            Path(fix_patch).write_text(patch)
+        result.metadata["fixes"] = {"fixes": [f.to_dict() for f in fixes], "skipped": unfixed,
+                                    "applied": changed, "patch": patch}
+        if changed:
+            notes.append(f"Fixed {len(fixes)} hits in {', '.join(changed)} (scan again to confirm)")
+        elif fixes:
+            notes.append(f"{len(fixes)} hits fixable" + (f", patch written to {fix_patch}" if fix_patch else ""))
+        notes += [f"Not fixed: {u['title']}: {u['reason']}" for u in unfixed]
+        notes += [f"After fixing {f.hit}: {note}" for f in fixes for note in f.notes]
+    failing = _failing(hits, fail_on)
+    if github:
+        result.metadata["github"] = posted = _post_github(github, github_mode, hits, source, chain_id, config, fail_on)
+        if posted["mode"] == "checks":
+            notes.append(f"Check run {posted['conclusion']} with {posted['annotations']} annotations: {posted['url']}")
+        else:
+            notes.append(f"Reviewed pull request #{posted['pr']}: {posted['comments']} comments, {posted['listed']} "
+                         f"findings in the summary, {posted['skipped']} already posted")
+    if as_json:
+        click.echo(json.dumps({"path": str(source), "chain": chain_id, "tools": result.metadata["tools"],
+                               "hypotheses": hits, "suppressed": len(result.suppressed),
+                               "baselined": result.metadata.get("baselined", 0),
+                               "usage": result.metadata.get("usage"), "diff": result.metadata.get("diff"),
+                               "github": result.metadata.get("github"), "fixes": result.metadata.get("fixes")},
+                              indent=2, default=str))
+        if failing:
+            raise SystemExit(1)
+        return
+
+    skipped = [name for name, meta in result.metadata["tools"].items() if meta.get("available") is False]
+    if skipped:
+        console.print(f"[dim]Not installed, skipped: {', '.join(skipped)}[/dim]")
+    for note in notes:
+        console.print(f"[dim]{note}[/dim]")
+    if not hits:
+        console.print("[green]No hits[/green]")
+    else:
+        table = Table(show_header=True, header_style="bold")
+        for column in ("Severity", "Title", "Detector", "Location", "Confidence") + (("Calls",) if usages else ()):
+            table.add_column(column)
+        for hit in hits:
+            severity = hit.get("severity", "medium")
+            color = _SEVERITY_COLORS.get(severity, "white")
+            row = [f"[{color}]{severity}[/{color}]", hit.get("title", "Unknown"),
+                   hit.get("properties", {}).get("source_tool", "-"), _location(hit),
+                   confidence_level(hit.get("confidence"))]
+            if usages:
+                usage = hit["properties"].get("usage")
+                row.append(f"{usage['calls']} by {usage['callers']}" if usage else "-")
+            table.add_row(*row)
+        console.print(table)
+        console.print(f"\n[bold]{len(hits)} hits[/bold]")
+    if output:
+        console.print(f"[dim]Results saved to: {output}[/dim]")
+    if sarif_path:
+        console.print(f"[dim]SARIF written to: {sarif_path}[/dim]")
+    if patch and dry_run and not fix_patch:
+        # Plain output: the patch is full of brackets rich would take for markup
+        click.echo(f"\n{patch}", nl=False)
+    if failing:
+        console.print(f"[red]{len(failing)} hits at or above {fail_on} severity (--fail-on {fail_on})[/red]")
+        raise SystemExit(1)
@@ -0,0 +1,195 @@
+"""
+Server mode commands: tenants, API keys and the JSON API.
+
+Usage:
+    ./baskerville.py server tenant-add <id> [--name N]             # Register a tenant (client engagement)
+    ./baskerville.py server tenants                                # List tenants
+    ./baskerville.py server tenant-remove <id>                     # Unregister a tenant, revoking its keys
+    ./baskerville.py server key-create <tenant> --role triager     # Issue an API key (shown once)
+    ./baskerville.py server keys [--tenant T]                      # List API keys
+    ./baskerville.py server key-revoke <key-id>                    # Revoke an API key
+    ./baskerville.py server serve [--host H] [--port P]            # Serve the JSON API
+    ./baskerville.py server scan <project> --url U [--tool T]      # Start a scan on a server and follow its progress
+"""
+
+import json
+import os
+import sys
+from pathlib import Path
+
+import click
+from rich.console import Console
+from rich.table import Table
+
+sys.path.insert(0, str(Path(__file__).parent.parent))
+
+from extensions.server import ApiClient, ApiError, ApiServer, Role, TenancyError, TenantStore
+
+
+console = Console()
+
+
+def get_store(data_dir: str | None = None) -> TenantStore:
+    """Get the tenant store (default: ~/.hound/server)."""
+    return TenantStore(Path(data_dir) if data_dir else None)
+
+
+@click.group("server")
+def server():
+    """Multi-tenant server mode."""
+    pass
+
+
+@server.command("tenant-add")
+@click.argument("tenant_id")
+@click.option("--name", help="Display name (default: the id)")
+@click.option("--data-dir", help="Server data directory (default: ~/.hound/server)")
+def tenant_add(tenant_id: str, name: str | None, data_dir: str | None):
+    """Register a tenant and create its storage namespace."""
+    store = get_store(data_dir)
+    try:
+        tenant = store.add_tenant(tenant_id, name)
+    except TenancyError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    console.print(f"[green]Added tenant {tenant.id}[/green]")
+    console.print(f"[dim]Storage: {store.storage_dir(tenant.id)} (project sources go in sources/)[/dim]")
+
+
+@server.command("tenants")
+@click.option("--data-dir", help="Server data directory (default: ~/.hound/server)")
+def tenants(data_dir: str | None):
+    """List tenants with their key and project counts."""
+    store = get_store(data_dir)
+    found = store.list_tenants()
+    if not found:
+        console.print("[yellow]No tenants[/yellow]")
+        return
+    table = Table(show_header=True, header_style="bold")
+    for column in ("ID", "Name", "Active keys", "Projects", "Created"):
+        table.add_column(column)
+    for tenant in found:
+        keys = [k for k in store.list_keys(tenant.id) if not k.revoked]
+        projects_dir = store.projects_dir(tenant.id)
+        count = sum(1 for _ in projects_dir.glob("*/project.json")) if projects_dir.exists() else 0
+        table.add_row(tenant.id, tenant.name, str(len(keys)), str(count), tenant.created_at[:10])
+    console.print(table)
+
+
+@server.command("tenant-remove")
+@click.argument("tenant_id")
+@click.option("--data-dir", help="Server data directory (default: ~/.hound/server)")
+def tenant_remove(tenant_id: str, data_dir: str | None):
+    """Unregister a tenant and revoke its keys (its storage is kept)."""
+    store = get_store(data_dir)
+    if not store.remove_tenant(tenant_id):
+        console.print(f"[red]Unknown tenant: {tenant_id}[/red]")
+        raise SystemExit(1)
+    console.print(f"[green]Removed tenant {tenant_id}; its keys are revoked[/green]")
+
+
+@server.command("key-create")
+@click.argument("tenant_id")
+@click.option("--role", type=click.Choice([r.value for r in Role]), default="viewer", help="Role the key grants")
+@click.option("--label", default="", help="Who or what the key is for")
+@click.option("--data-dir", help="Server data directory (default: ~/.hound/server)")
+def key_create(tenant_id: str, role: str, label: str, data_dir: str | None):
+    """Issue an API key for a tenant; the key is shown only once."""
+    try:
+        record, secret = get_store(data_dir).create_key(tenant_id, role, label)
+    except TenancyError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    console.print(f"[green]Created {record.role} key {record.id} for {tenant_id}[/green]")
+    click.echo(secret)
+    console.print("[dim]Store it now; it cannot be shown again.[/dim]")
+
+
+@server.command("keys")
+@click.option("--tenant", "tenant_id", help="Only this tenant's keys")
+@click.option("--json", "as_json", is_flag=True, help="Print keys as JSON")
+@click.option("--data-dir", help="Server data directory (default: ~/.hound/server)")
+def keys(tenant_id: str | None, as_json: bool, data_dir: str | None):
+    """List API keys (never their secrets)."""
+    found = get_store(data_dir).list_keys(tenant_id)
+    if as_json:
+        click.echo(json.dumps([k.public() for k in found], indent=2))
+        return
+    if not found:
+        console.print("[yellow]No keys[/yellow]")
+        return
+    table = Table(show_header=True, header_style="bold")
+    for column in ("ID", "Tenant", "Role", "Label", "Created", "Status"):
+        table.add_column(column)
+    for key in found:
+        status = "[red]revoked[/red]" if key.revoked else "[green]active[/green]"
+        table.add_row(key.id, key.tenant, key.role.value, key.label or "-", key.created_at[:10], status)
+    console.print(table)
+
+
+@server.command("key-revoke")
+@click.argument("key_id")
+@click.option("--data-dir", help="Server data directory (default: ~/.hound/server)")
+def key_revoke(key_id: str, data_dir: str | None):
+    """Revoke an API key."""
+    if not get_store(data_dir).revoke_key(key_id):
+        console.print(f"[red]Unknown key: {key_id}[/red]")
+        raise SystemExit(1)
+    console.print(f"[green]Revoked key {key_id}[/green]")
+
+
+@server.command("serve")
+@click.option("--host", default="127.0.0.1", help="Listen address")
+@click.option("--port", default=8080, help="Listen port")
+@click.option("--data-dir", help="Server data directory (default: ~/.hound/server)")
+def serve(host: str, port: int, data_dir: str | None):
+    """Serve the JSON API until interrupted."""
+    store = get_store(data_dir)
+    if not store.list_tenants():
+        console.print("[yellow]No tenants yet; add one with `server tenant-add`[/yellow]")
+    api = ApiServer(store, host, port)
+    bound_host, bound_port = api.address
+    console.print(f"[green]Serving on http://{bound_host}:{bound_port}/v1[/green] [dim](Ctrl+C to stop)[/dim]")
+    try:
+        api.serve_forever()
+    except KeyboardInterrupt:
+        pass
+    finally:
+        api.stop()
+
+
+@server.command("scan")
+@click.argument("project_name")
+@click.option("--url", default="http://127.0.0.1:8080", help="Server URL")
+@click.option("--key", "api_key", help="API key (default: $BASKERVILLE_API_KEY)")
+@click.option("--tool", "tools", multiple=True, help="Only run this detector (repeatable)")
+@click.option("--attach", "scan_id", help="Follow an existing scan instead of starting one")
+def scan(project_name: str, url: str, api_key: str | None, tools: tuple[str, ...], scan_id: str | None):
+    """Run static analysis on a server-side project, showing its progress as it streams."""
+    from commands.static import ScanProgressView
+
+    api_key = api_key or os.environ.get("BASKERVILLE_API_KEY")
+    if not api_key:
+        console.print("[red]No API key: pass --key or set BASKERVILLE_API_KEY[/red]")
+        raise SystemExit(1)
+    client = ApiClient(url, api_key, timeout=60)
+    try:
+        if scan_id is None:
+            scan_id = client.start_scan(project_name, list(tools) or None)["id"]
+            console.print(f"[dim]Started scan {scan_id}[/dim]")
+        with ScanProgressView(f"Scanning {project_name}") as view:
+            for event in client.scan_events(project_name, scan_id):
+                view.update(event)
+    except ApiError as e:
+        console.print(f"[red]{e.message}[/red]")
+        raise SystemExit(1)
+
+    last = view.last or {}
+    if last.get("event") == "failed":
+        console.print(f"[red]Scan {scan_id} failed: {last.get('error')}[/red]")
+        raise SystemExit(1)
+    console.print(
+        f"[green]Scan {scan_id} complete:[/green] {last.get('detectors_completed', 0)} detectors, "
+        f"{last.get('files_parsed', 0)} files, {last.get('findings', 0)} findings, "
+        f"{last.get('hypotheses', 0)} unique hypotheses"
+    )
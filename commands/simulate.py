@@ -0,0 +1,691 @@
+"""
+Economic simulation commands.
+
+Usage:
+    ./baskerville.py sim run <config.yaml> [--runs N] [--slots N] [--seed N] [--json] [--output FILE]
+    ./baskerville.py sim run <config.yaml> --project <name> [--import-hypotheses]
+    ./baskerville.py sim math <cases.yaml> [--trials N] [--steps N] [--seed N] [--json] [--output FILE]
+    ./baskerville.py sim liquidations <sweep.yaml> [--json] [--output FILE] [--project <name>] [--import-hypotheses]
+    ./baskerville.py sim substitute <tx.yaml> [--kind KIND ...] [--json] [--output FILE] [--project <name>]
+    ./baskerville.py sim boundaries <instructions.yaml> [--instruction NAME ...] [--json] [--output FILE]
+    ./baskerville.py sim diff <corpus.yaml> [--json] [--output FILE] [--project <name>]
+    ./baskerville.py sim migrate <fixtures.yaml> [--json] [--output FILE] [--project <name>] [--import-hypotheses]
+    ./baskerville.py sim search <instructions.yaml> [--iterations N] [--seed N] [--instruction NAME ...] [--json]
+    ./baskerville.py sim search <instructions.yaml> --project <name> [--corpus DIR] [--import-hypotheses]
+    ./baskerville.py sim corpus [--project <name> | --corpus DIR] [--json]
+    ./baskerville.py sim replay <instructions.yaml> [--bucket BUCKET ...] [--project <name> | --corpus DIR] [--json]
+    ./baskerville.py sim minimize <instructions.yaml> [--project <name> | --corpus DIR] [--json]
+"""
+
+import hashlib
+import json
+import sys
+from datetime import datetime
+from pathlib import Path
+
+import click
+import yaml
+from rich.console import Console
+from rich.table import Table
+
+sys.path.insert(0, str(Path(__file__).parent.parent))
+
+from commands.project import ProjectManager
+
+
+console = Console()
+
+
+def _import_findings(hypotheses: list[dict], hyp_file: Path) -> int:
+    store = json.loads(hyp_file.read_text()) if hyp_file.exists() else {"version": "1.0", "hypotheses": {}}
+    imported = 0
+    for hyp in hypotheses:
+        key = "|".join([hyp["vulnerability_type"], hyp["title"]])
+        hyp_id = f"sim_{hashlib.md5(key.encode()).hexdigest()[:8]}"
+        if hyp_id not in store["hypotheses"]:
+            store["hypotheses"][hyp_id] = {**hyp, "id": hyp_id, "created_at": datetime.now().isoformat(),
+                                           "created_by": "simulation"}
+            imported += 1
+    hyp_file.write_text(json.dumps(store, indent=2))
+    return imported
+
+
+def _save(export: dict, hypotheses: list[dict], output: str | None, project_name: str | None, name: str,
+          import_hypotheses: bool) -> Path | None:
+    """Write ``export`` to ``output`` or the project's simulations/ directory; optionally import findings."""
+    out_path = Path(output) if output else None
+    if project_name:
+        project = ProjectManager().get_project(project_name)
+        if not project:
+            console.print(f"[red]Project '{project_name}' not found[/red]")
+            raise SystemExit(1)
+        project_dir = Path(project["path"])
+        out_path = out_path or project_dir / "simulations" / f"{name}.json"
+        if import_hypotheses and hypotheses:
+            imported = _import_findings(hypotheses, project_dir / "hypotheses.json")
+            console.print(f"[green]Imported {imported} hypotheses to store[/green]")
+    if out_path:
+        out_path.parent.mkdir(parents=True, exist_ok=True)
+        out_path.write_text(json.dumps(export, indent=2))
+    return out_path
+
+
+def _corpus(corpus: str | None, project_name: str | None, required: bool = False):
+    """The corpus store at ``corpus``, else the project's; None (or an error when ``required``) without either."""
+    from extensions.simulation.corpus import CorpusStore
+
+    if corpus:
+        return CorpusStore(Path(corpus))
+    if project_name:
+        project = ProjectManager().get_project(project_name)
+        if not project:
+            console.print(f"[red]Project '{project_name}' not found[/red]")
+            raise SystemExit(1)
+        return CorpusStore(Path(project["path"]) / "corpus")
+    if required:
+        console.print("[red]Give a project (--project) or a corpus directory (--corpus)[/red]")
+        raise SystemExit(1)
+    return None
+
+
+@click.group("sim")
+def sim():
+    """Economic simulations and arithmetic stress tests."""
+    pass
+
+
+@sim.command("run")
+@click.argument("config_path", type=click.Path(exists=True, dir_okay=False))
+@click.option("--runs", type=int, default=None, help="Number of seeded runs (overrides the config)")
+@click.option("--slots", type=int, default=None, help="Slots per run (overrides the config)")
+@click.option("--seed", type=int, default=None, help="First seed (overrides the config)")
+@click.option("--json", "as_json", is_flag=True, help="Print results as JSON")
+@click.option("--output", "-o", default=None, help="Write results to a file")
+@click.option("--project", "project_name", default=None, help="Save results under this project")
+@click.option("--import-hypotheses", is_flag=True, help="Import findings into the project's hypothesis store")
+def run(config_path: str, runs: int | None, slots: int | None, seed: int | None, as_json: bool,
+        output: str | None, project_name: str | None, import_hypotheses: bool):
+    """Run agents against a protocol over many slots and report economic exploits."""
+    from extensions.reporting.manifest import build_manifest
+    from extensions.simulation import BackendError, load_simulation
+
+    path = Path(config_path)
+    data = yaml.safe_load(path.read_text()) or {}
+    overrides = {k: v for k, v in {"runs": runs, "slots": slots, "seed": seed}.items() if v is not None}
+    data["simulation"] = {**data.get("simulation", {}), **overrides}
+    try:
+        result = load_simulation(data, base=path.parent).run()
+    except (BackendError, ValueError, TypeError) as e:
+        console.print(f"[red]Simulation failed: {e}[/red]")
+        raise SystemExit(1)
+
+    export = result.to_dict()
+    export["manifest"] = build_manifest(
+        "sim run",
+        {"config_path": str(path.resolve()), "runs": runs, "slots": slots, "seed": seed, "as_json": False,
+         "output": output, "project_name": project_name, "import_hypotheses": False},
+        detectors={"simulation": export["version"]},
+    )
+    hypotheses = [f.to_hypothesis() for f in result.findings()]
+    out_path = _save(export, hypotheses, output, project_name, path.stem, import_hypotheses)
+    if as_json:
+        click.echo(json.dumps(export, indent=2))
+        return
+
+    summary = export["summary"]
+    table = Table(show_header=True, header_style="bold")
+    for column in ("Agent", "Type", "Mean PnL"):
+        table.add_column(column)
+    for name, agent in summary["agents"].items():
+        table.add_row(name, agent["kind"], f"{agent['mean_pnl']:,.2f}")
+    console.print(table)
+    console.print(
+        f"{summary['runs']} runs of {result.config.slots} slots: mean extracted {summary['mean_extracted']:,.2f}, "
+        f"mean bad debt {summary['mean_bad_debt']:,.2f}, max oracle gap {summary['max_oracle_gap']:.2%}"
+    )
+    styles = {"high": "red", "medium": "yellow", "low": "blue"}
+    for hyp in hypotheses:
+        style = styles.get(hyp["severity"], "white")
+        console.print(f"  [{style}][{hyp['severity'].upper()}][/{style}] {hyp['title']}")
+    if not hypotheses:
+        console.print("[green]No economic exploits found[/green]")
+    if out_path:
+        console.print(f"[dim]Results written to {out_path}[/dim]")
+
+
+@sim.command("math")
+@click.argument("config_path", type=click.Path(exists=True, dir_okay=False))
+@click.option("--trials", type=int, default=None, help="Trials per case (overrides the config)")
+@click.option("--steps", type=int, default=None, help="Steps per trial (overrides the config)")
+@click.option("--seed", type=int, default=0, help="Random seed")
+@click.option("--json", "as_json", is_flag=True, help="Print reports as JSON")
+@click.option("--output", "-o", default=None, help="Write reports to a file")
+@click.option("--project", "project_name", default=None, help="Save reports under this project")
+@click.option("--import-hypotheses", is_flag=True, help="Import findings into the project's hypothesis store")
+def math_stress(config_path: str, trials: int | None, steps: int | None, seed: int, as_json: bool, output: str | None,
+         project_name: str | None, import_hypotheses: bool):
+    """Stress fee, reward and interest math with randomized inputs over long horizons."""
+    from extensions.reporting.manifest import build_manifest
+    from extensions.simulation import BackendError
+    from extensions.simulation.math_stress import STRESS_VERSION, evaluate_report, load_cases, run_case
+
+    path = Path(config_path)
+    data = yaml.safe_load(path.read_text()) or {}
+    overrides = {k: v for k, v in {"trials": trials, "steps": steps}.items() if v is not None}
+    try:
+        cases = load_cases(data, base=path.parent)
+        for case in cases:
+            for key, value in overrides.items():
+                setattr(case, key, value)
+        reports = [run_case(case, seed) for case in cases]
+    except (BackendError, ValueError, TypeError, SyntaxError) as e:
+        console.print(f"[red]Stress run failed: {e}[/red]")
+        raise SystemExit(1)
+
+    findings = [f for report in reports for f in evaluate_report(report, **data.get("thresholds", {}))]
+    hypotheses = [f.to_hypothesis() for f in findings]
+    export = {
+        "tool": "math-stress",
+        "version": STRESS_VERSION,
+        "seed": seed,
+        "reports": [r.to_dict() for r in reports],
+        "findings": [{"case": f.report.case, "kind": f.kind, "severity": f.severity} for f in findings],
+        "manifest": build_manifest(
+            "sim math",
+            {"config_path": str(path.resolve()), "trials": trials, "steps": steps, "seed": seed, "as_json": False,
+             "output": output, "project_name": project_name, "import_hypotheses": False},
+            detectors={"math-stress": STRESS_VERSION},
+        ),
+    }
+    out_path = _save(export, hypotheses, output, project_name, path.stem, import_hypotheses)
+    if as_json:
+        click.echo(json.dumps(export, indent=2, default=str))
+        return
+
+    table = Table(show_header=True, header_style="bold")
+    for column in ("Case", "Width", "Worst drift", "Headroom", "Overflows", "Max step loss", "Rounded to 0"):
+        table.add_column(column)
+    for r in reports:
+        table.add_row(
+            r.case, f"u{r.width}", f"{r.drift['absolute']:,.6g} ({r.drift['relative']:.1e})",
+            f"{r.overflow['headroom_bits']} bits", str(r.overflow["events"]),
+            f"{r.precision['max_units']:,.6g}", str(r.precision["rounded_to_zero"]),
+        )
+    console.print(table)
+    styles = {"high": "red", "medium": "yellow", "low": "blue"}
+    for hyp in hypotheses:
+        style = styles.get(hyp["severity"], "white")
+        console.print(f"  [{style}][{hyp['severity'].upper()}][/{style}] {hyp['title']}")
+    if not hypotheses:
+        console.print("[green]No arithmetic issues found[/green]")
+    if out_path:
+        console.print(f"[dim]Reports written to {out_path}[/dim]")
+
+
+def _sweep_cell(scenario) -> str:
+    if scenario is None:
+        return "n/a"
+    styles = {"healthy": "green", "liquidated": "blue", "unprofitable": "yellow", "bad-debt": "red"}
+    label = f"bad debt {scenario.bad_debt_share:.1%}" if scenario.status == "bad-debt" else scenario.status
+    return f"[{styles[scenario.status]}]{label}[/{styles[scenario.status]}]"
+
+
+@sim.command("liquidations")
+@click.argument("config_path", type=click.Path(exists=True, dir_okay=False))
+@click.option("--json", "as_json", is_flag=True, help="Print the sweep as JSON")
+@click.option("--output", "-o", default=None, help="Write the sweep to a file")
+@click.option("--project", "project_name", default=None,
+              help="Save the sweep under this project (picked up by the report's economic-risk section)")
+@click.option("--import-hypotheses", is_flag=True, help="Import findings into the project's hypothesis store")
+def liquidations(config_path: str, as_json: bool, output: str | None, project_name: str | None,
+                 import_hypotheses: bool):
+    """Sweep price drops, oracle latencies and position sizes for unprofitable liquidations and bad debt."""
+    from extensions.reporting.manifest import build_manifest
+    from extensions.simulation import BackendError
+    from extensions.simulation.liquidation import SWEEP_VERSION, load_sweep
+
+    path = Path(config_path)
+    data = yaml.safe_load(path.read_text()) or {}
+    try:
+        result = load_sweep(data, base=path.parent).run()
+    except (BackendError, ValueError, TypeError) as e:
+        console.print(f"[red]Liquidation sweep failed: {e}[/red]")
+        raise SystemExit(1)
+
+    export = result.to_dict()
+    export["manifest"] = build_manifest(
+        "sim liquidations",
+        {"config_path": str(path.resolve()), "as_json": False, "output": output, "project_name": project_name,
+         "import_hypotheses": False},
+        detectors={"liquidation-sweep": SWEEP_VERSION},
+    )
+    hypotheses = [f.to_hypothesis() for f in result.findings()]
+    out_path = _save(export, hypotheses, output, project_name, path.stem, import_hypotheses)
+    if as_json:
+        click.echo(json.dumps(export, indent=2))
+        return
+
+    config = result.config
+    for size in config.sizes:
+        table = Table(title=f"Position size {size:,g}", show_header=True, header_style="bold")
+        table.add_column("Oracle lag")
+        for drop in config.drops:
+            table.add_column(f"{drop:.0%} drop")
+        for latency in config.latencies:
+            table.add_row(f"{latency} slots", *[
+                _sweep_cell(result.scenario(drop, latency, size)) for drop in config.drops
+            ])
+        console.print(table)
+    styles = {"high": "red", "medium": "yellow", "low": "blue"}
+    for hyp in hypotheses:
+        style = styles.get(hyp["severity"], "white")
+        console.print(f"  [{style}][{hyp['severity'].upper()}][/{style}] {hyp['title']}")
+    if not hypotheses:
+        console.print("[green]Liquidations stayed profitable without bad debt in every scenario[/green]")
+    if out_path:
+        console.print(f"[dim]Sweep written to {out_path}[/dim]")
+
+
+@sim.command("substitute")
+@click.argument("config_path", type=click.Path(exists=True, dir_okay=False))
+@click.option("--kind", "kinds", multiple=True,
+              type=click.Choice(["wrong-signer", "wrong-owner", "wrong-mint", "wrong-pda", "duplicate"]),
+              help="Substitution kind to try (repeatable; default: the config's, or all)")
+@click.option("--json", "as_json", is_flag=True, help="Print the substitutions as JSON")
+@click.option("--output", "-o", default=None, help="Write the substitutions to a file")
+@click.option("--project", "project_name", default=None, help="Save the substitutions under this project")
+@click.option("--import-hypotheses", is_flag=True, help="Import findings into the project's hypothesis store")
+def substitute(config_path: str, kinds: tuple[str, ...], as_json: bool, output: str | None,
+               project_name: str | None, import_hypotheses: bool):
+    """Replay a legitimate transaction with each account swapped for an attacker lookalike."""
+    from extensions.reporting.manifest import build_manifest
+    from extensions.simulation import BackendError
+    from extensions.simulation.substitution import SUBSTITUTION_VERSION, load_substitution
+
+    path = Path(config_path)
+    data = yaml.safe_load(path.read_text()) or {}
+    if kinds:
+        data["kinds"] = list(kinds)
+    try:
+        result = load_substitution(data, base=path.parent).run()
+    except (BackendError, ValueError, TypeError, KeyError) as e:
+        console.print(f"[red]Substitution fuzzing failed: {e}[/red]")
+        raise SystemExit(1)
+
+    export = result.to_dict()
+    export["manifest"] = build_manifest(
+        "sim substitute",
+        {"config_path": str(path.resolve()), "kinds": list(kinds), "as_json": False, "output": output,
+         "project_name": project_name, "import_hypotheses": False},
+        detectors={"account-fuzz": SUBSTITUTION_VERSION},
+    )
+    hypotheses = [f.to_hypothesis() for f in result.findings()]
+    out_path = _save(export, hypotheses, output, project_name, path.stem, import_hypotheses)
+    if as_json:
+        click.echo(json.dumps(export, indent=2))
+        return
+
+    table = Table(show_header=True, header_style="bold")
+    for column in ("Account", "Substitution", "Result"):
+        table.add_column(column)
+    for sub in result.substitutions:
+        kind = f"{sub.kind} ({sub.other})" if sub.other else sub.kind
+        outcome = "[red]accepted[/red]" if sub.accepted else f"[green]rejected[/green] [dim]{sub.error or ''}[/dim]"
+        table.add_row(sub.slot, kind, outcome)
+    console.print(table)
+    summary = export["summary"]
+    console.print(f"{summary['accepted']} of {summary['substitutions']} substitutions accepted")
+    styles = {"critical": "red", "high": "red", "medium": "yellow", "low": "blue"}
+    for hyp in hypotheses:
+        style = styles.get(hyp["severity"], "white")
+        console.print(f"  [{style}][{hyp['severity'].upper()}][/{style}] {hyp['title']}")
+    if not hypotheses:
+        console.print("[green]The program rejected every substitution[/green]")
+    if out_path:
+        console.print(f"[dim]Substitutions written to {out_path}[/dim]")
+
+
+@sim.command("boundaries")
+@click.argument("config_path", type=click.Path(exists=True, dir_okay=False))
+@click.option("--instruction", "only", multiple=True, help="Only fuzz this instruction (repeatable)")
+@click.option("--json", "as_json", is_flag=True, help="Print the cases as JSON")
+@click.option("--output", "-o", default=None, help="Write the cases to a file")
+@click.option("--project", "project_name", default=None, help="Save the cases under this project")
+@click.option("--corpus", default=None, help="Corpus directory to store crashes in (default: the project's)")
+@click.option("--import-hypotheses", is_flag=True, help="Import findings into the project's hypothesis store")
+def boundaries(config_path: str, only: tuple[str, ...], as_json: bool, output: str | None,
+               project_name: str | None, import_hypotheses: bool, corpus: str | None):
+    """Drive instructions with boundary argument values and classify panics, overflows and unexpected successes."""
+    from extensions.reporting.manifest import build_manifest
+    from extensions.simulation import BackendError
+    from extensions.simulation.boundary import BOUNDARY_VERSION, load_boundaries
+
+    path = Path(config_path)
+    data = yaml.safe_load(path.read_text()) or {}
+    if only:
+        data["instructions"] = [ix for ix in data.get("instructions") or [] if ix.get("instruction") in only]
+    try:
+        result = load_boundaries(data, base=path.parent).run()
+    except (BackendError, ValueError, TypeError, KeyError) as e:
+        console.print(f"[red]Boundary fuzzing failed: {e}[/red]")
+        raise SystemExit(1)
+
+    export = result.to_dict()
+    export["manifest"] = build_manifest(
+        "sim boundaries",
+        {"config_path": str(path.resolve()), "only": list(only), "as_json": False, "output": output,
+         "project_name": project_name, "import_hypotheses": False},
+        detectors={"arg-fuzz": BOUNDARY_VERSION},
+    )
+    hypotheses = [f.to_hypothesis() for f in result.findings()]
+    out_path = _save(export, hypotheses, output, project_name, path.stem, import_hypotheses)
+    store = _corpus(corpus, project_name)
+    if store:
+        from extensions.simulation.corpus import boundary_records
+
+        saved = sum(store.add(record) for record in boundary_records(result))
+        if not as_json:
+            console.print(f"[dim]{saved} new crashes stored in {store.root}[/dim]")
+    if as_json:
+        click.echo(json.dumps(export, indent=2, default=str))
+        return
+
+    styles = {"accepted": "green", "rejected": "green", "overflow": "red", "panic": "red",
+              "unexpected-success": "yellow"}
+    table = Table(show_header=True, header_style="bold")
+    for column in ("Instruction", "Argument", "Boundary", "Outcome"):
+        table.add_column(column)
+    for report in result.reports:
+        if report.error:
+            table.add_row(report.instruction.name, "", "", f"[dim]skipped: {report.error}[/dim]")
+        for case in report.cases:
+            style = styles[case.outcome]
+            table.add_row(report.instruction.name, case.arg, case.label, f"[{style}]{case.outcome}[/{style}]")
+    console.print(table)
+    summary = export["summary"]
+    console.print(f"{summary['cases']} boundary cases over {summary['instructions']} instructions")
+    severity_styles = {"high": "red", "medium": "yellow", "low": "blue"}
+    for hyp in hypotheses:
+        style = severity_styles.get(hyp["severity"], "white")
+        console.print(f"  [{style}][{hyp['severity'].upper()}][/{style}] {hyp['title']}")
+    if not hypotheses:
+        console.print("[green]Every boundary value was handled[/green]")
+    if out_path:
+        console.print(f"[dim]Cases written to {out_path}[/dim]")
+
+
+@sim.command("diff")
+@click.argument("config_path", type=click.Path(exists=True, dir_okay=False))
+@click.option("--json", "as_json", is_flag=True, help="Print the diff as JSON")
+@click.option("--output", "-o", default=None, help="Write the diff to a file")
+@click.option("--project", "project_name", default=None, help="Save the diff under this project")
+def diff(config_path: str, as_json: bool, output: str | None, project_name: str | None):
+    """Run a transaction corpus against the original and the patched build and diff the results."""
+    from extensions.reporting.manifest import build_manifest
+    from extensions.simulation import BackendError
+    from extensions.simulation.differential import DIFF_VERSION, load_differential
+
+    path = Path(config_path)
+    data = yaml.safe_load(path.read_text()) or {}
+    try:
+        result = load_differential(data, base=path.parent).run()
+    except (BackendError, ValueError, TypeError, KeyError) as e:
+        console.print(f"[red]Differential run failed: {e}[/red]")
+        raise SystemExit(1)
+
+    export = result.to_dict()
+    export["manifest"] = build_manifest(
+        "sim diff",
+        {"config_path": str(path.resolve()), "as_json": False, "output": output, "project_name": project_name},
+        detectors={"differential": DIFF_VERSION},
+    )
+    out_path = _save(export, [], output, project_name, path.stem, False)
+    if as_json:
+        click.echo(json.dumps(export, indent=2, default=str))
+    else:
+        styles = {"unchanged": "dim", "intended": "green", "downstream": "blue", "missing": "red",
+                  "unintended": "red"}
+        table = Table(show_header=True, header_style="bold")
+        for column in ("Transaction", "Original", "Patched", "Verdict"):
+            table.add_column(column)
+        for tx in result.transactions:
+            style = styles[tx.verdict]
+            table.add_row(tx.name, "ok" if tx.original.ok else tx.original.error or "failed",
+                          "ok" if tx.patched.ok else tx.patched.error or "failed", f"[{style}]{tx.verdict}[/{style}]")
+        console.print(table)
+        for tx in result.transactions:
+            if tx.verdict == "missing":
+                console.print(f"  [red]{tx.name}: expected the patch to make it {tx.expect}[/red]")
+            elif tx.verdict == "unintended":
+                console.print(f"  [red]{tx.name}: unintended change[/red]")
+                for account in tx.accounts:
+                    console.print(f"    {account.account}.{account.field}: {account.original} -> {account.patched}")
+                for line in tx.logs:
+                    console.print(f"    {line}", markup=False)
+        if result.verified:
+            console.print("[green]Patch verified: only the intended behavior changed[/green]")
+        if out_path:
+            console.print(f"[dim]Diff written to {out_path}[/dim]")
+    if not result.verified:
+        raise SystemExit(1)
+
+
+@sim.command("migrate")
+@click.argument("config_path", type=click.Path(exists=True, dir_okay=False))
+@click.option("--json", "as_json", is_flag=True, help="Print the results as JSON")
+@click.option("--output", "-o", default=None, help="Write the results to a file")
+@click.option("--project", "project_name", default=None, help="Save the results under this project")
+@click.option("--import-hypotheses", is_flag=True, help="Import findings into the project's hypothesis store")
+def migrate(config_path: str, as_json: bool, output: str | None, project_name: str | None, import_hypotheses: bool):
+    """Run the new build against accounts in the previous version's layout and show whether state survives."""
+    from extensions.reporting.manifest import build_manifest
+    from extensions.simulation import BackendError
+    from extensions.simulation.migration import MIGRATION_VERSION, load_migration
+
+    path = Path(config_path)
+    data = yaml.safe_load(path.read_text()) or {}
+    try:
+        result = load_migration(data, base=path.parent).run()
+    except (BackendError, ValueError, TypeError, KeyError) as e:
+        console.print(f"[red]Migration simulation failed: {e}[/red]")
+        raise SystemExit(1)
+
+    export = result.to_dict()
+    export["manifest"] = build_manifest(
+        "sim migrate",
+        {"config_path": str(path.resolve()), "as_json": False, "output": output, "project_name": project_name,
+         "import_hypotheses": False},
+        detectors={"migration-sim": MIGRATION_VERSION},
+    )
+    hypotheses = [f.to_hypothesis() for f in result.findings()]
+    out_path = _save(export, hypotheses, output, project_name, path.stem, import_hypotheses)
+    if as_json:
+        click.echo(json.dumps(export, indent=2, default=str))
+        return
+
+    for change in result.layouts:
+        handled = f" [dim](handled by {', '.join(change['handled_by'])})[/dim]" if change["handled_by"] else ""
+        console.print(f"[bold]{change['account_type']}[/bold]: {change['kind']} layout change{handled}")
+    styles = {"bricked": "red", "corrupted": "red", "migrated": "green", "compatible": "green"}
+    table = Table(show_header=True, header_style="bold")
+    for column in ("Account", "Type", "Transaction", "Verdict"):
+        table.add_column(column)
+    for fx in result.fixtures:
+        style = styles[fx.verdict]
+        outcome = "" if fx.result is None else (" (ok)" if fx.result.ok else f" ({fx.result.error or 'failed'})")
+        table.add_row(fx.fixture.pubkey, fx.fixture.target_type, f"{fx.transaction or '-'}{outcome}",
+                      f"[{style}]{fx.verdict}[/{style}]")
+    console.print(table)
+    for fx in result.fixtures:
+        for name, (old, new) in fx.differences.items():
+            console.print(f"  {fx.fixture.pubkey}.{name}: {old!r} reads as {new!r}", markup=False)
+    severity_styles = {"high": "red", "medium": "yellow", "low": "blue"}
+    for hyp in hypotheses:
+        style = severity_styles.get(hyp["severity"], "white")
+        console.print(f"  [{style}][{hyp['severity'].upper()}][/{style}] {hyp['title']}")
+    if not hypotheses:
+        console.print("[green]Every account survives the upgrade[/green]")
+    if out_path:
+        console.print(f"[dim]Results written to {out_path}[/dim]")
+
+
+@sim.command("search")
+@click.argument("config_path", type=click.Path(exists=True, dir_okay=False))
+@click.option("--iterations", type=int, default=None, help="Mutated inputs per instruction (overrides the config)")
+@click.option("--seed", type=int, default=None, help="Mutation seed (overrides the config)")
+@click.option("--instruction", "only", multiple=True, help="Only search this instruction (repeatable)")
+@click.option("--json", "as_json", is_flag=True, help="Print the search as JSON")
+@click.option("--output", "-o", default=None, help="Write the search to a file")
+@click.option("--project", "project_name", default=None, help="Save the search under this project")
+@click.option("--corpus", default=None, help="Corpus directory to resume from and store into (default: the project's)")
+@click.option("--import-hypotheses", is_flag=True, help="Import findings into the project's hypothesis store")
+def search(config_path: str, iterations: int | None, seed: int | None, only: tuple[str, ...], as_json: bool,
+           output: str | None, project_name: str | None, import_hypotheses: bool, corpus: str | None):
+    """Search for constraint bypasses and aborts with mutations steered by SBF coverage."""
+    from extensions.reporting.manifest import build_manifest
+    from extensions.simulation import BackendError
+    from extensions.simulation.search import SEARCH_VERSION, load_search
+
+    path = Path(config_path)
+    data = yaml.safe_load(path.read_text()) or {}
+    if iterations is not None:
+        data["iterations"] = iterations
+    if seed is not None:
+        data["seed"] = seed
+    if only:
+        data["instructions"] = [ix for ix in data.get("instructions") or [] if ix.get("instruction") in only]
+    store = _corpus(corpus, project_name)
+    try:
+        result = load_search(data, base=path.parent, corpus=store).run()
+    except (BackendError, ValueError, TypeError, KeyError) as e:
+        console.print(f"[red]Exploit search failed: {e}[/red]")
+        raise SystemExit(1)
+
+    export = result.to_dict()
+    export["manifest"] = build_manifest(
+        "sim search",
+        {"config_path": str(path.resolve()), "iterations": iterations, "seed": seed, "only": list(only),
+         "as_json": False, "output": output, "project_name": project_name, "import_hypotheses": False,
+         "corpus": corpus},
+        detectors={"coverage-search": SEARCH_VERSION},
+    )
+    hypotheses = [f.to_hypothesis() for f in result.findings()]
+    out_path = _save(export, hypotheses, output, project_name, path.stem, import_hypotheses)
+    if as_json:
+        click.echo(json.dumps(export, indent=2, default=str))
+        return
+
+    table = Table(show_header=True, header_style="bold")
+    for column in ("Instruction", "Executions", "Edges", "Validation Branches", "Corpus", "Findings"):
+        table.add_column(column)
+    for report, info in zip(result.reports, export["instructions"]):
+        if report.error:
+            table.add_row(report.instruction.name, "", "", "", "", f"[dim]skipped: {report.error}[/dim]")
+            continue
+        branches = info["validation_branches"]
+        table.add_row(report.instruction.name, str(report.executions), str(report.edges),
+                      f"{branches['explored']}/{branches['seen']} passed", str(len(report.corpus)),
+                      str(len(report.findings)))
+    console.print(table)
+    summary = export["summary"]
+    console.print(f"{summary['executions']} executions over {summary['instructions']} instructions")
+    if store:
+        replayed, saved = sum(r.replayed for r in result.reports), sum(r.saved for r in result.reports)
+        console.print(f"[dim]Replayed {replayed} stored inputs, stored {saved} new ones in {store.root}[/dim]")
+    severity_styles = {"critical": "red", "high": "red", "medium": "yellow", "low": "blue"}
+    for finding, hyp in zip(result.findings(), hypotheses):
+        style = severity_styles.get(hyp["severity"], "white")
+        console.print(f"  [{style}][{hyp['severity'].upper()}][/{style}] {hyp['title']} "
+                      f"[dim](execution {finding.executions})[/dim]")
+    if not hypotheses:
+        console.print("[green]No constraint bypass or abort found[/green]")
+    if out_path:
+        console.print(f"[dim]Search written to {out_path}[/dim]")
+
+
+@sim.command("corpus")
+@click.option("--project", "project_name", default=None, help="Project whose corpus to list")
+@click.option("--corpus", default=None, help="Corpus directory (default: the project's)")
+@click.option("--json", "as_json", is_flag=True, help="Print the counts as JSON")
+def corpus_stats(project_name: str | None, corpus: str | None, as_json: bool):
+    """List the stored fuzzing inputs per instruction."""
+    store = _corpus(corpus, project_name, required=True)
+    stats = store.stats()
+    if as_json:
+        click.echo(json.dumps([{"program_id": pid, "instruction": name, **counts}
+                               for (pid, name), counts in sorted(stats.items())], indent=2))
+        return
+    if not stats:
+        console.print(f"[yellow]No stored inputs in {store.root}[/yellow]")
+        return
+    table = Table(show_header=True, header_style="bold")
+    for column in ("Program", "Instruction", "Queue", "Crashes", "Witnesses"):
+        table.add_column(column)
+    for (pid, name), counts in sorted(stats.items()):
+        table.add_row(pid, name, str(counts["queue"]), str(counts["crashes"]), str(counts["witnesses"]))
+    console.print(table)
+
+
+@sim.command("replay")
+@click.argument("config_path", type=click.Path(exists=True, dir_okay=False))
+@click.option("--bucket", "buckets", multiple=True, type=click.Choice(["queue", "crashes", "witnesses"]),
+              help="Only replay this bucket (repeatable; default: crashes and witnesses)")
+@click.option("--project", "project_name", default=None, help="Project whose corpus to replay")
+@click.option("--corpus", default=None, help="Corpus directory (default: the project's)")
+@click.option("--json", "as_json", is_flag=True, help="Print the replays as JSON")
+def replay(config_path: str, buckets: tuple[str, ...], project_name: str | None, corpus: str | None,
+           as_json: bool):
+    """Run stored crashes and witnesses against the current build and report which still reproduce."""
+    from extensions.simulation import BackendError
+    from extensions.simulation.corpus import replay as replay_corpus
+    from extensions.simulation.search import load_search
+
+    store = _corpus(corpus, project_name, required=True)
+    path = Path(config_path)
+    data = yaml.safe_load(path.read_text()) or {}
+    try:
+        replays = replay_corpus(load_search(data, base=path.parent), store, buckets or ("crashes", "witnesses"))
+    except (BackendError, ValueError, TypeError, KeyError) as e:
+        console.print(f"[red]Replay failed: {e}[/red]")
+        raise SystemExit(1)
+    if as_json:
+        click.echo(json.dumps([r.to_dict() for r in replays], indent=2))
+        return
+
+    table = Table(show_header=True, header_style="bold")
+    for column in ("Input", "Instruction", "Bucket", "Stored", "Now"):
+        table.add_column(column)
+    for r in replays:
+        now = (f"[dim]{r.error}[/dim]" if r.outcome is None
+               else f"[red]{r.outcome}[/red]" if r.reproduced and r.record.bucket != "queue" else r.outcome)
+        table.add_row(r.record.id, r.record.instruction, r.record.bucket, r.record.outcome, now)
+    console.print(table)
+    reproduced = sum(1 for r in replays if r.reproduced)
+    console.print(f"{reproduced} of {len(replays)} stored inputs reproduce")
+
+
+@sim.command("minimize")
+@click.argument("config_path", type=click.Path(exists=True, dir_okay=False))
+@click.option("--project", "project_name", default=None, help="Project whose corpus to minimize")
+@click.option("--corpus", default=None, help="Corpus directory (default: the project's)")
+@click.option("--json", "as_json", is_flag=True, help="Print the counts as JSON")
+def minimize(config_path: str, project_name: str | None, corpus: str | None, as_json: bool):
+    """Minimize stored crashes and witnesses, and cut the queue to the inputs that keep its coverage."""
+    from extensions.simulation import BackendError
+    from extensions.simulation.corpus import minimize_corpus
+    from extensions.simulation.search import load_search
+
+    store = _corpus(corpus, project_name, required=True)
+    path = Path(config_path)
+    data = yaml.safe_load(path.read_text()) or {}
+    try:
+        stats = minimize_corpus(load_search(data, base=path.parent), store)
+    except (BackendError, ValueError, TypeError, KeyError) as e:
+        console.print(f"[red]Minimization failed: {e}[/red]")
+        raise SystemExit(1)
+    if as_json:
+        click.echo(json.dumps(stats, indent=2))
+        return
+    for name, counts in stats.items():
+        console.print(f"{name}: kept {counts['kept']} queue inputs, removed {counts['removed']}, "
+                      f"minimized {counts['minimized']} crashes and witnesses")
@@ -2,7 +2,7 @@
 Static analysis command for running Slither and Aderyn.
 
 Usage:
-    ./hound.py static <project_name> [--tool slither|aderyn|all] [--import]
+    ./hound.py static <project_name> [--tool slither|aderyn|all] [--import] [--min-confidence LEVEL] [--show-suppressed]
 """
 
 import sys
@@ -10,16 +10,75 @@ from pathlib import Path
 
 import click
 from rich.console import Console
+from rich.progress import BarColumn, MofNCompleteColumn, Progress, SpinnerColumn, TextColumn, TimeElapsedColumn
 from rich.table import Table
 
 # Add parent to path for imports
 sys.path.insert(0, str(Path(__file__).parent.parent))
 
 from commands.project import ProjectManager
-from extensions.static import StaticAnalysisPipeline
+from extensions.static import StaticAnalysisPipeline, confidence_level, parse_confidence
+from extensions.static.confidence import meets_confidence
+from extensions.static.project_config import ConfigError, ProjectConfig
+from extensions.static.suppressions import SuppressionError, SuppressionStore, WorkspaceBaseline
 
 
 console = Console()
+# Progress goes to stderr so piped output stays clean
+progress_console = Console(file=sys.stderr)
+
+
+class ScanProgressView:
+    """Live progress bar for a scan, fed progress events as dicts.
+
+    Shows detectors completed out of the total, with files parsed, findings
+    so far and the running detector, so long scans are not silent. Used for
+    local runs and for scans streamed from a server.
+    """
+
+    def __init__(self, title: str = "Scanning", console: Console | None = None):
+        self.title = title
+        self.progress = Progress(
+            SpinnerColumn(),
+            TextColumn("[progress.description]{task.description}"),
+            BarColumn(),
+            MofNCompleteColumn(),
+            TextColumn("[dim]{task.fields[files]} files · {task.fields[findings]} findings · {task.fields[current]}"),
+            TimeElapsedColumn(),
+            console=console or progress_console,
+            transient=True,
+        )
+        self.task = self.progress.add_task(title, total=None, files=0, findings=0, current="starting")
+        self.last: dict | None = None
+
+    def __enter__(self) -> "ScanProgressView":
+        self.progress.start()
+        return self
+
+    def __exit__(self, *exc) -> None:
+        self.progress.stop()
+
+    def update(self, event: dict) -> None:
+        kind = event.get("event")
+        if kind == "heartbeat":
+            return
+        self.last = event
+        current = {
+            "detector_started": f"running {event.get('detector')}",
+            "detector_completed": f"{event.get('detector')} done",
+            "detector_skipped": f"{event.get('detector')} not installed",
+            "files": "files counted",
+            "finished": "done",
+            "failed": "failed",
+        }.get(kind, "starting")
+        self.progress.update(
+            self.task,
+            total=event.get("detectors_total") or None,
+            completed=event.get("detectors_completed", 0),
+            files=event.get("files_parsed", 0),
+            findings=event.get("findings", 0),
+            current=current,
+        )
 
 
 @click.command("static")
@@ -41,6 +100,16 @@ console = Console()
     default="low",
     help="Minimum severity to include",
 )
+@click.option(
+    "--min-confidence",
+    default=None,
+    help="Drop hits below this confidence (high, medium, low or 0-1)",
+)
+@click.option(
+    "--show-suppressed",
+    is_flag=True,
+    help="Also show hits the project or workspace suppressed or baselined, and inline-allowed hits",
+)
 @click.option(
     "--no-dedup",
     is_flag=True,
@@ -58,6 +127,8 @@ def static(
     min_severity: str,
     no_dedup: bool,
     debug: bool,
+    min_confidence: str | None = None,
+    show_suppressed: bool = False,
 ):
     """Run static analysis on a project.
 
@@ -74,6 +145,14 @@ def static(
     source_path = Path(project["source_path"])
     project_dir = Path(project["path"])
 
+    try:
+        # The source's baskerville.toml, with the options given overriding it
+        config = ProjectConfig.load(source_path)
+        confidence_floor = parse_confidence(config.default("min_confidence", min_confidence))
+    except (ConfigError, ValueError) as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+
     console.print(f"\n[bold]Static Analysis: {project_name}[/bold]")
     console.print(f"Source: {source_path}\n")
 
@@ -98,6 +177,8 @@ def static(
         slither_config=slither_config if tool in ["slither", "all"] else None,
         aderyn_config=aderyn_config if tool in ["aderyn", "all"] else None,
         deduplicate=not no_dedup,
+        inline_suppressions=not show_suppressed,
+        config=config,
     )
 
     # Check tool availability
@@ -133,7 +214,40 @@ def static(
     # Run analysis
     console.print("[bold]Running static analysis...[/bold]")
 
-    result = pipeline.run(source_path)
+    with ScanProgressView("Running detectors") as view:
+        result = pipeline.run(source_path, progress=lambda event: view.update(event.to_dict()))
+    if confidence_floor is not None:
+        kept = [h for h in result.hypotheses if meets_confidence(h, confidence_floor)]
+        if len(kept) < len(result.hypotheses):
+            console.print(f"[dim]{len(result.hypotheses) - len(kept)} hits below confidence "
+                          f"{confidence_floor:.0%} dropped[/dim]")
+        result.hypotheses = kept
+
+    # Suppressions and baseline, migrated first if an older rule set wrote them
+    if not show_suppressed:
+        store = SuppressionStore.for_project(project_dir)
+        try:
+            report = store.load()
+        except (SuppressionError, ValueError) as e:
+            console.print(f"[yellow]Suppressions not applied: {e}[/yellow]")
+        else:
+            if report:
+                for line in report.lines():
+                    console.print(f"[yellow]{line}[/yellow]")
+            result.hypotheses, suppressed, baselined = store.apply(result.hypotheses)
+            # The workspace's own baseline file, if the source tree has one
+            try:
+                workspace = WorkspaceBaseline(source_path if source_path.is_dir() else source_path.parent).load()
+            except SuppressionError as e:
+                console.print(f"[yellow]Workspace baseline not applied: {e}[/yellow]")
+            else:
+                result.hypotheses, accepted = workspace.apply(result.hypotheses)
+                baselined += accepted
+            suppressed += result.suppressed
+            result.metadata.update(suppressed=len(suppressed), baselined=len(baselined))
+            if suppressed or baselined:
+                console.print(f"[dim]{len(suppressed)} suppressed and {len(baselined)} baselined hits hidden "
+                              f"(--show-suppressed to see them)[/dim]")
 
     # Display results
     console.print(f"\n[bold]Results:[/bold]")
@@ -183,6 +297,11 @@ def static(
         )
         console.print(f"  By severity: {severity_str}")
 
+        by_level = {"high": 0, "medium": 0, "low": 0}
+        for hyp in result.hypotheses:
+            by_level[confidence_level(hyp.get("confidence"))] += 1
+        console.print("  By confidence: " + ", ".join(f"{k}: {v}" for k, v in by_level.items() if v > 0))
+
         # Show top findings
         if debug:
             console.print("\n[bold]Top Findings:[/bold]")
@@ -199,10 +318,29 @@ def static(
                     "low": "blue",
                     "info": "dim",
                 }.get(sev, "white")
+                precision = hyp.get("properties", {}).get("precision")
                 console.print(
-                    f"  [{sev_color}][{sev.upper()}][/{sev_color}] {hyp.get('title', 'Unknown')}"
+                    f"  [{sev_color}][{sev.upper()}][/{sev_color}] {hyp.get('title', 'Unknown')} "
+                    f"[dim]({confidence_level(hyp.get('confidence'))} confidence"
+                    f"{', ' + precision if precision else ''})[/dim]"
                 )
 
+    # Record what ran so the results can be reproduced
+    from extensions.reporting.manifest import build_manifest
+
+    result.metadata["manifest"] = build_manifest(
+        "static",
+        {"project_name": project_name, "tool": tool, "import_hypotheses": import_hypotheses,
+         "min_severity": min_severity, "no_dedup": no_dedup, "debug": False, "min_confidence": min_confidence,
+         "show_suppressed": show_suppressed},
+        source=source_path,
+        detectors={
+            name: meta.get("version")
+            for name, meta in result.metadata.get("tools", {}).items()
+            if meta.get("available", True)
+        },
+    )
+
     # Save results
     static_dir = project_dir / "static_analysis"
     paths = pipeline.save_results(result, static_dir)
@@ -216,4 +354,13 @@ def static(
             f"\n[green]Imported {imported} hypotheses to store[/green]"
         )
 
+    # Auditor notes left as `@baskerville` comments, linked to the hits at the same code
+    from extensions.reporting.annotations import ingest_annotations
+
+    notes = ingest_annotations(project_dir, source_path)
+    current = [a for a in notes["annotations"] if not a.get("removed_at")]
+    if current:
+        console.print(f"[dim]{len(current)} auditor annotations ({notes['added']} new, {notes['removed']} removed), "
+                      f"{sum(1 for a in current if a['findings'])} at hits[/dim]")
+
     console.print("\n[bold green]Static analysis complete![/bold green]")
@@ -0,0 +1,161 @@
+"""
+Managed test-validator commands.
+
+Usage:
+    ./baskerville.py validator plan <project_or_path> [--poc FILE ...] [--hypothesis ID] [--clone ADDR ...] [--json]
+    ./baskerville.py validator run <project_or_path> [--poc FILE ...] [--hypothesis ID] [--url URL] [--exec CMD]
+"""
+
+import json
+import os
+import subprocess
+import sys
+from datetime import datetime
+from pathlib import Path
+
+import click
+from rich.console import Console
+from rich.table import Table
+
+sys.path.insert(0, str(Path(__file__).parent.parent))
+
+from commands.ir import resolve_source
+from extensions.poc import ClonePlan, TestValidator, ValidatorError, extract_addresses, plan_clones
+from extensions.rpc.providers import redact_url
+
+
+console = Console()
+
+
+def _poc_files(project_dir: Path | None, pocs: tuple[str, ...], hypothesis: str | None) -> list[Path]:
+    files = [Path(p) for p in pocs]
+    if hypothesis:
+        if project_dir is None:
+            console.print("[red]--hypothesis needs a project name, not a path[/red]")
+            raise SystemExit(1)
+        poc_dir = project_dir / "poc" / hypothesis
+        if not poc_dir.is_dir():
+            console.print(f"[red]No imported PoC for hypothesis '{hypothesis}' (run `poc import` first)[/red]")
+            raise SystemExit(1)
+        files += sorted(p for p in poc_dir.iterdir() if p.is_file() and p.name != "metadata.json")
+    return files
+
+
+def _build_plan(target: str, pocs: tuple[str, ...], hypothesis: str | None, clones: tuple[str, ...],
+                url: str | None) -> ClonePlan:
+    """Collect the PoC's accounts and resolve them against the source cluster."""
+    from extensions.ir.anchor_config import load_anchor_config
+
+    source, project_dir = resolve_source(target)
+    try:
+        anchor = load_anchor_config(source)
+    except ValueError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    addresses = list(clones)
+    for path in _poc_files(project_dir, pocs, hypothesis):
+        addresses += extract_addresses(path.read_text(errors="replace"))
+    try:
+        return plan_clones(addresses, url=url, anchor=anchor)
+    except ValidatorError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+
+
+def _record_run(project_dir: Path | None, hypothesis: str | None, command: str, code: int) -> None:
+    """Keep the outcome of a PoC run in the imported PoC's metadata (feeds engagement summaries)."""
+    if project_dir is None or not hypothesis:
+        return
+    metadata_file = project_dir / "poc" / hypothesis / "metadata.json"
+    try:
+        metadata = json.loads(metadata_file.read_text()) if metadata_file.exists() else {"hypothesis_id": hypothesis}
+    except ValueError:
+        return
+    metadata.setdefault("runs", []).append(
+        {"at": datetime.now().isoformat(), "command": command, "exit_code": code, "passed": code == 0}
+    )
+    metadata_file.write_text(json.dumps(metadata, indent=2))
+
+
+def _print_plan(plan: ClonePlan) -> None:
+    source = redact_url(plan.url) if plan.url else None
+    table = Table(show_header=True, header_style="bold")
+    table.add_column("Address")
+    table.add_column("Loaded as")
+    for address in plan.accounts:
+        table.add_row(address, f"--clone from {source}")
+    for address in plan.upgradeable_programs:
+        table.add_row(address, f"--clone-upgradeable-program from {source}")
+    for address, path in plan.programs.items():
+        table.add_row(address, f"--bpf-program {path}")
+    for address, path in plan.account_files.items():
+        table.add_row(address, f"--account {path}")
+    for address, reason in plan.skipped.items():
+        table.add_row(f"[dim]{address}[/dim]", f"[dim]skipped: {reason}[/dim]")
+    console.print(table)
+
+
+@click.group("validator")
+def validator():
+    """Managed solana-test-validator for PoCs."""
+    pass
+
+
+def _plan_options(f):
+    f = click.option("--url", default=None,
+                     help="Cluster or RPC provider to clone from (default: Anchor.toml [test.validator] url)")(f)
+    f = click.option("--clone", "clones", multiple=True, help="Additional account to clone")(f)
+    f = click.option("--hypothesis", default=None, help="Use the PoC imported for this hypothesis")(f)
+    f = click.option("--poc", "pocs", multiple=True, type=click.Path(exists=True, dir_okay=False),
+                     help="PoC source to scan for accounts")(f)
+    return click.argument("target")(f)
+
+
+@validator.command("plan")
+@_plan_options
+@click.option("--json", "as_json", is_flag=True, help="Print the plan and validator flags as JSON")
+def plan(target: str, pocs: tuple[str, ...], hypothesis: str | None, clones: tuple[str, ...], url: str | None,
+         as_json: bool):
+    """Show which accounts and programs the validator would clone or load."""
+    clone_plan = _build_plan(target, pocs, hypothesis, clones, url)
+    args = [redact_url(a) if "://" in a else a for a in clone_plan.args()]
+    if as_json:
+        click.echo(json.dumps({**clone_plan.to_dict(), "args": args}, indent=2))
+        return
+    _print_plan(clone_plan)
+    console.print(f"[dim]solana-test-validator {' '.join(args)}[/dim]")
+
+
+@validator.command("run")
+@_plan_options
+@click.option("--exec", "command", default=None,
+              help="Run this command against the validator, then stop it (e.g. 'anchor test --skip-local-validator')")
+@click.option("--timeout", default=60.0, help="Seconds to wait for the validator to become healthy")
+@click.option("--binary", default="solana-test-validator", help="Validator executable")
+def run(target: str, pocs: tuple[str, ...], hypothesis: str | None, clones: tuple[str, ...], url: str | None,
+        command: str | None, timeout: float, binary: str):
+    """Start a validator with the PoC's accounts cloned; stop it on exit, Ctrl-C or SIGTERM."""
+    clone_plan = _build_plan(target, pocs, hypothesis, clones, url)
+    _print_plan(clone_plan)
+    source, project_dir = resolve_source(target)
+    cwd = source if source.is_dir() else source.parent
+    try:
+        with TestValidator(clone_plan, binary=binary, startup_timeout=timeout) as node:
+            console.print(f"[green]Validator ready at {node.rpc_url}[/green] (logs: {node.log_file})")
+            if command:
+                env = {**os.environ, "ANCHOR_PROVIDER_URL": node.rpc_url, "SOLANA_URL": node.rpc_url,
+                       "SOLANA_WS_URL": node.ws_url}
+                code = subprocess.run(command, shell=True, cwd=cwd, env=env).returncode
+                _record_run(project_dir, hypothesis, command, code)
+                if code:
+                    raise SystemExit(code)
+                return
+            console.print("Press Ctrl-C to stop")
+            node.wait()
+            console.print(f"[red]Validator exited:[/red]\n{node.log_tail()}")
+            raise SystemExit(1)
+    except ValidatorError as e:
+        console.print(f"[red]{e}[/red]")
+        raise SystemExit(1)
+    except KeyboardInterrupt:
+        console.print("Validator stopped")
@@ -6,6 +6,7 @@ Handles the full lifecycle of participating in audit contests:
 - Audit execution with Hound
 - Human review workflow
 - Platform-specific export formatting
+- File-per-finding Markdown export for audit repos
 - Submission tracking (never auto-submit)
 """
 
@@ -14,6 +15,7 @@ from .finding import Finding, FindingState, Severity, InvalidFindingTransition
 from .scraper import ContestScraper, ScrapedContest
 from .storage import BountyStorage
 from .formatters import get_formatter, FORMATTERS
+from .finding_files import FindingFileWriter
 
 __all__ = [
     # Contest
@@ -33,4 +35,5 @@ __all__ = [
     # Formatters
     "get_formatter",
     "FORMATTERS",
+    "FindingFileWriter",
 ]
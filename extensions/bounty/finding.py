@@ -9,10 +9,12 @@ State Machine:
 """
 
 from dataclasses import dataclass, field
-from datetime import datetime
+from datetime import date, datetime
 from enum import Enum
 from typing import Any
 
+from ..knowledge.taxonomy import classify, hypothesis_tags
+
 
 class FindingState(Enum):
     """Finding lifecycle states."""
@@ -84,6 +86,7 @@ class Finding:
     # Classification
     vulnerability_type: str = ""  # reentrancy, access-control, etc.
     category: str = ""  # From Solodit checklist categories
+    tags: list[str] = field(default_factory=list)  # Taxonomy tags (extensions/knowledge/taxonomy.py)
 
     # Evidence
     code_snippet: str = ""
@@ -104,6 +107,11 @@ class Finding:
     reviewer_notes: str = ""
     rejection_reason: str = ""
 
+    # Assignment (who verifies the finding, who signs off, and by when)
+    assignee: str = ""
+    reviewer: str = ""
+    due_date: date | None = None
+
     # Timestamps
     detected_at: datetime = field(default_factory=datetime.now)
     triaged_at: datetime | None = None
@@ -119,6 +127,11 @@ class Finding:
     metadata: dict[str, Any] = field(default_factory=dict)
     chain: str = "evm"  # Chain: evm, solana, sui, aptos, etc.
 
+    def __post_init__(self):
+        if not self.tags:
+            self.tags = classify(texts=[self.vulnerability_type, self.category, self.title],
+                                 fallback_texts=[self.description])
+
     def transition_to(self, new_state: FindingState) -> None:
         """Transition to a new state.
 
@@ -150,6 +163,16 @@ class Finding:
         elif new_state == FindingState.EXPORTED:
             self.exported_at = now
 
+    def assign(self, assignee: str | None = None, reviewer: str | None = None,
+               due_date: date | None = None) -> None:
+        """Set the finding's assignee, reviewer and/or due date (``""`` clears a name)."""
+        if assignee is not None:
+            self.assignee = assignee.strip()
+        if reviewer is not None:
+            self.reviewer = reviewer.strip()
+        if due_date is not None:
+            self.due_date = due_date
+
     def can_transition_to(self, new_state: FindingState) -> bool:
         """Check if a transition is valid."""
         return new_state in FINDING_STATE_TRANSITIONS.get(self.state, [])
@@ -191,6 +214,12 @@ class Finding:
             FindingState.REFINED,
         ]
 
+    @property
+    def is_overdue(self) -> bool:
+        """Check if the finding is still awaiting a verdict past its due date."""
+        pending = self.state in [FindingState.DETECTED, FindingState.TRIAGED]
+        return pending and self.due_date is not None and self.due_date < date.today()
+
     @property
     def is_exportable(self) -> bool:
         """Check if finding can be exported."""
@@ -230,6 +259,7 @@ class Finding:
             "state_history": self.state_history,
             "vulnerability_type": self.vulnerability_type,
             "category": self.category,
+            "tags": self.tags,
             "code_snippet": self.code_snippet,
             "proof_of_concept": self.proof_of_concept,
             "impact": self.impact,
@@ -241,6 +271,9 @@ class Finding:
             "confidence": self.confidence,
             "reviewer_notes": self.reviewer_notes,
             "rejection_reason": self.rejection_reason,
+            "assignee": self.assignee,
+            "reviewer": self.reviewer,
+            "due_date": self.due_date.isoformat() if self.due_date else None,
             "detected_at": self.detected_at.isoformat(),
             "triaged_at": self.triaged_at.isoformat() if self.triaged_at else None,
             "accepted_at": self.accepted_at.isoformat() if self.accepted_at else None,
@@ -270,6 +303,7 @@ class Finding:
             state_history=data.get("state_history", []),
             vulnerability_type=data.get("vulnerability_type", ""),
             category=data.get("category", ""),
+            tags=data.get("tags", []),
             code_snippet=data.get("code_snippet", ""),
             proof_of_concept=data.get("proof_of_concept", ""),
             impact=data.get("impact", ""),
@@ -281,6 +315,9 @@ class Finding:
             confidence=data.get("confidence", 0.0),
             reviewer_notes=data.get("reviewer_notes", ""),
             rejection_reason=data.get("rejection_reason", ""),
+            assignee=data.get("assignee", ""),
+            reviewer=data.get("reviewer", ""),
+            due_date=date.fromisoformat(data["due_date"]) if data.get("due_date") else None,
             detected_at=datetime.fromisoformat(data["detected_at"]) if data.get("detected_at") else datetime.now(),
             triaged_at=datetime.fromisoformat(data["triaged_at"]) if data.get("triaged_at") else None,
             accepted_at=datetime.fromisoformat(data["accepted_at"]) if data.get("accepted_at") else None,
@@ -321,6 +358,7 @@ class Finding:
             function_name=hypothesis.get("location", {}).get("function", ""),
             contract_name=hypothesis.get("location", {}).get("contract", ""),
             vulnerability_type=hypothesis.get("vulnerability_type", ""),
+            tags=hypothesis_tags(hypothesis),
             code_snippet=hypothesis.get("evidence", {}).get("code", ""),
             impact=hypothesis.get("impact", ""),
             recommendation=hypothesis.get("recommendation", ""),
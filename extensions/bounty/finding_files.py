@@ -0,0 +1,173 @@
+"""
+File-per-finding Markdown export.
+
+Writes each finding to its own Markdown file with YAML front matter, the
+layout many audit firms keep in a `findings/` directory of the audit repo
+so findings can be reviewed, discussed, and edited as ordinary git diffs.
+
+    findings/
+        README.md                      index table, sorted by severity
+        H-01-missing-signer-check.md
+        M-01-unchecked-oracle-price.md
+        poc/H-01-missing-signer-check.t.sol
+
+File names are stable across re-exports (severity prefix + per-severity
+number + title slug), and front matter holds no export timestamps, so an
+unchanged finding produces no diff.
+"""
+
+import re
+from pathlib import Path
+
+import yaml
+
+from extensions.static.confidence import confidence_level
+
+from .finding import Finding, Severity
+
+_SEVERITY_PREFIX = {
+    Severity.CRITICAL: "C",
+    Severity.HIGH: "H",
+    Severity.MEDIUM: "M",
+    Severity.LOW: "L",
+    Severity.INFORMATIONAL: "I",
+    Severity.GAS: "G",
+}
+_POC_EXTENSIONS = {"evm": ".t.sol", "ethereum": ".t.sol", "solana": ".rs", "sui": ".move", "aptos": ".move"}
+
+
+def slugify(text: str, max_length: int = 60) -> str:
+    """Lowercase, dash-separated file name fragment."""
+    slug = re.sub(r"[^a-z0-9]+", "-", text.lower()).strip("-")
+    return slug[:max_length].rstrip("-") or "finding"
+
+
+class FindingFileWriter:
+    """Writes findings as individual Markdown files under ``<output>/findings``."""
+
+    def __init__(self, output_dir: Path, project_dir: Path | None = None):
+        """Initialize writer.
+
+        Args:
+            output_dir: Directory that will contain `findings/`
+            project_dir: Hound project directory, used to link imported PoCs
+        """
+        self.findings_dir = Path(output_dir) / "findings"
+        self.project_dir = Path(project_dir) if project_dir else None
+
+    def file_stems(self, findings: list[Finding]) -> dict[str, str]:
+        """Finding id → file stem such as "H-01-missing-signer-check"."""
+        stems: dict[str, str] = {}
+        counters: dict[Severity, int] = {}
+        for finding in sorted(findings, key=lambda f: (f.severity_rank, f.detected_at, f.id)):
+            counters[finding.severity] = counters.get(finding.severity, 0) + 1
+            prefix = _SEVERITY_PREFIX.get(finding.severity, "X")
+            stems[finding.id] = f"{prefix}-{counters[finding.severity]:02d}-{slugify(finding.title)}"
+        return stems
+
+    def write(self, findings: list[Finding], contest_name: str = "") -> list[Path]:
+        """Write every finding plus a README index; returns the finding file paths."""
+        self.findings_dir.mkdir(parents=True, exist_ok=True)
+        stems = self.file_stems(findings)
+        ordered = sorted(findings, key=lambda f: (f.severity_rank, stems[f.id]))
+        paths = []
+        for finding in ordered:
+            path = self.findings_dir / f"{stems[finding.id]}.md"
+            path.write_text(self.render(finding, stems[finding.id]))
+            paths.append(path)
+        (self.findings_dir / "README.md").write_text(self.render_index(ordered, stems, contest_name))
+        return paths
+
+    def poc_link(self, finding: Finding, stem: str) -> str | None:
+        """Relative link to the finding's PoC, writing inline PoC code to `poc/` if needed."""
+        explicit = finding.metadata.get("poc_url") or finding.metadata.get("poc_path")
+        if explicit:
+            return str(explicit)
+        if finding.proof_of_concept.strip():
+            poc_dir = self.findings_dir / "poc"
+            poc_dir.mkdir(exist_ok=True)
+            ext = _POC_EXTENSIONS.get(finding.chain.lower(), ".md")
+            poc_file = poc_dir / f"{stem}{ext}"
+            poc_file.write_text(_strip_fence(finding.proof_of_concept))
+            return f"poc/{poc_file.name}"
+        if self.project_dir and finding.hypothesis_id:
+            imported = self.project_dir / "poc" / finding.hypothesis_id
+            if imported.is_dir():
+                return str(imported)
+        return None
+
+    def front_matter(self, finding: Finding, poc: str | None) -> dict:
+        location = finding.file_path
+        if location and finding.line_start:
+            location += f"#L{finding.line_start}"
+            if finding.line_end and finding.line_end != finding.line_start:
+                location += f"-L{finding.line_end}"
+        meta = {
+            "id": finding.id,
+            "title": finding.title,
+            "severity": finding.severity.value,
+            "status": finding.state.value,
+            "type": finding.vulnerability_type or None,
+            "category": finding.category or None,
+            "chain": finding.chain,
+            "location": location or None,
+            "contract": finding.contract_name or None,
+            "function": finding.function_name or None,
+            "confidence": finding.confidence or None,
+            "confidence_level": confidence_level(finding.confidence) if finding.confidence else None,
+            "hypothesis": finding.hypothesis_id or None,
+            "poc": poc,
+            "references": finding.references or None,
+            "assignee": finding.assignee or None,
+            "reviewer": finding.reviewer or None,
+            "due": finding.due_date.isoformat() if finding.due_date else None,
+        }
+        return {k: v for k, v in meta.items() if v is not None}
+
+    def render(self, finding: Finding, stem: str) -> str:
+        """Markdown document for one finding."""
+        poc = self.poc_link(finding, stem)
+        meta = self.front_matter(finding, poc)
+        front = yaml.safe_dump(meta, sort_keys=False, allow_unicode=True).strip()
+        sections = [f"---\n{front}\n---", f"# [{_label(stem)}] {finding.title}"]
+        if "location" in meta:
+            sections.append(f"**Location:** `{meta['location']}`")
+        sections.append(f"## Description\n\n{finding.description.strip()}")
+        if finding.code_snippet.strip():
+            sections.append(_fenced(finding.code_snippet, finding.chain))
+        if finding.impact.strip():
+            sections.append(f"## Impact\n\n{finding.impact.strip()}")
+        if poc:
+            sections.append(f"## Proof of Concept\n\nSee [{Path(poc).name}]({poc}).")
+        if finding.recommendation.strip():
+            sections.append(f"## Recommendation\n\n{finding.recommendation.strip()}")
+        if finding.references:
+            sections.append("## References\n\n" + "\n".join(f"- {r}" for r in finding.references))
+        return "\n\n".join(sections) + "\n"
+
+    def render_index(self, findings: list[Finding], stems: dict[str, str], contest_name: str = "") -> str:
+        title = f"# Findings — {contest_name}" if contest_name else "# Findings"
+        lines = [title, "", "| ID | Title | Severity | Status |", "|----|-------|----------|--------|"]
+        for finding in findings:
+            stem = stems[finding.id]
+            title_cell = finding.title.replace("|", "\\|")
+            lines.append(f"| {_label(stem)} | [{title_cell}]({stem}.md) | {finding.severity.value} | {finding.state.value} |")
+        return "\n".join(lines) + "\n"
+
+
+def _label(stem: str) -> str:
+    """`H-01-some-title` -> `H-01`."""
+    return "-".join(stem.split("-", 2)[:2])
+
+
+def _strip_fence(code: str) -> str:
+    """Drop a surrounding ```lang fence so the PoC file is plain source."""
+    m = re.fullmatch(r"\s*```[\w+-]*\n(.*?)\n?```\s*", code, re.DOTALL)
+    return (m.group(1) if m else code.strip()) + "\n"
+
+
+def _fenced(code: str, chain: str) -> str:
+    if code.lstrip().startswith("```"):
+        return code.strip()
+    language = {"solana": "rust", "sui": "move", "aptos": "move"}.get(chain.lower(), "solidity")
+    return f"```{language}\n{code.strip()}\n```"
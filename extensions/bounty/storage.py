@@ -204,8 +204,15 @@ class BountyStorage:
         contest_id: str,
         state: FindingState | None = None,
         severity: str | None = None,
+        assignee: str | None = None,
+        reviewer: str | None = None,
+        overdue: bool = False,
     ) -> list[Finding]:
-        """List findings for a contest with optional filters."""
+        """List findings for a contest with optional filters.
+
+        ``assignee``/``reviewer`` compare case-insensitively; ``""`` selects
+        findings nobody is assigned to (or reviewing).
+        """
         findings = []
         contest_findings_dir = self.findings_dir / contest_id
 
@@ -222,6 +229,12 @@ class BountyStorage:
                 continue
             if severity and finding.severity.value != severity:
                 continue
+            if assignee is not None and finding.assignee.lower() != assignee.strip().lower():
+                continue
+            if reviewer is not None and finding.reviewer.lower() != reviewer.strip().lower():
+                continue
+            if overdue and not finding.is_overdue:
+                continue
 
             findings.append(finding)
 
@@ -261,6 +274,7 @@ class BountyStorage:
 
         by_state = {}
         by_severity = {}
+        by_assignee = {}
 
         for finding in findings:
             state = finding.state.value
@@ -268,11 +282,16 @@ class BountyStorage:
 
             by_state[state] = by_state.get(state, 0) + 1
             by_severity[severity] = by_severity.get(severity, 0) + 1
+            if finding.assignee:
+                by_assignee[finding.assignee] = by_assignee.get(finding.assignee, 0) + 1
 
         return {
             "total_findings": len(findings),
             "by_state": by_state,
             "by_severity": by_severity,
+            "by_assignee": by_assignee,
+            "unassigned": sum(1 for f in findings if not f.assignee),
+            "overdue": sum(1 for f in findings if f.is_overdue),
             "accepted": by_state.get("accepted", 0) + by_state.get("refined", 0) + by_state.get("exported", 0),
             "rejected": by_state.get("rejected", 0),
             "pending_review": by_state.get("detected", 0) + by_state.get("triaged", 0),
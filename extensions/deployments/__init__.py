@@ -0,0 +1,48 @@
+"""
+Deployment consistency across clusters and chains.
+
+- Snapshots: per deployment, the hash of each program's executable (behind
+  EIP-1967 proxies on EVM chains), when it was deployed, who can upgrade it,
+  and the values and authorities of its configuration accounts or getters
+- Checks: programs missing from a deployment, deployments running a build
+  other than the audited one another deployment runs, diverging builds,
+  weaker authority setups and configuration values that differ, as findings
+"""
+
+from .consistency import CHECKER_VERSION, DeploymentFinding, check_deployments
+from .snapshot import (
+    EIP1967_ADMIN,
+    EIP1967_IMPLEMENTATION,
+    SNAPSHOT_VERSION,
+    AccountSpec,
+    Authority,
+    CallSpec,
+    Deployment,
+    DeploymentSet,
+    ProgramState,
+    Snapshot,
+    abi_decode_word,
+    executable_hash,
+    load_deployments,
+    take_snapshot,
+)
+
+__all__ = [
+    "CHECKER_VERSION",
+    "EIP1967_ADMIN",
+    "EIP1967_IMPLEMENTATION",
+    "SNAPSHOT_VERSION",
+    "AccountSpec",
+    "Authority",
+    "CallSpec",
+    "Deployment",
+    "DeploymentFinding",
+    "DeploymentSet",
+    "ProgramState",
+    "Snapshot",
+    "abi_decode_word",
+    "check_deployments",
+    "executable_hash",
+    "load_deployments",
+    "take_snapshot",
+]
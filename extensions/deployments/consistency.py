@@ -0,0 +1,215 @@
+"""
+Cross-deployment consistency checks.
+
+Compares the snapshots of one protocol's deployments and reports where
+they diverge:
+
+- missing-program: a program deployed elsewhere is absent or closed here
+- stale-deployment: a deployment runs a build other than the audited one
+  while another deployment runs the audited build (mainnet missing a fix
+  devnet has)
+- version-divergence: deployments on one chain run different builds,
+  the older deployments possibly missing fixes
+- authority-divergence: a role (upgrade authority, config admin) is held
+  by a wallet where another deployment has a program (a multisig), or the
+  program is upgradeable where another deployment made it immutable
+- config-divergence: a configuration value differs across deployments
+
+Severities go up when the diverging deployment is a production one.
+"""
+
+from dataclasses import dataclass, field
+from datetime import datetime, timezone
+from typing import Any
+
+from .snapshot import Snapshot
+
+CHECKER_VERSION = "1.0.0"
+
+# Riskier holders rank higher; "unknown" is not compared
+_AUTHORITY_RANK = {"none": 0, "program": 1, "wallet": 2}
+
+_TITLES = {
+    "missing-program": "Program `{subject}` is missing from {where}",
+    "stale-deployment": "{where} runs a build of `{subject}` other than the audited one",
+    "version-divergence": "Deployments run different builds of `{subject}`",
+    "authority-divergence": "`{subject}` is held by a weaker authority on {where}",
+    "config-divergence": "Configuration `{subject}` differs across deployments",
+}
+
+_VULNERABILITY_TYPES = {
+    "missing-program": "deployment-missing-program",
+    "stale-deployment": "deployment-stale-build",
+    "version-divergence": "deployment-version-divergence",
+    "authority-divergence": "deployment-authority-divergence",
+    "config-divergence": "deployment-config-divergence",
+}
+
+
+@dataclass
+class DeploymentFinding:
+    """A divergence between deployments."""
+
+    kind: str
+    severity: str
+    # Program name, authority key or parameter
+    subject: str
+    message: str
+    # The deployments the finding is about (the ones diverging)
+    deployments: list[str] = field(default_factory=list)
+    # Deployment -> what it has (hash, holder, value)
+    values: dict[str, Any] = field(default_factory=dict)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"kind": self.kind, "severity": self.severity, "subject": self.subject, "message": self.message,
+                "deployments": self.deployments, "values": self.values}
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        where = ", ".join(self.deployments)
+        return {
+            "title": _TITLES[self.kind].format(subject=self.subject, where=where),
+            "description": self.message + ".",
+            "vulnerability_type": _VULNERABILITY_TYPES[self.kind],
+            "severity": self.severity,
+            "confidence": 0.9 if self.kind in ("stale-deployment", "missing-program") else 0.6,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "deployment-check",
+                "category": "deployment",
+                "subject": self.subject,
+                "deployments": self.deployments,
+                "values": self.values,
+            },
+        }
+
+
+def _date(timestamp: int | None) -> str:
+    if not timestamp:
+        return "at an unknown time"
+    return datetime.fromtimestamp(timestamp, timezone.utc).strftime("%Y-%m-%d")
+
+
+def _severity(snapshots: list[Snapshot], production: str, other: str) -> str:
+    return production if any(s.production for s in snapshots) else other
+
+
+def _short(value: str | None) -> str:
+    return value[:12] if value else "none"
+
+
+def _programs(snapshots: list[Snapshot], builds: dict[str, str]) -> list[DeploymentFinding]:
+    findings = []
+    names = list(dict.fromkeys(name for s in snapshots for name in s.programs))
+    for name in names:
+        having = [s for s in snapshots if name in s.programs]
+        found = [s for s in having if s.programs[name].found]
+        missing = [s for s in having if not s.programs[name].found]
+        if found and missing:
+            where = ", ".join(s.deployment for s in missing)
+            reasons = "; ".join(f"{s.deployment}: {s.programs[name].error}" for s in missing)
+            findings.append(DeploymentFinding(
+                "missing-program", _severity(missing, "high", "medium"), name,
+                f"`{name}` is deployed on {', '.join(s.deployment for s in found)} but not on {where} ({reasons})",
+                [s.deployment for s in missing],
+                {s.deployment: s.programs[name].address for s in having},
+            ))
+        for chain in dict.fromkeys(s.chain for s in found):
+            on_chain = [s for s in found if s.chain == chain and s.programs[name].hash]
+            hashes = {s.programs[name].hash for s in on_chain}
+            if len(hashes) < 2:
+                continue
+            values = {s.deployment: {"hash": s.programs[name].hash, "deployed_slot": s.programs[name].deployed_slot,
+                                     "deployed_at": s.programs[name].deployed_at} for s in on_chain}
+            audited = builds.get(name)
+            current = [s for s in on_chain if s.programs[name].hash == audited]
+            if audited and current:
+                stale = [s for s in on_chain if s not in current]
+                running = ", ".join(f"{s.deployment} runs {_short(s.programs[name].hash)}" for s in stale)
+                findings.append(DeploymentFinding(
+                    "stale-deployment", _severity(stale, "high", "low"), name,
+                    f"The audited build of `{name}` ({_short(audited)}) is deployed on "
+                    f"{', '.join(s.deployment for s in current)}, but {running}; the difference may include "
+                    "security fixes",
+                    [s.deployment for s in stale], values,
+                ))
+                continue
+            # Without the audited build, the deployments behind the most recent one
+            latest = max(on_chain, key=lambda s: s.programs[name].deployed_at or 0)
+            behind = [s for s in on_chain if s.programs[name].hash != latest.programs[name].hash]
+            described = ", ".join(f"{s.deployment} ({_short(s.programs[name].hash)}, deployed "
+                                  f"{_date(s.programs[name].deployed_at)})" for s in on_chain)
+            suffix = "; none matches the audited build" if audited else ""
+            findings.append(DeploymentFinding(
+                "version-divergence", _severity(behind, "medium", "low"), name,
+                f"`{name}` differs across deployments: {described}; the latest deploy is on "
+                f"{latest.deployment}{suffix}",
+                [s.deployment for s in behind], values,
+            ))
+    return findings
+
+
+def _authorities(snapshots: list[Snapshot]) -> list[DeploymentFinding]:
+    findings = []
+    keys = list(dict.fromkeys(key for s in snapshots for key in s.authorities))
+    for key in keys:
+        holders = {s.deployment: s.authorities[key] for s in snapshots
+                   if key in s.authorities and s.authorities[key].kind in _AUTHORITY_RANK}
+        ranks = {d: _AUTHORITY_RANK[a.kind] for d, a in holders.items()}
+        if len(set(ranks.values())) < 2:
+            continue
+        worst = max(ranks.values())
+        weaker = [s for s in snapshots if ranks.get(s.deployment) == worst]
+        strongest = min(ranks.values())
+        others = [d for d, r in ranks.items() if r == strongest]
+        kind = next(a.kind for d, a in holders.items() if ranks[d] == worst)
+        best = holders[others[0]].kind
+        upgrade = key.endswith(".upgrade_authority")
+        if kind == "wallet" and best == "program":
+            severity = _severity(weaker, "high", "low")
+            detail = f"a single wallet holds it on {', '.join(s.deployment for s in weaker)}, a program " \
+                     f"(multisig or governance) on {', '.join(others)}"
+        elif upgrade and best == "none":
+            severity = _severity(weaker, "medium", "low")
+            detail = f"the program is upgradeable on {', '.join(s.deployment for s in weaker)} but immutable " \
+                     f"on {', '.join(others)}"
+        else:
+            severity = _severity(weaker, "medium", "info")
+            detail = f"it is held ({kind}) on {', '.join(s.deployment for s in weaker)} but unset on " \
+                     f"{', '.join(others)}"
+        findings.append(DeploymentFinding(
+            "authority-divergence", severity, key, f"`{key}` is set up differently: {detail}",
+            [s.deployment for s in weaker],
+            {d: {"address": a.address, "kind": a.kind, "owner": a.owner} for d, a in holders.items()},
+        ))
+    return findings
+
+
+def _params(snapshots: list[Snapshot]) -> list[DeploymentFinding]:
+    findings = []
+    keys = list(dict.fromkeys(key for s in snapshots for key in s.params))
+    for key in keys:
+        values = {s.deployment: s.params[key] for s in snapshots if key in s.params}
+        distinct = []
+        for value in values.values():
+            if value not in distinct:
+                distinct.append(value)
+        if len(distinct) < 2:
+            continue
+        # The deployments holding a value fewer others share
+        counts = [list(values.values()).count(v) for v in distinct]
+        outliers = [d for d, v in values.items() if counts[distinct.index(v)] < max(counts)] or list(values)
+        described = ", ".join(f"{d}: {v}" for d, v in values.items())
+        diverging = [s for s in snapshots if s.deployment in outliers]
+        findings.append(DeploymentFinding(
+            "config-divergence", _severity(diverging, "low", "info"), key,
+            f"`{key}` differs across deployments ({described})", outliers, values,
+        ))
+    return findings
+
+
+def check_deployments(snapshots: list[Snapshot], builds: dict[str, str] | None = None) -> list[DeploymentFinding]:
+    """Divergences between ``snapshots``; ``builds`` maps program names to audited build hashes."""
+    return _programs(snapshots, builds or {}) + _authorities(snapshots) + _params(snapshots)
@@ -0,0 +1,455 @@
+"""
+Deployment snapshots.
+
+A protocol deployed to several clusters or chains is described by a
+deployments file: one entry per deployment with its RPC provider and the
+addresses of its programs (contracts on EVM chains) and configuration
+accounts, plus the account types to decode those with.
+
+    workspace: ../vault              # Anchor workspace: ids per cluster, account types, audited builds
+    accounts:
+      config: {type: Config}         # Decoded with the IDL or the #[account] structs
+    deployments:
+      mainnet: {provider: helius, accounts: {config: <address>}}
+      devnet: {accounts: {config: <address>}}         # Program ids from [programs.devnet]
+      base:
+        chain: evm
+        provider: https://mainnet.base.org
+        production: true
+        programs: {vault: "0x..."}
+        calls: {config.fee_bps: {contract: vault, call: "feeBps()", type: uint16}}
+
+``take_snapshot`` reads what one deployment runs: the hash of each
+program's executable (of the implementation behind EIP-1967 proxies), when
+it was deployed, who can upgrade it, and the configuration values and
+authorities held in its accounts or returned by its getters.
+"""
+
+import base64
+import hashlib
+import re
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Any, Protocol
+
+from extensions.ir.anchor_config import b58encode, load_anchor_config, normalize_cluster
+from extensions.rpc import RpcError
+from extensions.rpc.providers import PROVIDERS, PUBLIC_URLS
+
+SNAPSHOT_VERSION = "1.0.0"
+
+UPGRADEABLE_LOADER = "BPFLoaderUpgradeab1e11111111111111111111111"
+SYSTEM_PROGRAM = "11111111111111111111111111111111"
+
+# Programdata: u32 tag (3) + u64 slot + optional authority, then the ELF
+_PROGRAMDATA_HEADER = 45
+
+# EIP-1967 proxy slots: keccak256("eip1967.proxy.implementation") - 1 and keccak256("eip1967.proxy.admin") - 1
+EIP1967_IMPLEMENTATION = "0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc"
+EIP1967_ADMIN = "0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103"
+
+# Configuration fields and getters holding who controls the deployment
+AUTHORITY_NAME = re.compile(r"authority|admin|owner|governance|guardian|operator|manager|multisig|upgrade", re.I)
+
+_ABI_INT = re.compile(r"^(u?)int(\d*)$")
+
+
+class Client(Protocol):
+    """What snapshots need of ``RpcClient``."""
+
+    def call(self, method: str, params: list[Any] | None = None, cache: bool = True) -> Any: ...
+
+    def get_multiple_accounts(self, addresses: list[str], encoding: str = "base64",
+                              data_slice: dict[str, int] | None = None) -> list[dict[str, Any] | None]: ...
+
+
+@dataclass
+class CallSpec:
+    """An EVM getter taking no arguments and returning one word."""
+
+    contract: str  # Program name in the deployment, or an address
+    call: str  # Signature, e.g. "feeBps()"
+    type: str = "uint256"
+
+
+@dataclass
+class Deployment:
+    """One deployment of the protocol."""
+
+    name: str
+    chain: str = "solana"  # "solana" or "evm"
+    provider: str | None = None
+    cluster: str | None = None
+    # Whether divergences here matter most (default: Solana mainnet)
+    production: bool = False
+    # Program name -> address
+    programs: dict[str, str] = field(default_factory=dict)
+    # Account label -> address (Solana)
+    accounts: dict[str, str] = field(default_factory=dict)
+    # Parameter label -> getter (EVM)
+    calls: dict[str, CallSpec] = field(default_factory=dict)
+
+    @property
+    def provider_spec(self) -> str | None:
+        return self.provider or (self.cluster if self.chain == "solana" else None)
+
+
+@dataclass
+class AccountSpec:
+    """How to decode a configuration account."""
+
+    type: str
+    program: str | None = None
+
+
+@dataclass
+class DeploymentSet:
+    """A deployments file: the deployments to compare and what to decode them with."""
+
+    deployments: list[Deployment]
+    accounts: dict[str, AccountSpec] = field(default_factory=dict)
+    # Defined types (IDL form) of the configuration accounts
+    types: dict[str, dict[str, Any]] = field(default_factory=dict)
+    # Program name -> executable hash of the audited build (target/deploy)
+    builds: dict[str, str] = field(default_factory=dict)
+
+
+@dataclass
+class ProgramState:
+    """A program (contract) as deployed."""
+
+    address: str
+    found: bool = True
+    # sha256 of the executable, trailing zero padding stripped
+    hash: str | None = None
+    upgradeable: bool = False
+    deployed_slot: int | None = None
+    # Unix time of deployed_slot
+    deployed_at: int | None = None
+    # EIP-1967 implementation behind a proxy
+    implementation: str | None = None
+    error: str | None = None
+
+
+@dataclass
+class Authority:
+    """Who holds a role: a wallet, a program (a multisig or governance), or no one."""
+
+    address: str | None
+    kind: str  # "wallet", "program", "none" or "unknown"
+    # Owning program of a program-controlled Solana authority
+    owner: str | None = None
+
+
+@dataclass
+class Snapshot:
+    """What one deployment runs."""
+
+    deployment: str
+    chain: str
+    production: bool = False
+    programs: dict[str, ProgramState] = field(default_factory=dict)
+    # "<account>.<field>" or call label -> value
+    params: dict[str, Any] = field(default_factory=dict)
+    # "<program>.upgrade_authority", "<account>.<field>" or call label -> holder
+    authorities: dict[str, Authority] = field(default_factory=dict)
+    errors: list[str] = field(default_factory=list)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"version": SNAPSHOT_VERSION, **asdict(self)}
+
+
+def executable_hash(data: bytes) -> str:
+    """sha256 of a program's bytes without the zero padding programdata accounts are allocated with."""
+    return hashlib.sha256(data.rstrip(b"\x00")).hexdigest()
+
+
+def _cluster_of(name: str, provider: str | None) -> str | None:
+    """Solana cluster a deployment is on, from its provider spec or else its name."""
+    if provider and "://" not in provider:
+        parts = [p for p in provider.split(":") if p]
+        clusters = [normalize_cluster(p) for p in parts if p.lower() not in PROVIDERS]
+        if clusters:
+            return clusters[0]
+        if parts:
+            return "mainnet"
+    cluster = normalize_cluster(name)
+    return cluster if cluster in PUBLIC_URLS else None
+
+
+def _source_types(programs: list[Any]) -> dict[str, dict[str, Any]]:
+    """#[account] structs as defined types; fields of other types are left for the IDL to define."""
+    from extensions.simulation.boundary import EncodeError, rust_type
+
+    types = {}
+    for program in programs:
+        for at in program.account_types:
+            fields = []
+            for f in at.fields:
+                try:
+                    fields.append({"name": f.name, "type": rust_type(f.ty)})
+                except EncodeError:
+                    fields.append({"name": f.name, "type": {"defined": {"name": f.ty}}})
+            types[at.name] = {"kind": "struct", "fields": fields}
+    return types
+
+
+def load_deployments(data: dict[str, Any], base: Path | None = None) -> DeploymentSet:
+    """Build a deployment set from a deployments file (YAML) loaded as a dict.
+
+    Raises ValueError on malformed entries.
+    """
+    from extensions.ir import load_programs
+    from extensions.simulation.boundary import load_idl
+
+    base = base or Path.cwd()
+    entries = data.get("deployments") or {}
+    if isinstance(entries, list):
+        entries = {e.get("name"): e for e in entries}
+    if len(entries) < 2:
+        raise ValueError("deployments file needs at least two deployments to compare")
+    anchor = load_anchor_config(base / data["workspace"]) if data.get("workspace") else None
+    deployments = []
+    for name, entry in entries.items():
+        if not name:
+            raise ValueError("every deployment needs a name")
+        entry = entry or {}
+        chain = entry.get("chain", "solana")
+        if chain not in ("solana", "evm"):
+            raise ValueError(f"deployment {name}: unknown chain {chain!r} (expected solana or evm)")
+        cluster = entry.get("cluster") or (_cluster_of(name, entry.get("provider")) if chain == "solana" else None)
+        cluster = normalize_cluster(cluster) if cluster else None
+        programs = dict(anchor.programs.get(cluster) or {}) if anchor and chain == "solana" and cluster else {}
+        programs.update(entry.get("programs") or {})
+        if chain == "evm" and not entry.get("provider"):
+            raise ValueError(f"deployment {name}: EVM deployments need a provider URL")
+        if not programs:
+            raise ValueError(f"deployment {name}: no programs (list them, or point workspace at Anchor.toml)")
+        calls = {}
+        for label, spec in (entry.get("calls") or {}).items():
+            if not isinstance(spec, dict) or not spec.get("contract") or not spec.get("call"):
+                raise ValueError(f"deployment {name}: call {label} needs a contract and a call")
+            calls[label] = CallSpec(spec["contract"], spec["call"], spec.get("type", "uint256"))
+        deployments.append(Deployment(
+            name=name, chain=chain, provider=entry.get("provider"), cluster=cluster,
+            production=bool(entry.get("production", cluster == "mainnet")), programs=programs,
+            accounts=dict(entry.get("accounts") or {}), calls=calls,
+        ))
+
+    accounts = {}
+    for label, spec in (data.get("accounts") or {}).items():
+        spec = {"type": spec} if isinstance(spec, str) else spec or {}
+        if not spec.get("type"):
+            raise ValueError(f"account {label} needs a type")
+        accounts[label] = AccountSpec(spec["type"], spec.get("program"))
+    for dep in deployments:
+        unknown = set(dep.accounts) - set(accounts)
+        if unknown:
+            raise ValueError(f"deployment {dep.name}: no type for account {', '.join(sorted(unknown))}")
+
+    types: dict[str, dict[str, Any]] = {}
+    builds: dict[str, str] = {}
+    if anchor:
+        types.update(_source_types(load_programs(anchor.root)))
+        deploy = anchor.root / "target" / "deploy"
+        for name in {n for programs in anchor.programs.values() for n in programs}:
+            if (deploy / f"{name}.so").is_file():
+                builds[name] = executable_hash((deploy / f"{name}.so").read_bytes())
+    if data.get("idl"):
+        types.update(load_idl(base / data["idl"])[1])
+    for name, path in (data.get("builds") or {}).items():
+        builds[name] = executable_hash((base / path).read_bytes())
+    return DeploymentSet(deployments, accounts, types, builds)
+
+
+def _account_data(info: dict[str, Any] | None) -> bytes:
+    if not info:
+        return b""
+    data = info.get("data")
+    return base64.b64decode(data[0]) if isinstance(data, list) else b""
+
+
+def _flatten(snap: Snapshot, prefix: str, ty: Any, value: Any, types: dict[str, dict[str, Any]],
+             authorities: dict[str, str | None]) -> None:
+    """Record a decoded value: pubkeys named like authorities as such, other pubkeys (mints, vaults) not at all."""
+    from extensions.simulation.boundary import normalize_type
+
+    ty = normalize_type(ty)
+    name = prefix.rsplit(".", 1)[-1]
+    if ty == "pubkey":
+        if AUTHORITY_NAME.search(name):
+            authorities[prefix] = None if value == SYSTEM_PROGRAM else value
+        return
+    if isinstance(ty, dict) and "defined" in ty:
+        typedef = types.get(ty["defined"].get("name"), {})
+        if typedef.get("kind") != "enum" and isinstance(value, dict):
+            for f in typedef.get("fields") or []:
+                if isinstance(f, dict) and f.get("name") in value:
+                    _flatten(snap, f"{prefix}.{f['name']}", f["type"], value[f["name"]], types, authorities)
+            return
+    if isinstance(ty, dict) and "option" in ty and normalize_type(ty["option"]) == "pubkey":
+        if AUTHORITY_NAME.search(name):
+            authorities[prefix] = value
+        return
+    snap.params[prefix] = value
+
+
+def _solana_kinds(client: Client, addresses: dict[str, str | None]) -> dict[str, Authority]:
+    """Authorities by key; accounts owned by the system program (or not existing) are wallets."""
+    unique = sorted({a for a in addresses.values() if a})
+    infos = dict(zip(unique, client.get_multiple_accounts(unique))) if unique else {}
+    found = {}
+    for key, address in addresses.items():
+        if not address:
+            found[key] = Authority(None, "none")
+            continue
+        info = infos.get(address)
+        owner = info.get("owner") if info else None
+        if owner in (None, SYSTEM_PROGRAM):
+            found[key] = Authority(address, "wallet")
+        else:
+            found[key] = Authority(address, "program", owner)
+    return found
+
+
+def _solana_snapshot(dep: Deployment, client: Client, accounts: dict[str, AccountSpec],
+                     types: dict[str, dict[str, Any]]) -> Snapshot:
+    from extensions.monitor.events import decode_value
+
+    snap = Snapshot(dep.name, dep.chain, dep.production)
+    authorities: dict[str, str | None] = {}
+    names = list(dep.programs)
+    programdata: dict[str, str] = {}
+    for name, info in zip(names, client.get_multiple_accounts([dep.programs[n] for n in names])):
+        address = dep.programs[name]
+        data = _account_data(info)
+        if info is None:
+            snap.programs[name] = ProgramState(address, found=False, error="no account at this address")
+        elif info.get("owner") == UPGRADEABLE_LOADER and len(data) >= 36 and data[0] == 2:
+            snap.programs[name] = ProgramState(address, upgradeable=True)
+            programdata[name] = b58encode(data[4:36])
+        elif info.get("executable"):
+            snap.programs[name] = ProgramState(address, hash=executable_hash(data))
+            authorities[f"{name}.upgrade_authority"] = None
+        else:
+            snap.programs[name] = ProgramState(address, found=False, error="account is not a program")
+    infos = client.get_multiple_accounts(list(programdata.values())) if programdata else []
+    for name, info in zip(programdata, infos):
+        state = snap.programs[name]
+        data = _account_data(info)
+        if len(data) <= _PROGRAMDATA_HEADER:
+            state.found, state.error = False, f"programdata {programdata[name]} is closed"
+            continue
+        state.hash = executable_hash(data[_PROGRAMDATA_HEADER:])
+        state.deployed_slot = int.from_bytes(data[4:12], "little")
+        authorities[f"{name}.upgrade_authority"] = b58encode(data[13:45]) if data[12] else None
+        try:
+            state.deployed_at = client.call("getBlockTime", [state.deployed_slot])
+        except RpcError:
+            pass
+
+    labels = list(dep.accounts)
+    infos = client.get_multiple_accounts([dep.accounts[label] for label in labels]) if labels else []
+    for label, info in zip(labels, infos):
+        spec = accounts[label]
+        data = _account_data(info)
+        if not info:
+            snap.errors.append(f"account {label} ({dep.accounts[label]}) not found")
+            continue
+        if data[:8] != hashlib.sha256(f"account:{spec.type}".encode()).digest()[:8]:
+            snap.errors.append(f"account {label} ({dep.accounts[label]}) is not a {spec.type}")
+            continue
+        ty = {"defined": {"name": spec.type}}
+        try:
+            value, _ = decode_value(ty, data, 8, types)
+        except ValueError as e:
+            snap.errors.append(f"account {label}: {e}")
+            continue
+        _flatten(snap, label, ty, value, types, authorities)
+    snap.authorities = _solana_kinds(client, authorities)
+    return snap
+
+
+def _word(result: Any) -> bytes:
+    raw = bytes.fromhex(str(result or "0x")[2:])
+    return raw[-32:].rjust(32, b"\x00")
+
+
+def _address(word: bytes) -> str | None:
+    return None if not any(word[-20:]) else "0x" + word[-20:].hex()
+
+
+def abi_decode_word(word: bytes, ty: str) -> Any:
+    """One ABI-encoded return word as a Python value; raises ValueError on an unsupported type."""
+    if m := _ABI_INT.match(ty):
+        bits = int(m.group(2) or 256)
+        value = int.from_bytes(word, "big")
+        if not m.group(1) and value >= 1 << 255:
+            value -= 1 << 256
+        return value & ((1 << bits) - 1) if m.group(1) else value
+    if ty == "bool":
+        return word[-1] != 0
+    if ty == "address":
+        return _address(word)
+    if ty == "bytes32":
+        return word.hex()
+    raise ValueError(f"cannot decode return type {ty}")
+
+
+def _evm_snapshot(dep: Deployment, client: Client) -> Snapshot:
+    from extensions.ir.keccak import function_selector
+
+    snap = Snapshot(dep.name, dep.chain, dep.production)
+    authorities: dict[str, str | None] = {}
+    unknown: set[str] = set()
+    for name, address in dep.programs.items():
+        code = bytes.fromhex(str(client.call("eth_getCode", [address, "latest"]) or "0x")[2:])
+        if not code:
+            snap.programs[name] = ProgramState(address, found=False, error="no code at this address")
+            continue
+        state = ProgramState(address)
+        implementation = _address(_word(client.call("eth_getStorageAt", [address, EIP1967_IMPLEMENTATION, "latest"])))
+        if implementation:
+            state.upgradeable, state.implementation = True, implementation
+            code = bytes.fromhex(str(client.call("eth_getCode", [implementation, "latest"]) or "0x")[2:])
+            admin = _address(_word(client.call("eth_getStorageAt", [address, EIP1967_ADMIN, "latest"])))
+            # UUPS proxies keep no admin; the implementation decides who upgrades
+            if admin is None:
+                unknown.add(f"{name}.upgrade_authority")
+            authorities[f"{name}.upgrade_authority"] = admin
+        else:
+            authorities[f"{name}.upgrade_authority"] = None
+        state.hash = executable_hash(code)
+        snap.programs[name] = state
+    for label, spec in dep.calls.items():
+        target = dep.programs.get(spec.contract, spec.contract)
+        try:
+            result = client.call("eth_call", [{"to": target, "data": "0x" + function_selector(spec.call)}, "latest"])
+            value = abi_decode_word(_word(result), spec.type)
+        except (RpcError, ValueError) as e:
+            snap.errors.append(f"call {label} ({spec.call}): {e}")
+            continue
+        if spec.type == "address" and AUTHORITY_NAME.search(label.rsplit(".", 1)[-1]):
+            authorities[label] = value
+        else:
+            snap.params[label] = value
+    for key, address in authorities.items():
+        if key in unknown:
+            snap.authorities[key] = Authority(None, "unknown")
+        elif not address:
+            snap.authorities[key] = Authority(None, "none")
+        else:
+            code = str(client.call("eth_getCode", [address, "latest"]) or "0x")
+            snap.authorities[key] = Authority(address, "program" if len(code) > 2 else "wallet")
+    return snap
+
+
+def take_snapshot(dep: Deployment, client: Client, deployments: DeploymentSet | None = None) -> Snapshot:
+    """Snapshot ``dep`` through ``client``; RPC failures are recorded in ``errors``."""
+    deployments = deployments or DeploymentSet([dep])
+    try:
+        if dep.chain == "evm":
+            return _evm_snapshot(dep, client)
+        return _solana_snapshot(dep, client, deployments.accounts, deployments.types)
+    except RpcError as e:
+        return Snapshot(dep.name, dep.chain, dep.production, errors=[f"RPC failed: {e}"])
@@ -0,0 +1,21 @@
+"""
+Localized report and knowledge-base output.
+
+Client-facing deliverables often need non-English versions:
+
+- Message catalogs: report headings, labels and boilerplate per locale
+  (en, zh, es, ja), with English fallback for untranslated keys
+- Overlays: translated knowledge-base descriptions and fix guidance, keyed
+  by entry id next to the entries they translate
+"""
+
+from .catalog import DEFAULT_LOCALE, LOCALES, Translator, load_overlay, localize, normalize_locale
+
+__all__ = [
+    "DEFAULT_LOCALE",
+    "LOCALES",
+    "Translator",
+    "load_overlay",
+    "localize",
+    "normalize_locale",
+]
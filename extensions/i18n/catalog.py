@@ -0,0 +1,114 @@
+"""
+Message catalogs and translation overlays.
+
+Report text is looked up by dotted key (``report.findings``) in
+``messages/<locale>.yaml``, falling back to English and then to the key
+itself, so a partial translation still renders. Knowledge-base entries are
+translated by overlay files (``<kb dir>/locales/<locale>.yaml``) that map an
+entry id to translated fields; entries without one stay in English.
+"""
+
+import dataclasses
+from pathlib import Path
+from typing import Any
+
+import yaml
+
+DEFAULT_LOCALE = "en"
+
+# Locale code -> English name of the language (used when instructing the reporting LLM)
+LOCALES = {
+    "en": "English",
+    "zh": "Simplified Chinese",
+    "es": "Spanish",
+    "ja": "Japanese",
+}
+
+_ALIASES = {"zh-cn": "zh", "zh-hans": "zh", "cn": "zh", "jp": "ja", "ja-jp": "ja", "es-es": "es", "en-us": "en",
+            "en-gb": "en"}
+
+MESSAGES_DIR = Path(__file__).parent / "messages"
+
+
+def normalize_locale(locale: str | None) -> str:
+    """Supported locale code for ``zh``, ``zh-CN``, ``ES``, ``ja_JP`` and the like."""
+    if not locale:
+        return DEFAULT_LOCALE
+    code = locale.strip().lower().replace("_", "-")
+    code = _ALIASES.get(code, code)
+    if code not in LOCALES:
+        code = code.split("-")[0]
+    if code not in LOCALES:
+        raise ValueError(f"unsupported language {locale!r} (choose from {', '.join(LOCALES)})")
+    return code
+
+
+def _read_yaml(path: Path) -> dict[str, Any]:
+    if not path.exists():
+        return {}
+    try:
+        with open(path) as f:
+            return yaml.safe_load(f) or {}
+    except Exception as e:
+        print(f"[!] Failed to load translations from {path}: {e}")
+        return {}
+
+
+class Translator:
+    """Looks up report text for one locale."""
+
+    def __init__(self, locale: str | None = None, messages_dir: Path | None = None):
+        """Initialize translator.
+
+        Args:
+            locale: Locale code (en, zh, es, ja); defaults to English
+            messages_dir: Directory of ``<locale>.yaml`` message catalogs
+        """
+        self.locale = normalize_locale(locale)
+        self.messages_dir = messages_dir or MESSAGES_DIR
+        self._messages = _read_yaml(self.messages_dir / f"{self.locale}.yaml")
+        self._fallback = self._messages if self.locale == DEFAULT_LOCALE else \
+            _read_yaml(self.messages_dir / f"{DEFAULT_LOCALE}.yaml")
+
+    @property
+    def language(self) -> str:
+        return LOCALES[self.locale]
+
+    @property
+    def is_default(self) -> bool:
+        return self.locale == DEFAULT_LOCALE
+
+    @staticmethod
+    def _lookup(messages: dict[str, Any], key: str) -> Any:
+        node: Any = messages
+        for part in key.split("."):
+            if not isinstance(node, dict) or part not in node:
+                return None
+            node = node[part]
+        return node
+
+    def t(self, key: str, default: str | None = None, **kwargs: Any) -> str:
+        """Text for ``key`` in this locale, formatted with ``kwargs``."""
+        text = self._lookup(self._messages, key)
+        if not isinstance(text, str):
+            text = self._lookup(self._fallback, key)
+        if not isinstance(text, str):
+            text = default if default is not None else key
+        return text.format(**kwargs) if kwargs else text
+
+
+def load_overlay(directory: Path, locale: str | None) -> dict[str, dict[str, Any]]:
+    """Translated fields by entry id from ``<directory>/locales/<locale>.yaml``; empty for English."""
+    code = normalize_locale(locale)
+    if code == DEFAULT_LOCALE:
+        return {}
+    data = _read_yaml(directory / "locales" / f"{code}.yaml")
+    return {str(k): v for k, v in data.items() if isinstance(v, dict)}
+
+
+def localize(entry: Any, translated: dict[str, Any] | None, fields: tuple[str, ...]) -> Any:
+    """Copy of a dataclass entry with translations of ``fields`` (an overlay entry) applied."""
+    if not translated:
+        return entry
+    changes = {f: translated[f] for f in fields if translated.get(f)}
+    return dataclasses.replace(entry, **changes) if changes else entry
@@ -0,0 +1,68 @@
+"""
+Program intermediate representation (IR) for on-chain code.
+
+Front-ends lower program source into a common model of instructions,
+accounts, arguments, and outgoing calls so detectors and exports can be
+written once and run across frameworks.
+
+Front-ends:
+- Anchor programs (`#[program]` modules and `#[derive(Accounts)]` structs)
+- Native Solana programs (hand-rolled `process_instruction` dispatch)
+- Seahorse programs (generated Anchor code mapped back to Python lines)
+- CosmWasm contracts (message enums mapped to handlers, plus JSON schema)
+- Solidity contracts (solc standard-json AST, inheritance linearized)
+- Vyper contracts (`vyper -f ast` output)
+- Yul and Huff sources, and raw EVM bytecode (dispatcher recovery plus
+  storage/call effects, for contracts without high-level source)
+- Compiled Move modules for Aptos/Sui (signer and ability analysis)
+
+Use `load_programs(path)` to pick front-ends automatically; inside
+`use_program_cache(cache)` it reuses crates whose sources are unchanged.
+"""
+
+from .anchor import AnchorFrontend, load_anchor_programs
+from .cache import ProgramCache, use_program_cache
+from .cosmwasm import CosmWasmFrontend, load_cosmwasm_programs
+from .evm_assembly import HuffFrontend, YulFrontend, load_huff_programs, load_yul_programs
+from .evm_bytecode import BytecodeFrontend, load_bytecode_programs
+from .loader import load_programs
+from .model import AccountField, AccountType, Argument, CallSite, EventType, Instruction, Program
+from .move_bytecode import MoveBytecodeFrontend, load_move_bytecode
+from .native import NativeDispatchRecovery, recover_native_programs
+from .seahorse import SeahorseFrontend, SeahorseSourceMap, load_seahorse_programs
+from .solidity import SolidityFrontend, load_solidity_programs
+from .vyper import VyperFrontend, load_vyper_programs
+
+__all__ = [
+    "AccountField",
+    "AccountType",
+    "Argument",
+    "CallSite",
+    "EventType",
+    "Instruction",
+    "Program",
+    "ProgramCache",
+    "AnchorFrontend",
+    "BytecodeFrontend",
+    "CosmWasmFrontend",
+    "HuffFrontend",
+    "MoveBytecodeFrontend",
+    "NativeDispatchRecovery",
+    "SeahorseFrontend",
+    "SeahorseSourceMap",
+    "SolidityFrontend",
+    "VyperFrontend",
+    "YulFrontend",
+    "load_anchor_programs",
+    "load_bytecode_programs",
+    "load_cosmwasm_programs",
+    "load_huff_programs",
+    "load_move_bytecode",
+    "load_programs",
+    "load_seahorse_programs",
+    "load_solidity_programs",
+    "use_program_cache",
+    "load_vyper_programs",
+    "load_yul_programs",
+    "recover_native_programs",
+]
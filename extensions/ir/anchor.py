@@ -0,0 +1,250 @@
+"""
+Anchor front-end.
+
+Lowers an Anchor program crate into the IR: every `pub fn` in the
+`#[program]` module becomes an Instruction whose accounts come from its
+`#[derive(Accounts)]` struct (types, `#[account(...)]` constraints, seeds),
+`#[account]` structs become AccountTypes, and `#[event]` structs become
+EventTypes, linked to the handlers that `emit!` them.
+
+Handlers that only forward to the real logic (`instructions::withdraw::handler(ctx, amount)`,
+`ctx.accounts.process(amount)`, or Seahorse's `<name>_handler(...)`) are
+followed so that `body` holds the code that actually runs.
+"""
+
+import re
+from pathlib import Path
+
+from .model import AccountField, AccountType, Argument, EventType, Instruction, Program
+from .rust_source import (
+    RustFn,
+    RustStruct,
+    called_functions,
+    find_cpi_calls,
+    find_matching,
+    iter_functions,
+    iter_structs,
+    line_at,
+    parse_named_fields,
+    parse_params,
+    preceding_docs,
+    split_top_level,
+)
+from .workspace import SourceFile, crate_name, find_crates, load_rust_sources
+
+_PROGRAM_MOD_RE = re.compile(r"#\[program\]\s*(?:pub(?:\s*\([^)]*\))?\s+)?mod\s+([A-Za-z_]\w*)\s*\{")
+_DECLARE_ID_RE = re.compile(r"declare_id!\s*\(\s*\"([1-9A-HJ-NP-Za-km-z]{32,44})\"\s*\)")
+_CONTEXT_RE = re.compile(r"Context\s*<(.*)>\s*$", re.DOTALL)
+_EMIT_RE = re.compile(r"\bemit(?:_cpi)?!\s*\(\s*(?:[A-Za-z_]\w*::)*([A-Za-z_]\w*)\s*\{")
+# Constraints that imply the account is written
+_MUT_CONSTRAINTS = {"mut", "init", "init_if_needed", "zero"}
+
+
+class AnchorFrontend:
+    """Parses Anchor programs into IR Programs."""
+
+    def __init__(self, max_files: int = 2000):
+        """Initialize front-end.
+
+        Args:
+            max_files: Upper bound on Rust files read per program crate
+        """
+        self.max_files = max_files
+
+    @staticmethod
+    def is_anchor_source(source: str) -> bool:
+        """True if comment-stripped ``source`` contains a `#[program]` module."""
+        return bool(_PROGRAM_MOD_RE.search(source))
+
+    def find_program_roots(self, path: Path) -> list[Path]:
+        """Find Anchor program crates under ``path``."""
+        return find_crates(Path(path), self.is_anchor_source)
+
+    def load(self, path: Path) -> list[Program]:
+        """Load every Anchor program found under ``path``."""
+        return [self.load_program(root) for root in self.find_program_roots(path)]
+
+    def load_program(self, root: Path) -> Program:
+        """Lower a single Anchor program crate."""
+        root = Path(root)
+        files = load_rust_sources(root, self.max_files)
+        program = Program(
+            name=crate_name(root),
+            chain="solana",
+            framework="anchor",
+            root=str(root),
+            source_files=[f.relpath for f in files],
+        )
+
+        functions: dict[str, list[tuple[SourceFile, RustFn]]] = {}
+        accounts_structs: dict[str, tuple[SourceFile, RustStruct]] = {}
+        for sf in files:
+            for fn in iter_functions(sf.text, sf.original):
+                functions.setdefault(fn.name, []).append((sf, fn))
+            for st in iter_structs(sf.text):
+                attrs = " ".join(st.attributes)
+                if re.search(r"derive\s*\([^)]*\bAccounts\b", attrs):
+                    accounts_structs.setdefault(st.name, (sf, st))
+                elif re.search(r"#\[account(?:\s*\(|\])", attrs):
+                    program.account_types.append(self._account_type(sf, st))
+                elif re.search(r"#\[event\]", attrs):
+                    program.events.append(EventType(name=st.name, file=sf.relpath, line=st.line,
+                                                    fields=[Argument(name=n, ty=t)
+                                                            for n, t, _ in parse_named_fields(st.body)]))
+            if program.program_id is None:
+                idm = _DECLARE_ID_RE.search(sf.original)
+                if idm:
+                    program.program_id = idm.group(1)
+
+        for sf in files:
+            mod = _PROGRAM_MOD_RE.search(sf.text)
+            if not mod:
+                continue
+            program.metadata["module"] = mod.group(1)
+            mod_start = mod.end() - 1
+            mod_end = find_matching(sf.text, mod_start)
+            for fn in iter_functions(sf.text, sf.original):
+                if not (mod_start < fn.start < mod_end):
+                    continue
+                ix = self._instruction(sf, fn, functions, accounts_structs)
+                if ix is not None:
+                    program.instructions.append(ix)
+            break
+
+        for ix in program.instructions:
+            for name in dict.fromkeys(_EMIT_RE.findall(ix.body)):
+                event = program.event(name)
+                if event is not None:
+                    event.emitted_by.append(ix.name)
+        return program
+
+    # ------------------------------------------------------------------
+    # Lowering helpers
+    # ------------------------------------------------------------------
+
+    def _instruction(self, sf: SourceFile, fn: RustFn, functions, accounts_structs) -> Instruction | None:
+        params = parse_params(fn.params)
+        if not params:
+            return None
+        ctx_name, ctx_type = params[0]
+        cm = _CONTEXT_RE.search(ctx_type)
+        if not cm:
+            return None
+        generic_args = [a for a in split_top_level(cm.group(1)) if not a.startswith("'")]
+        struct_name = generic_args[-1].split("<", 1)[0].strip() if generic_args else ""
+
+        ix = Instruction(
+            name=fn.name,
+            handler=fn.name,
+            file=sf.relpath,
+            line=fn.line,
+            args=[Argument(name=n, ty=t) for n, t in params[1:]],
+            body=fn.body,
+            body_line=line_at(sf.text, fn.body_start),
+            accounts_struct=struct_name or None,
+        )
+
+        if struct_name in accounts_structs:
+            st_file, st = accounts_structs[struct_name]
+            ix.accounts = self._accounts(st_file, st, fn_file=sf.relpath)
+            ix.properties["accounts_file"] = st_file.relpath
+            ix.properties["accounts_line"] = st.line
+
+        target = self._delegate(fn, sf, struct_name, ctx_name, functions)
+        if target is not None:
+            t_sf, t_fn = target
+            ix.body = t_fn.body
+            ix.body_line = line_at(t_sf.text, t_fn.body_start)
+            if t_sf.relpath != sf.relpath:
+                ix.body_file = t_sf.relpath
+            ix.properties["delegates_to"] = f"{t_sf.relpath}:{t_fn.name}"
+
+        ix.calls = find_cpi_calls(ix.body, ix.body_line)
+        return ix
+
+    def _delegate(self, fn: RustFn, sf: SourceFile, struct_name: str, ctx_name: str, functions):
+        """Resolve the function a thin wrapper forwards to, if any."""
+        # ctx.accounts.process(..) -> impl block on the accounts struct
+        mm = re.search(rf"\b{re.escape(ctx_name)}\s*\.\s*accounts\s*\.\s*([a-z_]\w*)\s*\(", fn.body)
+        if mm:
+            for cand_sf, cand in functions.get(mm.group(1), []):
+                impl = re.compile(rf"impl\s*(?:<[^>]*>)?\s*{re.escape(struct_name)}\b[^{{]*\{{")
+                for im in impl.finditer(cand_sf.text):
+                    end = find_matching(cand_sf.text, im.end() - 1)
+                    if im.end() <= cand.start <= end:
+                        return cand_sf, cand
+
+        for path, name in called_functions(fn.body):
+            candidates = [(c_sf, c) for c_sf, c in functions.get(name, []) if c is not fn]
+            if not candidates:
+                continue
+            segments = [seg for seg in path.split("::") if seg and seg not in ("crate", "super", "self")]
+            if segments:
+                for c_sf, c in candidates:
+                    parts = Path(c_sf.relpath).with_suffix("").parts
+                    if segments[-1] in parts:
+                        return c_sf, c
+            if len(candidates) == 1:
+                return candidates[0]
+            if name in ("handler", f"{fn.name}_handler"):
+                return candidates[0]
+        return None
+
+    def _accounts(self, sf: SourceFile, st: RustStruct, fn_file: str) -> list[AccountField]:
+        body_offset = sf.text.find(st.body, st.start)
+        accounts: list[AccountField] = []
+        cursor = 0
+        for name, ty, attrs in parse_named_fields(st.body):
+            fm = re.search(rf"\b{re.escape(name)}\s*:", st.body[cursor:])
+            rel = cursor + fm.start() if fm else cursor
+            cursor = rel + len(name)
+            abs_offset = body_offset + rel if body_offset >= 0 else st.start
+            constraints: list[str] = []
+            for attr in attrs:
+                am = re.match(r"#\[account\s*\((.*)\)\s*\]$", attr, re.DOTALL)
+                if am:
+                    constraints.extend(" ".join(c.split()) for c in split_top_level(am.group(1)))
+            acc = AccountField(
+                name=name,
+                ty=ty,
+                constraints=constraints,
+                line=line_at(sf.text, abs_offset),
+                docs=" ".join(preceding_docs(sf.original, abs_offset)),
+                file=sf.relpath if sf.relpath != fn_file else "",
+            )
+            keys = {c.split("=", 1)[0].strip() for c in constraints}
+            acc.is_signer = acc.wrapper == "Signer" or "signer" in keys
+            acc.is_mut = bool(keys & _MUT_CONSTRAINTS)
+            for c in constraints:
+                sm = re.match(r"seeds\s*=\s*\[(.*)\]$", c, re.DOTALL)
+                if sm:
+                    acc.seeds = [" ".join(s.split()) for s in split_top_level(sm.group(1))]
+            accounts.append(acc)
+        return accounts
+
+    def _account_type(self, sf: SourceFile, st: RustStruct) -> AccountType:
+        parsed = parse_named_fields(st.body)
+        fields = [Argument(name=n, ty=t) for n, t, _ in parsed]
+        attrs = " ".join(st.attributes)
+        # `#[max_len(..)]` sizes Vec/String fields for `InitSpace`
+        max_len = {}
+        for name, _, field_attrs in parsed:
+            for attr in field_attrs:
+                mm = re.match(r"#\[max_len\s*\((.*)\)\s*\]$", attr, re.DOTALL)
+                if mm:
+                    max_len[name] = [" ".join(a.split()) for a in split_top_level(mm.group(1))]
+        properties = {"zero_copy": "zero_copy" in attrs}
+        if max_len:
+            properties["max_len"] = max_len
+        return AccountType(
+            name=st.name,
+            file=sf.relpath,
+            line=st.line,
+            fields=fields,
+            properties=properties,
+        )
+
+
+def load_anchor_programs(path: Path) -> list[Program]:
+    """Convenience wrapper around AnchorFrontend.load()."""
+    return AnchorFrontend().load(Path(path))
@@ -0,0 +1,337 @@
+"""
+Anchor workspace configuration (Anchor.toml).
+
+Parses the provider (cluster and wallet), program addresses per cluster,
+scripts, and the `[test]` / `[test.validator]` settings (genesis programs,
+cloned and preloaded accounts) so PoC generation and the execution
+backends run against the project's own setup rather than guesses.
+
+`check_consistency` compares the file with the workspace: program ids
+that differ across clusters, `declare_id!` values and deploy keypairs
+that disagree with the provider cluster's entry, programs missing from
+either side, and malformed addresses.
+"""
+
+import json
+import re
+import sys
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Any
+
+if sys.version_info >= (3, 11):
+    import tomllib
+else:
+    import tomli as tomllib
+
+from .model import Program
+from .workspace import is_skipped
+
+CONFIG_FILE = "Anchor.toml"
+
+_ADDRESS_RE = re.compile(r"^[1-9A-HJ-NP-Za-km-z]{32,44}$")
+_B58 = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+# Anchor cluster monikers (and their short forms) -> canonical name
+_CLUSTER_ALIASES = {
+    "l": "localnet", "localnet": "localnet",
+    "d": "devnet", "devnet": "devnet",
+    "t": "testnet", "testnet": "testnet",
+    "m": "mainnet", "mainnet": "mainnet", "mainnet-beta": "mainnet",
+}
+CLUSTER_URLS = {
+    "localnet": "http://127.0.0.1:8899",
+    "devnet": "https://api.devnet.solana.com",
+    "testnet": "https://api.testnet.solana.com",
+    "mainnet": "https://api.mainnet-beta.solana.com",
+}
+
+
+def normalize_cluster(cluster: str) -> str:
+    """Canonical cluster name for a moniker; URLs are returned unchanged."""
+    return _CLUSTER_ALIASES.get(cluster.strip().lower(), cluster.strip())
+
+
+def program_key(name: str) -> str:
+    """Anchor.toml / target/deploy name of a crate (`my-vault` -> `my_vault`)."""
+    return name.replace("-", "_")
+
+
+def b58encode(data: bytes) -> str:
+    n = int.from_bytes(data, "big")
+    out = ""
+    while n:
+        n, rem = divmod(n, 58)
+        out = _B58[rem] + out
+    return "1" * (len(data) - len(data.lstrip(b"\0"))) + out
+
+
+def b58decode(value: str) -> bytes:
+    n = 0
+    for ch in value:
+        n = n * 58 + _B58.index(ch)
+    body = n.to_bytes((n.bit_length() + 7) // 8, "big") if n else b""
+    return b"\0" * (len(value) - len(value.lstrip("1"))) + body
+
+
+def is_pubkey(value: str) -> bool:
+    """Base58 string that decodes to exactly 32 bytes."""
+    if not 32 <= len(value) <= 44 or not set(value) <= set(_B58):
+        return False
+    leading = len(value) - len(value.lstrip("1"))
+    return leading + len(b58decode(value).lstrip(b"\0")) == 32
+
+
+@dataclass
+class ValidatorConfig:
+    """`[test]` and `[test.validator]`: what `anchor test` starts the local validator with."""
+
+    # Cluster that `clone` entries are fetched from
+    url: str | None = None
+    rpc_port: int | None = None
+    bind_address: str | None = None
+    ledger: str | None = None
+    startup_wait: int | None = None
+    # [[test.genesis]]: programs loaded at genesis (address, program, upgradeable)
+    genesis: list[dict[str, Any]] = field(default_factory=list)
+    # [[test.validator.clone]]: accounts/programs cloned from ``url``
+    clones: list[str] = field(default_factory=list)
+    # [[test.validator.account]]: accounts preloaded from JSON dumps (address, filename)
+    accounts: list[dict[str, str]] = field(default_factory=list)
+    # Remaining `solana-test-validator` flags as written
+    extra: dict[str, Any] = field(default_factory=dict)
+
+    def to_dict(self) -> dict[str, Any]:
+        return asdict(self)
+
+
+@dataclass
+class AnchorConfigIssue:
+    """An inconsistency between Anchor.toml and the workspace."""
+
+    kind: str
+    severity: str
+    message: str
+    program: str | None = None
+    file: str = CONFIG_FILE
+    line: int = 0
+    details: dict[str, Any] = field(default_factory=dict)
+
+    def to_dict(self) -> dict[str, Any]:
+        return asdict(self)
+
+
+@dataclass
+class AnchorConfig:
+    """Parsed Anchor.toml of a workspace."""
+
+    root: Path
+    cluster: str = "localnet"
+    wallet: str | None = None
+    # Cluster -> program name -> address
+    programs: dict[str, dict[str, str]] = field(default_factory=dict)
+    scripts: dict[str, str] = field(default_factory=dict)
+    members: list[str] = field(default_factory=list)
+    toolchain: dict[str, Any] = field(default_factory=dict)
+    features: dict[str, Any] = field(default_factory=dict)
+    validator: ValidatorConfig = field(default_factory=ValidatorConfig)
+    # Program name -> line of its first address entry
+    lines: dict[str, int] = field(default_factory=dict)
+
+    @property
+    def path(self) -> Path:
+        return self.root / CONFIG_FILE
+
+    @property
+    def clusters(self) -> list[str]:
+        return list(self.programs)
+
+    def program_ids(self, cluster: str | None = None) -> dict[str, str]:
+        """Program name -> address on ``cluster`` (default: the provider cluster, else localnet)."""
+        cluster = normalize_cluster(cluster or self.cluster)
+        return dict(self.programs.get(cluster) or self.programs.get("localnet") or {})
+
+    def program_id(self, name: str, cluster: str | None = None) -> str | None:
+        return self.program_ids(cluster).get(program_key(name))
+
+    def rpc_url(self, cluster: str | None = None) -> str:
+        """RPC endpoint of ``cluster``; localnet honours `[test.validator] rpc_port`."""
+        cluster = normalize_cluster(cluster or self.cluster)
+        if cluster == "localnet" and self.validator.rpc_port:
+            return f"http://127.0.0.1:{self.validator.rpc_port}"
+        return CLUSTER_URLS.get(cluster, cluster)
+
+    def wallet_path(self) -> Path | None:
+        if not self.wallet:
+            return None
+        wallet = Path(self.wallet).expanduser()
+        return wallet if wallet.is_absolute() else self.root / wallet
+
+    def artifacts(self, cluster: str | None = None) -> dict[str, Path]:
+        """Program address -> compiled `.so` for every program built under target/deploy or loaded at genesis."""
+        deploy = self.root / "target" / "deploy"
+        artifacts = {
+            address: deploy / f"{name}.so"
+            for name, address in self.program_ids(cluster).items() if (deploy / f"{name}.so").exists()
+        }
+        for entry in self.validator.genesis:
+            if entry.get("address") and entry.get("program"):
+                artifacts[entry["address"]] = self.root / entry["program"]
+        return artifacts
+
+    def account_files(self) -> list[Path]:
+        """JSON account dumps preloaded by `[[test.validator.account]]`."""
+        return [self.root / a["filename"] for a in self.validator.accounts if a.get("filename")]
+
+    def summary(self) -> dict[str, Any]:
+        """What PoC generation needs: cluster, endpoint, wallet, program ids, test command and validator setup."""
+        return {
+            "cluster": self.cluster,
+            "rpc_url": self.rpc_url(),
+            "wallet": self.wallet,
+            "program_ids": self.program_ids(),
+            "test_script": self.scripts.get("test"),
+            "toolchain": self.toolchain,
+            "validator": {k: v for k, v in self.validator.to_dict().items() if v not in (None, [], {})},
+        }
+
+    def to_dict(self) -> dict[str, Any]:
+        data = asdict(self)
+        data["root"] = str(self.root)
+        data.pop("lines")
+        return data
+
+
+def find_anchor_config(path: Path) -> Path | None:
+    """Anchor.toml at ``path``, else the shallowest one below it."""
+    path = Path(path).resolve()
+    start = path if path.is_dir() else path.parent
+    if (start / CONFIG_FILE).is_file():
+        return start / CONFIG_FILE
+    below = [p for p in start.rglob(CONFIG_FILE) if not is_skipped(p.relative_to(start))]
+    return min(below, key=lambda p: (len(p.parts), str(p))) if below else None
+
+
+def parse_anchor_config(text: str, root: Path) -> AnchorConfig:
+    """Parse Anchor.toml contents (raises ValueError on malformed TOML)."""
+    try:
+        data = tomllib.loads(text)
+    except tomllib.TOMLDecodeError as e:
+        raise ValueError(f"{CONFIG_FILE}: {e}") from e
+    provider = data.get("provider", {})
+    programs: dict[str, dict[str, str]] = {}
+    for cluster, entries in data.get("programs", {}).items():
+        if isinstance(entries, dict):
+            programs[normalize_cluster(cluster)] = {
+                program_key(name): (value.get("address", "") if isinstance(value, dict) else str(value))
+                for name, value in entries.items()
+            }
+
+    test = dict(data.get("test", {}))
+    validator = dict(test.pop("validator", {}))
+    config = AnchorConfig(
+        root=Path(root),
+        cluster=normalize_cluster(str(provider.get("cluster", "localnet"))),
+        wallet=provider.get("wallet"),
+        programs=programs,
+        scripts={k: str(v) for k, v in data.get("scripts", {}).items()},
+        members=list(data.get("workspace", {}).get("members", [])),
+        toolchain=dict(data.get("toolchain", {})),
+        features=dict(data.get("features", {})),
+        validator=ValidatorConfig(
+            url=validator.pop("url", None),
+            rpc_port=validator.pop("rpc_port", None),
+            bind_address=validator.pop("bind_address", None),
+            ledger=validator.pop("ledger", None),
+            startup_wait=test.pop("startup_wait", None),
+            genesis=list(test.pop("genesis", [])),
+            clones=[c["address"] for c in validator.pop("clone", []) if c.get("address")],
+            accounts=list(validator.pop("account", [])),
+            extra={**validator, **test},
+        ),
+    )
+    for name in {n for ids in programs.values() for n in ids}:
+        m = re.search(rf"(?m)^\s*\"?{re.escape(name)}\"?\s*=", text)
+        config.lines[name] = text.count("\n", 0, m.start()) + 1 if m else 0
+    return config
+
+
+def load_anchor_config(path: Path) -> AnchorConfig | None:
+    """Parse the workspace's Anchor.toml (see ``find_anchor_config``), or None without one."""
+    config_file = find_anchor_config(path)
+    if config_file is None:
+        return None
+    return parse_anchor_config(config_file.read_text(errors="replace"), config_file.parent)
+
+
+def _keypair_address(keypair_file: Path) -> str | None:
+    try:
+        secret = json.loads(keypair_file.read_text())
+    except (OSError, ValueError):
+        return None
+    if not isinstance(secret, list) or len(secret) != 64:
+        return None
+    # Solana keypair files hold the 32-byte secret followed by the public key
+    return b58encode(bytes(secret[32:]))
+
+
+def check_consistency(config: AnchorConfig, programs: list[Program] | None = None) -> list[AnchorConfigIssue]:
+    """Inconsistencies between Anchor.toml, the workspace's programs and its deploy keypairs."""
+    issues: list[AnchorConfigIssue] = []
+    provider_ids = config.program_ids()
+
+    def issue(kind: str, severity: str, message: str, program: str | None = None, **details: Any) -> None:
+        issues.append(AnchorConfigIssue(kind, severity, message, program, line=config.lines.get(program or "", 0),
+                                        details=details))
+
+    if config.programs and normalize_cluster(config.cluster) not in config.programs:
+        issue("missing-cluster", "low",
+              f"Provider cluster `{config.cluster}` has no [programs.{config.cluster}] section; tooling falls back "
+              f"to localnet addresses", cluster=config.cluster)
+
+    for cluster, ids in config.programs.items():
+        for name, address in ids.items():
+            if not _ADDRESS_RE.match(address):
+                issue("invalid-address", "medium", f"`{name}` on {cluster} has a malformed address `{address}`",
+                      name, cluster=cluster, address=address)
+
+    names = sorted({n for ids in config.programs.values() for n in ids})
+    for name in names:
+        by_cluster = {c: ids[name] for c, ids in config.programs.items() if name in ids}
+        if len(set(by_cluster.values())) > 1:
+            issue("cluster-id-mismatch", "low",
+                  f"`{name}` has different program ids per cluster ({', '.join(f'{c}: {a}' for c, a in by_cluster.items())}); "
+                  f"PoCs and clones taken from one cluster will not exercise the program deployed on another",
+                  name, addresses=by_cluster)
+
+    deploy = config.root / "target" / "deploy"
+    for name, address in provider_ids.items():
+        keypair = _keypair_address(deploy / f"{name}-keypair.json")
+        if keypair and keypair != address:
+            issue("keypair-mismatch", "medium",
+                  f"target/deploy/{name}-keypair.json is {keypair}, but Anchor.toml lists `{name}` as {address} on "
+                  f"{config.cluster}; `anchor deploy` would publish it at a different address",
+                  name, keypair=keypair, address=address)
+
+    if programs is None:
+        return issues
+    workspace = {program_key(p.name): p for p in programs if p.chain == "solana"}
+    for key, program in workspace.items():
+        address = provider_ids.get(key)
+        if address is None:
+            if program.framework == "anchor":
+                issue("unlisted-program", "low",
+                      f"`{program.name}` is not listed under [programs.{config.cluster}]; `anchor test` will not "
+                      f"deploy it", key, root=program.root)
+            continue
+        if program.program_id and program.program_id != address:
+            issue("declare-id-mismatch", "medium",
+                  f"`{program.name}` declares {program.program_id} but Anchor.toml lists {address} on "
+                  f"{config.cluster}; the deployed program fails with DeclaredProgramIdMismatch and PDAs derived "
+                  f"off-chain from either id disagree",
+                  key, declared=program.program_id, address=address, root=program.root)
+    for name in names if workspace else []:
+        if name not in workspace:
+            issue("unknown-program", "low", f"Anchor.toml lists `{name}`, but no such program is in the workspace",
+                  name)
+    return issues
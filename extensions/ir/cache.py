@@ -0,0 +1,188 @@
+"""
+Cache of lowered programs, keyed by the content of their sources.
+
+Every detector loads the programs under the path it is given, so a scan of
+a workspace with dozens of crates lowers each crate once per detector, and
+a repeat scan lowers them all again. While a ``ProgramCache`` is active
+(``use_program_cache``), ``load_programs`` looks each crate up by the
+SHA-256 of its Rust sources and Cargo.toml and only lowers crates whose
+content changed; which directories are crates is cached the same way.
+
+Entries are kept in memory and, given a directory, on disk as pickles, so
+they outlive the process. Each lookup returns a fresh copy: a detector
+annotating a Program does not change what the next one loads. Detectors
+running in parallel share one cache; a crate one of them is lowering is
+waited for, not lowered twice. Worker processes get a copy of the entries
+(``entries``) and hand back what they lowered (``add``).
+"""
+
+import contextlib
+import contextvars
+import hashlib
+import json
+import os
+import pickle
+import threading
+from collections.abc import Callable, Iterator
+from pathlib import Path
+from typing import TypeVar
+
+from .workspace import is_skipped
+
+# Bumped when the IR model changes shape, so pickles of the old one are not loaded
+CACHE_VERSION = 1
+
+T = TypeVar("T")
+
+_active: contextvars.ContextVar["ProgramCache | None"] = contextvars.ContextVar("program_cache", default=None)
+
+
+class FileHashes:
+    """SHA-256 of files, re-read only when their size or mtime changed."""
+
+    def __init__(self, index: Path | None = None):
+        """Initialize the hashes.
+
+        Args:
+            index: JSON file the known hashes are loaded from and saved to (default: memory only)
+        """
+        self.index = Path(index) if index else None
+        self._known: dict[str, tuple[int, int, str]] = {}
+        if self.index and self.index.is_file():
+            try:
+                self._known = {path: tuple(entry) for path, entry in json.loads(self.index.read_text()).items()}
+            except (OSError, ValueError, TypeError):
+                self._known = {}
+
+    def hash(self, path: Path) -> str:
+        """Content hash of ``path``; raises OSError if it cannot be read."""
+        stat = path.stat()
+        key = str(path)
+        known = self._known.get(key)
+        if known and (known[0], known[1]) == (stat.st_mtime_ns, stat.st_size):
+            return known[2]
+        digest = hashlib.sha256(path.read_bytes()).hexdigest()
+        self._known[key] = (stat.st_mtime_ns, stat.st_size, digest)
+        return digest
+
+    def digest(self, root: Path, files: list[Path], scope: str = "") -> str:
+        """One hash over the names (relative to ``root``) and contents of ``files``."""
+        h = hashlib.sha256(f"{CACHE_VERSION}|{scope}|{root}".encode())
+        for file in files:
+            try:
+                content = self.hash(file)
+            except OSError:
+                continue
+            h.update(f"{file.relative_to(root) if root.is_dir() else file.name}|{content}\n".encode())
+        return h.hexdigest()
+
+    def save(self) -> None:
+        if self.index:
+            self.index.parent.mkdir(parents=True, exist_ok=True)
+            self.index.write_text(json.dumps(self._known))
+
+
+def crate_files(root: Path) -> list[Path]:
+    """The files a crate's lowering reads: its Rust sources and Cargo.toml."""
+    if root.is_file():
+        return [root]
+    return sorted(p for p in root.rglob("*") if (p.suffix == ".rs" or p.name == "Cargo.toml") and p.is_file()
+                  and not is_skipped(p.relative_to(root)))
+
+
+class ProgramCache:
+    """Lowered programs (and crate roots) by the content hash of what they were made from."""
+
+    def __init__(self, cache_dir: Path | None = None, hashes: FileHashes | None = None):
+        """Initialize the cache.
+
+        Args:
+            cache_dir: Directory for the pickles (default: memory only)
+            hashes: File hashes to share with other caches of the same workspace
+        """
+        self.cache_dir = Path(cache_dir) if cache_dir else None
+        self.hashes = hashes or FileHashes()
+        self._memory: dict[str, bytes] = {}
+        self._lock = threading.Lock()
+        self._loading: dict[str, threading.Lock] = {}
+        self.hits = 0
+        self.misses = 0
+
+    def get_or_load(self, kind: str, root: Path, load: Callable[[], T]) -> T:
+        """What ``load`` returns for ``root``, from the cache while the crate's files are unchanged.
+
+        Args:
+            kind: What is loaded (e.g. "anchor" for a lowered Anchor crate, "anchor-roots" for crate discovery)
+            root: Crate root, workspace or file ``load`` reads
+            load: Computes the value on a miss
+        """
+        root = Path(root).resolve()
+        key = self.hashes.digest(root, crate_files(root), kind)
+        with self._lock:
+            loading = self._loading.setdefault(key, threading.Lock())
+        with loading:
+            return self._get_or_load(key, load)
+
+    def entries(self) -> dict[str, bytes]:
+        """The pickled entries held in memory, by key."""
+        with self._lock:
+            return dict(self._memory)
+
+    def add(self, entries: dict[str, bytes], hits: int = 0, misses: int = 0) -> None:
+        """Hold ``entries`` (from ``entries`` of another cache of the workspace) in memory.
+
+        Args:
+            entries: Pickled entries by key
+            hits: Lookups the other cache answered, counted as this cache's
+            misses: Entries the other cache lowered, counted as this cache's
+        """
+        with self._lock:
+            self._memory.update(entries)
+            self.hits += hits
+            self.misses += misses
+
+    def _get_or_load(self, key: str, load: Callable[[], T]) -> T:
+        path = self.cache_dir / f"{key}.pickle" if self.cache_dir else None
+        data = self._memory.get(key)
+        if data is None and path is not None and path.is_file():
+            with contextlib.suppress(OSError):
+                data = path.read_bytes()
+                # Pruning by age keeps the entries still in use
+                os.utime(path)
+        if data is not None:
+            try:
+                value = pickle.loads(data)
+            except Exception:
+                # Written by an incompatible version of the model
+                value = None
+            else:
+                with self._lock:
+                    self._memory[key] = data
+                    self.hits += 1
+                return value
+        with self._lock:
+            self.misses += 1
+        value = load()
+        data = pickle.dumps(value)
+        self._memory[key] = data
+        if path is not None:
+            path.parent.mkdir(parents=True, exist_ok=True)
+            tmp = path.with_suffix(f".{os.getpid()}.{threading.get_ident()}.tmp")
+            tmp.write_bytes(data)
+            tmp.replace(path)
+        return pickle.loads(data)
+
+
+def active_program_cache() -> ProgramCache | None:
+    """The cache ``load_programs`` uses, if one is active."""
+    return _active.get()
+
+
+@contextlib.contextmanager
+def use_program_cache(cache: ProgramCache | None) -> Iterator[None]:
+    """Make ``load_programs`` go through ``cache`` inside the block (None: no caching)."""
+    token = _active.set(cache)
+    try:
+        yield
+    finally:
+        _active.reset(token)
@@ -0,0 +1,571 @@
+"""
+CosmWasm front-end.
+
+A CosmWasm contract exposes a fixed set of entry points (`instantiate`,
+`execute`, `query`, `migrate`, `sudo`, `reply`), each taking a message
+type. The enum-typed ones dispatch with a `match msg { .. }` whose arms call
+per-message handlers; this module maps every message variant to its handler
+and lowers it to an `Instruction`, so CosmWasm detectors get the same shape
+as the other front-ends.
+
+Project layouts handled:
+- Single crate: `src/contract.rs` + `src/msg.rs` (cw-template)
+- Handlers split into modules: `execute::deposit(..)` arms resolved to
+  `src/execute.rs`, `src/contract/execute.rs`, or an inline `mod execute {}`
+- Workspaces where message types live in a shared `packages/*` crate
+- Sylvia contracts (`#[sv::msg(exec)]` methods on a `#[contract]` impl)
+
+When the crate ships a generated JSON schema (`schema/<name>.json`, or the
+older per-message `schema/execute_msg.json` files), each instruction gets its
+schema entry, and variants only present in the schema are added with
+``properties["schema_only"]``.
+
+CosmWasm has no account list; the message sender is modelled as a single
+``sender`` signer account on entry points that receive `MessageInfo`.
+"""
+
+import json
+import os
+import re
+from pathlib import Path
+
+from .model import AccountField, AccountType, Argument, CallSite, Instruction, Program
+from .rust_source import (
+    RustEnum,
+    RustFn,
+    called_functions,
+    find_matching,
+    iter_enums,
+    iter_functions,
+    iter_matches,
+    iter_structs,
+    line_at,
+    parse_match_arms,
+    parse_named_fields,
+    parse_params,
+    snake_case,
+    split_top_level,
+    variant_args,
+)
+from .workspace import SourceFile, crate_name, find_crates, is_skipped, load_rust_sources
+
+ENTRY_POINTS = ("instantiate", "execute", "query", "migrate", "sudo", "reply")
+# Entry points whose message is an enum of sub-commands; prefix for their instruction names
+_DISPATCH_PREFIX = {"execute": "", "query": "query_", "sudo": "sudo_", "migrate": "migrate_"}
+_SYLVIA_KINDS = {"instantiate": "instantiate", "exec": "execute", "query": "query", "sudo": "sudo", "migrate": "migrate"}
+_WRAPPER_CALLS = {"to_binary", "to_json_binary", "from_binary", "from_json", "Ok", "Err"}
+_STORAGE_RE = re.compile(
+    r"(?:pub(?:\s*\([^)]*\))?\s+)?const\s+([A-Z_][A-Z0-9_]*)\s*:\s*"
+    r"((?:Item|Map|IndexedMap|SnapshotMap|SnapshotItem|Deque|MultiIndex|UniqueIndex)\s*<[^;=]*>)\s*=\s*"
+    r"[\w:<>, ]*?(?:new|new_with_[\w]+)\s*\(\s*\"([^\"]*)\""
+)
+_SENDER_CHECK_RE = re.compile(
+    r"info\s*\.\s*sender\s*[!=]="
+    r"|[!=]=\s*&?\s*info\s*\.\s*sender\b"
+    r"|ensure_eq!\s*\(\s*&?info\s*\.\s*sender"
+    r"|\bassert_(?:owner|admin|admin_or_owner)\s*\("
+    r"|\.is_admin\s*\("
+)
+_MAX_DELEGATION_DEPTH = 3
+
+
+class CosmWasmFrontend:
+    """Builds IR Programs from CosmWasm contract crates."""
+
+    def __init__(self, max_files: int = 2000):
+        """Initialize the front-end.
+
+        Args:
+            max_files: Upper bound on Rust files read per crate
+        """
+        self.max_files = max_files
+
+    @staticmethod
+    def is_cosmwasm_source(source: str) -> bool:
+        """True if a file defines CosmWasm entry points (plain or Sylvia)."""
+        if re.search(r"\bentry_points\b", source) and re.search(r"#\[\s*(?:sv\s*::\s*)?contract\b", source):
+            return True
+        if not re.search(r"\bentry_point\b", source):
+            return False
+        return bool(re.search(r"\bfn\s+(?:instantiate|execute|query)\s*\(", source))
+
+    def find_program_roots(self, path: Path) -> list[Path]:
+        """Find contract crate roots under ``path``."""
+        return find_crates(Path(path), self.is_cosmwasm_source)
+
+    def load(self, path: Path) -> list[Program]:
+        """Load every contract under ``path``."""
+        return [self.load_program(root) for root in self.find_program_roots(path)]
+
+    def load_program(self, root: Path) -> Program:
+        """Lower a single contract crate."""
+        root = Path(root)
+        files = load_rust_sources(root, self.max_files)
+        program = Program(
+            name=crate_name(root),
+            chain="cosmwasm",
+            framework="cosmwasm",
+            root=str(root),
+            source_files=[f.relpath for f in files],
+        )
+        index = _Index(files)
+        workspace = _workspace_root(root)
+        packages = _PackageIndex(workspace, root, self.max_files) if workspace else None
+
+        if any(re.search(r"#\[\s*(?:sv\s*::\s*)?contract\b", sf.text) for sf in files):
+            program.framework = "sylvia"
+            self._lower_sylvia(program, index)
+        else:
+            for entry in ENTRY_POINTS:
+                fn_entry = index.entry_point(entry)
+                if fn_entry is not None:
+                    self._lower_entry(program, entry, fn_entry, index, packages)
+
+        program.account_types.extend(_storage(files))
+        schema = _load_schema(root)
+        if schema:
+            program.metadata["schema_files"] = sorted({path for path, _ in schema.values()})
+            _apply_schema(program, schema)
+        return program
+
+    # ------------------------------------------------------------------
+    # Entry points
+    # ------------------------------------------------------------------
+
+    def _lower_entry(self, program, entry: str, fn_entry, index: "_Index", packages) -> None:
+        sf, fn = fn_entry
+        params = parse_params(fn.params)
+        msg_type = _msg_type(params)
+        program.metadata.setdefault("entry_points", {})[entry] = {
+            "file": sf.relpath, "line": fn.line, "message": msg_type,
+        }
+        enum_info = index.enum(msg_type) if msg_type else None
+        if enum_info is None and msg_type and packages is not None:
+            enum_info = packages.enum(msg_type, sf)
+        has_info = any(ty.replace(" ", "").endswith("MessageInfo") for _, ty in params)
+
+        if entry in _DISPATCH_PREFIX and enum_info is not None:
+            dispatch = self._find_dispatch(fn_entry, enum_info[1].name, index, depth=0)
+            if dispatch is not None:
+                arms, dispatch_fn = dispatch
+                self._lower_arms(program, entry, enum_info, arms, dispatch_fn, fn_entry, index, has_info)
+                return
+
+        ix = self._build_instruction(entry, entry, sf, fn, has_info)
+        if msg_type:
+            struct = index.struct(msg_type) or (packages.struct(msg_type, sf) if packages else None)
+            if struct is not None:
+                ix.args = [Argument(name=n, ty=t) for n, t, _ in parse_named_fields(struct.body)]
+            ix.properties["message"] = msg_type
+        program.instructions.append(ix)
+
+    def _find_dispatch(self, fn_entry, enum_name: str, index: "_Index", depth: int):
+        """Find the `match` over ``enum_name`` in a function or one it delegates to."""
+        sf, fn = fn_entry
+        for _start, _scrutinee, arms_text in iter_matches(fn.body):
+            arms = [
+                (m.group(1), m.group(2), expr)
+                for pattern, expr in parse_match_arms(arms_text)
+                for m in [re.match(rf"(?:[\w:]*::)?{enum_name}\s*::\s*([A-Z]\w*)\s*(.*)", pattern, re.DOTALL)]
+                if m
+            ]
+            if arms:
+                return arms, fn_entry
+        if depth >= _MAX_DELEGATION_DEPTH:
+            return None
+        for path, callee in called_functions(fn.body):
+            target = index.resolve(callee, path, prefer=sf)
+            if target is None or target[1] is fn:
+                continue
+            found = self._find_dispatch(target, enum_name, index, depth + 1)
+            if found is not None:
+                return found
+        return None
+
+    def _lower_arms(self, program, entry, enum_info, arms, dispatch_fn, entry_fn, index, has_info) -> None:
+        enum_sf, enum = enum_info
+        variants = {v.name: v for v in enum.variants}
+        snake_keys = _serde_snake_case(enum)
+        guarded = _sender_checked(dispatch_fn[1].body) or _sender_checked(entry_fn[1].body)
+        for variant_name, _rest, expr in arms:
+            variant = variants.get(variant_name)
+            name = _DISPATCH_PREFIX[entry] + snake_case(variant_name)
+            handler = self._handler_for_arm(expr, dispatch_fn, index)
+            if handler is None:
+                sf, fn = dispatch_fn
+                offset = sf.text.find(expr[:80], fn.body_start) if expr else -1
+                ix = self._build_instruction(
+                    name, entry, sf, fn, has_info,
+                    body=expr, body_line=line_at(sf.text, offset) if offset >= 0 else fn.line,
+                )
+                ix.handler = fn.name
+                ix.properties["inline_handler"] = True
+            else:
+                ix = self._build_instruction(name, entry, handler[0], handler[1], has_info)
+            ix.discriminator = snake_case(variant_name) if snake_keys else variant_name
+            ix.properties["message"] = enum.name
+            ix.properties["variant"] = variant_name
+            if variant is not None:
+                ix.args = variant_args(variant.payload)
+                ix.properties["message_file"] = enum_sf.relpath
+                ix.properties["message_line"] = variant.line
+            if guarded:
+                ix.properties["sender_checked"] = True
+            program.instructions.append(ix)
+
+    def _handler_for_arm(self, expr: str, dispatch_fn, index: "_Index"):
+        sf, fn = dispatch_fn
+        for path, callee in called_functions(expr):
+            if callee in _WRAPPER_CALLS or callee == fn.name:
+                continue
+            target = index.resolve(callee, path, prefer=sf)
+            if target is not None:
+                return target
+        return None
+
+    def _lower_sylvia(self, program: Program, index: "_Index") -> None:
+        for sf in index.files:
+            for fn in iter_functions(sf.text, sf.original):
+                kind = next(
+                    (
+                        _SYLVIA_KINDS[m.group(1)]
+                        for attr in fn.attributes
+                        for m in [re.match(r"#\[\s*(?:sv\s*::\s*)?msg\s*\(\s*(\w+)", attr)]
+                        if m and m.group(1) in _SYLVIA_KINDS
+                    ),
+                    None,
+                )
+                if kind is None:
+                    continue
+                params = [(n, t) for n, t in parse_params(fn.params) if not t.replace(" ", "").endswith("Ctx")]
+                has_info = bool(re.search(r"\b(?:Exec|Instantiate|Migrate)Ctx\b", fn.params))
+                name = kind if kind == "instantiate" else _DISPATCH_PREFIX[kind] + fn.name
+                ix = self._build_instruction(name, kind, sf, fn, has_info)
+                ix.args = [Argument(name=n, ty=t) for n, t in params]
+                ix.discriminator = fn.name
+                program.instructions.append(ix)
+
+    # ------------------------------------------------------------------
+    # Handler lowering
+    # ------------------------------------------------------------------
+
+    def _build_instruction(self, name, entry, sf: SourceFile, fn: RustFn, has_info: bool, body=None, body_line=None):
+        body = fn.body if body is None else body
+        body_line = line_at(sf.text, fn.body_start) if body_line is None else body_line
+        ix = Instruction(
+            name=name,
+            handler=fn.name,
+            file=sf.relpath,
+            line=fn.line,
+            body=body,
+            body_line=body_line,
+            properties={"entry_point": entry},
+        )
+        if has_info:
+            ix.accounts.append(AccountField(name="sender", ty="Addr", is_signer=True, line=fn.line))
+            if _sender_checked(body):
+                ix.properties["sender_checked"] = True
+            if re.search(r"info\s*\.\s*funds\b|\b(?:must_pay|may_pay|one_coin|nonpayable)\s*\(", body):
+                ix.properties["reads_funds"] = True
+        ix.args = [Argument(name=n, ty=t) for n, t in parse_params(fn.params) if not _is_context_param(t)]
+        ix.calls = find_cosmos_msgs(body, body_line)
+        submessages = sorted(set(re.findall(r"SubMsg\s*::\s*(new|reply_on_success|reply_on_error|reply_always|reply_never)\b", body)))
+        if submessages:
+            ix.properties["submessages"] = submessages
+        return ix
+
+
+_MSG_RE = re.compile(
+    r"\b(?P<kind>WasmMsg\s*::\s*(?:Execute|Instantiate2|Instantiate|Migrate|UpdateAdmin|ClearAdmin)"
+    r"|BankMsg\s*::\s*(?:Send|Burn)|IbcMsg\s*::\s*(?:Transfer|SendPacket|CloseChannel)"
+    r"|StakingMsg\s*::\s*(?:Delegate|Undelegate|Redelegate)|DistributionMsg\s*::\s*\w+"
+    r"|CosmosMsg\s*::\s*(?:Stargate|Any|Custom|Gov))\s*(?P<open>[{(])"
+)
+_TARGET_FIELDS = ("contract_addr", "to_address", "channel_id", "validator", "dst_validator", "code_id")
+
+
+def find_cosmos_msgs(body: str, base_line: int = 1) -> list[CallSite]:
+    """Locate outgoing `CosmosMsg` constructions and smart queries in a handler body.
+
+    The CallSite ``kind`` is the snake-cased message (``wasm_execute``,
+    ``bank_send``, ``ibc_transfer``, ...) and ``target`` the contract,
+    recipient, channel or validator expression when it can be recovered.
+    """
+    calls: list[CallSite] = []
+    for m in _MSG_RE.finditer(body):
+        family, variant = [p.strip() for p in m.group("kind").split("::")]
+        open_idx = m.end() - 1
+        close_idx = find_matching(body, open_idx)
+        inner = body[open_idx + 1:close_idx]
+        target = ""
+        if m.group("open") == "{":
+            fields = {}
+            for part in split_top_level(inner):
+                fm = re.match(r"\s*(\w+)\s*(?::\s*(.*))?$", part, re.DOTALL)
+                if fm:
+                    fields[fm.group(1)] = (fm.group(2) or fm.group(1)).strip()
+            target = next((fields[f] for f in _TARGET_FIELDS if f in fields), "")
+        calls.append(
+            CallSite(
+                kind=f"{family.replace('Msg', '').lower()}_{snake_case(variant)}",
+                target=" ".join(target.split()),
+                line=base_line + body.count("\n", 0, m.start()),
+                text=" ".join(body[m.start():close_idx + 1].split())[:200],
+            )
+        )
+    for m in re.finditer(r"\.\s*query_wasm_(?:smart|raw)\s*\(", body):
+        open_idx = m.end() - 1
+        close_idx = find_matching(body, open_idx)
+        args = split_top_level(body[open_idx + 1:close_idx])
+        calls.append(
+            CallSite(
+                kind="wasm_query",
+                target=" ".join(args[0].split()) if args else "",
+                line=base_line + body.count("\n", 0, m.start()),
+                text=" ".join(body[m.start():close_idx + 1].split())[:200],
+            )
+        )
+    calls.sort(key=lambda c: c.line)
+    return calls
+
+
+class _Index:
+    """Functions, enums and structs of one crate, with module-aware lookup."""
+
+    def __init__(self, files: list[SourceFile]):
+        self.files = files
+        self.functions: dict[str, list[tuple[SourceFile, RustFn]]] = {}
+        self.enums: dict[str, tuple[SourceFile, RustEnum]] = {}
+        self.structs: dict[str, object] = {}
+        self.modules: dict[int, set[str]] = {}
+        for sf in files:
+            inline_mods = [
+                (m.group(1), m.end() - 1, find_matching(sf.text, m.end() - 1))
+                for m in re.finditer(r"\bmod\s+(\w+)\s*\{", sf.text)
+            ]
+            file_mods = {p for p in Path(sf.relpath).with_suffix("").parts if p not in ("src", "mod", "lib")}
+            for fn in iter_functions(sf.text, sf.original):
+                self.functions.setdefault(fn.name, []).append((sf, fn))
+                self.modules[id(fn)] = file_mods | {name for name, s, e in inline_mods if s < fn.start < e}
+            for en in iter_enums(sf.text, sf.original):
+                self.enums.setdefault(en.name, (sf, en))
+            for st in iter_structs(sf.text):
+                self.structs.setdefault(st.name, st)
+
+    def entry_point(self, name: str):
+        candidates = self.functions.get(name, [])
+        # Prefer the `#[entry_point]`-annotated definition over same-named helpers
+        for sf, fn in candidates:
+            if any("entry_point" in a for a in fn.attributes):
+                return sf, fn
+        for sf, fn in candidates:
+            if re.search(r"\bDeps(?:Mut)?\b", fn.params):
+                return sf, fn
+        return None
+
+    def resolve(self, name: str, path: str = "", prefer: SourceFile | None = None):
+        candidates = self.functions.get(name, [])
+        if not candidates:
+            return None
+        segments = [s for s in path.split("::") if s and s not in ("crate", "self", "super")]
+        if segments:
+            for sf, fn in candidates:
+                if segments[-1] in self.modules.get(id(fn), set()):
+                    return sf, fn
+        if prefer is not None:
+            for sf, fn in candidates:
+                if sf is prefer:
+                    return sf, fn
+        return candidates[0]
+
+    def enum(self, name: str):
+        return self.enums.get(name)
+
+    def struct(self, name: str):
+        return self.structs.get(name)
+
+
+class _PackageIndex:
+    """Message types defined in sibling crates of a workspace (`packages/*`)."""
+
+    def __init__(self, workspace: Path, own_root: Path, max_files: int):
+        self.crates: dict[str, list[SourceFile]] = {}
+        self._paths: dict[str, Path] = {}
+        for cargo in sorted(workspace.rglob("Cargo.toml")):
+            crate = cargo.parent
+            if is_skipped(crate) or crate.resolve() in (own_root.resolve(), workspace.resolve()):
+                continue
+            self._paths[crate_name(crate).replace("-", "_")] = crate
+        self._own_root = own_root
+        self._max_files = max_files
+        self._indexes: dict[str, _Index] = {}
+
+    def _index(self, crate: str) -> "_Index | None":
+        if crate not in self._indexes and crate in self._paths:
+            files = load_rust_sources(self._paths[crate], self._max_files)
+            # Report package files relative to the contract crate, like its own sources
+            for sf in files:
+                sf.relpath = Path(os.path.relpath(self._paths[crate] / sf.relpath, self._own_root)).as_posix()
+            self._indexes[crate] = _Index(files)
+        return self._indexes.get(crate)
+
+    def _candidates(self, name: str, sf: SourceFile) -> list[str]:
+        """Crates the file imports ``name`` from first, then every other package."""
+        imported = [
+            m.group(1)
+            for m in re.finditer(r"\buse\s+(\w+)\s*::([^;]*);", sf.text)
+            if re.search(rf"\b{name}\b", m.group(2)) or m.group(2).rstrip().endswith("*")
+        ]
+        return [c for c in imported if c in self._paths] + [c for c in self._paths if c not in imported]
+
+    def enum(self, name: str, sf: SourceFile):
+        for crate in self._candidates(name, sf):
+            found = self._index(crate).enum(name)
+            if found is not None:
+                return found
+        return None
+
+    def struct(self, name: str, sf: SourceFile):
+        for crate in self._candidates(name, sf):
+            found = self._index(crate).struct(name)
+            if found is not None:
+                return found
+        return None
+
+
+def _workspace_root(root: Path) -> Path | None:
+    """Nearest ancestor (up to three levels) whose Cargo.toml declares a `[workspace]`."""
+    current = root
+    for _ in range(4):
+        cargo = current / "Cargo.toml"
+        if cargo.exists() and re.search(r"(?m)^\[workspace\]", cargo.read_text(errors="replace")):
+            return current
+        if current.parent == current:
+            break
+        current = current.parent
+    return None
+
+
+def _msg_type(params: list[tuple[str, str]]) -> str | None:
+    """Message type of an entry point: the `msg` parameter, else the last one."""
+    for name, ty in params:
+        if name in ("msg", "_msg"):
+            return ty.split("::")[-1].strip()
+    if params and not _is_context_param(params[-1][1]):
+        return params[-1][1].split("::")[-1].strip()
+    return None
+
+
+def _is_context_param(ty: str) -> bool:
+    compact = ty.replace(" ", "").lstrip("&")
+    return bool(re.fullmatch(r"(?:cosmwasm_std::)?(?:Deps|DepsMut|Env|MessageInfo)(?:<.*>)?", compact))
+
+
+def _serde_snake_case(enum: RustEnum) -> bool:
+    return any("cw_serde" in a or re.search(r"rename_all\s*=\s*\"snake_case\"", a) for a in enum.attributes)
+
+
+def _sender_checked(body: str) -> bool:
+    return bool(_SENDER_CHECK_RE.search(body))
+
+
+def _storage(files: list[SourceFile]) -> list[AccountType]:
+    """One storage layout per crate built from cw-storage-plus `Item`/`Map` constants."""
+    fields: list[Argument] = []
+    keys: dict[str, str] = {}
+    first = None
+    for sf in files:
+        for m in _STORAGE_RE.finditer(sf.text):
+            fields.append(Argument(name=m.group(1), ty=" ".join(m.group(2).split())))
+            keys[m.group(1)] = m.group(3)
+            first = first or (sf.relpath, line_at(sf.text, m.start()))
+    if not fields:
+        return []
+    return [AccountType(name="storage", file=first[0], line=first[1], fields=fields, properties={"kind": "storage", "keys": keys})]
+
+
+# ----------------------------------------------------------------------
+# JSON schema
+# ----------------------------------------------------------------------
+
+_SCHEMA_SECTIONS = {"instantiate": "instantiate", "execute": "execute", "query": "query", "migrate": "migrate", "sudo": "sudo"}
+
+
+def _load_schema(root: Path) -> dict[str, tuple[str, dict]]:
+    """Entry point → (schema file, JSON schema) from the crate's `schema/` directory."""
+    schema_dir = root / "schema"
+    if not schema_dir.is_dir():
+        return {}
+    sections: dict[str, tuple[str, dict]] = {}
+    for path in sorted(schema_dir.glob("*.json")):
+        try:
+            data = json.loads(path.read_text(errors="replace"))
+        except (OSError, json.JSONDecodeError):
+            continue
+        rel = path.relative_to(root).as_posix()
+        if isinstance(data, dict) and "contract_name" in data:
+            # Combined schema written by cosmwasm-schema >= 1.1
+            for key, entry in _SCHEMA_SECTIONS.items():
+                if isinstance(data.get(key), dict):
+                    sections[entry] = (rel, data[key])
+            continue
+        m = re.fullmatch(r"(instantiate|execute|query|migrate|sudo)_msg", path.stem)
+        if m and isinstance(data, dict):
+            sections.setdefault(m.group(1), (rel, data))
+    return sections
+
+
+def _schema_variants(schema: dict) -> dict[str, dict]:
+    """Serde key → variant schema for an enum message (`oneOf` entries)."""
+    variants: dict[str, dict] = {}
+    for option in schema.get("oneOf", []) or schema.get("anyOf", []):
+        if option.get("type") == "string":
+            for key in option.get("enum", []):
+                variants[key] = {"type": "object", "properties": {}}
+            continue
+        for key in option.get("required", []) or list(option.get("properties", {})):
+            variants[key] = option.get("properties", {}).get(key, {})
+    return variants
+
+
+def _schema_type(prop: dict) -> str:
+    if "$ref" in prop:
+        return prop["$ref"].rsplit("/", 1)[-1]
+    if "allOf" in prop and prop["allOf"]:
+        return _schema_type(prop["allOf"][0])
+    ty = prop.get("type", "")
+    if isinstance(ty, list):
+        ty = "|".join(str(t) for t in ty)
+    return ty or "any"
+
+
+def _apply_schema(program: Program, schema: dict[str, tuple[str, dict]]) -> None:
+    for entry, (rel, section) in schema.items():
+        if entry not in _DISPATCH_PREFIX or not ("oneOf" in section or "anyOf" in section):
+            for ix in program.instructions:
+                if ix.name == entry and ix.properties.get("entry_point") == entry:
+                    ix.properties["schema"] = section
+            continue
+        by_key = {
+            ix.discriminator: ix for ix in program.instructions
+            if ix.properties.get("entry_point") == entry and ix.discriminator
+        }
+        for key, variant in _schema_variants(section).items():
+            ix = by_key.get(key)
+            if ix is None:
+                ix = Instruction(
+                    name=_DISPATCH_PREFIX[entry] + key,
+                    handler="",
+                    file=rel,
+                    line=0,
+                    discriminator=key,
+                    properties={"entry_point": entry, "schema_only": True},
+                )
+                ix.args = [Argument(name=n, ty=_schema_type(p)) for n, p in variant.get("properties", {}).items()]
+                program.instructions.append(ix)
+            ix.properties["schema"] = variant
+
+
+def load_cosmwasm_programs(path: Path) -> list[Program]:
+    """Convenience wrapper around CosmWasmFrontend.load()."""
+    return CosmWasmFrontend().load(Path(path))
@@ -0,0 +1,275 @@
+"""
+Yul and Huff front-ends.
+
+Hand-written assembly contracts dispatch on the selector themselves, so we
+recover entry points from that dispatcher and expand each handler through
+the functions (Yul) or macros (Huff) it invokes, recording storage accesses
+and outgoing calls in source order. The resulting Instructions carry the
+same ``storage_reads`` / ``storage_writes`` / ``effects`` properties as the
+bytecode front-end, with real line numbers.
+
+- Yul: `switch <selector expr> case 0x... { ... }` inside the deployed
+  object's `code` block; `function f(..) -> .. { }` definitions are inlined
+- Huff: `<sig> eq <label> jumpi` chains in the MAIN macro, where `<sig>` is
+  a hex literal or `__FUNC_SIG(name)`; macros invoked after `<label>:` are
+  inlined
+"""
+
+import re
+from pathlib import Path
+
+from .evm_bytecode import instruction_from_effects
+from .keccak import function_selector
+from .model import Program
+from .rust_source import find_matching, line_at, split_top_level, strip_comments
+
+_CALL_BUILTINS = {"call": "call", "delegatecall": "delegatecall", "staticcall": "staticcall", "callcode": "callcode"}
+_MAX_DEPTH = 16
+
+
+class YulFrontend:
+    """Builds IR Programs from standalone Yul objects."""
+
+    @staticmethod
+    def has_sources(path: Path) -> bool:
+        path = Path(path)
+        return path.suffix == ".yul" if path.is_file() else any(path.rglob("*.yul"))
+
+    def load(self, path: Path) -> list[Program]:
+        path = Path(path)
+        files = [path] if path.is_file() else sorted(path.rglob("*.yul"))
+        root = path.parent if path.is_file() else path
+        return [self.load_source(f.read_text(errors="replace"), f.relative_to(root).as_posix(), str(root)) for f in files]
+
+    def load_source(self, source: str, file: str = "", root: str = "") -> Program:
+        text = strip_comments(source)
+        objects = re.findall(r'\bobject\s+"([^"]+)"', text)
+        program = Program(
+            name=objects[0] if objects else Path(file).stem,
+            chain="evm",
+            framework="yul",
+            root=root,
+            source_files=[file] if file else [],
+            metadata={"objects": objects},
+        )
+        functions = _yul_functions(text)
+        for selector, case_start, case_end in _yul_cases(text):
+            block = text[case_start + 1:case_end]
+            called = [m.group(1) for m in re.finditer(r"\b([A-Za-z_$][\w$.]*)\s*\(", block) if m.group(1) in functions]
+            name = called[0] if called else f"selector_{selector}"
+            effects, targets = [], {}
+            _yul_effects(text, case_start + 1, case_end, functions, effects, targets, depth=0)
+            program.instructions.append(instruction_from_effects(
+                name=name,
+                selector=selector,
+                file=file,
+                effects=effects,
+                targets=targets,
+                properties={},
+                body=" ".join(block.split()),
+                line=line_at(text, case_start),
+                body_line=line_at(text, case_start),
+            ))
+        return program
+
+
+def _yul_functions(text: str) -> dict[str, tuple[int, int]]:
+    """Function name → (body open brace, body close brace) offsets."""
+    functions = {}
+    for m in re.finditer(r"\bfunction\s+([A-Za-z_$][\w$.]*)\s*\([^)]*\)\s*(?:->\s*[^{]*)?\{", text):
+        start = m.end() - 1
+        functions[m.group(1)] = (start, find_matching(text, start))
+    return functions
+
+
+def _yul_cases(text: str) -> list[tuple[str, int, int]]:
+    """(selector, block open, block close) for every selector `case`."""
+    cases = []
+    for sw in re.finditer(r"\bswitch\b([^{}]*?)(?=\bcase\b)", text):
+        # The switch expression must look at calldata (directly or via a helper)
+        expr = sw.group(1)
+        if "calldataload" not in expr and "selector" not in expr.lower():
+            continue
+        pos = sw.end()
+        while True:
+            cm = re.compile(r"\s*case\s+(0x[0-9a-fA-F]+|\d+)\s*\{").match(text, pos)
+            if not cm:
+                break
+            open_brace = cm.end() - 1
+            close = find_matching(text, open_brace)
+            literal = cm.group(1)
+            value = int(literal, 16) if literal.startswith("0x") else int(literal)
+            cases.append((f"{value:08x}", open_brace, close))
+            pos = close + 1
+    return cases
+
+
+def _yul_effects(text, start, end, functions, effects, targets, depth) -> None:
+    """Append sload/sstore/call effects in [start, end), inlining user functions."""
+    if depth > _MAX_DEPTH:
+        return
+    for m in re.finditer(r"\b([A-Za-z_$][\w$.]*)\s*\(", text[start:end]):
+        name = m.group(1)
+        open_paren = start + m.end() - 1
+        close = find_matching(text, open_paren)
+        args = split_top_level(text[open_paren + 1:close]) if close > open_paren else []
+        line = line_at(text, start + m.start())
+        if name == "sload":
+            effects.append({"op": "sload", "arg": " ".join(args[0].split()) if args else "", "line": line})
+        elif name == "sstore":
+            effects.append({"op": "sstore", "arg": " ".join(args[0].split()) if args else "", "line": line})
+        elif name in _CALL_BUILTINS:
+            target = " ".join(args[1].split()) if len(args) > 1 else ""
+            targets[len(effects)] = " ".join(text[start + m.start():close + 1].split())
+            effects.append({"op": _CALL_BUILTINS[name], "arg": target, "line": line})
+        elif name in functions:
+            body_start, body_end = functions[name]
+            _yul_effects(text, body_start + 1, body_end, functions, effects, targets, depth + 1)
+
+
+class HuffFrontend:
+    """Builds IR Programs from Huff sources."""
+
+    @staticmethod
+    def has_sources(path: Path) -> bool:
+        path = Path(path)
+        return path.suffix == ".huff" if path.is_file() else any(path.rglob("*.huff"))
+
+    def load(self, path: Path) -> list[Program]:
+        """Load Huff contracts (files defining a MAIN macro) under ``path``."""
+        path = Path(path)
+        files = [path] if path.is_file() else sorted(path.rglob("*.huff"))
+        root = path.parent if path.is_file() else path
+        sources = {f: f.read_text(errors="replace") for f in files}
+        programs = []
+        for f, source in sources.items():
+            if not re.search(r"#define\s+macro\s+MAIN\b", source):
+                continue
+            programs.append(self.load_source(source, f.relative_to(root).as_posix(), str(root), _includes(f, source)))
+        return programs
+
+    def load_source(self, source: str, file: str = "", root: str = "", includes: list[str] | None = None) -> Program:
+        text = strip_comments(source)
+        # Included files contribute macro and function definitions, not entry points
+        defs_text = "\n".join([text] + [strip_comments(inc) for inc in includes or []])
+        macros = _huff_macros(text)
+        for inc in includes or []:
+            for name, entry in _huff_macros(strip_comments(inc)).items():
+                macros.setdefault(name, entry)
+        signatures = {
+            m.group(1): f"{m.group(1)}({','.join(a.split()[0] for a in split_top_level(m.group(2)) if a.strip())})"
+            for m in re.finditer(r"#define\s+function\s+(\w+)\s*\(([^)]*)\)", defs_text)
+        }
+        program = Program(
+            name=Path(file).stem,
+            chain="evm",
+            framework="huff",
+            root=root,
+            source_files=[file] if file else [],
+        )
+        if "MAIN" not in macros:
+            return program
+
+        main_start, main_end, _ = macros["MAIN"]
+        tokens = _tokens(text, main_start, main_end)
+        labels = {tok[:-1]: i for i, (tok, _) in enumerate(tokens) if tok.endswith(":")}
+        for i, (tok, _) in enumerate(tokens):
+            # <sig> eq <label> jumpi   (an optional dup between sig and eq)
+            if tok != "eq" or i + 2 >= len(tokens) or tokens[i + 2][0] != "jumpi":
+                continue
+            sig_tok = tokens[i - 1][0] if i else ""
+            if re.fullmatch(r"dup\d+", sig_tok) and i >= 2:
+                sig_tok = tokens[i - 2][0]
+            selector = _huff_selector(sig_tok, signatures)
+            label = tokens[i + 1][0]
+            if selector is None or label not in labels:
+                continue
+            label_idx = labels[label]
+            handler = next(
+                (t[:-2] for t, _ in tokens[label_idx + 1:] if t.endswith("()") and t[:-2] in macros),
+                label,
+            )
+            effects: list[dict] = []
+            _huff_effects(tokens, label_idx + 1, macros, text, effects, depth=0)
+            ix = instruction_from_effects(
+                name=label,
+                selector=selector,
+                file=file,
+                effects=effects,
+                properties={"label": label},
+                line=tokens[label_idx][1],
+                body_line=tokens[label_idx][1],
+                body=" ".join(t for t, _ in tokens[label_idx:]),
+            )
+            ix.handler = handler
+            program.instructions.append(ix)
+        return program
+
+
+def _includes(path: Path, source: str) -> list[str]:
+    included = []
+    for m in re.finditer(r'#include\s+"([^"]+)"', source):
+        candidate = path.parent / m.group(1)
+        if candidate.exists():
+            included.append(candidate.read_text(errors="replace"))
+    return included
+
+
+def _huff_macros(text: str) -> dict[str, tuple[int, int, str]]:
+    """Macro/fn name → (body open, body close, defining text)."""
+    macros = {}
+    for m in re.finditer(r"#define\s+(?:macro|fn)\s+(\w+)\s*\([^)]*\)\s*=[^{]*\{", text):
+        start = m.end() - 1
+        macros[m.group(1)] = (start, find_matching(text, start), text)
+    return macros
+
+
+def _tokens(text: str, start: int, end: int) -> list[tuple[str, int]]:
+    return [
+        (m.group(0), line_at(text, start + 1 + m.start()))
+        for m in re.finditer(r"__FUNC_SIG\([^)]*\)|\[\w+\]|<\w+>|[\w.]+:|\w+\(\)|\S+", text[start + 1:end])
+    ]
+
+
+def _huff_selector(token: str, signatures: dict[str, str]) -> str | None:
+    fm = re.fullmatch(r"__FUNC_SIG\(\s*\"?([^)\"]+)\"?\s*\)", token)
+    if fm:
+        sig = fm.group(1).strip()
+        return function_selector(signatures.get(sig, sig if "(" in sig else f"{sig}()"))
+    if re.fullmatch(r"0x[0-9a-fA-F]{1,8}", token):
+        return f"{int(token, 16):08x}"
+    return None
+
+
+def _huff_effects(tokens, start, macros, text, effects, depth) -> None:
+    """Append effects from ``tokens[start:]`` up to the next label, inlining macros."""
+    if depth > _MAX_DEPTH:
+        return
+    for i in range(start, len(tokens)):
+        tok, line = tokens[i]
+        if tok.endswith(":") and i != start:
+            break
+        prev = tokens[i - 1][0] if i > start else ""
+        if tok in ("sload", "sstore"):
+            slot = prev if re.fullmatch(r"0x[0-9a-fA-F]+|\[\w+\]", prev) else "dynamic"
+            effects.append({"op": tok, "arg": slot, "line": line})
+        elif tok in _CALL_BUILTINS:
+            effects.append({"op": _CALL_BUILTINS[tok], "arg": "stack", "line": line})
+        elif tok.endswith("()") and tok[:-2] in macros:
+            body_start, body_end, macro_text = macros[tok[:-2]]
+            if macro_text != text:
+                # Macro from an included file: effects without local line numbers
+                inner = [(t, 0) for t, _ in _tokens(macro_text, body_start, body_end)]
+            else:
+                inner = _tokens(text, body_start, body_end)
+            _huff_effects(inner, 0, macros, text, effects, depth + 1)
+
+
+def load_yul_programs(path: Path) -> list[Program]:
+    """Convenience wrapper around YulFrontend.load()."""
+    return YulFrontend().load(Path(path))
+
+
+def load_huff_programs(path: Path) -> list[Program]:
+    """Convenience wrapper around HuffFrontend.load()."""
+    return HuffFrontend().load(Path(path))
@@ -0,0 +1,341 @@
+"""
+EVM bytecode front-end.
+
+For hand-optimized or unverified contracts there may be no Solidity or
+Vyper source to lower. This front-end works from runtime bytecode:
+it disassembles, recovers the selector dispatcher (`PUSH4 sel EQ PUSH dest
+JUMPI`), walks the blocks reachable from each function entry, and records
+storage accesses and outgoing calls in program order.
+
+Recovered effects live on each Instruction:
+- ``calls`` - CALL / DELEGATECALL / STATICCALL / CALLCODE sites
+- ``properties["storage_reads"]`` / ``["storage_writes"]`` - constant slots
+  (hex) or "dynamic" for computed (mapping/array) slots
+- ``properties["effects"]`` - ordered ``{"op", "arg", "line"}`` records so
+  detectors can ask "is there an SSTORE after the CALL?"
+
+Locations are program counters: ``line`` is 0 and ``properties["pc"]``
+holds the entry offset.
+"""
+
+import json
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from .model import CallSite, Instruction, Program
+
+OPCODES: dict[int, str] = {
+    0x00: "STOP", 0x01: "ADD", 0x02: "MUL", 0x03: "SUB", 0x04: "DIV", 0x05: "SDIV", 0x06: "MOD",
+    0x07: "SMOD", 0x08: "ADDMOD", 0x09: "MULMOD", 0x0A: "EXP", 0x0B: "SIGNEXTEND",
+    0x10: "LT", 0x11: "GT", 0x12: "SLT", 0x13: "SGT", 0x14: "EQ", 0x15: "ISZERO", 0x16: "AND",
+    0x17: "OR", 0x18: "XOR", 0x19: "NOT", 0x1A: "BYTE", 0x1B: "SHL", 0x1C: "SHR", 0x1D: "SAR",
+    0x20: "KECCAK256",
+    0x30: "ADDRESS", 0x31: "BALANCE", 0x32: "ORIGIN", 0x33: "CALLER", 0x34: "CALLVALUE",
+    0x35: "CALLDATALOAD", 0x36: "CALLDATASIZE", 0x37: "CALLDATACOPY", 0x38: "CODESIZE",
+    0x39: "CODECOPY", 0x3A: "GASPRICE", 0x3B: "EXTCODESIZE", 0x3C: "EXTCODECOPY",
+    0x3D: "RETURNDATASIZE", 0x3E: "RETURNDATACOPY", 0x3F: "EXTCODEHASH",
+    0x40: "BLOCKHASH", 0x41: "COINBASE", 0x42: "TIMESTAMP", 0x43: "NUMBER", 0x44: "PREVRANDAO",
+    0x45: "GASLIMIT", 0x46: "CHAINID", 0x47: "SELFBALANCE", 0x48: "BASEFEE", 0x49: "BLOBHASH",
+    0x4A: "BLOBBASEFEE",
+    0x50: "POP", 0x51: "MLOAD", 0x52: "MSTORE", 0x53: "MSTORE8", 0x54: "SLOAD", 0x55: "SSTORE",
+    0x56: "JUMP", 0x57: "JUMPI", 0x58: "PC", 0x59: "MSIZE", 0x5A: "GAS", 0x5B: "JUMPDEST",
+    0x5C: "TLOAD", 0x5D: "TSTORE", 0x5E: "MCOPY", 0x5F: "PUSH0",
+    0xF0: "CREATE", 0xF1: "CALL", 0xF2: "CALLCODE", 0xF3: "RETURN", 0xF4: "DELEGATECALL",
+    0xF5: "CREATE2", 0xFA: "STATICCALL", 0xFD: "REVERT", 0xFE: "INVALID", 0xFF: "SELFDESTRUCT",
+}
+OPCODES.update({0x60 + i: f"PUSH{i + 1}" for i in range(32)})
+OPCODES.update({0x80 + i: f"DUP{i + 1}" for i in range(16)})
+OPCODES.update({0x90 + i: f"SWAP{i + 1}" for i in range(16)})
+OPCODES.update({0xA0 + i: f"LOG{i}" for i in range(5)})
+
+_TERMINATORS = {"STOP", "RETURN", "REVERT", "INVALID", "SELFDESTRUCT", "JUMP"}
+CALL_OPS = {"CALL": "call", "DELEGATECALL": "delegatecall", "STATICCALL": "staticcall", "CALLCODE": "callcode"}
+STORAGE_OPS = {"SLOAD": "sload", "SSTORE": "sstore"}
+_MAX_BLOCKS = 4096
+
+
+@dataclass
+class Op:
+    """One disassembled instruction."""
+
+    pc: int
+    name: str
+    arg: int | None = None  # PUSH immediate
+
+    def __str__(self) -> str:
+        return f"{self.pc:#06x} {self.name}" + (f" {self.arg:#x}" if self.arg is not None else "")
+
+
+def parse_hex(text: str) -> bytes:
+    """Decode bytecode from hex text (optional 0x prefix and whitespace)."""
+    clean = re.sub(r"\s+", "", text.strip())
+    clean = clean[2:] if clean.startswith("0x") else clean
+    if len(clean) % 2:
+        raise ValueError("odd-length hex bytecode")
+    return bytes.fromhex(clean)
+
+
+def strip_metadata(code: bytes) -> tuple[bytes, bool]:
+    """Drop the trailing CBOR metadata solc/vyper append (length in the last 2 bytes)."""
+    if len(code) < 4:
+        return code, False
+    length = int.from_bytes(code[-2:], "big")
+    start = len(code) - 2 - length
+    # CBOR map header (0xa1..0xa5) at the start of the metadata section
+    if 0 < length < len(code) and 0xA1 <= code[start] <= 0xA5:
+        return code[:start], True
+    return code, False
+
+
+def runtime_code(code: bytes) -> bytes:
+    """Extract runtime code when given creation code (constructor + CODECOPY + RETURN)."""
+    ops = disassemble(code[:256])
+    names = [op.name for op in ops]
+    if "CODECOPY" in names and "RETURN" in names:
+        marker = code.find(bytes.fromhex("6080604052"), 1)
+        if marker > 0:
+            return code[marker:]
+    return code
+
+
+def disassemble(code: bytes) -> list[Op]:
+    ops: list[Op] = []
+    pc = 0
+    while pc < len(code):
+        byte = code[pc]
+        name = OPCODES.get(byte, f"UNKNOWN_{byte:#04x}")
+        if 0x60 <= byte <= 0x7F:
+            width = byte - 0x5F
+            ops.append(Op(pc, name, int.from_bytes(code[pc + 1:pc + 1 + width].ljust(width, b"\0"), "big")))
+            pc += 1 + width
+        else:
+            ops.append(Op(pc, name, 0 if name == "PUSH0" else None))
+            pc += 1
+    return ops
+
+
+class BytecodeAnalysis:
+    """Blocks, dispatcher, and per-function reachability for one contract."""
+
+    def __init__(self, code: bytes):
+        self.ops = disassemble(code)
+        self.jumpdests = {op.pc for op in self.ops if op.name == "JUMPDEST"}
+        self.blocks: dict[int, list[Op]] = {}
+        current: list[Op] = []
+        for op in self.ops:
+            if op.name == "JUMPDEST" and current:
+                self.blocks[current[0].pc] = current
+                current = []
+            current.append(op)
+            if op.name in _TERMINATORS or op.name == "JUMPI":
+                self.blocks[current[0].pc] = current
+                current = []
+        if current:
+            self.blocks[current[0].pc] = current
+        self._order = sorted(self.blocks)
+
+    def selectors(self) -> dict[str, int]:
+        """Selector (hex, no 0x) → function entry pc, from the dispatcher."""
+        found: dict[str, int] = {}
+        ops = self.ops
+        for i, op in enumerate(ops):
+            if op.name != "PUSH4" or op.arg is None:
+                continue
+            window = ops[i + 1:i + 5]
+            names = [o.name for o in window]
+            if "EQ" not in names:
+                continue
+            eq = names.index("EQ")
+            if eq + 2 < len(window) and window[eq + 1].name.startswith("PUSH") and window[eq + 2].name == "JUMPI":
+                dest = window[eq + 1].arg
+                if dest in self.jumpdests:
+                    found.setdefault(f"{op.arg:08x}", dest)
+        return found
+
+    def _next_block(self, start: int) -> int | None:
+        idx = self._order.index(start)
+        return self._order[idx + 1] if idx + 1 < len(self._order) else None
+
+    def reachable(self, entry: int, stop_at: set[int]) -> list[int]:
+        """Block starts reachable from ``entry`` (in pc order).
+
+        Static jumps are followed directly. Dynamic jumps (internal-function
+        returns) are approximated by every JUMPDEST pushed as a constant in
+        already-visited blocks, excluding other functions' entries.
+        """
+        seen: set[int] = set()
+        pushed: set[int] = set()
+        work = [entry]
+        while work and len(seen) < _MAX_BLOCKS:
+            start = work.pop()
+            if start in seen or start not in self.blocks:
+                continue
+            seen.add(start)
+            block = self.blocks[start]
+            for op in block:
+                if op.name.startswith("PUSH") and op.arg in self.jumpdests and op.arg not in stop_at:
+                    pushed.add(op.arg)
+            last = block[-1]
+            prev = block[-2] if len(block) > 1 else None
+            static = prev.arg if prev is not None and prev.name.startswith("PUSH") else None
+            if last.name in ("JUMP", "JUMPI"):
+                if static is not None:
+                    if static not in stop_at:
+                        work.append(static)
+                else:
+                    work.extend(p for p in pushed if p not in seen)
+            if last.name not in _TERMINATORS:
+                nxt = self._next_block(start)
+                if nxt is not None and nxt not in stop_at:
+                    work.append(nxt)
+            if not work:
+                work.extend(p for p in pushed if p not in seen)
+        return sorted(seen)
+
+    def effects(self, blocks: list[int]) -> list[dict[str, Any]]:
+        """Storage and call effects in the given blocks, in pc order."""
+        effects: list[dict[str, Any]] = []
+        for start in blocks:
+            block = self.blocks[start]
+            for i, op in enumerate(block):
+                if op.name in STORAGE_OPS:
+                    prev = block[i - 1] if i else None
+                    slot = f"{prev.arg:#x}" if prev is not None and prev.name.startswith("PUSH") else "dynamic"
+                    effects.append({"op": STORAGE_OPS[op.name], "arg": slot, "line": 0, "pc": op.pc})
+                elif op.name in CALL_OPS:
+                    effects.append({"op": CALL_OPS[op.name], "arg": "stack", "line": 0, "pc": op.pc})
+        return effects
+
+
+class BytecodeFrontend:
+    """Builds IR Programs from runtime bytecode."""
+
+    SUFFIXES = (".bin", ".hex", ".bytecode")
+
+    @classmethod
+    def has_sources(cls, path: Path) -> bool:
+        path = Path(path)
+        if path.is_file():
+            return path.suffix in cls.SUFFIXES
+        return any(path.rglob("*.bin")) or any(path.rglob("*.hex"))
+
+    def load(self, path: Path) -> list[Program]:
+        """Load a bytecode file, an artifact JSON, or every `.bin`/`.hex` under a directory."""
+        path = Path(path)
+        if path.is_dir():
+            files = sorted(p for suffix in self.SUFFIXES for p in path.rglob(f"*{suffix}"))
+            return [self.load_file(f, root=path) for f in files]
+        return [self.load_file(path, root=path.parent)]
+
+    def load_file(self, path: Path, root: Path) -> Program:
+        text = path.read_text(errors="replace")
+        if path.suffix == ".json":
+            text = _artifact_bytecode(json.loads(text))
+        rel = path.relative_to(root).as_posix() if path.is_relative_to(root) else path.name
+        return self.load_bytecode(parse_hex(text), name=path.stem, file=rel, root=str(root))
+
+    def load_bytecode(self, code: bytes, name: str = "contract", file: str = "", root: str = "") -> Program:
+        """Lower raw bytecode (creation or runtime) into a Program."""
+        code = runtime_code(code)
+        code, had_metadata = strip_metadata(code)
+        analysis = BytecodeAnalysis(code)
+        selectors = analysis.selectors()
+        entries = set(selectors.values())
+
+        program = Program(
+            name=name,
+            chain="evm",
+            framework="bytecode",
+            root=root,
+            source_files=[file] if file else [],
+            metadata={"code_size": len(code), "stripped_metadata": had_metadata, "selectors": len(selectors)},
+        )
+        for selector, entry in selectors.items():
+            blocks = analysis.reachable(entry, stop_at=entries - {entry})
+            program.instructions.append(instruction_from_effects(
+                name=f"selector_{selector}",
+                selector=selector,
+                file=file,
+                effects=analysis.effects(blocks),
+                properties={"pc": entry, "blocks": len(blocks)},
+                body="\n".join(str(op) for start in blocks for op in analysis.blocks[start]),
+            ))
+        if not selectors:
+            # No dispatcher: treat the whole contract as one entry point
+            blocks = analysis.reachable(0, stop_at=set())
+            program.instructions.append(instruction_from_effects(
+                name="fallback",
+                selector=None,
+                file=file,
+                effects=analysis.effects(blocks),
+                properties={"pc": 0, "blocks": len(blocks), "kind": "fallback"},
+                body="\n".join(str(op) for start in blocks for op in analysis.blocks[start]),
+            ))
+        return program
+
+
+def instruction_from_effects(
+    name: str,
+    selector: str | None,
+    file: str,
+    effects: list[dict[str, Any]],
+    properties: dict[str, Any],
+    body: str = "",
+    line: int = 0,
+    body_line: int = 0,
+    targets: dict[int, str] | None = None,
+) -> Instruction:
+    """Build an Instruction from ordered effects (shared with the Yul/Huff front-ends)."""
+    targets = targets or {}
+    calls = [
+        CallSite(kind=e["op"], target=e["arg"], line=e["line"], text=targets.get(i, ""))
+        for i, e in enumerate(effects)
+        if e["op"] in CALL_OPS.values()
+    ]
+    properties = dict(properties)
+    properties.setdefault("kind", "function")
+    properties["storage_reads"] = _unique(e["arg"] for e in effects if e["op"] == "sload")
+    properties["storage_writes"] = _unique(e["arg"] for e in effects if e["op"] == "sstore")
+    properties["effects"] = effects
+    return Instruction(
+        name=name,
+        handler=name,
+        file=file,
+        line=line,
+        body=body,
+        body_line=body_line,
+        discriminator=selector,
+        calls=calls,
+        visibility="external",
+        properties=properties,
+    )
+
+
+def _unique(values) -> list[str]:
+    out: list[str] = []
+    for v in values:
+        if v not in out:
+            out.append(v)
+    return out
+
+
+def _artifact_bytecode(data: dict[str, Any]) -> str:
+    """Runtime bytecode from a Foundry/Hardhat artifact or solc contract JSON."""
+    for key in ("deployedBytecode", "runtimeBytecode", "bytecode"):
+        value = data.get(key)
+        if isinstance(value, dict):
+            value = value.get("object")
+        if value:
+            return value
+    evm = data.get("evm", {})
+    value = evm.get("deployedBytecode", {}).get("object")
+    if value:
+        return value
+    raise ValueError("artifact has no bytecode")
+
+
+def load_bytecode_programs(path: Path) -> list[Program]:
+    """Convenience wrapper around BytecodeFrontend.load()."""
+    return BytecodeFrontend().load(Path(path))
@@ -0,0 +1,254 @@
+"""
+Program inventory export.
+
+Builds an SBOM-style inventory from IR Programs: every instruction with its
+accounts and arguments, PDA seed schemas, the authorities that gate each
+instruction, and the external programs/contracts the code depends on. This
+is what auditors otherwise reconstruct by hand at the start of an
+engagement, in a form that can be diffed between releases.
+
+Formats:
+- json: the full nested inventory (``build_inventory`` output)
+- csv: one row per instruction account, for spreadsheets
+"""
+
+import csv
+import io
+import json
+import re
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Any
+
+from .model import AccountField, Instruction, Program
+
+INVENTORY_FORMAT = "baskerville-inventory"
+INVENTORY_VERSION = 1
+
+# Account names that conventionally hold a privileged role
+_AUTHORITY_NAME_RE = re.compile(r"(?:^|_)(?:authority|owner|admin|governance|governor|manager|operator|guardian|sender)(?:$|_)")
+# Accounts that name a well-known program even when typed as a bare AccountInfo
+_PROGRAM_ACCOUNT_RE = re.compile(r"(?:^|_)program$")
+_CONSTRAINT_REF_RE = re.compile(r"^(has_one|address|owner|constraint)\s*=\s*(.+)$", re.DOTALL)
+# Value transfers to an address, not calls into other code
+_VALUE_TRANSFERS = {"send", "transfer", "bank_send", "bank_burn"}
+
+
+def classify_seed(seed: str, ix: Instruction) -> dict[str, str]:
+    """Describe one PDA seed expression as const / account / account_field / arg / expr."""
+    expr = " ".join(seed.split())
+    m = re.fullmatch(r'b"([^"]*)"(?:\.as_ref\(\))?|"([^"]*)"\.as_bytes\(\)', expr)
+    if m:
+        return {"kind": "const", "value": m.group(1) if m.group(1) is not None else m.group(2)}
+    base = re.sub(r"\.(?:as_ref|to_le_bytes|to_be_bytes|as_bytes|key)\(\)", "", expr)
+    base = re.sub(r"\.key$", "", base).lstrip("&")
+    parts = base.split(".")
+    accounts = {a.name for a in ix.accounts}
+    args = {a.name for a in ix.args}
+    if parts[0] in accounts:
+        if len(parts) == 1:
+            return {"kind": "account", "account": parts[0], "expr": expr}
+        return {"kind": "account_field", "account": parts[0], "field": ".".join(parts[1:]), "expr": expr}
+    if parts[0] in args:
+        return {"kind": "arg", "arg": parts[0], "expr": expr}
+    return {"kind": "expr", "expr": expr}
+
+
+def _constraint_refs(ix: Instruction) -> dict[str, list[str]]:
+    """Account name → constraint kinds (has_one/address/owner) other accounts place on it."""
+    refs: dict[str, list[str]] = {}
+    names = {a.name for a in ix.accounts}
+    for acc in ix.accounts:
+        for constraint in acc.constraints:
+            m = _CONSTRAINT_REF_RE.match(constraint.strip())
+            if not m:
+                continue
+            kind, value = m.group(1), m.group(2)
+            for name in re.findall(r"[A-Za-z_]\w*", value):
+                if name in names and name != acc.name and kind not in refs.setdefault(name, []):
+                    refs[name].append(kind)
+    return refs
+
+
+def _account_entry(acc: AccountField, ix: Instruction) -> dict[str, Any]:
+    entry: dict[str, Any] = {
+        "name": acc.name,
+        "type": acc.ty,
+        "signer": acc.is_signer,
+        "writable": acc.is_mut,
+        "constraints": list(acc.constraints),
+    }
+    if acc.seeds is not None:
+        entry["seeds"] = [classify_seed(s, ix) for s in acc.seeds]
+    if acc.docs:
+        entry["docs"] = acc.docs
+    return entry
+
+
+def _is_authority(acc: AccountField) -> bool:
+    return acc.is_signer or bool(_AUTHORITY_NAME_RE.search(acc.name.lower()))
+
+
+def _program_dependency(acc: AccountField) -> tuple[str, bool] | None:
+    """(program, verified) for accounts that pass a program, else None."""
+    if acc.wrapper in ("Program", "Interface"):
+        return acc.inner_type or acc.name, True
+    if _PROGRAM_ACCOUNT_RE.search(acc.name):
+        verified = "address" in " ".join(acc.constraints) or "executable" in acc.constraints
+        return acc.name, verified
+    return None
+
+
+def program_inventory(program: Program) -> dict[str, Any]:
+    """Inventory of a single program."""
+    instructions = []
+    pdas = []
+    authorities: dict[str, dict[str, Any]] = {}
+    dependencies: dict[tuple[str, str], dict[str, Any]] = {}
+
+    for ix in program.instructions:
+        refs = _constraint_refs(ix)
+        seeded = {
+            seed["account"]
+            for acc in ix.accounts if acc.seeds
+            for seed in (classify_seed(s, ix) for s in acc.seeds)
+            if seed["kind"] in ("account", "account_field")
+        }
+        entry: dict[str, Any] = {
+            "name": ix.name,
+            "handler": ix.handler,
+            "file": ix.source_file,
+            "line": ix.line,
+            "discriminator": ix.discriminator,
+            "args": [{"name": a.name, "type": a.ty} for a in ix.args],
+            "accounts": [_account_entry(acc, ix) for acc in ix.accounts],
+            "calls": [{"kind": c.kind, "target": c.target, "line": c.line} for c in ix.calls],
+        }
+        if ix.visibility:
+            entry["visibility"] = ix.visibility
+        if ix.modifiers:
+            entry["modifiers"] = list(ix.modifiers)
+        instructions.append(entry)
+
+        for acc in ix.accounts:
+            if acc.seeds is not None:
+                pdas.append({
+                    "account": acc.name,
+                    "instruction": ix.name,
+                    "type": acc.inner_type or acc.ty,
+                    "seeds": [classify_seed(s, ix) for s in acc.seeds],
+                })
+            if _is_authority(acc):
+                enforced = (["signer"] if acc.is_signer else []) + refs.get(acc.name, [])
+                if acc.name in seeded:
+                    enforced.append("seeds")
+                if ix.properties.get("sender_checked") and acc.name == "sender":
+                    enforced.append("sender_checked")
+                record = authorities.setdefault(acc.name, {"name": acc.name, "instructions": []})
+                record["instructions"].append({"instruction": ix.name, "enforced": enforced})
+            dep = _program_dependency(acc)
+            if dep is not None:
+                record = dependencies.setdefault(
+                    ("account", dep[0]), {"program": dep[0], "via": "account", "verified": dep[1], "instructions": []}
+                )
+                record["verified"] = record["verified"] and dep[1]
+                if ix.name not in record["instructions"]:
+                    record["instructions"].append(ix.name)
+        for call in ix.calls:
+            if call.kind in _VALUE_TRANSFERS:
+                continue
+            target = call.target or "<unknown>"
+            acc = next((a for a in ix.accounts if re.search(rf"\b{re.escape(a.name)}\b", target)), None)
+            dep = _program_dependency(acc) if acc is not None else None
+            if dep is not None:
+                # CPI through a program account: fold into that account's dependency
+                record = dependencies[("account", dep[0])]
+            else:
+                record = dependencies.setdefault(
+                    ("call", target), {"program": target, "via": "call", "instructions": []}
+                )
+            record.setdefault("kinds", [])
+            if call.kind not in record["kinds"]:
+                record["kinds"].append(call.kind)
+            if ix.name not in record["instructions"]:
+                record["instructions"].append(ix.name)
+
+    return {
+        "name": program.name,
+        "chain": program.chain,
+        "framework": program.framework,
+        "program_id": program.program_id,
+        "root": program.root,
+        "instructions": instructions,
+        "pdas": pdas,
+        "authorities": list(authorities.values()),
+        "external_dependencies": list(dependencies.values()),
+        "state": [
+            {"name": at.name, "file": at.file, "line": at.line, "fields": [{"name": f.name, "type": f.ty} for f in at.fields]}
+            for at in program.account_types
+        ],
+        "summary": {
+            "instructions": len(instructions),
+            "accounts": sum(len(i["accounts"]) for i in instructions),
+            "pdas": len(pdas),
+            "authorities": len(authorities),
+            "external_dependencies": len(dependencies),
+        },
+    }
+
+
+def build_inventory(programs: list[Program], source: str | Path | None = None) -> dict[str, Any]:
+    """Inventory document covering ``programs``."""
+    return {
+        "format": INVENTORY_FORMAT,
+        "version": INVENTORY_VERSION,
+        "generated_at": datetime.now(timezone.utc).isoformat(),
+        "source": str(source) if source is not None else None,
+        "programs": [program_inventory(p) for p in programs],
+    }
+
+
+_CSV_COLUMNS = [
+    "program", "instruction", "handler", "file", "line", "account", "type",
+    "signer", "writable", "constraints", "seeds", "authority",
+]
+
+
+def inventory_to_csv(inventory: dict[str, Any]) -> str:
+    """Flatten an inventory to CSV, one row per instruction account."""
+    out = io.StringIO()
+    writer = csv.DictWriter(out, fieldnames=_CSV_COLUMNS, lineterminator="\n")
+    writer.writeheader()
+    for program in inventory["programs"]:
+        authority_names = {a["name"] for a in program["authorities"]}
+        for ix in program["instructions"]:
+            rows = ix["accounts"] or [None]
+            for acc in rows:
+                writer.writerow({
+                    "program": program["name"],
+                    "instruction": ix["name"],
+                    "handler": ix["handler"],
+                    "file": ix["file"],
+                    "line": ix["line"],
+                    "account": acc["name"] if acc else "",
+                    "type": acc["type"] if acc else "",
+                    "signer": acc["signer"] if acc else "",
+                    "writable": acc["writable"] if acc else "",
+                    "constraints": "; ".join(acc["constraints"]) if acc else "",
+                    "seeds": "; ".join(s.get("value") or s.get("expr", "") for s in acc.get("seeds", [])) if acc else "",
+                    "authority": bool(acc and acc["name"] in authority_names),
+                })
+    return out.getvalue()
+
+
+def write_inventory(inventory: dict[str, Any], output: Path, fmt: str = "json") -> Path:
+    """Write an inventory to ``output`` as json or csv."""
+    output = Path(output)
+    output.parent.mkdir(parents=True, exist_ok=True)
+    if fmt == "csv":
+        output.write_text(inventory_to_csv(inventory))
+    elif fmt == "json":
+        output.write_text(json.dumps(inventory, indent=2))
+    else:
+        raise ValueError(f"Unknown inventory format: {fmt}")
+    return output
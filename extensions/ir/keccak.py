@@ -0,0 +1,72 @@
+"""
+Keccak-256 (the pre-standard SHA-3 used by the EVM).
+
+hashlib only ships FIPS SHA3, whose padding differs, so selectors and
+event topics are computed with this small pure-Python implementation.
+Inputs here are signatures, so speed doesn't matter.
+"""
+
+_ROUND_CONSTANTS = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808A, 0x8000000080008000,
+    0x000000000000808B, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008A, 0x0000000000000088, 0x0000000080008009, 0x000000008000000A,
+    0x000000008000808B, 0x800000000000008B, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800A, 0x800000008000000A,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+]
+_ROTATIONS = [
+    [0, 36, 3, 41, 18],
+    [1, 44, 10, 45, 2],
+    [62, 6, 43, 15, 61],
+    [28, 55, 25, 21, 56],
+    [27, 20, 39, 8, 14],
+]
+_MASK = (1 << 64) - 1
+_RATE = 136  # bytes, for a 256-bit output
+
+
+def _rotl(value: int, shift: int) -> int:
+    return ((value << shift) | (value >> (64 - shift))) & _MASK if shift else value
+
+
+def _permute(state: list[list[int]]) -> None:
+    for rc in _ROUND_CONSTANTS:
+        c = [state[x][0] ^ state[x][1] ^ state[x][2] ^ state[x][3] ^ state[x][4] for x in range(5)]
+        d = [c[(x - 1) % 5] ^ _rotl(c[(x + 1) % 5], 1) for x in range(5)]
+        for x in range(5):
+            for y in range(5):
+                state[x][y] ^= d[x]
+        b = [[0] * 5 for _ in range(5)]
+        for x in range(5):
+            for y in range(5):
+                b[y][(2 * x + 3 * y) % 5] = _rotl(state[x][y], _ROTATIONS[x][y])
+        for x in range(5):
+            for y in range(5):
+                state[x][y] = b[x][y] ^ ((~b[(x + 1) % 5][y]) & b[(x + 2) % 5][y])
+        state[0][0] ^= rc
+
+
+def keccak256(data: bytes) -> bytes:
+    """Keccak-256 digest of ``data``."""
+    padded = bytearray(data)
+    padded.append(0x01)
+    while len(padded) % _RATE:
+        padded.append(0)
+    padded[-1] |= 0x80
+
+    state = [[0] * 5 for _ in range(5)]
+    for block in range(0, len(padded), _RATE):
+        chunk = padded[block:block + _RATE]
+        for i in range(_RATE // 8):
+            state[i % 5][i // 5] ^= int.from_bytes(chunk[i * 8:i * 8 + 8], "little")
+        _permute(state)
+
+    out = bytearray()
+    for i in range(4):
+        out += state[i % 5][i // 5].to_bytes(8, "little")
+    return bytes(out)
+
+
+def function_selector(signature: str) -> str:
+    """4-byte selector (hex, no 0x) for a canonical signature like "transfer(address,uint256)"."""
+    return keccak256(signature.replace(" ", "").encode()).hex()[:8]
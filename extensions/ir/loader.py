@@ -0,0 +1,107 @@
+"""
+Front-end selection: turn a project path into IR Programs.
+"""
+
+from collections.abc import Callable
+from pathlib import Path
+from typing import TypeVar
+
+from .anchor import AnchorFrontend
+from .cache import active_program_cache
+from .cosmwasm import CosmWasmFrontend
+from .evm_assembly import HuffFrontend, YulFrontend
+from .evm_bytecode import BytecodeFrontend
+from .model import Program
+from .move_bytecode import MoveBytecodeFrontend
+from .native import NativeDispatchRecovery
+from .seahorse import SeahorseFrontend
+from .solidity import SolidityFrontend
+from .vyper import VyperFrontend
+
+T = TypeVar("T")
+
+
+def _cached(kind: str, root: Path, load: Callable[[], T]) -> T:
+    cache = active_program_cache()
+    return cache.get_or_load(kind, root, load) if cache else load()
+
+
+def _rust_frontends() -> dict[str, tuple[Callable[[Path], list[Path]], Callable[[Path], Program]]]:
+    """Crate discovery and lowering of each Rust front-end, in the order crates are claimed."""
+    anchor, native, cosmwasm = AnchorFrontend(), NativeDispatchRecovery(), CosmWasmFrontend()
+    return {
+        "anchor": (anchor.find_program_roots, anchor.load_program),
+        "native": (native.find_program_roots, native.recover_program),
+        "cosmwasm": (cosmwasm.find_program_roots, cosmwasm.load_program),
+    }
+
+
+def crate_roots(path: Path, claimed: set[Path] | None = None) -> list[tuple[str, Path]]:
+    """The Rust crates under ``path`` as (front-end, root) pairs, each claimed by the first front-end finding it.
+
+    Args:
+        path: Project or workspace directory (or a single source file)
+        claimed: Resolved roots already loaded another way (updated with the ones returned)
+    """
+    path = Path(path)
+    claimed = set() if claimed is None else claimed
+    roots = []
+    for kind, (find, _) in _rust_frontends().items():
+        for root in _cached(f"{kind}-roots", path, lambda: find(path)):
+            if root.resolve() not in claimed:
+                roots.append((kind, root))
+                claimed.add(root.resolve())
+    return roots
+
+
+def lower_crate(kind: str, root: Path) -> Program:
+    """The crate at ``root`` lowered by the ``kind`` front-end of ``crate_roots``."""
+    return _cached(kind, root, lambda: _rust_frontends()[kind][1](root))
+
+
+def load_programs(path: Path, build_seahorse: bool = False) -> list[Program]:
+    """Load every program under ``path`` using the matching front-end.
+
+    Seahorse projects are loaded through their generated crates (so those
+    crates are not loaded a second time as plain Anchor), then remaining
+    Anchor crates, then native entrypoint crates, then CosmWasm contract
+    crates, then Solidity and Vyper
+    contracts (compiled with solc/vyper; compile errors are skipped, not
+    raised), then Yul and Huff sources, then compiled Move modules. Raw EVM
+    bytecode is only loaded when ``path`` is itself a `.bin`/`.hex` file.
+    Rust crates come from the active ``ProgramCache`` (see cache.py) while
+    their sources are unchanged.
+
+    Args:
+        path: Project or workspace directory (or a single source file)
+        build_seahorse: Run `seahorse build` if generated Rust is missing
+
+    Returns:
+        Programs in discovery order
+    """
+    path = Path(path)
+    programs: list[Program] = []
+    claimed: set[Path] = set()
+
+    if path.is_dir() and SeahorseFrontend.is_seahorse_project(path):
+        for program in SeahorseFrontend(build=build_seahorse).load(path):
+            programs.append(program)
+            claimed.add(Path(program.root).resolve())
+
+    for kind, root in crate_roots(path, claimed):
+        programs.append(lower_crate(kind, root))
+
+    if path.suffix != ".rs" and SolidityFrontend.has_sources(path):
+        programs.extend(SolidityFrontend().load(path))
+    if path.suffix != ".rs" and VyperFrontend.has_sources(path):
+        programs.extend(VyperFrontend().load(path))
+    if YulFrontend.has_sources(path):
+        programs.extend(YulFrontend().load(path))
+    if HuffFrontend.has_sources(path):
+        programs.extend(HuffFrontend().load(path))
+    if MoveBytecodeFrontend.has_sources(path):
+        programs.extend(MoveBytecodeFrontend().load(path))
+    if path.is_file() and path.suffix in BytecodeFrontend.SUFFIXES:
+        programs.extend(BytecodeFrontend().load(path))
+
+    return programs
@@ -0,0 +1,194 @@
+"""
+Codebase metrics and attack-surface statistics.
+
+Per-program and per-instruction numbers computed from the IR: handler
+lines of code and cyclomatic complexity, privileged instructions (gated by
+an authority bound to state or a known key), unchecked accounts (raw
+`AccountInfo`/`UncheckedAccount` slots nothing pins), and CPI fan-out
+(distinct programs a handler calls into). They give a report its scope
+context, and on large engagements a review order: instructions ranked by a
+priority score so manual review starts where complexity and exposure meet.
+"""
+
+import re
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Any
+
+from .inventory import _VALUE_TRANSFERS
+from .model import AccountField, Instruction, Program
+from .pda_map import _UNCHECKED_WRAPPERS
+
+METRICS_FORMAT = "baskerville-metrics"
+METRICS_VERSION = 1
+
+# Constraints that pin or validate an account Anchor would otherwise take as given
+_PINNING = ("address", "owner", "has_one", "constraint", "init", "associated_token::", "token::", "mint::",
+            "executable", "key")
+# Decision points, by source syntax
+_BRANCH_RES = {
+    "rust": re.compile(r"\b(?:if|while|for)\b|&&|\|\|"),
+    "solidity": re.compile(r"\b(?:if|while|for|catch)\b|&&|\|\||\s\?\s"),
+    "python": re.compile(r"\b(?:if|elif|while|for|and|or)\b"),
+}
+_PYTHON_SUFFIXES = (".py", ".vy")
+_SOLIDITY_SUFFIXES = (".sol", ".yul", ".huff")
+_STRING_RE = re.compile(r'"(?:[^"\\\n]|\\.)*"')
+_COMMENT_RES = {
+    "rust": re.compile(r"//[^\n]*|/\*.*?\*/", re.DOTALL),
+    "solidity": re.compile(r"//[^\n]*|/\*.*?\*/", re.DOTALL),
+    "python": re.compile(r"#[^\n]*"),
+}
+
+
+def _syntax(file: str) -> str:
+    if file.endswith(_PYTHON_SUFFIXES):
+        return "python"
+    return "solidity" if file.endswith(_SOLIDITY_SUFFIXES) else "rust"
+
+
+def _code(text: str, syntax: str) -> str:
+    """``text`` without comments and string contents (newlines kept)."""
+    text = _STRING_RE.sub('""', text)
+    return _COMMENT_RES[syntax].sub(lambda m: "\n" * m.group(0).count("\n"), text)
+
+
+def count_loc(text: str, syntax: str = "rust") -> int:
+    """Lines holding code, not blank or comment-only."""
+    return sum(1 for line in _code(text, syntax).splitlines() if line.strip())
+
+
+def cyclomatic_complexity(body: str, syntax: str = "rust") -> int:
+    """McCabe complexity of a handler: 1 + branches, loops, short-circuit operators and extra match arms."""
+    code = _code(body, syntax)
+    decisions = len(_BRANCH_RES[syntax].findall(code))
+    if syntax == "rust":
+        # A match with n arms adds n - 1 paths
+        decisions += max(0, code.count("=>") - len(re.findall(r"\bmatch\b", code)))
+    return 1 + decisions
+
+
+def _referenced(ix: Instruction, acc: AccountField) -> bool:
+    return any(other is not acc and re.search(rf"\b{re.escape(acc.name)}\b", text)
+               for other in ix.accounts for text in other.constraints + (other.seeds or []))
+
+
+def _key_checked(ix: Instruction, acc: AccountField) -> bool:
+    """Whether the handler compares the account's key (`require_keys_eq!`, `==`)."""
+    key = rf"\b{re.escape(acc.name)}\s*\.\s*key\b"
+    return bool(re.search(rf"require_keys_(?:eq|neq)!\s*\([^;]*{key}", ix.body)
+                or re.search(rf"{key}(?:\s*\(\s*\))?\s*[!=]=|[!=]=\s*\*?\s*(?:ctx\s*\.\s*accounts\s*\.\s*)?{key}",
+                             ix.body))
+
+
+def unchecked_accounts(ix: Instruction) -> list[str]:
+    """Raw account slots with no type check and nothing pinning which account is passed."""
+    return [acc.name for acc in ix.accounts
+            if acc.wrapper in _UNCHECKED_WRAPPERS and acc.seeds is None and not _referenced(ix, acc)
+            and not any(c.startswith(_PINNING) for c in acc.constraints) and not _key_checked(ix, acc)]
+
+
+def cpi_targets(ix: Instruction) -> list[str]:
+    """Distinct programs the handler calls into (by account name where the target names one)."""
+    targets = []
+    for call in ix.calls:
+        if call.kind in _VALUE_TRANSFERS:
+            continue
+        target = call.target or "<unknown>"
+        acc = next((a.name for a in ix.accounts if re.search(rf"\b{re.escape(a.name)}\b", target)), None)
+        if (acc or target) not in targets:
+            targets.append(acc or target)
+    return targets
+
+
+def is_privileged(ix: Instruction) -> bool:
+    """Whether only a specific authority can call the instruction."""
+    from extensions.static.privilege_paths import enforced_signers
+
+    return bool(enforced_signers(ix) or ix.modifiers or ix.properties.get("sender_checked"))
+
+
+def review_priority(entry: dict[str, Any]) -> int:
+    """Ranking score: complexity, plus weight for raw accounts, CPIs and open state-changing entry points."""
+    score = entry["complexity"] + 3 * len(entry["unchecked_accounts"]) + 2 * len(entry["cpi_targets"])
+    if not entry["privileged"] and entry["writable"]:
+        score += 5
+    return score
+
+
+def instruction_metrics(program: Program, ix: Instruction) -> dict[str, Any]:
+    syntax = _syntax(ix.source_file)
+    entry: dict[str, Any] = {
+        "program": program.name,
+        "instruction": ix.name,
+        "file": ix.source_file,
+        "line": ix.line,
+        "loc": count_loc(ix.body, syntax),
+        "complexity": cyclomatic_complexity(ix.body, syntax),
+        "accounts": len(ix.accounts),
+        "writable": sum(1 for a in ix.accounts if a.is_mut),
+        "signers": sum(1 for a in ix.accounts if a.is_signer),
+        "privileged": is_privileged(ix),
+        "unchecked_accounts": unchecked_accounts(ix),
+        "cpi_targets": cpi_targets(ix),
+    }
+    entry["priority"] = review_priority(entry)
+    return entry
+
+
+def _source_loc(program: Program) -> int:
+    total = 0
+    for rel in program.source_files:
+        path = Path(program.root) / rel
+        try:
+            total += count_loc(path.read_text(errors="replace"), _syntax(rel))
+        except OSError:
+            continue
+    return total
+
+
+def program_metrics(program: Program) -> dict[str, Any]:
+    """Statistics for one program, with its instructions' metrics."""
+    instructions = [instruction_metrics(program, ix) for ix in program.instructions]
+    complexity = [i["complexity"] for i in instructions]
+    targets = {t for i in instructions for t in i["cpi_targets"]}
+    return {
+        "name": program.name,
+        "chain": program.chain,
+        "framework": program.framework,
+        "loc": _source_loc(program),
+        "summary": {
+            "instructions": len(instructions),
+            "privileged_instructions": sum(1 for i in instructions if i["privileged"]),
+            "unchecked_accounts": sum(len(i["unchecked_accounts"]) for i in instructions),
+            "cpi_fan_out": len(targets),
+            "handler_loc": sum(i["loc"] for i in instructions),
+            "max_complexity": max(complexity, default=0),
+            "mean_complexity": round(sum(complexity) / len(complexity), 2) if complexity else 0.0,
+        },
+        "instructions": instructions,
+    }
+
+
+def build_metrics(programs: list[Program], source: str | Path | None = None, top: int = 10) -> dict[str, Any]:
+    """Metrics document covering ``programs``, with the ``top`` instructions to review first."""
+    per_program = [program_metrics(p) for p in programs]
+    ranked = sorted((i for p in per_program for i in p["instructions"]),
+                    key=lambda i: (-i["priority"], i["program"], i["instruction"]))
+    summaries = [p["summary"] for p in per_program]
+    return {
+        "format": METRICS_FORMAT,
+        "version": METRICS_VERSION,
+        "generated_at": datetime.now(timezone.utc).isoformat(),
+        "source": str(source) if source is not None else None,
+        "totals": {
+            "programs": len(per_program),
+            "loc": sum(p["loc"] for p in per_program),
+            **{key: sum(s[key] for s in summaries) for key in
+               ("instructions", "privileged_instructions", "unchecked_accounts", "handler_loc")},
+            "max_complexity": max((s["max_complexity"] for s in summaries), default=0),
+        },
+        "programs": per_program,
+        "review_order": [{key: i[key] for key in ("program", "instruction", "file", "line", "priority")}
+                         for i in ranked[:top]],
+    }
@@ -0,0 +1,160 @@
+"""
+Program intermediate representation.
+
+Every front-end (Anchor, native Solana, Solidity, Move, ...) lowers source
+into these types so that detectors and exports can be written once against
+a single shape: a program is a set of instructions, each with the accounts
+it touches, its arguments, its handler body, and the calls it makes out.
+"""
+
+from dataclasses import asdict, dataclass, field
+from typing import Any
+
+
+@dataclass
+class AccountField:
+    """An account passed to an instruction.
+
+    For Anchor this is a field of the `#[derive(Accounts)]` struct; for
+    native programs it is an entry pulled from the `accounts` slice.
+    """
+
+    name: str
+    ty: str = "AccountInfo"  # Raw type text, e.g. "Account<'info, Vault>"
+    is_signer: bool = False
+    is_mut: bool = False
+    constraints: list[str] = field(default_factory=list)
+    seeds: list[str] | None = None
+    line: int = 0
+    docs: str = ""
+    file: str = ""  # Set when the account is declared outside the instruction's file
+
+    @property
+    def inner_type(self) -> str | None:
+        """The wrapped account type for `Account<'info, T>`-style fields."""
+        text = self.ty.replace(" ", "")
+        if "<" not in text or not text.endswith(">"):
+            return None
+        args = [a for a in text[text.index("<") + 1:-1].split(",") if not a.startswith("'")]
+        return args[-1] if args else None
+
+    @property
+    def wrapper(self) -> str:
+        """Outer type name, e.g. "Account", "Signer", "AccountInfo"."""
+        return self.ty.split("<", 1)[0].strip().split("::")[-1]
+
+
+@dataclass
+class Argument:
+    """An instruction argument or state field."""
+
+    name: str
+    ty: str
+
+
+@dataclass
+class CallSite:
+    """An outgoing call from a handler (CPI, external call, delegatecall)."""
+
+    kind: str  # "invoke", "invoke_signed", "cpi", "call", "delegatecall", ...
+    target: str  # Expression naming the callee program/contract
+    line: int
+    text: str = ""
+
+
+@dataclass
+class Instruction:
+    """A callable entry point of a program."""
+
+    name: str
+    handler: str
+    file: str
+    line: int
+    accounts: list[AccountField] = field(default_factory=list)
+    args: list[Argument] = field(default_factory=list)
+    body: str = ""
+    body_line: int = 0
+    body_file: str | None = None  # Where the handler logic lives, if not `file`
+    accounts_struct: str | None = None
+    discriminator: str | None = None
+    calls: list[CallSite] = field(default_factory=list)
+    # EVM-style metadata (visibility, modifiers); empty for Solana
+    visibility: str = ""
+    modifiers: list[str] = field(default_factory=list)
+    properties: dict[str, Any] = field(default_factory=dict)
+
+    @property
+    def source_file(self) -> str:
+        """File containing the handler body (line numbers are relative to it)."""
+        return self.body_file or self.file
+
+    def account(self, name: str) -> AccountField | None:
+        for acc in self.accounts:
+            if acc.name == name:
+                return acc
+        return None
+
+
+@dataclass
+class AccountType:
+    """A persistent state layout (`#[account]` struct, contract storage, ...)."""
+
+    name: str
+    file: str
+    line: int
+    fields: list[Argument] = field(default_factory=list)
+    properties: dict[str, Any] = field(default_factory=dict)
+
+
+@dataclass
+class EventType:
+    """An event a program emits (Anchor `#[event]` struct)."""
+
+    name: str
+    file: str
+    line: int
+    fields: list[Argument] = field(default_factory=list)
+    # Instructions whose handlers emit it (`emit!` / `emit_cpi!`)
+    emitted_by: list[str] = field(default_factory=list)
+
+
+@dataclass
+class Program:
+    """A single program/contract lowered into the IR."""
+
+    name: str
+    chain: str  # "solana", "evm", "sui", "aptos", "cosmwasm"
+    framework: str  # "anchor", "native", "seahorse", "solidity", ...
+    root: str
+    instructions: list[Instruction] = field(default_factory=list)
+    account_types: list[AccountType] = field(default_factory=list)
+    events: list[EventType] = field(default_factory=list)
+    source_files: list[str] = field(default_factory=list)
+    program_id: str | None = None
+    metadata: dict[str, Any] = field(default_factory=dict)
+
+    def instruction(self, name: str) -> Instruction | None:
+        for ix in self.instructions:
+            if ix.name == name or ix.handler == name:
+                return ix
+        return None
+
+    def account_type(self, name: str) -> AccountType | None:
+        for at in self.account_types:
+            if at.name == name:
+                return at
+        return None
+
+    def event(self, name: str) -> EventType | None:
+        for ev in self.events:
+            if ev.name == name:
+                return ev
+        return None
+
+    def to_dict(self, include_bodies: bool = False) -> dict[str, Any]:
+        """Serialize to a JSON-friendly dict (handler bodies omitted by default)."""
+        data = asdict(self)
+        if not include_bodies:
+            for ix in data["instructions"]:
+                ix.pop("body", None)
+        return data
@@ -0,0 +1,459 @@
+"""
+Compiled Move module front-end (Aptos and Sui).
+
+When only deployed modules are available (no `sources/`), the `.mv`
+binaries still carry everything detectors need: function signatures,
+visibility and entry flags, struct abilities, global storage operations,
+and calls into other modules. This front-end deserializes the Move binary
+format directly and lowers each module into a Program.
+
+Signer analysis mirrors the Solana account model:
+- `signer` / `&signer` parameters (Aptos) become AccountFields with
+  ``is_signer=True``
+- `&mut T` parameters where T has `key` (Sui shared/owned objects)
+  become AccountFields with ``is_mut=True``
+- `&mut TxContext` is recorded as ``properties["tx_context"]``
+
+Each Instruction also records ``properties["global_reads"]`` /
+``["global_writes"]`` (struct names touched through `borrow_global`,
+`exists`, `move_to`, `move_from`) and outgoing ``calls``.
+"""
+
+from dataclasses import dataclass, field
+from pathlib import Path
+
+from .model import AccountField, AccountType, Argument, CallSite, Instruction, Program
+
+MAGIC = b"\xa1\x1c\xeb\x0b"
+
+# Table kinds
+_MODULE_HANDLES = 0x1
+_STRUCT_HANDLES = 0x2
+_FUNCTION_HANDLES = 0x3
+_FUNCTION_INST = 0x4
+_SIGNATURES = 0x5
+_CONSTANT_POOL = 0x6
+_IDENTIFIERS = 0x7
+_ADDRESS_IDENTIFIERS = 0x8
+_STRUCT_DEFS = 0xA
+_STRUCT_DEF_INST = 0xB
+_FUNCTION_DEFS = 0xC
+_FRIEND_DECLS = 0xF
+
+ABILITIES = {0x1: "copy", 0x2: "drop", 0x4: "store", 0x8: "key"}
+VISIBILITY = {0: "private", 1: "public", 2: "public", 3: "friend"}
+_FLAG_NATIVE = 0x2
+_FLAG_ENTRY = 0x4
+
+_PRIMITIVES = {
+    0x01: "bool", 0x02: "u8", 0x03: "u64", 0x04: "u128", 0x05: "address", 0x0C: "signer",
+    0x0D: "u16", 0x0E: "u32", 0x0F: "u256",
+}
+
+# Opcode -> operand layout ("u" uleb128, "b" u8, "8"/"16"/"2"/"4"/"32" fixed-width bytes)
+_OPERANDS: dict[int, str] = {
+    0x03: "u", 0x04: "u", 0x05: "u", 0x06: "8", 0x07: "u", 0x0A: "b", 0x0B: "b", 0x0C: "b",
+    0x0D: "b", 0x0E: "b", 0x0F: "u", 0x10: "u", 0x11: "u", 0x12: "u", 0x13: "u", 0x29: "u",
+    0x2A: "u", 0x2B: "u", 0x2C: "u", 0x2D: "u", 0x31: "b", 0x32: "16", 0x36: "u", 0x37: "u",
+    0x38: "u", 0x39: "u", 0x3A: "u", 0x3B: "u", 0x3C: "u", 0x3D: "u", 0x3E: "u", 0x3F: "u",
+    0x40: "u8", 0x41: "u", 0x42: "u", 0x43: "u", 0x44: "u", 0x45: "u", 0x46: "u8", 0x47: "u",
+    0x48: "2", 0x49: "4", 0x4A: "32",
+}
+_NO_OPERAND = set(range(0x01, 0x4E)) - set(_OPERANDS) - {0x00}
+_CALL = 0x11
+_CALL_GENERIC = 0x38
+# Global storage ops: opcode -> (effect, generic)
+_GLOBAL_OPS = {
+    0x29: ("read", False), 0x2B: ("read", False), 0x2A: ("write", False),
+    0x2C: ("write", False), 0x2D: ("write", False),
+    0x3B: ("read", True), 0x3D: ("read", True), 0x3C: ("write", True),
+    0x3E: ("write", True), 0x3F: ("write", True),
+}
+
+
+class MoveBinaryError(ValueError):
+    """Raised for malformed or unsupported module binaries."""
+
+
+class _Cursor:
+    def __init__(self, data: bytes, pos: int = 0, end: int | None = None):
+        self.data = data
+        self.pos = pos
+        self.end = len(data) if end is None else end
+
+    def done(self) -> bool:
+        return self.pos >= self.end
+
+    def u8(self) -> int:
+        if self.pos >= self.end:
+            raise MoveBinaryError("unexpected end of data")
+        value = self.data[self.pos]
+        self.pos += 1
+        return value
+
+    def bytes(self, n: int) -> bytes:
+        if self.pos + n > self.end:
+            raise MoveBinaryError("unexpected end of data")
+        value = self.data[self.pos:self.pos + n]
+        self.pos += n
+        return value
+
+    def uleb(self) -> int:
+        result = shift = 0
+        while True:
+            byte = self.u8()
+            result |= (byte & 0x7F) << shift
+            if not byte & 0x80:
+                return result
+            shift += 7
+            if shift > 63:
+                raise MoveBinaryError("uleb128 overflow")
+
+
+@dataclass
+class MoveFunction:
+    name: str
+    visibility: str
+    is_entry: bool
+    is_native: bool
+    params: list[str]
+    returns: list[str]
+    type_params: list[list[str]]
+    acquires: list[str]
+    code: list[tuple[int, int | None]] = field(default_factory=list)  # (opcode, first operand)
+    calls: list[str] = field(default_factory=list)  # Fully qualified callees, in code order
+    global_reads: list[str] = field(default_factory=list)
+    global_writes: list[str] = field(default_factory=list)
+
+
+@dataclass
+class MoveStruct:
+    name: str
+    abilities: list[str]
+    fields: list[tuple[str, str]]
+    is_native: bool = False
+
+
+@dataclass
+class MoveModule:
+    """A deserialized module (only the parts the IR needs)."""
+
+    version: int
+    address: str
+    name: str
+    functions: list[MoveFunction]
+    structs: list[MoveStruct]
+    friends: list[str]
+    dependencies: list[str]
+    key_types: set[str] = field(default_factory=set)  # Struct types (local or imported) with `key`
+
+    @property
+    def id(self) -> str:
+        return f"{self.address}::{self.name}"
+
+
+def deserialize_module(data: bytes) -> MoveModule:
+    """Parse a compiled Move module (`.mv`)."""
+    if data[:4] != MAGIC:
+        raise MoveBinaryError("not a Move module (bad magic)")
+    version = int.from_bytes(data[4:8], "little") & 0xFFFF
+    cur = _Cursor(data, 8)
+    headers = []
+    for _ in range(cur.uleb()):
+        headers.append((cur.u8(), cur.uleb(), cur.uleb()))
+    base = cur.pos
+    tables = {kind: (base + offset, base + offset + length) for kind, offset, length in headers}
+    tables_end = max((end for _, end in tables.values()), default=base)
+    self_handle = _Cursor(data, tables_end).uleb() if version >= 5 and tables_end < len(data) else 0
+
+    def table(kind: int) -> _Cursor:
+        start, end = tables.get(kind, (0, 0))
+        return _Cursor(data, start, end)
+
+    identifiers = []
+    c = table(_IDENTIFIERS)
+    while not c.done():
+        identifiers.append(c.bytes(c.uleb()).decode(errors="replace"))
+    addresses = []
+    c = table(_ADDRESS_IDENTIFIERS)
+    while not c.done():
+        addresses.append("0x" + (c.bytes(32).hex().lstrip("0") or "0"))
+    modules = []
+    c = table(_MODULE_HANDLES)
+    while not c.done():
+        modules.append((addresses[c.uleb()], identifiers[c.uleb()]))
+
+    struct_handles = []
+    c = table(_STRUCT_HANDLES)
+    while not c.done():
+        module_idx, name_idx = c.uleb(), c.uleb()
+        abilities = c.u8() if version >= 2 else (0x8 if c.u8() else 0x3)
+        for _ in range(c.uleb()):
+            c.u8()
+            if version >= 3:
+                c.u8()  # is_phantom
+        struct_handles.append((module_idx, identifiers[name_idx], abilities))
+
+    def struct_name(handle_idx: int) -> str:
+        module_idx, name, _ = struct_handles[handle_idx]
+        if module_idx == self_handle:
+            return name
+        addr, mod = modules[module_idx]
+        return f"{addr}::{mod}::{name}"
+
+    def token(c: _Cursor) -> str:
+        tag = c.u8()
+        if tag in _PRIMITIVES:
+            return _PRIMITIVES[tag]
+        if tag == 0x06:
+            return "&" + token(c)
+        if tag == 0x07:
+            return "&mut " + token(c)
+        if tag == 0x08:
+            return struct_name(c.uleb())
+        if tag == 0x09:
+            return f"T{c.uleb()}"
+        if tag == 0x0A:
+            return f"vector<{token(c)}>"
+        if tag == 0x0B:
+            name = struct_name(c.uleb())
+            args = [token(c) for _ in range(c.uleb())]
+            return f"{name}<{', '.join(args)}>"
+        raise MoveBinaryError(f"unsupported signature token {tag:#x}")
+
+    signatures = []
+    c = table(_SIGNATURES)
+    while not c.done():
+        signatures.append([token(c) for _ in range(c.uleb())])
+
+    function_handles = []
+    c = table(_FUNCTION_HANDLES)
+    while not c.done():
+        module_idx, name_idx, params_idx, returns_idx = c.uleb(), c.uleb(), c.uleb(), c.uleb()
+        type_params = [_abilities(c.u8()) for _ in range(c.uleb())]
+        if version >= 7 and c.u8():  # access specifiers (Aptos v7): not needed, and not skippable
+            raise MoveBinaryError("access specifiers are not supported")
+        function_handles.append((module_idx, identifiers[name_idx], params_idx, returns_idx, type_params))
+    function_inst = []
+    c = table(_FUNCTION_INST)
+    while not c.done():
+        function_inst.append((c.uleb(), c.uleb()))
+
+    def function_name(handle_idx: int) -> str:
+        module_idx, name, *_ = function_handles[handle_idx]
+        addr, mod = modules[module_idx]
+        return f"{addr}::{mod}::{name}"
+
+    structs: list[MoveStruct] = []
+    c = table(_STRUCT_DEFS)
+    while not c.done():
+        handle_idx = c.uleb()
+        kind = c.u8()
+        fields = []
+        if kind == 2:
+            for _ in range(c.uleb()):
+                fname = identifiers[c.uleb()]
+                fields.append((fname, token(c)))
+        elif kind != 1:
+            raise MoveBinaryError(f"unsupported struct layout {kind:#x}")
+        _, name, abilities = struct_handles[handle_idx]
+        structs.append(MoveStruct(name=name, abilities=_abilities(abilities), fields=fields, is_native=kind == 1))
+    struct_inst = []
+    c = table(_STRUCT_DEF_INST)
+    while not c.done():
+        struct_inst.append((c.uleb(), c.uleb()))
+
+    functions: list[MoveFunction] = []
+    c = table(_FUNCTION_DEFS)
+    while not c.done():
+        handle_idx = c.uleb()
+        if version == 1:
+            flags = c.u8()
+            visibility, is_entry = (1 if flags & 0x1 else 0), False
+        else:
+            visibility = c.u8()
+            flags = c.u8()
+            is_entry = visibility == 2 or bool(flags & _FLAG_ENTRY)
+        acquires = [structs[c.uleb()].name for _ in range(c.uleb())]
+        code: list[tuple[int, int | None]] = []
+        if not flags & _FLAG_NATIVE:
+            c.uleb()  # locals signature
+            for _ in range(c.uleb()):
+                code.append(_read_instruction(c))
+        _, name, params_idx, returns_idx, type_params = function_handles[handle_idx]
+        fn = MoveFunction(
+            name=name,
+            visibility=VISIBILITY.get(visibility, "private"),
+            is_entry=is_entry,
+            is_native=bool(flags & _FLAG_NATIVE),
+            params=signatures[params_idx],
+            returns=signatures[returns_idx],
+            type_params=type_params,
+            acquires=acquires,
+            code=code,
+        )
+        for op, operand in code:
+            if op == _CALL:
+                fn.calls.append(function_name(operand))
+            elif op == _CALL_GENERIC:
+                fn.calls.append(function_name(function_inst[operand][0]))
+            elif op in _GLOBAL_OPS:
+                effect, generic = _GLOBAL_OPS[op]
+                touched = structs[struct_inst[operand][0] if generic else operand].name
+                bucket = fn.global_reads if effect == "read" else fn.global_writes
+                if touched not in bucket:
+                    bucket.append(touched)
+        functions.append(fn)
+
+    friends = []
+    c = table(_FRIEND_DECLS)
+    while not c.done():
+        addr_idx, name_idx = c.uleb(), c.uleb()
+        friends.append(f"{addresses[addr_idx]}::{identifiers[name_idx]}")
+
+    address, name = modules[self_handle]
+    return MoveModule(
+        version=version,
+        address=address,
+        name=name,
+        functions=functions,
+        structs=structs,
+        friends=friends,
+        dependencies=[f"{a}::{m}" for i, (a, m) in enumerate(modules) if i != self_handle],
+        key_types={struct_name(i) for i, (_, _, abilities) in enumerate(struct_handles) if abilities & 0x8},
+    )
+
+
+def _read_instruction(c: _Cursor) -> tuple[int, int | None]:
+    op = c.u8()
+    layout = _OPERANDS.get(op)
+    if layout is None:
+        if op not in _NO_OPERAND:
+            raise MoveBinaryError(f"unsupported opcode {op:#x}")
+        return op, None
+    if layout == "u":
+        return op, c.uleb()
+    if layout == "b":
+        return op, c.u8()
+    if layout == "u8":  # signature index + u64 element count
+        idx = c.uleb()
+        c.bytes(8)
+        return op, idx
+    width = int(layout)
+    return op, int.from_bytes(c.bytes(width), "little")
+
+
+def _abilities(mask: int) -> list[str]:
+    return [name for bit, name in ABILITIES.items() if mask & bit]
+
+
+class MoveBytecodeFrontend:
+    """Builds IR Programs from compiled Move modules."""
+
+    def __init__(self, chain: str | None = None):
+        """Initialize front-end.
+
+        Args:
+            chain: "sui" or "aptos"; detected from framework dependencies when omitted
+        """
+        self.chain = chain
+        self.errors: list[str] = []
+
+    @staticmethod
+    def has_sources(path: Path) -> bool:
+        path = Path(path)
+        return path.suffix == ".mv" if path.is_file() else any(path.rglob("*.mv"))
+
+    def load(self, path: Path) -> list[Program]:
+        """Load a `.mv` file or every module under a directory (e.g. `build/<pkg>/bytecode_modules`).
+
+        Modules that fail to deserialize are reported in ``self.errors``.
+        """
+        path = Path(path)
+        self.errors = []
+        files = [path] if path.is_file() else sorted(p for p in path.rglob("*.mv") if "dependencies" not in p.parts)
+        root = path.parent if path.is_file() else path
+        programs = []
+        for mv in files:
+            rel = mv.relative_to(root).as_posix()
+            try:
+                module = deserialize_module(mv.read_bytes())
+            except (MoveBinaryError, IndexError) as e:
+                self.errors.append(f"{rel}: {e}")
+                continue
+            programs.append(self.lower(module, file=rel, root=str(root)))
+        return programs
+
+    def lower(self, module: MoveModule, file: str = "", root: str = "") -> Program:
+        chain = self.chain or _detect_chain(module)
+        program = Program(
+            name=module.name,
+            chain=chain,
+            framework="move-bytecode",
+            root=root,
+            source_files=[file] if file else [],
+            program_id=module.address,
+            metadata={
+                "module": module.id,
+                "bytecode_version": module.version,
+                "friends": module.friends,
+                "dependencies": module.dependencies,
+            },
+        )
+        for st in module.structs:
+            program.account_types.append(AccountType(
+                name=st.name,
+                file=file,
+                line=0,
+                fields=[Argument(name=n, ty=t) for n, t in st.fields],
+                properties={"abilities": st.abilities, "native": st.is_native},
+            ))
+        for fn in module.functions:
+            if fn.visibility == "private" and not fn.is_entry:
+                continue
+            program.instructions.append(self._instruction(fn, file, module.key_types))
+        return program
+
+    def _instruction(self, fn: MoveFunction, file: str, key_types: set[str]) -> Instruction:
+        ix = Instruction(
+            name=fn.name,
+            handler=fn.name,
+            file=file,
+            line=0,
+            args=[Argument(name=f"arg{i}", ty=ty) for i, ty in enumerate(fn.params)],
+            body="\n".join(f"{i}: {op:#04x}" + (f" {arg}" if arg is not None else "") for i, (op, arg) in enumerate(fn.code)),
+            calls=[CallSite(kind="call", target=target, line=0) for target in fn.calls],
+            visibility=fn.visibility,
+            modifiers=["entry"] if fn.is_entry else [],
+            properties={
+                "entry": fn.is_entry,
+                "native": fn.is_native,
+                "returns": fn.returns,
+                "acquires": fn.acquires,
+                "type_params": fn.type_params,
+                "global_reads": fn.global_reads,
+                "global_writes": fn.global_writes,
+            },
+        )
+        for i, ty in enumerate(fn.params):
+            base = ty.lstrip("&").removeprefix("mut ").split("<", 1)[0]
+            if base == "signer":
+                ix.accounts.append(AccountField(name=f"arg{i}", ty=ty, is_signer=True))
+            elif base.endswith("tx_context::TxContext"):
+                ix.properties["tx_context"] = f"arg{i}"
+            elif base in key_types:
+                ix.accounts.append(AccountField(name=f"arg{i}", ty=ty, is_mut=ty.startswith("&mut ")))
+        return ix
+
+
+def _detect_chain(module: MoveModule) -> str:
+    deps = module.dependencies
+    if any(d.startswith("0x2::") for d in deps) or module.address == "0x2":
+        return "sui"
+    return "aptos"
+
+
+def load_move_bytecode(path: Path, chain: str | None = None) -> list[Program]:
+    """Convenience wrapper around MoveBytecodeFrontend.load()."""
+    return MoveBytecodeFrontend(chain).load(Path(path))
+
@@ -0,0 +1,380 @@
+"""
+Instruction-dispatch recovery for native (non-Anchor) Solana programs.
+
+Native programs hand-roll what Anchor generates: a single
+`process_instruction` entrypoint that decodes the instruction data and
+dispatches to per-instruction handlers. This module recognizes the common
+shapes of that dispatch and reconstructs the same `Instruction` records the
+Anchor front-end produces, so account-level detectors work on both.
+
+Recognized dispatch shapes:
+- Tag byte: `match instruction_data[0] { 0 => .., 1 => .. }`, or
+  `let (tag, rest) = data.split_first()..; match tag { .. }`
+- Borsh enum: `match MyInstruction::try_from_slice(data)? { MyInstruction::Init { .. } => .. }`
+- SPL-style `unpack`: an enum whose `unpack` fn maps tag bytes to variants
+
+Per-handler account lists come from `next_account_info(iter)` sequences,
+slice patterns (`let [a, b, ..] = accounts`), or indexing (`&accounts[0]`),
+and are annotated with the signer/writable/owner checks found in the body
+plus the `0. [signer] ...` doc convention used on instruction enums.
+"""
+
+import re
+from pathlib import Path
+
+from .model import AccountField, Argument, Instruction, Program
+from .rust_source import (
+    RustEnum,
+    RustFn,
+    called_functions,
+    find_cpi_calls,
+    find_matching,
+    iter_enums,
+    iter_functions,
+    iter_matches,
+    line_at,
+    parse_match_arms,
+    parse_params,
+    snake_case,
+    split_top_level,
+    variant_args,
+)
+from .workspace import SourceFile, crate_name, find_crates, load_rust_sources
+
+_ENTRYPOINT_RE = re.compile(r"\bentrypoint!\s*\(\s*([A-Za-z_]\w*)\s*\)")
+_DECLARE_ID_RE = re.compile(r"declare_id!\s*\(\s*\"([1-9A-HJ-NP-Za-km-z]{32,44})\"\s*\)")
+_TAG_PATTERN_RE = re.compile(r"^(?:Some\s*\(\s*&?\s*)?(0x[0-9a-fA-F]+|\d+)(?:u8|u16|u32)?\s*\)?$")
+_ACCOUNT_DOC_RE = re.compile(r"^\s*(\d+)\.\s*`?\[([^\]]*)\]`?\s*(.*)$")
+_MAX_DELEGATION_DEPTH = 3
+
+
+class NativeDispatchRecovery:
+    """Reconstructs per-instruction handlers from native program sources."""
+
+    def __init__(self, max_files: int = 2000):
+        """Initialize recovery.
+
+        Args:
+            max_files: Upper bound on Rust files read per program
+        """
+        self.max_files = max_files
+
+    @staticmethod
+    def is_native_source(source: str) -> bool:
+        """True if a file declares a native entrypoint (and is not an Anchor program)."""
+        return bool(_ENTRYPOINT_RE.search(source)) and "#[program]" not in source
+
+    def find_program_roots(self, path: Path) -> list[Path]:
+        """Find crate roots under ``path`` that contain a native entrypoint."""
+        return find_crates(Path(path), self.is_native_source)
+
+    def recover(self, path: Path) -> list[Program]:
+        """Recover all native programs found under ``path``."""
+        return [self.recover_program(root) for root in self.find_program_roots(path)]
+
+    def recover_program(self, root: Path) -> Program:
+        """Recover the instruction set of a single native program crate."""
+        root = Path(root)
+        files = load_rust_sources(root, self.max_files)
+        program = Program(
+            name=crate_name(root),
+            chain="solana",
+            framework="native",
+            root=str(root),
+            source_files=[f.relpath for f in files],
+        )
+
+        functions: dict[str, list[tuple[SourceFile, RustFn]]] = {}
+        enums: dict[str, tuple[SourceFile, RustEnum]] = {}
+        entry_name = None
+        entry_file = None
+        for sf in files:
+            for fn in iter_functions(sf.text, sf.original):
+                functions.setdefault(fn.name, []).append((sf, fn))
+            for en in iter_enums(sf.text, sf.original):
+                enums.setdefault(en.name, (sf, en))
+            if entry_name is None:
+                em = _ENTRYPOINT_RE.search(sf.text)
+                if em:
+                    entry_name, entry_file = em.group(1), sf
+            if program.program_id is None:
+                idm = _DECLARE_ID_RE.search(sf.original)
+                if idm:
+                    program.program_id = idm.group(1)
+
+        entry_name = entry_name or "process_instruction"
+        entry = self._resolve_fn(functions, entry_name, prefer=entry_file)
+        program.metadata["entrypoint"] = entry_name
+        if entry is None:
+            program.metadata["dispatch"] = "unresolved"
+            return program
+
+        dispatch = self._find_dispatch(entry, functions, enums, depth=0)
+        if dispatch is None:
+            # Single-instruction program: the entrypoint is the only handler
+            sf, fn = entry
+            program.metadata["dispatch"] = "single"
+            program.instructions.append(self._build_instruction(entry_name, None, sf, fn, []))
+            return program
+
+        kind, arms, dispatch_fn, enum_info = dispatch
+        program.metadata["dispatch"] = kind
+        if enum_info is not None:
+            program.metadata["instruction_enum"] = enum_info[1].name
+
+        for discriminator, name, arm_expr, variant_args, account_docs in arms:
+            handler = self._handler_for_arm(arm_expr, functions, dispatch_fn)
+            if handler is None:
+                # Logic written directly in the match arm
+                sf, fn = dispatch_fn
+                ix = self._build_instruction(
+                    name, discriminator, sf, fn, account_docs,
+                    body=arm_expr, body_line=self._line_of_snippet(sf, arm_expr, fn.line),
+                )
+                ix.properties["inline_handler"] = True
+            else:
+                ix = self._build_instruction(name, discriminator, handler[0], handler[1], account_docs)
+            if variant_args is not None:
+                ix.args = variant_args
+            program.instructions.append(ix)
+
+        return program
+
+    # ------------------------------------------------------------------
+    # Dispatch discovery
+    # ------------------------------------------------------------------
+
+    def _resolve_fn(self, functions, name: str, prefer: SourceFile | None = None):
+        candidates = functions.get(name, [])
+        if not candidates:
+            return None
+        if prefer is not None:
+            for sf, fn in candidates:
+                if sf is prefer:
+                    return sf, fn
+        return candidates[0]
+
+    def _find_dispatch(self, fn_entry, functions, enums, depth: int):
+        """Find the best `match` in a function (or the function it delegates to)."""
+        sf, fn = fn_entry
+        best = None
+        for _start, scrutinee, arms_text in iter_matches(fn.body):
+            arms = parse_match_arms(arms_text)
+            tag_arms = self._tag_arms(arms)
+            enum_arms = self._enum_arms(arms, enums, scrutinee, fn.body)
+            candidate = None
+            if enum_arms and (best is None or len(enum_arms[0]) > len(best[1])):
+                candidate = ("borsh_enum" if enum_arms[2] else "enum", enum_arms[0], fn_entry, enum_arms[1])
+            elif len(tag_arms) >= 1 and (best is None or len(tag_arms) > len(best[1])):
+                candidate = ("tag_byte", tag_arms, fn_entry, None)
+            if candidate is not None:
+                best = candidate
+        if best is not None:
+            return best
+        if depth >= _MAX_DELEGATION_DEPTH:
+            return None
+        # Follow delegation such as `Processor::process(program_id, accounts, data)`
+        for callee in self._called_functions(fn.body):
+            if callee == fn.name:
+                continue
+            target = self._resolve_fn(functions, callee, prefer=sf)
+            if target is None:
+                continue
+            found = self._find_dispatch(target, functions, enums, depth + 1)
+            if found is not None:
+                return found
+        return None
+
+    def _tag_arms(self, arms: list[tuple[str, str]]):
+        result = []
+        for pattern, expr in arms:
+            for alternative in pattern.split("|"):
+                tm = _TAG_PATTERN_RE.match(alternative.strip())
+                if tm:
+                    tag = int(tm.group(1), 0)
+                    result.append((str(tag), f"instruction_{tag}", expr, None, []))
+        return result
+
+    def _enum_arms(self, arms, enums, scrutinee: str, body: str):
+        """Map `Enum::Variant` arms to (discriminator, name, expr, args, docs)."""
+        by_enum: dict[str, list] = {}
+        for pattern, expr in arms:
+            pm = re.match(r"(?:[\w:]*::)?([A-Z]\w*)::([A-Z]\w*)\s*(.*)", pattern, re.DOTALL)
+            if pm and pm.group(1) in enums:
+                by_enum.setdefault(pm.group(1), []).append((pm.group(2), pm.group(3), expr))
+        if not by_enum:
+            return None
+        enum_name = max(by_enum, key=lambda k: len(by_enum[k]))
+        sf, enum = enums[enum_name]
+        is_borsh = bool(re.search(rf"{enum_name}\s*::\s*(?:try_from_slice|deserialize)", body))
+        is_borsh = is_borsh or "try_from_slice" in scrutinee or "BorshDeserialize" in " ".join(enum.attributes)
+        tags = self._unpack_tags(enum_name, enums, sf)
+        variants = {v.name: v for v in enum.variants}
+        result = []
+        for variant_name, _pattern_rest, expr in by_enum[enum_name]:
+            variant = variants.get(variant_name)
+            if variant is None:
+                continue
+            disc = tags.get(variant_name, variant.index)
+            result.append(
+                (
+                    str(disc),
+                    snake_case(variant_name),
+                    expr,
+                    variant_args(variant.payload),
+                    _account_docs(variant.docs),
+                )
+            )
+        return (result, (sf, enum), is_borsh and not tags) if result else None
+
+    def _unpack_tags(self, enum_name: str, enums, sf: SourceFile) -> dict[str, int]:
+        """Recover tag → variant mapping from an SPL-style `unpack` impl."""
+        tags: dict[str, int] = {}
+        impl = re.search(rf"impl\s+(?:<[^>]*>\s*)?{enum_name}\s*\{{", sf.text)
+        if not impl:
+            return tags
+        end = find_matching(sf.text, impl.end() - 1)
+        impl_text = sf.text[impl.end():end]
+        for fn in iter_functions(impl_text):
+            if fn.name not in ("unpack", "try_from", "from_bytes", "decode"):
+                continue
+            for _start, _scrutinee, arms_text in iter_matches(fn.body):
+                for pattern, expr in parse_match_arms(arms_text):
+                    tm = _TAG_PATTERN_RE.match(pattern.strip())
+                    vm = re.search(r"(?:Self|" + enum_name + r")::([A-Z]\w*)", expr)
+                    if tm and vm:
+                        tags[vm.group(1)] = int(tm.group(1), 0)
+        return tags
+
+    def _called_functions(self, body: str) -> list[str]:
+        names: list[str] = []
+        for _path, name in called_functions(body):
+            if name not in names:
+                names.append(name)
+        return names
+
+    def _handler_for_arm(self, expr: str, functions, dispatch_fn):
+        sf, fn = dispatch_fn
+        for callee in self._called_functions(expr):
+            if callee == fn.name:
+                continue
+            target = self._resolve_fn(functions, callee, prefer=sf)
+            if target is not None:
+                return target
+        return None
+
+    # ------------------------------------------------------------------
+    # Handler lowering
+    # ------------------------------------------------------------------
+
+    def _build_instruction(
+        self,
+        name: str,
+        discriminator: str | None,
+        sf: SourceFile,
+        fn: RustFn,
+        account_docs: list[tuple[int, set[str], str]],
+        body: str | None = None,
+        body_line: int | None = None,
+    ) -> Instruction:
+        body = fn.body if body is None else body
+        body_line = line_at(sf.text, fn.body_start) if body_line is None else body_line
+        ix = Instruction(
+            name=name,
+            handler=fn.name,
+            file=sf.relpath,
+            line=fn.line,
+            body=body,
+            body_line=body_line,
+            discriminator=discriminator,
+        )
+        ix.args = [
+            Argument(name=n, ty=t)
+            for n, t in parse_params(fn.params)
+            if not _is_context_param(t)
+        ]
+        ix.accounts = self._accounts_from_body(body, body_line)
+        self._apply_account_docs(ix, account_docs)
+        ix.calls = find_cpi_calls(body, body_line)
+        return ix
+
+    def _accounts_from_body(self, body: str, body_line: int) -> list[AccountField]:
+        accounts: list[AccountField] = []
+        seen: set[str] = set()
+
+        def add(name: str, offset: int):
+            if name in seen or name == "_":
+                return
+            seen.add(name)
+            accounts.append(AccountField(name=name, line=body_line + body.count("\n", 0, offset)))
+
+        positions: list[tuple[int, str]] = []
+        for m in re.finditer(r"let\s+(?:mut\s+)?([A-Za-z_]\w*)\s*(?::[^=]+)?=\s*next_account_info\s*\(", body):
+            positions.append((m.start(), m.group(1)))
+        for m in re.finditer(r"let\s+(?:mut\s+)?([A-Za-z_]\w*)\s*(?::[^=]+)?=\s*&?\s*accounts\s*\[\s*(\d+)\s*\]", body):
+            positions.append((m.start(), m.group(1)))
+        for m in re.finditer(r"let\s+\[([^\]]+)\]\s*=\s*(?:&\s*)?accounts\b", body):
+            for part in split_top_level(m.group(1)):
+                part = part.strip().lstrip("ref ").strip()
+                if re.fullmatch(r"[A-Za-z_]\w*", part):
+                    positions.append((m.start(), part))
+        for offset, name in sorted(positions, key=lambda p: p[0]):
+            add(name, offset)
+
+        for acc in accounts:
+            n = re.escape(acc.name)
+            if re.search(rf"\b{n}\s*\.\s*is_signer\b", body):
+                acc.is_signer = True
+                acc.constraints.append("signer")
+            if re.search(rf"\b{n}\s*\.\s*is_writable\b", body) or re.search(
+                rf"\b{n}\s*\.\s*(?:try_borrow_mut_data|try_borrow_mut_lamports|data\.borrow_mut|lamports\.borrow_mut)\b",
+                body,
+            ):
+                acc.is_mut = True
+                acc.constraints.append("writable")
+            if re.search(rf"\b{n}\s*\.\s*owner\s*[!=]=", body) or re.search(rf"[!=]=\s*\*?\s*{n}\s*\.\s*owner\b", body):
+                acc.constraints.append("owner")
+            if re.search(rf"\b{n}\s*\.\s*key\s*[!=]=", body) or re.search(rf"[!=]=\s*\*?\s*{n}\s*\.\s*key\b", body):
+                acc.constraints.append("key")
+        return accounts
+
+    def _apply_account_docs(self, ix: Instruction, account_docs: list[tuple[int, set[str], str]]) -> None:
+        if not account_docs:
+            return
+        for index, flags, description in account_docs:
+            if index < len(ix.accounts):
+                acc = ix.accounts[index]
+            else:
+                acc = AccountField(name=f"account_{index}", line=ix.line)
+                ix.accounts.append(acc)
+            acc.docs = description
+            if "writable" in flags:
+                acc.is_mut = True
+            if "signer" in flags and "declared_signer" not in acc.constraints:
+                # Documented as a signer; enforcement shows up as a separate "signer" constraint
+                acc.constraints.append("declared_signer")
+
+    def _line_of_snippet(self, sf: SourceFile, snippet: str, default: int) -> int:
+        idx = sf.text.find(snippet[:80]) if snippet else -1
+        return line_at(sf.text, idx) if idx >= 0 else default
+
+
+def _is_context_param(ty: str) -> bool:
+    compact = ty.replace(" ", "")
+    return compact in ("&Pubkey", "&'aPubkey") or "AccountInfo" in compact
+
+
+def _account_docs(docs: list[str]) -> list[tuple[int, set[str], str]]:
+    """Parse `0. [writable, signer] description` lines from variant docs."""
+    entries = []
+    for line in docs:
+        m = _ACCOUNT_DOC_RE.match(line)
+        if m:
+            flags = {f.strip().lower() for f in m.group(2).split(",") if f.strip()}
+            entries.append((int(m.group(1)), flags, m.group(3).strip()))
+    return entries
+
+
+def recover_native_programs(path: Path) -> list[Program]:
+    """Convenience wrapper around NativeDispatchRecovery.recover()."""
+    return NativeDispatchRecovery().recover(Path(path))
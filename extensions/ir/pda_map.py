@@ -0,0 +1,460 @@
+"""
+Workspace-wide PDA derivation map and seed collision scan.
+
+Collects every program-derived address in a workspace, from Anchor
+`seeds = [...]` constraints (with `seeds::program` honored) and from
+`find_program_address` / `create_program_address` calls in handler bodies,
+and normalizes each seed tuple to a byte pattern: literal bytes for
+constant seeds, fixed-width slots for pubkeys and integers, and
+variable-width slots for everything else.
+
+The runtime hashes seeds concatenated without length prefixes, so two
+derivations under the same program collide whenever their byte patterns
+can produce the same string: `[b"user", key]` and `[b"use", b"r", key]`
+are the same address space. We report, for different account types:
+- collision: same deriving program, patterns that can be equal
+- prefix: same deriving program, one seed tuple extends the other
+  (schemas that move together and often end up colliding after an edit)
+- cross-program: sibling programs deriving the same schema for different
+  types, so a missing `seeds::program` check accepts the wrong PDA
+
+Seeds named by constants (`const USER_SEED: &[u8] = b"user"`) are resolved
+to their bytes. Separately, string seed constants are compared across the
+workspace and against seeds that well-known programs derive their own PDAs
+from, to flag namespace reuse (`find_namespace_reuse`).
+
+This generalizes the `pda_seed_collision` PoC template into a scan.
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+from .inventory import classify_seed
+from .model import AccountField, Instruction, Program
+from .rust_source import find_matching, line_at, split_top_level
+from .workspace import load_rust_sources
+
+# A seed's width when it is not a literal
+_INT_WIDTHS = {"u8": 1, "i8": 1, "u16": 2, "i16": 2, "u32": 4, "i32": 4, "u64": 8, "i64": 8, "u128": 16, "i128": 16}
+_PUBKEY_WIDTH = 32
+_DERIVE_RE = re.compile(r"\b(?:Pubkey\s*::\s*)?(?P<fn>find_program_address|create_program_address)\s*\(")
+_LET_DERIVE_RE = re.compile(r"\blet\s+(?:\(\s*)?(?:mut\s+)?(?P<name>[A-Za-z_]\w*)[^=]*=\s*(?:Pubkey\s*::\s*)?$")
+# Account wrappers whose data Anchor does not type-check
+_UNCHECKED_WRAPPERS = {"UncheckedAccount", "AccountInfo"}
+# `const NAME: &[u8] = b"..."`, `&str = "..."`, `[u8; N] = *b"..."`
+_SEED_CONST_RE = re.compile(
+    r"\bconst\s+(?P<name>[A-Z_][A-Z0-9_]*)\s*:\s*(?:&\s*(?:'static\s+)?(?:\[u8\]|str)|\[u8\s*;\s*\w+\])\s*=\s*"
+    r'\*?\s*b?"(?P<value>[^"]*)"'
+)
+# Seeds well-known programs derive their own PDAs from: seed -> (user, derived under the same program)
+ECOSYSTEM_SEEDS = {
+    "__event_authority": ("Anchor event CPI (#[event_cpi])", True),
+    "metadata": ("Metaplex Token Metadata", False),
+    "edition": ("Metaplex Token Metadata", False),
+    "collection_authority": ("Metaplex Token Metadata", False),
+    "governance": ("SPL Governance", False),
+    "account-governance": ("SPL Governance", False),
+    "withdraw": ("SPL Stake Pool", False),
+    "transient": ("SPL Stake Pool", False),
+    "multisig": ("Squads", False),
+}
+
+
+@dataclass
+class Seed:
+    """One normalized seed component."""
+
+    kind: str  # const, pubkey, int, bytes
+    expr: str
+    value: str | None = None  # literal text for const seeds
+    width: int | None = None  # byte width for fixed-size seeds
+
+    @property
+    def label(self) -> str:
+        if self.kind == "const":
+            return f'"{self.value}"'
+        if self.kind == "int":
+            return f"<u{self.width * 8}:{_short(self.expr)}>"
+        return f"<{self.kind}:{_short(self.expr)}>"
+
+    def glob(self) -> list[str]:
+        """Byte pattern: literal bytes, "?" for one unknown byte, "*" for a variable run."""
+        if self.kind == "const":
+            return list(self.value or "")
+        if self.width is not None:
+            return ["?"] * self.width
+        return ["*"]
+
+
+@dataclass
+class SeedConstant:
+    """A string or byte-string constant a program defines."""
+
+    program: str
+    name: str
+    value: str
+    file: str
+    line: int
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"program": self.program, "name": self.name, "value": self.value, "file": self.file, "line": self.line}
+
+
+@dataclass
+class PdaDerivation:
+    """A PDA derived by a program for an account type."""
+
+    program: str
+    owner: str  # Program the address is derived under
+    account_type: str  # State type, or the derived variable's name when untyped
+    instruction: str
+    file: str
+    line: int
+    seeds: list[Seed] = field(default_factory=list)
+    source: str = "constraint"  # constraint, find_program_address, create_program_address
+    typed: bool = True  # False when the account's discriminator is not checked
+
+    @property
+    def schema(self) -> str:
+        return " / ".join(s.label for s in self.seeds)
+
+    @property
+    def shape(self) -> tuple:
+        """Seed tuple with dynamic seeds reduced to their kind and width."""
+        return tuple((s.kind, s.value if s.kind == "const" else s.width) for s in self.seeds)
+
+    def glob(self) -> list[str]:
+        return [ch for s in self.seeds for ch in s.glob()]
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "program": self.program,
+            "owner": self.owner,
+            "account_type": self.account_type,
+            "instruction": self.instruction,
+            "file": self.file,
+            "line": self.line,
+            "source": self.source,
+            "schema": self.schema,
+            "seeds": [{"kind": s.kind, "expr": s.expr, "value": s.value, "width": s.width} for s in self.seeds],
+        }
+
+
+@dataclass
+class PdaConflict:
+    """Two derivations of different account types that can alias."""
+
+    kind: str  # collision, prefix, cross-program
+    a: PdaDerivation
+    b: PdaDerivation
+
+    @property
+    def severity(self) -> str:
+        if self.kind == "collision":
+            # Without a discriminator check the aliasing is a type confusion, not just a squat
+            return "high" if not (self.a.typed and self.b.typed) else "medium"
+        return "low" if self.kind == "cross-program" else "info"
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"kind": self.kind, "severity": self.severity, "a": self.a.to_dict(), "b": self.b.to_dict()}
+
+
+@dataclass
+class SeedNamespaceReuse:
+    """A constant seed shared between programs' address namespaces."""
+
+    value: str
+    kind: str  # workspace, ecosystem
+    derivations: list[PdaDerivation] = field(default_factory=list)
+    constants: list[SeedConstant] = field(default_factory=list)
+    ecosystem: str | None = None  # Well-known program that derives from the same seed
+
+    @property
+    def programs(self) -> list[str]:
+        return sorted({d.owner for d in self.derivations} | {c.program for c in self.constants})
+
+    @property
+    def severity(self) -> str:
+        # A framework seed under the program's own id is the framework's PDA
+        if self.kind == "ecosystem" and ECOSYSTEM_SEEDS[self.value][1]:
+            return "medium"
+        return "low"
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "value": self.value,
+            "kind": self.kind,
+            "severity": self.severity,
+            "ecosystem": self.ecosystem,
+            "programs": self.programs,
+            "derivations": [d.to_dict() for d in self.derivations],
+            "constants": [c.to_dict() for c in self.constants],
+        }
+
+
+def _short(expr: str) -> str:
+    expr = re.sub(r"\.(?:as_ref|to_le_bytes|to_be_bytes|as_bytes|key)\(\)|\.key\b", "", expr).lstrip("&")
+    return expr.split(".")[-1] if "." in expr else expr
+
+
+def _literal(expr: str) -> str | None:
+    m = re.fullmatch(r'&?\s*b"([^"]*)"(?:\.as_ref\(\))?|&?\s*"([^"]*)"\.as_bytes\(\)', expr.strip())
+    if not m:
+        return None
+    return m.group(1) if m.group(1) is not None else m.group(2)
+
+
+def _int_width(ty: str) -> int | None:
+    return _INT_WIDTHS.get(ty.strip())
+
+
+def seed_constants(program: Program) -> dict[str, SeedConstant]:
+    """String and byte-string constants defined anywhere in ``program``'s crate, by name."""
+    found: dict[str, SeedConstant] = {}
+    for sf in load_rust_sources(Path(program.root)):
+        for m in _SEED_CONST_RE.finditer(sf.text):
+            found.setdefault(m.group("name"), SeedConstant(
+                program=program.name,
+                name=m.group("name"),
+                value=m.group("value"),
+                file=sf.relpath,
+                line=line_at(sf.text, m.start()),
+            ))
+    return found
+
+
+def normalize_seed(
+    expr: str,
+    ix: Instruction | None,
+    program: Program | None = None,
+    constants: dict[str, SeedConstant] | None = None,
+) -> Seed:
+    """Classify one seed expression and give it a byte width where it is fixed."""
+    expr = " ".join(expr.split())
+    literal = _literal(expr)
+    if literal is not None:
+        return Seed("const", expr, value=literal, width=len(literal))
+    if constants:
+        ref = re.sub(r"\.(?:as_ref|as_bytes)\(\)$", "", expr).lstrip("&* ").split("::")[-1]
+        const = constants.get(ref)
+        if const is not None:
+            return Seed("const", expr, value=const.value, width=len(const.value))
+    if ix is not None:
+        info = classify_seed(expr, ix)
+        if info["kind"] == "account":
+            return Seed("pubkey", expr, width=_PUBKEY_WIDTH)
+        if info["kind"] == "arg":
+            arg = next(a for a in ix.args if a.name == info["arg"])
+            if arg.ty.strip() == "Pubkey":
+                return Seed("pubkey", expr, width=_PUBKEY_WIDTH)
+            width = _int_width(arg.ty)
+            if width is not None and re.search(r"to_(?:le|be)_bytes", expr):
+                return Seed("int", expr, width=width)
+        if info["kind"] == "account_field" and program is not None:
+            acc = ix.account(info["account"])
+            at = program.account_type(_state_type(acc)) if acc else None
+            fty = next((f.ty for f in at.fields if f.name == info["field"]), None) if at else None
+            if fty is not None and fty.strip() == "Pubkey":
+                return Seed("pubkey", expr, width=_PUBKEY_WIDTH)
+            if fty is not None and _int_width(fty) and re.search(r"to_(?:le|be)_bytes", expr):
+                return Seed("int", expr, width=_int_width(fty))
+    # Native idioms: `account.key.as_ref()`, `program_id.as_ref()`, `&[bump]`
+    if re.search(r"\b(?:key|program_id|pubkey|mint|owner|authority)\s*(?:\(\))?\s*\.as_ref\(\)$|\.key\(\)\.as_ref\(\)$", expr):
+        return Seed("pubkey", expr, width=_PUBKEY_WIDTH)
+    m = re.fullmatch(r"&\s*\[\s*[A-Za-z_][\w.]*\s*\]", expr)
+    if m:
+        return Seed("int", expr, width=1)
+    m = re.search(r"as\s+(u\d+|i\d+)\s*\)?\s*\.to_(?:le|be)_bytes", expr)
+    if m and _int_width(m.group(1)):
+        return Seed("int", expr, width=_int_width(m.group(1)))
+    return Seed("bytes", expr)
+
+
+def _state_type(acc: AccountField) -> str:
+    m = re.search(r"\b(?:Account|AccountLoader|InterfaceAccount)\s*<[^<>]*?(\w+)\s*>", acc.ty)
+    return m.group(1) if m else ""
+
+
+def _owner(program: Program, ix: Instruction, acc: AccountField, siblings: dict[str, str]) -> str:
+    """Program that ``acc``'s seeds are derived under (`seeds::program`, else ``program``)."""
+    for c in acc.constraints:
+        name, _, value = c.partition("=")
+        if name.strip() != "seeds::program":
+            continue
+        ref = re.sub(r"\.key\(\)|\.key\b|&", "", value).strip()
+        target = ix.account(ref)
+        inner = target.inner_type if target is not None else None
+        for candidate in (inner, ref):
+            if candidate and _norm(candidate) in siblings:
+                return siblings[_norm(candidate)]
+        return f"external:{inner or ref}"
+    return program.name
+
+
+def _norm(name: str) -> str:
+    return re.sub(r"[_\-]|program$", "", name.lower())
+
+
+def _body_derivations(program: Program, ix: Instruction, constants: dict[str, SeedConstant]) -> list[PdaDerivation]:
+    found = []
+    body = ix.body
+    for m in _DERIVE_RE.finditer(body):
+        close = find_matching(body, m.end() - 1)
+        args = split_top_level(body[m.end():close])
+        if not args:
+            continue
+        seeds_expr = args[0].strip().lstrip("&").strip()
+        if not (seeds_expr.startswith("[") and seeds_expr.endswith("]")):
+            continue  # seeds built elsewhere
+        exprs = split_top_level(seeds_expr[1:-1])
+        if m.group("fn") == "create_program_address" and exprs and re.search(r"\bbump", exprs[-1]):
+            exprs = exprs[:-1]  # explicit bump, appended implicitly by find_program_address
+        statement_start = max(body.rfind(ch, 0, m.start()) for ch in ";{}") + 1
+        lm = _LET_DERIVE_RE.search(body[statement_start:m.start()])
+        name = lm.group("name") if lm else f"{ix.name}_pda"
+        found.append(PdaDerivation(
+            program=program.name,
+            owner=program.name,
+            account_type=re.sub(r"_(?:pda|key|address|pubkey|info)$", "", name),
+            instruction=ix.name,
+            file=ix.source_file,
+            line=ix.body_line + body[:m.start()].count("\n"),
+            seeds=[normalize_seed(e, ix, program, constants) for e in exprs],
+            source=m.group("fn"),
+            typed=False,
+        ))
+    return found
+
+
+def build_pda_map(
+    programs: list[Program], constants: dict[str, dict[str, SeedConstant]] | None = None
+) -> list[PdaDerivation]:
+    """Every PDA derivation in ``programs`` (one entry per distinct type/owner/schema).
+
+    Args:
+        programs: Loaded IR programs; non-Solana ones are ignored
+        constants: ``seed_constants`` per program name, read from source when omitted
+    """
+    solana = [p for p in programs if p.chain == "solana"]
+    if constants is None:
+        constants = {p.name: seed_constants(p) for p in solana}
+    siblings = {_norm(p.name): p.name for p in solana}
+    for p in solana:
+        if p.metadata.get("module"):
+            siblings.setdefault(_norm(p.metadata["module"]), p.name)
+    derivations: list[PdaDerivation] = []
+    seen: set[tuple] = set()
+    for program in solana:
+        consts = constants.get(program.name, {})
+        for ix in program.instructions:
+            candidates = []
+            for acc in ix.accounts:
+                if acc.seeds is None:
+                    continue
+                candidates.append(PdaDerivation(
+                    program=program.name,
+                    owner=_owner(program, ix, acc, siblings),
+                    account_type=_state_type(acc) or acc.name,
+                    instruction=ix.name,
+                    file=acc.file or ix.file,
+                    line=acc.line,
+                    seeds=[normalize_seed(s, ix, program, consts) for s in acc.seeds],
+                    typed=acc.wrapper not in _UNCHECKED_WRAPPERS and bool(_state_type(acc)),
+                ))
+            candidates.extend(_body_derivations(program, ix, consts))
+            for d in candidates:
+                key = (d.owner, d.account_type, d.shape)
+                if key not in seen:
+                    seen.add(key)
+                    derivations.append(d)
+    return derivations
+
+
+def patterns_overlap(a: list[str], b: list[str]) -> bool:
+    """Whether two byte globs ("?" one byte, "*" any run) can match a common string."""
+    n, m = len(a), len(b)
+    reachable = [[False] * (m + 1) for _ in range(n + 1)]
+    reachable[0][0] = True
+    for i in range(n + 1):
+        for j in range(m + 1):
+            if not reachable[i][j]:
+                continue
+            if i < n and a[i] == "*":
+                reachable[i + 1][j] = True
+                if j < m:
+                    reachable[i][j + 1] = True
+            if j < m and b[j] == "*":
+                reachable[i][j + 1] = True
+                if i < n:
+                    reachable[i + 1][j] = True
+            if i < n and j < m and a[i] != "*" and b[j] != "*" and (a[i] == b[j] or "?" in (a[i], b[j])):
+                reachable[i + 1][j + 1] = True
+    return reachable[n][m]
+
+
+def _is_prefix(short: tuple, long: tuple) -> bool:
+    return len(short) < len(long) and long[:len(short)] == short
+
+
+def find_conflicts(derivations: list[PdaDerivation]) -> list[PdaConflict]:
+    """Pairs of derivations for different account types that collide or share a schema prefix.
+
+    An untyped derivation (a handler-body call, or an unchecked account) only
+    has a name for a type, so one with exactly the same seed shape as another
+    derivation is taken to be the same account rather than a collision.
+    """
+    conflicts: list[PdaConflict] = []
+    for i, a in enumerate(derivations):
+        for b in derivations[i + 1:]:
+            if _norm(a.account_type) == _norm(b.account_type):
+                continue
+            if a.shape == b.shape and not (a.typed and b.typed):
+                continue
+            if a.owner == b.owner:
+                if patterns_overlap(a.glob(), b.glob()):
+                    conflicts.append(PdaConflict("collision", a, b))
+                elif _is_prefix(a.shape, b.shape) or _is_prefix(b.shape, a.shape):
+                    conflicts.append(PdaConflict("prefix", a, b))
+            elif a.program != b.program and a.shape == b.shape:
+                conflicts.append(PdaConflict("cross-program", a, b))
+    return conflicts
+
+
+def find_namespace_reuse(
+    derivations: list[PdaDerivation], constants: dict[str, dict[str, SeedConstant]] | None = None
+) -> list[SeedNamespaceReuse]:
+    """Leading constant seeds shared by several workspace programs or with well-known programs.
+
+    The first constant seed of a derivation is its namespace. A namespace
+    derived under more than one workspace program means a consumer that
+    checks seeds but not the deriving program accepts either program's
+    account; one used by a well-known program invites the same confusion
+    with that program (or, for framework seeds, is that framework's PDA).
+    Derivations under another program (`seeds::program`) are references,
+    not uses, and are skipped. Seed constants (names containing `SEED`)
+    count even when no derivation in the IR uses them.
+    """
+    by_value: dict[str, list[PdaDerivation]] = {}
+    for d in derivations:
+        if d.owner != d.program:
+            continue
+        lead = next((s for s in d.seeds if s.kind == "const"), None)
+        if lead is not None and lead.value:
+            by_value.setdefault(lead.value, []).append(d)
+    defined: dict[str, list[SeedConstant]] = {}
+    for consts in (constants or {}).values():
+        for c in consts.values():
+            if "SEED" in c.name and c.value:
+                defined.setdefault(c.value, []).append(c)
+
+    found: list[SeedNamespaceReuse] = []
+    for value in sorted(set(by_value) | set(defined)):
+        uses, consts = by_value.get(value, []), defined.get(value, [])
+        owners = {d.owner for d in uses} | {c.program for c in consts}
+        if value in ECOSYSTEM_SEEDS:
+            found.append(SeedNamespaceReuse(value, "ecosystem", uses, consts, ecosystem=ECOSYSTEM_SEEDS[value][0]))
+        elif len(owners) > 1:
+            found.append(SeedNamespaceReuse(value, "workspace", uses, consts))
+    return found
@@ -0,0 +1,633 @@
+"""
+Lightweight Rust source scanning helpers.
+
+On-chain programs are scanned without a full Rust parser, so the front-ends
+share these helpers for the structural work they need: blanking comments,
+matching delimiters, and locating items (functions, structs, enums) with
+their attributes and line numbers.
+
+All offsets returned by this module index into the *comment-stripped* text,
+which has the same length and line layout as the original source.
+"""
+
+import re
+from dataclasses import dataclass, field
+
+from .model import Argument, CallSite
+
+_OPENERS = {"(": ")", "[": "]", "{": "}"}
+_CLOSERS = {")", "]", "}"}
+
+
+def strip_comments(source: str) -> str:
+    """Replace comments with spaces while keeping offsets and newlines intact.
+
+    String and char literals are left untouched so that byte-string seeds
+    such as ``b"vault"`` survive. Lifetimes (``'info``) are not mistaken for
+    char literals.
+    """
+    out = list(source)
+    i = 0
+    n = len(source)
+    while i < n:
+        ch = source[i]
+        nxt = source[i + 1] if i + 1 < n else ""
+        if ch == "/" and nxt == "/":
+            while i < n and source[i] != "\n":
+                out[i] = " "
+                i += 1
+            continue
+        if ch == "/" and nxt == "*":
+            depth = 0
+            while i < n:
+                if source.startswith("/*", i):
+                    depth += 1
+                    out[i] = out[i + 1] = " "
+                    i += 2
+                    continue
+                if source.startswith("*/", i):
+                    depth -= 1
+                    out[i] = out[i + 1] = " "
+                    i += 2
+                    if depth == 0:
+                        break
+                    continue
+                if source[i] != "\n":
+                    out[i] = " "
+                i += 1
+            continue
+        if ch == '"':
+            i = _skip_string(source, i)
+            continue
+        if ch == "r" and re.match(r'r#*"', source[i:i + 8]) and not _is_ident_char(source, i - 1):
+            i = _skip_raw_string(source, i)
+            continue
+        if ch == "'":
+            m = re.match(r"'(\\.|[^\\'\n])'", source[i:i + 12])
+            if m:
+                i += len(m.group(0))
+                continue
+        i += 1
+    return "".join(out)
+
+
+def _is_ident_char(text: str, idx: int) -> bool:
+    return 0 <= idx < len(text) and (text[idx].isalnum() or text[idx] == "_")
+
+
+def _skip_string(text: str, start: int) -> int:
+    i = start + 1
+    while i < len(text):
+        if text[i] == "\\":
+            i += 2
+            continue
+        if text[i] == '"':
+            return i + 1
+        i += 1
+    return len(text)
+
+
+def _skip_raw_string(text: str, start: int) -> int:
+    m = re.match(r'r(#*)"', text[start:])
+    if not m:
+        return start + 1
+    terminator = '"' + m.group(1)
+    end = text.find(terminator, start + len(m.group(0)))
+    return len(text) if end == -1 else end + len(terminator)
+
+
+def find_matching(text: str, open_idx: int) -> int:
+    """Return the index of the delimiter closing the one at ``open_idx``.
+
+    Handles nested ``()``, ``[]`` and ``{}`` and skips string literals.
+    Returns ``len(text) - 1`` if the delimiter is unbalanced.
+    """
+    stack = [_OPENERS[text[open_idx]]]
+    i = open_idx + 1
+    while i < len(text):
+        ch = text[i]
+        if ch == '"':
+            i = _skip_string(text, i)
+            continue
+        if ch in _OPENERS:
+            stack.append(_OPENERS[ch])
+        elif ch in _CLOSERS:
+            if stack and ch == stack[-1]:
+                stack.pop()
+                if not stack:
+                    return i
+        i += 1
+    return len(text) - 1
+
+
+def statement_end(text: str, start: int) -> int:
+    """Offset of the ``;`` (or unmatched closer) ending the statement that starts at ``start``."""
+    depth = 0
+    for i in range(start, len(text)):
+        ch = text[i]
+        if ch in _OPENERS:
+            depth += 1
+        elif ch in _CLOSERS:
+            if depth == 0:
+                return i
+            depth -= 1
+        elif ch == ";" and depth == 0:
+            return i
+    return len(text)
+
+
+def line_at(text: str, idx: int) -> int:
+    """1-based line number of offset ``idx``."""
+    return text.count("\n", 0, max(idx, 0)) + 1
+
+
+def split_top_level(text: str, sep: str = ",") -> list[str]:
+    """Split on ``sep`` ignoring separators nested in brackets, generics or strings."""
+    parts: list[str] = []
+    depth = 0
+    angle = 0
+    current: list[str] = []
+    i = 0
+    while i < len(text):
+        ch = text[i]
+        if ch == '"':
+            end = _skip_string(text, i)
+            current.append(text[i:end])
+            i = end
+            continue
+        if ch in _OPENERS:
+            depth += 1
+        elif ch in _CLOSERS:
+            depth -= 1
+        elif ch == "<":
+            angle += 1
+        elif ch == ">" and angle > 0 and text[i - 1] != "=" and text[i - 1] != "-":
+            angle -= 1
+        if ch == sep and depth == 0 and angle == 0:
+            parts.append("".join(current).strip())
+            current = []
+        else:
+            current.append(ch)
+        i += 1
+    tail = "".join(current).strip()
+    if tail:
+        parts.append(tail)
+    return parts
+
+
+@dataclass
+class RustFn:
+    """A function item located in a source file."""
+
+    name: str
+    params: str  # Raw parameter list without the surrounding parens
+    return_type: str
+    attributes: list[str]
+    start: int  # Offset of the `fn` keyword (or leading `pub`)
+    body_start: int  # Offset of the opening brace
+    body_end: int  # Offset of the closing brace
+    line: int
+    body: str = ""
+    # Documentation comments collected from the original (unstripped) text
+    docs: list[str] = field(default_factory=list)
+
+    @property
+    def body_line(self) -> int:
+        return self.line
+
+
+@dataclass
+class RustStruct:
+    """A struct item with its raw field list."""
+
+    name: str
+    attributes: list[str]
+    body: str  # Text between the braces (empty for tuple/unit structs)
+    start: int
+    line: int
+    generics: str = ""
+
+
+@dataclass
+class RustEnumVariant:
+    """One variant of an enum, with its payload and preceding doc lines."""
+
+    name: str
+    index: int
+    payload: str  # "{ a: u64 }", "(u64)", or ""
+    line: int
+    docs: list[str] = field(default_factory=list)
+    explicit_discriminant: int | None = None
+
+
+@dataclass
+class RustEnum:
+    """An enum item."""
+
+    name: str
+    attributes: list[str]
+    variants: list[RustEnumVariant]
+    start: int
+    line: int
+
+
+_FN_RE = re.compile(
+    r"(?P<prefix>(?:pub(?:\s*\([^)]*\))?\s+)?(?:(?:const|async|unsafe|extern\s+\"[^\"]*\")\s+)*)"
+    r"fn\s+(?P<name>[A-Za-z_]\w*)\s*(?P<generics><[^(]*>)?\s*\("
+)
+_STRUCT_RE = re.compile(
+    r"(?:pub(?:\s*\([^)]*\))?\s+)?struct\s+(?P<name>[A-Za-z_]\w*)\s*(?P<generics><[^{;(]*>)?\s*"
+)
+_ENUM_RE = re.compile(r"(?:pub(?:\s*\([^)]*\))?\s+)?enum\s+(?P<name>[A-Za-z_]\w*)\s*(?:<[^{]*>)?\s*\{")
+
+
+def _preceding_attributes(text: str, start: int) -> list[str]:
+    """Collect `#[...]` attributes directly above an item."""
+    attrs: list[str] = []
+    i = start
+    while True:
+        j = i - 1
+        while j >= 0 and text[j].isspace():
+            j -= 1
+        if j < 0 or text[j] != "]":
+            break
+        # Walk back to the matching '#['
+        depth = 0
+        k = j
+        while k >= 0:
+            if text[k] == "]":
+                depth += 1
+            elif text[k] == "[":
+                depth -= 1
+                if depth == 0:
+                    break
+            k -= 1
+        if k <= 0 or text[k - 1] != "#":
+            break
+        attrs.insert(0, text[k - 1:j + 1])
+        i = k - 1
+    return attrs
+
+
+def preceding_docs(original: str, start: int) -> list[str]:
+    """Collect `///` doc lines (and attributes) above an item in the original text."""
+    lines = original[:start].split("\n")
+    # The last element is the partial line the item starts on
+    docs: list[str] = []
+    for raw in reversed(lines[:-1]):
+        stripped = raw.strip()
+        if stripped.startswith("///"):
+            docs.insert(0, stripped[3:].strip())
+        elif stripped.startswith("#[") or stripped == "":
+            if stripped == "" and docs:
+                break
+            continue
+        else:
+            break
+    return docs
+
+
+def iter_functions(text: str, original: str | None = None) -> list[RustFn]:
+    """Find every function with a body in comment-stripped ``text``."""
+    fns: list[RustFn] = []
+    for m in _FN_RE.finditer(text):
+        if m.start() > 0 and _is_ident_char(text, m.start() - 1):
+            continue
+        paren = m.end() - 1
+        paren_end = find_matching(text, paren)
+        j = paren_end + 1
+        # Skip return type / where clause up to the body or a ';' (trait method)
+        brace = -1
+        depth = 0
+        while j < len(text):
+            ch = text[j]
+            if ch == "<":
+                depth += 1
+            elif ch == ">" and text[j - 1] != "-":
+                depth -= 1
+            elif ch == ";" and depth <= 0:
+                break
+            elif ch == "{" and depth <= 0:
+                brace = j
+                break
+            j += 1
+        if brace == -1:
+            continue
+        body_end = find_matching(text, brace)
+        ret = text[paren_end + 1:brace].strip()
+        if ret.startswith("->"):
+            ret = ret[2:].strip()
+        start = m.start()
+        fns.append(
+            RustFn(
+                name=m.group("name"),
+                params=text[paren + 1:paren_end],
+                return_type=ret,
+                attributes=_preceding_attributes(text, start),
+                start=start,
+                body_start=brace,
+                body_end=body_end,
+                line=line_at(text, start),
+                body=text[brace + 1:body_end],
+                docs=preceding_docs(original, start) if original is not None else [],
+            )
+        )
+    return fns
+
+
+def iter_structs(text: str) -> list[RustStruct]:
+    """Find struct items (named-field, tuple and unit) in ``text``."""
+    structs: list[RustStruct] = []
+    for m in _STRUCT_RE.finditer(text):
+        if m.start() > 0 and _is_ident_char(text, m.start() - 1):
+            continue
+        j = m.end()
+        # Skip a where-clause if present
+        while j < len(text) and text[j] not in "{(;":
+            j += 1
+        body = ""
+        if j < len(text) and text[j] in "{(":
+            end = find_matching(text, j)
+            body = text[j + 1:end]
+        structs.append(
+            RustStruct(
+                name=m.group("name"),
+                attributes=_preceding_attributes(text, m.start()),
+                body=body,
+                start=m.start(),
+                line=line_at(text, m.start()),
+                generics=(m.group("generics") or "").strip(),
+            )
+        )
+    return structs
+
+
+def iter_enums(text: str, original: str | None = None) -> list[RustEnum]:
+    """Find enum items and their variants in ``text``."""
+    enums: list[RustEnum] = []
+    for m in _ENUM_RE.finditer(text):
+        if m.start() > 0 and _is_ident_char(text, m.start() - 1):
+            continue
+        brace = m.end() - 1
+        end = find_matching(text, brace)
+        body_offset = brace + 1
+        variants: list[RustEnumVariant] = []
+        next_index = 0
+        for raw in _split_with_offsets(text[body_offset:end]):
+            chunk, rel = raw
+            stripped = chunk.strip()
+            if not stripped:
+                continue
+            # Drop variant attributes such as #[default]
+            without_attrs = re.sub(r"#\[[^\]]*\]\s*", "", stripped)
+            vm = re.match(r"([A-Za-z_]\w*)\s*(.*)", without_attrs, re.DOTALL)
+            if not vm:
+                continue
+            name, rest = vm.group(1), vm.group(2).strip()
+            explicit = None
+            dm = re.match(r"=\s*(0x[0-9a-fA-F]+|\d+)", rest)
+            if dm:
+                explicit = int(dm.group(1), 0)
+                rest = ""
+                next_index = explicit
+            abs_offset = body_offset + rel + (len(chunk) - len(chunk.lstrip()))
+            variants.append(
+                RustEnumVariant(
+                    name=name,
+                    index=next_index,
+                    payload=rest,
+                    line=line_at(text, abs_offset),
+                    docs=preceding_docs(original, abs_offset) if original is not None else [],
+                    explicit_discriminant=explicit,
+                )
+            )
+            next_index += 1
+        enums.append(
+            RustEnum(
+                name=m.group("name"),
+                attributes=_preceding_attributes(text, m.start()),
+                variants=variants,
+                start=m.start(),
+                line=line_at(text, m.start()),
+            )
+        )
+    return enums
+
+
+def _split_with_offsets(text: str) -> list[tuple[str, int]]:
+    """Like split_top_level(',') but keeps each chunk's offset."""
+    chunks: list[tuple[str, int]] = []
+    depth = 0
+    start = 0
+    for i, ch in enumerate(text):
+        if ch in _OPENERS:
+            depth += 1
+        elif ch in _CLOSERS:
+            depth -= 1
+        elif ch == "," and depth == 0:
+            chunks.append((text[start:i], start))
+            start = i + 1
+    chunks.append((text[start:], start))
+    return chunks
+
+
+def parse_named_fields(body: str) -> list[tuple[str, str, list[str]]]:
+    """Parse ``name: Type`` fields of a struct body.
+
+    Returns:
+        List of (name, type, attributes) tuples in declaration order.
+    """
+    fields: list[tuple[str, str, list[str]]] = []
+    for chunk in split_top_level(body):
+        attrs = re.findall(r"#\[(?:[^\[\]]|\[[^\]]*\])*\]", chunk)
+        remainder = chunk
+        for attr in attrs:
+            remainder = remainder.replace(attr, " ")
+        fm = re.match(r"\s*(?:pub(?:\s*\([^)]*\))?\s+)?([A-Za-z_]\w*)\s*:\s*(.+)", remainder, re.DOTALL)
+        if fm:
+            fields.append((fm.group(1), " ".join(fm.group(2).split()), attrs))
+    return fields
+
+
+def parse_params(params: str) -> list[tuple[str, str]]:
+    """Parse a function parameter list into (name, type) pairs."""
+    result: list[tuple[str, str]] = []
+    for chunk in split_top_level(params):
+        pm = re.match(r"\s*(?:mut\s+)?([A-Za-z_]\w*)\s*:\s*(.+)", chunk, re.DOTALL)
+        if pm:
+            result.append((pm.group(1), " ".join(pm.group(2).split())))
+    return result
+
+
+def iter_matches(body: str):
+    """Yield (offset, scrutinee, arms_text) for every ``match`` expression in ``body``."""
+    for m in re.finditer(r"\bmatch\s+", body):
+        j = m.end()
+        # Scrutinee runs up to the first top-level '{'
+        depth = 0
+        while j < len(body):
+            ch = body[j]
+            if ch in "([":
+                depth += 1
+            elif ch in ")]":
+                depth -= 1
+            elif ch == "{" and depth == 0:
+                break
+            j += 1
+        if j >= len(body):
+            continue
+        end = find_matching(body, j)
+        yield m.start(), body[m.end():j].strip(), body[j + 1:end]
+
+
+def parse_match_arms(body: str) -> list[tuple[str, str]]:
+    """Split the inside of a ``match { ... }`` block into (pattern, expression) arms."""
+    arms: list[tuple[str, str]] = []
+    i = 0
+    n = len(body)
+    while i < n:
+        arrow = body.find("=>", i)
+        if arrow == -1:
+            break
+        pattern = body[i:arrow].strip().lstrip(",").strip()
+        j = arrow + 2
+        while j < n and body[j].isspace():
+            j += 1
+        if j < n and body[j] == "{":
+            end = find_matching(body, j)
+            expr = body[j:end + 1]
+            i = end + 1
+        else:
+            depth = 0
+            k = j
+            while k < n:
+                ch = body[k]
+                if ch == '"':
+                    k = _skip_string(body, k)
+                    continue
+                if ch in _OPENERS:
+                    depth += 1
+                elif ch in _CLOSERS:
+                    depth -= 1
+                elif ch == "," and depth == 0:
+                    break
+                k += 1
+            expr = body[j:k]
+            i = k + 1
+        arms.append((pattern, expr.strip()))
+    return arms
+
+
+def snake_case(name: str) -> str:
+    """`InitializeMint` -> `initialize_mint` (how variants map to instruction names)."""
+    return re.sub(r"(?<!^)(?=[A-Z])", "_", name).lower()
+
+
+def variant_args(payload: str) -> list[Argument]:
+    """Arguments carried by an enum variant payload (`{ a: u64 }` or `(u64, u8)`)."""
+    payload = payload.strip()
+    if payload.startswith("{"):
+        return [Argument(name=n, ty=t) for n, t, _ in parse_named_fields(payload[1:-1])]
+    if payload.startswith("("):
+        return [Argument(name=f"arg{i}", ty=t) for i, t in enumerate(split_top_level(payload[1:-1]))]
+    return []
+
+
+# Calls that never name a user-defined instruction handler
+NON_HANDLER_CALLS = {
+    "Ok", "Err", "Some", "msg", "try_from_slice", "unpack", "from", "into",
+    "map_err", "ok_or", "iter", "next_account_info", "deserialize", "unwrap",
+    "clone", "key", "to_account_info", "as_ref", "len", "new",
+}
+
+
+def called_functions(body: str) -> list[tuple[str, str]]:
+    """Free-function and path calls in ``body`` as (path, name), in order.
+
+    Method calls (``x.foo()``) and macros (``foo!()``) are excluded; ``path``
+    holds any leading ``a::b::`` segments (without the trailing ``::``).
+    """
+    calls: list[tuple[str, str]] = []
+    for m in re.finditer(r"(?<![\w.!])((?:[A-Za-z_]\w*\s*::\s*)*)([a-z_]\w*)\s*\(", body):
+        before = body[:m.start()].rstrip()
+        if before.endswith("."):
+            continue
+        name = m.group(2)
+        if name in NON_HANDLER_CALLS or name in ("if", "while", "match", "return", "for"):
+            continue
+        path = re.sub(r"\s+", "", m.group(1)).rstrip(":")
+        if (path, name) not in calls:
+            calls.append((path, name))
+    return calls
+
+
+_CPI_CALL_RE = re.compile(
+    r"\b(?P<callee>invoke_signed_unchecked|invoke_unchecked|invoke_signed|invoke"
+    r"|CpiContext\s*::\s*new_with_signer|CpiContext\s*::\s*new)\s*\("
+)
+
+
+def find_cpi_calls(body: str, base_line: int = 1) -> list[CallSite]:
+    """Locate cross-program invocations in a handler body.
+
+    Args:
+        body: Comment-stripped handler source
+        base_line: Line number of the first character of ``body``
+
+    Returns:
+        CallSite entries whose ``target`` is the program expression when it
+        can be recovered (the CpiContext program, or the first argument of
+        an instruction builder passed to ``invoke``).
+    """
+    calls: list[CallSite] = []
+    for m in _CPI_CALL_RE.finditer(body):
+        callee = re.sub(r"\s+", "", m.group("callee"))
+        open_idx = m.end() - 1
+        close_idx = find_matching(body, open_idx)
+        args = split_top_level(body[open_idx + 1:close_idx])
+        kind = {
+            "CpiContext::new": "cpi",
+            "CpiContext::new_with_signer": "cpi_signed",
+        }.get(callee, callee.replace("_unchecked", ""))
+        target = args[0] if args else ""
+        if kind in ("invoke", "invoke_signed"):
+            target = _instruction_program(body, target)
+        calls.append(
+            CallSite(
+                kind=kind,
+                target=" ".join(target.split()),
+                line=base_line + body.count("\n", 0, m.start()),
+                text=" ".join(body[m.start():close_idx + 1].split())[:200],
+            )
+        )
+    return calls
+
+
+def _instruction_program(body: str, ix_expr: str) -> str:
+    """Best-effort recovery of the program id used to build an instruction."""
+    expr = ix_expr.lstrip("&").strip()
+    # Inline builder: spl_token::instruction::transfer(token_program.key, ...)
+    m = re.match(r"[\w:]+\s*\((.*)\)\s*(?:\?|\.unwrap\(\))?$", expr, re.DOTALL)
+    if m:
+        inner = split_top_level(m.group(1))
+        if inner:
+            return inner[0].lstrip("&")
+    # Named instruction variable: look for `let ix = builder(program, ...)`
+    name = re.match(r"[A-Za-z_]\w*", expr)
+    if name:
+        let = re.search(
+            rf"let\s+(?:mut\s+)?{re.escape(name.group(0))}\s*(?::[^=]+)?=\s*([\w:]+)\s*\(", body
+        )
+        if let:
+            open_idx = let.end() - 1
+            inner = split_top_level(body[open_idx + 1:find_matching(body, open_idx)])
+            if inner:
+                return inner[0].lstrip("&")
+        # Instruction { program_id: X, .. }
+        lit = re.search(
+            rf"let\s+(?:mut\s+)?{re.escape(name.group(0))}\s*(?::[^=]+)?=\s*Instruction\s*\{{[^}}]*program_id\s*:\s*([^,}}]+)",
+            body,
+        )
+        if lit:
+            return lit.group(1).strip().lstrip("&")
+    return expr
@@ -0,0 +1,358 @@
+"""
+Seahorse (Python-authored Solana programs) front-end.
+
+Seahorse compiles `programs_py/<name>.py` into an Anchor crate under
+`programs/<name>/`. We analyze that generated Rust with the Anchor
+front-end, then build a line map back to the Python source so findings
+point auditors at the code the team actually wrote.
+
+Seahorse does not emit source maps, so the mapping is reconstructed:
+- instruction handlers are matched by name (`def withdraw` ↔ `withdraw_handler`)
+- accounts struct fields are matched to the `@instruction` parameters
+- `Account` subclasses are matched to the generated account structs
+- statements inside a handler are aligned in order by shared identifiers
+  and string literals (assert messages survive as `panic!` text)
+"""
+
+import ast
+import re
+import shutil
+import subprocess
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+from .anchor import AnchorFrontend
+from .model import Program
+
+# Generated-code noise that carries no signal for alignment
+_RUST_NOISE = {
+    "let", "mut", "borrow", "borrow_mut", "clone", "assign", "panic", "if", "else",
+    "ok", "unwrap", "self", "return", "fn", "pub", "info", "as", "ref", "into",
+    "to_account_info", "account", "mutable", "loaded", "seahorse", "some", "none",
+}
+_PY_NOISE = {"def", "if", "else", "elif", "return", "self", "assert", "and", "or", "not", "in", "for"}
+_LOOKAHEAD = 6
+_MIN_SCORE = 0.34
+
+
+@dataclass
+class SeahorseSourceMap:
+    """Maps generated Rust locations back to the Seahorse Python source."""
+
+    python_file: str  # Relative to the Seahorse project root
+    rust_root: str  # Generated crate root
+    lines: dict[str, dict[int, int]] = field(default_factory=dict)
+
+    def lookup(self, rust_file: str, rust_line: int) -> tuple[str, int] | None:
+        """Map a generated Rust location to (python_file, python_line)."""
+        key = self._key_for(rust_file)
+        if key is None:
+            return None
+        table = self.lines[key]
+        if rust_line in table:
+            return self.python_file, table[rust_line]
+        # Fall back to the closest mapped line above (same handler region)
+        above = [ln for ln in table if ln <= rust_line]
+        if above:
+            return self.python_file, table[max(above)]
+        return None
+
+    def remap_hypothesis(self, hypothesis: dict[str, Any]) -> dict[str, Any]:
+        """Rewrite a hypothesis' source_files/affected_lines to Python locations.
+
+        The generated locations are preserved under
+        ``properties["generated_source"]``. Hypotheses that don't touch the
+        generated crate are returned unchanged.
+        """
+        props = hypothesis.setdefault("properties", {})
+        files = props.get("source_files", [])
+        lines = props.get("affected_lines", [])
+        mappable = [f for f in files if self._key_for(f) is not None]
+        if not mappable:
+            return hypothesis
+        mapped_lines = []
+        for rust_file in mappable:
+            for line in lines or []:
+                hit = self.lookup(rust_file, line)
+                if hit and hit[1] not in mapped_lines:
+                    mapped_lines.append(hit[1])
+        props["generated_source"] = {"source_files": list(files), "affected_lines": list(lines)}
+        props["source_files"] = [self.python_file] + [f for f in files if f not in mappable]
+        props["affected_lines"] = mapped_lines
+        props["source_language"] = "python"
+        return hypothesis
+
+    def _key_for(self, rust_file: str) -> str | None:
+        normalized = str(rust_file).replace("\\", "/")
+        for key in self.lines:
+            if normalized == key or normalized.endswith("/" + key):
+                return key
+        return None
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "python_file": self.python_file,
+            "rust_root": self.rust_root,
+            "lines": {f: {str(k): v for k, v in t.items()} for f, t in self.lines.items()},
+        }
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "SeahorseSourceMap":
+        return cls(
+            python_file=data["python_file"],
+            rust_root=data["rust_root"],
+            lines={f: {int(k): v for k, v in t.items()} for f, t in data.get("lines", {}).items()},
+        )
+
+    @classmethod
+    def from_program(cls, program: Program) -> "SeahorseSourceMap | None":
+        data = program.metadata.get("source_map")
+        return cls.from_dict(data) if data else None
+
+
+@dataclass
+class _PyInstruction:
+    name: str
+    line: int
+    end_line: int
+    params: dict[str, int]  # param name -> line
+    statements: list[tuple[int, str]]
+
+
+@dataclass
+class _PyAccount:
+    name: str
+    line: int
+    fields: dict[str, int]
+
+
+class SeahorseFrontend:
+    """Loads Seahorse projects through their generated Anchor code."""
+
+    def __init__(self, build: bool = False, timeout: int = 300):
+        """Initialize front-end.
+
+        Args:
+            build: Run `seahorse build` when generated Rust is missing
+            timeout: Maximum seconds to wait for `seahorse build`
+        """
+        self.build = build
+        self.timeout = timeout
+        self.anchor = AnchorFrontend()
+
+    @staticmethod
+    def is_seahorse_project(path: Path) -> bool:
+        """True if ``path`` has a `programs_py/` dir with Seahorse sources."""
+        py_dir = Path(path) / "programs_py"
+        if not py_dir.is_dir():
+            return False
+        return any("seahorse" in f.read_text(errors="replace") for f in py_dir.glob("*.py"))
+
+    def is_available(self) -> tuple[bool, str]:
+        """Check if the Seahorse compiler is installed.
+
+        Returns:
+            Tuple of (available, version_or_error)
+        """
+        if not shutil.which("seahorse"):
+            return False, "seahorse not found in PATH"
+        try:
+            result = subprocess.run(["seahorse", "--version"], capture_output=True, text=True, timeout=10)
+            if result.returncode == 0:
+                return True, result.stdout.strip()
+            return False, result.stderr.strip()
+        except subprocess.TimeoutExpired:
+            return False, "timeout checking seahorse version"
+        except Exception as e:
+            return False, str(e)
+
+    def python_sources(self, project: Path) -> list[Path]:
+        return sorted(
+            p for p in (Path(project) / "programs_py").glob("*.py")
+            if "seahorse" in p.read_text(errors="replace") and p.name != "prelude.py"
+        )
+
+    def load(self, project: Path) -> list[Program]:
+        """Load every Seahorse program in a project.
+
+        Programs whose generated crate is missing (and cannot be built) are
+        returned with no instructions and ``metadata["error"]`` set.
+        """
+        project = Path(project)
+        programs: list[Program] = []
+        for py_file in self.python_sources(project):
+            name = py_file.stem
+            crate = project / "programs" / name
+            if not (crate / "src").is_dir() and self.build:
+                self._run_build(project)
+            rel_py = str(py_file.relative_to(project))
+            if not (crate / "src").is_dir():
+                programs.append(
+                    Program(
+                        name=name,
+                        chain="solana",
+                        framework="seahorse",
+                        root=str(crate),
+                        source_files=[rel_py],
+                        metadata={"python_source": rel_py, "error": "generated Rust not found; run `seahorse build`"},
+                    )
+                )
+                continue
+            program = self.anchor.load_program(crate)
+            program.framework = "seahorse"
+            program.metadata["python_source"] = rel_py
+            source_map = self._build_source_map(program, py_file, rel_py)
+            program.metadata["source_map"] = source_map.to_dict()
+            programs.append(program)
+        return programs
+
+    def _run_build(self, project: Path) -> None:
+        available, _ = self.is_available()
+        if not available:
+            return
+        try:
+            subprocess.run(["seahorse", "build"], cwd=project, capture_output=True, text=True, timeout=self.timeout)
+        except (subprocess.TimeoutExpired, OSError):
+            pass
+
+    # ------------------------------------------------------------------
+    # Source map construction
+    # ------------------------------------------------------------------
+
+    def _build_source_map(self, program: Program, py_file: Path, rel_py: str) -> SeahorseSourceMap:
+        py_source = py_file.read_text(errors="replace")
+        py_instructions, py_accounts = self._parse_python(py_source)
+        crate = Path(program.root)
+        source_map = SeahorseSourceMap(python_file=rel_py, rust_root=str(crate))
+        rust_cache: dict[str, list[str]] = {}
+
+        def rust_lines(relpath: str) -> list[str]:
+            if relpath not in rust_cache:
+                rust_cache[relpath] = (crate / relpath).read_text(errors="replace").split("\n")
+            return rust_cache[relpath]
+
+        for ix in program.instructions:
+            py_ix = py_instructions.get(ix.name)
+            if py_ix is None:
+                continue
+            ix.properties["python_file"] = rel_py
+            ix.properties["python_line"] = py_ix.line
+            # The #[program] wrapper maps to the `def` line
+            table = source_map.lines.setdefault(ix.file, {})
+            table[ix.line] = py_ix.line
+            # Accounts struct fields map to the matching parameters
+            accounts_file = ix.properties.get("accounts_file")
+            if accounts_file:
+                acc_table = source_map.lines.setdefault(accounts_file, {})
+                acc_table.setdefault(ix.properties.get("accounts_line", 0), py_ix.line)
+                for acc in ix.accounts:
+                    acc_table[acc.line] = py_ix.params.get(acc.name, py_ix.line)
+            # Handler body is aligned statement by statement
+            body_file = ix.source_file
+            lines = rust_lines(body_file)
+            start = ix.body_line
+            end = start + ix.body.count("\n")
+            region = [(n, lines[n - 1]) for n in range(start, min(end, len(lines)) + 1)]
+            body_table = source_map.lines.setdefault(body_file, {})
+            body_table.update(_align(region, py_ix.line, py_ix.statements))
+
+        for at in program.account_types:
+            py_acc = py_accounts.get(at.name)
+            if py_acc is None:
+                continue
+            at.properties["python_line"] = py_acc.line
+            table = source_map.lines.setdefault(at.file, {})
+            table[at.line] = py_acc.line
+            lines = rust_lines(at.file)
+            for offset in range(1, len(at.fields) + 4):
+                n = at.line + offset
+                if n > len(lines):
+                    break
+                fm = re.match(r"\s*(?:pub\s+)?([A-Za-z_]\w*)\s*:", lines[n - 1])
+                if fm and fm.group(1) in py_acc.fields:
+                    table[n] = py_acc.fields[fm.group(1)]
+        return source_map
+
+    def _parse_python(self, source: str) -> tuple[dict[str, _PyInstruction], dict[str, _PyAccount]]:
+        tree = ast.parse(source)
+        src_lines = source.split("\n")
+        instructions: dict[str, _PyInstruction] = {}
+        accounts: dict[str, _PyAccount] = {}
+        for node in tree.body:
+            if isinstance(node, ast.FunctionDef) and any(_decorator_name(d) == "instruction" for d in node.decorator_list):
+                end = getattr(node, "end_lineno", node.lineno) or node.lineno
+                statements = [
+                    (n, src_lines[n - 1])
+                    for n in range(node.body[0].lineno, end + 1)
+                    if src_lines[n - 1].strip() and not src_lines[n - 1].strip().startswith("#")
+                ]
+                instructions[node.name] = _PyInstruction(
+                    name=node.name,
+                    line=node.lineno,
+                    end_line=end,
+                    params={a.arg: a.lineno for a in node.args.args},
+                    statements=statements,
+                )
+            elif isinstance(node, ast.ClassDef) and any(_base_name(b) == "Account" for b in node.bases):
+                fields = {
+                    stmt.target.id: stmt.lineno
+                    for stmt in node.body
+                    if isinstance(stmt, ast.AnnAssign) and isinstance(stmt.target, ast.Name)
+                }
+                accounts[node.name] = _PyAccount(name=node.name, line=node.lineno, fields=fields)
+        return instructions, accounts
+
+
+def _decorator_name(node: ast.expr) -> str:
+    if isinstance(node, ast.Call):
+        node = node.func
+    if isinstance(node, ast.Attribute):
+        return node.attr
+    return node.id if isinstance(node, ast.Name) else ""
+
+
+def _base_name(node: ast.expr) -> str:
+    if isinstance(node, ast.Attribute):
+        return node.attr
+    return node.id if isinstance(node, ast.Name) else ""
+
+
+def _tokens(text: str, noise: set[str]) -> set[str]:
+    words = set()
+    for literal in re.findall(r"[\"']([^\"']{3,})[\"']", text):
+        words.add("str:" + literal.strip().lower())
+    for word in re.findall(r"[A-Za-z_]\w*", text):
+        lw = word.lower()
+        if lw not in noise and len(lw) > 1:
+            words.add(lw)
+    return words
+
+
+def _align(rust: list[tuple[int, str]], def_line: int, python: list[tuple[int, str]]) -> dict[int, int]:
+    """Monotonic alignment of generated Rust lines onto Python statement lines."""
+    mapping: dict[int, int] = {}
+    py_tokens = [(n, _tokens(text, _PY_NOISE)) for n, text in python]
+    cursor = 0
+    last = def_line
+    for r_line, r_text in rust:
+        rt = _tokens(r_text, _RUST_NOISE)
+        if rt:
+            best, best_score = None, 0.0
+            for j in range(cursor, min(len(py_tokens), cursor + _LOOKAHEAD)):
+                pt = py_tokens[j][1]
+                if not pt:
+                    continue
+                score = len(rt & pt) / len(rt | pt)
+                if score > best_score:
+                    best, best_score = j, score
+            if best is not None and best_score >= _MIN_SCORE:
+                cursor = best
+                last = py_tokens[best][0]
+        mapping[r_line] = last
+    return mapping
+
+
+def load_seahorse_programs(path: Path, build: bool = False) -> list[Program]:
+    """Convenience wrapper around SeahorseFrontend.load()."""
+    return SeahorseFrontend(build=build).load(Path(path))
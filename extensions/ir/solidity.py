@@ -0,0 +1,487 @@
+"""
+Solidity front-end.
+
+Lowers solc's standard-json AST into the IR. Each deployable contract
+becomes a Program whose instructions are its public/external functions
+(including those inherited through the C3 linearization, most-derived
+definition wins), and whose storage layout is a single AccountType.
+
+Input can be:
+- a project directory (Foundry/Hardhat or loose `.sol` files), compiled
+  with `solc --standard-json`
+- a standard-json output file already produced by solc
+- a Hardhat/Foundry build-info file (`{"input": ..., "output": ...}`)
+"""
+
+import json
+import os
+import re
+import shutil
+import subprocess
+from pathlib import Path
+from typing import Any
+
+from .model import Argument, AccountType, CallSite, Instruction, Program
+from .workspace import SKIP_DIRS
+
+# Directories holding vendored dependencies rather than audited code
+DEPENDENCY_DIRS = {"lib", "node_modules"}
+# Directories holding tests and scripts, not deployed contracts
+NON_PROGRAM_DIRS = {"test", "tests", "script", "scripts"}
+# Low-level address members that transfer control
+_LOW_LEVEL_CALLS = {"call", "delegatecall", "staticcall", "transfer", "send"}
+_SRC_RE = re.compile(r"^(\d+):(\d+):(-?\d+)$")
+
+
+class SolidityFrontend:
+    """Parses Solidity projects into IR Programs via the solc AST."""
+
+    def __init__(self, solc: str | None = None, timeout: int = 300, include_dependencies: bool = False):
+        """Initialize front-end.
+
+        Args:
+            solc: Path to a solc binary (defaults to $SOLC, PATH, then svm installs)
+            timeout: Maximum seconds to wait for solc
+            include_dependencies: Also lower contracts under lib/ and node_modules/
+        """
+        self.solc = solc
+        self.timeout = timeout
+        self.include_dependencies = include_dependencies
+        self.errors: list[str] = []
+
+    # ------------------------------------------------------------------
+    # Discovery and compilation
+    # ------------------------------------------------------------------
+
+    def _find_solc(self) -> str | None:
+        if self.solc:
+            return self.solc
+        env = os.environ.get("SOLC")
+        if env:
+            return env
+        if shutil.which("solc"):
+            return "solc"
+        # Foundry's svm keeps versions under ~/.svm/<version>/solc-<version>
+        svm = Path.home() / ".svm"
+        if svm.is_dir():
+            binaries = sorted(svm.glob("*/solc-*"), key=lambda p: _version_key(p.parent.name))
+            if binaries:
+                return str(binaries[-1])
+        return None
+
+    def is_available(self) -> tuple[bool, str]:
+        """Check if solc is installed.
+
+        Returns:
+            Tuple of (available, version_or_error)
+        """
+        solc = self._find_solc()
+        if not solc:
+            return False, "solc not found in PATH, $SOLC, or ~/.svm"
+        try:
+            result = subprocess.run([solc, "--version"], capture_output=True, text=True, timeout=10)
+            if result.returncode == 0:
+                m = re.search(r"Version:\s*(\S+)", result.stdout)
+                return True, m.group(1) if m else result.stdout.strip()
+            return False, result.stderr.strip()
+        except subprocess.TimeoutExpired:
+            return False, "timeout checking solc version"
+        except Exception as e:
+            return False, str(e)
+
+    @staticmethod
+    def find_project_root(path: Path) -> Path:
+        """Nearest ancestor with a foundry.toml or Hardhat config (else ``path``)."""
+        start = path if path.is_dir() else path.parent
+        current = start.resolve()
+        for _ in range(10):
+            for config in ("foundry.toml", "hardhat.config.js", "hardhat.config.ts"):
+                if (current / config).exists():
+                    return current
+            if current.parent == current:
+                break
+            current = current.parent
+        return start
+
+    @staticmethod
+    def has_sources(path: Path) -> bool:
+        """True if ``path`` is or contains a Solidity source or solc output file."""
+        path = Path(path)
+        if path.is_file():
+            return path.suffix in (".sol", ".json")
+        return any(not _is_excluded(p.relative_to(path)) for p in path.rglob("*.sol"))
+
+    def source_files(self, root: Path) -> list[str]:
+        """Project sources to compile, relative to ``root``.
+
+        Uses foundry's `src` directory (or Hardhat's `contracts/`) when
+        present; dependencies are pulled in through imports.
+        """
+        src_dir = root / _foundry_setting(root, "src", "src")
+        if not src_dir.is_dir():
+            src_dir = root / "contracts"
+        if not src_dir.is_dir():
+            src_dir = root
+        files = []
+        for sol in sorted(src_dir.rglob("*.sol")):
+            rel = sol.relative_to(root)
+            if not _is_excluded(rel):
+                files.append(rel.as_posix())
+        return files
+
+    def remappings(self, root: Path) -> list[str]:
+        """Import remappings from remappings.txt and foundry.toml."""
+        remaps: list[str] = []
+        txt = root / "remappings.txt"
+        if txt.exists():
+            remaps.extend(line.strip() for line in txt.read_text().splitlines() if "=" in line)
+        for remap in _foundry_setting(root, "remappings", []):
+            if remap not in remaps:
+                remaps.append(remap)
+        return remaps
+
+    def compile(self, root: Path, files: list[str] | None = None) -> dict[str, Any]:
+        """Run `solc --standard-json` over a project and return its output.
+
+        Raises:
+            RuntimeError: solc is missing, times out, or reports errors
+        """
+        solc = self._find_solc()
+        if not solc:
+            raise RuntimeError("solc not found in PATH, $SOLC, or ~/.svm")
+        files = files if files is not None else self.source_files(root)
+        std_input = {
+            "language": "Solidity",
+            "sources": {rel: {"content": (root / rel).read_text(errors="replace")} for rel in files},
+            "settings": {
+                "remappings": self.remappings(root),
+                "outputSelection": {"*": {"": ["ast"]}},
+            },
+        }
+        cmd = [solc, "--standard-json", "--base-path", str(root), "--allow-paths", str(root)]
+        for dep in sorted(DEPENDENCY_DIRS):
+            if (root / dep).is_dir():
+                cmd.extend(["--include-path", str(root / dep)])
+        try:
+            result = subprocess.run(
+                cmd,
+                input=json.dumps(std_input),
+                capture_output=True,
+                text=True,
+                timeout=self.timeout,
+                cwd=root,
+            )
+        except subprocess.TimeoutExpired:
+            raise RuntimeError(f"solc timed out after {self.timeout}s")
+        except OSError as e:
+            raise RuntimeError(f"failed to run solc: {e}")
+        try:
+            output = json.loads(result.stdout)
+        except json.JSONDecodeError:
+            raise RuntimeError(f"solc failed: {(result.stderr or result.stdout).strip()[:200]}")
+        errors = [e for e in output.get("errors", []) if e.get("severity") == "error"]
+        if errors:
+            first = errors[0].get("formattedMessage") or errors[0].get("message", "")
+            raise RuntimeError(f"solc reported {len(errors)} error(s): {first.strip()[:300]}")
+        return output
+
+    # ------------------------------------------------------------------
+    # Loading
+    # ------------------------------------------------------------------
+
+    def load(self, path: Path) -> list[Program]:
+        """Load every deployable contract under ``path``.
+
+        Compilation errors are collected in ``self.errors`` rather than
+        raised so that multi-chain workspaces keep loading other front-ends.
+        """
+        path = Path(path)
+        self.errors = []
+        if path.is_file() and path.suffix == ".json":
+            data = json.loads(path.read_text())
+            root = self.find_project_root(path)
+            sources = None
+            if "output" in data and "input" in data:  # build-info
+                sources = {k: v.get("content", "") for k, v in data["input"].get("sources", {}).items()}
+                data = data["output"]
+            if not isinstance(data, dict) or "sources" not in data:
+                self.errors.append(f"{path.name} is not solc standard-json output")
+                return []
+            return self.load_standard_json(data, root, sources)
+
+        root = self.find_project_root(path)
+        files = None
+        if path.is_file():
+            files = [path.resolve().relative_to(root).as_posix()]
+        try:
+            output = self.compile(root, files)
+        except RuntimeError as e:
+            self.errors.append(str(e))
+            return []
+        return self.load_standard_json(output, root)
+
+    def load_standard_json(
+        self,
+        output: dict[str, Any],
+        root: Path,
+        sources: dict[str, str] | None = None,
+    ) -> list[Program]:
+        """Lower solc standard-json output into Programs.
+
+        Args:
+            output: Parsed standard-json output (must include `sources.*.ast`)
+            root: Directory that source unit paths are relative to
+            sources: Source text by path; read from ``root`` when omitted
+        """
+        return _Lowering(output, Path(root), sources or {}, self.include_dependencies).programs()
+
+
+class _Lowering:
+    """One pass over a standard-json output."""
+
+    def __init__(self, output: dict[str, Any], root: Path, sources: dict[str, str], include_dependencies: bool):
+        self.root = root
+        self.include_dependencies = include_dependencies
+        self.units: dict[str, dict] = {}  # path -> SourceUnit AST
+        self.file_index: dict[int, str] = {}  # solc source id -> path
+        for unit_path, entry in output.get("sources", {}).items():
+            ast_root = entry.get("ast") or entry.get("legacyAST")
+            if not ast_root:
+                continue
+            self.units[unit_path] = ast_root
+            if "id" in entry:
+                self.file_index[entry["id"]] = unit_path
+        self._text: dict[str, bytes] = {p: s.encode() for p, s in sources.items()}
+        self.contracts: dict[int, tuple[str, dict]] = {}
+        for unit_path, unit in self.units.items():
+            for node in unit.get("nodes", []):
+                if node.get("nodeType") == "ContractDefinition":
+                    self.contracts[node["id"]] = (unit_path, node)
+
+    def programs(self) -> list[Program]:
+        result = []
+        for unit_path, contract in self.contracts.values():
+            if contract.get("contractKind") != "contract" or contract.get("abstract"):
+                continue
+            if not self.include_dependencies and _is_excluded(Path(unit_path)):
+                continue
+            result.append(self._program(unit_path, contract))
+        return result
+
+    # -- source text ----------------------------------------------------
+
+    def _source(self, unit_path: str) -> bytes:
+        if unit_path not in self._text:
+            candidate = self.root / unit_path
+            if not candidate.exists():
+                # Include-path imports (e.g. "@openzeppelin/...") resolve under dependency dirs
+                for dep in sorted(DEPENDENCY_DIRS):
+                    if (self.root / dep / unit_path).exists():
+                        candidate = self.root / dep / unit_path
+                        break
+            try:
+                self._text[unit_path] = candidate.read_bytes()
+            except OSError:
+                self._text[unit_path] = b""
+        return self._text[unit_path]
+
+    def _location(self, node: dict) -> tuple[str, int, int]:
+        """Resolve a node's `src` to (path, byte_start, byte_length)."""
+        m = _SRC_RE.match(node.get("src", ""))
+        if not m:
+            return "", 0, 0
+        start, length, index = (int(g) for g in m.groups())
+        return self.file_index.get(index, ""), start, length
+
+    def _line(self, node: dict) -> int:
+        unit_path, start, _ = self._location(node)
+        return self._source(unit_path)[:start].count(b"\n") + 1 if unit_path else 0
+
+    def _snippet(self, node: dict) -> str:
+        unit_path, start, length = self._location(node)
+        if not unit_path:
+            return ""
+        return self._source(unit_path)[start:start + length].decode(errors="replace")
+
+    # -- lowering -------------------------------------------------------
+
+    def _program(self, unit_path: str, contract: dict) -> Program:
+        linearized = [self.contracts[i] for i in contract.get("linearizedBaseContracts", [contract["id"]])
+                      if i in self.contracts]
+        program = Program(
+            name=contract["name"],
+            chain="evm",
+            framework="solidity",
+            root=str(self.root),
+            source_files=sorted({p for p, _ in linearized}),
+            metadata={
+                "file": unit_path,
+                "line": self._line(contract),
+                "linearization": [c["name"] for _, c in linearized],
+            },
+        )
+
+        modifiers = {}
+        for _, base in reversed(linearized):  # most-derived override wins
+            for node in base.get("nodes", []):
+                if node.get("nodeType") == "ModifierDefinition":
+                    modifiers[node["name"]] = node
+        seen: set[str] = set()
+        for base_path, base in linearized:
+            for node in base.get("nodes", []):
+                if node.get("nodeType") != "FunctionDefinition" or not node.get("implemented", True):
+                    continue
+                kind = node.get("kind", "function")
+                if kind == "function" and node.get("visibility") not in ("public", "external"):
+                    continue
+                if kind not in ("function", "constructor", "fallback", "receive"):
+                    continue
+                if kind == "constructor" and base is not contract:
+                    continue
+                signature = self._signature(node)
+                if signature in seen:
+                    continue
+                seen.add(signature)
+                ix = self._instruction(base_path, base, node, modifiers)
+                if base is not contract:
+                    ix.properties["inherited_from"] = base["name"]
+                program.instructions.append(ix)
+
+        program.account_types.append(self._storage(contract, linearized))
+        return program
+
+    def _signature(self, fn: dict) -> str:
+        name = fn.get("name") or fn.get("kind", "")
+        types = [p.get("typeDescriptions", {}).get("typeString", "") for p in _params(fn.get("parameters"))]
+        return f"{name}({','.join(types)})"
+
+    def _instruction(self, unit_path: str, contract: dict, fn: dict, modifiers: dict[str, dict]) -> Instruction:
+        body = fn.get("body") or {}
+        ix = Instruction(
+            name=fn.get("name") or fn.get("kind", ""),
+            handler=f"{contract['name']}.{fn.get('name') or fn.get('kind')}",
+            file=unit_path,
+            line=self._line(fn),
+            args=[Argument(name=p.get("name", ""), ty=_type_string(p)) for p in _params(fn.get("parameters"))],
+            body=self._snippet(body) if body else "",
+            body_line=self._line(body) if body else 0,
+            discriminator=fn.get("functionSelector"),
+            visibility=fn.get("visibility", ""),
+            calls=self._calls(body),
+            properties={
+                "kind": fn.get("kind", "function"),
+                "state_mutability": fn.get("stateMutability", ""),
+                "signature": self._signature(fn),
+            },
+        )
+        for invocation in fn.get("modifiers", []):
+            name_node = invocation.get("modifierName", {})
+            name = name_node.get("name") or name_node.get("namePath", "")
+            if invocation.get("kind") == "baseConstructorSpecifier":
+                continue
+            ix.modifiers.append(name)
+            # Modifier bodies run as part of the call; keep their calls visible too
+            if name in modifiers and modifiers[name].get("body"):
+                ix.calls.extend(self._calls(modifiers[name]["body"]))
+        return ix
+
+    def _storage(self, contract: dict, linearized: list[tuple[str, dict]]) -> AccountType:
+        fields = []
+        constants = []
+        for _, base in reversed(linearized):  # storage layout is base-first
+            for node in base.get("nodes", []):
+                if node.get("nodeType") != "VariableDeclaration" or not node.get("stateVariable", True):
+                    continue
+                if node.get("constant") or node.get("mutability") in ("constant", "immutable"):
+                    constants.append(node["name"])
+                    continue
+                fields.append(Argument(name=node["name"], ty=_type_string(node)))
+        unit_path = self.contracts[contract["id"]][0]
+        return AccountType(
+            name=contract["name"],
+            file=unit_path,
+            line=self._line(contract),
+            fields=fields,
+            properties={"kind": "storage", "constants": constants},
+        )
+
+    def _calls(self, body: dict) -> list[CallSite]:
+        calls = []
+        for node in _walk(body):
+            if node.get("nodeType") != "FunctionCall":
+                continue
+            callee = node.get("expression", {})
+            if callee.get("nodeType") == "FunctionCallOptions":  # x.call{value: v}(...)
+                callee = callee.get("expression", {})
+            if callee.get("nodeType") != "MemberAccess":
+                continue
+            member = callee.get("memberName", "")
+            base = callee.get("expression", {})
+            base_type = base.get("typeDescriptions", {}).get("typeString", "")
+            if member in _LOW_LEVEL_CALLS and base_type.startswith("address"):
+                kind = member
+            elif " external" in callee.get("typeDescriptions", {}).get("typeString", ""):
+                kind = "external"
+            else:
+                continue
+            calls.append(CallSite(kind=kind, target=self._snippet(base), line=self._line(node), text=self._snippet(node)))
+        return sorted(calls, key=lambda c: c.line)
+
+
+def _params(parameter_list: dict | None) -> list[dict]:
+    return (parameter_list or {}).get("parameters", [])
+
+
+def _type_string(decl: dict) -> str:
+    type_string = decl.get("typeDescriptions", {}).get("typeString", "")
+    return re.sub(r"\s+(?:storage|memory|calldata)(?:\s+(?:ref|pointer))?$", "", type_string)
+
+
+def _walk(node: Any):
+    """Depth-first iteration over AST dict nodes."""
+    stack = [node]
+    while stack:
+        current = stack.pop()
+        if isinstance(current, dict):
+            if "nodeType" in current:
+                yield current
+            stack.extend(reversed(list(current.values())))
+        elif isinstance(current, list):
+            stack.extend(reversed(current))
+
+
+def _is_excluded(rel: Path) -> bool:
+    parts = rel.parts
+    if any(part in SKIP_DIRS or part in DEPENDENCY_DIRS for part in parts):
+        return True
+    if parts and parts[0].startswith("@"):  # Include-path import (npm scope)
+        return True
+    if parts and parts[0] in NON_PROGRAM_DIRS:
+        return True
+    return rel.name.endswith((".t.sol", ".s.sol"))
+
+
+def _foundry_setting(root: Path, key: str, default: Any) -> Any:
+    """Read a string or string-array key from foundry.toml's default profile."""
+    config = root / "foundry.toml"
+    if not config.exists():
+        return default
+    text = config.read_text(errors="replace")
+    section = re.search(r"(?ms)^\[profile\.default\]\s*$(.*?)(?=^\[|\Z)", text)
+    body = section.group(1) if section else text
+    m = re.search(rf'(?m)^\s*{re.escape(key)}\s*=\s*(\[[^\]]*\]|"[^"]*"|\'[^\']*\')', body)
+    if not m:
+        return default
+    value = m.group(1)
+    if value.startswith("["):
+        return re.findall(r"[\"']([^\"']*)[\"']", value)
+    return value[1:-1]
+
+
+def _version_key(version: str) -> tuple[int, ...]:
+    return tuple(int(p) for p in re.findall(r"\d+", version))
+
+
+def load_solidity_programs(path: Path) -> list[Program]:
+    """Convenience wrapper around SolidityFrontend.load()."""
+    return SolidityFrontend().load(Path(path))
@@ -0,0 +1,317 @@
+"""
+Transaction size and account-count feasibility.
+
+A Solana transaction must fit in one 1232-byte packet and may lock at most
+64 accounts. Instructions that take many accounts (or large fixed
+arguments) can be impossible to call in a legacy transaction; they then
+need a v0 transaction with an address lookup table (ALT), where every
+non-signer account other than the invoked program costs one index byte
+instead of a 32-byte key. This matters in both directions for an audit:
+a handler that is only reachable through an ALT is untested by most
+client code, and a finding whose exploit needs an ALT needs a harness to
+build one.
+
+For every Solana instruction we compute the minimum footprint of a
+transaction carrying only that instruction (one signature per signer
+account, a fee payer, the instruction's accounts assumed distinct, and
+the smallest Borsh encoding of its arguments) and classify it as
+``ok``, ``needs-alt`` or ``infeasible``. The account lock limit is a
+feature gate's (64, or 128 once raised); callers targeting a cluster pass
+its value.
+"""
+
+import re
+from dataclasses import asdict, dataclass, field
+from typing import Any
+
+from .model import Instruction, Program
+
+PACKET_DATA_SIZE = 1232
+MAX_TX_ACCOUNT_LOCKS = 64
+# Addresses a single lookup table can hold
+MAX_ALT_ADDRESSES = 256
+SIGNATURE_SIZE = 64
+PUBKEY_SIZE = 32
+ANCHOR_DISCRIMINATOR_SIZE = 8
+# Native programs conventionally dispatch on a one-byte Borsh enum tag
+NATIVE_TAG_SIZE = 1
+
+_BORSH_SIZES = {
+    "u8": 1, "i8": 1, "bool": 1,
+    "u16": 2, "i16": 2,
+    "u32": 4, "i32": 4, "f32": 4,
+    "u64": 8, "i64": 8, "f64": 8,
+    "u128": 16, "i128": 16,
+    "Pubkey": 32,
+}
+# Length-prefixed types: the minimum encoding is the empty value
+_LENGTH_PREFIXED = {"Vec", "String", "VecDeque", "BTreeMap", "HashMap", "BTreeSet", "HashSet"}
+
+
+@dataclass
+class InstructionFootprint:
+    """Minimum transaction footprint of one instruction."""
+
+    program: str
+    instruction: str
+    file: str
+    line: int
+    accounts: int  # Account metas passed to the instruction
+    unique_keys: int  # Distinct keys in the message, including fee payer and program id
+    signers: int
+    data_bytes: int
+    data_exact: bool  # False when an argument's size could not be resolved
+    legacy_bytes: int
+    v0_bytes: int  # With every eligible account moved into lookup tables
+    lookup_tables: int
+    uses_remaining_accounts: bool = False
+    unresolved_args: list[str] = field(default_factory=list)
+    max_account_locks: int = MAX_TX_ACCOUNT_LOCKS
+
+    @property
+    def fits_legacy(self) -> bool:
+        return self.legacy_bytes <= PACKET_DATA_SIZE
+
+    @property
+    def fits_v0(self) -> bool:
+        return self.v0_bytes <= PACKET_DATA_SIZE
+
+    @property
+    def status(self) -> str:
+        if self.unique_keys > self.max_account_locks or not self.fits_v0:
+            return "infeasible"
+        return "ok" if self.fits_legacy else "needs-alt"
+
+    def to_dict(self) -> dict[str, Any]:
+        data = asdict(self)
+        data["status"] = self.status
+        return data
+
+
+def compact_u16_len(value: int) -> int:
+    """Bytes used by Solana's compact-u16 (shortvec) encoding of ``value``."""
+    if value < 0x80:
+        return 1
+    return 2 if value < 0x4000 else 3
+
+
+def legacy_tx_size(signers: int, keys: int, metas: int, data: int) -> int:
+    """Size of a legacy transaction with one instruction."""
+    return (
+        compact_u16_len(signers) + signers * SIGNATURE_SIZE
+        + 3  # message header
+        + compact_u16_len(keys) + keys * PUBKEY_SIZE
+        + PUBKEY_SIZE  # recent blockhash
+        + compact_u16_len(1)
+        + 1 + compact_u16_len(metas) + metas + compact_u16_len(data) + data
+    )
+
+
+def v0_tx_size(signers: int, static_keys: int, lookup_keys: int, metas: int, data: int) -> tuple[int, int]:
+    """(size, table count) of a v0 transaction with one instruction and ``lookup_keys`` in ALTs.
+
+    Lookup keys are counted as one writable-or-readonly index list per table;
+    splitting a table's entries between the two lists adds at most one byte.
+    """
+    tables = -(-lookup_keys // MAX_ALT_ADDRESSES) if lookup_keys else 0
+    size = 1 + legacy_tx_size(signers, static_keys, metas, data) + compact_u16_len(tables)
+    remaining = lookup_keys
+    for _ in range(tables):
+        entries = min(remaining, MAX_ALT_ADDRESSES)
+        size += PUBKEY_SIZE + compact_u16_len(entries) + entries + compact_u16_len(0)
+        remaining -= entries
+    return size, tables
+
+
+def borsh_min_size(ty: str, program: Program | None = None, depth: int = 0) -> int | None:
+    """Smallest Borsh encoding of type text ``ty``, or None when unknown."""
+    ty = " ".join(ty.split()).strip()
+    if depth > 8 or not ty:
+        return None
+    if ty.startswith("[") and ty.endswith("]"):
+        parts = ty[1:-1].rsplit(";", 1)
+        n = re.sub(r"_|usize$", "", parts[1].strip()) if len(parts) == 2 else ""
+        elem = borsh_min_size(parts[0], program, depth + 1) if len(parts) == 2 else None
+        return elem * int(n) if elem is not None and n.isdigit() else None
+    if ty.startswith("(") and ty.endswith(")"):
+        sizes = [borsh_min_size(p, program, depth + 1) for p in ty[1:-1].split(",") if p.strip()]
+        return None if None in sizes else sum(sizes)
+    m = re.match(r"(?:[\w:]+::)?(?P<base>\w+)", ty)
+    base = m.group("base") if m else ty
+    if base in _BORSH_SIZES:
+        return _BORSH_SIZES[base]
+    if base in _LENGTH_PREFIXED:
+        return 4
+    if base == "Option":
+        return 1
+    if program is not None:
+        at = program.account_type(base)
+        if at is not None and at.fields:
+            sizes = [borsh_min_size(f.ty, program, depth + 1) for f in at.fields]
+            return None if None in sizes else sum(sizes)
+    return None
+
+
+def _is_optional(ty: str) -> bool:
+    return ty.replace(" ", "").startswith("Option<")
+
+
+def instruction_footprint(program: Program, ix: Instruction,
+                          max_account_locks: int = MAX_TX_ACCOUNT_LOCKS) -> InstructionFootprint:
+    """Minimum transaction footprint of calling ``ix`` on its own."""
+    metas = len(ix.accounts)
+    # Omitted optional accounts are passed as the program id, which is already a key
+    required = [acc for acc in ix.accounts if not _is_optional(acc.ty)]
+    signers = sum(1 for acc in required if acc.is_signer)
+    if signers == 0:
+        signers = 1  # fee payer
+        keys = len(required) + 1
+    else:
+        keys = len(required)
+    keys += 1  # invoked program id
+
+    data = ANCHOR_DISCRIMINATOR_SIZE if program.framework in ("anchor", "seahorse") else NATIVE_TAG_SIZE
+    unresolved = []
+    for arg in ix.args:
+        size = borsh_min_size(arg.ty, program)
+        if size is None:
+            unresolved.append(arg.name)
+        else:
+            data += size
+
+    legacy = legacy_tx_size(signers, keys, metas, data)
+    # Signers and the program id stay static; everything else can live in a table
+    static = signers + 1
+    v0, tables = v0_tx_size(signers, static, keys - static, metas, data)
+    return InstructionFootprint(
+        program=program.name,
+        instruction=ix.name,
+        file=ix.source_file,
+        line=ix.line,
+        accounts=metas,
+        unique_keys=keys,
+        signers=signers,
+        data_bytes=data,
+        data_exact=not unresolved,
+        legacy_bytes=legacy,
+        v0_bytes=v0,
+        lookup_tables=tables,
+        uses_remaining_accounts="remaining_accounts" in ix.body,
+        unresolved_args=unresolved,
+        max_account_locks=max_account_locks,
+    )
+
+
+def analyze_transactions(programs: list[Program],
+                         max_account_locks: int = MAX_TX_ACCOUNT_LOCKS) -> list[InstructionFootprint]:
+    """Footprints of every instruction of every Solana program."""
+    return [
+        instruction_footprint(program, ix, max_account_locks)
+        for program in programs if program.chain == "solana"
+        for ix in program.instructions
+    ]
+
+
+def alt_harness(program: Program, ix: Instruction) -> str:
+    """TypeScript helper that builds an ALT for ``ix``'s accounts and sends it as v0.
+
+    The account order follows the instruction's accounts struct; signers are
+    left out of the table because their keys must be static.
+    """
+    lookup = [acc.name for acc in ix.accounts if not acc.is_signer and not _is_optional(acc.ty)]
+    signer_names = [acc.name for acc in ix.accounts if acc.is_signer]
+    listed = "\n".join(f"//   - {name}" for name in lookup) or "//   (none)"
+    fn_name = "invoke" + "".join(part.capitalize() for part in ix.name.split("_"))
+    return f"""// Address lookup table harness for `{program.name}::{ix.name}`.
+// Generated by Baskerville: this instruction does not fit in a legacy
+// transaction, so the PoC must send it as a v0 transaction with an ALT.
+//
+// Accounts to place in the table (signers stay static: {", ".join(signer_names) or "fee payer"}):
+{listed}
+
+import {{
+  AddressLookupTableAccount,
+  AddressLookupTableProgram,
+  Connection,
+  Keypair,
+  PublicKey,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
+}} from "@solana/web3.js";
+
+// extendLookupTable instructions must themselves fit in a legacy transaction
+const EXTEND_CHUNK = 20;
+
+export async function buildLookupTable(
+  connection: Connection,
+  payer: Keypair,
+  addresses: PublicKey[],
+): Promise<AddressLookupTableAccount> {{
+  const recentSlot = await connection.getSlot("finalized");
+  const [createIx, table] = AddressLookupTableProgram.createLookupTable({{
+    authority: payer.publicKey,
+    payer: payer.publicKey,
+    recentSlot,
+  }});
+  await sendV0(connection, payer, [createIx], []);
+  for (let i = 0; i < addresses.length; i += EXTEND_CHUNK) {{
+    const extendIx = AddressLookupTableProgram.extendLookupTable({{
+      lookupTable: table,
+      authority: payer.publicKey,
+      payer: payer.publicKey,
+      addresses: addresses.slice(i, i + EXTEND_CHUNK),
+    }});
+    await sendV0(connection, payer, [extendIx], []);
+  }}
+  // Entries become usable one slot after they are added
+  const extendedAt = await connection.getSlot();
+  while ((await connection.getSlot()) <= extendedAt) {{
+    await new Promise((resolve) => setTimeout(resolve, 400));
+  }}
+  const account = (await connection.getAddressLookupTable(table)).value;
+  if (!account) throw new Error(`lookup table ${{table.toBase58()}} not found`);
+  return account;
+}}
+
+export async function sendV0(
+  connection: Connection,
+  payer: Keypair,
+  instructions: TransactionInstruction[],
+  tables: AddressLookupTableAccount[],
+  signers: Keypair[] = [],
+): Promise<string> {{
+  const {{ blockhash, lastValidBlockHeight }} = await connection.getLatestBlockhash();
+  const message = new TransactionMessage({{
+    payerKey: payer.publicKey,
+    recentBlockhash: blockhash,
+    instructions,
+  }}).compileToV0Message(tables);
+  const tx = new VersionedTransaction(message);
+  tx.sign([payer, ...signers]);
+  const signature = await connection.sendTransaction(tx);
+  await connection.confirmTransaction({{ signature, blockhash, lastValidBlockHeight }});
+  return signature;
+}}
+
+// Build the `{ix.name}` instruction (e.g. `await program.methods.{_camel(ix.name)}(...).accounts({{...}}).instruction()`),
+// then pass it here together with the non-signer account keys listed above.
+export async function {fn_name}(
+  connection: Connection,
+  payer: Keypair,
+  instruction: TransactionInstruction,
+  signers: Keypair[] = [],
+): Promise<string> {{
+  const signerKeys = new Set([payer, ...signers].map((k) => k.publicKey.toBase58()));
+  const addresses = instruction.keys
+    .map((meta) => meta.pubkey)
+    .filter((key) => !signerKeys.has(key.toBase58()));
+  const table = await buildLookupTable(connection, payer, addresses);
+  return sendV0(connection, payer, [instruction], [table], signers);
+}}
+"""
+
+
+def _camel(name: str) -> str:
+    head, *rest = name.split("_")
+    return head + "".join(part.capitalize() for part in rest)
@@ -0,0 +1,364 @@
+"""
+Vyper front-end.
+
+Lowers the Vyper compiler's AST output (`vyper -f ast`) into the IR so the
+EVM detectors that only look at calls, state writes, and guards cover
+Vyper protocols too. Each `.vy` module is one Program: external functions
+(plus `__init__` and `__default__`) are instructions, decorators other
+than visibility become modifiers (`nonreentrant`, `payable`, ...), and
+storage variables form the module's AccountType.
+
+Input can be a directory of `.vy` files, a single `.vy` file (compiled
+with the `vyper` binary), or a saved `-f ast` / standard-json output.
+"""
+
+import json
+import re
+import shutil
+import subprocess
+from pathlib import Path
+from typing import Any
+
+from .model import Argument, AccountType, CallSite, Instruction, Program
+from .solidity import DEPENDENCY_DIRS, NON_PROGRAM_DIRS
+from .workspace import SKIP_DIRS
+
+_VISIBILITY = {"external", "internal", "deploy"}
+_MUTABILITY = {"pure", "view", "payable", "nonpayable"}
+# Builtins that hand control to another address
+_LOW_LEVEL_CALLS = {"raw_call": "call", "send": "send", "create_forwarder_to": "create", "create_copy_of": "create"}
+
+
+class VyperFrontend:
+    """Parses Vyper contracts into IR Programs via the compiler AST."""
+
+    def __init__(self, vyper: str | None = None, timeout: int = 120):
+        """Initialize front-end.
+
+        Args:
+            vyper: Path to a vyper binary (defaults to PATH)
+            timeout: Maximum seconds to wait per compiled file
+        """
+        self.vyper = vyper
+        self.timeout = timeout
+        self.errors: list[str] = []
+
+    def _find_vyper(self) -> str | None:
+        if self.vyper:
+            return self.vyper
+        return "vyper" if shutil.which("vyper") else None
+
+    def is_available(self) -> tuple[bool, str]:
+        """Check if vyper is installed.
+
+        Returns:
+            Tuple of (available, version_or_error)
+        """
+        vyper = self._find_vyper()
+        if not vyper:
+            return False, "vyper not found in PATH"
+        try:
+            result = subprocess.run([vyper, "--version"], capture_output=True, text=True, timeout=10)
+            if result.returncode == 0:
+                return True, result.stdout.strip()
+            return False, result.stderr.strip()
+        except subprocess.TimeoutExpired:
+            return False, "timeout checking vyper version"
+        except Exception as e:
+            return False, str(e)
+
+    @staticmethod
+    def has_sources(path: Path) -> bool:
+        """True if ``path`` is or contains a Vyper source file."""
+        path = Path(path)
+        if path.is_file():
+            return path.suffix == ".vy"
+        return any(not _is_excluded(p.relative_to(path)) for p in path.rglob("*.vy"))
+
+    def source_files(self, root: Path) -> list[str]:
+        """Vyper modules under ``root`` (tests, scripts, and dependencies excluded)."""
+        files = []
+        for vy in sorted(root.rglob("*.vy")):
+            rel = vy.relative_to(root)
+            if not _is_excluded(rel):
+                files.append(rel.as_posix())
+        return files
+
+    def compile(self, root: Path, rel: str) -> dict[str, Any]:
+        """Run `vyper -f ast` (and method identifiers) for one module.
+
+        Raises:
+            RuntimeError: vyper is missing, times out, or rejects the file
+        """
+        vyper = self._find_vyper()
+        if not vyper:
+            raise RuntimeError("vyper not found in PATH")
+        outputs = []
+        for fmt in ("ast", "method_identifiers"):
+            try:
+                result = subprocess.run(
+                    [vyper, "-f", fmt, "-p", str(root), rel],
+                    capture_output=True,
+                    text=True,
+                    timeout=self.timeout,
+                    cwd=root,
+                )
+            except subprocess.TimeoutExpired:
+                raise RuntimeError(f"vyper timed out after {self.timeout}s on {rel}")
+            except OSError as e:
+                raise RuntimeError(f"failed to run vyper: {e}")
+            if result.returncode != 0:
+                raise RuntimeError(f"vyper failed on {rel}: {result.stderr.strip()[-300:]}")
+            outputs.append(result.stdout)
+        data = json.loads(outputs[0])
+        try:
+            data["method_identifiers"] = json.loads(outputs[1].replace("'", '"'))
+        except json.JSONDecodeError:
+            pass
+        return data
+
+    def load(self, path: Path) -> list[Program]:
+        """Load every Vyper module under ``path``.
+
+        Compilation errors are collected in ``self.errors`` rather than raised.
+        """
+        path = Path(path)
+        self.errors = []
+        if path.is_file() and path.suffix == ".json":
+            data = json.loads(path.read_text())
+            root = path.parent
+            if "sources" in data:  # standard-json output
+                return [
+                    self.load_ast(entry["ast"], root, rel)
+                    for rel, entry in data["sources"].items()
+                    if entry.get("ast")
+                ]
+            if "ast" not in data:
+                self.errors.append(f"{path.name} is not vyper AST output")
+                return []
+            rel = data["ast"].get("path") or data.get("contract_name") or path.stem
+            return [self.load_ast(data["ast"], root, rel, data.get("method_identifiers"))]
+
+        root = path.parent if path.is_file() else path
+        files = [path.name] if path.is_file() else self.source_files(root)
+        programs = []
+        for rel in files:
+            try:
+                data = self.compile(root, rel)
+            except (RuntimeError, json.JSONDecodeError) as e:
+                self.errors.append(str(e))
+                continue
+            programs.append(self.load_ast(data["ast"], root, rel, data.get("method_identifiers")))
+        return programs
+
+    def load_ast(
+        self,
+        module: dict[str, Any],
+        root: Path,
+        rel: str,
+        method_identifiers: dict[str, str] | None = None,
+    ) -> Program:
+        """Lower one Vyper `Module` AST into a Program.
+
+        Args:
+            module: The `ast` object from `vyper -f ast`
+            root: Directory ``rel`` is relative to
+            rel: Module path (used for names and locations)
+            method_identifiers: Signature → selector map, if available
+        """
+        source = (Path(root) / rel).read_bytes() if (Path(root) / rel).exists() else b""
+        lowering = _Lowering(source)
+        body = module.get("body", [])
+        interfaces = {n["name"] for n in body if n.get("ast_type") == "InterfaceDef"}
+        for node in body:
+            if node.get("ast_type") in ("Import", "ImportFrom"):
+                for alias in node.get("names", []) or [node]:
+                    name = alias.get("asname") or alias.get("name") or ""
+                    interfaces.add(name.split(".")[-1])
+
+        program = Program(
+            name=Path(rel).stem,
+            chain="evm",
+            framework="vyper",
+            root=str(root),
+            source_files=[rel],
+            metadata={"file": rel},
+        )
+        storage = AccountType(name=Path(rel).stem, file=rel, line=1, properties={"kind": "storage", "constants": []})
+        interface_vars: set[str] = set()
+        for node in body:
+            if node.get("ast_type") != "VariableDecl":
+                continue
+            name = node.get("target", {}).get("id", "")
+            if node.get("is_constant") or node.get("is_immutable"):
+                storage.properties["constants"].append(name)
+                continue
+            ty = lowering.type_text(node.get("annotation", {}))
+            storage.fields.append(Argument(name=name, ty=ty))
+            if ty in interfaces:
+                interface_vars.add(name)
+        program.account_types.append(storage)
+
+        selectors = {sig: sel.removeprefix("0x") for sig, sel in (method_identifiers or {}).items()}
+        for node in body:
+            if node.get("ast_type") != "FunctionDef":
+                continue
+            ix = lowering.instruction(node, rel, interfaces, interface_vars)
+            if ix is None:
+                continue
+            sig = ix.properties["signature"]
+            ix.discriminator = selectors.get(sig)
+            program.instructions.append(ix)
+        return program
+
+
+class _Lowering:
+    """Source-aware helpers for one module."""
+
+    def __init__(self, source: bytes):
+        self.source = source
+
+    def snippet(self, node: dict) -> str:
+        m = re.match(r"^(\d+):(\d+):", node.get("src", "") or "")
+        if not m or not self.source:
+            return ""
+        start, length = int(m.group(1)), int(m.group(2))
+        return self.source[start:start + length].decode(errors="replace")
+
+    def type_text(self, annotation: dict) -> str:
+        # `public(T)` only adds a getter; the stored type is T
+        if annotation.get("ast_type") == "Call" and annotation.get("func", {}).get("id") == "public":
+            annotation = (annotation.get("args") or [{}])[0]
+        return self.snippet(annotation) or _render(annotation)
+
+    def instruction(self, fn: dict, rel: str, interfaces: set[str], interface_vars: set[str]) -> Instruction | None:
+        decorators = [_decorator_name(d) for d in fn.get("decorator_list", [])]
+        name = fn.get("name", "")
+        if name == "__init__":
+            kind = "constructor"
+        elif name == "__default__":
+            kind = "fallback"
+        else:
+            kind = "function"
+        visibility = next((d for d in decorators if d in _VISIBILITY), "internal")
+        if kind == "function" and visibility != "external":
+            return None
+        mutability = next((d for d in decorators if d in _MUTABILITY), "nonpayable")
+
+        args = [
+            Argument(name=a.get("arg", ""), ty=self.type_text(a.get("annotation") or {}))
+            for a in fn.get("args", {}).get("args", [])
+        ]
+        statements = fn.get("body", [])
+        body = "\n".join(self.snippet(s) for s in statements)
+        return Instruction(
+            name=name,
+            handler=name,
+            file=rel,
+            line=fn.get("lineno", 0),
+            args=args,
+            body=body,
+            body_line=statements[0].get("lineno", 0) if statements else fn.get("lineno", 0),
+            visibility=visibility,
+            modifiers=[d for d in decorators if d not in _VISIBILITY],
+            calls=self.calls(statements, interfaces, interface_vars),
+            properties={
+                "kind": kind,
+                "state_mutability": mutability,
+                "signature": f"{name}({','.join(a.ty for a in args)})",
+            },
+        )
+
+    def calls(self, statements: list, interfaces: set[str], interface_vars: set[str]) -> list[CallSite]:
+        calls = []
+        for node in _walk(statements):
+            kind = None
+            target = None
+            ast_type = node.get("ast_type")
+            if ast_type in ("ExtCall", "StaticCall"):  # Vyper 0.4 `extcall` / `staticcall`
+                call = node.get("value", {})
+                kind = "external"
+                target = call.get("func", {}).get("value", {})
+            elif ast_type == "Call":
+                func = node.get("func", {})
+                if func.get("ast_type") == "Name" and func.get("id") in _LOW_LEVEL_CALLS:
+                    kind = _LOW_LEVEL_CALLS[func["id"]]
+                    if kind == "call" and any(
+                        k.get("arg") == "is_delegate_call" and k.get("value", {}).get("value") is True
+                        for k in node.get("keywords", [])
+                    ):
+                        kind = "delegatecall"
+                    target = (node.get("args") or [{}])[0]
+                elif func.get("ast_type") == "Attribute" and _is_interface_ref(func.get("value", {}), interfaces, interface_vars):
+                    kind = "external"
+                    target = func["value"]
+            if kind is None:
+                continue
+            calls.append(CallSite(
+                kind=kind,
+                target=self.snippet(target or {}) or _render(target or {}),
+                line=node.get("lineno", 0),
+                text=self.snippet(node),
+            ))
+        return sorted(calls, key=lambda c: c.line)
+
+
+def _is_interface_ref(node: dict, interfaces: set[str], interface_vars: set[str]) -> bool:
+    """`IFoo(addr)` or `self.<interface-typed var>`."""
+    if node.get("ast_type") == "Call":
+        return node.get("func", {}).get("id") in interfaces
+    if node.get("ast_type") == "Attribute" and node.get("value", {}).get("id") == "self":
+        return node.get("attr") in interface_vars
+    return False
+
+
+def _decorator_name(node: dict) -> str:
+    if node.get("ast_type") == "Call":  # @nonreentrant("lock")
+        node = node.get("func", {})
+    return node.get("id", "")
+
+
+def _render(node: dict) -> str:
+    """Best-effort text for a node when source offsets are unavailable."""
+    t = node.get("ast_type")
+    if t == "Name":
+        return node.get("id", "")
+    if t == "Attribute":
+        return f"{_render(node.get('value', {}))}.{node.get('attr', '')}"
+    if t == "Subscript":
+        return f"{_render(node.get('value', {}))}[{_render(node.get('slice', {}))}]"
+    if t == "Call":
+        args = ", ".join(_render(a) for a in node.get("args", []))
+        return f"{_render(node.get('func', {}))}({args})"
+    if t == "Tuple":
+        return ", ".join(_render(e) for e in node.get("elements", node.get("elts", [])))
+    if t == "Index":
+        return _render(node.get("value", {}))
+    if "value" in node and not isinstance(node["value"], dict):
+        return str(node["value"])
+    return ""
+
+
+def _walk(node: Any):
+    """Depth-first iteration over AST dict nodes."""
+    stack = [node]
+    while stack:
+        current = stack.pop()
+        if isinstance(current, dict):
+            if "ast_type" in current:
+                yield current
+            stack.extend(reversed(list(current.values())))
+        elif isinstance(current, list):
+            stack.extend(reversed(current))
+
+
+def _is_excluded(rel: Path) -> bool:
+    parts = rel.parts
+    if any(part in SKIP_DIRS or part in DEPENDENCY_DIRS or part.startswith(".") for part in parts):
+        return True
+    return bool(parts) and parts[0] in NON_PROGRAM_DIRS
+
+
+def load_vyper_programs(path: Path) -> list[Program]:
+    """Convenience wrapper around VyperFrontend.load()."""
+    return VyperFrontend().load(Path(path))
@@ -0,0 +1,94 @@
+"""
+Crate discovery and source loading shared by the Rust front-ends.
+"""
+
+import re
+from collections.abc import Callable
+from dataclasses import dataclass
+from pathlib import Path
+
+from .rust_source import strip_comments
+
+# Directories that never contain program sources worth scanning
+SKIP_DIRS = {"target", "node_modules", ".git", ".anchor", "test-ledger", ".baskerville"}
+
+
+@dataclass
+class SourceFile:
+    """A Rust source file with its comment-stripped twin."""
+
+    relpath: str
+    original: str
+    text: str  # comment-stripped, same offsets as original
+
+
+def is_skipped(path: Path) -> bool:
+    return any(part in SKIP_DIRS for part in path.parts)
+
+
+def crate_root(rs_file: Path, limit: Path) -> Path:
+    """Nearest ancestor of ``rs_file`` holding a Cargo.toml, bounded by ``limit``."""
+    limit = limit if limit.is_dir() else limit.parent
+    current = rs_file.parent
+    while True:
+        if (current / "Cargo.toml").exists():
+            return current
+        if current == limit or current.parent == current:
+            return limit
+        current = current.parent
+
+
+def crate_name(root: Path) -> str:
+    """Package name from Cargo.toml, falling back to the directory name."""
+    cargo = root / "Cargo.toml"
+    if cargo.exists():
+        m = re.search(r'(?ms)^\[package\].*?^name\s*=\s*"([^"]+)"', cargo.read_text(errors="replace"))
+        if m:
+            return m.group(1)
+    return root.name
+
+
+def find_crates(path: Path, predicate: Callable[[str], bool]) -> list[Path]:
+    """Find crate roots under ``path`` containing a file that satisfies ``predicate``.
+
+    Args:
+        path: File or directory to search
+        predicate: Called with comment-stripped file contents
+    """
+    path = Path(path)
+    roots: list[Path] = []
+    files = [path] if path.is_file() else sorted(path.rglob("*.rs"))
+    for rs_file in files:
+        if is_skipped(rs_file):
+            continue
+        try:
+            content = rs_file.read_text(errors="replace")
+        except OSError:
+            continue
+        if not predicate(strip_comments(content)):
+            continue
+        root = crate_root(rs_file, path)
+        if root not in roots:
+            roots.append(root)
+    return roots
+
+
+def load_rust_sources(root: Path, max_files: int = 2000, include_tests: bool = False) -> list[SourceFile]:
+    """Read every Rust file of a crate (excluding build output and, by default, tests/)."""
+    root = Path(root)
+    files: list[SourceFile] = []
+    candidates = [root] if root.is_file() else sorted(root.rglob("*.rs"))
+    for rs_file in candidates:
+        if is_skipped(rs_file):
+            continue
+        if root.is_dir() and not include_tests and rs_file.relative_to(root).parts[0] == "tests":
+            continue
+        try:
+            original = rs_file.read_text(errors="replace")
+        except OSError:
+            continue
+        rel = rs_file.name if root.is_file() else str(rs_file.relative_to(root))
+        files.append(SourceFile(relpath=rel, original=original, text=strip_comments(original)))
+        if len(files) >= max_files:
+            break
+    return files
@@ -3,19 +3,75 @@ Knowledge base for smart contract security auditing.
 
 Provides structured access to:
 - Security checklists (380+ items organized by category)
-- PoC templates for common vulnerability classes
+- Chain-agnostic vulnerability classes that chain-specific templates hang off
+- Real-world exploits (Wormhole, Cashio, The DAO, ...) linked to classes and templates, for report enrichment
+- PoC templates for common vulnerability classes, namespaced by chain (EVM, Solana, Sui, Aptos, CosmWasm)
+- Template metadata from YAML/TOML front matter, queryable by chain, severity, class and test harness
+- Test harness models (Foundry, Anchor, sui move test, aptos move test, cw-multi-test): where PoCs go, how they run
+- Rendering of PoC templates with validated placeholder values into test files
+- Complete solana-program-test (BanksClient) exploit tests generated from a template and an Anchor IDL
 - Auditor tips and heuristics
+- Validation requirements of third-party integrations (oracles, governance, bridges, DEXs)
+- Sane ranges for hardcoded protocol parameters (fees, LTV, cooldowns, max supply)
+- Canonical deployment addresses of common programs (SPL Token, Token-2022, Metaplex, oracles, DEXs)
+- Solana runtime feature gates (syscalls, precompiles, rent, CPI and transaction limits) per cluster
+- A controlled tag taxonomy for faceted filtering of entries and findings
+- Translated descriptions and fix guidance via per-locale overlays
+- Content-addressed, signed bundles of checklists, templates and tips, pinned per workspace
+- Private PoC template roots (config, BASKERVILLE_TEMPLATE_DIRS, --template-dir) merged over the bundled ones
+- Ranked full-text search of templates' titles, descriptions and fixes, with tag, chain and severity filters
 - Semantic search via vector embeddings
 """
 
 from .manager import KnowledgeBase
+from .bundles import Bundle, BundleError, BundleStore
 from .checklist_loader import ChecklistLoader
-from .template_loader import TemplateLoader
+from .integration_loader import Integration, IntegrationLoader, IntegrationRequirement
+from .parameter_loader import ParameterLoader, ParameterRange
+from .program_loader import KnownProgram, ProgramLoader
+from .feature_loader import ClusterFeatures, FeatureGate, FeatureLoader
+from .chains import Chain
+from .harness import HARNESSES, Harness
+from .class_loader import ClassLoader, VulnerabilityClass
+from .incident_loader import Incident, IncidentLoader
+from .render import TemplateEngine, TemplateError
+from .front_matter import FrontMatterError
+from .template_loader import TemplateLoader, user_template_dirs
+from .template_registry import TemplateRegistry
+from .search import SearchHit, SearchQuery, TemplateIndex
 from .tip_loader import TipLoader
 
 __all__ = [
     "KnowledgeBase",
+    "Bundle",
+    "BundleError",
+    "BundleStore",
     "ChecklistLoader",
+    "Integration",
+    "IntegrationLoader",
+    "IntegrationRequirement",
+    "ParameterLoader",
+    "ParameterRange",
+    "KnownProgram",
+    "ProgramLoader",
+    "ClusterFeatures",
+    "FeatureGate",
+    "FeatureLoader",
+    "Chain",
+    "Harness",
+    "HARNESSES",
+    "ClassLoader",
+    "VulnerabilityClass",
+    "Incident",
+    "IncidentLoader",
+    "TemplateEngine",
+    "TemplateError",
     "TemplateLoader",
+    "TemplateRegistry",
+    "user_template_dirs",
+    "SearchHit",
+    "SearchQuery",
+    "TemplateIndex",
+    "FrontMatterError",
     "TipLoader",
 ]
@@ -0,0 +1,401 @@
+"""
+Content-addressed knowledge bundles.
+
+A bundle packs checklists, PoC templates and auditor tips (the rule content
+the knowledge base and reports draw on) into one archive that a team can
+review once, sign, and pin:
+
+    bundle.json            name, version and the SHA-256 of every file
+    bundle.sig.json        optional DSSE envelope over bundle.json (see
+                           extensions/reporting/signing.py)
+    checklists/*.yaml      laid out like the built-in knowledge base
+    templates/<chain>/*
+    tips/*.yaml
+
+A bundle's digest is the SHA-256 of its name, version and file digests, so
+it names the content, not the archive bytes. ``BundleStore`` keeps added
+bundles under ``~/.hound/bundles/<digest>/`` and re-checks every file
+against the manifest each time one is loaded, so a bundle edited in place
+stops loading instead of silently changing what reports are built from.
+
+A workspace pins the bundles it is audited with in ``baskerville.toml``:
+
+    [[bundle]]
+    name = "acme-solana"
+    digest = "sha256:3f2a..."
+    path = "audit/acme-solana-3f2a0c1d9e4b.tar.gz"  # optional, added when not in the store
+    key = "audit/acme.pub.pem"                        # optional, require a signature by this key
+
+``resolve_bundles`` loads exactly the pinned content or raises; reports and
+signatures record the pinned digests in their rule-set version.
+"""
+
+import gzip
+import hashlib
+import io
+import json
+import os
+import shutil
+import tarfile
+import tempfile
+from dataclasses import dataclass, field
+from pathlib import Path, PurePosixPath
+from typing import Any
+
+try:
+    import tomllib
+except ModuleNotFoundError:  # Python < 3.11
+    import tomli as tomllib
+
+BUNDLE_FORMAT = "baskerville-kb-bundle"
+BUNDLE_VERSION = 1
+BUNDLE_SUFFIX = ".tar.gz"
+CONFIG_FILE = "baskerville.toml"
+CONTENT_DIRS = ("checklists", "templates", "tips")
+
+_MANIFEST_NAME = "bundle.json"
+_SIGNATURE_NAME = "bundle.sig.json"
+_SKIP_NAMES = {"__pycache__", ".DS_Store"}
+_SKIP_PREFIXES = (".",)
+
+
+class BundleError(ValueError):
+    """A bundle is unreadable, fails verification, or does not match its pin."""
+    pass
+
+
+def _sha256(data: bytes) -> str:
+    return hashlib.sha256(data).hexdigest()
+
+
+def bundle_digest(name: str, version: str, files: dict[str, str]) -> str:
+    """Digest of a bundle's name, version and file digests."""
+    canonical = json.dumps({"name": name, "version": version, "files": dict(sorted(files.items()))},
+                           sort_keys=True, separators=(",", ":"))
+    return f"sha256:{_sha256(canonical.encode())}"
+
+
+def _safe_name(name: str) -> bool:
+    path = PurePosixPath(name)
+    return (not path.is_absolute() and ".." not in path.parts and len(path.parts) > 1
+            and path.parts[0] in CONTENT_DIRS)
+
+
+@dataclass
+class Bundle:
+    """A verified bundle, extracted under ``root``."""
+
+    name: str
+    version: str
+    digest: str
+    root: Path
+    files: dict[str, str] = field(default_factory=dict)
+    description: str = ""
+
+    @property
+    def signed(self) -> bool:
+        return (self.root / _SIGNATURE_NAME).is_file()
+
+    def signer(self) -> str | None:
+        """Key id of the bundle's signature, if it has one (not checked; see ``verify_signature``)."""
+        if not self.signed:
+            return None
+        try:
+            signatures = json.loads((self.root / _SIGNATURE_NAME).read_text()).get("signatures") or []
+        except (OSError, ValueError):
+            return None
+        return signatures[0].get("keyid") if signatures else None
+
+    def verify_signature(self, public_key: Path) -> None:
+        """Check the bundle is signed by ``public_key``; raises BundleError."""
+        from extensions.reporting.signing import SigningError, verify_envelope
+
+        if not self.signed:
+            raise BundleError(f"bundle {self.name} ({self.digest}) is not signed")
+        try:
+            result = verify_envelope(self.root / _SIGNATURE_NAME, Path(public_key), root=self.root)
+        except SigningError as e:
+            raise BundleError(str(e)) from None
+        predicate = (result.statement or {}).get("predicate") or {}
+        if not result.valid:
+            raise BundleError(f"bundle {self.name} signature does not verify: {'; '.join(result.errors)}")
+        if result.verified_files != [_MANIFEST_NAME] or predicate.get("ruleset_version") != self.digest:
+            raise BundleError(f"bundle {self.name} signature covers other content than {self.digest}")
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"name": self.name, "version": self.version, "digest": self.digest, "description": self.description,
+                "files": len(self.files), "signer": self.signer()}
+
+
+def _content(source: Path) -> dict[str, bytes]:
+    contents = {}
+    for sub in CONTENT_DIRS:
+        root = source / sub
+        if not root.is_dir():
+            continue
+        for path in sorted(root.rglob("*")):
+            rel = path.relative_to(source)
+            if not path.is_file() or path.is_symlink():
+                continue
+            if any(part in _SKIP_NAMES or part.startswith(_SKIP_PREFIXES) for part in rel.parts):
+                continue
+            contents[rel.as_posix()] = path.read_bytes()
+    return contents
+
+
+def _add(tar: tarfile.TarFile, name: str, data: bytes) -> None:
+    # Fixed metadata, so the same content always packs to the same archive
+    info = tarfile.TarInfo(name)
+    info.size, info.mode, info.mtime = len(data), 0o644, 0
+    tar.addfile(info, io.BytesIO(data))
+
+
+def build_bundle(source: Path, output_dir: Path, name: str, version: str, description: str = "",
+                 key: Path | None = None) -> tuple[Path, str]:
+    """Pack the checklists, templates and tips of ``source`` into a bundle.
+
+    Args:
+        source: Directory laid out like the knowledge base (`checklists/`, `templates/`, `tips/`)
+        output_dir: Where the archive goes, as `<name>-<digest prefix>.tar.gz`
+        name: Bundle name
+        version: Bundle version
+        description: What the bundle is for
+        key: ed25519 private key (PEM) to sign the bundle with
+
+    Returns:
+        The archive path and the bundle digest
+    """
+    source = Path(source)
+    contents = _content(source)
+    if not contents:
+        raise BundleError(f"{source} has no {', '.join(f'{d}/' for d in CONTENT_DIRS)} content")
+    files = {path: _sha256(data) for path, data in contents.items()}
+    digest = bundle_digest(name, version, files)
+    manifest = {"format": BUNDLE_FORMAT, "version": BUNDLE_VERSION, "name": name, "bundle_version": version,
+                "description": description, "digest": digest, "files": files}
+    manifest_data = (json.dumps(manifest, indent=2, sort_keys=True) + "\n").encode()
+
+    members = [(_MANIFEST_NAME, manifest_data)]
+    if key is not None:
+        from extensions.reporting.signing import SigningError, sign_files
+
+        with tempfile.TemporaryDirectory() as scratch:
+            (Path(scratch) / _MANIFEST_NAME).write_bytes(manifest_data)
+            try:
+                envelope = sign_files([Path(scratch) / _MANIFEST_NAME], Path(key),
+                                      output=Path(scratch) / _SIGNATURE_NAME, ruleset=digest)
+            except SigningError as e:
+                raise BundleError(str(e)) from None
+            members.append((_SIGNATURE_NAME, envelope.read_bytes()))
+    members += sorted(contents.items())
+
+    output_dir = Path(output_dir)
+    output_dir.mkdir(parents=True, exist_ok=True)
+    archive = output_dir / f"{name}-{digest.split(':', 1)[1][:12]}{BUNDLE_SUFFIX}"
+    buffer = io.BytesIO()
+    with tarfile.open(fileobj=buffer, mode="w") as tar:
+        for member, data in members:
+            _add(tar, member, data)
+    archive.write_bytes(gzip.compress(buffer.getvalue(), mtime=0))
+    return archive, digest
+
+
+def _manifest(data: bytes, origin: str) -> dict[str, Any]:
+    try:
+        manifest = json.loads(data)
+    except ValueError:
+        raise BundleError(f"{origin}: {_MANIFEST_NAME} is not valid JSON") from None
+    if not isinstance(manifest, dict) or manifest.get("format") != BUNDLE_FORMAT:
+        raise BundleError(f"{origin} is not a knowledge bundle")
+    if manifest.get("version", 0) > BUNDLE_VERSION:
+        raise BundleError(f"{origin}: bundle version {manifest['version']} is newer than supported "
+                          f"({BUNDLE_VERSION})")
+    return manifest
+
+
+def _check(manifest: dict[str, Any], files: dict[str, str], origin: str) -> str:
+    """The bundle's digest, after checking ``files`` (name -> sha256) against its manifest."""
+    expected = manifest.get("files") or {}
+    problems = [f"missing: {name}" for name in sorted(set(expected) - set(files))]
+    problems += [f"not in manifest: {name}" for name in sorted(set(files) - set(expected))]
+    problems += [f"checksum mismatch: {name}" for name in sorted(set(files) & set(expected))
+                 if files[name] != expected[name]]
+    digest = bundle_digest(str(manifest.get("name", "")), str(manifest.get("bundle_version", "")), expected)
+    if manifest.get("digest") != digest:
+        problems.append(f"manifest digest {manifest.get('digest')} is not its content's ({digest})")
+    if problems:
+        raise BundleError(f"{origin} failed verification: " + "; ".join(problems))
+    return digest
+
+
+def read_bundle(archive: Path) -> tuple[dict[str, Any], dict[str, bytes]]:
+    """The manifest and members (manifest, signature, content) of a bundle archive, after checking them.
+
+    Raises:
+        BundleError: If the archive is not a bundle, has unsafe member paths,
+            or a file is missing or does not match its checksum
+    """
+    try:
+        tar = tarfile.open(archive, "r:gz")
+    except (OSError, tarfile.TarError) as e:
+        raise BundleError(f"cannot read bundle {archive}: {e}") from None
+    members: dict[str, bytes] = {}
+    with tar:
+        try:
+            entries = tar.getmembers()
+        except (OSError, EOFError, tarfile.TarError) as e:
+            raise BundleError(f"cannot read bundle {archive}: {e}") from None
+        for member in entries:
+            if member.isdir():
+                continue
+            if not member.isfile():
+                raise BundleError(f"unexpected non-file member: {member.name}")
+            if member.name not in (_MANIFEST_NAME, _SIGNATURE_NAME) and not _safe_name(member.name):
+                raise BundleError(f"unsafe member path: {member.name}")
+            members[member.name] = tar.extractfile(member).read()
+    if _MANIFEST_NAME not in members:
+        raise BundleError(f"{archive} is not a knowledge bundle (no {_MANIFEST_NAME})")
+    manifest = _manifest(members[_MANIFEST_NAME], str(archive))
+    content = {name: data for name, data in members.items() if name not in (_MANIFEST_NAME, _SIGNATURE_NAME)}
+    _check(manifest, {name: _sha256(data) for name, data in content.items()}, str(archive))
+    return manifest, members
+
+
+class BundleStore:
+    """Bundles added on this machine, one directory per digest."""
+
+    def __init__(self, root: Path | None = None):
+        """Initialize the store.
+
+        Args:
+            root: Store directory. Defaults to ~/.hound/bundles
+        """
+        self.root = Path(root) if root else Path.home() / ".hound" / "bundles"
+
+    def _dir(self, digest: str) -> Path:
+        algorithm, _, value = digest.partition(":")
+        if algorithm != "sha256" or len(value) != 64 or not all(c in "0123456789abcdef" for c in value):
+            raise BundleError(f"{digest!r} is not a sha256:<64 hex digits> bundle digest")
+        return self.root / value
+
+    def add(self, archive: Path, public_key: Path | None = None) -> Bundle:
+        """Verify ``archive`` (and its signature, given the signer's key) and store it."""
+        manifest, members = read_bundle(Path(archive))
+        digest = manifest["digest"]
+        target = self._dir(digest)
+        if target.is_dir():
+            bundle = self._load(target, digest)
+            if public_key is not None:
+                bundle.verify_signature(public_key)
+            return bundle
+        self.root.mkdir(parents=True, exist_ok=True)
+        staging = Path(tempfile.mkdtemp(prefix=".staging-", dir=self.root))
+        try:
+            for name, data in members.items():
+                path = staging / name
+                path.parent.mkdir(parents=True, exist_ok=True)
+                path.write_bytes(data)
+            # Nothing unsigned (or signed by someone else) reaches the store when a key is given
+            if public_key is not None:
+                self._load(staging, digest).verify_signature(public_key)
+            os.replace(staging, target)
+        finally:
+            shutil.rmtree(staging, ignore_errors=True)
+        return self._load(target, digest)
+
+    def _load(self, directory: Path, digest: str) -> Bundle:
+        manifest_path = directory / _MANIFEST_NAME
+        try:
+            manifest = _manifest(manifest_path.read_bytes(), str(directory))
+        except OSError as e:
+            raise BundleError(f"cannot read {manifest_path}: {e}") from None
+        found = {}
+        for sub in CONTENT_DIRS:
+            for path in sorted(p for p in (directory / sub).rglob("*") if p.is_file()):
+                found[path.relative_to(directory).as_posix()] = _sha256(path.read_bytes())
+        actual = _check(manifest, found, f"stored bundle {digest}")
+        if actual != digest:
+            raise BundleError(f"stored bundle {digest} holds {actual}")
+        return Bundle(name=manifest["name"], version=str(manifest.get("bundle_version", "")), digest=digest,
+                      root=directory, files=dict(manifest["files"]), description=manifest.get("description", ""))
+
+    def get(self, digest: str) -> Bundle | None:
+        """The stored bundle ``digest``, re-verified; None if it was never added."""
+        directory = self._dir(digest)
+        if not directory.is_dir():
+            return None
+        return self._load(directory, digest)
+
+    def list(self) -> list[Bundle]:
+        """Every stored bundle that still verifies, by name."""
+        if not self.root.is_dir():
+            return []
+        bundles = []
+        for directory in sorted(d for d in self.root.iterdir() if d.is_dir() and not d.name.startswith(".")):
+            try:
+                bundles.append(self._load(directory, f"sha256:{directory.name}"))
+            except BundleError as e:
+                print(f"[!] Skipping bundle {directory.name}: {e}")
+        return sorted(bundles, key=lambda b: (b.name, b.version))
+
+
+@dataclass
+class BundlePin:
+    """One ``[[bundle]]`` entry of a workspace's baskerville.toml."""
+
+    name: str
+    digest: str
+    # Archive to add when the store does not have the bundle, and the signer's public key
+    path: Path | None = None
+    key: Path | None = None
+
+
+def load_pins(workspace: Path) -> list[BundlePin]:
+    """Bundles ``workspace`` pins in its baskerville.toml (none without one); raises BundleError."""
+    config = Path(workspace) / CONFIG_FILE
+    if not config.is_file():
+        return []
+    try:
+        data = tomllib.loads(config.read_text())
+    except (OSError, tomllib.TOMLDecodeError) as e:
+        raise BundleError(f"cannot read {config}: {e}") from None
+    entries = data.get("bundle") or []
+    if not isinstance(entries, list):
+        raise BundleError(f"{config}: `bundle` must be an array of tables ([[bundle]])")
+    pins = []
+    for entry in entries:
+        if not isinstance(entry, dict) or not entry.get("name") or not entry.get("digest"):
+            raise BundleError(f"{config}: every [[bundle]] needs a name and a digest")
+        base = config.parent
+        pins.append(BundlePin(str(entry["name"]), str(entry["digest"]),
+                              path=base / entry["path"] if entry.get("path") else None,
+                              key=base / entry["key"] if entry.get("key") else None))
+    return pins
+
+
+def resolve_bundles(workspace: Path, store: BundleStore | None = None) -> list[Bundle]:
+    """The bundles ``workspace`` pins, verified against their pins (adding pinned archives to the store).
+
+    Raises:
+        BundleError: If a pinned bundle is missing, altered, named differently
+            or not signed by the pinned key
+    """
+    store = store or BundleStore()
+    bundles = []
+    for pin in load_pins(workspace):
+        bundle = store.get(pin.digest)
+        if bundle is None and pin.path is not None:
+            if not pin.path.is_file():
+                raise BundleError(f"pinned bundle {pin.name}: {pin.path} does not exist")
+            bundle = store.add(pin.path)
+            if bundle.digest != pin.digest:
+                raise BundleError(f"pinned bundle {pin.name} is {pin.digest}, but {pin.path} is {bundle.digest}")
+        if bundle is None:
+            raise BundleError(f"pinned bundle {pin.name} ({pin.digest}) is not in {store.root}; "
+                              f"add it with `bundle add <archive>`")
+        if bundle.name != pin.name:
+            raise BundleError(f"pinned bundle {pin.name} ({pin.digest}) is named {bundle.name}")
+        if pin.key is not None:
+            bundle.verify_signature(pin.key)
+        bundles.append(bundle)
+    return bundles
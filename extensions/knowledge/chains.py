@@ -0,0 +1,57 @@
+"""
+Chains the knowledge base has templates for.
+
+Every PoC template belongs to one chain, and each chain keeps its templates
+in its own namespace under `templates/` (`templates/evm/*.sol`,
+`templates/solana/*.rs`, ...). `Chain` compares equal to its id, so
+`template.chain == "evm"` and dictionaries keyed by chain id keep working.
+"""
+
+from enum import Enum
+
+
+class Chain(str, Enum):
+    """A chain with its own template namespace."""
+
+    EVM = "evm"
+    SOLANA = "solana"
+    SUI = "sui"
+    APTOS = "aptos"
+    COSMWASM = "cosmwasm"
+
+    def __str__(self) -> str:
+        return self.value
+
+    @property
+    def label(self) -> str:
+        """Display name ("EVM", "Solana", ...)."""
+        return _LABELS[self]
+
+    @property
+    def template_suffix(self) -> str:
+        """File suffix of the chain's templates."""
+        return _SUFFIXES[self]
+
+    @classmethod
+    def parse(cls, value: "Chain | str") -> "Chain":
+        """The chain for an id ("evm", "Solana", ...).
+
+        Raises:
+            ValueError: If ``value`` names no known chain
+        """
+        if isinstance(value, cls):
+            return value
+        try:
+            return cls(str(value).strip().lower())
+        except ValueError:
+            raise ValueError(
+                f"Unknown chain '{value}' (expected one of {', '.join(c.value for c in cls)})"
+            ) from None
+
+
+_LABELS = {
+    Chain.EVM: "EVM", Chain.SOLANA: "Solana", Chain.SUI: "Sui", Chain.APTOS: "Aptos", Chain.COSMWASM: "CosmWasm",
+}
+_SUFFIXES = {
+    Chain.EVM: ".sol", Chain.SOLANA: ".rs", Chain.SUI: ".move", Chain.APTOS: ".move", Chain.COSMWASM: ".rs",
+}
@@ -8,13 +8,15 @@ Hybrid approach:
 
 import json
 import yaml
-import aiohttp
 import asyncio
 from pathlib import Path
 from dataclasses import dataclass, field
 from datetime import datetime, timedelta
 from typing import Iterator
 
+from ..i18n import load_overlay, localize
+from .taxonomy import classify, filter_by_tags
+
 
 SOLODIT_CHECKLIST_URL = "https://raw.githubusercontent.com/Cyfrin/audit-checklist/main/checklist.json"
 CACHE_TTL = timedelta(days=7)
@@ -34,6 +36,12 @@ class ChecklistItem:
     references: list[str]
     source: str  # "solodit" or "custom"
     chain: str = "evm"
+    taxonomy: list[str] = field(default_factory=list)  # Controlled tags, see taxonomy.py
+
+    def __post_init__(self):
+        if not self.taxonomy:
+            self.taxonomy = classify(self.tags, [self.category, self.subcategory, self.question],
+                                     [self.description])
 
     def matches(self, query: str) -> bool:
         """Check if item matches a search query."""
@@ -50,17 +58,22 @@ class ChecklistItem:
 class ChecklistLoader:
     """Loads and queries security checklists from Solodit + custom sources."""
 
-    def __init__(self, base_dir: Path | None = None):
+    def __init__(self, base_dir: Path | None = None, locale: str | None = None,
+                 extra_dirs: list[Path] | None = None):
         """Initialize loader.
 
         Args:
             base_dir: Path to knowledge base directory
+            locale: Language of questions, descriptions and remediation (en, zh, es, ja)
+            extra_dirs: More checklist directories (e.g. of pinned bundles), loaded after the built-in one
         """
         if base_dir is None:
             base_dir = Path(__file__).parent
 
         self.checklists_dir = base_dir / "checklists"
+        self.extra_dirs = [Path(d) for d in extra_dirs or []]
         self.cache_path = base_dir / "checklists" / ".solodit_cache.json"
+        self.locale = locale
 
         self._items: list[ChecklistItem] = []
         self._by_category: dict[str, list[ChecklistItem]] = {}
@@ -68,6 +81,11 @@ class ChecklistLoader:
 
     async def _fetch_solodit_checklist(self) -> list[dict] | None:
         """Fetch Solodit checklist from GitHub."""
+        try:
+            import aiohttp
+        except ImportError:
+            print("[!] Solodit checklist not fetched: requires aiohttp (pip install .[net])")
+            return None
         try:
             async with aiohttp.ClientSession() as session:
                 async with session.get(SOLODIT_CHECKLIST_URL, timeout=30) as resp:
@@ -159,10 +177,8 @@ class ChecklistLoader:
         """Load custom YAML checklists."""
         items = []
 
-        if not self.checklists_dir.exists():
-            return items
-
-        for yaml_file in self.checklists_dir.glob("*.yaml"):
+        directories = [d for d in [self.checklists_dir, *self.extra_dirs] if d.exists()]
+        for yaml_file in (f for d in directories for f in d.glob("*.yaml")):
             try:
                 with open(yaml_file) as f:
                     data = yaml.safe_load(f)
@@ -193,6 +209,13 @@ class ChecklistLoader:
 
         return items
 
+    def _localize_items(self) -> None:
+        """Apply translations from checklists/locales/<locale>.yaml, keyed by item id."""
+        overlay = load_overlay(self.checklists_dir, self.locale)
+        if overlay:
+            fields = ("question", "description", "remediation")
+            self._items = [localize(item, overlay.get(item.id), fields) for item in self._items]
+
     def _load_sync(self) -> None:
         """Load all checklists synchronously."""
         if self._loaded:
@@ -221,6 +244,7 @@ class ChecklistLoader:
 
         # Load custom checklists
         self._items.extend(self._load_custom_checklists())
+        self._localize_items()
 
         # Index by category
         for item in self._items:
@@ -250,6 +274,7 @@ class ChecklistLoader:
 
         # Load custom checklists
         self._items.extend(self._load_custom_checklists())
+        self._localize_items()
 
         # Index by category
         for item in self._items:
@@ -314,6 +339,11 @@ class ChecklistLoader:
             if any(t.lower() in tags_lower for t in item.tags)
         ]
 
+    def get_by_taxonomy(self, tags: list[str], match_all: bool = False) -> list[ChecklistItem]:
+        """Get checklist items carrying any (or all) of the given taxonomy tags."""
+        self._load_sync()
+        return filter_by_tags(self._items, tags, match_all)
+
     def get_solodit_items(self) -> list[ChecklistItem]:
         """Get only Solodit checklist items."""
         self._load_sync()
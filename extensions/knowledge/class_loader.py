@@ -0,0 +1,165 @@
+"""
+Chain-agnostic vulnerability classes.
+
+A class such as "unauthenticated privileged action" describes a bug once and
+lists its chain-specific forms (missing signer check on Solana, missing
+onlyOwner on EVM, missing capability on Sui) together with the PoC templates
+that exercise them. Hypotheses, detector hits and templates resolve to a
+class through their vulnerability type, so descriptions and fix guidance can
+be reused and findings reported per class across chains.
+"""
+
+import re
+import yaml
+from pathlib import Path
+from dataclasses import dataclass, field
+
+from ..i18n import load_overlay, localize
+
+
+def _normalize(name: str) -> str:
+    return re.sub(r"[\s_]+", "-", name.strip().lower())
+
+
+@dataclass
+class ChainVariant:
+    """What a vulnerability class looks like on one chain."""
+    chain: str
+    name: str
+    description: str = ""
+    fix: str = ""  # Overrides the class fix guidance when set
+    aliases: list[str] = field(default_factory=list)  # Vulnerability-type names used for this form
+    templates: list[str] = field(default_factory=list)  # PoC template ids
+
+
+@dataclass
+class VulnerabilityClass:
+    """A chain-agnostic vulnerability class."""
+    id: str
+    name: str
+    description: str
+    impact: str
+    fix: str
+    taxonomy: list[str] = field(default_factory=list)
+    variants: dict[str, ChainVariant] = field(default_factory=dict)
+
+    def variant(self, chain: str | None) -> ChainVariant | None:
+        return self.variants.get(chain.lower()) if chain else None
+
+    def fix_for(self, chain: str | None = None) -> str:
+        """Fix guidance, specific to the chain when the class has guidance for it."""
+        variant = self.variant(chain)
+        return variant.fix if variant and variant.fix else self.fix
+
+    def describe(self, chain: str | None = None) -> str:
+        """Class description, followed by the chain-specific form when known."""
+        variant = self.variant(chain)
+        if variant and variant.description:
+            return f"{self.description} On {variant.chain}: {variant.description}"
+        return self.description
+
+    @property
+    def templates(self) -> list[str]:
+        return [t for v in self.variants.values() for t in v.templates]
+
+
+class ClassLoader:
+    """Loads vulnerability classes and resolves vulnerability types onto them."""
+
+    def __init__(self, classes_dir: Path | None = None, locale: str | None = None):
+        """Initialize loader.
+
+        Args:
+            classes_dir: Path to the directory of class YAML files
+            locale: Language of names, descriptions and fix guidance (en, zh, es, ja)
+        """
+        if classes_dir is None:
+            classes_dir = Path(__file__).parent / "classes"
+        self.classes_dir = classes_dir
+        self.locale = locale
+        self._classes: dict[str, VulnerabilityClass] = {}
+        self._loaded = False
+
+    def _load(self) -> None:
+        """Load all classes."""
+        if self._loaded:
+            return
+
+        self._classes = {}
+        if self.classes_dir.exists():
+            for yaml_file in sorted(self.classes_dir.glob("*.yaml")):
+                try:
+                    with open(yaml_file) as f:
+                        data = yaml.safe_load(f)
+                    for cls_data in (data or {}).get("classes", []):
+                        variants = {
+                            chain.lower(): ChainVariant(
+                                chain=chain.lower(),
+                                name=v.get("name", ""),
+                                description=v.get("description", ""),
+                                fix=v.get("fix", ""),
+                                aliases=[_normalize(a) for a in v.get("aliases", [])],
+                                templates=v.get("templates", []),
+                            )
+                            for chain, v in (cls_data.get("variants") or {}).items()
+                        }
+                        self._classes[cls_data["id"]] = VulnerabilityClass(
+                            id=cls_data["id"],
+                            name=cls_data.get("name", cls_data["id"]),
+                            description=cls_data.get("description", ""),
+                            impact=cls_data.get("impact", ""),
+                            fix=cls_data.get("fix", ""),
+                            taxonomy=cls_data.get("taxonomy", []),
+                            variants=variants,
+                        )
+                except Exception as e:
+                    print(f"[!] Failed to load vulnerability classes from {yaml_file}: {e}")
+
+        overlay = load_overlay(self.classes_dir, self.locale)
+        for class_id, vuln_class in self._classes.items():
+            translated = localize(vuln_class, overlay.get(class_id), ("name", "description", "impact", "fix"))
+            variant_overlay = (overlay.get(class_id) or {}).get("variants") or {}
+            translated.variants = {
+                chain: localize(v, variant_overlay.get(chain), ("name", "description", "fix"))
+                for chain, v in vuln_class.variants.items()
+            }
+            self._classes[class_id] = translated
+
+        self._loaded = True
+
+    def get(self, class_id: str) -> VulnerabilityClass | None:
+        """Get a class by ID."""
+        self._load()
+        return self._classes.get(class_id)
+
+    def list_all(self) -> list[VulnerabilityClass]:
+        """Get all classes."""
+        self._load()
+        return list(self._classes.values())
+
+    def for_template(self, template_id: str) -> VulnerabilityClass | None:
+        """Class a PoC template belongs to."""
+        self._load()
+        return next((c for c in self._classes.values() if template_id in c.templates), None)
+
+    def resolve(self, vulnerability_type: str, chain: str | None = None) -> VulnerabilityClass | None:
+        """Class for a vulnerability type, an alias of any of its chain variants or its ID.
+
+        Args:
+            vulnerability_type: e.g. "missing_signer", "access-control", "unauthenticated-privileged-action"
+            chain: Prefer a class whose variant for this chain uses the name, when several do
+        """
+        self._load()
+        name = _normalize(vulnerability_type or "")
+        if not name:
+            return None
+        if name in self._classes:
+            return self._classes[name]
+
+        candidates = [c for c in self._classes.values() if any(name in v.aliases for v in c.variants.values())]
+        if chain:
+            for c in candidates:
+                variant = c.variant(chain)
+                if variant and name in variant.aliases:
+                    return c
+        return candidates[0] if candidates else None
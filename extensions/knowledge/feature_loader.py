@@ -0,0 +1,197 @@
+"""
+Runtime feature gate loader.
+
+Each entry is a Solana feature gate that changes what program code can rely
+on: a syscall or precompile that only exists once the gate is active, a
+behavior (rent collection) it removes, or a runtime limit it raises. Gates
+record their feature account, the clusters they were active on when the
+snapshot was taken, the source patterns of code that depends on them and
+the limits they change. ``ClusterFeatures`` answers what holds on one
+cluster, from the snapshot or from the feature accounts read over RPC.
+"""
+
+import base64
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+import yaml
+
+# solana-test-validator and solana-program-test activate every feature
+LOCALNET = "localnet"
+
+
+@dataclass
+class FeatureGate:
+    """A runtime feature gate and the code that depends on it."""
+    id: str
+    name: str
+    feature: str  # Feature account address
+    category: str = "runtime"
+    simd: str = ""
+    active_on: list[str] = field(default_factory=list)
+    patterns: list[str] = field(default_factory=list)
+    # Whether matching code needs the gate active (syscalls) or inactive (removed behavior)
+    requires: str = "active"
+    impact: str = ""
+    # Limit name -> (value while inactive, value once active)
+    limits: dict[str, tuple[int, int]] = field(default_factory=dict)
+    chain: str = "solana"
+
+    def uses(self, source: str) -> list[re.Match]:
+        """Matches of the gate's patterns in ``source``, in source order."""
+        found = [m for p in self.patterns for m in re.finditer(p, source)]
+        return sorted(found, key=lambda m: m.start())
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"id": self.id, "name": self.name, "feature": self.feature, "category": self.category,
+                "simd": self.simd, "active_on": self.active_on, "requires": self.requires,
+                "limits": {name: list(values) for name, values in self.limits.items()}}
+
+
+class FeatureLoader:
+    """Loads and queries runtime feature gates."""
+
+    def __init__(self, features_dir: Path | None = None):
+        """Initialize loader.
+
+        Args:
+            features_dir: Path to features directory
+        """
+        if features_dir is None:
+            features_dir = Path(__file__).parent / "features"
+        self.features_dir = features_dir
+        self._features: list[FeatureGate] = []
+        # Chain -> date the activation snapshot was taken
+        self.as_of: dict[str, str] = {}
+        self._loaded = False
+
+    def _load(self) -> None:
+        """Load all feature gates."""
+        if self._loaded:
+            return
+
+        if self.features_dir.exists():
+            for yaml_file in sorted(self.features_dir.glob("*.yaml")):
+                try:
+                    with open(yaml_file) as f:
+                        data = yaml.safe_load(f) or {}
+                    file_chain = data.get("chain", "solana")
+                    if data.get("as_of"):
+                        self.as_of[file_chain] = str(data["as_of"])
+                    for entry in data.get("features", []):
+                        gate = FeatureGate(
+                            id=entry["id"],
+                            name=entry.get("name", entry["id"]),
+                            feature=str(entry["feature"]),
+                            category=entry.get("category", "runtime"),
+                            simd=str(entry.get("simd", "")),
+                            active_on=[_cluster(c) for c in entry.get("active_on", [])],
+                            patterns=entry.get("patterns", []),
+                            requires=entry.get("requires", "active"),
+                            impact=" ".join(str(entry.get("impact", "")).split()),
+                            limits={k: (int(v[0]), int(v[1])) for k, v in (entry.get("limits") or {}).items()},
+                            chain=entry.get("chain", file_chain),
+                        )
+                        if gate.requires not in ("active", "inactive"):
+                            raise ValueError(f"{gate.id}: requires must be active or inactive")
+                        for pattern in gate.patterns:
+                            re.compile(pattern)
+                        self._features.append(gate)
+                except Exception as e:
+                    print(f"[!] Failed to load feature gates from {yaml_file}: {e}")
+
+        self._loaded = True
+
+    def get(self, gate_id: str) -> FeatureGate | None:
+        """Get a gate by its knowledge base ID."""
+        self._load()
+        return next((g for g in self._features if g.id == gate_id), None)
+
+    def by_address(self, address: str) -> FeatureGate | None:
+        """The gate whose feature account is ``address``."""
+        self._load()
+        return next((g for g in self._features if g.feature == address), None)
+
+    def list_all(self) -> list[FeatureGate]:
+        """Get all gates."""
+        self._load()
+        return list(self._features)
+
+    def get_by_chain(self, chain_id: str) -> list[FeatureGate]:
+        """Get gates for a chain (e.g., "solana")."""
+        return [g for g in self.list_all() if g.chain.lower() == chain_id.lower()]
+
+
+def _cluster(cluster: str) -> str:
+    from extensions.ir.anchor_config import normalize_cluster
+
+    return normalize_cluster(cluster)
+
+
+def activation_slot(info: dict[str, Any] | None) -> int | None:
+    """Slot a feature account (``getAccountInfo`` value, base64) was activated at; None while pending."""
+    if not info:
+        return None
+    data = base64.b64decode(info["data"][0]) if isinstance(info.get("data"), list) else b""
+    # bincode Option<u64>
+    if len(data) >= 9 and data[0] == 1:
+        return int.from_bytes(data[1:9], "little")
+    return None
+
+
+def fetch_activation(client: Any, gates: list[FeatureGate]) -> dict[str, bool]:
+    """Gate id -> active, read from the feature accounts through ``client`` (an RpcClient)."""
+    infos = client.get_multiple_accounts([g.feature for g in gates])
+    return {g.id: activation_slot(info) is not None for g, info in zip(gates, infos)}
+
+
+class ClusterFeatures:
+    """Which feature gates hold on one cluster."""
+
+    def __init__(self, cluster: str = "mainnet", active: dict[str, bool] | None = None,
+                 loader: FeatureLoader | None = None):
+        """Initialize the view.
+
+        Args:
+            cluster: Target cluster (mainnet, devnet, testnet, localnet)
+            active: Gate id -> active as read from the cluster, overriding the snapshot
+            loader: Feature gates (default: the knowledge base's)
+        """
+        self.cluster = _cluster(cluster)
+        self.active = dict(active or {})
+        self.loader = loader or FeatureLoader()
+        self.gates = self.loader.get_by_chain("solana")
+
+    @property
+    def source(self) -> str:
+        """Where activation status comes from: "rpc", or the snapshot's date."""
+        if self.active:
+            return "rpc"
+        return f"snapshot {self.loader.as_of.get('solana', '')}".strip()
+
+    def is_active(self, gate: FeatureGate) -> bool:
+        if gate.id in self.active:
+            return self.active[gate.id]
+        return self.cluster == LOCALNET or self.cluster in gate.active_on
+
+    def holds(self, gate: FeatureGate) -> bool:
+        """Whether code depending on ``gate`` gets what it assumes on this cluster."""
+        return self.is_active(gate) == (gate.requires == "active")
+
+    def inactive(self) -> list[FeatureGate]:
+        return [g for g in self.gates if not self.is_active(g)]
+
+    def limits(self) -> dict[str, int]:
+        """Runtime limit name -> its value on this cluster."""
+        return {name: values[self.is_active(g)] for g in self.gates for name, values in g.limits.items()}
+
+    def unmet(self, source: str) -> list[tuple[FeatureGate, re.Match]]:
+        """Each gate ``source`` depends on whose assumption fails here, with its first use."""
+        found = []
+        for gate in self.gates:
+            uses = gate.uses(source) if not self.holds(gate) else []
+            if uses:
+                found.append((gate, uses[0]))
+        return found
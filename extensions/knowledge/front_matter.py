@@ -0,0 +1,159 @@
+"""
+PoC template front matter.
+
+A template file can open with a metadata block, commented out so the file
+stays valid source for its language:
+
+    // ---
+    // name: Missing Signer Check
+    // class: unauthenticated-privileged-action
+    // severity: high
+    // cwe: [CWE-862]
+    // harness: anchor
+    // context:
+    //   PROGRAM_ID: Program under test
+    // references:
+    //   - https://github.com/coral-xyz/sealevel-attacks
+    // incidents: [wormhole-2022, cashio-2022]
+    // ---
+
+`---` fences hold YAML and `+++` fences TOML; the comment prefix (`//` or
+`#`) is optional. `context` lists the variables a template needs filled in
+(a list of names, or names mapped to descriptions), and every one of them
+must be a placeholder of the template. `harness` names the test framework
+the template is written for (see harness.py; default: the chain's), and
+`remediation` is the fix guidance reports print with findings it matches.
+`incidents` names real-world exploits of the bug (see incident_loader.py).
+"""
+
+import re
+import sys
+from typing import Any
+
+import yaml
+
+from .chains import Chain
+from .harness import parse_harness
+
+if sys.version_info >= (3, 11):
+    import tomllib
+else:
+    import tomli as tomllib
+
+FENCES = {"---": "yaml", "+++": "toml"}
+SEVERITIES = ("critical", "high", "medium", "low", "info")
+FIELDS = {"id", "name", "description", "vulnerability_type", "class", "chain", "severity", "cwe", "swc",
+          "harness", "remediation", "context", "references", "tags", "incidents"}
+
+_FENCE = re.compile(r"^(?P<prefix>\s*(?://|#)?\s*)(?P<fence>---|\+\+\+)\s*$")
+
+
+class FrontMatterError(ValueError):
+    """Front matter that does not parse or has invalid fields."""
+
+
+def _strip(line: str, prefix: str) -> str:
+    line = line.rstrip("\r\n")
+    if line.startswith(prefix):
+        return line[len(prefix):]
+    # Blank comment lines ("//") inside the block
+    return line[len(prefix.rstrip()):] if line.startswith(prefix.rstrip()) else line
+
+
+def split_front_matter(content: str) -> tuple[dict[str, Any], str | None, str]:
+    """Split ``content`` into (raw metadata, "yaml"/"toml"/None, body without the block)."""
+    lines = content.splitlines(keepends=True)
+    start = next((i for i, line in enumerate(lines) if line.strip()), None)
+    opening = _FENCE.match(lines[start].rstrip("\r\n")) if start is not None else None
+    if opening is None:
+        return {}, None, content
+    prefix, fence = opening.group("prefix"), opening.group("fence")
+    end = next((i for i in range(start + 1, len(lines)) if _strip(lines[i], prefix).strip() == fence), None)
+    if end is None:
+        raise FrontMatterError(f"front matter opened with '{fence}' is not closed")
+    text = "\n".join(_strip(line, prefix) for line in lines[start + 1:end])
+    fmt = FENCES[fence]
+    try:
+        data = yaml.safe_load(text) if fmt == "yaml" else tomllib.loads(text)
+    except (yaml.YAMLError, tomllib.TOMLDecodeError) as e:
+        raise FrontMatterError(f"invalid {fmt.upper()} front matter: {e}") from None
+    if data is None:
+        data = {}
+    if not isinstance(data, dict):
+        raise FrontMatterError("front matter must be a mapping")
+    body = "".join(lines[end + 1:]).lstrip("\n")
+    return data, fmt, body
+
+
+def _ids(value: Any, prefix: str) -> list[str]:
+    """`862`, `"862"`, `"cwe-862"` or a list of them → ["CWE-862", ...]."""
+    items = value if isinstance(value, list) else [value]
+    ids = []
+    for item in items:
+        m = re.fullmatch(rf"(?:{prefix}-?)?(\d+)", str(item).strip(), re.IGNORECASE)
+        if not m:
+            raise FrontMatterError(f"invalid {prefix} id: {item!r}")
+        ids.append(f"{prefix}-{int(m.group(1))}")
+    return ids
+
+
+def _strings(value: Any, name: str) -> list[str]:
+    items = value if isinstance(value, list) else [value]
+    if not all(isinstance(i, str) and i.strip() for i in items):
+        raise FrontMatterError(f"'{name}' must be a string or a list of strings")
+    return [i.strip() for i in items]
+
+
+def normalize_front_matter(data: dict[str, Any], placeholders: list[str] | None = None) -> dict[str, Any]:
+    """Validate raw front matter and bring every field to one shape.
+
+    Args:
+        data: Parsed front matter
+        placeholders: The template's placeholders, which `context` variables must be among
+    """
+    unknown = sorted(set(data) - FIELDS)
+    if unknown:
+        raise FrontMatterError(f"unknown front matter field(s): {', '.join(unknown)}")
+    meta: dict[str, Any] = {}
+    for key in ("id", "name", "description", "vulnerability_type", "class", "chain", "harness", "remediation"):
+        if data.get(key) is not None:
+            if not isinstance(data[key], str) or not data[key].strip():
+                raise FrontMatterError(f"'{key}' must be a non-empty string")
+            meta[key] = " ".join(data[key].split())
+    if "chain" in meta:
+        try:
+            meta["chain"] = Chain.parse(meta["chain"])
+        except ValueError as e:
+            raise FrontMatterError(str(e)) from None
+    if "harness" in meta:
+        try:
+            meta["harness"] = parse_harness(meta["harness"], meta.get("chain"))
+        except ValueError as e:
+            raise FrontMatterError(str(e)) from None
+    if data.get("severity") is not None:
+        severity = str(data["severity"]).strip().lower()
+        if severity not in SEVERITIES:
+            raise FrontMatterError(f"severity must be one of {', '.join(SEVERITIES)}, got {data['severity']!r}")
+        meta["severity"] = severity
+    for key, prefix in (("cwe", "CWE"), ("swc", "SWC")):
+        if data.get(key) is not None:
+            meta[key] = _ids(data[key], prefix)
+    for key in ("references", "tags", "incidents"):
+        if data.get(key) is not None:
+            meta[key] = _strings(data[key], key)
+
+    context = data.get("context")
+    if context is not None:
+        if isinstance(context, dict):
+            variables = {str(k).upper(): " ".join(str(v or "").split()) for k, v in context.items()}
+        elif isinstance(context, list):
+            variables = {str(k).upper(): "" for k in context}
+        else:
+            raise FrontMatterError("'context' must be a list of variables or a mapping of variables to descriptions")
+        if placeholders is not None:
+            stray = [k for k in variables if k not in placeholders]
+            if stray:
+                raise FrontMatterError(f"context variable(s) not used as placeholders: {', '.join(stray)}")
+        meta["context"] = variables
+    return meta
+
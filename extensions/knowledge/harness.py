@@ -0,0 +1,99 @@
+"""
+Test harnesses PoC templates are written for.
+
+A rendered PoC is only useful if it builds where it lands, and each chain's
+test framework wants something different: Anchor and cw-multi-test PoCs are
+Rust integration tests under `tests/`, Foundry tests are `test/*.t.sol`, and
+Move PoCs are `#[test_only]` modules inside the package under test, declared
+at the package's named address and run with `sui move test` (or `aptos move
+test`). Complete `solana-program-test` exploits generated from an IDL
+(program_test.py) are integration tests run with `cargo test-sbf`, and a
+template's TypeScript target is a Mocha test under `tests/` for `anchor
+test`. Every template names its harness in front matter (default: the
+chain's), and the harness says where the rendered file goes, how to run it,
+and which values its placeholders take.
+"""
+
+import re
+from dataclasses import dataclass, field
+
+from .chains import Chain
+
+
+@dataclass(frozen=True)
+class Harness:
+    """How PoCs for one test framework are laid out and run."""
+
+    id: str
+    label: str
+    chains: tuple[Chain, ...]
+    test_dir: str
+    suffix: str
+    command: str  # "{name}" is the rendered file's stem
+    # Placeholder suffixes -> value kind (see render.py), on top of the chain's
+    kinds: dict[str, str] = field(default_factory=dict)
+    # Text a rendered PoC must contain to build under the harness
+    requires: tuple[str, ...] = ()
+
+    def test_path(self, name: str) -> str:
+        """Where a PoC named ``name`` goes, relative to the workspace or package root."""
+        return f"{self.test_dir}/{name}{self.suffix}"
+
+    def run_command(self, name: str) -> str:
+        return self.command.format(name=name)
+
+    def problems(self, text: str) -> list[str]:
+        """What keeps ``text`` from building under this harness."""
+        return [f"missing {needed!r}" for needed in self.requires if needed not in text]
+
+
+HARNESSES: dict[str, Harness] = {h.id: h for h in (
+    Harness("foundry", "Foundry", (Chain.EVM,), "test", ".t.sol", "forge test --match-path test/{name}.t.sol",
+            requires=("forge-std/Test.sol",)),
+    Harness("anchor", "Anchor (Rust tests)", (Chain.SOLANA,), "tests", ".rs", "cargo test --test {name}"),
+    Harness("anchor-ts", "Anchor (TypeScript tests)", (Chain.SOLANA,), "tests", ".ts", "anchor test",
+            requires=("@coral-xyz/anchor",)),
+    Harness("solana-program-test", "solana-program-test (BanksClient)", (Chain.SOLANA,), "tests", ".rs",
+            "cargo test-sbf --test {name}", requires=("solana_program_test",)),
+    Harness("cw-multi-test", "cw-multi-test", (Chain.COSMWASM,), "tests", ".rs", "cargo test --test {name}",
+            requires=("cw_multi_test",)),
+    Harness("sui-move-test", "sui move test", (Chain.SUI,), "tests", ".move", "sui move test {name}",
+            kinds={"_PACKAGE": "identifier", "_MODULE": "identifier", "_TYPE": "identifier",
+                   "_AMOUNT": "move_integer"},
+            requires=("#[test_only]", "#[test")),
+    Harness("aptos-move-test", "aptos move test", (Chain.APTOS,), "tests", ".move", "aptos move test --filter {name}",
+            kinds={"_PACKAGE": "identifier", "_MODULE": "identifier", "_TYPE": "identifier",
+                   "_AMOUNT": "move_integer"},
+            requires=("#[test_only]", "#[test")),
+)}
+
+_DEFAULTS = {
+    Chain.EVM: "foundry",
+    Chain.SOLANA: "anchor",
+    Chain.SUI: "sui-move-test",
+    Chain.APTOS: "aptos-move-test",
+    Chain.COSMWASM: "cw-multi-test",
+}
+
+
+def default_harness(chain: Chain | str) -> Harness:
+    """The harness a chain's templates use unless they name another."""
+    return HARNESSES[_DEFAULTS[Chain.parse(chain)]]
+
+
+def parse_harness(value: "Harness | str", chain: Chain | str | None = None) -> Harness:
+    """The harness for an id ("sui-move-test", "Foundry", ...).
+
+    Raises:
+        ValueError: If ``value`` names no harness, or one that does not run ``chain``'s code
+    """
+    if isinstance(value, Harness):
+        harness = value
+    else:
+        key = re.sub(r"[\s_]+", "-", str(value).strip().lower())
+        harness = HARNESSES.get(key)
+        if harness is None:
+            raise ValueError(f"Unknown harness '{value}' (expected one of {', '.join(HARNESSES)})")
+    if chain is not None and Chain.parse(chain) not in harness.chains:
+        raise ValueError(f"Harness '{harness.id}' does not run {Chain.parse(chain).label} tests")
+    return harness
@@ -0,0 +1,298 @@
+"""
+Anchor IDL ingestion for PoC templates.
+
+Reads an Anchor-generated IDL (`target/idl/<program>.json`, legacy or
+0.30+) and turns one of its instructions into template values, so a
+rendered PoC names the program's real `instruction::` and `accounts::`
+structs instead of the template's example ones:
+
+- ``PROGRAM_ID``: the IDL's address, when it has one
+- ``PROGRAM_NAME``: the program's name
+- ``INSTRUCTION``: the instruction, snake_case (templates use ``|camel``
+  for the structs)
+- ``ARG_FIELDS``: one `name: value,` line per argument, with a zero value
+  of its type and the IDL type in a trailing comment
+- ``ACCOUNT_FIELDS``: one `name: key,` line per account; signers and the
+  authority-like accounts the attacker stands in for take
+  `attacker.pubkey()`, known programs and sysvars their constant, fixed
+  addresses a `pubkey!`, the rest `<name>.pubkey()`
+
+For a template's TypeScript target (``typescript``), the same values are
+TypeScript: positional `program.methods` arguments (`new BN(0)` for 64-bit
+and wider integers) and camelCase `accountsStrict` fields (`attacker.publicKey`,
+`SystemProgram.programId`, `new PublicKey("...")`).
+
+Multi-line values continue the comment prefix of the line the placeholder
+is on (see ``TemplateEngine.render``). The account types' fields and the
+PDA seeds are kept too, for generated program tests (program_test.py)
+that have to set up real account state.
+"""
+
+import json
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+# Account names the attacker passes their own key for
+_AUTHORITY_NAMES = re.compile(r"^(?:\w+_)?(?:authority|owner|admin|signer|payer|user|creator|manager|operator)$")
+# Accounts with a well-known address, by name
+_KNOWN_ACCOUNTS = {
+    "system_program": "system_program::ID",
+    "token_program": "token::ID",
+    "token_2022_program": "token_2022::ID",
+    "associated_token_program": "associated_token::ID",
+    "rent": "sysvar::rent::ID",
+    "clock": "sysvar::clock::ID",
+    "instructions": "sysvar::instructions::ID",
+}
+_KNOWN_ADDRESSES = {
+    "11111111111111111111111111111111": "system_program::ID",
+    "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA": "token::ID",
+    "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb": "token_2022::ID",
+    "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL": "associated_token::ID",
+    "SysvarRent111111111111111111111111111111111": "sysvar::rent::ID",
+    "SysvarC1ock11111111111111111111111111111111": "sysvar::clock::ID",
+    "Sysvar1nstructions1111111111111111111111111": "sysvar::instructions::ID",
+}
+_INT = re.compile(r"^[ui](?:8|16|32|64|128|256)$")
+_RUST_TYPES = {"pubkey": "Pubkey", "publicKey": "Pubkey", "string": "String", "bytes": "Vec<u8>"}
+
+
+def _snake(name: str) -> str:
+    return re.sub(r"(?<=[a-z0-9])([A-Z])", r"_\1", name).lower()
+
+
+@dataclass
+class IdlAccount:
+    """One account an instruction takes."""
+
+    name: str
+    writable: bool = False
+    signer: bool = False
+    optional: bool = False
+    # Fixed address (0.30+ `address`), if the IDL pins one
+    address: str | None = None
+    pda: bool = False
+    # 0.30+ PDA seeds as written: {"kind": "const" | "account" | "arg", "value" | "path": ...}
+    seeds: list[dict[str, Any]] = field(default_factory=list)
+
+
+@dataclass
+class IdlArgument:
+    """One instruction argument, with its IDL type as written (legacy or 0.30+)."""
+
+    name: str
+    ty: Any
+
+
+@dataclass
+class IdlInstruction:
+    """One instruction of an IDL."""
+
+    name: str
+    accounts: list[IdlAccount] = field(default_factory=list)
+    args: list[IdlArgument] = field(default_factory=list)
+
+
+@dataclass
+class Idl:
+    """The parts of an Anchor IDL PoC templates use."""
+
+    name: str
+    address: str | None = None
+    instructions: dict[str, IdlInstruction] = field(default_factory=dict)
+    # Account (state) types, as written (PascalCase) -> their fields
+    account_types: dict[str, list[IdlArgument]] = field(default_factory=dict)
+
+    def instruction(self, name: str | None = None) -> IdlInstruction:
+        """The instruction ``name`` (any case), or the only one; raises ValueError naming the choices."""
+        if name is None:
+            if len(self.instructions) == 1:
+                return next(iter(self.instructions.values()))
+            raise ValueError(f"{self.name} has {len(self.instructions)} instructions; pick one of "
+                             f"{', '.join(self.instructions) or '(none)'}")
+        found = self.instructions.get(_snake(name))
+        if found is None:
+            raise ValueError(f"no instruction {name!r} in {self.name} (choose from "
+                             f"{', '.join(self.instructions) or '(none)'})")
+        return found
+
+
+def _accounts(items: list[dict[str, Any]]) -> list[IdlAccount]:
+    accounts = []
+    for a in items:
+        # Composite account groups nest their accounts
+        if "accounts" in a:
+            accounts.extend(_accounts(a["accounts"]))
+            continue
+        accounts.append(IdlAccount(
+            name=_snake(a["name"]),
+            writable=bool(a.get("writable", a.get("isMut", False))),
+            signer=bool(a.get("signer", a.get("isSigner", False))),
+            optional=bool(a.get("optional", a.get("isOptional", False))),
+            address=a.get("address"),
+            pda="pda" in a,
+            seeds=list((a.get("pda") or {}).get("seeds") or [])))
+    return accounts
+
+
+def _fields(ty: dict[str, Any] | None) -> list[IdlArgument] | None:
+    if not isinstance(ty, dict) or ty.get("kind") != "struct":
+        return None
+    return [IdlArgument(_snake(f["name"]), f["type"]) for f in ty.get("fields") or [] if isinstance(f, dict)]
+
+
+def parse_idl(data: dict[str, Any]) -> Idl:
+    """An ``Idl`` from the decoded JSON of an Anchor IDL, legacy or 0.30+."""
+    metadata = data.get("metadata") or {}
+    idl = Idl(name=metadata.get("name") or data.get("name") or "program",
+              address=data.get("address") or metadata.get("address"))
+    for ix in data.get("instructions") or []:
+        name = _snake(ix["name"])
+        idl.instructions[name] = IdlInstruction(
+            name=name, accounts=_accounts(ix.get("accounts") or []),
+            args=[IdlArgument(_snake(a["name"]), a["type"]) for a in ix.get("args") or []])
+    # Legacy IDLs give account layouts inline; 0.30+ under `types`
+    types = {t["name"]: t.get("type") for t in data.get("types") or [] if isinstance(t, dict) and "name" in t}
+    for account in data.get("accounts") or []:
+        fields = _fields(account.get("type") or types.get(account.get("name")))
+        if fields is not None:
+            idl.account_types[account["name"]] = fields
+    return idl
+
+
+def load_idl(path: Path) -> Idl:
+    """Parse the IDL at ``path``; raises ValueError if it is not an Anchor IDL."""
+    try:
+        data = json.loads(Path(path).read_text())
+    except (OSError, ValueError) as e:
+        raise ValueError(f"cannot load IDL {path}: {e}") from e
+    if not isinstance(data, dict) or not isinstance(data.get("instructions"), list):
+        raise ValueError(f"{path} is not an Anchor IDL (no instructions)")
+    return parse_idl(data)
+
+
+def rust_type(ty: Any) -> str:
+    """The Rust spelling of an IDL type (``u64``, ``Pubkey``, ``Vec<T>``, ``Option<T>``, ``[T; N]``)."""
+    if isinstance(ty, str):
+        return _RUST_TYPES.get(ty, ty)
+    if "vec" in ty:
+        return f"Vec<{rust_type(ty['vec'])}>"
+    if "option" in ty:
+        return f"Option<{rust_type(ty['option'])}>"
+    if "array" in ty:
+        return f"[{rust_type(ty['array'][0])}; {ty['array'][1]}]"
+    if "defined" in ty:
+        # Legacy IDLs name the type directly, 0.30+ in a {"name": ...} object
+        defined = ty["defined"]
+        return defined if isinstance(defined, str) else defined["name"]
+    return json.dumps(ty)
+
+
+def zero_value(ty: Any) -> str:
+    """A Rust expression of type ``ty`` to start a PoC's argument from."""
+    if isinstance(ty, str):
+        if _INT.match(ty):
+            return "0"
+        return {"bool": "false", "pubkey": "Pubkey::default()", "publicKey": "Pubkey::default()",
+                "string": "String::new()", "bytes": "vec![]"}.get(ty, "Default::default()")
+    if "vec" in ty:
+        return "vec![]"
+    if "option" in ty:
+        return "None"
+    if "array" in ty:
+        return f"[{zero_value(ty['array'][0])}; {ty['array'][1]}]"
+    return "Default::default()"
+
+
+def _account_value(account: IdlAccount) -> tuple[str, str]:
+    if account.address:
+        known = _KNOWN_ADDRESSES.get(account.address)
+        return known or f'pubkey!("{account.address}")', ""
+    if account.name in _KNOWN_ACCOUNTS:
+        return _KNOWN_ACCOUNTS[account.name], ""
+    if account.signer or _AUTHORITY_NAMES.match(account.name):
+        return "attacker.pubkey()", "Attacker, not the real one!"
+    return f"{account.name}.pubkey()", ""
+
+
+def account_fields(instruction: IdlInstruction) -> str:
+    """The ``accounts::`` struct fields of ``instruction``, one per line."""
+    lines = []
+    for account in instruction.accounts:
+        value, note = _account_value(account)
+        flags = [flag for flag, on in (("mut", account.writable), ("signer", account.signer),
+                                       ("pda", account.pda), ("optional", account.optional)) if on]
+        comment = "; ".join(part for part in (", ".join(flags), note) if part)
+        lines.append(f"{account.name}: {value},{f'  // {comment}' if comment else ''}")
+    return "\n".join(lines)
+
+
+def arg_fields(instruction: IdlInstruction) -> str:
+    """The ``instruction::`` struct fields of ``instruction``, one per line."""
+    return "\n".join(f"{a.name}: {zero_value(a.ty)},  // {rust_type(a.ty)}" for a in instruction.args)
+
+
+def _camel(name: str) -> str:
+    head, *rest = name.split("_")
+    return head + "".join(w[:1].upper() + w[1:] for w in rest)
+
+
+def ts_zero_value(ty: Any) -> str:
+    """A TypeScript value of IDL type ``ty`` for a `program.methods` argument."""
+    if isinstance(ty, str):
+        if _INT.match(ty):
+            return "new BN(0)" if int(ty[1:]) >= 64 else "0"
+        return {"bool": "false", "pubkey": "PublicKey.default", "publicKey": "PublicKey.default",
+                "string": '""', "bytes": "Buffer.alloc(0)"}.get(ty, "{}")
+    if "vec" in ty:
+        return "[]"
+    if "option" in ty:
+        return "null"
+    if "array" in ty:
+        return f"new Array({ty['array'][1]}).fill({ts_zero_value(ty['array'][0])})"
+    return "{}"
+
+
+def _ts_account_value(account: IdlAccount) -> tuple[str, str]:
+    value, note = _account_value(account)
+    address = account.address or next((a for a, expr in _KNOWN_ADDRESSES.items() if expr == value), None)
+    if address:
+        return "SystemProgram.programId" if value == "system_program::ID" else f'new PublicKey("{address}")', ""
+    if value == "attacker.pubkey()":
+        return "attacker.publicKey", note
+    return f"{_camel(account.name)}.publicKey", note
+
+
+def ts_account_fields(instruction: IdlInstruction) -> str:
+    """The ``accountsStrict`` fields of ``instruction`` for a TypeScript test, one per line."""
+    lines = []
+    for account in instruction.accounts:
+        value, note = _ts_account_value(account)
+        flags = [flag for flag, on in (("mut", account.writable), ("signer", account.signer),
+                                       ("pda", account.pda), ("optional", account.optional)) if on]
+        comment = "; ".join(part for part in (", ".join(flags), note) if part)
+        lines.append(f"{_camel(account.name)}: {value},{f'  // {comment}' if comment else ''}")
+    return "\n".join(lines)
+
+
+def ts_arg_fields(instruction: IdlInstruction) -> str:
+    """The ``program.methods`` arguments of ``instruction`` for a TypeScript test, one per line."""
+    return "\n".join(f"{ts_zero_value(a.ty)},  // {a.name}: {rust_type(a.ty)}" for a in instruction.args)
+
+
+def template_context(idl: Idl, instruction: str | None = None, typescript: bool = False) -> dict[str, str]:
+    """Template values for ``instruction`` of ``idl`` (the only one if None); raises ValueError.
+
+    With ``typescript``, the argument and account fields are written for a TypeScript target.
+    """
+    from extensions.ir.anchor_config import is_pubkey
+
+    ix = idl.instruction(instruction)
+    context = {"PROGRAM_NAME": idl.name, "INSTRUCTION": ix.name,
+               "ARG_FIELDS": ts_arg_fields(ix) if typescript else arg_fields(ix),
+               "ACCOUNT_FIELDS": ts_account_fields(ix) if typescript else account_fields(ix)}
+    if idl.address and is_pubkey(idl.address):
+        context["PROGRAM_ID"] = idl.address
+    return context
@@ -0,0 +1,158 @@
+"""
+Real-world exploit database.
+
+Each incident is a historical exploit (Wormhole, Cashio, The DAO, ...)
+recorded with its date, loss, root cause and write-ups, and linked to the
+vulnerability classes (class_loader.py) it is an instance of and the
+vulnerability types findings of it carry. Template files link incidents
+from their front matter (``incidents:``); the built-in templates, which
+have none, are linked from the incident's ``templates``. Reports cite the
+incidents related to each finding (see ``IncidentLoader.for_finding``).
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+import yaml
+
+# How much each kind of link counts towards an incident's relevance to a finding
+TEMPLATE_WEIGHT = 4
+CLASS_WEIGHT = 2
+TYPE_WEIGHT = 1
+
+
+def _normalize(name: str) -> str:
+    return re.sub(r"[\s_]+", "-", str(name).strip().lower())
+
+
+@dataclass
+class Incident:
+    """A real-world exploit."""
+    id: str
+    name: str
+    date: str  # ISO date of the exploit
+    chain: str = "evm"
+    protocol: str = ""
+    loss_usd: int | None = None  # Approximate, at the time of the exploit
+    classes: list[str] = field(default_factory=list)  # Vulnerability class ids
+    templates: list[str] = field(default_factory=list)  # Built-in PoC template ids
+    vulnerability_types: list[str] = field(default_factory=list)
+    taxonomy: list[str] = field(default_factory=list)  # Controlled tags, see taxonomy.py
+    summary: str = ""
+    root_cause: str = ""
+    references: list[str] = field(default_factory=list)
+
+    def loss(self) -> str:
+        """Loss in millions or thousands of dollars, e.g. "$326M"."""
+        if not self.loss_usd:
+            return "unknown"
+        if self.loss_usd >= 1_000_000:
+            return f"${self.loss_usd / 1_000_000:.3g}M"
+        return f"${self.loss_usd / 1_000:.3g}K"
+
+
+class IncidentLoader:
+    """Loads real-world exploits and finds the ones related to a finding."""
+
+    def __init__(self, incidents_dir: Path | None = None):
+        """Initialize loader.
+
+        Args:
+            incidents_dir: Path to incidents directory
+        """
+        if incidents_dir is None:
+            incidents_dir = Path(__file__).parent / "incidents"
+        self.incidents_dir = incidents_dir
+        self._incidents: list[Incident] = []
+        self._loaded = False
+
+    def _load(self) -> None:
+        """Load all incidents, newest first."""
+        if self._loaded:
+            return
+
+        if self.incidents_dir.exists():
+            for yaml_file in sorted(self.incidents_dir.glob("*.yaml")):
+                try:
+                    with open(yaml_file) as f:
+                        data = yaml.safe_load(f)
+                    file_chain = (data or {}).get("chain", "evm")
+                    for entry in (data or {}).get("incidents", []):
+                        self._incidents.append(Incident(
+                            id=entry["id"],
+                            name=entry.get("name", entry["id"]),
+                            date=str(entry["date"]),
+                            chain=entry.get("chain", file_chain),
+                            protocol=entry.get("protocol", ""),
+                            loss_usd=entry.get("loss_usd"),
+                            classes=entry.get("classes", []),
+                            templates=entry.get("templates", []),
+                            vulnerability_types=[_normalize(t) for t in entry.get("vulnerability_types", [])],
+                            taxonomy=entry.get("taxonomy", []),
+                            summary=" ".join(entry.get("summary", "").split()),
+                            root_cause=" ".join(entry.get("root_cause", "").split()),
+                            references=entry.get("references", []),
+                        ))
+                except Exception as e:
+                    print(f"[!] Failed to load incidents from {yaml_file}: {e}")
+
+        self._incidents.sort(key=lambda i: i.date, reverse=True)
+        self._loaded = True
+
+    def get(self, incident_id: str) -> Incident | None:
+        """Get an incident by ID."""
+        self._load()
+        return next((i for i in self._incidents if i.id == incident_id), None)
+
+    def list_all(self) -> list[Incident]:
+        """Get all incidents, newest first."""
+        self._load()
+        return list(self._incidents)
+
+    def get_by_chain(self, chain_id: str) -> list[Incident]:
+        """Get incidents on a chain (e.g., "solana")."""
+        return [i for i in self.list_all() if i.chain.lower() == chain_id.lower()]
+
+    def for_class(self, class_id: str) -> list[Incident]:
+        """Incidents that are instances of a vulnerability class."""
+        return [i for i in self.list_all() if class_id in i.classes]
+
+    def for_template(self, template: Any) -> list[Incident]:
+        """Incidents a PoC template reproduces: those its front matter names, then those naming it."""
+        named = [self.get(i) for i in template.incidents]
+        linked = [i for i in self.list_all() if template.id in i.templates and i.id not in template.incidents]
+        return [i for i in named if i] + linked
+
+    def for_finding(self, finding: dict[str, Any], chain: str | None = None, vuln_class: str | None = None,
+                    template: Any = None, limit: int | None = 3) -> list[Incident]:
+        """Historical exploits related to a finding, most related first.
+
+        An incident linked to the finding's PoC template counts most, then
+        one of the finding's class, then one sharing only its vulnerability
+        type. Among equally related incidents, those on the finding's chain
+        and those with the larger loss come first.
+
+        Args:
+            finding: Hypothesis or detector hit, with its ``vulnerability_type``
+            chain: Chain of the audited code
+            vuln_class: Class id the finding resolved to (see ClassLoader.resolve)
+            template: The finding's PoCTemplate
+            limit: Most incidents to return (None: all)
+        """
+        vuln_type = _normalize(finding.get("vulnerability_type") or "")
+        linked = {i.id for i in self.for_template(template)} if template else set()
+        ranked = []
+        for incident in self.list_all():
+            score = (
+                TEMPLATE_WEIGHT * (incident.id in linked)
+                + CLASS_WEIGHT * (vuln_class in incident.classes)
+                + TYPE_WEIGHT * (bool(vuln_type) and vuln_type in incident.vulnerability_types)
+            )
+            if score:
+                other_chain = bool(chain) and incident.chain.lower() != chain.lower()
+                ranked.append(((-score, other_chain, -(incident.loss_usd or 0), incident.id), incident))
+        ranked.sort(key=lambda r: r[0])
+        incidents = [incident for _, incident in ranked]
+        return incidents[:limit] if limit is not None else incidents
@@ -0,0 +1,141 @@
+"""
+Third-party integration requirements loader.
+
+Each integration (an oracle, governance program, bridge or DEX other
+programs build on) lists the validation its documentation requires of
+integrators: which accounts to pin or owner-check, which magic numbers
+and version fields to verify, which reads need staleness bounds. The
+`integrations` static checker matches programs against them.
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+
+import yaml
+
+
+@dataclass
+class IntegrationRequirement:
+    """One documented validation requirement."""
+    id: str
+    title: str
+    vulnerability_type: str
+    severity: str
+    description: str
+    # "instruction" (handler and accounts) or "program" (any source file)
+    scope: str = "instruction"
+    when: list[str] = field(default_factory=list)
+    forbid: list[str] = field(default_factory=list)
+    require: list[str] = field(default_factory=list)
+    # Declarations of the accounts the requirement is checked for, how the instruction must use them
+    # (`{account}` is the account's name) to count, and patterns their declarations can meet it with
+    accounts: str | None = None
+    account_use: list[str] = field(default_factory=list)
+    require_account: list[str] = field(default_factory=list)
+
+
+@dataclass
+class Integration:
+    """A third-party program and what integrating with it requires."""
+    id: str
+    name: str
+    category: str
+    docs: str
+    detect: list[str]
+    requirements: list[IntegrationRequirement]
+    chain: str = "solana"
+
+    def requirement(self, requirement_id: str) -> IntegrationRequirement | None:
+        for req in self.requirements:
+            if req.id == requirement_id:
+                return req
+        return None
+
+
+def _check_patterns(integration: Integration) -> None:
+    """Raise re.error naming the requirement for any pattern that does not compile."""
+    patterns = [("detect", p) for p in integration.detect]
+    for req in integration.requirements:
+        patterns += [(req.id, p) for p in req.when + req.forbid + req.require + req.account_use + req.require_account]
+        if req.accounts:
+            patterns.append((req.id, req.accounts))
+    for where, pattern in patterns:
+        try:
+            re.compile(pattern.replace("{account}", "account"))
+        except re.error as e:
+            raise re.error(f"{integration.id} {where}: {e}") from None
+
+
+class IntegrationLoader:
+    """Loads and queries integration requirements."""
+
+    def __init__(self, integrations_dir: Path | None = None):
+        """Initialize loader.
+
+        Args:
+            integrations_dir: Path to integrations directory
+        """
+        if integrations_dir is None:
+            integrations_dir = Path(__file__).parent / "integrations"
+        self.integrations_dir = integrations_dir
+        self._integrations: dict[str, Integration] = {}
+        self._loaded = False
+
+    def _load(self) -> None:
+        """Load all integrations."""
+        if self._loaded:
+            return
+
+        if self.integrations_dir.exists():
+            for yaml_file in sorted(self.integrations_dir.glob("*.yaml")):
+                try:
+                    with open(yaml_file) as f:
+                        data = yaml.safe_load(f)
+                    file_chain = (data or {}).get("chain", "solana")
+                    for entry in (data or {}).get("integrations", []):
+                        integration = Integration(
+                            id=entry["id"],
+                            name=entry.get("name", entry["id"]),
+                            category=entry.get("category", "integration"),
+                            docs=entry.get("docs", ""),
+                            detect=entry.get("detect", []),
+                            requirements=[
+                                IntegrationRequirement(
+                                    id=req["id"],
+                                    title=req["title"],
+                                    vulnerability_type=req.get("vulnerability_type", f"{entry['id']}-integration"),
+                                    severity=req.get("severity", "medium"),
+                                    description=" ".join(req.get("description", "").split()),
+                                    scope=req.get("scope", "instruction"),
+                                    when=req.get("when", []),
+                                    forbid=req.get("forbid", []),
+                                    require=req.get("require", []),
+                                    accounts=req.get("accounts"),
+                                    account_use=req.get("account_use", []),
+                                    require_account=req.get("require_account", []),
+                                )
+                                for req in entry.get("requirements", [])
+                            ],
+                            chain=entry.get("chain", file_chain),
+                        )
+                        _check_patterns(integration)
+                        self._integrations[integration.id] = integration
+                except Exception as e:
+                    print(f"[!] Failed to load integrations from {yaml_file}: {e}")
+
+        self._loaded = True
+
+    def list_all(self) -> list[Integration]:
+        """Get all integrations."""
+        self._load()
+        return list(self._integrations.values())
+
+    def get(self, integration_id: str) -> Integration | None:
+        """Get an integration by ID."""
+        self._load()
+        return self._integrations.get(integration_id)
+
+    def get_by_chain(self, chain_id: str) -> list[Integration]:
+        """Get integrations for a chain (e.g., "solana")."""
+        return [i for i in self.list_all() if i.chain.lower() == chain_id.lower()]
@@ -1,16 +1,22 @@
 """
 Knowledge base manager.
 
-Unified interface for accessing checklists, templates, and tips.
+Unified interface for accessing checklists, templates, tips, the
+vulnerability classes they hang off and the real-world exploits of them.
 """
 
 from pathlib import Path
-from dataclasses import dataclass
+from dataclasses import dataclass, field
 from typing import Any
 
 from .checklist_loader import ChecklistLoader, ChecklistItem
+from .class_loader import ClassLoader, VulnerabilityClass
+from .incident_loader import Incident, IncidentLoader
 from .template_loader import TemplateLoader, PoCTemplate
+from .template_registry import TemplateRegistry
+from .search import SearchHit, TemplateIndex
 from .tip_loader import TipLoader, AuditorTip
+from .taxonomy import facet_counts, filter_by_tags
 
 
 @dataclass
@@ -19,11 +25,38 @@ class KnowledgeQuery:
     checklists: list[ChecklistItem]
     templates: list[PoCTemplate]
     tips: list[AuditorTip]
+    classes: list[VulnerabilityClass] = field(default_factory=list)
+    chain: str | None = None
+
+    def filter(self, tags: list[str], match_all: bool = False) -> "KnowledgeQuery":
+        """Narrow the result to entries carrying any (or all) of the given taxonomy tags."""
+        return KnowledgeQuery(
+            checklists=filter_by_tags(self.checklists, tags, match_all),
+            templates=filter_by_tags(self.templates, tags, match_all),
+            tips=filter_by_tags(self.tips, tags, match_all),
+            classes=filter_by_tags(self.classes, tags, match_all),
+            chain=self.chain,
+        )
+
+    def facets(self) -> dict[str, int]:
+        """Number of matching entries per taxonomy tag."""
+        return facet_counts([*self.checklists, *self.templates, *self.tips])
 
     def to_context(self) -> str:
         """Convert to context string for LLM prompts."""
         parts = []
 
+        for vuln_class in self.classes[:2]:
+            parts.append(f"## Vulnerability Class: {vuln_class.name}")
+            parts.append(vuln_class.describe(self.chain))
+            if vuln_class.impact:
+                parts.append(f"Impact: {vuln_class.impact}")
+            parts.append(f"Fix: {vuln_class.fix_for(self.chain)}")
+            others = [f"{v.name} ({v.chain})" for v in vuln_class.variants.values() if v.chain != self.chain]
+            if others:
+                parts.append(f"Same class elsewhere: {'; '.join(others)}")
+            parts.append("")
+
         if self.checklists:
             parts.append("## Relevant Checklist Items")
             for item in self.checklists[:10]:
@@ -47,19 +80,32 @@ class KnowledgeQuery:
 class KnowledgeBase:
     """Unified knowledge base for smart contract security auditing."""
 
-    def __init__(self, base_dir: Path | None = None):
+    def __init__(self, base_dir: Path | None = None, locale: str | None = None, bundles: list | None = None,
+                 template_dirs: list[Path] | None = None):
         """Initialize knowledge base.
 
         Args:
             base_dir: Path to knowledge base directory
+            locale: Language of descriptions and fix guidance (en, zh, es, ja); untranslated entries stay English
+            bundles: Verified content bundles (see ``bundles.resolve_bundles``) whose checklists, templates
+                and tips are loaded on top of the built-in ones
+            template_dirs: The user's own template roots (see ``user_template_dirs``), loaded after the bundles
         """
         if base_dir is None:
             base_dir = Path(__file__).parent
 
         self.base_dir = base_dir
-        self.checklists = ChecklistLoader(base_dir)
-        self.templates = TemplateLoader(base_dir / "templates")
-        self.tips = TipLoader(base_dir / "tips")
+        self.locale = locale
+        self.bundles = list(bundles or [])
+        roots = [b.root for b in self.bundles]
+        self.checklists = ChecklistLoader(base_dir, locale=locale, extra_dirs=[r / "checklists" for r in roots])
+        self.classes = ClassLoader(base_dir / "classes", locale=locale)
+        self.templates = TemplateLoader(base_dir / "templates", classes=self.classes,
+                                        extra_dirs=[r / "templates" for r in roots], user_dirs=template_dirs)
+        self.registry = TemplateRegistry(self.templates)
+        self.tips = TipLoader(base_dir / "tips", locale=locale, extra_dirs=[r / "tips" for r in roots])
+        self.incidents = IncidentLoader(base_dir / "incidents")
+        self._index: TemplateIndex | None = None
 
     def query(
         self,
@@ -68,6 +114,8 @@ class KnowledgeBase:
         include_templates: bool = True,
         include_tips: bool = True,
         chain: str | None = None,
+        tags: list[str] | None = None,
+        match_all: bool = False,
     ) -> KnowledgeQuery:
         """Query the knowledge base.
 
@@ -77,6 +125,8 @@ class KnowledgeBase:
             include_templates: Include template results
             include_tips: Include tip results
             chain: Optional chain filter (e.g., "evm", "solana", "sui")
+            tags: Optional taxonomy tag filter (e.g., ["oracle", "economic"])
+            match_all: Require every tag instead of any
 
         Returns:
             KnowledgeQuery with matching items
@@ -104,11 +154,37 @@ class KnowledgeBase:
             if tips:
                 tips = [t for t in tips if t.chain.lower() == chain_lower]
 
-        return KnowledgeQuery(
+        vuln_class = self.classes.resolve(search_term, chain)
+        if vuln_class and include_templates:
+            ids = {t.id for t in templates}
+            templates += [t for t in self.templates_for_class(vuln_class.id, chain) if t.id not in ids]
+
+        result = KnowledgeQuery(
             checklists=checklists,
             templates=templates,
             tips=tips,
+            classes=[vuln_class] if vuln_class else [],
+            chain=chain,
         )
+        return result.filter(tags, match_all) if tags else result
+
+    def search(self, query: str, chain: str | None = None, tags: list[str] | None = None,
+               match_all: bool = False, limit: int | None = 10) -> list[SearchHit]:
+        """PoC templates ranked by how well their titles, descriptions and fixes match ``query``.
+
+        Args:
+            query: Words, "quoted phrases" and ``tag:``/``chain:``/``severity:`` filters (see search.py)
+            chain: Optional chain filter (e.g., "evm", "solana", "sui")
+            tags: Optional taxonomy tag filter (e.g., ["oracle", "economic"])
+            match_all: Require every tag instead of any
+            limit: Most matches to return (None: all)
+
+        Raises:
+            ValueError: If the query or filters name an unknown tag, chain or severity
+        """
+        if self._index is None:
+            self._index = TemplateIndex(self.templates.list_all(), self.classes)
+        return self._index.search(query, limit=limit, chain=chain, tags=tags or (), match_all=match_all)
 
     def get_audit_context(self, vulnerability_type: str, chain: str | None = None) -> str:
         """Get context for auditing a specific vulnerability type.
@@ -184,17 +260,68 @@ class KnowledgeBase:
             Rendered template string or None
         """
         templates = self.templates.get_by_vulnerability(vulnerability_type)
+        if not templates:
+            vuln_class = self.classes.resolve(vulnerability_type)
+            templates = self.templates_for_class(vuln_class.id) if vuln_class else []
         if not templates:
             return None
 
         return self.templates.render(templates[0].id, **kwargs)
 
+    def resolve_class(self, vulnerability_type: str, chain: str | None = None) -> VulnerabilityClass | None:
+        """Chain-agnostic class of a hypothesis or detector vulnerability type."""
+        return self.classes.resolve(vulnerability_type, chain)
+
+    def templates_for_class(self, class_id: str, chain: str | None = None) -> list[PoCTemplate]:
+        """Templates of a class, preferring the chain's own and falling back to other chains'."""
+        if chain:
+            own = self.templates.get_by_class(class_id, chain)
+            if own:
+                return own
+        return self.templates.get_by_class(class_id)
+
+    def related_incidents(self, finding: dict[str, Any], chain: str | None = None,
+                          limit: int | None = 3) -> list[Incident]:
+        """Real-world exploits like a finding, through its class and PoC template (see IncidentLoader.for_finding).
+
+        Args:
+            finding: Hypothesis or detector hit, with its ``vulnerability_type``
+            chain: Chain of the audited code
+            limit: Most incidents to return (None: all)
+        """
+        vuln_type = finding.get("vulnerability_type") or ""
+        vuln_class = self.classes.resolve(vuln_type, chain) if vuln_type else None
+        template = self.templates.get((finding.get("properties") or {}).get("poc_template") or "")
+        if template is None and vuln_class:
+            template = next(iter(self.templates_for_class(vuln_class.id, chain)), None)
+        return self.incidents.for_finding(finding, chain, vuln_class.id if vuln_class else None, template, limit)
+
+    def browse(self, tags: list[str] | None = None, match_all: bool = False,
+               chain: str | None = None) -> KnowledgeQuery:
+        """Every entry, optionally narrowed by taxonomy tags and chain."""
+        result = KnowledgeQuery(
+            checklists=self.checklists.get_all(),
+            templates=self.templates.list_all(),
+            tips=self.tips.get_all(),
+        )
+        if chain:
+            chain_lower = chain.lower()
+            result = KnowledgeQuery(
+                checklists=[c for c in result.checklists if c.chain.lower() == chain_lower],
+                templates=[t for t in result.templates if t.chain.lower() == chain_lower],
+                tips=[t for t in result.tips if t.chain.lower() == chain_lower],
+            )
+        return result.filter(tags, match_all) if tags else result
+
     def stats(self) -> dict[str, Any]:
         """Get knowledge base statistics."""
         return {
             "checklists": self.checklists.stats(),
             "templates": len(self.templates.list_all()),
             "tips": len(self.tips.get_all()),
+            "classes": len(self.classes.list_all()),
+            "incidents": len(self.incidents.list_all()),
+            "taxonomy": self.browse().facets(),
         }
 
     def list_categories(self) -> dict[str, list[str]]:
@@ -0,0 +1,102 @@
+"""
+Protocol parameter sanity ranges loader.
+
+Each entry names a kind of hardcoded protocol parameter (fees, LTV ratios,
+cooldowns, max supply, ...), how to recognise it by name, its unit
+(``ratio``, ``duration`` or ``amount``) and the range a sane value falls
+in. The `protocol-params` static pass checks extracted values against
+them.
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+
+import yaml
+
+UNITS = ("ratio", "duration", "amount")
+
+
+@dataclass
+class ParameterRange:
+    """A kind of protocol parameter and its sane range."""
+    id: str
+    name: str
+    unit: str
+    match: list[str]
+    exclude: list[str] = field(default_factory=list)
+    min: float | None = None
+    max: float | None = None
+    severity: str = "medium"
+    description: str = ""
+
+    def matches(self, name: str) -> bool:
+        """Whether a snake_case parameter name is of this kind."""
+        return any(re.search(p, name) for p in self.match) and not any(re.search(p, name) for p in self.exclude)
+
+    def contains(self, value: float) -> bool:
+        return (self.min is None or value >= self.min) and (self.max is None or value <= self.max)
+
+
+class ParameterLoader:
+    """Loads protocol parameter ranges."""
+
+    def __init__(self, parameters_dir: Path | None = None):
+        """Initialize loader.
+
+        Args:
+            parameters_dir: Path to parameters directory
+        """
+        if parameters_dir is None:
+            parameters_dir = Path(__file__).parent / "parameters"
+        self.parameters_dir = parameters_dir
+        self._ranges: list[ParameterRange] = []
+        self._loaded = False
+
+    def _load(self) -> None:
+        """Load all ranges, in file order (the first kind a name matches wins)."""
+        if self._loaded:
+            return
+
+        if self.parameters_dir.exists():
+            for yaml_file in sorted(self.parameters_dir.glob("*.yaml")):
+                try:
+                    with open(yaml_file) as f:
+                        data = yaml.safe_load(f)
+                    for entry in (data or {}).get("parameters", []):
+                        bounds = entry.get("range") or {}
+                        kind = ParameterRange(
+                            id=entry["id"],
+                            name=entry.get("name", entry["id"]),
+                            unit=entry.get("unit", "amount"),
+                            match=entry.get("match", []),
+                            exclude=entry.get("exclude", []),
+                            min=bounds.get("min"),
+                            max=bounds.get("max"),
+                            severity=entry.get("severity", "medium"),
+                            description=" ".join(entry.get("description", "").split()),
+                        )
+                        if kind.unit not in UNITS:
+                            raise ValueError(f"{kind.id}: unknown unit '{kind.unit}'")
+                        for pattern in kind.match + kind.exclude:
+                            re.compile(pattern)
+                        self._ranges.append(kind)
+                except Exception as e:
+                    print(f"[!] Failed to load parameter ranges from {yaml_file}: {e}")
+
+        self._loaded = True
+
+    def get(self, parameter_id: str) -> ParameterRange | None:
+        """Get a range by ID."""
+        self._load()
+        return next((r for r in self._ranges if r.id == parameter_id), None)
+
+    def classify(self, name: str) -> ParameterRange | None:
+        """The first kind a snake_case parameter name matches."""
+        self._load()
+        return next((r for r in self._ranges if r.matches(name)), None)
+
+    def list_all(self) -> list[ParameterRange]:
+        """Get all ranges."""
+        self._load()
+        return list(self._ranges)
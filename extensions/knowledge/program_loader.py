@@ -0,0 +1,109 @@
+"""
+Known program fingerprints loader.
+
+Each entry is a program other programs commonly depend on (SPL Token,
+Token-2022, Metaplex, oracles, DEXs, ...): its canonical deployment
+address, any other addresses it is legitimately deployed at, and the names
+code refers to it by. The `program-ids` static checker matches hardcoded
+program ids against them to catch lookalike and forked ids.
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+
+import yaml
+
+
+@dataclass
+class KnownProgram:
+    """A widely used on-chain program and where it is deployed."""
+    id: str
+    name: str
+    program_id: str
+    category: str = "program"
+    # Other canonical addresses (older versions, other clusters)
+    also: list[str] = field(default_factory=list)
+    match: list[str] = field(default_factory=list)
+    exclude: list[str] = field(default_factory=list)
+    docs: str = ""
+    chain: str = "solana"
+
+    @property
+    def addresses(self) -> list[str]:
+        return [self.program_id, *self.also]
+
+    def matches(self, name: str) -> bool:
+        """Whether a snake_case identifier refers to this program."""
+        return any(re.search(p, name) for p in self.match) and not any(re.search(p, name) for p in self.exclude)
+
+
+class ProgramLoader:
+    """Loads and queries known program fingerprints."""
+
+    def __init__(self, programs_dir: Path | None = None):
+        """Initialize loader.
+
+        Args:
+            programs_dir: Path to programs directory
+        """
+        if programs_dir is None:
+            programs_dir = Path(__file__).parent / "programs"
+        self.programs_dir = programs_dir
+        self._programs: list[KnownProgram] = []
+        self._loaded = False
+
+    def _load(self) -> None:
+        """Load all programs, in file order (the first program a name matches wins)."""
+        if self._loaded:
+            return
+
+        if self.programs_dir.exists():
+            for yaml_file in sorted(self.programs_dir.glob("*.yaml")):
+                try:
+                    with open(yaml_file) as f:
+                        data = yaml.safe_load(f)
+                    file_chain = (data or {}).get("chain", "solana")
+                    for entry in (data or {}).get("programs", []):
+                        program = KnownProgram(
+                            id=entry["id"],
+                            name=entry.get("name", entry["id"]),
+                            program_id=str(entry["program_id"]),
+                            category=entry.get("category", "program"),
+                            also=[str(a) for a in entry.get("also", [])],
+                            match=entry.get("match", []),
+                            exclude=entry.get("exclude", []),
+                            docs=entry.get("docs", ""),
+                            chain=entry.get("chain", file_chain),
+                        )
+                        for pattern in program.match + program.exclude:
+                            re.compile(pattern)
+                        self._programs.append(program)
+                except Exception as e:
+                    print(f"[!] Failed to load known programs from {yaml_file}: {e}")
+
+        self._loaded = True
+
+    def get(self, program_id: str) -> KnownProgram | None:
+        """Get a program by its knowledge base ID."""
+        self._load()
+        return next((p for p in self._programs if p.id == program_id), None)
+
+    def by_address(self, address: str) -> KnownProgram | None:
+        """The program deployed at ``address``, if it is a known one."""
+        self._load()
+        return next((p for p in self._programs if address in p.addresses), None)
+
+    def classify(self, name: str) -> KnownProgram | None:
+        """The first program a snake_case identifier refers to."""
+        self._load()
+        return next((p for p in self._programs if p.matches(name)), None)
+
+    def list_all(self) -> list[KnownProgram]:
+        """Get all programs."""
+        self._load()
+        return list(self._programs)
+
+    def get_by_chain(self, chain_id: str) -> list[KnownProgram]:
+        """Get programs for a chain (e.g., "solana")."""
+        return [p for p in self.list_all() if p.chain.lower() == chain_id.lower()]
@@ -0,0 +1,205 @@
+"""
+Complete `solana-program-test` exploit tests.
+
+Rendered PoC templates sketch the exploit in comments for an auditor to
+finish. ``generate_program_test`` writes the finished form instead: a
+`BanksClient` integration test for one instruction of an Anchor IDL that
+builds and runs with `cargo test-sbf`:
+
+1. setup: the program loaded from its built `.so` under its declared id
+2. funding: a funded attacker, and each account the instruction takes;
+   accounts whose name matches an IDL account type start from that type's
+   serialized state, PDAs are derived from their IDL seeds where those
+   are constants, accounts or arguments
+3. the malicious transaction: the instruction with the attacker in every
+   signer and authority position, signed by the attacker alone
+4. the assertion: the transaction goes through, so the test passes while
+   the program is vulnerable and fails once the template's fix is in
+
+The template supplies the vulnerability, the explanation and the fix for
+the header; the IDL supplies everything that has to compile. For a target
+cluster, the test deactivates the feature gates that cluster does not have
+(solana-program-test enables every feature), so an exploit that relies on
+a missing syscall fails as it would there.
+"""
+
+import json
+import re
+import textwrap
+from typing import Any
+
+from .chains import Chain
+from .feature_loader import ClusterFeatures
+from .idl import _KNOWN_ADDRESSES, Idl, IdlAccount, IdlArgument, _account_value, _snake, rust_type, zero_value
+from .render import FILTERS
+from .template_loader import PoCTemplate
+
+# What the program crate needs under [dev-dependencies]
+DEV_DEPENDENCIES = ('solana-program-test = "~2.1"', 'solana-sdk = "~2.1"',
+                    'tokio = { version = "1", features = ["macros"] }')
+
+_SYSTEM = "11111111111111111111111111111111"
+# `template_context`'s names for well-known accounts -> their address
+_ADDRESSES = {expr: address for address, expr in _KNOWN_ADDRESSES.items()}
+# Variables the test body uses itself
+_RESERVED = {"test", "attacker", "banks", "payer", "recent_blockhash", "ix", "tx", "result"}
+
+
+def _camel(name: str) -> str:
+    return FILTERS["camel"](name)
+
+
+def _binding(name: str) -> str:
+    return f"{name}_account" if name in _RESERVED else name
+
+
+def _state_value(field: IdlArgument) -> str:
+    if field.ty in ("pubkey", "publicKey"):
+        # Someone other than the attacker: the real authority, mint, ...
+        return "Pubkey::new_unique()"
+    return zero_value(field.ty)
+
+
+def _seed(seed: dict[str, Any], keys: dict[str, str], args: dict[str, IdlArgument]) -> str | None:
+    """A Rust seed expression, or None if the seed needs more than the IDL says."""
+    kind = seed.get("kind")
+    if kind == "const" and isinstance(seed.get("value"), list):
+        value = bytes(seed["value"])
+        if value and all(0x20 <= b < 0x7f and b not in b'"\\' for b in value):
+            return f'b"{value.decode()}"'
+        return f"&{json.dumps(list(value)).replace(' ', '')}"
+    path = _snake(str(seed.get("path", "")))
+    if kind == "account" and path in keys:
+        return f"{keys[path]}.as_ref()"
+    if kind == "arg" and path in args:
+        if rust_type(args[path].ty) == "Pubkey":
+            return f"{path}.as_ref()"
+        if re.fullmatch(r"[ui](?:8|16|32|64|128)", rust_type(args[path].ty)):
+            return f"&{path}.to_le_bytes()"
+    return None
+
+
+def _account_setup(account: IdlAccount, idl: Idl, crate: str, keys: dict[str, str],
+                   args: dict[str, IdlArgument]) -> tuple[list[str], str]:
+    """Lines binding ``account`` (added to the test's genesis) and the expression of its key."""
+    value, _ = _account_value(account)
+    if account.optional:
+        return [], "None"
+    if value == "attacker.pubkey()":
+        return [], value
+    address = account.address or _ADDRESSES.get(value)
+    if address:
+        return [], "system_program::ID" if address == _SYSTEM else f'solana_sdk::pubkey!("{address}")'
+    name = _binding(account.name)
+    state = next((t for t in idl.account_types if _snake(t) == account.name), None)
+    if account.pda:
+        seeds = [_seed(s, keys, args) for s in account.seeds]
+        if account.seeds and None not in seeds:
+            lines = [f"let ({name}, _) = Pubkey::find_program_address(&[{', '.join(seeds)}], &PROGRAM_ID);"]
+        else:
+            lines = [f"// TODO: derive `{account.name}` from its seeds",
+                     f"let {name} = Pubkey::new_unique();"]
+        key = name
+    else:
+        lines = [f"let {name} = Keypair::new();"]
+        key = f"{name}.pubkey()"
+    if state:
+        lines.append(f"test.add_account({key}, program_account(&{crate}::{state} {{")
+        lines += [f"    {f.name}: {_state_value(f)},  // {rust_type(f.ty)}" for f in idl.account_types[state]]
+        lines.append("}));")
+    elif account.writable or not account.pda:
+        lines.append(f"test.add_account({key}, Account {{ lamports: LAMPORTS_PER_SOL, ..Account::default() }});")
+    return lines, key
+
+
+def generate_program_test(template: PoCTemplate, idl: Idl, instruction: str | None = None,
+                          cluster: str | None = None) -> str:
+    """A `solana-program-test` exploit test for ``instruction`` of ``idl`` (the only one if None).
+
+    With ``cluster``, the runtime only has the feature gates active there.
+
+    Raises:
+        ValueError: If the template is not a Solana one, or the IDL has no such instruction
+    """
+    if template.chain != Chain.SOLANA:
+        raise ValueError(f"{template.id} is a {template.chain.label} template; program tests are for Solana")
+    ix = idl.instruction(instruction)
+    crate = _snake(idl.name)
+    struct = _camel(ix.name)
+    args = {a.name: a for a in ix.args}
+
+    body = [f'// 1. Program setup: loads {crate}.so (target/deploy, or $SBF_OUT_DIR under `cargo test-sbf`)',
+            f'let mut test = ProgramTest::new("{crate}", PROGRAM_ID, None);']
+    features = ClusterFeatures(cluster) if cluster else None
+    if features and features.inactive():
+        body.append(f"// Runtime features as on {features.cluster} ({features.source}): "
+                    "solana-program-test enables them all")
+        body += [f'test.deactivate_feature(solana_sdk::pubkey!("{gate.feature}"));  // {gate.id}'
+                 for gate in features.inactive()]
+    body += ["",
+             "// 2. Accounts: a funded attacker and the state the exploit starts from",
+             "let attacker = Keypair::new();",
+             "test.add_account(attacker.pubkey(), Account { lamports: 10 * LAMPORTS_PER_SOL, ..Account::default() });"]
+    body += [f"let {a.name}: {rust_type(a.ty)} = {zero_value(a.ty)};" for a in ix.args]
+    keys: dict[str, str] = {}
+    fields = []
+    # PDAs last, so their seeds can name the other accounts
+    for account in sorted(ix.accounts, key=lambda a: a.pda):
+        lines, keys[account.name] = _account_setup(account, idl, crate, keys, args)
+        body += lines
+    for account in ix.accounts:
+        _, note = _account_value(account)
+        key = keys[account.name]
+        field = account.name if key == account.name else f"{account.name}: {key}"
+        fields.append(f"{field},{f'  // {note}' if note else ''}")
+
+    body += ["let (banks, _payer, recent_blockhash) = test.start().await;", "",
+             f"// 3. The malicious transaction: `{ix.name}` signed by the attacker alone",
+             "let ix = Instruction {",
+             "    program_id: PROGRAM_ID,",
+             f"    accounts: accounts::{struct} {{"]
+    body += [f"        {line}" for line in fields]
+    body += ["    }",
+             "    .to_account_metas(None),",
+             f"    data: instruction::{struct} {{ {', '.join(args)} }}.data()," if args
+             else f"    data: instruction::{struct} {{}}.data(),",
+             "};",
+             "let tx = Transaction::new_signed_with_payer(&[ix], Some(&attacker.pubkey()), &[&attacker], "
+             "recent_blockhash);", "",
+             "// 4. The program should reject this; accepting it is the finding",
+             "let result = banks.process_transaction(tx).await;",
+             f'assert!(result.is_ok(), "{ix.name} rejected the exploit (fixed?): {{result:?}}");']
+    code = "\n".join(body)
+
+    sdk = [item for item, used in (
+        ("account::Account", True), ("instruction::Instruction", True), ("native_token::LAMPORTS_PER_SOL", True),
+        ("pubkey::Pubkey", "Pubkey" in code), ("rent::Rent", "program_account(" in code),
+        ("signature::{Keypair, Signer}", True), ("system_program", "system_program::" in code),
+        ("transaction::Transaction", True)) if used]
+    anchor = ["AccountSerialize", "InstructionData", "ToAccountMetas"] if "program_account(" in code \
+        else ["InstructionData", "ToAccountMetas"]
+    name = template.name or template.id
+    header = [f"// Generated by Baskerville from the `{template.id}` PoC template and the {idl.name} IDL.", "//",
+              f"// {name}{f': {template.description}' if template.description else ''}", "//",
+              f"// Sends `{ix.name}` as an attacker and passes while the program accepts it."]
+    if template.remediation:
+        header += [f"// {line}" for line in textwrap.wrap(f"Fix: {template.remediation}", 96)]
+    header += ["//", "// Build the program first (`anchor build` or `cargo build-sbf`). The program crate needs",
+               "// in [dev-dependencies]:"]
+    header += [f"//   {dep}" for dep in DEV_DEPENDENCIES]
+    lines = header + ["", f"use anchor_lang::{{{', '.join(anchor)}}};", "use solana_program_test::ProgramTest;",
+                      "use solana_sdk::{"]
+    lines += [f"    {item}," for item in sdk]
+    lines += ["};", "", f"use {crate}::{{accounts, instruction, ID as PROGRAM_ID}};", ""]
+    if "program_account(" in code:
+        lines += ["/// An account holding `state`, owned by the program under test.",
+                  "fn program_account<T: AccountSerialize>(state: &T) -> Account {",
+                  "    let mut data = Vec::new();",
+                  '    state.try_serialize(&mut data).expect("serialize account state");',
+                  "    let lamports = Rent::default().minimum_balance(data.len()).max(LAMPORTS_PER_SOL);",
+                  "    Account { lamports, data, owner: PROGRAM_ID, ..Account::default() }",
+                  "}", ""]
+    lines += ["#[tokio::test]", f"async fn exploit_{_snake(template.id)}() {{"]
+    lines += [f"    {line}" if line else "" for line in code.split("\n")]
+    lines.append("}")
+    return "\n".join(lines) + "\n"
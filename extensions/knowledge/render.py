@@ -0,0 +1,223 @@
+"""
+PoC template rendering.
+
+Templates mark what an audit fills in with placeholders:
+
+- ``{{NAME}}``: a required value
+- ``{{NAME=default}}``: a value that falls back to ``default``
+- ``{{NAME|filter}}``: a value passed through a filter first; ``pubkey``
+  (a Rust `Pubkey` literal), ``str`` (a Rust string literal), ``snake``
+  and ``camel`` (instruction names as handlers and as `instruction::`
+  structs), ``lower_camel`` (as TypeScript `program.methods`), ``upper``
+  and ``lower``
+
+``TemplateEngine`` parses them, checks a context map against them (every
+required value given; program ids and accounts valid base58 pubkeys,
+instruction names identifiers and amounts integers, going by the
+placeholder's suffix and the template's test harness; Move amounts are
+plain literals like `1000` or `1000u64`) and renders the template into a
+test file. A multi-line value continues the indentation and comment
+markers its placeholder is preceded by on every line, so a block of
+struct fields lands inside a commented-out exploit as written.
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+from .harness import Harness, default_harness
+from .template_loader import PoCTemplate
+
+_PLACEHOLDER = re.compile(r"\{\{\s*([A-Za-z_]\w*)\s*(?:=([^|}]*))?((?:\|\s*\w+\s*)*)\}\}")
+_IDENT = re.compile(r"^[A-Za-z_]\w*$")
+# What may precede a placeholder for its multi-line values to repeat it on each line (indent, comment markers)
+_LINE_PREFIX = re.compile(r"[ \t/#*;-]*")
+# Cosmos addresses: human-readable prefix, separator "1", bech32 data
+_BECH32 = re.compile(r"^[a-z]{1,83}1[02-9ac-hj-np-z]{38,58}$")
+# Solidity amounts may carry an exponent or a unit (`1e18`, `10 ether`)
+_INTEGER = re.compile(r"^(?:\d[\d_]*(?:e\d+)?(?:\s+(?:wei|gwei|ether))?|0x[0-9a-fA-F_]+)$")
+# Move integer literals: no exponents or units, optionally typed (`1000u64`)
+_MOVE_INTEGER = re.compile(r"^(?:\d[\d_]*|0x[0-9a-fA-F_]+)(?:u8|u16|u32|u64|u128|u256)?$")
+
+# Placeholder suffix (or whole name, without the underscore) -> what its value must be, per chain
+_KINDS = {
+    "solana": {"_ID": "pubkey", "_ACCOUNT": "pubkey", "_PUBKEY": "pubkey", "_MINT": "pubkey",
+               "_AUTHORITY": "pubkey", "_INSTRUCTION": "identifier", "_AMOUNT": "integer"},
+    "evm": {"_ADDRESS": "address", "_FUNCTION": "identifier", "_AMOUNT": "integer"},
+    "sui": {"_ID": "address", "_ADDRESS": "address", "_FUNCTION": "identifier", "_AMOUNT": "integer"},
+    "cosmwasm": {"_ADDRESS": "bech32", "_ADDR": "bech32", "_MSG": "identifier", "_AMOUNT": "integer"},
+}
+
+
+class TemplateError(ValueError):
+    """A template could not be rendered with the given context."""
+
+    def __init__(self, message: str, missing: list[str] | None = None, invalid: dict[str, str] | None = None):
+        super().__init__(message)
+        self.missing = missing or []
+        self.invalid = invalid or {}
+
+
+@dataclass
+class Placeholder:
+    """One placeholder, as first written in the template."""
+
+    name: str
+    default: str | None = None
+    filters: list[str] = field(default_factory=list)
+    line: int = 0
+
+    @property
+    def required(self) -> bool:
+        return self.default is None
+
+
+def _words(value: str) -> list[str]:
+    value = re.sub(r"(?<=[a-z0-9])([A-Z])", r" \1", value)
+    return [w for w in re.split(r"[^A-Za-z0-9]+", value) if w]
+
+
+def _pubkey(value: str) -> str:
+    return f'anchor_lang::solana_program::pubkey!("{value}")'
+
+
+def _rust_str(value: str) -> str:
+    return '"' + value.replace("\\", "\\\\").replace('"', '\\"').replace("\n", "\\n") + '"'
+
+
+FILTERS = {
+    "pubkey": _pubkey,
+    "str": _rust_str,
+    "snake": lambda v: "_".join(w.lower() for w in _words(v)),
+    "camel": lambda v: "".join(w[:1].upper() + w[1:] for w in _words(v)),
+    "lower_camel": lambda v: "".join(w.lower() if i == 0 else w[:1].upper() + w[1:]
+                                     for i, w in enumerate(_words(v))),
+    "upper": str.upper,
+    "lower": str.lower,
+}
+
+
+def parse_placeholders(text: str) -> list[Placeholder]:
+    """Placeholders of ``text`` in order of first use; raises TemplateError on unknown filters."""
+    found: dict[str, Placeholder] = {}
+    for m in _PLACEHOLDER.finditer(text):
+        name = m.group(1)
+        filters = [f.strip() for f in m.group(3).split("|") if f.strip()]
+        unknown = [f for f in filters if f not in FILTERS]
+        line = text.count("\n", 0, m.start()) + 1
+        if unknown:
+            raise TemplateError(f"line {line}: unknown filter {unknown[0]!r} on {name} "
+                                f"(expected one of {', '.join(FILTERS)})")
+        placeholder = found.setdefault(name, Placeholder(name, line=line))
+        if m.group(2) is not None and placeholder.default is None:
+            placeholder.default = m.group(2).strip()
+        for f in filters:
+            if f not in placeholder.filters:
+                placeholder.filters.append(f)
+    return list(found.values())
+
+
+def _check(name: str, value: str, chain: str, harness: Harness | None = None) -> str | None:
+    """Why ``value`` does not fit placeholder ``name`` on ``chain`` (under ``harness``), or None."""
+    from extensions.ir.anchor_config import is_pubkey
+
+    kinds = {**_KINDS.get(chain, {}), **(harness.kinds if harness else {})}
+    kind = next((k for suffix, k in kinds.items() if name.endswith(suffix) or name == suffix[1:]), None)
+    if kind == "pubkey" and not is_pubkey(value):
+        return f"{value!r} is not a base58 public key"
+    if kind == "address" and not re.fullmatch(r"0x[0-9a-fA-F]{40}" if chain == "evm" else r"0x[0-9a-fA-F]{1,64}",
+                                              value):
+        return f"{value!r} is not a hex address"
+    if kind == "bech32" and not _BECH32.match(value):
+        return f"{value!r} is not a bech32 address"
+    if kind == "identifier" and not _IDENT.match(value):
+        return f"{value!r} is not an identifier"
+    if kind == "integer" and not _INTEGER.match(value):
+        return f"{value!r} is not an integer"
+    if kind == "move_integer" and not _MOVE_INTEGER.match(value):
+        return f"{value!r} is not a Move integer literal"
+    return None
+
+
+class TemplateEngine:
+    """Fills in one template's placeholders."""
+
+    def __init__(self, template: PoCTemplate | str, chain: str | None = None, name: str | None = None,
+                 harness: Harness | None = None):
+        """Initialize the engine.
+
+        Args:
+            template: A loaded template, or template text
+            chain: Chain whose value formats are checked (default: the template's, else solana)
+            name: Template name for the generated header (default: the template's id)
+            harness: Test harness the output is for (default: the template's, else the chain's)
+        """
+        if isinstance(template, PoCTemplate):
+            self.text, self.chain, self.name = template.template, chain or template.chain, name or template.id
+            self.harness = harness or (template.harness if not chain else None)
+        else:
+            self.text, self.chain, self.name = template, chain or "solana", name
+            self.harness = harness
+        if self.harness is None:
+            try:
+                self.harness = default_harness(self.chain)
+            except ValueError:
+                pass
+        self.placeholders = parse_placeholders(self.text)
+
+    @property
+    def required(self) -> list[str]:
+        return [p.name for p in self.placeholders if p.required]
+
+    def _values(self, context: dict[str, Any]) -> dict[str, str]:
+        # Context keys match placeholders regardless of case (program_id fills {{PROGRAM_ID}})
+        return {str(k).upper(): str(v) for k, v in context.items() if v is not None}
+
+    def validate(self, context: dict[str, Any]) -> tuple[list[str], dict[str, str]]:
+        """Required placeholders ``context`` leaves out, and values that do not fit theirs (name -> why)."""
+        values = self._values(context)
+        missing = [p.name for p in self.placeholders if p.required and p.name.upper() not in values]
+        invalid = {}
+        for p in self.placeholders:
+            value = values.get(p.name.upper(), p.default)
+            problem = _check(p.name.upper(), value, self.chain, self.harness) if value is not None else None
+            if problem:
+                invalid[p.name] = problem
+        return missing, invalid
+
+    def render(self, context: dict[str, Any], header: bool = True) -> str:
+        """The template with every placeholder filled in; raises TemplateError naming what is missing or invalid."""
+        missing, invalid = self.validate(context)
+        if missing or invalid:
+            problems = [f"missing {', '.join(missing)}"] if missing else []
+            problems += [f"{name}: {why}" for name, why in invalid.items()]
+            raise TemplateError(f"cannot render {self.name or 'template'}: {'; '.join(problems)}", missing, invalid)
+        values = self._values(context)
+        defaults = {p.name: p.default for p in self.placeholders}
+
+        def fill(m: re.Match) -> str:
+            name = m.group(1)
+            value = values.get(name.upper(), defaults[name])
+            for f in (f.strip() for f in m.group(3).split("|") if f.strip()):
+                value = FILTERS[f](value)
+            if name.upper() in values and "\n" in value:
+                prefix = self.text[self.text.rfind("\n", 0, m.start()) + 1:m.start()]
+                if _LINE_PREFIX.fullmatch(prefix):
+                    value = value.replace("\n", "\n" + prefix)
+            return value
+
+        rendered = _PLACEHOLDER.sub(fill, self.text)
+        if header and self.name:
+            comment = "//" if self.chain in ("solana", "sui", "aptos", "evm", "cosmwasm") else "#"
+            rendered = (f"{comment} Generated by Baskerville from the `{self.name}` PoC template; "
+                        f"edit the exploit steps below.\n\n{rendered}")
+        return rendered
+
+    def write(self, path: Path, context: dict[str, Any]) -> Path:
+        """Render into ``path`` (e.g. the workspace's `tests/<name>.rs`), creating its directory."""
+        path = Path(path)
+        rendered = self.render(context)
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(rendered if rendered.endswith("\n") else rendered + "\n")
+        return path
@@ -0,0 +1,250 @@
+"""
+Full-text search over PoC templates.
+
+Each template is indexed by four fields: its title (name, id and
+vulnerability type), its vulnerability description (the template's and its
+class's), its fix guidance (remediation, the body's ``FIX`` sections and the
+class's fix) and its tags (tags, taxonomy tags, CWE/SWC ids). Queries are
+ranked with BM25, titles and tags weighing most, and templates matching
+every word rank above those matching some:
+
+    reentrancy cpi                 # Any of the words, best first
+    "signer seeds"                 # A phrase, required
+    signer tag:access-control      # Only templates with the taxonomy tag
+    chain:solana severity:high     # Only Solana templates of that severity
+
+Words match their plural and ``-ing``/``-ed`` forms; a word of four or more
+letters also matches words it starts (``reentr`` finds "reentrancy").
+Each hit names the field that matched best and carries a snippet of its
+description or fix guidance around the first match.
+"""
+
+import math
+import re
+from collections import Counter
+from dataclasses import dataclass, field
+from typing import Any, Iterable
+
+from .chains import Chain
+from .class_loader import ClassLoader
+from .front_matter import SEVERITIES
+from .taxonomy import resolve_tags
+from .template_loader import PoCTemplate
+
+# Field -> weight of its matches
+FIELDS = {"title": 3.0, "tags": 2.0, "description": 1.5, "fix": 1.0}
+SNIPPET_CHARS = 160
+
+_BM25_K1 = 1.2
+_BM25_B = 0.75
+_PREFIX_WEIGHT = 0.5
+_WORD = re.compile(r"[a-z0-9]+")
+_FILTER = re.compile(r"\b(tag|chain|severity):(\S+)", re.I)
+_PHRASE = re.compile(r'"([^"]*)"')
+_COMMENT = re.compile(r"^\s*(?://+|#+|/\*+|\*+/?)\s?", re.M)
+
+
+def _stem(word: str) -> str:
+    for suffix in ("ies", "ing", "es", "ed", "s"):
+        if word.endswith(suffix) and len(word) - len(suffix) >= 3:
+            return word[:-len(suffix)] + ("y" if suffix == "ies" else "")
+    return word
+
+
+def tokenize(text: str) -> list[str]:
+    """Lowercase word stems of ``text`` (identifiers split at ``_`` and ``-``)."""
+    return [_stem(w) for w in _WORD.findall((text or "").lower())]
+
+
+def _prose(text: str) -> str:
+    """Text with comment markers and runs of whitespace removed."""
+    return " ".join(_COMMENT.sub("", text or "").split())
+
+
+@dataclass
+class SearchQuery:
+    """A parsed query: words to rank by, phrases and filters to require."""
+
+    terms: list[str] = field(default_factory=list)
+    phrases: list[str] = field(default_factory=list)
+    tags: list[str] = field(default_factory=list)
+    chain: Chain | None = None
+    severity: str | None = None
+
+    @classmethod
+    def parse(cls, text: str) -> "SearchQuery":
+        """Query from its text; raises ValueError on an unknown tag, chain or severity."""
+        query = cls()
+        for key, value in _FILTER.findall(text):
+            key = key.lower()
+            if key == "tag":
+                query.tags += [t for t in resolve_tags([value]) if t not in query.tags]
+            elif key == "chain":
+                query.chain = Chain.parse(value)
+            elif value.lower() not in SEVERITIES:
+                raise ValueError(f"unknown severity {value!r} (expected one of {', '.join(SEVERITIES)})")
+            else:
+                query.severity = value.lower()
+        text = _FILTER.sub(" ", text)
+        query.phrases = [" ".join(tokenize(p)) for p in _PHRASE.findall(text) if tokenize(p)]
+        words = tokenize(_PHRASE.sub(" ", text)) + [w for p in query.phrases for w in p.split()]
+        query.terms = list(dict.fromkeys(words))
+        return query
+
+
+@dataclass
+class SearchHit:
+    """A template matching a query."""
+
+    template: PoCTemplate
+    score: float
+    field: str  # The field that matched best
+    snippet: str  # Of the description or fix guidance
+    matched: list[str] = field(default_factory=list)  # Query words found
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "id": self.template.id, "name": self.template.name, "chain": self.template.chain.value,
+            "severity": self.template.severity, "vulnerability_type": self.template.vulnerability_type,
+            "score": round(self.score, 3), "field": self.field, "snippet": self.snippet, "matched": self.matched,
+            "origin": self.template.origin,
+        }
+
+
+@dataclass
+class _Document:
+    template: PoCTemplate
+    text: dict[str, str]  # Field -> its text, for snippets
+    tokens: dict[str, list[str]]  # Field -> its word stems
+    counts: dict[str, Counter]
+
+
+class TemplateIndex:
+    """Inverted index of PoC templates' titles, descriptions, fix guidance and tags."""
+
+    def __init__(self, templates: Iterable[PoCTemplate], classes: ClassLoader | None = None):
+        """Build the index.
+
+        Args:
+            templates: Templates to index
+            classes: Vulnerability classes whose descriptions and fixes are indexed with their templates
+        """
+        self.documents = [self._document(t, classes) for t in templates]
+        self._vocabulary: dict[str, int] = Counter(
+            word for doc in self.documents for word in {w for tokens in doc.tokens.values() for w in tokens})
+        self._avg_len = {
+            name: (sum(len(d.tokens[name]) for d in self.documents) / len(self.documents)) or 1.0
+            if self.documents else 1.0
+            for name in FIELDS
+        }
+
+    @staticmethod
+    def _document(template: PoCTemplate, classes: ClassLoader | None) -> _Document:
+        vuln_class = None
+        if classes is not None:
+            vuln_class = classes.get(template.vuln_class) if template.vuln_class else None
+            vuln_class = vuln_class or classes.for_template(template.id)
+        chain = template.chain.value
+        fixes = [text for title, text in template.sections().items() if title.upper().startswith("FIX")]
+        text = {
+            "title": " ".join([template.name, template.id.replace("_", " "), template.vulnerability_type]),
+            "tags": " ".join([*template.tags, *template.taxonomy, *template.cwe, *template.swc]),
+            "description": " ".join(filter(None, [template.description,
+                                                  vuln_class.describe(chain) if vuln_class else None])),
+            "fix": " ".join(filter(None, [template.remediation, *(_prose(f) for f in fixes),
+                                          vuln_class.fix_for(chain) if vuln_class else None])),
+        }
+        tokens = {name: tokenize(value) for name, value in text.items()}
+        return _Document(template, text, tokens, {name: Counter(words) for name, words in tokens.items()})
+
+    def _expand(self, term: str) -> list[tuple[str, float]]:
+        """Indexed words ``term`` matches, with the weight of each."""
+        words = [(term, 1.0)] if term in self._vocabulary else []
+        if len(term) >= 4:
+            words += [(w, _PREFIX_WEIGHT) for w in self._vocabulary if w != term and w.startswith(term)]
+        return words
+
+    def _idf(self, word: str) -> float:
+        n = self._vocabulary.get(word, 0)
+        return math.log(1 + (len(self.documents) - n + 0.5) / (n + 0.5))
+
+    def search(self, query: str | SearchQuery, limit: int | None = 10, chain: Chain | str | None = None,
+               tags: Iterable[str] = (), match_all: bool = False) -> list[SearchHit]:
+        """Templates matching ``query``, best first.
+
+        Args:
+            query: Query text (see the module docstring) or a parsed query
+            limit: Most hits to return (None: all)
+            chain: Only templates of this chain
+            tags: Only templates with any (or all) of these taxonomy tags
+            match_all: Require every tag of ``tags`` instead of any
+
+        Raises:
+            ValueError: If the query names an unknown tag, chain or severity
+        """
+        parsed = query if isinstance(query, SearchQuery) else SearchQuery.parse(query)
+        chain = Chain.parse(chain) if chain else parsed.chain
+        wanted = resolve_tags(tags)
+        expanded = {term: self._expand(term) for term in parsed.terms}
+
+        hits = []
+        for doc in self.documents:
+            t = doc.template
+            if (chain and t.chain != chain) or (parsed.severity and t.severity != parsed.severity):
+                continue
+            if parsed.tags and not set(parsed.tags) <= set(t.taxonomy):
+                continue
+            if wanted and not (set(wanted) <= set(t.taxonomy) if match_all else set(wanted) & set(t.taxonomy)):
+                continue
+            if parsed.phrases and not all(any(f" {p} " in f" {' '.join(doc.tokens[f])} " for f in FIELDS)
+                                          for p in parsed.phrases):
+                continue
+            by_field = dict.fromkeys(FIELDS, 0.0)
+            matched = []
+            for term, words in expanded.items():
+                term_score = 0.0
+                for word, weight in words:
+                    idf = self._idf(word)
+                    for name, field_weight in FIELDS.items():
+                        tf = doc.counts[name][word]
+                        if not tf:
+                            continue
+                        norm = 1 - _BM25_B + _BM25_B * len(doc.tokens[name]) / self._avg_len[name]
+                        score = weight * field_weight * idf * tf * (_BM25_K1 + 1) / (tf + _BM25_K1 * norm)
+                        by_field[name] += score
+                        term_score += score
+                if term_score:
+                    matched.append(term)
+            if parsed.terms and not matched:
+                continue
+            # Templates with every word rank above those with some
+            coverage = len(matched) / len(parsed.terms) if parsed.terms else 1.0
+            score = sum(by_field.values()) * coverage
+            best = max(FIELDS, key=lambda name: by_field[name])
+            # Snippets come from prose: the description when only the title or tags matched
+            prose = max(("description", "fix"), key=lambda name: by_field[name])
+            prose = prose if by_field[prose] else "description"
+            hits.append(SearchHit(t, score, best, _snippet(doc.text[prose], matched), matched))
+        hits.sort(key=lambda h: (-h.score, h.template.chain.value, h.template.id))
+        return hits[:limit] if limit is not None else hits
+
+
+def _snippet(text: str, terms: list[str], width: int = SNIPPET_CHARS) -> str:
+    """About ``width`` characters of ``text`` around the first word matching ``terms``."""
+    text = " ".join(text.split())
+    start = 0
+    for match in _WORD.finditer(text.lower()):
+        word = _stem(match.group())
+        if any(word == t or (len(t) >= 4 and word.startswith(t)) for t in terms):
+            start = match.start()
+            break
+    begin = max(0, start - width // 3)
+    if begin:
+        # Start at a word boundary
+        space = text.find(" ", begin)
+        begin = space + 1 if 0 <= space < start else begin
+    end = begin + width
+    if end < len(text):
+        space = text.rfind(" ", begin, end)
+        end = space if space > start else end
+    return ("…" if begin else "") + text[begin:end] + ("…" if end < len(text) else "")
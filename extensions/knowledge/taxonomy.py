@@ -0,0 +1,185 @@
+"""
+Controlled tag taxonomy.
+
+Checklist items, tips, templates and findings all carry free-form tags and
+categories ("Oracle & Price Feeds", "flash-loan", "access_control", ...).
+This module maps them onto one fixed set of tags so large result sets can be
+sliced the same way in the CLI, the review workflow and the HTML report.
+
+Classification is keyword based: an entry's own tags, category and title are
+matched first; its description is only consulted when those say nothing, and
+entries that match no tag at all fall back to ``logic``.
+"""
+
+import re
+from collections.abc import Iterable
+from dataclasses import dataclass
+from typing import Any
+
+
+@dataclass(frozen=True)
+class Tag:
+    """A controlled taxonomy tag."""
+    id: str
+    label: str
+    description: str
+    terms: tuple[str, ...] = ()  # Aliases and keywords, matched as whole words
+
+
+TAGS = [
+    Tag("access-control", "Access Control", "Missing or bypassable authorization, roles and signer checks",
+        ("access control", "authorization", "authorisation", "unauthorized", "permission", "permissions",
+         "privilege", "privilege escalation", "rbac", "role", "roles", "owner", "ownership", "onlyowner",
+         "admin", "modifier", "signer", "missing signer", "authority", "whitelist", "two step", "renounce")),
+    Tag("arithmetic", "Arithmetic", "Overflow, precision loss, rounding and unit conversion",
+        ("arithmetic", "math", "overflow", "underflow", "precision", "rounding", "division", "multiplication",
+         "decimals", "casting", "cast", "safecast", "unchecked", "off by one", "basis points", "percentage",
+         "truncation")),
+    Tag("cpi", "CPI & External Calls", "Cross-program invocations and calls into untrusted code",
+        ("cpi", "cross program invocation", "invoke", "invoke signed", "arbitrary program", "program id",
+         "signer seeds", "external call", "external calls", "cross contract", "delegatecall")),
+    Tag("reentrancy", "Reentrancy", "Re-entering a program or contract before its state is consistent",
+        ("reentrancy", "reentrant", "re entrancy", "nonreentrant", "cei", "checks effects interactions",
+         "callback", "hooks", "tokensreceived", "cpi reentrancy")),
+    Tag("oracle", "Oracle", "Price feeds, staleness and oracle manipulation",
+        ("oracle", "oracles", "price feed", "price feeds", "chainlink", "pyth", "switchboard", "twap",
+         "staleness", "stale price", "sequencer")),
+    Tag("economic", "Economic", "Flash loans, MEV, slippage, liquidations and incentive design",
+        ("economic", "defi", "flash loan", "flashloan", "mev", "front running", "frontrunning", "sandwich",
+         "slippage", "price manipulation", "liquidation", "bad debt", "inflation", "inflation attack",
+         "first deposit", "donation", "interest rate", "rewards", "staking", "amm", "lending", "liquidity", "vault",
+         "erc4626", "incentive", "arbitrage")),
+    Tag("upgradeability", "Upgradeability", "Proxies, upgrade authority and storage layout",
+        ("upgradeability", "upgradeable", "upgrade", "upgrade safety", "proxy", "uups", "storage collision",
+         "implementation")),
+    Tag("initialization", "Initialization", "Missing, repeatable or front-runnable initialization",
+        ("initialization", "initialize", "initializer", "reinit", "reinitialization", "uninitialized")),
+    Tag("account-validation", "Account Validation", "Owner, type, PDA and lifecycle checks on passed accounts",
+        ("account validation", "account security", "account lifecycle", "owner check", "type confusion",
+         "type cosplay", "discriminator", "pda", "pda security", "seeds", "bump", "pda seed collision",
+         "account revival", "close account", "closing accounts", "rent", "duplicate accounts")),
+    Tag("object-safety", "Object & Capability Safety", "Move objects, capabilities, witnesses and visibility",
+        ("object safety", "capability", "capabilities", "capability leak", "capability patterns", "shared object",
+         "shared objects", "shared object race", "dynamic field", "dynamic fields", "witness", "one time witness",
+         "witness patterns", "hot potato", "type safety", "generics", "module visibility", "friend", "visibility",
+         "wrapping", "frozen", "entry function")),
+    Tag("token", "Token Handling", "Non-standard tokens, approvals, mints and token accounts",
+        ("token", "tokens", "token security", "erc20", "erc721", "erc777", "erc1155", "spl", "spl token",
+         "fee on transfer", "rebasing", "safetransfer", "approval", "allowance", "mint")),
+    Tag("signature", "Signatures & Replay", "Signature verification, nonces and replay protection",
+        ("signature", "signatures", "replay", "nonce", "eip 712", "ecrecover", "permit", "malleability",
+         "merkle")),
+    Tag("denial-of-service", "Denial of Service", "Griefing, unbounded work and resource exhaustion",
+        ("dos", "denial of service", "griefing", "gas", "gas limit", "unbounded loop", "loop", "out of gas",
+         "block stuffing", "compute units")),
+    Tag("timing", "Timing & Ordering", "Timestamps, deadlines, delays and transaction ordering",
+        ("timing", "time safety", "timestamp", "clock", "deadline", "delay", "block number", "race condition",
+         "ordering")),
+    Tag("governance", "Governance", "Voting, proposals and timelocks",
+        ("governance", "voting", "vote", "proposal", "proposals", "quorum", "timelock")),
+    Tag("cross-chain", "Cross-Chain", "Bridges and cross-chain messaging",
+        ("cross chain", "bridge", "bridges", "relay", "relayer", "layerzero", "wormhole")),
+    Tag("randomness", "Randomness", "Predictable or manipulable randomness",
+        ("randomness", "random", "vrf", "blockhash", "prevrandao", "entropy")),
+    Tag("logic", "Business Logic", "Incorrect conditions, accounting and state transitions",
+        ("logic", "business logic", "accounting", "invariant", "state machine", "comparison", "boundary",
+         "bounds", "require", "revert")),
+]
+
+TAXONOMY: dict[str, Tag] = {tag.id: tag for tag in TAGS}
+
+FALLBACK_TAG = "logic"
+
+
+def _norm(text: str) -> str:
+    return " ".join(re.sub(r"[^a-z0-9]+", " ", text.lower()).split())
+
+
+_ALIASES: dict[str, str] = {}
+for _tag in TAGS:
+    for _term in (_tag.id, _tag.label, *_tag.terms):
+        _ALIASES.setdefault(_norm(_term), _tag.id)
+
+_PATTERNS = {
+    tag.id: re.compile(r"\b(?:" + "|".join(re.escape(_norm(t)) for t in (tag.id, *tag.terms)) + r")\b")
+    for tag in TAGS
+}
+
+
+def normalize_tag(value: str) -> str | None:
+    """Canonical taxonomy id for a tag id, label or alias, or None."""
+    return _ALIASES.get(_norm(value))
+
+
+def resolve_tags(values: Iterable[str]) -> list[str]:
+    """Canonical ids for user-supplied tags; raises ValueError on unknown ones."""
+    resolved = []
+    for value in values:
+        tag = normalize_tag(value)
+        if tag is None:
+            raise ValueError(f"unknown tag {value!r} (tags: {', '.join(TAXONOMY)})")
+        if tag not in resolved:
+            resolved.append(tag)
+    return resolved
+
+
+def _match(texts: Iterable[str]) -> set[str]:
+    found = set()
+    for text in texts:
+        if not text:
+            continue
+        alias = normalize_tag(text)
+        if alias:
+            found.add(alias)
+        normalized = _norm(text)
+        found.update(tag for tag, pattern in _PATTERNS.items() if pattern.search(normalized))
+    return found
+
+
+def classify(tags: Iterable[str] = (), texts: Iterable[str] = (), fallback_texts: Iterable[str] = ()) -> list[str]:
+    """Taxonomy tags for an entry, in taxonomy order.
+
+    Args:
+        tags: The entry's own free-form tags
+        texts: Short descriptive fields (category, title, vulnerability type)
+        fallback_texts: Longer text only consulted when nothing else matched
+    """
+    found = _match([*tags, *texts]) or _match(fallback_texts)
+    return [tag.id for tag in TAGS if tag.id in found] or [FALLBACK_TAG]
+
+
+def hypothesis_tags(hyp: dict[str, Any]) -> list[str]:
+    """Taxonomy tags for a hypothesis or report finding dict."""
+    if hyp.get("taxonomy"):
+        return list(hyp["taxonomy"])
+    kind = hyp.get("vulnerability_type", hyp.get("type", ""))
+    return classify(hyp.get("tags") or [], [str(kind), hyp.get("category", ""), hyp.get("title", "")],
+                    [hyp.get("description", "")])
+
+
+def entry_tags(entry: Any) -> list[str]:
+    """Taxonomy tags of a KB entry, bounty finding or hypothesis dict."""
+    if isinstance(entry, dict):
+        return hypothesis_tags(entry)
+    if hasattr(entry, "taxonomy"):
+        return entry.taxonomy
+    return entry.tags
+
+
+def filter_by_tags(entries: Iterable[Any], tags: Iterable[str], match_all: bool = False) -> list[Any]:
+    """Entries carrying any (or, with ``match_all``, every) of ``tags``."""
+    wanted = resolve_tags(tags)
+    if not wanted:
+        return list(entries)
+    check = all if match_all else any
+    return [e for e in entries if check(t in entry_tags(e) for t in wanted)]
+
+
+def facet_counts(entries: Iterable[Any]) -> dict[str, int]:
+    """Number of entries per taxonomy tag, most common first; unused tags are omitted."""
+    counts: dict[str, int] = {}
+    for entry in entries:
+        for tag in entry_tags(entry):
+            counts[tag] = counts.get(tag, 0) + 1
+    order = list(TAXONOMY)
+    return dict(sorted(counts.items(), key=lambda kv: (-kv[1], order.index(kv[0]))))
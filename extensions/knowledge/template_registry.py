@@ -0,0 +1,125 @@
+"""
+Queryable registry of PoC templates.
+
+Indexes every template the loader finds by the structured metadata in its
+front matter (chain, severity, vulnerability class, CWE/SWC ids, test
+harness), so callers can ask for "high-severity Solana templates for this
+class" instead of globbing the templates directory. Chains are `Chain` members; anywhere a
+chain is taken, its id string works too.
+"""
+
+from typing import Any
+
+from .chains import Chain
+from .front_matter import SEVERITIES
+from .harness import parse_harness
+from .template_loader import ORIGINS, PoCTemplate, TemplateLoader
+
+
+class TemplateRegistry:
+    """PoC templates queryable by their metadata."""
+
+    def __init__(self, loader: TemplateLoader | None = None):
+        """Initialize registry.
+
+        Args:
+            loader: Loader whose templates are indexed (default: the built-in templates directory)
+        """
+        self.loader = loader or TemplateLoader()
+
+    def get(self, template_id: str) -> PoCTemplate | None:
+        """Get a template by ID."""
+        return self.loader.get(template_id)
+
+    def list_all(self) -> list[PoCTemplate]:
+        """Get all templates, ordered by chain and ID."""
+        return sorted(self.loader.list_all(), key=lambda t: (t.chain, t.id))
+
+    def query(
+        self,
+        chain: Chain | str | None = None,
+        severity: str | None = None,
+        min_severity: str | None = None,
+        vuln_class: str | None = None,
+        cwe: str | None = None,
+        swc: str | None = None,
+        tag: str | None = None,
+        harness: str | None = None,
+        origin: str | None = None,
+    ) -> list[PoCTemplate]:
+        """Templates matching every given filter.
+
+        Args:
+            chain: Chain, or its id (e.g., "evm", "solana", "sui")
+            severity: Exact severity
+            min_severity: Lowest severity to include ("critical" > "high" > "medium" > "low" > "info")
+            vuln_class: Vulnerability class ID, or any vulnerability type that resolves to a class
+            cwe: CWE id ("CWE-862" or "862")
+            swc: SWC id ("SWC-107" or "107")
+            tag: Tag or taxonomy tag
+            harness: Test harness id (e.g., "anchor", "sui-move-test"); templates with a target for it match too
+            origin: Where the template was loaded from ("builtin", "bundle" or "user")
+        """
+        for name, value in (("severity", severity), ("min_severity", min_severity)):
+            if value is not None and value.lower() not in SEVERITIES:
+                raise ValueError(f"Unknown {name} '{value}' (expected one of {', '.join(SEVERITIES)})")
+        if origin is not None and origin.lower() not in ORIGINS:
+            raise ValueError(f"Unknown origin '{origin}' (expected one of {', '.join(ORIGINS)})")
+        chain = Chain.parse(chain) if chain else None
+        harness_id = parse_harness(harness).id if harness else None
+        class_id = None
+        if vuln_class:
+            resolved = self.loader.classes.resolve(vuln_class, chain)
+            class_id = resolved.id if resolved else vuln_class
+        floor = SEVERITIES.index(min_severity.lower()) if min_severity else None
+
+        results = []
+        for t in self.list_all():
+            if chain and t.chain != chain:
+                continue
+            if severity and t.severity != severity.lower():
+                continue
+            if floor is not None and (t.severity is None or SEVERITIES.index(t.severity) > floor):
+                continue
+            if class_id and t.vuln_class != class_id:
+                continue
+            if cwe and _id(cwe, "CWE") not in t.cwe:
+                continue
+            if swc and _id(swc, "SWC") not in t.swc:
+                continue
+            if tag and tag.lower() not in {x.lower() for x in t.tags + t.taxonomy}:
+                continue
+            if harness_id and t.harness.id != harness_id and harness_id not in t.targets:
+                continue
+            if origin and t.origin != origin.lower():
+                continue
+            results.append(t)
+        return results
+
+    def chains(self) -> list[Chain]:
+        """Chains with at least one template."""
+        return sorted({t.chain for t in self.list_all()})
+
+    def by_chain(self) -> dict[Chain, list[str]]:
+        """Template IDs per chain."""
+        grouped: dict[Chain, list[str]] = {}
+        for t in self.list_all():
+            grouped.setdefault(t.chain, []).append(t.id)
+        return grouped
+
+    def by_class(self) -> dict[str, list[str]]:
+        """Template IDs per vulnerability class (templates without one are left out)."""
+        grouped: dict[str, list[str]] = {}
+        for t in self.list_all():
+            if t.vuln_class:
+                grouped.setdefault(t.vuln_class, []).append(t.id)
+        return grouped
+
+    def index(self, templates: list[PoCTemplate] | None = None) -> list[dict[str, Any]]:
+        """Metadata of ``templates`` (default: all), without their bodies."""
+        return [t.metadata() for t in (self.list_all() if templates is None else templates)]
+
+
+def _id(value: str, prefix: str) -> str:
+    digits = value.strip().upper().removeprefix(prefix).lstrip("-")
+    return f"{prefix}-{int(digits)}" if digits.isdigit() else value.strip().upper()
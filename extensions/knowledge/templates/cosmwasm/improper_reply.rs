@@ -0,0 +1,84 @@
+// ---
+// name: Improper Reply Handling
+// description: The reply entry point ignores the submessage result or its id, so failures are recorded as success
+// vulnerability_type: improper-reply-handling
+// class: unchecked-call-result
+// chain: cosmwasm
+// severity: high
+// cwe: [CWE-252]
+// remediation: "Match each reply id explicitly, handle the SubMsgResult error case, and use
+//   reply_on_success when a failed submessage must revert the whole transaction."
+// tags: [submsg, reply, reply-on, cw-multi-test]
+// context:
+//   CONTRACT_CRATE: Crate of the contract under test
+//   TRIGGER_MSG: ExecuteMsg variant that dispatches the submessage
+//   REPLY_AMOUNT: Amount the submessage moves
+// references:
+//   - https://github.com/CosmWasm/cosmwasm/blob/main/SEMANTICS.md#submessages
+// ---
+// PoC Template: Improper Reply Handling
+// Vulnerability: The reply entry point ignores the submessage result or its id, so failures are recorded as success
+// Chain: CosmWasm
+//
+// This template makes the submessage fail and checks whether the contract
+// still records it as done.
+
+use cosmwasm_std::{coins, Addr, Empty};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+
+use {{CONTRACT_CRATE}}::contract::{execute, instantiate, query, reply};
+use {{CONTRACT_CRATE}}::msg::{ExecuteMsg, InstantiateMsg};
+
+// ============================================================
+// VULNERABLE REPLY (example)
+// ============================================================
+// // Dispatched with SubMsg::reply_always(transfer, TRANSFER_REPLY_ID)
+// pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+//     // BUG: No match on msg.id and msg.result is never checked
+//     PENDING.remove(deps.storage);
+//     TOTAL_SENT.update(deps.storage, |t| Ok(t + AMOUNT))?;
+//     Ok(Response::new())
+// }
+
+fn contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query).with_reply(reply))
+}
+
+// ============================================================
+// EXPLOIT TEST (cw-multi-test)
+// ============================================================
+#[test]
+fn test_improper_reply_exploit() {
+    let mut app = App::default();
+    let user = Addr::unchecked("user");
+
+    let code_id = app.store_code(contract());
+    // Deliberately unfunded, so the bank send in the submessage fails
+    let contract_addr = app
+        .instantiate_contract(code_id, user.clone(), &InstantiateMsg { /* fill in */ }, &[], "target", None)
+        .unwrap();
+    assert!(app.wrap().query_all_balances(&contract_addr).unwrap().is_empty());
+
+    // 1. Trigger the submessage; it fails inside the reply_always/reply_on_error dispatch
+    let result = app.execute_contract(user.clone(), contract_addr.clone(), &ExecuteMsg::{{TRIGGER_MSG}} {}, &[]);
+
+    // 2. The transaction still succeeds and state says the funds went out
+    assert!(result.is_ok(), "Exploit: failed submessage was swallowed by reply");
+    assert!(app.wrap().query_all_balances(&user).unwrap().is_empty());
+    // e.g. query the contract's bookkeeping and assert it counts {{REPLY_AMOUNT}} as sent
+    let _expected = coins({{REPLY_AMOUNT=1_000}}, "uatom");
+}
+
+// ============================================================
+// FIX: Match the reply id and handle the result explicitly
+// ============================================================
+// pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+//     match msg.id {
+//         TRANSFER_REPLY_ID => match msg.result {
+//             SubMsgResult::Ok(_) => record_sent(deps),
+//             SubMsgResult::Err(err) => Err(ContractError::TransferFailed { err }),
+//         },
+//         id => Err(ContractError::UnknownReplyId { id }),
+//     }
+// }
+// // and use reply_on_success when failures should revert the whole tx
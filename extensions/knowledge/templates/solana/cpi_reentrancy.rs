@@ -1,9 +1,28 @@
+// ---
+// name: CPI Reentrancy / Privilege Escalation
+// description: Unsafe CPI call allowing privilege escalation
+// vulnerability_type: cpi-reentrancy
+// class: reentrant-state-inconsistency
+// chain: solana
+// severity: high
+// cwe: [CWE-841]
+// remediation: "Type the CPI target as Program<'info, T> (or compare its key with the expected
+//   program id) so callers cannot substitute their own program."
+// tags: [cpi, reentrancy, privilege-escalation, anchor]
+// references:
+//   - https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/5-arbitrary-cpi
+// ---
 // PoC Template: CPI Reentrancy / Privilege Escalation
 // Vulnerability: Unsafe CPI call allowing privilege escalation
 // Chain: Solana/Anchor
 //
 // Demonstrates how an attacker can exploit CPI to escalate privileges
 // or re-enter the program with unexpected state.
+//
+// Detection: the `cpi-privileges` static analyzer reports CPIs that hand a
+// PDA signature (or a forwarded signer) to a program the caller can choose;
+// `cpi-targets` reports every CPI whose target program is an unchecked
+// `AccountInfo`, with the fix below.
 
 // ============================================================
 // VULNERABLE CODE PATTERN
@@ -32,6 +51,9 @@
 // 2. Attacker calls process_payment with malicious_program as token_program
 // 3. Malicious program re-enters process_payment before state is finalized
 // 4. Vault balance is drained through repeated withdrawals
+//
+// PoC: `poc harness --fake-token` writes a fake SPL Token program that logs
+// the signers it is handed and accepts transfers without moving tokens.
 
 // ============================================================
 // FIX: Validate CPI target program
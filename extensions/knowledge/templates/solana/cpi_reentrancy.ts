@@ -0,0 +1,46 @@
+// PoC Template: CPI Reentrancy / Privilege Escalation (Anchor TypeScript test)
+// Vulnerability: Unsafe CPI call allowing privilege escalation
+// Chain: Solana/Anchor, run with `anchor test`
+//
+// The attacker passes a program that mimics the token program where the
+// real one is expected; see cpi_reentrancy.rs for the vulnerable pattern
+// and the fix (`poc harness --fake-token` writes such a program).
+
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+
+// ============================================================
+// EXPLOIT SCENARIO (Mocha)
+// ============================================================
+describe("cpi_reentrancy exploit", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.{{PROGRAM_NAME=my_program|camel}} as Program;
+  const attacker = Keypair.generate();
+
+  before(async () => {
+    const signature = await provider.connection.requestAirdrop(attacker.publicKey, 10 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction({ signature, ...(await provider.connection.getLatestBlockhash()) });
+  });
+
+  // Drop .skip once the steps below are filled in
+  it.skip("pays out through a fake token program", async () => {
+    // 1. Deploy the fake token program (logs the signers it is handed, moves no tokens)
+    // const fakeToken = anchor.workspace.FakeToken.programId;
+    // const vaultBefore = await program.account.vault.fetch(vault);
+
+    // 2. Call process_payment with the fake program as token_program
+    // await program.methods
+    //   .processPayment(new BN(vaultBefore.balance))
+    //   .accounts({ vault, vaultToken, recipientToken: attackerToken, tokenProgram: fakeToken })
+    //   .signers([attacker])
+    //   .rpc();
+
+    // 3. Should have been rejected; the vault's books were debited while no tokens moved,
+    //    and the fake program re-entered with the vault authority's signature
+    // const vaultAfter = await program.account.vault.fetch(vault);
+    // assert.ok(vaultAfter.balance.lt(vaultBefore.balance), "Exploit: fake token program accepted");
+  });
+});
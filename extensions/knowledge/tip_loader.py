@@ -6,7 +6,10 @@ Provides quick-access tips and patterns for common audit scenarios.
 
 import yaml
 from pathlib import Path
-from dataclasses import dataclass
+from dataclasses import dataclass, field
+
+from ..i18n import load_overlay, localize
+from .taxonomy import classify, filter_by_tags
 
 
 @dataclass
@@ -20,16 +23,30 @@ class AuditorTip:
     tags: list[str]
     priority: str  # high, medium, low
     chain: str = "evm"
+    taxonomy: list[str] = field(default_factory=list)  # Controlled tags, see taxonomy.py
+
+    def __post_init__(self):
+        if not self.taxonomy:
+            self.taxonomy = classify(self.tags, [self.category, self.title], [self.tip])
 
 
 class TipLoader:
     """Loads and queries auditor tips."""
 
-    def __init__(self, tips_dir: Path | None = None):
-        """Initialize loader."""
+    def __init__(self, tips_dir: Path | None = None, locale: str | None = None,
+                 extra_dirs: list[Path] | None = None):
+        """Initialize loader.
+
+        Args:
+            tips_dir: Path to tips directory
+            locale: Language of tip titles and text (en, zh, es, ja)
+            extra_dirs: More tip directories (e.g. of pinned bundles), loaded after the built-in one
+        """
         if tips_dir is None:
             tips_dir = Path(__file__).parent / "tips"
         self.tips_dir = tips_dir
+        self.extra_dirs = [Path(d) for d in extra_dirs or []]
+        self.locale = locale
         self._tips: list[AuditorTip] = []
         self._loaded = False
 
@@ -41,8 +58,8 @@ class TipLoader:
         self._tips = self._get_builtin_tips()
 
         # Load from YAML files
-        if self.tips_dir.exists():
-            for yaml_file in self.tips_dir.glob("*.yaml"):
+        for tips_dir in (d for d in [self.tips_dir, *self.extra_dirs] if d.exists()):
+            for yaml_file in tips_dir.glob("*.yaml"):
                 try:
                     with open(yaml_file) as f:
                         data = yaml.safe_load(f)
@@ -63,6 +80,10 @@ class TipLoader:
                 except Exception as e:
                     print(f"[!] Failed to load tips from {yaml_file}: {e}")
 
+        overlay = load_overlay(self.tips_dir, self.locale)
+        if overlay:
+            self._tips = [localize(tip, overlay.get(tip.id), ("title", "tip")) for tip in self._tips]
+
         self._loaded = True
 
     def _get_builtin_tips(self) -> list[AuditorTip]:
@@ -238,6 +259,11 @@ class TipLoader:
         self._load()
         return [t for t in self._tips if t.priority == priority]
 
+    def get_by_taxonomy(self, tags: list[str], match_all: bool = False) -> list[AuditorTip]:
+        """Get tips carrying any (or all) of the given taxonomy tags."""
+        self._load()
+        return filter_by_tags(self._tips, tags, match_all)
+
     def search(self, query: str) -> list[AuditorTip]:
         """Search tips."""
         self._load()
@@ -0,0 +1,123 @@
+"""
+Live exploit-attempt monitoring.
+
+Watches a deployed program's transactions and evaluates rules derived
+from the project's findings, raising alerts when exploit-shaped activity
+appears on-chain: an instruction called without its expected authority,
+anomalous outflows from program vaults, bursts of failing calls from one
+payer.
+
+- Decoding: `getTransaction` results to program calls (top-level and CPI)
+  with instruction names and token balance changes
+- Events: event schemas from the IDL or `#[event]` structs, decoding of
+  emitted events (logged and self-CPI), and their Rust decoders
+- Rules: rule types, rules files, derivation from hypotheses
+- Sources: RPC polling, websocket `logsSubscribe`, provider webhooks
+- Monitor: fetch, de-duplicate, evaluate, dispatch alerts to sinks
+- Alerting: routing to Slack, PagerDuty, email and webhooks with
+  deduplication windows and severity-based escalation
+- Geyser: rules compiled into a standalone Geyser plugin crate
+- Canaries: decoy vaults and tripwire PDAs planned from the findings, with
+  rules alerting on any call that touches them
+- Usage: per-instruction call counts and callers from a sample of the
+  program's history, and hits ranked by that exposure
+"""
+
+from .alerting import (
+    CHANNEL_TYPES,
+    AlertDeliveryError,
+    AlertRouter,
+    Channel,
+    EmailChannel,
+    Escalation,
+    Notification,
+    PagerDutyChannel,
+    Route,
+    SlackChannel,
+    WebhookChannel,
+    make_channel,
+)
+from .canaries import Canary, CanaryPlan, CanarySetup, plan_canaries
+from .decode import InstructionCall, TokenDelta, TxEvent, anchor_discriminator, decode_transaction
+from .events import (
+    EVENT_IX_TAG,
+    DecodedEvent,
+    EventDecoder,
+    EventSchema,
+    event_discriminator,
+    generate_event_decoders,
+    schemas_from_idl,
+    schemas_from_program,
+)
+from .geyser import generate_geyser_plugin, write_geyser_plugin
+from .monitor import JsonlSink, Monitor
+from .rules import (
+    RULE_TYPES,
+    Alert,
+    CanaryTouchedRule,
+    FailureBurstRule,
+    InstructionCalledRule,
+    LargeOutflowRule,
+    Rule,
+    RuleSet,
+    UnexpectedAuthorityRule,
+    make_rule,
+    rules_from_findings,
+)
+from .sources import Observed, PollingSource, WebhookSource, WebsocketSource
+from .usage import InstructionUsage, ProgramUsage, collect_usage, weight_hits
+
+__all__ = [
+    "CHANNEL_TYPES",
+    "EVENT_IX_TAG",
+    "RULE_TYPES",
+    "Alert",
+    "AlertDeliveryError",
+    "AlertRouter",
+    "Canary",
+    "CanaryPlan",
+    "CanarySetup",
+    "CanaryTouchedRule",
+    "Channel",
+    "DecodedEvent",
+    "EmailChannel",
+    "Escalation",
+    "EventDecoder",
+    "EventSchema",
+    "FailureBurstRule",
+    "InstructionCall",
+    "InstructionUsage",
+    "InstructionCalledRule",
+    "JsonlSink",
+    "LargeOutflowRule",
+    "Monitor",
+    "Notification",
+    "Observed",
+    "PagerDutyChannel",
+    "PollingSource",
+    "ProgramUsage",
+    "Route",
+    "Rule",
+    "RuleSet",
+    "SlackChannel",
+    "TokenDelta",
+    "TxEvent",
+    "UnexpectedAuthorityRule",
+    "WebhookChannel",
+    "WebhookSource",
+    "WebsocketSource",
+    "anchor_discriminator",
+    "collect_usage",
+    "decode_transaction",
+    "event_discriminator",
+    "generate_event_decoders",
+    "generate_geyser_plugin",
+    "make_channel",
+    "make_rule",
+    "plan_canaries",
+    "rules_from_findings",
+    "schemas_from_idl",
+    "schemas_from_program",
+    "weight_hits",
+    "write_geyser_plugin",
+]
@@ -0,0 +1,412 @@
+"""
+Alert routing.
+
+An ``AlertRouter`` is a monitor sink that sends alerts to the channels
+an incident-response team actually watches:
+
+- Channels: Slack incoming webhooks, PagerDuty Events API v2, email
+  (SMTP) and plain JSON webhooks
+- Routes: which channels get which alerts, by rule id/type pattern and
+  minimum severity
+- Deduplication: repeats of the same alert (same rule and instruction
+  by default) within a route's window are held back and counted; the
+  next one sent after the window says how many were suppressed
+- Escalation: alerts at or above a severity, optionally only once they
+  repeat a number of times within a period, go to additional channels
+  (typically the pager), once per window
+
+Routing files are YAML; string values of the form ``$VAR``/``${VAR}``
+are read from the environment so webhook URLs and keys stay out of the
+file.
+"""
+
+import fnmatch
+import json
+import os
+import re
+import smtplib
+import sys
+import time
+import urllib.parse
+from collections import deque
+from collections.abc import Callable, Mapping
+from dataclasses import dataclass, field
+from email.message import EmailMessage
+from typing import Any, ClassVar
+
+from extensions.rpc.client import urllib_transport
+
+from .rules import SEVERITY_ORDER, Alert
+
+PAGERDUTY_EVENTS_URL = "https://events.pagerduty.com/v2/enqueue"
+EXPLORER_TX_URL = "https://explorer.solana.com/tx/{signature}"
+
+# PagerDuty only knows four severities
+_PAGERDUTY_SEVERITY = {"critical": "critical", "high": "error", "medium": "warning", "low": "info", "info": "info"}
+_SLACK_EMOJI = {"critical": ":rotating_light:", "high": ":red_circle:", "medium": ":large_orange_circle:",
+                "low": ":large_blue_circle:", "info": ":white_circle:"}
+_ENV_REF = re.compile(r"^\$\{?(\w+)\}?$")
+
+
+class AlertDeliveryError(Exception):
+    """A channel could not deliver a notification."""
+
+
+@dataclass
+class Notification:
+    """An alert as delivered: with its dedup key, suppressed repeats and escalation reason."""
+
+    alert: Alert
+    dedup_key: str
+    # Repeats held back since the last notification for this key
+    suppressed: int = 0
+    escalation: str | None = None
+
+    @property
+    def summary(self) -> str:
+        prefix = "[ESCALATED] " if self.escalation else ""
+        return f"{prefix}[{self.alert.severity.upper()}] {self.alert.title}"
+
+    def lines(self) -> list[str]:
+        a = self.alert
+        lines = [a.message, f"Rule: {a.rule_id} ({a.kind})"]
+        if a.finding:
+            lines.append(f"Finding: {a.finding}")
+        lines.append(f"Transaction: {EXPLORER_TX_URL.format(signature=a.signature)} (slot {a.slot})")
+        if self.suppressed:
+            lines.append(f"{self.suppressed} similar alerts suppressed since the last notification")
+        if self.escalation:
+            lines.append(f"Escalated: {self.escalation}")
+        return lines
+
+
+@dataclass
+class Channel:
+    """Base channel; subclasses implement ``send``."""
+
+    kind: ClassVar[str] = ""
+
+    name: str
+    timeout: float = 10.0
+    # HTTP transport (default: urllib), same shape as the RPC client's
+    transport: Callable | None = field(default=None, repr=False)
+
+    def send(self, notification: Notification) -> None:
+        raise NotImplementedError
+
+    def _post(self, url: str, payload: dict[str, Any], ok: tuple[int, ...] = (200, 201, 202, 204)) -> None:
+        # Webhook URLs carry their secret in the path; only the host goes into errors
+        host = urllib.parse.urlsplit(url).netloc or "endpoint"
+        try:
+            status, _, body = (self.transport or urllib_transport)(url, json.dumps(payload).encode(),
+                                                                   {"Content-Type": "application/json"},
+                                                                   self.timeout)
+        except (OSError, ValueError) as e:
+            raise AlertDeliveryError(f"{self.name}: {host} unreachable: {e}") from e
+        if status not in ok:
+            raise AlertDeliveryError(f"{self.name}: HTTP {status} from {host}: {body[:200]!r}")
+
+
+@dataclass
+class SlackChannel(Channel):
+    """Slack incoming webhook."""
+
+    kind: ClassVar[str] = "slack"
+
+    url: str = ""
+
+    def send(self, notification: Notification) -> None:
+        alert = notification.alert
+        emoji = _SLACK_EMOJI.get(alert.severity, "")
+        text = f"{emoji} *{notification.summary}*"
+        self._post(self.url, {
+            "text": f"{emoji} {notification.summary}",
+            "blocks": [
+                {"type": "section", "text": {"type": "mrkdwn", "text": text}},
+                {"type": "section", "text": {"type": "mrkdwn", "text": "\n".join(notification.lines())}},
+            ],
+        })
+
+
+@dataclass
+class PagerDutyChannel(Channel):
+    """PagerDuty Events API v2; the dedup key groups repeats into one incident."""
+
+    kind: ClassVar[str] = "pagerduty"
+
+    routing_key: str = ""
+    url: str = PAGERDUTY_EVENTS_URL
+
+    def send(self, notification: Notification) -> None:
+        alert = notification.alert
+        self._post(self.url, {
+            "routing_key": self.routing_key,
+            "event_action": "trigger",
+            "dedup_key": notification.dedup_key,
+            "payload": {
+                "summary": notification.summary[:1024],
+                "source": alert.program_id or "baskerville",
+                "severity": _PAGERDUTY_SEVERITY.get(alert.severity, "warning"),
+                "component": alert.instruction or alert.program_id,
+                "class": alert.kind,
+                "custom_details": {**alert.to_dict(), "suppressed": notification.suppressed,
+                                   "escalation": notification.escalation},
+            },
+            "links": [{"href": EXPLORER_TX_URL.format(signature=alert.signature), "text": "Transaction"}],
+        })
+
+
+@dataclass
+class EmailChannel(Channel):
+    """Email over SMTP (STARTTLS unless ``ssl`` or ``starttls: false``)."""
+
+    kind: ClassVar[str] = "email"
+
+    to: list[str] = field(default_factory=list)
+    sender: str = "baskerville@localhost"
+    host: str = "localhost"
+    port: int = 587
+    username: str | None = None
+    password: str | None = None
+    starttls: bool = True
+    ssl: bool = False
+    smtp_factory: Callable | None = field(default=None, repr=False)
+
+    def send(self, notification: Notification) -> None:
+        msg = EmailMessage()
+        msg["Subject"] = notification.summary
+        msg["From"] = self.sender
+        msg["To"] = ", ".join(self.to)
+        msg.set_content("\n".join(notification.lines()) + "\n\n"
+                        + json.dumps(notification.alert.to_dict(), indent=2) + "\n")
+        factory = self.smtp_factory or (smtplib.SMTP_SSL if self.ssl else smtplib.SMTP)
+        try:
+            with factory(self.host, self.port, timeout=self.timeout) as smtp:
+                if self.starttls and not self.ssl:
+                    smtp.starttls()
+                if self.username:
+                    smtp.login(self.username, self.password or "")
+                smtp.send_message(msg)
+        except (OSError, smtplib.SMTPException) as e:
+            raise AlertDeliveryError(f"{self.name}: SMTP {self.host}:{self.port}: {e}") from e
+
+
+@dataclass
+class WebhookChannel(Channel):
+    """Any HTTP endpoint; receives the alert as JSON."""
+
+    kind: ClassVar[str] = "webhook"
+
+    url: str = ""
+
+    def send(self, notification: Notification) -> None:
+        self._post(self.url, {**notification.alert.to_dict(), "dedup_key": notification.dedup_key,
+                              "suppressed": notification.suppressed, "escalation": notification.escalation})
+
+
+CHANNEL_TYPES: dict[str, type[Channel]] = {
+    cls.kind: cls for cls in (SlackChannel, PagerDutyChannel, EmailChannel, WebhookChannel)
+}
+
+_REQUIRED = {"slack": ("url",), "pagerduty": ("routing_key",), "email": ("to",), "webhook": ("url",)}
+
+
+def _expand(value: Any, env: Mapping[str, str]) -> Any:
+    """Resolve ``$VAR``/``${VAR}`` strings (recursively) from ``env``."""
+    if isinstance(value, str) and (m := _ENV_REF.match(value)):
+        if m.group(1) not in env:
+            raise ValueError(f"environment variable {m.group(1)} is not set")
+        return env[m.group(1)]
+    if isinstance(value, list):
+        return [_expand(v, env) for v in value]
+    if isinstance(value, dict):
+        return {k: _expand(v, env) for k, v in value.items()}
+    return value
+
+
+def make_channel(name: str, spec: dict[str, Any], env: Mapping[str, str] | None = None) -> Channel:
+    """Build a channel from a routing-file entry: ``{"type": "slack", "url": "$SLACK_WEBHOOK_URL"}``."""
+    spec = dict(spec)
+    kind = spec.pop("type", None)
+    if kind not in CHANNEL_TYPES:
+        raise ValueError(f"channel {name}: unknown type {kind!r} (expected one of {', '.join(CHANNEL_TYPES)})")
+    try:
+        spec = _expand(spec, os.environ if env is None else env)
+    except ValueError as e:
+        raise ValueError(f"channel {name}: {e}") from None
+    if kind == "email" and isinstance(spec.get("to"), str):
+        spec["to"] = [spec["to"]]
+    missing = [k for k in _REQUIRED[kind] if not spec.get(k)]
+    if missing:
+        raise ValueError(f"channel {name}: missing {', '.join(missing)}")
+    return CHANNEL_TYPES[kind](name=name, **spec)
+
+
+def _check_severity(value: str, where: str) -> str:
+    if value not in SEVERITY_ORDER:
+        raise ValueError(f"{where}: unknown severity {value!r}")
+    return value
+
+
+@dataclass
+class Route:
+    """Sends alerts matching ``rules`` (fnmatch on rule id or type) at ``min_severity`` or above to ``channels``."""
+
+    channels: list[str]
+    rules: list[str] = field(default_factory=lambda: ["*"])
+    min_severity: str = "info"
+    # Seconds during which repeats of an alert are suppressed (None: the router default)
+    dedup_window: float | None = None
+
+    def matches(self, alert: Alert) -> bool:
+        return (SEVERITY_ORDER.index(alert.severity) >= SEVERITY_ORDER.index(self.min_severity)
+                and any(fnmatch.fnmatchcase(alert.rule_id, p) or fnmatch.fnmatchcase(alert.kind, p)
+                        for p in self.rules))
+
+
+@dataclass
+class Escalation:
+    """Also notify ``channels`` for alerts at ``min_severity`` or above seen ``repeats`` times within ``within``."""
+
+    channels: list[str]
+    min_severity: str = "critical"
+    repeats: int = 1
+    # Seconds the repeats must fall in (None: the router's dedup window)
+    within: float | None = None
+
+    def reason(self, alert: Alert, count: int) -> str:
+        if self.repeats > 1:
+            return f"{alert.severity} alert repeated {count} times"
+        return f"{alert.severity} severity"
+
+
+@dataclass
+class Delivery:
+    """Outcome of sending one notification to one channel."""
+
+    channel: str
+    dedup_key: str
+    signature: str
+    escalated: bool
+    error: str | None = None
+
+    @property
+    def ok(self) -> bool:
+        return self.error is None
+
+
+@dataclass
+class _KeyState:
+    last_sent: float | None = None
+    suppressed: int = 0
+    seen: deque = field(default_factory=deque)
+    escalated_at: float | None = None
+
+
+class AlertRouter:
+    """Monitor sink routing alerts to channels with deduplication and escalation."""
+
+    def __init__(self, channels: dict[str, Channel], routes: list[Route], escalations: list[Escalation] | None = None,
+                 dedup_window: float = 600.0, dedup_by: list[str] | None = None,
+                 clock: Callable[[], float] = time.time, on_error: Callable[[str], None] | None = None):
+        """Initialize the router.
+
+        Args:
+            channels: Channel name -> channel
+            routes: Routes; an alert goes to the channels of every route it matches
+            escalations: Escalation tiers, checked for every alert
+            dedup_window: Default suppression window in seconds (0 disables deduplication)
+            dedup_by: Alert fields (or `details` keys) identifying repeats (default: rule id and instruction)
+            clock: Time source
+            on_error: Called with a message when a channel fails (default: print to stderr)
+        """
+        for holder in [*routes, *(escalations or [])]:
+            unknown = [c for c in holder.channels if c not in channels]
+            if unknown:
+                raise ValueError(f"unknown channel {', '.join(unknown)} (defined: {', '.join(channels) or 'none'})")
+        self.channels = channels
+        self.routes = routes
+        self.escalations = escalations or []
+        self.dedup_window = dedup_window
+        self.dedup_by = dedup_by or ["rule_id", "instruction"]
+        self.clock = clock
+        self.on_error = on_error or (lambda msg: print(f"alert delivery failed: {msg}", file=sys.stderr))
+        self.deliveries: list[Delivery] = []
+        self._routes: dict[tuple[int, str], _KeyState] = {}
+        self._escalations: dict[tuple[int, str], _KeyState] = {}
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any], env: Mapping[str, str] | None = None, **kwargs: Any) -> "AlertRouter":
+        """Build a router from a routing file's contents (see the module docstring)."""
+        channels = {name: make_channel(name, spec or {}, env) for name, spec in (data.get("channels") or {}).items()}
+        routes = []
+        for i, spec in enumerate(data.get("routes") or []):
+            spec = dict(spec)
+            if isinstance(spec.get("rules"), str):
+                spec["rules"] = [spec["rules"]]
+            _check_severity(spec.get("min_severity", "info"), f"route {i + 1}")
+            routes.append(Route(**spec))
+        escalations = []
+        for i, spec in enumerate(data.get("escalation") or []):
+            _check_severity(spec.get("min_severity", "critical"), f"escalation {i + 1}")
+            escalations.append(Escalation(**spec))
+        if not routes and not escalations:
+            raise ValueError("routing file has no routes")
+        return cls(channels, routes, escalations, dedup_window=float(data.get("dedup_window", 600)),
+                   dedup_by=data.get("dedup_by"), **kwargs)
+
+    def dedup_key(self, alert: Alert) -> str:
+        parts = []
+        for name in self.dedup_by:
+            value = getattr(alert, name) if hasattr(alert, name) else alert.details.get(name)
+            parts.append("" if value is None else str(value))
+        return ":".join(parts)
+
+    def _deliver(self, names: list[str], notification: Notification, escalated: bool) -> None:
+        for name in names:
+            error = None
+            try:
+                self.channels[name].send(notification)
+            except AlertDeliveryError as e:
+                error = str(e)
+                self.on_error(error)
+            self.deliveries.append(Delivery(name, notification.dedup_key, notification.alert.signature,
+                                            escalated, error))
+
+    def __call__(self, alert: Alert) -> list[Delivery]:
+        """Route one alert; returns the deliveries it caused (none when suppressed)."""
+        now = self.clock()
+        key = self.dedup_key(alert)
+        before = len(self.deliveries)
+        targets: dict[str, int] = {}
+        for i, route in enumerate(self.routes):
+            if not route.matches(alert):
+                continue
+            window = self.dedup_window if route.dedup_window is None else route.dedup_window
+            state = self._routes.setdefault((i, key), _KeyState())
+            if state.last_sent is not None and now - state.last_sent < window:
+                state.suppressed += 1
+                continue
+            for name in route.channels:
+                targets.setdefault(name, state.suppressed)
+            state.last_sent, state.suppressed = now, 0
+        # A channel reached by several routes gets the alert once
+        for name, suppressed in targets.items():
+            self._deliver([name], Notification(alert, key, suppressed), escalated=False)
+
+        for i, esc in enumerate(self.escalations):
+            if SEVERITY_ORDER.index(alert.severity) < SEVERITY_ORDER.index(esc.min_severity):
+                continue
+            within = self.dedup_window if esc.within is None else esc.within
+            state = self._escalations.setdefault((i, key), _KeyState())
+            state.seen.append(now)
+            while state.seen and now - state.seen[0] > within:
+                state.seen.popleft()
+            if len(state.seen) < esc.repeats:
+                continue
+            if state.escalated_at is not None and now - state.escalated_at < self.dedup_window:
+                continue
+            state.escalated_at = now
+            notification = Notification(alert, key, escalation=esc.reason(alert, len(state.seen)))
+            self._deliver([c for c in esc.channels if c not in targets], notification, escalated=True)
+        return self.deliveries[before:]
@@ -0,0 +1,230 @@
+"""
+Canary accounts.
+
+An audit's findings are a map of where the program is weak. Once they are
+fixed and the program ships, the same spots are what an attacker who read
+the report, or found them again, probes first. ``plan_canaries`` turns the
+findings into decoys the team creates after deploying, which no honest
+user has a reason to touch:
+
+- decoy-vault: a real, lightly funded account of the type a finding
+  attacked (a `Vault`, a token account), whose authority is a canary key
+  kept offline
+- tripwire-pda: the same for state at a program-derived address, created
+  through the program's own `init` instruction under the canary key, so
+  it looks like any other user's state but belongs to no user
+
+Each canary says which instruction creates it, with what seeds and which
+signers take the canary key. Once its address is filled in, the plan
+yields ``canary-touched`` rules that alert on any call passing the
+account, failed calls included: a fixed weakness is probed with
+transactions that fail.
+"""
+
+import re
+from dataclasses import dataclass, field
+from typing import Any
+
+from .rules import SEVERITY_ORDER, CanaryTouchedRule, _instruction_of
+
+PLAN_FORMAT = "baskerville-canaries"
+PLAN_VERSION = 1
+
+CANARY_KINDS = ("decoy-vault", "tripwire-pda")
+
+_STATE_RE = re.compile(r"\b(?:Account|AccountLoader|InterfaceAccount)\s*<[^<>]*?(\w+)\s*>")
+# Token accounts are created with the token program rather than one of the program's instructions
+_TOKEN_TYPES = ("TokenAccount", "Mint")
+
+
+@dataclass
+class CanarySetup:
+    """How the team creates a canary."""
+
+    # Program instruction that initializes it (None: created with the token program or out of band)
+    instruction: str | None = None
+    account: str | None = None
+    seeds: list[str] | None = None
+    # Signers of the setup instruction that take the canary key
+    signers: list[str] = field(default_factory=list)
+
+    def to_dict(self) -> dict[str, Any]:
+        data = {"instruction": self.instruction, "account": self.account, "seeds": self.seeds,
+                "signers": self.signers}
+        return {k: v for k, v in data.items() if v not in (None, [])}
+
+
+@dataclass
+class Canary:
+    """One decoy account, and the findings whose probes it catches."""
+
+    id: str
+    kind: str
+    account_type: str
+    # Instruction slot a probe passes the canary in
+    instruction: str
+    account: str
+    findings: list[str] = field(default_factory=list)
+    severity: str = "high"
+    setup: CanarySetup = field(default_factory=CanarySetup)
+    # Filled in once the canary is created
+    address: str | None = None
+    notes: str = ""
+
+    def steps(self) -> list[str]:
+        """Setup steps, in words."""
+        setup = self.setup
+        if setup.instruction:
+            seeds = f" (seeds {', '.join(setup.seeds)})" if setup.seeds else ""
+            signers = ", ".join(f"`{s}`" for s in setup.signers) or "the payer"
+            steps = [f"Call `{setup.instruction}` with a fresh canary key as {signers}, creating "
+                     f"`{setup.account}`{seeds}; keep the key offline"]
+        elif self.account_type in _TOKEN_TYPES:
+            steps = ["Create it with the token program (`spl-token create-account <mint> --owner <canary key>`); "
+                     "keep the key offline"]
+        else:
+            steps = [f"Create a `{self.account_type}` account owned by the program; no instruction initializes one"]
+        if self.kind == "decoy-vault":
+            steps.append("Fund it with a small amount, enough to look worth draining")
+        steps.append(f"Record its address as `address` of `{self.id}` and regenerate the rules with --canaries")
+        return steps
+
+    def rule(self) -> CanaryTouchedRule:
+        findings = ", ".join(self.findings)
+        return CanaryTouchedRule(id=self.id, severity=self.severity, account=self.address or "", canary=self.id,
+                                 finding=self.findings[0] if self.findings else None,
+                                 title=f"Canary `{self.id}` touched (probe of {findings} via `{self.instruction}`)")
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"id": self.id, "kind": self.kind, "account_type": self.account_type,
+                "instruction": self.instruction, "account": self.account, "findings": self.findings,
+                "severity": self.severity, "setup": self.setup.to_dict(), "address": self.address,
+                "notes": self.notes}
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "Canary":
+        for key in ("id", "kind", "account_type", "instruction", "account"):
+            if not data.get(key):
+                raise ValueError(f"canary {data.get('id', '?')}: missing {key}")
+        if data["kind"] not in CANARY_KINDS:
+            raise ValueError(f"canary {data['id']}: unknown kind {data['kind']!r}")
+        if data.get("severity", "high") not in SEVERITY_ORDER:
+            raise ValueError(f"canary {data['id']}: unknown severity {data['severity']!r}")
+        setup = data.get("setup") or {}
+        return cls(id=data["id"], kind=data["kind"], account_type=data["account_type"],
+                   instruction=data["instruction"], account=data["account"],
+                   findings=list(data.get("findings") or []), severity=data.get("severity", "high"),
+                   setup=CanarySetup(instruction=setup.get("instruction"), account=setup.get("account"),
+                                     seeds=setup.get("seeds"), signers=list(setup.get("signers") or [])),
+                   address=data.get("address") or None, notes=data.get("notes", ""))
+
+
+@dataclass
+class CanaryPlan:
+    """The canaries for one program."""
+
+    program: str
+    program_id: str | None = None
+    canaries: list[Canary] = field(default_factory=list)
+
+    def rules(self) -> list[CanaryTouchedRule]:
+        """Rules for the canaries that have an address."""
+        return [c.rule() for c in self.canaries if c.address]
+
+    def pending(self) -> list[Canary]:
+        """Canaries not created (or not recorded) yet."""
+        return [c for c in self.canaries if not c.address]
+
+    def merge(self, previous: "CanaryPlan") -> None:
+        """Keep the addresses already recorded in ``previous``."""
+        addresses = {c.id: c.address for c in previous.canaries if c.address}
+        for canary in self.canaries:
+            canary.address = canary.address or addresses.get(canary.id)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"format": PLAN_FORMAT, "version": PLAN_VERSION, "program": self.program,
+                "program_id": self.program_id, "canaries": [c.to_dict() for c in self.canaries]}
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "CanaryPlan":
+        if data.get("format") != PLAN_FORMAT:
+            raise ValueError(f"not a canary plan (format {data.get('format')!r})")
+        if not data.get("program"):
+            raise ValueError("canary plan needs a program")
+        canaries = [Canary.from_dict(c) for c in data.get("canaries") or []]
+        ids = [c.id for c in canaries]
+        if len(set(ids)) != len(ids):
+            raise ValueError("every canary needs a unique id")
+        return cls(program=data["program"], program_id=data.get("program_id"), canaries=canaries)
+
+
+def _state_type(acc: Any) -> str | None:
+    m = _STATE_RE.search(acc.ty)
+    return m.group(1) if m else None
+
+
+def _bait(hyp: dict[str, Any], ix: Any) -> Any | None:
+    """The account of ``ix`` a probe of the finding passes: the one it names, else the state it writes."""
+    props = hyp.get("properties") or {}
+    named = [props.get("account"), *(props.get("accounts") or [])]
+    for name in named:
+        acc = ix.account(name) if name else None
+        if acc is not None and _state_type(acc):
+            return acc
+    state = [a for a in ix.accounts if _state_type(a)]
+    return next((a for a in state if a.is_mut), state[0] if state else None)
+
+
+def _setup(program: Any, account_type: str) -> tuple[CanarySetup, Any | None]:
+    """The instruction initializing accounts of ``account_type``, and that account."""
+    for ix in program.instructions:
+        for acc in ix.accounts:
+            if _state_type(acc) == account_type and {"init", "init_if_needed"} & set(acc.constraints):
+                signers = [a.name for a in ix.accounts if a.is_signer]
+                return CanarySetup(instruction=ix.name, account=acc.name, seeds=acc.seeds, signers=signers), acc
+    return CanarySetup(), None
+
+
+def plan_canaries(hypotheses: dict[str, dict[str, Any]], program: Any, program_id: str | None = None,
+                  min_severity: str = "low") -> CanaryPlan:
+    """Plan canary accounts for one IR ``Program`` from a hypothesis store's entries.
+
+    Every finding pinned to an instruction gets a canary in the state slot
+    its exploit goes through; findings sharing a slot share the canary.
+
+    Args:
+        hypotheses: Hypothesis id -> hypothesis (the `hypotheses` map of hypotheses.json)
+        program: IR ``Program`` the canaries are created in
+        program_id: Deployed address (default: the IR's `declare_id!`)
+        min_severity: Ignore findings below this severity
+    """
+    by_name = {ix.name: ix for ix in program.instructions}
+    floor = SEVERITY_ORDER.index(min_severity)
+    plan = CanaryPlan(program=program.name, program_id=program_id or program.program_id)
+    slots: dict[tuple[str, str], Canary] = {}
+    for hyp_id, hyp in sorted(hypotheses.items()):
+        severity = str(hyp.get("severity", "medium")).lower()
+        props = hyp.get("properties") or {}
+        if (hyp.get("status") in ("rejected", "false_positive") or severity not in SEVERITY_ORDER
+                or SEVERITY_ORDER.index(severity) < floor
+                or props.get("program") not in (None, program.name)):
+            continue
+        name = _instruction_of(hyp, list(by_name))
+        bait = _bait(hyp, by_name[name]) if name else None
+        if bait is None:
+            continue
+        canary = slots.get((name, bait.name))
+        if canary is None:
+            account_type = _state_type(bait)
+            setup, init = _setup(program, account_type)
+            seeds = bait.seeds if bait.seeds is not None else (init.seeds if init is not None else None)
+            kind = "tripwire-pda" if seeds is not None else "decoy-vault"
+            canary = Canary(id=f"canary-{name}-{bait.name}".replace("_", "-"), kind=kind,
+                            account_type=account_type, instruction=name, account=bait.name, severity=severity,
+                            setup=setup, notes=f"Passed as `{bait.name}` to `{name}` only by a probe of the findings")
+            slots[(name, bait.name)] = canary
+            plan.canaries.append(canary)
+        canary.findings.append(hyp_id)
+        if SEVERITY_ORDER.index(severity) > SEVERITY_ORDER.index(canary.severity):
+            canary.severity = severity
+    return plan
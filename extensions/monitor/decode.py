@@ -0,0 +1,167 @@
+"""
+Transaction decoding for monitoring.
+
+Turns a `getTransaction` result (`json` encoding, legacy or v0 with
+loaded addresses) into a ``TxEvent``: the calls into the monitored
+program, top-level and CPI, with resolved accounts, signer and writable
+flags and the instruction name recovered from its discriminator, plus
+per-account token balance changes and, given the program's event schemas,
+the events it emitted.
+"""
+
+import hashlib
+from dataclasses import dataclass, field
+from typing import TYPE_CHECKING, Any
+
+from extensions.ir.anchor_config import b58decode
+
+if TYPE_CHECKING:
+    from .events import DecodedEvent, EventDecoder
+
+
+def anchor_discriminator(name: str) -> bytes:
+    """First 8 bytes of sha256("global:<name>"), Anchor's instruction discriminator."""
+    return hashlib.sha256(f"global:{name}".encode()).digest()[:8]
+
+
+def instruction_discriminators(program: Any) -> dict[str, str]:
+    """Discriminator (hex) -> instruction name for an IR ``Program``.
+
+    Anchor programs use the sighash of the handler name; native programs a
+    leading enum tag byte when the front-end recovered a numeric one.
+    """
+    table: dict[str, str] = {}
+    for ix in program.instructions:
+        if program.framework in ("anchor", "seahorse"):
+            table[anchor_discriminator(ix.handler or ix.name).hex()] = ix.name
+        elif ix.discriminator is not None and str(ix.discriminator).isdigit() and int(ix.discriminator) < 256:
+            table[bytes([int(ix.discriminator)]).hex()] = ix.name
+    return table
+
+
+@dataclass
+class InstructionCall:
+    """One invocation of the monitored program."""
+
+    program_id: str
+    data: bytes
+    accounts: list[str]
+    signer: list[bool]
+    writable: list[bool]
+    # Position of the top-level instruction, and whether this is a CPI under it
+    index: int
+    inner: bool = False
+    name: str | None = None
+
+    def position(self, ref: int | str | None, names: list[str] | None = None) -> int | None:
+        """Index of an account given by position or (with ``names``, the IR account order) by name."""
+        if isinstance(ref, str) and names and ref in names:
+            ref = names.index(ref)
+        if isinstance(ref, int) and 0 <= ref < len(self.accounts):
+            return ref
+        return None
+
+    def account(self, ref: int | str | None, names: list[str] | None = None) -> str | None:
+        pos = self.position(ref, names)
+        return None if pos is None else self.accounts[pos]
+
+
+@dataclass
+class TokenDelta:
+    """Change of one token account's balance within a transaction."""
+
+    account: str
+    mint: str
+    owner: str | None
+    # Raw amount change (negative: tokens left the account)
+    delta: int
+    decimals: int
+
+    @property
+    def ui_delta(self) -> float:
+        return self.delta / 10 ** self.decimals
+
+
+@dataclass
+class TxEvent:
+    """A transaction, reduced to what monitoring rules look at."""
+
+    signature: str
+    slot: int
+    block_time: int | None
+    success: bool
+    error: Any
+    signers: list[str]
+    calls: list[InstructionCall] = field(default_factory=list)
+    token_deltas: list[TokenDelta] = field(default_factory=list)
+    logs: list[str] = field(default_factory=list)
+    events: list["DecodedEvent"] = field(default_factory=list)
+
+    @property
+    def fee_payer(self) -> str | None:
+        return self.signers[0] if self.signers else None
+
+    def calls_to(self, name: str) -> list[InstructionCall]:
+        return [c for c in self.calls if c.name == name]
+
+
+def decode_transaction(tx: dict[str, Any], program_id: str, discriminators: dict[str, str] | None = None,
+                       signature: str | None = None, events: "EventDecoder | None" = None) -> TxEvent:
+    """Decode a `getTransaction` result (json encoding) for calls into ``program_id``."""
+    message = tx["transaction"]["message"]
+    meta = tx.get("meta") or {}
+    keys = list(message["accountKeys"])
+    header = message.get("header", {})
+    n_signed = header.get("numRequiredSignatures", 0)
+    n_signed_ro = header.get("numReadonlySignedAccounts", 0)
+    n_unsigned_ro = header.get("numReadonlyUnsignedAccounts", 0)
+    static = len(keys)
+    writable = [
+        i < n_signed - n_signed_ro if i < n_signed else i < static - n_unsigned_ro
+        for i in range(static)
+    ]
+    loaded = meta.get("loadedAddresses") or {}
+    keys += loaded.get("writable", []) + loaded.get("readonly", [])
+    writable += [True] * len(loaded.get("writable", [])) + [False] * len(loaded.get("readonly", []))
+    signer = [i < n_signed for i in range(len(keys))]
+    discriminators = discriminators or {}
+
+    def make(ix: dict[str, Any], index: int, inner: bool) -> InstructionCall | None:
+        if keys[ix["programIdIndex"]] != program_id:
+            return None
+        data = b58decode(ix.get("data", ""))
+        name = discriminators.get(data[:8].hex()) or discriminators.get(data[:1].hex())
+        idx = ix.get("accounts", [])
+        return InstructionCall(program_id=program_id, data=data, accounts=[keys[i] for i in idx],
+                               signer=[signer[i] for i in idx], writable=[writable[i] for i in idx],
+                               index=index, inner=inner, name=name)
+
+    calls = [c for i, ix in enumerate(message.get("instructions", [])) if (c := make(ix, i, False))]
+    for group in meta.get("innerInstructions") or []:
+        calls += [c for ix in group.get("instructions", []) if (c := make(ix, group["index"], True))]
+    calls.sort(key=lambda c: (c.index, c.inner))
+
+    balances: dict[int, dict[str, Any]] = {}
+    for side, sign in (("preTokenBalances", -1), ("postTokenBalances", 1)):
+        for entry in meta.get(side) or []:
+            slot = balances.setdefault(entry["accountIndex"], {"mint": entry["mint"], "owner": entry.get("owner"),
+                                                               "decimals": entry["uiTokenAmount"]["decimals"],
+                                                               "delta": 0})
+            slot["delta"] += sign * int(entry["uiTokenAmount"]["amount"])
+    deltas = [TokenDelta(account=keys[i], mint=b["mint"], owner=b["owner"], delta=b["delta"], decimals=b["decimals"])
+              for i, b in sorted(balances.items()) if b["delta"]]
+
+    logs = meta.get("logMessages") or []
+    signatures = tx["transaction"].get("signatures") or []
+    return TxEvent(
+        signature=signature or (signatures[0] if signatures else ""),
+        slot=tx.get("slot", 0),
+        block_time=tx.get("blockTime"),
+        success=meta.get("err") is None,
+        error=meta.get("err"),
+        signers=keys[:n_signed],
+        calls=calls,
+        token_deltas=deltas,
+        logs=logs,
+        events=events.from_logs(logs, program_id) + events.from_calls(calls) if events else [],
+    )
@@ -0,0 +1,431 @@
+"""
+Program event schemas and decoding.
+
+Anchor programs emit events as borsh-serialized structs prefixed with the
+event's discriminator (the first 8 bytes of sha256("event:<Name>")):
+``emit!`` logs them as a ``Program data:`` line, ``emit_cpi!`` passes them
+as the data of a self-CPI tagged with ``EVENT_IX_TAG``. Schemas come from
+the Anchor IDL (legacy or 0.30+) or, without one, from the program's
+`#[event]` structs, which also tell which instructions emit each event.
+
+``EventDecoder`` turns a transaction's logs and self-CPIs into
+``DecodedEvent``s for the monitor and `monitor replay`;
+``generate_event_decoders`` compiles the same schemas into the Rust the
+Geyser plugin decodes them with.
+"""
+
+import base64
+import hashlib
+import json
+import re
+import struct
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+from extensions.ir.anchor_config import b58encode
+
+from .decode import InstructionCall
+
+# Anchor's `EVENT_IX_TAG` (0x1d9acb512ea545e4), little-endian, leading self-CPI event data
+EVENT_IX_TAG = bytes.fromhex("e445a52e51cb9a1d")
+
+_INT = re.compile(r"^(?P<sign>[ui])(?P<bits>8|16|32|64|128)$")
+_INVOKE = re.compile(r"^Program (\w+) invoke \[\d+\]$")
+_EXIT = re.compile(r"^Program (\w+) (?:success|failed)")
+
+
+def event_discriminator(name: str) -> bytes:
+    """First 8 bytes of sha256("event:<Name>"), Anchor's event discriminator."""
+    return hashlib.sha256(f"event:{name}".encode()).digest()[:8]
+
+
+@dataclass
+class EventSchema:
+    """The layout of one event."""
+
+    name: str
+    discriminator: str  # Hex
+    # {"name", "type"} per field, types in the IDL's (0.30+) form
+    fields: list[dict[str, Any]] = field(default_factory=list)
+    file: str = ""
+    line: int = 0
+    # Instructions whose handlers emit it (known from sources only)
+    emitted_by: list[str] = field(default_factory=list)
+
+    def to_dict(self) -> dict[str, Any]:
+        data: dict[str, Any] = {"name": self.name, "discriminator": self.discriminator, "fields": self.fields}
+        if self.file:
+            data.update(file=self.file, line=self.line)
+        if self.emitted_by:
+            data["emitted_by"] = self.emitted_by
+        return data
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "EventSchema":
+        if not data.get("name"):
+            raise ValueError("every event needs a name")
+        disc = data.get("discriminator") or event_discriminator(data["name"]).hex()
+        return cls(name=data["name"], discriminator=disc,
+                   fields=[dict(f) for f in data.get("fields") or []], file=data.get("file", ""),
+                   line=int(data.get("line", 0)), emitted_by=list(data.get("emitted_by") or []))
+
+
+@dataclass
+class DecodedEvent:
+    """An event found in a transaction."""
+
+    name: str
+    fields: dict[str, Any] | None
+    source: str  # "log" (emit!) or "cpi" (emit_cpi!)
+    # Set when the data does not match the schema
+    error: str | None = None
+
+    def to_dict(self) -> dict[str, Any]:
+        data = {"name": self.name, "source": self.source, "fields": self.fields}
+        if self.error:
+            data["error"] = self.error
+        return data
+
+
+def _struct_fields(fields: list[Any]) -> list[dict[str, Any]]:
+    """Named fields, or tuple fields named by position."""
+    return [f if isinstance(f, dict) and "name" in f else {"name": str(i), "type": f} for i, f in enumerate(fields)]
+
+
+def decode_value(ty: Any, data: bytes, pos: int, types: dict[str, dict[str, Any]]) -> tuple[Any, int]:
+    """Borsh-decode one value of IDL type ``ty`` at ``pos``; returns it and the position after it.
+
+    Pubkeys decode to base58, bytes to hex; raises ValueError on short data or an unknown type.
+    """
+    from extensions.simulation.boundary import normalize_type
+
+    ty = normalize_type(ty)
+
+    def take(n: int) -> bytes:
+        if pos + n > len(data):
+            raise ValueError(f"data ends at byte {len(data)}, {pos + n} needed")
+        return data[pos:pos + n]
+
+    if isinstance(ty, str):
+        m = _INT.match(ty)
+        if m:
+            size = int(m.group("bits")) // 8
+            return int.from_bytes(take(size), "little", signed=m.group("sign") == "i"), pos + size
+        if ty == "bool":
+            return take(1) != b"\x00", pos + 1
+        if ty in ("f32", "f64"):
+            size = 4 if ty == "f32" else 8
+            return struct.unpack("<f" if ty == "f32" else "<d", take(size))[0], pos + size
+        if ty == "pubkey":
+            return b58encode(take(32)), pos + 32
+        if ty in ("string", "bytes"):
+            n = int.from_bytes(take(4), "little")
+            pos += 4
+            raw = take(n)
+            if ty == "bytes":
+                return raw.hex(), pos + n
+            return raw.decode("utf-8", errors="replace"), pos + n
+        raise ValueError(f"cannot decode type {ty}")
+    if "option" in ty:
+        tag = take(1)
+        return (None, pos + 1) if tag == b"\x00" else decode_value(ty["option"], data, pos + 1, types)
+    if "vec" in ty or "array" in ty:
+        if "vec" in ty:
+            elem, n = ty["vec"], int.from_bytes(take(4), "little")
+            pos += 4
+        else:
+            elem, n = ty["array"]
+        if elem == "u8":
+            return take(n).hex(), pos + n
+        values = []
+        for _ in range(n):
+            value, pos = decode_value(elem, data, pos, types)
+            values.append(value)
+        return values, pos
+    name = ty.get("defined", {}).get("name")
+    if name not in types:
+        raise ValueError(f"cannot decode type {name or json.dumps(ty)}")
+    typedef = types[name]
+    if typedef.get("kind") == "enum":
+        index = take(1)[0]
+        variants = typedef.get("variants") or []
+        if index >= len(variants):
+            raise ValueError(f"{name} has no variant {index}")
+        variant = variants[index]
+        if not variant.get("fields"):
+            return variant["name"], pos + 1
+        values, pos = _decode_fields(_struct_fields(variant["fields"]), data, pos + 1, types)
+        return {variant["name"]: values}, pos
+    return _decode_fields(_struct_fields(typedef.get("fields") or []), data, pos, types)
+
+
+def _decode_fields(fields: list[dict[str, Any]], data: bytes, pos: int,
+                   types: dict[str, dict[str, Any]]) -> tuple[dict[str, Any], int]:
+    values = {}
+    for f in fields:
+        values[f["name"]], pos = decode_value(f["type"], data, pos, types)
+    return values, pos
+
+
+class EventDecoder:
+    """Decodes one program's events by discriminator."""
+
+    def __init__(self, events: list[EventSchema], types: dict[str, dict[str, Any]] | None = None):
+        self.events = {bytes.fromhex(e.discriminator): e for e in events}
+        self.types = types or {}
+
+    def decode(self, data: bytes, source: str = "log") -> DecodedEvent | None:
+        """The event ``data`` (discriminator first) holds; None when no schema matches."""
+        schema = self.events.get(bytes(data[:8]))
+        if schema is None:
+            return None
+        try:
+            fields, _ = _decode_fields(schema.fields, data, 8, self.types)
+        except ValueError as e:
+            return DecodedEvent(schema.name, None, source, error=str(e))
+        return DecodedEvent(schema.name, fields, source)
+
+    def from_logs(self, logs: list[str], program_id: str) -> list[DecodedEvent]:
+        """Events ``program_id`` logged itself (``Program data:`` lines while it is executing)."""
+        stack: list[str] = []
+        events = []
+        for line in logs:
+            if m := _INVOKE.match(line):
+                stack.append(m.group(1))
+            elif (m := _EXIT.match(line)) and stack and stack[-1] == m.group(1):
+                stack.pop()
+            elif line.startswith("Program data: ") and stack and stack[-1] == program_id:
+                try:
+                    data = b"".join(base64.b64decode(chunk) for chunk in line[len("Program data: "):].split())
+                except ValueError:
+                    continue
+                event = self.decode(data, "log")
+                if event:
+                    events.append(event)
+        return events
+
+    def from_calls(self, calls: list[InstructionCall]) -> list[DecodedEvent]:
+        """Events passed to self-CPIs (``emit_cpi!``)."""
+        events = []
+        for call in calls:
+            if call.inner and call.data.startswith(EVENT_IX_TAG):
+                event = self.decode(call.data[len(EVENT_IX_TAG):], "cpi")
+                if event:
+                    events.append(event)
+        return events
+
+
+def schemas_from_idl(path: Path) -> tuple[list[EventSchema], dict[str, dict[str, Any]]]:
+    """Event schemas and defined types of an Anchor IDL, legacy (fields inline) or 0.30+ (fields in types)."""
+    from extensions.simulation.boundary import load_idl
+
+    _, types = load_idl(path)
+    idl = json.loads(Path(path).read_text())
+    events = []
+    for ev in idl.get("events") or []:
+        disc = bytes(ev["discriminator"]).hex() if "discriminator" in ev else event_discriminator(ev["name"]).hex()
+        fields = ev["fields"] if "fields" in ev else types.get(ev["name"], {}).get("fields") or []
+        events.append(EventSchema(ev["name"], disc, [{"name": f["name"], "type": f["type"]} for f in fields]))
+    return events, types
+
+
+def schemas_from_program(program: Any) -> tuple[list[EventSchema], dict[str, dict[str, Any]]]:
+    """Event schemas of an IR ``Program``'s `#[event]` structs, with its account types for nested fields."""
+    from extensions.simulation.boundary import EncodeError, _ir_types, rust_type
+
+    events = []
+    for ev in program.events:
+        fields = []
+        for f in ev.fields:
+            try:
+                fields.append({"name": f.name, "type": rust_type(f.ty)})
+            except EncodeError:
+                fields.append({"name": f.name, "type": {"defined": {"name": f.ty}}})
+        events.append(EventSchema(ev.name, event_discriminator(ev.name).hex(), fields, file=ev.file, line=ev.line,
+                                  emitted_by=list(ev.emitted_by)))
+    return events, _ir_types(program)
+
+
+# ---------------------------------------------------------------------------
+# Rust decoders for the Geyser plugin
+# ---------------------------------------------------------------------------
+
+def _rs_ident(name: str) -> str:
+    return "read_" + re.sub(r"[^a-z0-9]+", "_", re.sub(r"(?<=[a-z0-9])([A-Z])", r"_\1", name).lower()).strip("_")
+
+
+def _rs_str(value: str) -> str:
+    return json.dumps(value)
+
+
+def _rs_read(ty: Any, types: dict[str, dict[str, Any]]) -> str:
+    """Rust expression reading one value of ``ty`` from ``r`` as a ``serde_json::Value``."""
+    from extensions.simulation.boundary import normalize_type
+
+    ty = normalize_type(ty)
+    if isinstance(ty, str):
+        m = _INT.match(ty)
+        if m:
+            bits = m.group("bits")
+            read = f"r.{m.group('sign')}{bits}()?"
+            # JSON numbers lose precision past 2^53; 128-bit values are given as strings
+            return f"Value::from({read}.to_string())" if bits == "128" else f"Value::from({read})"
+        simple = {"bool": "Value::from(r.u8()? != 0)", "f32": "Value::from(r.f32()?)", "f64": "Value::from(r.f64()?)",
+                  "pubkey": "Value::from(r.pubkey()?)", "string": "Value::from(r.string()?)",
+                  "bytes": "{ let n = r.u32()? as usize; Value::from(r.hex(n)?) }"}
+        if ty not in simple:
+            raise ValueError(f"cannot decode type {ty}")
+        return simple[ty]
+    if "option" in ty:
+        return f"if r.u8()? == 0 {{ Value::Null }} else {{ {_rs_read(ty['option'], types)} }}"
+    if "vec" in ty or "array" in ty:
+        elem = ty["vec"] if "vec" in ty else ty["array"][0]
+        count = "r.u32()? as usize" if "vec" in ty else str(int(ty["array"][1]))
+        if elem == "u8":
+            return f"{{ let n = {count}; Value::from(r.hex(n)?) }}"
+        return (f"{{ let n = {count}; let mut v = Vec::new(); for _ in 0..n {{ v.push({_rs_read(elem, types)}); }} "
+                "Value::Array(v) }")
+    name = ty.get("defined", {}).get("name")
+    if name not in types:
+        raise ValueError(f"cannot decode type {name or json.dumps(ty)}")
+    return f"{_rs_ident(name)}(r)?"
+
+
+def _rs_map(fields: list[dict[str, Any]], types: dict[str, dict[str, Any]], indent: str) -> str:
+    """Statements reading ``fields`` in order into ``m``, a JSON object."""
+    lines = [f"{indent}let mut m = Map::new();"]
+    lines += [f"{indent}m.insert({_rs_str(f['name'])}.into(), {_rs_read(f['type'], types)});" for f in fields]
+    return "\n".join(lines)
+
+
+def _rs_reader(fn: str, fields: list[dict[str, Any]], types: dict[str, dict[str, Any]]) -> str:
+    body = _rs_map(fields, types, "    ")
+    return f"fn {fn}(r: &mut Reader) -> Option<Value> {{\n{body}\n    Some(Value::Object(m))\n}}"
+
+
+def _rs_type(name: str, types: dict[str, dict[str, Any]]) -> str:
+    typedef = types[name]
+    if typedef.get("kind") != "enum":
+        return _rs_reader(_rs_ident(name), _struct_fields(typedef.get("fields") or []), types)
+    arms = []
+    for i, variant in enumerate(typedef.get("variants") or []):
+        label = _rs_str(variant["name"])
+        if not variant.get("fields"):
+            arms.append(f"        {i} => Value::from({label}),")
+            continue
+        arms.append(f"        {i} => {{\n{_rs_map(_struct_fields(variant['fields']), types, ' ' * 12)}\n"
+                    f"            Value::Object([({label}.to_string(), Value::Object(m))].into_iter().collect())\n"
+                    "        }")
+    arms.append("        _ => return None,")
+    return (f"fn {_rs_ident(name)}(r: &mut Reader) -> Option<Value> {{\n    Some(match r.u8()? {{\n"
+            + "\n".join(arms) + "\n    })\n}")
+
+
+def _used_types(ty: Any, types: dict[str, dict[str, Any]], seen: list[str]) -> None:
+    """Add the defined types ``ty`` refers to, dependencies first, to ``seen``."""
+    from extensions.simulation.boundary import normalize_type
+
+    ty = normalize_type(ty)
+    if isinstance(ty, str):
+        return
+    for key in ("option", "vec"):
+        if key in ty:
+            return _used_types(ty[key], types, seen)
+    if "array" in ty:
+        return _used_types(ty["array"][0], types, seen)
+    name = ty.get("defined", {}).get("name")
+    if name in types and name not in seen:
+        seen.append(name)
+        typedef = types[name]
+        for f in _struct_fields(typedef.get("fields") or []):
+            _used_types(f["type"], types, seen)
+        for variant in typedef.get("variants") or []:
+            for f in _struct_fields(variant.get("fields") or []):
+                _used_types(f["type"], types, seen)
+
+
+def generate_event_decoders(events: list[EventSchema], types: dict[str, dict[str, Any]] | None = None,
+                            program: str | None = None) -> str:
+    """Rust module (the Geyser plugin's `src/events.rs`) decoding ``events`` into JSON values.
+
+    Raises ValueError when a field type cannot be decoded (a defined type missing from ``types``).
+    """
+    types = types or {}
+    used: list[str] = []
+    for ev in events:
+        for f in ev.fields:
+            _used_types(f["type"], types, used)
+    table = "\n".join(
+        f"    (&[{', '.join(f'0x{b:02x}' for b in bytes.fromhex(ev.discriminator))}], {_rs_str(ev.name)}, "
+        f"{_rs_ident('event_' + ev.name)}),"
+        for ev in events
+    )
+    readers = [_rs_type(name, types) for name in used]
+    readers += [_rs_reader(_rs_ident("event_" + ev.name), ev.fields, types) for ev in events]
+    readers_rs = "\n\n".join(readers)
+    origin = f" of `{program}`" if program else ""
+    return f"""//! Event decoders{origin}, generated by Baskerville from the program's event schemas.
+//! Regenerate with `baskerville monitor geyser` rather than editing by hand.
+#![allow(dead_code, unused_mut)]
+
+use serde_json::{{json, Map, Value}};
+use solana_sdk::pubkey::Pubkey;
+
+/// Anchor's `EVENT_IX_TAG`, leading the data of `emit_cpi!` self-CPIs
+pub const EVENT_IX_TAG: [u8; 8] = [0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d];
+
+struct Reader<'a> {{
+    data: &'a [u8],
+    pos: usize,
+}}
+
+macro_rules! le {{
+    ($($name:ident: $ty:ty),*) => {{
+        $(fn $name(&mut self) -> Option<$ty> {{
+            Some(<$ty>::from_le_bytes(self.take(std::mem::size_of::<$ty>())?.try_into().ok()?))
+        }})*
+    }};
+}}
+
+impl<'a> Reader<'a> {{
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {{
+        let bytes = self.data.get(self.pos..self.pos.checked_add(n)?)?;
+        self.pos += n;
+        Some(bytes)
+    }}
+
+    le!(u8: u8, u16: u16, u32: u32, u64: u64, u128: u128, i8: i8, i16: i16, i32: i32, i64: i64, i128: i128,
+        f32: f32, f64: f64);
+
+    fn pubkey(&mut self) -> Option<String> {{
+        Some(Pubkey::new_from_array(self.take(32)?.try_into().ok()?).to_string())
+    }}
+
+    fn string(&mut self) -> Option<String> {{
+        let n = self.u32()? as usize;
+        Some(String::from_utf8_lossy(self.take(n)?).into_owned())
+    }}
+
+    fn hex(&mut self, n: usize) -> Option<String> {{
+        Some(self.take(n)?.iter().map(|b| format!("{{:02x}}", b)).collect())
+    }}
+}}
+
+{readers_rs}
+
+/// Event discriminator -> name and decoder
+const EVENTS: &[(&[u8], &str, fn(&mut Reader) -> Option<Value>)] = &[
+{table}
+];
+
+/// Decode event data (discriminator first) into `{{"name", "fields"}}`; None for unknown events.
+pub fn decode(data: &[u8], source: &str) -> Option<Value> {{
+    let (_, name, read) = EVENTS.iter().find(|(disc, _, _)| data.starts_with(disc))?;
+    let mut r = Reader {{ data, pos: 8 }};
+    Some(match read(&mut r) {{
+        Some(fields) => json!({{ "name": name, "source": source, "fields": fields }}),
+        None => json!({{ "name": name, "source": source, "fields": null, "error": "data does not match the schema" }}),
+    }})
+}}
+"""
@@ -0,0 +1,273 @@
+"""
+Geyser plugin generation.
+
+Compiles monitoring rules into a standalone Rust crate implementing the
+Agave `GeyserPlugin` interface, so validator and RPC operators can run
+the same detection on the transaction firehose instead of polling RPC.
+
+The crate's runtime (`src/lib.rs`) is fixed; ``generate_geyser_plugin``
+only emits the tables the runtime reads: the program id, instruction
+discriminators and the selected rules, with account names resolved to
+their positions in each instruction, plus decoders for the program's
+events, which alerts carry alongside the rule's details.
+"""
+
+import json
+import math
+import re
+from pathlib import Path
+from typing import Any
+
+from .events import generate_event_decoders
+from .rules import (
+    CanaryTouchedRule,
+    FailureBurstRule,
+    InstructionCalledRule,
+    LargeOutflowRule,
+    Rule,
+    RuleSet,
+    UnexpectedAuthorityRule,
+)
+
+GEYSER_INTERFACE_VERSION = "~2.1"
+SOLANA_VERSION = "~2.1"
+
+_RUNTIME = Path(__file__).parent / "templates" / "geyser" / "lib.rs"
+
+
+def _rs_str(value: str) -> str:
+    escaped = value.replace("\\", "\\\\").replace('"', '\\"').replace("\n", "\\n").replace("\r", "\\r")
+    return f'"{escaped}"'
+
+
+def _rs_opt_str(value: str | None) -> str:
+    return "None" if value is None else f"Some({_rs_str(value)})"
+
+
+def _rs_f64(value: float | None, rule_id: str) -> str:
+    if value is None:
+        return "None"
+    if not math.isfinite(float(value)):
+        raise ValueError(f"rule {rule_id}: {value!r} is not a finite number")
+    return f"Some({float(value)!r}_f64)"
+
+
+def crate_name(ruleset: RuleSet) -> str:
+    base = re.sub(r"[^a-z0-9]+", "-", (ruleset.program or ruleset.program_id[:8]).lower()).strip("-")
+    return f"{base or 'program'}-baskerville-geyser"
+
+
+def _account_ref(rule: UnexpectedAuthorityRule, ruleset: RuleSet) -> str:
+    if isinstance(rule.account, int):
+        return f"AccountRef::Index({rule.account})"
+    names = [rule.instruction] if rule.instruction else sorted(ruleset.accounts)
+    table = [(ix, ruleset.accounts[ix].index(rule.account)) for ix in names
+             if rule.account in ruleset.accounts.get(ix, [])]
+    if not table:
+        where = f"`{rule.instruction}`" if rule.instruction else "any instruction"
+        raise ValueError(f"rule {rule.id}: account `{rule.account}` is not an account of {where} "
+                         "(use its position instead)")
+    entries = ", ".join(f"({_rs_str(ix)}, {pos})" for ix, pos in table)
+    return f"AccountRef::Named(&[{entries}])"
+
+
+def _rule_kind(rule: Rule, ruleset: RuleSet) -> str:
+    if isinstance(rule, UnexpectedAuthorityRule):
+        expected = ", ".join(_rs_str(e) for e in rule.expected)
+        return (f"RuleKind::UnexpectedAuthority {{ account: {_account_ref(rule, ruleset)}, "
+                f"account_label: {_rs_str(str(rule.account))}, expected: &[{expected}], "
+                f"successful_only: {str(rule.successful_only).lower()} }}")
+    if isinstance(rule, InstructionCalledRule):
+        return f"RuleKind::InstructionCalled {{ successful_only: {str(rule.successful_only).lower()} }}"
+    if isinstance(rule, LargeOutflowRule):
+        return (f"RuleKind::LargeOutflow {{ mint: {_rs_opt_str(rule.mint)}, "
+                f"threshold: {_rs_f64(rule.threshold, rule.id)}, multiplier: {_rs_f64(rule.multiplier, rule.id)}, "
+                f"window: {int(rule.window)}, min_samples: {int(rule.min_samples)} }}")
+    if isinstance(rule, FailureBurstRule):
+        return f"RuleKind::FailureBurst {{ count: {int(rule.count)}, window_slots: {int(rule.window_slots)} }}"
+    if isinstance(rule, CanaryTouchedRule):
+        if not rule.account:
+            raise ValueError(f"rule {rule.id}: canary `{rule.canary}` has no address yet")
+        return f"RuleKind::CanaryTouched {{ account: {_rs_str(rule.account)}, canary: {_rs_str(rule.canary)} }}"
+    raise ValueError(f"rule {rule.id}: {rule.kind} rules cannot run in a Geyser plugin")
+
+
+def _rules_rs(ruleset: RuleSet, rules: list[Rule], name: str, source: str | None) -> str:
+    discs = sorted(((bytes.fromhex(h), n) for h, n in ruleset.discriminators.items()), key=lambda d: -len(d[0]))
+    disc_lines = "\n".join(
+        f"    (&[{', '.join(f'0x{b:02x}' for b in disc)}], {_rs_str(ix)})," for disc, ix in discs
+    )
+    rule_lines = "\n".join(
+        "        Rule {\n"
+        f"            id: {_rs_str(r.id)},\n"
+        f"            severity: {_rs_str(r.severity)},\n"
+        f"            title: {_rs_str(r.title)},\n"
+        f"            instruction: {_rs_opt_str(r.instruction)},\n"
+        f"            finding: {_rs_opt_str(r.finding)},\n"
+        f"            include_inner: {str(r.include_inner).lower()},\n"
+        f"            kind: {_rule_kind(r, ruleset)},\n"
+        "        },"
+        for r in rules
+    )
+    origin = f" from {source}" if source else ""
+    return f"""//! Rules for `{ruleset.program or ruleset.program_id}`, generated by Baskerville{origin}.
+//! Regenerate with `baskerville monitor geyser` rather than editing by hand.
+#![allow(dead_code)]
+
+pub const PLUGIN_NAME: &str = {_rs_str(name)};
+pub const PROGRAM_ID: &str = {_rs_str(ruleset.program_id)};
+
+/// Instruction discriminator prefix -> name, longest first
+pub const DISCRIMINATORS: &[(&[u8], &str)] = &[
+{disc_lines}
+];
+
+/// An account given by position, or by name with its position per instruction
+#[derive(Debug)]
+pub enum AccountRef {{
+    Index(usize),
+    Named(&'static [(&'static str, usize)]),
+}}
+
+#[derive(Debug)]
+pub enum RuleKind {{
+    UnexpectedAuthority {{
+        account: AccountRef,
+        account_label: &'static str,
+        expected: &'static [&'static str],
+        successful_only: bool,
+    }},
+    InstructionCalled {{
+        successful_only: bool,
+    }},
+    LargeOutflow {{
+        mint: Option<&'static str>,
+        threshold: Option<f64>,
+        multiplier: Option<f64>,
+        window: usize,
+        min_samples: usize,
+    }},
+    FailureBurst {{
+        count: usize,
+        window_slots: u64,
+    }},
+    CanaryTouched {{
+        account: &'static str,
+        canary: &'static str,
+    }},
+}}
+
+#[derive(Debug)]
+pub struct Rule {{
+    pub id: &'static str,
+    pub severity: &'static str,
+    pub title: &'static str,
+    pub instruction: Option<&'static str>,
+    pub finding: Option<&'static str>,
+    pub include_inner: bool,
+    pub kind: RuleKind,
+}}
+
+pub fn rules() -> Vec<Rule> {{
+    vec![
+{rule_lines}
+    ]
+}}
+"""
+
+
+def _cargo_toml(name: str) -> str:
+    return f"""[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2021"
+publish = false
+description = "Baskerville monitoring rules as an Agave Geyser plugin"
+
+[lib]
+crate-type = ["cdylib", "rlib"]
+
+[dependencies]
+agave-geyser-plugin-interface = "{GEYSER_INTERFACE_VERSION}"
+base64 = "0.22"
+log = {{ version = "0.4", features = ["std"] }}
+serde = {{ version = "1", features = ["derive"] }}
+serde_json = "1"
+solana-sdk = "{SOLANA_VERSION}"
+solana-transaction-status = "{SOLANA_VERSION}"
+"""
+
+
+def _readme(ruleset: RuleSet, name: str, rules: list[Rule]) -> str:
+    lib = name.replace("-", "_")
+    listed = "\n".join(f"- `{r.id}` ({r.kind}, {r.severity})" for r in rules)
+    return f"""# {name}
+
+Geyser plugin watching `{ruleset.program or ruleset.program_id}` (`{ruleset.program_id}`), generated by Baskerville.
+
+Rules:
+
+{listed}
+
+Build it with the same Rust toolchain and Agave version as the validator it is loaded into:
+
+```bash
+cargo build --release
+solana-validator ... --geyser-plugin-config config.json
+```
+
+`config.json` points the validator at `target/release/lib{lib}.so`. Its other keys are:
+
+- `alerts_path`: JSON-lines file that alerts are appended to. Alerts are always logged as well.
+- `accounts`: only evaluate transactions that also mention one of these accounts.
+- `include_failed`: evaluate failed transactions too. Failure-burst rules need this.
+
+Alerts list the events the transaction emitted under `details.events`, decoded by `src/events.rs`.
+"""
+
+
+def generate_geyser_plugin(ruleset: RuleSet, rule_ids: list[str] | None = None, name: str | None = None,
+                           source: str | None = None) -> dict[str, str]:
+    """Files (relative path -> content) of a Geyser plugin crate running ``ruleset``.
+
+    Args:
+        ruleset: Rules and decoding tables for the watched program
+        rule_ids: Rules to include (default: all)
+        name: Crate name (default: `<program>-baskerville-geyser`)
+        source: Where the rules came from, recorded in the generated code
+    """
+    by_id = {r.id: r for r in ruleset.rules}
+    unknown = [i for i in rule_ids or [] if i not in by_id]
+    if unknown:
+        raise ValueError(f"unknown rule {', '.join(unknown)} (rules: {', '.join(by_id)})")
+    rules = [by_id[i] for i in rule_ids] if rule_ids else list(ruleset.rules)
+    if not rules:
+        raise ValueError("no rules selected")
+    name = name or crate_name(ruleset)
+    if not re.fullmatch(r"[A-Za-z][A-Za-z0-9_-]*", name):
+        raise ValueError(f"invalid crate name {name!r}")
+    lib = name.replace("-", "_")
+    config: dict[str, Any] = {"libpath": f"target/release/lib{lib}.so", "alerts_path": "baskerville-alerts.jsonl",
+                              "accounts": [], "include_failed": True}
+    return {
+        "Cargo.toml": _cargo_toml(name),
+        "README.md": _readme(ruleset, name, rules),
+        "config.json": json.dumps(config, indent=2) + "\n",
+        "src/events.rs": generate_event_decoders(ruleset.events, ruleset.types, ruleset.program),
+        "src/lib.rs": _RUNTIME.read_text(),
+        "src/rules.rs": _rules_rs(ruleset, rules, name, source),
+    }
+
+
+def write_geyser_plugin(files: dict[str, str], out_dir: Path, force: bool = False) -> list[Path]:
+    """Write a generated crate; refuses to overwrite a non-empty directory unless ``force``."""
+    out_dir = Path(out_dir)
+    if out_dir.exists() and any(out_dir.iterdir()) and not force:
+        raise FileExistsError(f"{out_dir} is not empty (pass force to overwrite)")
+    written = []
+    for rel, content in files.items():
+        path = out_dir / rel
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(content)
+        written.append(path)
+    return written
@@ -0,0 +1,112 @@
+"""
+The monitoring loop.
+
+``Monitor`` pulls observed signatures from a source, fetches and decodes
+each transaction once, evaluates the rule set and hands alerts to its
+sinks (callables taking an ``Alert``).
+"""
+
+import json
+from collections import deque
+from collections.abc import Callable, Iterable
+from pathlib import Path
+
+from extensions.rpc import RpcClient, RpcError
+
+from .decode import TxEvent, decode_transaction
+from .rules import Alert, RuleSet
+from .sources import Observed, TransactionFetcher
+
+Sink = Callable[[Alert], None]
+
+
+class JsonlSink:
+    """Appends alerts to a JSON-lines file."""
+
+    def __init__(self, path: Path):
+        self.path = Path(path)
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+
+    def __call__(self, alert: Alert) -> None:
+        with open(self.path, "a") as f:
+            f.write(json.dumps(alert.to_dict()) + "\n")
+
+
+class Monitor:
+    """Evaluates a rule set against a program's transactions as they land."""
+
+    def __init__(self, ruleset: RuleSet, client: RpcClient | None = None, sinks: Iterable[Sink] = (),
+                 remember: int = 10_000, on_event: Callable[[TxEvent], None] | None = None):
+        """Initialize the monitor.
+
+        Args:
+            ruleset: Rules and decoding tables for the watched program
+            client: RPC client used to fetch transactions sources only announce
+            sinks: Called with every alert
+            remember: Signatures remembered for de-duplication across sources
+            on_event: Called with every decoded transaction that calls the program, before its rules run
+        """
+        self.ruleset = ruleset
+        self.events = ruleset.event_decoder()
+        self.client = client
+        self.sinks = list(sinks)
+        self.on_event = on_event
+        self._fetch = TransactionFetcher(client) if client else None
+        self._seen: deque[str] = deque(maxlen=remember)
+        self._seen_set: set[str] = set()
+        self.processed = 0
+        self.alerts = 0
+        self.errors = 0
+
+    def decode(self, tx: dict, signature: str | None = None) -> TxEvent:
+        return decode_transaction(tx, self.ruleset.program_id, self.ruleset.discriminators, signature,
+                                  events=self.events)
+
+    def process(self, tx: dict, signature: str | None = None) -> list[Alert]:
+        """Evaluate one transaction and dispatch its alerts."""
+        event = self.decode(tx, signature)
+        self.processed += 1
+        if not event.calls:
+            return []
+        if self.on_event:
+            self.on_event(event)
+        alerts = self.ruleset.evaluate(event)
+        for alert in alerts:
+            self.alerts += 1
+            for sink in self.sinks:
+                sink(alert)
+        return alerts
+
+    def _first_time(self, signature: str) -> bool:
+        if signature in self._seen_set:
+            return False
+        if len(self._seen) == self._seen.maxlen:
+            self._seen_set.discard(self._seen[0])
+        self._seen.append(signature)
+        self._seen_set.add(signature)
+        return True
+
+    def handle(self, observed: Observed) -> list[Alert]:
+        """Fetch (if needed) and process one observed transaction, once per signature."""
+        if not self._first_time(observed.signature):
+            return []
+        if observed.tx is None and self._fetch is None:
+            raise ValueError("this source only announces signatures; the monitor needs an RPC client")
+        try:
+            tx = self._fetch(observed) if self._fetch else observed.tx
+        except RpcError:
+            self.errors += 1
+            return []
+        if tx is None:
+            self.errors += 1
+            return []
+        return self.process(tx, observed.signature)
+
+    def run(self, source: Iterable[Observed], max_events: int | None = None) -> int:
+        """Consume ``source`` until it ends (or ``max_events`` transactions); returns the number of alerts."""
+        before = self.alerts
+        for count, observed in enumerate(source, 1):
+            self.handle(observed)
+            if max_events is not None and count >= max_events:
+                break
+        return self.alerts - before
@@ -0,0 +1,406 @@
+"""
+Monitoring rules.
+
+Each rule looks at decoded transactions of one program and raises
+``Alert``s for exploit-shaped activity:
+
+- unexpected-authority: an instruction called without its authority
+  account signing, or signed by an authority outside the expected set
+- instruction-called: any call to a sensitive instruction
+- large-outflow: tokens leaving program-controlled accounts above a fixed
+  threshold or a multiple of the usual outflow
+- failure-burst: one fee payer failing repeatedly against the program,
+  the usual shape of an exploit being tuned on mainnet
+- canary-touched: any call, failed or not, passing a canary account that
+  only a probe of a known weakness would reach
+
+``rules_from_findings`` derives a rule set from a project's hypotheses:
+access-control findings watch their instruction's authority, economic
+and arithmetic findings watch outflows, and other findings pinned to an
+instruction watch calls to it. The rule set also carries the program's
+event schemas, so alerts show the events the matched transaction emitted.
+"""
+
+import re
+import statistics
+from collections import deque
+from dataclasses import dataclass, field
+from datetime import datetime, timezone
+from typing import Any, ClassVar
+
+from .decode import InstructionCall, TxEvent, instruction_discriminators
+from .events import EventDecoder, EventSchema, schemas_from_program
+
+SEVERITY_ORDER = ["info", "low", "medium", "high", "critical"]
+
+_AUTHORITY_MARKERS = ("signer", "authority", "access", "privilege", "owner-check", "permission", "admin")
+_OUTFLOW_MARKERS = ("withdraw", "drain", "economic", "bad-debt", "oracle", "arithmetic", "overflow", "precision",
+                    "rounding", "fee", "liquidat", "reward")
+_AUTHORITY_NAMES = ("authority", "admin", "owner", "signer", "operator", "manager")
+
+
+@dataclass
+class Alert:
+    """Exploit-shaped activity matched by a rule."""
+
+    rule_id: str
+    kind: str
+    severity: str
+    title: str
+    message: str
+    signature: str
+    slot: int
+    program_id: str
+    instruction: str | None = None
+    # Hypothesis the rule was derived from
+    finding: str | None = None
+    details: dict[str, Any] = field(default_factory=dict)
+    timestamp: str = field(default_factory=lambda: datetime.now(timezone.utc).isoformat())
+
+    @property
+    def key(self) -> str:
+        return f"{self.rule_id}:{self.signature}"
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "rule_id": self.rule_id,
+            "kind": self.kind,
+            "severity": self.severity,
+            "title": self.title,
+            "message": self.message,
+            "signature": self.signature,
+            "slot": self.slot,
+            "program_id": self.program_id,
+            "instruction": self.instruction,
+            "finding": self.finding,
+            "details": self.details,
+            "timestamp": self.timestamp,
+        }
+
+
+@dataclass
+class Rule:
+    """Base rule; subclasses implement ``evaluate``."""
+
+    kind: ClassVar[str] = ""
+
+    id: str
+    severity: str = "medium"
+    title: str = ""
+    instruction: str | None = None
+    finding: str | None = None
+    # Also match CPIs into the program, not just top-level instructions
+    include_inner: bool = True
+
+    def calls(self, event: TxEvent) -> list[InstructionCall]:
+        calls = event.calls if self.instruction is None else event.calls_to(self.instruction)
+        return [c for c in calls if self.include_inner or not c.inner]
+
+    def alert(self, event: TxEvent, message: str, call: InstructionCall | None = None, **details: Any) -> Alert:
+        if event.events:
+            details["events"] = [e.to_dict() for e in event.events]
+        return Alert(rule_id=self.id, kind=self.kind, severity=self.severity, title=self.title or message,
+                     message=message, signature=event.signature, slot=event.slot,
+                     program_id=call.program_id if call else "", instruction=call.name if call else self.instruction,
+                     finding=self.finding, details={"success": event.success, **details})
+
+    def evaluate(self, event: TxEvent, accounts: dict[str, list[str]]) -> list[Alert]:
+        """Alerts for one transaction; ``accounts`` maps instruction names to their IR account order."""
+        raise NotImplementedError
+
+    def to_dict(self) -> dict[str, Any]:
+        data = {"id": self.id, "type": self.kind, "severity": self.severity, "title": self.title,
+                "instruction": self.instruction, "finding": self.finding, "include_inner": self.include_inner}
+        data.update(self._params())
+        return {k: v for k, v in data.items() if v not in (None, "", [])}
+
+    def _params(self) -> dict[str, Any]:
+        return {}
+
+
+@dataclass
+class UnexpectedAuthorityRule(Rule):
+    """Instruction called without ``account`` signing, or by an authority outside ``expected``."""
+
+    kind: ClassVar[str] = "unexpected-authority"
+
+    # Account by IR name or position
+    account: str | int = 0
+    expected: list[str] = field(default_factory=list)
+    # Only alert on successful calls (failed attempts are covered by failure-burst)
+    successful_only: bool = True
+
+    def evaluate(self, event: TxEvent, accounts: dict[str, list[str]]) -> list[Alert]:
+        if self.successful_only and not event.success:
+            return []
+        alerts = []
+        for call in self.calls(event):
+            pos = call.position(self.account, accounts.get(call.name or "", []))
+            if pos is None:
+                continue
+            address = call.accounts[pos]
+            if not call.signer[pos]:
+                alerts.append(self.alert(event, f"`{call.name}` called without `{self.account}` ({address}) signing",
+                                         call, account=address, signers=event.signers))
+            elif self.expected and address not in self.expected:
+                alerts.append(self.alert(event, f"`{call.name}` authorized by unexpected `{self.account}` {address}",
+                                         call, account=address, expected=self.expected))
+        return alerts
+
+    def _params(self) -> dict[str, Any]:
+        return {"account": self.account, "expected": self.expected, "successful_only": self.successful_only}
+
+
+@dataclass
+class InstructionCalledRule(Rule):
+    """Any (successful) call to ``instruction``."""
+
+    kind: ClassVar[str] = "instruction-called"
+
+    successful_only: bool = True
+
+    def evaluate(self, event: TxEvent, accounts: dict[str, list[str]]) -> list[Alert]:
+        if self.successful_only and not event.success:
+            return []
+        return [self.alert(event, f"`{call.name}` called by {event.fee_payer}", call, accounts=call.accounts)
+                for call in self.calls(event)]
+
+    def _params(self) -> dict[str, Any]:
+        return {"successful_only": self.successful_only}
+
+
+@dataclass
+class LargeOutflowRule(Rule):
+    """Tokens leaving accounts the transaction's signers don't own (program vaults).
+
+    Fires when a transaction's outflow of one mint reaches ``threshold``
+    (UI units), or exceeds ``multiplier`` times the median of the last
+    ``window`` outflows once ``min_samples`` have been seen.
+    """
+
+    kind: ClassVar[str] = "large-outflow"
+
+    mint: str | None = None
+    threshold: float | None = None
+    multiplier: float | None = 10.0
+    window: int = 200
+    min_samples: int = 20
+    _history: dict[str, deque] = field(default_factory=dict, repr=False)
+
+    def evaluate(self, event: TxEvent, accounts: dict[str, list[str]]) -> list[Alert]:
+        if not event.success or not self.calls(event):
+            return []
+        signers = set(event.signers)
+        outflows: dict[str, float] = {}
+        for d in event.token_deltas:
+            if d.delta < 0 and d.owner not in signers and (self.mint is None or d.mint == self.mint):
+                outflows[d.mint] = outflows.get(d.mint, 0.0) - d.ui_delta
+        alerts = []
+        call = self.calls(event)[0]
+        for mint, amount in outflows.items():
+            history = self._history.setdefault(mint, deque(maxlen=self.window))
+            median = statistics.median(history) if history else None
+            reason = None
+            if self.threshold is not None and amount >= self.threshold:
+                reason = f"reaches the threshold of {self.threshold:g}"
+            elif (self.multiplier and median and len(history) >= self.min_samples
+                  and amount > self.multiplier * median):
+                reason = f"is {amount / median:.1f}x the median outflow of {median:g}"
+            if reason:
+                alerts.append(self.alert(event, f"Outflow of {amount:g} {mint} {reason}", call, mint=mint,
+                                         amount=amount, median=median, samples=len(history)))
+            history.append(amount)
+        return alerts
+
+    def _params(self) -> dict[str, Any]:
+        return {"mint": self.mint, "threshold": self.threshold, "multiplier": self.multiplier, "window": self.window,
+                "min_samples": self.min_samples}
+
+
+@dataclass
+class FailureBurstRule(Rule):
+    """``count`` failed calls from one fee payer within ``window_slots``."""
+
+    kind: ClassVar[str] = "failure-burst"
+
+    count: int = 5
+    window_slots: int = 150
+    _failures: dict[str, deque] = field(default_factory=dict, repr=False)
+
+    def evaluate(self, event: TxEvent, accounts: dict[str, list[str]]) -> list[Alert]:
+        calls = self.calls(event)
+        if event.success or not calls or not event.fee_payer:
+            return []
+        failures = self._failures.setdefault(event.fee_payer, deque())
+        failures.append(event.slot)
+        while failures and failures[0] < event.slot - self.window_slots:
+            failures.popleft()
+        if len(failures) != self.count:
+            return []
+        return [self.alert(event, f"{event.fee_payer} failed {self.count} calls within {self.window_slots} slots",
+                           calls[0], fee_payer=event.fee_payer, error=event.error,
+                           instructions=sorted({c.name or "unknown" for c in calls}))]
+
+    def _params(self) -> dict[str, Any]:
+        return {"count": self.count, "window_slots": self.window_slots}
+
+
+@dataclass
+class CanaryTouchedRule(Rule):
+    """Any call passing the canary ``account``, successful or not (probes usually fail)."""
+
+    kind: ClassVar[str] = "canary-touched"
+
+    account: str = ""
+    # Canary id from the canary plan
+    canary: str = ""
+
+    def evaluate(self, event: TxEvent, accounts: dict[str, list[str]]) -> list[Alert]:
+        label = self.canary or self.account
+        return [self.alert(event, f"`{call.name or 'unknown'}` touched canary `{label}` ({self.account})", call,
+                           account=self.account, canary=self.canary, position=call.accounts.index(self.account),
+                           fee_payer=event.fee_payer)
+                for call in self.calls(event) if self.account in call.accounts]
+
+    def _params(self) -> dict[str, Any]:
+        return {"account": self.account, "canary": self.canary}
+
+
+RULE_TYPES: dict[str, type[Rule]] = {
+    cls.kind: cls for cls in (UnexpectedAuthorityRule, InstructionCalledRule, LargeOutflowRule, FailureBurstRule,
+                              CanaryTouchedRule)
+}
+
+
+def make_rule(spec: dict[str, Any]) -> Rule:
+    """Build a rule from a rules-file entry: ``{"id": "r1", "type": "large-outflow", "threshold": 1e6}``."""
+    spec = dict(spec)
+    kind = spec.pop("type", None)
+    if kind not in RULE_TYPES:
+        raise ValueError(f"unknown rule type {kind!r} (expected one of {', '.join(RULE_TYPES)})")
+    if spec.get("severity", "medium") not in SEVERITY_ORDER:
+        raise ValueError(f"rule {spec.get('id')}: unknown severity {spec['severity']!r}")
+    return RULE_TYPES[kind](**spec)
+
+
+@dataclass
+class RuleSet:
+    """The rules watching one program, with what is needed to decode its instructions."""
+
+    program_id: str
+    rules: list[Rule] = field(default_factory=list)
+    program: str | None = None
+    # Discriminator (hex) -> instruction name
+    discriminators: dict[str, str] = field(default_factory=dict)
+    # Instruction name -> account names in order
+    accounts: dict[str, list[str]] = field(default_factory=dict)
+    # Event schemas, and the defined types their fields use
+    events: list[EventSchema] = field(default_factory=list)
+    types: dict[str, dict[str, Any]] = field(default_factory=dict)
+
+    def evaluate(self, event: TxEvent) -> list[Alert]:
+        return [a for rule in self.rules for a in rule.evaluate(event, self.accounts)]
+
+    def event_decoder(self) -> EventDecoder | None:
+        return EventDecoder(self.events, self.types) if self.events else None
+
+    def to_dict(self) -> dict[str, Any]:
+        data = {"program": self.program, "program_id": self.program_id, "discriminators": self.discriminators,
+                "accounts": self.accounts, "rules": [r.to_dict() for r in self.rules]}
+        if self.events:
+            data.update(events=[e.to_dict() for e in self.events], types=self.types)
+        return data
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "RuleSet":
+        if not data.get("program_id"):
+            raise ValueError("rules file needs a program_id")
+        ids = [r.get("id") for r in data.get("rules", [])]
+        if None in ids or len(set(ids)) != len(ids):
+            raise ValueError("every rule needs a unique id")
+        return cls(program_id=data["program_id"], program=data.get("program"),
+                   discriminators=dict(data.get("discriminators") or {}),
+                   accounts={k: list(v) for k, v in (data.get("accounts") or {}).items()},
+                   rules=[make_rule(r) for r in data.get("rules", [])],
+                   events=[EventSchema.from_dict(e) for e in data.get("events") or []],
+                   types=dict(data.get("types") or {}))
+
+
+def _instruction_of(hyp: dict[str, Any], names: list[str]) -> str | None:
+    props = hyp.get("properties") or {}
+    if props.get("instruction") in names:
+        return props["instruction"]
+    text = " ".join([hyp.get("title", ""), *map(str, hyp.get("node_refs") or [])])
+    for name in names:
+        if re.search(rf"(?<![\w]){re.escape(name)}(?![\w])", text):
+            return name
+    return None
+
+
+def _authority_account(hyp: dict[str, Any], ix: Any) -> str | None:
+    props = hyp.get("properties") or {}
+    names = [a.name for a in ix.accounts]
+    if props.get("account") in names:
+        return props["account"]
+    for acc in ix.accounts:
+        if acc.name in _AUTHORITY_NAMES or any(acc.name.endswith(f"_{n}") for n in _AUTHORITY_NAMES):
+            return acc.name
+    return next((a.name for a in ix.accounts if a.is_signer), None)
+
+
+def rules_from_findings(hypotheses: dict[str, dict[str, Any]], program: Any, program_id: str | None = None,
+                        min_severity: str = "low",
+                        events: tuple[list[EventSchema], dict[str, dict[str, Any]]] | None = None) -> RuleSet:
+    """Derive monitoring rules for one IR ``Program`` from a hypothesis store's entries.
+
+    Args:
+        hypotheses: Hypothesis id -> hypothesis (the `hypotheses` map of hypotheses.json)
+        program: IR ``Program`` being monitored
+        program_id: Deployed address (default: the IR's `declare_id!`)
+        min_severity: Ignore findings below this severity
+        events: Event schemas and their types (default: the program's `#[event]` structs)
+    """
+    program_id = program_id or program.program_id
+    if not program_id:
+        raise ValueError(f"program {program.name} has no known address (pass program_id)")
+    by_name = {ix.name: ix for ix in program.instructions}
+    ruleset = RuleSet(program_id=program_id, program=program.name,
+                      discriminators=instruction_discriminators(program),
+                      accounts={ix.name: [a.name for a in ix.accounts] for ix in program.instructions})
+    ruleset.events, ruleset.types = events or schemas_from_program(program)
+    floor = SEVERITY_ORDER.index(min_severity)
+    seen: set[tuple] = set()
+    outflow: LargeOutflowRule | None = None
+    for hyp_id, hyp in sorted(hypotheses.items()):
+        severity = str(hyp.get("severity", "medium")).lower()
+        props = hyp.get("properties") or {}
+        if (hyp.get("status") in ("rejected", "false_positive") or severity not in SEVERITY_ORDER
+                or SEVERITY_ORDER.index(severity) < floor
+                or props.get("program") not in (None, program.name)):
+            continue
+        text = " ".join([hyp.get("vulnerability_type", ""), props.get("category", ""), hyp.get("title", "")]).lower()
+        name = _instruction_of(hyp, list(by_name))
+        title = hyp.get("title", "")
+        if name and any(m in text for m in _AUTHORITY_MARKERS):
+            account = _authority_account(hyp, by_name[name])
+            if account and ("auth", name, account) not in seen:
+                seen.add(("auth", name, account))
+                ruleset.rules.append(UnexpectedAuthorityRule(
+                    id=f"authority-{name}-{account}", severity=severity, instruction=name, account=account,
+                    finding=hyp_id, title=f"`{name}` without expected `{account}` ({title})"))
+                continue
+        if any(m in text for m in _OUTFLOW_MARKERS):
+            if outflow is None:
+                outflow = LargeOutflowRule(id="large-outflow", severity=severity, finding=hyp_id,
+                                           title=f"Anomalous outflow from {program.name} ({title})")
+                ruleset.rules.append(outflow)
+            elif SEVERITY_ORDER.index(severity) > SEVERITY_ORDER.index(outflow.severity):
+                outflow.severity, outflow.finding = severity, hyp_id
+            continue
+        if name and ("call", name) not in seen:
+            seen.add(("call", name))
+            ruleset.rules.append(InstructionCalledRule(id=f"call-{name}", severity=severity, instruction=name,
+                                                       finding=hyp_id, title=f"`{name}` called ({title})"))
+    ruleset.rules.append(FailureBurstRule(id="failure-burst", severity="medium",
+                                          title=f"Repeated failing calls into {program.name}"))
+    return ruleset
@@ -0,0 +1,213 @@
+"""
+Transaction sources for the monitor.
+
+- ``PollingSource``: `getSignaturesForAddress` on an interval; works with
+  any RPC provider
+- ``WebsocketSource``: `logsSubscribe` mentioning the program, reconnecting
+  with backoff when the socket drops
+- ``WebhookSource``: an HTTP endpoint for provider webhooks (Helius "raw"
+  webhooks post arrays of `getTransaction` results)
+
+Each yields ``Observed`` items; only the webhook carries the transaction
+itself, the monitor fetches the rest through the shared RPC client.
+"""
+
+import asyncio
+import json
+import queue
+import threading
+import time
+from collections.abc import Iterator
+from dataclasses import dataclass
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from typing import Any
+
+from extensions.rpc import RpcClient, RpcError
+
+
+@dataclass
+class Observed:
+    """A transaction seen by a source."""
+
+    signature: str
+    slot: int | None = None
+    tx: dict[str, Any] | None = None
+    failed: bool = False
+
+
+class Source:
+    """Iterable of ``Observed``; ``close()`` ends the iteration."""
+
+    def __init__(self):
+        self._closed = threading.Event()
+
+    @property
+    def closed(self) -> bool:
+        return self._closed.is_set()
+
+    def close(self) -> None:
+        self._closed.set()
+
+    def __iter__(self) -> Iterator[Observed]:
+        raise NotImplementedError
+
+
+class PollingSource(Source):
+    """Polls the program's signatures, oldest first, starting from now (or ``backfill`` back)."""
+
+    def __init__(self, client: RpcClient, program_id: str, interval: float = 2.0, backfill: int = 0,
+                 commitment: str = "confirmed"):
+        super().__init__()
+        self.client = client
+        self.program_id = program_id
+        self.interval = interval
+        self.backfill = backfill
+        self.commitment = commitment
+
+    def __iter__(self) -> Iterator[Observed]:
+        until = None
+        first = True
+        while not self.closed:
+            try:
+                batch = self.client.get_signatures_for_address(self.program_id, limit=1000, until=until,
+                                                               commitment=self.commitment)
+            except RpcError:
+                batch = None
+            if batch:
+                until = batch[0]["signature"]
+                for info in reversed(batch[:self.backfill] if first else batch):
+                    yield Observed(info["signature"], info.get("slot"), failed=info.get("err") is not None)
+            if batch is not None:
+                first = False
+            self._closed.wait(self.interval)
+
+
+class WebsocketSource(Source):
+    """`logsSubscribe` notifications for transactions mentioning the program."""
+
+    def __init__(self, ws_url: str, program_id: str, commitment: str = "confirmed", max_backoff: float = 30.0):
+        super().__init__()
+        self.ws_url = ws_url
+        self.program_id = program_id
+        self.commitment = commitment
+        self.max_backoff = max_backoff
+        self._queue: queue.Queue[Observed] = queue.Queue()
+
+    async def _listen(self) -> None:
+        import aiohttp
+
+        backoff = 1.0
+        request = {"jsonrpc": "2.0", "id": 1, "method": "logsSubscribe",
+                   "params": [{"mentions": [self.program_id]}, {"commitment": self.commitment}]}
+        while not self.closed:
+            try:
+                async with aiohttp.ClientSession() as session:
+                    async with session.ws_connect(self.ws_url, heartbeat=30) as ws:
+                        await ws.send_json(request)
+                        backoff = 1.0
+                        async for msg in ws:
+                            if self.closed:
+                                return
+                            if msg.type != aiohttp.WSMsgType.TEXT:
+                                break
+                            data = json.loads(msg.data)
+                            value = ((data.get("params") or {}).get("result") or {}).get("value")
+                            if value and value.get("signature"):
+                                slot = data["params"]["result"].get("context", {}).get("slot")
+                                self._queue.put(Observed(value["signature"], slot, failed=value.get("err") is not None))
+            except (aiohttp.ClientError, asyncio.TimeoutError, OSError):
+                pass
+            await asyncio.sleep(backoff)
+            backoff = min(backoff * 2, self.max_backoff)
+
+    def __iter__(self) -> Iterator[Observed]:
+        thread = threading.Thread(target=lambda: asyncio.run(self._listen()), daemon=True)
+        thread.start()
+        while not self.closed:
+            try:
+                yield self._queue.get(timeout=0.5)
+            except queue.Empty:
+                continue
+
+
+class WebhookSource(Source):
+    """HTTP endpoint receiving transactions pushed by a provider webhook."""
+
+    def __init__(self, host: str = "127.0.0.1", port: int = 8787, path: str = "/", auth: str | None = None):
+        """Initialize the endpoint.
+
+        Args:
+            host: Interface to listen on
+            port: Port to listen on (0 picks a free one; see ``address``)
+            path: Request path the webhook posts to
+            auth: Required `Authorization` header value (Helius "authHeader")
+        """
+        super().__init__()
+        self.path = path
+        self.auth = auth
+        self._queue: queue.Queue[Observed] = queue.Queue()
+        source = self
+
+        class Handler(BaseHTTPRequestHandler):
+            def do_POST(self):
+                if self.path.split("?")[0] != source.path:
+                    self.send_response(404)
+                elif source.auth and self.headers.get("Authorization") != source.auth:
+                    self.send_response(401)
+                else:
+                    try:
+                        payload = json.loads(self.rfile.read(int(self.headers.get("Content-Length", 0))))
+                    except ValueError:
+                        self.send_response(400)
+                    else:
+                        for tx in payload if isinstance(payload, list) else [payload]:
+                            sigs = (tx.get("transaction") or {}).get("signatures") or [tx.get("signature")]
+                            if sigs[0]:
+                                source._queue.put(Observed(sigs[0], tx.get("slot"), tx if "meta" in tx else None))
+                        self.send_response(200)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+            def log_message(self, *args):
+                pass
+
+        self._server = ThreadingHTTPServer((host, port), Handler)
+        self._thread = threading.Thread(target=self._server.serve_forever, daemon=True)
+        self._thread.start()
+
+    @property
+    def address(self) -> tuple[str, int]:
+        return self._server.server_address[:2]
+
+    def close(self) -> None:
+        super().close()
+        self._server.shutdown()
+        self._server.server_close()
+
+    def __iter__(self) -> Iterator[Observed]:
+        while not self.closed:
+            try:
+                yield self._queue.get(timeout=0.5)
+            except queue.Empty:
+                continue
+
+
+class TransactionFetcher:
+    """Fetches observed transactions, waiting briefly for ones the node has not indexed yet."""
+
+    def __init__(self, client: RpcClient, attempts: int = 5, delay: float = 1.0, commitment: str = "confirmed"):
+        self.client = client
+        self.attempts = attempts
+        self.delay = delay
+        self.commitment = commitment
+
+    def __call__(self, observed: Observed) -> dict[str, Any] | None:
+        if observed.tx is not None:
+            return observed.tx
+        for attempt in range(self.attempts):
+            tx = self.client.get_transaction(observed.signature, commitment=self.commitment)
+            if tx is not None:
+                return tx
+            if attempt + 1 < self.attempts:
+                time.sleep(self.delay)
+        return None
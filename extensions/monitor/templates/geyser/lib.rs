@@ -0,0 +1,467 @@
+//! Geyser plugin evaluating Baskerville monitoring rules on the validator's
+//! transaction stream.
+//!
+//! The rules and decoding tables live in `rules.rs` and the event decoders in
+//! `events.rs`, both generated from a rules file with `baskerville monitor
+//! geyser`; this file is the fixed runtime.
+//! Alerts have the same shape as `monitor run --alerts` writes, so the same
+//! tooling can consume both.
+
+mod events;
+mod rules;
+
+use std::collections::{HashMap, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use agave_geyser_plugin_interface::geyser_plugin_interface::{
+    GeyserPlugin, GeyserPluginError, ReplicaTransactionInfoVersions, Result,
+};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use solana_sdk::{clock::Slot, instruction::CompiledInstruction, pubkey::Pubkey};
+
+use rules::{AccountRef, Rule, RuleKind};
+
+fn yes() -> bool {
+    true
+}
+
+/// Plugin configuration (the validator's `--geyser-plugin-config` file).
+#[derive(Debug, Deserialize)]
+struct Config {
+    /// JSON-lines file alerts are appended to; alerts are always logged
+    #[serde(default)]
+    alerts_path: Option<String>,
+    /// Only evaluate transactions that also mention one of these accounts
+    #[serde(default)]
+    accounts: Vec<String>,
+    /// Evaluate failed transactions (failure-burst rules need them)
+    #[serde(default = "yes")]
+    include_failed: bool,
+}
+
+/// One invocation of the monitored program (top-level or CPI).
+struct Call {
+    index: usize,
+    inner: bool,
+    name: Option<&'static str>,
+    accounts: Vec<Pubkey>,
+    signer: Vec<bool>,
+}
+
+impl Call {
+    fn label(&self) -> &'static str {
+        self.name.unwrap_or("unknown")
+    }
+}
+
+/// A transaction reduced to what the rules look at.
+struct Event {
+    signature: String,
+    slot: Slot,
+    success: bool,
+    error: Option<String>,
+    signers: Vec<Pubkey>,
+    calls: Vec<Call>,
+    /// Mint -> tokens (UI units) that left accounts the signers don't own
+    outflows: Vec<(String, f64)>,
+    /// Events the program emitted, logged (`emit!`) or through self-CPIs (`emit_cpi!`)
+    events: Vec<Value>,
+}
+
+#[derive(Serialize)]
+struct Alert {
+    rule_id: &'static str,
+    kind: &'static str,
+    severity: &'static str,
+    title: String,
+    message: String,
+    signature: String,
+    slot: Slot,
+    program_id: &'static str,
+    instruction: Option<&'static str>,
+    finding: Option<&'static str>,
+    details: Value,
+    timestamp: u64,
+}
+
+impl Alert {
+    fn new(rule: &Rule, event: &Event, call: &Call, message: String, details: Value) -> Self {
+        let mut all = json!({ "success": event.success, "error": event.error });
+        if let (Some(map), Some(extra)) = (all.as_object_mut(), details.as_object()) {
+            map.extend(extra.clone());
+            if !event.events.is_empty() {
+                map.insert("events".into(), Value::Array(event.events.clone()));
+            }
+        }
+        Alert {
+            rule_id: rule.id,
+            kind: rule.kind.name(),
+            severity: rule.severity,
+            title: if rule.title.is_empty() { message.clone() } else { rule.title.to_string() },
+            message,
+            signature: event.signature.clone(),
+            slot: event.slot,
+            program_id: rules::PROGRAM_ID,
+            instruction: call.name.or(rule.instruction),
+            finding: rule.finding,
+            details: all,
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+        }
+    }
+}
+
+impl AccountRef {
+    fn position(&self, call: &Call) -> Option<usize> {
+        let pos = match self {
+            AccountRef::Index(i) => Some(*i),
+            AccountRef::Named(table) => table.iter().find(|(ix, _)| Some(*ix) == call.name).map(|(_, p)| *p),
+        };
+        pos.filter(|p| *p < call.accounts.len())
+    }
+}
+
+impl RuleKind {
+    fn name(&self) -> &'static str {
+        match self {
+            RuleKind::UnexpectedAuthority { .. } => "unexpected-authority",
+            RuleKind::InstructionCalled { .. } => "instruction-called",
+            RuleKind::LargeOutflow { .. } => "large-outflow",
+            RuleKind::FailureBurst { .. } => "failure-burst",
+            RuleKind::CanaryTouched { .. } => "canary-touched",
+        }
+    }
+}
+
+fn instruction_name(data: &[u8]) -> Option<&'static str> {
+    rules::DISCRIMINATORS.iter().find(|(disc, _)| data.starts_with(disc)).map(|(_, name)| *name)
+}
+
+fn median(values: &VecDeque<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted: Vec<f64> = values.iter().copied().collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mid = sorted.len() / 2;
+    Some(if sorted.len() % 2 == 1 { sorted[mid] } else { (sorted[mid - 1] + sorted[mid]) / 2.0 })
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// (rule, mint) -> recent outflows
+    history: HashMap<(usize, String), VecDeque<f64>>,
+    /// (rule, fee payer) -> slots of recent failed calls
+    failures: HashMap<(usize, Pubkey), VecDeque<Slot>>,
+}
+
+#[derive(Debug)]
+pub struct BaskervillePlugin {
+    program_id: Pubkey,
+    rules: Vec<Rule>,
+    accounts: Vec<Pubkey>,
+    include_failed: bool,
+    alerts: Mutex<Option<File>>,
+    state: Mutex<State>,
+}
+
+impl Default for BaskervillePlugin {
+    fn default() -> Self {
+        BaskervillePlugin {
+            program_id: Pubkey::from_str(rules::PROGRAM_ID).expect("generated program id is valid"),
+            rules: rules::rules(),
+            accounts: Vec::new(),
+            include_failed: true,
+            alerts: Mutex::new(None),
+            state: Mutex::new(State::default()),
+        }
+    }
+}
+
+fn config_error(msg: impl Into<String>) -> GeyserPluginError {
+    GeyserPluginError::ConfigFileReadError { msg: msg.into() }
+}
+
+impl BaskervillePlugin {
+    fn decode(
+        &self,
+        signature: String,
+        slot: Slot,
+        tx: &solana_sdk::transaction::SanitizedTransaction,
+        meta: &solana_transaction_status::TransactionStatusMeta,
+    ) -> Option<Event> {
+        let message = tx.message();
+        let keys: Vec<Pubkey> = message.account_keys().iter().copied().collect();
+        if !keys.contains(&self.program_id) {
+            return None;
+        }
+        if !self.accounts.is_empty() && !keys.iter().any(|k| self.accounts.contains(k)) {
+            return None;
+        }
+        let make = |ix: &CompiledInstruction, index: usize, inner: bool| -> Option<Call> {
+            if keys.get(ix.program_id_index as usize) != Some(&self.program_id) {
+                return None;
+            }
+            let accounts = ix.accounts.iter().filter_map(|i| keys.get(*i as usize).copied()).collect();
+            Some(Call {
+                index,
+                inner,
+                name: instruction_name(&ix.data),
+                accounts,
+                signer: ix.accounts.iter().map(|i| message.is_signer(*i as usize)).collect(),
+            })
+        };
+        let mut calls: Vec<Call> =
+            message.instructions().iter().enumerate().filter_map(|(i, ix)| make(ix, i, false)).collect();
+        for group in meta.inner_instructions.iter().flatten() {
+            calls.extend(group.instructions.iter().filter_map(|inner| make(&inner.instruction, group.index as usize, true)));
+        }
+        if calls.is_empty() {
+            return None;
+        }
+        calls.sort_by_key(|c| (c.index, c.inner));
+
+        let n_signers = (message.header().num_required_signatures as usize).min(keys.len());
+        let signers = keys[..n_signers].to_vec();
+        // account index -> (mint, owner, decimals, raw delta)
+        let mut balances: HashMap<u8, (String, String, u8, i128)> = HashMap::new();
+        for (side, sign) in [(&meta.pre_token_balances, -1i128), (&meta.post_token_balances, 1i128)] {
+            for b in side.iter().flatten() {
+                let amount: i128 = b.ui_token_amount.amount.parse().unwrap_or(0);
+                let entry = balances
+                    .entry(b.account_index)
+                    .or_insert_with(|| (b.mint.clone(), b.owner.clone(), b.ui_token_amount.decimals, 0));
+                entry.3 += sign * amount;
+            }
+        }
+        let mut outflows: HashMap<String, f64> = HashMap::new();
+        for (mint, owner, decimals, delta) in balances.into_values() {
+            let owned_by_signer = Pubkey::from_str(&owner).map(|o| signers.contains(&o)).unwrap_or(false);
+            if delta < 0 && !owned_by_signer {
+                *outflows.entry(mint).or_insert(0.0) += (-delta) as f64 / 10f64.powi(decimals as i32);
+            }
+        }
+
+        let mut emitted = Vec::new();
+        let mut stack: Vec<&str> = Vec::new();
+        for line in meta.log_messages.iter().flatten() {
+            let words: Vec<&str> = line.split(' ').collect();
+            match words.as_slice() {
+                ["Program", id, "invoke", _] => stack.push(*id),
+                ["Program", id, status, ..] if (*status == "success" || *status == "failed:") && stack.last() == Some(id) => {
+                    stack.pop();
+                }
+                ["Program", "data:", chunks @ ..] if stack.last() == Some(&rules::PROGRAM_ID) => {
+                    let data: Vec<u8> = chunks.iter().filter_map(|c| STANDARD.decode(c).ok()).flatten().collect();
+                    emitted.extend(events::decode(&data, "log"));
+                }
+                _ => {}
+            }
+        }
+        for group in meta.inner_instructions.iter().flatten() {
+            for inner in &group.instructions {
+                let ix = &inner.instruction;
+                if keys.get(ix.program_id_index as usize) == Some(&self.program_id) && ix.data.starts_with(&events::EVENT_IX_TAG) {
+                    emitted.extend(events::decode(&ix.data[events::EVENT_IX_TAG.len()..], "cpi"));
+                }
+            }
+        }
+
+        Some(Event {
+            signature,
+            slot,
+            success: meta.status.is_ok(),
+            error: meta.status.as_ref().err().map(|e| e.to_string()),
+            signers,
+            calls,
+            outflows: outflows.into_iter().collect(),
+            events: emitted,
+        })
+    }
+
+    fn evaluate(&self, event: &Event) -> Vec<Alert> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let mut alerts = Vec::new();
+        for (r, rule) in self.rules.iter().enumerate() {
+            let calls: Vec<&Call> = event
+                .calls
+                .iter()
+                .filter(|c| rule.instruction.is_none() || c.name == rule.instruction)
+                .filter(|c| rule.include_inner || !c.inner)
+                .collect();
+            let Some(first) = calls.first() else { continue };
+            match &rule.kind {
+                RuleKind::UnexpectedAuthority { account, account_label, expected, successful_only } => {
+                    if *successful_only && !event.success {
+                        continue;
+                    }
+                    for call in &calls {
+                        let Some(pos) = account.position(call) else { continue };
+                        let address = call.accounts[pos].to_string();
+                        if !call.signer[pos] {
+                            let msg = format!("`{}` called without `{}` ({}) signing", call.label(), account_label, address);
+                            let signers: Vec<String> = event.signers.iter().map(|s| s.to_string()).collect();
+                            alerts.push(Alert::new(rule, event, call, msg, json!({ "account": address, "signers": signers })));
+                        } else if !expected.is_empty() && !expected.contains(&address.as_str()) {
+                            let msg = format!("`{}` authorized by unexpected `{}` {}", call.label(), account_label, address);
+                            alerts.push(Alert::new(rule, event, call, msg, json!({ "account": address, "expected": expected })));
+                        }
+                    }
+                }
+                RuleKind::InstructionCalled { successful_only } => {
+                    if *successful_only && !event.success {
+                        continue;
+                    }
+                    let payer = event.signers.first().map(|p| p.to_string()).unwrap_or_default();
+                    for call in &calls {
+                        let accounts: Vec<String> = call.accounts.iter().map(|a| a.to_string()).collect();
+                        let msg = format!("`{}` called by {}", call.label(), payer);
+                        alerts.push(Alert::new(rule, event, call, msg, json!({ "accounts": accounts })));
+                    }
+                }
+                RuleKind::LargeOutflow { mint, threshold, multiplier, window, min_samples } => {
+                    if !event.success {
+                        continue;
+                    }
+                    for (m, amount) in &event.outflows {
+                        if let Some(want) = mint {
+                            if *want != m.as_str() {
+                                continue;
+                            }
+                        }
+                        let history = state.history.entry((r, m.clone())).or_default();
+                        let med = median(history);
+                        let mut reason = None;
+                        if let Some(t) = threshold {
+                            if *amount >= *t {
+                                reason = Some(format!("reaches the threshold of {}", t));
+                            }
+                        }
+                        if let (None, Some(mult), Some(med)) = (&reason, multiplier, med) {
+                            if history.len() >= *min_samples && med > 0.0 && *amount > *mult * med {
+                                reason = Some(format!("is {:.1}x the median outflow of {}", *amount / med, med));
+                            }
+                        }
+                        if let Some(reason) = reason {
+                            let msg = format!("Outflow of {} {} {}", amount, m, reason);
+                            let details = json!({ "mint": m, "amount": amount, "median": med, "samples": history.len() });
+                            alerts.push(Alert::new(rule, event, first, msg, details));
+                        }
+                        if history.len() >= *window {
+                            history.pop_front();
+                        }
+                        history.push_back(*amount);
+                    }
+                }
+                RuleKind::FailureBurst { count, window_slots } => {
+                    if event.success {
+                        continue;
+                    }
+                    let Some(payer) = event.signers.first() else { continue };
+                    let failures = state.failures.entry((r, *payer)).or_default();
+                    failures.push_back(event.slot);
+                    while failures.front().is_some_and(|s| s + window_slots < event.slot) {
+                        failures.pop_front();
+                    }
+                    if failures.len() == *count {
+                        let mut names: Vec<&str> = calls.iter().map(|c| c.label()).collect();
+                        names.sort_unstable();
+                        names.dedup();
+                        let msg = format!("{} failed {} calls within {} slots", payer, count, window_slots);
+                        let details = json!({ "fee_payer": payer.to_string(), "instructions": names });
+                        alerts.push(Alert::new(rule, event, first, msg, details));
+                    }
+                }
+                RuleKind::CanaryTouched { account, canary } => {
+                    let payer = event.signers.first().map(|p| p.to_string());
+                    for call in &calls {
+                        let Some(pos) = call.accounts.iter().position(|a| a.to_string() == *account) else { continue };
+                        let label = if canary.is_empty() { *account } else { *canary };
+                        let msg = format!("`{}` touched canary `{}` ({})", call.label(), label, account);
+                        let details = json!({ "account": account, "canary": canary, "position": pos, "fee_payer": payer });
+                        alerts.push(Alert::new(rule, event, call, msg, details));
+                    }
+                }
+            }
+        }
+        alerts
+    }
+
+    fn emit(&self, alert: &Alert) -> Result<()> {
+        let line = serde_json::to_string(alert).map_err(|e| GeyserPluginError::Custom(Box::new(e)))?;
+        log::warn!("baskerville alert: {}", line);
+        let mut file = self.alerts.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(file) = file.as_mut() {
+            writeln!(file, "{}", line).map_err(|e| GeyserPluginError::Custom(Box::new(e)))?;
+        }
+        Ok(())
+    }
+}
+
+impl GeyserPlugin for BaskervillePlugin {
+    fn name(&self) -> &'static str {
+        rules::PLUGIN_NAME
+    }
+
+    fn setup_logger(&self, logger: &'static dyn log::Log, level: log::LevelFilter) -> Result<()> {
+        log::set_max_level(level);
+        log::set_logger(logger).map_err(|e| GeyserPluginError::Custom(Box::new(e)))
+    }
+
+    fn on_load(&mut self, config_file: &str, _is_reload: bool) -> Result<()> {
+        let text = std::fs::read_to_string(config_file).map_err(GeyserPluginError::ConfigFileOpenError)?;
+        let config: Config = serde_json::from_str(&text).map_err(|e| config_error(e.to_string()))?;
+        self.accounts = config
+            .accounts
+            .iter()
+            .map(|a| Pubkey::from_str(a).map_err(|_| config_error(format!("invalid account {}", a))))
+            .collect::<Result<_>>()?;
+        self.include_failed = config.include_failed;
+        if let Some(path) = config.alerts_path {
+            let file = OpenOptions::new().create(true).append(true).open(&path).map_err(GeyserPluginError::ConfigFileOpenError)?;
+            *self.alerts.lock().unwrap_or_else(|e| e.into_inner()) = Some(file);
+        }
+        log::info!("{}: watching {} with {} rules", rules::PLUGIN_NAME, rules::PROGRAM_ID, self.rules.len());
+        Ok(())
+    }
+
+    fn notify_transaction(&self, transaction: ReplicaTransactionInfoVersions, slot: Slot) -> Result<()> {
+        #[allow(unreachable_patterns)]
+        let (signature, is_vote, tx, meta) = match transaction {
+            ReplicaTransactionInfoVersions::V0_0_1(i) => (i.signature, i.is_vote, i.transaction, i.transaction_status_meta),
+            ReplicaTransactionInfoVersions::V0_0_2(i) => (i.signature, i.is_vote, i.transaction, i.transaction_status_meta),
+            _ => return Ok(()),
+        };
+        if is_vote || (!self.include_failed && meta.status.is_err()) {
+            return Ok(());
+        }
+        let Some(event) = self.decode(signature.to_string(), slot, tx, meta) else { return Ok(()) };
+        for alert in self.evaluate(&event) {
+            self.emit(&alert)?;
+        }
+        Ok(())
+    }
+
+    fn account_data_notifications_enabled(&self) -> bool {
+        false
+    }
+
+    fn transaction_notifications_enabled(&self) -> bool {
+        true
+    }
+}
+
+/// # Safety
+///
+/// Called by the validator's plugin manager, which takes ownership of the plugin.
+#[no_mangle]
+#[allow(improper_ctypes_definitions)]
+pub unsafe extern "C" fn _create_plugin() -> *mut dyn GeyserPlugin {
+    let plugin: Box<dyn GeyserPlugin> = Box::new(BaskervillePlugin::default());
+    Box::into_raw(plugin)
+}
@@ -0,0 +1,143 @@
+"""
+On-chain usage of a program's instructions.
+
+Samples the program's recent transactions (`getSignaturesForAddress`, then
+`getTransaction` through the shared RPC client), decodes the calls into
+it and counts, per instruction, the calls (top-level and CPI), the
+distinct fee payers behind them and how many failed.
+
+``weight_hits`` turns that into exposure for prioritization: a hit in the
+instruction that carries the program's traffic ranks above one of the
+same severity in an admin path called twice in the sample. Exposure is
+relative to the busiest instruction, on a log scale, so that the hot
+path does not drown out everything else.
+"""
+
+import math
+from dataclasses import dataclass, field
+from typing import Any
+
+from extensions.rpc import RpcClient, RpcError
+
+from .decode import decode_transaction, instruction_discriminators
+
+# Calls whose discriminator matches no instruction
+UNKNOWN = "<unknown>"
+
+
+@dataclass
+class InstructionUsage:
+    """Calls of one instruction in the sample."""
+
+    name: str
+    calls: int = 0
+    cpi_calls: int = 0
+    failed: int = 0
+    callers: set[str] = field(default_factory=set)
+    last_slot: int | None = None
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"name": self.name, "calls": self.calls, "cpi_calls": self.cpi_calls, "failed": self.failed,
+                "callers": len(self.callers), "last_slot": self.last_slot}
+
+
+@dataclass
+class ProgramUsage:
+    """What a sample of a program's transactions says about its instructions."""
+
+    program: str
+    program_id: str
+    transactions: int = 0
+    first_slot: int | None = None
+    last_slot: int | None = None
+    instructions: dict[str, InstructionUsage] = field(default_factory=dict)
+
+    def usage(self, name: str) -> InstructionUsage:
+        return self.instructions.get(name) or InstructionUsage(name)
+
+    def exposure(self, name: str) -> float:
+        """0..1: the instruction's calls and callers against the busiest instruction's."""
+        known = [u for n, u in self.instructions.items() if n != UNKNOWN]
+        most_calls = max((u.calls for u in known), default=0)
+        most_callers = max((len(u.callers) for u in known), default=0)
+        if not most_calls:
+            return 0.0
+        usage = self.usage(name)
+        calls = math.log1p(usage.calls) / math.log1p(most_calls)
+        callers = math.log1p(len(usage.callers)) / math.log1p(most_callers) if most_callers else 0.0
+        return round((calls + callers) / 2, 3)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"program": self.program, "program_id": self.program_id, "transactions": self.transactions,
+                "first_slot": self.first_slot, "last_slot": self.last_slot,
+                "instructions": {name: {**u.to_dict(), "exposure": self.exposure(name)}
+                                 for name, u in sorted(self.instructions.items(), key=lambda item: -item[1].calls)}}
+
+
+def collect_usage(client: RpcClient, program: Any, program_id: str, limit: int = 1000,
+                  commitment: str = "finalized") -> ProgramUsage:
+    """Usage of ``program``'s instructions in its last ``limit`` transactions at ``program_id``.
+
+    Raises RpcError when the signatures cannot be listed; transactions the
+    node no longer has are skipped.
+    """
+    signatures: list[dict[str, Any]] = []
+    before = None
+    while len(signatures) < limit:
+        want = min(1000, limit - len(signatures))
+        page = client.get_signatures_for_address(program_id, limit=want, before=before, commitment=commitment)
+        signatures += page
+        if len(page) < want:
+            break
+        before = page[-1]["signature"]
+
+    def fetch(signature: str) -> dict[str, Any] | None:
+        try:
+            return client.get_transaction(signature, commitment=commitment)
+        except RpcError:
+            return None
+
+    discriminators = instruction_discriminators(program)
+    usage = ProgramUsage(program.name, program_id)
+    names = [s["signature"] for s in signatures]
+    for signature, tx in zip(names, client.parallel(fetch, names)):
+        if tx is None:
+            continue
+        event = decode_transaction(tx, program_id, discriminators, signature=signature)
+        usage.transactions += 1
+        if event.slot:
+            usage.first_slot = min(usage.first_slot or event.slot, event.slot)
+            usage.last_slot = max(usage.last_slot or event.slot, event.slot)
+        for call in event.calls:
+            entry = usage.instructions.setdefault(call.name or UNKNOWN, InstructionUsage(call.name or UNKNOWN))
+            entry.calls += 1
+            entry.cpi_calls += call.inner
+            entry.failed += not event.success
+            if event.fee_payer:
+                entry.callers.add(event.fee_payer)
+            entry.last_slot = max(entry.last_slot or event.slot, event.slot)
+    return usage
+
+
+def weight_hits(hits: list[dict[str, Any]], usages: dict[str, ProgramUsage]) -> list[dict[str, Any]]:
+    """Hits ordered by severity weighted by exposure, highest first.
+
+    Hits in a sampled program's instruction record ``properties.usage``;
+    their priority is the severity weight times 0.5 (never called) to 1.5
+    (the busiest instruction). Hits that name no sampled instruction keep
+    the neutral factor 1.0.
+    """
+    from extensions.reporting.heatmap import SEVERITY_WEIGHTS
+
+    for hit in hits:
+        props = hit.setdefault("properties", {})
+        usage = usages.get(props.get("program"))
+        factor = 1.0
+        if usage and props.get("instruction"):
+            exposure = usage.exposure(props["instruction"])
+            props["usage"] = {**usage.usage(props["instruction"]).to_dict(), "program_id": usage.program_id,
+                              "transactions": usage.transactions, "exposure": exposure}
+            factor = 0.5 + exposure
+        severity = str(hit.get("severity", "medium")).lower()
+        props["priority"] = round(SEVERITY_WEIGHTS.get(severity, SEVERITY_WEIGHTS["medium"]) * factor, 2)
+    return sorted(hits, key=lambda h: -h["properties"]["priority"])
@@ -0,0 +1,67 @@
+"""
+PoC execution support.
+
+- Test validator: a managed `solana-test-validator` whose `--clone` list
+  is derived from the accounts a PoC touches, on allocated ports, with a
+  readiness check and teardown that survives exceptions and signals
+- Packages: a PoC bundled into a standalone repository with pinned
+  lockfiles, snapshotted accounts, a run script and reproduction steps
+- Redaction: a disclosure-safe copy of a PoC with placeholder addresses,
+  consistent across sources, fixtures and binaries, and amounts removed
+- Harness: shared attacker helpers (funded keypairs, airdrops, token
+  accounts, malicious-program deployment) and a fake token program
+- Scenarios: multi-step exploits (actors, preconditions, ordered
+  transactions, expected state deltas) compiled into an Anchor test
+- Matrix: every imported PoC run on LiteSVM, a local validator, a fork
+  of live state or the native runner, with results kept per backend
+"""
+
+from .harness import generate_harness, write_harness
+from .matrix import MATRIX_BACKENDS, PocRun, PocRunStore, parse_metrics, run_matrix, run_poc
+from .package import BACKENDS, PackageError, PocPackage, package_poc
+from .redact import Redaction, redact_poc
+from .scenario import Scenario, ScenarioError, ScenarioHarness, compile_scenario, load_scenario
+from .validator import (
+    BUILTIN_ACCOUNTS,
+    VALIDATOR_VERSION,
+    ClonePlan,
+    TestValidator,
+    ValidatorError,
+    allocate_ports,
+    extract_addresses,
+    fetch_accounts,
+    is_pubkey,
+    plan_clones,
+)
+
+__all__ = [
+    "BACKENDS",
+    "BUILTIN_ACCOUNTS",
+    "MATRIX_BACKENDS",
+    "VALIDATOR_VERSION",
+    "ClonePlan",
+    "PackageError",
+    "PocPackage",
+    "PocRun",
+    "PocRunStore",
+    "Redaction",
+    "Scenario",
+    "ScenarioError",
+    "ScenarioHarness",
+    "TestValidator",
+    "ValidatorError",
+    "allocate_ports",
+    "compile_scenario",
+    "extract_addresses",
+    "fetch_accounts",
+    "generate_harness",
+    "is_pubkey",
+    "load_scenario",
+    "package_poc",
+    "parse_metrics",
+    "plan_clones",
+    "redact_poc",
+    "run_matrix",
+    "run_poc",
+    "write_harness",
+]
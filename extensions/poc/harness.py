@@ -0,0 +1,134 @@
+"""
+Shared attacker environment for generated PoCs.
+
+Every Solana PoC needs the same setup before the exploit itself: funded
+attacker keypairs, airdrops that survive faucet limits, SPL mints and
+associated token accounts, and for arbitrary-CPI findings a malicious
+program to pass where the Token program is expected. ``attacker.ts`` holds
+that once; generated harnesses (scenario tests, PoC prompts) import it
+instead of reimplementing it.
+
+The fake token program is a native crate emitted next to the library. Its
+runtime (`src/lib.rs`) is fixed; only the manifest is generated.
+"""
+
+from pathlib import Path
+from typing import Any
+
+from extensions.knowledge.class_loader import ClassLoader
+
+HARNESS_VERSION = "1.0.0"
+SOLANA_VERSION = "~2.1"
+
+# Module name generated tests import the helpers from
+LIBRARY = "attacker"
+FAKE_TOKEN_CRATE = "fake-token"
+
+_TEMPLATES = Path(__file__).parent / "templates" / "harness"
+
+# Exported by attacker.ts, for prompts that tell a coding agent what is there
+HELPERS = {
+    "createAttacker / createAttackers": "fresh keypairs funded by airdrop (or by a funder on faucet-less clusters)",
+    "airdrop": "airdrop in faucet-sized chunks and wait for confirmation",
+    "createMint / ensureAta / mintTo / fundedTokenAccount": "SPL mints and associated token accounts, "
+                                                            "without @solana/spl-token",
+    "send / expectFailure": "send a legacy transaction; assert a rejection by error or program log",
+    "lamports / tokenAmount": "read balances for state-delta assertions",
+    "deployProgram / deployFakeTokenProgram": "deploy a malicious program with the solana CLI",
+    "fakeTokenSigners": "signers the fake token program was handed in a transaction",
+    "warpSlots / warpSeconds / warpToEpoch / setUnixTimestamp": "move the clock of a LiteSVM or bankrun test "
+                                                                 "(staking, vesting, staleness)",
+}
+
+
+def _cargo_toml() -> str:
+    return f"""[package]
+name = "{FAKE_TOKEN_CRATE}"
+version = "0.1.0"
+edition = "2021"
+description = "Fake SPL Token program for arbitrary-CPI PoCs (generated by Baskerville)"
+publish = false
+
+[lib]
+crate-type = ["cdylib", "lib"]
+
+[dependencies]
+solana-program = "{SOLANA_VERSION}"
+
+# Built on its own, not as a member of the audited workspace
+[workspace]
+"""
+
+
+def _fake_token_readme() -> str:
+    return f"""# Fake token program
+
+Generated by Baskerville. Pass it where the target program expects the SPL
+Token program, to show that the target does not validate its CPI target.
+
+```bash
+cargo build-sbf          # -> target/deploy/fake_token.so
+```
+
+Deploy it from the PoC with `deployFakeTokenProgram` from `{LIBRARY}.ts`,
+or preload it on a test validator with
+`--bpf-program <id> {FAKE_TOKEN_CRATE}/target/deploy/fake_token.so`.
+
+The program:
+
+- logs every signer it receives as `fake-token: signer <key>`
+  (`fakeTokenSigners` reads them back), so a leaked PDA signature shows up
+  in the transaction logs
+- accepts transfers, mints and burns without moving tokens, so a deposit
+  the target credits after the CPI was never paid
+- replays a transfer on the real Token program for the source's full
+  balance when the Token program is among its accounts and the authority
+  signed
+"""
+
+
+def generate_harness(fake_token: bool = False) -> dict[str, str]:
+    """Harness files by path relative to the PoC's test directory.
+
+    Args:
+        fake_token: Also emit the fake token program crate
+    """
+    files = {f"{LIBRARY}.ts": (_TEMPLATES / "attacker.ts").read_text()}
+    if fake_token:
+        files.update({
+            f"{FAKE_TOKEN_CRATE}/Cargo.toml": _cargo_toml(),
+            f"{FAKE_TOKEN_CRATE}/README.md": _fake_token_readme(),
+            f"{FAKE_TOKEN_CRATE}/src/lib.rs": (_TEMPLATES / "fake_token" / "lib.rs").read_text(),
+        })
+    return files
+
+
+def write_harness(files: dict[str, str], out_dir: Path, force: bool = False) -> list[Path]:
+    """Write harness files into a (usually existing) test directory.
+
+    Files already there with the same content are left alone; a file that
+    differs is only replaced with ``force``, so a PoC's edited copy survives.
+    Returns the files written.
+    """
+    out_dir = Path(out_dir)
+    conflicts = [rel for rel, content in files.items()
+                 if (out_dir / rel).exists() and (out_dir / rel).read_text() != content]
+    if conflicts and not force:
+        raise FileExistsError(f"{', '.join(conflicts)} already exist in {out_dir} with other content "
+                              "(pass force to overwrite)")
+    written = []
+    for rel, content in files.items():
+        path = out_dir / rel
+        if path.exists() and path.read_text() == content:
+            continue
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(content)
+        written.append(path)
+    return written
+
+
+def needs_fake_token(hypothesis: dict[str, Any], chain: str = "solana") -> bool:
+    """Whether the finding's class uses the fake token program (arbitrary CPI / CPI reentrancy)."""
+    vuln_class = ClassLoader().resolve(str(hypothesis.get("vulnerability_type") or ""), chain)
+    variant = vuln_class.variant(chain) if vuln_class else None
+    return bool(variant and {"cpi_reentrancy", "arbitrary_cpi"} & set(variant.templates))
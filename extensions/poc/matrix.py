@@ -0,0 +1,274 @@
+"""
+PoC execution matrix.
+
+Runs every imported PoC of a project on each selected backend and keeps
+the results, so which exploits are verified, and where, can be read off
+a hypothesis × backend grid:
+
+- litesvm: in-process LiteSVM, with the workspace's programs and account dumps
+- test-validator: a local `solana-test-validator` loaded with the same
+- fork: a `solana-test-validator` preloaded with the accounts the PoC
+  touches, snapshotted from a live cluster when the run starts
+- native: the project's own test runner (forge, sui move test, cargo test)
+
+Each run packages the PoC (see ``package_poc``) into a scratch directory
+and executes its ``run.sh``. A run passes when the PoC's tests pass, fails
+when its tests ran and failed, and is an error when it exited or timed out
+before any test ran. Runs that cannot apply (a LiteSVM backend for a PoC
+that does not use LiteSVM, a fork without a cluster) are skipped with the
+reason.
+
+Results go to ``PocRunStore``, one record per (batch, hypothesis,
+backend), with the exit code, duration, test counts, the most compute
+units one instruction consumed and the tail of the output.
+"""
+
+import os
+import re
+import subprocess
+import tempfile
+import time
+from collections.abc import Callable, Iterable
+from dataclasses import asdict, dataclass, fields
+from datetime import datetime
+from pathlib import Path
+from typing import Any
+
+from analysis.concurrent_knowledge import ConcurrentFileStore
+from extensions.ir.anchor_config import load_anchor_config
+
+from .package import PackageError, detect_backend, package_poc
+from .validator import ValidatorError, allocate_ports
+
+STORE_FORMAT = "baskerville-poc-runs"
+STORE_VERSION = 1
+
+# Matrix backend -> what the PoC runs against
+MATRIX_BACKENDS = {
+    "litesvm": "in-process LiteSVM with the workspace's programs and account dumps",
+    "test-validator": "local solana-test-validator with the workspace's programs and account dumps",
+    "fork": "solana-test-validator with the PoC's accounts snapshotted from a live cluster at run time",
+    "native": "the project's own test runner (forge, sui move test, cargo test)",
+}
+# Matrix backend -> (package backend, snapshot live state)
+_PACKAGE_BACKENDS = {
+    "litesvm": ("litesvm", False),
+    "test-validator": ("validator", False),
+    "fork": ("validator", True),
+    "native": ("native", False),
+}
+RUN_STATUSES = ("passed", "failed", "error", "skipped")
+
+# Output lines kept per run
+LOG_TAIL = 40
+
+_LITESVM_RE = re.compile(r"\b(?:litesvm|LiteSVM|bankrun|solana[-_]program[-_]test)\b")
+# mocha, cargo test / forge, sui move test
+_MOCHA_RE = re.compile(r"^\s*(\d+) (passing|failing)\b", re.MULTILINE)
+_CARGO_RE = re.compile(r"\b(\d+) passed; (\d+) failed\b")
+_MOVE_RE = re.compile(r"\bpassed: (\d+); failed: (\d+)\b")
+_CU_RE = re.compile(r"\bconsumed (\d+) of \d+ compute units\b")
+
+Runner = Callable[[list[str], Path, dict[str, str], float], tuple[int, str]]
+
+
+@dataclass
+class PocRun:
+    """One PoC run on one backend."""
+
+    hypothesis: str
+    backend: str
+    status: str
+    batch: str = ""
+    started_at: str = ""
+    command: str = ""
+    exit_code: int | None = None
+    duration: float = 0.0
+    tests_passed: int | None = None
+    tests_failed: int | None = None
+    # Most compute units one instruction consumed, from the program logs
+    compute_units: int | None = None
+    reason: str = ""
+    log: str = ""
+
+    def to_dict(self) -> dict[str, Any]:
+        return asdict(self)
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "PocRun":
+        known = {f.name for f in fields(cls)}
+        return cls(**{k: v for k, v in data.items() if k in known})
+
+
+class PocRunStore(ConcurrentFileStore):
+    """PoC run results of a project (``<project>/poc_runs.json``)."""
+
+    def _get_empty_data(self) -> dict:
+        return {"format": STORE_FORMAT, "version": STORE_VERSION, "runs": [],
+                "metadata": {"last_modified": datetime.now().isoformat()}}
+
+    def record(self, runs: Iterable[PocRun]) -> None:
+        records = [r.to_dict() for r in runs]
+
+        def update(data):
+            data.setdefault("runs", []).extend(records)
+            data.setdefault("metadata", {})["last_modified"] = datetime.now().isoformat()
+            return data, None
+
+        self.update_atomic(update)
+
+    def runs(self, hypothesis: str | None = None, backend: str | None = None) -> list[PocRun]:
+        """Stored runs, oldest first."""
+        lock = self._acquire_lock()
+        try:
+            data = self._load_data()
+        finally:
+            self._release_lock(lock)
+        runs = [PocRun.from_dict(r) for r in data.get("runs", [])]
+        return [r for r in runs if hypothesis in (None, r.hypothesis) and backend in (None, r.backend)]
+
+    def latest(self) -> dict[tuple[str, str], PocRun]:
+        """The last run of each (hypothesis, backend)."""
+        return {(r.hypothesis, r.backend): r for r in self.runs()}
+
+
+def default_backends(source: Path) -> list[str]:
+    """Every Solana backend for Solana workspaces, else the project's own runner."""
+    if detect_backend(Path(source)) == "native":
+        return ["native"]
+    return ["litesvm", "test-validator", "fork"]
+
+
+def parse_metrics(output: str) -> dict[str, int | None]:
+    """Test counts and peak compute units from a PoC run's output."""
+    passed = failed = None
+    mocha = _MOCHA_RE.findall(output)
+    counts = ([(int(n), 0) if kind == "passing" else (0, int(n)) for n, kind in mocha]
+              or [(int(p), int(f)) for p, f in _CARGO_RE.findall(output) + _MOVE_RE.findall(output)])
+    if counts:
+        passed, failed = sum(p for p, _ in counts), sum(f for _, f in counts)
+    units = [int(n) for n in _CU_RE.findall(output)]
+    return {"tests_passed": passed, "tests_failed": failed, "compute_units": max(units) if units else None}
+
+
+def _run(cmd: list[str], cwd: Path, env: dict[str, str], timeout: float) -> tuple[int, str]:
+    result = subprocess.run(cmd, cwd=cwd, env={**os.environ, **env}, stdout=subprocess.PIPE,
+                            stderr=subprocess.STDOUT, text=True, errors="replace", timeout=timeout)
+    return result.returncode, result.stdout
+
+
+def _skip_reason(source: Path, pocs: dict[str, Path], backend: str, url: str | None) -> str | None:
+    if backend == "native":
+        return None
+    if detect_backend(source) == "native":
+        return "not a Solana workspace"
+    if backend == "litesvm" and not any(_LITESVM_RE.search(p.read_text(errors="replace")) for p in pocs.values()):
+        return "the PoC does not use LiteSVM"
+    if backend == "fork":
+        try:
+            anchor = load_anchor_config(source)
+        except ValueError as e:
+            return str(e)
+        if not (url or (anchor.validator.url if anchor is not None else None)):
+            return "no cluster to fork (pass --url or set Anchor.toml [test.validator] url)"
+    return None
+
+
+def run_poc(source: Path, pocs: dict[str, Path], hypothesis_id: str, backend: str,
+            hypothesis: dict[str, Any] | None = None, url: str | None = None, command: str | None = None,
+            timeout: float = 600.0, batch: str = "", runner: Runner | None = None,
+            fetch: Callable[[list[str]], list[dict[str, Any] | None]] | None = None) -> PocRun:
+    """Package one PoC for ``backend`` in a scratch directory and run it.
+
+    Args:
+        source: Audited workspace
+        pocs: Destination path in the package -> PoC file (as for ``package_poc``)
+        hypothesis_id: Finding the PoC demonstrates
+        backend: One of ``MATRIX_BACKENDS``
+        hypothesis: The finding's hypothesis record
+        url: Cluster the fork backend snapshots (default: Anchor.toml `[test.validator] url`)
+        command: Test command (default: inferred from the PoC files)
+        timeout: Seconds before the run is stopped and recorded as an error
+        batch: Id shared by the runs of one matrix
+        runner: Runs ``run.sh`` and returns (exit code, output); defaults to a subprocess
+        fetch: Account lookup for the fork snapshot (default: an RPC client for ``url``)
+    """
+    if backend not in MATRIX_BACKENDS:
+        raise ValueError(f"unknown backend {backend!r} (choose from {', '.join(MATRIX_BACKENDS)})")
+    source = Path(source)
+    run = PocRun(hypothesis=hypothesis_id, backend=backend, status="skipped", batch=batch,
+                 started_at=datetime.now().isoformat(timespec="seconds"))
+    reason = _skip_reason(source, pocs, backend, url)
+    if reason:
+        run.reason = reason
+        return run
+
+    package_backend, snapshot = _PACKAGE_BACKENDS[backend]
+    runner = runner or _run
+    with tempfile.TemporaryDirectory(prefix="baskerville-poc-") as scratch:
+        start = time.monotonic()
+        try:
+            package = package_poc(source, pocs, Path(scratch) / "package", hypothesis_id, hypothesis=hypothesis,
+                                  backend=package_backend, url=url, command=command, fetch=fetch, pin=False,
+                                  snapshot=snapshot)
+            run.command = package.command
+            env = {"RPC_PORT": str(allocate_ports()["rpc"])} if package_backend == "validator" else {}
+            run.exit_code, output = runner(["bash", "run.sh"], package.root, env, timeout)
+        except (PackageError, ValidatorError) as e:
+            run.status, run.reason = "error", str(e)
+            return run
+        except subprocess.TimeoutExpired as e:
+            output = e.output.decode(errors="replace") if isinstance(e.output, bytes) else e.output or ""
+            run.status, run.reason = "error", f"timed out after {timeout:g}s"
+        finally:
+            run.duration = round(time.monotonic() - start, 2)
+
+    metrics = parse_metrics(output)
+    run.tests_passed, run.tests_failed, run.compute_units = (
+        metrics["tests_passed"], metrics["tests_failed"], metrics["compute_units"])
+    run.log = "\n".join(output.rstrip().splitlines()[-LOG_TAIL:])
+    if run.status == "error":
+        return run
+    if run.exit_code == 0:
+        run.status = "passed"
+    elif run.tests_passed is not None:
+        run.status = "failed"
+    else:
+        run.status, run.reason = "error", f"run.sh exited with {run.exit_code} before any test ran"
+    return run
+
+
+def run_matrix(source: Path, pocs: dict[str, dict[str, Path]], backends: list[str],
+               hypotheses: dict[str, dict[str, Any]] | None = None,
+               on_run: Callable[[PocRun], None] | None = None, **kwargs: Any) -> list[PocRun]:
+    """Run each hypothesis' PoC on each backend, in order.
+
+    Args:
+        source: Audited workspace
+        pocs: Hypothesis id -> its PoC files (as for ``run_poc``)
+        backends: ``MATRIX_BACKENDS`` to run on
+        hypotheses: Hypothesis records by id
+        on_run: Called with each result as it completes
+        **kwargs: Passed to ``run_poc`` (url, timeout, runner, fetch)
+    """
+    hypotheses = hypotheses or {}
+    batch = datetime.now().strftime("%Y%m%dT%H%M%S")
+    runs = []
+    for hypothesis_id, files in pocs.items():
+        for backend in backends:
+            run = run_poc(source, files, hypothesis_id, backend, hypothesis=hypotheses.get(hypothesis_id),
+                          batch=batch, **kwargs)
+            runs.append(run)
+            if on_run:
+                on_run(run)
+    return runs
+
+
+def matrix_rows(latest: dict[tuple[str, str], PocRun], backends: list[str] | None = None
+                ) -> tuple[list[str], list[tuple[str, list[PocRun | None]]]]:
+    """The backends (columns) and each hypothesis' last run per backend (rows)."""
+    if backends is None:
+        seen = {b for _, b in latest}
+        backends = [b for b in MATRIX_BACKENDS if b in seen]
+    hypotheses = sorted({h for h, _ in latest})
+    return backends, [(h, [latest.get((h, b)) for b in backends]) for h in hypotheses]
@@ -0,0 +1,527 @@
+"""
+Standalone PoC repositories.
+
+``package_poc`` bundles an exploit with everything a client needs to run it
+without Baskerville: a copy of the audited workspace (build output and
+installed dependencies left out) with its lockfiles, the PoC files, the
+on-chain state the PoC touches snapshotted into account dumps and program
+binaries, a ``run.sh`` that drives the chosen execution backend, and a
+README with reproduction steps.
+
+Cloned accounts are snapshotted rather than cloned at run time, so the
+package keeps reproducing after mainnet state moves on. Programs built from
+the workspace are rebuilt by ``run.sh`` from the copied sources.
+"""
+
+import base64
+import json
+import shlex
+import shutil
+import subprocess
+from collections.abc import Callable
+from dataclasses import asdict, dataclass, field
+from datetime import datetime
+from pathlib import Path
+from typing import Any
+
+from extensions.ir.anchor_config import b58encode, load_anchor_config
+from extensions.rpc import RpcClient, RpcError
+from extensions.rpc.providers import redact_url
+
+from .validator import UPGRADEABLE_LOADER, ValidatorError, extract_addresses, plan_clones
+
+PACKAGE_VERSION = "1.0.0"
+
+# Backend -> what `run.sh` runs the PoC against
+BACKENDS = {
+    "validator": "solana-test-validator preloaded with the snapshotted accounts and programs",
+    "litesvm": "in-process LiteSVM; the PoC loads the snapshots listed in fixtures/manifest.json",
+    "native": "the project's own test runner (forge, sui move test, cargo test)",
+}
+
+# Never copied into the package: VCS data, build output, installed dependencies, local ledgers
+WORKSPACE_EXCLUDES = {
+    ".git", "target", "node_modules", ".anchor", "test-ledger", ".ledger", "out", "cache",
+    "__pycache__", ".venv", ".DS_Store",
+}
+
+# Manifest -> lockfiles that pin it (any one is enough)
+LOCKFILES = {
+    "Cargo.toml": ("Cargo.lock",),
+    "package.json": ("package-lock.json", "yarn.lock", "pnpm-lock.yaml"),
+    "Move.toml": ("Move.lock",),
+}
+
+# Commands that write a missing lockfile without installing anything
+_LOCK_COMMANDS = {
+    "Cargo.toml": ["cargo", "generate-lockfile"],
+    "package.json": ["npm", "install", "--package-lock-only", "--ignore-scripts"],
+}
+
+_SOLANA_CRATES = ("solana-program", "anchor-lang", "pinocchio", "solana-sdk")
+
+# Program account: u32 tag (2) + programdata address; programdata: u32 tag + slot + optional authority
+_PROGRAMDATA_HEADER = 45
+
+
+class PackageError(RuntimeError):
+    """The PoC package could not be built."""
+
+
+@dataclass
+class PocPackage:
+    """What went into a packaged PoC repository."""
+
+    root: Path
+    hypothesis_id: str
+    title: str
+    backend: str
+    command: str
+    # Paths relative to the package root
+    poc_files: list[str] = field(default_factory=list)
+    lockfiles: list[str] = field(default_factory=list)
+    # Address -> snapshot file (account dump or program binary)
+    accounts: dict[str, str] = field(default_factory=dict)
+    programs: dict[str, str] = field(default_factory=dict)
+    # Address -> program built from the workspace by run.sh
+    built_programs: dict[str, str] = field(default_factory=dict)
+    snapshot_url: str | None = None
+    skipped: dict[str, str] = field(default_factory=dict)
+    warnings: list[str] = field(default_factory=list)
+    created_at: str = ""
+
+    def to_dict(self) -> dict[str, Any]:
+        data = asdict(self)
+        data["root"] = str(self.root)
+        return data
+
+
+def detect_backend(source: Path) -> str:
+    """Default backend for a workspace: the test validator for Solana programs, else ``native``."""
+    if _manifests(source, "Anchor.toml"):
+        return "validator"
+    for manifest in _manifests(source, "Cargo.toml"):
+        text = manifest.read_text(errors="replace")
+        if any(crate in text for crate in _SOLANA_CRATES):
+            return "validator"
+    return "native"
+
+
+def poc_destination(path: Path, source: Path) -> str:
+    """Where a PoC file goes in the package: its place in the workspace if it came from there, else poc/."""
+    return _within(path, source) or f"poc/{Path(path).name}"
+
+
+def _within(path: Path, root: Path) -> str | None:
+    """``path`` relative to ``root`` as a POSIX string, or None if it lies outside."""
+    try:
+        return Path(path).resolve().relative_to(Path(root).resolve()).as_posix()
+    except ValueError:
+        return None
+
+
+def _manifests(root: Path, name: str) -> list[Path]:
+    return sorted(p for p in root.rglob(name) if not WORKSPACE_EXCLUDES & set(p.relative_to(root).parts))
+
+
+def _nearest(root: Path, rel: str, name: str) -> Path | None:
+    """Closest ``name`` at or above ``rel`` inside ``root``."""
+    current = (root / rel).parent
+    while True:
+        if (current / name).exists():
+            return current / name
+        if current == root:
+            return None
+        current = current.parent
+
+
+def poc_command(root: Path, poc_files: list[str]) -> str:
+    """Command that runs the PoC with the tooling its language implies."""
+    scripts = [f for f in poc_files if Path(f).suffix in (".ts", ".js", ".mjs")]
+    if scripts:
+        runner = "npx ts-mocha" if any(f.endswith(".ts") for f in scripts) else "npx mocha"
+        tsconfig = " -p ./tsconfig.json" if (root / "tsconfig.json").exists() and runner.endswith("ts-mocha") else ""
+        return f"{runner}{tsconfig} -t 1000000 " + " ".join(shlex.quote(f) for f in scripts)
+    for rel in poc_files:
+        suffix = Path(rel).suffix
+        if suffix == ".sol":
+            return f"forge test --match-path {shlex.quote(rel)} -vvv"
+        if suffix == ".move":
+            package = _nearest(root, rel, "Move.toml")
+            path = package.parent.relative_to(root).as_posix() if package else "."
+            return f"sui move test --path {shlex.quote(path)}"
+        if suffix == ".rs":
+            manifest = _nearest(root, rel, "Cargo.toml")
+            where = f" --manifest-path {shlex.quote(manifest.relative_to(root).as_posix())}" if manifest else ""
+            target = f" --test {Path(rel).stem}" if Path(rel).parent.name == "tests" else ""
+            return f"cargo test{where}{target} -- --nocapture"
+    raise PackageError("cannot tell how to run the PoC; pass a test command")
+
+
+def pin_dependencies(root: Path, generate: bool = True,
+                     run: Callable[[list[str], Path], bool] | None = None) -> tuple[list[str], list[str]]:
+    """Lockfiles present in the package, generating missing ones where the tool is installed.
+
+    Returns (lockfiles relative to ``root``, warnings for manifests left unpinned).
+    """
+    run = run or _run_quiet
+    locks: list[str] = []
+    warnings: list[str] = []
+    for name, candidates in LOCKFILES.items():
+        manifests = _manifests(root, name)
+        if name == "Cargo.toml":
+            # Workspace members are pinned by the Cargo.lock of the outermost manifest
+            manifests = [m for m in manifests
+                         if not any(o != m and m.is_relative_to(o.parent) for o in manifests)]
+        for manifest in manifests:
+            directory = manifest.parent
+            found = [directory / c for c in candidates if (directory / c).exists()]
+            if not found and generate and name in _LOCK_COMMANDS and shutil.which(_LOCK_COMMANDS[name][0]):
+                if run(_LOCK_COMMANDS[name], directory):
+                    found = [directory / c for c in candidates if (directory / c).exists()]
+            if found:
+                locks += [f.relative_to(root).as_posix() for f in found]
+            else:
+                warnings.append(f"no lockfile for {manifest.relative_to(root).as_posix()}; "
+                                "dependency versions are not pinned")
+    return locks, warnings
+
+
+def _run_quiet(cmd: list[str], cwd: Path) -> bool:
+    try:
+        return subprocess.run(cmd, cwd=cwd, capture_output=True, timeout=600).returncode == 0
+    except (OSError, subprocess.TimeoutExpired):
+        return False
+
+
+def _account_dump(address: str, info: dict[str, Any]) -> dict[str, Any]:
+    """`solana account --output json` layout, which `--account` and ``load_account_dump`` read."""
+    data = info.get("data")
+    if not isinstance(data, list):
+        data = [data or "", "base64"]
+    return {
+        "pubkey": address,
+        "account": {
+            "lamports": info.get("lamports", 0),
+            "data": data,
+            "owner": info.get("owner"),
+            "executable": bool(info.get("executable")),
+            "rentEpoch": info.get("rentEpoch", 0),
+            "space": info.get("space", len(base64.b64decode(data[0]))),
+        },
+    }
+
+
+def _decode(info: dict[str, Any]) -> bytes:
+    data = info.get("data")
+    return base64.b64decode(data[0] if isinstance(data, list) else data or "")
+
+
+def snapshot_accounts(root: Path, accounts: list[str], programs: list[str],
+                      fetch: Callable[[list[str]], list[dict[str, Any] | None]]
+                      ) -> tuple[dict[str, str], dict[str, str], dict[str, str]]:
+    """Write account dumps and program binaries under ``fixtures/``.
+
+    Args:
+        root: Package root
+        accounts: Data accounts (and non-upgradeable programs) to dump as JSON
+        programs: Upgradeable programs, whose executable is read from their programdata account
+        fetch: Full account infos (base64 data) for a list of addresses
+
+    Returns (address -> account dump, address -> program binary, address -> why it was not snapshotted).
+    """
+    dumps: dict[str, str] = {}
+    binaries: dict[str, str] = {}
+    skipped: dict[str, str] = {}
+    if accounts:
+        (root / "fixtures" / "accounts").mkdir(parents=True, exist_ok=True)
+        for address, info in zip(accounts, fetch(accounts)):
+            if info is None:
+                skipped[address] = "not found when snapshotting"
+                continue
+            rel = f"fixtures/accounts/{address}.json"
+            (root / rel).write_text(json.dumps(_account_dump(address, info), indent=2) + "\n")
+            dumps[address] = rel
+    if programs:
+        (root / "fixtures" / "programs").mkdir(parents=True, exist_ok=True)
+        programdata: dict[str, str] = {}
+        for address, info in zip(programs, fetch(programs)):
+            data = _decode(info) if info else b""
+            if not info or info.get("owner") != UPGRADEABLE_LOADER or len(data) < 36 or data[0] != 2:
+                skipped[address] = "not an upgradeable program when snapshotting"
+                continue
+            programdata[address] = b58encode(data[4:36])
+        for (address, data_address), info in zip(programdata.items(), fetch(list(programdata.values()))):
+            elf = _decode(info)[_PROGRAMDATA_HEADER:] if info else b""
+            if not elf:
+                skipped[address] = f"programdata {data_address} is empty or missing"
+                continue
+            rel = f"fixtures/programs/{address}.so"
+            (root / rel).write_bytes(elf)
+            binaries[address] = rel
+    return dumps, binaries, skipped
+
+
+def _rpc_fetch(url: str) -> tuple[str, Callable[[list[str]], list[dict[str, Any] | None]]]:
+    try:
+        client = RpcClient(url)
+    except ValueError as e:
+        raise PackageError(str(e)) from e
+
+    def fetch(addresses: list[str]) -> list[dict[str, Any] | None]:
+        try:
+            return client.get_multiple_accounts(addresses)
+        except RpcError as e:
+            raise PackageError(f"account lookup on {redact_url(client.url)} failed: {e}") from e
+
+    return client.url, fetch
+
+
+def _install_steps(root: Path) -> list[str]:
+    if (root / "package-lock.json").exists():
+        return ["npm ci"]
+    if (root / "yarn.lock").exists():
+        return ["yarn install --frozen-lockfile"]
+    if (root / "pnpm-lock.yaml").exists():
+        return ["pnpm install --frozen-lockfile"]
+    if (root / "package.json").exists():
+        return ["npm install"]
+    return []
+
+
+def _build_steps(root: Path, package: PocPackage) -> list[str]:
+    if package.backend == "native":
+        return []
+    anchor = _manifests(root, "Anchor.toml")
+    if anchor:
+        directory = anchor[0].parent.relative_to(root).as_posix()
+        return ["anchor build" if directory == "." else f"(cd {shlex.quote(directory)} && anchor build)"]
+    if _manifests(root, "Cargo.toml"):
+        return ["cargo build-sbf"]
+    return []
+
+
+def render_run_script(root: Path, package: PocPackage) -> str:
+    """``run.sh``: install pinned dependencies, build, start the backend, run the PoC."""
+    lines = [
+        "#!/usr/bin/env bash",
+        f"# Reproduce {package.hypothesis_id}: {package.title}",
+        f"# Backend: {package.backend} ({BACKENDS[package.backend]})",
+        "set -euo pipefail",
+        'cd "$(dirname "$0")"',
+        "",
+    ]
+    steps = _install_steps(root) + _build_steps(root, package)
+    if steps:
+        lines += steps + [""]
+    if package.backend == "validator":
+        flags = [f"--bpf-program {a} {shlex.quote(p)}" for a, p in {**package.built_programs,
+                                                                    **package.programs}.items()]
+        flags += [f"--account {a} {shlex.quote(p)}" for a, p in package.accounts.items()]
+        validator = ' \\\n  '.join(['solana-test-validator --reset --quiet --ledger "$LEDGER" --rpc-port "$RPC_PORT"',
+                                    *flags])
+        lines += [
+            'RPC_PORT="${RPC_PORT:-8899}"',
+            'LEDGER="$(mktemp -d)"',
+            f"{validator} &",
+            "VALIDATOR=$!",
+            "trap 'kill \"$VALIDATOR\" 2>/dev/null || true; rm -rf \"$LEDGER\"' EXIT",
+            'export ANCHOR_PROVIDER_URL="http://127.0.0.1:$RPC_PORT"',
+            'export ANCHOR_WALLET="${ANCHOR_WALLET:-$HOME/.config/solana/id.json}"',
+            "for _ in $(seq 120); do",
+            "  curl -s -X POST -H 'Content-Type: application/json' "
+            "-d '{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"getHealth\"}' \"$ANCHOR_PROVIDER_URL\" "
+            "| grep -q '\"ok\"' && break",
+            "  sleep 0.5",
+            "done",
+            "",
+        ]
+    elif package.backend == "litesvm":
+        lines += ['export POC_FIXTURES="$PWD/fixtures"', ""]
+    lines.append(package.command)
+    return "\n".join(lines) + "\n"
+
+
+def _requirements(root: Path, package: PocPackage, toolchain: dict[str, Any]) -> list[str]:
+    reqs = []
+    if package.backend == "validator":
+        version = toolchain.get("solana_version")
+        reqs.append(f"Solana CLI{f' {version}' if version else ''} (`solana-test-validator`) and `curl`")
+    if _manifests(root, "Anchor.toml"):
+        version = toolchain.get("anchor_version")
+        reqs.append(f"Anchor{f' {version}' if version else ''}")
+    if _manifests(root, "Cargo.toml"):
+        toolchain_file = next((f for f in ("rust-toolchain.toml", "rust-toolchain") if (root / f).exists()), None)
+        reqs.append(f"Rust (toolchain pinned by `{toolchain_file}`)" if toolchain_file else "Rust")
+    if (root / "package.json").exists():
+        reqs.append("Node.js with npm, yarn or pnpm")
+    if package.command.startswith("forge"):
+        reqs.append("Foundry (`forge`)")
+    if package.command.startswith("sui"):
+        reqs.append("Sui CLI")
+    return reqs
+
+
+def render_readme(root: Path, package: PocPackage, hypothesis: dict[str, Any],
+                  toolchain: dict[str, Any] | None = None) -> str:
+    """Reproduction steps for the client."""
+    details = ", ".join(str(v) for v in (hypothesis.get("severity"), hypothesis.get("vulnerability_type")) if v)
+    parts = [f"# PoC: {package.title}", ""]
+    parts.append(f"Proof of concept for finding `{package.hypothesis_id}`" + (f" ({details})." if details else "."))
+    if hypothesis.get("description"):
+        parts += ["", hypothesis["description"].strip()]
+    parts += ["", "## Requirements", ""]
+    parts += [f"- {r}" for r in _requirements(root, package, toolchain or {})]
+
+    parts += ["", "## Reproduce", "", "```bash", "./run.sh", "```", "", "`run.sh`:", ""]
+    steps = [f"installs the pinned dependencies (`{s}`)" for s in _install_steps(root)]
+    steps += [f"builds the programs (`{s}`)" for s in _build_steps(root, package)]
+    if package.backend == "validator":
+        steps.append("starts `solana-test-validator` on `$RPC_PORT` (default 8899) with the snapshotted "
+                     "accounts and programs loaded, and stops it when the run ends")
+    elif package.backend == "litesvm":
+        steps.append("points `$POC_FIXTURES` at the snapshotted accounts and programs for LiteSVM")
+    steps.append(f"runs the PoC: `{package.command}`")
+    parts += [f"{i}. {s[0].upper()}{s[1:]}" for i, s in enumerate(steps, 1)]
+    parts += ["", "The run passes when the PoC's assertions about the exploit hold."]
+
+    parts += ["", "## Contents", ""]
+    parts += [f"- `{f}`: the PoC" for f in package.poc_files]
+    parts += [f"- `{f}`: pinned dependency versions" for f in package.lockfiles]
+    if package.accounts or package.programs:
+        source = f" from {package.snapshot_url}" if package.snapshot_url else ""
+        parts.append(f"- `fixtures/`: {len(package.accounts)} account(s) and {len(package.programs)} program(s) "
+                     f"snapshotted{source} on {package.created_at[:10]}; `fixtures/manifest.json` lists them")
+    if package.built_programs:
+        parts.append(f"- {len(package.built_programs)} program(s) built from the workspace sources by `run.sh`")
+    parts.append("- `poc.json`: package metadata and the reproducibility manifest")
+    if (root / "README.project.md").exists():
+        parts.append("- `README.project.md`: the audited workspace's own README")
+
+    notes = package.warnings + [f"`{a}` not included: {reason}" for a, reason in package.skipped.items()]
+    if notes:
+        parts += ["", "## Notes", ""] + [f"- {n}" for n in notes]
+    parts += ["", "---", "", "*Packaged by Baskerville*", ""]
+    return "\n".join(parts)
+
+
+def package_poc(source: Path, pocs: dict[str, Path], output: Path, hypothesis_id: str,
+                hypothesis: dict[str, Any] | None = None, backend: str | None = None, url: str | None = None,
+                clones: list[str] | None = None, command: str | None = None,
+                fetch: Callable[[list[str]], list[dict[str, Any] | None]] | None = None,
+                pin: bool = True, manifest: dict[str, Any] | None = None, force: bool = False,
+                snapshot: bool = True) -> PocPackage:
+    """Build a standalone repository that reproduces one PoC.
+
+    Args:
+        source: Audited workspace
+        pocs: Destination path in the package (see ``poc_destination``) -> PoC file
+        output: Package directory to create
+        hypothesis_id: Finding the PoC demonstrates
+        hypothesis: The finding's hypothesis record (title, severity, description)
+        backend: One of ``BACKENDS`` (default: ``detect_backend``)
+        url: Cluster to snapshot accounts from (default: Anchor.toml `[test.validator] url`)
+        clones: Further accounts to snapshot besides those quoted in the PoC
+        command: Test command (default: ``poc_command``)
+        fetch: Full account infos for a list of addresses (default: an RPC client for ``url``)
+        pin: Generate missing lockfiles where the tool is installed
+        manifest: Reproducibility manifest recorded in poc.json
+        force: Replace an existing ``output`` directory
+        snapshot: Snapshot the accounts the PoC touches from ``url``; when False the package
+            loads only built-in programs and the workspace's own programs and account dumps
+    """
+    hypothesis = hypothesis or {}
+    source = Path(source)
+    output = Path(output)
+    if not source.is_dir():
+        raise PackageError(f"workspace {source} is not a directory")
+    if not pocs:
+        raise PackageError(f"no PoC files for {hypothesis_id}")
+    backend = backend or detect_backend(source)
+    if backend not in BACKENDS:
+        raise PackageError(f"unknown backend {backend!r} (choose from {', '.join(BACKENDS)})")
+    if output.resolve().is_relative_to(source.resolve()):
+        raise PackageError("the package cannot be written inside the workspace it copies")
+    if output.exists() and any(output.iterdir()):
+        if not force:
+            raise PackageError(f"{output} already exists (use --force to replace it)")
+        shutil.rmtree(output)
+
+    shutil.copytree(source, output, ignore=shutil.ignore_patterns(*WORKSPACE_EXCLUDES), dirs_exist_ok=True)
+    for rel, path in pocs.items():
+        (output / rel).parent.mkdir(parents=True, exist_ok=True)
+        shutil.copy2(path, output / rel)
+
+    package = PocPackage(
+        root=output, hypothesis_id=hypothesis_id, title=hypothesis.get("title") or hypothesis_id, backend=backend,
+        command=command or poc_command(output, list(pocs)), poc_files=list(pocs),
+        created_at=datetime.now().isoformat(timespec="seconds"),
+    )
+    package.lockfiles, package.warnings = pin_dependencies(output, generate=pin)
+
+    toolchain: dict[str, Any] = {}
+    if backend != "native":
+        try:
+            anchor = load_anchor_config(source)
+        except ValueError as e:
+            raise PackageError(str(e)) from e
+        if anchor is not None:
+            toolchain = anchor.toolchain
+            url = url or anchor.validator.url
+        addresses = list(clones or [])
+        for path in pocs.values():
+            addresses += extract_addresses(path.read_text(errors="replace"))
+        if fetch is None and url and snapshot:
+            url, fetch = _rpc_fetch(url)
+        try:
+            plan = plan_clones(addresses, url=url, anchor=anchor, fetch=fetch, remote=snapshot)
+        except ValidatorError as e:
+            raise PackageError(str(e)) from e
+        # Built-in and workspace programs are present anyway; anything else skipped is worth a note
+        package.skipped.update({a: reason for a, reason in plan.skipped.items()
+                                if not reason.startswith(("built in", "loaded from the workspace"))})
+        if anchor is not None:
+            # Workspace programs are rebuilt by run.sh, so they need not be built yet
+            deploy = _within(anchor.root / "target" / "deploy", source)
+            package.built_programs.update({address: f"{deploy}/{name}.so"
+                                           for name, address in anchor.program_ids().items()})
+        # Genesis programs and account dumps the workspace ships
+        shipped = {**{a: p for a, p in plan.programs.items() if a not in package.built_programs},
+                   **plan.account_files}
+        for address, path in shipped.items():
+            rel = _within(path, source)
+            if rel is None or not (output / rel).exists():
+                package.warnings.append(f"{address} ({path}) is not in the workspace and not included")
+            elif address in plan.account_files:
+                package.accounts[address] = rel
+            else:
+                package.programs[address] = rel
+        if plan.clones:
+            package.snapshot_url = redact_url(plan.url) if plan.url else None
+            dumps, binaries, skipped = snapshot_accounts(output, plan.accounts, plan.upgradeable_programs, fetch)
+            package.accounts.update(dumps)
+            package.programs.update(binaries)
+            package.skipped.update(skipped)
+        (output / "fixtures").mkdir(exist_ok=True)
+        (output / "fixtures" / "manifest.json").write_text(json.dumps({
+            "cluster": package.snapshot_url,
+            "accounts": package.accounts,
+            "programs": {**package.built_programs, **package.programs},
+        }, indent=2) + "\n")
+
+    if (output / "README.md").exists():
+        # The package README takes the top-level slot; the workspace's own moves aside
+        (output / "README.md").rename(output / "README.project.md")
+    run_script = output / "run.sh"
+    run_script.write_text(render_run_script(output, package))
+    run_script.chmod(0o755)
+    (output / "README.md").write_text(render_readme(output, package, hypothesis, toolchain))
+    with open(output / ".gitignore", "a") as f:
+        f.write("\n# Build output and installed dependencies\ntarget/\nnode_modules/\ntest-ledger/\n.anchor/\n")
+    (output / "poc.json").write_text(json.dumps({
+        "format": "baskerville-poc-package",
+        "version": PACKAGE_VERSION,
+        **{k: v for k, v in package.to_dict().items() if k != "root"},
+        "manifest": manifest,
+    }, indent=2) + "\n")
+    return package
@@ -0,0 +1,377 @@
+"""
+Disclosure-safe PoC artifacts.
+
+``redact_poc`` rewrites a PoC directory (usually a ``poc package``) so it
+can be shared before the fix is deployed without pointing at the live
+deployment: every program ID and account address is swapped for a
+placeholder key, and drain amounts quoted in comments, strings and docs
+are removed.
+
+The swap is applied consistently everywhere the address occurs, as base58
+text in sources, Anchor.toml, ``run.sh`` and the fixture manifest, as raw
+32 bytes inside account dumps and program binaries, and in fixture file
+names, so the artifact still runs against its local fixtures. Placeholders
+are derived from a random salt, which keeps them unlinkable to the real
+addresses; the mapping back is returned for the auditor to keep, never
+written into the artifact.
+"""
+
+import base64
+import hashlib
+import json
+import re
+import secrets
+import shutil
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+from extensions.ir.anchor_config import b58encode
+from extensions.monitor.decode import b58decode
+
+from .package import PackageError
+from .validator import BUILTIN_ACCOUNTS, is_pubkey
+
+REDACTED_AMOUNT = "[redacted amount]"
+
+# Written by `poc package`; an address only these mention is not referenced by the PoC itself
+_GENERATED = {"run.sh", "poc.json", "fixtures/manifest.json"}
+
+_SKIP_DIRS = {".git", "target", "node_modules", ".anchor", "test-ledger", ".ledger", "__pycache__"}
+
+_ADDRESS = re.compile(r"(?<![1-9A-HJ-NP-Za-km-z])[1-9A-HJ-NP-Za-km-z]{32,44}(?![1-9A-HJ-NP-Za-km-z])")
+
+_NUMBER = r"\d[\d,_]*(?:\.\d+)?"
+_SCALE = r"(?:\s?(?:[kKmMbB]|thousand|million|billion)\b)?"
+_UNIT = r"(?:USD[CT]?|[wW]?SOL|[wW]?ETH|W?BTC|DAI|lamports?|tokens?|dollars)"
+_AMOUNTS = [
+    re.compile(rf"[$€£]\s?{_NUMBER}{_SCALE}"),
+    re.compile(rf"\b{_NUMBER}{_SCALE}\s?{_UNIT}\b"),
+]
+# Unitless figures (four digits or more) right after drain wording: "drained 1,250,000"
+_DRAIN_CONTEXT = re.compile(
+    r"(?i:\b(?:drain|steal|stole|profit|loss|lost|extract)\w*)(?:\W+\w+){0,3}?\W+"
+    r"(?P<amount>\d{1,3}(?:[,_]\d{3})+(?:\.\d+)?|\d{4,}(?:\.\d+)?)\b")
+
+# Suffix -> (line comment prefixes, string quotes); amounts are only redacted inside these
+_CODE = {
+    ".ts": (("//",), "\"'`"), ".tsx": (("//",), "\"'`"), ".js": (("//",), "\"'`"),
+    ".mjs": (("//",), "\"'`"), ".cjs": (("//",), "\"'`"),
+    ".rs": (("//",), '"'), ".sol": (("//",), "\"'"), ".move": (("//",), '"'),
+    ".py": (("#",), "\"'"),
+}
+_PROSE = {".md", ".txt", ".log", ".rst"}
+
+# SPL Token layouts: where a mint or token account stores other addresses
+_MINT_SIZE = 82
+_TOKEN_ACCOUNT_SIZE = 165
+_TOKEN_PROGRAMS = {"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"}
+
+
+@dataclass
+class Redaction:
+    """What a redaction pass replaced."""
+
+    root: Path
+    # Real address -> placeholder
+    mapping: dict[str, str] = field(default_factory=dict)
+    kept: list[str] = field(default_factory=list)
+    amounts: int = 0
+    # Paths relative to the artifact root
+    files: list[str] = field(default_factory=list)
+    warnings: list[str] = field(default_factory=list)
+
+    def redaction_map(self) -> dict[str, Any]:
+        """The private mapping back to the live addresses."""
+        return {"format": "baskerville-redaction-map", "root": str(self.root),
+                "addresses": self.mapping, "kept": self.kept}
+
+
+def placeholder(address: str, salt: bytes) -> str:
+    """Salted stand-in key for ``address``, stable within one redaction."""
+    return b58encode(hashlib.sha256(salt + b58decode(address)).digest())
+
+
+def _files(root: Path) -> list[Path]:
+    return sorted(p for p in root.rglob("*")
+                  if p.is_file() and not p.is_symlink()
+                  and not _SKIP_DIRS.intersection(p.relative_to(root).parts[:-1]))
+
+
+def _text(path: Path) -> str | None:
+    try:
+        return path.read_text()
+    except (UnicodeDecodeError, OSError):
+        return None
+
+
+def _account_dump(text: str) -> dict[str, Any] | None:
+    """A `solana account --output json` dump, as written to ``fixtures/accounts``."""
+    if '"account"' not in text:
+        return None
+    try:
+        dump = json.loads(text)
+    except ValueError:
+        return None
+    if isinstance(dump, dict) and isinstance(dump.get("account"), dict) and "pubkey" in dump:
+        return dump
+    return None
+
+
+def _keypair(text: str) -> str | None:
+    """Public key of a keypair file (64-byte JSON array)."""
+    if not text.lstrip().startswith("["):
+        return None
+    try:
+        values = json.loads(text)
+    except ValueError:
+        return None
+    if isinstance(values, list) and len(values) == 64 and all(isinstance(v, int) and 0 <= v < 256 for v in values):
+        return b58encode(bytes(values[32:]))
+    return None
+
+
+def _dump_data(dump: dict[str, Any]) -> bytes:
+    data = dump["account"].get("data")
+    return base64.b64decode(data[0] if isinstance(data, list) else data or "")
+
+
+def _token_addresses(owner: str | None, data: bytes) -> list[bytes]:
+    """Addresses stored in an SPL mint (authorities) or token account (mint, owner, delegate, close authority)."""
+    if owner not in _TOKEN_PROGRAMS:
+        return []
+    found = []
+    if len(data) == _MINT_SIZE:
+        slots = [(0, 4), (46, 50)]
+    elif len(data) >= _TOKEN_ACCOUNT_SIZE:
+        found += [data[0:32], data[32:64]]
+        slots = [(72, 76), (129, 133)]
+    else:
+        return []
+    # COption<Pubkey>: u32 tag, then the key when the tag is 1
+    found += [data[key:key + 32] for tag, key in slots if data[tag] == 1]
+    return found
+
+
+def redact_amounts(text: str) -> tuple[str, int]:
+    """Replace quantified amounts in prose; returns the text and how many were removed."""
+    count = 0
+
+    def sub(match: re.Match) -> str:
+        nonlocal count
+        count += 1
+        return REDACTED_AMOUNT
+
+    def sub_context(match: re.Match) -> str:
+        nonlocal count
+        count += 1
+        start, end = match.span("amount")
+        return match.group(0)[:start - match.start()] + REDACTED_AMOUNT + match.group(0)[end - match.start():]
+
+    for pattern in _AMOUNTS:
+        text = pattern.sub(sub, text)
+    text = _DRAIN_CONTEXT.sub(sub_context, text)
+    return text, count
+
+
+def _literals(comments: tuple[str, ...], quotes: str) -> re.Pattern:
+    alternatives = [rf"{re.escape(c)}[^\n]*" for c in comments]
+    if "//" in comments:
+        alternatives.append(r"/\*.*?\*/")
+    alternatives += [rf"{q}(?:\\.|[^{q}\\\n])*{q}" if q != "`" else r"`(?:\\.|[^`\\])*`" for q in quotes]
+    return re.compile("|".join(alternatives), re.S)
+
+
+def redact_code_amounts(text: str, suffix: str) -> tuple[str, int]:
+    """Redact amounts inside comments and string literals only, leaving the code's own numbers alone."""
+    comments, quotes = _CODE[suffix]
+    count = 0
+
+    def sub(match: re.Match) -> str:
+        nonlocal count
+        redacted, n = redact_amounts(match.group(0))
+        count += n
+        return redacted
+
+    return _literals(comments, quotes).sub(sub, text), count
+
+
+def redact_poc(path: Path, output: Path | None = None, keep: list[str] | tuple[str, ...] = (),
+               salt: bytes | None = None, force: bool = False) -> Redaction:
+    """Write a disclosure-safe copy of a PoC directory.
+
+    Args:
+        path: PoC package (or any PoC directory) to redact
+        output: Directory for the redacted copy (default: redact ``path`` in place)
+        keep: Addresses to leave as they are, such as program IDs an SDK the PoC uses hardcodes
+        salt: Placeholder salt (default: random per run, so placeholders cannot be matched to guesses)
+        force: Replace an existing ``output`` directory
+
+    Built-in programs and sysvars, and the public keys of keypair files in
+    the tree (local test signers), are kept too.
+    """
+    path = Path(path)
+    if not path.is_dir():
+        raise PackageError(f"{path} is not a directory")
+    if output is not None:
+        output = Path(output)
+        if output.resolve().is_relative_to(path.resolve()):
+            raise PackageError("the redacted copy cannot be written inside the PoC it redacts")
+        if output.exists() and any(output.iterdir()):
+            if not force:
+                raise PackageError(f"{output} already exists (use --force to replace it)")
+            shutil.rmtree(output)
+        shutil.copytree(path, output, ignore=shutil.ignore_patterns(*_SKIP_DIRS), symlinks=True,
+                        dirs_exist_ok=True)
+        path = output
+    for address in keep:
+        if not is_pubkey(address):
+            raise PackageError(f"{address} is not a public key")
+    salt = secrets.token_bytes(16) if salt is None else salt
+    result = Redaction(root=path)
+
+    # Pass 1: every address the artifact mentions, in text or in known account layouts
+    files = _files(path)
+    texts = {f: _text(f) for f in files}
+    dumps = {f: d for f, t in texts.items() if t is not None and (d := _account_dump(t)) is not None}
+    keypairs = {k for t in texts.values() if t is not None and (k := _keypair(t))}
+    found: dict[str, None] = {}
+    referenced: set[str] = set()
+    for f, text in texts.items():
+        rel = f.relative_to(path).as_posix()
+        for part in f.relative_to(path).parts:
+            if is_pubkey(Path(part).stem):
+                found[Path(part).stem] = None
+        if text is None or f in dumps:
+            continue
+        for token in _ADDRESS.findall(text):
+            if is_pubkey(token):
+                found[token] = None
+                if rel not in _GENERATED:
+                    referenced.add(token)
+    for dump in dumps.values():
+        owner = dump["account"].get("owner")
+        for address in (dump.get("pubkey"), owner):
+            if isinstance(address, str) and is_pubkey(address):
+                found[address] = None
+        for raw in _token_addresses(owner, _dump_data(dump)):
+            if any(raw):
+                found[b58encode(raw)] = None
+
+    kept = set(BUILTIN_ACCOUNTS) | set(keep) | keypairs
+    for address in found:
+        if address in kept:
+            result.kept.append(address)
+        else:
+            result.mapping[address] = placeholder(address, salt)
+    if not result.mapping:
+        result.warnings.append("no addresses to redact")
+    raw = {b58decode(a).rjust(32, b"\0"): b58decode(p).rjust(32, b"\0") for a, p in result.mapping.items()}
+
+    def swap_text(text: str) -> str:
+        return _ADDRESS.sub(lambda m: result.mapping.get(m.group(0), m.group(0)), text)
+
+    def swap_bytes(data: bytes) -> bytes:
+        for real, fake in raw.items():
+            data = data.replace(real, fake)
+        return data
+
+    # The snapshot source would say which cluster the state came from
+    meta_file = path / "poc.json"
+    snapshot_url = None
+    if meta_file in texts and texts[meta_file]:
+        try:
+            snapshot_url = json.loads(texts[meta_file]).get("snapshot_url")
+        except ValueError:
+            pass
+
+    # Pass 2: rewrite every file, moving those named after an address
+    for f in files:
+        rel = f.relative_to(path)
+        dest = path.joinpath(*(swap_text(part) for part in rel.parts))
+        text = texts[f]
+        if f in dumps:
+            dump = dumps[f]
+            account = dump["account"]
+            data = swap_bytes(_dump_data(dump))
+            account["data"] = [base64.b64encode(data).decode(), "base64"]
+            if isinstance(account.get("owner"), str):
+                account["owner"] = result.mapping.get(account["owner"], account["owner"])
+            if dump["pubkey"] not in referenced and account.get("owner") in result.mapping.values():
+                result.warnings.append(
+                    f"{rel.as_posix()} is owned by a redacted program and only the fixtures name it; if the "
+                    "PoC derives its address (a PDA), the derivation no longer matches the fixture")
+            dump["pubkey"] = result.mapping.get(str(dump["pubkey"]), dump["pubkey"])
+            new: str | bytes = json.dumps(dump, indent=2) + "\n"
+        elif text is None:
+            new = swap_bytes(f.read_bytes())
+        else:
+            new = swap_text(text)
+            if snapshot_url:
+                new = new.replace(snapshot_url, "<cluster>")
+            if f.suffix in _PROSE:
+                new, count = redact_amounts(new)
+                result.amounts += count
+            elif f.suffix in _CODE:
+                new, count = redact_code_amounts(new, f.suffix)
+                result.amounts += count
+        if f == meta_file and isinstance(new, str):
+            new, count = _redact_metadata(new)
+            result.amounts += count
+        original = text if text is not None else f.read_bytes()
+        if new != original or dest != f:
+            result.files.append(dest.relative_to(path).as_posix())
+        dest.parent.mkdir(parents=True, exist_ok=True)
+        if isinstance(new, str):
+            dest.write_text(new)
+        else:
+            dest.write_bytes(new)
+        if dest != f:
+            f.unlink()
+    for directory in sorted((d for d in path.rglob("*") if d.is_dir()), key=lambda d: -len(d.parts)):
+        if directory.name != swap_text(directory.name) and not any(directory.iterdir()):
+            directory.rmdir()
+
+    readme = path / "README.md"
+    if readme.exists():
+        readme.write_text(readme.read_text().rstrip("\n") + "\n\n" + _NOTICE)
+    result.warnings += _leaks(path, result.mapping)
+    return result
+
+
+def _redact_metadata(text: str) -> tuple[str, int]:
+    try:
+        meta = json.loads(text)
+    except ValueError:
+        return text, 0
+    if not isinstance(meta, dict):
+        return text, 0
+    count = 0
+    if isinstance(meta.get("title"), str):
+        meta["title"], count = redact_amounts(meta["title"])
+    meta["snapshot_url"] = None
+    meta["redacted"] = True
+    return json.dumps(meta, indent=2) + "\n", count
+
+
+_NOTICE = """## Redaction
+
+This copy is redacted for disclosure. Program IDs and account addresses are
+placeholder keys, consistently replaced in the sources, the fixtures and the
+program binaries, so `./run.sh` reproduces the issue against the local
+fixtures only. Amounts in comments, strings and documentation are removed.
+"""
+
+
+def _leaks(root: Path, mapping: dict[str, str]) -> list[str]:
+    """Real addresses still present after redaction, as text or raw bytes."""
+    if not mapping:
+        return []
+    raw = {a: b58decode(a).rjust(32, b"\0") for a in mapping}
+    leaks = []
+    for f in _files(root):
+        data = f.read_bytes()
+        for address, key in raw.items():
+            if address.encode() in data or key in data:
+                leaks.append(f"{f.relative_to(root).as_posix()} still contains {address}")
+    return leaks
@@ -0,0 +1,581 @@
+"""
+Multi-step exploit scenarios.
+
+Attacks such as close-then-revive or a governance takeover need several
+transactions in a fixed order, with state carried between them, which the
+single-instruction PoC templates cannot express. A scenario describes one
+in YAML (or JSON):
+
+- ``actors``: keypairs that sign, funded with ``lamports`` (or ``wallet: true``
+  for the provider wallet, e.g. an admin that already holds authority)
+- ``accounts``: named addresses: a fixed ``address``, a ``pda`` (seeds, and the
+  ``program`` deriving it) or a fresh ``keypair``. Seeds are UTF-8 strings,
+  ``{key: <name>}`` or ``{u8|u16|u32|u64: n}``; ``{bump: <pda>}`` passes a
+  PDA's bump as an instruction argument
+- ``preconditions``: state checked before the first step
+- ``steps``: one transaction each, in order; a step lists ``instructions``
+  (program instructions or ``transfer``s, sent atomically), optional extra
+  ``signers``, and ``fails`` when the transaction is expected to be rejected
+- ``expect`` (per step) and ``deltas`` (whole scenario): expected state
+  changes, measured from before the step or before the first step
+
+A check names an ``account`` (actor or account) and one of ``exists``,
+``lamports``, ``token`` (SPL token amount) or ``field`` + ``equals`` (a field
+of the decoded Anchor account). In preconditions ``lamports`` and ``token``
+are absolute; in ``expect`` and ``deltas`` they are changes: an exact signed
+amount, or ``increase``, ``decrease`` or ``unchanged``.
+
+``compile_scenario`` checks the scenario against the workspace IR
+(instruction, account and argument names, signers, account types) and emits
+an Anchor mocha test that runs the transactions in order, using the shared
+attacker helpers (see ``harness``) for funding, sending and balances.
+"""
+
+import json
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+import yaml
+
+from extensions.ir.model import Instruction, Program
+
+from .harness import LIBRARY
+from .validator import is_pubkey
+
+# Names a scenario can use for well-known programs and sysvars
+BUILTIN_NAMES = {
+    "system": "11111111111111111111111111111111",
+    "token": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+    "token_2022": "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
+    "associated_token": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+    "rent": "SysvarRent111111111111111111111111111111111",
+    "clock": "SysvarC1ock11111111111111111111111111111111",
+    "instructions": "Sysvar1nstructions1111111111111111111111111",
+}
+
+DELTAS = ("increase", "decrease", "unchanged")
+_CHECK_KINDS = ("exists", "lamports", "token", "field")
+_BN_TYPES = {"u64", "i64", "u128", "i128", "usize", "isize"}
+_NUMBER_TYPES = {"u8", "i8", "u16", "i16", "u32", "i32", "f32", "f64"}
+_SEED_INTS = {"u8": 1, "u16": 2, "u32": 4, "u64": 8}
+
+
+class ScenarioError(ValueError):
+    """The scenario is malformed or does not match the programs."""
+
+
+@dataclass
+class Actor:
+    """A keypair that signs scenario transactions."""
+
+    name: str
+    lamports: int = 10_000_000_000
+    wallet: bool = False
+
+
+@dataclass
+class ScenarioAccount:
+    """A named address used by the steps."""
+
+    name: str
+    address: str | None = None
+    pda: list[Any] | None = None
+    program: str | None = None  # Program deriving the PDA
+    keypair: bool = False
+    type: str | None = None  # Anchor account type, for field checks
+
+
+@dataclass
+class Check:
+    """A precondition or expected state change."""
+
+    account: str
+    kind: str  # One of _CHECK_KINDS
+    value: Any
+    field: str | None = None
+
+
+@dataclass
+class Call:
+    """One instruction inside a step's transaction."""
+
+    instruction: str | None = None
+    program: str | None = None
+    accounts: dict[str, str] = field(default_factory=dict)
+    args: dict[str, Any] = field(default_factory=dict)
+    # System transfer instead of a program instruction
+    transfer: dict[str, Any] | None = None
+
+
+@dataclass
+class Step:
+    """One transaction."""
+
+    name: str
+    calls: list[Call]
+    signers: list[str] = field(default_factory=list)
+    fails: str | None = None
+    expect: list[Check] = field(default_factory=list)
+    description: str = ""
+
+
+@dataclass
+class Scenario:
+    """A multi-transaction exploit."""
+
+    name: str
+    steps: list[Step]
+    description: str = ""
+    program: str | None = None  # Default program for calls that do not name one
+    actors: dict[str, Actor] = field(default_factory=dict)
+    accounts: dict[str, ScenarioAccount] = field(default_factory=dict)
+    preconditions: list[Check] = field(default_factory=list)
+    deltas: list[Check] = field(default_factory=list)
+
+
+@dataclass
+class ScenarioHarness:
+    """A compiled scenario."""
+
+    name: str
+    source: str
+    programs: list[str] = field(default_factory=list)
+    transactions: int = 0
+    warnings: list[str] = field(default_factory=list)
+
+
+def _mapping(value: Any, where: str) -> dict[str, Any]:
+    if value is None:
+        return {}
+    if not isinstance(value, dict):
+        raise ScenarioError(f"{where}: expected a mapping")
+    return value
+
+
+def _list(value: Any, where: str) -> list[Any]:
+    if value is None:
+        return []
+    if not isinstance(value, list):
+        raise ScenarioError(f"{where}: expected a list")
+    return value
+
+
+def _check(data: Any, where: str) -> Check:
+    data = _mapping(data, where)
+    if not data.get("account"):
+        raise ScenarioError(f"{where}: a check needs an account")
+    kinds = [k for k in _CHECK_KINDS if k in data]
+    if len(kinds) != 1:
+        raise ScenarioError(f"{where}: give exactly one of {', '.join(_CHECK_KINDS)}")
+    kind = kinds[0]
+    if kind == "field":
+        if "equals" not in data:
+            raise ScenarioError(f"{where}: a field check needs equals")
+        return Check(str(data["account"]), kind, data["equals"], field=str(data["field"]))
+    return Check(str(data["account"]), kind, data[kind])
+
+
+def _call(data: Any, where: str) -> Call:
+    data = _mapping(data, where)
+    if "transfer" in data:
+        transfer = _mapping(data["transfer"], f"{where}.transfer")
+        missing = [k for k in ("from", "to", "lamports") if k not in transfer]
+        if missing:
+            raise ScenarioError(f"{where}.transfer: missing {', '.join(missing)}")
+        return Call(transfer=transfer)
+    if not data.get("instruction"):
+        raise ScenarioError(f"{where}: needs an instruction or a transfer")
+    return Call(instruction=str(data["instruction"]), program=data.get("program"),
+                accounts={str(k): str(v) for k, v in _mapping(data.get("accounts"), f"{where}.accounts").items()},
+                args=_mapping(data.get("args"), f"{where}.args"))
+
+
+def parse_scenario(data: Any) -> Scenario:
+    """Build a ``Scenario`` from its decoded YAML/JSON form."""
+    data = _mapping(data, "scenario")
+    if not data.get("name"):
+        raise ScenarioError("scenario: needs a name")
+    actors = {}
+    for name, spec in _mapping(data.get("actors"), "actors").items():
+        spec = _mapping(spec, f"actors.{name}")
+        actors[str(name)] = Actor(str(name), lamports=int(spec.get("lamports", Actor.lamports)),
+                                  wallet=bool(spec.get("wallet")))
+    accounts = {}
+    for name, spec in _mapping(data.get("accounts"), "accounts").items():
+        where = f"accounts.{name}"
+        spec = {"address": spec} if isinstance(spec, str) else _mapping(spec, where)
+        kinds = [k for k in ("address", "pda", "keypair") if spec.get(k)]
+        if len(kinds) != 1:
+            raise ScenarioError(f"{where}: give exactly one of address, pda or keypair")
+        if spec.get("address") and not is_pubkey(str(spec["address"])):
+            raise ScenarioError(f"{where}: {spec['address']} is not a public key")
+        accounts[str(name)] = ScenarioAccount(
+            str(name), address=spec.get("address"), pda=_list(spec.get("pda"), f"{where}.pda") or None,
+            program=spec.get("program"), keypair=bool(spec.get("keypair")), type=spec.get("type"))
+    clash = set(actors) & set(accounts)
+    if clash:
+        raise ScenarioError(f"names used for both an actor and an account: {', '.join(sorted(clash))}")
+
+    steps = []
+    for i, spec in enumerate(_list(data.get("steps"), "steps")):
+        where = f"steps[{i}]"
+        spec = _mapping(spec, where)
+        raw_calls = _list(spec.get("instructions"), f"{where}.instructions")
+        if spec.get("instruction") or spec.get("transfer"):
+            raw_calls = [{k: spec[k] for k in ("instruction", "program", "accounts", "args", "transfer")
+                          if k in spec}] + raw_calls
+        if not raw_calls:
+            raise ScenarioError(f"{where}: a step needs at least one instruction")
+        steps.append(Step(
+            name=str(spec.get("name") or f"step {i + 1}"),
+            calls=[_call(c, f"{where}.instructions[{j}]") for j, c in enumerate(raw_calls)],
+            signers=[str(s) for s in _list(spec.get("signers"), f"{where}.signers")],
+            fails=spec.get("fails"),
+            expect=[_check(c, f"{where}.expect[{j}]") for j, c in enumerate(_list(spec.get("expect"), where))],
+            description=str(spec.get("description") or ""),
+        ))
+    if not steps:
+        raise ScenarioError("scenario: needs at least one step")
+    return Scenario(
+        name=str(data["name"]), steps=steps, description=str(data.get("description") or ""),
+        program=data.get("program"), actors=actors, accounts=accounts,
+        preconditions=[_check(c, f"preconditions[{i}]")
+                       for i, c in enumerate(_list(data.get("preconditions"), "preconditions"))],
+        deltas=[_check(c, f"deltas[{i}]") for i, c in enumerate(_list(data.get("deltas"), "deltas"))],
+    )
+
+
+def load_scenario(path: Path) -> Scenario:
+    """Read a scenario file (YAML or JSON)."""
+    path = Path(path)
+    try:
+        data = json.loads(path.read_text()) if path.suffix == ".json" else yaml.safe_load(path.read_text())
+    except (OSError, ValueError, yaml.YAMLError) as e:
+        raise ScenarioError(f"cannot read {path}: {e}") from e
+    return parse_scenario(data)
+
+
+def _camel(name: str) -> str:
+    head, *rest = name.split("_")
+    return head + "".join(p[:1].upper() + p[1:] for p in rest)
+
+
+def _pascal(name: str) -> str:
+    return "".join(p[:1].upper() + p[1:] for p in re.split(r"[_\-]", name))
+
+
+def _ident(name: str) -> str:
+    """TypeScript identifier for a scenario name."""
+    ident = _camel(re.sub(r"\W", "_", name))
+    return f"_{ident}" if ident[:1].isdigit() else ident
+
+
+def _accounts_method(anchor_version: str | None) -> str:
+    """Anchor 0.30 resolves PDAs and programs itself; partial account lists go through accountsPartial."""
+    parts = [int(p) for p in re.findall(r"\d+", anchor_version or "")[:2]]
+    return "accountsPartial" if parts and (parts[0], parts[1] if len(parts) > 1 else 0) >= (0, 30) else "accounts"
+
+
+class _Compiler:
+    def __init__(self, scenario: Scenario, programs: list[Program], anchor_version: str | None):
+        self.scenario = scenario
+        self.programs = {p.name: p for p in programs if p.chain == "solana"}
+        self.accounts_method = _accounts_method(anchor_version)
+        self.used: dict[str, None] = {}
+        self.types: dict[str, str] = {a.name: a.type for a in scenario.accounts.values() if a.type}
+        self.warnings: list[str] = []
+
+    # References
+
+    def program(self, name: str | None, where: str) -> Program:
+        name = name or self.scenario.program
+        if name is None:
+            if len(self.programs) != 1:
+                raise ScenarioError(f"{where}: name the program ({', '.join(self.programs) or 'none loaded'})")
+            name = next(iter(self.programs))
+        program = self.programs.get(name)
+        if program is None:
+            raise ScenarioError(f"{where}: unknown program {name!r} ({', '.join(self.programs) or 'none loaded'})")
+        if program.framework != "anchor":
+            raise ScenarioError(f"{where}: {name} is a {program.framework} program; scenarios compile to Anchor "
+                                "clients")
+        self.used[name] = None
+        return program
+
+    def key(self, ref: Any, where: str) -> str:
+        """TypeScript expression for the public key ``ref`` names."""
+        ref = str(ref)
+        if ref in self.scenario.actors:
+            return f"{self.signer(ref, where)}.publicKey"
+        if ref in self.scenario.accounts:
+            account = self.scenario.accounts[ref]
+            return f"{_ident(ref)}.publicKey" if account.keypair else _ident(ref)
+        if ref in self.programs:
+            self.used[ref] = None
+            return f"{_ident(ref)}Program.programId"
+        if ref in BUILTIN_NAMES:
+            return f'new PublicKey("{BUILTIN_NAMES[ref]}")'
+        if is_pubkey(ref):
+            return f'new PublicKey("{ref}")'
+        raise ScenarioError(f"{where}: unknown actor, account or program {ref!r}")
+
+    def signer(self, ref: str, where: str) -> str:
+        if ref in self.scenario.actors:
+            actor = self.scenario.actors[ref]
+            return "wallet" if actor.wallet else _ident(ref)
+        account = self.scenario.accounts.get(ref)
+        if account is not None and account.keypair:
+            return _ident(ref)
+        raise ScenarioError(f"{where}: {ref!r} cannot sign (only actors and keypair accounts can)")
+
+    def seed(self, seed: Any, where: str) -> str:
+        if isinstance(seed, dict) and len(seed) == 1:
+            kind, value = next(iter(seed.items()))
+            if kind == "str":
+                return f"Buffer.from({json.dumps(str(value))})"
+            if kind == "key":
+                return f"{self.key(value, where)}.toBuffer()"
+            if kind in _SEED_INTS:
+                return f"new anchor.BN({json.dumps(str(value))}).toArrayLike(Buffer, \"le\", {_SEED_INTS[kind]})"
+        if isinstance(seed, str):
+            return f"Buffer.from({json.dumps(seed)})"
+        raise ScenarioError(f"{where}: a seed is a string or one of {{key: name}}, {{u8|u16|u32|u64: n}}")
+
+    def arg(self, value: Any, ty: str, where: str) -> str:
+        ty = ty.replace(" ", "")
+        if isinstance(value, dict) and set(value) == {"bump"}:
+            account = self.scenario.accounts.get(str(value["bump"]))
+            if account is None or account.pda is None:
+                raise ScenarioError(f"{where}: {value['bump']!r} is not a PDA account")
+            return f"{_ident(account.name)}Bump"
+        if ty.startswith("Option<"):
+            return "null" if value is None else self.arg(value, ty[7:-1], where)
+        if ty in _BN_TYPES:
+            if isinstance(value, bool) or not re.fullmatch(r"-?\d+", str(value).replace("_", "")):
+                raise ScenarioError(f"{where}: {value!r} is not an integer")
+            return f"new anchor.BN({json.dumps(str(value).replace('_', ''))})"
+        if ty in _NUMBER_TYPES:
+            if isinstance(value, bool) or not isinstance(value, int | float):
+                raise ScenarioError(f"{where}: {value!r} is not a number")
+            return str(value)
+        if ty == "Pubkey":
+            return self.key(value, where)
+        if ty in ("Vec<u8>", "Bytes") or re.fullmatch(r"\[u8;\d+\]", ty):
+            return f"Buffer.from({json.dumps(value)})"
+        return json.dumps(value)
+
+    # Calls and checks
+
+    def call(self, call: Call, signers: list[str], where: str) -> str:
+        if call.transfer is not None:
+            transfer = call.transfer
+            signers.append(str(transfer["from"]))
+            return (f"SystemProgram.transfer({{ fromPubkey: {self.key(transfer['from'], where)}, "
+                    f"toPubkey: {self.key(transfer['to'], where)}, "
+                    f"lamports: {self._amount(transfer['lamports'], where, bigint=False)} }})")
+        program = self.program(call.program, where)
+        ix = program.instruction(call.instruction)
+        if ix is None:
+            raise ScenarioError(f"{where}: {program.name} has no instruction {call.instruction!r}")
+        accounts = []
+        for name, ref in call.accounts.items():
+            acc = ix.account(name)
+            if acc is None:
+                raise ScenarioError(f"{where}: {ix.name} has no account {name!r} "
+                                    f"({', '.join(a.name for a in ix.accounts)})")
+            accounts.append(f"{_camel(name)}: {self.key(ref, where)}")
+            if acc.is_signer:
+                self.signer(ref, where)
+                signers.append(ref)
+            if acc.inner_type and ref in self.scenario.accounts and acc.wrapper in ("Account", "AccountLoader"):
+                self.types.setdefault(ref, acc.inner_type)
+        self._missing_accounts(ix, call, where)
+        args = []
+        for arg in ix.args:
+            if arg.name not in call.args:
+                raise ScenarioError(f"{where}: {ix.name} needs argument {arg.name!r} ({arg.ty})")
+            args.append(self.arg(call.args[arg.name], arg.ty, f"{where}.args.{arg.name}"))
+        extra = set(call.args) - {a.name for a in ix.args}
+        if extra:
+            raise ScenarioError(f"{where}: {ix.name} takes no argument {', '.join(sorted(extra))}")
+        return (f"await {_ident(program.name)}Program.methods.{_camel(ix.name)}({', '.join(args)})"
+                f".{self.accounts_method}({{ {', '.join(accounts)} }}).instruction()")
+
+    def _missing_accounts(self, ix: Instruction, call: Call, where: str) -> None:
+        """Accounts the client cannot fill in: not passed, not a program or sysvar, not a PDA."""
+        for acc in ix.accounts:
+            if acc.name in call.accounts or acc.seeds or acc.ty.replace(" ", "").startswith("Option<"):
+                continue
+            if acc.wrapper in ("Program", "Sysvar", "Interface") or acc.name in ("system_program", "rent"):
+                continue
+            self.warnings.append(f"{where}: {ix.name} account {acc.name!r} is not given")
+
+    def check(self, check: Check, delta: bool, where: str) -> tuple[str, str | None]:
+        """(assertion, value to snapshot first for a delta) for one check."""
+        key = self.key(check.account, where)
+        label = f"{check.account} {check.field or check.kind}"
+        if check.kind == "exists":
+            verb = "isNotNull" if check.value else "isNull"
+            return f'assert.{verb}(await connection.getAccountInfo({key}), "{label}");', None
+        if check.kind == "field":
+            type_name = self.types.get(check.account)
+            if type_name is None:
+                raise ScenarioError(f"{where}: cannot tell the account type of {check.account!r}; give it a type")
+            owner = self._type_owner(type_name, check, where)
+            value = self._field_value(check.value, owner, type_name, check.field, where)
+            fetched = f"{_ident(owner.name)}Program.account.{type_name[:1].lower() + type_name[1:]}.fetch({key})"
+            return f'assert.equal(String((await {fetched}).{_camel(check.field)}), String({value}), "{label}");', None
+        reader = "lamports" if check.kind == "lamports" else "tokenAmount"
+        current = f"await {reader}(connection, {key})"
+        if not delta:
+            return f'assert.equal({current}, {self._amount(check.value, where)}, "{label}");', None
+        snapshot = f"{reader}:{check.account}"
+        before = f"prior[{json.dumps(snapshot)}]"
+        if check.value in DELTAS:
+            op = {"increase": ">", "decrease": "<", "unchanged": "==="}[check.value]
+            return f'assert.isTrue({current} {op} {before}, "{label}: {check.value}");', snapshot
+        return f'assert.equal({current} - {before}, {self._amount(check.value, where)}, "{label} change");', snapshot
+
+    def _type_owner(self, type_name: str, check: Check, where: str) -> Program:
+        owners = [p for p in self.programs.values() if p.account_type(type_name)]
+        if not owners:
+            raise ScenarioError(f"{where}: no program defines account type {type_name!r}")
+        owner = owners[0]
+        known = {f.name for f in owner.account_type(type_name).fields}
+        if known and check.field not in known:
+            raise ScenarioError(f"{where}: {type_name} has no field {check.field!r} ({', '.join(sorted(known))})")
+        self.used[owner.name] = None
+        return owner
+
+    def _field_value(self, value: Any, owner: Program, type_name: str, name: str, where: str) -> str:
+        fields = {f.name: f.ty for f in owner.account_type(type_name).fields}
+        if fields.get(name, "").replace(" ", "") == "Pubkey":
+            return self.key(value, where)
+        return json.dumps(str(value).lower() if isinstance(value, bool) else str(value).replace("_", ""))
+
+    @staticmethod
+    def _amount(value: Any, where: str, bigint: bool = True) -> str:
+        text = str(value).replace("_", "")
+        if isinstance(value, bool) or not re.fullmatch(r"[+-]?\d+", text):
+            raise ScenarioError(f"{where}: {value!r} is not an amount (or one of {', '.join(DELTAS)})")
+        return f"{int(text)}n" if bigint else str(int(text))
+
+
+def compile_scenario(scenario: Scenario, programs: list[Program], anchor_version: str | None = None
+                     ) -> ScenarioHarness:
+    """Check ``scenario`` against the workspace programs and emit an Anchor mocha test.
+
+    Args:
+        scenario: Parsed scenario
+        programs: IR programs of the workspace
+        anchor_version: Anchor.toml toolchain version (selects the client's account-list method)
+    """
+    compiler = _Compiler(scenario, programs, anchor_version)
+    for name, account in scenario.accounts.items():
+        if account.pda is not None:
+            compiler.program(account.program, f"accounts.{name}")
+    pre = [compiler.check(c, False, f"preconditions[{i}]")[0] for i, c in enumerate(scenario.preconditions)]
+
+    steps = []
+    for i, step in enumerate(scenario.steps):
+        where = f"steps[{i}]"
+        signers: list[str] = list(step.signers)
+        calls = [compiler.call(c, signers, f"{where}.instructions[{j}]") for j, c in enumerate(step.calls)]
+        expect = [compiler.check(c, True, f"{where}.expect[{j}]") for j, c in enumerate(step.expect)]
+        ordered = [compiler.signer(s, where) for s in dict.fromkeys(signers)]
+        steps.append((step, calls, ordered, expect))
+    deltas = [compiler.check(c, True, f"deltas[{i}]") for i, c in enumerate(scenario.deltas)]
+
+    body = _render(scenario, compiler, pre, steps, deltas)
+    return ScenarioHarness(name=scenario.name, source=body, programs=list(compiler.used),
+                           transactions=len(scenario.steps), warnings=compiler.warnings)
+
+
+def _snapshot(snapshots: list[str | None], compiler: _Compiler, indent: str) -> list[str]:
+    lines = []
+    for key in dict.fromkeys(s for s in snapshots if s):
+        reader, name = key.split(":", 1)
+        lines.append(f"{indent}{json.dumps(key)}: await {reader}(connection, {compiler.key(name, 'snapshot')}),")
+    return lines
+
+
+def _render(scenario: Scenario, compiler: _Compiler, pre: list[str], steps: list[tuple], deltas: list[tuple]
+            ) -> str:
+    lines = [f"// Scenario `{scenario.name}`, compiled by Baskerville: {len(steps)} transaction(s) run in order."]
+    lines += [f"// {line}" for line in scenario.description.strip().splitlines()]
+    lines += [
+        "",
+        'import * as anchor from "@coral-xyz/anchor";',
+        'import { Program } from "@coral-xyz/anchor";',
+        'import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";',
+        'import { assert } from "chai";',
+        f'import {{ airdrop, expectFailure, lamports, send, tokenAmount }} from "./{LIBRARY}";',
+        "",
+        f"describe({json.dumps('scenario: ' + scenario.name)}, () => {{",
+        "  const provider = anchor.AnchorProvider.env();",
+        "  anchor.setProvider(provider);",
+        "  const connection = provider.connection;",
+        "  const wallet = (provider.wallet as anchor.Wallet).payer;",
+    ]
+    for name in compiler.used:
+        lines.append(f"  const {_ident(name)}Program = anchor.workspace.{_pascal(name)} as Program;")
+
+    lines += ["", "  // Actors"]
+    for actor in scenario.actors.values():
+        if not actor.wallet:
+            lines.append(f"  const {_ident(actor.name)} = Keypair.generate();")
+    lines += ["", "  // Accounts"]
+    for account in scenario.accounts.values():
+        ident = _ident(account.name)
+        if account.keypair:
+            lines.append(f"  const {ident} = Keypair.generate();")
+        elif account.address:
+            lines.append(f'  const {ident} = new PublicKey("{account.address}");')
+        else:
+            lines += [f"  let {ident}: PublicKey;", f"  let {ident}Bump: number;"]
+
+    lines += ["", "  let start: Record<string, bigint> = {};", "", "  before(async () => {"]
+    for actor in scenario.actors.values():
+        if not actor.wallet:
+            lines.append(f"    await airdrop(connection, {_ident(actor.name)}.publicKey, {actor.lamports}, wallet);")
+    for account in scenario.accounts.values():
+        if account.pda is not None:
+            program = compiler.program(account.program, f"accounts.{account.name}")
+            seeds = ", ".join(compiler.seed(s, f"accounts.{account.name}.pda") for s in account.pda)
+            ident = _ident(account.name)
+            lines.append(f"    [{ident}, {ident}Bump] = PublicKey.findProgramAddressSync([{seeds}], "
+                         f"{_ident(program.name)}Program.programId);")
+    lines.append("  });")
+
+    lines += ["", '  it("preconditions", async () => {']
+    lines += [f"    {assertion}" for assertion in pre] or ["    // none"]
+    start = _snapshot([s for _, s in deltas], compiler, "      ")
+    if start:
+        lines += ["    start = {"] + start + ["    };"]
+    lines.append("  });")
+
+    for i, (step, calls, signers, expect) in enumerate(steps, 1):
+        lines += ["", f"  it({json.dumps(f'{i}. {step.name}')}, async () => {{"]
+        lines += [f"    // {line}" for line in step.description.strip().splitlines()]
+        snapshot = _snapshot([s for _, s in expect], compiler, "      ")
+        if snapshot:
+            lines += ["    const prior: Record<string, bigint> = {"] + snapshot + ["    };"]
+        lines.append("    const instructions = [")
+        lines += [f"      {call}," for call in calls]
+        lines.append("    ];")
+        sent = f"send(connection, instructions, [{', '.join(signers or ['wallet'])}])"
+        if step.fails:
+            lines.append(f"    await expectFailure({sent}, {json.dumps(str(step.fails))});")
+        else:
+            lines.append(f"    await {sent};")
+        lines += [f"    {assertion}" for assertion, _ in expect]
+        lines.append("  });")
+
+    if deltas:
+        lines += ["", '  it("expected state", async () => {', "    const prior = start;"]
+        lines += [f"    {assertion}" for assertion, _ in deltas]
+        lines.append("  });")
+    lines += ["});", ""]
+    return "\n".join(lines)
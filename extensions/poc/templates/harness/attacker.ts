@@ -0,0 +1,385 @@
+// Attacker environment helpers shared by Baskerville-generated PoCs.
+//
+// Keypairs, airdrops, SPL mints and associated token accounts, transaction
+// sending, clock warping and malicious-program deployment, with no
+// dependency beyond @solana/web3.js so the file drops into any Anchor or
+// web3.js test suite.
+
+import { execFileSync } from "child_process";
+import { mkdtempSync, rmSync, writeFileSync } from "fs";
+import { tmpdir } from "os";
+import { join } from "path";
+import {
+  Connection,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+
+export const TOKEN_PROGRAM_ID = new PublicKey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+export const TOKEN_2022_PROGRAM_ID = new PublicKey("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+export const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+const MINT_SIZE = 82;
+
+// ---------------------------------------------------------------------------
+// Transactions
+// ---------------------------------------------------------------------------
+
+/** Send ``instructions`` in one legacy transaction; the first signer pays the fee. */
+export async function send(
+  connection: Connection,
+  instructions: TransactionInstruction[],
+  signers: Keypair[],
+): Promise<string> {
+  const tx = new Transaction().add(...instructions);
+  return await sendAndConfirmTransaction(connection, tx, signers, { commitment: "confirmed" });
+}
+
+/** Assert that ``sent`` is rejected with ``error`` in the message or the program logs. */
+export async function expectFailure(sent: Promise<unknown>, error: string): Promise<void> {
+  try {
+    await sent;
+  } catch (e: any) {
+    const text = `${e}\n${(e.logs ?? []).join("\n")}`;
+    if (!text.includes(error)) {
+      throw new Error(`expected the transaction to fail with ${error}, got:\n${text}`);
+    }
+    return;
+  }
+  throw new Error(`expected the transaction to fail with ${error}`);
+}
+
+// ---------------------------------------------------------------------------
+// Keypairs and SOL
+// ---------------------------------------------------------------------------
+
+/**
+ * Airdrop ``lamports`` to ``to`` and wait for confirmation. Faucets cap a
+ * single request, so large amounts go out in several; when the cluster has
+ * no faucet (a fork, a rate-limited devnet) ``funder`` pays by transfer.
+ */
+export async function airdrop(
+  connection: Connection,
+  to: PublicKey,
+  lamports: number = 10 * LAMPORTS_PER_SOL,
+  funder?: Keypair,
+): Promise<void> {
+  const chunk = 100 * LAMPORTS_PER_SOL;
+  try {
+    for (let left = lamports; left > 0; left -= chunk) {
+      const signature = await connection.requestAirdrop(to, Math.min(left, chunk));
+      const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+      await connection.confirmTransaction({ signature, blockhash, lastValidBlockHeight }, "confirmed");
+    }
+  } catch (e) {
+    if (!funder) throw e;
+    await send(connection, [SystemProgram.transfer({ fromPubkey: funder.publicKey, toPubkey: to, lamports })], [funder]);
+  }
+}
+
+/** A fresh keypair holding ``lamports``. */
+export async function createAttacker(
+  connection: Connection,
+  lamports: number = 10 * LAMPORTS_PER_SOL,
+  funder?: Keypair,
+): Promise<Keypair> {
+  const keypair = Keypair.generate();
+  await airdrop(connection, keypair.publicKey, lamports, funder);
+  return keypair;
+}
+
+/** ``count`` funded keypairs, e.g. voters for a governance takeover. */
+export async function createAttackers(
+  connection: Connection,
+  count: number,
+  lamports: number = 10 * LAMPORTS_PER_SOL,
+  funder?: Keypair,
+): Promise<Keypair[]> {
+  return await Promise.all(Array.from({ length: count }, () => createAttacker(connection, lamports, funder)));
+}
+
+// ---------------------------------------------------------------------------
+// Account state
+// ---------------------------------------------------------------------------
+
+export async function lamports(connection: Connection, key: PublicKey): Promise<bigint> {
+  return BigInt((await connection.getAccountInfo(key))?.lamports ?? 0);
+}
+
+/** SPL token account amount: u64 at offset 64 (0 when the account does not exist). */
+export async function tokenAmount(connection: Connection, key: PublicKey): Promise<bigint> {
+  const info = await connection.getAccountInfo(key);
+  return info && info.data.length >= 72 ? info.data.readBigUInt64LE(64) : 0n;
+}
+
+// ---------------------------------------------------------------------------
+// SPL tokens
+// ---------------------------------------------------------------------------
+
+function u64(value: number | bigint): Buffer {
+  const data = Buffer.alloc(8);
+  data.writeBigUInt64LE(BigInt(value));
+  return data;
+}
+
+export function associatedTokenAddress(
+  mint: PublicKey,
+  owner: PublicKey,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+): PublicKey {
+  // PDA owners (vault authorities) are off the curve, which the derivation allows
+  return PublicKey.findProgramAddressSync(
+    [owner.toBuffer(), tokenProgram.toBuffer(), mint.toBuffer()],
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+  )[0];
+}
+
+/** Create a mint with ``authority`` as mint authority and no freeze authority. */
+export async function createMint(
+  connection: Connection,
+  payer: Keypair,
+  authority: PublicKey,
+  decimals: number = 6,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+): Promise<PublicKey> {
+  const mint = Keypair.generate();
+  const rent = await connection.getMinimumBalanceForRentExemption(MINT_SIZE);
+  // InitializeMint2: tag 20, decimals, mint authority, no freeze authority
+  const init = new TransactionInstruction({
+    programId: tokenProgram,
+    keys: [{ pubkey: mint.publicKey, isSigner: false, isWritable: true }],
+    data: Buffer.concat([Buffer.from([20, decimals]), authority.toBuffer(), Buffer.from([0])]),
+  });
+  await send(connection, [
+    SystemProgram.createAccount({
+      fromPubkey: payer.publicKey,
+      newAccountPubkey: mint.publicKey,
+      lamports: rent,
+      space: MINT_SIZE,
+      programId: tokenProgram,
+    }),
+    init,
+  ], [payer, mint]);
+  return mint.publicKey;
+}
+
+/** The owner's associated token account for ``mint``, created if it does not exist yet. */
+export async function ensureAta(
+  connection: Connection,
+  payer: Keypair,
+  mint: PublicKey,
+  owner: PublicKey,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+): Promise<PublicKey> {
+  const ata = associatedTokenAddress(mint, owner, tokenProgram);
+  if (await connection.getAccountInfo(ata)) return ata;
+  // CreateIdempotent: tag 1
+  await send(connection, [new TransactionInstruction({
+    programId: ASSOCIATED_TOKEN_PROGRAM_ID,
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+      { pubkey: ata, isSigner: false, isWritable: true },
+      { pubkey: owner, isSigner: false, isWritable: false },
+      { pubkey: mint, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: tokenProgram, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.from([1]),
+  })], [payer]);
+  return ata;
+}
+
+/** Mint ``amount`` base units to ``destination``; ``authority`` must be the mint authority. */
+export async function mintTo(
+  connection: Connection,
+  payer: Keypair,
+  mint: PublicKey,
+  destination: PublicKey,
+  authority: Keypair,
+  amount: number | bigint,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+): Promise<void> {
+  // MintTo: tag 7, amount
+  await send(connection, [new TransactionInstruction({
+    programId: tokenProgram,
+    keys: [
+      { pubkey: mint, isSigner: false, isWritable: true },
+      { pubkey: destination, isSigner: false, isWritable: true },
+      { pubkey: authority.publicKey, isSigner: true, isWritable: false },
+    ],
+    data: Buffer.concat([Buffer.from([7]), u64(amount)]),
+  })], [payer, ...(authority.publicKey.equals(payer.publicKey) ? [] : [authority])]);
+}
+
+/** An attacker-controlled mint and the owner's funded token account for it. */
+export async function fundedTokenAccount(
+  connection: Connection,
+  payer: Keypair,
+  owner: PublicKey,
+  amount: number | bigint,
+  decimals: number = 6,
+): Promise<{ mint: PublicKey; account: PublicKey }> {
+  const mint = await createMint(connection, payer, payer.publicKey, decimals);
+  const account = await ensureAta(connection, payer, mint, owner);
+  await mintTo(connection, payer, mint, account, payer, amount);
+  return { mint, account };
+}
+
+// ---------------------------------------------------------------------------
+// Clock
+// ---------------------------------------------------------------------------
+
+/** Target slot time; warps move unix_timestamp along with the slot at this rate. */
+export const SLOT_MS = 400;
+const MINIMUM_SLOTS_PER_EPOCH = 32n;
+
+/**
+ * A test runtime whose Clock sysvar can be set: a `LiteSVM` instance, or a
+ * solana-bankrun `ProgramTestContext`. solana-test-validator cannot move its
+ * clock after start (only `--warp-slot`), so time-dependent PoCs run on one
+ * of these.
+ */
+export interface ClockHost {
+  getClock?(): any;
+  getEpochSchedule?(): any;
+  banksClient?: { getClock(): Promise<any> };
+  setClock(clock: any): void;
+  warpToSlot(slot: bigint): void;
+}
+
+interface Schedule {
+  slotsPerEpoch: bigint;
+  warmup: boolean;
+  firstNormalEpoch: bigint;
+  firstNormalSlot: bigint;
+}
+
+// Mainnet's schedule, which both runtimes default to
+const DEFAULT_SCHEDULE: Schedule = {
+  slotsPerEpoch: 432000n, warmup: true, firstNormalEpoch: 14n, firstNormalSlot: 524256n,
+};
+
+function schedule(host: ClockHost): Schedule {
+  const s = host.getEpochSchedule?.();
+  return s ? { slotsPerEpoch: BigInt(s.slotsPerEpoch), warmup: s.warmup, firstNormalEpoch: BigInt(s.firstNormalEpoch),
+               firstNormalSlot: BigInt(s.firstNormalSlot) } : DEFAULT_SCHEDULE;
+}
+
+function epochOf(s: Schedule, slot: bigint): bigint {
+  if (s.warmup && slot < s.firstNormalSlot) {
+    // Warmup epoch n holds 32 * 2^n slots
+    return BigInt((slot + MINIMUM_SLOTS_PER_EPOCH).toString(2).length - MINIMUM_SLOTS_PER_EPOCH.toString(2).length);
+  }
+  return s.firstNormalEpoch + (slot - s.firstNormalSlot) / s.slotsPerEpoch;
+}
+
+function firstSlot(s: Schedule, epoch: bigint): bigint {
+  if (s.warmup && epoch <= s.firstNormalEpoch) return (2n ** epoch - 1n) * MINIMUM_SLOTS_PER_EPOCH;
+  return s.firstNormalSlot + (epoch - s.firstNormalEpoch) * s.slotsPerEpoch;
+}
+
+export async function getClock(host: ClockHost): Promise<any> {
+  return host.banksClient ? await host.banksClient.getClock() : host.getClock!();
+}
+
+async function setClock(host: ClockHost, fields: { slot?: bigint; unixTimestamp?: bigint }): Promise<any> {
+  const clock = await getClock(host);
+  const s = schedule(host);
+  const slot = fields.slot ?? clock.slot;
+  const unixTimestamp = fields.unixTimestamp ?? clock.unixTimestamp;
+  const epoch = epochOf(s, slot);
+  if (slot !== clock.slot) host.warpToSlot(slot);
+  // Both runtimes' Clock classes take (slot, epochStartTimestamp, epoch, leaderScheduleEpoch, unixTimestamp)
+  const next = new clock.constructor(
+    slot, epoch === clock.epoch ? clock.epochStartTimestamp : unixTimestamp, epoch, epoch + 1n, unixTimestamp);
+  host.setClock(next);
+  return next;
+}
+
+/** Advance ``slots`` slots; time follows at SLOT_MS a slot unless ``seconds`` is given. */
+export async function warpSlots(host: ClockHost, slots: number | bigint, seconds?: number | bigint): Promise<any> {
+  const clock = await getClock(host);
+  const elapsed = seconds === undefined ? (BigInt(slots) * BigInt(SLOT_MS)) / 1000n : BigInt(seconds);
+  return await setClock(host, { slot: clock.slot + BigInt(slots), unixTimestamp: clock.unixTimestamp + elapsed });
+}
+
+/** Let ``seconds`` pass (past a vesting cliff, a cooldown, an oracle's staleness window). */
+export async function warpSeconds(host: ClockHost, seconds: number | bigint): Promise<any> {
+  return await warpSlots(host, (BigInt(seconds) * 1000n) / BigInt(SLOT_MS), seconds);
+}
+
+/** Jump to the first slot of ``epoch``, e.g. to activate or deactivate stake. */
+export async function warpToEpoch(host: ClockHost, epoch: number | bigint): Promise<any> {
+  const clock = await getClock(host);
+  return await warpSlots(host, firstSlot(schedule(host), BigInt(epoch)) - clock.slot);
+}
+
+/** Set the time without moving the slot. */
+export async function setUnixTimestamp(host: ClockHost, unixTimestamp: number | bigint): Promise<any> {
+  return await setClock(host, { unixTimestamp: BigInt(unixTimestamp) });
+}
+
+// ---------------------------------------------------------------------------
+// Malicious programs
+// ---------------------------------------------------------------------------
+
+/**
+ * Deploy the program at ``soPath`` (e.g. the fake token program built with
+ * `cargo build-sbf`) with the `solana` CLI and return its id. ``programId``
+ * fixes the address; by default a fresh one is used. On a managed
+ * solana-test-validator, preloading it with `--bpf-program` is faster.
+ */
+export async function deployProgram(
+  connection: Connection,
+  payer: Keypair,
+  soPath: string,
+  programId: Keypair = Keypair.generate(),
+): Promise<PublicKey> {
+  const dir = mkdtempSync(join(tmpdir(), "baskerville-deploy-"));
+  try {
+    const payerFile = join(dir, "payer.json");
+    const programFile = join(dir, "program.json");
+    writeFileSync(payerFile, JSON.stringify(Array.from(payer.secretKey)));
+    writeFileSync(programFile, JSON.stringify(Array.from(programId.secretKey)));
+    execFileSync("solana", [
+      "program", "deploy", soPath,
+      "--program-id", programFile,
+      "--keypair", payerFile,
+      "--url", connection.rpcEndpoint,
+      "--commitment", "confirmed",
+    ], { stdio: "pipe" });
+  } finally {
+    rmSync(dir, { recursive: true, force: true });
+  }
+  return programId.publicKey;
+}
+
+/**
+ * Deploy the fake token program (see fake-token/) and return its id. It
+ * accepts every SPL Token instruction: transfers, mints and burns succeed
+ * without moving tokens, every signer it was handed is logged as
+ * `fake-token: signer <key>`, and when the real Token program is among its
+ * accounts a transfer is replayed there for the full balance of the source.
+ */
+export async function deployFakeTokenProgram(
+  connection: Connection,
+  payer: Keypair,
+  soPath: string = "fake-token/target/deploy/fake_token.so",
+): Promise<PublicKey> {
+  return await deployProgram(connection, payer, soPath);
+}
+
+/** Signers the fake token program received in the transaction ``signature``. */
+export async function fakeTokenSigners(connection: Connection, signature: string): Promise<PublicKey[]> {
+  const tx = await connection.getTransaction(signature, { commitment: "confirmed", maxSupportedTransactionVersion: 0 });
+  const logs = tx?.meta?.logMessages ?? [];
+  return logs
+    .map((line) => line.match(/fake-token: signer (\w+)/))
+    .filter((m): m is RegExpMatchArray => m !== null)
+    .map((m) => new PublicKey(m[1]));
+}
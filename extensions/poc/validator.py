@@ -0,0 +1,411 @@
+"""
+Managed `solana-test-validator` for running PoCs.
+
+``plan_clones`` turns the accounts a PoC touches (addresses in its source,
+Anchor.toml `[[test.validator.clone]]` entries, explicit lists) into clone
+flags: each address is looked up on the source cluster, upgradeable
+programs are cloned with `--clone-upgradeable-program`, other accounts
+(mints, oracles, config) with `--clone`, and the owner programs of cloned
+accounts are pulled in as well. Programs every test validator already
+ships, workspace programs (loaded from target/deploy instead) and
+addresses missing on the cluster are skipped with a reason.
+
+``TestValidator`` runs the validator on a freshly allocated block of ports
+with a throwaway ledger, waits until `getHealth` answers, and always
+tears it down: on context exit, at interpreter exit, on SIGTERM, and
+(on Linux) when the parent process dies.
+"""
+
+import atexit
+import os
+import random
+import re
+import shutil
+import signal
+import socket
+import subprocess
+import sys
+import tempfile
+import threading
+import time
+from collections.abc import Callable, Iterable
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+from extensions.ir.anchor_config import is_pubkey
+from extensions.rpc import RpcClient, RpcError, generic
+from extensions.rpc.providers import redact_url
+
+VALIDATOR_VERSION = "1.0.0"
+
+UPGRADEABLE_LOADER = "BPFLoaderUpgradeab1e11111111111111111111111"
+
+# Programs and sysvars present in every solana-test-validator genesis
+BUILTIN_ACCOUNTS = {
+    "11111111111111111111111111111111": "System Program",
+    "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA": "SPL Token",
+    "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb": "SPL Token-2022",
+    "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL": "Associated Token Account",
+    "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr": "SPL Memo",
+    "Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo": "SPL Memo (v1)",
+    "ComputeBudget111111111111111111111111111111": "Compute Budget",
+    "AddressLookupTab1e1111111111111111111111111": "Address Lookup Table",
+    "Stake11111111111111111111111111111111111111": "Stake Program",
+    "Vote111111111111111111111111111111111111111": "Vote Program",
+    "Config1111111111111111111111111111111111111": "Config Program",
+    "BPFLoader2111111111111111111111111111111111": "BPF Loader",
+    UPGRADEABLE_LOADER: "BPF Upgradeable Loader",
+    "Sysvar1nstructions1111111111111111111111111": "Instructions sysvar",
+    "SysvarC1ock11111111111111111111111111111111": "Clock sysvar",
+    "SysvarRent111111111111111111111111111111111": "Rent sysvar",
+    "SysvarEpochSchedu1e111111111111111111111111": "Epoch schedule sysvar",
+    "SysvarRecentB1ockHashes11111111111111111111": "Recent blockhashes sysvar",
+    "SysvarS1otHashes111111111111111111111111111": "Slot hashes sysvar",
+    "SysvarStakeHistory1111111111111111111111111": "Stake history sysvar",
+}
+
+class ValidatorError(RuntimeError):
+    """The validator could not be planned, started or reached."""
+
+
+def extract_addresses(text: str) -> list[str]:
+    """Public keys quoted in PoC source (TypeScript or Rust), in order of appearance."""
+    found = re.findall(r"[\"'`]([1-9A-HJ-NP-Za-km-z]{32,44})[\"'`]", text)
+    return list(dict.fromkeys(a for a in found if is_pubkey(a)))
+
+
+def fetch_accounts(source: "str | RpcClient") -> Callable[[list[str]], list[dict[str, Any] | None]]:
+    """Account lookup (owner and executable flag) against a provider spec, URL or client."""
+    client = source if isinstance(source, RpcClient) else RpcClient(source)
+
+    def fetch(addresses: list[str]) -> list[dict[str, Any] | None]:
+        try:
+            return client.get_multiple_accounts(addresses, data_slice={"offset": 0, "length": 0})
+        except RpcError as e:
+            raise ValidatorError(f"account lookup on {redact_url(client.url)} failed: {e}") from e
+
+    return fetch
+
+
+@dataclass
+class ClonePlan:
+    """What the validator loads: cloned accounts and programs, local programs and account files."""
+
+    url: str | None = None
+    # --clone: data accounts and non-upgradeable programs
+    accounts: list[str] = field(default_factory=list)
+    # --clone-upgradeable-program: programs owned by the upgradeable loader
+    upgradeable_programs: list[str] = field(default_factory=list)
+    # --bpf-program: address -> local .so
+    programs: dict[str, Path] = field(default_factory=dict)
+    # --account: address -> JSON account dump
+    account_files: dict[str, Path] = field(default_factory=dict)
+    # Address -> why it is not cloned
+    skipped: dict[str, str] = field(default_factory=dict)
+
+    @property
+    def clones(self) -> list[str]:
+        return self.accounts + self.upgradeable_programs
+
+    def args(self) -> list[str]:
+        """`solana-test-validator` flags for this plan."""
+        args: list[str] = []
+        if self.clones:
+            if not self.url:
+                raise ValidatorError("cloning needs a source cluster url")
+            args += ["--url", self.url]
+        for address in self.accounts:
+            args += ["--clone", address]
+        for address in self.upgradeable_programs:
+            args += ["--clone-upgradeable-program", address]
+        for address, path in self.programs.items():
+            args += ["--bpf-program", address, str(path)]
+        for address, path in self.account_files.items():
+            args += ["--account", address, str(path)]
+        return args
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "url": redact_url(self.url) if self.url else None,
+            "accounts": self.accounts,
+            "upgradeable_programs": self.upgradeable_programs,
+            "programs": {a: str(p) for a, p in self.programs.items()},
+            "account_files": {a: str(p) for a, p in self.account_files.items()},
+            "skipped": self.skipped,
+        }
+
+
+def plan_clones(addresses: Iterable[str], url: str | None = None, anchor: Any = None,
+                fetch: Callable[[list[str]], list[dict[str, Any] | None]] | None = None,
+                include_owners: bool = True, remote: bool = True) -> ClonePlan:
+    """Resolve the accounts a PoC needs into a clone plan.
+
+    Args:
+        addresses: Accounts the PoC touches (e.g. from ``extract_addresses``)
+        url: Cluster to clone from, as a URL or RPC provider spec such as `devnet:helius`
+            (default: Anchor.toml `[test.validator] url`)
+        anchor: Parsed Anchor.toml (``extensions.ir.anchor_config.AnchorConfig``) whose
+            built programs, genesis programs, clones and account dumps are added
+        fetch: Account lookup, defaults to ``fetch_accounts(url)``
+        include_owners: Also clone the owner programs of cloned data accounts
+        remote: Clone from the cluster; when False only built-in and workspace accounts are loaded
+    """
+    plan = ClonePlan(url=url)
+    pending = list(dict.fromkeys(addresses))
+    local: set[str] = set()
+    if anchor is not None:
+        plan.url = plan.url or anchor.validator.url
+        plan.programs.update(anchor.artifacts())
+        plan.account_files.update({
+            a["address"]: anchor.root / a["filename"]
+            for a in anchor.validator.accounts if a.get("address") and a.get("filename")
+        })
+        local = set(anchor.program_ids().values()) | set(plan.programs) | set(plan.account_files)
+        pending += [a for a in anchor.validator.clones if a not in pending]
+
+    seen: set[str] = set()
+    while pending:
+        batch = []
+        for address in dict.fromkeys(pending):
+            if address in seen:
+                continue
+            seen.add(address)
+            if address in BUILTIN_ACCOUNTS:
+                plan.skipped[address] = f"built in ({BUILTIN_ACCOUNTS[address]})"
+            elif address in local:
+                plan.skipped[address] = "loaded from the workspace"
+            else:
+                batch.append(address)
+        pending = []
+        if not batch:
+            break
+        if not remote:
+            plan.skipped.update({a: "not cloned (local run)" for a in batch})
+            break
+        if not plan.url:
+            raise ValidatorError(f"{len(batch)} accounts need cloning but no source cluster url is configured")
+        if fetch is None:
+            try:
+                client = RpcClient(plan.url)
+            except ValueError as e:
+                raise ValidatorError(str(e)) from e
+            # The validator needs the endpoint itself, not a provider spec
+            plan.url = client.url
+            fetch = fetch_accounts(client)
+        infos = fetch(batch)
+        for address, info in zip(batch, infos):
+            if info is None:
+                plan.skipped[address] = f"not found on {redact_url(plan.url)}"
+            elif info.get("executable") and info.get("owner") == UPGRADEABLE_LOADER:
+                plan.upgradeable_programs.append(address)
+            else:
+                plan.accounts.append(address)
+                if include_owners and not info.get("executable") and info.get("owner"):
+                    pending.append(info["owner"])
+    return plan
+
+
+def _bindable(port: int, host: str) -> bool:
+    for kind in (socket.SOCK_STREAM, socket.SOCK_DGRAM):
+        with socket.socket(socket.AF_INET, kind) as s:
+            try:
+                s.bind((host, port))
+            except OSError:
+                return False
+    return True
+
+
+def allocate_ports(dynamic: int = 25, host: str = "127.0.0.1", attempts: int = 100,
+                   rng: random.Random | None = None) -> dict[str, int]:
+    """A free contiguous block: RPC (and its websocket at +1), faucet, gossip and the dynamic range."""
+    rng = rng or random.Random()
+    size = 4 + dynamic
+    for _ in range(attempts):
+        base = rng.randrange(20_000, 60_000 - size)
+        if all(_bindable(port, host) for port in range(base, base + size)):
+            return {"rpc": base, "faucet": base + 2, "gossip": base + 3,
+                    "dynamic_start": base + 4, "dynamic_end": base + size - 1}
+    raise ValidatorError(f"no free block of {size} ports found")
+
+
+def _die_with_parent() -> None:
+    """Ask Linux to SIGTERM the validator if the process that started it dies."""
+    if sys.platform.startswith("linux"):
+        try:
+            import ctypes
+
+            ctypes.CDLL("libc.so.6", use_errno=True).prctl(1, signal.SIGTERM)  # PR_SET_PDEATHSIG
+        except (OSError, AttributeError):
+            pass
+
+
+class TestValidator:
+    """A `solana-test-validator` process with allocated ports and guaranteed teardown."""
+
+    __test__ = False  # not a pytest class
+
+    def __init__(self, plan: ClonePlan | None = None, binary: str = "solana-test-validator",
+                 ledger: Path | None = None, startup_timeout: float = 60.0, extra_args: Iterable[str] = (),
+                 log_file: Path | None = None, dynamic_ports: int = 25):
+        """Initialize the validator.
+
+        Args:
+            plan: Accounts and programs to load (see ``plan_clones``)
+            binary: Validator executable
+            ledger: Ledger directory (default: a temporary one, removed on stop)
+            startup_timeout: Seconds to wait for `getHealth` to report ok
+            extra_args: Further `solana-test-validator` flags
+            log_file: Where validator output goes (default: next to a temporary ledger)
+            dynamic_ports: Size of the dynamic port range
+        """
+        self.plan = plan or ClonePlan()
+        self.binary = binary
+        self.ledger = Path(ledger) if ledger else None
+        self.startup_timeout = startup_timeout
+        self.extra_args = list(extra_args)
+        self._log_arg = Path(log_file) if log_file else None
+        self.log_file = self._log_arg
+        self.dynamic_ports = dynamic_ports
+        self.ports: dict[str, int] = {}
+        self._proc: subprocess.Popen | None = None
+        self._log = None
+        self._workdir: Path | None = None
+        self._previous_sigterm: Any = None
+
+    @property
+    def rpc_url(self) -> str:
+        return f"http://127.0.0.1:{self.ports['rpc']}"
+
+    @property
+    def ws_url(self) -> str:
+        return f"ws://127.0.0.1:{self.ports['rpc'] + 1}"
+
+    @property
+    def running(self) -> bool:
+        return self._proc is not None and self._proc.poll() is None
+
+    def is_available(self) -> tuple[bool, str]:
+        """Check the validator binary is installed."""
+        binary = shutil.which(self.binary)
+        if not binary:
+            return False, f"{self.binary} not found (install the Solana CLI)"
+        try:
+            out = subprocess.run([binary, "--version"], capture_output=True, text=True, timeout=30)
+        except (OSError, subprocess.TimeoutExpired) as e:
+            return False, str(e)
+        return out.returncode == 0, out.stdout.strip() or out.stderr.strip()
+
+    def command(self) -> list[str]:
+        if not self.ports:
+            raise ValidatorError("ports are allocated on start")
+        ports = self.ports
+        return [
+            self.binary, "--reset", "--quiet",
+            "--ledger", str(self.ledger),
+            "--rpc-port", str(ports["rpc"]),
+            "--faucet-port", str(ports["faucet"]),
+            "--gossip-port", str(ports["gossip"]),
+            "--dynamic-port-range", f"{ports['dynamic_start']}-{ports['dynamic_end']}",
+            "--bind-address", "127.0.0.1",
+            *self.plan.args(),
+            *self.extra_args,
+        ]
+
+    def start(self) -> "TestValidator":
+        """Start the validator and wait until it is healthy."""
+        if self.running:
+            return self
+        binary = shutil.which(self.binary)
+        if not binary:
+            raise ValidatorError(f"{self.binary} not found (install the Solana CLI)")
+        self.ports = allocate_ports(self.dynamic_ports)
+        if self.ledger is None:
+            # `--reset` wipes the ledger, so the log lives next to it
+            self._workdir = Path(tempfile.mkdtemp(prefix="baskerville-validator-"))
+            self.ledger = self._workdir / "ledger"
+        log_path = self._log_arg or (self._workdir or self.ledger.parent) / "validator.log"
+        log_path.parent.mkdir(parents=True, exist_ok=True)
+        self._log = open(log_path, "w")
+        self.log_file = log_path
+        atexit.register(self.stop)
+        self._trap_sigterm()
+        try:
+            self._proc = subprocess.Popen(
+                [binary, *self.command()[1:]], stdout=self._log, stderr=subprocess.STDOUT,
+                stdin=subprocess.DEVNULL, start_new_session=True, preexec_fn=_die_with_parent,
+            )
+            self.wait_ready()
+        except BaseException:
+            self.stop()
+            raise
+        return self
+
+    def wait_ready(self) -> None:
+        """Poll `getHealth` until it reports ok; fail if the process exits or the timeout passes."""
+        health = RpcClient(generic(self.rpc_url), cache=False, retries=0, timeout=2)
+        deadline = time.monotonic() + self.startup_timeout
+        while time.monotonic() < deadline:
+            if not self.running:
+                raise ValidatorError(f"validator exited during startup:\n{self.log_tail()}")
+            try:
+                if health.get_health() == "ok":
+                    return
+            except RpcError:
+                pass
+            time.sleep(0.25)
+        raise ValidatorError(f"validator not healthy after {self.startup_timeout:g}s:\n{self.log_tail()}")
+
+    def wait(self) -> int | None:
+        """Block until the validator exits; returns its exit code."""
+        return self._proc.wait() if self._proc else None
+
+    def log_tail(self, lines: int = 20) -> str:
+        if not self.log_file or not self.log_file.exists():
+            return ""
+        return "\n".join(self.log_file.read_text(errors="replace").splitlines()[-lines:])
+
+    def stop(self, grace: float = 10.0) -> None:
+        """Terminate the validator's process group, then remove the temporary ledger. Safe to call twice."""
+        proc, self._proc = self._proc, None
+        if proc is not None and proc.poll() is None:
+            self._signal(proc, signal.SIGTERM)
+            try:
+                proc.wait(timeout=grace)
+            except subprocess.TimeoutExpired:
+                self._signal(proc, signal.SIGKILL)
+                proc.wait()
+        if self._log:
+            self._log.close()
+            self._log = None
+        if self._workdir:
+            shutil.rmtree(self._workdir, ignore_errors=True)
+            self.ledger = self._workdir = None
+        atexit.unregister(self.stop)
+        self._restore_sigterm()
+
+    @staticmethod
+    def _signal(proc: subprocess.Popen, sig: int) -> None:
+        try:
+            os.killpg(proc.pid, sig)
+        except (ProcessLookupError, PermissionError):
+            pass
+
+    def _trap_sigterm(self) -> None:
+        # Turn SIGTERM into SystemExit so context managers and atexit still run
+        if threading.current_thread() is not threading.main_thread():
+            return
+        if signal.getsignal(signal.SIGTERM) is signal.SIG_DFL:
+            self._previous_sigterm = signal.SIG_DFL
+            signal.signal(signal.SIGTERM, lambda signum, frame: sys.exit(128 + signum))
+
+    def _restore_sigterm(self) -> None:
+        if self._previous_sigterm is not None and threading.current_thread() is threading.main_thread():
+            signal.signal(signal.SIGTERM, self._previous_sigterm)
+            self._previous_sigterm = None
+
+    def __enter__(self) -> "TestValidator":
+        return self.start()
+
+    def __exit__(self, *exc: Any) -> None:
+        self.stop()
@@ -0,0 +1,36 @@
+"""
+Precedent from published audit reports.
+
+Cross-references detector hits and hypotheses with findings from public
+audits, for precedent and severity calibration:
+
+- Catalog: report metadata (firm, protocol, chain, findings with their
+  taxonomy classes), imported from YAML/JSON, published Markdown reports
+  or Solodit search results
+- Matcher: scores hypotheses against every imported finding and suggests a
+  severity from the matches
+"""
+
+from .catalog import (
+    AuditReport,
+    PrecedentCatalog,
+    PublishedFinding,
+    load_report_file,
+    normalize_severity,
+    parse_markdown_report,
+    reports_from_solodit,
+)
+from .matcher import PrecedentMatch, PrecedentMatcher, SeverityCalibration
+
+__all__ = [
+    "AuditReport",
+    "PrecedentCatalog",
+    "PrecedentMatch",
+    "PrecedentMatcher",
+    "PublishedFinding",
+    "SeverityCalibration",
+    "load_report_file",
+    "normalize_severity",
+    "parse_markdown_report",
+    "reports_from_solodit",
+]
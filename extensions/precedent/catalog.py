@@ -0,0 +1,213 @@
+"""
+Published audit report catalog.
+
+Stores metadata of public audit reports (firm, protocol, chain and each
+finding's id, title, severity and taxonomy classes) so detector hits and
+hypotheses can be matched against real precedent. Reports are imported
+from a metadata file (YAML/JSON), from the Markdown of a published report,
+or from Solodit search results, and are kept one JSON file per report.
+"""
+
+import json
+import re
+from collections.abc import Iterable
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Any
+
+import yaml
+
+from ..knowledge.taxonomy import classify
+
+SEVERITIES = ["critical", "high", "medium", "low", "info"]
+
+_SEVERITY_ALIASES = {
+    "c": "critical", "crit": "critical", "critical": "critical",
+    "h": "high", "high": "high",
+    "m": "medium", "med": "medium", "medium": "medium",
+    "l": "low", "low": "low",
+    "i": "info", "info": "info", "informational": "info", "n": "info", "nc": "info",
+    "g": "info", "gas": "info", "qa": "info",
+}
+
+
+def normalize_severity(value: str) -> str:
+    """Map report severity labels (``H``, ``High``, ``Informational``, ...) onto Hound's levels."""
+    severity = _SEVERITY_ALIASES.get(str(value).strip().lower())
+    if severity is None:
+        raise ValueError(f"unknown severity {value!r}")
+    return severity
+
+
+def _slug(text: str) -> str:
+    return re.sub(r"[^a-z0-9]+", "-", text.lower()).strip("-") or "report"
+
+
+@dataclass
+class PublishedFinding:
+    """A finding from a published audit report."""
+    id: str  # As numbered in the report, e.g. "M-03"
+    title: str
+    severity: str
+    classes: list[str] = field(default_factory=list)  # Taxonomy tags
+    detectors: list[str] = field(default_factory=list)  # Detector names known to flag this pattern
+    description: str = ""
+    url: str | None = None
+
+    def __post_init__(self):
+        self.severity = normalize_severity(self.severity)
+        if not self.classes:
+            self.classes = classify(texts=[self.title], fallback_texts=[self.description])
+
+
+@dataclass
+class AuditReport:
+    """Metadata of a published audit report."""
+    firm: str
+    protocol: str
+    findings: list[PublishedFinding]
+    chain: str | None = None
+    date: str | None = None
+    url: str | None = None
+
+    @property
+    def key(self) -> str:
+        return f"{_slug(self.firm)}--{_slug(self.protocol)}" + (f"--{self.date}" if self.date else "")
+
+    def to_dict(self) -> dict[str, Any]:
+        return asdict(self)
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "AuditReport":
+        for key in ("firm", "protocol"):
+            if not data.get(key):
+                raise ValueError(f"report metadata needs `{key}`")
+        findings = []
+        for i, item in enumerate(data.get("findings") or []):
+            if not item.get("id") or not item.get("title") or not item.get("severity"):
+                raise ValueError(f"finding #{i + 1} needs `id`, `title` and `severity`")
+            findings.append(PublishedFinding(
+                id=str(item["id"]),
+                title=item["title"],
+                severity=item["severity"],
+                classes=list(item.get("classes") or []),
+                detectors=list(item.get("detectors") or []),
+                description=item.get("description", ""),
+                url=item.get("url"),
+            ))
+        date = data.get("date")
+        return cls(firm=data["firm"], protocol=data["protocol"], findings=findings, chain=data.get("chain"),
+                   date=str(date) if date else None, url=data.get("url"))
+
+
+# e.g. "## [M-03] Title", "### H-1: Title", "#### [L-02]: Title", "## M01 - Title"
+_HEADING = re.compile(
+    r"^#{1,6}\s*\[?(?P<sev>[CHMLIG]|QA|NC)-?(?P<num>\d{1,3})\]?\s*[:.\-–—)]?\s*(?P<title>.+?)\s*$",
+    re.IGNORECASE | re.MULTILINE,
+)
+
+
+def parse_markdown_report(text: str, firm: str, protocol: str, chain: str | None = None, date: str | None = None,
+                          url: str | None = None) -> AuditReport:
+    """Findings of a published Markdown report, read from its ``[M-03] Title`` style headings."""
+    matches = list(_HEADING.finditer(text))
+    findings = []
+    seen = set()
+    for i, m in enumerate(matches):
+        sev = m["sev"].upper()
+        finding_id = f"{sev}-{int(m['num']):02d}"
+        if finding_id in seen:  # Table of contents entries repeat the headings
+            continue
+        seen.add(finding_id)
+        end = matches[i + 1].start() if i + 1 < len(matches) else len(text)
+        body = text[m.end():end].strip()
+        description = " ".join(body.split())[:600]
+        findings.append(PublishedFinding(id=finding_id, title=m["title"].strip("*` "), severity=sev,
+                                         description=description))
+    if not findings:
+        raise ValueError("no findings found (expected headings like `## [M-03] Title`)")
+    return AuditReport(firm=firm, protocol=protocol, findings=findings, chain=chain, date=date, url=url)
+
+
+def reports_from_solodit(findings: Iterable[Any], chain: str | None = None) -> list[AuditReport]:
+    """Group Solodit findings into one report per firm and protocol."""
+    reports: dict[tuple[str, str], AuditReport] = {}
+    counters: dict[tuple[str, str, str], int] = {}
+    for f in findings:
+        key = (f.firm_name or "Unknown firm", f.protocol_name or "Unknown protocol")
+        report = reports.setdefault(key, AuditReport(firm=key[0], protocol=key[1], findings=[], chain=chain,
+                                                     date=(f.report_date or "")[:10] or None))
+        severity = normalize_severity(f.impact)
+        counters[(*key, severity)] = counters.get((*key, severity), 0) + 1
+        report.findings.append(PublishedFinding(
+            id=f"{severity[0].upper()}-{counters[(*key, severity)]:02d}",
+            title=f.title,
+            severity=severity,
+            classes=classify(f.tags, [f.title], [f.content]),
+            description=" ".join((f.content or "").split())[:600],
+            url=f.url,
+        ))
+    return list(reports.values())
+
+
+def load_report_file(path: Path, **metadata: Any) -> AuditReport:
+    """Report from a YAML/JSON metadata file, or a Markdown report plus ``firm``/``protocol`` metadata."""
+    path = Path(path)
+    text = path.read_text()
+    if path.suffix.lower() in (".md", ".markdown"):
+        for key in ("firm", "protocol"):
+            if not metadata.get(key):
+                raise ValueError(f"importing a Markdown report needs `{key}`")
+        return parse_markdown_report(text, **metadata)
+    data = json.loads(text) if path.suffix.lower() == ".json" else yaml.safe_load(text)
+    if not isinstance(data, dict):
+        raise ValueError(f"{path} is not a report metadata mapping")
+    data.update({k: v for k, v in metadata.items() if v})
+    return AuditReport.from_dict(data)
+
+
+class PrecedentCatalog:
+    """Imported audit reports, one JSON file per report."""
+
+    def __init__(self, base_dir: Path | None = None):
+        """Initialize catalog.
+
+        Args:
+            base_dir: Directory holding the reports. Defaults to ~/.hound/precedents
+        """
+        if base_dir is None:
+            base_dir = Path.home() / ".hound" / "precedents"
+        self.base_dir = Path(base_dir)
+        self._reports: list[AuditReport] | None = None
+
+    def add(self, report: AuditReport) -> Path:
+        """Store a report, replacing an earlier import of the same firm, protocol and date."""
+        self.base_dir.mkdir(parents=True, exist_ok=True)
+        path = self.base_dir / f"{report.key}.json"
+        path.write_text(json.dumps(report.to_dict(), indent=2))
+        self._reports = None
+        return path
+
+    def remove(self, key: str) -> bool:
+        path = self.base_dir / f"{key}.json"
+        if not path.exists():
+            return False
+        path.unlink()
+        self._reports = None
+        return True
+
+    def reports(self) -> list[AuditReport]:
+        if self._reports is None:
+            self._reports = []
+            if self.base_dir.exists():
+                for path in sorted(self.base_dir.glob("*.json")):
+                    try:
+                        self._reports.append(AuditReport.from_dict(json.loads(path.read_text())))
+                    except (ValueError, TypeError, json.JSONDecodeError) as e:
+                        print(f"[!] Skipping invalid precedent report {path}: {e}")
+        return self._reports
+
+    def findings(self) -> Iterable[tuple[AuditReport, PublishedFinding]]:
+        for report in self.reports():
+            for finding in report.findings:
+                yield report, finding
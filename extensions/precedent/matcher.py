@@ -0,0 +1,145 @@
+"""
+Precedent matching.
+
+Scores a hypothesis or detector hit against every imported published finding
+by taxonomy class overlap, shared title terms and known detector names, and
+summarizes the best matches as "this pattern matches finding M-03 from
+<firm>'s audit of <protocol>" together with a severity calibration.
+"""
+
+import re
+from dataclasses import dataclass, field
+from typing import Any
+
+from ..knowledge.taxonomy import FALLBACK_TAG, hypothesis_tags
+from .catalog import SEVERITIES, AuditReport, PrecedentCatalog, PublishedFinding, normalize_severity
+
+_STOPWORDS = {
+    "the", "and", "for", "can", "be", "are", "not", "via", "with", "from", "into", "that", "this", "when", "may",
+    "does", "due", "lack", "missing", "incorrect", "improper", "allows", "allow", "could", "lead", "leads", "user",
+    "users", "function", "functions", "contract", "program", "protocol", "issue", "check", "checks", "which",
+}
+
+# Matches on other chains still count as precedent, just less strongly
+CROSS_CHAIN_FACTOR = 0.8
+
+
+def _terms(*texts: str) -> set[str]:
+    words = set()
+    for text in texts:
+        for word in re.findall(r"[a-z][a-z0-9]+", re.sub(r"([a-z])([A-Z])", r"\1 \2", text or "").lower()):
+            if len(word) > 2 and word not in _STOPWORDS:
+                words.add(word.rstrip("s") if len(word) > 4 else word)
+    return words
+
+
+@dataclass
+class PrecedentMatch:
+    """A published finding matching a hypothesis."""
+    report: AuditReport
+    finding: PublishedFinding
+    score: float
+    reasons: list[str] = field(default_factory=list)
+
+    @property
+    def summary(self) -> str:
+        return (f"matches finding {self.finding.id} from {self.report.firm}'s audit of {self.report.protocol}"
+                f" ({self.finding.severity})")
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "firm": self.report.firm,
+            "protocol": self.report.protocol,
+            "chain": self.report.chain,
+            "finding_id": self.finding.id,
+            "title": self.finding.title,
+            "severity": self.finding.severity,
+            "url": self.finding.url or self.report.url,
+            "score": round(self.score, 3),
+            "reasons": self.reasons,
+        }
+
+
+@dataclass
+class SeverityCalibration:
+    """Severity of matching precedent, weighted by match score."""
+    suggested: str | None
+    weights: dict[str, float]
+    support: int  # Number of matches behind the suggestion
+
+    def differs_from(self, severity: str) -> bool:
+        try:
+            return self.suggested is not None and normalize_severity(severity) != self.suggested
+        except ValueError:
+            return False
+
+    def describe(self) -> str:
+        if self.suggested is None:
+            return "no precedent"
+        spread = ", ".join(f"{w:.1f} {sev}" for sev, w in self.weights.items() if w)
+        return f"{self.suggested} ({self.support} precedent{'s' if self.support != 1 else ''}: {spread})"
+
+
+class PrecedentMatcher:
+    """Match hypotheses against published findings."""
+
+    def __init__(self, catalog: PrecedentCatalog, min_score: float = 0.3):
+        self.catalog = catalog
+        self.min_score = min_score
+
+    def _score(self, hyp: dict[str, Any], chain: str | None, report: AuditReport,
+               finding: PublishedFinding) -> tuple[float, list[str]]:
+        reasons = []
+        classes = set(hypothesis_tags(hyp))
+        shared_classes = classes & set(finding.classes)
+        class_score = len(shared_classes) / len(classes | set(finding.classes)) if classes else 0.0
+        if shared_classes:
+            reasons.append(f"class {', '.join(sorted(shared_classes))}")
+
+        kind = str(hyp.get("vulnerability_type", ""))
+        hyp_terms = _terms(hyp.get("title", ""), kind)
+        shared_terms = hyp_terms & _terms(finding.title)
+        text_score = 2 * len(shared_terms) / (len(hyp_terms) + len(_terms(finding.title))) if shared_terms else 0.0
+        if shared_terms:
+            reasons.append(f"terms {', '.join(sorted(shared_terms))}")
+
+        detector_bonus = 0.0
+        if kind and kind.lower() in {d.lower() for d in finding.detectors}:
+            detector_bonus = 0.3
+            reasons.append(f"detector {kind}")
+
+        # Sharing only the catch-all class is no precedent on its own
+        if not shared_classes - {FALLBACK_TAG} and not detector_bonus:
+            return 0.0, []
+        score = min(1.0, 0.5 * class_score + 0.5 * text_score + detector_bonus)
+        if chain and report.chain and chain.lower() != report.chain.lower():
+            score *= CROSS_CHAIN_FACTOR
+            reasons.append(f"on {report.chain}")
+        return score, reasons
+
+    def match(self, hyp: dict[str, Any], chain: str | None = None, limit: int = 3) -> list[PrecedentMatch]:
+        """Best-scoring published findings for a hypothesis dict, best first.
+
+        Args:
+            hyp: Hypothesis or static-analysis hit (title, vulnerability_type, description)
+            chain: Chain of the audited code; precedent from other chains scores lower
+            limit: Maximum matches returned
+        """
+        matches = []
+        for report, finding in self.catalog.findings():
+            score, reasons = self._score(hyp, chain, report, finding)
+            if score >= self.min_score:
+                matches.append(PrecedentMatch(report, finding, score, reasons))
+        matches.sort(key=lambda m: (-m.score, SEVERITIES.index(m.finding.severity)))
+        return matches[:limit]
+
+    @staticmethod
+    def calibrate(matches: list[PrecedentMatch]) -> SeverityCalibration:
+        """Score-weighted severity of the matches; ties go to the more severe level."""
+        weights = {sev: 0.0 for sev in SEVERITIES}
+        for m in matches:
+            weights[m.finding.severity] += m.score
+        if not matches:
+            return SeverityCalibration(None, weights, 0)
+        suggested = max(SEVERITIES, key=lambda sev: (weights[sev], -SEVERITIES.index(sev)))
+        return SeverityCalibration(suggested, weights, len(matches))
@@ -0,0 +1,91 @@
+"""
+Report data exports.
+
+Aggregations over a project's findings that feed dashboards and reports:
+- Risk heat-map: per-file/per-module finding density for treemaps
+- Signing: detached ed25519 signatures over delivered report files
+- Manifest: reproducibility manifest embedded in every output
+- Economic risk: liquidation sweep charts and tables for the report
+- Codebase metrics: program size, attack surface and review priority
+- Risk scores: per-program aggregate score under a configurable model
+- Engagement summary: scans, findings, PoCs and checklist coverage over a window
+- Engagement archives: one checksummed file with a project's findings, triage,
+  PoCs, reports, bounty contests and manifest, for hand-over and retention
+- Annotations: ``@baskerville`` notes from source comments, kept as an audit
+  trail linked to the findings at the same code
+- SARIF: detector hits as SARIF 2.1.0 for GitHub code scanning and other dashboards
+- GitHub: findings posted as check-run annotations or pull request review comments,
+  with their suggested fixes
+- Protocol parameters: appendix of hardcoded parameters against their sane
+  ranges, for client confirmation
+- Findings reports: pluggable formatters (JSON, Markdown, SARIF) with each finding's
+  PoC template snippet and remediation
+- Severity scores: CVSS-like 0-10 scores from a finding's impact, privileges and
+  complexity under a configurable rubric, so reports rank findings consistently
+- Attack trees: findings composed under the attacker goals they serve, with chains
+  of findings that only reach a goal together, as Mermaid and a report section
+"""
+
+from .annotations import embed_annotations, ingest_annotations, load_annotations, scan_annotations
+from .archive import ArchiveError, export_engagement, import_engagement, read_archive
+from .attack_trees import build_attack_trees, embed_attack_trees, render_attack_trees
+from .economic import embed_economic_risk, load_sweeps
+from .engagement import build_engagement_summary, render_methodology
+from .formatters import FORMATTERS, build_findings_report, get_formatter, register_formatter
+from .github import GitHubClient, GitHubContext, GitHubError
+from .heatmap import HeatMapBuilder, build_heatmap
+from .manifest import build_manifest, embed_manifest, extract_manifest
+from .metrics import embed_metrics, load_metrics
+from .parameters import embed_parameters, load_parameters
+from .risk import RiskModel, build_risk_report, load_risk_model, score_project
+from .sarif import build_sarif, write_sarif
+from .scoring import SeverityRubric, SeverityScore, load_severity_rubric
+from .signing import SigningError, VerificationResult, generate_keypair, sign_files, verify_envelope
+
+__all__ = [
+    "embed_annotations",
+    "ingest_annotations",
+    "load_annotations",
+    "scan_annotations",
+    "ArchiveError",
+    "export_engagement",
+    "import_engagement",
+    "read_archive",
+    "build_attack_trees",
+    "embed_attack_trees",
+    "render_attack_trees",
+    "embed_economic_risk",
+    "load_sweeps",
+    "build_engagement_summary",
+    "render_methodology",
+    "FORMATTERS",
+    "build_findings_report",
+    "get_formatter",
+    "register_formatter",
+    "GitHubClient",
+    "GitHubContext",
+    "GitHubError",
+    "HeatMapBuilder",
+    "build_heatmap",
+    "build_manifest",
+    "embed_manifest",
+    "extract_manifest",
+    "embed_metrics",
+    "load_metrics",
+    "embed_parameters",
+    "load_parameters",
+    "RiskModel",
+    "build_risk_report",
+    "load_risk_model",
+    "score_project",
+    "build_sarif",
+    "write_sarif",
+    "SeverityRubric",
+    "SeverityScore",
+    "load_severity_rubric",
+    "SigningError",
+    "VerificationResult",
+    "generate_keypair",
+    "sign_files",
+    "verify_envelope",
+]
@@ -0,0 +1,262 @@
+"""
+Auditor annotations from source comments.
+
+Auditors leave structured notes in the code under review:
+
+    // @baskerville note: reviewed, invariant held by the vault's has_one
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+
+    require(msg.sender == owner); // @baskerville reviewed(alice): owner is immutable
+
+The kind is any word (``note``, ``reviewed``, ``todo``, ``question``...),
+optionally followed by the author in parentheses. A note on a line of its
+own annotates the next line of code; a trailing note annotates its own line.
+
+Ingesting a source tree updates the project's ``annotations.json``: new
+notes are added, ones still present get their position refreshed and ones
+deleted from the source are kept with ``removed_at``, so the file is an
+audit trail rather than a snapshot. Each note is linked to the findings
+whose affected lines fall within a few lines of the code it annotates, and
+the report gets an "Audit Trail" section listing them.
+"""
+
+import hashlib
+import html
+import json
+import re
+from datetime import datetime
+from pathlib import Path
+from typing import Any
+
+from .economic import insert_before_findings
+from .heatmap import discover_source_files, finding_files
+
+ANNOTATIONS_FORMAT = "baskerville-annotations"
+ANNOTATIONS_FILE = "annotations.json"
+# A finding's affected line this close to an annotated line is the same code
+ATTACH_WINDOW = 3
+
+# Comment markers by file type; everything else uses // and /* */
+_MARKERS = {".py": ("#",), ".vy": ("#",), ".fc": (";;",), ".func": (";;",)}
+_C_MARKERS = ("//", "/*", "*")
+
+
+def _patterns(file: str) -> tuple[re.Pattern, re.Pattern]:
+    markers = _MARKERS.get(Path(file).suffix, _C_MARKERS)
+    marker = "|".join(re.escape(m) + ("+" if m in ("//", "/*") else "") for m in markers)
+    annotation = re.compile(
+        rf"(?:{marker})\s*@baskerville\s+(?P<kind>[A-Za-z][\w-]*)"
+        r"(?:\((?P<author>[^)]*)\))?\s*:\s*(?P<text>.*?)\s*(?:\*/)?\s*$"
+    )
+    # A lone "*" only opens a comment line if followed by space, "/" or nothing ("*ptr = x" is code)
+    comment = re.compile(r"^\s*(?:" + "|".join(r"\*(?=\s|/|$)" if m == "*" else re.escape(m) for m in markers) + ")")
+    return annotation, comment
+
+
+def _annotation_id(file: str, kind: str, text: str, occurrence: int) -> str:
+    key = f"{file}|{kind}|{text}|{occurrence}"
+    return f"note_{hashlib.md5(key.encode()).hexdigest()[:8]}"
+
+
+def parse_annotations(source: str, file: str) -> list[dict[str, Any]]:
+    """Annotations in one file's source, each with the line of code it annotates."""
+    annotation_re, comment_re = _patterns(file)
+    found, pending, seen = [], [], {}
+    for number, line in enumerate(source.splitlines(), 1):
+        match = annotation_re.search(line)
+        if match:
+            kind, text = match["kind"].lower(), match["text"]
+            seen[(kind, text)] = seen.get((kind, text), 0) + 1
+            annotation = {
+                "id": _annotation_id(file, kind, text, seen[(kind, text)]),
+                "kind": kind,
+                "text": text,
+                "author": (match["author"] or "").strip() or None,
+                "file": file,
+                "line": number,
+                "target_line": None,
+                "code": None,
+            }
+            found.append(annotation)
+            code = line[:match.start()].strip()
+            if code and not comment_re.match(line):
+                annotation.update(target_line=number, code=code)
+            else:
+                pending.append(annotation)
+            continue
+        if pending and line.strip() and not comment_re.match(line):
+            for annotation in pending:
+                annotation.update(target_line=number, code=line.strip())
+            pending = []
+    return found
+
+
+def scan_annotations(root: Path) -> list[dict[str, Any]]:
+    """Annotations in every source file under ``root`` (or in ``root`` itself)."""
+    root = Path(root)
+    if root.is_file():
+        return parse_annotations(root.read_text(errors="replace"), root.name)
+    found = []
+    for rel in discover_source_files(root):
+        try:
+            source = (root / rel).read_text(errors="replace")
+        except OSError:
+            continue
+        if "@baskerville" in source:
+            found += parse_annotations(source, rel)
+    return found
+
+
+def _near(annotation: dict[str, Any], lines: list[Any]) -> bool:
+    target = annotation.get("target_line") or annotation["line"]
+    for line in lines:
+        try:
+            if abs(int(line) - target) <= ATTACH_WINDOW:
+                return True
+        except (TypeError, ValueError):
+            continue
+    return False
+
+
+def attach_findings(annotations: list[dict[str, Any]], findings: dict[str, dict] | list[dict],
+                    root: Path | None = None) -> dict[str, list[str]]:
+    """Link annotations to findings at the same code; returns finding id -> annotation ids."""
+    items = findings.items() if isinstance(findings, dict) else ((f.get("id"), f) for f in findings)
+    links: dict[str, list[str]] = {}
+    for annotation in annotations:
+        annotation["findings"] = []
+    for finding_id, finding in items:
+        if not finding_id:
+            continue
+        files = finding_files(finding, root)
+        lines = (finding.get("properties") or {}).get("affected_lines") or []
+        for annotation in annotations:
+            if annotation.get("removed_at") or annotation["file"] not in files or not _near(annotation, lines):
+                continue
+            annotation["findings"].append(finding_id)
+            links.setdefault(finding_id, []).append(annotation["id"])
+    return links
+
+
+def load_annotations(project_dir: Path | None) -> list[dict[str, Any]]:
+    """The annotations saved in the project directory (current and removed)."""
+    if project_dir is None:
+        return []
+    try:
+        data = json.loads((Path(project_dir) / ANNOTATIONS_FILE).read_text())
+    except (OSError, ValueError):
+        return []
+    if not isinstance(data, dict) or data.get("format") != ANNOTATIONS_FORMAT:
+        return []
+    return [a for a in data.get("annotations", []) if isinstance(a, dict)]
+
+
+def ingest_annotations(project_dir: Path, source: Path, now: datetime | None = None) -> dict[str, Any]:
+    """Scan ``source``, merge into the project's annotations and link them to its findings.
+
+    Linked annotation ids are also recorded on the findings in
+    ``hypotheses.json`` (``properties.annotations``).
+
+    Returns:
+        Counts of added, updated and removed annotations, plus the full list
+    """
+    project_dir = Path(project_dir)
+    stamp = (now or datetime.now()).isoformat()
+    previous = {a["id"]: a for a in load_annotations(project_dir)}
+    current = scan_annotations(source)
+    counts = {"added": 0, "updated": 0, "removed": 0}
+
+    merged = []
+    for annotation in current:
+        before = previous.pop(annotation["id"], None)
+        if before is None:
+            counts["added"] += 1
+            merged.append({**annotation, "first_seen": stamp, "last_seen": stamp, "removed_at": None})
+            continue
+        if before.get("removed_at") or (before["line"], before.get("code")) != (annotation["line"], annotation["code"]):
+            counts["updated"] += 1
+        merged.append({**annotation, "first_seen": before.get("first_seen", stamp), "last_seen": stamp,
+                       "removed_at": None})
+    for annotation in previous.values():
+        if not annotation.get("removed_at"):
+            counts["removed"] += 1
+            annotation["removed_at"] = stamp
+        merged.append(annotation)
+
+    hyp_file = project_dir / "hypotheses.json"
+    store = json.loads(hyp_file.read_text()) if hyp_file.exists() else {}
+    hypotheses = store.get("hypotheses", {})
+    static = project_dir / "static_analysis" / "static_hypotheses.json"
+    static_hits = json.loads(static.read_text()) if static.exists() else []
+    source_root = Path(source) if Path(source).is_dir() else None
+    links = attach_findings(merged, {**{h.get("id"): h for h in static_hits if isinstance(h, dict)}, **hypotheses},
+                            source_root)
+    changed = False
+    for finding_id, hyp in hypotheses.items():
+        props = hyp.setdefault("properties", {})
+        ids = links.get(finding_id, [])
+        if props.get("annotations", []) != ids:
+            props["annotations"] = ids
+            changed = True
+    if changed:
+        hyp_file.write_text(json.dumps(store, indent=2))
+
+    (project_dir / ANNOTATIONS_FILE).write_text(json.dumps({
+        "format": ANNOTATIONS_FORMAT,
+        "source": str(source),
+        "ingested_at": stamp,
+        "annotations": merged,
+    }, indent=2))
+    return {**counts, "annotations": merged}
+
+
+def _location(annotation: dict[str, Any]) -> str:
+    return f"{annotation['file']}:{annotation.get('target_line') or annotation['line']}"
+
+
+def _rows(annotations: list[dict[str, Any]]) -> list[list[str]]:
+    current = sorted((a for a in annotations if not a.get("removed_at")),
+                     key=lambda a: (a["file"], a["line"]))
+    return [[_location(a), a["kind"], a["text"], a.get("author") or "-", ", ".join(a.get("findings") or []) or "-"]
+            for a in current]
+
+
+_HEADERS = ["Location", "Kind", "Note", "Author", "Findings"]
+
+
+def annotations_markdown(annotations: list[dict[str, Any]]) -> str:
+    def cell(text: str) -> str:
+        return text.replace("|", "\\|")
+
+    lines = ["## Audit Trail", "", "Notes auditors left in the source, with the findings at the same code.", ""]
+    lines.append("| " + " | ".join(_HEADERS) + " |")
+    lines.append("|---" * len(_HEADERS) + "|")
+    lines += ["| " + " | ".join(cell(c) for c in row) + " |" for row in _rows(annotations)]
+    return "\n".join(lines) + "\n"
+
+
+def annotations_html(annotations: list[dict[str, Any]]) -> str:
+    header = "".join(f"<th>{name}</th>" for name in _HEADERS)
+    body = "".join("<tr>" + "".join(f"<td>{html.escape(cell)}</td>" for cell in row) + "</tr>"
+                   for row in _rows(annotations))
+    return "\n".join([
+        '<div class="section">', "<h2>Audit Trail</h2>",
+        "<p>Notes auditors left in the source, with the findings at the same code.</p>",
+        f"<table><thead><tr>{header}</tr></thead><tbody>{body}</tbody></table>", "</div>",
+    ]) + "\n"
+
+
+def annotations_section(annotations: list[dict[str, Any]], fmt: str) -> str:
+    """The whole "Audit Trail" section in ``fmt`` (html or markdown)."""
+    if fmt == "html":
+        return annotations_html(annotations)
+    if fmt in ("markdown", "md"):
+        return annotations_markdown(annotations) + "\n"
+    raise ValueError(f"Cannot render an audit-trail section in format: {fmt}")
+
+
+def embed_annotations(content: str, annotations: list[dict[str, Any]], fmt: str) -> str:
+    """Insert the audit-trail section ahead of the findings of a rendered report."""
+    if not any(not a.get("removed_at") for a in annotations):
+        return content
+    return insert_before_findings(content, annotations_section(annotations, fmt), fmt)
@@ -0,0 +1,310 @@
+"""
+Engagement archives.
+
+One ``.tar.gz`` holding everything a project keeps for an engagement, so it
+can be handed to another machine or put away for the years a retention
+policy asks for:
+
+    engagement.json        archive manifest: project config, reproducibility
+                           manifest, section counts and the SHA-256 of every file
+    project/...            the project directory: findings and their triage
+                           history (hypotheses.json), PoCs and their runs,
+                           reports, static analysis results, agent sessions
+    bounty/contests/...    bounty contests linked to the project, with their
+    bounty/findings/...    findings (assignees, reviewers, submission state)
+    source/...             the audited source, when exported with it
+
+Importing checks the format, every checksum and every member path before
+writing anything, then recreates the project (optionally under a new name)
+and its bounty contests.
+"""
+
+import hashlib
+import io
+import json
+import shutil
+import tarfile
+from datetime import datetime
+from pathlib import Path, PurePosixPath
+from typing import Any
+
+from extensions.ir.workspace import is_skipped
+
+from .manifest import build_manifest, project_detectors
+
+ARCHIVE_FORMAT = "baskerville-engagement-archive"
+ARCHIVE_VERSION = 1
+ARCHIVE_SUFFIX = ".tar.gz"
+
+_MANIFEST_NAME = "engagement.json"
+_SECTIONS = ("project", "bounty", "source")
+# Lock files and caches that would be stale on another machine
+_SKIP_NAMES = {"__pycache__", ".DS_Store"}
+_SKIP_SUFFIXES = (".lock", ".tmp", ".pyc")
+
+
+class ArchiveError(ValueError):
+    """The archive is unreadable, tampered with, or conflicts with existing data."""
+    pass
+
+
+def _sha256(data: bytes) -> str:
+    return hashlib.sha256(data).hexdigest()
+
+
+def _files(root: Path, skip_build_dirs: bool = False) -> list[Path]:
+    if not root.is_dir():
+        return []
+    found = []
+    for path in sorted(root.rglob("*")):
+        rel = path.relative_to(root)
+        if not path.is_file() or path.is_symlink():
+            continue
+        if any(part in _SKIP_NAMES for part in rel.parts) or path.name.endswith(_SKIP_SUFFIXES):
+            continue
+        if skip_build_dirs and is_skipped(rel):
+            continue
+        found.append(path)
+    return found
+
+
+def _read_json(path: Path) -> Any:
+    try:
+        return json.loads(path.read_text())
+    except (OSError, ValueError):
+        return None
+
+
+def linked_contests(project_name: str, bounty_dir: Path) -> list[str]:
+    """Ids of the bounty contests linked to ``project_name``."""
+    contests = []
+    for path in sorted((bounty_dir / "contests").glob("*.json")):
+        data = _read_json(path)
+        if isinstance(data, dict) and data.get("project_name") == project_name:
+            contests.append(path.stem)
+    return contests
+
+
+def _sections(project_dir: Path, contests: list[str], bounty_dir: Path) -> dict[str, int]:
+    hypotheses = (_read_json(project_dir / "hypotheses.json") or {}).get("hypotheses", {})
+    return {
+        "hypotheses": len(hypotheses),
+        "triaged": sum(1 for h in hypotheses.values() if h.get("status", "proposed") != "proposed"),
+        "pocs": sum(1 for _ in (project_dir / "poc").glob("*/metadata.json")),
+        "reports": len(_files(project_dir / "reports")),
+        "sessions": sum(1 for _ in (project_dir / "sessions").glob("*.json")),
+        "contests": len(contests),
+        "bounty_findings": sum(len(list((bounty_dir / "findings" / c).glob("*.json"))) for c in contests),
+    }
+
+
+def export_engagement(project: dict[str, Any], output: Path, bounty_dir: Path | None = None,
+                      include_source: bool = False, include_bounty: bool = True,
+                      config: dict[str, Any] | None = None) -> dict[str, Any]:
+    """Write a project's engagement archive.
+
+    Args:
+        project: Project config, as returned by ProjectManager.get_project
+        output: Archive file to write
+        bounty_dir: Bounty storage (default: ~/.hound/bounty)
+        include_source: Also archive the audited source tree (without build output)
+        include_bounty: Archive the bounty contests linked to the project
+        config: Loaded configuration, for the reproducibility manifest
+
+    Returns:
+        The archive manifest (also stored in the archive as engagement.json)
+    """
+    project_dir = Path(project["path"])
+    bounty_dir = bounty_dir or Path.home() / ".hound" / "bounty"
+    source = Path(project.get("source_path", ""))
+    contests = linked_contests(project["name"], bounty_dir) if include_bounty else []
+
+    members: list[tuple[str, Path]] = [(f"project/{p.relative_to(project_dir).as_posix()}", p)
+                                       for p in _files(project_dir)]
+    for contest_id in contests:
+        members.append((f"bounty/contests/{contest_id}.json", bounty_dir / "contests" / f"{contest_id}.json"))
+        members += [(f"bounty/findings/{contest_id}/{p.name}", p)
+                    for p in _files(bounty_dir / "findings" / contest_id)]
+    # A previously imported project already carries its source
+    in_project = source.is_dir() and source.resolve().is_relative_to(project_dir.resolve())
+    include_source = include_source and source.is_dir() and not in_project
+    if include_source:
+        members += [(f"source/{p.relative_to(source).as_posix()}", p) for p in _files(source, skip_build_dirs=True)]
+
+    contents = [(name, path.read_bytes()) for name, path in members]
+    manifest = {
+        "format": ARCHIVE_FORMAT,
+        "version": ARCHIVE_VERSION,
+        "exported_at": datetime.now().isoformat(),
+        "project": {k: v for k, v in project.items() if k != "path"},
+        "contests": contests,
+        "includes_source": include_source,
+        "source_in_project": source.resolve().relative_to(project_dir.resolve()).as_posix() if in_project else None,
+        "sections": _sections(project_dir, contests, bounty_dir),
+        "manifest": build_manifest(
+            "export engagement",
+            {"project_name": project["name"], "include_source": include_source, "include_bounty": include_bounty},
+            source=source if source.exists() else None,
+            config=config,
+            detectors=project_detectors(project_dir),
+        ),
+        "files": {name: _sha256(data) for name, data in contents},
+    }
+
+    output = Path(output)
+    output.parent.mkdir(parents=True, exist_ok=True)
+    with tarfile.open(output, "w:gz") as tar:
+        for name, data in [(_MANIFEST_NAME, json.dumps(manifest, indent=2).encode())] + contents:
+            info = tarfile.TarInfo(name)
+            info.size = len(data)
+            info.mode = 0o644
+            info.mtime = int(datetime.now().timestamp())
+            tar.addfile(info, io.BytesIO(data))
+    return manifest
+
+
+def _safe_name(name: str) -> bool:
+    path = PurePosixPath(name)
+    return (not path.is_absolute() and ".." not in path.parts and len(path.parts) > 1
+            and path.parts[0] in _SECTIONS)
+
+
+def read_archive(archive: Path) -> tuple[dict[str, Any], dict[str, bytes]]:
+    """The manifest and file contents of an archive, after checking both.
+
+    Raises:
+        ArchiveError: If the archive is not an engagement archive, has unsafe
+            member paths, or a file is missing or does not match its checksum
+    """
+    try:
+        tar = tarfile.open(archive, "r:gz")
+    except (OSError, tarfile.TarError) as e:
+        raise ArchiveError(f"Cannot read archive {archive}: {e}") from None
+    files: dict[str, bytes] = {}
+    manifest: Any = None
+    with tar:
+        try:
+            members = tar.getmembers()
+        except (OSError, EOFError, tarfile.TarError) as e:
+            raise ArchiveError(f"Cannot read archive {archive}: {e}") from None
+        for member in members:
+            if member.isdir():
+                continue
+            if not member.isfile():
+                raise ArchiveError(f"Unexpected non-file member: {member.name}")
+            data = tar.extractfile(member).read()
+            if member.name == _MANIFEST_NAME:
+                try:
+                    manifest = json.loads(data)
+                except ValueError:
+                    raise ArchiveError(f"{_MANIFEST_NAME} is not valid JSON") from None
+            elif not _safe_name(member.name):
+                raise ArchiveError(f"Unsafe member path: {member.name}")
+            else:
+                files[member.name] = data
+
+    if not isinstance(manifest, dict) or manifest.get("format") != ARCHIVE_FORMAT:
+        raise ArchiveError(f"{archive} is not an engagement archive")
+    if manifest.get("version", 0) > ARCHIVE_VERSION:
+        raise ArchiveError(f"Archive version {manifest['version']} is newer than supported ({ARCHIVE_VERSION})")
+
+    expected = manifest.get("files", {})
+    problems = [f"missing: {name}" for name in sorted(set(expected) - set(files))]
+    problems += [f"not in manifest: {name}" for name in sorted(set(files) - set(expected))]
+    problems += [f"checksum mismatch: {name}" for name in sorted(set(files) & set(expected))
+                 if _sha256(files[name]) != expected[name]]
+    if problems:
+        raise ArchiveError("Archive failed verification: " + "; ".join(problems))
+    return manifest, files
+
+
+def import_engagement(archive: Path, manager, bounty_storage=None, name: str | None = None,
+                      source_path: Path | None = None, force: bool = False) -> dict[str, Any]:
+    """Recreate the project (and linked bounty contests) of an archive.
+
+    Args:
+        archive: Archive written by export_engagement
+        manager: ProjectManager for the projects directory to import into
+        bounty_storage: BountyStorage for the contests (default: ~/.hound/bounty)
+        name: Project name (default: the exported project's name)
+        source_path: Audited source on this machine (default: the archived
+            source, extracted into the project, or the exported path)
+        force: Replace an existing project and contests of the same names
+
+    Returns:
+        Summary of the import: project, path, source_path, files, contests, warnings
+    """
+    from extensions.bounty.contest import Contest
+    from extensions.bounty.finding import Finding
+    from extensions.bounty.storage import BountyStorage
+
+    manifest, files = read_archive(Path(archive))
+    exported = manifest.get("project", {})
+    name = name or exported.get("name")
+    if not name or "/" in name or name.startswith("."):
+        raise ArchiveError(f"Invalid project name: {name!r}")
+
+    project_dir = manager.projects_dir / name
+    bounty_storage = bounty_storage or BountyStorage()
+    contests = manifest.get("contests", [])
+    conflicts = [f"project '{name}'"] if project_dir.exists() else []
+    conflicts += [f"contest '{c}'" for c in contests if bounty_storage.load_contest(c) is not None]
+    if conflicts and not force:
+        raise ArchiveError(f"Already exists: {', '.join(conflicts)} (use --force to replace)")
+
+    warnings = []
+    if project_dir.exists():
+        shutil.rmtree(project_dir)
+    for member, data in files.items():
+        section, _, rel = member.partition("/")
+        if section == "project":
+            target = project_dir / rel
+        elif section == "source":
+            target = project_dir / "source" / rel
+        else:
+            continue
+        target.parent.mkdir(parents=True, exist_ok=True)
+        target.write_bytes(data)
+
+    if source_path is not None:
+        source = Path(source_path).resolve()
+    elif manifest.get("includes_source"):
+        source = project_dir / "source"
+    elif manifest.get("source_in_project"):
+        source = project_dir / manifest["source_in_project"]
+    else:
+        source = Path(exported.get("source_path", ""))
+    if not source.exists():
+        warnings.append(f"Source {source} does not exist here; pass --source to point the project at it")
+
+    config = _read_json(project_dir / "project.json") or dict(exported)
+    config.update({
+        "name": name,
+        "source_path": str(source),
+        "last_accessed": datetime.now().isoformat(),
+        "imported_from": {"archive": Path(archive).name, "exported_at": manifest.get("exported_at"),
+                          "name": exported.get("name")},
+    })
+    (project_dir / "project.json").write_text(json.dumps(config, indent=2))
+    # A project.json without a registry entry is registered on first lookup
+    manager.get_project(name)
+
+    for contest_id in contests:
+        # Replacing a contest drops findings the archive does not have
+        bounty_storage.delete_contest(contest_id)
+        contest = Contest.from_dict(json.loads(files[f"bounty/contests/{contest_id}.json"]))
+        contest.project_name, contest.project_path = name, str(project_dir)
+        bounty_storage.save_contest(contest)
+        prefix = f"bounty/findings/{contest_id}/"
+        for member in sorted(m for m in files if m.startswith(prefix)):
+            bounty_storage.save_finding(Finding.from_dict(json.loads(files[member])))
+
+    return {
+        "project": name,
+        "path": str(project_dir),
+        "source_path": str(source),
+        "files": len(files),
+        "contests": contests,
+        "sections": manifest.get("sections", {}),
+        "warnings": warnings,
+    }
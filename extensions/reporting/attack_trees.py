@@ -0,0 +1,316 @@
+"""
+Attack trees composed from findings.
+
+Individual findings understate risk when two of them only matter together:
+a stale oracle is a medium, a flash-loanable pool is a note, both at once
+drain the vault. This step places every finding under the attacker goals it
+serves (drain vault funds, take over privileged control, lock funds):
+
+- directly, when the finding alone reaches the goal (a missing owner check
+  lets a forged account through; a reinitializable config hands over the
+  admin key)
+- in a chain, an AND branch whose steps are all backed by findings (oracle
+  staleness + flash loan), rated one level above its least severe step,
+  capped at the goal's impact
+- through another goal it leads to (take over control, then withdraw as the
+  authority)
+
+Findings are matched to goals by their taxonomy tags (``hypothesis_tags``).
+Trees export as JSON and Mermaid and are summarized in a report section
+ahead of the findings.
+"""
+
+import html
+import json
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from extensions.knowledge.taxonomy import hypothesis_tags
+from extensions.static.graph_export import SEVERITY_COLORS, SEVERITY_ORDER
+
+from .economic import insert_before_findings
+
+ATTACK_TREES_FORMAT = "baskerville-attack-trees"
+ATTACK_TREES_VERSION = 1
+FORMATS = ("mermaid", "markdown", "json")
+
+
+@dataclass(frozen=True)
+class Chain:
+    """Findings that only reach a goal together; each step is met by a finding with any of its tags."""
+    title: str
+    steps: tuple[tuple[str, tuple[str, ...]], ...]  # (step title, tags)
+
+
+@dataclass(frozen=True)
+class Goal:
+    """An attacker goal and the kinds of finding that reach it."""
+    id: str
+    title: str
+    impact: str  # Severity of reaching the goal
+    tags: tuple[str, ...]  # A finding with any of these reaches the goal alone
+    chains: tuple[Chain, ...] = ()
+    via: tuple[tuple[str, str], ...] = ()  # (goal id, how reaching it leads here)
+
+
+GOALS = [
+    Goal("drain-funds", "Drain vault funds", "critical",
+         ("account-validation", "reentrancy", "arithmetic", "token", "cpi", "cross-chain"),
+         chains=(
+             Chain("Price manipulation", (("Price can be moved or is stale", ("oracle",)),
+                                          ("Capital or ordering to exploit it", ("economic", "timing")))),
+             Chain("Predictable outcome", (("Outcome can be predicted", ("randomness",)),
+                                           ("Caller can act on it first", ("timing", "economic")))),
+         ),
+         via=(("take-over", "then withdraw as the authority"),)),
+    Goal("take-over", "Take over privileged control", "critical",
+         ("access-control", "initialization", "signature", "upgradeability", "governance")),
+    Goal("lock-funds", "Lock or freeze user funds", "high", ("denial-of-service", "upgradeability")),
+]
+
+
+def _severity(finding: dict[str, Any]) -> str:
+    severity = str(finding.get("severity", "medium")).lower()
+    return "info" if severity == "informational" else severity if severity in SEVERITY_ORDER else "medium"
+
+
+def _worst(severities: list[str]) -> str:
+    return min(severities, key=SEVERITY_ORDER.index) if severities else "info"
+
+
+def _capped(severity: str, cap: str) -> str:
+    return severity if SEVERITY_ORDER.index(severity) >= SEVERITY_ORDER.index(cap) else cap
+
+
+def _escalate(severity: str, cap: str) -> str:
+    """One level above ``severity``, but not above ``cap``."""
+    return _capped(SEVERITY_ORDER[max(SEVERITY_ORDER.index(severity) - 1, 0)], cap)
+
+
+def _items(findings: dict[str, dict] | list[dict]) -> list[tuple[str, dict]]:
+    if isinstance(findings, dict):
+        return [(str(fid), f) for fid, f in findings.items()]
+    return [(str(f.get("id") or f"finding-{i + 1}"), f) for i, f in enumerate(findings)]
+
+
+def build_attack_trees(findings: dict[str, dict] | list[dict], goals: list[Goal] | None = None) -> dict[str, Any]:
+    """Attack trees for ``findings`` (hypotheses by id, or report findings with ``id``).
+
+    Informational findings are left out. Goals no finding reaches get no tree;
+    findings that reach no goal are listed under ``unplaced``.
+    """
+    goals = GOALS if goals is None else goals
+    entries = {}
+    for fid, finding in _items(findings):
+        severity = _severity(finding)
+        if severity == "info":
+            continue
+        files = (finding.get("properties") or {}).get("source_files") or []
+        entries[fid] = {"id": fid, "title": finding.get("title", fid), "severity": severity,
+                        "tags": hypothesis_tags(finding), "file": files[0] if files else None}
+
+    def tagged(tags: tuple[str, ...]) -> list[str]:
+        return [fid for fid, e in entries.items() if any(t in e["tags"] for t in tags)]
+
+    trees: dict[str, dict[str, Any]] = {}
+    for goal in goals:
+        branches = [{"kind": "finding", "finding": fid, "title": entries[fid]["title"],
+                     "severity": entries[fid]["severity"]} for fid in tagged(goal.tags)]
+        direct = {b["finding"] for b in branches}
+        for chain in goal.chains:
+            # A finding meeting every step is a path of its own
+            alone = [fid for fid in entries if fid not in direct
+                     and all(any(t in entries[fid]["tags"] for t in tags) for _, tags in chain.steps)]
+            for fid in alone:
+                branches.append({"kind": "finding", "finding": fid, "title": entries[fid]["title"],
+                                 "severity": entries[fid]["severity"]})
+                direct.add(fid)
+            steps = [{"title": title, "findings": [f for f in tagged(tags) if f not in alone]}
+                     for title, tags in chain.steps]
+            if all(step["findings"] for step in steps) and len({f for s in steps for f in s["findings"]}) > 1:
+                weakest = max((_worst([entries[f]["severity"] for f in s["findings"]]) for s in steps),
+                              key=SEVERITY_ORDER.index)
+                branches.append({"kind": "chain", "title": chain.title, "steps": steps,
+                                 "severity": _escalate(weakest, goal.impact)})
+        trees[goal.id] = {"goal": goal.id, "title": goal.title, "impact": goal.impact, "branches": branches}
+
+    # Goals reached through others, once every goal's own branches are known
+    for goal in goals:
+        for other, how in goal.via:
+            sub = trees.get(other)
+            if sub and sub["branches"]:
+                trees[goal.id]["branches"].append({
+                    "kind": "goal", "goal": other, "title": f"{sub['title']}, {how}",
+                    "severity": _capped(_worst([b["severity"] for b in sub["branches"]]), goal.impact)})
+
+    result = []
+    for tree in trees.values():
+        if not tree["branches"]:
+            continue
+        order = {"chain": 0, "finding": 1, "goal": 2}
+        tree["branches"].sort(key=lambda b: (SEVERITY_ORDER.index(b["severity"]), order[b["kind"]]))
+        tree["severity"] = _worst([b["severity"] for b in tree["branches"]])
+        tree["chains"] = sum(1 for b in tree["branches"] if b["kind"] == "chain")
+        result.append(tree)
+    result.sort(key=lambda t: SEVERITY_ORDER.index(t["severity"]))
+
+    placed = set()
+    for branch in (b for t in result for b in t["branches"]):
+        if branch["kind"] == "finding":
+            placed.add(branch["finding"])
+        elif branch["kind"] == "chain":
+            placed.update(f for step in branch["steps"] for f in step["findings"])
+    return {
+        "format": ATTACK_TREES_FORMAT,
+        "version": ATTACK_TREES_VERSION,
+        "trees": result,
+        "findings": entries,
+        "unplaced": [fid for fid in entries if fid not in placed],
+    }
+
+
+def _mermaid_text(text: str) -> str:
+    return text.replace('"', "#quot;").replace("\n", "<br/>")
+
+
+def _node_id(prefix: str, key: str) -> str:
+    return prefix + re.sub(r"\W+", "_", key)
+
+
+def to_mermaid(data: dict[str, Any]) -> str:
+    """Mermaid flowchart of every tree; a finding serving several goals is drawn once."""
+    lines = ["flowchart TD"]
+    findings = data["findings"]
+    ids = {fid: f"f{i}" for i, fid in enumerate(findings)}
+    used_findings: list[str] = []
+    classes: dict[str, str] = {}
+
+    def leaf(fid: str) -> str:
+        if fid not in used_findings:
+            used_findings.append(fid)
+        return ids[fid]
+
+    for tree in data["trees"]:
+        goal = _node_id("g_", tree["goal"])
+        lines.append(f'  {goal}{{{{"{_mermaid_text(tree["title"])}<br/>{tree["severity"]}"}}}}')
+        classes[goal] = tree["severity"]
+        for i, branch in enumerate(tree["branches"]):
+            if branch["kind"] == "finding":
+                lines.append(f"  {goal} --> {leaf(branch['finding'])}")
+            elif branch["kind"] == "goal":
+                lines.append(f'  {goal} -->|"{_mermaid_text(branch["title"])}"| {_node_id("g_", branch["goal"])}')
+            else:
+                gate = f"{goal}_c{i}"
+                lines.append(f'  {gate}[/"AND: {_mermaid_text(branch["title"])}<br/>{branch["severity"]}"\\]')
+                classes[gate] = branch["severity"]
+                lines.append(f"  {goal} --> {gate}")
+                for step in branch["steps"]:
+                    for fid in step["findings"]:
+                        lines.append(f'  {gate} -->|"{_mermaid_text(step["title"])}"| {leaf(fid)}')
+    for fid in used_findings:
+        finding = findings[fid]
+        lines.append(f'  {ids[fid]}["{_mermaid_text(finding["title"])}<br/>{finding["severity"]}"]')
+        classes[ids[fid]] = finding["severity"]
+    for node, severity in classes.items():
+        lines.append(f"  class {node} {severity}")
+    for severity in SEVERITY_ORDER:
+        if severity in classes.values():
+            lines.append(f"  classDef {severity} fill:{SEVERITY_COLORS[severity]}")
+    return "\n".join(lines) + "\n"
+
+
+def _branch_text(branch: dict[str, Any], findings: dict[str, dict]) -> tuple[str, str]:
+    """(headline, detail) of one branch."""
+    if branch["kind"] == "finding":
+        return f"via {branch['title']}", ""
+    if branch["kind"] == "goal":
+        return f"via {branch['title']}", "see its tree"
+    steps = "; ".join(f"{s['title']}: " + ", ".join(findings[f]["title"] for f in s["findings"])
+                      for s in branch["steps"])
+    return f"via {branch['title']} (chain)", steps
+
+
+_INTRO = ("Findings composed into the attacker goals they serve. Chains are paths that need several findings "
+          "at once; each is rated one level above its least severe step, as the findings together do more "
+          "than any does alone.")
+
+
+def attack_trees_markdown(data: dict[str, Any]) -> str:
+    lines = ["## Attack Trees", "", _INTRO, ""]
+    for tree in data["trees"]:
+        lines += [f"### {tree['title']} ({tree['severity']})", ""]
+        for branch in tree["branches"]:
+            headline, detail = _branch_text(branch, data["findings"])
+            lines.append(f"- **{branch['severity'].upper()}** {headline}" + (f" — {detail}" if detail else ""))
+        lines.append("")
+    lines += ["```mermaid", to_mermaid(data).rstrip("\n"), "```"]
+    if data["unplaced"]:
+        lines += ["", f"{len(data['unplaced'])} findings reach none of these goals on their own."]
+    return "\n".join(lines) + "\n"
+
+
+def attack_trees_html(data: dict[str, Any]) -> str:
+    parts = ['<div class="section">', "<h2>Attack Trees</h2>", f"<p>{html.escape(_INTRO)}</p>"]
+    for tree in data["trees"]:
+        parts.append(f"<h3>{html.escape(tree['title'])} ({tree['severity']})</h3>")
+        items = []
+        for branch in tree["branches"]:
+            headline, detail = _branch_text(branch, data["findings"])
+            items.append(f"<li><strong>[{branch['severity'].upper()}]</strong> {html.escape(headline)}"
+                         + (f" &mdash; {html.escape(detail)}" if detail else "") + "</li>")
+        parts.append(f"<ul>{''.join(items)}</ul>")
+    parts.append(f'<pre class="mermaid">{html.escape(to_mermaid(data))}</pre>')
+    if data["unplaced"]:
+        parts.append(f"<p>{len(data['unplaced'])} findings reach none of these goals on their own.</p>")
+    parts.append("</div>")
+    return "\n".join(parts) + "\n"
+
+
+def attack_trees_section(data: dict[str, Any], fmt: str) -> str:
+    """The whole "Attack Trees" section in ``fmt`` (html or markdown)."""
+    if fmt == "html":
+        return attack_trees_html(data)
+    if fmt in ("markdown", "md"):
+        return attack_trees_markdown(data)
+    raise ValueError(f"Cannot render an attack-trees section in format: {fmt}")
+
+
+def render_attack_trees(data: dict[str, Any], fmt: str) -> str:
+    """``data`` in one of ``FORMATS``."""
+    if fmt == "mermaid":
+        return to_mermaid(data)
+    if fmt == "markdown":
+        return attack_trees_markdown(data)
+    if fmt == "json":
+        return json.dumps(data, indent=2) + "\n"
+    raise ValueError(f"Unknown attack-tree format '{fmt}' (available: {', '.join(FORMATS)})")
+
+
+def load_findings(project_dir: Path | None, confirmed_only: bool = False) -> dict[str, dict]:
+    """The project's findings by id: confirmed hypotheses, else every one not rejected plus the static hits."""
+    if project_dir is None:
+        return {}
+    items: dict[str, dict] = {}
+    try:
+        items.update(json.loads((Path(project_dir) / "hypotheses.json").read_text()).get("hypotheses", {}))
+    except (OSError, ValueError, AttributeError):
+        pass
+    if confirmed_only:
+        return {hid: h for hid, h in items.items() if h.get("status") == "confirmed"}
+    try:
+        hits = json.loads((Path(project_dir) / "static_analysis" / "static_hypotheses.json").read_text())
+    except (OSError, ValueError):
+        hits = []
+    for hit in hits if isinstance(hits, list) else []:
+        if isinstance(hit, dict):
+            items.setdefault(hit.get("id") or f"static_{len(items)}", hit)
+    return {hid: h for hid, h in items.items() if h.get("status") != "rejected"}
+
+
+def embed_attack_trees(content: str, data: dict[str, Any], fmt: str) -> str:
+    """Insert the attack-trees section ahead of the findings of a rendered report."""
+    if not data["trees"]:
+        return content
+    return insert_before_findings(content, attack_trees_section(data, fmt), fmt)
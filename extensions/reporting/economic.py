@@ -0,0 +1,172 @@
+"""
+Economic-risk report section.
+
+Renders the liquidation sweeps saved under a project's simulations/
+directory (``sim liquidations --project``) as an "Economic Risk" section:
+one outcome grid per position size, oracle latency against price drop,
+as an SVG chart plus table in HTML reports and as tables in markdown.
+The section is inserted ahead of the findings.
+"""
+
+import html
+import json
+import re
+from pathlib import Path
+from typing import Any
+
+_HTML_FINDINGS_RE = re.compile(r'<div class="section">\s*<h2>(?:Findings|All Hypotheses \(UNREVIEWED\))</h2>')
+_MD_FINDINGS_RE = re.compile(r"^## Findings\s*$", re.MULTILINE)
+
+# Status -> (fill colour, short label)
+_STATUS_STYLE = {
+    "healthy": ("#2e7d32", "healthy"),
+    "liquidated": ("#1565c0", "liquidated"),
+    "unprofitable": ("#f9a825", "unprofitable"),
+    "bad-debt": ("#c62828", "bad debt"),
+}
+
+_CELL_W, _CELL_H, _LABEL_W, _HEADER_H = 84, 26, 92, 22
+
+
+def load_sweeps(project_dir: Path | None) -> list[dict[str, Any]]:
+    """Liquidation sweep exports saved under the project's simulations/ directory."""
+    if project_dir is None:
+        return []
+    sweeps = []
+    for path in sorted((Path(project_dir) / "simulations").glob("*.json")):
+        try:
+            data = json.loads(path.read_text())
+        except (OSError, ValueError):
+            continue
+        if isinstance(data, dict) and data.get("tool") == "liquidation-sweep":
+            sweeps.append({**data, "name": path.stem})
+    return sweeps
+
+
+def _grid(sweep: dict[str, Any], size: float) -> list[tuple[int, list[dict[str, Any] | None]]]:
+    """Rows of (latency, scenario per drop) for one position size."""
+    by_key = {(s["drop"], s["latency"]): s for s in sweep["scenarios"] if s["size"] == size}
+    config = sweep["config"]
+    return [(lat, [by_key.get((drop, lat)) for drop in config["drops"]]) for lat in config["latencies"]]
+
+
+def _cell(scenario: dict[str, Any] | None) -> str:
+    if scenario is None:
+        return "n/a"
+    if scenario["status"] == "bad-debt":
+        return f"bad debt {scenario['bad_debt_share']:.1%}"
+    return _STATUS_STYLE[scenario["status"]][1]
+
+
+def _summary(sweep: dict[str, Any]) -> str:
+    config = sweep["config"]
+    params = ", ".join(f"{k}={v}" for k, v in sweep.get("params", {}).items() if v is not None)
+    depth = f"{config['external_depth']:,g}" if config.get("external_depth") else "unlimited"
+    return (f"{len(sweep['scenarios'])} crash scenarios against {sweep['protocol']} ({params}); borrower at "
+            f"{config['utilization']:.0%} of the LTV limit, exit venue fee {config['external_fee']:g}, depth {depth}.")
+
+
+def sweep_markdown(sweep: dict[str, Any]) -> str:
+    """Markdown tables for one sweep, one per position size."""
+    config = sweep["config"]
+    lines = [f"### Liquidation sweep: {sweep.get('name', sweep['protocol'])}", "", _summary(sweep), ""]
+    for size in config["sizes"]:
+        lines += [f"**Position size {size:,g}**", ""]
+        lines.append("| Oracle lag \\ Drop | " + " | ".join(f"{d:.0%}" for d in config["drops"]) + " |")
+        lines.append("|---" * (len(config["drops"]) + 1) + "|")
+        for latency, row in _grid(sweep, size):
+            lines.append(f"| {latency} slots | " + " | ".join(_cell(s) for s in row) + " |")
+        lines.append("")
+    for finding in sweep.get("findings", []):
+        lines.append(f"- **[{finding['severity'].upper()}]** {finding['title']}")
+    return "\n".join(lines).rstrip() + "\n"
+
+
+def sweep_chart_svg(sweep: dict[str, Any], size: float) -> str:
+    """Outcome heat-grid (oracle lag rows, price drop columns) for one position size."""
+    config = sweep["config"]
+    drops = config["drops"]
+    rows = _grid(sweep, size)
+    width = _LABEL_W + _CELL_W * len(drops)
+    height = _HEADER_H + _CELL_H * len(rows)
+    parts = [f'<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" '
+             f'font-family="sans-serif" font-size="11" role="img">']
+    for i, drop in enumerate(drops):
+        x = _LABEL_W + i * _CELL_W + _CELL_W / 2
+        parts.append(f'<text x="{x}" y="15" fill="#8892b0" text-anchor="middle">{drop:.0%} drop</text>')
+    for j, (latency, row) in enumerate(rows):
+        y = _HEADER_H + j * _CELL_H
+        parts.append(f'<text x="4" y="{y + 17}" fill="#8892b0">lag {latency}</text>')
+        for i, scenario in enumerate(row):
+            status = scenario["status"] if scenario else "healthy"
+            fill = _STATUS_STYLE[status][0] if scenario else "#37474f"
+            # Deeper red for a larger share of the debt lost
+            opacity = 0.45 + 0.55 * min(1.0, scenario["bad_debt_share"] * 4) if status == "bad-debt" else 0.85
+            x = _LABEL_W + i * _CELL_W
+            parts.append(f'<rect x="{x + 1}" y="{y + 1}" width="{_CELL_W - 2}" height="{_CELL_H - 2}" '
+                         f'fill="{fill}" fill-opacity="{opacity:.2f}"><title>{html.escape(_cell(scenario))}'
+                         f'</title></rect>')
+            parts.append(f'<text x="{x + _CELL_W / 2}" y="{y + 17}" fill="#ffffff" text-anchor="middle">'
+                         f'{html.escape(_cell(scenario))}</text>')
+    parts.append("</svg>")
+    return "".join(parts)
+
+
+def sweep_html(sweep: dict[str, Any]) -> str:
+    """Charts and tables for one sweep."""
+    config = sweep["config"]
+    parts = [f"<h3>Liquidation sweep: {html.escape(str(sweep.get('name', sweep['protocol'])))}</h3>",
+             f"<p>{html.escape(_summary(sweep))}</p>"]
+    for size in config["sizes"]:
+        parts.append(f"<h4>Position size {size:,g}</h4>")
+        parts.append(f'<div class="economic-chart">{sweep_chart_svg(sweep, size)}</div>')
+        header = "".join(f"<th>{d:.0%}</th>" for d in config["drops"])
+        body = "".join(
+            f"<tr><td>{latency} slots</td>" + "".join(f"<td>{html.escape(_cell(s))}</td>" for s in row) + "</tr>"
+            for latency, row in _grid(sweep, size)
+        )
+        parts.append(f"<table><thead><tr><th>Oracle lag \\ Drop</th>{header}</tr></thead><tbody>{body}</tbody>"
+                     f"</table>")
+    findings = "".join(
+        f"<li><strong>[{html.escape(f['severity'].upper())}]</strong> {html.escape(f['title'])}</li>"
+        for f in sweep.get("findings", [])
+    )
+    if findings:
+        parts.append(f"<ul>{findings}</ul>")
+    return "\n".join(parts)
+
+
+def economic_risk_section(sweeps: list[dict[str, Any]], fmt: str) -> str:
+    """The whole "Economic Risk" section in ``fmt`` (html or markdown)."""
+    intro = ("Simulated collateral price crashes across oracle latencies and position sizes, showing where "
+             "liquidation stops being profitable and where bad debt accrues.")
+    if fmt == "html":
+        body = "\n".join(sweep_html(s) for s in sweeps)
+        return f'<div class="section">\n<h2>Economic Risk</h2>\n<p>{intro}</p>\n{body}\n</div>\n'
+    if fmt in ("markdown", "md"):
+        return "## Economic Risk\n\n" + intro + "\n\n" + "\n".join(sweep_markdown(s) for s in sweeps) + "\n"
+    raise ValueError(f"Cannot render an economic-risk section in format: {fmt}")
+
+
+def insert_before_findings(content: str, section: str, fmt: str) -> str:
+    """Insert a rendered section ahead of the findings (or at the end) of a report in ``fmt``."""
+    match = (_HTML_FINDINGS_RE if fmt == "html" else _MD_FINDINGS_RE).search(content)
+    if match:
+        return content[:match.start()] + section + "\n" + content[match.start():]
+    return append_section(content, section, fmt)
+
+
+def append_section(content: str, section: str, fmt: str) -> str:
+    """Append a rendered section (an appendix) at the end of a report in ``fmt``, ahead of any footer."""
+    if fmt == "html":
+        idx = content.rfind('<div class="footer">')
+        idx = idx if idx != -1 else content.rfind("</body>")
+        return content[:idx] + section + content[idx:] if idx != -1 else content + section
+    return content.rstrip("\n") + "\n\n" + section
+
+
+def embed_economic_risk(content: str, sweeps: list[dict[str, Any]], fmt: str) -> str:
+    """Insert the economic-risk section ahead of the findings of a rendered report."""
+    if not sweeps:
+        return content
+    return insert_before_findings(content, economic_risk_section(sweeps, fmt), fmt)
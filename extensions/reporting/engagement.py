@@ -0,0 +1,317 @@
+"""
+Engagement summary.
+
+Summarizes one engagement window from what a project keeps on disk, for the
+methodology section of the final report:
+
+- scans: agent sessions (``sessions/*.json``) and static analysis runs
+  (``static_analysis/runs.jsonl``, or the last ``static_metadata.json``)
+- findings: hypotheses opened in the window, and those confirmed, fixed or
+  rejected in it according to their ``status_history``; a status set before
+  history was kept counts as of the hypothesis' creation
+- PoCs: imported PoCs (``poc/<id>/metadata.json``) and the validator runs
+  recorded against them; a PoC is verified when a run in the window passed
+- checklist coverage: items of the audit checklist that a finding active in
+  the window cites by id (``properties.checklist``) or shares a taxonomy
+  class with
+
+The window is inclusive on both ends; either end may be open.
+"""
+
+import json
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Iterable
+
+from extensions.knowledge.taxonomy import hypothesis_tags
+
+ENGAGEMENT_FORMAT = "baskerville-engagement"
+ENGAGEMENT_VERSION = 1
+
+SEVERITIES = ("critical", "high", "medium", "low", "info")
+# Statuses whose findings do not count towards checklist coverage
+_DISMISSED = {"rejected", "refuted"}
+
+
+def parse_time(value: Any) -> datetime | None:
+    """Naive local datetime from an ISO string (timezone-aware values are converted)."""
+    if isinstance(value, datetime):
+        moment = value
+    elif isinstance(value, str) and value:
+        try:
+            moment = datetime.fromisoformat(value.replace("Z", "+00:00"))
+        except ValueError:
+            return None
+    else:
+        return None
+    return moment.astimezone().replace(tzinfo=None) if moment.tzinfo else moment
+
+
+def _within(moment: datetime | None, start: datetime | None, end: datetime | None) -> bool:
+    if moment is None:
+        return False
+    return (start is None or moment >= start) and (end is None or moment <= end)
+
+
+def _read_json(path: Path) -> Any:
+    try:
+        return json.loads(path.read_text())
+    except (OSError, ValueError):
+        return None
+
+
+def status_events(hyp: dict[str, Any]) -> list[tuple[str, datetime | None]]:
+    """(status, when) for every recorded status change of a hypothesis."""
+    events = []
+    for entry in hyp.get("status_history") or []:
+        if isinstance(entry, dict) and entry.get("status"):
+            events.append((str(entry["status"]).lower(), parse_time(entry.get("at"))))
+    status = str(hyp.get("status", "proposed")).lower()
+    if not events and status != "proposed":
+        events.append((status, parse_time(hyp.get("created_at"))))
+    return events
+
+
+def load_scans(project_dir: Path) -> list[dict[str, Any]]:
+    """Agent sessions and static analysis runs recorded for a project, oldest first."""
+    project_dir = Path(project_dir)
+    scans = []
+    for path in sorted((project_dir / "sessions").glob("*.json")):
+        data = _read_json(path)
+        if not isinstance(data, dict):
+            continue
+        scans.append({
+            "kind": "agent",
+            "id": data.get("session_id", path.stem),
+            "start": data.get("start_time"),
+            "end": data.get("end_time"),
+            "status": data.get("status"),
+            "investigations": len(data.get("investigations") or []),
+            "coverage": data.get("coverage") or {},
+        })
+
+    static_dir = project_dir / "static_analysis"
+    runs_file = static_dir / "runs.jsonl"
+    runs = []
+    if runs_file.exists():
+        for line in runs_file.read_text().splitlines():
+            try:
+                runs.append(json.loads(line))
+            except ValueError:
+                continue
+    else:
+        metadata = _read_json(static_dir / "static_metadata.json")
+        if isinstance(metadata, dict):
+            tools = metadata.get("tools", {})
+            runs.append({"run_time": metadata.get("run_time"), "chain_id": metadata.get("chain_id"),
+                         "tools": sorted(t for t, m in tools.items() if m.get("available", True)),
+                         "hypotheses": None})
+    for run in runs:
+        scans.append({"kind": "static", "start": run.get("run_time"), "end": run.get("run_time"),
+                      "tools": run.get("tools") or [], "hypotheses": run.get("hypotheses")})
+    return sorted(scans, key=lambda s: parse_time(s["start"]) or datetime.min)
+
+
+def load_pocs(project_dir: Path) -> list[dict[str, Any]]:
+    """Imported PoCs with their recorded runs."""
+    pocs = []
+    for path in sorted((Path(project_dir) / "poc").glob("*/metadata.json")):
+        data = _read_json(path)
+        if not isinstance(data, dict):
+            continue
+        pocs.append({
+            "hypothesis_id": data.get("hypothesis_id", path.parent.name),
+            "title": data.get("title"),
+            "created_at": data.get("created_at"),
+            "runs": [r for r in data.get("runs") or [] if isinstance(r, dict)],
+        })
+    return pocs
+
+
+def _load_hypotheses(project_dir: Path) -> dict[str, dict]:
+    data = _read_json(Path(project_dir) / "hypotheses.json")
+    return data.get("hypotheses", {}) if isinstance(data, dict) else {}
+
+
+def checklist_coverage(items: Iterable[Any], findings: Iterable[dict[str, Any]]) -> dict[str, Any]:
+    """Checklist items the findings cite by id or share a taxonomy class with."""
+    items = list(items)
+    findings = list(findings)
+    cited = {ref for f in findings for ref in (f.get("properties") or {}).get("checklist") or []}
+    classes = {tag for f in findings for tag in hypothesis_tags(f)}
+    by_category: dict[str, dict[str, int]] = {}
+    covered, via_id, uncovered = [], [], []
+    for item in items:
+        category = by_category.setdefault(item.category, {"total": 0, "covered": 0})
+        category["total"] += 1
+        if item.id in cited:
+            via_id.append(item.id)
+        if item.id in cited or classes & set(item.taxonomy):
+            covered.append(item.id)
+            category["covered"] += 1
+        else:
+            uncovered.append(item.id)
+    total = len(items)
+    return {
+        "total": total,
+        "covered": len(covered),
+        "cited": len(via_id),
+        "percent": round(len(covered) / total * 100.0, 1) if total else 0.0,
+        "by_category": dict(sorted(by_category.items())),
+        "uncovered": uncovered,
+    }
+
+
+def build_engagement_summary(
+    project_dir: Path,
+    start: datetime | None = None,
+    end: datetime | None = None,
+    checklist: Iterable[Any] | None = None,
+    project: str | None = None,
+) -> dict[str, Any]:
+    """Summary of the engagement window ``start``..``end`` for a project.
+
+    Args:
+        project_dir: Project directory (hypotheses, sessions, static results, PoCs)
+        start: First moment of the window (default: open)
+        end: Last moment of the window (default: open)
+        checklist: Checklist items to measure coverage against (``ChecklistItem``)
+        project: Project name recorded in the summary
+    """
+    project_dir = Path(project_dir)
+
+    scans = [s for s in load_scans(project_dir) if _within(parse_time(s["start"]), start, end)]
+    agent = [s for s in scans if s["kind"] == "agent"]
+    static = [s for s in scans if s["kind"] == "static"]
+
+    counts = {key: 0 for key in ("opened", "confirmed", "fixed", "rejected")}
+    by_severity: dict[str, dict[str, int]] = {}
+    rows, active = [], []
+    for hid, hyp in _load_hypotheses(project_dir).items():
+        severity = str(hyp.get("severity", "medium")).lower()
+        row = {"id": hyp.get("id", hid), "title": hyp.get("title", ""), "severity": severity,
+               "status": str(hyp.get("status", "proposed")).lower()}
+        if _within(parse_time(hyp.get("created_at")), start, end):
+            row["opened"] = hyp.get("created_at")
+        for status, moment in status_events(hyp):
+            if status in ("confirmed", "fixed", "rejected") and _within(moment, start, end):
+                row[status] = moment.isoformat()
+        events = [key for key in counts if key in row]
+        if not events:
+            continue
+        rows.append(row)
+        sev = by_severity.setdefault(severity, {"opened": 0, "confirmed": 0, "fixed": 0})
+        for key in events:
+            counts[key] += 1
+            if key in sev:
+                sev[key] += 1
+        if row["status"] not in _DISMISSED:
+            active.append(hyp)
+
+    pocs = []
+    for poc in load_pocs(project_dir):
+        runs = [r for r in poc["runs"] if _within(parse_time(r.get("at")), start, end)]
+        imported = _within(parse_time(poc["created_at"]), start, end)
+        if not runs and not imported:
+            continue
+        pocs.append({
+            "hypothesis_id": poc["hypothesis_id"],
+            "title": poc["title"],
+            "imported": imported,
+            "runs": len(runs),
+            "verified": any(r.get("passed") for r in runs),
+        })
+
+    # The latest session in the window carries the coverage reached by then
+    coverage = next((s["coverage"] for s in reversed(agent) if s["coverage"]), {})
+    order = {s: i for i, s in enumerate(SEVERITIES)}
+    return {
+        "format": ENGAGEMENT_FORMAT,
+        "version": ENGAGEMENT_VERSION,
+        "project": project,
+        "generated_at": datetime.now().isoformat(),
+        "window": {"start": start.isoformat() if start else None, "end": end.isoformat() if end else None},
+        "scans": {
+            "agent_sessions": len(agent),
+            "investigations": sum(s["investigations"] for s in agent),
+            "static_runs": len(static),
+            "static_tools": sorted({t for s in static for t in s["tools"]}),
+            "runs": scans,
+        },
+        "findings": {
+            **counts,
+            "by_severity": dict(sorted(by_severity.items(), key=lambda kv: order.get(kv[0], len(order)))),
+            "items": rows,
+        },
+        "pocs": {
+            "total": len(pocs),
+            "imported": sum(1 for p in pocs if p["imported"]),
+            "verified": sum(1 for p in pocs if p["verified"]),
+            "failing": sum(1 for p in pocs if p["runs"] and not p["verified"]),
+            "items": pocs,
+        },
+        "code_coverage": {key: coverage[key] for key in ("nodes", "cards") if key in coverage},
+        "checklist": checklist_coverage(checklist, active) if checklist is not None else None,
+    }
+
+
+def _day(value: str | None, fallback: str) -> str:
+    moment = parse_time(value)
+    return moment.date().isoformat() if moment else fallback
+
+
+def render_methodology(summary: dict[str, Any]) -> str:
+    """Markdown for the methodology section of the final report."""
+    window = summary["window"]
+    scans, findings, pocs = summary["scans"], summary["findings"], summary["pocs"]
+    lines = ["## Methodology", ""]
+    lines.append(
+        f"This summary covers the engagement from {_day(window['start'], 'its start')} "
+        f"to {_day(window['end'], 'the present')}."
+    )
+    lines.append("")
+
+    lines += ["### Analysis performed", ""]
+    lines.append(f"- {scans['agent_sessions']} agent analysis session(s), {scans['investigations']} investigation(s)")
+    tools = ", ".join(scans["static_tools"]) or "none"
+    lines.append(f"- {scans['static_runs']} static analysis run(s) (tools: {tools})")
+    for key, label in (("nodes", "graph nodes"), ("cards", "code cards")):
+        stats = summary["code_coverage"].get(key)
+        if stats and stats.get("total"):
+            lines.append(f"- Code coverage: {stats['visited']} of {stats['total']} {label} ({stats['percent']}%)")
+    lines.append("")
+
+    lines += ["### Findings", ""]
+    lines.append(
+        f"{findings['opened']} finding(s) opened, {findings['confirmed']} confirmed, {findings['fixed']} fixed "
+        f"and {findings['rejected']} rejected during the window."
+    )
+    if findings["by_severity"]:
+        lines += ["", "| Severity | Opened | Confirmed | Fixed |", "|---|---:|---:|---:|"]
+        for severity, row in findings["by_severity"].items():
+            lines.append(f"| {severity.capitalize()} | {row['opened']} | {row['confirmed']} | {row['fixed']} |")
+    lines.append("")
+
+    lines += ["### Proof-of-concept verification", ""]
+    if pocs["total"]:
+        lines.append(
+            f"{pocs['verified']} of {pocs['total']} proof(s) of concept passed against a local validator"
+            + (f"; {pocs['failing']} did not pass." if pocs["failing"] else ".")
+        )
+    else:
+        lines.append("No proofs of concept were imported or run during the window.")
+    lines.append("")
+
+    checklist = summary.get("checklist")
+    if checklist is not None:
+        lines += ["### Checklist coverage", ""]
+        lines.append(
+            f"{checklist['covered']} of {checklist['total']} audit checklist item(s) ({checklist['percent']}%) "
+            f"are covered by findings, {checklist['cited']} of them cited by id."
+        )
+        if checklist["by_category"]:
+            lines += ["", "| Category | Covered | Items |", "|---|---:|---:|"]
+            for category, row in checklist["by_category"].items():
+                lines.append(f"| {category} | {row['covered']} | {row['total']} |")
+        lines.append("")
+    return "\n".join(lines)
@@ -0,0 +1,257 @@
+"""
+Findings report formatters.
+
+A findings report is one project's findings, each enriched with the PoC
+template that exercises it and remediation guidance, rendered by a
+registered formatter:
+
+- ``json``: machine-readable, for CI pipelines and ticket sync
+- ``markdown``: audit-style report, findings grouped by severity, each with
+  the exploit part of its PoC template and how to fix it
+- ``sarif``: detector hits for code-scanning dashboards (see sarif.py)
+
+Remediation comes from the matched template's front matter
+(``remediation``), else the vulnerability class's fix guidance for the
+chain, else the detector's own suggestion. Given a severity rubric (see
+scoring.py), findings are ranked by their score. Further formats register
+with ``@register_formatter``.
+"""
+
+import json
+from pathlib import Path
+from typing import Any
+
+from extensions.static.finding import Finding, number_occurrences
+
+from .heatmap import finding_files
+from .manifest import embed_manifest
+from .sarif import build_sarif
+
+SEVERITIES = ["critical", "high", "medium", "low", "info"]
+FINDINGS_FORMAT = "baskerville-findings"
+FINDINGS_VERSION = 1
+
+# Fence language of each chain's PoC templates
+_FENCES = {"evm": "solidity", "solana": "rust", "cosmwasm": "rust", "sui": "move", "aptos": "move"}
+
+FORMATTERS: dict[str, type["ReportFormatter"]] = {}
+
+
+def register_formatter(cls: type["ReportFormatter"]) -> type["ReportFormatter"]:
+    """Class decorator making a formatter available by its ``name``."""
+    FORMATTERS[cls.name] = cls
+    return cls
+
+
+def get_formatter(name: str) -> "ReportFormatter":
+    """A formatter instance by name.
+
+    Raises:
+        ValueError: If no formatter is registered under ``name``
+    """
+    try:
+        return FORMATTERS[name.lower()]()
+    except KeyError:
+        raise ValueError(f"Unknown report format '{name}' (expected one of {', '.join(sorted(FORMATTERS))})") from None
+
+
+class ReportFormatter:
+    """Renders a findings report (see build_findings_report) to text."""
+
+    name = ""
+    suffix = ".txt"
+
+    def format(self, report: dict[str, Any]) -> str:
+        raise NotImplementedError
+
+
+def _severity(finding: dict[str, Any]) -> str:
+    severity = str(finding.get("severity") or "medium").lower()
+    return "info" if severity == "informational" else severity if severity in SEVERITIES else "medium"
+
+
+def _score(confidence: Any) -> float:
+    return float(confidence) if isinstance(confidence, (int, float)) else 0.0
+
+
+def _template_for(finding: dict[str, Any], chain: str | None, templates: Any, vuln_class: Any) -> Any:
+    """The PoC template for a finding: the one its detector names, else its class's, else by type."""
+    named = (finding.get("properties") or {}).get("poc_template")
+    if named and templates.get(named):
+        return templates.get(named)
+    variant = vuln_class.variant(chain) if vuln_class else None
+    for template_id in variant.templates if variant else []:
+        if templates.get(template_id):
+            return templates.get(template_id)
+    vuln_type = finding.get("vulnerability_type")
+    if not vuln_type:
+        return None
+    return next((t for t in templates.get_by_vulnerability(vuln_type) if not chain or t.chain == chain.lower()), None)
+
+
+def build_findings_report(findings: dict[str, dict[str, Any]] | list[dict[str, Any]], project: str,
+                          chain: str | None = None, source: Path | None = None, templates: Any = None,
+                          classes: Any = None, manifest: dict[str, Any] | None = None,
+                          incidents: Any = None, rubric: Any = None) -> dict[str, Any]:
+    """Findings enriched with their PoC template, remediation and related exploits, ordered by severity.
+
+    Args:
+        findings: Hypotheses by id, or a list of findings carrying their ``id``
+        project: Project name, for the title
+        chain: Chain of the code, for template matching and chain-specific fixes
+        source: Audited source root; absolute finding paths under it are made relative
+        templates: A TemplateLoader
+        classes: A ClassLoader
+        manifest: Reproducibility manifest, embedded by the formatters
+        incidents: An IncidentLoader, to cite the real-world exploits each finding is like
+        rubric: A SeverityRubric; findings are then ranked by their score, and their severity is the score's
+            band rather than the one they declare (kept as ``reported_severity``)
+    """
+    items = [{"id": hid, **f} for hid, f in findings.items()] if isinstance(findings, dict) else list(findings)
+    entries = []
+    models = number_occurrences([Finding.from_hypothesis(finding) for finding in items])
+    for finding, model in zip(items, models):
+        props = finding.get("properties") or {}
+        vuln_type = finding.get("vulnerability_type") or ""
+        vuln_class = classes.resolve(vuln_type, chain) if classes and vuln_type else None
+        template = _template_for(finding, chain, templates, vuln_class) if templates else None
+        remediation = (
+            (template.remediation if template else None)
+            or (vuln_class.fix_for(chain) if vuln_class else None)
+            or model.remediation
+        )
+        entry: dict[str, Any] = {
+            "id": finding.get("id"),
+            "title": finding.get("title") or vuln_type or finding.get("id"),
+            "severity": _severity(finding),
+            "confidence": finding.get("confidence"),
+            "status": finding.get("status"),
+            "reported_severity": _severity(finding),
+            "score": None,
+            "vulnerability_type": vuln_type or None,
+            "class": vuln_class.id if vuln_class else None,
+            "description": finding.get("description", ""),
+            "files": finding_files(finding, Path(source).resolve() if source else None),
+            "lines": [n for n in props.get("affected_lines") or [] if isinstance(n, int)],
+            "detector": model.detector,
+            # Detector hits only; LLM hypotheses have no rule
+            "rule_id": model.rule_id if model.detector else None,
+            "fingerprint": model.fingerprint,
+            "remediation": remediation,
+            "template": None,
+            "incidents": [],
+        }
+        if rubric:
+            scored = rubric.score(finding, vuln_class.id if vuln_class else None)
+            entry["severity"], entry["score"] = scored.severity, scored.to_dict()
+        if template:
+            entry["template"] = {"id": template.id, "chain": template.chain.value, "harness": template.harness.id,
+                                 "snippet": template.exploit_snippet()}
+        if incidents:
+            related = incidents.for_finding(finding, chain, vuln_class.id if vuln_class else None, template)
+            entry["incidents"] = [{"id": i.id, "name": i.name, "date": i.date, "chain": i.chain,
+                                   "loss_usd": i.loss_usd, "references": i.references} for i in related]
+        entries.append(entry)
+    entries.sort(key=lambda e: (SEVERITIES.index(e["severity"]), -(e["score"] or {}).get("score", 0.0),
+                                -_score(e["confidence"]), str(e["id"])))
+    counts = {severity: sum(e["severity"] == severity for e in entries) for severity in SEVERITIES}
+    return {"project": project, "chain": chain, "findings": entries, "counts": counts, "manifest": manifest,
+            "findings_raw": items, "source": str(source) if source else None}
+
+
+@register_formatter
+class JsonFormatter(ReportFormatter):
+    """Machine-readable findings for pipelines."""
+
+    name = "json"
+    suffix = ".json"
+
+    def format(self, report: dict[str, Any]) -> str:
+        data = {
+            "format": FINDINGS_FORMAT, "version": FINDINGS_VERSION, "project": report["project"],
+            "chain": report["chain"], "counts": report["counts"], "findings": report["findings"],
+        }
+        if report.get("manifest"):
+            data["manifest"] = report["manifest"]
+        return json.dumps(data, indent=2)
+
+
+@register_formatter
+class MarkdownFormatter(ReportFormatter):
+    """Audit-style report grouping findings by severity."""
+
+    name = "markdown"
+    suffix = ".md"
+
+    def _finding(self, number: str, entry: dict[str, Any], chain: str | None) -> list[str]:
+        lines = [f"#### {number}. {entry['title']}", ""]
+        if entry["files"]:
+            where = ", ".join(f"`{f}`" for f in entry["files"])
+            if entry["lines"]:
+                where += f" (line{'s' if len(entry['lines']) > 1 else ''} {', '.join(map(str, entry['lines']))})"
+            lines.append(f"- **Location:** {where}")
+        if isinstance(entry["confidence"], (int, float)):
+            lines.append(f"- **Confidence:** {entry['confidence']:.0%}")
+        elif entry["confidence"]:
+            lines.append(f"- **Confidence:** {entry['confidence']}")
+        if entry["detector"]:
+            rule = f" (`{entry['rule_id']}`)" if entry.get("rule_id") else ""
+            lines.append(f"- **Detector:** {entry['detector']}{rule}")
+        if entry["class"]:
+            lines.append(f"- **Class:** {entry['class']}")
+        score = entry.get("score")
+        if score and score["basis"] == "vector":
+            vector = ", ".join(f"{metric} {level}" for metric, level in score["vector"].items())
+            lines.append(f"- **Score:** {score['score']:.1f} ({vector})")
+        if lines[-1]:
+            lines.append("")
+        if entry["description"]:
+            lines += [str(entry["description"]).strip(), ""]
+        if entry["remediation"]:
+            lines += [f"**Remediation:** {entry['remediation']}", ""]
+        if entry.get("incidents"):
+            cited = [
+                (f"[{i['name']}]({i['references'][0]})" if i["references"] else i["name"]) + f" ({i['date'][:4]})"
+                for i in entry["incidents"]
+            ]
+            lines += [f"**Related incidents:** {'; '.join(cited)}", ""]
+        template = entry["template"]
+        if template:
+            fence = _FENCES.get(template["chain"] or chain or "", "")
+            lines += [f"**Proof of concept** (template `{template['id']}`, {template['harness']}):", "",
+                      f"```{fence}", template["snippet"], "```", ""]
+        return lines
+
+    def format(self, report: dict[str, Any]) -> str:
+        entries = report["findings"]
+        lines = [f"# Security Findings: {report['project']}", ""]
+        if report["chain"]:
+            lines += [f"Chain: {report['chain']}", ""]
+        lines += ["## Summary", "", "| Severity | Findings |", "|---|---:|"]
+        lines += [f"| {severity.title()} | {count} |" for severity, count in report["counts"].items()]
+        lines += [f"| **Total** | **{len(entries)}** |", "", "## Findings", ""]
+        if not entries:
+            lines += ["No findings.", ""]
+        for severity in SEVERITIES:
+            group = [e for e in entries if e["severity"] == severity]
+            if not group:
+                continue
+            lines += [f"### {severity.title()}", ""]
+            for index, entry in enumerate(group, 1):
+                lines += self._finding(f"{severity[0].upper()}-{index}", entry, report["chain"])
+        content = "\n".join(lines).rstrip() + "\n"
+        return embed_manifest(content, report["manifest"], "markdown") if report.get("manifest") else content
+
+
+@register_formatter
+class SarifFormatter(ReportFormatter):
+    """SARIF 2.1.0 log of the findings."""
+
+    name = "sarif"
+    suffix = ".sarif"
+
+    def format(self, report: dict[str, Any]) -> str:
+        from extensions.knowledge.class_loader import ClassLoader
+
+        source = Path(report["source"]) if report.get("source") else None
+        return json.dumps(build_sarif(report["findings_raw"], source, report["chain"], classes=ClassLoader()), indent=2)
@@ -0,0 +1,307 @@
+"""
+GitHub pull request annotations.
+
+Posts findings to GitHub through its REST API, so a workflow running
+``scan`` reports them where the change is reviewed:
+
+- ``checks``: a completed check run on the commit, one annotation per
+  finding at its first affected line (failure for critical and high,
+  warning for medium, notice below); the run fails when any finding is
+  high or worse. GitHub takes 50 annotations per request, so the rest are
+  added by updating the run.
+- ``review``: a review on the pull request, with a comment on each finding
+  whose line is in the pull request's diff (GitHub rejects comments
+  elsewhere); the others are listed in the review's body. Findings already
+  commented on by an earlier review are left out, so re-running on every
+  push does not repeat them.
+
+Every annotation and comment carries the suggested fix: the matched
+template's remediation, else the vulnerability class's fix guidance (see
+build_findings_report). Findings are read from a findings report, whose
+paths are relative to the scanned workspace; ``prefix`` makes them
+relative to the repository.
+
+The token comes from ``GITHUB_TOKEN`` (or ``GH_TOKEN``); the repository,
+commit and pull request from the variables GitHub Actions sets, so a
+workflow step needs nothing but ``permissions: checks: write`` or
+``pull-requests: write``.
+"""
+
+import json
+import os
+import subprocess
+import urllib.error
+import urllib.parse
+import urllib.request
+from collections.abc import Callable, Mapping
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from extensions.static.diff_scope import parse_diff
+
+from .formatters import SEVERITIES
+
+GITHUB_API_URL = "https://api.github.com"
+TOKEN_ENV = ("GITHUB_TOKEN", "GH_TOKEN")
+MODES = ("checks", "review")
+CHECK_NAME = "baskerville"
+# Most annotations GitHub takes in one check-run request
+ANNOTATION_BATCH = 50
+
+LEVELS = {"critical": "failure", "high": "failure", "medium": "warning", "low": "notice", "info": "notice"}
+
+# (method, url, body, headers, timeout) -> (status, headers, body)
+Transport = Callable[[str, str, bytes | None, dict[str, str], float], tuple[int, dict[str, str], bytes]]
+
+
+class GitHubError(Exception):
+    """Missing credentials or context, or a request GitHub refused."""
+
+
+def urllib_request(method: str, url: str, body: bytes | None, headers: dict[str, str],
+                   timeout: float) -> tuple[int, dict[str, str], bytes]:
+    req = urllib.request.Request(url, data=body, headers=headers, method=method)
+    try:
+        with urllib.request.urlopen(req, timeout=timeout) as resp:
+            return resp.status, dict(resp.headers), resp.read()
+    except urllib.error.HTTPError as e:
+        return e.code, dict(e.headers or {}), e.read() or b""
+
+
+def _git(workspace: Path, *args: str) -> str | None:
+    root = workspace if workspace.is_dir() else workspace.parent
+    try:
+        return subprocess.run(["git", "-C", str(root), *args], capture_output=True, text=True,
+                              check=True).stdout.strip()
+    except (OSError, subprocess.CalledProcessError):
+        return None
+
+
+def repo_prefix(workspace: Path) -> str:
+    """Path of ``workspace`` inside its git repository ("" at the root or outside one)."""
+    return (_git(Path(workspace).resolve(), "rev-parse", "--show-prefix") or "").strip("/")
+
+
+@dataclass
+class GitHubContext:
+    """Where findings are posted: repository, commit, pull request and credentials."""
+
+    repo: str  # owner/name
+    sha: str
+    token: str
+    pr: int | None = None
+    api_url: str = GITHUB_API_URL
+
+    @classmethod
+    def from_env(cls, env: Mapping[str, str] | None = None, repo: str | None = None, sha: str | None = None,
+                 pr: int | None = None, workspace: Path | None = None) -> "GitHubContext":
+        """Context from GitHub Actions' variables; arguments given win.
+
+        In a ``pull_request`` workflow the pull request and its head commit
+        come from the event payload (``GITHUB_SHA`` is then the merge
+        commit); elsewhere the commit falls back to the workspace's HEAD.
+
+        Raises:
+            GitHubError: If the token, repository or commit cannot be found
+        """
+        env = os.environ if env is None else env
+        token = next((env[name] for name in TOKEN_ENV if env.get(name)), None)
+        if not token:
+            raise GitHubError(f"set {' or '.join(TOKEN_ENV)} to post to GitHub")
+        repo = repo or env.get("GITHUB_REPOSITORY")
+        if not repo or repo.count("/") != 1:
+            raise GitHubError("repository unknown: set GITHUB_REPOSITORY to owner/name")
+        event: dict[str, Any] = {}
+        if env.get("GITHUB_EVENT_PATH"):
+            try:
+                event = json.loads(Path(env["GITHUB_EVENT_PATH"]).read_text())
+            except (OSError, ValueError):
+                event = {}
+        pull = event.get("pull_request") if isinstance(event, dict) else None
+        pull = pull if isinstance(pull, dict) else {}
+        pr = pr or pull.get("number")
+        sha = (sha or (pull.get("head") or {}).get("sha") or env.get("GITHUB_SHA")
+               or (_git(Path(workspace).resolve(), "rev-parse", "HEAD") if workspace else None))
+        if not sha:
+            raise GitHubError("commit unknown: set GITHUB_SHA")
+        api_url = (env.get("GITHUB_API_URL") or GITHUB_API_URL).rstrip("/")
+        return cls(repo, sha, token, int(pr) if pr else None, api_url)
+
+
+def _path(entry: dict[str, Any], prefix: str) -> str | None:
+    files = entry.get("files") or []
+    if not files:
+        return None
+    return f"{prefix}/{files[0]}" if prefix else files[0]
+
+
+def _marker(entry: dict[str, Any]) -> str:
+    return f"<!-- baskerville:{entry.get('fingerprint') or entry.get('id')} -->"
+
+
+def _heading(entry: dict[str, Any]) -> str:
+    code = f" ({entry['rule_id']})" if entry.get("rule_id") else ""
+    return f"[{entry['severity'].upper()}] {entry['title']}{code}"
+
+
+def _fix(entry: dict[str, Any]) -> list[str]:
+    lines = [f"Suggested fix: {entry['remediation']}"] if entry.get("remediation") else []
+    if entry.get("template"):
+        template = entry["template"]["id"]
+        lines.append(f"PoC template: {template} (./baskerville.py kb render {template})")
+    return lines
+
+
+def annotation(entry: dict[str, Any], prefix: str = "") -> dict[str, Any] | None:
+    """A check-run annotation for a findings-report entry; None if it has no file."""
+    path = _path(entry, prefix)
+    if path is None:
+        return None
+    line = (entry.get("lines") or [1])[0]
+    message = "\n\n".join(part for part in [entry.get("description") or "", "\n".join(_fix(entry))] if part)
+    return {"path": path, "start_line": line, "end_line": line,
+            "annotation_level": LEVELS.get(entry["severity"], "warning"),
+            "title": _heading(entry)[:255], "message": (message or entry["title"])[:60000]}
+
+
+def comment_body(entry: dict[str, Any]) -> str:
+    """Markdown of a review comment for a findings-report entry."""
+    parts = [f"**{_heading(entry)}**"]
+    if entry.get("description"):
+        parts.append(entry["description"])
+    if entry.get("remediation"):
+        parts.append(f"**Suggested fix:** {entry['remediation']}")
+    if entry.get("template"):
+        template = entry["template"]["id"]
+        parts.append(f"PoC template: `{template}` (`./baskerville.py kb render {template}`)")
+    return "\n\n".join(parts) + f"\n\n{_marker(entry)}"
+
+
+def _summary(entries: list[dict[str, Any]]) -> str:
+    counts = [f"{n} {s}" for s in SEVERITIES if (n := sum(e["severity"] == s for e in entries))]
+    return f"{len(entries)} findings ({', '.join(counts)})" if entries else "No findings"
+
+
+class GitHubClient:
+    """Posts a findings report to a commit or pull request."""
+
+    def __init__(self, context: GitHubContext, transport: Transport | None = None, timeout: float = 30.0):
+        """Initialize client.
+
+        Args:
+            context: Repository, commit, pull request and token
+            transport: HTTP transport (tests substitute a fake)
+            timeout: Per-request timeout in seconds
+        """
+        self.context = context
+        self.transport = transport or urllib_request
+        self.timeout = timeout
+
+    def _request(self, method: str, path: str, payload: dict[str, Any] | None = None) -> Any:
+        url = f"{self.context.api_url}/repos/{self.context.repo}/{path}"
+        headers = {"Accept": "application/vnd.github+json", "Authorization": f"Bearer {self.context.token}",
+                   "X-GitHub-Api-Version": "2022-11-28", "User-Agent": CHECK_NAME}
+        body = None
+        if payload is not None:
+            body = json.dumps(payload).encode()
+            headers["Content-Type"] = "application/json"
+        try:
+            status, _, data = self.transport(method, url, body, headers, self.timeout)
+        except (OSError, ValueError) as e:
+            raise GitHubError(f"{method} {path}: {urllib.parse.urlsplit(url).netloc} unreachable: {e}") from e
+        try:
+            parsed = json.loads(data) if data else None
+        except ValueError:
+            parsed = None
+        if not 200 <= status < 300:
+            detail = parsed.get("message") if isinstance(parsed, dict) else data[:200].decode(errors="replace")
+            raise GitHubError(f"{method} {path}: HTTP {status}: {detail}")
+        return parsed
+
+    def _pages(self, path: str, per_page: int = 100, limit: int = 30) -> list[dict[str, Any]]:
+        items: list[dict[str, Any]] = []
+        for page in range(1, limit + 1):
+            batch = self._request("GET", f"{path}?per_page={per_page}&page={page}") or []
+            items += [item for item in batch if isinstance(item, dict)]
+            if len(batch) < per_page:
+                break
+        return items
+
+    def create_check_run(self, report: dict[str, Any], prefix: str = "", name: str = CHECK_NAME,
+                         fail_on: str = "never") -> dict[str, Any]:
+        """A completed check run on the context's commit, annotated with the report's findings.
+
+        The run fails when a finding is ``fail_on`` severe or worse (never, for ``never``).
+        """
+        entries = report["findings"]
+        annotations = [a for a in (annotation(e, prefix) for e in entries) if a]
+        failing = fail_on != "never" and any(SEVERITIES.index(e["severity"]) <= SEVERITIES.index(fail_on)
+                                             for e in entries)
+        conclusion = "failure" if failing else "neutral" if entries else "success"
+        unlocated = [e for e in entries if not e.get("files")]
+        summary = _summary(entries)
+        if unlocated:
+            summary += "\n\nWithout a location:\n" + "\n".join(f"- {_heading(e)}" for e in unlocated)
+        output = {"title": _summary(entries), "summary": summary}
+        run = self._request("POST", "check-runs", {
+            "name": name, "head_sha": self.context.sha, "status": "completed", "conclusion": conclusion,
+            "output": {**output, "annotations": annotations[:ANNOTATION_BATCH]},
+        })
+        for start in range(ANNOTATION_BATCH, len(annotations), ANNOTATION_BATCH):
+            self._request("PATCH", f"check-runs/{run['id']}",
+                          {"output": {**output, "annotations": annotations[start:start + ANNOTATION_BATCH]}})
+        return {"mode": "checks", "id": run.get("id"), "url": run.get("html_url"), "conclusion": conclusion,
+                "annotations": len(annotations)}
+
+    def commentable_lines(self) -> dict[str, set[int]]:
+        """Lines of each file a review comment can be left on: those in the pull request's diff."""
+        lines: dict[str, set[int]] = {}
+        for changed in self._pages(f"pulls/{self._pr()}/files"):
+            if changed.get("patch"):
+                lines.update(parse_diff(f"+++ b/{changed['filename']}\n{changed['patch']}"))
+        return lines
+
+    def _pr(self) -> int:
+        if not self.context.pr:
+            raise GitHubError("pull request unknown: run on a pull_request event or give its number")
+        return self.context.pr
+
+    def post_review(self, report: dict[str, Any], prefix: str = "") -> dict[str, Any]:
+        """A review on the context's pull request; findings it already commented on are skipped."""
+        pr = self._pr()
+        posted = "\n".join(c.get("body") or "" for c in self._pages(f"pulls/{pr}/comments"))
+        posted += "\n" + "\n".join(r.get("body") or "" for r in self._pages(f"pulls/{pr}/reviews"))
+        entries = [e for e in report["findings"] if _marker(e) not in posted]
+        commentable = self.commentable_lines()
+        comments, listed = [], []
+        for entry in entries:
+            path = _path(entry, prefix)
+            line = next((n for n in entry.get("lines") or [] if n in commentable.get(path or "", ())), None)
+            if line is None:
+                listed.append(entry)
+            else:
+                comments.append({"path": path, "line": line, "side": "RIGHT", "body": comment_body(entry)})
+        result = {"mode": "review", "pr": pr, "comments": len(comments), "listed": len(listed),
+                  "skipped": len(report["findings"]) - len(entries), "url": None}
+        if not entries:
+            return result
+        body = [f"baskerville: {_summary(entries)}"]
+        if listed:
+            body += ["", "Outside the lines this pull request changes:"]
+            for entry in listed:
+                path = _path(entry, prefix)
+                where = f" in `{path}:{(entry.get('lines') or [1])[0]}`" if path else ""
+                fix = f" Suggested fix: {entry['remediation']}" if entry.get("remediation") else ""
+                body.append(f"- **{_heading(entry)}**{where}.{fix} {_marker(entry)}")
+        review = self._request("POST", f"pulls/{pr}/reviews", {"commit_id": self.context.sha, "event": "COMMENT",
+                                                               "body": "\n".join(body), "comments": comments})
+        return {**result, "url": (review or {}).get("html_url")}
+
+    def post(self, report: dict[str, Any], mode: str, prefix: str = "", fail_on: str = "never") -> dict[str, Any]:
+        """Post ``report`` as a check run (failing at ``fail_on``) or a review (see MODES)."""
+        if mode == "checks":
+            return self.create_check_run(report, prefix, fail_on=fail_on)
+        if mode == "review":
+            return self.post_review(report, prefix)
+        raise ValueError(f"Unknown GitHub mode '{mode}' (expected one of {', '.join(MODES)})")
@@ -0,0 +1,219 @@
+"""
+Risk heat-map aggregation.
+
+Scores every source file, and every directory above it, by the findings that
+touch it: each finding contributes its severity weight (scaled by status and
+confidence) split evenly across its files. Dividing by size gives a density
+per thousand lines, and ``heat`` normalizes density to 0..1 across the tree,
+so leads can see where review time is best spent in a large protocol.
+
+The export is a nested tree (`name` / `children`, leaves carry `loc` as the
+natural treemap area) plus a flat list of files sorted hottest first; both
+load directly into d3-hierarchy or similar treemap/heat-map tooling.
+"""
+
+import os
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Any
+
+HEATMAP_FORMAT = "baskerville-heatmap"
+HEATMAP_VERSION = 1
+
+SEVERITY_WEIGHTS = {"critical": 10.0, "high": 5.0, "medium": 2.0, "low": 1.0, "info": 0.25, "informational": 0.25}
+# Confirmed findings count fully; unreviewed ones by their confidence
+STATUS_FACTORS = {"confirmed": 1.0, "supported": 0.8, "rejected": 0.0, "refuted": 0.0}
+
+SOURCE_SUFFIXES = {".sol", ".vy", ".rs", ".move", ".cairo", ".fc", ".func", ".tact", ".yul", ".huff", ".py", ".go"}
+_SKIP_DIRS = {".git", "node_modules", "target", "lib", "out", "cache", "build", "test", "tests", ".anchor", "artifacts"}
+
+
+def finding_weight(finding: dict[str, Any]) -> float:
+    """Score a single hypothesis/finding dict contributes before splitting across files."""
+    severity = str(finding.get("severity", "medium")).lower()
+    weight = SEVERITY_WEIGHTS.get(severity, SEVERITY_WEIGHTS["medium"])
+    status = str(finding.get("status", "proposed")).lower()
+    if status in STATUS_FACTORS:
+        return weight * STATUS_FACTORS[status]
+    try:
+        confidence = float(finding.get("confidence", 0.5))
+    except (TypeError, ValueError):
+        confidence = 0.5
+    return weight * max(0.0, min(confidence, 1.0))
+
+
+def finding_files(finding: dict[str, Any], root: Path | None = None) -> list[str]:
+    """Repository-relative files a finding points at."""
+    props = finding.get("properties") or {}
+    raw = list(props.get("source_files") or [])
+    for ev in finding.get("supporting_evidence") or []:
+        if isinstance(ev, dict) and isinstance(ev.get("file"), str):
+            raw.append(ev["file"])
+    files: list[str] = []
+    for entry in raw:
+        if not isinstance(entry, str) or not entry:
+            continue
+        path = entry.split("#", 1)[0].split(":", 1)[0]
+        if root is not None and Path(path).is_absolute() and Path(path).is_relative_to(root):
+            path = os.path.relpath(path, root)
+        # Anything else absolute is taken as repo-rooted ("/src/A.sol")
+        path = Path(path).as_posix().lstrip("/")
+        if path and path not in files:
+            files.append(path)
+    return files
+
+
+def count_lines(path: Path) -> int:
+    """Non-blank line count (0 if unreadable)."""
+    try:
+        return sum(1 for line in path.read_text(errors="replace").splitlines() if line.strip())
+    except OSError:
+        return 0
+
+
+def discover_source_files(root: Path) -> list[str]:
+    """Source files under ``root`` (excluding dependencies, tests and build output)."""
+    root = Path(root)
+    found = []
+    for dirpath, dirnames, filenames in os.walk(root):
+        dirnames[:] = sorted(d for d in dirnames if d not in _SKIP_DIRS and not d.startswith("."))
+        for name in sorted(filenames):
+            if Path(name).suffix in SOURCE_SUFFIXES:
+                found.append(Path(os.path.relpath(Path(dirpath) / name, root)).as_posix())
+    return found
+
+
+class HeatMapBuilder:
+    """Aggregates findings into per-file and per-directory risk scores."""
+
+    def __init__(self, root: Path | None = None, include_unreferenced: bool = True):
+        """Initialize the builder.
+
+        Args:
+            root: Source root used for line counts and file discovery
+            include_unreferenced: Add files without findings (heat 0) so the
+                treemap shows cold areas too
+        """
+        self.root = Path(root) if root else None
+        self.include_unreferenced = include_unreferenced
+        self.files: dict[str, dict[str, Any]] = {}
+        self.unlocated = 0
+
+    def _file(self, rel: str) -> dict[str, Any]:
+        if rel not in self.files:
+            loc = count_lines(self.root / rel) if self.root else 0
+            self.files[rel] = {"path": rel, "loc": loc, "findings": 0, "score": 0.0, "by_severity": {}, "finding_ids": []}
+        return self.files[rel]
+
+    def add_findings(self, findings: dict[str, dict] | list[dict]) -> None:
+        """Add hypothesis-shaped findings (a hypotheses.json mapping or a list)."""
+        items = findings.items() if isinstance(findings, dict) else ((f.get("id", ""), f) for f in findings)
+        for fid, finding in items:
+            weight = finding_weight(finding)
+            if weight <= 0:
+                continue
+            files = finding_files(finding, self.root)
+            if not files:
+                self.unlocated += 1
+                continue
+            severity = str(finding.get("severity", "medium")).lower()
+            share = weight / len(files)
+            for rel in files:
+                entry = self._file(rel)
+                entry["findings"] += 1
+                entry["score"] += share
+                entry["by_severity"][severity] = entry["by_severity"].get(severity, 0) + 1
+                if fid:
+                    entry["finding_ids"].append(fid)
+
+    def build(self) -> dict[str, Any]:
+        """Heat-map document with the nested tree and the flat hottest-first list."""
+        if self.root and self.include_unreferenced:
+            for rel in discover_source_files(self.root):
+                self._file(rel)
+        for entry in self.files.values():
+            entry["score"] = round(entry["score"], 4)
+            entry["density"] = _density(entry["score"], entry["loc"])
+
+        tree = self._tree()
+        max_heat = max((_heat_basis(e) for e in self.files.values()), default=0.0)
+        _apply_heat(tree, max_heat)
+        files = sorted(self.files.values(), key=lambda e: (-_heat_basis(e), e["path"]))
+        for entry in files:
+            entry["heat"] = round(_heat_basis(entry) / max_heat, 4) if max_heat else 0.0
+        return {
+            "format": HEATMAP_FORMAT,
+            "version": HEATMAP_VERSION,
+            "generated_at": datetime.now(timezone.utc).isoformat(),
+            "root_path": str(self.root) if self.root else None,
+            "weights": {"severity": SEVERITY_WEIGHTS, "status": STATUS_FACTORS},
+            "unlocated_findings": self.unlocated,
+            "tree": tree,
+            "files": files,
+        }
+
+    def _tree(self) -> dict[str, Any]:
+        root: dict[str, Any] = {"name": self.root.name if self.root else ".", "path": "", "children": []}
+        dirs = {"": root}
+        for rel in sorted(self.files):
+            parts = rel.split("/")
+            parent = root
+            for i in range(1, len(parts)):
+                key = "/".join(parts[:i])
+                if key not in dirs:
+                    node = {"name": parts[i - 1], "path": key, "children": []}
+                    dirs[key] = node
+                    parent["children"].append(node)
+                parent = dirs[key]
+            leaf = self.files[rel]
+            parent["children"].append({
+                "name": parts[-1],
+                "path": rel,
+                "loc": leaf["loc"],
+                "findings": leaf["findings"],
+                "score": leaf["score"],
+                "density": leaf["density"],
+                "by_severity": leaf["by_severity"],
+            })
+        _aggregate(root)
+        return root
+
+
+def _density(score: float, loc: int) -> float | None:
+    """Score per thousand lines (None when size is unknown)."""
+    return round(score * 1000 / loc, 4) if loc else None
+
+
+def _heat_basis(node: dict[str, Any]) -> float:
+    return node["density"] if node.get("density") is not None else node["score"]
+
+
+def _aggregate(node: dict[str, Any]) -> None:
+    """Sum leaf metrics into directory nodes (depth first)."""
+    if "children" not in node:
+        return
+    node.update({"loc": 0, "findings": 0, "score": 0.0, "by_severity": {}})
+    for child in node["children"]:
+        _aggregate(child)
+        node["loc"] += child["loc"]
+        node["findings"] += child["findings"]
+        node["score"] += child["score"]
+        for sev, count in child["by_severity"].items():
+            node["by_severity"][sev] = node["by_severity"].get(sev, 0) + count
+    node["score"] = round(node["score"], 4)
+    node["density"] = _density(node["score"], node["loc"])
+
+
+def _apply_heat(node: dict[str, Any], max_heat: float) -> None:
+    # Directories holding unsized files can exceed the file maximum; clamp to 1
+    node["heat"] = round(min(_heat_basis(node) / max_heat, 1.0), 4) if max_heat else 0.0
+    for child in node.get("children", []):
+        _apply_heat(child, max_heat)
+
+
+def build_heatmap(findings: dict[str, dict] | list[dict], root: Path | None = None,
+                  include_unreferenced: bool = True) -> dict[str, Any]:
+    """Convenience wrapper around HeatMapBuilder."""
+    builder = HeatMapBuilder(root, include_unreferenced=include_unreferenced)
+    builder.add_findings(findings)
+    return builder.build()
@@ -0,0 +1,219 @@
+"""
+Reproducibility manifest.
+
+Every generated output carries a manifest recording what produced it: tool
+versions, the knowledge-base (rule-set) version, the detectors that ran and
+their versions, the audited commit, the configuration (secrets redacted) and
+its hash, the execution backends, and the command with its parameters.
+With that, ``baskerville reproduce <output>`` can re-run the same command
+under the same configuration and show what drifted since. When the audited
+workspace pins content bundles in ``baskerville.toml``, their digests are
+recorded too and folded into the rule-set version.
+
+The manifest deliberately carries no timestamp, so two runs of the same
+configuration produce the same manifest.
+
+Embedding by output type:
+- json: a top-level ``"manifest"`` key
+- html: ``<script type="application/json" id="baskerville-manifest">``
+- markdown: a trailing ``<!-- baskerville-manifest ... -->`` comment
+- anything else (csv): a ``<file>.manifest.json`` sidecar
+"""
+
+import hashlib
+import json
+import platform
+import re
+from pathlib import Path
+from typing import Any
+
+from .signing import ruleset_version, source_commit
+
+MANIFEST_FORMAT = "baskerville-manifest"
+MANIFEST_VERSION = 1
+
+BASKERVILLE_VERSION = "1.0.0"
+HOUND_VERSION = "2.0.0"
+
+MANIFEST_SUFFIX = ".manifest.json"
+
+REDACTED = "<redacted>"
+_SECRET_KEY_RE = re.compile(r"(?:api_?key|token|secret|password|credentials?)$", re.IGNORECASE)
+
+_HTML_TAG = '<script type="application/json" id="baskerville-manifest">'
+_HTML_RE = re.compile(re.escape(_HTML_TAG) + r"(.*?)</script>", re.DOTALL)
+_MD_OPEN = "<!-- baskerville-manifest"
+_MD_RE = re.compile(re.escape(_MD_OPEN) + r"\n(.*?)\n-->", re.DOTALL)
+
+
+def redact_config(config: Any) -> Any:
+    """Copy of ``config`` with secret values (API keys, tokens) replaced."""
+    if isinstance(config, dict):
+        return {
+            k: REDACTED if _SECRET_KEY_RE.search(str(k)) and isinstance(v, str) and v else redact_config(v)
+            for k, v in config.items()
+        }
+    if isinstance(config, list):
+        return [redact_config(v) for v in config]
+    return config
+
+
+def restore_secrets(recorded: Any, current: Any) -> Any:
+    """Fill redacted values in a recorded config from the current one."""
+    if isinstance(recorded, dict):
+        current = current if isinstance(current, dict) else {}
+        restored = {}
+        for k, v in recorded.items():
+            if v == REDACTED:
+                if k in current:
+                    restored[k] = current[k]
+            else:
+                restored[k] = restore_secrets(v, current.get(k))
+        return restored
+    if isinstance(recorded, list):
+        return [restore_secrets(v, None) for v in recorded]
+    return recorded
+
+
+def config_hash(config: dict[str, Any] | None) -> str:
+    """SHA-256 over the canonical JSON of the redacted config."""
+    canonical = json.dumps(redact_config(config or {}), sort_keys=True, separators=(",", ":"), default=str)
+    return f"sha256:{hashlib.sha256(canonical.encode()).hexdigest()}"
+
+
+def model_backends(config: dict[str, Any] | None) -> dict[str, str]:
+    """Role → "provider/model" for every configured LLM role."""
+    backends = {}
+    for role, spec in sorted(((config or {}).get("models") or {}).items()):
+        if isinstance(spec, dict) and spec.get("model"):
+            provider = spec.get("provider")
+            backends[role] = f"{provider}/{spec['model']}" if provider else str(spec["model"])
+    return backends
+
+
+def project_detectors(project_dir: Path | None) -> dict[str, str | None]:
+    """Detector → version from the project's last static analysis run."""
+    if project_dir is None:
+        return {}
+    metadata_file = Path(project_dir) / "static_analysis" / "static_metadata.json"
+    if not metadata_file.exists():
+        return {}
+    try:
+        tools = json.loads(metadata_file.read_text()).get("tools", {})
+    except (OSError, ValueError):
+        return {}
+    return {name: meta.get("version") for name, meta in sorted(tools.items()) if meta.get("available", True)}
+
+
+def build_manifest(command: str, params: dict[str, Any] | None = None, source: Path | str | None = None,
+                   config: dict[str, Any] | None = None, detectors: dict[str, str | None] | None = None,
+                   knowledge_dir: Path | None = None) -> dict[str, Any]:
+    """Manifest for one output.
+
+    Args:
+        command: CLI command that produced the output (e.g. "reporting heatmap")
+        params: Parameters the command was invoked with
+        source: Audited source tree (for the target commit)
+        config: Loaded configuration (redacted before embedding)
+        detectors: Detector name → version that contributed findings
+        knowledge_dir: Knowledge base override (default: the bundled one)
+    """
+    from extensions.knowledge.bundles import load_pins
+
+    target = None
+    pins = []
+    if source is not None:
+        target = {"path": str(source), **(source_commit(Path(source)) or {"commit": None, "dirty": None})}
+        pins = load_pins(Path(source))
+    return {
+        "format": MANIFEST_FORMAT,
+        "version": MANIFEST_VERSION,
+        "baskerville_version": BASKERVILLE_VERSION,
+        "hound_version": HOUND_VERSION,
+        "kb_version": ruleset_version(knowledge_dir, [p.digest for p in pins]),
+        # Content bundles the audited workspace pins in baskerville.toml
+        **({"bundles": [{"name": p.name, "digest": p.digest} for p in pins]} if pins else {}),
+        "detectors": dict(sorted((detectors or {}).items())),
+        "target": target,
+        "config_hash": config_hash(config),
+        "config": redact_config(config or {}),
+        "backends": {
+            "python": platform.python_version(),
+            "platform": platform.system().lower(),
+            "models": model_backends(config),
+        },
+        "command": {"name": command, "params": dict(params or {})},
+    }
+
+
+def embed_manifest(content: str, manifest: dict[str, Any], fmt: str) -> str:
+    """Embed ``manifest`` in rendered ``content`` (html or markdown), replacing any existing one."""
+    body = json.dumps(manifest, indent=2, sort_keys=True)
+    if fmt == "html":
+        # Keep "</script>" in config strings from closing the tag
+        escaped = body.replace("</", "<\\/")
+        block = f"{_HTML_TAG}{escaped}</script>"
+        if _HTML_RE.search(content):
+            return _HTML_RE.sub(lambda _: block, content, count=1)
+        idx = content.rfind("</body>")
+        return content[:idx] + block + "\n" + content[idx:] if idx != -1 else content + "\n" + block + "\n"
+    if fmt in ("markdown", "md"):
+        escaped = body.replace("-->", "--\\u003e")
+        block = f"{_MD_OPEN}\n{escaped}\n-->"
+        if _MD_RE.search(content):
+            return _MD_RE.sub(lambda _: block, content, count=1)
+        return content.rstrip("\n") + "\n\n" + block + "\n"
+    raise ValueError(f"Cannot embed a manifest in format: {fmt}")
+
+
+def write_sidecar(output: Path, manifest: dict[str, Any]) -> Path:
+    """Write the manifest next to an output that cannot embed one."""
+    sidecar = Path(output).with_name(Path(output).name + MANIFEST_SUFFIX)
+    sidecar.write_text(json.dumps(manifest, indent=2, sort_keys=True) + "\n")
+    return sidecar
+
+
+def extract_manifest(path: Path) -> dict[str, Any] | None:
+    """Read the manifest of an output file (embedded json/html/markdown, or a sidecar)."""
+    sidecar = Path(path).with_name(Path(path).name + MANIFEST_SUFFIX)
+    if sidecar.exists():
+        try:
+            return json.loads(sidecar.read_text())
+        except ValueError:
+            return None
+    text = Path(path).read_text(errors="replace")
+    if Path(path).suffix == ".json":
+        try:
+            data = json.loads(text)
+        except ValueError:
+            return None
+        manifest = data.get("manifest") if isinstance(data, dict) else None
+        return manifest if isinstance(manifest, dict) else None
+    for pattern in (_HTML_RE, _MD_RE):
+        m = pattern.search(text)
+        if m:
+            try:
+                return json.loads(m.group(1))
+            except ValueError:
+                return None
+    return None
+
+
+def compare_manifests(recorded: dict[str, Any], current: dict[str, Any]) -> list[tuple[str, Any, Any]]:
+    """(field, recorded, current) for every reproducibility-relevant difference."""
+    def target_commit(m):
+        return (m.get("target") or {}).get("commit")
+
+    checks = [
+        ("baskerville_version", recorded.get("baskerville_version"), current.get("baskerville_version")),
+        ("hound_version", recorded.get("hound_version"), current.get("hound_version")),
+        ("kb_version", recorded.get("kb_version"), current.get("kb_version")),
+        ("target.commit", target_commit(recorded), target_commit(current)),
+        ("backends.python", recorded.get("backends", {}).get("python"), current.get("backends", {}).get("python")),
+    ]
+    diffs = [(name, old, new) for name, old, new in checks if old != new]
+    old_detectors, new_detectors = recorded.get("detectors") or {}, current.get("detectors") or {}
+    for name in sorted(set(old_detectors) | set(new_detectors)):
+        if old_detectors.get(name) != new_detectors.get(name):
+            diffs.append((f"detectors.{name}", old_detectors.get(name), new_detectors.get(name)))
+    return diffs
@@ -0,0 +1,96 @@
+"""
+Codebase-metrics report section.
+
+Renders the metrics saved by ``ir metrics <project>`` (``metrics.json`` in
+the project directory) as a "Codebase Metrics" section: per-program size
+and attack-surface statistics, and the instructions ranked first for manual
+review. The section is inserted ahead of the findings.
+"""
+
+import html
+import json
+from pathlib import Path
+from typing import Any
+
+from .economic import insert_before_findings
+
+_COLUMNS = [
+    ("Program", None), ("Framework", None), ("LoC", "loc"), ("Instructions", "instructions"),
+    ("Privileged", "privileged_instructions"), ("Unchecked accounts", "unchecked_accounts"),
+    ("CPI fan-out", "cpi_fan_out"), ("Max complexity", "max_complexity"),
+]
+
+
+def load_metrics(project_dir: Path | None) -> dict[str, Any] | None:
+    """The metrics document saved in the project directory, if any."""
+    if project_dir is None:
+        return None
+    try:
+        data = json.loads((Path(project_dir) / "metrics.json").read_text())
+    except (OSError, ValueError):
+        return None
+    return data if isinstance(data, dict) and data.get("format") == "baskerville-metrics" else None
+
+
+def _rows(metrics: dict[str, Any]) -> list[list[str]]:
+    rows = []
+    for program in metrics["programs"]:
+        summary = {**program["summary"], "loc": program["loc"]}
+        rows.append([program["name"], program["framework"]] + [f"{summary[key]:,}" for _, key in _COLUMNS[2:]])
+    return rows
+
+
+def _intro(metrics: dict[str, Any]) -> str:
+    t = metrics["totals"]
+    return (f"{t['programs']} program(s), {t['loc']:,} lines of code, {t['instructions']} instructions of which "
+            f"{t['privileged_instructions']} are privileged; {t['unchecked_accounts']} unchecked account slots.")
+
+
+def _location(entry: dict[str, Any]) -> str:
+    return f"{entry['file']}:{entry['line']}"
+
+
+def metrics_markdown(metrics: dict[str, Any]) -> str:
+    lines = ["## Codebase Metrics", "", _intro(metrics), ""]
+    lines.append("| " + " | ".join(name for name, _ in _COLUMNS) + " |")
+    lines.append("|---" * len(_COLUMNS) + "|")
+    lines += ["| " + " | ".join(row) + " |" for row in _rows(metrics)]
+    if metrics.get("review_order"):
+        lines += ["", "### Review Priority", "", "| # | Instruction | Location | Priority |", "|---|---|---|---|"]
+        lines += [f"| {i} | {e['program']}.{e['instruction']} | {_location(e)} | {e['priority']} |"
+                  for i, e in enumerate(metrics["review_order"], 1)]
+    return "\n".join(lines) + "\n"
+
+
+def metrics_html(metrics: dict[str, Any]) -> str:
+    header = "".join(f"<th>{name}</th>" for name, _ in _COLUMNS)
+    body = "".join("<tr>" + "".join(f"<td>{html.escape(cell)}</td>" for cell in row) + "</tr>"
+                   for row in _rows(metrics))
+    parts = ['<div class="section">', "<h2>Codebase Metrics</h2>", f"<p>{html.escape(_intro(metrics))}</p>",
+             f"<table><thead><tr>{header}</tr></thead><tbody>{body}</tbody></table>"]
+    if metrics.get("review_order"):
+        review = "".join(
+            f"<tr><td>{i}</td><td>{html.escape(e['program'])}.{html.escape(e['instruction'])}</td>"
+            f"<td>{html.escape(_location(e))}</td><td>{e['priority']}</td></tr>"
+            for i, e in enumerate(metrics["review_order"], 1)
+        )
+        parts += ["<h3>Review Priority</h3>",
+                  "<table><thead><tr><th>#</th><th>Instruction</th><th>Location</th><th>Priority</th></tr></thead>"
+                  f"<tbody>{review}</tbody></table>"]
+    return "\n".join(parts + ["</div>"]) + "\n"
+
+
+def metrics_section(metrics: dict[str, Any], fmt: str) -> str:
+    """The whole "Codebase Metrics" section in ``fmt`` (html or markdown)."""
+    if fmt == "html":
+        return metrics_html(metrics)
+    if fmt in ("markdown", "md"):
+        return metrics_markdown(metrics) + "\n"
+    raise ValueError(f"Cannot render a codebase-metrics section in format: {fmt}")
+
+
+def embed_metrics(content: str, metrics: dict[str, Any] | None, fmt: str) -> str:
+    """Insert the codebase-metrics section ahead of the findings of a rendered report."""
+    if not metrics:
+        return content
+    return insert_before_findings(content, metrics_section(metrics, fmt), fmt)
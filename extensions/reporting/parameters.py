@@ -0,0 +1,84 @@
+"""
+Protocol parameters report appendix.
+
+Lists the hardcoded protocol parameters the `protocol-params` static pass
+extracted (fees, LTV ratios, cooldowns, max supply, ...) with the value
+each reads as, the sane range for its kind and where it is set, so the
+client can confirm every value is the one they intend. Values outside
+their range are also findings; unclear ones (a bare `5` that may be
+percent or bps) are flagged for the client to state the unit.
+"""
+
+import html
+import json
+from pathlib import Path
+from typing import Any
+
+from .economic import append_section
+
+PARAMETER_COLUMNS = ["Parameter", "Kind", "Value", "Reads as", "Sane range", "Location", "Status"]
+_STATUS = {"ok": "in range", "out-of-range": "OUT OF RANGE", "unclear": "unit unclear"}
+
+
+def load_parameters(project_dir: Path | None) -> list[dict[str, Any]]:
+    """Parameters from the project's last static analysis run."""
+    if project_dir is None:
+        return []
+    metadata_file = Path(project_dir) / "static_analysis" / "static_metadata.json"
+    try:
+        metadata = json.loads(metadata_file.read_text())
+    except (OSError, ValueError):
+        return []
+    tool = (metadata.get("tools") or {}).get("protocol-params") or {}
+    return [p for p in tool.get("parameters") or [] if isinstance(p, dict)]
+
+
+def parameter_rows(parameters: list[dict[str, Any]]) -> list[list[str]]:
+    """Table rows (see PARAMETER_COLUMNS), out-of-range first, then unclear ones."""
+    order = {"out-of-range": 0, "unclear": 1, "ok": 2}
+    ranked = sorted(parameters, key=lambda p: (order.get(p.get("status"), 3), p.get("file", ""), p.get("line", 0)))
+    return [[p["name"], p.get("kind_name") or p.get("kind", ""), p.get("raw", ""),
+             f"{p.get('display', '?')} ({p.get('scale', '')})", p.get("range", ""),
+             f"{p.get('file')}:{p.get('line')}", _STATUS.get(p.get("status"), p.get("status", ""))]
+            for p in ranked]
+
+
+_INTRO = ("Protocol parameters hardcoded in the audited code, for the client to confirm each is intended. "
+          "Values outside the sane range are also reported as findings.")
+
+
+def parameters_markdown(parameters: list[dict[str, Any]]) -> str:
+    def cell(text: str) -> str:
+        return text.replace("|", "\\|")
+
+    lines = ["## Appendix: Protocol Parameters", "", _INTRO, ""]
+    lines.append("| " + " | ".join(PARAMETER_COLUMNS) + " |")
+    lines.append("|---" * len(PARAMETER_COLUMNS) + "|")
+    lines += ["| " + " | ".join(cell(c) for c in row) + " |" for row in parameter_rows(parameters)]
+    return "\n".join(lines) + "\n"
+
+
+def parameters_html(parameters: list[dict[str, Any]]) -> str:
+    header = "".join(f"<th>{name}</th>" for name in PARAMETER_COLUMNS)
+    body = "".join("<tr>" + "".join(f"<td>{html.escape(cell)}</td>" for cell in row) + "</tr>"
+                   for row in parameter_rows(parameters))
+    return "\n".join([
+        '<div class="section">', "<h2>Appendix: Protocol Parameters</h2>", f"<p>{html.escape(_INTRO)}</p>",
+        f"<table><thead><tr>{header}</tr></thead><tbody>{body}</tbody></table>", "</div>",
+    ]) + "\n"
+
+
+def parameters_section(parameters: list[dict[str, Any]], fmt: str) -> str:
+    """The whole "Protocol Parameters" appendix in ``fmt`` (html or markdown)."""
+    if fmt == "html":
+        return parameters_html(parameters)
+    if fmt in ("markdown", "md"):
+        return parameters_markdown(parameters)
+    raise ValueError(f"Cannot render a protocol-parameters appendix in format: {fmt}")
+
+
+def embed_parameters(content: str, parameters: list[dict[str, Any]], fmt: str) -> str:
+    """Append the protocol-parameters appendix to a rendered report."""
+    if not parameters:
+        return content
+    return append_section(content, parameters_section(parameters, fmt), fmt)
@@ -0,0 +1,236 @@
+"""
+Aggregate program risk scoring.
+
+Combines four components into one 0..100 score per program, with the
+breakdown kept next to it so the number can be argued with:
+
+- findings: severity-weighted findings located in the program's sources
+  (status and confidence scaled as in the heat-map)
+- centralization: how much of the program only an authority can call, and
+  how concentrated that power is in a single role
+- dependencies: dependency-audit advisories (`cargo-audit`) for the
+  workspace the program is built in
+- attack_surface: unchecked accounts, CPI fan-out, state-changing
+  instructions anyone can call and handler complexity (``ir.metrics``)
+
+Raw component values saturate towards 100 (``1 - e^(-raw / scale)``), so one
+more finding matters less once a program is already bad. Weights, severity
+weights, scales and grade bands come from a ``RiskModel``, loadable from
+YAML, so a portfolio is ranked with the same model every time.
+"""
+
+import math
+import os
+from dataclasses import asdict, dataclass, field
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Any
+
+import yaml
+
+from extensions.ir.metrics import is_privileged, program_metrics
+from extensions.ir.model import Program
+
+from .heatmap import SEVERITY_WEIGHTS, STATUS_FACTORS, finding_files
+
+RISK_FORMAT = "baskerville-risk"
+RISK_VERSION = 1
+COMPONENTS = ("findings", "centralization", "dependencies", "attack_surface")
+DEPENDENCY_TOOLS = {"cargo-audit"}
+# Chains whose programs build against the workspace's Cargo.lock
+RUST_CHAINS = {"solana", "cosmwasm"}
+
+
+@dataclass
+class RiskModel:
+    """Weights and scales of the risk score."""
+
+    weights: dict[str, float] = field(default_factory=lambda: {
+        "findings": 0.45, "centralization": 0.15, "dependencies": 0.15, "attack_surface": 0.25})
+    severity_weights: dict[str, float] = field(default_factory=lambda: dict(SEVERITY_WEIGHTS))
+    # Raw value at which a component reaches 63 (1 - 1/e)
+    scales: dict[str, float] = field(default_factory=lambda: {
+        "findings": 10.0, "dependencies": 5.0, "attack_surface": 20.0})
+    # Lowest score of each grade, highest first
+    grades: dict[str, float] = field(default_factory=lambda: {"critical": 75, "high": 50, "medium": 25, "low": 0})
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "RiskModel":
+        """Model from a (partial) mapping; unspecified values keep their defaults."""
+        unknown = set(data) - {"weights", "severity_weights", "scales", "grades"}
+        if unknown:
+            raise ValueError(f"unknown risk model keys: {', '.join(sorted(unknown))}")
+        model = cls()
+        for key in ("weights", "severity_weights", "scales", "grades"):
+            values = data.get(key) or {}
+            if not isinstance(values, dict):
+                raise ValueError(f"risk model {key} must be a mapping")
+            if key in ("weights", "scales"):
+                bad = set(values) - set(COMPONENTS)
+                if bad:
+                    raise ValueError(f"unknown risk components in {key}: {', '.join(sorted(bad))}")
+            try:
+                getattr(model, key).update({str(k).lower(): float(v) for k, v in values.items()})
+            except (TypeError, ValueError) as e:
+                raise ValueError(f"risk model {key} values must be numbers") from e
+        if sum(model.weights.values()) <= 0:
+            raise ValueError("risk model weights must not all be zero")
+        model.grades = dict(sorted(model.grades.items(), key=lambda item: -item[1]))
+        return model
+
+    def saturate(self, component: str, raw: float) -> float:
+        return round(100 * (1 - math.exp(-raw / self.scales[component])), 1) if raw > 0 else 0.0
+
+    def grade(self, score: float) -> str:
+        return next((name for name, floor in self.grades.items() if score >= floor), list(self.grades)[-1])
+
+    def finding_weight(self, finding: dict[str, Any]) -> float:
+        severity = str(finding.get("severity", "medium")).lower()
+        weight = self.severity_weights.get(severity, self.severity_weights.get("medium", 2.0))
+        status = str(finding.get("status", "proposed")).lower()
+        if status in STATUS_FACTORS:
+            return weight * STATUS_FACTORS[status]
+        try:
+            confidence = float(finding.get("confidence", 0.5))
+        except (TypeError, ValueError):
+            confidence = 0.5
+        return weight * max(0.0, min(confidence, 1.0))
+
+
+def load_risk_model(path: Path | None) -> RiskModel:
+    """Risk model from a YAML file, or the defaults without one."""
+    if path is None:
+        return RiskModel()
+    try:
+        data = yaml.safe_load(Path(path).read_text()) or {}
+    except (OSError, yaml.YAMLError) as e:
+        raise ValueError(f"cannot read risk model {path}: {e}") from e
+    if not isinstance(data, dict):
+        raise ValueError(f"risk model {path} must be a mapping")
+    return RiskModel.from_dict(data)
+
+
+def _program_dir(program: Program, source: Path | None) -> str:
+    root = Path(program.root)
+    if source is not None and root.is_absolute() == Path(source).is_absolute():
+        rel = os.path.relpath(root, source)
+        return "" if rel == "." else Path(rel).as_posix()
+    return root.as_posix()
+
+
+def attribute_findings(programs: list[Program], findings: list[dict[str, Any]],
+                       source: Path | None = None) -> tuple[dict[str, list[dict]], list[dict]]:
+    """Findings by program name, and the ones no program owns.
+
+    A file belongs to the deepest program root holding it, or to the program
+    listing it among its (root-relative) sources. Findings without files go
+    to the program when there is only one.
+    """
+    dirs = sorted(((p, _program_dir(p, source)) for p in programs), key=lambda item: -len(item[1]))
+    owned: dict[str, list[dict]] = {p.name: [] for p in programs}
+    rest = []
+    for finding in findings:
+        files = finding_files(finding, source)
+        names = []
+        for rel in files:
+            owner = next((p for p, d in dirs if not d or rel == d or rel.startswith(d + "/")), None)
+            owner = owner or next((p for p in programs if rel in p.source_files), None)
+            if owner and owner.name not in names:
+                names.append(owner.name)
+        if not files and len(programs) == 1:
+            names = [programs[0].name]
+        for name in names:
+            owned[name].append(finding)
+        if not names:
+            rest.append(finding)
+    return owned, rest
+
+
+def _severity_counts(findings: list[dict[str, Any]], model: RiskModel) -> dict[str, int]:
+    counts: dict[str, int] = {}
+    for f in findings:
+        if model.finding_weight(f) > 0:
+            severity = str(f.get("severity", "medium")).lower()
+            counts[severity] = counts.get(severity, 0) + 1
+    return counts
+
+
+def _centralization(program: Program) -> dict[str, Any]:
+    from extensions.static.privilege_paths import enforced_signers
+
+    privileged = [ix for ix in program.instructions if is_privileged(ix)]
+    roles: dict[str, int] = {}
+    for ix in privileged:
+        for name in enforced_signers(ix) or ["<modifier>"]:
+            roles[name] = roles.get(name, 0) + 1
+    share = len(privileged) / len(program.instructions) if program.instructions else 0.0
+    dominant = max(roles.items(), key=lambda item: item[1]) if roles else None
+    concentration = dominant[1] / len(privileged) if dominant else 0.0
+    return {
+        "score": round(100 * share * concentration, 1),
+        "privileged_instructions": len(privileged),
+        "instructions": len(program.instructions),
+        "roles": roles,
+        "dominant_role": dominant[0] if dominant else None,
+    }
+
+
+def score_program(program: Program, findings: list[dict[str, Any]], advisories: list[dict[str, Any]],
+                  model: RiskModel | None = None) -> dict[str, Any]:
+    """Score one program from its findings and its workspace's dependency advisories."""
+    model = model or RiskModel()
+    measured = program_metrics(program)
+    metrics = measured["summary"]
+    open_writes = sum(1 for i in measured["instructions"] if not i["privileged"] and i["writable"])
+    surface_raw = (3 * metrics["unchecked_accounts"] + 2 * metrics["cpi_fan_out"] + open_writes
+                   + max(0.0, metrics["mean_complexity"] - 1))
+    findings_raw = sum(model.finding_weight(f) for f in findings)
+    dependency_raw = sum(model.finding_weight(f) for f in advisories)
+    components = {
+        "findings": {"score": model.saturate("findings", findings_raw), "raw": round(findings_raw, 2),
+                     "by_severity": _severity_counts(findings, model)},
+        "centralization": _centralization(program),
+        "dependencies": {"score": model.saturate("dependencies", dependency_raw), "raw": round(dependency_raw, 2),
+                         "advisories": sorted({str(f.get("properties", {}).get("advisory_id") or f.get("title", ""))
+                                               for f in advisories if model.finding_weight(f) > 0})},
+        "attack_surface": {"score": model.saturate("attack_surface", surface_raw), "raw": round(surface_raw, 2),
+                           "unchecked_accounts": metrics["unchecked_accounts"], "cpi_fan_out": metrics["cpi_fan_out"],
+                           "open_state_changing": open_writes, "mean_complexity": metrics["mean_complexity"]},
+    }
+    total_weight = sum(model.weights.get(c, 0.0) for c in COMPONENTS)
+    for name, component in components.items():
+        component["weight"] = model.weights.get(name, 0.0)
+        component["contribution"] = round(component["score"] * component["weight"] / total_weight, 1)
+    score = round(sum(c["contribution"] for c in components.values()), 1)
+    return {"program": program.name, "chain": program.chain, "framework": program.framework,
+            "score": score, "grade": model.grade(score), "components": components}
+
+
+def score_project(programs: list[Program], findings: list[dict[str, Any]], source: Path | None = None,
+                  model: RiskModel | None = None, project: str | None = None) -> dict[str, Any]:
+    """Scores for the programs of one workspace; dependency advisories apply to all its Rust programs."""
+    model = model or RiskModel()
+    advisories = [f for f in findings if (f.get("properties") or {}).get("source_tool") in DEPENDENCY_TOOLS]
+    located = [f for f in findings if f not in advisories]
+    owned, rest = attribute_findings(programs, located, source)
+    scores = []
+    for program in programs:
+        entry = score_program(program, owned[program.name], advisories if program.chain in RUST_CHAINS else [], model)
+        scores.append({"project": project, **entry})
+    return {"project": project, "source": str(source) if source else None, "programs": scores,
+            "unattributed_findings": len(rest)}
+
+
+def build_risk_report(projects: list[dict[str, Any]], model: RiskModel | None = None) -> dict[str, Any]:
+    """Ranking document over ``score_project`` results, riskiest program first."""
+    model = model or RiskModel()
+    ranked = sorted((p for project in projects for p in project["programs"]),
+                    key=lambda p: (-p["score"], p["project"] or "", p["program"]))
+    return {
+        "format": RISK_FORMAT,
+        "version": RISK_VERSION,
+        "generated_at": datetime.now(timezone.utc).isoformat(),
+        "model": asdict(model),
+        "projects": [{k: v for k, v in project.items() if k != "programs"} for project in projects],
+        "programs": ranked,
+    }
@@ -0,0 +1,159 @@
+"""
+SARIF 2.1.0 export of detector hits.
+
+Serializes static analysis hits into a SARIF log that GitHub code scanning
+(``upload-sarif``) and other code-scanning dashboards ingest directly. A log
+has one run; each rule id (``<detector>/<vulnerability-type>``, see
+extensions/static/rules.py) becomes a reportingDescriptor and each hit a
+result:
+
+- level: critical and high are errors, medium warnings, low and info notes;
+  ``security-severity`` on the rule lets GitHub rank alerts the same way
+- location: every source file of the hit, with the region at its first
+  affected line, relative to the scanned root (``%SRCROOT%``)
+- fix suggestion: the detector's own (``suggested_fix``), else the
+  vulnerability class's fix guidance; SARIF ``fixes`` need concrete edits,
+  so the suggestion goes in the rule's help and the result's properties
+- fingerprint: the hit id, so re-uploads keep tracking the same alert
+- code: the rule's stable code (``SOL-001-MISSING-SIGNER``), on the rule
+  and each result, when it has one
+
+Hits are read through the shared finding model (extensions/static/finding.py).
+"""
+
+import json
+from pathlib import Path
+from typing import Any
+from urllib.parse import quote
+
+from extensions.static.finding import Finding
+
+from .heatmap import finding_files
+from .manifest import BASKERVILLE_VERSION
+
+SARIF_VERSION = "2.1.0"
+SARIF_SCHEMA = "https://json.schemastore.org/sarif-2.1.0.json"
+
+LEVELS = {"critical": "error", "high": "error", "medium": "warning", "low": "note", "info": "note",
+          "informational": "note"}
+# GitHub's security-severity bands: >= 9 critical, >= 7 high, >= 4 medium, else low
+SECURITY_SEVERITY = {"critical": 9.5, "high": 8.0, "medium": 5.5, "low": 3.0, "info": 1.0, "informational": 1.0}
+
+
+def _region(finding: Finding) -> dict[str, int] | None:
+    span = finding.location
+    if span is None or span.start_line is None:
+        return None
+    region = {"startLine": span.start_line}
+    for key, value in (("endLine", span.end_line), ("startColumn", span.start_column),
+                       ("endColumn", span.end_column)):
+        if value is not None:
+            region[key] = value
+    return region
+
+
+def _locations(hit: dict[str, Any], finding: Finding, root: Path | None) -> list[dict[str, Any]]:
+    region = _region(finding)
+    locations = []
+    for path in finding_files(hit, root):
+        physical: dict[str, Any] = {"artifactLocation": {"uri": quote(path), "uriBaseId": "%SRCROOT%"}}
+        if region:
+            physical["region"] = region
+        locations.append({"physicalLocation": physical})
+    return locations
+
+
+def _rule(rule: str, findings: list[Finding], fix: str | None, vuln_class: Any) -> dict[str, Any]:
+    first = findings[0]
+    worst = max((f.severity for f in findings), key=SECURITY_SEVERITY.__getitem__)
+    descriptor: dict[str, Any] = {
+        "id": rule,
+        "name": "".join(part.title() for part in rule.replace("/", "-").split("-") if part),
+        "shortDescription": {"text": first.vulnerability_type or rule},
+        "fullDescription": {"text": vuln_class.description if vuln_class else first.title},
+        "defaultConfiguration": {"level": LEVELS[worst]},
+        "properties": {
+            "security-severity": f"{SECURITY_SEVERITY[worst]:.1f}",
+            "tags": ["security", *(vuln_class.taxonomy if vuln_class else [])],
+            "precision": {"syntactic": "low", "semantic": "medium", "dataflow": "high",
+                          "verified": "very-high"}.get(first.properties.get("precision"), "medium"),
+        },
+    }
+    if first.rule_id != rule:
+        descriptor["properties"]["code"] = first.rule_id
+    if fix:
+        descriptor["help"] = {"text": f"Fix: {fix}", "markdown": f"**Fix:** {fix}"}
+    return descriptor
+
+
+def build_sarif(hits: list[dict[str, Any]], root: Path | None = None, chain: str | None = None,
+                detectors: dict[str, dict[str, Any]] | None = None, classes: Any = None) -> dict[str, Any]:
+    """SARIF 2.1.0 log for detector hits.
+
+    Args:
+        hits: Detector hits (pipeline hypotheses)
+        root: Scanned root; absolute paths under it are made relative
+        chain: Chain of the code, for chain-specific fix guidance
+        detectors: Pipeline tool metadata, recorded with the run
+        classes: A ClassLoader, for fix guidance where a detector has none
+    """
+    root = Path(root).resolve() if root else None
+    by_rule: dict[str, list[tuple[dict[str, Any], Finding]]] = {}
+    for hit in hits:
+        finding = Finding.from_hypothesis(hit)
+        by_rule.setdefault(finding.rule, []).append((hit, finding))
+
+    rules, results = [], []
+    for index, (rule, rule_hits) in enumerate(sorted(by_rule.items())):
+        first = rule_hits[0][1]
+        vuln_class = classes.resolve(first.vulnerability_type, chain) if classes else None
+        class_fix = vuln_class.fix_for(chain) if vuln_class else None
+        rules.append(_rule(rule, [f for _, f in rule_hits], class_fix or first.remediation, vuln_class))
+        for hit, finding in rule_hits:
+            fix = finding.remediation or class_fix
+            result: dict[str, Any] = {
+                "ruleId": rule,
+                "ruleIndex": index,
+                "level": LEVELS[finding.severity],
+                "message": {"text": finding.title},
+                "locations": _locations(hit, finding, root),
+                "properties": {"severity": finding.severity, "confidence": hit.get("confidence"),
+                               "detector": finding.detector},
+            }
+            if finding.description:
+                result["message"]["markdown"] = f"**{finding.title}**\n\n{finding.description}"
+            if finding.rule_id != rule:
+                result["properties"]["code"] = finding.rule_id
+            if fix:
+                result["properties"]["suggestedFix"] = fix
+            if finding.id:
+                result["partialFingerprints"] = {"baskervilleHitId/v1": finding.id}
+            results.append(result)
+
+    run: dict[str, Any] = {
+        "tool": {"driver": {"name": "baskerville", "version": BASKERVILLE_VERSION,
+                            "semanticVersion": BASKERVILLE_VERSION, "rules": rules}},
+        "results": results,
+        "columnKind": "utf16CodeUnits",
+        "properties": {"chain": chain},
+    }
+    if root:
+        run["originalUriBaseIds"] = {"SRCROOT": {"uri": root.as_uri() + "/"}}
+    if detectors:
+        run["invocations"] = [{
+            "executionSuccessful": True,
+            "toolExecutionNotifications": [
+                {"level": "warning", "message": {"text": f"{name} not run: {meta.get('error', 'not installed')}"}}
+                for name, meta in detectors.items() if meta.get("available") is False
+            ],
+        }]
+        run["properties"]["detectors"] = {name: meta.get("version") for name, meta in detectors.items()
+                                          if meta.get("available", True)}
+    return {"$schema": SARIF_SCHEMA, "version": SARIF_VERSION, "runs": [run]}
+
+
+def write_sarif(log: dict[str, Any], output: Path) -> Path:
+    output = Path(output)
+    output.parent.mkdir(parents=True, exist_ok=True)
+    output.write_text(json.dumps(log, indent=2))
+    return output
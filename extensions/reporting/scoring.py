@@ -0,0 +1,181 @@
+"""
+Severity scoring of findings.
+
+A CVSS-like score from 0 to 10, computed from three attributes of a finding
+instead of the severity its detector, template or hypothesis happened to
+declare:
+
+- impact: what the attacker gets (``funds`` stolen, funds ``frozen``,
+  protocol ``state`` corrupted, ``availability`` lost, or ``none``)
+- privileges: who can exploit it (``none`` - anyone, any ``user`` holding a
+  position or account, or only a ``privileged`` role)
+- complexity: ``low`` when one transaction does it, ``high`` when it needs
+  capital, timing or conditions the attacker does not control
+
+The score is 10 times the product of the attributes' weights, and severity
+comes from the band it falls in. A finding's attributes come from its
+``properties.severity_vector``, then from its vulnerability class's vector
+in the rubric, then from the rubric's defaults; a finding none of them say
+anything about keeps its declared severity. Weights, class vectors and
+bands come from a ``SeverityRubric``, loadable from YAML, so every report
+ranks findings by the same rules.
+"""
+
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Any
+
+import yaml
+
+from extensions.static.finding import SEVERITIES, normalize_severity
+
+METRICS = {
+    "impact": {"funds": 1.0, "frozen": 0.7, "state": 0.5, "availability": 0.4, "none": 0.0},
+    "privileges": {"none": 1.0, "user": 0.7, "privileged": 0.3},
+    "complexity": {"low": 1.0, "high": 0.8},
+}
+
+# Attributes of a finding of each vulnerability class (classes/core.yaml)
+CLASS_VECTORS = {
+    "unauthenticated-privileged-action": {"impact": "funds", "privileges": "none", "complexity": "low"},
+    "reentrant-state-inconsistency": {"impact": "funds", "privileges": "none", "complexity": "high"},
+    "account-type-confusion": {"impact": "funds", "privileges": "none", "complexity": "low"},
+    "aliased-mutable-accounts": {"impact": "funds", "privileges": "user", "complexity": "low"},
+    "shared-state-race": {"impact": "state", "privileges": "none", "complexity": "high"},
+    "derived-address-collision": {"impact": "funds", "privileges": "none", "complexity": "high"},
+    "price-manipulation": {"impact": "funds", "privileges": "none", "complexity": "high"},
+    "share-inflation": {"impact": "funds", "privileges": "none", "complexity": "high"},
+    "unbounded-resource-consumption": {"impact": "availability", "privileges": "none", "complexity": "low"},
+    "unchecked-arithmetic": {"impact": "funds", "privileges": "none", "complexity": "high"},
+    "repeatable-initialization": {"impact": "funds", "privileges": "none", "complexity": "low"},
+    "untrusted-delegated-execution": {"impact": "funds", "privileges": "none", "complexity": "low"},
+    "unmigrated-state-layout": {"impact": "frozen", "privileges": "none", "complexity": "high"},
+    "untrusted-program-id": {"impact": "funds", "privileges": "none", "complexity": "low"},
+    "unchecked-call-result": {"impact": "funds", "privileges": "none", "complexity": "high"},
+    "signature-replay": {"impact": "funds", "privileges": "user", "complexity": "low"},
+}
+
+RUBRIC_KEYS = ("metrics", "defaults", "classes", "bands")
+
+
+@dataclass
+class SeverityScore:
+    """A finding's score, the severity band it falls in and the attributes it came from."""
+
+    score: float
+    severity: str
+    vector: dict[str, str] = field(default_factory=dict)
+    # Where each attribute came from: finding, class or default
+    sources: dict[str, str] = field(default_factory=dict)
+    # "vector" when scored from attributes, "reported" when the finding's own severity was kept
+    basis: str = "vector"
+
+    def to_dict(self) -> dict[str, Any]:
+        return asdict(self)
+
+
+@dataclass
+class SeverityRubric:
+    """Attribute weights, class vectors and severity bands of the score."""
+
+    metrics: dict[str, dict[str, float]] = field(default_factory=lambda: {m: dict(w) for m, w in METRICS.items()})
+    # Level of an attribute neither the finding nor its class gives
+    defaults: dict[str, str] = field(default_factory=lambda: {"privileges": "none", "complexity": "low"})
+    classes: dict[str, dict[str, str]] = field(default_factory=lambda: {c: dict(v) for c, v in CLASS_VECTORS.items()})
+    # Lowest score of each severity, highest first; GitHub's security-severity bands by default
+    bands: dict[str, float] = field(default_factory=lambda: {
+        "critical": 9.0, "high": 7.0, "medium": 4.0, "low": 0.1, "info": 0.0})
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "SeverityRubric":
+        """Rubric from a (partial) mapping; unspecified values keep their defaults.
+
+        ``metrics`` adds levels to an attribute or changes their weights; a
+        new attribute takes part in every score, so give it a level in
+        ``defaults``. ``classes`` entries are merged into the built-in vectors.
+        """
+        unknown = set(data) - set(RUBRIC_KEYS)
+        if unknown:
+            raise ValueError(f"unknown severity rubric keys: {', '.join(sorted(unknown))}")
+        rubric = cls()
+        for key in RUBRIC_KEYS:
+            if not isinstance(data.get(key) or {}, dict):
+                raise ValueError(f"severity rubric {key} must be a mapping")
+        try:
+            for metric, levels in (data.get("metrics") or {}).items():
+                if not isinstance(levels, dict) or not levels:
+                    raise ValueError(f"severity rubric metric {metric} must map levels to weights")
+                rubric.metrics.setdefault(str(metric), {}).update({str(k): float(v) for k, v in levels.items()})
+            rubric.bands.update({str(k).lower(): float(v) for k, v in (data.get("bands") or {}).items()})
+        except (TypeError, ValueError) as e:
+            raise ValueError(f"severity rubric weights and bands must be numbers ({e})") from e
+        rubric.defaults.update({str(k): str(v) for k, v in (data.get("defaults") or {}).items()})
+        for class_id, vector in (data.get("classes") or {}).items():
+            if not isinstance(vector, dict):
+                raise ValueError(f"severity rubric vector of class {class_id} must be a mapping")
+            rubric.classes.setdefault(str(class_id), {}).update({str(k): str(v) for k, v in vector.items()})
+        for name, vector in [("defaults", rubric.defaults), *rubric.classes.items()]:
+            rubric.check(vector, name)
+        unknown = set(rubric.bands) - set(SEVERITIES)
+        if unknown:
+            raise ValueError(f"unknown severities in severity rubric bands: {', '.join(sorted(unknown))}")
+        rubric.bands = dict(sorted(rubric.bands.items(), key=lambda item: -item[1]))
+        return rubric
+
+    def check(self, vector: dict[str, str], where: str = "vector") -> None:
+        """Raise ValueError if ``vector`` names an unknown attribute or level."""
+        for metric, level in vector.items():
+            if metric not in self.metrics:
+                raise ValueError(f"{where}: unknown attribute {metric} (expected one of {', '.join(self.metrics)})")
+            if level not in self.metrics[metric]:
+                raise ValueError(f"{where}: unknown {metric} level {level} "
+                                 f"(expected one of {', '.join(self.metrics[metric])})")
+
+    def band(self, score: float) -> str:
+        return next((name for name, floor in self.bands.items() if score >= floor), list(self.bands)[-1])
+
+    def vector(self, finding: dict[str, Any], vuln_class: str | None = None) -> tuple[dict[str, str], dict[str, str]]:
+        """A finding's attributes and where each came from; attributes nothing gives are left out."""
+        given = (finding.get("properties") or {}).get("severity_vector") or {}
+        if not isinstance(given, dict):
+            given = {}
+        vector, sources = {}, {}
+        for metric in self.metrics:
+            for source, values in (("finding", given), ("class", self.classes.get(vuln_class or "") or {}),
+                                   ("default", self.defaults)):
+                level = values.get(metric)
+                if level is not None and str(level).lower() in self.metrics[metric]:
+                    vector[metric], sources[metric] = str(level).lower(), source
+                    break
+        return vector, sources
+
+    def score(self, finding: dict[str, Any], vuln_class: str | None = None) -> SeverityScore:
+        """Score a hypothesis or detector hit.
+
+        Args:
+            finding: The finding, with any ``properties.severity_vector``
+            vuln_class: Class id the finding resolved to (see ClassLoader.resolve)
+        """
+        vector, sources = self.vector(finding, vuln_class)
+        if len(vector) < len(self.metrics) or all(s == "default" for s in sources.values()):
+            severity = normalize_severity(finding.get("severity"))
+            floor = self.bands.get(severity, min(self.bands.values()))
+            return SeverityScore(floor, severity, vector, sources, basis="reported")
+        score = 10.0
+        for metric, level in vector.items():
+            score *= self.metrics[metric][level]
+        score = round(score, 1)
+        return SeverityScore(score, self.band(score), vector, sources)
+
+
+def load_severity_rubric(path: Path | None) -> SeverityRubric:
+    """Severity rubric from a YAML file, or the defaults without one."""
+    if path is None:
+        return SeverityRubric()
+    try:
+        data = yaml.safe_load(Path(path).read_text()) or {}
+    except (OSError, yaml.YAMLError) as e:
+        raise ValueError(f"cannot read severity rubric {path}: {e}") from e
+    if not isinstance(data, dict):
+        raise ValueError(f"severity rubric {path} must be a mapping")
+    return SeverityRubric.from_dict(data)
@@ -0,0 +1,287 @@
+"""
+Detached signing of report artifacts.
+
+A signature covers a statement listing the SHA-256 of every signed file plus
+the context needed to reproduce it: the rule-set version (a digest of the
+knowledge base checklists/templates/tips in use) and the commit of the
+audited source. The statement is wrapped in a DSSE envelope (the format
+sigstore uses) and signed with ed25519, so a client holding the auditor's
+public key can check that a delivered report and its findings files were not
+altered after generation.
+
+    report.html
+    report.html.sig.json     {"payloadType", "payload", "signatures": [...]}
+
+Requires the optional `cryptography` package (`pip install .[signing]`).
+"""
+
+import base64
+import hashlib
+import json
+import os
+import subprocess
+from dataclasses import dataclass, field
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Any
+
+PAYLOAD_TYPE = "application/vnd.baskerville.statement+json"
+STATEMENT_TYPE = "https://baskerville.dev/attestation/report/v1"
+SIGNATURE_SUFFIX = ".sig.json"
+
+_KNOWLEDGE_DIR = Path(__file__).resolve().parent.parent / "knowledge"
+_RULESET_DIRS = ("checklists", "templates", "tips")
+
+
+class SigningError(Exception):
+    """Signing or key handling failed."""
+    pass
+
+
+def _ed25519():
+    try:
+        from cryptography.hazmat.primitives.asymmetric import ed25519
+    except ImportError as e:
+        raise SigningError("Signing requires the 'cryptography' package (pip install cryptography)") from e
+    return ed25519
+
+
+def sha256_file(path: Path) -> str:
+    digest = hashlib.sha256()
+    with open(path, "rb") as f:
+        for chunk in iter(lambda: f.read(65536), b""):
+            digest.update(chunk)
+    return digest.hexdigest()
+
+
+def ruleset_version(knowledge_dir: Path | None = None, bundles: list[str] | None = None) -> str:
+    """Content digest of the rule set (knowledge base checklists, templates, tips, plus pinned bundles)."""
+    base = Path(knowledge_dir) if knowledge_dir else _KNOWLEDGE_DIR
+    digest = hashlib.sha256()
+    for sub in _RULESET_DIRS:
+        root = base / sub
+        if not root.is_dir():
+            continue
+        for path in sorted(p for p in root.rglob("*") if p.is_file()):
+            digest.update(path.relative_to(base).as_posix().encode() + b"\0")
+            digest.update(path.read_bytes())
+    # Bundles are content-addressed, so their digests stand for their files
+    for bundle in sorted(bundles or []):
+        digest.update(b"bundle\0" + bundle.encode() + b"\0")
+    return f"sha256:{digest.hexdigest()[:16]}"
+
+
+def pinned_ruleset_version(source: Path | None = None, knowledge_dir: Path | None = None) -> str:
+    """``ruleset_version`` with the bundles ``source``'s baskerville.toml pins."""
+    from extensions.knowledge.bundles import load_pins
+
+    pins = load_pins(Path(source)) if source is not None else []
+    return ruleset_version(knowledge_dir, [p.digest for p in pins])
+
+
+def source_commit(source: Path | None) -> dict[str, Any] | None:
+    """Git commit (and dirty flag) of the audited source tree, if it is a repository."""
+    if source is None:
+        return None
+    try:
+        head = subprocess.run(
+            ["git", "-C", str(source), "rev-parse", "HEAD"],
+            capture_output=True, text=True, timeout=10,
+        )
+        if head.returncode != 0:
+            return None
+        status = subprocess.run(
+            ["git", "-C", str(source), "status", "--porcelain", "--untracked-files=no"],
+            capture_output=True, text=True, timeout=10,
+        )
+    except (subprocess.TimeoutExpired, OSError):
+        return None
+    return {"commit": head.stdout.strip(), "dirty": bool(status.stdout.strip())}
+
+
+def _pae(payload_type: str, payload: bytes) -> bytes:
+    """DSSE pre-authentication encoding: what the signature actually covers."""
+    t = payload_type.encode()
+    return b"DSSEv1 %d %s %d %s" % (len(t), t, len(payload), payload)
+
+
+def _canonical(data: dict[str, Any]) -> bytes:
+    return json.dumps(data, sort_keys=True, separators=(",", ":")).encode()
+
+
+def key_id(public_key) -> str:
+    """Short fingerprint of a public key (first 16 hex chars of SHA-256 of the raw key)."""
+    from cryptography.hazmat.primitives import serialization
+
+    raw = public_key.public_bytes(serialization.Encoding.Raw, serialization.PublicFormat.Raw)
+    return hashlib.sha256(raw).hexdigest()[:16]
+
+
+def generate_keypair(private_path: Path, public_path: Path) -> str:
+    """Write a new ed25519 key pair as PEM files; returns the key id."""
+    ed25519 = _ed25519()
+    from cryptography.hazmat.primitives import serialization
+
+    private_key = ed25519.Ed25519PrivateKey.generate()
+    private_path, public_path = Path(private_path), Path(public_path)
+    private_path.parent.mkdir(parents=True, exist_ok=True)
+    public_path.parent.mkdir(parents=True, exist_ok=True)
+    private_path.write_bytes(private_key.private_bytes(
+        serialization.Encoding.PEM, serialization.PrivateFormat.PKCS8, serialization.NoEncryption(),
+    ))
+    os.chmod(private_path, 0o600)
+    public_path.write_bytes(private_key.public_key().public_bytes(
+        serialization.Encoding.PEM, serialization.PublicFormat.SubjectPublicKeyInfo,
+    ))
+    return key_id(private_key.public_key())
+
+
+def load_private_key(path: Path):
+    ed25519 = _ed25519()
+    from cryptography.hazmat.primitives import serialization
+
+    key = serialization.load_pem_private_key(Path(path).read_bytes(), password=None)
+    if not isinstance(key, ed25519.Ed25519PrivateKey):
+        raise SigningError(f"{path} is not an ed25519 private key")
+    return key
+
+
+def load_public_key(path: Path):
+    ed25519 = _ed25519()
+    from cryptography.hazmat.primitives import serialization
+
+    key = serialization.load_pem_public_key(Path(path).read_bytes())
+    if not isinstance(key, ed25519.Ed25519PublicKey):
+        raise SigningError(f"{path} is not an ed25519 public key")
+    return key
+
+
+def build_statement(files: list[Path], base: Path, ruleset: str | None = None,
+                    source: Path | None = None, tool: str = "baskerville") -> dict[str, Any]:
+    """Statement covering ``files`` (named relative to ``base``)."""
+    subjects = []
+    for path in files:
+        path = Path(path)
+        subjects.append({
+            "name": Path(os.path.relpath(path.resolve(), Path(base).resolve())).as_posix(),
+            "digest": {"sha256": sha256_file(path)},
+        })
+    return {
+        "_type": STATEMENT_TYPE,
+        "subject": subjects,
+        "predicate": {
+            "tool": tool,
+            "created_at": datetime.now(timezone.utc).isoformat(),
+            "ruleset_version": ruleset or pinned_ruleset_version(source),
+            "source": source_commit(source),
+        },
+    }
+
+
+def sign_files(files: list[Path], private_key_path: Path, output: Path | None = None,
+               source: Path | None = None, ruleset: str | None = None) -> Path:
+    """Sign ``files`` and write a detached DSSE envelope.
+
+    Args:
+        files: Report/finding files to cover
+        private_key_path: ed25519 private key (PEM)
+        output: Envelope path (default: `<file>.sig.json` for a single file,
+            else `SIGNATURES.sig.json` next to the first file)
+        source: Audited source tree, for the input commit hash
+        ruleset: Rule-set version override (default: digest of the knowledge base)
+
+    Returns:
+        Path of the written envelope
+    """
+    files = [Path(f) for f in files]
+    if not files:
+        raise SigningError("No files to sign")
+    missing = [str(f) for f in files if not f.is_file()]
+    if missing:
+        raise SigningError(f"Missing files: {', '.join(missing)}")
+    if output is None:
+        output = files[0].with_name(files[0].name + SIGNATURE_SUFFIX) if len(files) == 1 else files[0].parent / f"SIGNATURES{SIGNATURE_SUFFIX}"
+    output = Path(output)
+
+    key = load_private_key(private_key_path)
+    # Subjects are named relative to the envelope so the bundle can be moved as a unit
+    statement = build_statement(files, output.parent, ruleset=ruleset, source=source)
+    payload = _canonical(statement)
+    envelope = {
+        "payloadType": PAYLOAD_TYPE,
+        "payload": base64.b64encode(payload).decode(),
+        "signatures": [{
+            "keyid": key_id(key.public_key()),
+            "sig": base64.b64encode(key.sign(_pae(PAYLOAD_TYPE, payload))).decode(),
+        }],
+    }
+    output.parent.mkdir(parents=True, exist_ok=True)
+    output.write_text(json.dumps(envelope, indent=2) + "\n")
+    return output
+
+
+def read_statement(envelope_path: Path) -> dict[str, Any]:
+    """Decode an envelope's statement without checking the signature."""
+    envelope = json.loads(Path(envelope_path).read_text())
+    return json.loads(base64.b64decode(envelope["payload"]))
+
+
+@dataclass
+class VerificationResult:
+    """Outcome of verifying an envelope against a public key and the files on disk."""
+
+    valid: bool
+    statement: dict[str, Any] | None = None
+    errors: list[str] = field(default_factory=list)
+    verified_files: list[str] = field(default_factory=list)
+
+
+def verify_envelope(envelope_path: Path, public_key_path: Path, root: Path | None = None) -> VerificationResult:
+    """Check the signature and every subject digest.
+
+    Args:
+        envelope_path: `.sig.json` file written by sign_files()
+        public_key_path: Signer's ed25519 public key (PEM)
+        root: Directory subjects are relative to (default: the envelope's directory)
+    """
+    _ed25519()
+    from cryptography.exceptions import InvalidSignature
+
+    envelope_path = Path(envelope_path)
+    try:
+        envelope = json.loads(envelope_path.read_text())
+        payload = base64.b64decode(envelope["payload"])
+        signatures = envelope["signatures"]
+    except (OSError, ValueError, KeyError, TypeError) as e:
+        return VerificationResult(valid=False, errors=[f"Malformed envelope: {e}"])
+    if envelope.get("payloadType") != PAYLOAD_TYPE:
+        return VerificationResult(valid=False, errors=[f"Unexpected payload type: {envelope.get('payloadType')}"])
+
+    public_key = load_public_key(public_key_path)
+    expected_id = key_id(public_key)
+    signed = False
+    for sig in signatures:
+        if sig.get("keyid") not in (None, expected_id):
+            continue
+        try:
+            public_key.verify(base64.b64decode(sig.get("sig", "")), _pae(PAYLOAD_TYPE, payload))
+            signed = True
+            break
+        except (InvalidSignature, ValueError):
+            continue
+    if not signed:
+        return VerificationResult(valid=False, errors=["No valid signature for this public key"])
+
+    statement = json.loads(payload)
+    result = VerificationResult(valid=True, statement=statement)
+    base = Path(root) if root else envelope_path.parent
+    for subject in statement.get("subject", []):
+        path = base / subject["name"]
+        if not path.is_file():
+            result.errors.append(f"Missing file: {subject['name']}")
+        elif sha256_file(path) != subject["digest"]["sha256"]:
+            result.errors.append(f"Modified file: {subject['name']}")
+        else:
+            result.verified_files.append(subject["name"])
+    result.valid = not result.errors
+    return result
@@ -0,0 +1,33 @@
+"""
+Solana RPC access.
+
+Shared plumbing for everything that talks to a cluster (clone planning
+for the managed test validator, on-chain monitoring) so none of them
+reimplements it:
+
+- Providers: generic JSON-RPC endpoints, the public clusters, Helius and
+  Triton, each with its own rate and concurrency limits
+- Client: rate limiting, bounded concurrency, retries with backoff on
+  transient failures, batching
+- Cache: per-method TTLs, in memory or persisted to SQLite
+"""
+
+from .cache import DEFAULT_TTLS, RpcCache
+from .client import RateLimiter, RpcClient, RpcError, default_cache_dir
+from .providers import PUBLIC_URLS, Provider, generic, helius, public, resolve_provider, triton
+
+__all__ = [
+    "DEFAULT_TTLS",
+    "PUBLIC_URLS",
+    "Provider",
+    "RateLimiter",
+    "RpcCache",
+    "RpcClient",
+    "RpcError",
+    "default_cache_dir",
+    "generic",
+    "helius",
+    "public",
+    "resolve_provider",
+    "triton",
+]
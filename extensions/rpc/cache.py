@@ -0,0 +1,149 @@
+"""
+Response cache for RPC calls.
+
+Entries are keyed by cluster, method and params and expire per method:
+finalized transactions and blocks never change, account state is only
+reused for a short while, and anything slot- or health-related is never
+cached. The cache lives in memory and, when given a directory, in SQLite
+so repeated runs (re-planning clones, re-fetching a program's accounts)
+don't hit the provider again.
+"""
+
+import hashlib
+import json
+import sqlite3
+import threading
+import time
+from collections import OrderedDict
+from pathlib import Path
+from typing import Any
+
+# Seconds a result stays valid; None caches forever, missing methods are not cached
+DEFAULT_TTLS: dict[str, float | None] = {
+    "getTransaction": None,
+    "getBlock": None,
+    "getBlockTime": None,
+    "getGenesisHash": None,
+    "getAccountInfo": 30.0,
+    "getMultipleAccounts": 30.0,
+    "getProgramAccounts": 30.0,
+    "getTokenSupply": 30.0,
+    "getTokenLargestAccounts": 30.0,
+    "getSignaturesForAddress": 5.0,
+}
+
+
+class RpcCache:
+    """Thread-safe TTL cache for RPC results, optionally persisted to SQLite."""
+
+    def __init__(self, cache_dir: Path | None = None, ttls: dict[str, float | None] | None = None,
+                 max_entries: int = 10_000):
+        """Initialize cache.
+
+        Args:
+            cache_dir: Directory for the SQLite file (default: memory only); ~/.hound/cache/rpc/ is the usual choice
+            ttls: Per-method TTLs overriding ``DEFAULT_TTLS``
+            max_entries: In-memory entries kept before the least recently used are dropped
+        """
+        self.ttls = {**DEFAULT_TTLS, **(ttls or {})}
+        self.max_entries = max_entries
+        self._memory: OrderedDict[str, tuple[float | None, Any]] = OrderedDict()
+        self._lock = threading.Lock()
+        self.hits = 0
+        self.misses = 0
+        self.db_path: Path | None = None
+        if cache_dir is not None:
+            cache_dir = Path(cache_dir)
+            cache_dir.mkdir(parents=True, exist_ok=True)
+            self.db_path = cache_dir / "rpc_cache.db"
+            with sqlite3.connect(self.db_path) as conn:
+                conn.execute("""
+                    CREATE TABLE IF NOT EXISTS responses (
+                        key TEXT PRIMARY KEY,
+                        method TEXT NOT NULL,
+                        result TEXT NOT NULL,
+                        expires_at REAL
+                    )
+                """)
+
+    def cacheable(self, method: str) -> bool:
+        return method in self.ttls and self.ttls[method] != 0
+
+    @staticmethod
+    def key(scope: str, method: str, params: Any) -> str:
+        raw = json.dumps([scope, method, params], sort_keys=True, separators=(",", ":"))
+        return hashlib.sha256(raw.encode()).hexdigest()
+
+    def get(self, scope: str, method: str, params: Any) -> tuple[bool, Any]:
+        """(hit, result) for a call."""
+        if not self.cacheable(method):
+            return False, None
+        key = self.key(scope, method, params)
+        now = time.time()
+        with self._lock:
+            entry = self._memory.get(key)
+            if entry is not None:
+                if entry[0] is None or entry[0] > now:
+                    self._memory.move_to_end(key)
+                    self.hits += 1
+                    return True, entry[1]
+                del self._memory[key]
+        if self.db_path:
+            with sqlite3.connect(self.db_path) as conn:
+                row = conn.execute("SELECT result, expires_at FROM responses WHERE key = ? AND "
+                                   "(expires_at IS NULL OR expires_at > ?)", (key, now)).fetchone()
+            if row:
+                result = json.loads(row[0])
+                self._remember(key, row[1], result)
+                with self._lock:
+                    self.hits += 1
+                return True, result
+        with self._lock:
+            self.misses += 1
+        return False, None
+
+    def put(self, scope: str, method: str, params: Any, result: Any) -> None:
+        # Don't pin "account does not exist" answers
+        if not self.cacheable(method) or result is None:
+            return
+        ttl = self.ttls[method]
+        expires_at = None if ttl is None else time.time() + ttl
+        key = self.key(scope, method, params)
+        self._remember(key, expires_at, result)
+        if self.db_path:
+            with sqlite3.connect(self.db_path) as conn:
+                conn.execute("INSERT OR REPLACE INTO responses (key, method, result, expires_at) VALUES (?, ?, ?, ?)",
+                             (key, method, json.dumps(result), expires_at))
+
+    def _remember(self, key: str, expires_at: float | None, result: Any) -> None:
+        with self._lock:
+            self._memory[key] = (expires_at, result)
+            self._memory.move_to_end(key)
+            while len(self._memory) > self.max_entries:
+                self._memory.popitem(last=False)
+
+    def clear(self) -> None:
+        with self._lock:
+            self._memory.clear()
+        if self.db_path:
+            with sqlite3.connect(self.db_path) as conn:
+                conn.execute("DELETE FROM responses")
+
+    def cleanup_expired(self) -> None:
+        """Remove expired entries."""
+        now = time.time()
+        with self._lock:
+            for key in [k for k, (exp, _) in self._memory.items() if exp is not None and exp <= now]:
+                del self._memory[key]
+        if self.db_path:
+            with sqlite3.connect(self.db_path) as conn:
+                conn.execute("DELETE FROM responses WHERE expires_at IS NOT NULL AND expires_at <= ?", (now,))
+
+    def stats(self) -> dict[str, Any]:
+        """Get cache statistics."""
+        stored: dict[str, int] = {}
+        if self.db_path:
+            with sqlite3.connect(self.db_path) as conn:
+                stored = dict(conn.execute("SELECT method, COUNT(*) FROM responses GROUP BY method").fetchall())
+        return {"memory_entries": len(self._memory), "hits": self.hits, "misses": self.misses, "stored": stored,
+                "db_path": str(self.db_path) if self.db_path else None}
@@ -0,0 +1,286 @@
+"""
+JSON-RPC client with caching, retries and rate limiting.
+
+One ``RpcClient`` per provider: every request waits for a rate-limit slot
+and a concurrency slot, transient failures (HTTP 429/5xx, timeouts, nodes
+that are behind) are retried with exponential backoff and jitter,
+honouring `Retry-After`, and cacheable results are served from an
+``RpcCache``. Batches are split at the provider's batch limit and only
+the uncached calls are sent.
+"""
+
+import json
+import random
+import threading
+import time
+import urllib.error
+import urllib.request
+from collections.abc import Callable, Iterable
+from concurrent.futures import ThreadPoolExecutor
+from pathlib import Path
+from typing import Any
+
+from .cache import RpcCache
+from .providers import Provider, resolve_provider
+
+# (url, body, headers, timeout) -> (status, headers, body)
+Transport = Callable[[str, bytes, dict[str, str], float], tuple[int, dict[str, str], bytes]]
+
+RETRYABLE_STATUS = {408, 429, 500, 502, 503, 504}
+# Node unhealthy / behind, block not yet available, provider-side rate limits
+RETRYABLE_CODES = {-32005, -32004, -32014, -32429, 429}
+
+
+def default_cache_dir() -> Path:
+    return Path.home() / ".hound" / "cache" / "rpc"
+
+
+class RpcError(RuntimeError):
+    """An RPC call failed (transport, HTTP or JSON-RPC error)."""
+
+    def __init__(self, message: str, code: int | None = None, retryable: bool = False, data: Any = None):
+        super().__init__(message)
+        self.code = code
+        self.retryable = retryable
+        self.data = data
+
+
+class RateLimiter:
+    """Spaces requests at most ``rate`` per second across threads."""
+
+    def __init__(self, rate: float, sleep: Callable[[float], None] = time.sleep,
+                 clock: Callable[[], float] = time.monotonic):
+        self.interval = 1.0 / rate if rate > 0 else 0.0
+        self._sleep = sleep
+        self._clock = clock
+        self._next = 0.0
+        self._lock = threading.Lock()
+
+    def acquire(self) -> None:
+        """Wait until a request slot is available."""
+        with self._lock:
+            now = self._clock()
+            slot = max(now, self._next)
+            self._next = slot + self.interval
+        if slot > now:
+            self._sleep(slot - now)
+
+    def pause(self, seconds: float) -> None:
+        """Hold back all requests for ``seconds`` (server asked us to slow down)."""
+        with self._lock:
+            self._next = max(self._next, self._clock() + seconds)
+
+
+def urllib_transport(url: str, body: bytes, headers: dict[str, str],
+                     timeout: float) -> tuple[int, dict[str, str], bytes]:
+    req = urllib.request.Request(url, data=body, headers=headers)
+    try:
+        with urllib.request.urlopen(req, timeout=timeout) as resp:
+            return resp.status, dict(resp.headers), resp.read()
+    except urllib.error.HTTPError as e:
+        return e.code, dict(e.headers or {}), e.read() or b""
+
+
+class RpcClient:
+    """Rate-limited, retrying, caching JSON-RPC client for one provider."""
+
+    def __init__(self, provider: Provider | str | None = None, cache: RpcCache | bool = True,
+                 retries: int = 3, backoff: float = 0.5, max_backoff: float = 8.0, timeout: float = 30.0,
+                 transport: Transport | None = None, sleep: Callable[[float], None] = time.sleep):
+        """Initialize client.
+
+        Args:
+            provider: Provider or spec for ``resolve_provider`` (default: $SOLANA_RPC_URL or mainnet)
+            cache: An ``RpcCache``, True for an in-memory one, False to disable caching
+            retries: Retries after the first attempt for transient failures
+            backoff: First retry delay in seconds, doubled per attempt
+            max_backoff: Cap on a single retry delay
+            timeout: Per-request timeout in seconds
+            transport: HTTP transport (tests substitute a fake)
+            sleep: Sleep function (tests substitute a recorder)
+        """
+        self.provider = resolve_provider(provider)
+        self.cache = RpcCache() if cache is True else (cache or None)
+        self.retries = retries
+        self.backoff = backoff
+        self.max_backoff = max_backoff
+        self.timeout = timeout
+        self._transport = transport or urllib_transport
+        self._sleep = sleep
+        self._limiter = RateLimiter(self.provider.max_rps, sleep=sleep)
+        self._slots = threading.BoundedSemaphore(self.provider.max_concurrency)
+        self._ids = iter(range(1, 1 << 62))
+        self._id_lock = threading.Lock()
+        self.requests = 0
+        self.retried = 0
+
+    @property
+    def url(self) -> str:
+        return self.provider.url
+
+    def _next_id(self) -> int:
+        with self._id_lock:
+            return next(self._ids)
+
+    def _cacheable(self, method: str, params: list[Any]) -> bool:
+        if self.cache is None or not self.cache.cacheable(method):
+            return False
+        # Unfinalized transactions and blocks can still change
+        config = params[-1] if params and isinstance(params[-1], dict) else {}
+        return config.get("commitment", "finalized") == "finalized" or method not in ("getTransaction", "getBlock")
+
+    def _delay(self, attempt: int, retry_after: str | None) -> float:
+        if retry_after:
+            try:
+                return min(float(retry_after), self.max_backoff * 4)
+            except ValueError:
+                pass
+        delay = min(self.backoff * (2 ** attempt), self.max_backoff)
+        return delay * (0.5 + random.random() / 2)
+
+    def _post(self, payload: Any) -> Any:
+        """Send one HTTP request, retrying transient failures; returns the decoded body."""
+        body = json.dumps(payload).encode()
+        headers = {"Content-Type": "application/json", **self.provider.headers}
+        attempt = 0
+        while True:
+            self._limiter.acquire()
+            retry_after = None
+            with self._slots:
+                self.requests += 1
+                try:
+                    status, resp_headers, data = self._transport(self.provider.url, body, headers, self.timeout)
+                except (OSError, urllib.error.URLError) as e:
+                    error = RpcError(f"{self.provider.name} request failed: {e}", retryable=True)
+                else:
+                    if status == 200:
+                        try:
+                            reply = json.loads(data)
+                        except ValueError as e:
+                            raise RpcError(f"{self.provider.name} returned invalid JSON: {e}") from e
+                        error = _retryable_reply(reply)
+                        if error is None:
+                            return reply
+                    else:
+                        retry_after = {k.lower(): v for k, v in resp_headers.items()}.get("retry-after")
+                        error = RpcError(f"{self.provider.name} returned HTTP {status}", code=status,
+                                         retryable=status in RETRYABLE_STATUS)
+            if not error.retryable or attempt == self.retries:
+                raise error
+            delay = self._delay(attempt, retry_after)
+            self.retried += 1
+            attempt += 1
+            if retry_after:
+                # The provider throttles us as a whole, so hold back every thread
+                self._limiter.pause(delay)
+            else:
+                self._sleep(delay)
+
+    def call(self, method: str, params: list[Any] | None = None, cache: bool = True) -> Any:
+        """Call ``method``; raises RpcError on failure."""
+        params = params or []
+        use_cache = cache and self._cacheable(method, params)
+        if use_cache:
+            hit, result = self.cache.get(self.provider.cache_scope, method, params)
+            if hit:
+                return result
+        reply = self._post({"jsonrpc": "2.0", "id": self._next_id(), "method": method, "params": params})
+        result = _result(reply, method)
+        if use_cache:
+            self.cache.put(self.provider.cache_scope, method, params, result)
+        return result
+
+    def batch(self, calls: Iterable[tuple[str, list[Any]]], cache: bool = True) -> list[Any]:
+        """Results of several calls, sent as JSON-RPC batches of at most ``max_batch``."""
+        calls = [(m, p or []) for m, p in calls]
+        results: list[Any] = [None] * len(calls)
+        pending: list[int] = []
+        for i, (method, params) in enumerate(calls):
+            if cache and self._cacheable(method, params):
+                hit, result = self.cache.get(self.provider.cache_scope, method, params)
+                if hit:
+                    results[i] = result
+                    continue
+            pending.append(i)
+        size = max(1, self.provider.max_batch)
+        for start in range(0, len(pending), size):
+            chunk = pending[start:start + size]
+            ids = {self._next_id(): i for i in chunk}
+            replies = self._post([{"jsonrpc": "2.0", "id": rid, "method": calls[i][0], "params": calls[i][1]}
+                                  for rid, i in ids.items()])
+            if not isinstance(replies, list):
+                raise RpcError(f"{self.provider.name} does not support batch requests")
+            for reply in replies:
+                i = ids[reply["id"]]
+                method, params = calls[i]
+                results[i] = _result(reply, method)
+                if cache and self._cacheable(method, params):
+                    self.cache.put(self.provider.cache_scope, method, params, results[i])
+        return results
+
+    def parallel(self, fn: Callable[[Any], Any], items: Iterable[Any]) -> list[Any]:
+        """Map ``fn`` (which makes calls on this client) over ``items`` at the provider's concurrency."""
+        with ThreadPoolExecutor(max_workers=self.provider.max_concurrency) as pool:
+            return list(pool.map(fn, items))
+
+    def get_health(self) -> str:
+        return self.call("getHealth")
+
+    def get_slot(self, commitment: str = "confirmed") -> int:
+        return self.call("getSlot", [{"commitment": commitment}])
+
+    def get_account_info(self, address: str, encoding: str = "base64") -> dict[str, Any] | None:
+        return self.call("getAccountInfo", [address, {"encoding": encoding}])["value"]
+
+    def get_multiple_accounts(self, addresses: list[str], encoding: str = "base64",
+                              data_slice: dict[str, int] | None = None) -> list[dict[str, Any] | None]:
+        """Account infos for ``addresses`` (None where missing), 100 per request."""
+        config: dict[str, Any] = {"encoding": encoding}
+        if data_slice:
+            config["dataSlice"] = data_slice
+        infos: list[dict[str, Any] | None] = []
+        for i in range(0, len(addresses), 100):
+            infos.extend(self.call("getMultipleAccounts", [addresses[i:i + 100], config])["value"])
+        return infos
+
+    def get_program_accounts(self, program_id: str, filters: list[dict[str, Any]] | None = None,
+                             encoding: str = "base64") -> list[dict[str, Any]]:
+        config: dict[str, Any] = {"encoding": encoding}
+        if filters:
+            config["filters"] = filters
+        return self.call("getProgramAccounts", [program_id, config])
+
+    def get_signatures_for_address(self, address: str, limit: int = 1000, before: str | None = None,
+                                   until: str | None = None, commitment: str = "confirmed") -> list[dict[str, Any]]:
+        config: dict[str, Any] = {"limit": limit, "commitment": commitment}
+        if before:
+            config["before"] = before
+        if until:
+            config["until"] = until
+        return self.call("getSignaturesForAddress", [address, config])
+
+    def get_transaction(self, signature: str, commitment: str = "finalized",
+                        encoding: str = "json") -> dict[str, Any] | None:
+        return self.call("getTransaction", [signature, {"encoding": encoding, "commitment": commitment,
+                                                        "maxSupportedTransactionVersion": 0}])
+
+    def stats(self) -> dict[str, Any]:
+        return {"provider": self.provider.to_dict(), "requests": self.requests, "retries": self.retried,
+                "cache": self.cache.stats() if self.cache else None}
+
+
+def _retryable_reply(reply: Any) -> RpcError | None:
+    """A retryable JSON-RPC error in a single reply (batches are checked per item)."""
+    if isinstance(reply, dict) and reply.get("error"):
+        code = reply["error"].get("code")
+        if code in RETRYABLE_CODES:
+            return RpcError(reply["error"].get("message", "RPC error"), code=code, retryable=True)
+    return None
+
+
+def _result(reply: dict[str, Any], method: str) -> Any:
+    if reply.get("error"):
+        error = reply["error"]
+        raise RpcError(f"{method} failed: {error.get('message', error)}", code=error.get("code"),
+                       retryable=error.get("code") in RETRYABLE_CODES, data=error.get("data"))
+    return reply.get("result")
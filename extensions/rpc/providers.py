@@ -0,0 +1,154 @@
+"""
+RPC providers.
+
+A ``Provider`` is an endpoint plus the limits it should be used with.
+``resolve_provider`` turns the short specs accepted on the command line
+(`mainnet`, `devnet:helius`, `triton`, a URL) into one, reading API keys
+from the environment.
+"""
+
+import os
+from dataclasses import dataclass, field, replace
+from typing import Any
+from urllib.parse import urlsplit, urlunsplit
+
+# Public Solana Labs endpoints: 100 requests / 10 s per IP, 40 concurrent connections
+PUBLIC_URLS = {
+    "mainnet": "https://api.mainnet-beta.solana.com",
+    "devnet": "https://api.devnet.solana.com",
+    "testnet": "https://api.testnet.solana.com",
+    "localnet": "http://127.0.0.1:8899",
+}
+
+_CLUSTER_ALIASES = {"mainnet-beta": "mainnet", "m": "mainnet", "d": "devnet", "t": "testnet", "l": "localnet",
+                    "localhost": "localnet"}
+
+
+@dataclass(frozen=True)
+class Provider:
+    """An RPC endpoint and the limits to respect when calling it."""
+
+    name: str
+    url: str
+    cluster: str | None = None
+    # Websocket endpoint for subscriptions (default: derived from ``url``)
+    ws_url: str | None = None
+    headers: dict[str, str] = field(default_factory=dict)
+    # Requests per second (0: unlimited) and requests in flight
+    max_rps: float = 10.0
+    max_concurrency: int = 4
+    # Largest JSON-RPC batch the endpoint accepts
+    max_batch: int = 100
+
+    @property
+    def websocket_url(self) -> str:
+        if self.ws_url:
+            return self.ws_url
+        parts = urlsplit(self.url)
+        scheme = "wss" if parts.scheme == "https" else "ws"
+        netloc = parts.netloc
+        # solana-test-validator serves websockets on rpc port + 1
+        if parts.port and parts.hostname in ("127.0.0.1", "localhost"):
+            netloc = f"{parts.hostname}:{parts.port + 1}"
+        return urlunsplit((scheme, netloc, parts.path, parts.query, ""))
+
+    @property
+    def cache_scope(self) -> str:
+        """Key under which responses are cached: the cluster where known, else the endpoint."""
+        return self.cluster or redact_url(self.url)
+
+    def with_limits(self, max_rps: float | None = None, max_concurrency: int | None = None) -> "Provider":
+        return replace(self, max_rps=max_rps or self.max_rps, max_concurrency=max_concurrency or self.max_concurrency)
+
+    def to_dict(self) -> dict[str, Any]:
+        """Serializable form with credentials removed."""
+        return {"name": self.name, "url": redact_url(self.url), "cluster": self.cluster, "max_rps": self.max_rps,
+                "max_concurrency": self.max_concurrency, "max_batch": self.max_batch}
+
+
+def redact_url(url: str) -> str:
+    """``url`` with API keys and tokens masked, for logs and exports."""
+    parts = urlsplit(url)
+    query = "&".join(
+        f"{k}=***" if k.lower() in ("api-key", "api_key", "apikey", "token") else f"{k}={v}"
+        for k, _, v in (p.partition("=") for p in parts.query.split("&") if p)
+    )
+    path = parts.path
+    # Triton-style credentials in the last path segment
+    if parts.hostname and parts.hostname.endswith("rpcpool.com") and path.strip("/"):
+        path = "/***"
+    return urlunsplit((parts.scheme, parts.netloc, path, query, ""))
+
+
+def normalize_cluster(cluster: str) -> str:
+    cluster = cluster.strip().lower()
+    return _CLUSTER_ALIASES.get(cluster, cluster)
+
+
+def generic(url: str, cluster: str | None = None, **limits: Any) -> Provider:
+    """Any JSON-RPC endpoint."""
+    if cluster is None:
+        cluster = next((c for c, u in PUBLIC_URLS.items() if u == url.rstrip("/")), None)
+    if cluster == "localnet":
+        limits = {"max_rps": 1000.0, "max_concurrency": 16, **limits}
+    return Provider(name="rpc", url=url, cluster=cluster, **limits)
+
+
+def public(cluster: str = "mainnet") -> Provider:
+    """Solana Labs public endpoint for ``cluster``."""
+    cluster = normalize_cluster(cluster)
+    if cluster not in PUBLIC_URLS:
+        raise ValueError(f"unknown cluster '{cluster}' (expected one of {', '.join(PUBLIC_URLS)})")
+    return generic(PUBLIC_URLS[cluster], cluster)
+
+
+def helius(api_key: str | None = None, cluster: str = "mainnet", **limits: Any) -> Provider:
+    """Helius RPC; the key defaults to $HELIUS_API_KEY."""
+    api_key = api_key or os.environ.get("HELIUS_API_KEY")
+    if not api_key:
+        raise ValueError("Helius needs an API key (set HELIUS_API_KEY)")
+    cluster = normalize_cluster(cluster)
+    if cluster not in ("mainnet", "devnet"):
+        raise ValueError(f"Helius does not serve '{cluster}'")
+    host = f"{cluster}.helius-rpc.com"
+    return Provider(name="helius", url=f"https://{host}/?api-key={api_key}", cluster=cluster,
+                    ws_url=f"wss://{host}/?api-key={api_key}", **{"max_rps": 10.0, "max_concurrency": 8, **limits})
+
+
+def triton(url: str | None = None, cluster: str = "mainnet", **limits: Any) -> Provider:
+    """Triton One endpoint (`https://<name>.mainnet.rpcpool.com/<token>`); defaults to $TRITON_RPC_URL."""
+    url = url or os.environ.get("TRITON_RPC_URL")
+    if not url:
+        raise ValueError("Triton needs an endpoint URL (set TRITON_RPC_URL)")
+    return Provider(name="triton", url=url, cluster=normalize_cluster(cluster),
+                    **{"max_rps": 50.0, "max_concurrency": 16, **limits})
+
+
+PROVIDERS = {"rpc": generic, "public": public, "helius": helius, "triton": triton}
+
+
+def resolve_provider(spec: str | Provider | None = None, **limits: Any) -> Provider:
+    """Provider from a spec string.
+
+    Accepted forms: a URL; a cluster (`mainnet`, `devnet`, `localnet`, ...)
+    for the public endpoint; a provider name (`helius`, `triton`) with an
+    optional cluster as `devnet:helius` or `helius:devnet`. Without a spec,
+    $SOLANA_RPC_URL or mainnet.
+    """
+    limits = {k: v for k, v in limits.items() if v is not None}
+    if isinstance(spec, Provider):
+        return spec.with_limits(**limits) if limits else spec
+    spec = (spec or os.environ.get("SOLANA_RPC_URL") or "mainnet").strip()
+    if "://" in spec:
+        return generic(spec, **limits)
+    parts = [p for p in spec.split(":") if p]
+    names = [p for p in parts if p.lower() in PROVIDERS]
+    clusters = [normalize_cluster(p) for p in parts if p.lower() not in PROVIDERS]
+    if len(names) > 1 or len(clusters) > 1 or not parts:
+        raise ValueError(f"cannot parse RPC provider '{spec}'")
+    cluster = clusters[0] if clusters else "mainnet"
+    name = names[0].lower() if names else "public"
+    if name in ("public", "rpc"):
+        provider = public(cluster)
+        return provider.with_limits(**limits) if limits else provider
+    return PROVIDERS[name](cluster=cluster, **limits)
@@ -0,0 +1,31 @@
+"""
+Server mode: one shared Baskerville service for several client engagements.
+
+- Tenants with their own storage namespace (projects live under the tenant)
+- API keys scoped to one tenant, stored hashed, each granting a role
+  (viewer, triager, admin)
+- A JSON API over HTTP that authenticates every request and only resolves
+  projects inside the caller's tenant
+- Background static analysis scans whose progress events stream to clients
+  as newline-delimited JSON, and a small client for them
+"""
+
+from .app import ApiError, ApiHandlers, ApiServer, EventStream
+from .client import ApiClient
+from .scans import ScanJob, ScanManager
+from .tenancy import ApiKey, Role, Tenant, TenancyError, TenantStore
+
+__all__ = [
+    "ApiClient",
+    "ApiError",
+    "ApiHandlers",
+    "ApiKey",
+    "ApiServer",
+    "EventStream",
+    "Role",
+    "ScanJob",
+    "ScanManager",
+    "Tenant",
+    "TenancyError",
+    "TenantStore",
+]
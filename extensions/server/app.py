@@ -0,0 +1,346 @@
+"""
+JSON API for server mode.
+
+Every request authenticates with an API key (``Authorization: Bearer <key>``
+or ``X-API-Key``) and only ever sees the key's tenant: project names are
+looked up in that tenant's namespace, so another tenant's project is a 404
+rather than a 403.
+
+    GET    /v1/whoami                                     viewer
+    GET    /v1/projects                                   viewer
+    GET    /v1/projects/<name>                            viewer
+    GET    /v1/projects/<name>/hypotheses                 viewer
+    POST   /v1/projects/<name>/hypotheses/<id>/status     triager  {"status": "confirmed"}
+    GET    /v1/projects/<name>/scans                      viewer
+    POST   /v1/projects/<name>/scans                      triager  {"tools": ["slither"]}
+    GET    /v1/projects/<name>/scans/<id>                 viewer
+    GET    /v1/projects/<name>/scans/<id>/events          viewer   ?since=<seq>
+    POST   /v1/projects                                   admin    {"name", "source", "chain"}
+    GET    /v1/keys                                       admin
+    POST   /v1/keys                                       admin    {"role", "label"}
+    DELETE /v1/keys/<id>                                  admin
+
+Project sources must live in the tenant's ``sources/`` directory, so one
+tenant cannot point a project at another's files.
+
+Scans run in the background. Their ``events`` endpoint streams progress as
+newline-delimited JSON (one ProgressEvent per line) until the scan finishes
+or fails, so clients can render files parsed, detectors completed and
+findings so far while it runs. A ``{"event": "heartbeat"}`` line is sent
+when a detector runs for a while without news.
+"""
+
+import json
+import threading
+from datetime import datetime
+from http import HTTPStatus
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from pathlib import Path
+from typing import Any, Iterator
+from urllib.parse import parse_qsl, urlsplit
+
+from .scans import ScanJob, ScanManager
+from .tenancy import ApiKey, Role, Tenant, TenancyError, TenantStore, validate_project_name
+
+HYPOTHESIS_STATUSES = ("proposed", "confirmed", "rejected", "fixed")
+
+
+class ApiError(Exception):
+    """An error response."""
+
+    def __init__(self, status: HTTPStatus, message: str):
+        super().__init__(message)
+        self.status = status
+        self.message = message
+
+
+class EventStream:
+    """A handler result streamed as newline-delimited JSON instead of one body."""
+
+    def __init__(self, events: Iterator[dict[str, Any]]):
+        self.events = events
+
+
+def _project(store: TenantStore, tenant: Tenant, name: str) -> dict[str, Any]:
+    try:
+        validate_project_name(name)
+    except TenancyError:
+        raise ApiError(HTTPStatus.NOT_FOUND, f"project '{name}' not found") from None
+    project = store.projects(tenant.id).get_project(name)
+    if not project:
+        raise ApiError(HTTPStatus.NOT_FOUND, f"project '{name}' not found")
+    return project
+
+
+def _hypotheses_file(project: dict[str, Any]) -> Path:
+    return Path(project["path"]) / "hypotheses.json"
+
+
+def _load_hypotheses(project: dict[str, Any]) -> dict[str, Any]:
+    try:
+        return json.loads(_hypotheses_file(project).read_text())
+    except (OSError, ValueError):
+        return {"hypotheses": {}}
+
+
+class ApiHandlers:
+    """Route handlers, independent of the HTTP transport."""
+
+    # Status updates rewrite hypotheses.json; serialize them
+    _write_lock = threading.Lock()
+
+    # Seconds without a progress event before an event stream sends a heartbeat
+    HEARTBEAT_SECONDS = 15.0
+
+    def __init__(self, store: TenantStore, scans: ScanManager | None = None):
+        self.store = store
+        self.scans = scans or ScanManager()
+
+    def dispatch(self, method: str, path: str, tenant: Tenant, key: ApiKey, body: dict[str, Any]) -> Any:
+        parts = [p for p in path.split("?")[0].split("/") if p]
+        if parts[:1] != ["v1"]:
+            raise ApiError(HTTPStatus.NOT_FOUND, "unknown endpoint")
+        parts = parts[1:]
+        for route_method, pattern, role, handler in self.ROUTES:
+            if route_method != method or len(pattern) != len(parts):
+                continue
+            if any(p != s and not p.startswith("<") for p, s in zip(pattern, parts)):
+                continue
+            if not key.role.allows(role):
+                raise ApiError(HTTPStatus.FORBIDDEN, f"requires the {role} role (this key is {key.role})")
+            args = [s for p, s in zip(pattern, parts) if p.startswith("<")]
+            return handler(self, tenant, key, body, *args)
+        raise ApiError(HTTPStatus.NOT_FOUND, "unknown endpoint")
+
+    def whoami(self, tenant: Tenant, key: ApiKey, body: dict[str, Any]) -> dict[str, Any]:
+        return {"tenant": {"id": tenant.id, "name": tenant.name}, "key": key.id, "role": key.role.value}
+
+    def list_projects(self, tenant: Tenant, key: ApiKey, body: dict[str, Any]) -> list[dict[str, Any]]:
+        return [{k: v for k, v in p.items() if k != "path"} for p in self.store.projects(tenant.id).list_projects()]
+
+    def get_project(self, tenant: Tenant, key: ApiKey, body: dict[str, Any], name: str) -> dict[str, Any]:
+        project = _project(self.store, tenant, name)
+        return {k: v for k, v in project.items() if k != "path"}
+
+    def create_project(self, tenant: Tenant, key: ApiKey, body: dict[str, Any]) -> dict[str, Any]:
+        name, source = body.get("name"), body.get("source")
+        if not isinstance(name, str) or not isinstance(source, str):
+            raise ApiError(HTTPStatus.BAD_REQUEST, "'name' and 'source' are required")
+        try:
+            validate_project_name(name)
+        except TenancyError as e:
+            raise ApiError(HTTPStatus.BAD_REQUEST, str(e)) from None
+        sources = (self.store.storage_dir(tenant.id) / "sources").resolve()
+        source_path = (sources / source).resolve()
+        if not source_path.is_relative_to(sources):
+            raise ApiError(HTTPStatus.BAD_REQUEST, "'source' must be inside the tenant's sources directory")
+        try:
+            config = self.store.projects(tenant.id).create_project(name, str(source_path),
+                                                                   description=body.get("description"),
+                                                                   chain=body.get("chain", "auto"))
+        except ValueError as e:
+            raise ApiError(HTTPStatus.BAD_REQUEST, str(e)) from None
+        return config
+
+    def list_hypotheses(self, tenant: Tenant, key: ApiKey, body: dict[str, Any], name: str) -> dict[str, Any]:
+        return _load_hypotheses(_project(self.store, tenant, name)).get("hypotheses", {})
+
+    def set_status(self, tenant: Tenant, key: ApiKey, body: dict[str, Any], name: str, hid: str) -> dict[str, Any]:
+        project = _project(self.store, tenant, name)
+        status = str(body.get("status", "")).lower()
+        if status not in HYPOTHESIS_STATUSES:
+            raise ApiError(HTTPStatus.BAD_REQUEST, f"'status' must be one of {', '.join(HYPOTHESIS_STATUSES)}")
+        with self._write_lock:
+            data = _load_hypotheses(project)
+            hypothesis = data.get("hypotheses", {}).get(hid)
+            if hypothesis is None:
+                raise ApiError(HTTPStatus.NOT_FOUND, f"hypothesis '{hid}' not found")
+            hypothesis["status"] = status
+            # Who changed it, for the engagement summary and the audit trail
+            hypothesis.setdefault("status_history", []).append(
+                {"status": status, "at": datetime.now().isoformat(), "by": key.id}
+            )
+            _hypotheses_file(project).write_text(json.dumps(data, indent=2))
+        return hypothesis
+
+    def list_scans(self, tenant: Tenant, key: ApiKey, body: dict[str, Any], name: str) -> list[dict[str, Any]]:
+        _project(self.store, tenant, name)
+        return [job.public() for job in self.scans.list(tenant.id, name)]
+
+    def start_scan(self, tenant: Tenant, key: ApiKey, body: dict[str, Any], name: str) -> dict[str, Any]:
+        project = _project(self.store, tenant, name)
+        tools = body.get("tools")
+        if tools is not None and (not isinstance(tools, list) or not all(isinstance(t, str) for t in tools)):
+            raise ApiError(HTTPStatus.BAD_REQUEST, "'tools' must be a list of tool names")
+        return self.scans.start(tenant.id, project, tools, created_by=key.id).public()
+
+    def _scan(self, tenant: Tenant, name: str, scan_id: str) -> ScanJob:
+        _project(self.store, tenant, name)
+        job = self.scans.get(tenant.id, name, scan_id)
+        if job is None:
+            raise ApiError(HTTPStatus.NOT_FOUND, f"scan '{scan_id}' not found")
+        return job
+
+    def get_scan(self, tenant: Tenant, key: ApiKey, body: dict[str, Any], name: str, scan_id: str) -> dict[str, Any]:
+        return self._scan(tenant, name, scan_id).public()
+
+    def scan_events(self, tenant: Tenant, key: ApiKey, body: dict[str, Any], name: str, scan_id: str) -> EventStream:
+        job = self._scan(tenant, name, scan_id)
+        try:
+            since = max(0, int(body.get("since", 0)))
+        except (TypeError, ValueError):
+            raise ApiError(HTTPStatus.BAD_REQUEST, "'since' must be an event sequence number") from None
+        return EventStream(self._follow(job, since))
+
+    def _follow(self, job: ScanJob, since: int) -> Iterator[dict[str, Any]]:
+        while True:
+            for event in job.progress.follow(since, timeout=self.HEARTBEAT_SECONDS):
+                since = event.seq + 1
+                yield event.to_dict()
+                if event.done:
+                    return
+            if job.progress.done:
+                return
+            # Keep the connection (and the client's read timeout) alive during long detectors
+            yield {"event": "heartbeat"}
+
+    def list_keys(self, tenant: Tenant, key: ApiKey, body: dict[str, Any]) -> list[dict[str, Any]]:
+        return [k.public() for k in self.store.list_keys(tenant.id)]
+
+    def create_key(self, tenant: Tenant, key: ApiKey, body: dict[str, Any]) -> dict[str, Any]:
+        try:
+            record, secret = self.store.create_key(tenant.id, body.get("role", "viewer"), str(body.get("label", "")))
+        except TenancyError as e:
+            raise ApiError(HTTPStatus.BAD_REQUEST, str(e)) from None
+        return {**record.public(), "key": secret}
+
+    def revoke_key(self, tenant: Tenant, key: ApiKey, body: dict[str, Any], key_id: str) -> dict[str, Any]:
+        if not self.store.revoke_key(key_id, tenant.id):
+            raise ApiError(HTTPStatus.NOT_FOUND, f"key '{key_id}' not found")
+        return {"id": key_id, "revoked": True}
+
+    ROUTES = [
+        ("GET", ["whoami"], Role.VIEWER, whoami),
+        ("GET", ["projects"], Role.VIEWER, list_projects),
+        ("POST", ["projects"], Role.ADMIN, create_project),
+        ("GET", ["projects", "<name>"], Role.VIEWER, get_project),
+        ("GET", ["projects", "<name>", "hypotheses"], Role.VIEWER, list_hypotheses),
+        ("POST", ["projects", "<name>", "hypotheses", "<id>", "status"], Role.TRIAGER, set_status),
+        ("GET", ["projects", "<name>", "scans"], Role.VIEWER, list_scans),
+        ("POST", ["projects", "<name>", "scans"], Role.TRIAGER, start_scan),
+        ("GET", ["projects", "<name>", "scans", "<id>"], Role.VIEWER, get_scan),
+        ("GET", ["projects", "<name>", "scans", "<id>", "events"], Role.VIEWER, scan_events),
+        ("GET", ["keys"], Role.ADMIN, list_keys),
+        ("POST", ["keys"], Role.ADMIN, create_key),
+        ("DELETE", ["keys", "<id>"], Role.ADMIN, revoke_key),
+    ]
+
+
+class _Handler(BaseHTTPRequestHandler):
+    server_version = "Baskerville/1.0"
+
+    def _send(self, status: HTTPStatus, payload: Any) -> None:
+        data = json.dumps(payload).encode()
+        self.send_response(status)
+        self.send_header("Content-Type", "application/json")
+        self.send_header("Content-Length", str(len(data)))
+        self.end_headers()
+        self.wfile.write(data)
+
+    def _stream(self, stream: EventStream) -> None:
+        # No Content-Length: the body ends when the connection closes
+        self.send_response(HTTPStatus.OK)
+        self.send_header("Content-Type", "application/x-ndjson")
+        self.send_header("Cache-Control", "no-cache")
+        self.end_headers()
+        try:
+            for event in stream.events:
+                self.wfile.write(json.dumps(event).encode() + b"\n")
+                self.wfile.flush()
+        except (BrokenPipeError, ConnectionResetError):
+            pass
+
+    def _secret(self) -> str | None:
+        auth = self.headers.get("Authorization", "")
+        if auth.lower().startswith("bearer "):
+            return auth[7:].strip()
+        return self.headers.get("X-API-Key")
+
+    def _handle(self, method: str) -> None:
+        handlers: ApiHandlers = self.server.handlers  # type: ignore[attr-defined]
+        identity = handlers.store.authenticate(self._secret())
+        if identity is None:
+            self._send(HTTPStatus.UNAUTHORIZED, {"error": "missing or invalid API key"})
+            return
+        # GET parameters come from the query string
+        body: dict[str, Any] = dict(parse_qsl(urlsplit(self.path).query)) if method == "GET" else {}
+        length = int(self.headers.get("Content-Length") or 0)
+        if length:
+            try:
+                body = json.loads(self.rfile.read(length))
+            except ValueError:
+                body = None  # type: ignore[assignment]
+            if not isinstance(body, dict):
+                self._send(HTTPStatus.BAD_REQUEST, {"error": "body must be a JSON object"})
+                return
+        try:
+            result = handlers.dispatch(method, self.path, *identity, body)
+        except ApiError as e:
+            self._send(e.status, {"error": e.message})
+            return
+        except Exception as e:  # noqa: BLE001 - report instead of dropping the connection
+            self._send(HTTPStatus.INTERNAL_SERVER_ERROR, {"error": f"internal error: {type(e).__name__}"})
+            return
+        if isinstance(result, EventStream):
+            self._stream(result)
+            return
+        self._send(HTTPStatus.CREATED if method == "POST" else HTTPStatus.OK, result)
+
+    def do_GET(self):  # noqa: N802
+        self._handle("GET")
+
+    def do_POST(self):  # noqa: N802
+        self._handle("POST")
+
+    def do_DELETE(self):  # noqa: N802
+        self._handle("DELETE")
+
+    def log_message(self, *args):
+        pass
+
+
+class ApiServer:
+    """Threaded HTTP server for the JSON API."""
+
+    def __init__(self, store: TenantStore | None = None, host: str = "127.0.0.1", port: int = 8080,
+                 scans: ScanManager | None = None):
+        """Initialize server.
+
+        Args:
+            store: Tenants and keys (default: ~/.hound/server)
+            host: Listen address
+            port: Listen port (0 picks a free one)
+            scans: Runs and tracks scans (default: the static pipeline in worker threads)
+        """
+        self.store = store or TenantStore()
+        self.httpd = ThreadingHTTPServer((host, port), _Handler)
+        self.httpd.handlers = ApiHandlers(self.store, scans)  # type: ignore[attr-defined]
+        self.thread: threading.Thread | None = None
+
+    @property
+    def address(self) -> tuple[str, int]:
+        return self.httpd.server_address[:2]
+
+    def start(self) -> None:
+        """Serve in a background thread."""
+        self.thread = threading.Thread(target=self.httpd.serve_forever, name="baskerville-api", daemon=True)
+        self.thread.start()
+
+    def serve_forever(self) -> None:
+        self.httpd.serve_forever()
+
+    def stop(self) -> None:
+        # shutdown() waits for serve_forever, so only a background loop needs it
+        if self.thread is not None:
+            self.httpd.shutdown()
+        self.httpd.server_close()
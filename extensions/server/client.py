@@ -0,0 +1,58 @@
+"""
+Minimal client for the server-mode JSON API.
+"""
+
+import json
+from typing import Any, Iterator
+from urllib.error import HTTPError, URLError
+from urllib.request import Request, urlopen
+
+from .app import ApiError
+
+
+class ApiClient:
+    """Calls one server as one API key."""
+
+    def __init__(self, url: str, key: str, timeout: float = 30.0):
+        """Initialize client.
+
+        Args:
+            url: Server base URL ("http://host:port", with or without /v1)
+            key: API key
+            timeout: Seconds to wait for a response (or, when streaming, for the next event)
+        """
+        self.base = url.rstrip("/").removesuffix("/v1") + "/v1"
+        self.key = key
+        self.timeout = timeout
+
+    def _open(self, method: str, path: str, body: dict[str, Any] | None = None):
+        data = json.dumps(body).encode() if body is not None else None
+        request = Request(self.base + path, data=data, method=method,
+                          headers={"Authorization": f"Bearer {self.key}", "Content-Type": "application/json"})
+        try:
+            return urlopen(request, timeout=self.timeout)
+        except HTTPError as e:
+            try:
+                message = json.loads(e.read()).get("error", e.reason)
+            except ValueError:
+                message = e.reason
+            raise ApiError(e.code, message) from None
+        except URLError as e:
+            raise ApiError(0, f"cannot reach {self.base}: {e.reason}") from None
+
+    def request(self, method: str, path: str, body: dict[str, Any] | None = None) -> Any:
+        with self._open(method, path, body) as response:
+            return json.loads(response.read())
+
+    def stream(self, path: str) -> Iterator[dict[str, Any]]:
+        """Yield the objects of a newline-delimited JSON response as they arrive."""
+        with self._open("GET", path) as response:
+            for line in response:
+                if line.strip():
+                    yield json.loads(line)
+
+    def start_scan(self, project: str, tools: list[str] | None = None) -> dict[str, Any]:
+        return self.request("POST", f"/projects/{project}/scans", {"tools": tools} if tools else {})
+
+    def scan_events(self, project: str, scan_id: str, since: int = 0) -> Iterator[dict[str, Any]]:
+        return self.stream(f"/projects/{project}/scans/{scan_id}/events?since={since}")
@@ -0,0 +1,108 @@
+"""
+Background static analysis scans for server mode.
+
+A scan runs the static pipeline on one of a tenant's projects in a worker
+thread and records its progress events, so API clients can start it, poll
+it, or stream the events as they happen. Results are saved to the project's
+``static_analysis/`` directory, as `static` does on the command line.
+"""
+
+import secrets
+import threading
+from dataclasses import dataclass, field, replace
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Callable
+
+from extensions.static.progress import ProgressEvent, ScanProgress
+
+
+@dataclass
+class ScanJob:
+    """One static analysis run of a tenant's project."""
+
+    id: str
+    tenant: str
+    project: str
+    tools: list[str] | None = None
+    created_by: str = ""
+    created_at: str = field(default_factory=lambda: datetime.now().isoformat())
+    progress: ScanProgress = field(default_factory=ScanProgress)
+
+    @property
+    def status(self) -> str:
+        latest = self.progress.latest
+        if latest is None:
+            return "queued"
+        if latest.event == "finished":
+            return "completed"
+        return "failed" if latest.event == "failed" else "running"
+
+    def public(self) -> dict[str, Any]:
+        latest = self.progress.latest
+        return {
+            "id": self.id,
+            "project": self.project,
+            "tools": self.tools,
+            "created_by": self.created_by,
+            "created_at": self.created_at,
+            "status": self.status,
+            "progress": latest.to_dict() if latest else None,
+        }
+
+
+# (project config, tools, progress callback) -> None
+ScanRunner = Callable[[dict[str, Any], "list[str] | None", Callable[[ProgressEvent], None]], None]
+
+
+def _run_pipeline(project: dict[str, Any], tools: list[str] | None, progress: Callable[[ProgressEvent], None]) -> None:
+    from extensions.static import StaticAnalysisPipeline
+
+    pipeline = StaticAnalysisPipeline(chain_id=project.get("chain_id", "evm"))
+    result = pipeline.run(Path(project["source_path"]), tools, progress=progress)
+    pipeline.save_results(result, Path(project["path"]) / "static_analysis")
+
+
+class ScanManager:
+    """Starts scans and keeps them, by tenant, for the life of the server."""
+
+    def __init__(self, run: ScanRunner = _run_pipeline):
+        """Initialize manager.
+
+        Args:
+            run: Runs one scan, reporting to the progress callback (default: the static pipeline)
+        """
+        self._run = run
+        self._jobs: dict[tuple[str, str], ScanJob] = {}
+        self._lock = threading.Lock()
+
+    def start(self, tenant_id: str, project: dict[str, Any], tools: list[str] | None = None,
+              created_by: str = "") -> ScanJob:
+        job = ScanJob(id=secrets.token_hex(6), tenant=tenant_id, project=project["name"], tools=tools,
+                      created_by=created_by)
+        with self._lock:
+            self._jobs[(tenant_id, job.id)] = job
+        threading.Thread(target=self._work, args=(job, project), name=f"scan-{job.id}", daemon=True).start()
+        return job
+
+    def _work(self, job: ScanJob, project: dict[str, Any]) -> None:
+        try:
+            self._run(project, job.tools, job.progress.emit)
+        except Exception as e:  # noqa: BLE001 - surfaced to clients as a failed event
+            # Keep the counters reached so far
+            latest = job.progress.latest or ProgressEvent("started")
+            job.progress.emit(replace(latest, event="failed", detector=None, error=f"{type(e).__name__}: {e}",
+                                      at=datetime.now().isoformat()))
+            return
+        if not job.progress.done:
+            job.progress.emit(ProgressEvent("finished"))
+
+    def get(self, tenant_id: str, project: str, scan_id: str) -> ScanJob | None:
+        with self._lock:
+            job = self._jobs.get((tenant_id, scan_id))
+        return job if job is not None and job.project == project else None
+
+    def list(self, tenant_id: str, project: str) -> list[ScanJob]:
+        with self._lock:
+            jobs = [j for (t, _), j in self._jobs.items() if t == tenant_id and j.project == project]
+        return sorted(jobs, key=lambda j: j.created_at)
@@ -0,0 +1,244 @@
+"""
+Tenants, API keys and roles for server mode.
+
+One server can back several client engagements. Each tenant gets its own
+storage namespace (``<base>/tenants/<id>/``, holding its projects), and every
+request carries an API key that belongs to exactly one tenant and grants one
+role:
+
+- viewer: read projects, hypotheses and reports
+- triager: viewer, plus changing hypothesis status
+- admin: triager, plus creating projects and managing the tenant's API keys
+
+Keys are shown once when created; only their SHA-256 is stored.
+"""
+
+import hashlib
+import hmac
+import json
+import re
+import secrets
+import threading
+from dataclasses import asdict, dataclass, field
+from datetime import datetime
+from enum import Enum
+from pathlib import Path
+from typing import Any
+
+KEY_PREFIX = "bsk"
+_ID = re.compile(r"^[a-z0-9][a-z0-9_-]{0,62}$")
+_PROJECT = re.compile(r"^[A-Za-z0-9][A-Za-z0-9_.-]{0,127}$")
+
+
+class Role(str, Enum):
+    """What an API key may do within its tenant."""
+
+    VIEWER = "viewer"
+    TRIAGER = "triager"
+    ADMIN = "admin"
+
+    def __str__(self) -> str:
+        return self.value
+
+    def allows(self, required: "Role") -> bool:
+        """Whether this role includes everything ``required`` may do."""
+        return _RANK[self] >= _RANK[required]
+
+
+_RANK = {Role.VIEWER: 0, Role.TRIAGER: 1, Role.ADMIN: 2}
+
+
+class TenancyError(ValueError):
+    """Invalid tenant or key operation."""
+
+
+def validate_id(value: str, kind: str = "tenant id") -> str:
+    """A tenant id safe to use as a directory name."""
+    if not isinstance(value, str) or not _ID.match(value):
+        raise TenancyError(f"invalid {kind} {value!r} (lowercase letters, digits, '-' and '_')")
+    return value
+
+
+def validate_project_name(value: str) -> str:
+    """A project name that stays inside the tenant's namespace."""
+    if not isinstance(value, str) or not _PROJECT.match(value) or ".." in value:
+        raise TenancyError(f"invalid project name {value!r}")
+    return value
+
+
+def _hash(secret: str) -> str:
+    return hashlib.sha256(secret.encode()).hexdigest()
+
+
+@dataclass
+class Tenant:
+    """A client engagement with its own storage namespace."""
+
+    id: str
+    name: str
+    created_at: str = field(default_factory=lambda: datetime.now().isoformat())
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "Tenant":
+        return cls(id=data["id"], name=data.get("name", data["id"]), created_at=data.get("created_at", ""))
+
+
+@dataclass
+class ApiKey:
+    """An API key record; the secret itself is never stored."""
+
+    id: str
+    tenant: str
+    role: Role
+    key_hash: str
+    label: str = ""
+    created_at: str = field(default_factory=lambda: datetime.now().isoformat())
+    revoked: bool = False
+
+    def to_dict(self) -> dict[str, Any]:
+        data = asdict(self)
+        data["role"] = self.role.value
+        return data
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "ApiKey":
+        return cls(id=data["id"], tenant=data["tenant"], role=Role(data["role"]), key_hash=data["key_hash"],
+                   label=data.get("label", ""), created_at=data.get("created_at", ""),
+                   revoked=bool(data.get("revoked", False)))
+
+    def public(self) -> dict[str, Any]:
+        """The record without its hash, for listings."""
+        return {k: v for k, v in self.to_dict().items() if k != "key_hash"}
+
+
+class TenantStore:
+    """Tenants and API keys, persisted in ``<base>/tenants.json``."""
+
+    def __init__(self, base_dir: Path | None = None):
+        """Initialize store.
+
+        Args:
+            base_dir: Server data directory. Defaults to ~/.hound/server
+        """
+        if base_dir is None:
+            base_dir = Path.home() / ".hound" / "server"
+        self.base_dir = Path(base_dir)
+        self.file = self.base_dir / "tenants.json"
+        # Server handlers update the file from several threads
+        self._lock = threading.RLock()
+
+    def _read(self) -> dict[str, Any]:
+        try:
+            data = json.loads(self.file.read_text())
+        except (OSError, ValueError):
+            data = {}
+        return {"tenants": data.get("tenants", {}), "keys": data.get("keys", {})}
+
+    def _write(self, data: dict[str, Any]) -> None:
+        self.base_dir.mkdir(parents=True, exist_ok=True)
+        tmp = self.file.with_suffix(".tmp")
+        tmp.write_text(json.dumps(data, indent=2))
+        tmp.chmod(0o600)
+        tmp.replace(self.file)
+
+    # Tenants
+
+    def add_tenant(self, tenant_id: str, name: str | None = None) -> Tenant:
+        """Register a tenant and create its storage namespace."""
+        validate_id(tenant_id)
+        with self._lock:
+            data = self._read()
+            if tenant_id in data["tenants"]:
+                raise TenancyError(f"tenant '{tenant_id}' already exists")
+            tenant = Tenant(id=tenant_id, name=name or tenant_id)
+            data["tenants"][tenant_id] = asdict(tenant)
+            self._write(data)
+            self.projects_dir(tenant_id).mkdir(parents=True, exist_ok=True)
+            return tenant
+
+    def get_tenant(self, tenant_id: str) -> Tenant | None:
+        entry = self._read()["tenants"].get(tenant_id)
+        return Tenant.from_dict(entry) if entry else None
+
+    def list_tenants(self) -> list[Tenant]:
+        return [Tenant.from_dict(t) for _, t in sorted(self._read()["tenants"].items())]
+
+    def remove_tenant(self, tenant_id: str) -> bool:
+        """Unregister a tenant and revoke its keys; its storage is left on disk."""
+        with self._lock:
+            data = self._read()
+            if data["tenants"].pop(tenant_id, None) is None:
+                return False
+            for key in data["keys"].values():
+                if key["tenant"] == tenant_id:
+                    key["revoked"] = True
+            self._write(data)
+            return True
+
+    def storage_dir(self, tenant_id: str) -> Path:
+        """Root of a tenant's storage namespace."""
+        return self.base_dir / "tenants" / validate_id(tenant_id)
+
+    def projects_dir(self, tenant_id: str) -> Path:
+        return self.storage_dir(tenant_id) / "projects"
+
+    def projects(self, tenant_id: str):
+        """ProjectManager over the tenant's projects only."""
+        from commands.project import ProjectManager
+
+        return ProjectManager(projects_dir=self.projects_dir(tenant_id))
+
+    # Keys
+
+    def create_key(self, tenant_id: str, role: Role | str, label: str = "") -> tuple[ApiKey, str]:
+        """Issue a key for a tenant.
+
+        Returns:
+            (record, secret); the secret is not recoverable afterwards
+        """
+        with self._lock:
+            data = self._read()
+            if tenant_id not in data["tenants"]:
+                raise TenancyError(f"unknown tenant '{tenant_id}'")
+            try:
+                role = Role(str(role).lower())
+            except ValueError:
+                roles = ", ".join(r.value for r in Role)
+                raise TenancyError(f"unknown role '{role}' (expected one of {roles})") from None
+            key_id = secrets.token_hex(4)
+            while key_id in data["keys"]:
+                key_id = secrets.token_hex(4)
+            secret = f"{KEY_PREFIX}_{key_id}_{secrets.token_urlsafe(24)}"
+            record = ApiKey(id=key_id, tenant=tenant_id, role=role, key_hash=_hash(secret), label=label)
+            data["keys"][key_id] = record.to_dict()
+            self._write(data)
+            return record, secret
+
+    def list_keys(self, tenant_id: str | None = None) -> list[ApiKey]:
+        keys = [ApiKey.from_dict(k) for k in self._read()["keys"].values()]
+        return sorted((k for k in keys if tenant_id is None or k.tenant == tenant_id), key=lambda k: k.created_at)
+
+    def revoke_key(self, key_id: str, tenant_id: str | None = None) -> bool:
+        """Revoke a key (only one of ``tenant_id``'s, when given)."""
+        with self._lock:
+            data = self._read()
+            entry = data["keys"].get(key_id)
+            if entry is None or (tenant_id is not None and entry["tenant"] != tenant_id):
+                return False
+            entry["revoked"] = True
+            self._write(data)
+            return True
+
+    def authenticate(self, secret: str | None) -> tuple[Tenant, ApiKey] | None:
+        """The tenant and key a secret belongs to, or None if it is unknown or revoked."""
+        parts = (secret or "").split("_", 2)
+        if len(parts) != 3 or parts[0] != KEY_PREFIX:
+            return None
+        data = self._read()
+        entry = data["keys"].get(parts[1])
+        if entry is None or entry.get("revoked") or not hmac.compare_digest(entry["key_hash"], _hash(secret)):
+            return None
+        tenant = data["tenants"].get(entry["tenant"])
+        if tenant is None:
+            return None
+        return Tenant.from_dict(tenant), ApiKey.from_dict(entry)
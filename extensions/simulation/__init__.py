@@ -0,0 +1,191 @@
+"""
+Agent-based economic simulation.
+
+Configurable agents (borrowers, liquidators, arbitrageurs, oracle-lag
+attackers) interact with a protocol over many simulated slots, against a
+stochastic reference price and a lagging oracle, to surface economic
+exploits static rules cannot see: incentive parameters that leave
+liquidations unprofitable, bad debt under realistic price paths, and
+value extracted through oracle lag.
+
+- Protocols: a reference lending model, or an ``SvmProtocol`` adapter that
+  runs the deployed program on the LiteSVM execution backend
+- Backend clock: warps to a slot or epoch and unix_timestamp changes, with
+  slot, epoch and time kept consistent
+- Agents: per-slot strategies with balances and mark-to-market PnL
+- Engine: seeded repeated runs aggregated into hypotheses
+- Math stress: Monte Carlo runs of fee/reward/interest calculations
+  against exact arithmetic, reporting drift, overflow headroom and
+  precision loss
+- Liquidation sweeps: crash scenarios across price drops, oracle
+  latencies and position sizes, locating unprofitable liquidations and
+  bad debt for the report's economic-risk section
+- Account substitution: a legitimate transaction replayed with each account
+  swapped for an attacker lookalike (wrong signer, owner, mint, PDA or a
+  duplicate), reporting the substitutions the program accepts
+- Argument boundaries: each instruction driven with 0, 1, integer limits,
+  account size limits and the longest values that fit, for arguments typed
+  from the IDL; panics, overflows and unexpected successes become findings
+- Differential execution: the same transaction corpus on an original and a
+  patched build, diffing outcomes, normalized logs and account state to
+  verify a patch changes only what it is meant to
+- Upgrade migration: accounts in a previous version's layout run through
+  the new build, classified as bricked, corrupted, migrated or compatible
+- Exploit search: boundary values, integer nudges and account substitutions
+  stacked and steered by SBF coverage from the backend, prioritizing inputs
+  that reach validation branches not yet passed
+- Fuzzing corpora: queue inputs, crashes and exploit witnesses stored per
+  project and instruction, replayed by later searches and against fixed
+  builds, and minimized
+"""
+
+from .agents import AGENT_TYPES, Agent, Arbitrageur, Borrower, Liquidator, OracleLagAttacker, World, make_agent
+from .backend import (
+    AccountMeta,
+    BackendError,
+    Clock,
+    EpochSchedule,
+    ExecutionBackend,
+    Instruction,
+    LiteSvmBackend,
+    TxResult,
+    read_sbf_traces,
+)
+from .boundary import (
+    BoundaryCase,
+    BoundaryFinding,
+    BoundaryFuzzer,
+    BoundaryResult,
+    InstructionSpec,
+    load_boundaries,
+    load_idl,
+)
+from .corpus import CorpusRecord, CorpusStore, Replay, boundary_records, minimize_corpus, minimize_input, replay
+from .differential import AccountDiff, DiffResult, DifferentialRun, TransactionDiff, load_differential
+from .engine import (
+    EconomicFinding,
+    RunResult,
+    Simulation,
+    SimulationConfig,
+    SimulationResult,
+    backend_factory,
+    load_simulation,
+    protocol_factory,
+)
+from .liquidation import CrashPath, LiquidationSweep, ScenarioResult, SweepConfig, SweepResult, load_sweep
+from .market import OracleModel, PriceModel
+from .migration import Fixture, FixtureResult, MigrationFinding, MigrationResult, MigrationSimulation, load_migration
+from .math_stress import (
+    Distribution,
+    Evaluator,
+    MathStressFinding,
+    ProgramCalculation,
+    StressCase,
+    StressReport,
+    evaluate_report,
+    load_cases,
+    run_case,
+)
+from .protocols import PROTOCOL_MODELS, Action, ActionResult, LendingMarket, ProtocolModel, SvmProtocol
+from .search import CorpusEntry, CoverageMap, ExploitSearch, SearchFinding, SearchInput, SearchResult, load_search
+from .substitution import (
+    AccountSlot,
+    ChainState,
+    Substitution,
+    SubstitutionFinding,
+    SubstitutionFuzzer,
+    SubstitutionResult,
+    TransactionSpec,
+    load_substitution,
+)
+
+__all__ = [
+    "AGENT_TYPES",
+    "Agent",
+    "Arbitrageur",
+    "Borrower",
+    "Liquidator",
+    "OracleLagAttacker",
+    "World",
+    "make_agent",
+    "AccountMeta",
+    "BackendError",
+    "Clock",
+    "EpochSchedule",
+    "ExecutionBackend",
+    "Instruction",
+    "LiteSvmBackend",
+    "TxResult",
+    "read_sbf_traces",
+    "BoundaryCase",
+    "BoundaryFinding",
+    "BoundaryFuzzer",
+    "BoundaryResult",
+    "InstructionSpec",
+    "load_boundaries",
+    "load_idl",
+    "CorpusRecord",
+    "CorpusStore",
+    "Replay",
+    "boundary_records",
+    "minimize_corpus",
+    "minimize_input",
+    "replay",
+    "AccountDiff",
+    "DiffResult",
+    "DifferentialRun",
+    "TransactionDiff",
+    "load_differential",
+    "EconomicFinding",
+    "RunResult",
+    "Simulation",
+    "SimulationConfig",
+    "SimulationResult",
+    "backend_factory",
+    "load_simulation",
+    "protocol_factory",
+    "CrashPath",
+    "LiquidationSweep",
+    "ScenarioResult",
+    "SweepConfig",
+    "SweepResult",
+    "load_sweep",
+    "OracleModel",
+    "PriceModel",
+    "Fixture",
+    "FixtureResult",
+    "MigrationFinding",
+    "MigrationResult",
+    "MigrationSimulation",
+    "load_migration",
+    "Distribution",
+    "Evaluator",
+    "MathStressFinding",
+    "ProgramCalculation",
+    "StressCase",
+    "StressReport",
+    "evaluate_report",
+    "load_cases",
+    "run_case",
+    "PROTOCOL_MODELS",
+    "Action",
+    "ActionResult",
+    "LendingMarket",
+    "ProtocolModel",
+    "SvmProtocol",
+    "CorpusEntry",
+    "CoverageMap",
+    "ExploitSearch",
+    "SearchFinding",
+    "SearchInput",
+    "SearchResult",
+    "load_search",
+    "AccountSlot",
+    "ChainState",
+    "Substitution",
+    "SubstitutionFinding",
+    "SubstitutionFuzzer",
+    "SubstitutionResult",
+    "TransactionSpec",
+    "load_substitution",
+]
@@ -0,0 +1,226 @@
+"""
+Simulation agents.
+
+Each agent holds quote and collateral balances, looks at the world once
+per slot and returns actions. ``trade`` actions go to the external venue
+at the reference price (with price impact when the venue has finite
+depth); everything else goes to the protocol. An amount
+of None means "everything I hold", and the engine clamps amounts to the
+agent's balance, so agents can size trades from estimates.
+"""
+
+import random
+from collections import Counter
+from dataclasses import dataclass
+from typing import Any
+
+from .protocols import Action, ActionResult
+
+
+@dataclass
+class World:
+    """What an agent observes in a slot."""
+
+    slot: int
+    price: float
+    oracle_price: float
+    view: dict[str, Any]
+    external_fee: float
+    rng: random.Random
+    # Quote-side depth of the external constant-product venue; None for unlimited
+    external_depth: float | None = None
+
+    def sell_value(self, amount: float) -> float:
+        """Quote received for selling ``amount`` collateral on the external venue."""
+        amount *= 1 - self.external_fee
+        if self.external_depth is None:
+            return amount * self.price
+        return self.external_depth * amount / (self.external_depth / self.price + amount)
+
+    def buy_amount(self, quote: float) -> float:
+        """Collateral received for spending ``quote`` on the external venue."""
+        quote *= 1 - self.external_fee
+        if self.external_depth is None:
+            return quote / self.price
+        return self.external_depth / self.price * quote / (self.external_depth + quote)
+
+
+class Agent:
+    """Base agent: balances, action bookkeeping and mark-to-market value."""
+
+    kind = "agent"
+    # Agents whose profit comes out of the protocol
+    extractive = False
+
+    def __init__(self, name: str | None = None, quote: float = 0.0, collateral: float = 0.0):
+        self.name = name or self.kind
+        self.balances = {"quote": float(quote), "collateral": float(collateral)}
+        self.initial = dict(self.balances)
+        self.actions: Counter = Counter()
+        self.failures: Counter = Counter()
+
+    def act(self, world: World) -> list[Action]:
+        raise NotImplementedError
+
+    def settle(self, action: Action, result: ActionResult) -> None:
+        if not result.ok:
+            self.failures[action.kind] += 1
+            return
+        self.actions[action.kind] += 1
+        for asset, delta in result.deltas.items():
+            self.balances[asset] = self.balances.get(asset, 0.0) + delta
+
+    def value(self, price: float, view: dict[str, Any]) -> float:
+        """Holdings plus equity in own (non-recourse) positions at ``price``."""
+        equity = sum(
+            max(0.0, p["collateral"] * price - p["debt"]) for p in view.get("positions", []) if p["owner"] == self.name
+        )
+        return self.balances["quote"] + self.balances["collateral"] * price + equity
+
+    def pnl(self, price: float, view: dict[str, Any]) -> float:
+        """Profit against having held the initial balances."""
+        return self.value(price, view) - (self.initial["quote"] + self.initial["collateral"] * price)
+
+    def summary(self) -> dict[str, Any]:
+        return {"kind": self.kind, "actions": dict(self.actions), "failures": dict(self.failures)}
+
+    def _action(self, kind: str, **params: Any) -> Action:
+        return Action(kind, self.name, params)
+
+
+class Borrower(Agent):
+    """Passive users: open positions near the loan-to-value limit in the first slot and hold."""
+
+    kind = "borrower"
+
+    def __init__(self, name: str | None = None, positions: int = 10, collateral: float = 10.0,
+                 utilization: float = 0.8, spread: float = 0.1, quote: float = 0.0):
+        super().__init__(name, quote, positions * collateral)
+        self.positions = positions
+        self.size = collateral
+        self.utilization = utilization
+        self.spread = spread
+        self.opened = False
+
+    def act(self, world: World) -> list[Action]:
+        if self.opened:
+            return []
+        self.opened = True
+        limit = self.size * world.oracle_price * world.view["params"]["ltv"]
+        return [
+            self._action("open", collateral=self.size,
+                         borrow=limit * min(1.0, self.utilization * (1 + world.rng.uniform(-self.spread, self.spread))))
+            for _ in range(self.positions)
+        ]
+
+
+class Liquidator(Agent):
+    """Liquidates unhealthy positions when the bonus covers the exit and its costs."""
+
+    kind = "liquidator"
+
+    def __init__(self, name: str | None = None, quote: float = 100_000.0, cost: float = 0.0,
+                 min_profit: float = 0.0, collateral: float = 0.0):
+        super().__init__(name, quote, collateral)
+        self.cost = cost
+        self.min_profit = min_profit
+        # Position id -> (first slot declined, best expected profit)
+        self.declined: dict[int, tuple[int, float]] = {}
+
+    def act(self, world: World) -> list[Action]:
+        actions = []
+        if self.balances["collateral"] > 0:
+            actions.append(self._action("trade", side="sell", amount=None))
+        params = world.view["params"]
+        budget = self.balances["quote"]
+        for pos in world.view["positions"]:
+            if pos["health"] >= 1 or pos["debt"] <= 0:
+                continue
+            insolvent = pos["collateral"] * world.oracle_price < pos["debt"]
+            repay = min(pos["debt"] * (1.0 if insolvent else params["close_factor"]), budget)
+            if repay <= 0:
+                continue
+            seized = min(pos["collateral"], repay * (1 + params["liquidation_bonus"]) / world.oracle_price)
+            profit = world.sell_value(seized) - repay - self.cost
+            if profit <= self.min_profit:
+                first, best = self.declined.get(pos["id"], (world.slot, profit))
+                self.declined[pos["id"]] = (first, max(best, profit))
+                continue
+            actions.append(self._action("liquidate", position=pos["id"], repay=repay))
+            budget -= repay
+        return actions
+
+    def summary(self) -> dict[str, Any]:
+        return {**super().summary(), "declined": len(self.declined)}
+
+
+class Arbitrageur(Agent):
+    """Trades the protocol's oracle-priced swap against the external venue."""
+
+    kind = "arbitrageur"
+    extractive = True
+
+    def __init__(self, name: str | None = None, quote: float = 100_000.0, threshold: float = 0.0,
+                 size: float | None = None, collateral: float = 0.0):
+        super().__init__(name, quote, collateral)
+        self.threshold = threshold
+        self.size = size
+
+    def act(self, world: World) -> list[Action]:
+        fee = world.view["params"].get("swap_fee")
+        if fee is None:
+            return []
+        budget = min(self.balances["quote"], self.size or float("inf"))
+        gap = world.oracle_price / world.price - 1
+        cost = fee + world.external_fee + self.threshold
+        if gap > cost:
+            # The protocol overpays for collateral: buy outside, sell in
+            return [self._action("trade", side="buy", amount=budget), self._action("swap", side="sell", amount=None)]
+        if gap < -cost and world.view["inventory"] > 0:
+            affordable = world.view["inventory"] * world.oracle_price / (1 - fee)
+            return [
+                self._action("swap", side="buy", amount=min(budget, affordable)),
+                self._action("trade", side="sell", amount=None),
+            ]
+        return []
+
+
+class OracleLagAttacker(Agent):
+    """Borrows against collateral the stale oracle overvalues and walks away from the debt."""
+
+    kind = "oracle-lag-attacker"
+    extractive = True
+
+    def __init__(self, name: str | None = None, quote: float = 100_000.0, min_edge: float = 0.0,
+                 size: float | None = None, collateral: float = 0.0):
+        super().__init__(name, quote, collateral)
+        self.min_edge = min_edge
+        self.size = size
+
+    def act(self, world: World) -> list[Action]:
+        ltv = world.view["params"]["ltv"]
+        # Quote borrowed per unit of quote spent buying collateral outside
+        edge = (1 - world.external_fee) * ltv * world.oracle_price / world.price - 1
+        if edge <= self.min_edge:
+            return []
+        budget = min(self.balances["quote"], self.size or float("inf"), world.view["liquidity"] / (1 + edge))
+        if budget <= 0:
+            return []
+        return [
+            self._action("trade", side="buy", amount=budget),
+            self._action("open", collateral=None, borrow=None),
+        ]
+
+
+AGENT_TYPES: dict[str, type[Agent]] = {
+    cls.kind: cls for cls in (Borrower, Liquidator, Arbitrageur, OracleLagAttacker)
+}
+
+
+def make_agent(spec: dict[str, Any]) -> Agent:
+    """Build an agent from a config entry: ``{"type": "liquidator", "quote": 50000, ...}``."""
+    spec = dict(spec)
+    kind = spec.pop("type", None)
+    if kind not in AGENT_TYPES:
+        raise ValueError(f"unknown agent type {kind!r} (expected one of {', '.join(AGENT_TYPES)})")
+    return AGENT_TYPES[kind](**spec)
@@ -0,0 +1,491 @@
+"""
+Program execution backends.
+
+``LiteSvmBackend`` runs deployed SBF programs in-process with LiteSVM (the
+`litesvm` npm package) instead of a full validator: a small Node driver is
+started once per simulation run and spoken to over JSON lines on stdio, so
+thousands of transactions and slot warps stay cheap. Keypairs live inside
+the driver and are referred to by name; anything else that looks like a
+public key is passed through as base58. ``LiteSvmBackend.from_anchor``
+loads the programs and preloaded accounts of the workspace's Anchor.toml.
+
+Backends expose the Clock sysvar for time travel: warping to a slot or
+epoch, or setting ``unix_timestamp``, moves slot, epoch and time together
+(400ms slots, the cluster's epoch schedule) so staking, vesting and
+staleness logic sees a consistent clock.
+
+With ``trace`` on, the driver runs with ``SBF_TRACE_DIR`` set, and the SBF
+register traces the runtime writes there become each transaction's
+coverage: the distinct (program, pc, next pc) edges it executed, in the
+order they were first taken.
+"""
+
+import base64
+import json
+import os
+import shutil
+import struct
+import subprocess
+import tempfile
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import TYPE_CHECKING, Any
+
+if TYPE_CHECKING:
+    from extensions.ir.anchor_config import AnchorConfig
+
+BACKEND_VERSION = "1.0.0"
+
+# Target slot time, used to advance unix_timestamp with the slot
+SLOT_MS = 400
+# Warmup epochs start at 32 slots and double until the normal epoch length
+MINIMUM_SLOTS_PER_EPOCH = 32
+# A register trace holds r0-r10 and the pc (u64 each) per executed instruction
+TRACE_REGISTERS = 12
+
+_DRIVER = r"""
+const readline = require("readline");
+const { LiteSVM } = require("litesvm");
+const { Keypair, PublicKey, Transaction, TransactionInstruction } = require("@solana/web3.js");
+
+const svm = new LiteSVM();
+const keypairs = {};
+const key = (k) => (keypairs[k] ? keypairs[k].publicKey : new PublicKey(k));
+
+const ops = {
+  add_program({ program_id, path }) {
+    svm.addProgramFromFile(key(program_id), path);
+    return null;
+  },
+  keypair({ name, lamports }) {
+    if (!keypairs[name]) {
+      keypairs[name] = Keypair.generate();
+      if (lamports) svm.airdrop(keypairs[name].publicKey, BigInt(lamports));
+    }
+    return keypairs[name].publicKey.toBase58();
+  },
+  airdrop({ pubkey, lamports }) {
+    svm.airdrop(key(pubkey), BigInt(lamports));
+    return null;
+  },
+  set_account({ pubkey, lamports, data, owner, executable }) {
+    svm.setAccount(key(pubkey), {
+      lamports: Number(lamports), data: Buffer.from(data, "base64"), owner: key(owner),
+      executable: !!executable, rentEpoch: 0,
+    });
+    return null;
+  },
+  get_account({ pubkey }) {
+    const a = svm.getAccount(key(pubkey));
+    if (!a) return null;
+    return { lamports: Number(a.lamports), data: Buffer.from(a.data).toString("base64"),
+             owner: a.owner.toBase58(), executable: a.executable };
+  },
+  warp({ slot }) {
+    svm.warpToSlot(BigInt(slot));
+    return null;
+  },
+  get_clock() {
+    const c = svm.getClock();
+    return { slot: Number(c.slot), epoch_start_timestamp: Number(c.epochStartTimestamp), epoch: Number(c.epoch),
+             leader_schedule_epoch: Number(c.leaderScheduleEpoch), unix_timestamp: Number(c.unixTimestamp) };
+  },
+  set_clock({ slot, epoch_start_timestamp, epoch, leader_schedule_epoch, unix_timestamp }) {
+    if (BigInt(slot) !== svm.getClock().slot) svm.warpToSlot(BigInt(slot));
+    const c = svm.getClock();
+    c.slot = BigInt(slot);
+    c.epochStartTimestamp = BigInt(epoch_start_timestamp);
+    c.epoch = BigInt(epoch);
+    c.leaderScheduleEpoch = BigInt(leader_schedule_epoch);
+    c.unixTimestamp = BigInt(unix_timestamp);
+    svm.setClock(c);
+    return null;
+  },
+  epoch_schedule() {
+    if (!svm.getEpochSchedule) return null;
+    const s = svm.getEpochSchedule();
+    return { slots_per_epoch: Number(s.slotsPerEpoch), leader_schedule_slot_offset: Number(s.leaderScheduleSlotOffset),
+             warmup: s.warmup, first_normal_epoch: Number(s.firstNormalEpoch),
+             first_normal_slot: Number(s.firstNormalSlot) };
+  },
+  send({ instructions, payer, signers }) {
+    const tx = new Transaction();
+    for (const ix of instructions) {
+      tx.add(new TransactionInstruction({
+        programId: key(ix.program_id),
+        keys: ix.accounts.map((a) => ({ pubkey: key(a.pubkey), isSigner: a.is_signer, isWritable: a.is_writable })),
+        data: Buffer.from(ix.data, "base64"),
+      }));
+    }
+    tx.recentBlockhash = svm.latestBlockhash();
+    tx.feePayer = keypairs[payer].publicKey;
+    tx.sign(...[...new Set([payer, ...signers])].map((n) => keypairs[n]));
+    const res = svm.sendTransaction(tx);
+    svm.expireBlockhash();
+    if (typeof res.err === "function") {
+      const meta = res.meta();
+      return { ok: false, error: String(res.err()), logs: meta.logs(), compute_units: Number(meta.computeUnitsConsumed()) };
+    }
+    return { ok: true, error: null, logs: res.logs(), compute_units: Number(res.computeUnitsConsumed()) };
+  },
+};
+
+readline.createInterface({ input: process.stdin }).on("line", (line) => {
+  const { id, op, params } = JSON.parse(line);
+  let reply;
+  try {
+    reply = { id, result: ops[op](params) };
+  } catch (e) {
+    reply = { id, error: String((e && e.message) || e) };
+  }
+  process.stdout.write(JSON.stringify(reply) + "\n");
+});
+"""
+
+
+class BackendError(RuntimeError):
+    """Raised when the execution backend cannot start or rejects a request."""
+
+
+@dataclass
+class AccountMeta:
+    """Account reference of an instruction (keypair name or base58 key)."""
+
+    pubkey: str
+    is_signer: bool = False
+    is_writable: bool = False
+
+
+@dataclass
+class Instruction:
+    """A raw instruction to execute."""
+
+    program_id: str
+    accounts: list[AccountMeta] = field(default_factory=list)
+    data: bytes = b""
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "program_id": self.program_id,
+            "accounts": [
+                {"pubkey": a.pubkey, "is_signer": a.is_signer, "is_writable": a.is_writable} for a in self.accounts
+            ],
+            "data": base64.b64encode(self.data).decode(),
+        }
+
+
+@dataclass
+class EpochSchedule:
+    """The cluster's epoch schedule (mainnet defaults, which LiteSVM also uses)."""
+
+    slots_per_epoch: int = 432_000
+    leader_schedule_slot_offset: int = 432_000
+    warmup: bool = True
+    first_normal_epoch: int = 14
+    first_normal_slot: int = 524_256
+
+    def epoch_of(self, slot: int) -> int:
+        if self.warmup and slot < self.first_normal_slot:
+            # Epoch n of the warmup holds 32 * 2^n slots
+            return (slot + MINIMUM_SLOTS_PER_EPOCH).bit_length() - MINIMUM_SLOTS_PER_EPOCH.bit_length()
+        return self.first_normal_epoch + (slot - self.first_normal_slot) // self.slots_per_epoch
+
+    def first_slot(self, epoch: int) -> int:
+        if self.warmup and epoch <= self.first_normal_epoch:
+            return (2 ** epoch - 1) * MINIMUM_SLOTS_PER_EPOCH
+        return self.first_normal_slot + (epoch - self.first_normal_epoch) * self.slots_per_epoch
+
+    def leader_schedule_epoch(self, slot: int) -> int:
+        if self.warmup and slot < self.first_normal_slot:
+            return self.epoch_of(slot) + 1
+        return self.first_normal_epoch + (
+            slot - self.first_normal_slot + self.leader_schedule_slot_offset) // self.slots_per_epoch
+
+
+@dataclass
+class Clock:
+    """The Clock sysvar."""
+
+    slot: int = 0
+    epoch_start_timestamp: int = 0
+    epoch: int = 0
+    leader_schedule_epoch: int = 1
+    unix_timestamp: int = 0
+
+    def advanced(self, schedule: EpochSchedule, slots: int = 0, seconds: int | None = None) -> "Clock":
+        """The clock ``slots`` later; time moves ``seconds``, or with the slots at the target slot time."""
+        if slots < 0:
+            raise BackendError(f"cannot warp back {-slots} slots (the clock only moves forward)")
+        slot = self.slot + slots
+        unix_timestamp = self.unix_timestamp + (slots * SLOT_MS // 1000 if seconds is None else seconds)
+        epoch = schedule.epoch_of(slot)
+        return Clock(slot=slot, epoch=epoch, leader_schedule_epoch=schedule.leader_schedule_epoch(slot),
+                     unix_timestamp=unix_timestamp,
+                     epoch_start_timestamp=self.epoch_start_timestamp if epoch == self.epoch else unix_timestamp)
+
+    def to_dict(self) -> dict[str, int]:
+        return {"slot": self.slot, "epoch_start_timestamp": self.epoch_start_timestamp, "epoch": self.epoch,
+                "leader_schedule_epoch": self.leader_schedule_epoch, "unix_timestamp": self.unix_timestamp}
+
+
+@dataclass
+class TxResult:
+    """Outcome of one transaction."""
+
+    ok: bool
+    error: str | None = None
+    logs: list[str] = field(default_factory=list)
+    compute_units: int = 0
+    # Distinct (program id, pc, next pc) edges in first-taken order, when the backend traces execution
+    coverage: list[tuple[str, int, int]] | None = None
+
+
+class ExecutionBackend:
+    """Interface the simulation engine and protocol adapters execute against."""
+
+    name = ""
+
+    def is_available(self) -> tuple[bool, str]:
+        raise NotImplementedError
+
+    def start(self) -> None:
+        """Start a fresh chain state."""
+
+    def close(self) -> None:
+        """Release the backend."""
+
+    def keypair(self, name: str, lamports: int = 0) -> str:
+        """Create (once) a named keypair, optionally funded; returns its base58 key."""
+        raise NotImplementedError
+
+    def airdrop(self, pubkey: str, lamports: int) -> None:
+        raise NotImplementedError
+
+    def set_account(self, pubkey: str, data: bytes, owner: str, lamports: int = 0, executable: bool = False) -> None:
+        raise NotImplementedError
+
+    def get_account(self, pubkey: str) -> dict[str, Any] | None:
+        """Account as {lamports, data (bytes), owner, executable}, or None."""
+        raise NotImplementedError
+
+    def warp(self, slot: int) -> None:
+        """Set the slot only; time and epoch stay where they are (see ``warp_to_slot``)."""
+        raise NotImplementedError
+
+    def get_clock(self) -> Clock:
+        raise NotImplementedError
+
+    def set_clock(self, clock: Clock) -> None:
+        """Overwrite the Clock sysvar (slot included) as given."""
+        raise NotImplementedError
+
+    def epoch_schedule(self) -> EpochSchedule:
+        return EpochSchedule()
+
+    def advance(self, slots: int = 0, seconds: int | None = None) -> Clock:
+        """Move the clock ``slots`` forward, with time following at 400ms a slot unless ``seconds`` is given."""
+        clock = self.get_clock().advanced(self.epoch_schedule(), slots, seconds)
+        self.set_clock(clock)
+        return clock
+
+    def advance_seconds(self, seconds: int) -> Clock:
+        """Let ``seconds`` pass, with the slots a cluster would produce meanwhile (e.g. past a vesting cliff)."""
+        return self.advance(seconds * 1000 // SLOT_MS, seconds)
+
+    def warp_to_slot(self, slot: int) -> Clock:
+        return self.advance(slot - self.get_clock().slot)
+
+    def warp_to_epoch(self, epoch: int) -> Clock:
+        """Jump to the first slot of ``epoch``, e.g. to activate or deactivate stake."""
+        return self.warp_to_slot(self.epoch_schedule().first_slot(epoch))
+
+    def advance_epochs(self, count: int = 1) -> Clock:
+        """Move ``count`` epoch boundaries forward (rent, stake and reward epochs)."""
+        return self.warp_to_epoch(self.get_clock().epoch + count)
+
+    def set_unix_timestamp(self, unix_timestamp: int) -> Clock:
+        """Set the time only, e.g. to make an oracle price stale without moving the slot."""
+        clock = self.get_clock()
+        clock.unix_timestamp = unix_timestamp
+        self.set_clock(clock)
+        return clock
+
+    def send(self, instructions: list[Instruction], payer: str, signers: tuple[str, ...] = ()) -> TxResult:
+        raise NotImplementedError
+
+    def __enter__(self) -> "ExecutionBackend":
+        self.start()
+        return self
+
+    def __exit__(self, *exc: Any) -> None:
+        self.close()
+
+
+def load_account_dump(path: Path) -> dict[str, Any]:
+    """``set_account`` arguments from a `solana account --output json` dump."""
+    try:
+        dump = json.loads(Path(path).read_text())
+        account = dump["account"]
+        data, encoding = account["data"] if isinstance(account["data"], list) else (account["data"], "base64")
+        if encoding != "base64":
+            raise ValueError(f"unsupported encoding {encoding}")
+        return {"pubkey": dump["pubkey"], "data": base64.b64decode(data), "owner": account["owner"],
+                "lamports": int(account.get("lamports", 0)), "executable": bool(account.get("executable", False))}
+    except (OSError, ValueError, KeyError, TypeError) as e:
+        raise BackendError(f"cannot load account dump {path}: {e}") from e
+
+
+def read_sbf_traces(trace_dir: Path, program_id: str = "") -> list[tuple[str, int, int]]:
+    """Coverage edges of the register traces in ``trace_dir``, consuming them.
+
+    Each program invocation leaves ``<id>.regs`` and, beside it, the invoked
+    program in ``<id>.program_id`` (``program_id`` when missing).
+    """
+    edges: dict[tuple[str, int, int], None] = {}
+    for regs in sorted(Path(trace_dir).glob("*.regs"), key=lambda p: (p.stat().st_mtime_ns, p.name)):
+        owner = regs.with_suffix(".program_id")
+        program = owner.read_text().strip() if owner.exists() else program_id
+        raw = regs.read_bytes()
+        raw = raw[:len(raw) - len(raw) % (8 * TRACE_REGISTERS)]
+        pcs = [step[-1] for step in struct.iter_unpack(f"<{TRACE_REGISTERS}Q", raw)]
+        for a, b in zip(pcs, pcs[1:]):
+            edges.setdefault((program, a, b), None)
+    for path in Path(trace_dir).iterdir():
+        if path.is_file():
+            path.unlink()
+    return list(edges)
+
+
+class LiteSvmBackend(ExecutionBackend):
+    """Executes programs in LiteSVM through a Node driver."""
+
+    name = "litesvm"
+
+    def __init__(self, programs: dict[str, str | Path] | None = None, node: str = "node",
+                 cwd: Path | None = None, timeout: int = 30, accounts: list[str | Path] | None = None,
+                 trace: bool = False):
+        """Initialize the backend.
+
+        Args:
+            programs: Program id -> compiled `.so` to load on start
+            node: Node.js executable
+            cwd: Directory `litesvm` and `@solana/web3.js` resolve from (its node_modules)
+            timeout: Seconds to wait for the availability check
+            accounts: Account dumps (`solana account --output json`) to preload on start
+            trace: Report coverage from SBF register traces (needs a LiteSVM build with register tracing)
+        """
+        self.programs = {pid: Path(path) for pid, path in (programs or {}).items()}
+        self.accounts = [Path(path) for path in accounts or []]
+        self.node = node
+        self.cwd = Path(cwd) if cwd else None
+        self.timeout = timeout
+        self.trace = trace
+        self._trace_dir: Path | None = None
+        self._proc: subprocess.Popen | None = None
+        self._next_id = 0
+        self._schedule: EpochSchedule | None = None
+
+    def is_available(self) -> tuple[bool, str]:
+        """Check Node.js and the litesvm package are installed."""
+        node = shutil.which(self.node)
+        if not node:
+            return False, "node not found"
+        try:
+            result = subprocess.run(
+                [node, "-e", "console.log(require('litesvm/package.json').version)"],
+                capture_output=True, text=True, timeout=self.timeout, cwd=self.cwd,
+            )
+        except (OSError, subprocess.TimeoutExpired) as e:
+            return False, str(e)
+        if result.returncode != 0:
+            return False, "litesvm not installed (npm install litesvm @solana/web3.js)"
+        return True, f"litesvm-{result.stdout.strip()}"
+
+    def start(self) -> None:
+        self.close()
+        node = shutil.which(self.node)
+        if not node:
+            raise BackendError("node not found")
+        env = None
+        if self.trace:
+            self._trace_dir = Path(tempfile.mkdtemp(prefix="sbf-trace-"))
+            env = {**os.environ, "SBF_TRACE_DIR": str(self._trace_dir)}
+        self._proc = subprocess.Popen(
+            [node, "-e", _DRIVER], stdin=subprocess.PIPE, stdout=subprocess.PIPE, stderr=subprocess.PIPE,
+            text=True, bufsize=1, cwd=self.cwd, env=env,
+        )
+        for program_id, path in self.programs.items():
+            self._request("add_program", program_id=program_id, path=str(path.resolve()))
+        for path in self.accounts:
+            self.set_account(**load_account_dump(path))
+
+    @classmethod
+    def from_anchor(cls, config: "AnchorConfig", cluster: str | None = None, **kwargs: Any) -> "LiteSvmBackend":
+        """Backend with the workspace's built and genesis programs plus `[[test.validator.account]]` dumps."""
+        programs = {**config.artifacts(cluster), **kwargs.pop("programs", {})}
+        kwargs.setdefault("cwd", config.root)
+        return cls(programs=programs, accounts=config.account_files() + kwargs.pop("accounts", []), **kwargs)
+
+    def close(self) -> None:
+        if self._proc:
+            self._proc.stdin.close()
+            self._proc.wait(timeout=5)
+            self._proc = None
+        if self._trace_dir:
+            shutil.rmtree(self._trace_dir, ignore_errors=True)
+            self._trace_dir = None
+
+    def keypair(self, name: str, lamports: int = 0) -> str:
+        return self._request("keypair", name=name, lamports=lamports)
+
+    def airdrop(self, pubkey: str, lamports: int) -> None:
+        self._request("airdrop", pubkey=pubkey, lamports=lamports)
+
+    def set_account(self, pubkey: str, data: bytes, owner: str, lamports: int = 0, executable: bool = False) -> None:
+        self._request("set_account", pubkey=pubkey, lamports=lamports, data=base64.b64encode(data).decode(),
+                      owner=owner, executable=executable)
+
+    def get_account(self, pubkey: str) -> dict[str, Any] | None:
+        account = self._request("get_account", pubkey=pubkey)
+        if account:
+            account["data"] = base64.b64decode(account["data"])
+        return account
+
+    def warp(self, slot: int) -> None:
+        self._request("warp", slot=slot)
+
+    def get_clock(self) -> Clock:
+        return Clock(**self._request("get_clock"))
+
+    def set_clock(self, clock: Clock) -> None:
+        self._request("set_clock", **clock.to_dict())
+
+    def epoch_schedule(self) -> EpochSchedule:
+        if self._schedule is None:
+            schedule = self._request("epoch_schedule")
+            self._schedule = EpochSchedule(**schedule) if schedule else EpochSchedule()
+        return self._schedule
+
+    def send(self, instructions: list[Instruction], payer: str, signers: tuple[str, ...] = ()) -> TxResult:
+        reply = self._request("send", instructions=[ix.to_dict() for ix in instructions], payer=payer,
+                              signers=list(signers))
+        coverage = None
+        if self._trace_dir:
+            coverage = read_sbf_traces(self._trace_dir, instructions[0].program_id if instructions else "")
+        return TxResult(reply["ok"], reply["error"], reply["logs"], reply["compute_units"], coverage)
+
+    def _request(self, op: str, **params: Any) -> Any:
+        if not self._proc:
+            raise BackendError("backend not started")
+        self._next_id += 1
+        try:
+            self._proc.stdin.write(json.dumps({"id": self._next_id, "op": op, "params": params}) + "\n")
+            line = self._proc.stdout.readline()
+        except BrokenPipeError:
+            line = ""
+        if not line:
+            raise BackendError(f"litesvm driver exited: {self._proc.stderr.read().strip()[:500]}")
+        reply = json.loads(line)
+        if "error" in reply:
+            raise BackendError(f"{op}: {reply['error']}")
+        return reply["result"]
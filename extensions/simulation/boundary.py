@@ -0,0 +1,526 @@
+"""
+Instruction argument boundary fuzzing.
+
+Drives each instruction with boundary values for one argument at a time,
+the others kept at their legitimate values, on a fresh chain state per
+case. Argument types come from the program's Anchor IDL, or from the
+handler signature the IR recovered when there is no IDL:
+
+- unsigned integers: 0, 1 and the type's maximum, plus the largest
+  account realloc (10 KiB) and account size (10 MiB) where they fit
+- signed integers: the minimum, -1, 0, 1 and the maximum
+- strings, bytes and vectors: empty, and the longest that still fits in
+  the transaction
+- options: None, and the boundaries of the inner type; struct fields and
+  array elements are fuzzed like arguments of their own
+
+Each case is classified from the transaction result and its logs:
+
+- accepted / rejected: the program handled the value (an error it returns
+  is a rejection)
+- overflow: the program aborted on a checked arithmetic overflow
+- panic: any other abort (panic, access violation, failed allocation)
+- unexpected-success: 0, a type's minimum or maximum, or the longest
+  value was accepted; ``allow`` lists the boundaries an instruction is
+  meant to accept
+"""
+
+import copy
+import json
+import re
+import struct
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Callable
+
+from extensions.ir.tx_size import PACKET_DATA_SIZE, compact_u16_len, legacy_tx_size
+from extensions.monitor.decode import anchor_discriminator, b58decode
+
+from .backend import ExecutionBackend, TxResult
+from .substitution import ChainState, TransactionSpec, _find_instruction, _source_path
+
+BOUNDARY_VERSION = "1.0.0"
+
+OUTCOMES = ("accepted", "rejected", "overflow", "panic", "unexpected-success")
+
+# Largest growth of an account in one instruction, and largest account
+MAX_PERMITTED_DATA_INCREASE = 10_240
+MAX_PERMITTED_DATA_LENGTH = 10 * 1024 * 1024
+
+# Boundaries whose acceptance is reported, with the severity of accepting them
+UNEXPECTED = {"zero": "low", "min": "medium", "max": "medium", "max-length": "low"}
+
+_INT = re.compile(r"^(?P<sign>[ui])(?P<bits>8|16|32|64|128)$")
+_OVERFLOW = re.compile(r"attempt to [a-z ]+ with overflow", re.I)
+_PANIC = re.compile(r"panicked at|ProgramFailedToComplete|Program failed to complete|Access violation|"
+                    r"memory allocation failed|divide by zero", re.I)
+_SYSTEM_PROGRAM = "11111111111111111111111111111111"
+
+# Vulnerability type, category and severity per finding outcome (None: from the boundaries accepted)
+_OUTCOME_INFO = {
+    "overflow": ("integer-overflow", "arithmetic", "medium"),
+    "panic": ("unhandled-panic", "robustness", "medium"),
+    "unexpected-success": ("missing-input-validation", "input-validation", None),
+}
+
+
+class EncodeError(ValueError):
+    """Raised when an argument type cannot be encoded."""
+
+
+def _snake(name: str) -> str:
+    return re.sub(r"(?<=[a-z0-9])([A-Z])", r"_\1", name).lower()
+
+
+def normalize_type(ty: Any) -> Any:
+    """IDL type in the current (0.30+) form: ``pubkey`` and ``{"defined": {"name": ...}}``."""
+    if ty == "publicKey":
+        return "pubkey"
+    if isinstance(ty, dict):
+        if "defined" in ty:
+            defined = ty["defined"]
+            return {"defined": {"name": defined if isinstance(defined, str) else defined["name"]}}
+        if "array" in ty:
+            return {"array": [normalize_type(ty["array"][0]), ty["array"][1]]}
+        return {key: normalize_type(value) for key, value in ty.items()}
+    return ty
+
+
+def rust_type(text: str) -> Any:
+    """IDL type of a Rust argument type (``u64``, ``Pubkey``, ``Vec<T>``, ``Option<T>``, ``[T; N]``)."""
+    ty = " ".join(text.split()).strip()
+    if ty.startswith("[") and ty.endswith("]") and ";" in ty:
+        elem, n = ty[1:-1].rsplit(";", 1)
+        return {"array": [rust_type(elem), int(re.sub(r"_|usize$", "", n.strip()))]}
+    m = re.match(r"(?:[\w:]+::)?(?P<base>\w+)\s*(?:<(?P<inner>.*)>)?$", ty)
+    if not m:
+        raise EncodeError(f"cannot encode argument type {text}")
+    base, inner = m.group("base"), m.group("inner")
+    if _INT.match(base) or base in ("bool", "f32", "f64"):
+        return base
+    if base == "Pubkey":
+        return "pubkey"
+    if base == "String":
+        return "string"
+    if base == "Vec" and inner:
+        return "bytes" if inner.strip() == "u8" else {"vec": rust_type(inner)}
+    if base == "Option" and inner:
+        return {"option": rust_type(inner)}
+    return {"defined": {"name": base}}
+
+
+@dataclass
+class ArgumentSpec:
+    name: str
+    ty: Any
+
+
+@dataclass
+class InstructionSpec:
+    """An instruction to fuzz: its transaction, argument types and legitimate values."""
+
+    transaction: TransactionSpec
+    args: list[ArgumentSpec]
+    values: dict[str, Any] = field(default_factory=dict)
+    discriminator: bytes = b""
+    # Defined types by name: {"kind": "struct", "fields": [...]} or {"kind": "enum", "variants": [...]}
+    types: dict[str, dict[str, Any]] = field(default_factory=dict)
+    # Argument path -> boundary labels the instruction is meant to accept
+    allow: dict[str, list[str]] = field(default_factory=dict)
+    file: str = ""
+    line: int = 0
+
+    @property
+    def name(self) -> str:
+        return self.transaction.instruction
+
+    def default(self, ty: Any) -> Any:
+        """The value used for an argument the config leaves out."""
+        if isinstance(ty, str):
+            if _INT.match(ty):
+                return 1
+            return {"bool": False, "f32": 0.0, "f64": 0.0, "string": "", "bytes": b"", "pubkey": _SYSTEM_PROGRAM}[ty]
+        if "vec" in ty:
+            return []
+        if "option" in ty:
+            return None
+        if "array" in ty:
+            return [self.default(ty["array"][0])] * ty["array"][1]
+        typedef = self._typedef(ty)
+        if typedef.get("kind") == "enum":
+            return typedef["variants"][0]["name"]
+        return {f["name"]: self.default(f["type"]) for f in typedef.get("fields", [])}
+
+    def baseline(self) -> dict[str, Any]:
+        return {arg.name: self.values.get(arg.name, self.default(arg.ty)) for arg in self.args}
+
+    def encode(self, values: dict[str, Any], resolve: Callable[[str], bytes]) -> bytes:
+        return self.discriminator + b"".join(self._encode(arg.ty, values[arg.name], resolve) for arg in self.args)
+
+    def _typedef(self, ty: dict[str, Any]) -> dict[str, Any]:
+        name = ty.get("defined", {}).get("name")
+        if name not in self.types:
+            raise EncodeError(f"cannot encode argument type {name or json.dumps(ty)}")
+        return self.types[name]
+
+    def _encode(self, ty: Any, value: Any, resolve: Callable[[str], bytes]) -> bytes:
+        if isinstance(ty, str):
+            m = _INT.match(ty)
+            if m:
+                return int(value).to_bytes(int(m.group("bits")) // 8, "little", signed=m.group("sign") == "i")
+            if ty == "bool":
+                return b"\x01" if value else b"\x00"
+            if ty in ("f32", "f64"):
+                return struct.pack("<f" if ty == "f32" else "<d", float(value))
+            if ty == "string":
+                raw = str(value).encode()
+                return len(raw).to_bytes(4, "little") + raw
+            if ty == "bytes":
+                # Config values of bytes arguments are hex
+                raw = bytes.fromhex(value) if isinstance(value, str) else bytes(value)
+                return len(raw).to_bytes(4, "little") + raw
+            if ty == "pubkey":
+                return resolve(value)
+            raise EncodeError(f"cannot encode argument type {ty}")
+        if "vec" in ty:
+            return len(value).to_bytes(4, "little") + b"".join(self._encode(ty["vec"], v, resolve) for v in value)
+        if "option" in ty:
+            return b"\x00" if value is None else b"\x01" + self._encode(ty["option"], value, resolve)
+        if "array" in ty:
+            return b"".join(self._encode(ty["array"][0], v, resolve) for v in value)
+        typedef = self._typedef(ty)
+        if typedef.get("kind") == "enum":
+            variants = [v["name"] for v in typedef["variants"]]
+            name, fields = (value, {}) if isinstance(value, str) else next(iter(value.items()))
+            variant = typedef["variants"][variants.index(name)]
+            return bytes([variants.index(name)]) + b"".join(
+                self._encode(f["type"], fields[f["name"]], resolve) for f in variant.get("fields") or [])
+        return b"".join(self._encode(f["type"], value[f["name"]], resolve) for f in typedef.get("fields", []))
+
+    def free_bytes(self, data_len: int) -> int:
+        """Bytes left in a legacy transaction carrying ``data_len`` bytes of instruction data."""
+        tx = self.transaction
+        keys = {slot.pubkey for slot in tx.accounts} | {tx.payer, tx.program_id}
+        signers = set(tx.signers) | {tx.payer}
+        size = legacy_tx_size(len(signers), len(keys), len(tx.accounts), data_len)
+        # Leave room for the data length prefix growing
+        return PACKET_DATA_SIZE - size - (compact_u16_len(PACKET_DATA_SIZE) - compact_u16_len(data_len))
+
+    def boundaries(self, ty: Any, path: str, value: Any, free: int) -> list[tuple[str, str, Any]]:
+        """(path, label, value) boundary cases of one argument or field, ``free`` bytes left in the transaction."""
+        cases: list[tuple[str, str, Any]] = []
+        if isinstance(ty, str):
+            m = _INT.match(ty)
+            if m:
+                bits, signed = int(m.group("bits")), m.group("sign") == "i"
+                if signed:
+                    cases += [("min", -(2 ** (bits - 1))), ("minus-one", -1), ("zero", 0), ("one", 1),
+                              ("max", 2 ** (bits - 1) - 1)]
+                else:
+                    cases += [("zero", 0), ("one", 1), ("max", 2 ** bits - 1)]
+                    if bits >= 32:
+                        cases += [("max-realloc", MAX_PERMITTED_DATA_INCREASE),
+                                  ("max-account-size", MAX_PERMITTED_DATA_LENGTH)]
+            elif ty in ("string", "bytes"):
+                length = len(self._encode(ty, value, _placeholder)) - 4 + free
+                cases += [("empty", "" if ty == "string" else b"")]
+                if length > 0:
+                    cases += [("max-length", "A" * length if ty == "string" else b"\xff" * length)]
+            return [(path, label, v) for label, v in cases]
+        if "vec" in ty:
+            item = self.default(ty["vec"])
+            used = len(self._encode(ty, value, _placeholder)) - 4
+            count = (used + free) // max(len(self._encode(ty["vec"], item, _placeholder)), 1)
+            return [(path, "empty", [])] + ([(path, "max-length", [item] * count)] if count > 0 else [])
+        if "option" in ty:
+            inner = value if value is not None else self.default(ty["option"])
+            return [(path, "none", None)] + self.boundaries(ty["option"], path, inner, free - 1)
+        if "array" in ty:
+            elem, n = ty["array"]
+            return [(f"{path}[*]", label, [v] * n)
+                    for _, label, v in self.boundaries(elem, path, value[0] if value else self.default(elem), 0)]
+        typedef = self._typedef(ty)
+        if typedef.get("kind") == "enum":
+            return []
+        return [case for f in typedef.get("fields", [])
+                for case in self.boundaries(f["type"], f"{path}.{f['name']}", value[f["name"]], free)]
+
+
+def _placeholder(key: str) -> bytes:
+    """Stands in for a public key when only the encoded size matters."""
+    return b"\x00" * 32
+
+
+def resolver(backend: ExecutionBackend) -> Callable[[str], bytes]:
+    """Public key bytes of a base58 key, or of a keypair the backend names."""
+
+    def resolve(key: str) -> bytes:
+        try:
+            raw = b58decode(key)
+        except ValueError:
+            raw = b""
+        return raw if len(raw) == 32 else b58decode(backend.keypair(key))
+
+    return resolve
+
+
+def _set(values: dict[str, Any], path: str, value: Any) -> dict[str, Any]:
+    """Copy of ``values`` with the argument or field at ``path`` (``a.b``, ``a[*]``) replaced."""
+    values = copy.deepcopy(values)
+    keys = path.replace("[*]", "").split(".")
+    target = values
+    for key in keys[:-1]:
+        target = target[key]
+    target[keys[-1]] = value
+    return values
+
+
+def _describe(value: Any) -> Any:
+    if isinstance(value, (bytes, str)) and len(value) > 32:
+        return f"<{len(value)} bytes>"
+    if isinstance(value, list) and len(value) > 4:
+        return f"<{len(value)} items>"
+    if isinstance(value, bytes):
+        return value.hex()
+    return value
+
+
+def classify(result: TxResult, label: str, allowed: list[str] | None = None) -> str:
+    """Outcome of one boundary case."""
+    if result.ok:
+        return "unexpected-success" if label in UNEXPECTED and label not in (allowed or []) else "accepted"
+    text = "\n".join([result.error or "", *result.logs])
+    if _OVERFLOW.search(text):
+        return "overflow"
+    if _PANIC.search(text):
+        return "panic"
+    return "rejected"
+
+
+@dataclass
+class BoundaryCase:
+    """One argument set to one boundary value, and how the program took it."""
+
+    arg: str
+    label: str
+    value: Any
+    outcome: str
+    error: str | None = None
+    logs: list[str] = field(default_factory=list)
+    compute_units: int = 0
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"arg": self.arg, "label": self.label, "value": _describe(self.value), "outcome": self.outcome,
+                "error": self.error, "logs": self.logs[-10:], "compute_units": self.compute_units}
+
+
+@dataclass
+class InstructionReport:
+    """Boundary cases of one instruction; ``error`` says why it was not fuzzed."""
+
+    instruction: InstructionSpec
+    cases: list[BoundaryCase] = field(default_factory=list)
+    error: str | None = None
+
+    def to_dict(self) -> dict[str, Any]:
+        counts = {outcome: sum(1 for c in self.cases if c.outcome == outcome) for outcome in OUTCOMES}
+        return {"instruction": self.instruction.name, "error": self.error,
+                "outcomes": {k: v for k, v in counts.items() if v},
+                "cases": [c.to_dict() for c in self.cases]}
+
+
+@dataclass
+class BoundaryFinding:
+    """Boundary values of one argument the instruction mishandled the same way."""
+
+    instruction: InstructionSpec
+    arg: str
+    outcome: str
+    cases: list[BoundaryCase]
+
+    @property
+    def severity(self) -> str:
+        severity = _OUTCOME_INFO[self.outcome][2]
+        if severity:
+            return severity
+        return "medium" if any(UNEXPECTED[c.label] == "medium" for c in self.cases) else "low"
+
+    def _abort(self) -> str:
+        """The log line (or error) the first aborted case ended with."""
+        for case in self.cases:
+            for line in case.logs:
+                if _PANIC.search(line) or _OVERFLOW.search(line):
+                    return line.strip()
+        return self.cases[0].error or "the transaction aborted"
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        vulnerability_type, category, _ = _OUTCOME_INFO[self.outcome]
+        name = self.instruction.name
+        labels = ", ".join(c.label for c in self.cases)
+        if self.outcome == "unexpected-success":
+            title = f"{name} accepts boundary values of `{self.arg}` ({labels})"
+            detail = "the transaction succeeds, so nothing in the handler bounds this argument."
+        else:
+            what = "aborts on an arithmetic overflow" if self.outcome == "overflow" else "panics"
+            title = f"{name} {what} for boundary values of `{self.arg}` ({labels})"
+            detail = f"the transaction aborts instead of failing with a program error ({self._abort()})."
+        values = ", ".join(f"{c.label} ({_describe(c.value)})" for c in self.cases)
+        return {
+            "title": title,
+            "description": f"Executing {name} with `{self.arg}` set to {values} and every other argument at "
+                           f"its legitimate value: {detail}",
+            "vulnerability_type": vulnerability_type,
+            "severity": self.severity,
+            "confidence": 0.9,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "arg-fuzz",
+                "category": category,
+                "source_files": [self.instruction.file] if self.instruction.file else [],
+                "affected_lines": [self.instruction.line] if self.instruction.line else [],
+                "boundary_cases": [c.to_dict() for c in self.cases],
+                "precision": "verified",
+            },
+        }
+
+
+@dataclass
+class BoundaryResult:
+    reports: list[InstructionReport]
+
+    def findings(self) -> list[BoundaryFinding]:
+        findings = []
+        for report in self.reports:
+            groups: dict[tuple[str, str], list[BoundaryCase]] = {}
+            for case in report.cases:
+                if case.outcome in _OUTCOME_INFO:
+                    groups.setdefault((case.arg, case.outcome), []).append(case)
+            findings += [BoundaryFinding(report.instruction, arg, outcome, cases)
+                         for (arg, outcome), cases in groups.items()]
+        return findings
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "tool": "arg-fuzz",
+            "version": BOUNDARY_VERSION,
+            "summary": {"instructions": len(self.reports), "cases": sum(len(r.cases) for r in self.reports),
+                        "findings": len(self.findings())},
+            "instructions": [r.to_dict() for r in self.reports],
+        }
+
+
+class BoundaryFuzzer:
+    """Runs every boundary case of every instruction against a fresh chain state."""
+
+    def __init__(self, make_backend: Callable[[], ExecutionBackend], instructions: list[InstructionSpec],
+                 state: ChainState | None = None):
+        self.make_backend = make_backend
+        self.instructions = instructions
+        self.state = state or ChainState()
+
+    def _send(self, backend: ExecutionBackend, ix: InstructionSpec, values: dict[str, Any]) -> TxResult:
+        self.state.prepare(backend)
+        tx = ix.transaction
+        tx.data = ix.encode(values, resolver(backend))
+        return tx.send(backend)
+
+    def fuzz(self, backend: ExecutionBackend, ix: InstructionSpec) -> InstructionReport:
+        report = InstructionReport(ix)
+        try:
+            baseline = ix.baseline()
+            baseline_len = len(ix.encode(baseline, _placeholder))
+            free = ix.free_bytes(baseline_len)
+            cases = [case for arg in ix.args for case in ix.boundaries(arg.ty, arg.name, baseline[arg.name], free)]
+        except (EncodeError, KeyError, TypeError) as e:
+            report.error = str(e)
+            return report
+        result = self._send(backend, ix, baseline)
+        if not result.ok:
+            report.error = f"the legitimate transaction fails: {result.error}"
+            return report
+        for path, label, value in cases:
+            result = self._send(backend, ix, _set(baseline, path, value))
+            report.cases.append(BoundaryCase(path, label, value, classify(result, label, ix.allow.get(path)),
+                                             result.error, list(result.logs), result.compute_units))
+        return report
+
+    def run(self) -> BoundaryResult:
+        backend = self.make_backend()
+        try:
+            reports = [self.fuzz(backend, ix) for ix in self.instructions]
+        finally:
+            backend.close()
+        return BoundaryResult(reports)
+
+
+def load_idl(path: Path) -> tuple[dict[str, dict[str, Any]], dict[str, dict[str, Any]]]:
+    """Instructions (by snake_case name) and defined types of an Anchor IDL, legacy or 0.30+."""
+    try:
+        idl = json.loads(Path(path).read_text())
+    except (OSError, ValueError) as e:
+        raise ValueError(f"cannot load IDL {path}: {e}") from e
+    types = {t["name"]: normalize_type(t["type"]) for t in idl.get("types") or []}
+    # Legacy IDLs list #[account] structs separately, with the layout inline
+    types.update({a["name"]: normalize_type(a["type"]) for a in idl.get("accounts") or [] if "type" in a})
+    instructions = {}
+    for ix in idl.get("instructions") or []:
+        name = _snake(ix["name"])
+        instructions[name] = {
+            "accounts": [_snake(a["name"]) for a in ix.get("accounts") or []],
+            "args": [ArgumentSpec(a["name"], normalize_type(a["type"])) for a in ix.get("args") or []],
+            "discriminator": bytes(ix["discriminator"]) if "discriminator" in ix else anchor_discriminator(name),
+        }
+    return instructions, types
+
+
+def _ir_types(program: Any) -> dict[str, dict[str, Any]]:
+    return {at.name: {"kind": "struct", "fields": [{"name": f.name, "type": rust_type(f.ty)} for f in at.fields]}
+            for at in program.account_types if at.fields}
+
+
+def load_boundaries(data: dict[str, Any], base: Path | None = None,
+                    make_backend: Callable[[], ExecutionBackend] | None = None) -> BoundaryFuzzer:
+    """Build a fuzzer from a config dict (the YAML file of `sim boundaries`).
+
+    Argument types come from ``idl``, else from the handler signatures of
+    the ``program`` sources, which also point findings at the handlers.
+    """
+    from .engine import backend_factory
+
+    base = base or Path.cwd()
+    if not data.get("instructions"):
+        raise ValueError("config needs instructions")
+    idl_ixs, idl_types = load_idl(base / data["idl"]) if data.get("idl") else ({}, {})
+    instructions = []
+    for entry in data["instructions"]:
+        if not entry.get("instruction"):
+            raise ValueError("every instruction needs an instruction name")
+        name = entry["instruction"]
+        found = _find_instruction(base / data["program"], name) if data.get("program") else None
+        declared = idl_ixs.get(_snake(name))
+        if declared is None and found is None:
+            raise ValueError(f"instruction {name} is neither in the IDL nor in the program sources")
+        if declared:
+            names, args, discriminator, types = (declared["accounts"], declared["args"],
+                                                 declared["discriminator"], idl_types)
+        else:
+            _, program, ir = found
+            names = [a.name for a in ir.accounts]
+            args = [ArgumentSpec(a.name, rust_type(a.ty)) for a in ir.args]
+            discriminator, types = anchor_discriminator(ir.handler or ir.name), _ir_types(program)
+        if "discriminator" in entry:
+            discriminator = bytes.fromhex(str(entry["discriminator"]))
+        tx = {k: v for k, v in entry.items() if k not in ("args", "data", "allow", "discriminator")}
+        spec = InstructionSpec(TransactionSpec.from_dict(tx, names), args, values=dict(entry.get("args") or {}),
+                               discriminator=discriminator, types=types,
+                               allow={k: list(v) for k, v in (entry.get("allow") or {}).items()})
+        if found:
+            root, _, ir = found
+            spec.file, spec.line = _source_path(root, ir.file), ir.line
+        instructions.append(spec)
+    return BoundaryFuzzer(make_backend or backend_factory(data.get("backend", {}), base), instructions,
+                          state=ChainState.from_dict(data, base))
+
@@ -0,0 +1,280 @@
+"""
+Persistent fuzzing corpora.
+
+A project keeps what its fuzzing campaigns found under ``corpus/``, one
+directory per program and instruction, so a search picks up where the last
+audit session stopped instead of rediscovering the same coverage:
+
+- queue: inputs that took new edges, replayed to seed the next search
+- crashes: inputs that abort the transaction (overflow, panic)
+- witnesses: inputs the program wrongly accepts (constraint bypasses)
+
+Each input is a JSON file named after its content hash, holding argument
+values (bytes as hex, as in configs) and account substitutions by slot, so
+the same input found twice is stored once. ``minimize_corpus`` keeps the
+fewest queue inputs that still take every edge, ``minimize_input`` reverts
+the mutations of a crash or witness it does not need, and ``replay`` runs
+stored inputs against the current build, e.g. to confirm no witness
+reproduces after a fix.
+"""
+
+import hashlib
+import json
+from dataclasses import dataclass, field
+from datetime import datetime
+from pathlib import Path
+from typing import Any
+
+from .backend import ExecutionBackend, TxResult
+from .boundary import BoundaryResult, InstructionSpec, _describe, _set
+from .search import ExploitSearch, SearchInput, outcome_of
+from .substitution import Substitution
+
+CORPUS_VERSION = "1.0.0"
+
+BUCKETS = ("queue", "crashes", "witnesses")
+
+
+def _jsonable(value: Any) -> Any:
+    """``value`` with bytes as hex, the way configs give them."""
+    if isinstance(value, bytes):
+        return value.hex()
+    if isinstance(value, dict):
+        return {k: _jsonable(v) for k, v in value.items()}
+    if isinstance(value, (list, tuple)):
+        return [_jsonable(v) for v in value]
+    return value
+
+
+def bucket_of(outcome: str) -> str:
+    return {"bypass": "witnesses", "overflow": "crashes", "panic": "crashes"}.get(outcome, "queue")
+
+
+@dataclass
+class CorpusRecord:
+    """One stored input of an instruction."""
+
+    program_id: str
+    instruction: str
+    bucket: str
+    values: dict[str, Any]
+    # {"kind", "slot", "other"} per substituted account
+    substitutions: list[dict[str, Any]] = field(default_factory=list)
+    mutations: list[str] = field(default_factory=list)
+    outcome: str = ""
+    error: str | None = None
+    source: str = ""  # Tool that found it
+    created_at: str = ""
+    minimized: bool = False
+
+    @property
+    def id(self) -> str:
+        key = {"program_id": self.program_id, "instruction": self.instruction, "values": self.values,
+               "substitutions": sorted(self.substitutions, key=lambda s: s["slot"])}
+        return hashlib.sha256(json.dumps(key, sort_keys=True).encode()).hexdigest()[:16]
+
+    @classmethod
+    def from_input(cls, ix: InstructionSpec, inp: SearchInput, outcome: str, error: str | None = None,
+                   source: str = "coverage-search") -> "CorpusRecord":
+        return cls(
+            program_id=ix.transaction.program_id,
+            instruction=ix.name,
+            bucket=bucket_of(outcome),
+            values=_jsonable(inp.values),
+            substitutions=[{"kind": s.kind, "slot": s.slot, "other": s.other} for s in inp.substitutions],
+            mutations=list(inp.mutations),
+            outcome=outcome,
+            error=error,
+            source=source,
+            created_at=datetime.now().isoformat(),
+        )
+
+    def to_input(self, ix: InstructionSpec) -> SearchInput:
+        """The input against ``ix``; raises ValueError when its accounts no longer match."""
+        slots = [slot.name for slot in ix.transaction.accounts]
+        subs = []
+        for sub in self.substitutions:
+            if sub["slot"] not in slots or (sub.get("other") and sub["other"] not in slots):
+                raise ValueError(f"{self.id}: {ix.name} has no account {sub['slot']}")
+            subs.append(Substitution(sub["kind"], sub["slot"], slots.index(sub["slot"]), other=sub.get("other")))
+        missing = {arg.name for arg in ix.args} - set(self.values)
+        if missing:
+            raise ValueError(f"{self.id}: no value for {', '.join(sorted(missing))}")
+        return SearchInput(dict(self.values), subs, list(self.mutations))
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"id": self.id, "version": CORPUS_VERSION, "program_id": self.program_id,
+                "instruction": self.instruction, "bucket": self.bucket, "values": self.values,
+                "substitutions": self.substitutions, "mutations": self.mutations, "outcome": self.outcome,
+                "error": self.error, "source": self.source, "created_at": self.created_at,
+                "minimized": self.minimized}
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "CorpusRecord":
+        return cls(**{k: v for k, v in data.items() if k not in ("id", "version")})
+
+
+class CorpusStore:
+    """Corpus files under ``root``: ``<program id>/<instruction>/<bucket>/<id>.json``."""
+
+    def __init__(self, root: Path):
+        self.root = Path(root)
+
+    def path(self, record: CorpusRecord) -> Path:
+        return self.root / record.program_id / record.instruction / record.bucket / f"{record.id}.json"
+
+    def add(self, record: CorpusRecord) -> bool:
+        """Store ``record``; False when the same input is already stored."""
+        path = self.path(record)
+        if path.exists():
+            return False
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(json.dumps(record.to_dict(), indent=2))
+        return True
+
+    def remove(self, record: CorpusRecord) -> None:
+        self.path(record).unlink(missing_ok=True)
+
+    def records(self, program_id: str = "*", instruction: str = "*",
+                buckets: tuple[str, ...] = BUCKETS) -> list[CorpusRecord]:
+        """Stored inputs, oldest first."""
+        records = []
+        for bucket in buckets:
+            for path in self.root.glob(f"{program_id}/{instruction}/{bucket}/*.json"):
+                try:
+                    records.append(CorpusRecord.from_dict(json.loads(path.read_text())))
+                except (OSError, ValueError, TypeError):
+                    continue
+        return sorted(records, key=lambda r: (r.created_at, r.id))
+
+    def stats(self) -> dict[tuple[str, str], dict[str, int]]:
+        """Input count per bucket, per (program id, instruction)."""
+        counts: dict[tuple[str, str], dict[str, int]] = {}
+        for record in self.records():
+            group = counts.setdefault((record.program_id, record.instruction), dict.fromkeys(BUCKETS, 0))
+            group[record.bucket] += 1
+        return counts
+
+
+@dataclass
+class Replay:
+    """A stored input run against the current build."""
+
+    record: CorpusRecord
+    outcome: str | None = None
+    error: str | None = None
+
+    @property
+    def reproduced(self) -> bool:
+        return self.outcome == self.record.outcome
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"id": self.record.id, "instruction": self.record.instruction, "bucket": self.record.bucket,
+                "stored": self.record.outcome, "outcome": self.outcome, "reproduced": self.reproduced,
+                "error": self.error, "mutations": self.record.mutations}
+
+
+def _run(search: ExploitSearch, backend: ExecutionBackend, ix: InstructionSpec,
+         inp: SearchInput) -> tuple[str, TxResult]:
+    result = search.execute(backend, ix, inp)
+    return outcome_of(result, inp), result
+
+
+def replay(search: ExploitSearch, store: CorpusStore, buckets: tuple[str, ...] = BUCKETS) -> list[Replay]:
+    """Run the stored inputs of the search's instructions."""
+    replays = []
+    backend = search.make_backend()
+    try:
+        for ix in search.instructions:
+            for record in store.records(ix.transaction.program_id, ix.name, buckets):
+                try:
+                    outcome, result = _run(search, backend, ix, record.to_input(ix))
+                except (ValueError, KeyError, TypeError) as e:
+                    replays.append(Replay(record, error=str(e)))
+                    continue
+                replays.append(Replay(record, outcome, result.error))
+    finally:
+        backend.close()
+    return replays
+
+
+def minimize_input(search: ExploitSearch, backend: ExecutionBackend, ix: InstructionSpec,
+                   record: CorpusRecord) -> CorpusRecord:
+    """``record`` with every substitution dropped and argument reverted that it reproduces without."""
+    inp = record.to_input(ix)
+    baseline = ix.baseline()
+    for sub in list(inp.substitutions):
+        trial = SearchInput(inp.values, [s for s in inp.substitutions if s is not sub])
+        if _run(search, backend, ix, trial)[0] == record.outcome:
+            inp = trial
+    for name, value in baseline.items():
+        if _jsonable(inp.values.get(name)) == _jsonable(value):
+            continue
+        trial = SearchInput({**inp.values, name: value}, inp.substitutions)
+        if _run(search, backend, ix, trial)[0] == record.outcome:
+            inp = trial
+    inp.mutations = [f"{name}={_describe(value)}" for name, value in inp.values.items()
+                     if _jsonable(value) != _jsonable(baseline[name])] + [s.label for s in inp.substitutions]
+    minimized = CorpusRecord.from_input(ix, inp, record.outcome, record.error, record.source)
+    minimized.created_at, minimized.minimized = record.created_at, True
+    return minimized
+
+
+def minimize_corpus(search: ExploitSearch, store: CorpusStore) -> dict[str, dict[str, int]]:
+    """Minimize the crashes and witnesses, and cut the queue to the fewest inputs that take every edge."""
+    stats: dict[str, dict[str, int]] = {}
+    backend = search.make_backend()
+    try:
+        for ix in search.instructions:
+            program_id = ix.transaction.program_id
+            counts = stats.setdefault(ix.name, {"kept": 0, "removed": 0, "minimized": 0})
+            for record in store.records(program_id, ix.name, ("crashes", "witnesses")):
+                if record.minimized:
+                    continue
+                try:
+                    minimized = minimize_input(search, backend, ix, record)
+                except (ValueError, KeyError, TypeError):
+                    continue
+                store.remove(record)
+                store.add(minimized)
+                counts["minimized"] += 1
+
+            legitimate = search.execute(backend, ix, SearchInput(ix.baseline()))
+            covered = set(legitimate.coverage or [])
+            paths = {}
+            queue = store.records(program_id, ix.name, ("queue",))
+            for record in queue:
+                try:
+                    result = search.execute(backend, ix, record.to_input(ix))
+                except (ValueError, KeyError, TypeError):
+                    continue
+                paths[record.id] = set(result.coverage or []) - covered
+            kept: set[str] = set()
+            while True:
+                # The input adding the most edges, the one with fewer mutations on ties
+                best = max((r for r in queue if r.id in paths and r.id not in kept),
+                           key=lambda r: (len(paths[r.id] - covered), -len(r.mutations)), default=None)
+                if best is None or not paths[best.id] - covered:
+                    break
+                kept.add(best.id)
+                covered |= paths[best.id]
+            for record in queue:
+                if record.id not in kept:
+                    store.remove(record)
+                    counts["removed"] += 1
+            counts["kept"] += len(kept)
+    finally:
+        backend.close()
+    return stats
+
+
+def boundary_records(result: BoundaryResult) -> list[CorpusRecord]:
+    """Crashes of a boundary run (overflows and panics), as inputs of the search."""
+    records = []
+    for report in result.reports:
+        ix = report.instruction
+        for case in report.cases:
+            if case.outcome in ("overflow", "panic"):
+                inp = SearchInput(_set(ix.baseline(), case.arg, case.value), mutations=[f"{case.arg}={case.label}"])
+                records.append(CorpusRecord.from_input(ix, inp, case.outcome, case.error, source="arg-fuzz"))
+    return records
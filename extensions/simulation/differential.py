@@ -0,0 +1,293 @@
+"""
+Differential execution of an original and a patched build.
+
+Runs the same transaction corpus, in order, against the pre-fix and the
+post-fix build of a program, each from the same keypairs, accounts and
+setup transactions, and diffs every transaction's outcome, normalized logs
+and the state of the accounts it watches. Keypairs differ between the two
+chains, so their public keys are replaced by their names in account data
+and logs before comparing; compute units are reported but never count as a
+change.
+
+Transactions carry the change a patch is meant to make (``expect``):
+
+- unchanged: no difference beyond compute units
+- intended: the expected change happened (``rejected``: the patched build
+  rejects the transaction; ``changed``: any difference)
+- missing: the expected change did not happen
+- unintended: a difference nobody asked for
+- downstream: only account state differs, after an intended change earlier
+  in the corpus (the state it left behind diverges)
+
+A patch verifies when nothing is missing or unintended.
+"""
+
+import difflib
+import hashlib
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Callable
+
+from extensions.monitor.decode import b58decode
+
+from .backend import ExecutionBackend
+from .substitution import ChainState, TransactionSpec
+
+DIFF_VERSION = "1.0.0"
+
+BUILDS = ("original", "patched")
+EXPECTATIONS = ("rejected", "changed")
+VERDICTS = ("unchanged", "intended", "missing", "unintended", "downstream")
+
+_CONSUMED = re.compile(r"consumed \d+ of \d+ compute units")
+
+
+@dataclass
+class Execution:
+    """One transaction on one build, with the watched accounts after it."""
+
+    ok: bool
+    error: str | None = None
+    logs: list[str] = field(default_factory=list)
+    compute_units: int = 0
+    accounts: dict[str, dict[str, Any] | None] = field(default_factory=dict)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"ok": self.ok, "error": self.error, "logs": self.logs, "compute_units": self.compute_units}
+
+
+@dataclass
+class AccountDiff:
+    """A watched account that differs between the builds after a transaction."""
+
+    account: str
+    field: str  # "exists", "lamports", "owner", "executable" or "data"
+    original: Any = None
+    patched: Any = None
+    # Changed byte ranges [start, end) of the data
+    ranges: list[tuple[int, int]] = field(default_factory=list)
+
+    def to_dict(self) -> dict[str, Any]:
+        data = {"account": self.account, "field": self.field, "original": self.original, "patched": self.patched}
+        if self.ranges:
+            data["ranges"] = [list(r) for r in self.ranges]
+        return data
+
+
+@dataclass
+class TransactionDiff:
+    """How one transaction of the corpus differs between the builds."""
+
+    name: str
+    original: Execution
+    patched: Execution
+    expect: str | None = None
+    accounts: list[AccountDiff] = field(default_factory=list)
+    logs: list[str] = field(default_factory=list)  # Unified diff of the normalized logs
+    # Set when an intended change happened earlier in the corpus
+    after_intended: bool = False
+
+    @property
+    def status_changed(self) -> bool:
+        return self.original.ok != self.patched.ok or self.original.error != self.patched.error
+
+    @property
+    def changed(self) -> bool:
+        return self.status_changed or bool(self.logs) or bool(self.accounts)
+
+    @property
+    def verdict(self) -> str:
+        if self.expect == "rejected":
+            return "missing" if self.patched.ok else ("intended" if self.changed else "unchanged")
+        if self.expect == "changed":
+            return "intended" if self.changed else "missing"
+        if not self.changed:
+            return "unchanged"
+        if self.after_intended and not self.status_changed and not self.logs:
+            return "downstream"
+        return "unintended"
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "transaction": self.name,
+            "expect": self.expect,
+            "verdict": self.verdict,
+            "original": self.original.to_dict(),
+            "patched": self.patched.to_dict(),
+            "compute_units_delta": self.patched.compute_units - self.original.compute_units,
+            "logs_diff": self.logs,
+            "accounts": [a.to_dict() for a in self.accounts],
+        }
+
+
+@dataclass
+class DiffResult:
+    transactions: list[TransactionDiff]
+
+    @property
+    def verified(self) -> bool:
+        return not any(t.verdict in ("missing", "unintended") for t in self.transactions)
+
+    def to_dict(self) -> dict[str, Any]:
+        counts = {v: sum(1 for t in self.transactions if t.verdict == v) for v in VERDICTS}
+        return {
+            "tool": "differential",
+            "version": DIFF_VERSION,
+            "verified": self.verified,
+            "summary": {"transactions": len(self.transactions), **counts},
+            "transactions": [t.to_dict() for t in self.transactions],
+        }
+
+
+def _ranges(a: bytes, b: bytes) -> list[tuple[int, int]]:
+    """Byte ranges where ``a`` and ``b`` differ (past the shorter one counts as different)."""
+    ranges: list[tuple[int, int]] = []
+    for i in range(max(len(a), len(b))):
+        if i < len(a) and i < len(b) and a[i] == b[i]:
+            continue
+        if ranges and ranges[-1][1] == i:
+            ranges[-1] = (ranges[-1][0], i + 1)
+        else:
+            ranges.append((i, i + 1))
+    return ranges
+
+
+def _hex(data: bytes) -> str:
+    return data.hex() if len(data) <= 64 else f"{data[:64].hex()}... ({len(data)} bytes)"
+
+
+def diff_accounts(original: dict[str, dict[str, Any] | None],
+                  patched: dict[str, dict[str, Any] | None]) -> list[AccountDiff]:
+    diffs = []
+    for name in original:
+        a, b = original[name], patched.get(name)
+        if (a is None) != (b is None):
+            diffs.append(AccountDiff(name, "exists", a is not None, b is not None))
+            continue
+        if a is None:
+            continue
+        for key in ("lamports", "owner", "executable"):
+            if a[key] != b[key]:
+                diffs.append(AccountDiff(name, key, a[key], b[key]))
+        if a["data"] != b["data"]:
+            diffs.append(AccountDiff(name, "data", _hex(a["data"]), _hex(b["data"]), _ranges(a["data"], b["data"])))
+    return diffs
+
+
+class DifferentialRun:
+    """Runs a transaction corpus on both builds and diffs the results."""
+
+    def __init__(self, builds: dict[str, Callable[[], ExecutionBackend]], transactions: list[TransactionSpec],
+                 state: ChainState | None = None, watch: list[str] | None = None,
+                 expect: dict[str, str] | None = None):
+        """Initialize the run.
+
+        Args:
+            builds: Backend factory per build ("original", "patched")
+            transactions: The corpus, run in order on one chain per build
+            state: Keypairs, accounts and setup transactions both chains start from
+            watch: Accounts (keypair names or keys) to diff after every transaction; default: all the
+                corpus's accounts
+            expect: Transaction name -> the change the patch is meant to make ("rejected" or "changed")
+        """
+        if set(builds) != set(BUILDS):
+            raise ValueError("differential runs need an original and a patched build")
+        unknown = {v for v in (expect or {}).values()} - set(EXPECTATIONS)
+        if unknown:
+            raise ValueError(f"unknown expectations {', '.join(sorted(unknown))} (expected {', '.join(EXPECTATIONS)})")
+        self.builds = builds
+        self.transactions = transactions
+        self.state = state or ChainState()
+        self.watch = watch or list(dict.fromkeys(slot.pubkey for tx in transactions for slot in tx.accounts))
+        self.expect = expect or {}
+
+    def _names(self) -> list[str]:
+        """Keypairs whose keys differ between the chains."""
+        names = list(self.state.keypairs)
+        for tx in self.state.setup + self.transactions:
+            names += [tx.payer, *tx.signers]
+        return list(dict.fromkeys(n for n in names if n))
+
+    def execute(self, build: str) -> list[Execution]:
+        backend = self.builds[build]()
+        try:
+            self.state.prepare(backend)
+            keys = {backend.keypair(name): name for name in self._names()}
+            executions = []
+            for tx in self.transactions:
+                result = tx.send(backend)
+                accounts = {name: _normalize_account(backend.get_account(name), keys) for name in self.watch}
+                executions.append(Execution(result.ok, _normalize_text(result.error, keys),
+                                            [_normalize_text(line, keys) for line in result.logs],
+                                            result.compute_units, accounts))
+        finally:
+            backend.close()
+        return executions
+
+    def run(self) -> DiffResult:
+        original, patched = self.execute("original"), self.execute("patched")
+        diffs = []
+        intended = False
+        for tx, a, b in zip(self.transactions, original, patched):
+            logs = [line.rstrip("\n") for line in difflib.unified_diff(
+                [_CONSUMED.sub("consumed N compute units", line) for line in a.logs],
+                [_CONSUMED.sub("consumed N compute units", line) for line in b.logs],
+                "original", "patched", lineterm="", n=1)]
+            diff = TransactionDiff(tx.instruction, a, b, self.expect.get(tx.instruction),
+                                   diff_accounts(a.accounts, b.accounts), logs, after_intended=intended)
+            intended = intended or diff.verdict == "intended"
+            diffs.append(diff)
+        return DiffResult(diffs)
+
+
+def _placeholder(name: str) -> bytes:
+    """Stands in for a keypair's key in normalized account data, the same on both chains."""
+    return hashlib.sha256(f"keypair:{name}".encode()).digest()
+
+
+def _normalize_account(account: dict[str, Any] | None, keys: dict[str, str]) -> dict[str, Any] | None:
+    if account is None:
+        return None
+    data = bytes(account["data"])
+    for key, name in keys.items():
+        data = data.replace(b58decode(key), _placeholder(name))
+    return {**account, "data": data, "owner": keys.get(account["owner"], account["owner"])}
+
+
+def _normalize_text(text: str | None, keys: dict[str, str]) -> str | None:
+    if text is None:
+        return None
+    for key, name in keys.items():
+        text = text.replace(key, f"<{name}>")
+    return text
+
+
+def load_differential(data: dict[str, Any], base: Path | None = None,
+                      builds: dict[str, Callable[[], ExecutionBackend]] | None = None) -> DifferentialRun:
+    """Build a run from a config dict (the YAML file of `sim diff`).
+
+    ``original`` and ``patched`` are backend sections laid over the shared
+    ``backend`` one, typically each loading its own ``.so`` for the program id.
+    """
+    from .engine import backend_factory
+
+    base = base or Path.cwd()
+    if not data.get("transactions"):
+        raise ValueError("config needs transactions")
+    if builds is None:
+        if not all(data.get(build) for build in BUILDS):
+            raise ValueError("config needs original and patched builds")
+        builds = {build: backend_factory({**data.get("backend", {}), **data[build]}, base) for build in BUILDS}
+    transactions, expect = [], {}
+    for i, entry in enumerate(data["transactions"]):
+        tx = TransactionSpec.from_dict({k: v for k, v in entry.items() if k not in ("name", "expect")})
+        tx.instruction = entry.get("name") or tx.instruction or f"transaction {i}"
+        if entry.get("expect"):
+            expect[tx.instruction] = entry["expect"]
+        transactions.append(tx)
+    names = [tx.instruction for tx in transactions]
+    if len(set(names)) != len(names):
+        raise ValueError("transaction names must be unique (set name: on repeated instructions)")
+    return DifferentialRun(builds, transactions, state=ChainState.from_dict(data, base),
+                           watch=data.get("watch"), expect=expect)
@@ -0,0 +1,372 @@
+"""
+Slot-by-slot simulation engine and economic findings.
+
+Each run draws a reference price path, derives the oracle feed from it,
+and lets every agent act once per slot (in a freshly shuffled order, so
+no agent always moves first) against a fresh protocol instance. Runs are
+repeated over consecutive seeds and the outcomes aggregated into findings:
+
+- oracle-lag-arbitrage: extractive agents (arbitrageurs, oracle-lag
+  attackers) profit from the gap between oracle and reference price
+- bad-debt: debt is written off or left uncovered by collateral
+- unprofitable-liquidation: liquidators pass on unhealthy positions
+  because the bonus does not cover the exit
+"""
+
+import importlib
+import random
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Any, Callable
+
+from .agents import Agent, Liquidator, World, make_agent
+from .backend import BackendError, LiteSvmBackend
+from .market import OracleModel, PriceModel
+from .protocols import PROTOCOL_MODELS, Action, ActionResult, ProtocolModel
+
+SIMULATION_VERSION = "1.0.0"
+
+# (action kind, side) -> (parameter holding the amount, balance it is paid from)
+_FUNDING = {
+    ("trade", "buy"): ("amount", "quote"),
+    ("trade", "sell"): ("amount", "collateral"),
+    ("swap", "buy"): ("amount", "quote"),
+    ("swap", "sell"): ("amount", "collateral"),
+    ("open", None): ("collateral", "collateral"),
+    ("repay", None): ("amount", "quote"),
+    ("liquidate", None): ("repay", "quote"),
+}
+
+
+@dataclass
+class SimulationConfig:
+    """Run length, repetitions and market models."""
+
+    slots: int = 5000
+    runs: int = 1
+    seed: int = 0
+    # Fee of the external venue agents exit through
+    external_fee: float = 0.001
+    # Quote-side depth of the external venue (constant product); None for unlimited
+    external_depth: float | None = None
+    sample_every: int = 100
+    price: PriceModel = field(default_factory=PriceModel)
+    oracle: OracleModel = field(default_factory=OracleModel)
+
+    def to_dict(self) -> dict[str, Any]:
+        return asdict(self)
+
+
+@dataclass
+class RunResult:
+    """Outcome of one seeded run."""
+
+    seed: int
+    initial: dict[str, float]
+    final: dict[str, float]
+    agents: dict[str, dict[str, Any]]
+    max_oracle_gap: float
+    declined_liquidations: int
+    timeline: list[dict[str, float]] = field(default_factory=list)
+
+    @property
+    def extracted(self) -> float:
+        """Profit taken by extractive agents."""
+        return sum(max(0.0, a["pnl"]) for a in self.agents.values() if a["extractive"])
+
+    def to_dict(self) -> dict[str, Any]:
+        return {**asdict(self), "extracted": self.extracted}
+
+
+@dataclass
+class EconomicFinding:
+    """An economic weakness observed across simulation runs."""
+
+    kind: str
+    severity: str
+    title: str
+    description: str
+    evidence: dict[str, Any] = field(default_factory=dict)
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        affected = self.evidence.get("runs_affected", 0) / max(self.evidence.get("runs", 1), 1)
+        return {
+            "title": self.title,
+            "description": self.description,
+            "vulnerability_type": f"economic-{self.kind}",
+            "severity": self.severity,
+            "confidence": round(0.4 + 0.5 * affected, 2),
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "simulation",
+                "category": "economic",
+                "source_files": [],
+                "affected_lines": [],
+                "simulation": self.evidence,
+                "precision": "verified",
+            },
+        }
+
+
+@dataclass
+class SimulationResult:
+    """All runs of a simulation."""
+
+    protocol: str
+    params: dict[str, Any]
+    config: SimulationConfig
+    runs: list[RunResult]
+
+    def findings(self, min_extraction: float = 0.001, min_bad_debt: float = 0.0) -> list[EconomicFinding]:
+        """Aggregate runs into findings.
+
+        Args:
+            min_extraction: Profit of extractive agents, as a share of the initial TVL, worth reporting
+            min_bad_debt: Bad debt, as a share of the initial TVL, worth reporting
+        """
+        size = self.runs[0].initial.get("tvl") or 1.0
+        oracle = self.config.oracle
+        findings = []
+
+        extracting = [r for r in self.runs if r.extracted > min_extraction * size]
+        if extracting:
+            worst = max(extracting, key=lambda r: r.extracted)
+            agents = sorted({
+                name for r in extracting for name, a in r.agents.items() if a["extractive"] and a["pnl"] > 0
+            })
+            findings.append(EconomicFinding(
+                "oracle-lag-arbitrage",
+                "high" if worst.extracted >= 0.01 * size else "medium",
+                f"Oracle lag lets {', '.join(agents)} extract value from {self.protocol}",
+                f"In {len(extracting)} of {len(self.runs)} runs, agents trading the gap between the oracle "
+                f"(lag {oracle.lag_slots} slots, heartbeat {oracle.heartbeat}, deviation {oracle.deviation:g}) and "
+                f"the reference price took {worst.extracted:,.2f} at worst ({worst.extracted / size:.2%} of TVL, "
+                f"seed {worst.seed}); the oracle was up to {worst.max_oracle_gap:.2%} off. Tighter update "
+                f"triggers, a confidence/staleness check, or borrow limits priced at the worse of oracle and "
+                f"TWAP close the window.",
+                {**self._evidence(extracting, lambda r: r.extracted, worst), "agents": agents,
+                 "oracle": oracle.to_dict()},
+            ))
+
+        indebted = [r for r in self.runs if r.final.get("bad_debt", 0.0) > max(min_bad_debt * size, 1e-6)]
+        if indebted:
+            worst = max(indebted, key=lambda r: r.final["bad_debt"])
+            findings.append(EconomicFinding(
+                "bad-debt",
+                "high" if worst.final["bad_debt"] >= 0.01 * size else "medium",
+                f"{self.protocol} accrues bad debt under simulated price paths",
+                f"In {len(indebted)} of {len(self.runs)} runs positions ended with debt their collateral no longer "
+                f"covers, up to {worst.final['bad_debt']:,.2f} ({worst.final['bad_debt'] / size:.2%} of TVL, "
+                f"seed {worst.seed}). Parameters: {self._params()}.",
+                {**self._evidence(indebted, lambda r: r.final["bad_debt"], worst), "params": self.params},
+            ))
+
+        declined = [r for r in self.runs if r.declined_liquidations]
+        if declined:
+            worst = max(declined, key=lambda r: r.declined_liquidations)
+            findings.append(EconomicFinding(
+                "unprofitable-liquidation",
+                "medium" if any(r in indebted for r in declined) else "low",
+                f"Liquidating unhealthy {self.protocol} positions is unprofitable",
+                f"In {len(declined)} of {len(self.runs)} runs liquidators passed on up to "
+                f"{worst.declined_liquidations} unhealthy positions (seed {worst.seed}) because the seized "
+                f"collateral did not cover the repayment, exit fees and costs. Parameters: {self._params()}.",
+                {**self._evidence(declined, lambda r: r.declined_liquidations, worst), "params": self.params},
+            ))
+        return findings
+
+    def _evidence(self, affected: list[RunResult], metric: Callable[[RunResult], float],
+                  worst: RunResult) -> dict[str, Any]:
+        return {
+            "runs": len(self.runs),
+            "runs_affected": len(affected),
+            "mean": sum(metric(r) for r in self.runs) / len(self.runs),
+            "worst": metric(worst),
+            "worst_seed": worst.seed,
+            "initial_tvl": self.runs[0].initial.get("tvl", 0.0),
+            "slots": self.config.slots,
+        }
+
+    def _params(self) -> str:
+        return ", ".join(f"{k}={v}" for k, v in self.params.items() if v is not None)
+
+    def summary(self) -> dict[str, Any]:
+        """Per-agent mean PnL and mean protocol outcomes."""
+        n = len(self.runs)
+        agents: dict[str, dict[str, Any]] = {}
+        for run in self.runs:
+            for name, a in run.agents.items():
+                entry = agents.setdefault(name, {"kind": a["kind"], "mean_pnl": 0.0})
+                entry["mean_pnl"] += a["pnl"] / n
+        return {
+            "runs": n,
+            "agents": agents,
+            "mean_extracted": sum(r.extracted for r in self.runs) / n,
+            "mean_bad_debt": sum(r.final.get("bad_debt", 0.0) for r in self.runs) / n,
+            "max_oracle_gap": max(r.max_oracle_gap for r in self.runs),
+        }
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "tool": "simulation",
+            "version": SIMULATION_VERSION,
+            "protocol": self.protocol,
+            "params": self.params,
+            "config": self.config.to_dict(),
+            "summary": self.summary(),
+            "runs": [r.to_dict() for r in self.runs],
+            "findings": [asdict(f) for f in self.findings()],
+        }
+
+
+class Simulation:
+    """Agents interacting with a protocol over many simulated slots."""
+
+    def __init__(self, protocol: Callable[[], ProtocolModel], agents: list[dict[str, Any]],
+                 config: SimulationConfig | None = None):
+        """Initialize the simulation.
+
+        Args:
+            protocol: Factory for a fresh protocol instance per run
+            agents: Agent config entries (see ``make_agent``)
+            config: Run length, repetitions and market models
+        """
+        self.protocol = protocol
+        self.agents = agents
+        self.config = config or SimulationConfig()
+        self._described: tuple[str, dict[str, Any]] = ("", {})
+
+    def run(self) -> SimulationResult:
+        runs = [self.run_once(self.config.seed + i) for i in range(self.config.runs)]
+        name, params = self._described
+        return SimulationResult(name, params, self.config, runs)
+
+    def run_once(self, seed: int) -> RunResult:
+        config = self.config
+        rng = random.Random(seed)
+        prices = config.price.path(config.slots, rng)
+        oracle = config.oracle.feed(prices)
+        agents = [make_agent(spec) for spec in self.agents]
+        protocol = self.protocol()
+        self._described = (protocol.name, dict(getattr(protocol, "params", {})))
+        timeline = []
+        try:
+            protocol.set_price(0, oracle[0])
+            initial = protocol.metrics(prices[0])
+            for slot in range(config.slots):
+                protocol.set_price(slot, oracle[slot])
+                order = list(agents)
+                rng.shuffle(order)
+                for agent in order:
+                    world = World(slot, prices[slot], oracle[slot], protocol.view(), config.external_fee, rng,
+                                  config.external_depth)
+                    for action in agent.act(world):
+                        agent.settle(action, self._execute(protocol, agent, action, world))
+                if slot % config.sample_every == 0 or slot == config.slots - 1:
+                    timeline.append({"slot": slot, "price": prices[slot], "oracle": oracle[slot],
+                                     **protocol.metrics(prices[slot])})
+            final_price = prices[-1]
+            view = protocol.view()
+            final = protocol.metrics(final_price)
+        finally:
+            protocol.close()
+        return RunResult(
+            seed=seed,
+            initial=initial,
+            final=final,
+            agents={
+                a.name: {**a.summary(), "extractive": a.extractive, "pnl": a.pnl(final_price, view)} for a in agents
+            },
+            max_oracle_gap=max(abs(o / p - 1) for o, p in zip(oracle, prices)),
+            declined_liquidations=sum(len(a.declined) for a in agents if isinstance(a, Liquidator)),
+            timeline=timeline,
+        )
+
+    def _execute(self, protocol: ProtocolModel, agent: Agent, action: Action, world: World) -> ActionResult:
+        side = action.params.get("side") if action.kind in ("trade", "swap") else None
+        funding = _FUNDING.get((action.kind, side))
+        if funding:
+            param, asset = funding
+            held = agent.balances.get(asset, 0.0)
+            requested = action.params.get(param)
+            amount = held if requested is None else min(requested, held)
+            if amount <= 0:
+                return ActionResult(False, f"insufficient {asset}")
+            action = Action(action.kind, action.agent, {**action.params, param: amount})
+        if action.kind != "trade":
+            return protocol.apply(action)
+        # External venue at the reference price
+        amount = action.params["amount"]
+        if side == "buy":
+            return ActionResult(True, deltas={"quote": -amount, "collateral": world.buy_amount(amount)})
+        return ActionResult(True, deltas={"collateral": -amount, "quote": world.sell_value(amount)})
+
+
+def backend_factory(spec: dict[str, Any], base: Path | None = None) -> Callable[[], LiteSvmBackend]:
+    """Execution backend factory from a config ``backend`` section.
+
+    ``{"programs": {id: so}, "accounts": [dump, ...]}`` loads programs and
+    account dumps relative to ``base``; an ``anchor`` entry (path to the
+    workspace, plus an optional ``cluster``) loads the programs and accounts
+    its Anchor.toml declares.
+    """
+    backend = dict(spec)
+    if backend.pop("type", "litesvm") != "litesvm":
+        raise ValueError("only the litesvm backend is supported")
+    base = base or Path.cwd()
+    backend["programs"] = {pid: base / path for pid, path in backend.get("programs", {}).items()}
+    backend["accounts"] = [base / path for path in backend.get("accounts", [])]
+    anchor, cluster = backend.pop("anchor", None), backend.pop("cluster", None)
+    config = None
+    if anchor:
+        from extensions.ir.anchor_config import load_anchor_config
+
+        config = load_anchor_config(base / anchor)
+        if config is None:
+            raise ValueError(f"no Anchor.toml found at {base / anchor}")
+    else:
+        backend.setdefault("cwd", base)
+
+    def make() -> LiteSvmBackend:
+        if config:
+            return LiteSvmBackend.from_anchor(config, cluster, **backend)
+        return LiteSvmBackend(**backend)
+
+    available, info = make().is_available()
+    if not available:
+        raise BackendError(info)
+    return make
+
+
+def protocol_factory(spec: dict[str, Any], base: Path | None = None) -> Callable[[], ProtocolModel]:
+    """Protocol factory from a config section.
+
+    ``{"model": "lending", "params": {...}, "liquidity": ...}`` uses a built-in
+    model; ``{"adapter": "pkg.module:Class", "backend": {"programs": {id: so}},
+    "params": {...}}`` runs an ``SvmProtocol`` adapter on LiteSVM (see
+    ``backend_factory`` for the backend section).
+    """
+    spec = dict(spec)
+    if "adapter" in spec:
+        module, _, attr = spec.pop("adapter").partition(":")
+        cls = getattr(importlib.import_module(module), attr)
+        make = backend_factory(spec.pop("backend", {}), base)
+        params = spec.pop("params", {})
+        return lambda: cls(make(), **params)
+    model = spec.pop("model", "lending")
+    if model not in PROTOCOL_MODELS:
+        raise ValueError(f"unknown protocol model {model!r} (expected one of {', '.join(PROTOCOL_MODELS)})")
+    return lambda: PROTOCOL_MODELS[model](**spec)
+
+
+def load_simulation(data: dict[str, Any], base: Path | None = None) -> Simulation:
+    """Build a simulation from a config dict (the YAML file of `sim run`)."""
+    config = SimulationConfig(
+        price=PriceModel(**data.get("price", {})),
+        oracle=OracleModel(**data.get("oracle", {})),
+        **data.get("simulation", {}),
+    )
+    return Simulation(protocol_factory(data.get("protocol", {}), base), list(data.get("agents", [])), config)
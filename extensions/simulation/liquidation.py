@@ -0,0 +1,262 @@
+"""
+Liquidation scenario sweeps for lending protocols.
+
+Runs one deterministic crash per configuration — a collateral price drop,
+an oracle latency and a position size — with a single borrower opening
+near the loan-to-value limit and a liquidator exiting seized collateral
+through an external venue of finite depth. Each scenario is classified:
+
+- healthy: the position never became liquidatable
+- liquidated: liquidators closed it without loss to the protocol
+- unprofitable: liquidators passed on it and it was still unhealthy at the end
+- bad-debt: debt ended written off or uncovered by collateral
+
+The grid of outcomes locates the configurations where liquidation stops
+being profitable or bad debt accrues, and feeds the economic-risk
+section of the report (see ``extensions.reporting.economic``).
+"""
+
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Any, Callable
+
+from .engine import EconomicFinding, Simulation, SimulationConfig, protocol_factory
+from .market import OracleModel
+from .protocols import ProtocolModel
+
+SWEEP_VERSION = "1.0.0"
+
+STATUSES = ("healthy", "liquidated", "unprofitable", "bad-debt")
+
+
+@dataclass
+class CrashPath:
+    """Flat price, a linear drop of ``drop`` over ``over`` slots starting at ``at``, then flat."""
+
+    start: float = 100.0
+    drop: float = 0.2
+    at: int = 5
+    over: int = 10
+
+    def path(self, slots: int, rng: Any = None) -> list[float]:
+        bottom = self.start * (1 - self.drop)
+        prices = []
+        for slot in range(slots):
+            progress = min(1.0, max(0.0, (slot - self.at + 1) / max(self.over, 1)))
+            prices.append(self.start + (bottom - self.start) * progress)
+        return prices
+
+    def to_dict(self) -> dict[str, Any]:
+        return asdict(self)
+
+
+@dataclass
+class SweepConfig:
+    """Axes of the sweep and the fixed market around each scenario."""
+
+    # Collateral price drops, as fractions of the start price
+    drops: list[float] = field(default_factory=lambda: [0.05, 0.1, 0.2, 0.3, 0.5])
+    # Oracle lag in slots
+    latencies: list[int] = field(default_factory=lambda: [0, 10, 30, 60])
+    # Position sizes in collateral units
+    sizes: list[float] = field(default_factory=lambda: [10.0, 1_000.0, 100_000.0])
+    # Borrowed share of the loan-to-value limit
+    utilization: float = 0.95
+    start_price: float = 100.0
+    crash_slots: int = 10
+    # Slots after the oracle has caught up with the bottom
+    tail_slots: int = 50
+    external_fee: float = 0.001
+    # Quote-side depth of the venue liquidators sell into; None for unlimited
+    external_depth: float | None = 5_000_000.0
+    # Liquidator overrides: cost, min_profit, quote (default: twice the debt)
+    liquidator: dict[str, Any] = field(default_factory=dict)
+    # Oracle update triggers besides the swept lag: heartbeat, deviation
+    oracle: dict[str, Any] = field(default_factory=dict)
+    # Drops up to this size count as plausible market moves when rating findings
+    plausible_drop: float = 0.3
+
+    def to_dict(self) -> dict[str, Any]:
+        return asdict(self)
+
+
+@dataclass
+class ScenarioResult:
+    """Outcome of one (drop, latency, size) scenario."""
+
+    drop: float
+    latency: int
+    size: float
+    debt: float
+    liquidations: int
+    declined: int
+    liquidatable: int
+    bad_debt: float
+    liquidator_pnl: float
+
+    @property
+    def bad_debt_share(self) -> float:
+        return self.bad_debt / self.debt if self.debt else 0.0
+
+    @property
+    def status(self) -> str:
+        if self.bad_debt > 1e-6 * max(self.debt, 1.0):
+            return "bad-debt"
+        if self.declined and self.liquidatable:
+            return "unprofitable"
+        return "liquidated" if self.liquidations else "healthy"
+
+    def to_dict(self) -> dict[str, Any]:
+        return {**asdict(self), "bad_debt_share": self.bad_debt_share, "status": self.status}
+
+
+@dataclass
+class SweepResult:
+    """All scenarios of a sweep."""
+
+    protocol: str
+    params: dict[str, Any]
+    config: SweepConfig
+    scenarios: list[ScenarioResult]
+
+    def scenario(self, drop: float, latency: int, size: float) -> ScenarioResult | None:
+        return next(
+            (s for s in self.scenarios if (s.drop, s.latency, s.size) == (drop, latency, size)), None
+        )
+
+    def findings(self) -> list[EconomicFinding]:
+        """Bad-debt and unprofitable-liquidation findings with the configurations that trigger them."""
+        plausible = self.config.plausible_drop
+        findings = []
+        indebted = [s for s in self.scenarios if s.status == "bad-debt"]
+        if indebted:
+            worst = max(indebted, key=lambda s: s.bad_debt_share)
+            thresholds = self._thresholds(indebted)
+            first = min(s.drop for s in indebted)
+            findings.append(EconomicFinding(
+                "bad-debt",
+                "high" if first <= plausible else "medium",
+                f"{self.protocol} accrues bad debt from a {first:.0%} collateral price drop",
+                f"{len(indebted)} of {len(self.scenarios)} liquidation scenarios ended with bad debt, up to "
+                f"{worst.bad_debt_share:.2%} of the position's debt ({worst.drop:.0%} drop, oracle lag "
+                f"{worst.latency} slots, {worst.size:,g} collateral). Smallest drop with bad debt per size and "
+                f"oracle lag: {self._describe(thresholds)}. Parameters: {self._params()}.",
+                self._evidence(indebted, thresholds),
+            ))
+        stuck = [s for s in self.scenarios if s.status == "unprofitable"]
+        if stuck:
+            thresholds = self._thresholds(stuck)
+            first = min(s.drop for s in stuck)
+            findings.append(EconomicFinding(
+                "unprofitable-liquidation",
+                "medium" if first <= plausible else "low",
+                f"Liquidating {self.protocol} positions is unprofitable after a {first:.0%} price drop",
+                f"In {len(stuck)} of {len(self.scenarios)} liquidation scenarios liquidators passed on an "
+                f"unhealthy position that stayed unhealthy, because the bonus did not cover the exit "
+                f"({self.config.external_fee:g} fee, depth {self.config.external_depth or 'unlimited'}) and "
+                f"liquidation costs. Smallest such drop per size and oracle lag: "
+                f"{self._describe(thresholds)}. Parameters: {self._params()}.",
+                self._evidence(stuck, thresholds),
+            ))
+        return findings
+
+    @staticmethod
+    def _thresholds(affected: list[ScenarioResult]) -> list[dict[str, Any]]:
+        smallest: dict[tuple[float, int], float] = {}
+        for s in affected:
+            key = (s.size, s.latency)
+            smallest[key] = min(smallest.get(key, s.drop), s.drop)
+        return [{"size": size, "latency": latency, "drop": drop} for (size, latency), drop in sorted(smallest.items())]
+
+    @staticmethod
+    def _describe(thresholds: list[dict[str, Any]]) -> str:
+        return "; ".join(f"size {t['size']:,g} / lag {t['latency']}: {t['drop']:.0%}" for t in thresholds)
+
+    def _evidence(self, affected: list[ScenarioResult], thresholds: list[dict[str, Any]]) -> dict[str, Any]:
+        return {
+            "runs": len(self.scenarios),
+            "runs_affected": len(affected),
+            "thresholds": thresholds,
+            "params": self.params,
+            "sweep": self.config.to_dict(),
+        }
+
+    def _params(self) -> str:
+        return ", ".join(f"{k}={v}" for k, v in self.params.items() if v is not None)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "tool": "liquidation-sweep",
+            "version": SWEEP_VERSION,
+            "protocol": self.protocol,
+            "params": self.params,
+            "config": self.config.to_dict(),
+            "scenarios": [s.to_dict() for s in self.scenarios],
+            "findings": [asdict(f) for f in self.findings()],
+        }
+
+
+class LiquidationSweep:
+    """Sweeps price drops, oracle latencies and position sizes over a lending protocol."""
+
+    def __init__(self, protocol: Callable[[], ProtocolModel], config: SweepConfig | None = None):
+        """Initialize the sweep.
+
+        Args:
+            protocol: Factory for a fresh protocol instance per scenario; its
+                liquidity must cover the largest position's borrow
+            config: Sweep axes and market
+        """
+        self.protocol = protocol
+        self.config = config or SweepConfig()
+        self._described: tuple[str, dict[str, Any]] = ("", {})
+
+    def run(self) -> SweepResult:
+        scenarios = [
+            self.run_scenario(drop, latency, size)
+            for size in self.config.sizes for latency in self.config.latencies for drop in self.config.drops
+        ]
+        name, params = self._described
+        return SweepResult(name, params, self.config, scenarios)
+
+    def run_scenario(self, drop: float, latency: int, size: float) -> ScenarioResult:
+        cfg = self.config
+        price = CrashPath(start=cfg.start_price, drop=drop, over=cfg.crash_slots)
+        debt_limit = size * cfg.start_price
+        agents = [
+            {"type": "borrower", "name": "borrower", "positions": 1, "collateral": size,
+             "utilization": cfg.utilization, "spread": 0.0},
+            {"type": "liquidator", "name": "liquidator", "quote": 2 * debt_limit, **cfg.liquidator},
+        ]
+        simulation = Simulation(self.protocol, agents, SimulationConfig(
+            slots=price.at + cfg.crash_slots + latency + cfg.tail_slots,
+            external_fee=cfg.external_fee,
+            external_depth=cfg.external_depth,
+            sample_every=1,
+            price=price,
+            oracle=OracleModel(lag_slots=latency, **cfg.oracle),
+        ))
+        result = simulation.run()
+        self._described = (result.protocol, result.params)
+        run = result.runs[0]
+        debt = run.timeline[0]["debt"]
+        if debt <= 0:
+            raise ValueError(f"the borrower could not open a position of {size:g} collateral; "
+                             f"raise the protocol liquidity")
+        liquidator = run.agents["liquidator"]
+        return ScenarioResult(
+            drop=drop,
+            latency=latency,
+            size=size,
+            debt=debt,
+            liquidations=liquidator["actions"].get("liquidate", 0),
+            declined=liquidator["declined"],
+            liquidatable=int(run.final.get("liquidatable", 0)),
+            bad_debt=run.final.get("bad_debt", 0.0),
+            liquidator_pnl=liquidator["pnl"],
+        )
+
+
+def load_sweep(data: dict[str, Any], base: Path | None = None) -> LiquidationSweep:
+    """Build a sweep from a config dict (the YAML file of `sim liquidations`)."""
+    return LiquidationSweep(protocol_factory(data.get("protocol", {}), base), SweepConfig(**data.get("sweep", {})))
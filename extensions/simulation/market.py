@@ -0,0 +1,68 @@
+"""
+Reference price and oracle models.
+
+The reference price is what the asset trades for on an external venue;
+the oracle is what the protocol sees. Oracles publish a price that is
+``lag_slots`` old and only update on a heartbeat or when the move exceeds
+the deviation threshold, so during fast moves the two drift apart — the
+window arbitrageurs and oracle-lag attackers trade against.
+"""
+
+import math
+import random
+from dataclasses import asdict, dataclass
+from typing import Any
+
+
+@dataclass
+class PriceModel:
+    """Geometric Brownian motion with optional downward (or upward) jumps."""
+
+    start: float = 100.0
+    # Per-slot log-return standard deviation and drift
+    volatility: float = 0.001
+    drift: float = 0.0
+    jump_probability: float = 0.0
+    jump_size: float = -0.2
+
+    def path(self, slots: int, rng: random.Random) -> list[float]:
+        price = self.start
+        prices = []
+        for _ in range(slots):
+            prices.append(price)
+            step = self.drift - self.volatility ** 2 / 2 + self.volatility * rng.gauss(0.0, 1.0)
+            price *= math.exp(step)
+            if self.jump_probability and rng.random() < self.jump_probability:
+                price *= max(1.0 + self.jump_size, 1e-9)
+        return prices
+
+    def to_dict(self) -> dict[str, Any]:
+        return asdict(self)
+
+
+@dataclass
+class OracleModel:
+    """Push oracle with publication latency, heartbeat and deviation threshold."""
+
+    lag_slots: int = 0
+    # 0 disables the heartbeat / deviation trigger; both 0 updates every slot
+    heartbeat: int = 0
+    deviation: float = 0.0
+
+    def feed(self, prices: list[float]) -> list[float]:
+        published: list[float] = []
+        last, last_slot = 0.0, 0
+        for slot in range(len(prices)):
+            observed = prices[max(0, slot - self.lag_slots)]
+            if (
+                slot == 0
+                or not (self.heartbeat or self.deviation)
+                or (self.heartbeat and slot - last_slot >= self.heartbeat)
+                or (self.deviation and abs(observed - last) / last >= self.deviation)
+            ):
+                last, last_slot = observed, slot
+            published.append(last)
+        return published
+
+    def to_dict(self) -> dict[str, Any]:
+        return asdict(self)
@@ -0,0 +1,522 @@
+"""
+Monte Carlo stress testing of fee, reward and interest math.
+
+A case is one calculation and the state it accumulates into, e.g. a
+reward-per-token accumulator updated every slot. Each trial draws inputs
+from realistic distributions (log-uniform amounts, occasional boundary
+values) and applies the update for many steps twice: with the program's
+integer semantics and with exact arithmetic. That yields
+concrete numbers for
+
+- drift: how far the accumulated state ends from the exact value
+- overflow proximity: the largest intermediate against the integer
+  width, and the first input that overflows or underflows
+- precision loss: the worst single-step rounding error (in units of the
+  result) and inputs whose increment rounds away entirely
+
+The calculation is either a Rust-style integer expression emulated here
+(``amount * fee_bps / 10_000`` over u64/u128) or an instruction of the
+deployed program executed on the LiteSVM backend, which returns its
+result through ``set_return_data``.
+"""
+
+import ast
+import base64
+import math
+import random
+import re
+import struct
+from dataclasses import dataclass, field
+from decimal import Decimal, localcontext
+from pathlib import Path
+from typing import Any, Callable
+
+from .backend import ExecutionBackend, Instruction, LiteSvmBackend
+
+STRESS_VERSION = "1.0.0"
+
+_CAST_RE = re.compile(r"\s+as\s+[ui](?:8|16|32|64|128|size)\b")
+_RETURN_RE = re.compile(r"^Program return: (\S+) (\S+)")
+_INT_FORMATS = {"u8": "<B", "u16": "<H", "u32": "<I", "u64": "<Q", "i64": "<q"}
+_FUNCTIONS = ("min", "max", "mul_div", "ceil_div", "isqrt")
+# Significant digits of the exact reference; far beyond any u128 result
+_EXACT_DIGITS = 80
+
+
+class Overflow(ArithmeticError):
+    """An intermediate left the integer range."""
+
+    def __init__(self, kind: str, value: Any):
+        super().__init__(f"{kind}: {value}")
+        self.kind = kind
+        self.value = value
+
+
+@dataclass
+class Distribution:
+    """Input distribution: uniform, loguniform, choice or a constant."""
+
+    kind: str
+    args: list[Any] = field(default_factory=list)
+
+    @classmethod
+    def parse(cls, spec: Any) -> "Distribution":
+        """``{"loguniform": [1, 1e9]}``, ``{"choice": [...]}``, ``{"uniform": [lo, hi]}`` or a number."""
+        if isinstance(spec, dict):
+            (kind, args), = spec.items()
+            if kind not in ("uniform", "loguniform", "choice"):
+                raise ValueError(f"unknown distribution {kind!r}")
+            return cls(kind, list(args))
+        return cls("constant", [spec])
+
+    @property
+    def bounds(self) -> list[int]:
+        values = [float(v) for v in (self.args[:2] if self.kind in ("uniform", "loguniform") else self.args)]
+        return [int(min(values)), int(max(values))]
+
+    def sample(self, rng: random.Random) -> int:
+        if self.kind == "constant":
+            return int(float(self.args[0]))
+        if self.kind == "choice":
+            return int(float(rng.choice(self.args)))
+        low, high = float(self.args[0]), float(self.args[1])
+        if self.kind == "loguniform":
+            return int(round(math.exp(rng.uniform(math.log(max(low, 1.0)), math.log(max(high, 1.0))))))
+        return int(round(rng.uniform(low, high)))
+
+
+class Evaluator:
+    """Evaluates an arithmetic expression with integer or exact semantics.
+
+    Integer mode emulates checked Rust arithmetic of the given width: ``/``
+    truncates, every intermediate is range-checked, and the largest magnitude
+    seen is tracked. Exact mode uses 80-digit decimals, so the difference
+    between the two is the rounding error.
+    """
+
+    def __init__(self, expression: str, width: int = 64, signed: bool = False):
+        self.expression = expression
+        self.tree = ast.parse(_CAST_RE.sub("", expression).strip(), mode="eval").body
+        self.width = width
+        self.signed = signed
+        self.names = sorted({n.id for n in ast.walk(self.tree) if isinstance(n, ast.Name)} - set(_FUNCTIONS))
+        self.peak = 0
+
+    @property
+    def limit(self) -> int:
+        return 2 ** (self.width - 1) - 1 if self.signed else 2 ** self.width - 1
+
+    def integer(self, env: dict[str, int]) -> int:
+        return self._eval(self.tree, env, exact=False)
+
+    def exact(self, env: dict[str, Any]) -> Decimal:
+        with localcontext() as ctx:
+            ctx.prec = _EXACT_DIGITS
+            return self._eval(self.tree, {k: Decimal(v) for k, v in env.items()}, exact=True)
+
+    def _check(self, value: int, width: int | None = None) -> int:
+        limit = 2 ** width - 1 if width else self.limit
+        if value > limit:
+            raise Overflow("overflow", value)
+        if value < (-limit - 1 if self.signed else 0):
+            raise Overflow("underflow", value)
+        if not width:
+            self.peak = max(self.peak, abs(value))
+        return value
+
+    def _eval(self, node: ast.AST, env: dict[str, Any], exact: bool) -> Any:
+        if isinstance(node, ast.Constant) and isinstance(node.value, int):
+            return Decimal(node.value) if exact else node.value
+        if isinstance(node, ast.Name):
+            if node.id not in env:
+                raise ValueError(f"unbound name {node.id!r} in {self.expression!r}")
+            return env[node.id] if exact else self._check(env[node.id])
+        if isinstance(node, ast.UnaryOp) and isinstance(node.op, ast.USub):
+            value = -self._eval(node.operand, env, exact)
+            return value if exact else self._check(value)
+        if isinstance(node, ast.BinOp):
+            a, b = self._eval(node.left, env, exact), self._eval(node.right, env, exact)
+            if isinstance(node.op, ast.Add):
+                value = a + b
+            elif isinstance(node.op, ast.Sub):
+                value = a - b
+            elif isinstance(node.op, ast.Mult):
+                value = a * b
+            elif isinstance(node.op, (ast.Div, ast.FloorDiv)):
+                if b == 0:
+                    raise ZeroDivisionError(self.expression)
+                value = a / b if exact else _trunc_div(a, b)
+            elif isinstance(node.op, ast.Mod):
+                value = a % b
+            elif isinstance(node.op, ast.Pow):
+                value = a ** int(b)
+            else:
+                raise ValueError(f"unsupported operator in {self.expression!r}")
+            return value if exact else self._check(value)
+        if isinstance(node, ast.Call) and isinstance(node.func, ast.Name) and node.func.id in _FUNCTIONS:
+            args = [self._eval(arg, env, exact) for arg in node.args]
+            return self._call(node.func.id, args, exact)
+        raise ValueError(f"unsupported expression {ast.unparse(node)!r}")
+
+    def _call(self, name: str, args: list[Any], exact: bool) -> Any:
+        if name in ("min", "max"):
+            return (min if name == "min" else max)(args)
+        if name == "isqrt":
+            return args[0].sqrt() if exact else math.isqrt(args[0])
+        if exact:
+            return args[0] * args[1] / args[2] if name == "mul_div" else args[0] / args[1]
+        if name == "mul_div":
+            # a * b / c with a double-width intermediate, as in U256/u128 helpers
+            return self._check(_trunc_div(self._check(args[0] * args[1], self.width * 2), args[2]))
+        return self._check(-(-args[0] // args[1]) if args[1] > 0 else _trunc_div(args[0], args[1]))
+
+
+def _trunc_div(a: int, b: int) -> int:
+    """Integer division rounding toward zero, like Rust."""
+    q = abs(a) // abs(b)
+    return q if (a >= 0) == (b >= 0) else -q
+
+
+class ProgramCalculation:
+    """Runs a calculation as an instruction of a deployed program on LiteSVM.
+
+    ``layout`` describes the instruction data: a list of ``"u8:3"`` literal
+    fields and ``"u64:amount"`` input fields, little-endian. The program
+    must return the result with ``set_return_data``.
+    """
+
+    def __init__(self, backend: ExecutionBackend, program_id: str, layout: list[str], returns: str = "u64",
+                 accounts: list[str] | None = None):
+        self.backend = backend
+        self.program_id = program_id
+        self.fields = [tuple(spec.split(":", 1)) for spec in layout]
+        self.returns = returns
+        self.accounts = accounts or []
+        self.names = [v for _, v in self.fields if not v.lstrip("-").isdigit()]
+        self.peak = 0
+        self.width = int(returns[1:])
+        self.backend.start()
+        self.backend.keypair("payer", lamports=10 ** 12)
+
+    def encode(self, env: dict[str, int]) -> bytes:
+        data = b""
+        for ty, value in self.fields:
+            number = int(value) if value.lstrip("-").isdigit() else env[value]
+            data += _pack(ty, number)
+        return data
+
+    def integer(self, env: dict[str, int]) -> int:
+        from .backend import AccountMeta
+
+        ix = Instruction(self.program_id, [AccountMeta(a) for a in self.accounts], self.encode(env))
+        result = self.backend.send([ix], payer="payer")
+        if not result.ok:
+            kind = "overflow" if "overflow" in (result.error or "").lower() + " ".join(result.logs).lower() else "error"
+            raise Overflow(kind, result.error)
+        for line in reversed(result.logs):
+            match = _RETURN_RE.match(line)
+            if match:
+                value = int.from_bytes(base64.b64decode(match.group(2)), "little", signed=self.returns.startswith("i"))
+                self.peak = max(self.peak, abs(value))
+                return value
+        raise Overflow("error", "no return data")
+
+    def close(self) -> None:
+        self.backend.close()
+
+
+def _pack(ty: str, value: int) -> bytes:
+    if ty in _INT_FORMATS:
+        return struct.pack(_INT_FORMATS[ty], value)
+    if ty in ("u128", "i128"):
+        return value.to_bytes(16, "little", signed=ty == "i128")
+    raise ValueError(f"unsupported field type {ty!r}")
+
+
+@dataclass
+class StressCase:
+    """One calculation, its inputs and the state it accumulates into.
+
+    ``step`` maps each state variable to the expression producing its next
+    value. With a ``program`` calculation, the program computes ``into``
+    and ``reference`` is the exact formula it implements.
+    """
+
+    name: str
+    inputs: dict[str, Distribution]
+    step: dict[str, str] = field(default_factory=dict)
+    state: dict[str, int] = field(default_factory=dict)
+    reference: str | None = None
+    into: str | None = None
+    width: int = 64
+    signed: bool = False
+    trials: int = 100
+    steps: int = 1000
+    # Share of input draws taken from the distribution bounds
+    edges: float = 0.05
+    program: Callable[[], ProgramCalculation] | None = None
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any], base: Path | None = None) -> "StressCase":
+        data = dict(data)
+        inputs = {name: Distribution.parse(spec) for name, spec in data.pop("inputs", {}).items()}
+        if "expression" in data:
+            data["step"] = {data.pop("into", "result"): data.pop("expression")}
+        program = data.pop("program", None)
+        case = cls(inputs=inputs, **data)
+        if program:
+            case.program = _program_factory(program, base or Path.cwd())
+            case.into = case.into or "result"
+            if not case.reference:
+                raise ValueError(f"{case.name}: program calculations need a reference formula")
+        return case
+
+
+def _program_factory(spec: dict[str, Any], base: Path) -> Callable[[], ProgramCalculation]:
+    program_id = spec.get("id")
+    programs = {program_id: base / spec["so"]} if spec.get("so") else {}
+    config = None
+    if spec.get("anchor"):
+        # Program id and build artifact from the workspace's Anchor.toml
+        from extensions.ir.anchor_config import load_anchor_config
+
+        config = load_anchor_config(base / spec["anchor"])
+        if config is None:
+            raise ValueError(f"no Anchor.toml found at {base / spec['anchor']}")
+        program_id = program_id or config.program_id(spec.get("name", ""), spec.get("cluster"))
+    if not program_id:
+        raise ValueError("program calculations need an id (or an Anchor.toml program name)")
+
+    def build() -> ProgramCalculation:
+        if config:
+            backend = LiteSvmBackend.from_anchor(config, spec.get("cluster"), programs=programs)
+        else:
+            backend = LiteSvmBackend(programs=programs, cwd=base)
+        return ProgramCalculation(backend, program_id, spec["layout"], spec.get("returns", "u64"), spec.get("accounts"))
+
+    return build
+
+
+@dataclass
+class StressReport:
+    """Concrete numbers from one case."""
+
+    case: str
+    trials: int
+    steps: int
+    width: int
+    # Accumulated state after the horizon: exact minus actual
+    drift: dict[str, Any]
+    overflow: dict[str, Any]
+    precision: dict[str, Any]
+    errors: int = 0
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "case": self.case, "trials": self.trials, "steps": self.steps, "width": self.width,
+            "drift": self.drift, "overflow": self.overflow, "precision": self.precision, "errors": self.errors,
+        }
+
+
+def run_case(case: StressCase, seed: int = 0) -> StressReport:
+    """Run every trial of ``case``."""
+    rng = random.Random(seed)
+    program = case.program() if case.program else None
+    evaluators = {var: Evaluator(expr, case.width, case.signed) for var, expr in case.step.items()}
+    references = {var: Evaluator(expr, case.width, case.signed) for var, expr in case.step.items()}
+    if case.reference:
+        references[case.into or next(iter(case.step))] = Evaluator(case.reference, case.width, case.signed)
+    state_vars = list(references)
+    # State read by the next step (accumulators and variables other steps use)
+    carried = [var for var in state_vars if any(var in ev.names for ev in references.values())]
+    accumulators = {var for var, ev in references.items() if var in ev.names}
+
+    drift: dict[str, Any] = {"absolute": 0.0, "relative": 0.0}
+    drifts: list[float] = []
+    precision: dict[str, Any] = {"max_units": 0.0, "relative": 0.0, "rounded_to_zero": 0}
+    first_overflow: dict[str, Any] | None = None
+    overflows = errors = 0
+    try:
+        for trial in range(case.trials):
+            actual = {var: case.state.get(var, 0) for var in state_vars}
+            exact = {var: Decimal(v) for var, v in actual.items()}
+            for step in range(case.steps):
+                env = {name: _draw(dist, rng, case.edges) for name, dist in case.inputs.items()}
+                values = {**env, **{var: actual[var] for var in carried}}
+                try:
+                    updates = {}
+                    for var in state_vars:
+                        if program and var == case.into:
+                            updates[var] = program.integer(values)
+                        else:
+                            updates[var] = evaluators[var].integer(values)
+                        # Loss of this step alone: exact result from the same (rounded) state
+                        precise = references[var].exact(values)
+                        _record_loss(precision, precise, updates[var], actual[var] if var in accumulators else 0,
+                                     {"inputs": values, "variable": var, "trial": trial, "step": step})
+                    exact_values = {**env, **{var: exact[var] for var in carried}}
+                    exact = {var: references[var].exact(exact_values) for var in state_vars}
+                    actual = updates
+                except Overflow as e:
+                    if e.kind == "error":
+                        errors += 1
+                    else:
+                        overflows += 1
+                        if first_overflow is None:
+                            first_overflow = {"kind": e.kind, "trial": trial, "step": step, "inputs": values}
+                    break
+                except ZeroDivisionError:
+                    errors += 1
+                    break
+            for var in carried:
+                diff = exact[var] - actual[var]
+                drifts.append(float(diff))
+                if abs(diff) > abs(drift["absolute"]):
+                    drift = {
+                        "absolute": float(diff), "relative": float(abs(diff) / abs(exact[var])) if exact[var] else 0.0,
+                        "variable": var, "trial": trial, "exact": float(exact[var]), "actual": actual[var],
+                    }
+    finally:
+        if program:
+            program.close()
+
+    peak = max([e.peak for e in evaluators.values()] + [program.peak if program else 0])
+    width = program.width if program else case.width
+    return StressReport(
+        case=case.name,
+        trials=case.trials,
+        steps=case.steps,
+        width=width,
+        drift={**drift, "mean": sum(drifts) / len(drifts) if drifts else 0.0},
+        overflow={
+            "peak": peak,
+            "peak_ratio": peak / (2 ** width - 1),
+            "headroom_bits": round(width - math.log2(peak), 2) if peak else float(width),
+            "events": overflows,
+            "first": first_overflow,
+        },
+        precision=precision,
+        errors=errors,
+    )
+
+
+def _record_loss(precision: dict[str, Any], precise: Decimal, actual: int, previous: int,
+                 where: dict[str, Any]) -> None:
+    loss = abs(precise - actual)
+    if loss > precision["max_units"]:
+        precision.update(max_units=float(loss), max_units_at={**where, "exact": float(precise), "actual": actual})
+    # Truncation alone loses under one unit; relative loss only means something for results above that
+    if abs(precise) >= 1 and loss / abs(precise) > precision["relative"]:
+        precision.update(relative=float(loss / abs(precise)),
+                         relative_at={**where, "exact": float(precise), "actual": actual})
+    if actual == previous and precise - previous > 0:
+        precision["rounded_to_zero"] += 1
+        precision.setdefault("rounded_to_zero_at", {**where, "exact": float(precise), "actual": actual})
+
+
+def _draw(dist: Distribution, rng: random.Random, edges: float) -> int:
+    if dist.kind != "constant" and edges and rng.random() < edges:
+        return rng.choice(dist.bounds)
+    return dist.sample(rng)
+
+
+@dataclass
+class MathStressFinding:
+    """A fee/reward/interest calculation that drifts, loses precision or overflows."""
+
+    kind: str
+    severity: str
+    report: StressReport
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        r = self.report
+        if self.kind == "overflow":
+            first = r.overflow["first"]
+            title = f"{r.case}: arithmetic {first['kind']} under realistic inputs"
+            description = (
+                f"{r.overflow['events']} of {r.trials} trials hit an integer {first['kind']} in `{r.case}` "
+                f"(u{r.width}), first at step {first['step']} of trial {first['trial']} with inputs "
+                f"{first['inputs']}. With overflow checks the instruction aborts; without them the value wraps."
+            )
+        elif self.kind == "overflow-proximity":
+            title = f"{r.case}: intermediates come within {r.overflow['headroom_bits']} bits of u{r.width}"
+            description = (
+                f"The largest intermediate in `{r.case}` was {r.overflow['peak']:,} ({r.overflow['peak_ratio']:.2e} "
+                f"of the u{r.width} range) over {r.trials} trials of {r.steps} steps; inputs or horizons slightly "
+                f"beyond those sampled overflow."
+            )
+        elif self.kind == "drift":
+            d = r.drift
+            title = f"{r.case}: rounding drift accumulates to {d['relative']:.2e} over {r.steps} steps"
+            description = (
+                f"After {r.steps} steps, `{d['variable']}` ended {d['absolute']:,.6g} ({d['relative']:.2e} "
+                f"relative) from the exact value ({d['exact']:,.6g} vs {d['actual']:,}) in the worst of "
+                f"{r.trials} trials; the mean drift was {d['mean']:,.6g}. Truncation always rounds the same way, "
+                f"so the error accrues to one side."
+            )
+        elif self.kind == "precision":
+            at = r.precision["max_units_at"]
+            title = f"{r.case}: one step loses {r.precision['max_units']:,.6g} units to intermediate rounding"
+            description = (
+                f"`{at['variable']}` evaluated to {at['actual']:,} where the exact result is {at['exact']:,.6g}, "
+                f"with inputs {at['inputs']}. Truncating the result costs under one unit; more means an "
+                f"intermediate division discards precision. Multiplying before dividing or scaling by a larger "
+                f"precision factor avoids it."
+            )
+        else:
+            at = r.precision["rounded_to_zero_at"]
+            title = f"{r.case}: small {at['variable']} increments round to zero"
+            description = (
+                f"In {r.precision['rounded_to_zero']} of {r.trials * r.steps} steps the increment of "
+                f"`{at['variable']}` truncated to nothing (e.g. exact {at['exact']:,.6g}, got {at['actual']:,}, "
+                f"inputs {at['inputs']}). Callers can split amounts so fees round to zero, or rewards accrued "
+                f"between updates are lost."
+            )
+        return {
+            "title": title,
+            "description": description,
+            "vulnerability_type": "arithmetic-overflow" if self.kind.startswith("overflow") else "precision-loss",
+            "severity": self.severity,
+            "confidence": 0.8 if self.kind == "overflow" else 0.5,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "math-stress",
+                "category": "arithmetic",
+                "source_files": [],
+                "affected_lines": [],
+                "stress": r.to_dict(),
+                "precision": "verified",
+            },
+        }
+
+
+def evaluate_report(report: StressReport, min_headroom_bits: float = 8.0, max_drift: float = 1e-6,
+                    max_step_units: float = 1.0) -> list[MathStressFinding]:
+    """Findings for a report.
+
+    Args:
+        min_headroom_bits: Intermediates closer than this to the width limit are reported
+        max_drift: Tolerated relative drift of the accumulated state
+        max_step_units: Tolerated single-step loss, in units of the result
+    """
+    findings = []
+    if report.overflow["events"]:
+        findings.append(MathStressFinding("overflow", "high", report))
+    elif report.overflow["headroom_bits"] < min_headroom_bits:
+        findings.append(MathStressFinding("overflow-proximity", "medium", report))
+    if report.drift["relative"] > max_drift:
+        findings.append(MathStressFinding("drift", "medium" if report.drift["relative"] > 1e-3 else "low", report))
+    if report.precision["max_units"] >= max_step_units:
+        findings.append(MathStressFinding("precision", "medium", report))
+    if report.precision["rounded_to_zero"]:
+        findings.append(MathStressFinding("rounds-to-zero", "low", report))
+    return findings
+
+
+def load_cases(data: dict[str, Any], base: Path | None = None) -> list[StressCase]:
+    """Cases from a config dict (the YAML file of `sim math`)."""
+    defaults = {k: v for k, v in data.items() if k in ("trials", "steps", "edges", "width")}
+    return [StressCase.from_dict({**defaults, **case}, base) for case in data.get("cases", [])]
@@ -0,0 +1,373 @@
+"""
+Upgrade simulation of accounts written by a previous program version.
+
+Proves what the `state-migration` static analyzer predicts: account
+fixtures are encoded with the baseline version's layout (or loaded from
+dumps of real accounts), placed on a chain running the new build, and the
+transactions of the config are sent against them. Each fixture is
+classified from how the new program took it:
+
+- bricked: the first transaction touching it failed to deserialize it
+  (Anchor's discriminator and deserialization errors), or nothing touched
+  it and the new layout cannot decode it
+- corrupted: the new layout reads fields the two versions share with other
+  values, so the program acts on state nobody wrote (the transactions show
+  what it does with it)
+- migrated: the first transaction touching it rewrote it into the new
+  layout with the shared fields intact (a migration instruction)
+- compatible: the new layout reads the old bytes as the old values
+
+Layouts come from the `#[account]` structs of the new and baseline
+sources (``program`` and ``baseline``, a directory or a git ref of the
+program's repository), or from their IDLs.
+"""
+
+import hashlib
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Callable
+
+from extensions.monitor.events import decode_value
+
+from .backend import ExecutionBackend, TxResult, load_account_dump
+from .boundary import EncodeError, InstructionSpec, _ir_types, load_idl, resolver
+from .substitution import ChainState, TransactionSpec
+
+MIGRATION_VERSION = "1.0.0"
+
+VERDICTS = ("bricked", "corrupted", "migrated", "compatible")
+SEVERITY = {"corrupted": "high", "bricked": "medium"}
+
+# Lamports a fixture account holds unless the config says otherwise
+FIXTURE_LAMPORTS = 1_000_000_000
+
+# AccountDiscriminatorNotFound, AccountDiscriminatorMismatch, AccountDidNotDeserialize
+_DESERIALIZE = re.compile(r"AccountDiscriminator(?:NotFound|Mismatch)|AccountDidNotDeserialize|"
+                          r"custom program error: 0xbb[9ab]\b|Failed to deserialize", re.I)
+
+
+def account_discriminator(name: str) -> bytes:
+    """First 8 bytes of sha256("account:<name>"), Anchor's account discriminator."""
+    return hashlib.sha256(f"account:{name}".encode()).digest()[:8]
+
+
+def read_account(type_name: str, data: bytes, types: dict[str, dict[str, Any]]) -> tuple[Any, str | None]:
+    """(fields, None) of ``data`` read as Anchor account ``type_name``, or (None, why it cannot be read)."""
+    if data[:8] != account_discriminator(type_name):
+        return None, f"discriminator is not {type_name}'s"
+    try:
+        value, _ = decode_value({"defined": {"name": type_name}}, data, 8, types)
+    except ValueError as e:
+        return None, str(e)
+    return value, None
+
+
+@dataclass
+class Fixture:
+    """An account in the baseline layout."""
+
+    pubkey: str  # Keypair name or base58 key
+    type: str  # Account type in the baseline
+    new_type: str = ""  # In the new version, when renamed
+    values: dict[str, Any] | None = None
+    data: bytes | None = None  # Raw account data, discriminator included
+    lamports: int = FIXTURE_LAMPORTS
+
+    @property
+    def target_type(self) -> str:
+        return self.new_type or self.type
+
+    def encode(self, types: dict[str, dict[str, Any]], resolve: Callable[[str], bytes]) -> bytes:
+        if self.data is not None:
+            return self.data
+        # Account fields encode like a struct argument
+        spec = InstructionSpec(TransactionSpec(""), [], types=types)
+        try:
+            return account_discriminator(self.type) + spec._encode({"defined": {"name": self.type}}, self.values or {},
+                                                                   resolve)
+        except KeyError as e:
+            raise ValueError(f"fixture {self.pubkey} has no value for field {e}") from e
+
+
+@dataclass
+class FixtureResult:
+    """How the new build took one fixture."""
+
+    fixture: Fixture
+    verdict: str
+    old: Any = None  # Fields in the baseline layout
+    read_as: Any = None  # The same bytes in the new layout
+    read_error: str | None = None
+    after: Any = None  # The account after the transactions, in the new layout
+    transaction: str | None = None  # First transaction touching it
+    result: TxResult | None = None
+    # Shared fields the new layout reads differently: name -> (old, new)
+    differences: dict[str, tuple[Any, Any]] = field(default_factory=dict)
+    file: str = ""
+    line: int = 0
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "account": self.fixture.pubkey,
+            "type": self.fixture.type,
+            "new_type": self.fixture.target_type,
+            "verdict": self.verdict,
+            "old": self.old,
+            "read_as": self.read_as,
+            "read_error": self.read_error,
+            "after": self.after,
+            "transaction": self.transaction,
+            "ok": self.result.ok if self.result else None,
+            "error": self.result.error if self.result else None,
+            "differences": {name: list(pair) for name, pair in self.differences.items()},
+        }
+
+
+@dataclass
+class MigrationFinding:
+    """A fixture the new build bricks or misreads."""
+
+    fixture: FixtureResult
+
+    @property
+    def severity(self) -> str:
+        return SEVERITY[self.fixture.verdict]
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        fx = self.fixture
+        name, ty = fx.fixture.pubkey, fx.fixture.target_type
+        if fx.verdict == "corrupted":
+            read = ", ".join(f"`{field}` {old!r} as {new!r}" for field, (old, new) in fx.differences.items())
+            title = f"Upgrade misreads existing `{ty}` accounts ({', '.join(fx.differences)})"
+            if fx.result is None:
+                outcome = "so any instruction loading it acts on values nobody wrote"
+            elif fx.result.ok:
+                outcome = f"and {fx.transaction} succeeded against it, acting on values nobody wrote"
+            else:
+                outcome = f"and {fx.transaction} failed against it ({fx.result.error})"
+            detail = f"The new build reads {read}, {outcome}."
+        else:
+            reason = fx.result.error if fx.result and not fx.result.ok else fx.read_error
+            title = f"Upgrade bricks existing `{ty}` accounts"
+            detail = (f"The new build cannot load it ({reason}), so the account and whatever it holds stay "
+                      f"unusable until it is migrated.")
+        return {
+            "title": title,
+            "description": f"Account {name}, written in the baseline's `{fx.fixture.type}` layout and run through "
+                           f"the upgraded program: {detail}",
+            "vulnerability_type": "unmigrated-account-layout",
+            "severity": self.severity,
+            "confidence": 0.9,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "migration-sim",
+                "category": "upgradeability",
+                "source_files": [fx.file] if fx.file else [],
+                "affected_lines": [fx.line] if fx.line else [],
+                "account_type": ty,
+                "verdict": fx.verdict,
+                "fixture": fx.to_dict(),
+                "precision": "verified",
+            },
+        }
+
+
+@dataclass
+class MigrationResult:
+    fixtures: list[FixtureResult]
+    transactions: list[tuple[str, TxResult]] = field(default_factory=list)
+    # Static layout changes of the fixtures' types (LayoutChange dicts)
+    layouts: list[dict[str, Any]] = field(default_factory=list)
+
+    def findings(self) -> list[MigrationFinding]:
+        return [MigrationFinding(fx) for fx in self.fixtures if fx.verdict in SEVERITY]
+
+    def to_dict(self) -> dict[str, Any]:
+        counts = {v: sum(1 for fx in self.fixtures if fx.verdict == v) for v in VERDICTS}
+        return {
+            "tool": "migration-sim",
+            "version": MIGRATION_VERSION,
+            "summary": {"fixtures": len(self.fixtures), **counts},
+            "fixtures": [fx.to_dict() for fx in self.fixtures],
+            "transactions": [{"transaction": name, "ok": r.ok, "error": r.error, "logs": r.logs}
+                             for name, r in self.transactions],
+            "layouts": self.layouts,
+        }
+
+
+def _named(value: Any, keys: dict[str, str]) -> Any:
+    """``value`` with keypair public keys replaced by ``<name>``."""
+    if isinstance(value, dict):
+        return {k: _named(v, keys) for k, v in value.items()}
+    if isinstance(value, list):
+        return [_named(v, keys) for v in value]
+    return f"<{keys[value]}>" if isinstance(value, str) and value in keys else value
+
+
+class MigrationSimulation:
+    """Runs the new build against accounts in the baseline layout."""
+
+    def __init__(self, make_backend: Callable[[], ExecutionBackend], program_id: str, fixtures: list[Fixture],
+                 old_types: dict[str, dict[str, Any]], new_types: dict[str, dict[str, Any]],
+                 transactions: list[TransactionSpec] | None = None, state: ChainState | None = None,
+                 locations: dict[str, tuple[str, int]] | None = None,
+                 layouts: list[dict[str, Any]] | None = None):
+        """Initialize the simulation.
+
+        Args:
+            make_backend: Factory of a backend running the new build
+            program_id: Owner of the fixture accounts
+            fixtures: Accounts in the baseline layout
+            old_types, new_types: Defined types of the baseline and the new version
+            transactions: Sent in order after the fixtures are in place
+            state: Keypairs, accounts and setup transactions the chain starts from
+            locations: Account type -> (file, line) of its struct in the new sources
+            layouts: Static layout changes of the fixtures' types, for the result
+        """
+        self.make_backend = make_backend
+        self.program_id = program_id
+        self.fixtures = fixtures
+        self.old_types = old_types
+        self.new_types = new_types
+        self.transactions = transactions or []
+        self.state = state or ChainState()
+        self.locations = locations or {}
+        self.layouts = layouts or []
+
+    def run(self) -> MigrationResult:
+        backend = self.make_backend()
+        try:
+            self.state.prepare(backend)
+            resolve = resolver(backend)
+            written = {}
+            for fx in self.fixtures:
+                written[fx.pubkey] = fx.encode(self.old_types, resolve)
+                backend.set_account(fx.pubkey, written[fx.pubkey], self.program_id, lamports=fx.lamports)
+            sent, touched = [], {}
+            for tx in self.transactions:
+                result = tx.send(backend)
+                sent.append((tx.instruction, result))
+                for fx in self.fixtures:
+                    if fx.pubkey not in touched and any(slot.pubkey == fx.pubkey for slot in tx.accounts):
+                        account = backend.get_account(fx.pubkey)
+                        touched[fx.pubkey] = (tx.instruction, result, bytes(account["data"]) if account else b"")
+            final = {fx.pubkey: backend.get_account(fx.pubkey) for fx in self.fixtures}
+            keys = {backend.keypair(name): name for name in self.state.keypairs}
+        finally:
+            backend.close()
+        results = []
+        for fx in self.fixtures:
+            account = final[fx.pubkey]
+            results.append(self._classify(fx, written[fx.pubkey], touched.get(fx.pubkey),
+                                          bytes(account["data"]) if account else b"", keys))
+        return MigrationResult(results, sent, self.layouts)
+
+    def _classify(self, fx: Fixture, data: bytes, touched: tuple[str, TxResult, bytes] | None, final: bytes,
+                  keys: dict[str, str]) -> FixtureResult:
+        old, _ = read_account(fx.type, data, self.old_types)
+        read_as, read_error = read_account(fx.target_type, data, self.new_types)
+        after, _ = read_account(fx.target_type, final, self.new_types)
+        out = FixtureResult(fx, "compatible", _named(old, keys), _named(read_as, keys), read_error,
+                            _named(after, keys))
+        out.file, out.line = self.locations.get(fx.target_type, ("", 0))
+        if isinstance(old, dict) and isinstance(read_as, dict):
+            out.differences = {name: (out.old[name], out.read_as[name]) for name in old
+                               if name in read_as and old[name] != read_as[name]}
+        if touched:
+            out.transaction, out.result, data_after = touched
+            if not out.result.ok and _DESERIALIZE.search("\n".join([out.result.error or "", *out.result.logs])):
+                out.verdict = "bricked"
+                return out
+            if out.result.ok and data_after != data:
+                rewritten, _ = read_account(fx.target_type, data_after, self.new_types)
+                if isinstance(old, dict) and isinstance(rewritten, dict) and all(
+                        rewritten[name] == old[name] for name in old if name in rewritten):
+                    out.verdict = "migrated"
+                    return out
+        if read_error and not (touched and out.result.ok):
+            out.verdict = "bricked"
+        elif out.differences:
+            out.verdict = "corrupted"
+        return out
+
+
+def _types(data: dict[str, Any], base: Path, idl_key: str, programs: list[Any]) -> dict[str, dict[str, Any]]:
+    if data.get(idl_key):
+        return load_idl(base / data[idl_key])[1]
+    types: dict[str, dict[str, Any]] = {}
+    for program in programs:
+        try:
+            types.update(_ir_types(program))
+        except (EncodeError, ValueError) as e:
+            raise ValueError(f"cannot read the layouts of {program.name} ({e}); give an IDL") from e
+    return types
+
+
+def load_migration(data: dict[str, Any], base: Path | None = None,
+                   make_backend: Callable[[], ExecutionBackend] | None = None) -> MigrationSimulation:
+    """Build a simulation from a config dict (the YAML file of `sim migrate`).
+
+    ``backend`` runs the new build; ``program`` and ``baseline`` are the new
+    and previous sources (``idl`` and ``baseline_idl`` override them).
+    """
+    from extensions.ir import load_programs
+    from extensions.static.state_migration import baseline_checkout, compare_programs
+
+    from .engine import backend_factory
+
+    base = base or Path.cwd()
+    if not data.get("program_id"):
+        raise ValueError("config needs the program_id of the new build")
+    if not data.get("fixtures"):
+        raise ValueError("config needs fixtures")
+    if not data.get("baseline") and not data.get("baseline_idl"):
+        raise ValueError("config needs a baseline (directory or git ref) or a baseline_idl")
+    program_dir = base / data.get("program", ".")
+    programs = [p for p in load_programs(program_dir) if p.framework == "anchor"]
+    new_types = _types(data, base, "idl", programs)
+    old_programs: list[Any] = []
+    if data.get("baseline"):
+        with baseline_checkout(program_dir, str(data["baseline"])) as root:
+            old_programs = [p for p in load_programs(root) if p.framework == "anchor"]
+            old_types = _types(data, base, "baseline_idl", old_programs)
+    else:
+        old_types = _types(data, base, "baseline_idl", [])
+
+    fixtures = []
+    for entry in data["fixtures"]:
+        if "dump" in entry:
+            dump = load_account_dump(base / entry["dump"])
+            entry = {"pubkey": dump["pubkey"], "lamports": dump["lamports"], **entry, "data": dump["data"].hex()}
+        if not entry.get("pubkey") or not entry.get("type"):
+            raise ValueError("every fixture needs a pubkey and an account type")
+        if entry["type"] not in old_types:
+            raise ValueError(f"account type {entry['type']} is not in the baseline")
+        fixture = Fixture(entry["pubkey"], entry["type"], entry.get("new_type", ""), values=entry.get("values"),
+                          data=bytes.fromhex(entry["data"]) if "data" in entry else None,
+                          lamports=int(entry.get("lamports", FIXTURE_LAMPORTS)))
+        if fixture.target_type not in new_types:
+            raise ValueError(f"account type {fixture.target_type} is not in the new version (set new_type:)")
+        fixtures.append(fixture)
+
+    transactions = []
+    for i, entry in enumerate(data.get("transactions") or []):
+        tx = TransactionSpec.from_dict({k: v for k, v in entry.items() if k != "name"})
+        tx.instruction = entry.get("name") or tx.instruction or f"transaction {i}"
+        transactions.append(tx)
+
+    locations = {}
+    for program in programs:
+        for at in program.account_types:
+            rel = Path(program.root).resolve().relative_to(program_dir.resolve()) / at.file
+            locations.setdefault(at.name, (rel.as_posix(), at.line))
+    wanted = {fx.target_type for fx in fixtures}
+    old_by_name = {p.name: p for p in old_programs}
+    layouts = [change.to_dict() for program in programs if program.name in old_by_name
+               for change in compare_programs(old_by_name[program.name], program) if change.account_type in wanted]
+    return MigrationSimulation(make_backend or backend_factory(data.get("backend", {}), base), data["program_id"],
+                               fixtures, old_types, new_types, transactions, ChainState.from_dict(data, base),
+                               locations, layouts)
@@ -0,0 +1,265 @@
+"""
+Protocols agents interact with.
+
+A protocol takes the oracle price each slot, applies agent actions and
+reports metrics; agents only see ``view()``. ``LendingMarket`` is a
+reference single-collateral money market (loan-to-value, liquidation
+threshold / bonus / close factor, an optional oracle-priced swap) for
+tuning parameters before code exists. ``SvmProtocol`` is the base for
+adapters that run the real program on an execution backend: subclasses
+encode actions as instructions and read balances back from accounts.
+"""
+
+from dataclasses import dataclass, field
+from typing import Any
+
+from .backend import ExecutionBackend, Instruction, TxResult
+
+_EPS = 1e-9
+
+
+@dataclass
+class Action:
+    """Something an agent asks the protocol (or the external venue) to do."""
+
+    kind: str
+    agent: str
+    params: dict[str, Any] = field(default_factory=dict)
+
+
+@dataclass
+class ActionResult:
+    """Outcome of an action; ``deltas`` are the agent's balance changes."""
+
+    ok: bool
+    error: str = ""
+    deltas: dict[str, float] = field(default_factory=dict)
+    data: dict[str, Any] = field(default_factory=dict)
+
+
+class ProtocolModel:
+    """Interface the simulation engine drives."""
+
+    name = ""
+
+    def set_price(self, slot: int, price: float) -> None:
+        """Advance to ``slot`` and publish the oracle price."""
+        raise NotImplementedError
+
+    def apply(self, action: Action) -> ActionResult:
+        raise NotImplementedError
+
+    def view(self) -> dict[str, Any]:
+        """State visible to agents: oracle_price, params, positions (id/owner/collateral/debt/health)."""
+        raise NotImplementedError
+
+    def metrics(self, price: float) -> dict[str, float]:
+        """Protocol metrics at the reference price; must include ``tvl`` and ``bad_debt``."""
+        raise NotImplementedError
+
+    def close(self) -> None:
+        """Release resources at the end of a run."""
+
+
+@dataclass
+class Position:
+    id: int
+    owner: str
+    collateral: float
+    debt: float
+
+
+LENDING_DEFAULTS = {
+    "ltv": 0.75,
+    "liquidation_threshold": 0.8,
+    "liquidation_bonus": 0.05,
+    "close_factor": 0.5,
+    # Fee of the oracle-priced collateral swap; None disables it
+    "swap_fee": None,
+    # Interest accrued per slot on outstanding debt
+    "borrow_rate": 0.0,
+}
+
+
+class LendingMarket(ProtocolModel):
+    """Reference money market: one collateral asset, debt in the quote asset."""
+
+    name = "lending"
+
+    def __init__(self, params: dict[str, Any] | None = None, liquidity: float = 1_000_000.0,
+                 inventory: float = 0.0):
+        """Initialize the market.
+
+        Args:
+            params: Overrides of ``LENDING_DEFAULTS``
+            liquidity: Quote asset available to borrow (and pay out swaps)
+            inventory: Collateral the swap can sell
+        """
+        self.params = {**LENDING_DEFAULTS, **(params or {})}
+        unknown = set(self.params) - set(LENDING_DEFAULTS)
+        if unknown:
+            raise ValueError(f"unknown lending parameters: {', '.join(sorted(unknown))}")
+        if not 0 < self.params["ltv"] <= self.params["liquidation_threshold"] <= 1:
+            raise ValueError("lending parameters need 0 < ltv <= liquidation_threshold <= 1")
+        self.liquidity = liquidity
+        self.inventory = inventory
+        self.positions: dict[int, Position] = {}
+        self.bad_debt = 0.0
+        self.oracle_price = 0.0
+        self.slot = 0
+        self._next_id = 0
+
+    def set_price(self, slot: int, price: float) -> None:
+        rate = self.params["borrow_rate"]
+        if rate and slot > self.slot:
+            for pos in self.positions.values():
+                pos.debt *= (1 + rate) ** (slot - self.slot)
+        self.slot = slot
+        self.oracle_price = price
+
+    def health(self, pos: Position, price: float | None = None) -> float:
+        """Liquidation health factor; below 1 the position can be liquidated."""
+        if pos.debt <= 0:
+            return float("inf")
+        price = self.oracle_price if price is None else price
+        return pos.collateral * price * self.params["liquidation_threshold"] / pos.debt
+
+    def apply(self, action: Action) -> ActionResult:
+        handler = getattr(self, f"_{action.kind}", None)
+        if not handler:
+            return ActionResult(False, f"unsupported action {action.kind}")
+        return handler(action.agent, **action.params)
+
+    def _open(self, agent: str, collateral: float, borrow: float | None = None) -> ActionResult:
+        if collateral <= 0:
+            return ActionResult(False, "no collateral")
+        limit = collateral * self.oracle_price * self.params["ltv"]
+        borrow = min(limit, self.liquidity) if borrow is None else borrow
+        if borrow > limit * (1 + _EPS):
+            return ActionResult(False, "borrow exceeds loan-to-value")
+        if borrow > self.liquidity:
+            return ActionResult(False, "insufficient liquidity")
+        self._next_id += 1
+        self.positions[self._next_id] = Position(self._next_id, agent, collateral, borrow)
+        self.liquidity -= borrow
+        return ActionResult(True, deltas={"collateral": -collateral, "quote": borrow}, data={"position": self._next_id})
+
+    def _repay(self, agent: str, position: int, amount: float) -> ActionResult:
+        pos = self.positions.get(position)
+        if not pos:
+            return ActionResult(False, "unknown position")
+        amount = min(amount, pos.debt)
+        pos.debt -= amount
+        self.liquidity += amount
+        return ActionResult(True, deltas={"quote": -amount})
+
+    def _liquidate(self, agent: str, position: int, repay: float) -> ActionResult:
+        pos = self.positions.get(position)
+        if not pos or pos.debt <= 0:
+            return ActionResult(False, "nothing to liquidate")
+        if self.health(pos) >= 1:
+            return ActionResult(False, "position is healthy")
+        insolvent = pos.collateral * self.oracle_price < pos.debt
+        max_repay = pos.debt if insolvent else pos.debt * self.params["close_factor"]
+        repay = min(repay, max_repay)
+        seized = min(pos.collateral, repay * (1 + self.params["liquidation_bonus"]) / self.oracle_price)
+        pos.collateral -= seized
+        pos.debt -= repay
+        self.liquidity += repay
+        if pos.collateral <= _EPS and pos.debt > 0:
+            # Nothing left to seize: the remainder is written off
+            self.bad_debt += pos.debt
+            pos.debt = 0.0
+        if pos.debt <= 0 and pos.collateral <= _EPS:
+            del self.positions[position]
+        return ActionResult(True, deltas={"quote": -repay, "collateral": seized})
+
+    def _swap(self, agent: str, side: str, amount: float) -> ActionResult:
+        fee = self.params["swap_fee"]
+        if fee is None:
+            return ActionResult(False, "swap disabled")
+        if side == "buy":
+            out = amount * (1 - fee) / self.oracle_price
+            if out > self.inventory:
+                return ActionResult(False, "insufficient inventory")
+            self.inventory -= out
+            self.liquidity += amount
+            return ActionResult(True, deltas={"quote": -amount, "collateral": out})
+        out = amount * self.oracle_price * (1 - fee)
+        if out > self.liquidity:
+            return ActionResult(False, "insufficient liquidity")
+        self.liquidity -= out
+        self.inventory += amount
+        return ActionResult(True, deltas={"collateral": -amount, "quote": out})
+
+    def view(self) -> dict[str, Any]:
+        return {
+            "oracle_price": self.oracle_price,
+            "params": dict(self.params),
+            "liquidity": self.liquidity,
+            "inventory": self.inventory,
+            "positions": [
+                {"id": p.id, "owner": p.owner, "collateral": p.collateral, "debt": p.debt, "health": self.health(p)}
+                for p in self.positions.values()
+            ],
+        }
+
+    def metrics(self, price: float) -> dict[str, float]:
+        collateral = sum(p.collateral for p in self.positions.values())
+        debt = sum(p.debt for p in self.positions.values())
+        underwater = sum(max(0.0, p.debt - p.collateral * price) for p in self.positions.values())
+        return {
+            "tvl": self.liquidity + (self.inventory + collateral) * price,
+            "liquidity": self.liquidity,
+            "inventory": self.inventory,
+            "debt": debt,
+            "positions": len(self.positions),
+            "liquidatable": sum(1 for p in self.positions.values() if self.health(p, price) < 1),
+            # Written-off debt plus debt no longer covered by collateral at the reference price
+            "bad_debt": self.bad_debt + underwater,
+        }
+
+
+class SvmProtocol(ProtocolModel):
+    """Base for adapters that execute a deployed program on an execution backend."""
+
+    def __init__(self, backend: ExecutionBackend, **params: Any):
+        self.backend = backend
+        self.params = params
+        self.backend.start()
+        self.setup()
+
+    def setup(self) -> None:
+        """Create keypairs, mints and program state."""
+
+    def set_price(self, slot: int, price: float) -> None:
+        # Time follows the slot, so staleness checks against unix_timestamp see the lag
+        self.backend.warp_to_slot(slot)
+        self.write_oracle(price)
+
+    def write_oracle(self, price: float) -> None:
+        """Write ``price`` into the oracle account the program reads."""
+        raise NotImplementedError
+
+    def encode(self, action: Action) -> list[Instruction] | None:
+        """Instructions implementing ``action``; None if the protocol has no equivalent."""
+        raise NotImplementedError
+
+    def settle(self, action: Action, result: TxResult) -> dict[str, float]:
+        """Balance changes of the acting agent after a successful transaction."""
+        raise NotImplementedError
+
+    def apply(self, action: Action) -> ActionResult:
+        instructions = self.encode(action)
+        if instructions is None:
+            return ActionResult(False, f"unsupported action {action.kind}")
+        result = self.backend.send(instructions, payer=action.agent, signers=(action.agent,))
+        if not result.ok:
+            return ActionResult(False, result.error or "transaction failed", data={"logs": result.logs})
+        return ActionResult(True, deltas=self.settle(action, result), data={"compute_units": result.compute_units})
+
+    def close(self) -> None:
+        self.backend.close()
+
+
+PROTOCOL_MODELS: dict[str, type[ProtocolModel]] = {"lending": LendingMarket}
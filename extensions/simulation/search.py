@@ -0,0 +1,430 @@
+"""
+Coverage-guided exploit search.
+
+Combines the argument boundary and account substitution mutators into one
+search per instruction, steered by the SBF coverage the execution backend
+reports (see ``LiteSvmBackend(trace=True)``). Starting from the legitimate
+transaction, each step picks an input from the corpus, stacks one to three
+mutations onto it (a boundary value, an integer nudged by one, doubled or
+halved, or an account swapped for an attacker lookalike) and runs it on a
+fresh chain state. Inputs that take new edges join the corpus.
+
+Validation branches are located from the rejections: once a rejected
+input's path leaves every edge an accepted input took, the branches it
+takes lead to the rejection, the check that rejected it among them. A
+branch whose other side no input has taken yet is unexplored, and inputs
+that reach one are picked far more often, so the search spends its budget
+getting past the checks it has seen fail. What it reports:
+
+- bypass: an input with a substituted account that the program accepts (a
+  constraint bypass, possibly only for particular argument values)
+- overflow / panic: an input that aborts the transaction
+
+Given a corpus store (see ``corpus.py``), the search first replays the
+inputs stored for the instruction, so coverage carries over between
+sessions, and stores the inputs it kept and found when it is done.
+"""
+
+import random
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import TYPE_CHECKING, Any, Callable
+
+from .backend import ExecutionBackend, TxResult
+from .boundary import _OUTCOME_INFO, _OVERFLOW, _PANIC, InstructionSpec, _describe, _set, classify, resolver
+from .substitution import _KIND_INFO, KINDS, ChainState, Substitution, SubstitutionFuzzer
+
+if TYPE_CHECKING:
+    from .corpus import CorpusStore
+
+SEARCH_VERSION = "1.0.0"
+
+# Picks an unexplored validation branch is worth, against one new edge
+VALIDATION_WEIGHT = 8
+# Mutations stacked onto a parent per child
+MAX_STACK = 3
+DEFAULT_ITERATIONS = 500
+
+_SEVERITY_ORDER = ("critical", "high", "medium", "low")
+
+Edge = tuple[str, int, int]
+Site = tuple[str, int]
+
+
+@dataclass
+class SearchInput:
+    """Argument values and substitutions of one candidate transaction, and the mutations that made it."""
+
+    values: dict[str, Any]
+    substitutions: list[Substitution] = field(default_factory=list)
+    mutations: list[str] = field(default_factory=list)
+
+    @property
+    def label(self) -> str:
+        return " + ".join(self.mutations) or "legitimate"
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"mutations": self.mutations, "values": {k: _describe(v) for k, v in self.values.items()},
+                "substitutions": [s.label for s in self.substitutions]}
+
+
+def outcome_of(result: TxResult, inp: SearchInput) -> str:
+    """``bypass`` for an accepted input with substituted accounts, else the boundary classification."""
+    return "bypass" if result.ok and inp.substitutions else classify(result, "")
+
+
+@dataclass
+class CorpusEntry:
+    """An input kept for taking new edges."""
+
+    input: SearchInput
+    outcome: str
+    new_edges: int
+    # Branch sources on its path
+    sources: set[Site] = field(default_factory=set)
+    picked: int = 0
+    error: str | None = None
+
+    def to_dict(self) -> dict[str, Any]:
+        return {**self.input.to_dict(), "outcome": self.outcome, "new_edges": self.new_edges,
+                "picked": self.picked, "error": self.error}
+
+
+class CoverageMap:
+    """Edges taken so far, and the validation branches rejections revealed."""
+
+    def __init__(self) -> None:
+        self.edges: set[Edge] = set()
+        # Branch source -> targets accepted inputs took from it
+        self.passing: dict[Site, set[int]] = {}
+        # Validation branch -> targets taken from it
+        self.sites: dict[Site, set[int]] = {}
+
+    def add(self, path: list[Edge], rejected: bool) -> int:
+        """Record a path; returns how many of its edges are new."""
+        new = sum(1 for edge in path if edge not in self.edges)
+        self.edges.update(path)
+        for program, a, b in path:
+            if (program, a) in self.sites:
+                self.sites[(program, a)].add(b)
+            if not rejected:
+                self.passing.setdefault((program, a), set()).add(b)
+        if rejected:
+            # Past the first edge no accepted input took, every branch leads to the rejection
+            tail = next((i for i, (p, a, b) in enumerate(path) if b not in self.passing.get((p, a), ())), len(path))
+            for program, a, b in path[tail:]:
+                site = self.sites.setdefault((program, a), set())
+                site.add(b)
+                site.update(self.passing.get((program, a), ()))
+        return new
+
+    def unexplored(self) -> set[Site]:
+        """Validation branches seen with a single side."""
+        return {site for site, targets in self.sites.items() if len(targets) < 2}
+
+    def energy(self, entry: CorpusEntry, unexplored: set[Site]) -> float:
+        bonus = VALIDATION_WEIGHT if entry.sources & unexplored else 0
+        return (1 + entry.new_edges + bonus) / (1 + entry.picked)
+
+
+@dataclass
+class SearchFinding:
+    """An input the search found the program mishandling."""
+
+    instruction: InstructionSpec
+    input: SearchInput
+    kind: str  # "bypass", "overflow" or "panic"
+    result: TxResult
+    # Executions it took to find
+    executions: int = 0
+
+    @property
+    def severity(self) -> str:
+        if self.kind != "bypass":
+            return _OUTCOME_INFO[self.kind][2]
+        return min((_KIND_INFO[s.kind][1] for s in self.input.substitutions), key=_SEVERITY_ORDER.index)
+
+    def _abort(self) -> str:
+        for line in self.result.logs:
+            if _PANIC.search(line) or _OVERFLOW.search(line):
+                return line.strip()
+        return self.result.error or "the transaction aborted"
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        name = self.instruction.name
+        args = [m for m in self.input.mutations if m not in {s.label for s in self.input.substitutions}]
+        when = f" when {', '.join(args)}" if args else ""
+        if self.kind == "bypass":
+            first = min(self.input.substitutions, key=lambda s: _SEVERITY_ORDER.index(_KIND_INFO[s.kind][1]))
+            vulnerability_type, _, accepted = _KIND_INFO[first.kind]
+            category = "account-validation"
+            swapped = ", ".join(f"`{s.slot}` ({s.kind})" for s in self.input.substitutions)
+            title = f"{name} accepts {accepted} as `{first.slot}`{when}"
+            detail = (f"the transaction succeeds ({self.result.compute_units:,} compute units) with {swapped} "
+                      "replaced by attacker lookalikes, so a constraint the program enforces for the legitimate "
+                      "transaction can be bypassed.")
+        else:
+            vulnerability_type, category, _ = _OUTCOME_INFO[self.kind]
+            what = "aborts on an arithmetic overflow" if self.kind == "overflow" else "panics"
+            title = f"{name} {what} for {self.input.label}"
+            detail = f"the transaction aborts instead of failing with a program error ({self._abort()})."
+        return {
+            "title": title,
+            "description": f"After {self.executions:,} executions, a coverage-guided search ran {name} with "
+                           f"{self.input.label}: {detail}",
+            "vulnerability_type": vulnerability_type,
+            "severity": self.severity,
+            "confidence": 0.9,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "coverage-search",
+                "category": category,
+                "source_files": [self.instruction.file] if self.instruction.file else [],
+                "affected_lines": [self.instruction.line] if self.instruction.line else [],
+                "input": self.input.to_dict(),
+                "logs": self.result.logs[-10:],
+                "precision": "verified",
+            },
+        }
+
+
+@dataclass
+class SearchReport:
+    """The search over one instruction; ``error`` says why it did not run."""
+
+    instruction: InstructionSpec
+    executions: int = 0
+    edges: int = 0
+    corpus: list[CorpusEntry] = field(default_factory=list)
+    sites: dict[Site, set[int]] = field(default_factory=dict)
+    findings: list[SearchFinding] = field(default_factory=list)
+    # (executions, edges) each time coverage grew
+    growth: list[tuple[int, int]] = field(default_factory=list)
+    # Stored inputs replayed first, and inputs stored after the search
+    replayed: int = 0
+    saved: int = 0
+    error: str | None = None
+
+    def to_dict(self) -> dict[str, Any]:
+        explored = sum(1 for targets in self.sites.values() if len(targets) > 1)
+        return {
+            "instruction": self.instruction.name,
+            "error": self.error,
+            "executions": self.executions,
+            "replayed": self.replayed,
+            "saved": self.saved,
+            "edges": self.edges,
+            "validation_branches": {"seen": len(self.sites), "explored": explored},
+            "growth": [list(point) for point in self.growth],
+            "corpus": [entry.to_dict() for entry in self.corpus],
+            "findings": [{"kind": f.kind, "executions": f.executions, **f.input.to_dict()} for f in self.findings],
+        }
+
+
+@dataclass
+class SearchResult:
+    reports: list[SearchReport]
+
+    def findings(self) -> list[SearchFinding]:
+        return [finding for report in self.reports for finding in report.findings]
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "tool": "coverage-search",
+            "version": SEARCH_VERSION,
+            "summary": {"instructions": len(self.reports), "executions": sum(r.executions for r in self.reports),
+                        "findings": len(self.findings())},
+            "instructions": [r.to_dict() for r in self.reports],
+        }
+
+
+def _get(values: dict[str, Any], path: str) -> Any:
+    for key in path.split("."):
+        values = values[key]
+    return values
+
+
+class ExploitSearch:
+    """Runs a coverage-guided search per instruction, each execution on a fresh chain state."""
+
+    def __init__(self, make_backend: Callable[[], ExecutionBackend], instructions: list[InstructionSpec],
+                 state: ChainState | None = None, iterations: int = DEFAULT_ITERATIONS, seed: int = 0,
+                 kinds: tuple[str, ...] = KINDS, corpus: "CorpusStore | None" = None):
+        """Initialize the search.
+
+        Args:
+            make_backend: Backend factory (tracing execution), called once
+            instructions: Instructions to search, each from its legitimate transaction
+            state: Keypairs, accounts and setup transactions every execution starts from
+            iterations: Mutated inputs to execute per instruction
+            seed: Seed of the mutation choices
+            kinds: Account substitution kinds to mutate with
+            corpus: Store whose inputs seed the search, and which keeps what it finds
+        """
+        unknown = set(kinds) - set(KINDS)
+        if unknown:
+            raise ValueError(f"unknown substitution kinds {', '.join(sorted(unknown))} "
+                             f"(expected {', '.join(KINDS)})")
+        self.make_backend = make_backend
+        self.instructions = instructions
+        self.state = state or ChainState()
+        self.iterations = iterations
+        self.seed = seed
+        self.kinds = kinds
+        self.corpus = corpus
+
+    def execute(self, backend: ExecutionBackend, ix: InstructionSpec, inp: SearchInput) -> TxResult:
+        """Run ``inp`` on a fresh chain state."""
+        self.state.prepare(backend)
+        fuzzer = SubstitutionFuzzer(self.make_backend, ix.transaction, self.state, self.kinds)
+        replace: dict[int, str] = {}
+        signer = None
+        for sub in inp.substitutions:
+            swapped, swapped_signer = fuzzer._lookalike(backend, sub)
+            replace.update(swapped)
+            signer = signer or swapped_signer
+        tx = ix.transaction
+        tx.data = ix.encode(inp.values, resolver(backend))
+        return tx.send(backend, replace, signer)
+
+    def _mutations(self, ix: InstructionSpec, baseline: dict[str, Any], subs: list[Substitution]) -> list[tuple]:
+        """(kind, target, label, value) of every mutation the search can stack."""
+        free = ix.free_bytes(len(ix.encode(baseline, lambda _: b"\0" * 32)))
+        cases = [case for arg in ix.args for case in ix.boundaries(arg.ty, arg.name, baseline[arg.name], free)]
+        mutations: list[tuple] = [("boundary", path, f"{path}={label}", value) for path, label, value in cases]
+        limits: dict[str, list[int]] = {}
+        for path, label, value in cases:
+            if isinstance(value, int) and not isinstance(value, bool) and "[*]" not in path:
+                limits.setdefault(path, []).append(value)
+        for path, values in limits.items():
+            bounds = (min(min(values), 0), max(values))
+            mutations += [("nudge", path, f"{path}{op}", (op, bounds)) for op in ("+1", "-1", "*2", "/2")]
+        return mutations + [("substitution", sub.slot, sub.label, sub) for sub in subs]
+
+    def _mutate(self, rng: random.Random, parent: SearchInput, mutations: list[tuple]) -> SearchInput:
+        child = SearchInput(parent.values, list(parent.substitutions), list(parent.mutations))
+        for kind, target, label, value in rng.sample(mutations, min(rng.randint(1, MAX_STACK), len(mutations))):
+            if kind == "substitution":
+                if any(s.index == value.index for s in child.substitutions):
+                    continue
+                child.substitutions.append(value)
+            elif kind == "nudge":
+                current = _get(child.values, target)
+                if not isinstance(current, int):
+                    continue
+                op, (low, high) = value
+                nudged = {"+1": current + 1, "-1": current - 1, "*2": current * 2, "/2": current // 2}[op]
+                child.values = _set(child.values, target, max(low, min(high, nudged)))
+            else:
+                child.values = _set(child.values, target, value)
+            child.mutations.append(label)
+        return child
+
+    def _observe(self, report: SearchReport, coverage: CoverageMap, found: set[tuple], inp: SearchInput,
+                 result: TxResult) -> None:
+        """Add an executed input to the corpus when it took new edges, and to the findings."""
+        report.executions += 1
+        outcome = outcome_of(result, inp)
+        path = result.coverage or []
+        new = coverage.add(path, rejected=not result.ok)
+        if new:
+            report.corpus.append(CorpusEntry(inp, outcome, new, {(p, a) for p, a, _ in path}, error=result.error))
+            report.growth.append((report.executions, len(coverage.edges)))
+        key = ((outcome, frozenset(s.label for s in inp.substitutions)) if outcome == "bypass"
+               else (outcome, path[-1] if path else None))
+        if outcome in ("bypass", "overflow", "panic") and key not in found:
+            found.add(key)
+            report.findings.append(SearchFinding(report.instruction, inp, outcome, result, report.executions))
+
+    def search(self, backend: ExecutionBackend, ix: InstructionSpec) -> SearchReport:
+        report = SearchReport(ix)
+        fuzzer = SubstitutionFuzzer(self.make_backend, ix.transaction, self.state, self.kinds)
+        try:
+            legitimate = SearchInput(ix.baseline())
+            self.state.prepare(backend)
+            states = {i: backend.get_account(slot.pubkey) for i, slot in enumerate(ix.transaction.accounts)}
+            mutations = self._mutations(ix, legitimate.values, fuzzer.candidates(states))
+            result = self.execute(backend, ix, legitimate)
+        except (ValueError, KeyError, TypeError) as e:
+            report.error = str(e)
+            return report
+        report.executions = 1
+        if not result.ok:
+            report.error = f"the legitimate transaction fails: {result.error}"
+            return report
+        if not result.coverage:
+            report.error = ("the backend reports no coverage (trace execution with a LiteSVM build that writes "
+                            "SBF register traces)")
+            return report
+
+        coverage = CoverageMap()
+        coverage.add(result.coverage, rejected=False)
+        report.corpus.append(CorpusEntry(legitimate, "accepted", len(result.coverage),
+                                         {(p, a) for p, a, _ in result.coverage}))
+        report.growth.append((1, len(coverage.edges)))
+        found: set[tuple] = set()
+        for record in self.corpus.records(ix.transaction.program_id, ix.name) if self.corpus else []:
+            try:
+                inp = record.to_input(ix)
+                result = self.execute(backend, ix, inp)
+            except (ValueError, KeyError, TypeError):
+                continue
+            self._observe(report, coverage, found, inp, result)
+            report.replayed += 1
+
+        rng = random.Random(self.seed)
+        for _ in range(self.iterations):
+            if not mutations:
+                break
+            unexplored = coverage.unexplored()
+            parent = rng.choices(report.corpus, weights=[coverage.energy(e, unexplored) for e in report.corpus])[0]
+            parent.picked += 1
+            child = self._mutate(rng, parent.input, mutations)
+            try:
+                result = self.execute(backend, ix, child)
+            except (ValueError, KeyError, TypeError):
+                continue
+            self._observe(report, coverage, found, child, result)
+        report.edges, report.sites = len(coverage.edges), coverage.sites
+        if self.corpus:
+            self._save(report)
+        return report
+
+    def _save(self, report: SearchReport) -> None:
+        from .corpus import CorpusRecord
+
+        ix = report.instruction
+        records = [CorpusRecord.from_input(ix, e.input, e.outcome, e.error) for e in report.corpus[1:]]
+        records += [CorpusRecord.from_input(ix, f.input, f.kind, f.result.error) for f in report.findings]
+        report.saved = sum(self.corpus.add(record) for record in records)
+
+    def run(self) -> SearchResult:
+        backend = self.make_backend()
+        try:
+            reports = [self.search(backend, ix) for ix in self.instructions]
+        finally:
+            backend.close()
+        return SearchResult(reports)
+
+
+def load_search(data: dict[str, Any], base: Path | None = None,
+                make_backend: Callable[[], ExecutionBackend] | None = None,
+                corpus: "CorpusStore | None" = None) -> ExploitSearch:
+    """Build a search from a config dict (the YAML file of `sim search`).
+
+    Instructions are given as for `sim boundaries`; ``iterations``,
+    ``seed`` and ``kinds`` tune the search, and the backend traces execution.
+    """
+    from .boundary import load_boundaries
+    from .engine import backend_factory
+
+    base = base or Path.cwd()
+    if make_backend is None and data.get("instructions"):
+        make_backend = backend_factory({**data.get("backend", {}), "trace": True}, base)
+    fuzzer = load_boundaries(data, base, make_backend)
+    return ExploitSearch(fuzzer.make_backend, fuzzer.instructions, state=fuzzer.state,
+                         iterations=int(data.get("iterations", DEFAULT_ITERATIONS)), seed=int(data.get("seed", 0)),
+                         kinds=tuple(data.get("kinds") or KINDS), corpus=corpus)
@@ -0,0 +1,436 @@
+"""
+Account substitution fuzzing.
+
+Replays a legitimate transaction with one account at a time swapped for an
+attacker-controlled lookalike, each mutant on a fresh chain state, and
+reports the substitutions the program accepts. An accepted substitution is
+a validation check the program does not make — the executed counterpart of
+the signer, owner, address and duplicate-account detectors:
+
+- wrong-signer: an attacker keypair signs in place of a signer
+- wrong-owner: a copy of the account owned by an attacker program
+- wrong-mint: a copy of a token account (or a mint) for an attacker mint
+- wrong-pda: a copy of a program account at an address the program did not derive
+- duplicate: another account of the transaction with the same owner
+
+Programs, sysvars and accounts that do not exist before the transaction
+(``init`` targets) are not substituted; a slot with ``substitute: false``
+is skipped, e.g. the depositor of a permissionless deposit.
+"""
+
+import os
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import TYPE_CHECKING, Any, Callable
+
+from extensions.ir.anchor_config import b58encode
+from extensions.monitor.decode import anchor_discriminator, b58decode
+
+from .backend import AccountMeta, BackendError, ExecutionBackend, Instruction, TxResult, load_account_dump
+
+if TYPE_CHECKING:
+    from extensions.ir.model import Instruction as IrInstruction
+    from extensions.ir.model import Program
+
+SUBSTITUTION_VERSION = "1.0.0"
+
+KINDS = ("wrong-signer", "wrong-owner", "wrong-mint", "wrong-pda", "duplicate")
+
+TOKEN_PROGRAMS = {"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"}
+SYSVAR_OWNER = "Sysvar1111111111111111111111111111111111111"
+
+# SPL layouts: a token account starts with its mint; Token-2022 accounts
+# longer than the base layout carry the account type at byte 165
+MINT_LEN = 82
+TOKEN_ACCOUNT_LEN = 165
+
+# Keypairs the lookalikes belong to (named inside the backend)
+ATTACKER = "attacker"
+ATTACKER_PROGRAM = "attacker-program"
+ATTACKER_MINT = "attacker-mint"
+ATTACKER_LAMPORTS = 10_000_000_000
+# Rent exemption of a mint
+MINT_LAMPORTS = 1_461_600
+
+# Vulnerability type, severity and what was accepted, per kind
+_KIND_INFO = {
+    "wrong-signer": ("missing-signer-check", "critical", "an attacker signer"),
+    "wrong-owner": ("missing-owner-check", "high", "a lookalike owned by another program"),
+    "wrong-mint": ("missing-mint-check", "high", "an account for an attacker mint"),
+    "wrong-pda": ("missing-key-check", "high", "a copy at an address it did not derive"),
+    "duplicate": ("duplicate-mutable-accounts", "high", "another account of the transaction"),
+}
+
+
+@dataclass
+class AccountSlot:
+    """An account of the transaction under test."""
+
+    name: str
+    pubkey: str  # Keypair name or base58 key
+    is_signer: bool = False
+    is_writable: bool = False
+    substitute: bool = True
+    # Declaration in the program source, when an instruction was resolved
+    file: str = ""
+    line: int = 0
+
+    def meta(self, pubkey: str | None = None) -> AccountMeta:
+        return AccountMeta(pubkey or self.pubkey, self.is_signer, self.is_writable)
+
+
+@dataclass
+class TransactionSpec:
+    """One instruction with its payer and signers."""
+
+    program_id: str
+    accounts: list[AccountSlot] = field(default_factory=list)
+    data: bytes = b""
+    payer: str = ""
+    signers: list[str] = field(default_factory=list)
+    instruction: str = ""
+
+    def send(self, backend: ExecutionBackend, replace: dict[int, str] | None = None,
+             signer: str | None = None) -> TxResult:
+        """Execute, with slot index -> pubkey ``replace``-d and ``signer`` taking the place of a replaced signer."""
+        replace = replace or {}
+        accounts = [slot.meta(replace.get(i)) for i, slot in enumerate(self.accounts)]
+        swapped = {self.accounts[i].pubkey: signer for i in replace if signer and self.accounts[i].is_signer}
+        payer = swapped.get(self.payer, self.payer)
+        signers = tuple(dict.fromkeys(swapped.get(s, s) for s in self.signers))
+        return backend.send([Instruction(self.program_id, accounts, self.data)], payer=payer, signers=signers)
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any], names: list[str] | None = None) -> "TransactionSpec":
+        """From a config entry; ``data`` is hex, and ``instruction`` + ``args`` builds Anchor instruction data."""
+        if "program_id" not in data:
+            raise ValueError("transaction needs a program_id")
+        names = names or []
+        accounts = []
+        for i, entry in enumerate(data.get("accounts") or []):
+            entry = {"pubkey": entry} if isinstance(entry, str) else entry
+            accounts.append(AccountSlot(
+                name=entry.get("name") or (names[i] if i < len(names) else f"account_{i}"),
+                pubkey=entry["pubkey"],
+                is_signer=bool(entry.get("signer", False)),
+                is_writable=bool(entry.get("writable", False)),
+                substitute=bool(entry.get("substitute", True)),
+            ))
+        instruction = data.get("instruction") or ""
+        if "data" in data:
+            payload = bytes.fromhex(str(data["data"]))
+        else:
+            prefix = anchor_discriminator(instruction) if instruction else b""
+            payload = prefix + bytes.fromhex(str(data.get("args", "")))
+        payer = data.get("payer") or next((a.pubkey for a in accounts if a.is_signer), "")
+        if not payer:
+            raise ValueError("transaction needs a payer (a keypair name)")
+        signers = list(data.get("signers") or [a.pubkey for a in accounts if a.is_signer])
+        return cls(data["program_id"], accounts, payload, payer, signers, instruction)
+
+
+@dataclass
+class Substitution:
+    """One mutant of the transaction and how the program took it."""
+
+    kind: str
+    slot: str
+    index: int
+    # Slot whose account was passed again, for duplicates
+    other: str | None = None
+    accepted: bool = False
+    error: str | None = None
+    logs: list[str] = field(default_factory=list)
+    compute_units: int = 0
+
+    @property
+    def label(self) -> str:
+        return f"{self.kind}:{self.slot}" + (f"={self.other}" if self.other else "")
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"kind": self.kind, "slot": self.slot, "index": self.index, "other": self.other,
+                "accepted": self.accepted, "error": self.error, "logs": self.logs[-10:],
+                "compute_units": self.compute_units}
+
+
+@dataclass
+class SubstitutionFinding:
+    """A lookalike account the program accepted."""
+
+    substitution: Substitution
+    instruction: str
+    slot: AccountSlot
+    other: AccountSlot | None = None
+
+    @property
+    def severity(self) -> str:
+        severity = _KIND_INFO[self.substitution.kind][1]
+        if self.substitution.kind == "duplicate" and not (self.slot.is_writable and self.other
+                                                         and self.other.is_writable):
+            return "medium"
+        return severity
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        sub = self.substitution
+        vulnerability_type, _, accepted = _KIND_INFO[sub.kind]
+        instruction = self.instruction or "The instruction"
+        if sub.kind == "duplicate":
+            title = f"{instruction} accepts `{sub.other}` passed again as `{sub.slot}`"
+        else:
+            title = f"{instruction} accepts {accepted} as `{sub.slot}`"
+        return {
+            "title": title,
+            "description": (
+                f"Replaying a successful {instruction} transaction with `{sub.slot}` replaced by "
+                f"{accepted if sub.kind != 'duplicate' else f'`{sub.other}`'} still succeeded "
+                f"({sub.compute_units:,} compute units): the program does not tie `{sub.slot}` to the "
+                "account the transaction is meant to use."
+            ),
+            "vulnerability_type": vulnerability_type,
+            "severity": self.severity,
+            "confidence": 0.9,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "account-fuzz",
+                "category": "account-validation",
+                "source_files": [self.slot.file] if self.slot.file else [],
+                "affected_lines": [self.slot.line] if self.slot.line else [],
+                "substitution": sub.to_dict(),
+                "precision": "verified",
+            },
+        }
+
+
+@dataclass
+class SubstitutionResult:
+    """The baseline transaction and every mutant of it."""
+
+    transaction: TransactionSpec
+    baseline: TxResult
+    substitutions: list[Substitution]
+
+    def accepted(self) -> list[Substitution]:
+        return [s for s in self.substitutions if s.accepted]
+
+    def findings(self) -> list[SubstitutionFinding]:
+        slots = {slot.name: slot for slot in self.transaction.accounts}
+        return [SubstitutionFinding(s, self.transaction.instruction, slots[s.slot], slots.get(s.other or ""))
+                for s in self.accepted()]
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "tool": "account-fuzz",
+            "version": SUBSTITUTION_VERSION,
+            "instruction": self.transaction.instruction,
+            "program_id": self.transaction.program_id,
+            "accounts": [slot.name for slot in self.transaction.accounts],
+            "baseline": {"ok": self.baseline.ok, "compute_units": self.baseline.compute_units},
+            "summary": {"substitutions": len(self.substitutions), "accepted": len(self.accepted())},
+            "substitutions": [s.to_dict() for s in self.substitutions],
+        }
+
+
+def _token_kind(data: bytes) -> str | None:
+    """"mint" or "account" for SPL Token / Token-2022 account data."""
+    if len(data) == MINT_LEN:
+        return "mint"
+    if len(data) == TOKEN_ACCOUNT_LEN:
+        return "account"
+    if len(data) > TOKEN_ACCOUNT_LEN:
+        return {1: "mint", 2: "account"}.get(data[TOKEN_ACCOUNT_LEN])
+    return None
+
+
+def _mint_data(authority: bytes, decimals: int) -> bytes:
+    """An initialized mint with ``authority`` as mint authority, no supply and no freeze authority."""
+    return (b"\x01\x00\x00\x00" + authority + (0).to_bytes(8, "little") + bytes([decimals, 1])
+            + b"\x00" * 36)
+
+
+@dataclass
+class ChainState:
+    """The state a transaction runs against, rebuilt from scratch for every run."""
+
+    # Keypair name -> lamports to fund it with
+    keypairs: dict[str, int] = field(default_factory=dict)
+    # ``set_account`` arguments
+    accounts: list[dict[str, Any]] = field(default_factory=list)
+    # Transactions run first (e.g. initialize)
+    setup: list[TransactionSpec] = field(default_factory=list)
+
+    def prepare(self, backend: ExecutionBackend) -> None:
+        """Start a fresh chain state with the keypairs, accounts and setup transactions."""
+        backend.start()
+        for name, lamports in self.keypairs.items():
+            backend.keypair(name, lamports)
+        for account in self.accounts:
+            backend.set_account(**account)
+        for i, tx in enumerate(self.setup):
+            result = tx.send(backend)
+            if not result.ok:
+                raise BackendError(f"setup transaction {tx.instruction or i} failed: {result.error}")
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any], base: Path | None = None) -> "ChainState":
+        """From the ``keypairs``, ``accounts`` (hex data or a ``dump``) and ``setup`` entries of a config."""
+        base = base or Path.cwd()
+        accounts = []
+        for entry in data.get("accounts") or []:
+            if "dump" in entry:
+                accounts.append(load_account_dump(base / entry["dump"]))
+                continue
+            accounts.append({"pubkey": entry["pubkey"], "owner": entry["owner"],
+                             "data": bytes.fromhex(entry.get("data", "")), "lamports": int(entry.get("lamports", 0)),
+                             "executable": bool(entry.get("executable"))})
+        return cls(
+            keypairs={name: int(lamports) for name, lamports in (data.get("keypairs") or {}).items()},
+            accounts=accounts,
+            setup=[TransactionSpec.from_dict(tx) for tx in data.get("setup") or []],
+        )
+
+
+class SubstitutionFuzzer:
+    """Runs every applicable substitution of a transaction against a fresh chain state."""
+
+    def __init__(self, make_backend: Callable[[], ExecutionBackend], transaction: TransactionSpec,
+                 state: ChainState | None = None, kinds: tuple[str, ...] = KINDS):
+        """Initialize the fuzzer.
+
+        Args:
+            make_backend: Backend factory, called once; the backend is restarted for every mutant
+            transaction: The legitimate transaction
+            state: Keypairs, accounts and setup transactions the transaction runs against
+            kinds: Substitution kinds to try
+        """
+        unknown = set(kinds) - set(KINDS)
+        if unknown:
+            raise ValueError(f"unknown substitution kinds {', '.join(sorted(unknown))} "
+                             f"(expected {', '.join(KINDS)})")
+        self.make_backend = make_backend
+        self.transaction = transaction
+        self.state = state or ChainState()
+        self.kinds = kinds
+
+    def candidates(self, states: dict[int, dict[str, Any] | None]) -> list[Substitution]:
+        """Substitutions that apply to the accounts as they are before the transaction."""
+        tx = self.transaction
+        subs = []
+        for i, slot in enumerate(tx.accounts):
+            if not slot.substitute:
+                continue
+            if slot.is_signer:
+                subs.append(Substitution("wrong-signer", slot.name, i))
+                continue
+            account = states.get(i)
+            if not account or account["executable"] or account["owner"] == SYSVAR_OWNER:
+                continue
+            if account["data"]:
+                subs.append(Substitution("wrong-owner", slot.name, i))
+            if account["owner"] in TOKEN_PROGRAMS and _token_kind(account["data"]):
+                subs.append(Substitution("wrong-mint", slot.name, i))
+            if account["owner"] == tx.program_id:
+                subs.append(Substitution("wrong-pda", slot.name, i))
+            for j, other in enumerate(tx.accounts):
+                peer = states.get(j)
+                if (j != i and not other.is_signer and other.pubkey != slot.pubkey and peer
+                        and peer["owner"] == account["owner"] and (slot.is_writable or other.is_writable)):
+                    subs.append(Substitution("duplicate", slot.name, i, other=other.name))
+        return [s for s in subs if s.kind in self.kinds]
+
+    def _lookalike(self, backend: ExecutionBackend, sub: Substitution) -> tuple[dict[int, str], str | None]:
+        """Create the substitute account; returns the slot replacement and the replacing signer."""
+        slot = self.transaction.accounts[sub.index]
+        if sub.kind == "wrong-signer":
+            backend.keypair(ATTACKER, ATTACKER_LAMPORTS)
+            return {sub.index: ATTACKER}, ATTACKER
+        if sub.kind == "duplicate":
+            other = next(s for s in self.transaction.accounts if s.name == sub.other)
+            return {sub.index: other.pubkey}, None
+        account = backend.get_account(slot.pubkey)
+        data, owner = account["data"], account["owner"]
+        if sub.kind == "wrong-owner":
+            owner = backend.keypair(ATTACKER_PROGRAM)
+        elif sub.kind == "wrong-mint":
+            kind = _token_kind(data)
+            mint = account if kind == "mint" else backend.get_account(b58encode(data[:32]))
+            decimals = mint["data"][44] if mint and _token_kind(mint["data"]) == "mint" else 0
+            authority = b58decode(backend.keypair(ATTACKER, ATTACKER_LAMPORTS))
+            mint_key = backend.keypair(ATTACKER_MINT)
+            backend.set_account(ATTACKER_MINT, _mint_data(authority, decimals), owner, lamports=MINT_LAMPORTS)
+            if kind == "mint":
+                return {sub.index: ATTACKER_MINT}, None
+            data = b58decode(mint_key) + data[32:]
+        name = f"lookalike-{sub.index}"
+        backend.keypair(name)
+        backend.set_account(name, data, owner, lamports=account["lamports"])
+        return {sub.index: name}, None
+
+    def run(self) -> SubstitutionResult:
+        backend = self.make_backend()
+        try:
+            self.state.prepare(backend)
+            states = {i: backend.get_account(slot.pubkey) for i, slot in enumerate(self.transaction.accounts)}
+            baseline = self.transaction.send(backend)
+            if not baseline.ok:
+                raise BackendError(f"the legitimate transaction fails: {baseline.error}")
+            subs = self.candidates(states)
+            for sub in subs:
+                self.state.prepare(backend)
+                replace, signer = self._lookalike(backend, sub)
+                result = self.transaction.send(backend, replace, signer)
+                sub.accepted, sub.error = result.ok, result.error
+                sub.logs, sub.compute_units = list(result.logs), result.compute_units
+        finally:
+            backend.close()
+        return SubstitutionResult(self.transaction, baseline, subs)
+
+
+def load_substitution(data: dict[str, Any], base: Path | None = None,
+                      make_backend: Callable[[], ExecutionBackend] | None = None) -> SubstitutionFuzzer:
+    """Build a fuzzer from a config dict (the YAML file of `sim substitute`).
+
+    A ``program`` entry (source path) names the transaction's accounts after
+    the instruction's account struct and points findings at its fields.
+    """
+    from .engine import backend_factory
+
+    base = base or Path.cwd()
+    if "transaction" not in data:
+        raise ValueError("config needs a transaction")
+    names, declared = [], {}
+    instruction = data["transaction"].get("instruction")
+    if data.get("program") and instruction:
+        names, declared = _declared_accounts(base / data["program"], instruction)
+    transaction = TransactionSpec.from_dict(data["transaction"], names)
+    for slot in transaction.accounts:
+        slot.file, slot.line = declared.get(slot.name, ("", 0))
+    return SubstitutionFuzzer(
+        make_backend or backend_factory(data.get("backend", {}), base),
+        transaction,
+        state=ChainState.from_dict(data, base),
+        kinds=tuple(data.get("kinds") or KINDS),
+    )
+
+
+def _find_instruction(path: Path, instruction: str) -> tuple[str, "Program", "IrInstruction"]:
+    """``instruction`` (by name or handler) in the sources at ``path``, with its program and root relative to it."""
+    from extensions.ir import load_programs
+
+    for program in load_programs(path):
+        for ix in program.instructions:
+            if instruction in (ix.name, ix.handler):
+                return os.path.relpath(program.root, path), program, ix
+    raise ValueError(f"instruction {instruction} not found in {path}")
+
+
+def _source_path(root: str, file: str) -> str:
+    return file if root == "." or not file else (Path(root) / file).as_posix()
+
+
+def _declared_accounts(path: Path, instruction: str) -> tuple[list[str], dict[str, tuple[str, int]]]:
+    """Account names of ``instruction`` in order, and each one's file and line relative to ``path``."""
+    root, _, ix = _find_instruction(path, instruction)
+    return [a.name for a in ix.accounts], {a.name: (_source_path(root, a.file or ix.file), a.line) for a in ix.accounts}
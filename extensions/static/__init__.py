@@ -6,8 +6,51 @@ to provide static vulnerability detection that feeds into Hound's hypothesis sys
 
 Supported tools:
 - EVM: Slither (Trail of Bits), Aderyn (Cyfrin)
-- Solana: Soteria, cargo-audit
+- Solana: Soteria, cargo-audit, built-in unsafe Rust auditor, SBF stack/heap estimator,
+  account growth analyzer, PDA collision scanner, CPI privilege analyzer, privilege path queries,
+  Anchor.toml checker, third-party integration checker (Pyth, Switchboard, SPL Governance,
+  Wormhole, Jupiter), missing signer detector, missing owner check detector,
+  type cosplay (missing discriminator check) detector, duplicate mutable account
+  detector, arbitrary signed CPI (PDA signature leak) detector, unvalidated CPI target
+  detector, unchecked balance arithmetic detector, account layout changes since a
+  baseline version without a migration, hardcoded program ids checked against known
+  deployments (lookalikes and forks), runtime feature gates the code depends on checked
+  against the target cluster
 - Sui/Aptos: Move Prover, Sui Move Lint
+- All chains: protocol parameter extractor (hardcoded fees, LTV ratios, cooldowns,
+  max supply checked against the knowledge base's sane ranges)
+
+Hit confidence comes from each detector's precision tier (syntactic,
+semantic, dataflow, verified), calibrated on the benchmark corpus.
+
+Each hit carries a versioned rule id; project suppressions and baselines
+are migrated when a release renames, splits or merges rules. The built-in
+detectors' rules also have stable codes (``SOL-001-MISSING-SIGNER``), and
+``Finding`` is the typed view of a hit (code, severity, confidence, source
+span, remediation, PoC template) that serializers share.
+``// baskerville:allow(SOL-003)`` comments and a workspace's
+.baskerville-baseline.json hide accepted hits where they live in the code.
+
+Detectors are also classed by latency (hot, warm, cold) so editor
+integrations can run the sub-10ms single-file rules synchronously and
+schedule the rest in the background.
+
+Pipeline runs report progress events (files parsed, detectors completed,
+findings so far) to an optional callback, for progress displays and the
+server's streaming scan API.
+
+Audit firms' own detectors subclass ``Detector`` and register with
+``@register_detector`` in a separate package (see plugins.py); scans run
+them next to the built-in ones.
+
+A ``ScanCache`` makes runs incremental: parsed programs and detector
+results are kept under the workspace's .baskerville/cache by content hash.
+A ``ProjectConfig`` read from the workspace's baskerville.toml enables and
+disables rules, overrides their severities and ignores paths. A
+``DiffScope`` narrows a scan's hits to the lines changed since a git ref,
+for pull request gating. ``plan_fixes`` turns the hits that have a
+mechanical fix (a Signer authority, a has_one, separate PDA seed prefixes)
+into source edits, rendered as a patch or applied in place.
 """
 
 from .pipeline import StaticAnalysisPipeline
@@ -17,6 +60,36 @@ from .soteria_runner import SoteriaRunner
 from .cargo_audit_runner import CargoAuditRunner
 from .move_prover_runner import MoveProverRunner
 from .sui_move_lint_runner import SuiMoveLintRunner
+from .unsafe_rust import UnsafeRustAuditor
+from .sbf_limits import SbfResourceEstimator
+from .account_growth import AccountGrowthAnalyzer
+from .pda_collisions import PdaCollisionScanner
+from .cpi_privileges import CpiPrivilegeAnalyzer
+from .privilege_paths import PrivilegePathAnalyzer
+from .anchor_toml import AnchorTomlChecker
+from .integrations import IntegrationChecker
+from .missing_signer import MissingSignerDetector
+from .missing_owner import MissingOwnerDetector
+from .type_cosplay import TypeCosplayDetector
+from .duplicate_accounts import DuplicateMutableAccountDetector
+from .arbitrary_cpi import ArbitraryCpiDetector
+from .cpi_targets import UnvalidatedCpiTargetDetector
+from .protocol_params import ProtocolParameterChecker
+from .program_ids import ProgramIdChecker
+from .feature_gates import FeatureGateChecker
+from .unchecked_math import UncheckedArithmeticDetector
+from .state_migration import StateMigrationAnalyzer
+from .latency import BackgroundScheduler, HotPathRunner, latency_of
+from .progress import ProgressEvent, ScanProgress
+from .cache import ScanCache
+from .plugins import Detector, DetectorHit, register_detector
+from .project_config import ConfigError, ProjectConfig
+from .diff_scope import DiffError, DiffScope
+from .autofix import Fix, FixError, apply_fixes, plan_fixes, register_fixer, render_patch
+from .confidence import Calibration, calibrate_hypothesis, confidence_level, load_calibration, parse_confidence
+from .rules import RuleCatalog, load_catalog, rule_code, rule_id
+from .finding import Finding, FindingDiff, Span, diff_findings
+from .suppressions import SuppressionStore
 
 __all__ = [
     "StaticAnalysisPipeline",
@@ -26,4 +99,56 @@ __all__ = [
     "CargoAuditRunner",
     "MoveProverRunner",
     "SuiMoveLintRunner",
+    "UnsafeRustAuditor",
+    "SbfResourceEstimator",
+    "AccountGrowthAnalyzer",
+    "PdaCollisionScanner",
+    "CpiPrivilegeAnalyzer",
+    "PrivilegePathAnalyzer",
+    "AnchorTomlChecker",
+    "IntegrationChecker",
+    "MissingSignerDetector",
+    "MissingOwnerDetector",
+    "TypeCosplayDetector",
+    "DuplicateMutableAccountDetector",
+    "ArbitraryCpiDetector",
+    "UnvalidatedCpiTargetDetector",
+    "ProtocolParameterChecker",
+    "ProgramIdChecker",
+    "FeatureGateChecker",
+    "UncheckedArithmeticDetector",
+    "StateMigrationAnalyzer",
+    "HotPathRunner",
+    "BackgroundScheduler",
+    "latency_of",
+    "ProgressEvent",
+    "ScanProgress",
+    "ScanCache",
+    "Detector",
+    "DetectorHit",
+    "register_detector",
+    "ProjectConfig",
+    "ConfigError",
+    "DiffScope",
+    "DiffError",
+    "plan_fixes",
+    "render_patch",
+    "apply_fixes",
+    "register_fixer",
+    "Fix",
+    "FixError",
+    "Calibration",
+    "calibrate_hypothesis",
+    "confidence_level",
+    "load_calibration",
+    "parse_confidence",
+    "RuleCatalog",
+    "load_catalog",
+    "rule_id",
+    "rule_code",
+    "Finding",
+    "FindingDiff",
+    "Span",
+    "diff_findings",
+    "SuppressionStore",
 ]
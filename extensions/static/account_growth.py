@@ -0,0 +1,365 @@
+"""
+Account data growth and storage-exhaustion analysis.
+
+State accounts holding a `Vec`/`String` that any caller can append to are
+a classic Solana griefing vector:
+- fixed-size accounts (`space = ...`, `#[max_len]`) fill up, after which
+  every instruction that serializes the account fails for every user
+- accounts grown with Anchor's `realloc` can be bloated until loading them
+  exceeds the 32KB heap (Anchor deserializes `Account<T>` onto the heap),
+  bricking the account; if the program pays the rent, the attacker also
+  drains it
+
+For every Anchor instruction that appends to a growable field of a shared
+(not per-signer) state account without a privileged signer, we report the
+growth site, the capacity left to fill and an estimate of what it costs an
+attacker to exhaust it (transaction fees, plus rent when they pay for the
+realloc). The estimate assumes one append per transaction.
+"""
+
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from extensions.ir import load_programs
+from extensions.ir.model import AccountField, AccountType, Instruction, Program
+from extensions.ir.rust_source import strip_comments
+
+from .sbf_limits import HEAP_LIMIT, TypeSizer
+
+ANALYZER_VERSION = "1.0.0"
+
+# Rent-exempt minimum per byte: 3480 lamports/byte-year x 2 years
+RENT_LAMPORTS_PER_BYTE = 6960
+BASE_FEE_LAMPORTS = 5000
+LAMPORTS_PER_SOL = 1_000_000_000
+ANCHOR_DISCRIMINATOR_SIZE = 8
+
+_GROWABLE = {"Vec", "String", "VecDeque", "BTreeMap", "HashMap", "BTreeSet", "HashSet"}
+_GROW_OPS = "push|push_str|push_back|extend|extend_from_slice|insert|append|resize"
+_ALIAS_RE = re.compile(r"\blet\s+(?:mut\s+)?(?P<alias>[A-Za-z_]\w*)\s*=\s*&\s*mut\s+\w+\s*\.\s*accounts\s*\.\s*(?P<acc>\w+)\s*;")
+_CHECK_STMT_RE = re.compile(r"\b(?:if|require|assert|ensure)\w*!?")
+_COMPARE_RE = re.compile(r"<=?|>=?")
+_PRIVILEGE_RE = re.compile(r"^(has_one|address|constraint)\s*=\s*(.+)$", re.DOTALL)
+
+
+@dataclass
+class AccountGrowthFinding:
+    """An attacker-drivable growth site in shared account state."""
+
+    kind: str  # unbounded-realloc, capacity-exhaustion
+    program: str
+    instruction: str
+    file_path: str
+    line: int
+    account: str
+    account_type: str
+    field: str
+    op: str
+    checked: bool = False
+    realloc_payer: str | None = None
+    payer_is_signer: bool = True
+    capacity_bytes: int | None = None
+    element_bytes: int | None = None
+    cost_lamports: int | None = None
+
+    @property
+    def severity(self) -> str:
+        if self.kind == "unbounded-realloc":
+            return "medium" if self.payer_is_signer else "high"
+        return "low" if self.checked else "medium"
+
+    @property
+    def appends_to_exhaust(self) -> int | None:
+        if not self.capacity_bytes or not self.element_bytes:
+            return None
+        return -(-self.capacity_bytes // self.element_bytes)
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        target = f"`{self.account}.{self.field}`"
+        cost = ""
+        if self.cost_lamports is not None:
+            cost = (f" Estimated attacker cost: ~{self.cost_lamports / LAMPORTS_PER_SOL:.4f} SOL "
+                    f"({self.appends_to_exhaust} appends).")
+        if self.kind == "unbounded-realloc":
+            title = f"Unbounded account growth in {self.instruction}"
+            payer = f"`{self.realloc_payer}`" if self.realloc_payer else "the payer"
+            description = (
+                f"`{self.instruction}` appends to {target} (`{self.account_type}`) with `{self.op}` and reallocs the "
+                f"account without a length limit, and any signer can call it. Growing the account past the "
+                f"{HEAP_LIMIT // 1024}KB heap makes every later load of it fail. Rent is paid by {payer}"
+                f"{'' if self.payer_is_signer else ', which the program funds, so the attacker also drains it'}.{cost}"
+            )
+        else:
+            title = f"Storage exhaustion of {self.account_type}.{self.field} via {self.instruction}"
+            limit = "to its length cap" if self.checked else "past its allocated space"
+            description = (
+                f"Any signer can call `{self.instruction}` to append to {target}, a shared `{self.account_type}` "
+                f"account with fixed space. Filling it {limit} makes the instruction fail for every other user "
+                f"(denial of service / griefing).{cost}"
+            )
+        return {
+            "title": title,
+            "description": description,
+            "vulnerability_type": f"storage-{self.kind}",
+            "severity": self.severity,
+            "confidence": 0.6 if self.kind == "unbounded-realloc" or not self.checked else 0.4,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "account-growth",
+                "category": "denial-of-service",
+                "source_files": [self.file_path],
+                "affected_lines": [self.line],
+                "function": self.instruction,
+                "account": self.account,
+                "field": f"{self.account_type}.{self.field}",
+                "capacity_bytes": self.capacity_bytes,
+                "cost_lamports": self.cost_lamports,
+            },
+        }
+
+
+class AccountGrowthAnalyzer:
+    """Finds shared account state that attackers can grow or fill."""
+
+    def __init__(self, heap_limit: int = HEAP_LIMIT):
+        """Initialize the analyzer.
+
+        Args:
+            heap_limit: Account size (bytes) at which realloc'd accounts stop loading
+        """
+        self.heap_limit = heap_limit
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{ANALYZER_VERSION}"
+
+    def run(self, project_path: Path) -> tuple[list[AccountGrowthFinding], dict]:
+        """Analyze every Anchor program under ``project_path``.
+
+        Returns:
+            Tuple of (findings, metadata); metadata["growth_sites"] lists every append,
+            including per-user and privileged ones that are not reported
+        """
+        metadata: dict[str, Any] = {
+            "tool": "account-growth",
+            "version": ANALYZER_VERSION,
+            "success": True,
+            "error": None,
+            "programs": [],
+            "growth_sites": [],
+        }
+        findings: list[AccountGrowthFinding] = []
+        for program in load_programs(Path(project_path)):
+            if program.chain != "solana" or program.framework not in ("anchor", "seahorse"):
+                continue
+            metadata["programs"].append(program.name)
+            sites, program_findings = self.analyze_program(program, _program_sizer(program))
+            metadata["growth_sites"].extend(sites)
+            findings.extend(program_findings)
+        return findings, metadata
+
+    def analyze_program(self, program: Program,
+                        sizer: TypeSizer) -> tuple[list[dict[str, Any]], list[AccountGrowthFinding]]:
+        """Growth sites and findings for one program."""
+        sites: list[dict[str, Any]] = []
+        findings: list[AccountGrowthFinding] = []
+        spaces = _allocated_space(program)
+        for ix in program.instructions:
+            signers = {acc.name for acc in ix.accounts if acc.is_signer}
+            privileged = _privileged_signers(ix, signers)
+            aliases = {m.group("alias"): m.group("acc") for m in _ALIAS_RE.finditer(ix.body)}
+            for acc in ix.accounts:
+                at = program.account_type(_state_type(acc))
+                if at is None:
+                    continue
+                per_user = any(re.search(rf"\b{re.escape(s)}\b", seed) for seed in acc.seeds or [] for s in signers)
+                refs = [rf"\w+\s*\.\s*accounts\s*\.\s*{re.escape(acc.name)}"]
+                refs += [rf"\b{re.escape(alias)}" for alias, target in aliases.items() if target == acc.name]
+                for field in at.fields:
+                    if _base(field.ty) not in _GROWABLE:
+                        continue
+                    op_re = re.compile(rf"(?:{'|'.join(refs)})\s*\.\s*{re.escape(field.name)}\s*\.\s*(?P<op>{_GROW_OPS})\s*\(")
+                    for m in op_re.finditer(ix.body):
+                        line = ix.body_line + ix.body[:m.start()].count("\n")
+                        checked = _length_checked(ix.body[:m.start()], field.name)
+                        sites.append({
+                            "program": program.name, "instruction": ix.name, "file": ix.source_file,
+                            "line": line, "account": acc.name, "field": f"{at.name}.{field.name}",
+                            "op": m.group("op"), "checked": checked, "realloc": _constraint(acc, "realloc") is not None,
+                            "per_user": per_user, "privileged": bool(privileged),
+                        })
+                        if per_user or privileged:
+                            continue
+                        findings.append(self._finding(program, ix, acc, at, field.name, m.group("op"),
+                                                      line, checked, spaces, sizer))
+        return sites, findings
+
+    def _finding(self, program: Program, ix: Instruction, acc: AccountField, at: AccountType,
+                 field: str, op: str, line: int, checked: bool, spaces: dict[str, str],
+                 sizer: TypeSizer) -> AccountGrowthFinding:
+        realloc = _constraint(acc, "realloc")
+        payer = _constraint(acc, "realloc::payer")
+        payer_acc = ix.account(payer) if payer else None
+        field_ty = next(f.ty for f in at.fields if f.name == field)
+        element = _element_bytes(field_ty, sizer)
+        finding = AccountGrowthFinding(
+            kind="unbounded-realloc" if realloc is not None and not checked else "capacity-exhaustion",
+            program=program.name,
+            instruction=ix.name,
+            file_path=ix.source_file,
+            line=line,
+            account=acc.name,
+            account_type=at.name,
+            field=field,
+            op=op,
+            checked=checked,
+            realloc_payer=payer,
+            payer_is_signer=payer_acc is None or payer_acc.is_signer,
+            element_bytes=element,
+        )
+        if finding.kind == "unbounded-realloc":
+            finding.capacity_bytes = max(self.heap_limit - _fixed_bytes(at, sizer), 0)
+            if element:
+                rent = finding.capacity_bytes * RENT_LAMPORTS_PER_BYTE if finding.payer_is_signer else 0
+                finding.cost_lamports = rent + finding.appends_to_exhaust * BASE_FEE_LAMPORTS
+        else:
+            space = _constraint(acc, "space") or spaces.get(at.name)
+            finding.capacity_bytes = _capacity(space, at, field, checked, sizer)
+            if finding.appends_to_exhaust is not None:
+                finding.cost_lamports = finding.appends_to_exhaust * BASE_FEE_LAMPORTS
+        return finding
+
+
+def _program_sizer(program: Program) -> TypeSizer:
+    sizer = TypeSizer()
+    for rel in program.source_files:
+        try:
+            sizer.add_source(strip_comments((Path(program.root) / rel).read_text(errors="replace")))
+        except OSError:
+            continue
+    return sizer
+
+
+def _state_type(acc: AccountField) -> str:
+    """`T` of `Account<'info, T>`, also when boxed."""
+    m = re.search(r"\b(?:Account|InterfaceAccount)\s*<[^<>]*?(\w+)\s*>", acc.ty)
+    return m.group(1) if m else ""
+
+
+def _base(ty: str) -> str:
+    m = re.match(r"(?:[\w:]+::)?(\w+)", ty.strip())
+    return m.group(1) if m else ty
+
+
+def _generic_arg(ty: str) -> str | None:
+    m = re.search(r"<(.*)>\s*$", ty)
+    return m.group(1).split(",")[-1].strip() if m else None
+
+
+def _borsh_bytes(ty: str, sizer: TypeSizer, depth: int = 0) -> int:
+    """Minimum Borsh size: length prefix only for growable types, struct fields summed."""
+    base = _base(ty)
+    if base in _GROWABLE:
+        return 4
+    if base in sizer.structs and depth < 8:
+        return sum(_borsh_bytes(f_ty, sizer, depth + 1) for _, f_ty in sizer.structs[base])
+    return sizer.size(ty)
+
+
+def _element_bytes(ty: str, sizer: TypeSizer) -> int | None:
+    """Bytes one append adds to a growable field (one byte per char for strings)."""
+    if _base(ty) == "String":
+        return 1
+    inner = _generic_arg(ty)
+    return _borsh_bytes(inner, sizer) if inner else None
+
+
+def _fixed_bytes(at: AccountType, sizer: TypeSizer) -> int:
+    return ANCHOR_DISCRIMINATOR_SIZE + sum(_borsh_bytes(f.ty, sizer) for f in at.fields)
+
+
+def _init_space(at: AccountType, sizer: TypeSizer) -> int | None:
+    """`InitSpace` of ``at`` from `#[max_len]`, or None when a growable field has none."""
+    max_len = at.properties.get("max_len", {})
+    total = 0
+    for f in at.fields:
+        if _base(f.ty) not in _GROWABLE:
+            total += _borsh_bytes(f.ty, sizer)
+            continue
+        n = sizer.eval_len(max_len[f.name][0]) if f.name in max_len else None
+        element = _element_bytes(f.ty, sizer)
+        if n is None or element is None:
+            return None
+        total += 4 + n * element
+    return total
+
+
+def _capacity(space: str | None, at: AccountType, field: str, checked: bool, sizer: TypeSizer) -> int | None:
+    """Bytes ``field`` can grow by: the account's unused space, or the field's `#[max_len]` cap.
+
+    A length check usually enforces the `#[max_len]` cap, so checked appends
+    are measured against it; unchecked ones against all free space.
+    """
+    max_len = at.properties.get("max_len", {}).get(field)
+    element = _element_bytes(next(f.ty for f in at.fields if f.name == field), sizer)
+    n = sizer.eval_len(max_len[0]) if max_len else None
+    field_cap = n * element if n is not None and element is not None else None
+    if checked and field_cap is not None:
+        return field_cap
+    if space is not None:
+        init_space = _init_space(at, sizer)
+        expr = re.sub(r"\b\w+\s*::\s*INIT_SPACE\b", str(init_space) if init_space is not None else "?", space)
+        expr = re.sub(r"\b\w+\s*::\s*([A-Z_][A-Z0-9_]*)\b", r"\1", expr)
+        total = sizer.eval_len(expr)
+        if total is not None:
+            return max(total - _fixed_bytes(at, sizer), 0)
+    return field_cap
+
+
+def _allocated_space(program: Program) -> dict[str, str]:
+    """State type → `space = ...` expression of the instruction that creates it."""
+    spaces: dict[str, str] = {}
+    for ix in program.instructions:
+        for acc in ix.accounts:
+            space = _constraint(acc, "space")
+            if space is not None:
+                spaces.setdefault(_state_type(acc), space)
+    return spaces
+
+
+def _constraint(acc: AccountField, key: str) -> str | None:
+    """Value of constraint ``key = value`` on ``acc``."""
+    for c in acc.constraints:
+        name, _, value = c.partition("=")
+        if name.strip() == key and value:
+            return value.strip()
+    return None
+
+
+def _privileged_signers(ix: Instruction, signers: set[str]) -> set[str]:
+    """Signers pinned by `has_one`/`address`/`constraint` (i.e. not just anyone)."""
+    pinned = set()
+    for acc in ix.accounts:
+        for c in acc.constraints:
+            m = _PRIVILEGE_RE.match(c.strip())
+            if not m:
+                continue
+            names = set(re.findall(r"[A-Za-z_]\w*", m.group(2)))
+            if acc.name in signers and m.group(1) in ("address", "constraint"):
+                pinned.add(acc.name)
+            pinned |= names & signers
+    return pinned
+
+
+def _length_checked(before: str, field: str) -> bool:
+    """Whether ``field``'s length is compared against a bound before the append."""
+    for statement in re.split(r"[;{}]", before):
+        if re.search(rf"\.\s*{re.escape(field)}\s*\.\s*len\s*\(\s*\)", statement) \
+                and _COMPARE_RE.search(statement) and _CHECK_STMT_RE.search(statement):
+            return True
+    return False
@@ -0,0 +1,106 @@
+"""
+Anchor.toml consistency checker.
+
+Pipeline wrapper around `extensions.ir.anchor_config`: parses the
+workspace's Anchor.toml and reports program ids that differ across
+clusters or disagree with `declare_id!` and the deploy keypairs, programs
+listed on only one side, and malformed addresses.
+"""
+
+import os
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from extensions.ir import load_programs
+from extensions.ir.anchor_config import AnchorConfigIssue, check_consistency, load_anchor_config
+
+CHECKER_VERSION = "1.0.0"
+
+_TITLES = {
+    "cluster-id-mismatch": "Program `{program}` has different ids across Anchor.toml clusters",
+    "declare-id-mismatch": "`declare_id!` of `{program}` disagrees with Anchor.toml",
+    "keypair-mismatch": "Deploy keypair of `{program}` disagrees with Anchor.toml",
+    "unlisted-program": "Program `{program}` is missing from Anchor.toml",
+    "unknown-program": "Anchor.toml lists unknown program `{program}`",
+    "invalid-address": "Malformed program address for `{program}` in Anchor.toml",
+    "missing-cluster": "Anchor.toml has no programs for the provider cluster",
+}
+
+
+@dataclass
+class AnchorTomlFinding:
+    """An Anchor.toml inconsistency."""
+
+    issue: AnchorConfigIssue
+    # Anchor.toml relative to the scanned project
+    config_file: str = "Anchor.toml"
+
+    @property
+    def kind(self) -> str:
+        return self.issue.kind
+
+    @property
+    def severity(self) -> str:
+        return self.issue.severity
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        return {
+            "title": _TITLES.get(self.kind, "Anchor.toml inconsistency").format(program=self.issue.program),
+            "description": self.issue.message + ".",
+            "vulnerability_type": f"anchor-config-{self.kind}",
+            "severity": self.severity,
+            "confidence": 0.8 if self.kind in ("declare-id-mismatch", "keypair-mismatch") else 0.4,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "anchor-toml",
+                "category": "configuration",
+                "source_files": [self.config_file],
+                "affected_lines": [self.issue.line] if self.issue.line else [],
+                "program": self.issue.program,
+                **self.issue.details,
+            },
+        }
+
+
+class AnchorTomlChecker:
+    """Reports inconsistencies between Anchor.toml and the workspace."""
+
+    def __init__(self, report_cluster_mismatch: bool = True):
+        """Initialize the checker.
+
+        Args:
+            report_cluster_mismatch: Report programs whose id differs across clusters
+        """
+        self.report_cluster_mismatch = report_cluster_mismatch
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{CHECKER_VERSION}"
+
+    def run(self, project_path: Path) -> tuple[list[AnchorTomlFinding], dict]:
+        """Check the Anchor.toml under ``project_path``.
+
+        Returns:
+            Tuple of (findings, metadata); metadata["config"] is the parsed file
+        """
+        project_path = Path(project_path)
+        metadata: dict[str, Any] = {"tool": "anchor-toml", "version": CHECKER_VERSION, "success": True,
+                                    "error": None, "config": None}
+        try:
+            config = load_anchor_config(project_path)
+        except ValueError as e:
+            return [], {**metadata, "success": False, "error": str(e)}
+        if config is None:
+            return [], metadata
+        issues = check_consistency(config, load_programs(config.root))
+        if not self.report_cluster_mismatch:
+            issues = [i for i in issues if i.kind != "cluster-id-mismatch"]
+        base = project_path if project_path.is_dir() else project_path.parent
+        config_file = Path(os.path.relpath(config.path, base.resolve())).as_posix()
+        metadata["config"] = config.to_dict()
+        metadata["issues"] = len(issues)
+        return [AnchorTomlFinding(issue, config_file) for issue in issues], metadata
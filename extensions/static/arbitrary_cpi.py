@@ -0,0 +1,226 @@
+"""
+Arbitrary signed CPI (PDA signature leakage) detector for Solana programs.
+
+`invoke_signed` and `CpiContext::new_with_signer` make the program sign for
+its PDAs. The signature is good for whatever the callee does with it, so it
+is only safe when the program fixes all three inputs: which program runs,
+which of its instructions runs, and which PDA signs. A caller who chooses
+any of them borrows the PDA's authority. They can run their own program,
+which moves the vault's tokens or re-enters with the signer. They can pick
+the instruction, for example `SetAuthority` in place of `Transfer`. Or they
+can choose the signer seeds, and sign as another user's PDA.
+
+Builds on the CPI sites of the `cpi-privileges` analyzer and flags PDA-signed
+CPIs where:
+- the target program is not pinned (no `Program<'info, T>`, address
+  constraint, key check or constant id) (high)
+- the signer seeds take an instruction argument or the key of an account
+  nothing checks, so the caller picks which PDA signs (high)
+- the instruction data is built from caller-supplied bytes (a `Vec<u8>` /
+  `&[u8]` argument or the raw instruction data), so the caller picks what
+  the signed call does (medium)
+
+Each finding links to the `arbitrary_cpi` PoC template. Findings share the
+`arbitrary-cpi` vulnerability type with `cpi-privileges` and `cpi-targets`,
+so the pipeline reports a CPI they all flag once, at the worst severity, with
+the other detectors in `also_reported_by`.
+
+Built in (no external tool), so it is always available.
+"""
+
+import os
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from extensions.ir import load_programs
+from extensions.ir.model import Instruction, Program
+from extensions.ir.rust_source import find_matching, split_top_level
+
+from .cpi_privileges import CpiSite, _resolve, _struct_fields, analyze_instruction
+from .missing_signer import _RAW_WRAPPERS
+from .privilege_paths import _mentions, _referenced
+
+DETECTOR_VERSION = "1.0.0"
+TEMPLATE_ID = "arbitrary_cpi"
+TEMPLATE_FILE = "extensions/knowledge/templates/solana/arbitrary_cpi.rs"
+
+# Finding kind → (severity, short description)
+REASONS = {
+    "caller-program": ("high", "the PDA signs a CPI into a program the caller chooses"),
+    "caller-seeds": ("high", "the caller chooses the signer seeds, and so which PDA signs"),
+    "caller-data": ("medium", "the caller chooses the instruction data of the signed CPI"),
+}
+_SEVERITY_ORDER = {"high": 3, "medium": 2}
+_BYTES_RE = re.compile(r"^(?:Vec\s*<\s*u8\s*>|&\s*(?:'\w+\s+)?\[\s*u8\s*\]|\[\s*u8\s*;[^\]]+\])$")
+_INVOKE_RE = re.compile(r"\binvoke_signed(?:_unchecked)?\s*\(")
+
+
+@dataclass
+class ArbitraryCpiFinding:
+    """A PDA-signed CPI whose program, signer seeds or data the caller controls."""
+
+    site: CpiSite
+    reasons: list[str]
+    path: str
+    # What the caller controls, per reason (account, argument or expression)
+    controlled: dict[str, str]
+    program_id: str | None = None
+
+    @property
+    def kind(self) -> str:
+        return self.reasons[0]
+
+    @property
+    def severity(self) -> str:
+        return max((REASONS[r][0] for r in self.reasons), key=_SEVERITY_ORDER.__getitem__)
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        s = self.site
+        signers = ", ".join(f"`{n}`" for n in s.pda_signers) or "its PDA"
+        parts = []
+        if "caller-program" in self.reasons:
+            parts.append(f"the target program `{self.controlled['caller-program']}` is not pinned to a program id")
+        if "caller-seeds" in self.reasons:
+            parts.append(f"the signer seeds `{s.signer_seeds}` take `{self.controlled['caller-seeds']}` from the caller")
+        if "caller-data" in self.reasons:
+            parts.append(f"the instruction data comes from `{self.controlled['caller-data']}`")
+        context = {"PROGRAM_ID": self.program_id} if self.program_id else {}
+        program_id = self.program_id or "<program id>"
+        description = (
+            f"`{s.program}::{s.instruction}` signs a {s.kind} CPI for {signers}, but {'; '.join(parts)}. The callee "
+            f"can use the PDA signature for anything the PDA may do, so the caller moves or re-delegates what it "
+            f"controls. Pin the program (`Program<'info, T>` or a key check), derive signer seeds from validated "
+            f"accounts only, and build the instruction data in the program. PoC: `kb render {TEMPLATE_ID} "
+            f"-s PROGRAM_ID={program_id} -s VAULT_ACCOUNT=<pubkey>`."
+        )
+        return {
+            "title": f"Arbitrary signed CPI in {s.instruction}: {REASONS[self.kind][1]}",
+            "description": description,
+            "vulnerability_type": "arbitrary-cpi",
+            "severity": self.severity,
+            "confidence": 0.6 if self.severity == "high" else 0.4,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "arbitrary-cpi",
+                "category": "cpi-security",
+                "source_files": [self.path],
+                "affected_lines": [s.line],
+                "program": s.program,
+                "instruction": s.instruction,
+                "reasons": self.reasons,
+                "controlled": self.controlled,
+                "pda_signers": s.pda_signers,
+                "signer_seeds": s.signer_seeds,
+                "target_check": s.target_check,
+                "poc_template": TEMPLATE_ID,
+                "poc_template_file": TEMPLATE_FILE,
+                "poc_context": context,
+                "checklist": ["SOL-CPI-01", "SOL-CPI-02"],
+                "precision": "semantic",
+            },
+        }
+
+
+def _caller_data(ix: Instruction, site: CpiSite) -> str | None:
+    """The caller-supplied bytes the CPI's instruction data is built from, if any."""
+    if site.data_source:
+        return site.data_source
+    if site.kind.startswith("cpi"):
+        return None  # Anchor CPI helpers serialize typed arguments
+    body = ix.body
+    m = _INVOKE_RE.search(body, _offset(ix, site.line))
+    args = split_top_level(body[m.end():find_matching(body, m.end() - 1)]) if m else []
+    if not args:
+        return None
+    ix_expr = _resolve(body, args[0], m.start())
+    data = _struct_fields(ix_expr).get("data")
+    if data is None:
+        builder = re.search(r"Instruction\s*::\s*new_with_bytes\s*\(", ix_expr)
+        parts = split_top_level(ix_expr[builder.end():find_matching(ix_expr, builder.end() - 1)]) if builder else []
+        data = parts[1] if len(parts) > 1 else ""
+    data = _resolve(body, data, m.start())
+    for arg in ix.args:
+        if _BYTES_RE.match(arg.ty.strip()) and _mentions(data, arg.name):
+            return arg.name
+    return "instruction_data" if _mentions(data, "instruction_data") else None
+
+
+def _offset(ix: Instruction, line: int) -> int:
+    """Offset in the handler body of the start of ``line``."""
+    offset = 0
+    for _ in range(line - ix.body_line):
+        offset = ix.body.find("\n", offset) + 1
+    return offset
+
+
+def _caller_seeds(ix: Instruction, site: CpiSite) -> str | None:
+    """The argument or unchecked account key the signer seeds take, if any."""
+    seeds = site.signer_seeds
+    for arg in ix.args:
+        if "bump" not in arg.name and _mentions(seeds, arg.name):
+            return arg.name
+    for acc in ix.accounts:
+        if (acc.wrapper in _RAW_WRAPPERS and not acc.is_signer and acc.seeds is None and not acc.constraints
+                and not _referenced(ix, acc) and re.search(rf"\b{re.escape(acc.name)}\s*\.\s*key\b", seeds)):
+            return acc.name
+    return None
+
+
+def check_instruction(program: Program, ix: Instruction) -> list[ArbitraryCpiFinding]:
+    """PDA-signed CPIs in ``ix`` the caller can steer (paths relative to the program)."""
+    findings = []
+    for site in analyze_instruction(program, ix):
+        if not site.pda_signed:
+            continue
+        controlled = {}
+        if not site.target_validated:
+            controlled["caller-program"] = site.target_account or site.target
+        if (seeds := _caller_seeds(ix, site)) is not None:
+            controlled["caller-seeds"] = seeds
+        if (data := _caller_data(ix, site)) is not None:
+            controlled["caller-data"] = data
+        if controlled:
+            findings.append(ArbitraryCpiFinding(site, list(controlled), site.file, controlled, program.program_id))
+    return findings
+
+
+class ArbitraryCpiDetector:
+    """Flags PDA-signed CPIs whose program, signer seeds or instruction data the caller controls."""
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{DETECTOR_VERSION}"
+
+    def run(self, project_path: Path) -> tuple[list[ArbitraryCpiFinding], dict]:
+        """Check every Solana program under ``project_path``.
+
+        Returns:
+            Tuple of (findings, metadata)
+        """
+        project_path = Path(project_path)
+        findings: list[ArbitraryCpiFinding] = []
+        signed = 0
+        for program in load_programs(project_path):
+            if program.chain != "solana":
+                continue
+            root = Path(os.path.relpath(program.root, project_path))
+            for ix in program.instructions:
+                signed += sum(1 for s in analyze_instruction(program, ix) if s.pda_signed)
+                for finding in check_instruction(program, ix):
+                    if str(root) != ".":
+                        finding.path = (root / finding.path).as_posix()
+                    findings.append(finding)
+        metadata = {
+            "tool": "arbitrary-cpi",
+            "version": DETECTOR_VERSION,
+            "success": True,
+            "error": None,
+            "signed_cpis": signed,
+            "template": TEMPLATE_ID,
+        }
+        return findings, metadata
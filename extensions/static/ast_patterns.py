@@ -47,7 +47,7 @@ class ASTMatch:
             "node_refs": [],
             "evidence": [],
             "properties": {
-                "source_tool": "ast_patterns",
+                "source_tool": "ast-patterns",
                 "source_files": [self.file_path],
                 "affected_lines": [self.line_number],
                 "pattern_type": self.pattern.pattern_type,
@@ -106,7 +106,7 @@ class ASTPatternMatcher:
         3. Return matches with source locations
         """
         metadata = {
-            "tool": "ast_patterns",
+            "tool": "ast-patterns",
             "version": "stub-1.0.0",
             "success": True,
             "patterns_checked": len(self.patterns),
@@ -0,0 +1,377 @@
+"""
+Mechanical fixes for Anchor account constraints.
+
+Some hits have one obviously right fix, an edit to the `#[derive(Accounts)]`
+struct that a reviewer would make by hand. ``plan_fixes`` turns those hits
+into source edits:
+
+- missing-signer: the authority field becomes `Signer<'info>` (unless it is
+  a program the instruction invokes or pins to a fixed id); when only its
+  name makes it the authority, the state accounts the handler writes that
+  store its key also get `has_one = <authority>`, so that the signer must be
+  the stored one
+- pda-collisions: every `seeds = [..]` constraint deriving each colliding
+  account type gets the type's own leading seed (see suggested_seeds)
+
+Edits are made on the original text at the spans the IR parser found, so
+comments, formatting and the rest of the file are left as they were; a new
+constraint follows the layout of the attribute it goes into. A hit whose fix
+is not mechanical (a name-only authority no state field binds, a type the
+span cannot be found for) is returned with the reason instead. Fixes are
+rendered as a unified diff relative to the workspace, or applied in place.
+
+Other detectors (plugins included) add fixes with ``@register_fixer``.
+"""
+
+import difflib
+import os
+import re
+from collections.abc import Callable
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+from extensions.ir import load_programs
+from extensions.ir.model import AccountField, Instruction, Program
+from extensions.ir.pda_map import PdaDerivation, normalize_seed, seed_constants
+from extensions.ir.rust_source import _skip_string, find_matching, iter_structs, strip_comments
+
+from .missing_signer import _FIELD_WRITE_RE, program_account
+from .pda_collisions import suggested_seeds, type_seed
+
+_LIFETIME_RAW_RE = re.compile(r"(?:[\w:]*::)?(?:AccountInfo|UncheckedAccount)\s*<\s*('[A-Za-z_]\w*)\s*>")
+_STATE_TYPE_RE = re.compile(r"\b(?:Account|AccountLoader|InterfaceAccount)\s*<[^<>]*?(\w+)\s*>")
+
+
+class FixError(ValueError):
+    """Edits that overlap or no longer match the file they were planned on."""
+
+
+@dataclass
+class Edit:
+    """Replace ``file``'s text between two offsets (equal for an insertion)."""
+
+    file: str  # Relative to the workspace
+    start: int
+    end: int
+    text: str
+    # Joins this edit's text to that of other edits of the same span carrying it, instead of clashing
+    separator: str | None = None
+
+
+@dataclass
+class Fix:
+    """The edits that fix one hit."""
+
+    hit: str  # Hit id
+    detector: str
+    title: str
+    edits: list[Edit] = field(default_factory=list)
+    # What else the change implies (clients to update, migrations)
+    notes: list[str] = field(default_factory=list)
+
+    @property
+    def files(self) -> list[str]:
+        return list(dict.fromkeys(e.file for e in self.edits))
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"hit": self.hit, "detector": self.detector, "title": self.title, "files": self.files,
+                "edits": len(self.edits), "notes": self.notes}
+
+
+class Unfixable(Exception):
+    """Raised by a fixer for a hit it knows but cannot fix mechanically."""
+
+
+# Detector (source_tool) -> fixer(hit, workspace) returning its Fix
+FIXERS: dict[str, Callable[[dict[str, Any], "_Workspace"], Fix]] = {}
+
+
+def register_fixer(tool: str) -> Callable:
+    """Decorator making a fixer handle the hits of ``tool``."""
+    def decorator(fn: Callable[[dict[str, Any], "_Workspace"], Fix]) -> Callable:
+        FIXERS[tool] = fn
+        return fn
+    return decorator
+
+
+@dataclass
+class _Field:
+    """Spans of one field of an accounts struct, as offsets into its file."""
+
+    start: int  # First character of the field (its attributes included)
+    type_start: int
+    type_end: int
+    # Inside the parentheses of its #[account(..)], when it has one
+    args: tuple[int, int] | None = None
+
+
+class _Workspace:
+    """The workspace's programs and files, loaded once for every fix."""
+
+    def __init__(self, root: Path):
+        self.root = Path(root).resolve()
+        self.programs = [p for p in load_programs(self.root) if p.chain == "solana" and p.framework == "anchor"]
+        self._texts: dict[str, tuple[str, str]] = {}
+
+    def program(self, name: str | None) -> Program | None:
+        return next((p for p in self.programs if p.name == name), None)
+
+    def path(self, program: Program, ix: Instruction, acc: AccountField) -> str:
+        """Workspace-relative file declaring ``acc``."""
+        root = Path(os.path.relpath(program.root, self.root))
+        return (root / (acc.file or ix.file)).as_posix()
+
+    def text(self, rel: str) -> tuple[str, str]:
+        """A file's original text and its comment-stripped twin (same offsets)."""
+        if rel not in self._texts:
+            original = (self.root / rel).read_text()
+            self._texts[rel] = (original, strip_comments(original))
+        return self._texts[rel]
+
+    def field(self, rel: str, acc: AccountField) -> _Field:
+        _, text = self.text(rel)
+        line_start = 0
+        for _ in range(acc.line - 1):
+            line_start = text.find("\n", line_start) + 1
+            if not line_start:
+                raise Unfixable(f"{rel} has no line {acc.line} (changed since the scan?)")
+        m = re.compile(rf"\b{re.escape(acc.name)}\s*:(?!:)").search(text, line_start)
+        if m is None or "\n" in text[line_start:m.start()]:
+            raise Unfixable(f"cannot find field `{acc.name}` at {rel}:{acc.line}")
+        for struct in iter_structs(text):
+            open_brace = text.find("{", struct.start)
+            if open_brace < 0 or not open_brace < m.start() < find_matching(text, open_brace):
+                continue
+            start, end = next((s, e) for s, e in _chunks(text, open_brace + 1, find_matching(text, open_brace))
+                              if s <= m.start() < e)
+            type_start = m.end() + len(text[m.end():end]) - len(text[m.end():end].lstrip())
+            span = _Field(start + len(text[start:end]) - len(text[start:end].lstrip()), type_start,
+                          type_start + len(text[type_start:end].rstrip()))
+            attr = None
+            for attr in re.finditer(r"#\[\s*account\s*\(", text[start:m.start()]):
+                pass
+            if attr is not None:
+                open_paren = start + attr.end() - 1
+                span.args = (open_paren + 1, find_matching(text, open_paren))
+            return span
+        raise Unfixable(f"cannot find the struct declaring `{acc.name}` in {rel}")
+
+
+def _chunks(text: str, start: int, end: int):
+    """(start, end) of each top-level comma-separated part of ``text[start:end]``."""
+    depth = angle = 0
+    chunk = i = start
+    while i < end:
+        ch = text[i]
+        if ch == '"':
+            i = _skip_string(text, i)
+            continue
+        if ch in "([{":
+            depth += 1
+        elif ch in ")]}":
+            depth -= 1
+        elif ch == "<":
+            angle += 1
+        elif ch == ">" and angle > 0 and text[i - 1] not in "=-":
+            angle -= 1
+        elif ch == "," and depth == 0 and angle == 0:
+            yield chunk, i
+            chunk = i + 1
+        i += 1
+    yield chunk, end
+
+
+def _indent(original: str, pos: int) -> str:
+    line = original[original.rfind("\n", 0, pos) + 1:pos]
+    return line[:len(line) - len(line.lstrip())]
+
+
+def add_constraint(ws: _Workspace, rel: str, acc: AccountField, constraint: str) -> Edit:
+    """An edit adding ``constraint`` to ``acc``'s #[account(..)], or giving it one."""
+    original, text = ws.text(rel)
+    span = ws.field(rel, acc)
+    if span.args is None:
+        return Edit(rel, span.start, span.start, f"#[account({constraint})]\n{_indent(original, span.start)}")
+    start, end = span.args
+    content = text[start:end]
+    if not content.strip():
+        # Other constraints added to the same empty attribute join this one
+        return Edit(rel, start, end, constraint, separator=", ")
+    last = start + len(content.rstrip())
+    comma = text[last - 1] == ","
+    if "\n" in content:
+        # On a line of its own after the last one, which keeps any trailing comment
+        indent = _indent(original, last - 1)
+        eol = original.find("\n", last, end)
+        eol = last if eol < 0 else eol
+        return Edit(rel, last, eol, ("" if comma else ",") + original[last:eol] + f"\n{indent}{constraint}"
+                    + ("," if comma else ""))
+    return Edit(rel, last, last, f" {constraint}" if comma else f", {constraint}")
+
+
+def _state_type(acc: AccountField) -> str:
+    m = _STATE_TYPE_RE.search(acc.ty)
+    return m.group(1) if m else ""
+
+
+@register_fixer("missing-signer")
+def fix_missing_signer(hit: dict[str, Any], ws: _Workspace) -> Fix:
+    props = hit.get("properties") or {}
+    program = ws.program(props.get("program"))
+    ix = program.instruction(props.get("instruction") or "") if program else None
+    acc = ix.account(props.get("account") or "") if ix else None
+    if acc is None:
+        raise Unfixable("instruction or account not found in the workspace")
+    if program_account(program, ix, acc):
+        raise Unfixable(f"`{acc.name}` is invoked as a program or checked against a fixed id; a program cannot sign")
+    rel = ws.path(program, ix, acc)
+    _, text = ws.text(rel)
+    span = ws.field(rel, acc)
+    m = _LIFETIME_RAW_RE.fullmatch(text[span.type_start:span.type_end])
+    if m is None:
+        raise Unfixable(f"`{acc.name}` is not a raw AccountInfo/UncheckedAccount")
+    fix = Fix(hit.get("id") or "", "missing-signer", f"Require `{acc.name}` to sign {ix.name}",
+              [Edit(rel, span.type_start, span.type_end, f"Signer<{m.group(1)}>")])
+    if all(g.get("kind") == "name" for g in props.get("gates") or [{"kind": "name"}]):
+        # Named the authority but bound to nothing: any signer would pass, so bind it to the state it guards
+        written = {w.group("acc") for w in _FIELD_WRITE_RE.finditer(ix.body)}
+        bound = []
+        for other in ix.accounts:
+            state = program.account_type(_state_type(other))
+            if other.name not in written or state is None:
+                continue
+            if any(f.name == acc.name and "Pubkey" in f.ty for f in state.fields):
+                fix.edits.append(add_constraint(ws, ws.path(program, ix, other), other, f"has_one = {acc.name}"))
+                bound.append(other.name)
+        if not bound:
+            raise Unfixable(f"no account {ix.name} writes stores `{acc.name}`; bind it to state by hand")
+        fix.title += f" and bind it to {', '.join(f'`{b}`' for b in bound)}"
+    return fix
+
+
+@register_fixer("pda-collisions")
+def fix_pda_collision(hit: dict[str, Any], ws: _Workspace) -> Fix:
+    props = hit.get("properties") or {}
+    fixed = props.get("suggested_fix")
+    schemas = dict(zip(props.get("account_types") or [], props.get("schemas") or []))
+    if not isinstance(fixed, dict) or not fixed:
+        raise Unfixable("no seed prefix separates these derivations")
+    fix = Fix(hit.get("id") or "", "pda-collisions",
+              "Give " + " and ".join(f"`{t}`" for t in fixed) + " their own seed prefixes")
+    for program in ws.programs:
+        constants = seed_constants(program)
+        for ix in program.instructions:
+            for acc in ix.accounts:
+                account_type = _state_type(acc)
+                if acc.seeds is None or account_type not in fixed:
+                    continue
+                derivation = PdaDerivation(program.name, program.name, account_type, ix.name, acc.file or ix.file,
+                                           acc.line, [normalize_seed(s, ix, program, constants) for s in acc.seeds])
+                if derivation.schema != schemas.get(account_type):
+                    continue
+                rel = ws.path(program, ix, acc)
+                _, text = ws.text(rel)
+                span = ws.field(rel, acc)
+                seeds = re.search(r"\bseeds\s*=\s*\[", text[span.args[0]:span.args[1]]) if span.args else None
+                if seeds is None:
+                    continue
+                start = span.args[0] + seeds.start()
+                end = find_matching(text, span.args[0] + seeds.end() - 1) + 1
+                edit = Edit(rel, start, end, suggested_seeds(derivation))
+                if edit not in fix.edits:
+                    fix.edits.append(edit)
+    if not fix.edits:
+        raise Unfixable("no seeds constraint of these types found")
+    prefixes = ", ".join(f'b"{type_seed(t)}"' for t in fixed)
+    fix.notes.append(f"Addresses change: update find_program_address calls and clients to the new prefixes "
+                     f"({prefixes}), and migrate accounts already created under the old seeds")
+    return fix
+
+
+def plan_fixes(hits: list[dict[str, Any]], workspace: Path) -> tuple[list[Fix], list[dict[str, Any]]]:
+    """Fixes for the hits that have a mechanical one.
+
+    Returns:
+        Tuple of (fixes, skipped): skipped lists each fixable detector's hit
+        that could not be fixed, with the reason
+    """
+    ws = _Workspace(workspace)
+    fixes, skipped = [], []
+    for hit in hits:
+        tool = (hit.get("properties") or {}).get("source_tool")
+        if tool not in FIXERS:
+            continue
+        try:
+            fixes.append(FIXERS[tool](hit, ws))
+        except (Unfixable, OSError) as e:
+            skipped.append({"hit": hit.get("id"), "title": hit.get("title"), "reason": str(e)})
+    return fixes, skipped
+
+
+def _rewrite(original: str, edits: list[Edit]) -> str:
+    """``original`` with ``edits`` made; identical edits count once."""
+    ordered = sorted({(e.start, e.end, i, e.text) for i, e in enumerate(_joined(_unique(edits)))})
+    for (start, end, _, _), (next_start, _, _, _) in zip(ordered, ordered[1:]):
+        if next_start < end:
+            raise FixError(f"overlapping edits at offset {next_start}")
+    for start, end, _, text in reversed(ordered):
+        original = original[:start] + text + original[end:]
+    return original
+
+
+def _unique(edits: list[Edit]) -> list[Edit]:
+    seen, unique = set(), []
+    for e in edits:
+        key = (e.file, e.start, e.end, e.text)
+        if key not in seen:
+            seen.add(key)
+            unique.append(e)
+    return unique
+
+
+def _joined(edits: list[Edit]) -> list[Edit]:
+    """``edits`` with those of one span that carry the same separator merged into one."""
+    joined: list[Edit] = []
+    for e in edits:
+        same = next((j for j in joined if e.separator is not None and (j.file, j.start, j.end, j.separator)
+                     == (e.file, e.start, e.end, e.separator)), None)
+        if same is None:
+            joined.append(Edit(e.file, e.start, e.end, e.text, e.separator))
+        else:
+            same.text += e.separator + e.text
+    return joined
+
+
+def _rewritten(fixes: list[Fix], workspace: Path) -> dict[str, tuple[str, str]]:
+    """Each touched file's (before, after) text."""
+    root = Path(workspace).resolve()
+    by_file: dict[str, list[Edit]] = {}
+    for fix in fixes:
+        for edit in fix.edits:
+            by_file.setdefault(edit.file, []).append(edit)
+    result = {}
+    for rel, edits in sorted(by_file.items()):
+        before = (root / rel).read_text()
+        result[rel] = (before, _rewrite(before, edits))
+    return result
+
+
+def render_patch(fixes: list[Fix], workspace: Path) -> str:
+    """The fixes as one unified diff, paths relative to the workspace (``patch -p1`` inside it)."""
+    return "".join(
+        "".join(difflib.unified_diff(before.splitlines(keepends=True), after.splitlines(keepends=True),
+                                     f"a/{rel}", f"b/{rel}"))
+        for rel, (before, after) in _rewritten(fixes, workspace).items()
+    )
+
+
+def apply_fixes(fixes: list[Fix], workspace: Path) -> list[str]:
+    """Write the fixes in place; returns the workspace-relative files changed."""
+    root = Path(workspace).resolve()
+    changed = []
+    for rel, (before, after) in _rewritten(fixes, workspace).items():
+        if after != before:
+            (root / rel).write_text(after)
+            changed.append(rel)
+    return changed
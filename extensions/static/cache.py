@@ -0,0 +1,138 @@
+"""
+Incremental scan cache.
+
+A workspace's `.baskerville/cache` keeps what scans computed, keyed by
+content hashes, so scanning again after editing one file only redoes the
+work that file affects:
+
+- ``programs/``: the lowered program of each crate (see
+  extensions/ir/cache.py); detectors reuse every crate but the edited one
+  instead of parsing the workspace again
+- ``results/``: each detector's findings and run metadata, keyed by the
+  detector and its version, its config, the analysis code and the content
+  hash of every file in the scanned tree; an unchanged tree replays every
+  detector without running it
+- ``files.json``: the content hash of each file with its size and mtime,
+  so unchanged files are not read again to be hashed
+
+Detectors configured with another tree (``state-migration``'s baseline)
+are not cached, nor runs that failed. Entries not used for
+``MAX_AGE_DAYS`` are dropped.
+"""
+
+import copy
+import hashlib
+import json
+import os
+import time
+from dataclasses import dataclass
+from functools import lru_cache
+from pathlib import Path
+from typing import Any
+
+from extensions.ir.cache import FileHashes, ProgramCache
+from extensions.ir.workspace import is_skipped
+
+CACHE_DIR = ".baskerville/cache"
+MAX_AGE_DAYS = 30
+
+# Config keys that point outside the scanned tree, whose content the key cannot cover
+_EXTERNAL_INPUTS = ("baseline",)
+
+
+@dataclass
+class CachedFinding:
+    """A finding replayed from the cache, as the hypothesis its detector made of it."""
+
+    hypothesis: dict[str, Any]
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        return copy.deepcopy(self.hypothesis)
+
+
+@lru_cache(maxsize=1)
+def code_fingerprint() -> str:
+    """Hash of the analysis code and the knowledge it reads, so results of other versions are not replayed."""
+    extensions = Path(__file__).resolve().parent.parent
+    h = hashlib.sha256()
+    for package in ("ir", "static", "knowledge"):
+        for path in sorted((extensions / package).rglob("*")):
+            if path.is_file() and path.suffix in (".py", ".yaml", ".yml", ".json", ".toml"):
+                h.update(f"{path.relative_to(extensions)}|".encode() + hashlib.sha256(path.read_bytes()).digest())
+    return h.hexdigest()
+
+
+class ScanCache:
+    """Parsed programs and detector results of one workspace, under its `.baskerville/cache`."""
+
+    def __init__(self, root: Path, cache_dir: Path | None = None):
+        """Initialize the cache.
+
+        Args:
+            root: Workspace root (a directory; a file's directory is used for a file)
+            cache_dir: Where entries are kept (default: ``root``/.baskerville/cache)
+        """
+        root = Path(root).resolve()
+        self.root = root if root.is_dir() else root.parent
+        self.dir = Path(cache_dir) if cache_dir else self.root / CACHE_DIR
+        self.hashes = FileHashes(self.dir / "files.json")
+        self.programs = ProgramCache(self.dir / "programs", self.hashes)
+
+    def fingerprint(self, project_path: Path) -> str:
+        """Content hash of every file under ``project_path`` (build output and VCS data aside)."""
+        path = Path(project_path).resolve()
+        if path.is_file():
+            files = [path]
+        else:
+            files = sorted(p for p in path.rglob("*") if p.is_file() and not is_skipped(p.relative_to(path)))
+        return self.hashes.digest(path, files, "tree")
+
+    @staticmethod
+    def cacheable(config: dict[str, Any] | None) -> bool:
+        return not any((config or {}).get(key) for key in _EXTERNAL_INPUTS)
+
+    def key(self, tool: str, version: str, config: dict[str, Any] | None, fingerprint: str) -> str:
+        raw = json.dumps([tool, version, config or {}, code_fingerprint(), fingerprint],
+                         sort_keys=True, default=str)
+        return hashlib.sha256(raw.encode()).hexdigest()
+
+    def get(self, key: str) -> tuple[list[CachedFinding], dict[str, Any]] | None:
+        """Findings and metadata stored under ``key``, or None."""
+        path = self.dir / "results" / f"{key}.json"
+        try:
+            entry = json.loads(path.read_text())
+            os.utime(path)
+        except (OSError, ValueError):
+            return None
+        return [CachedFinding(h) for h in entry["findings"]], {**entry["metadata"], "cached": True}
+
+    def put(self, key: str, findings: list, metadata: dict[str, Any]) -> None:
+        """Store a detector's run; failed runs and results that do not serialize are left out."""
+        if metadata.get("success") is False or metadata.get("error"):
+            return
+        try:
+            text = json.dumps({"findings": [f.to_hypothesis() for f in findings], "metadata": metadata})
+        except (TypeError, ValueError):
+            return
+        path = self.dir / "results" / f"{key}.json"
+        path.parent.mkdir(parents=True, exist_ok=True)
+        tmp = path.with_suffix(".tmp")
+        tmp.write_text(text)
+        tmp.replace(path)
+
+    def save(self) -> None:
+        """Write the file hashes and drop entries unused for ``MAX_AGE_DAYS``."""
+        self.dir.mkdir(parents=True, exist_ok=True)
+        ignore = self.dir / ".gitignore"
+        if not ignore.exists():
+            # Like .pytest_cache: never committed
+            ignore.write_text("# Created by baskerville scan\n*\n")
+        self.hashes.save()
+        cutoff = time.time() - MAX_AGE_DAYS * 86400
+        for sub in ("programs", "results"):
+            for path in (self.dir / sub).glob("*") if (self.dir / sub).is_dir() else ():
+                try:
+                    if path.stat().st_mtime < cutoff:
+                        path.unlink()
+                except OSError:
+                    continue
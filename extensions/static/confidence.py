@@ -0,0 +1,247 @@
+"""
+Finding confidence from analysis precision.
+
+Each detector hit is placed in a precision tier by how its result was
+established:
+
+- syntactic: a pattern or lint match on the source text
+- semantic: resolved structure (account types and constraints, seeds,
+  manifests, lockfiles)
+- dataflow: a path from caller-controlled input, or from anyone, to a sink
+- verified: observed in execution (simulation, stress runs) or a prover
+  counterexample
+
+A hit's confidence is its detector's precision at that tier on the labelled
+benchmark corpus, shrunk towards the tier's prior so a detector measured on
+three hits is not trusted as if it had thirty. `calibrate` re-measures the
+corpus; the result ships as ``confidence_calibration.yaml``. Levels
+(high/medium/low) use the bands the hypothesis listing always has.
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+import yaml
+
+TIERS = ("syntactic", "semantic", "dataflow", "verified")
+TIER_PRIORS = {"syntactic": 0.35, "semantic": 0.55, "dataflow": 0.7, "verified": 0.9}
+# Pseudo-hits the tier prior counts for against measured ones
+PRIOR_WEIGHT = 4
+LEVELS = {"high": 0.8, "medium": 0.5, "low": 0.0}
+
+# Tier of a tool's hits when the hit does not say (``properties.precision``)
+DETECTOR_PRECISION = {
+    "slither": "dataflow",
+    "aderyn": "syntactic",
+    "soteria": "dataflow",
+    "cargo-audit": "semantic",
+    "unsafe-rust": "syntactic",
+    "sbf-limits": "semantic",
+    "account-growth": "semantic",
+    "pda-collisions": "semantic",
+    "cpi-privileges": "dataflow",
+    "privilege-paths": "dataflow",
+    "anchor-toml": "semantic",
+    "integrations": "syntactic",
+    "missing-signer": "semantic",
+    "missing-owner": "syntactic",
+    "type-cosplay": "syntactic",
+    "duplicate-mutable": "syntactic",
+    "arbitrary-cpi": "semantic",
+    "cpi-targets": "semantic",
+    "unchecked-math": "syntactic",
+    "state-migration": "semantic",
+    "program-ids": "syntactic",
+    "feature-gates": "syntactic",
+    "protocol-params": "syntactic",
+    "move-prover": "verified",
+    "sui-move-lint": "syntactic",
+    "simulation": "verified",
+    "math-stress": "verified",
+    "account-fuzz": "verified",
+    "arg-fuzz": "verified",
+    "migration-sim": "verified",
+}
+
+CALIBRATION_FILE = Path(__file__).parent / "confidence_calibration.yaml"
+CORPUS_FILE = Path(__file__).resolve().parents[2] / "tests" / "fixtures" / "benchmark" / "corpus.yaml"
+
+
+def confidence_level(value: float | None) -> str:
+    """high, medium or low for a 0..1 confidence."""
+    value = value or 0.0
+    return next(name for name, floor in LEVELS.items() if value >= floor)
+
+
+def parse_confidence(text: str | float | None) -> float | None:
+    """Threshold from "high"/"medium"/"low", "0.7" or "70%"."""
+    if text is None or text == "":
+        return None
+    if isinstance(text, (int, float)):
+        value = float(text)
+    else:
+        raw = str(text).strip().lower()
+        if raw in LEVELS:
+            return LEVELS[raw]
+        try:
+            value = float(raw[:-1]) / 100 if raw.endswith("%") else float(raw)
+        except ValueError as e:
+            raise ValueError(f"invalid confidence: {text} (use high, medium, low or 0-1)") from e
+    if not 0.0 <= value <= 1.0:
+        raise ValueError(f"invalid confidence: {text} (use high, medium, low or 0-1)")
+    return value
+
+
+def meets_confidence(hypothesis: dict[str, Any], minimum: float | None) -> bool:
+    if minimum is None:
+        return True
+    try:
+        return float(hypothesis.get("confidence") or 0.0) >= minimum
+    except (TypeError, ValueError):
+        return False
+
+
+def source_tool(hypothesis: dict[str, Any], tool: str | None = None) -> str | None:
+    """The detector behind a hypothesis (``static_<tool>_<hash>`` ids carry it too)."""
+    props = hypothesis.get("properties") or {}
+    if tool or props.get("source_tool"):
+        return tool or props["source_tool"]
+    match = re.match(r"static_(.+)_[0-9a-f]{8}$", str(hypothesis.get("id", "")))
+    return match.group(1) if match else None
+
+
+def precision_of(hypothesis: dict[str, Any], tool: str | None = None) -> str | None:
+    """Precision tier of a hit, or None for hypotheses no detector produced."""
+    declared = (hypothesis.get("properties") or {}).get("precision")
+    if declared in TIERS:
+        return declared
+    return DETECTOR_PRECISION.get(source_tool(hypothesis, tool) or "")
+
+
+@dataclass
+class Calibration:
+    """Per-detector, per-tier hit counts from the benchmark corpus."""
+
+    priors: dict[str, float] = field(default_factory=lambda: dict(TIER_PRIORS))
+    # tool -> tier -> {"hits": n, "true_positives": tp}
+    detectors: dict[str, dict[str, dict[str, int]]] = field(default_factory=dict)
+    corpus: str | None = None
+
+    def counts(self, tool: str, tier: str) -> dict[str, int]:
+        return self.detectors.get(tool, {}).get(tier, {"hits": 0, "true_positives": 0})
+
+    def confidence(self, tool: str | None, tier: str) -> float:
+        counts = self.counts(tool or "", tier)
+        prior = self.priors.get(tier, TIER_PRIORS[tier])
+        return round((counts["true_positives"] + PRIOR_WEIGHT * prior) / (counts["hits"] + PRIOR_WEIGHT), 2)
+
+    def add(self, tool: str, tier: str, true_positive: bool):
+        counts = self.detectors.setdefault(tool, {}).setdefault(tier, {"hits": 0, "true_positives": 0})
+        counts["hits"] += 1
+        counts["true_positives"] += int(true_positive)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "version": 1,
+            "corpus": self.corpus,
+            "priors": self.priors,
+            "detectors": {tool: {tier: dict(c) for tier, c in sorted(tiers.items())}
+                          for tool, tiers in sorted(self.detectors.items())},
+        }
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "Calibration":
+        priors = {**TIER_PRIORS, **{k: float(v) for k, v in (data.get("priors") or {}).items() if k in TIERS}}
+        detectors = {
+            tool: {tier: {"hits": int(c.get("hits", 0)), "true_positives": int(c.get("true_positives", 0))}
+                   for tier, c in (tiers or {}).items() if tier in TIERS}
+            for tool, tiers in (data.get("detectors") or {}).items()
+        }
+        return cls(priors=priors, detectors=detectors, corpus=data.get("corpus"))
+
+
+def load_calibration(path: Path | None = None) -> Calibration:
+    """Calibration from ``path`` (default: the shipped file); tier priors alone if it is missing."""
+    path = Path(path) if path else CALIBRATION_FILE
+    try:
+        data = yaml.safe_load(path.read_text()) or {}
+    except (OSError, yaml.YAMLError):
+        return Calibration()
+    return Calibration.from_dict(data) if isinstance(data, dict) else Calibration()
+
+
+def calibrate_hypothesis(hypothesis: dict[str, Any], tool: str | None = None,
+                         calibration: Calibration | None = None) -> dict[str, Any]:
+    """Set a detector hit's confidence from its precision tier (in place; returns it).
+
+    The detector's own estimate is kept as ``properties.detector_confidence``.
+    Hypotheses no detector produced are returned unchanged.
+    """
+    tier = precision_of(hypothesis, tool)
+    if tier is None:
+        return hypothesis
+    calibration = calibration or load_calibration()
+    props = hypothesis.setdefault("properties", {})
+    props.setdefault("detector_confidence", hypothesis.get("confidence"))
+    props["precision"] = tier
+    props["calibration"] = calibration.counts(source_tool(hypothesis, tool) or "", tier)
+    hypothesis["confidence"] = calibration.confidence(source_tool(hypothesis, tool), tier)
+    return hypothesis
+
+
+def _labelled(hit: dict[str, Any], tool: str, expected: list[dict[str, Any]]) -> bool:
+    props = hit.get("properties") or {}
+    files = props.get("source_files") or []
+    lines = props.get("affected_lines") or []
+    return any(
+        e.get("tool") == tool and e.get("type") == hit.get("vulnerability_type")
+        and (e.get("file") is None or e["file"] in files) and (e.get("line") is None or e["line"] in lines)
+        for e in expected
+    )
+
+
+def measure(corpus: Path | None = None, tools: set[str] | None = None) -> tuple[Calibration, list[dict[str, Any]]]:
+    """Run the detectors over a labelled corpus and count hits per tool and tier.
+
+    Every hit matching an ``expected`` entry of its case is a true positive,
+    any other hit a false positive. Only ``tools`` run when given; tools that
+    are not installed are skipped.
+
+    Returns:
+        The calibration and one row per hit (case, tool, type, tier, true_positive)
+    """
+    from .pipeline import _CHAIN_RUNNERS
+
+    corpus = Path(corpus) if corpus else CORPUS_FILE
+    data = yaml.safe_load(corpus.read_text()) or {}
+    calibration = Calibration(corpus=data.get("name") or corpus.name)
+    rows = []
+    for case in data.get("cases") or []:
+        path = (corpus.parent / case["path"]).resolve()
+        expected = case.get("expected") or []
+        for tool, runner_class, _ in _CHAIN_RUNNERS.get(case.get("chain", "solana"), []):
+            if tools is not None and tool not in tools:
+                continue
+            runner = runner_class()
+            if not runner.is_available()[0]:
+                continue
+            findings, _metadata = runner.run(path)
+            for finding in findings:
+                hit = finding.to_hypothesis()
+                tier = precision_of(hit, tool)
+                if tier is None:
+                    continue
+                true_positive = _labelled(hit, tool, expected)
+                calibration.add(tool, tier, true_positive)
+                rows.append({"case": case["path"], "tool": tool, "type": hit.get("vulnerability_type"),
+                             "tier": tier, "true_positive": true_positive})
+    return calibration, rows
+
+
+def write_calibration(calibration: Calibration, path: Path | None = None) -> Path:
+    path = Path(path) if path else CALIBRATION_FILE
+    header = "# Detector precision on the benchmark corpus. Regenerate: ./baskerville.py calibrate -o <this file>\n"
+    path.write_text(header + yaml.safe_dump(calibration.to_dict(), sort_keys=False))
+    return path
@@ -0,0 +1,430 @@
+"""
+Signer-privilege propagation across CPI.
+
+Solana passes the caller's account privileges into every cross-program
+invocation: an account that is a signer or writable in the outer
+transaction stays so in the callee, and `invoke_signed` adds the
+signatures of the program's own PDAs. Whatever program receives the call
+can use those privileges for anything, including further CPIs, so a PDA
+signature is only as safe as the program it is handed to.
+
+For every CPI in an instruction (Anchor `CpiContext`, `invoke`,
+`invoke_signed`, and native programs alike) this records the target
+program and how it is validated, which accounts are forwarded with signer
+or writable privilege, which of them are PDA-signed, and whether the
+account list or instruction data comes from the caller. It flags:
+- a PDA signature extended to a program the caller can choose (high)
+- a PDA-signed CPI over caller-supplied accounts (`remaining_accounts`)
+  or caller-supplied instruction data (medium)
+- a transaction signer forwarded to a program the caller can choose (low)
+
+This is the privilege-escalation half of the `cpi_reentrancy` template.
+
+Built in (no external tool), so it is always available.
+"""
+
+import os
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+from extensions.ir import load_programs
+from extensions.ir.model import AccountField, Instruction, Program
+from extensions.ir.rust_source import find_matching, split_top_level, statement_end
+
+ANALYZER_VERSION = "1.0.0"
+
+_CPI_RE = re.compile(
+    r"\b(?P<callee>invoke_signed_unchecked|invoke_unchecked|invoke_signed|invoke"
+    r"|CpiContext\s*::\s*new_with_signer|CpiContext\s*::\s*new)\s*\("
+)
+# Program ids fixed at compile time: `spl_token::ID`, `token::id()`, `crate::ID`, `PLUGIN_ID`
+_CONSTANT_ID_RE = re.compile(r"^(?:[\w:]*::)?(?:[A-Z][A-Z0-9_]*|id\(\))$")
+# Account names a native program's PDA signer usually goes by
+_PDA_NAME_RE = re.compile(r"(?:^|_)(?:authority|signer|pda)(?:$|_)")
+_SEVERITY_ORDER = {"high": 3, "medium": 2, "low": 1}
+# Instruction builders → account role of each leading argument
+_BUILDER_ROLES = {
+    "system_instruction::transfer": ["from", "to"],
+    "spl_token::instruction::transfer": ["token_program", "from", "to", "authority"],
+    "spl_token::instruction::transfer_checked": ["token_program", "from", "mint", "to", "authority"],
+}
+
+# Finding kind → (severity, short description)
+REASONS = {
+    "pda-signer-to-unvalidated-program": ("high", "a PDA signature is extended to a caller-chosen program"),
+    "pda-signer-with-remaining-accounts": ("medium", "a PDA-signed CPI forwards caller-supplied accounts"),
+    "pda-signer-with-attacker-data": ("medium", "a PDA-signed CPI carries caller-supplied instruction data"),
+    "signer-to-unvalidated-program": ("low", "a transaction signer is forwarded to a caller-chosen program"),
+}
+
+
+@dataclass
+class ForwardedAccount:
+    """An account passed into a CPI, with the privileges it carries."""
+
+    name: str
+    signer: bool
+    writable: bool
+    origin: str  # "pda" (signed by the program), "signer" (transaction signer), "account"
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"name": self.name, "signer": self.signer, "writable": self.writable, "origin": self.origin}
+
+
+@dataclass
+class CpiSite:
+    """One cross-program invocation and the privileges it propagates."""
+
+    program: str
+    instruction: str
+    file: str
+    line: int
+    kind: str  # invoke, invoke_signed, cpi, cpi_signed
+    target: str
+    target_account: str | None = None
+    # program-type, address, key-check, constant, self, unchecked, unknown
+    target_check: str = "unknown"
+    pda_signed: bool = False
+    signer_seeds: str = ""
+    forwarded: list[ForwardedAccount] = field(default_factory=list)
+    forwards_remaining: bool = False
+    data_source: str | None = None  # Instruction argument passed through as CPI data
+    callee: str = ""  # CPI accounts struct (`Transfer`) or instruction builder path
+    roles: dict[str, str] = field(default_factory=dict)  # Callee role (from, to, authority) → account
+
+    @property
+    def target_validated(self) -> bool:
+        return self.target_check in ("program-type", "address", "key-check", "constant", "self")
+
+    @property
+    def pda_signers(self) -> list[str]:
+        return [a.name for a in self.forwarded if a.origin == "pda"]
+
+    @property
+    def reasons(self) -> list[str]:
+        found = []
+        if self.pda_signed:
+            if not self.target_validated:
+                found.append("pda-signer-to-unvalidated-program")
+            if self.forwards_remaining:
+                found.append("pda-signer-with-remaining-accounts")
+            if self.data_source:
+                found.append("pda-signer-with-attacker-data")
+        elif not self.target_validated and any(a.origin == "signer" for a in self.forwarded):
+            found.append("signer-to-unvalidated-program")
+        return found
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "program": self.program,
+            "instruction": self.instruction,
+            "file": self.file,
+            "line": self.line,
+            "kind": self.kind,
+            "target": self.target,
+            "target_account": self.target_account,
+            "target_check": self.target_check,
+            "pda_signed": self.pda_signed,
+            "forwarded": [a.to_dict() for a in self.forwarded],
+            "forwards_remaining": self.forwards_remaining,
+            "data_source": self.data_source,
+            "callee": self.callee,
+            "roles": self.roles,
+            "reasons": self.reasons,
+        }
+
+
+@dataclass
+class CpiPrivilegeFinding:
+    """A CPI that hands the program's or the caller's privileges to code it does not control."""
+
+    site: CpiSite
+    path: str  # Source file relative to the scanned project
+
+    @property
+    def kind(self) -> str:
+        return self.site.reasons[0]
+
+    @property
+    def severity(self) -> str:
+        return max((REASONS[r][0] for r in self.site.reasons), key=_SEVERITY_ORDER.__getitem__)
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        s = self.site
+        privileged = [a for a in s.forwarded if a.signer or a.writable]
+        carried = ", ".join(
+            f"{a.name} ({'PDA signer' if a.origin == 'pda' else 'signer' if a.signer else 'writable'})"
+            for a in privileged
+        ) or "no privileged accounts"
+        target = f"`{s.target_account}`" if s.target_account else f"`{s.target}`"
+        check = {
+            "unchecked": "is not checked against a known program id",
+            "unknown": "could not be resolved to a checked program",
+        }.get(s.target_check, f"is validated ({s.target_check})")
+        details = "; ".join(REASONS[r][1] for r in s.reasons)
+        description = (
+            f"`{s.instruction}` makes a {s.kind} CPI to {target}, which {check}. "
+            f"Privileges carried into the callee: {carried}"
+            + (" plus every account in `remaining_accounts`" if s.forwards_remaining else "")
+            + (f"; instruction data comes from `{s.data_source}`" if s.data_source else "")
+            + f". Here {details}, so the callee can act with those privileges on the attacker's behalf."
+        )
+        return {
+            "title": f"CPI privilege escalation in {s.instruction}: {REASONS[self.kind][1]}",
+            "description": description,
+            "vulnerability_type": "arbitrary-cpi",
+            "severity": self.severity,
+            "confidence": 0.6 if self.severity == "high" else 0.4,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "cpi-privileges",
+                "category": "cpi-security",
+                "source_files": [self.path],
+                "affected_lines": [s.line],
+                "reasons": s.reasons,
+                "target_check": s.target_check,
+                "pda_signers": s.pda_signers,
+                "checklist": ["SOL-CPI-01", "SOL-CPI-03"],
+            },
+        }
+
+
+def _account_ref(expr: str, ix: Instruction) -> str | None:
+    """Account named by ``expr`` (`ctx.accounts.x.to_account_info()`, `&x.key`, `x.clone()`)."""
+    base = expr.strip().lstrip("&*").strip()
+    base = re.sub(r"^(?:ctx\s*\.\s*accounts|self)\s*\.\s*", "", base)
+    base = re.sub(r"(?:\s*\.\s*(?:to_account_info|clone|key|as_ref)\s*\(\s*\)|\s*\.\s*key\b)+$", "", base)
+    return base if ix.account(base) is not None else None
+
+
+def _let_binding(body: str, name: str, before: int) -> tuple[str, int] | None:
+    """Right-hand side and offset of the last `let name = ...;` before ``before``."""
+    binding = None
+    for m in re.finditer(rf"\blet\s+(?:mut\s+)?{re.escape(name)}\s*(?::[^=]+)?=(?!=)", body[:before]):
+        binding = body[m.end():statement_end(body, m.end())].strip().rstrip(";").strip(), m.start()
+    return binding
+
+
+def _let_value(body: str, name: str, before: int) -> str | None:
+    """Right-hand side of the last `let name = ...;` before ``before``."""
+    binding = _let_binding(body, name, before)
+    return binding[0] if binding else None
+
+
+def _resolve(body: str, expr: str, before: int, depth: int = 6) -> str:
+    """Follow identifiers through their `let` bindings, past `&x[..]` and into slice literals."""
+    expr = expr.strip()
+    inner = expr.lstrip("&").strip()
+    if depth == 0 or not inner:
+        return expr
+    if inner.startswith("[") and find_matching(inner, 0) == len(inner) - 1:
+        parts = split_top_level(inner[1:-1])
+        resolved = [_resolve(body, part, before, depth - 1) for part in parts]
+        return expr if resolved == parts else f"{expr[:len(expr) - len(inner)]}[{', '.join(resolved)}]"
+    name = re.sub(r"\s*\[\s*\.\.\s*\]$", "", inner)
+    if not re.fullmatch(r"[A-Za-z_]\w*", name) or (binding := _let_binding(body, name, before)) is None:
+        return expr
+    # Resolve the binding where it was made, so `let x = &x[..];` reaches the earlier `x`
+    return _resolve(body, binding[0], binding[1], depth - 1)
+
+
+def _struct_fields(expr: str) -> dict[str, str]:
+    """Field → value for a struct literal `Name { a: x, b }`."""
+    open_idx = expr.find("{")
+    if open_idx < 0:
+        return {}
+    fields = {}
+    for part in split_top_level(expr[open_idx + 1:find_matching(expr, open_idx)]):
+        name, sep, value = part.partition(":")
+        if name.strip():
+            fields[name.strip()] = value.strip() if sep else name.strip()
+    return fields
+
+
+def _target_check(program: Program, ix: Instruction, target: str, acc: AccountField | None) -> str:
+    expr = target.strip().lstrip("&*").strip()
+    if acc is None:
+        if re.search(r"\bprogram_id\b", expr):
+            return "self"
+        if _CONSTANT_ID_RE.match(re.sub(r"\s+", "", expr)):
+            return "constant"
+        return "unknown"
+    if acc.wrapper in ("Program", "Interface"):
+        return "program-type"
+    if any(re.match(r"address\s*=", c) for c in acc.constraints):
+        return "address"
+    if "key" in acc.constraints and program.framework != "anchor":
+        return "key-check"
+    n = re.escape(acc.name)
+    key = rf"\b{n}\s*\.\s*key\b(?:\s*\(\s*\))?"
+    if re.search(rf"require_keys_(?:eq|neq)!\s*\([^;]*{key}", ix.body) or re.search(
+        rf"{key}\s*[!=]=|[!=]=\s*\*?\s*(?:ctx\s*\.\s*accounts\s*\.\s*)?{key}", ix.body
+    ):
+        return "key-check"
+    return "unchecked"
+
+
+def _data_source(body: str, ix_expr: str, ix: Instruction, before: int) -> str | None:
+    """The instruction argument a CPI's data is copied from, if any."""
+    data = _struct_fields(ix_expr).get("data")
+    if data is None:
+        m = re.search(r"Instruction\s*::\s*new_with_bytes\s*\(", ix_expr)
+        if m:
+            args = split_top_level(ix_expr[m.end():find_matching(ix_expr, m.end() - 1)])
+            data = args[1] if len(args) > 1 else None
+    if data is None:
+        return None
+    data = _resolve(body, data, before)
+    base = re.sub(r"(?:\s*\.\s*(?:to_vec|clone|into|as_ref)\s*\(\s*\)|\s*\[[^\]]*\])+$", "", data.lstrip("&").strip())
+    args = {a.name for a in ix.args} | {"instruction_data"}
+    return base if base in args else None
+
+
+def _forwarded_names(body: str, expr: str, ix: Instruction, before: int) -> tuple[list[str], bool]:
+    """Accounts named in a CPI account list (struct literal or slice), and whether caller-supplied ones are included."""
+    expr = _resolve(body, expr, before)
+    remaining = bool(re.search(r"\bremaining_accounts\b", expr)) or expr.lstrip("&").strip() == "accounts"
+    if "{" in expr:
+        parts = list(_struct_fields(expr).values())
+    else:
+        inner = expr.strip().lstrip("&").strip()
+        inner = inner[1:-1] if inner.startswith("[") and inner.endswith("]") else inner
+        inner = re.sub(r"^vec!\s*\[(.*)\]$", r"\1", inner, flags=re.DOTALL)
+        parts = split_top_level(inner)
+    names = []
+    for part in parts:
+        name = _account_ref(part, ix)
+        if name is not None and name not in names:
+            names.append(name)
+    return names, remaining
+
+
+def _pda_signers(names: list[str], ix: Instruction) -> list[str]:
+    """Forwarded accounts the program signs for (seeded accounts, or PDA-named ones in native code)."""
+    seeded = [n for n in names if ix.account(n).seeds is not None and not ix.account(n).is_signer]
+    if seeded:
+        return seeded
+    return [n for n in names if not ix.account(n).is_signer and _PDA_NAME_RE.search(n)]
+
+
+def analyze_instruction(program: Program, ix: Instruction) -> list[CpiSite]:
+    """Every CPI in ``ix`` with the privileges it propagates."""
+    sites = []
+    body = ix.body
+    for m in _CPI_RE.finditer(body):
+        callee = re.sub(r"\s+", "", m.group("callee"))
+        close = find_matching(body, m.end() - 1)
+        args = split_top_level(body[m.end():close])
+        if not args:
+            continue
+        after = body[close + 1:statement_end(body, close + 1)]
+        site = CpiSite(
+            program=program.name,
+            instruction=ix.name,
+            file=ix.source_file,
+            line=ix.body_line + body.count("\n", 0, m.start()),
+            kind={"CpiContext::new": "cpi", "CpiContext::new_with_signer": "cpi_signed"}.get(
+                callee, callee.replace("_unchecked", "")
+            ),
+            target="",
+        )
+        if site.kind.startswith("cpi"):
+            site.target = " ".join(args[0].split())
+            accounts_expr = args[1] if len(args) > 1 else ""
+            seeds = args[2] if site.kind == "cpi_signed" and len(args) > 2 else ""
+            signer = re.search(r"\.\s*with_signer\s*\(", after)
+            if signer:
+                seeds = after[signer.end():find_matching(after, signer.end() - 1)]
+            if re.search(r"\.\s*with_remaining_accounts\s*\(", after):
+                site.forwards_remaining = True
+            # The instruction data is built by the CPI helper from typed arguments
+            struct = _resolve(body, accounts_expr, m.start())
+            name = re.match(r"\s*([\w:]+)\s*\{", struct)
+            if name:
+                site.callee = name.group(1)
+                site.roles = {f: a for f, v in _struct_fields(struct).items() if (a := _account_ref(v, ix))}
+        else:
+            call = next((c for c in ix.calls if c.line == site.line), None)
+            ix_expr = _resolve(body, args[0], m.start())
+            site.target = call.target if call is not None else ""
+            accounts_expr = args[1] if len(args) > 1 else ""
+            seeds = args[2] if site.kind == "invoke_signed" and len(args) > 2 else ""
+            site.data_source = _data_source(body, ix_expr, ix, m.start())
+            builder = re.match(r"([\w:]+)\s*\(", ix_expr.lstrip("&").strip())
+            if builder and builder.group(1) != "Instruction":
+                site.callee = builder.group(1)
+                inner = split_top_level(ix_expr[ix_expr.index("(") + 1:find_matching(ix_expr, ix_expr.index("("))])
+                roles = next((r for path, r in _BUILDER_ROLES.items() if site.callee.endswith(path)), [])
+                site.roles = {r: a for r, v in zip(roles, inner) if (a := _account_ref(v, ix))}
+            metas = _struct_fields(ix_expr).get("accounts", "")
+            if re.search(r"\bremaining_accounts\b", _resolve(body, metas, m.start())):
+                site.forwards_remaining = True
+        site.pda_signed = bool(seeds.strip())
+        site.signer_seeds = " ".join(_resolve(body, seeds, m.start()).split()) if seeds else ""
+        names, remaining = _forwarded_names(body, accounts_expr, ix, m.start())
+        site.forwards_remaining = site.forwards_remaining or remaining
+        pda = set(_pda_signers(names, ix)) if site.pda_signed else set()
+        for name in names:
+            acc = ix.account(name)
+            origin = "pda" if name in pda else "signer" if acc.is_signer else "account"
+            site.forwarded.append(ForwardedAccount(name, acc.is_signer or name in pda, acc.is_mut, origin))
+        site.target_account = _account_ref(site.target, ix)
+        acc = ix.account(site.target_account) if site.target_account else None
+        site.target_check = _target_check(program, ix, site.target, acc)
+        sites.append(site)
+    return sites
+
+
+class CpiPrivilegeAnalyzer:
+    """Tracks signer/writable privileges across CPI boundaries."""
+
+    def __init__(self, report_signers: bool = True):
+        """Initialize the analyzer.
+
+        Args:
+            report_signers: Also report transaction signers forwarded to caller-chosen programs
+        """
+        self.report_signers = report_signers
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{ANALYZER_VERSION}"
+
+    def analyze_program(self, program: Program) -> list[CpiSite]:
+        """Every CPI site in ``program``."""
+        return [site for ix in program.instructions for site in analyze_instruction(program, ix)]
+
+    def run(self, project_path: Path) -> tuple[list[CpiPrivilegeFinding], dict]:
+        """Analyze every Solana program under ``project_path``.
+
+        Returns:
+            Tuple of (findings, metadata); metadata["cpi_sites"] lists every CPI and what it forwards
+        """
+        project_path = Path(project_path)
+        findings: list[CpiPrivilegeFinding] = []
+        sites: list[CpiSite] = []
+        for program in load_programs(project_path):
+            if program.chain != "solana":
+                continue
+            root = Path(os.path.relpath(program.root, project_path))
+            for site in self.analyze_program(program):
+                sites.append(site)
+                reasons = site.reasons
+                if not reasons or (reasons == ["signer-to-unvalidated-program"] and not self.report_signers):
+                    continue
+                path = site.file if str(root) == "." else (root / site.file).as_posix()
+                findings.append(CpiPrivilegeFinding(site, path))
+        metadata = {
+            "tool": "cpi-privileges",
+            "version": ANALYZER_VERSION,
+            "success": True,
+            "error": None,
+            "cpi_sites": [s.to_dict() for s in sites],
+            "signed_cpis": sum(1 for s in sites if s.pda_signed),
+        }
+        return findings, metadata
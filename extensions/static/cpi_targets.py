@@ -0,0 +1,190 @@
+"""
+Unvalidated CPI target detector.
+
+Finds `CpiContext::new` / `CpiContext::new_with_signer` / `invoke` /
+`invoke_signed` calls whose target program comes from an `AccountInfo` or
+`UncheckedAccount` that is never pinned to a program id: no
+`Program<'info, T>` / `Interface` typing, no `address = ...` constraint, no
+`constraint = x.key() == ...` and no key comparison in the handler. Whoever
+builds the transaction then chooses which program runs, with every account
+(and any PDA signature) the CPI forwards.
+
+Each finding names the offending account and the CPI line and suggests the
+fix the `cpi_reentrancy` template shows: type the account as
+`Program<'info, T>` in Anchor, or compare its key against the expected
+program id in native code.
+
+Built in (no external tool), so it is always available.
+"""
+
+import os
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from extensions.ir import load_programs
+from extensions.ir.model import AccountField, Instruction, Program
+
+from .cpi_privileges import CpiSite, analyze_instruction
+
+DETECTOR_VERSION = "1.0.0"
+TEMPLATE_ID = "cpi_reentrancy"
+TEMPLATE_FILE = "extensions/knowledge/templates/solana/cpi_reentrancy.rs"
+
+_RAW_WRAPPERS = {"AccountInfo", "UncheckedAccount"}
+# Known programs: Anchor program type, its id, and how CPIs into it are recognised
+# (account name, CPI accounts struct or instruction builder path)
+_PROGRAMS = [
+    ("Token", "anchor_spl::token::ID", "spl_token::id()",
+     re.compile(r"^token_program$|spl_token|\btoken::|^(?:Transfer|TransferChecked|MintTo|Burn|Approve|Revoke"
+                r"|CloseAccount|SetAuthority|FreezeAccount|ThawAccount|InitializeAccount3?)$")),
+    ("System", "anchor_lang::system_program::ID", "solana_program::system_program::id()",
+     re.compile(r"^system_program$|system_instruction|system_program::|^(?:CreateAccount|Allocate|Assign)$")),
+    ("AssociatedToken", "anchor_spl::associated_token::ID", "spl_associated_token_account::id()",
+     re.compile(r"^associated_token_program$|associated_token")),
+]
+_SEVERITY_ORDER = {"high": 3, "medium": 2}
+
+
+@dataclass
+class UnvalidatedCpiTargetFinding:
+    """A CPI whose target program is a raw account the caller can choose."""
+
+    site: CpiSite
+    account: str
+    account_type: str
+    account_line: int
+    path: str
+    framework: str
+
+    @property
+    def severity(self) -> str:
+        privileged = self.site.pda_signed or any(a.origin == "signer" for a in self.site.forwarded)
+        return "high" if privileged else "medium"
+
+    @property
+    def expected_program(self) -> tuple[str, str, str] | None:
+        """(Anchor type, Anchor id, native id) of the program the CPI is meant for, when recognisable."""
+        # The account name settles it before the callee (`Transfer` exists in both System and Token)
+        for text in (self.account, self.site.callee):
+            for ty, anchor_id, native_id, pattern in _PROGRAMS:
+                if text and pattern.search(text):
+                    return ty, anchor_id, native_id
+        return None
+
+    @property
+    def suggested_fix(self) -> str:
+        expected = self.expected_program
+        if self.framework == "anchor":
+            ty, address = (expected[0], expected[1]) if expected else ("T", "<expected program>::ID")
+            return f"pub {self.account}: Program<'info, {ty}>,  // or #[account(address = {address})]"
+        program_id = expected[2] if expected else "<expected program>::id()"
+        return f"if *{self.account}.key != {program_id} {{ return Err(ProgramError::IncorrectProgramId); }}"
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        s = self.site
+        privileged = [a.name for a in s.forwarded if a.signer or a.writable]
+        carried = ", ".join(f"`{n}`" for n in privileged) or "no privileged accounts"
+        signature = " and the program's PDA signature" if s.pda_signed else ""
+        description = (
+            f"`{s.instruction}` makes a {s.kind} CPI (line {s.line}) to the program in `{self.account}` "
+            f"({self.account_type}), which is never checked against a program id. A caller can pass any "
+            f"program there, and it receives {carried}{signature}. Fix: `{self.suggested_fix}`. "
+            f"PoC: `kb render {TEMPLATE_ID}`."
+        )
+        return {
+            "title": f"Unvalidated CPI target `{self.account}` in {s.instruction}",
+            "description": description,
+            "vulnerability_type": "arbitrary-cpi",
+            "severity": self.severity,
+            "confidence": 0.7,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "cpi-targets",
+                "category": "cpi-security",
+                "source_files": [self.path],
+                "affected_lines": [s.line, self.account_line],
+                "program": s.program,
+                "instruction": s.instruction,
+                "account": self.account,
+                "cpi_kind": s.kind,
+                "callee": s.callee,
+                "pda_signed": s.pda_signed,
+                "suggested_fix": self.suggested_fix,
+                "poc_template": TEMPLATE_ID,
+                "poc_template_file": TEMPLATE_FILE,
+                "checklist": ["SOL-CPI-01"],
+            },
+        }
+
+
+def _constraint_checked(acc: AccountField) -> bool:
+    key = rf"\b{re.escape(acc.name)}\s*\.\s*key\b"
+    return any(c.startswith("constraint") and re.search(key, c) and "==" in c for c in acc.constraints)
+
+
+def check_instruction(program: Program, ix: Instruction) -> list[UnvalidatedCpiTargetFinding]:
+    """CPIs in ``ix`` whose target is an unpinned raw account (paths relative to the program)."""
+    findings = []
+    for site in analyze_instruction(program, ix):
+        acc = ix.account(site.target_account) if site.target_account else None
+        if acc is None or acc.wrapper not in _RAW_WRAPPERS or site.target_check != "unchecked":
+            continue
+        if _constraint_checked(acc):
+            continue
+        findings.append(UnvalidatedCpiTargetFinding(
+            site, acc.name, " ".join(acc.ty.split()), acc.line or site.line, site.file, program.framework,
+        ))
+    return findings
+
+
+class UnvalidatedCpiTargetDetector:
+    """Flags CPIs into programs the caller chooses through a raw account."""
+
+    def __init__(self, min_severity: str = "medium"):
+        """Initialize the detector.
+
+        Args:
+            min_severity: Lowest severity to report ("medium" or "high")
+        """
+        self.min_severity = min_severity
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{DETECTOR_VERSION}"
+
+    def run(self, project_path: Path) -> tuple[list[UnvalidatedCpiTargetFinding], dict]:
+        """Check every Solana program under ``project_path``.
+
+        Returns:
+            Tuple of (findings, metadata)
+        """
+        project_path = Path(project_path)
+        floor = _SEVERITY_ORDER.get(self.min_severity, 2)
+        findings: list[UnvalidatedCpiTargetFinding] = []
+        checked = 0
+        for program in load_programs(project_path):
+            if program.chain != "solana":
+                continue
+            root = Path(os.path.relpath(program.root, project_path))
+            for ix in program.instructions:
+                for finding in check_instruction(program, ix):
+                    if _SEVERITY_ORDER[finding.severity] < floor:
+                        continue
+                    if str(root) != ".":
+                        finding.path = (root / finding.path).as_posix()
+                    findings.append(finding)
+                checked += 1
+        metadata = {
+            "tool": "cpi-targets",
+            "version": DETECTOR_VERSION,
+            "success": True,
+            "error": None,
+            "instructions_checked": checked,
+            "template": TEMPLATE_ID,
+        }
+        return findings, metadata
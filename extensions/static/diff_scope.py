@@ -0,0 +1,133 @@
+"""
+Diff-aware scanning.
+
+A ``DiffScope`` is the set of lines a change touches: what ``git diff``
+reports between the merge base of a ref (``origin/main``) and the working
+tree, plus untracked files, which are new in their entirety. ``scan --diff``
+keeps only the hits it touches, so a pull request is gated on the findings
+it introduces or edits rather than on everything already in the codebase:
+
+- a hit touches the change when any of its lines (``affected_lines``, up
+  to its ``end_line``, and the lines of the state changes it lists under
+  ``mutations``, in its first file) is within ``context`` lines of a
+  changed one, or when its file is new
+- a hit without lines touches it when one of its files changed
+- a deleted line counts as touching the lines on either side of it
+
+Paths are relative to the scanned workspace, which can be any directory
+inside the repository.
+"""
+
+import re
+import subprocess
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+_HUNK = re.compile(r"^@@ -\d+(?:,\d+)? \+(\d+)(?:,(\d+))? @@")
+
+
+class DiffError(ValueError):
+    """A ref that does not resolve, or a workspace outside a git repository."""
+
+
+def _git(root: Path, *args: str) -> str:
+    try:
+        # Unquoted paths whatever the user's config says (quoted ones would not match the hits' paths)
+        return subprocess.run(["git", "-C", str(root), "-c", "core.quotePath=false", *args],
+                              capture_output=True, text=True, check=True).stdout
+    except (OSError, subprocess.CalledProcessError) as e:
+        detail = e.stderr.strip() if isinstance(e, subprocess.CalledProcessError) and e.stderr else str(e)
+        raise DiffError(f"git {' '.join(args[:2])} failed in {root}: {detail}") from e
+
+
+def parse_diff(text: str) -> dict[str, set[int]]:
+    """Changed lines (new-file numbering) by file, from ``git diff --unified=0`` output."""
+    changed: dict[str, set[int]] = {}
+    current = None
+    for line in text.splitlines():
+        if line.startswith("+++ "):
+            target = line[4:].strip()
+            current = None if target == "/dev/null" else target[2:] if target.startswith("b/") else target
+            if current is not None:
+                changed.setdefault(current, set())
+        elif current is not None and (m := _HUNK.match(line)):
+            start, count = int(m.group(1)), int(m.group(2) if m.group(2) is not None else 1)
+            if count:
+                changed[current].update(range(start, start + count))
+            else:
+                # Lines removed after line `start`
+                changed[current].update(n for n in (start, start + 1) if n > 0)
+    return changed
+
+
+@dataclass
+class DiffScope:
+    """Lines changed since a ref, relative to a workspace."""
+
+    ref: str
+    base: str = ""  # Commit the change is measured from: the merge base of ref and HEAD
+    # Changed line numbers by workspace-relative file; None: the whole file is new
+    files: dict[str, set[int] | None] = field(default_factory=dict)
+    context: int = 0
+
+    @classmethod
+    def from_git(cls, workspace: Path, ref: str, context: int = 0) -> "DiffScope":
+        """What changed in ``workspace`` since its merge base with ``ref``, committed or not.
+
+        Raises:
+            DiffError: If the workspace is not in a git repository or ``ref`` does not resolve
+        """
+        workspace = Path(workspace).resolve()
+        root = workspace if workspace.is_dir() else workspace.parent
+        top = Path(_git(root, "rev-parse", "--show-toplevel").strip()).resolve()
+        base = _git(root, "merge-base", ref, "HEAD").strip()
+        prefix = root.relative_to(top).as_posix() if root != top else ""
+        scope = cls(ref, base, context=context)
+        diff = _git(top, "diff", "--unified=0", "--no-color", "--no-ext-diff", "--src-prefix=a/", "--dst-prefix=b/",
+                    base, "--", prefix or ".")
+        for path, lines in parse_diff(diff).items():
+            scope._add(path, prefix, lines)
+        for path in _git(top, "ls-files", "--others", "--exclude-standard", "--", prefix or ".").splitlines():
+            scope._add(path, prefix, None)
+        return scope
+
+    def _add(self, path: str, prefix: str, lines: set[int] | None) -> None:
+        if prefix and not path.startswith(prefix + "/"):
+            return
+        self.files[path[len(prefix) + 1:] if prefix else path] = lines
+
+    def _relative(self, file: str, workspace: Path | None) -> str:
+        file = re.sub(r":\d+(?:-\d+)?$", "", file.split("#", 1)[0])
+        path = Path(file)
+        if path.is_absolute() and workspace is not None and path.resolve().is_relative_to(workspace.resolve()):
+            return path.resolve().relative_to(workspace.resolve()).as_posix()
+        return path.as_posix()
+
+    def touches(self, hit: dict[str, Any], workspace: Path | None = None) -> bool:
+        """Whether the change introduced or edited ``hit`` (absolute paths are taken relative to ``workspace``)."""
+        props = hit.get("properties") or {}
+        files = [self._relative(str(f), workspace) for f in props.get("source_files") or [] if f]
+        lines = [n for n in props.get("affected_lines") or [] if isinstance(n, int) and n > 0]
+        if lines and isinstance(props.get("end_line"), int) and props["end_line"] > lines[0]:
+            lines += range(lines[0] + 1, props["end_line"] + 1)
+        lines += [m["line"] for m in props.get("mutations") or []
+                  if isinstance(m, dict) and isinstance(m.get("line"), int)]
+        if not lines:
+            return any(f in self.files for f in files)
+        # Affected lines are lines of the first file; the others are related files
+        if not files or files[0] not in self.files:
+            return False
+        changed = self.files[files[0]]
+        return changed is None or any(abs(n - c) <= self.context for n in lines for c in changed)
+
+    def filter(self, hits: list[dict[str, Any]],
+               workspace: Path | None = None) -> tuple[list[dict[str, Any]], list[dict[str, Any]]]:
+        """Split hits into those the change touches and the rest."""
+        touched, rest = [], []
+        for hit in hits:
+            (touched if self.touches(hit, workspace) else rest).append(hit)
+        return touched, rest
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"ref": self.ref, "base": self.base, "files": sorted(self.files), "context": self.context}
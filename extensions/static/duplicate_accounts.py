@@ -0,0 +1,208 @@
+"""
+Duplicate mutable account detector for Anchor programs.
+
+Nothing stops a caller from passing the same account for two slots of an
+instruction. When both are `#[account(mut)]` of the same type, the handler
+works on two views of one account: a deposit "transfers" tokens from
+`user_token` to `vault_token` (a no-op when they are the same account) and
+still credits the vault; a swap debits `from` and credits `to`, and the
+credit written last wins, minting the amount.
+
+Walks every instruction of Anchor programs through the program IR and flags
+pairs of mutable accounts that hold the same type (`Account<'info, T>`,
+`AccountLoader`, `InterfaceAccount`) with nothing keeping them apart: a
+`constraint = a.key() != b.key()` on either, a `require_keys_neq!` (or
+`a.key() != b.key()` check, or an `if a.key() == b.key()` that returns an
+error) in the handler, seeds whose constant parts differ so no two
+derivations can meet, or `init` on one of them. Other constraints relating
+the two (`from.mint == to.mint`) and seeds that only differ in which
+account they take (`owner_a` and `owner_b` may be one) do not count. A pair
+is high severity when the aliasing pays: the handler writes fields of both,
+or books a token or lamport transfer between the two with a state write.
+Each finding links to the `duplicate_mutable_accounts` PoC template.
+
+Built in (no external tool), so it is always available.
+"""
+
+import os
+import re
+from dataclasses import dataclass
+from itertools import combinations
+from pathlib import Path
+from typing import Any
+
+from extensions.ir import load_programs
+from extensions.ir.model import AccountField, Instruction, Program
+from extensions.ir.pda_map import normalize_seed, patterns_overlap, seed_constants
+
+from .missing_signer import _FIELD_WRITE_RE
+from .privilege_paths import _lamport_flows, _mentions, _state_type
+
+DETECTOR_VERSION = "1.0.0"
+TEMPLATE_ID = "duplicate_mutable_accounts"
+TEMPLATE_FILE = "extensions/knowledge/templates/solana/duplicate_mutable_accounts.rs"
+
+# `Transfer { from: .., to: .., .. }`, `TransferChecked { .. }`, `system_program::Transfer { .. }`
+_TRANSFER_RE = re.compile(r"\b(?:Transfer|TransferChecked)\s*\{(?P<fields>[^{}]*)\}")
+
+
+@dataclass
+class DuplicateAccountFinding:
+    """Two mutable accounts of one type that may be the same account."""
+
+    program: str
+    instruction: str
+    first: str
+    second: str
+    state_type: str
+    path: str
+    line: int  # Declaration of the second account
+    moves: str | None = None  # How the handler gains from the two being one, if it does
+    program_id: str | None = None
+
+    @property
+    def severity(self) -> str:
+        return "high" if self.moves else "medium"
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        context = {"PROGRAM_ID": self.program_id} if self.program_id else {}
+        program_id = self.program_id or "<program id>"
+        effect = (f"The handler {self.moves}, so passing one account for both makes it a no-op the handler still "
+                  f"books (a credited deposit, the last written balance winning)." if self.moves else
+                  "The handler then works on one account through two views.")
+        description = (
+            f"`{self.program}::{self.instruction}` takes `{self.first}` and `{self.second}`, both mutable "
+            f"`{self.state_type}` accounts, and nothing requires them to differ. {effect} Add "
+            f"`constraint = {self.first}.key() != {self.second}.key()` (or distinct seeds). PoC: "
+            f"`kb render {TEMPLATE_ID} -s PROGRAM_ID={program_id} -s VAULT_ACCOUNT=<pubkey>`."
+        )
+        return {
+            "title": f"`{self.first}` and `{self.second}` can be the same account in {self.instruction}",
+            "description": description,
+            "vulnerability_type": "duplicate-mutable-accounts",
+            "severity": self.severity,
+            "confidence": 0.6 if self.moves else 0.4,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "duplicate-mutable",
+                "category": "account-validation",
+                "source_files": [self.path],
+                "affected_lines": [self.line],
+                "program": self.program,
+                "instruction": self.instruction,
+                "accounts": [self.first, self.second],
+                "state_type": self.state_type,
+                "moves": self.moves,
+                "poc_template": TEMPLATE_ID,
+                "poc_template_file": TEMPLATE_FILE,
+                "poc_context": context,
+                "precision": "syntactic",
+            },
+        }
+
+
+def _key(name: str) -> str:
+    return rf"\b{re.escape(name)}\s*\.\s*key\b"
+
+
+def _keys_differ(text: str, a: AccountField, b: AccountField) -> bool:
+    """Whether ``text`` requires ``a`` and ``b`` to have different keys."""
+    for first, second in ((a.name, b.name), (b.name, a.name)):
+        x, y = _key(first) + r"\s*(?:\(\s*\))?", _key(second) + r"\s*(?:\(\s*\))?"
+        # `a.key() != b.key()`, `require_keys_neq!(a.key(), b.key())`, `assert_ne!(a.key(), b.key())`
+        if re.search(rf"{x}\s*!=\s*[\w.\s&*]*?{y}", text) or re.search(
+                rf"\b(?:require_keys_neq|assert_ne)!\s*\([\w.\s&*]*?{x}\s*,[\w.\s&*]*?{y}", text):
+            return True
+        # `if a.key() == b.key() { return err!(..) }` (or `return Err(..)`)
+        if re.search(rf"\bif\s+[\w.\s&*]*?{x}\s*==\s*[\w.\s&*]*?{y}\s*\{{\s*return\s+(?:err!|Err)\s*\(", text):
+            return True
+    return False
+
+
+def _seeds_differ(program: Program, ix: Instruction, a: AccountField, b: AccountField) -> bool:
+    """Whether ``a``'s and ``b``'s seeds can never derive the same address, whatever accounts are passed."""
+    constants = seed_constants(program)
+    globs = [[ch for seed in acc.seeds for ch in normalize_seed(seed, ix, program, constants).glob()]
+             for acc in (a, b)]
+    return not patterns_overlap(*globs)
+
+
+def _distinct(program: Program, ix: Instruction, a: AccountField, b: AccountField) -> bool:
+    """Whether a constraint, the seeds or the handler keep ``a`` and ``b`` apart."""
+    if any(c == "init" for c in a.constraints + b.constraints):
+        return True
+    if a.seeds is not None and b.seeds is not None and _seeds_differ(program, ix, a, b):
+        return True
+    constraints = [c for c in a.constraints + b.constraints if re.match(r"constraint\s*=", c)]
+    return any(_keys_differ(text, a, b) for text in [*constraints, ix.body])
+
+
+def _moves(ix: Instruction, a: AccountField, b: AccountField) -> str | None:
+    """How the handler turns ``a`` and ``b`` being one account into a gain, if it does."""
+    written = {m.group("acc") for m in _FIELD_WRITE_RE.finditer(ix.body)}
+    if a.name in written and b.name in written:
+        return "writes fields of both"
+    # A self-transfer is a no-op; it only pays when the handler books it
+    if not written:
+        return None
+    for m in _TRANSFER_RE.finditer(ix.body):
+        fields = dict(re.findall(r"\b(from|to)\s*:\s*([^,}]+)", m.group("fields")))
+        if all(any(_mentions(v, n) for v in fields.values()) for n in (a.name, b.name)):
+            source = a.name if _mentions(fields.get("from", ""), a.name) else b.name
+            return f"books a transfer from `{source}` to the other"
+    for debited, credited, how, _ in _lamport_flows(ix):
+        if {debited, *credited} >= {a.name, b.name} and how != "close":
+            return f"books lamports moved from `{debited}` to the other"
+    return None
+
+
+def check_instruction(program: Program, ix: Instruction) -> list[DuplicateAccountFinding]:
+    """Pairs of mutable same-type accounts of ``ix`` that may alias (paths relative to the program)."""
+    mutable = [acc for acc in ix.accounts if acc.is_mut and _state_type(acc)]
+    findings = []
+    for a, b in combinations(mutable, 2):
+        if _state_type(a) != _state_type(b) or _distinct(program, ix, a, b):
+            continue
+        findings.append(DuplicateAccountFinding(program.name, ix.name, a.name, b.name, _state_type(a),
+                                                b.file or ix.file, b.line, _moves(ix, a, b), program.program_id))
+    return findings
+
+
+class DuplicateMutableAccountDetector:
+    """Flags mutable accounts of the same type an instruction does not require to be distinct."""
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{DETECTOR_VERSION}"
+
+    def run(self, project_path: Path) -> tuple[list[DuplicateAccountFinding], dict]:
+        """Check every Anchor program under ``project_path``.
+
+        Returns:
+            Tuple of (findings, metadata)
+        """
+        project_path = Path(project_path)
+        findings: list[DuplicateAccountFinding] = []
+        checked = 0
+        for program in load_programs(project_path):
+            if program.chain != "solana" or program.framework != "anchor":
+                continue
+            root = Path(os.path.relpath(program.root, project_path))
+            for ix in program.instructions:
+                checked += 1
+                for finding in check_instruction(program, ix):
+                    if str(root) != ".":
+                        finding.path = (root / finding.path).as_posix()
+                    findings.append(finding)
+        metadata = {
+            "tool": "duplicate-mutable",
+            "version": DETECTOR_VERSION,
+            "success": True,
+            "error": None,
+            "instructions_checked": checked,
+            "template": TEMPLATE_ID,
+        }
+        return findings, metadata
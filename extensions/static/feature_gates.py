@@ -0,0 +1,160 @@
+"""
+Runtime feature gate dependencies checked against the target cluster.
+
+Solana activates syscalls, precompiles and behavior changes per cluster
+through feature gates, so code that runs under solana-program-test (every
+feature on) and on devnet can fail to deploy, or abort, on mainnet: a
+`big_mod_exp` call before that syscall is enabled, a passkey check that
+introspects a secp256r1 precompile instruction the cluster rejects. The
+reverse holds for behavior a gate removes: with rent collection disabled,
+`Rent::due` bookkeeping describes charges that no longer happen.
+
+Matches the Rust sources under the project against the knowledge base's
+feature gates (`extensions/knowledge/features/`) and flags each gate the
+code depends on whose state on the target cluster (default mainnet) is not
+what the code assumes. Activation comes from the knowledge base's snapshot
+unless read from the cluster (``active``). Every dependency found, met or
+not, is listed in the run metadata, with the cluster's runtime limits.
+
+Built in (no external tool), so it is always available.
+"""
+
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from extensions.ir.rust_source import strip_comments
+from extensions.ir.workspace import is_skipped
+from extensions.knowledge.feature_loader import ClusterFeatures, FeatureGate, FeatureLoader
+
+CHECKER_VERSION = "1.0.0"
+
+
+@dataclass
+class FeatureGateFinding:
+    """Code depending on a feature gate whose state on the target cluster breaks its assumption."""
+
+    gate: FeatureGate
+    cluster: str
+    active: bool  # The gate's state on the cluster
+    path: str
+    line: int
+    text: str
+
+    @property
+    def kind(self) -> str:
+        # Needs an inactive gate, or relies on behavior an active gate removed
+        return "inactive" if not self.active else "active"
+
+    @property
+    def severity(self) -> str:
+        if self.kind == "active":
+            return "low"
+        return "medium" if self.cluster == "mainnet" else "low"
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        gate = self.gate
+        if self.kind == "inactive":
+            title = f"Depends on {gate.name}, not active on {self.cluster}"
+            why = (f"`{self.text}` needs the `{gate.id}` feature gate (`{gate.feature}`), which is not active on "
+                   f"{self.cluster}, while solana-program-test and solana-test-validator enable it. {gate.impact}")
+        else:
+            title = f"Relies on runtime behavior removed on {self.cluster} ({gate.name})"
+            why = (f"`{self.text}` relies on what the runtime did before the `{gate.id}` feature gate "
+                   f"(`{gate.feature}`), which is active on {self.cluster}. {gate.impact}")
+        return {
+            "title": title,
+            "description": f"{why} Gate the code path on the cluster's features, or confirm activation on the "
+                           f"target cluster (`kb features --provider {self.cluster}`).",
+            "vulnerability_type": f"feature-gate-{self.kind}",
+            "severity": self.severity,
+            "confidence": 0.5,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "feature-gates",
+                "category": "runtime",
+                "precision": "syntactic",
+                "source_files": [self.path],
+                "affected_lines": [self.line],
+                "feature_gate": gate.id,
+                "feature": gate.feature,
+                "cluster": self.cluster,
+                "active": self.active,
+                "simd": gate.simd or None,
+            },
+        }
+
+
+class FeatureGateChecker:
+    """Flags code whose feature gate assumptions do not hold on the target cluster."""
+
+    def __init__(self, cluster: str = "mainnet", active: dict[str, bool] | None = None,
+                 features_dir: str | None = None):
+        """Initialize the checker.
+
+        Args:
+            cluster: Cluster the program is deployed to (mainnet, devnet, testnet, localnet)
+            active: Gate id -> active as read from the cluster, overriding the knowledge base's snapshot
+            features_dir: Directory of feature gates (default: the knowledge base's)
+        """
+        loader = FeatureLoader(Path(features_dir) if features_dir else None)
+        self.features = ClusterFeatures(cluster, active=active, loader=loader)
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{CHECKER_VERSION}"
+
+    def check_source(self, source: str, path: str) -> tuple[list[FeatureGateFinding], list[dict]]:
+        """Findings and every feature gate dependency in one Rust file."""
+        source = strip_comments(source)
+        findings: list[FeatureGateFinding] = []
+        dependencies: list[dict] = []
+        for gate in self.features.gates:
+            uses = gate.uses(source)
+            if not uses:
+                continue
+            line = source.count("\n", 0, uses[0].start()) + 1
+            active = self.features.is_active(gate)
+            dependencies.append({"feature_gate": gate.id, "name": gate.name, "active": active,
+                                 "holds": self.features.holds(gate), "path": path, "line": line})
+            if not self.features.holds(gate):
+                text = source[source.rfind("\n", 0, uses[0].start()) + 1:source.find("\n", uses[0].end())]
+                findings.append(FeatureGateFinding(gate, self.features.cluster, active, path, line,
+                                                   " ".join(text.split())))
+        return findings, dependencies
+
+    def run(self, project_path: Path) -> tuple[list[FeatureGateFinding], dict]:
+        """Check every Rust source file under ``project_path``.
+
+        Returns:
+            Tuple of (findings, metadata); metadata["dependencies"] lists every gate the code depends on
+        """
+        project_path = Path(project_path)
+        files = [project_path] if project_path.is_file() else sorted(
+            p for p in project_path.rglob("*.rs") if p.is_file() and not is_skipped(p.relative_to(project_path)))
+        findings: list[FeatureGateFinding] = []
+        dependencies: list[dict] = []
+        for file in files:
+            try:
+                source = file.read_text(errors="replace")
+            except OSError:
+                continue
+            rel = file.name if project_path.is_file() else file.relative_to(project_path).as_posix()
+            found, deps = self.check_source(source, rel)
+            findings += found
+            dependencies += deps
+        metadata = {
+            "tool": "feature-gates",
+            "version": CHECKER_VERSION,
+            "success": True,
+            "error": None,
+            "files_checked": len(files),
+            "cluster": self.features.cluster,
+            "status_source": self.features.source,
+            "dependencies": dependencies,
+            "limits": self.features.limits(),
+        }
+        return findings, metadata
@@ -0,0 +1,244 @@
+"""
+The finding model shared by detectors and serializers.
+
+Detectors emit hits as hypothesis dicts (``to_hypothesis``), which Hound's
+hypothesis store, suppressions and reports consume. ``Finding`` is the
+typed view of one hit that consumers can rely on across detectors:
+
+- rule_id: the rule's stable code (``SOL-001-MISSING-SIGNER``, see
+  rules.py), or its ``<detector>/<vulnerability-type>`` id if it has none
+- severity and confidence, normalized (``info``..``critical``, 0-1)
+- location: the span in the first source file; related: the other files
+- remediation: the detector's suggested fix, if it has one
+- template: the PoC template that demonstrates the hit, if any
+
+``fingerprint`` identifies a finding across runs: it leaves out line
+numbers, so edits elsewhere in a file do not make old findings look new.
+Besides rule, file and title it takes the instruction, account, field and
+operator the hit is about, and, for hits alike in all of those, which of
+them it is in line order (``number_occurrences``). ``diff_findings``
+compares two runs by fingerprint.
+"""
+
+import hashlib
+from dataclasses import dataclass, field
+from typing import Any
+
+from .confidence import parse_confidence, source_tool
+from .rules import RuleCatalog, rule_code, rule_id
+
+SEVERITIES = ("info", "low", "medium", "high", "critical")
+_SEVERITY_ALIASES = {"informational": "info", "note": "info", "warning": "medium", "error": "high"}
+# Hit properties that tell apart findings of one rule in one file
+_DISCRIMINATORS = ("instruction", "account", "field", "operator")
+
+
+def normalize_severity(value: Any) -> str:
+    """One of ``SEVERITIES`` (``medium`` for anything unknown)."""
+    severity = str(value or "medium").strip().lower()
+    severity = _SEVERITY_ALIASES.get(severity, severity)
+    return severity if severity in SEVERITIES else "medium"
+
+
+def fix_text(fix: Any) -> str | None:
+    """A detector's ``suggested_fix`` (text or a mapping) as one line."""
+    if isinstance(fix, str):
+        return fix or None
+    if isinstance(fix, dict) and fix:
+        return "; ".join(f"{key}: {value}" for key, value in fix.items())
+    return None
+
+
+@dataclass(frozen=True)
+class Span:
+    """A source range; lines and columns are 1-based and inclusive."""
+
+    path: str
+    start_line: int | None = None
+    end_line: int | None = None
+    start_column: int | None = None
+    end_column: int | None = None
+
+    def __str__(self) -> str:
+        if self.start_line is None:
+            return self.path
+        end = f"-{self.end_line}" if self.end_line and self.end_line != self.start_line else ""
+        return f"{self.path}:{self.start_line}{end}"
+
+    def to_dict(self) -> dict[str, Any]:
+        return {key: value for key, value in self.__dict__.items() if value is not None}
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "Span":
+        return cls(str(data["path"]), data.get("start_line"), data.get("end_line"), data.get("start_column"),
+                   data.get("end_column"))
+
+
+def _lines(props: dict[str, Any]) -> list[int]:
+    lines = []
+    for line in props.get("affected_lines") or []:
+        try:
+            if int(line) > 0:
+                lines.append(int(line))
+        except (TypeError, ValueError):
+            continue
+    return lines
+
+
+@dataclass
+class Finding:
+    """One detector hit."""
+
+    rule_id: str
+    rule: str
+    title: str
+    severity: str = "medium"
+    confidence: float | None = None
+    detector: str | None = None
+    vulnerability_type: str = ""
+    location: Span | None = None
+    related: list[Span] = field(default_factory=list)
+    description: str = ""
+    remediation: str | None = None
+    template: str | None = None
+    # The pipeline's hit id
+    id: str | None = None
+    properties: dict[str, Any] = field(default_factory=dict)
+
+    def _identity(self) -> tuple[str, ...]:
+        path = self.location.path if self.location else ""
+        return (self.rule_id, path, self.title, *(str(self.properties.get(k) or "") for k in _DISCRIMINATORS))
+
+    @property
+    def fingerprint(self) -> str:
+        """Identity across runs: rule, file, title, what the hit is about and its occurrence, not lines."""
+        key = "|".join((*self._identity(), str(self.properties.get("occurrence") or 0)))
+        return hashlib.sha256(key.encode()).hexdigest()[:16]
+
+    @classmethod
+    def from_hypothesis(cls, hypothesis: dict[str, Any], tool: str | None = None,
+                        catalog: RuleCatalog | None = None) -> "Finding":
+        """The finding for a detector hit (a pipeline hypothesis, or a detector's ``to_hypothesis()``)."""
+        props = dict(hypothesis.get("properties") or {})
+        rule = rule_id(hypothesis, tool)
+        files = [str(f).split("#", 1)[0] for f in props.get("source_files") or [] if f]
+        lines = _lines(props)
+        location = None
+        if files:
+            start = lines[0] if lines else None
+            end = props.get("end_line") if isinstance(props.get("end_line"), int) else None
+            location = Span(files[0], start, end, props.get("column"), props.get("end_column"))
+        try:
+            confidence = parse_confidence(hypothesis.get("confidence"))
+        except ValueError:
+            confidence = None
+        return cls(
+            rule_id=rule_code(hypothesis, tool, catalog) or rule,
+            rule=rule,
+            title=str(hypothesis.get("title") or rule),
+            severity=normalize_severity(hypothesis.get("severity")),
+            confidence=confidence,
+            detector=source_tool(hypothesis, tool),
+            vulnerability_type=str(hypothesis.get("vulnerability_type") or ""),
+            location=location,
+            related=[Span(f) for f in files[1:]],
+            description=str(hypothesis.get("description") or ""),
+            remediation=fix_text(props.get("suggested_fix")),
+            template=props.get("poc_template"),
+            id=hypothesis.get("id"),
+            properties=props,
+        )
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """The hypothesis dict Hound's store and reports take."""
+        props = dict(self.properties)
+        props.update({"rule_id": self.rule, "source_files": [s.path for s in [self.location, *self.related] if s]})
+        if self.rule_id != self.rule:
+            props["rule_code"] = self.rule_id
+        if self.detector:
+            props["source_tool"] = self.detector
+        if self.location and self.location.start_line and not _lines(props):
+            props["affected_lines"] = [self.location.start_line]
+        if self.remediation and not props.get("suggested_fix"):
+            props["suggested_fix"] = self.remediation
+        if self.template:
+            props["poc_template"] = self.template
+        hypothesis = {"title": self.title, "description": self.description,
+                      "vulnerability_type": self.vulnerability_type, "severity": self.severity,
+                      "confidence": self.confidence, "properties": props}
+        if self.id:
+            hypothesis["id"] = self.id
+        return hypothesis
+
+    def to_dict(self) -> dict[str, Any]:
+        """JSON form, for consumers outside Hound."""
+        return {
+            "rule_id": self.rule_id,
+            "rule": self.rule,
+            "fingerprint": self.fingerprint,
+            "title": self.title,
+            "severity": self.severity,
+            "confidence": self.confidence,
+            "detector": self.detector,
+            "vulnerability_type": self.vulnerability_type,
+            "location": self.location.to_dict() if self.location else None,
+            "related": [s.to_dict() for s in self.related],
+            "description": self.description,
+            "remediation": self.remediation,
+            "template": self.template,
+            "id": self.id,
+            # What the fingerprint is made of, so it survives the round trip
+            "properties": {k: v for k, v in self.properties.items() if k in (*_DISCRIMINATORS, "occurrence")},
+        }
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "Finding":
+        location = data.get("location")
+        return cls(
+            rule_id=data["rule_id"], rule=data.get("rule") or data["rule_id"], title=data.get("title", ""),
+            severity=normalize_severity(data.get("severity")), confidence=data.get("confidence"),
+            detector=data.get("detector"), vulnerability_type=data.get("vulnerability_type", ""),
+            location=Span.from_dict(location) if location else None,
+            related=[Span.from_dict(s) for s in data.get("related") or []],
+            description=data.get("description", ""), remediation=data.get("remediation"),
+            template=data.get("template"), id=data.get("id"), properties=dict(data.get("properties") or {}),
+        )
+
+
+def number_occurrences(findings: list[Finding]) -> list[Finding]:
+    """Number findings alike in all but their lines, in line order, as ``properties.occurrence``.
+
+    Copies of one hit (same lines too) share a number.
+    """
+    def position(finding: Finding) -> tuple[int, int]:
+        location = finding.location
+        return (location.start_line or 0, location.start_column or 0) if location else (0, 0)
+
+    seen: dict[tuple[str, ...], dict[tuple[int, int], int]] = {}
+    for finding in sorted(findings, key=position):
+        numbers = seen.setdefault(finding._identity(), {})
+        finding.properties["occurrence"] = numbers.setdefault(position(finding), len(numbers))
+    return findings
+
+
+@dataclass
+class FindingDiff:
+    """Findings of a later run against an earlier one."""
+
+    new: list[Finding] = field(default_factory=list)
+    fixed: list[Finding] = field(default_factory=list)
+    unchanged: list[Finding] = field(default_factory=list)
+
+    def summary(self) -> str:
+        return f"{len(self.new)} new, {len(self.fixed)} fixed, {len(self.unchanged)} unchanged"
+
+
+def diff_findings(before: list[Finding], after: list[Finding]) -> FindingDiff:
+    """Which findings of ``after`` are new, which of ``before`` are gone, and which are in both."""
+    number_occurrences(before)
+    number_occurrences(after)
+    earlier = {f.fingerprint for f in before}
+    later = {f.fingerprint for f in after}
+    return FindingDiff(new=[f for f in after if f.fingerprint not in earlier],
+                       fixed=[f for f in before if f.fingerprint not in later],
+                       unchanged=[f for f in after if f.fingerprint in earlier])
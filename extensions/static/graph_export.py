@@ -0,0 +1,253 @@
+"""
+Account/instruction/CPI graph export.
+
+Renders the workspace graph `privilege_paths` builds (programs, their
+instructions, the account slots each takes, the state types those accounts
+hold, and the CPIs and lamport transfers between them) as Graphviz DOT,
+Mermaid or knowledge-graph JSON, so kickoff diagrams come from the code
+instead of being drawn by hand.
+
+Findings can be laid over the graph: a finding is attached to the account
+slot declared on one of its affected lines, else to the instruction whose
+handler spans the line, else to the program owning its file, and to any
+graph node it names in ``node_refs``. Annotated nodes carry the findings and
+their worst severity, which DOT and Mermaid show as a fill colour.
+"""
+
+import os
+from pathlib import Path
+from typing import Any
+
+from extensions.ir.model import Program
+
+from .privilege_paths import GraphEdge, PrivilegeGraph, _acc_id, _ix_id
+
+FORMATS = ("dot", "mermaid", "json")
+
+SEVERITY_ORDER = ["critical", "high", "medium", "low", "info"]
+SEVERITY_COLORS = {"critical": "#d7263d", "high": "#f46036", "medium": "#f6ae2d", "low": "#c4e7d4", "info": "#e0e0e0"}
+
+# Membership is drawn as clusters/subgraphs, so `contains` edges are left out of the diagrams
+_DIAGRAM_SKIP = {"contains"}
+_DOT_SHAPES = {"instruction": "box", "account": "ellipse", "state": "cylinder", "external": "octagon"}
+_DOT_EDGE_STYLES = {
+    "cpi": 'color="#d7263d", penwidth=2',
+    "lamport_transfer": 'color="#f46036", style=dashed',
+    "authorizes": 'color="#2e86ab"',
+    "gates": 'color="#2e86ab", style=dotted',
+    "writes_field": 'color="#6c757d", style=dashed',
+    "uses": 'color="#adb5bd"',
+    "instance_of": 'color="#adb5bd", style=dotted',
+}
+_MERMAID_SHAPES = {"instruction": ('["', '"]'), "account": ('("', '")'), "state": ('[("', '")]'),
+                   "external": ('{{"', '"}}')}
+
+
+def _severity(finding: dict[str, Any]) -> str:
+    severity = str(finding.get("severity", "medium")).lower()
+    return "info" if severity == "informational" else severity if severity in SEVERITY_ORDER else "medium"
+
+
+def _lines(finding: dict[str, Any]) -> list[int]:
+    lines = []
+    for line in (finding.get("properties") or {}).get("affected_lines") or []:
+        try:
+            lines.append(int(line))
+        except (TypeError, ValueError):
+            continue
+    return lines
+
+
+def _program_dir(program: Program, source: Path | None) -> str:
+    root = Path(program.root)
+    if source is not None and root.is_absolute() == Path(source).is_absolute():
+        rel = os.path.relpath(root, source)
+        return "" if rel == "." else Path(rel).as_posix()
+    return root.as_posix()
+
+
+def _rel(program_dir: str, file: str) -> str:
+    return f"{program_dir}/{file}" if program_dir and file else file
+
+
+def _targets(graph: PrivilegeGraph, programs: list[Program], finding: dict[str, Any],
+             source: Path | None) -> list[str]:
+    """Graph node ids ``finding`` points at."""
+    from extensions.reporting.heatmap import finding_files
+
+    files = finding_files(finding, source)
+    lines = _lines(finding)
+    found = [ref for ref in finding.get("node_refs") or [] if isinstance(ref, str) and ref in graph.nodes]
+    for program in programs:
+        pdir = _program_dir(program, source)
+        owned = [f for f in files if (not pdir or f.startswith(pdir + "/")) or f in program.source_files]
+        if not owned:
+            continue
+        hits = []
+        for ix in program.instructions:
+            for acc in ix.accounts:
+                if _rel(pdir, acc.file or ix.file) in owned and acc.line in lines:
+                    hits.append(_acc_id(program.name, ix.name, acc.name))
+            end = ix.body_line + ix.body.count("\n")
+            if _rel(pdir, ix.source_file) in owned and any(ix.line <= line <= end for line in lines):
+                hits.append(_ix_id(program.name, ix.name))
+        hits = [h for h in hits if h in graph.nodes]
+        found += hits or ([f"program:{program.name}"] if f"program:{program.name}" in graph.nodes else [])
+    return list(dict.fromkeys(found))
+
+
+def annotate_findings(graph: PrivilegeGraph, programs: list[Program], findings: list[dict[str, Any]],
+                      source: Path | None = None) -> list[dict[str, Any]]:
+    """Attach ``findings`` to the nodes they point at; returns the findings no node took."""
+    unplaced = []
+    for finding in findings:
+        targets = _targets(graph, programs, finding, source)
+        if not targets:
+            unplaced.append(finding)
+            continue
+        entry = {"id": finding.get("id"), "title": finding.get("title", ""), "severity": _severity(finding)}
+        for node_id in targets:
+            props = graph.nodes[node_id].properties
+            props.setdefault("findings", []).append(entry)
+            worst = min((f["severity"] for f in props["findings"]), key=SEVERITY_ORDER.index)
+            props["max_severity"] = worst
+    return unplaced
+
+
+def _program_of(graph: PrivilegeGraph, node_id: str) -> str | None:
+    node = graph.nodes[node_id]
+    if node.type == "program":
+        return node.label
+    return node.properties.get("program")
+
+
+def _label(graph: PrivilegeGraph, node_id: str) -> str:
+    node = graph.nodes[node_id]
+    label = node.label
+    if node.type == "instruction":
+        label = label.split("::", 1)[-1]
+        if not node.properties.get("authenticated"):
+            label += " (unauthenticated)"
+    elif node.type == "account":
+        label = node.properties.get("name", label)
+        flags = [f for f, on in (("signer", node.properties.get("signer")), ("mut", node.properties.get("writable")))
+                 if on]
+        if flags:
+            label += f" [{', '.join(flags)}]"
+    findings = node.properties.get("findings") or []
+    if findings:
+        label += f"\n{len(findings)} finding{'s' if len(findings) != 1 else ''} ({node.properties['max_severity']})"
+    return label
+
+
+def _edge_label(edge: GraphEdge) -> str:
+    if edge.type in ("gates", "writes_field") and edge.properties.get("field"):
+        return f"{edge.type.replace('_', ' ')} .{edge.properties['field']}"
+    if edge.type == "cpi" and edge.properties.get("pda_signed"):
+        return "cpi (PDA-signed)"
+    return edge.type.replace("_", " ")
+
+
+def _groups(graph: PrivilegeGraph) -> dict[str | None, list[str]]:
+    """Non-program node ids by owning program (None for external nodes)."""
+    groups: dict[str | None, list[str]] = {}
+    for node in graph.nodes.values():
+        if node.type != "program":
+            groups.setdefault(_program_of(graph, node.id), []).append(node.id)
+    return groups
+
+
+def _dot_quote(text: str) -> str:
+    return '"' + text.replace("\\", "\\\\").replace('"', '\\"').replace("\n", "\\n") + '"'
+
+
+def to_dot(graph: PrivilegeGraph, name: str = "programs") -> str:
+    """Graphviz DOT, one cluster per program."""
+    lines = [f"digraph {_dot_quote(name)} {{", "  rankdir=LR;", '  node [fontname="Helvetica", fontsize=10];',
+             '  edge [fontname="Helvetica", fontsize=8];']
+
+    def node_line(node_id: str, indent: str) -> str:
+        node = graph.nodes[node_id]
+        attrs = [f"label={_dot_quote(_label(graph, node_id))}", f"shape={_DOT_SHAPES.get(node.type, 'box')}"]
+        severity = node.properties.get("max_severity")
+        if severity:
+            attrs.append(f'style=filled, fillcolor="{SEVERITY_COLORS[severity]}"')
+        return f"{indent}{_dot_quote(node_id)} [{', '.join(attrs)}];"
+
+    groups = _groups(graph)
+    for i, program in enumerate(graph.of_type("program")):
+        lines.append(f"  subgraph cluster_{i} {{")
+        title = _label(graph, program.id)
+        if program.properties.get("program_id"):
+            title = title.replace(program.label, f"{program.label}\n{program.properties['program_id']}", 1)
+        lines.append(f"    label={_dot_quote(title)};")
+        if program.properties.get("max_severity"):
+            lines.append(f'    style=filled; fillcolor="{SEVERITY_COLORS[program.properties["max_severity"]]}40";')
+        lines += [node_line(node_id, "    ") for node_id in groups.get(program.label, [])]
+        lines.append("  }")
+    lines += [node_line(node_id, "  ") for node_id in groups.get(None, [])]
+    for edge in graph.edges:
+        if edge.type in _DIAGRAM_SKIP:
+            continue
+        style = _DOT_EDGE_STYLES.get(edge.type, "")
+        attrs = f"label={_dot_quote(_edge_label(edge))}" + (f", {style}" if style else "")
+        lines.append(f"  {_dot_quote(edge.source_id)} -> {_dot_quote(edge.target_id)} [{attrs}];")
+    lines.append("}")
+    return "\n".join(lines) + "\n"
+
+
+def _mermaid_text(text: str) -> str:
+    return text.replace('"', "#quot;").replace("\n", "<br/>")
+
+
+def to_mermaid(graph: PrivilegeGraph) -> str:
+    """Mermaid flowchart, one subgraph per program."""
+    ids = {node_id: f"n{i}" for i, node_id in enumerate(graph.nodes)}
+    lines = ["flowchart LR"]
+
+    def node_line(node_id: str, indent: str) -> str:
+        start, end = _MERMAID_SHAPES.get(graph.nodes[node_id].type, ('["', '"]'))
+        return f"{indent}{ids[node_id]}{start}{_mermaid_text(_label(graph, node_id))}{end}"
+
+    groups = _groups(graph)
+    for program in graph.of_type("program"):
+        lines.append(f'  subgraph {ids[program.id]}["{_mermaid_text(_label(graph, program.id))}"]')
+        lines += [node_line(node_id, "    ") for node_id in groups.get(program.label, [])]
+        lines.append("  end")
+    lines += [node_line(node_id, "  ") for node_id in groups.get(None, [])]
+    for edge in graph.edges:
+        if edge.type in _DIAGRAM_SKIP:
+            continue
+        arrow = "==>" if edge.type == "cpi" else "-.->" if edge.type in ("lamport_transfer", "gates") else "-->"
+        lines.append(f'  {ids[edge.source_id]} {arrow}|"{_mermaid_text(_edge_label(edge))}"| {ids[edge.target_id]}')
+    used = set()
+    for node_id, node in graph.nodes.items():
+        severity = node.properties.get("max_severity")
+        if severity:
+            used.add(severity)
+            lines.append(f"  class {ids[node_id]} {severity}")
+    for severity in SEVERITY_ORDER:
+        if severity in used:
+            lines.append(f"  classDef {severity} fill:{SEVERITY_COLORS[severity]}")
+    return "\n".join(lines) + "\n"
+
+
+def to_json(graph: PrivilegeGraph) -> dict[str, Any]:
+    """Knowledge-graph JSON, with findings in the annotated nodes' properties."""
+    data = graph.to_dict()
+    data["stats"]["annotated_nodes"] = sum(1 for n in graph.nodes.values() if n.properties.get("findings"))
+    return data
+
+
+def render_graph(graph: PrivilegeGraph, fmt: str) -> str:
+    """``graph`` in one of ``FORMATS``."""
+    if fmt == "dot":
+        return to_dot(graph)
+    if fmt == "mermaid":
+        return to_mermaid(graph)
+    if fmt == "json":
+        import json
+
+        return json.dumps(to_json(graph), indent=2) + "\n"
+    raise ValueError(f"Unknown graph format '{fmt}' (available: {', '.join(FORMATS)})")
+
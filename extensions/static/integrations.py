@@ -0,0 +1,238 @@
+"""
+Third-party integration assumption checker.
+
+Programs that read Pyth or Switchboard prices, act on SPL Governance
+records, consume Wormhole messages or swap through Jupiter inherit the
+validation their provider documents as the integrator's job: pinning the
+provider's accounts and program, checking owners, magic numbers and
+version fields of accounts parsed by hand, bounding staleness, checking
+emitters and replays, and measuring what a swap delivered.
+
+The requirements come from the knowledge base
+(`extensions/knowledge/integrations/`). For every instruction that uses
+an integration, each requirement is checked against the instruction's
+accounts and handler, or the whole program for requirements usually met
+in a shared helper, and each deviation is reported with the provider's
+documentation.
+
+Built in (no external tool), so it is always available.
+"""
+
+import os
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from extensions.ir import load_programs
+from extensions.ir.model import AccountField, Instruction, Program
+from extensions.knowledge.integration_loader import Integration, IntegrationLoader, IntegrationRequirement
+
+CHECKER_VERSION = "1.0.0"
+
+
+@dataclass
+class IntegrationFinding:
+    """An instruction that deviates from an integration's documented requirement."""
+
+    integration: Integration
+    requirement: IntegrationRequirement
+    program: str
+    instruction: str
+    path: str
+    line: int
+    # The account the requirement was checked for, if any
+    account: str | None = None
+    # The forbidden call, or what showed the integration is used
+    evidence: str = ""
+
+    @property
+    def severity(self) -> str:
+        return self.requirement.severity
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        req = self.requirement
+        subject = f"`{self.account}` in " if self.account else ""
+        description = (
+            f"{subject}`{self.program}::{self.instruction}` integrates {self.integration.name}"
+            + (f" (`{self.evidence}`)" if self.evidence else "")
+            + f". {req.description}"
+            + (f" See {self.integration.docs}." if self.integration.docs else "")
+        )
+        return {
+            "title": f"{req.title} in {self.instruction}" + (f" (`{self.account}`)" if self.account else ""),
+            "description": description,
+            "vulnerability_type": req.vulnerability_type,
+            "severity": req.severity,
+            "confidence": 0.5 if req.severity == "high" else 0.3,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "integrations",
+                "category": self.integration.category,
+                "source_files": [self.path],
+                "affected_lines": [self.line],
+                "integration": self.integration.id,
+                "requirement": req.id,
+                "account": self.account,
+                "docs": self.integration.docs,
+                "precision": "syntactic",
+            },
+        }
+
+
+def _declaration(acc: AccountField) -> str:
+    """`name: Type #[account(...)]`, on one line."""
+    text = f"{acc.name}: {' '.join(acc.ty.split())}"
+    if acc.constraints:
+        text += f" #[account({', '.join(' '.join(c.split()) for c in acc.constraints)})]"
+    return text
+
+
+class _Surface:
+    """An instruction's account declarations (one per line) followed by its handler body."""
+
+    def __init__(self, ix: Instruction):
+        self.ix = ix
+        self.declarations = [_declaration(acc) for acc in ix.accounts]
+        self.head = "".join(d + "\n" for d in self.declarations)
+        self.text = self.head + ix.body
+
+    def locate(self, pos: int) -> tuple[str, int]:
+        """File and line of offset ``pos`` in ``text``."""
+        if pos < len(self.head):
+            acc = self.ix.accounts[self.head.count("\n", 0, pos)]
+            return acc.file or self.ix.file, acc.line
+        body = pos - len(self.head)
+        return self.ix.source_file, self.ix.body_line + self.ix.body.count("\n", 0, body)
+
+
+def _search(patterns: list[str], text: str, account: str | None = None) -> re.Match | None:
+    for pattern in patterns:
+        if account is not None:
+            pattern = pattern.replace("{account}", re.escape(account))
+        elif "{account}" in pattern:
+            continue
+        m = re.search(pattern, text)
+        if m:
+            return m
+    return None
+
+
+def _program_text(program: Program) -> str:
+    texts = []
+    for rel in program.source_files:
+        try:
+            texts.append((Path(program.root) / rel).read_text(errors="replace"))
+        except OSError:
+            continue
+    return "\n".join(texts)
+
+
+def check_instruction(program: Program, ix: Instruction, integrations: list[Integration],
+                      program_text: str | None = None) -> list[IntegrationFinding]:
+    """Deviations of ``ix`` from the requirements of the integrations it uses (paths relative to the program)."""
+    surface = _Surface(ix)
+    findings = []
+    for integration in integrations:
+        used = _search(integration.detect, surface.text)
+        if not used:
+            continue
+        for req in integration.requirements:
+            if req.when and not _search(req.when, surface.text):
+                continue
+            scope = surface.text
+            if req.scope == "program":
+                if program_text is None:
+                    program_text = _program_text(program)
+                scope = surface.text + "\n" + program_text
+            if req.accounts:
+                for acc, declaration in zip(ix.accounts, surface.declarations):
+                    if not re.search(req.accounts, declaration):
+                        continue
+                    if req.account_use and not _search(req.account_use, surface.text, acc.name):
+                        continue
+                    if _search(req.require_account, declaration) or _search(req.require, scope, acc.name):
+                        continue
+                    findings.append(IntegrationFinding(integration, req, program.name, ix.name, acc.file or ix.file,
+                                                       acc.line, acc.name, declaration))
+                continue
+            forbidden = _search(req.forbid, surface.text)
+            if forbidden:
+                match = forbidden
+            elif req.require and not _search(req.require, scope):
+                match = used
+            else:
+                continue
+            file, line = surface.locate(match.start())
+            findings.append(IntegrationFinding(integration, req, program.name, ix.name, file, line,
+                                               evidence=match.group(0).strip()))
+    return findings
+
+
+class IntegrationChecker:
+    """Checks programs against the validation their third-party integrations require."""
+
+    def __init__(self, integrations: list[str] | None = None, min_severity: str = "low"):
+        """Initialize the checker.
+
+        Args:
+            integrations: Integration IDs to check (default: every Solana integration in the knowledge base)
+            min_severity: Lowest requirement severity to report ("high", "medium", "low")
+        """
+        self.integration_ids = integrations
+        self.min_severity = min_severity
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{CHECKER_VERSION}"
+
+    def integrations(self) -> list[Integration]:
+        known = IntegrationLoader().get_by_chain("solana")
+        if self.integration_ids is None:
+            return known
+        return [i for i in known if i.id in self.integration_ids]
+
+    def run(self, project_path: Path) -> tuple[list[IntegrationFinding], dict]:
+        """Check every Solana program under ``project_path``.
+
+        Returns:
+            Tuple of (findings, metadata); metadata["integrations"] lists the instructions using each integration
+        """
+        order = {"high": 3, "medium": 2, "low": 1}
+        floor = order.get(self.min_severity, 1)
+        project_path = Path(project_path)
+        integrations = self.integrations()
+        findings: list[IntegrationFinding] = []
+        used: dict[str, list[str]] = {}
+        for program in load_programs(project_path):
+            if program.chain != "solana":
+                continue
+            root = Path(os.path.relpath(program.root, project_path))
+            program_text = None
+            for ix in program.instructions:
+                surface = _Surface(ix).text
+                hits = [i for i in integrations if _search(i.detect, surface)]
+                if not hits:
+                    continue
+                for integration in hits:
+                    used.setdefault(integration.id, []).append(f"{program.name}::{ix.name}")
+                if program_text is None:
+                    program_text = _program_text(program)
+                for finding in check_instruction(program, ix, hits, program_text):
+                    if order.get(finding.severity, 1) < floor:
+                        continue
+                    if str(root) != ".":
+                        finding.path = (root / finding.path).as_posix()
+                    findings.append(finding)
+        metadata = {
+            "tool": "integrations",
+            "version": CHECKER_VERSION,
+            "success": True,
+            "error": None,
+            "integrations": used,
+            "requirements": {i.id: [r.id for r in i.requirements] for i in integrations},
+        }
+        return findings, metadata
@@ -0,0 +1,230 @@
+"""
+Latency classes for static detectors.
+
+Editor integrations (LSP, watch mode) need feedback on every change, so
+detectors are split by what one run costs:
+
+- hot: syntactic rules over a single file's text that finish within a
+  10 ms budget; run synchronously on every change
+- warm: built-in analyses that load the whole program IR or workspace;
+  scheduled in the background once an edit lands
+- cold: external tools (subprocess analyzers, provers); scheduled in the
+  background with the warm analyses
+
+`HotPathRunner` runs the hot rules of a pipeline against one file and times
+each. A rule that overruns the budget on a file is deferred for that file
+from then on, so large files stay instant; `BackgroundScheduler` picks the
+deferred rules up with the warm and cold detectors.
+"""
+
+import os
+import threading
+import time
+from concurrent.futures import Future, ThreadPoolExecutor
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Callable, Iterable
+
+from .confidence import calibrate_hypothesis
+from .pipeline import PipelineResult, StaticAnalysisPipeline
+
+LATENCY_CLASSES = ("hot", "warm", "cold")
+HOT_BUDGET_MS = 10.0
+
+# Class of each pipeline tool; a runner's own ``latency`` attribute takes precedence
+DETECTOR_LATENCY = {
+    "slither": "cold",
+    "aderyn": "cold",
+    "soteria": "cold",
+    "cargo-audit": "cold",
+    "unsafe-rust": "hot",
+    # Sizes types declared anywhere in the crate
+    "sbf-limits": "warm",
+    "account-growth": "warm",
+    "pda-collisions": "warm",
+    "cpi-privileges": "warm",
+    "privilege-paths": "warm",
+    "anchor-toml": "warm",
+    "integrations": "warm",
+    "missing-signer": "warm",
+    "missing-owner": "warm",
+    "type-cosplay": "warm",
+    "duplicate-mutable": "warm",
+    "arbitrary-cpi": "warm",
+    "cpi-targets": "warm",
+    "unchecked-math": "warm",
+    # Loads a second version of the workspace, from git when the baseline is a ref
+    "state-migration": "cold",
+    "program-ids": "warm",
+    "feature-gates": "warm",
+    "protocol-params": "warm",
+    "move-prover": "cold",
+    "sui-move-lint": "cold",
+}
+
+
+def latency_of(tool: str, runner: Any = None) -> str:
+    """Latency class of a pipeline tool (unknown tools are cold)."""
+    declared = getattr(runner, "latency", None)
+    if declared in LATENCY_CLASSES:
+        return declared
+    return DETECTOR_LATENCY.get(tool, "cold")
+
+
+def partition(pipeline: StaticAnalysisPipeline) -> dict[str, list[str]]:
+    """The pipeline's tools grouped by latency class."""
+    groups: dict[str, list[str]] = {name: [] for name in LATENCY_CLASSES}
+    for tool, runner in pipeline.runners.items():
+        groups[latency_of(tool, runner)].append(tool)
+    return groups
+
+
+@dataclass
+class HotPathResult:
+    """Hot-rule hits for one file."""
+
+    path: str
+    hypotheses: list[dict] = field(default_factory=list)
+    timings_ms: dict[str, float] = field(default_factory=dict)
+    # Rules skipped on this file because they overran the budget on it
+    deferred: list[str] = field(default_factory=list)
+    budget_ms: float = HOT_BUDGET_MS
+
+    @property
+    def elapsed_ms(self) -> float:
+        return sum(self.timings_ms.values())
+
+    @property
+    def over_budget(self) -> list[str]:
+        """Rules that overran the budget on this check."""
+        return [tool for tool, ms in self.timings_ms.items() if ms > self.budget_ms]
+
+
+class HotPathRunner:
+    """Runs a pipeline's hot rules synchronously against single files."""
+
+    def __init__(self, pipeline: StaticAnalysisPipeline, budget_ms: float = HOT_BUDGET_MS):
+        """Initialize the runner.
+
+        Args:
+            pipeline: Pipeline whose hot runners (those with ``check_source``) are used
+            budget_ms: Per-rule, per-file budget; overrunning it defers the rule for that file
+        """
+        self.pipeline = pipeline
+        self.budget_ms = budget_ms
+        self.rules = {
+            tool: runner for tool, runner in pipeline.runners.items()
+            if latency_of(tool, runner) == "hot" and hasattr(runner, "check_source")
+        }
+        self._deferred: dict[str, set[str]] = {}
+
+    def deferred(self, path: str | None = None) -> set[str]:
+        """Rules deferred for ``path``, or for any file."""
+        if path is not None:
+            return set(self._deferred.get(path, ()))
+        return set().union(*self._deferred.values()) if self._deferred else set()
+
+    def reset(self, path: str | None = None) -> None:
+        """Give deferred rules another try on ``path`` (default: every file)."""
+        if path is None:
+            self._deferred.clear()
+        else:
+            self._deferred.pop(path, None)
+
+    def check_file(self, path: Path | str, text: str | None = None, root: Path | None = None) -> HotPathResult:
+        """Run the hot rules against one file.
+
+        Args:
+            path: File to check
+            text: Unsaved editor contents (default: read ``path``)
+            root: Project root finding paths are made relative to
+
+        Returns:
+            HotPathResult with calibrated hypotheses and per-rule timings
+        """
+        path = Path(path)
+        rel = Path(os.path.relpath(path, root)).as_posix() if root else path.as_posix()
+        result = HotPathResult(path=rel, budget_ms=self.budget_ms)
+        if text is None:
+            try:
+                text = path.read_text(errors="replace")
+            except OSError:
+                return result
+
+        skipped = self._deferred.get(rel, set())
+        for tool, runner in self.rules.items():
+            if path.suffix not in getattr(runner, "source_suffixes", (path.suffix,)):
+                continue
+            if tool in skipped:
+                result.deferred.append(tool)
+                continue
+            start = time.perf_counter()
+            findings = runner.check_source(text, rel)
+            result.timings_ms[tool] = (time.perf_counter() - start) * 1000
+            for finding in findings:
+                hyp = finding.to_hypothesis()
+                hyp["id"] = self.pipeline._generate_hypothesis_id(hyp, tool)
+                calibrate_hypothesis(hyp, tool, self.pipeline.calibration)
+                result.hypotheses.append(hyp)
+        for tool in result.over_budget:
+            self._deferred.setdefault(rel, set()).add(tool)
+        return result
+
+
+class BackgroundScheduler:
+    """Runs the warm and cold detectors of a pipeline off the caller's thread."""
+
+    def __init__(self, pipeline: StaticAnalysisPipeline, hot: HotPathRunner | None = None, max_workers: int = 1):
+        """Initialize the scheduler.
+
+        Args:
+            pipeline: Pipeline whose detectors are run
+            hot: Hot-path runner whose deferred rules are scheduled too
+            max_workers: Concurrent background runs (one keeps runs ordered)
+        """
+        self.pipeline = pipeline
+        self.hot = hot
+        self._pool = ThreadPoolExecutor(max_workers=max_workers, thread_name_prefix="static-bg")
+        self._pending: dict[str, Future] = {}
+        self._lock = threading.Lock()
+
+    def tools(self) -> list[str]:
+        """Tools a background run covers: every warm and cold tool, plus deferred hot rules."""
+        groups = partition(self.pipeline)
+        deferred = self.hot.deferred() if self.hot else set()
+        return [t for t in self.pipeline.runners if t in deferred or t not in groups["hot"]]
+
+    def schedule(
+        self,
+        project_path: Path,
+        tools: Iterable[str] | None = None,
+        callback: Callable[[PipelineResult], None] | None = None,
+    ) -> Future:
+        """Queue a background run for ``project_path``.
+
+        A run still queued for the same project is cancelled, so a burst of
+        edits costs one analysis rather than one per keystroke.
+
+        Args:
+            project_path: Project to analyze
+            tools: Tools to run (default: ``tools()``)
+            callback: Called with the PipelineResult when the run completes
+
+        Returns:
+            Future resolving to the PipelineResult
+        """
+        selected = list(tools) if tools is not None else self.tools()
+        key = str(Path(project_path).resolve())
+        with self._lock:
+            previous = self._pending.get(key)
+            if previous is not None:
+                previous.cancel()
+            future = self._pool.submit(self.pipeline.run, Path(project_path), selected)
+            self._pending[key] = future
+        if callback is not None:
+            future.add_done_callback(lambda f: None if f.cancelled() or f.exception() else callback(f.result()))
+        return future
+
+    def shutdown(self, wait: bool = True) -> None:
+        """Stop accepting runs; cancels queued ones."""
+        self._pool.shutdown(wait=wait, cancel_futures=True)
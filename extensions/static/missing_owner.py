@@ -0,0 +1,246 @@
+"""
+Missing account owner check detector for Solana programs.
+
+Any program can create an account with any data. An `#[account]` type
+deserialized through `Account<'info, T>` is checked against the program id
+by Anchor, but a handler that borrows the data of a raw `AccountInfo` /
+`UncheckedAccount` and parses it itself (`T::try_from_slice`,
+`T::try_deserialize`, `Pack::unpack`, `bytemuck::from_bytes`, a pointer
+cast) trusts whatever the caller passes in: an attacker creates an account
+with the same layout under their own program and hands it over as the
+protocol's config, oracle or token account.
+
+Walks every instruction of Anchor and native programs through the program
+IR and flags raw accounts whose data reaches a deserializer while nothing
+ties the account to its owner: an `owner = ..` constraint, a handler check
+of `acc.owner` against the program id (or another program's `ID`), a
+`seeds` or `address` constraint, or a key compared against stored state.
+`Account::try_from_unchecked` is flagged too. Reads and owner checks count
+through locals bound to the account (`let info = &ctx.accounts.cfg;`); type
+cosplay shares them. Each finding links to the `missing_owner_check` PoC
+template, with the program id filled in where `declare_id!` gives it.
+
+Built in (no external tool), so it is always available.
+"""
+
+import os
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from extensions.ir import load_programs
+from extensions.ir.model import AccountField, Instruction, Program
+
+from .missing_signer import _RAW_WRAPPERS
+from .privilege_paths import _key_checked, _referenced
+
+DETECTOR_VERSION = "1.0.0"
+TEMPLATE_ID = "missing_owner_check"
+TEMPLATE_FILE = "extensions/knowledge/templates/solana/missing_owner_check.rs"
+
+_ACCOUNT = r"(?:ctx\s*\.\s*accounts\s*\.\s*)?\b{name}\s*(?:\.\s*to_account_info\s*\(\s*\)\s*)?"
+# `let info = &ctx.accounts.cfg;`, `let info = ctx.accounts.cfg.to_account_info();`
+_ALIAS_RE = (r"\blet\s+(?:mut\s+)?(?P<alias>[A-Za-z_]\w*)\s*(?::[^=;]+)?=\s*&?\s*(?:mut\s+)?"
+             r"ctx\s*\.\s*accounts\s*\.\s*{name}\s*(?:\.\s*(?:to_account_info|clone)\s*\(\s*\)\s*)*;")
+# `acc.data.borrow()`, `acc.try_borrow_data()?`, `acc.data.try_borrow_mut()`
+_BORROW = r"\.\s*(?:data\s*\.\s*(?:try_)?borrow(?:_mut)?|try_borrow(?:_mut)?_data)\s*\(\s*\)"
+# `Vault::try_from_slice(..)`, `Mint::unpack(..)`, `bytemuck::from_bytes::<Pool>(..)`
+_DESERIALIZE_RE = re.compile(
+    r"(?:\b(?P<ty>[A-Z]\w*)(?:\s*::\s*<[^>]*>)?\s*::\s*)?"
+    r"\b(?P<call>try_from_slice|try_deserialize(?:_unchecked)?|deserialize|unpack(?:_unchecked|_from_slice)?"
+    r"|(?:try_)?from_bytes(?:_mut)?)\s*(?:::\s*<\s*(?P<arg>\w+)\s*>)?\s*\("
+)
+# `&*(data.as_ptr() as *const Pool)`
+_CAST_RE = re.compile(r"as\s*\*\s*(?:const|mut)\s+(?P<ty>\w+)")
+_UNCHECKED_TRY_FROM_RE = (r"\bAccount\s*(?:::\s*<[^>]*?(?P<ty>\w+)\s*>)?\s*::\s*try_from_unchecked\s*\(\s*&?\s*"
+                          + _ACCOUNT)
+# The other side of an owner comparison names a program id
+_PROGRAM_ID_RE = re.compile(r"\bprogram_id\b|\bID\b|\bid\s*\(\s*\)")
+
+
+@dataclass
+class MissingOwnerFinding:
+    """Data of a raw account deserialized without checking which program owns it."""
+
+    program: str
+    instruction: str
+    account: str
+    account_type: str
+    path: str
+    line: int  # Where the data is deserialized
+    text: str
+    state_type: str | None = None  # The type the data is parsed as, when it can be read off the call
+    account_line: int = 0
+    program_id: str | None = None
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        parsed = f"as `{self.state_type}`" if self.state_type else "itself"
+        context = {"PROGRAM_ID": self.program_id} if self.program_id else {}
+        program_id = self.program_id or "<program id>"
+        description = (
+            f"`{self.program}::{self.instruction}` parses the data of `{self.account}` ({self.account_type}) "
+            f"{parsed} (`{self.text}`) without checking that the program owns the account. An attacker creates an "
+            f"account with the same layout under a program they control and passes it in, so the handler acts on "
+            f"forged state. Use `Account<'info, T>`, add an `owner = crate::ID` constraint, or check "
+            f"`{self.account}.owner` against the program id before deserializing. PoC: `kb render {TEMPLATE_ID} "
+            f"-s PROGRAM_ID={program_id} -s VAULT_ACCOUNT=<pubkey>`."
+        )
+        return {
+            "title": f"Missing owner check on `{self.account}` in {self.instruction}",
+            "description": description,
+            "vulnerability_type": "missing-owner-check",
+            "severity": "high",
+            "confidence": 0.6,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "missing-owner",
+                "category": "account-validation",
+                "source_files": [self.path],
+                "affected_lines": [self.line],
+                "program": self.program,
+                "instruction": self.instruction,
+                "account": self.account,
+                "account_line": self.account_line,
+                "state_type": self.state_type,
+                "poc_template": TEMPLATE_ID,
+                "poc_template_file": TEMPLATE_FILE,
+                "poc_context": context,
+                "precision": "syntactic",
+            },
+        }
+
+
+def _statement(body: str, pos: int) -> tuple[int, int]:
+    """Bounds of the statement around ``pos``; `unsafe { .. }` blocks stay part of it."""
+    start = pos
+    while True:
+        start = max(body.rfind(";", 0, start), body.rfind("{", 0, start), body.rfind("}", 0, start))
+        if start < 0 or body[start] != "{" or not body[:start].rstrip().endswith("unsafe"):
+            break
+    start += 1
+    start += len(body[start:pos]) - len(body[start:pos].lstrip())
+    end = body.find(";", pos)
+    return start, end if end >= 0 else len(body)
+
+
+def _deserializer(text: str) -> str | None:
+    """The type ``text`` parses account data into ("" when the call does not name it), or None."""
+    m = _DESERIALIZE_RE.search(text)
+    if m:
+        return m.group("ty") or m.group("arg") or ""
+    m = _CAST_RE.search(text)
+    return m.group("ty") if m else None
+
+
+def _local_read(body: str, start: int, end: int, depth: int = 2) -> tuple[int, int, str] | None:
+    """The statement deserializing the local bound at ``body[start:end]``, following re-slices."""
+    # `let data = acc.try_borrow_data()?;` then `T::try_from_slice(&data[8..])`, or via `let rest = &data[8..];`
+    local = re.match(r"\s*let\s+(?:mut\s+)?(\w+)\s*(?::[^=]+)?=", body[start:end])
+    if not local:
+        return None
+    for use in re.finditer(rf"\b{re.escape(local.group(1))}\b", body[end:]):
+        use_start, use_end = _statement(body, end + use.start())
+        ty = _deserializer(body[use_start:use_end])
+        if ty is not None:
+            return use_start, use_end, ty
+        if depth > 1:
+            found = _local_read(body, use_start, use_end, depth - 1)
+            if found:
+                return found
+    return None
+
+
+def _names(ix: Instruction, acc: AccountField) -> str:
+    """Pattern for ``acc``'s name and the locals ``ix`` binds to it (`let info = &ctx.accounts.cfg;`)."""
+    names = [acc.name] + [m.group("alias") for m in re.finditer(_ALIAS_RE.format(name=re.escape(acc.name)), ix.body)]
+    return "(?:" + "|".join(re.escape(n) for n in dict.fromkeys(names)) + ")"
+
+
+def _reads(ix: Instruction, acc: AccountField) -> list[tuple[int, str, str | None]]:
+    """Places ``ix`` deserializes the data of ``acc``, itself or a local alias: (offset, statement, parsed type)."""
+    body, found = ix.body, []
+    account = _ACCOUNT.format(name=_names(ix, acc))
+    for m in re.finditer(account + _BORROW, body):
+        start, end = _statement(body, m.start())
+        ty = _deserializer(body[start:end])
+        if ty is None:
+            local = _local_read(body, start, end)
+            if not local:
+                continue
+            start, end, ty = local
+        found.append((start, " ".join(body[start:end].split()), ty or None))
+    for m in re.finditer(_UNCHECKED_TRY_FROM_RE.format(name=_names(ix, acc)), body):
+        start, end = _statement(body, m.start())
+        found.append((start, " ".join(body[start:end].split()), m.group("ty")))
+    return found
+
+
+def _owner_checked(ix: Instruction, acc: AccountField) -> bool:
+    """Whether a constraint or the handler ties ``acc`` to its owning program."""
+    if any(re.match(r"(?:owner|address)\s*=", c) for c in acc.constraints):
+        return True
+    owner = _ACCOUNT.format(name=_names(ix, acc)) + r"\.\s*owner\b"
+    for m in re.finditer(owner, ix.body):
+        start, end = _statement(ix.body, m.start())
+        text = ix.body[start:end]
+        if re.search(r"[!=]=|require_keys_eq!|assert_owned_by|check_program_account", text) and _PROGRAM_ID_RE.search(
+                text[:m.start() - start] + text[m.end() - start:]):
+            return True
+    return False
+
+
+def check_instruction(program: Program, ix: Instruction) -> list[MissingOwnerFinding]:
+    """Raw accounts of ``ix`` deserialized without an owner check (paths relative to the program)."""
+    findings = []
+    for acc in ix.accounts:
+        if acc.wrapper not in _RAW_WRAPPERS or acc.seeds is not None:
+            continue
+        reads = _reads(ix, acc)
+        if not reads or _owner_checked(ix, acc) or _referenced(ix, acc) or _key_checked(ix, acc):
+            continue
+        offset, text, ty = min(reads)
+        findings.append(MissingOwnerFinding(program.name, ix.name, acc.name, " ".join(acc.ty.split()),
+                                            ix.source_file, ix.body_line + ix.body.count("\n", 0, offset), text,
+                                            ty, acc.line, program.program_id))
+    return findings
+
+
+class MissingOwnerDetector:
+    """Flags raw `AccountInfo`/`UncheckedAccount` data deserialized without checking the account's owner."""
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{DETECTOR_VERSION}"
+
+    def run(self, project_path: Path) -> tuple[list[MissingOwnerFinding], dict]:
+        """Check every Anchor and native Solana program under ``project_path``.
+
+        Returns:
+            Tuple of (findings, metadata)
+        """
+        project_path = Path(project_path)
+        findings: list[MissingOwnerFinding] = []
+        checked = 0
+        for program in load_programs(project_path):
+            if program.chain != "solana" or program.framework not in ("anchor", "native"):
+                continue
+            root = Path(os.path.relpath(program.root, project_path))
+            for ix in program.instructions:
+                checked += 1
+                for finding in check_instruction(program, ix):
+                    if str(root) != ".":
+                        finding.path = (root / finding.path).as_posix()
+                    findings.append(finding)
+        metadata = {
+            "tool": "missing-owner",
+            "version": DETECTOR_VERSION,
+            "success": True,
+            "error": None,
+            "instructions_checked": checked,
+            "template": TEMPLATE_ID,
+        }
+        return findings, metadata
@@ -0,0 +1,282 @@
+"""
+Missing signer check detector for Anchor programs.
+
+Walks the `#[derive(Accounts)]` struct and handler of every Anchor
+instruction through the program IR and flags raw `AccountInfo` /
+`UncheckedAccount` fields the instruction treats as its authority while it
+mutates privileged state. An account is treated as the authority when
+state is bound to it (`has_one`), a constraint or the handler compares it
+against a stored key, or it is named like one (`authority`, `admin`,
+`owner`, ...). Privileged state mutation is a write to a field of an
+existing account, a lamport debit or close, or a PDA-signed CPI. Unless the
+field is a `Signer`, carries a `signer` constraint or the handler checks
+`is_signer`, anyone can pass the stored key and act as the authority.
+
+Program accounts are not authorities: a field the instruction invokes as a
+CPI program, or whose key is only compared against a fixed id (`ID`,
+`crate::id()`, a const `Pubkey`, `pubkey!(..)`), is never reported.
+
+Each finding links to the `missing_signer` PoC template, with the program
+id filled in where `declare_id!` gives it.
+
+Built in (no external tool), so it is always available.
+"""
+
+import os
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+from extensions.ir import load_programs
+from extensions.ir.model import AccountField, Instruction, Program
+from extensions.ir.rust_source import find_matching, split_top_level
+
+from .cpi_privileges import analyze_instruction
+from .privilege_paths import _lamport_flows, _state_type
+
+DETECTOR_VERSION = "1.0.0"
+TEMPLATE_ID = "missing_signer"
+TEMPLATE_FILE = "extensions/knowledge/templates/solana/missing_signer.rs"
+
+# Wrappers that neither deserialize nor check anything about the account
+_RAW_WRAPPERS = {"AccountInfo", "UncheckedAccount"}
+# `vault.balance = ..`, `vault.balance -= ..` (through `ctx.accounts.` or a local alias of the account)
+_FIELD_WRITE_RE = re.compile(
+    r"(?:ctx\s*\.\s*accounts\s*\.\s*)?\b(?P<acc>[A-Za-z_]\w*)\s*\.\s*(?P<field>[A-Za-z_]\w*)"
+    r"\s*(?:[-+*/%|&^]|<<|>>)?=(?!=)"
+)
+# One side of a key comparison: `ctx.accounts.config.admin`, `admin.key()`, `crate::ID`, `pubkey!("..")`
+_OPERAND = r"(?:[*&]\s*)*(?:\w+!\s*\([^()]*\)|[\w:]+(?:\s*\(\s*\))?(?:\s*\.\s*\w+(?:\s*\(\s*\))?)*)"
+_COMPARISON_RE = re.compile(rf"(?P<left>{_OPERAND})\s*[!=]=\s*(?P<right>{_OPERAND})")
+# A fixed id rather than stored state: `ID`, `crate::ID`, `id()`, `PLUGIN_ID`, `pubkey!("..")`
+_CONSTANT_KEY_RE = re.compile(r"(?:\w+::)*(?:[A-Z][A-Z0-9_]*|id\s*\(\s*\))|\w+!\s*\(.*\)", re.S)
+_AUTHORITY_NAME_RE = re.compile(
+    r"(?:^|_)(?:authority|owner|admin|governance|governor|manager|operator|guardian)(?:$|_)"
+)
+
+
+@dataclass
+class Gate:
+    """How the instruction treats an account as its authority."""
+
+    kind: str  # has_one, constraint, key-check, name
+    line: int
+    text: str
+
+
+@dataclass
+class Mutation:
+    """A privileged state change the instruction makes."""
+
+    kind: str  # field-write, lamport-debit, close, pda-signed-cpi
+    line: int
+    text: str
+
+
+@dataclass
+class MissingSignerFinding:
+    """A raw account the instruction treats as its authority without requiring its signature."""
+
+    program: str
+    instruction: str
+    account: str
+    account_type: str
+    path: str
+    line: int
+    gates: list[Gate] = field(default_factory=list)
+    mutations: list[Mutation] = field(default_factory=list)
+    program_id: str | None = None
+
+    @property
+    def severity(self) -> str:
+        moves_funds = any(m.kind in ("lamport-debit", "close", "pda-signed-cpi") for m in self.mutations)
+        return "critical" if moves_funds and self.bound else "high"
+
+    @property
+    def bound(self) -> bool:
+        """Whether state or a key check (not just the name) ties the account to the authority."""
+        return any(g.kind != "name" for g in self.gates)
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        gate = self.gates[0]
+        how = {
+            "has_one": f"state is bound to it (`{gate.text}`)",
+            "constraint": f"a constraint compares it against stored state (`{gate.text}`)",
+            "key-check": f"the handler compares its key (`{gate.text}`)",
+            "name": "it is named as the authority",
+        }[gate.kind]
+        changes = "; ".join(f"{m.text} (line {m.line})" for m in self.mutations[:3])
+        context = {"PROGRAM_ID": self.program_id} if self.program_id else {}
+        program_id = self.program_id or "<program id>"
+        description = (
+            f"`{self.program}::{self.instruction}` treats `{self.account}` ({self.account_type}) as its authority — "
+            f"{how} — but never requires it to sign. Knowing the authority's public key is enough to pass the "
+            f"check and make the instruction's privileged changes: {changes}. Declare `{self.account}` as "
+            f"`Signer<'info>` (or add a `signer` constraint). PoC: `kb render {TEMPLATE_ID} "
+            f"-s PROGRAM_ID={program_id} -s VAULT_ACCOUNT=<pubkey>`."
+        )
+        return {
+            "title": f"Missing signer check on `{self.account}` in {self.instruction}",
+            "description": description,
+            "vulnerability_type": "missing-signer-check",
+            "severity": self.severity,
+            "confidence": 0.7 if self.bound else 0.4,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "missing-signer",
+                "category": "access-control",
+                "source_files": [self.path],
+                "affected_lines": [self.line],
+                "program": self.program,
+                "instruction": self.instruction,
+                "account": self.account,
+                "gates": [g.__dict__ for g in self.gates],
+                "mutations": [m.__dict__ for m in self.mutations],
+                "poc_template": TEMPLATE_ID,
+                "poc_template_file": TEMPLATE_FILE,
+                "poc_context": context,
+                "precision": "semantic" if self.bound else "syntactic",
+            },
+        }
+
+
+def _key_comparisons(text: str, acc: AccountField, fixed: bool = False) -> list[tuple[int, str]]:
+    """(offset, text) of each comparison of ``acc``'s key against stored state (a fixed id with ``fixed``)."""
+    key = re.compile(rf"\b{re.escape(acc.name)}\s*\.\s*key\b")
+    found = []
+    for m in re.finditer(r"require_keys_(?:eq|neq)!\s*\(", text):
+        end = find_matching(text, m.end() - 1)
+        sides = [a.strip() for a in split_top_level(text[m.end():end])[:2]]
+        if len(sides) == 2 and any(key.search(s) for s in sides):
+            other = sides[1] if key.search(sides[0]) else sides[0]
+            if bool(_CONSTANT_KEY_RE.fullmatch(other.lstrip("*& "))) == fixed:
+                found.append((m.start(), text[m.start():end + 1]))
+    for m in _COMPARISON_RE.finditer(text):
+        left, right = m.group("left"), m.group("right")
+        if key.search(left) or key.search(right):
+            other = right if key.search(left) else left
+            if bool(_CONSTANT_KEY_RE.fullmatch(other.lstrip("*& "))) == fixed:
+                found.append((m.start(), m.group(0)))
+    return sorted(found)
+
+
+def program_account(program: Program, ix: Instruction, acc: AccountField) -> bool:
+    """Whether ``acc`` is a program ``ix`` invokes or pins to a fixed id, which can never sign."""
+    constraints = [c for other in ix.accounts for c in other.constraints if c.startswith("constraint")]
+    return (any(site.target_account == acc.name for site in analyze_instruction(program, ix))
+            or any(_key_comparisons(text, acc, fixed=True) for text in [ix.body, *constraints]))
+
+
+def _gates(ix: Instruction, acc: AccountField) -> list[Gate]:
+    gates = []
+    for other in ix.accounts:
+        for c in other.constraints:
+            text = " ".join(c.split())
+            if re.fullmatch(rf"has_one\s*=\s*{re.escape(acc.name)}(?:\s*@.*)?", text):
+                gates.append(Gate("has_one", other.line, f"{other.name}: {text}"))
+            elif text.startswith("constraint") and _key_comparisons(text, acc):
+                gates.append(Gate("constraint", other.line, text))
+    checks = _key_comparisons(ix.body, acc)
+    if checks:
+        offset, text = checks[0]
+        gates.append(Gate("key-check", ix.body_line + ix.body.count("\n", 0, offset), " ".join(text.split())))
+    if not gates and _AUTHORITY_NAME_RE.search(acc.name.lower()):
+        gates.append(Gate("name", acc.line, acc.name))
+    return gates
+
+
+def _mutations(program: Program, ix: Instruction) -> list[Mutation]:
+    found = []
+    for m in _FIELD_WRITE_RE.finditer(ix.body):
+        acc = ix.account(m.group("acc"))
+        if acc is None or not _state_type(acc) or any(c.startswith("init") for c in acc.constraints):
+            continue
+        end = ix.body.find(";", m.end())
+        text = " ".join(ix.body[m.start():end if end >= 0 else m.end()].split())
+        found.append(Mutation("field-write", ix.body_line + ix.body.count("\n", 0, m.start()), text))
+    for debited, _, how, line in _lamport_flows(ix):
+        kind = "close" if how == "close" else "lamport-debit"
+        found.append(Mutation(kind, line, f"closes `{debited}`" if kind == "close" else f"debits `{debited}` lamports"))
+    for site in analyze_instruction(program, ix):
+        if site.pda_signed:
+            found.append(Mutation("pda-signed-cpi", site.line, f"PDA-signed CPI to {site.callee or site.target}"))
+    return found
+
+
+def _signature_checked(ix: Instruction, acc: AccountField) -> bool:
+    return bool(re.search(rf"\b{re.escape(acc.name)}\s*\.\s*is_signer\b", ix.body))
+
+
+def check_instruction(program: Program, ix: Instruction) -> list[MissingSignerFinding]:
+    """Raw authority accounts of ``ix`` that are never required to sign (paths relative to the program)."""
+    candidates = [
+        acc for acc in ix.accounts
+        if acc.wrapper in _RAW_WRAPPERS and not acc.is_signer and acc.seeds is None
+        and not _signature_checked(ix, acc)
+    ]
+    if not candidates:
+        return []
+    mutations = _mutations(program, ix)
+    if not mutations:
+        return []
+    findings = []
+    for acc in candidates:
+        # A program the instruction invokes or pins to a fixed id is an executable account, never a signer
+        if program_account(program, ix, acc):
+            continue
+        gates = _gates(ix, acc)
+        if gates:
+            findings.append(MissingSignerFinding(program.name, ix.name, acc.name, " ".join(acc.ty.split()),
+                                                 acc.file or ix.file, acc.line, gates, mutations, program.program_id))
+    return findings
+
+
+class MissingSignerDetector:
+    """Flags Anchor authority accounts typed `AccountInfo`/`UncheckedAccount` instead of `Signer`."""
+
+    def __init__(self, include_name_only: bool = True):
+        """Initialize the detector.
+
+        Args:
+            include_name_only: Also report accounts that are authorities only by name (lower confidence)
+        """
+        self.include_name_only = include_name_only
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{DETECTOR_VERSION}"
+
+    def run(self, project_path: Path) -> tuple[list[MissingSignerFinding], dict]:
+        """Check every Anchor program under ``project_path``.
+
+        Returns:
+            Tuple of (findings, metadata)
+        """
+        project_path = Path(project_path)
+        findings: list[MissingSignerFinding] = []
+        checked = 0
+        for program in load_programs(project_path):
+            if program.chain != "solana" or program.framework != "anchor":
+                continue
+            root = Path(os.path.relpath(program.root, project_path))
+            for ix in program.instructions:
+                checked += 1
+                for finding in check_instruction(program, ix):
+                    if not finding.bound and not self.include_name_only:
+                        continue
+                    if str(root) != ".":
+                        finding.path = (root / finding.path).as_posix()
+                    findings.append(finding)
+        metadata = {
+            "tool": "missing-signer",
+            "version": DETECTOR_VERSION,
+            "success": True,
+            "error": None,
+            "instructions_checked": checked,
+            "template": TEMPLATE_ID,
+        }
+        return findings, metadata
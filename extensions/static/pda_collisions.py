@@ -0,0 +1,271 @@
+"""
+PDA seed collision scanner.
+
+Pipeline wrapper around the workspace PDA map (`extensions.ir.pda_map`):
+loads every Solana program under the project, so sibling programs in one
+workspace are compared with each other, and reports seed schemas of
+different account types that can derive the same address, plus constant
+seed namespaces reused across programs or with well-known programs.
+
+Collisions and shared prefixes are the check behind the `pda_seed_collision`
+template: each finding links it and, where that separates the two,
+suggests the template's fix of a type-specific leading seed for both.
+"""
+
+import os
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+from extensions.ir import load_programs
+from extensions.ir.pda_map import (
+    PdaConflict,
+    PdaDerivation,
+    Seed,
+    SeedNamespaceReuse,
+    build_pda_map,
+    find_conflicts,
+    find_namespace_reuse,
+    patterns_overlap,
+    seed_constants,
+)
+
+SCANNER_VERSION = "1.0.0"
+TEMPLATE_ID = "pda_seed_collision"
+TEMPLATE_FILE = "extensions/knowledge/templates/solana/pda_seed_collision.rs"
+
+
+def type_seed(account_type: str) -> str:
+    """Type-specific seed prefix for an account type (``UserProfile`` -> ``user_profile``)."""
+    return re.sub(r"(?<=[a-z0-9])(?=[A-Z])", "_", account_type).lower()
+
+
+def _retyped(derivation: PdaDerivation) -> list[Seed]:
+    """The derivation's seeds with its leading constant replaced by a type-specific one."""
+    prefix = type_seed(derivation.account_type)
+    rest = derivation.seeds[1:] if derivation.seeds and derivation.seeds[0].kind == "const" else derivation.seeds
+    return [Seed("const", f'b"{prefix}"', prefix), *rest]
+
+
+def _glob(seeds: list[Seed]) -> list[str]:
+    return [ch for seed in seeds for ch in seed.glob()]
+
+
+def suggested_seeds(derivation: PdaDerivation) -> str:
+    """Seeds constraint with a type-specific leading constant."""
+    return "seeds = [" + ", ".join(s.expr for s in _retyped(derivation)) + "]"
+
+
+@dataclass
+class PdaCollisionFinding:
+    """Two PDA derivations for different account types that can alias."""
+
+    conflict: PdaConflict
+    # Program name -> program root relative to the scanned project
+    roots: dict[str, str] = field(default_factory=dict)
+
+    @property
+    def kind(self) -> str:
+        return self.conflict.kind
+
+    @property
+    def severity(self) -> str:
+        return self.conflict.severity
+
+    @property
+    def suggested_fix(self) -> dict[str, str] | None:
+        """Account type -> seeds with a type-specific prefix, for same-program conflicts.
+
+        None when new prefixes would not separate the two, i.e. a
+        variable-length seed can still spell the other type's prefix.
+        """
+        a, b = self.conflict.a, self.conflict.b
+        if self.kind == "cross-program":
+            return None
+        if patterns_overlap(_glob(_retyped(a)), _glob(_retyped(b))):
+            return None
+        return {d.account_type: suggested_seeds(d) for d in (a, b)}
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        a, b = self.conflict.a, self.conflict.b
+        if self.kind == "collision":
+            title = f"PDA seed collision between {a.account_type} and {b.account_type}"
+            description = (
+                f"`{a.account_type}` ({a.program}::{a.instruction}: {a.schema}) and `{b.account_type}` "
+                f"({b.program}::{b.instruction}: {b.schema}) are derived under `{a.owner}` from seeds that "
+                f"can produce the same bytes, so both types can live at the same address. An attacker can "
+                f"initialize one where the other is expected"
+                + (", and an unchecked account makes this a type confusion." if self.severity == "high"
+                   else ", blocking its creation.")
+            )
+        elif self.kind == "prefix":
+            title = f"PDA seed schemas of {a.account_type} and {b.account_type} share a prefix"
+            description = (
+                f"`{a.schema}` ({a.account_type}) and `{b.schema}` ({b.account_type}) share a seed prefix under "
+                f"`{a.owner}`. They do not collide today, but any change that drops or widens a trailing seed will."
+            )
+        else:
+            title = f"Sibling programs share PDA schema {a.schema}"
+            description = (
+                f"`{a.program}` derives `{a.account_type}` and `{b.program}` derives `{b.account_type}` from the "
+                f"same schema ({a.schema}). A consumer that validates these seeds without `seeds::program` (or an "
+                f"owner check) accepts either program's account."
+            )
+        fix = self.suggested_fix
+        if fix:
+            description += " Fix: " + "; ".join(f"`{seeds}` for {ty}" for ty, seeds in fix.items()) + "."
+        if self.kind != "cross-program":
+            description += f" PoC: `kb render {TEMPLATE_ID}`."
+        files = [self._path(a.program, a.file), self._path(b.program, b.file)]
+        properties = {
+            "source_tool": "pda-collisions",
+            "category": "account-validation",
+            "source_files": list(dict.fromkeys(files)),
+            "affected_lines": [a.line, b.line] if files[0] == files[1] else [a.line],
+            "account_types": [a.account_type, b.account_type],
+            "schemas": [a.schema, b.schema],
+            "precision": "semantic" if self.kind == "collision" else "syntactic",
+        }
+        if fix:
+            properties["suggested_fix"] = fix
+        if self.kind != "cross-program":
+            properties.update(poc_template=TEMPLATE_ID, poc_template_file=TEMPLATE_FILE)
+        return {
+            "title": title,
+            "description": description,
+            "vulnerability_type": f"pda-seed-{self.kind}",
+            "severity": self.severity,
+            "confidence": 0.7 if self.kind == "collision" else 0.3,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": properties,
+        }
+
+    def _path(self, program: str, rel: str) -> str:
+        return _rebase(self.roots, program, rel)
+
+
+def _rebase(roots: dict[str, str], program: str, rel: str) -> str:
+    root = roots.get(program, "")
+    return (Path(root) / rel).as_posix() if root not in ("", ".") else rel
+
+
+@dataclass
+class SeedNamespaceFinding:
+    """A constant seed namespace shared with another program."""
+
+    reuse: SeedNamespaceReuse
+    roots: dict[str, str] = field(default_factory=dict)
+
+    @property
+    def kind(self) -> str:
+        return "namespace"
+
+    @property
+    def severity(self) -> str:
+        return self.reuse.severity
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        r = self.reuse
+        types = list(dict.fromkeys(d.account_type for d in r.derivations))
+        if r.kind == "ecosystem":
+            title = f'PDA seed "{r.value}" reuses the {r.ecosystem} namespace'
+            if r.severity == "medium":
+                impact = "The framework derives its own PDA from this seed under the same program id, so the two addresses coincide."
+            else:
+                impact = (
+                    f"A consumer that checks these seeds without pinning the deriving program accepts "
+                    f"{r.ecosystem} accounts in their place."
+                )
+            description = f'{", ".join(r.programs)} derive {", ".join(types) or "PDAs"} from "{r.value}". {impact}'
+        else:
+            title = f'PDA seed "{r.value}" is shared by {", ".join(r.programs)}'
+            description = (
+                f'{" and ".join(r.programs)} all derive PDAs from the leading seed "{r.value}" '
+                f'({", ".join(types) or "seed constants only"}). The namespaces only differ by program id, so '
+                f"any check that validates these seeds without `seeds::program` or an owner check accepts the "
+                f"other program's account. Program-specific seed prefixes avoid this."
+            )
+        locations = [(d.program, d.file, d.line) for d in r.derivations] + [(c.program, c.file, c.line) for c in r.constants]
+        files = [_rebase(self.roots, program, rel) for program, rel, _ in locations]
+        return {
+            "title": title,
+            "description": description,
+            "vulnerability_type": "pda-seed-namespace-reuse",
+            "severity": self.severity,
+            "confidence": 0.3,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "pda-collisions",
+                "category": "account-validation",
+                "source_files": list(dict.fromkeys(files)),
+                "affected_lines": [line for _, _, line in locations],
+                "account_types": types,
+                "seed": r.value,
+                "programs": r.programs,
+                "precision": "syntactic",
+            },
+        }
+
+
+class PdaCollisionScanner:
+    """Reports PDA seed collisions, shared prefixes and cross-program schema reuse."""
+
+    def __init__(self, report_prefix: bool = False, report_cross_program: bool = True, report_namespace: bool = True):
+        """Initialize the scanner.
+
+        Args:
+            report_prefix: Also report schemas that only share a prefix
+            report_cross_program: Report schemas reused across sibling programs
+            report_namespace: Report constant seeds shared across programs or with well-known programs
+        """
+        self.report_prefix = report_prefix
+        self.report_cross_program = report_cross_program
+        self.report_namespace = report_namespace
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{SCANNER_VERSION}"
+
+    def run(self, project_path: Path) -> tuple[list[PdaCollisionFinding | SeedNamespaceFinding], dict]:
+        """Scan every Solana program under ``project_path``.
+
+        Returns:
+            Tuple of (findings, metadata); metadata["derivations"] is the full PDA map
+        """
+        project_path = Path(project_path)
+        programs = load_programs(project_path)
+        roots = {p.name: Path(os.path.relpath(p.root, project_path)).as_posix() for p in programs}
+        constants = {p.name: seed_constants(p) for p in programs if p.chain == "solana"}
+        derivations = build_pda_map(programs, constants)
+        conflicts = find_conflicts(derivations)
+        reuse = find_namespace_reuse(derivations, constants)
+        wanted = {"collision"}
+        if self.report_prefix:
+            wanted.add("prefix")
+        if self.report_cross_program:
+            wanted.add("cross-program")
+        findings: list[PdaCollisionFinding | SeedNamespaceFinding] = [
+            PdaCollisionFinding(c, roots) for c in conflicts if c.kind in wanted
+        ]
+        if self.report_namespace:
+            findings.extend(SeedNamespaceFinding(r, roots) for r in reuse)
+        metadata = {
+            "tool": "pda-collisions",
+            "version": SCANNER_VERSION,
+            "success": True,
+            "error": None,
+            "template": TEMPLATE_ID,
+            "derivations": [d.to_dict() for d in derivations],
+            "seed_constants": [c.to_dict() for consts in constants.values() for c in consts.values()],
+            "namespace_reuse": len(reuse),
+            "conflicts": {kind: sum(1 for c in conflicts if c.kind == kind)
+                          for kind in ("collision", "prefix", "cross-program")},
+        }
+        return findings, metadata
@@ -2,16 +2,39 @@
 Static analysis pipeline orchestrator.
 
 Runs multiple static analyzers (Slither, Aderyn, Soteria, cargo-audit,
-Move Prover, Sui Move Lint) and aggregates their findings into Hound's
-hypothesis system. Supports EVM, Solana, and Sui/Aptos chains.
+the built-in unsafe Rust auditor, SBF resource estimator, account growth
+analyzer, PDA collision scanner, CPI privilege analyzer,
+privilege path queries, Anchor.toml checker, integration checker,
+missing signer detector and unvalidated CPI target detector, Move Prover,
+Sui Move Lint) and aggregates their findings into Hound's hypothesis system.
+Supports EVM, Solana, and Sui/Aptos chains; third-party detectors plug in
+through plugins.py. A workspace's baskerville.toml (project_config.py)
+chooses rules, ignored paths and severities. Given a ``ScanCache``, parsed
+programs and detector results are reused while the files they came from are
+unchanged (see cache.py).
+
+With more than one job, the work runs in a pool of worker processes, so
+parsing and detectors are not serialized by the GIL: each crate is lowered
+as its own work unit first, then each detector runs in a worker over a copy
+of the lowered programs. What a worker lowers beyond those is handed back
+to the run's program cache. Third-party detectors run in the scanning
+process, over the same programs.
 """
 
+import contextlib
 import hashlib
 import json
+import os
+import pickle
+from concurrent.futures import Future, ProcessPoolExecutor
 from dataclasses import dataclass, field
 from datetime import datetime
 from pathlib import Path
-from typing import Any
+from typing import Any, Iterable
+
+from extensions.ir.cache import FileHashes, ProgramCache, use_program_cache
+from extensions.ir.loader import crate_roots, lower_crate
+from extensions.ir.workspace import is_skipped
 
 from .slither_runner import SlitherRunner, SlitherFinding
 from .aderyn_runner import AderynRunner, AderynFinding
@@ -19,6 +42,33 @@ from .soteria_runner import SoteriaRunner, SoteriaFinding
 from .cargo_audit_runner import CargoAuditRunner, CargoAuditFinding
 from .move_prover_runner import MoveProverRunner, MoveProverFinding
 from .sui_move_lint_runner import SuiMoveLintRunner, SuiMoveLintFinding
+from .unsafe_rust import UnsafeRustAuditor
+from .sbf_limits import SbfResourceEstimator
+from .account_growth import AccountGrowthAnalyzer
+from .pda_collisions import PdaCollisionScanner
+from .cpi_privileges import CpiPrivilegeAnalyzer
+from .privilege_paths import PrivilegePathAnalyzer
+from .anchor_toml import AnchorTomlChecker
+from .integrations import IntegrationChecker
+from .protocol_params import ProtocolParameterChecker
+from .program_ids import ProgramIdChecker
+from .feature_gates import FeatureGateChecker
+from .unchecked_math import UncheckedArithmeticDetector
+from .state_migration import StateMigrationAnalyzer
+from .missing_signer import MissingSignerDetector
+from .missing_owner import MissingOwnerDetector
+from .type_cosplay import TypeCosplayDetector
+from .duplicate_accounts import DuplicateMutableAccountDetector
+from .arbitrary_cpi import ArbitraryCpiDetector
+from .cpi_targets import UnvalidatedCpiTargetDetector
+from .cache import ScanCache
+from .plugins import LOAD_ERRORS, detectors_for, load_plugins
+from .project_config import ProjectConfig
+from .confidence import Calibration, calibrate_hypothesis, load_calibration
+from .finding import SEVERITIES, Finding, number_occurrences
+from .rules import load_catalog, rule_code, rule_id
+from .suppressions import InlineSuppressions
+from .progress import ProgressCallback, ProgressEvent
 
 
 @dataclass
@@ -28,6 +78,8 @@ class PipelineResult:
     tool_findings: dict[str, list] = field(default_factory=dict)
     hypotheses: list[dict] = field(default_factory=list)
     metadata: dict = field(default_factory=dict)
+    # Hits a `baskerville:allow(...)` comment in the source hides
+    suppressed: list[dict] = field(default_factory=list)
 
     # Backward-compatible properties for EVM tools
     @property
@@ -46,6 +98,11 @@ class PipelineResult:
     def aderyn_findings(self, value: list[AderynFinding]) -> None:
         self.tool_findings["aderyn"] = value
 
+    @property
+    def findings(self) -> list[Finding]:
+        """The hypotheses as ``Finding``s, alike ones numbered (see finding.py)."""
+        return number_occurrences([Finding.from_hypothesis(hyp) for hyp in self.hypotheses])
+
     @property
     def total_findings(self) -> int:
         return sum(len(findings) for findings in self.tool_findings.values())
@@ -81,21 +138,90 @@ _CHAIN_RUNNERS: dict[str, list[tuple[str, type, str]]] = {
     "evm": [
         ("slither", SlitherRunner, "slither_config"),
         ("aderyn", AderynRunner, "aderyn_config"),
+        ("protocol-params", ProtocolParameterChecker, "protocol_params_config"),
     ],
     "solana": [
         ("soteria", SoteriaRunner, "soteria_config"),
         ("cargo-audit", CargoAuditRunner, "cargo_audit_config"),
+        ("unsafe-rust", UnsafeRustAuditor, "unsafe_rust_config"),
+        ("sbf-limits", SbfResourceEstimator, "sbf_limits_config"),
+        ("account-growth", AccountGrowthAnalyzer, "account_growth_config"),
+        ("pda-collisions", PdaCollisionScanner, "pda_collisions_config"),
+        ("cpi-privileges", CpiPrivilegeAnalyzer, "cpi_privileges_config"),
+        ("privilege-paths", PrivilegePathAnalyzer, "privilege_paths_config"),
+        ("anchor-toml", AnchorTomlChecker, "anchor_toml_config"),
+        ("integrations", IntegrationChecker, "integrations_config"),
+        ("missing-signer", MissingSignerDetector, "missing_signer_config"),
+        ("missing-owner", MissingOwnerDetector, "missing_owner_config"),
+        ("type-cosplay", TypeCosplayDetector, "type_cosplay_config"),
+        ("duplicate-mutable", DuplicateMutableAccountDetector, "duplicate_mutable_config"),
+        ("arbitrary-cpi", ArbitraryCpiDetector, "arbitrary_cpi_config"),
+        ("cpi-targets", UnvalidatedCpiTargetDetector, "cpi_targets_config"),
+        ("unchecked-math", UncheckedArithmeticDetector, "unchecked_math_config"),
+        ("state-migration", StateMigrationAnalyzer, "state_migration_config"),
+        ("program-ids", ProgramIdChecker, "program_ids_config"),
+        ("feature-gates", FeatureGateChecker, "feature_gates_config"),
+        ("protocol-params", ProtocolParameterChecker, "protocol_params_config"),
     ],
     "sui": [
         ("move-prover", MoveProverRunner, "move_prover_config"),
         ("sui-move-lint", SuiMoveLintRunner, "sui_move_lint_config"),
+        ("protocol-params", ProtocolParameterChecker, "protocol_params_config"),
     ],
     "aptos": [
         ("move-prover", MoveProverRunner, "move_prover_config"),
         ("sui-move-lint", SuiMoveLintRunner, "sui_move_lint_config"),
+        ("protocol-params", ProtocolParameterChecker, "protocol_params_config"),
     ],
 }
 
+# Source files counted for the "files" progress event
+_CHAIN_SUFFIXES: dict[str, tuple[str, ...]] = {
+    "evm": (".sol", ".vy"),
+    "solana": (".rs",),
+    "sui": (".move",),
+    "aptos": (".move",),
+}
+
+
+def _severity_rank(hypothesis: dict) -> int:
+    severity = str(hypothesis.get("severity") or "").lower()
+    return SEVERITIES.index(severity) if severity in SEVERITIES else -1
+
+
+def _in_worker(programs: tuple[Path | None, FileHashes, dict[str, bytes]], work: Any, *args: Any) -> tuple:
+    """``work(*args)`` in a worker process, over a copy of the run's lowered programs.
+
+    Returns what ``work`` returned, the entries the worker lowered itself and its cache hits and misses.
+    """
+    cache_dir, hashes, entries = programs
+    cache = ProgramCache(cache_dir, hashes)
+    cache.add(entries)
+    with use_program_cache(cache):
+        value = work(*args)
+    lowered = {key: data for key, data in cache.entries().items() if key not in entries}
+    return value, lowered, cache.hits, cache.misses
+
+
+def _portable(runner: Any) -> bool:
+    """Whether ``runner`` can be sent to a worker process."""
+    try:
+        pickle.dumps(runner)
+    except Exception:
+        return False
+    return True
+
+
+def count_source_files(project_path: Path, chain_id: str) -> int:
+    """Count the chain's source files under ``project_path``, skipping build output."""
+    suffixes = _CHAIN_SUFFIXES.get(chain_id, _CHAIN_SUFFIXES["evm"])
+    if project_path.is_file():
+        return int(project_path.suffix in suffixes)
+    return sum(
+        1 for path in project_path.rglob("*")
+        if path.suffix in suffixes and path.is_file() and not is_skipped(path.relative_to(project_path))
+    )
+
 
 class StaticAnalysisPipeline:
     """Orchestrates static analysis tools and aggregates findings."""
@@ -106,11 +232,37 @@ class StaticAnalysisPipeline:
         aderyn_config: dict | None = None,
         soteria_config: dict | None = None,
         cargo_audit_config: dict | None = None,
+        unsafe_rust_config: dict | None = None,
+        sbf_limits_config: dict | None = None,
+        account_growth_config: dict | None = None,
+        pda_collisions_config: dict | None = None,
+        cpi_privileges_config: dict | None = None,
+        privilege_paths_config: dict | None = None,
+        anchor_toml_config: dict | None = None,
+        integrations_config: dict | None = None,
+        missing_signer_config: dict | None = None,
+        missing_owner_config: dict | None = None,
+        type_cosplay_config: dict | None = None,
+        duplicate_mutable_config: dict | None = None,
+        arbitrary_cpi_config: dict | None = None,
+        cpi_targets_config: dict | None = None,
+        unchecked_math_config: dict | None = None,
+        state_migration_config: dict | None = None,
+        program_ids_config: dict | None = None,
+        feature_gates_config: dict | None = None,
+        protocol_params_config: dict | None = None,
         move_prover_config: dict | None = None,
         sui_move_lint_config: dict | None = None,
         deduplicate: bool = True,
         line_tolerance: int = 2,
         chain_id: str = "evm",
+        calibration: Calibration | None = None,
+        inline_suppressions: bool = True,
+        cache: ScanCache | None = None,
+        jobs: int | None = None,
+        plugins: bool = True,
+        plugin_configs: dict[str, dict] | None = None,
+        config: ProjectConfig | None = None,
     ):
         """Initialize the pipeline.
 
@@ -119,15 +271,47 @@ class StaticAnalysisPipeline:
             aderyn_config: Config dict for AderynRunner
             soteria_config: Config dict for SoteriaRunner
             cargo_audit_config: Config dict for CargoAuditRunner
+            unsafe_rust_config: Config dict for UnsafeRustAuditor
+            sbf_limits_config: Config dict for SbfResourceEstimator
+            account_growth_config: Config dict for AccountGrowthAnalyzer
+            pda_collisions_config: Config dict for PdaCollisionScanner
+            cpi_privileges_config: Config dict for CpiPrivilegeAnalyzer
+            privilege_paths_config: Config dict for PrivilegePathAnalyzer
+            anchor_toml_config: Config dict for AnchorTomlChecker
+            integrations_config: Config dict for IntegrationChecker
+            missing_signer_config: Config dict for MissingSignerDetector
+            missing_owner_config: Config dict for MissingOwnerDetector
+            type_cosplay_config: Config dict for TypeCosplayDetector
+            duplicate_mutable_config: Config dict for DuplicateMutableAccountDetector
+            arbitrary_cpi_config: Config dict for ArbitraryCpiDetector
+            cpi_targets_config: Config dict for UnvalidatedCpiTargetDetector
+            unchecked_math_config: Config dict for UncheckedArithmeticDetector
+            state_migration_config: Config dict for StateMigrationAnalyzer (``baseline``: the previous version)
+            program_ids_config: Config dict for ProgramIdChecker
+            feature_gates_config: Config dict for FeatureGateChecker (``cluster``: the target cluster)
+            protocol_params_config: Config dict for ProtocolParameterChecker
             move_prover_config: Config dict for MoveProverRunner
             sui_move_lint_config: Config dict for SuiMoveLintRunner
             deduplicate: Whether to deduplicate findings across tools
             line_tolerance: Line number tolerance for deduplication
             chain_id: Target chain ("evm", "solana", "sui", "aptos")
+            calibration: Detector precision used for hit confidence (default: the shipped calibration)
+            inline_suppressions: Honor `baskerville:allow(...)` comments in the source
+            cache: Incremental cache of parsed programs and detector results (default: none)
+            jobs: Worker processes (default: one per CPU; 1 runs everything in this process, one detector
+                after another)
+            plugins: Also run the chain's third-party detectors (see plugins.py)
+            plugin_configs: Third-party detector name -> keyword arguments for it
+            config: Project rules, ignored paths and severity overrides (default: none)
         """
         self.deduplicate = deduplicate
+        self.inline_suppressions = inline_suppressions
+        self.calibration = calibration or load_calibration()
         self.line_tolerance = line_tolerance
         self.chain_id = chain_id.lower()
+        self.cache = cache
+        self.jobs = jobs
+        self.config = config
 
         # Collect all config dicts by their config key name
         all_configs = {
@@ -135,6 +319,25 @@ class StaticAnalysisPipeline:
             "aderyn_config": aderyn_config,
             "soteria_config": soteria_config,
             "cargo_audit_config": cargo_audit_config,
+            "unsafe_rust_config": unsafe_rust_config,
+            "sbf_limits_config": sbf_limits_config,
+            "account_growth_config": account_growth_config,
+            "pda_collisions_config": pda_collisions_config,
+            "cpi_privileges_config": cpi_privileges_config,
+            "privilege_paths_config": privilege_paths_config,
+            "anchor_toml_config": anchor_toml_config,
+            "integrations_config": integrations_config,
+            "missing_signer_config": missing_signer_config,
+            "missing_owner_config": missing_owner_config,
+            "type_cosplay_config": type_cosplay_config,
+            "duplicate_mutable_config": duplicate_mutable_config,
+            "arbitrary_cpi_config": arbitrary_cpi_config,
+            "cpi_targets_config": cpi_targets_config,
+            "unchecked_math_config": unchecked_math_config,
+            "state_migration_config": state_migration_config,
+            "program_ids_config": program_ids_config,
+            "feature_gates_config": feature_gates_config,
+            "protocol_params_config": protocol_params_config,
             "move_prover_config": move_prover_config,
             "sui_move_lint_config": sui_move_lint_config,
         }
@@ -142,9 +345,31 @@ class StaticAnalysisPipeline:
         # Instantiate runners for the selected chain
         runner_specs = _CHAIN_RUNNERS.get(self.chain_id, _CHAIN_RUNNERS["evm"])
         self.runners: dict[str, Any] = {}
+        self.configs: dict[str, dict] = {}
         for tool_name, runner_class, config_key in runner_specs:
             config = all_configs.get(config_key) or {}
             self.runners[tool_name] = runner_class(**config)
+            self.configs[tool_name] = config
+
+        # Third-party detectors run after the built-in ones; they cannot take a built-in's name
+        self.plugin_tools: set[str] = set()
+        self.plugin_errors: dict[str, str] = {}
+        if plugins:
+            load_plugins()
+            self.plugin_errors.update(LOAD_ERRORS)
+            builtin = {name for specs in _CHAIN_RUNNERS.values() for name, _, _ in specs}
+            for tool_name, detector_class in detectors_for(self.chain_id).items():
+                if tool_name in builtin:
+                    self.plugin_errors[tool_name] = f"{detector_class.__module__}: name taken by a built-in detector"
+                    continue
+                config = (plugin_configs or {}).get(tool_name) or {}
+                try:
+                    self.runners[tool_name] = detector_class(**config)
+                except Exception as e:
+                    self.plugin_errors[tool_name] = f"{type(e).__name__}: {e}"
+                    continue
+                self.configs[tool_name] = config
+                self.plugin_tools.add(tool_name)
 
         # Keep backward-compatible attributes for EVM tools
         if self.chain_id == "evm":
@@ -162,35 +387,138 @@ class StaticAnalysisPipeline:
             for name, runner in self.runners.items()
         }
 
-    def run(self, project_path: Path) -> PipelineResult:
+    def run(
+        self,
+        project_path: Path,
+        tools: Iterable[str] | None = None,
+        progress: ProgressCallback | None = None,
+    ) -> PipelineResult:
         """Run the full static analysis pipeline.
 
         Args:
             project_path: Path to project
+            tools: Only run these tools (default: every tool for the chain the project config leaves)
+            progress: Called with a ProgressEvent as files are counted and each detector starts and ends
 
         Returns:
             PipelineResult with findings and hypotheses
         """
         result = PipelineResult()
+        catalog = load_catalog()
         result.metadata = {
             "project_path": str(project_path),
             "run_time": datetime.now().isoformat(),
             "chain_id": self.chain_id,
+            "rules_version": catalog.version,
             "tools": {},
         }
 
-        # Run each tool for the configured chain
-        for tool_name, runner in self.runners.items():
+        selected = set(tools) if tools is not None else None
+        if selected is None and self.config:
+            selected = set(self.config.tools(self.runners, catalog))
+        runners = [(name, runner) for name, runner in self.runners.items() if selected is None or name in selected]
+        counters = {"detectors_total": len(runners), "detectors_completed": 0, "files_parsed": 0, "findings": 0}
+
+        def emit(event: str, **extra: Any) -> None:
+            if progress is not None:
+                progress(ProgressEvent(event, **counters, **extra))
+
+        emit("started")
+        if progress is not None:
+            counters["files_parsed"] = count_source_files(Path(project_path), self.chain_id)
+            emit("files")
+
+        fingerprint = self.cache.fingerprint(Path(project_path)) if self.cache else None
+        # Shared by every detector, so each crate is parsed once per run
+        programs = self.cache.programs if self.cache else ProgramCache()
+
+        def check(tool_name: str, runner: Any) -> tuple[str | None, str | None, tuple | None]:
+            """The tool's error if it is not installed, else its cache key and cached results."""
             available, version_or_error = runner.is_available()
-            if available:
-                findings, metadata = runner.run(project_path)
+            if not available:
+                return version_or_error, None, None
+            config = self.configs.get(tool_name)
+            key = None
+            if self.cache and tool_name not in self.plugin_tools and self.cache.cacheable(config):
+                key = self.cache.key(tool_name, version_or_error, config, fingerprint)
+            return None, key, self.cache.get(key) if key else None
+
+        def finish(tool_name: str, findings: list, metadata: dict, key: str | None, cached: bool) -> tuple:
+            if key and not cached:
+                self.cache.put(key, findings, metadata)
+            counters["detectors_completed"] += 1
+            counters["findings"] += len(findings)
+            emit("detector_completed", detector=tool_name, cached=cached)
+            return findings, metadata
+
+        def run_tool(tool_name: str, runner: Any, checked: tuple, future: Future | None = None) -> tuple:
+            """The tool's findings and metadata: skipped, replayed, from its worker or run here."""
+            error, key, cached = checked
+            if error is not None:
+                counters["detectors_completed"] += 1
+                emit("detector_skipped", detector=tool_name, error=error)
+                return None, {"available": False, "error": error}
+            if future is None:
+                emit("detector_started", detector=tool_name)
+            if cached:
+                return finish(tool_name, *cached, key, True)
+            if future is not None:
+                return finish(tool_name, *merge(future.result()), key, False)
+            with use_program_cache(programs):
+                if tool_name in self.plugin_tools:
+                    findings, metadata = self._run_plugin(tool_name, runner, project_path)
+                else:
+                    findings, metadata = runner.run(project_path)
+            return finish(tool_name, findings, metadata, key, False)
+
+        def merge(outcome: tuple) -> Any:
+            value, lowered, hits, misses = outcome
+            programs.add(lowered, hits, misses)
+            return value
+
+        # Run the chain's tools, `jobs` worker processes at once; results and their events keep the tools' order
+        jobs = max(1, min(self.jobs or os.cpu_count() or 1, len(runners) or 1))
+        result.metadata["jobs"] = jobs
+        if self.plugin_tools or self.plugin_errors:
+            result.metadata["plugins"] = sorted(self.plugin_tools & {name for name, _ in runners})
+            result.metadata["plugin_errors"] = dict(self.plugin_errors)
+        if jobs == 1:
+            outcomes = [run_tool(name, runner, check(name, runner)) for name, runner in runners]
+        else:
+            checked = {name: check(name, runner) for name, runner in runners}
+            # Third-party detectors and runners that cannot be sent to a worker run here, alongside the pool
+            pooled = [(name, runner) for name, runner in runners if checked[name][0] is None
+                      and not checked[name][2] and name not in self.plugin_tools and _portable(runner)]
+            futures: dict[str, Future] = {}
+            with ProcessPoolExecutor(max_workers=jobs) if pooled else contextlib.nullcontext() as executor:
+                if pooled:
+                    # Every crate up front, one work unit each, so no detector lowers one another is lowering
+                    with use_program_cache(programs):
+                        crates = crate_roots(Path(project_path))
+                    snapshot = (programs.cache_dir, programs.hashes, programs.entries())
+                    for future in [executor.submit(_in_worker, snapshot, lower_crate, *crate) for crate in crates]:
+                        try:
+                            merge(future.result())
+                        except Exception:
+                            # Left to the detectors, which fail on it as they would in one process
+                            continue
+                    snapshot = (programs.cache_dir, programs.hashes, programs.entries())
+                    for name, runner in pooled:
+                        emit("detector_started", detector=name)
+                        futures[name] = executor.submit(_in_worker, snapshot, runner.run, project_path)
+                outcomes = [run_tool(name, runner, checked[name], futures.get(name)) for name, runner in runners]
+        for (tool_name, _), (findings, metadata) in zip(runners, outcomes):
+            if findings is not None:
                 result.tool_findings[tool_name] = findings
-                result.metadata["tools"][tool_name] = metadata
-            else:
-                result.metadata["tools"][tool_name] = {
-                    "available": False,
-                    "error": version_or_error,
-                }
+            result.metadata["tools"][tool_name] = metadata
+        if self.cache:
+            result.metadata["cache"] = {
+                "path": str(self.cache.dir),
+                "detectors_replayed": sum(1 for m in result.metadata["tools"].values() if m.get("cached")),
+                "programs_reused": self.cache.programs.hits,
+                "programs_parsed": self.cache.programs.misses,
+            }
+            self.cache.save()
 
         # Convert all findings to hypotheses
         all_hypotheses = []
@@ -199,16 +527,43 @@ class StaticAnalysisPipeline:
             for finding in findings:
                 hyp = finding.to_hypothesis()
                 hyp["id"] = self._generate_hypothesis_id(hyp, tool_name)
+                calibrate_hypothesis(hyp, tool_name, self.calibration)
+                hyp.setdefault("properties", {})["rule_id"] = rule_id(hyp, tool_name)
+                code = rule_code(hyp, tool_name, catalog)
+                if code:
+                    hyp["properties"]["rule_code"] = code
                 all_hypotheses.append(hyp)
 
+        if self.config:
+            all_hypotheses, dropped = self.config.apply(all_hypotheses, tools)
+            result.metadata["config"] = {
+                "path": str(self.config.path) if self.config.path else None,
+                "tools_disabled": sorted(set(self.runners) - {name for name, _ in runners}) if tools is None else [],
+                "filtered": len(dropped),
+                "severity_overridden": sum(1 for h in all_hypotheses if "detector_severity" in h["properties"]),
+            }
+
+        if self.inline_suppressions:
+            all_hypotheses, result.suppressed = InlineSuppressions(Path(project_path)).apply(all_hypotheses)
+            result.metadata["inline_suppressed"] = len(result.suppressed)
+
         # Deduplicate if enabled
         if self.deduplicate:
             result.hypotheses = self._deduplicate_hypotheses(all_hypotheses)
         else:
             result.hypotheses = all_hypotheses
 
+        emit("finished", hypotheses=len(result.hypotheses))
         return result
 
+    @staticmethod
+    def _run_plugin(tool_name: str, runner: Any, project_path: Path) -> tuple[list, dict]:
+        """Run a third-party detector; its errors fail the detector, not the scan."""
+        try:
+            return runner.run(project_path)
+        except Exception as e:
+            return [], {"tool": tool_name, "success": False, "error": f"{type(e).__name__}: {e}", "plugin": True}
+
     def _generate_hypothesis_id(self, hypothesis: dict, tool: str) -> str:
         """Generate a unique ID for a hypothesis."""
         # Use hash of key fields for dedup-friendly IDs
@@ -226,9 +581,11 @@ class StaticAnalysisPipeline:
         """Remove duplicate findings across tools.
 
         Uses file path, line numbers, and vulnerability type for matching.
+        The kept version takes the worst severity of its duplicates and lists
+        the other tools that reported it in ``properties.also_reported_by``.
         """
         unique = []
-        seen_keys = set()
+        kept: dict[str, dict] = {}
 
         # Sort by confidence (higher first) so we keep the best version
         sorted_hyps = sorted(
@@ -239,9 +596,17 @@ class StaticAnalysisPipeline:
 
         for hyp in sorted_hyps:
             key = self._make_dedup_key(hyp)
-            if key not in seen_keys:
-                seen_keys.add(key)
+            first = kept.get(key)
+            if first is None:
+                kept[key] = hyp
                 unique.append(hyp)
+                continue
+            if _severity_rank(hyp) > _severity_rank(first):
+                first["severity"] = hyp["severity"]
+            tool = hyp.get("properties", {}).get("source_tool")
+            props = first.setdefault("properties", {})
+            if tool and tool != props.get("source_tool") and tool not in props.get("also_reported_by", []):
+                props.setdefault("also_reported_by", []).append(tool)
 
         return unique
 
@@ -313,6 +678,17 @@ class StaticAnalysisPipeline:
             json.dump(result.metadata, f, indent=2)
         paths["metadata"] = metadata_path
 
+        # One line per run; static_metadata.json only keeps the latest
+        runs_path = output_dir / "runs.jsonl"
+        with open(runs_path, "a") as f:
+            f.write(json.dumps({
+                "run_time": result.metadata.get("run_time"),
+                "chain_id": result.metadata.get("chain_id"),
+                "tools": sorted(t for t, m in result.metadata.get("tools", {}).items() if m.get("available", True)),
+                "hypotheses": len(result.hypotheses),
+            }) + "\n")
+        paths["runs"] = runs_path
+
         # Save raw findings per tool
         for tool_name, findings in result.tool_findings.items():
             if findings:
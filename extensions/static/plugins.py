@@ -0,0 +1,197 @@
+"""
+Third-party detectors.
+
+Audit firms can ship their own checks as separate Python packages that the
+scan pipeline runs next to the built-in detectors, without forking. A
+plugin subclasses ``Detector`` and registers it with ``@register_detector``;
+its hits are ordinary hypotheses, so suppressions, baselines, confidence
+calibration and every report format (JSON, Markdown, SARIF) handle them:
+
+    from extensions.static.plugins import Detector, register_detector
+
+    @register_detector
+    class UncheckedOracle(Detector):
+        name = "acme-oracle"
+        chains = ("solana",)
+        version = "1.2.0"
+
+        def run(self, project_path):
+            hits = [self.hit("Stale oracle price", "...", "stale-oracle", "high", "src/lib.rs", 42)]
+            return hits, self.metadata(files_checked=1)
+
+Plugins are found through the ``baskerville.detectors`` entry point group
+of installed packages (``[project.entry-points."baskerville.detectors"]
+acme = "acme_checks"``) and the comma-separated modules in
+``BASKERVILLE_PLUGINS``. An entry point names a module (imported for its
+``@register_detector`` classes) or a ``Detector`` class. Setting
+``BASKERVILLE_NO_PLUGINS=1`` (or ``StaticAnalysisPipeline(plugins=False)``)
+loads none. A plugin that fails to import, or whose name clashes with a
+built-in detector, is reported rather than raised.
+
+Plugin results are not kept in the scan cache: only the built-in code is
+fingerprinted.
+"""
+
+import importlib
+import os
+import re
+import threading
+from dataclasses import dataclass, field
+from importlib import metadata as importlib_metadata
+from pathlib import Path
+from typing import Any
+
+ENTRY_POINT_GROUP = "baskerville.detectors"
+PLUGINS_ENV = "BASKERVILLE_PLUGINS"
+NO_PLUGINS_ENV = "BASKERVILLE_NO_PLUGINS"
+
+_NAME_RE = re.compile(r"^[a-z][a-z0-9]*(?:-[a-z0-9]+)*$")
+
+DETECTORS: dict[str, type["Detector"]] = {}
+# Plugin (entry point or module) -> why it did not load
+LOAD_ERRORS: dict[str, str] = {}
+_loaded: set[str] = set()
+_lock = threading.Lock()
+
+
+class PluginError(ValueError):
+    """A detector class that cannot be registered."""
+    pass
+
+
+@dataclass
+class DetectorHit:
+    """One hit of a third-party detector."""
+
+    source_tool: str
+    title: str
+    description: str
+    vulnerability_type: str
+    severity: str
+    path: str
+    line: int | None = None
+    confidence: float = 0.5
+    precision: str = "syntactic"
+    suggested_fix: str | None = None
+    properties: dict[str, Any] = field(default_factory=dict)
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        return {
+            "title": self.title,
+            "description": self.description,
+            "vulnerability_type": self.vulnerability_type,
+            "severity": self.severity,
+            "confidence": self.confidence,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                **self.properties,
+                "source_tool": self.source_tool,
+                "precision": self.precision,
+                "source_files": [self.path],
+                "affected_lines": [self.line] if self.line else [],
+                **({"suggested_fix": self.suggested_fix} if self.suggested_fix else {}),
+            },
+        }
+
+
+class Detector:
+    """Base class of third-party detectors; runs like the built-in ones.
+
+    Subclasses set ``name`` (kebab-case; the ``--tool`` name and rule id
+    prefix), ``chains`` (empty: every chain) and ``version``, and implement
+    ``run``. Keyword arguments given to the pipeline for the detector
+    (``plugin_configs``) arrive in ``self.config``.
+    """
+
+    name = ""
+    chains: tuple[str, ...] = ()
+    version = "0.0.0"
+    latency = "cold"
+    description = ""
+
+    def __init__(self, **config: Any):
+        self.config = config
+
+    def is_available(self) -> tuple[bool, str]:
+        """Available unless overridden, e.g. to look for an external tool."""
+        return True, f"plugin-{self.version}"
+
+    def run(self, project_path: Path) -> tuple[list[Any], dict[str, Any]]:
+        """Check ``project_path``.
+
+        Returns:
+            Tuple of (findings with ``to_hypothesis()``, metadata)
+        """
+        raise NotImplementedError
+
+    def hit(self, title: str, description: str, vulnerability_type: str, severity: str, path: str,
+            line: int | None = None, **extra: Any) -> DetectorHit:
+        """A finding of this detector (``extra``: the other ``DetectorHit`` fields)."""
+        return DetectorHit(self.name, title, description, vulnerability_type, severity, path, line, **extra)
+
+    def metadata(self, **extra: Any) -> dict[str, Any]:
+        """Run metadata in the built-in detectors' shape."""
+        return {"tool": self.name, "version": self.version, "success": True, "error": None, "plugin": True,
+                **extra}
+
+
+def register_detector(cls: type[Detector]) -> type[Detector]:
+    """Class decorator adding a third-party detector to the scan pipeline.
+
+    Raises:
+        PluginError: If the class is not a ``Detector``, its name is not kebab-case or is taken by another class
+    """
+    if not (isinstance(cls, type) and issubclass(cls, Detector)):
+        raise PluginError(f"{cls!r} is not a Detector subclass")
+    if not _NAME_RE.match(cls.name or ""):
+        raise PluginError(f"{cls.__qualname__} needs a kebab-case name (got {cls.name!r})")
+    other = DETECTORS.get(cls.name)
+    if other is not None and other.__qualname__ != cls.__qualname__:
+        raise PluginError(f"detector name {cls.name!r} is taken by {other.__module__}.{other.__qualname__}")
+    DETECTORS[cls.name] = cls
+    return cls
+
+
+def unregister_detector(name: str) -> None:
+    """Remove a registered detector (e.g. one a test registered)."""
+    DETECTORS.pop(name, None)
+
+
+def _plugin_specs() -> list[tuple[str, Any]]:
+    specs: list[tuple[str, Any]] = []
+    try:
+        entry_points = importlib_metadata.entry_points(group=ENTRY_POINT_GROUP)
+    except Exception:  # A broken installed distribution must not stop scans
+        entry_points = []
+    specs += [(f"{ep.name} ({ep.value})", ep) for ep in entry_points]
+    specs += [(module, module) for module in (m.strip() for m in os.environ.get(PLUGINS_ENV, "").split(",")) if module]
+    return specs
+
+
+def load_plugins() -> dict[str, type[Detector]]:
+    """Import every plugin not yet loaded; the registered detectors by name.
+
+    Plugins that fail are recorded in ``LOAD_ERRORS`` and skipped.
+    """
+    if os.environ.get(NO_PLUGINS_ENV, "").lower() in ("1", "true", "yes"):
+        return dict(DETECTORS)
+    with _lock:
+        for label, spec in _plugin_specs():
+            if label in _loaded:
+                continue
+            _loaded.add(label)
+            try:
+                loaded = importlib.import_module(spec) if isinstance(spec, str) else spec.load()
+                if isinstance(loaded, type) and issubclass(loaded, Detector):
+                    register_detector(loaded)
+            except Exception as e:  # Any error in third-party code
+                LOAD_ERRORS[label] = f"{type(e).__name__}: {e}"
+    return dict(DETECTORS)
+
+
+def detectors_for(chain_id: str) -> dict[str, type[Detector]]:
+    """Registered third-party detectors that apply to ``chain_id``."""
+    return {name: cls for name, cls in DETECTORS.items() if not cls.chains or chain_id in cls.chains}
@@ -0,0 +1,538 @@
+"""
+Privilege-escalation path queries over a program graph.
+
+Builds a graph of every Solana program in a workspace from the IR:
+programs, instructions, the account slots each instruction takes, the state
+types those accounts hold, the authorities that gate instructions, the CPIs
+between instructions and programs (from `cpi_privileges`), and the lamport
+transfers handlers perform. Canned queries then look for multi-step paths
+an unauthenticated caller can take:
+
+- lamport-drain: an unauthenticated instruction reaches a lamport transfer
+  out of a program-owned account to a caller-chosen recipient, directly or
+  through PDA-signed CPIs into instructions the PDA authorizes
+- authority-takeover: an unauthenticated instruction writes the state field
+  another instruction is gated on (`has_one`, key constraints)
+- pda-transfer: an unauthenticated instruction makes a PDA-signed token or
+  SOL transfer to a caller-chosen account
+
+Each result is reported as a multi-step finding whose hops name the edge
+taken and where in the source it comes from. The graph itself is exported
+in the knowledge-graph JSON shape, so it can be saved next to the agent's
+graphs and explored like them.
+"""
+
+import os
+import re
+from collections.abc import Callable
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+from extensions.ir import load_programs
+from extensions.ir.model import AccountField, Instruction, Program
+from extensions.ir.rust_source import find_matching, split_top_level
+
+from .cpi_privileges import analyze_instruction
+
+ANALYZER_VERSION = "1.0.0"
+GRAPH_NAME = "PrivilegeGraph"
+
+_FIELD_WRITE_RE = re.compile(r"(?:ctx\s*\.\s*accounts\s*\.\s*)?\b(?P<acc>[A-Za-z_]\w*)\s*\.\s*(?P<field>[A-Za-z_]\w*)\s*=(?!=)")
+_LAMPORTS = r"(?:\.\s*to_account_info\s*\(\s*\))?\s*\.\s*(?:try_borrow_mut_lamports\s*\(\s*\)\s*\?|lamports\s*\.\s*borrow_mut\s*\(\s*\))"
+_ACC = r"(?:ctx\s*\.\s*accounts\s*\.\s*)?(?P<acc>[A-Za-z_]\w*)"
+_DEBIT_RES = [
+    re.compile(rf"\*\*\s*{_ACC}{_LAMPORTS}\s*-="),
+    re.compile(rf"\b{_ACC}(?:\.\s*to_account_info\s*\(\s*\))?\s*\.\s*sub_lamports\s*\("),
+]
+_CREDIT_RES = [
+    re.compile(rf"\*\*\s*{_ACC}{_LAMPORTS}\s*\+="),
+    re.compile(rf"\b{_ACC}(?:\.\s*to_account_info\s*\(\s*\))?\s*\.\s*add_lamports\s*\("),
+]
+_ANCHOR_CPI_RE = re.compile(r"\b(?P<program>[A-Za-z_]\w*)\s*::\s*cpi\s*::\s*(?P<ix>[A-Za-z_]\w*)\s*\(")
+# Wrappers whose address the runtime or Anchor pins
+_FIXED_WRAPPERS = {"Program", "Interface", "Sysvar"}
+# Accounts structs of the CPIs that move tokens or SOL
+_TRANSFER_CALLEES = re.compile(r"(?:^|::)(?:Transfer|TransferChecked|transfer|transfer_checked)$")
+
+
+@dataclass
+class GraphNode:
+    """A node, in the knowledge-graph node shape."""
+
+    id: str
+    type: str  # program, instruction, account, state, external
+    label: str
+    properties: dict[str, Any] = field(default_factory=dict)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"id": self.id, "type": self.type, "label": self.label, "properties": self.properties,
+                "source_refs": [self.properties["file"]] if self.properties.get("file") else []}
+
+
+@dataclass
+class GraphEdge:
+    """A directed edge, in the knowledge-graph edge shape."""
+
+    id: str
+    type: str  # contains, uses, instance_of, authorizes, writes_field, gates, cpi, lamport_transfer
+    source_id: str
+    target_id: str
+    properties: dict[str, Any] = field(default_factory=dict)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"id": self.id, "type": self.type, "source_id": self.source_id, "target_id": self.target_id,
+                "label": self.type.replace("_", " "), "properties": self.properties}
+
+
+@dataclass
+class PrivilegeGraph:
+    """Accounts, authorities, instructions and CPIs of a workspace."""
+
+    nodes: dict[str, GraphNode] = field(default_factory=dict)
+    edges: list[GraphEdge] = field(default_factory=list)
+
+    def add_node(self, id: str, type: str, label: str, **properties: Any) -> GraphNode:
+        if id not in self.nodes:
+            self.nodes[id] = GraphNode(id, type, label, properties)
+        return self.nodes[id]
+
+    def add_edge(self, type: str, source: str, target: str, **properties: Any) -> GraphEdge:
+        edge = GraphEdge(f"e{len(self.edges) + 1}", type, source, target, properties)
+        self.edges.append(edge)
+        return edge
+
+    def out_edges(self, node_id: str, type: str | None = None) -> list[GraphEdge]:
+        return [e for e in self.edges if e.source_id == node_id and (type is None or e.type == type)]
+
+    def in_edges(self, node_id: str, type: str | None = None) -> list[GraphEdge]:
+        return [e for e in self.edges if e.target_id == node_id and (type is None or e.type == type)]
+
+    def of_type(self, type: str) -> list[GraphNode]:
+        return [n for n in self.nodes.values() if n.type == type]
+
+    def to_dict(self) -> dict[str, Any]:
+        """Knowledge-graph JSON (the `graph_<name>.json` shape the agent loads)."""
+        return {
+            "name": GRAPH_NAME,
+            "internal_name": GRAPH_NAME,
+            "focus": "privileges: accounts, authorities, instructions and CPIs",
+            "nodes": [n.to_dict() for n in self.nodes.values()],
+            "edges": [e.to_dict() for e in self.edges],
+            "metadata": {"generated_by": f"privilege-paths-{ANALYZER_VERSION}"},
+            "stats": {
+                "num_nodes": len(self.nodes),
+                "num_edges": len(self.edges),
+                "node_types": sorted({n.type for n in self.nodes.values()}),
+                "edge_types": sorted({e.type for e in self.edges}),
+            },
+        }
+
+
+@dataclass
+class Hop:
+    """One annotated step of a path."""
+
+    source: str
+    edge: str
+    target: str
+    note: str
+    program: str = ""  # Program whose source ``file`` belongs to
+    file: str = ""
+    line: int = 0
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"from": self.source, "edge": self.edge, "to": self.target, "note": self.note,
+                "program": self.program, "file": self.file, "line": self.line}
+
+
+@dataclass
+class PrivilegePath:
+    """A query result: a path from an entry instruction to a privileged effect."""
+
+    query: str
+    entry: str  # Entry instruction node id
+    hops: list[Hop] = field(default_factory=list)
+
+    @property
+    def severity(self) -> str:
+        return QUERIES[self.query][1]
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"query": self.query, "severity": self.severity, "entry": self.entry,
+                "hops": [h.to_dict() for h in self.hops]}
+
+
+def _ix_id(program: str, ix: str) -> str:
+    return f"instruction:{program}.{ix}"
+
+
+def _acc_id(program: str, ix: str, acc: str) -> str:
+    return f"account:{program}.{ix}.{acc}"
+
+
+def _state_type(acc: AccountField) -> str | None:
+    m = re.search(r"\b(?:Account|AccountLoader|InterfaceAccount)\s*<[^<>]*?(\w+)\s*>", acc.ty)
+    return m.group(1) if m else None
+
+
+def _mentions(text: str, name: str) -> bool:
+    return bool(re.search(rf"\b{re.escape(name)}\b", text))
+
+
+def _referenced(ix: Instruction, acc: AccountField) -> bool:
+    """Whether other accounts' constraints or seeds pin ``acc`` (has_one, key equality, seeds)."""
+    for other in ix.accounts:
+        texts = [c for c in other.constraints if not re.match(r"(?:payer|close|realloc::payer)\s*=", c)]
+        texts += other.seeds or []
+        if other is not acc and any(_mentions(t, acc.name) for t in texts):
+            return True
+    return False
+
+
+def _key_checked(ix: Instruction, acc: AccountField) -> bool:
+    key = rf"\b{re.escape(acc.name)}\s*\.\s*key\b"
+    return bool(re.search(rf"require_keys_(?:eq|neq)!\s*\([^;]*{key}", ix.body)
+                or re.search(rf"{key}(?:\s*\(\s*\))?\s*[!=]=|[!=]=\s*\*?\s*(?:ctx\s*\.\s*accounts\s*\.\s*)?{key}", ix.body))
+
+
+def enforced_signers(ix: Instruction) -> list[str]:
+    """Signers the instruction binds to state or a known key; a payer alone does not authenticate."""
+    found = []
+    for acc in ix.accounts:
+        if not acc.is_signer:
+            continue
+        if (_referenced(ix, acc) or _key_checked(ix, acc) or "key" in acc.constraints
+                or any(re.match(r"(?:address|constraint)\s*=", c) for c in acc.constraints)):
+            found.append(acc.name)
+    return found
+
+
+def _caller_chosen(ix: Instruction, acc: AccountField) -> bool:
+    """Whether the caller can pass any account here (nothing pins its address)."""
+    if acc.wrapper in _FIXED_WRAPPERS or acc.seeds is not None or _referenced(ix, acc) or _key_checked(ix, acc):
+        return False
+    pinned = ("address", "init", "associated_token::", "token::authority", "has_one", "key")
+    return not any(c.startswith(pinned) for c in acc.constraints)
+
+
+def _lamport_flows(ix: Instruction) -> list[tuple[str, list[str], str, int]]:
+    """(debited account, credited accounts, how, line) for lamports moved out of program-owned accounts."""
+    body = ix.body
+    credits = list(dict.fromkeys(m.group("acc") for r in _CREDIT_RES for m in r.finditer(body) if ix.account(m.group("acc"))))
+    flows = []
+    for r in _DEBIT_RES:
+        for m in r.finditer(body):
+            if ix.account(m.group("acc")) is not None:
+                line = ix.body_line + body.count("\n", 0, m.start())
+                flows.append((m.group("acc"), [c for c in credits if c != m.group("acc")], "direct debit", line))
+    for acc in ix.accounts:
+        for c in acc.constraints:
+            m = re.match(r"close\s*=\s*(\w+)", c)
+            if m:
+                flows.append((acc.name, [m.group(1)], "close", acc.line))
+    return flows
+
+
+def _add_program(graph: PrivilegeGraph, program: Program, siblings: dict[str, str]) -> None:
+    pid = f"program:{program.name}"
+    graph.add_node(pid, "program", program.name, framework=program.framework, program_id=program.program_id)
+    for ix in program.instructions:
+        iid = _ix_id(program.name, ix.name)
+        authorities = enforced_signers(ix)
+        graph.add_node(iid, "instruction", f"{program.name}::{ix.name}", program=program.name,
+                       file=ix.source_file, line=ix.line, authenticated=bool(authorities), authorities=authorities)
+        graph.add_edge("contains", pid, iid)
+        for acc in ix.accounts:
+            aid = _acc_id(program.name, ix.name, acc.name)
+            state = _state_type(acc)
+            graph.add_node(aid, "account", f"{ix.name}.{acc.name}", program=program.name, instruction=iid, name=acc.name,
+                           wrapper=acc.wrapper, signer=acc.is_signer, writable=acc.is_mut,
+                           caller_chosen=_caller_chosen(ix, acc), init=any(c.startswith("init") for c in acc.constraints),
+                           file=acc.file or ix.file, line=acc.line)
+            graph.add_edge("uses", iid, aid, signer=acc.is_signer, writable=acc.is_mut)
+            if acc.name in authorities:
+                graph.add_edge("authorizes", aid, iid)
+            if state:
+                sid = f"state:{program.name}.{state}"
+                graph.add_node(sid, "state", state, program=program.name)
+                graph.add_edge("instance_of", aid, sid)
+                for c in acc.constraints:
+                    m = re.match(r"has_one\s*=\s*(\w+)", c)
+                    if m:
+                        graph.add_edge("gates", sid, iid, field=m.group(1), account=acc.name, line=acc.line)
+                    for fm in re.finditer(rf"\b{re.escape(acc.name)}\s*\.\s*(\w+)\s*==", c):
+                        graph.add_edge("gates", sid, iid, field=fm.group(1), account=acc.name, line=acc.line)
+        for m in _FIELD_WRITE_RE.finditer(ix.body):
+            acc = ix.account(m.group("acc"))
+            state = _state_type(acc) if acc is not None else None
+            if state and not any(c.startswith("init") for c in acc.constraints):
+                graph.add_edge("writes_field", iid, f"state:{program.name}.{state}", field=m.group("field"),
+                               account=acc.name, line=ix.body_line + ix.body.count("\n", 0, m.start()))
+        for debited, credited, how, line in _lamport_flows(ix):
+            for recipient in credited or ["<unknown>"]:
+                target = _acc_id(program.name, ix.name, recipient)
+                if ix.account(recipient) is None:
+                    target = f"external:{recipient}"
+                    graph.add_node(target, "external", recipient)
+                graph.add_edge("lamport_transfer", _acc_id(program.name, ix.name, debited), target,
+                               via=how, instruction=iid, line=line)
+        callees = _anchor_cpi_callees(ix, siblings)
+        for site in analyze_instruction(program, ix):
+            target = callees.get(site.line)
+            if target is None:
+                target = f"external:{site.target_account or site.target or 'unknown'}"
+                graph.add_node(target, "external", site.target_account or site.target or "unknown")
+            graph.add_edge("cpi", iid, target, kind=site.kind, line=site.line, pda_signed=site.pda_signed,
+                           pda_signers=site.pda_signers, target_check=site.target_check, callee=site.callee,
+                           roles=site.roles, forwarded=[a.to_dict() for a in site.forwarded])
+
+
+def _anchor_cpi_callees(ix: Instruction, siblings: dict[str, str]) -> dict[int, str]:
+    """Line of each `CpiContext` passed to `sibling::cpi::method(..)` → the sibling instruction's node id."""
+    body = ix.body
+    found = {}
+    for m in _ANCHOR_CPI_RE.finditer(body):
+        program = siblings.get(m.group("program").lower().replace("_", ""))
+        if program is None:
+            continue
+        start = m.end()
+        args = split_top_level(body[start:find_matching(body, start - 1)])
+        name = re.fullmatch(r"\s*([A-Za-z_]\w*)\s*", args[0]) if args else None
+        if name:
+            lets = list(re.finditer(rf"\blet\s+(?:mut\s+)?{re.escape(name.group(1))}\b", body[:m.start()]))
+            start = lets[-1].end() if lets else start
+        ctx = body.find("CpiContext", start)
+        if ctx >= 0:
+            found[ix.body_line + body.count("\n", 0, ctx)] = _ix_id(program, m.group("ix"))
+    return found
+
+
+def build_privilege_graph(programs: list[Program]) -> PrivilegeGraph:
+    """Graph of every Solana program in ``programs``."""
+    solana = [p for p in programs if p.chain == "solana"]
+    siblings = {p.name.lower().replace("_", ""): p.name for p in solana}
+    for p in solana:
+        if p.metadata.get("module"):
+            siblings.setdefault(p.metadata["module"].lower().replace("_", ""), p.name)
+    graph = PrivilegeGraph()
+    for program in solana:
+        _add_program(graph, program, siblings)
+    # Anchor CPI targets resolved to sibling instructions that were not loaded stay external
+    for edge in graph.edges:
+        if edge.type == "cpi" and edge.target_id not in graph.nodes:
+            graph.add_node(edge.target_id, "external", edge.target_id.split(":", 1)[1])
+    return graph
+
+
+def find_paths(
+    graph: PrivilegeGraph,
+    start: str,
+    follow: Callable[[list[GraphEdge], GraphEdge], bool],
+    goal: Callable[[list[GraphEdge], GraphEdge], bool],
+    max_hops: int = 6,
+) -> list[list[GraphEdge]]:
+    """Edge paths from ``start`` whose last edge satisfies ``goal``.
+
+    ``follow(path, edge)`` decides whether the search continues through
+    ``edge``; ``goal(path, edge)`` whether ``path + [edge]`` is a result.
+    Both see the path so far, so later hops can depend on earlier ones.
+    """
+    results = []
+    stack: list[tuple[str, list[GraphEdge]]] = [(start, [])]
+    while stack:
+        node, path = stack.pop()
+        seen = {start} | {e.target_id for e in path}
+        for edge in graph.out_edges(node):
+            if goal(path, edge):
+                results.append(path + [edge])
+            elif len(path) + 1 < max_hops and edge.target_id not in seen and follow(path, edge):
+                stack.append((edge.target_id, path + [edge]))
+    return sorted(results, key=lambda p: [e.id for e in p])
+
+
+def _props(graph: PrivilegeGraph, node_id: str) -> dict[str, Any]:
+    node = graph.nodes.get(node_id)
+    return node.properties if node else {}
+
+
+def _cpi_hop_allowed(graph: PrivilegeGraph, edge: GraphEdge) -> bool:
+    """A CPI into an instruction is reachable if the callee is open or the caller's PDA signs for it."""
+    callee = graph.nodes.get(edge.target_id)
+    if callee is None or callee.type != "instruction":
+        return False
+    return not callee.properties["authenticated"] or edge.properties["pda_signed"]
+
+
+def _follow_calls(graph: PrivilegeGraph) -> Callable[[list[GraphEdge], GraphEdge], bool]:
+    def follow(path: list[GraphEdge], edge: GraphEdge) -> bool:
+        if edge.type == "cpi":
+            return _cpi_hop_allowed(graph, edge)
+        return edge.type == "uses"
+    return follow
+
+
+def _lamport_drain(graph: PrivilegeGraph, start: str) -> list[list[GraphEdge]]:
+    def goal(path: list[GraphEdge], edge: GraphEdge) -> bool:
+        return (edge.type == "lamport_transfer" and bool(path) and path[-1].type == "uses"
+                and _props(graph, edge.target_id).get("caller_chosen", edge.target_id.startswith("external:")))
+    return find_paths(graph, start, _follow_calls(graph), goal)
+
+
+def _authority_takeover(graph: PrivilegeGraph, start: str) -> list[list[GraphEdge]]:
+    def follow(path: list[GraphEdge], edge: GraphEdge) -> bool:
+        return not path and edge.type == "writes_field"
+
+    def goal(path: list[GraphEdge], edge: GraphEdge) -> bool:
+        return (edge.type == "gates" and bool(path) and path[-1].type == "writes_field"
+                and edge.properties["field"] == path[-1].properties["field"] and edge.target_id != start)
+    return find_paths(graph, start, follow, goal, max_hops=2)
+
+
+def _pda_transfer(graph: PrivilegeGraph, start: str) -> list[list[GraphEdge]]:
+    def goal(path: list[GraphEdge], edge: GraphEdge) -> bool:
+        if edge.type != "cpi" or not edge.properties["pda_signed"] or not _TRANSFER_CALLEES.search(edge.properties["callee"]):
+            return False
+        roles = edge.properties["roles"]
+        caller = _props(graph, edge.source_id)
+        to = _props(graph, _acc_id(caller["program"], edge.source_id.split(".", 1)[1], roles.get("to", "")))
+        signer = roles.get("authority", roles.get("from"))
+        return signer in edge.properties["pda_signers"] and to.get("caller_chosen", False)
+
+    def follow(path: list[GraphEdge], edge: GraphEdge) -> bool:
+        return edge.type == "cpi" and _cpi_hop_allowed(graph, edge)
+    return find_paths(graph, start, follow, goal)
+
+
+# Query name → (search, severity, description)
+QUERIES: dict[str, tuple[Callable[[PrivilegeGraph, str], list[list[GraphEdge]]], str, str]] = {
+    "lamport-drain": (_lamport_drain, "high",
+                      "Unauthenticated instruction to a lamport transfer out of a program-owned account"),
+    "authority-takeover": (_authority_takeover, "high",
+                           "Unauthenticated write to a state field another instruction is gated on"),
+    "pda-transfer": (_pda_transfer, "high",
+                     "Unauthenticated instruction to a PDA-signed transfer to a caller-chosen account"),
+}
+
+
+def _describe(graph: PrivilegeGraph, edge: GraphEdge) -> Hop:
+    p = edge.properties
+    src, dst = graph.nodes[edge.source_id], graph.nodes[edge.target_id]
+    at = _props(graph, p.get("instruction", edge.source_id))
+    program, file = at.get("program", ""), at.get("file", "")
+    if edge.type == "uses":
+        flags = [f for f in ("signer", "writable") if p[f]]
+        chosen = " (caller-chosen)" if dst.properties["caller_chosen"] else ""
+        note = f"takes `{dst.properties['name']}`{chosen}" + (f" as {'/'.join(flags)}" if flags else "")
+        return Hop(src.id, edge.type, dst.id, note, program, dst.properties["file"], dst.properties["line"])
+    if edge.type == "cpi":
+        signed = f", signed by PDA {', '.join(p['pda_signers']) or '?'}" if p["pda_signed"] else ""
+        if dst.type == "instruction" and dst.properties["authenticated"]:
+            signed += f", which satisfies its authority `{'`, `'.join(dst.properties['authorities'])}`"
+        return Hop(src.id, edge.type, dst.id, f"{p['kind']} CPI to {dst.label}{signed}", program, file, p["line"])
+    if edge.type == "lamport_transfer":
+        note = f"moves lamports out of `{src.properties['name']}` to `{dst.label}` ({p['via']})"
+        return Hop(src.id, edge.type, dst.id, note, program, file, p["line"])
+    if edge.type == "writes_field":
+        note = f"writes `{p['account']}.{p['field']}` ({dst.label})"
+        return Hop(src.id, edge.type, dst.id, note, program, file, p["line"])
+    if edge.type == "gates":
+        note = f"`{dst.label}` trusts {src.label}.{p['field']} (via `{p['account']}`)"
+        gated = dst.properties
+        return Hop(src.id, edge.type, dst.id, note, gated["program"], gated["file"], p["line"])
+    return Hop(src.id, edge.type, dst.id, edge.type, program, file, p.get("line", 0))
+
+
+def run_query(graph: PrivilegeGraph, name: str) -> list[PrivilegePath]:
+    """Run canned query ``name`` from every unauthenticated instruction."""
+    search = QUERIES[name][0]
+    results = []
+    for node in graph.of_type("instruction"):
+        if node.properties["authenticated"]:
+            continue
+        entry = Hop("", "entry", node.id, f"`{node.label}` requires no authority signer",
+                    node.properties["program"], node.properties["file"], node.properties["line"])
+        for path in search(graph, node.id):
+            results.append(PrivilegePath(name, node.id, [entry] + [_describe(graph, e) for e in path]))
+    return results
+
+
+@dataclass
+class PrivilegePathFinding:
+    """A multi-step privilege-escalation path."""
+
+    path: PrivilegePath
+    roots: dict[str, str] = field(default_factory=dict)  # Program name → root relative to the project
+
+    @property
+    def severity(self) -> str:
+        return self.path.severity
+
+    def _file(self, hop: Hop) -> str:
+        root = self.roots.get(hop.program, ".")
+        return hop.file if root == "." or not hop.file else (Path(root) / hop.file).as_posix()
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        p = self.path
+        steps = [{**h.to_dict(), "file": self._file(h), "step": i + 1} for i, h in enumerate(p.hops)]
+        description = (
+            f"{QUERIES[p.query][2]}:\n"
+            + "\n".join(f"{s['step']}. {s['note']} ({s['file']}:{s['line']})" for s in steps)
+        )
+        entry = p.hops[0].note.split("`")[1]
+        return {
+            "title": f"Privilege escalation path from {entry}: {p.query}",
+            "description": description,
+            "vulnerability_type": f"privilege-path-{p.query}",
+            "severity": p.severity,
+            "confidence": 0.5,
+            "status": "proposed",
+            "node_refs": list(dict.fromkeys([h.target for h in p.hops])),
+            "evidence": [],
+            "properties": {
+                "source_tool": "privilege-paths",
+                "category": "access-control",
+                "source_files": list(dict.fromkeys(s["file"] for s in steps if s["file"])),
+                "affected_lines": [s["line"] for s in steps],
+                "steps": steps,
+                "query": p.query,
+            },
+        }
+
+
+class PrivilegePathAnalyzer:
+    """Runs the canned privilege-escalation queries over the workspace graph."""
+
+    def __init__(self, queries: list[str] | None = None):
+        """Initialize the analyzer.
+
+        Args:
+            queries: Query names to run (default: all of ``QUERIES``)
+        """
+        self.queries = queries or list(QUERIES)
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{ANALYZER_VERSION}"
+
+    def run(self, project_path: Path) -> tuple[list[PrivilegePathFinding], dict]:
+        """Build the graph for ``project_path`` and run every configured query.
+
+        Returns:
+            Tuple of (findings, metadata); metadata["graph"] is the exported graph
+        """
+        project_path = Path(project_path)
+        programs = load_programs(project_path)
+        roots = {p.name: Path(os.path.relpath(p.root, project_path)).as_posix() for p in programs}
+        graph = build_privilege_graph(programs)
+        findings = [PrivilegePathFinding(path, roots) for q in self.queries for path in run_query(graph, q)]
+        metadata = {
+            "tool": "privilege-paths",
+            "version": ANALYZER_VERSION,
+            "success": True,
+            "error": None,
+            "graph": graph.to_dict(),
+            "paths": {q: sum(1 for f in findings if f.path.query == q) for q in self.queries},
+        }
+        return findings, metadata
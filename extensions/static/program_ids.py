@@ -0,0 +1,244 @@
+"""
+Hardcoded program id verification against known deployments.
+
+Programs pin the programs they call by address: `pubkey!("...")`
+constants, `address = ..` constraints, key comparisons in handlers. An
+address that is almost a well-known id (a vanity prefix ground to match
+`Tokenkeg...`, a character swapped) or that sits where code names a known
+program but is some other deployment (a fork of Metaplex, a devnet Pyth
+receiver) makes every call go to a program the auditor never reviewed.
+
+Collects every base58 literal that decodes to 32 bytes from the Rust
+sources under the project (the projects' own `declare_id!` ids excepted)
+and compares it with the knowledge base's fingerprints of common programs
+(`extensions/knowledge/programs/`):
+
+- known: the address is a canonical deployment; recorded in the metadata
+  as the project's dependency inventory
+- lookalike: not a known address, but within a few edits of one or sharing
+  its vanity prefix, so a reader takes it for the real program
+- fork: not a known address, but bound to or compared with a name that
+  refers to a known program (`TOKEN_PROGRAM_ID`, `ctx.accounts.token_program`)
+
+Built in (no external tool), so it is always available.
+"""
+
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from extensions.ir.anchor_config import b58decode
+from extensions.ir.rust_source import find_matching, strip_comments
+from extensions.ir.workspace import is_skipped
+from extensions.knowledge.program_loader import KnownProgram, ProgramLoader
+
+CHECKER_VERSION = "1.0.0"
+
+_LITERAL_RE = re.compile(r"\"(?P<address>[1-9A-HJ-NP-Za-km-z]{32,44})\"")
+_DECLARE_ID_RE = re.compile(r"declare_id!\s*\(\s*\"(?P<address>[1-9A-HJ-NP-Za-km-z]{32,44})\"")
+_IDENT_RE = re.compile(r"\b[A-Za-z_]\w*\b")
+_FIELD_RE = re.compile(r"\s*(?:#\[[^\]]*\]\s*)*pub\s+(?P<name>[A-Za-z_]\w*)\s*:")
+
+# Edits within which an unknown address passes for a known one; a shared vanity prefix of PREFIX
+# characters, or AFFIX characters at both ends, does too
+MAX_EDITS = 4
+PREFIX = 6
+AFFIX = 4
+
+
+@dataclass
+class ProgramIdFinding:
+    """A hardcoded address that passes for a known program without being its deployment."""
+
+    kind: str  # lookalike, fork
+    address: str
+    program: KnownProgram
+    path: str
+    line: int
+    text: str
+    name: str | None = None  # Identifier tying the address to the program (forks)
+    edits: int | None = None  # Edit distance to the closest known address (lookalikes)
+
+    @property
+    def severity(self) -> str:
+        return "high" if self.kind == "lookalike" else "medium"
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        known = self.program
+        if self.kind == "lookalike":
+            title = f"Lookalike {known.name} program id `{self.address[:8]}…`"
+            why = (f"`{self.address}` is not {known.name} (`{known.program_id}`) but differs from it by "
+                   f"{self.edits} character(s) or shares its vanity prefix, so it reads as the real program. "
+                   "Calls and ownership checks against it trust whatever program is deployed there.")
+        else:
+            title = f"`{self.name}` points at a non-canonical {known.name} deployment"
+            why = (f"`{self.name}` names {known.name}, whose canonical deployment is `{known.program_id}`, but the "
+                   f"code pins `{self.address}`: a fork or another cluster's deployment, which may not behave like "
+                   "the audited program.")
+        return {
+            "title": title,
+            "description": f"{why} Found in `{self.text}`. Use the id the program's crate exports "
+                           f"(e.g. `{known.id}::ID`) or confirm the deployment with its maintainers.",
+            "vulnerability_type": f"program-id-{self.kind}",
+            "severity": self.severity,
+            "confidence": 0.7 if self.kind == "lookalike" else 0.5,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "program-ids",
+                "category": "dependency",
+                "precision": "syntactic",
+                "source_files": [self.path],
+                "affected_lines": [self.line],
+                "address": self.address,
+                "known_program": known.id,
+                "canonical_address": known.program_id,
+                "name": self.name,
+                "edits": self.edits,
+                "docs": known.docs,
+            },
+        }
+
+
+def is_pubkey(value: str) -> bool:
+    return len(b58decode(value)) == 32
+
+
+def edit_distance(a: str, b: str) -> int:
+    """Levenshtein distance of ``a`` and ``b``."""
+    previous = list(range(len(b) + 1))
+    for i, ca in enumerate(a, 1):
+        current = [i]
+        for j, cb in enumerate(b, 1):
+            current.append(min(previous[j] + 1, current[j - 1] + 1, previous[j - 1] + (ca != cb)))
+        previous = current
+    return previous[-1]
+
+
+def _common_prefix(a: str, b: str) -> int:
+    n = 0
+    while n < min(len(a), len(b)) and a[n] == b[n]:
+        n += 1
+    return n
+
+
+def lookalike(address: str, programs: list[KnownProgram]) -> tuple[KnownProgram, int] | None:
+    """The known program ``address`` passes for, and its edit distance to it."""
+    best = None
+    for program in programs:
+        for known in program.addresses:
+            edits = edit_distance(address, known)
+            # Native ids are padded with `1`s (`Stake111...`); only a vanity prefix or suffix counts
+            vanity = not known.endswith("1")
+            prefix, suffix = _common_prefix(address, known), _common_prefix(address[::-1], known[::-1])
+            if edits <= MAX_EDITS or vanity and (prefix >= PREFIX or prefix >= AFFIX and suffix >= AFFIX):
+                if best is None or edits < best[1]:
+                    best = (program, edits)
+    return best
+
+
+def _statement(source: str, pos: int) -> tuple[int, int]:
+    start = max(source.rfind(";", 0, pos), source.rfind("{", 0, pos), source.rfind("}", 0, pos)) + 1
+    end = min((i for i in (source.find(";", pos), source.find("{", pos)) if i >= 0), default=len(source))
+    return start, end
+
+
+def _names(source: str, pos: int) -> tuple[list[str], str]:
+    """Identifiers the literal at ``pos`` is bound to or compared with, and its statement text."""
+    start, end = _statement(source, pos)
+    attr = source.rfind("#[", start, pos)
+    close = find_matching(source, attr + 1) if attr >= 0 else -1
+    if close > pos:
+        # `#[account(address = pubkey!("..."))] pub token_program: ..` names the field after the attribute
+        field = _FIELD_RE.match(source, close + 1)
+        start, end = attr, field.end() if field else close + 1
+    names = _IDENT_RE.findall(source[start:end])
+    return names, " ".join(source[start:end].split())
+
+
+def _snake(name: str) -> str:
+    return re.sub(r"(?<=[a-z0-9])(?=[A-Z])", "_", name).lower()
+
+
+class ProgramIdChecker:
+    """Flags hardcoded program ids that imitate or stand in for known programs."""
+
+    def __init__(self, include_forks: bool = True, programs_dir: str | None = None):
+        """Initialize the checker.
+
+        Args:
+            include_forks: Also report addresses named like a known program that are another deployment
+            programs_dir: Directory of known program fingerprints (default: the knowledge base's)
+        """
+        self.include_forks = include_forks
+        self.loader = ProgramLoader(Path(programs_dir) if programs_dir else None)
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{CHECKER_VERSION}"
+
+    def check_source(self, source: str, path: str, own: set[str]) -> tuple[list[ProgramIdFinding], list[dict]]:
+        """Findings and known-program references in one Rust file; ``own`` holds the project's program ids."""
+        programs = self.loader.get_by_chain("solana")
+        source = strip_comments(source)
+        findings: list[ProgramIdFinding] = []
+        known: list[dict] = []
+        for m in _LITERAL_RE.finditer(source):
+            address = m.group("address")
+            if address in own or not is_pubkey(address):
+                continue
+            line = source.count("\n", 0, m.start()) + 1
+            program = self.loader.by_address(address)
+            if program:
+                known.append({"program": program.id, "name": program.name, "address": address, "path": path,
+                              "line": line})
+                continue
+            names, text = _names(source, m.start())
+            match = lookalike(address, programs)
+            if match:
+                findings.append(ProgramIdFinding("lookalike", address, match[0], path, line, text, edits=match[1]))
+                continue
+            if not self.include_forks:
+                continue
+            for name in names:
+                program = self.loader.classify(_snake(name))
+                if program:
+                    findings.append(ProgramIdFinding("fork", address, program, path, line, text, name=name))
+                    break
+        return findings, known
+
+    def run(self, project_path: Path) -> tuple[list[ProgramIdFinding], dict]:
+        """Check every Rust source file under ``project_path``.
+
+        Returns:
+            Tuple of (findings, metadata); metadata["known_programs"] lists references to canonical deployments
+        """
+        project_path = Path(project_path)
+        files = [project_path] if project_path.is_file() else sorted(
+            p for p in project_path.rglob("*.rs") if p.is_file() and not is_skipped(p.relative_to(project_path)))
+        sources = {}
+        for file in files:
+            try:
+                sources[file] = file.read_text(errors="replace")
+            except OSError:
+                continue
+        own = {m.group("address") for source in sources.values() for m in _DECLARE_ID_RE.finditer(source)}
+        findings: list[ProgramIdFinding] = []
+        known: list[dict] = []
+        for file, source in sources.items():
+            rel = file.name if project_path.is_file() else file.relative_to(project_path).as_posix()
+            found, refs = self.check_source(source, rel, own)
+            findings += found
+            known += refs
+        metadata = {
+            "tool": "program-ids",
+            "version": CHECKER_VERSION,
+            "success": True,
+            "error": None,
+            "files_checked": len(sources),
+            "known_programs": known,
+        }
+        return findings, metadata
@@ -0,0 +1,111 @@
+"""
+Progress events for static analysis runs.
+
+The pipeline reports what it is doing as a sequence of events, so long scans
+of large protocols can show files parsed, detectors completed and findings
+so far instead of running silently:
+
+    started              detectors_total is known
+    files                source files counted (files_parsed)
+    detector_started     one detector is running
+    detector_completed   it finished (or was replayed from the cache, cached);
+                         findings is the running total
+    detector_skipped     it is not installed
+    finished             hypotheses is the deduplicated count
+
+`ScanProgress` keeps the events of one run so several readers (the CLI and
+any number of API clients) can follow it from their own position.
+"""
+
+import threading
+from dataclasses import asdict, dataclass, field
+from datetime import datetime
+from typing import Any, Callable, Iterator
+
+PROGRESS_EVENTS = (
+    "started", "files", "detector_started", "detector_completed", "detector_skipped", "finished", "failed",
+)
+
+
+@dataclass
+class ProgressEvent:
+    """One step of a scan, with the counters as of that step."""
+
+    event: str
+    detectors_total: int = 0
+    detectors_completed: int = 0
+    files_parsed: int = 0
+    findings: int = 0
+    detector: str | None = None
+    hypotheses: int | None = None
+    error: str | None = None
+    cached: bool | None = None
+    seq: int = 0
+    at: str = field(default_factory=lambda: datetime.now().isoformat())
+
+    @property
+    def done(self) -> bool:
+        return self.event in ("finished", "failed")
+
+    def to_dict(self) -> dict[str, Any]:
+        return {k: v for k, v in asdict(self).items() if v is not None}
+
+
+ProgressCallback = Callable[[ProgressEvent], None]
+
+
+class ScanProgress:
+    """Thread-safe event log of one scan.
+
+    Pass ``scan_progress.emit`` as the pipeline's progress callback; readers
+    call `follow` to get every event from a position onwards, blocking until
+    new ones arrive or the scan ends.
+    """
+
+    def __init__(self):
+        self._events: list[ProgressEvent] = []
+        self._cond = threading.Condition()
+
+    def emit(self, event: ProgressEvent) -> None:
+        with self._cond:
+            event.seq = len(self._events)
+            self._events.append(event)
+            self._cond.notify_all()
+
+    @property
+    def latest(self) -> ProgressEvent | None:
+        with self._cond:
+            return self._events[-1] if self._events else None
+
+    @property
+    def done(self) -> bool:
+        latest = self.latest
+        return latest is not None and latest.done
+
+    def events(self, since: int = 0) -> list[ProgressEvent]:
+        with self._cond:
+            return self._events[since:]
+
+    def follow(self, since: int = 0, timeout: float | None = None) -> Iterator[ProgressEvent]:
+        """Yield events from ``since`` until the scan ends.
+
+        Args:
+            since: Sequence number of the first event to yield
+            timeout: Give up after this many seconds without a new event
+        """
+        position = since
+        while True:
+            with self._cond:
+                if not self._cond.wait_for(
+                    lambda: len(self._events) > position or (self._events and self._events[-1].done),
+                    timeout,
+                ):
+                    return
+                pending = self._events[position:]
+            for event in pending:
+                position = event.seq + 1
+                yield event
+                if event.done:
+                    return
+            if not pending:
+                return
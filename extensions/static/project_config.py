@@ -0,0 +1,212 @@
+"""
+Project configuration from a workspace's ``baskerville.toml``.
+
+The file that pins knowledge bundles (see extensions/knowledge/bundles.py)
+also holds the project's scan settings, read by the CLI and by
+``StaticAnalysisPipeline(config=...)``:
+
+    [rules]
+    enable = ["missing-signer", "SOL-003"]   # Only these (default: every rule)
+    disable = ["program-ids", "SOL-016"]
+    [rules.severity]                         # Reported severity of a rule's hits
+    SOL-008 = "low"
+    "missing-owner/missing-owner-check" = "critical"
+
+    [paths]
+    ignore = ["programs/legacy/**", "tests/**"]
+
+    [templates]
+    dirs = ["audit/pocs"]                    # PoC template roots, relative to the file
+
+    [output]
+    format = "json"                          # scan: table or json
+    report_format = "sarif"                  # report findings: json, markdown or sarif
+    min_confidence = "medium"
+    sarif = "baskerville.sarif"
+
+Rules are named as in ``baskerville:allow`` comments: stable codes (short
+or full), rule ids or detector names. A detector every ``enable`` entry
+leaves out is not run, nor one ``disable`` names; the other entries filter
+hits. ``ignore`` globs match hits' files relative to the workspace.
+Command-line flags win over the file: ``--tool`` replaces ``enable`` and
+the detectors ``disable`` names, and any option given replaces its
+``[output]`` default.
+"""
+
+from dataclasses import dataclass, field
+from fnmatch import fnmatch
+from pathlib import Path
+from typing import Any, Iterable
+
+try:
+    import tomllib
+except ModuleNotFoundError:  # Python < 3.11
+    import tomli as tomllib
+
+from .confidence import parse_confidence
+from .finding import normalize_severity, SEVERITIES
+from .rules import RuleCatalog, load_catalog
+from .suppressions import allows
+
+CONFIG_FILE = "baskerville.toml"
+SCAN_FORMATS = ("table", "json")
+REPORT_FORMATS = ("json", "markdown", "sarif")
+
+
+class ConfigError(ValueError):
+    """A baskerville.toml that does not parse or has invalid settings."""
+    pass
+
+
+def _strings(value: Any, where: str) -> list[str]:
+    if value is None:
+        return []
+    if isinstance(value, str):
+        value = [value]
+    if not isinstance(value, list) or not all(isinstance(v, str) and v.strip() for v in value):
+        raise ConfigError(f"{where} must be a list of strings")
+    return [v.strip() for v in value]
+
+
+@dataclass
+class ProjectConfig:
+    """Scan settings of one workspace."""
+
+    path: Path | None = None  # The file they came from (None: defaults)
+    enable: list[str] = field(default_factory=list)
+    disable: list[str] = field(default_factory=list)
+    severity: dict[str, str] = field(default_factory=dict)  # Rule -> reported severity
+    ignore: list[str] = field(default_factory=list)
+    template_dirs: list[Path] = field(default_factory=list)
+    output: dict[str, str] = field(default_factory=dict)
+
+    @property
+    def root(self) -> Path | None:
+        return self.path.parent if self.path else None
+
+    @classmethod
+    def load(cls, workspace: Path) -> "ProjectConfig":
+        """The settings in ``workspace``'s baskerville.toml (defaults without one); raises ConfigError."""
+        workspace = Path(workspace)
+        path = (workspace if workspace.is_dir() else workspace.parent) / CONFIG_FILE
+        if not path.is_file():
+            return cls()
+        try:
+            data = tomllib.loads(path.read_text())
+        except (OSError, tomllib.TOMLDecodeError) as e:
+            raise ConfigError(f"cannot read {path}: {e}") from None
+        try:
+            return cls.from_dict(data, path)
+        except ConfigError as e:
+            raise ConfigError(f"{path}: {e}") from None
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any], path: Path | None = None) -> "ProjectConfig":
+        """Settings from a parsed baskerville.toml (``[[bundle]]`` pins are read by bundles.py)."""
+        rules = data.get("rules") or {}
+        paths = data.get("paths") or {}
+        templates = data.get("templates") or {}
+        output = data.get("output") or {}
+        for name, section in (("rules", rules), ("paths", paths), ("templates", templates), ("output", output)):
+            if not isinstance(section, dict):
+                raise ConfigError(f"[{name}] must be a table")
+
+        severity = rules.get("severity") or {}
+        if not isinstance(severity, dict):
+            raise ConfigError("[rules.severity] must be a table of rule = severity")
+        for rule, value in severity.items():
+            if not isinstance(value, str) or value.lower() not in (*SEVERITIES, "informational"):
+                raise ConfigError(f"[rules.severity] {rule}: expected one of {', '.join(SEVERITIES)}, got {value!r}")
+
+        output = {key: str(value) for key, value in output.items()}
+        if output.get("format") and output["format"] not in SCAN_FORMATS:
+            raise ConfigError(f"[output] format must be one of {', '.join(SCAN_FORMATS)}")
+        if output.get("report_format") and output["report_format"] not in REPORT_FORMATS:
+            raise ConfigError(f"[output] report_format must be one of {', '.join(REPORT_FORMATS)}")
+        if output.get("min_confidence"):
+            try:
+                parse_confidence(output["min_confidence"])
+            except ValueError as e:
+                raise ConfigError(f"[output] min_confidence: {e}") from None
+
+        base = path.parent if path else Path.cwd()
+        return cls(
+            path=path,
+            enable=_strings(rules.get("enable"), "[rules] enable"),
+            disable=_strings(rules.get("disable"), "[rules] disable"),
+            severity={rule: normalize_severity(value) for rule, value in severity.items()},
+            ignore=_strings(paths.get("ignore"), "[paths] ignore"),
+            template_dirs=[base / Path(d).expanduser() for d in _strings(templates.get("dirs"), "[templates] dirs")],
+            output=output,
+        )
+
+    def default(self, key: str, given: Any = None) -> Any:
+        """``given`` (a command-line value) if set, else the ``[output]`` default for ``key``."""
+        return given if given not in (None, "", ()) else self.output.get(key)
+
+    def tools(self, available: Iterable[str], catalog: RuleCatalog | None = None) -> list[str]:
+        """The detectors of ``available`` the rules leave to run."""
+        available = list(available)
+        disabled = set(self.disable) & set(available)
+        if not self.enable:
+            return [t for t in available if t not in disabled]
+        catalog = catalog or load_catalog()
+        wanted = {_detector_of(entry, available, catalog) for entry in self.enable}
+        # An entry that names no detector cannot narrow the run
+        if None in wanted:
+            return [t for t in available if t not in disabled]
+        return [t for t in available if t in wanted and t not in disabled]
+
+    def ignored(self, hit: dict[str, Any]) -> bool:
+        """Whether every file of ``hit`` is under an ignored path."""
+        files = (hit.get("properties") or {}).get("source_files") or []
+        files = [self._relative(str(f).split("#", 1)[0]) for f in files if f]
+        return bool(files and self.ignore) and all(any(fnmatch(f, glob) for glob in self.ignore) for f in files)
+
+    def _relative(self, file: str) -> str:
+        path = Path(file)
+        root = self.root.resolve() if self.root else None
+        if path.is_absolute() and root and path.resolve().is_relative_to(root):
+            return path.resolve().relative_to(root).as_posix()
+        return path.as_posix()
+
+    def apply(self, hits: list[dict[str, Any]], tools: Iterable[str] | None = None) -> tuple[list[dict], list[dict]]:
+        """Drop hits of disabled rules and ignored paths, and set configured severities.
+
+        Args:
+            hits: Hypotheses with their rule ids and codes
+            tools: Detectors chosen on the command line, which replace the rules' choice of detectors
+
+        Returns:
+            Tuple of (kept hits, dropped hits)
+        """
+        chosen = set(tools) if tools is not None else None
+        enable = self.enable if chosen is None else []
+        disable = [entry for entry in self.disable if chosen is None or entry not in chosen]
+        kept, dropped = [], []
+        for hit in hits:
+            if (self.ignored(hit) or any(allows(entry, hit) for entry in disable)
+                    or (enable and not any(allows(entry, hit) for entry in enable))):
+                dropped.append(hit)
+                continue
+            for rule, severity in self.severity.items():
+                if allows(rule, hit):
+                    if hit.get("severity") != severity:
+                        hit.setdefault("properties", {})["detector_severity"] = hit.get("severity")
+                        hit["severity"] = severity
+                    break
+            kept.append(hit)
+        return kept, dropped
+
+
+def _detector_of(entry: str, detectors: list[str], catalog: RuleCatalog) -> str | None:
+    """The detector a rules entry belongs to, if that can be told from the entry."""
+    if entry in detectors:
+        return entry
+    rule = entry
+    if "/" not in entry:
+        code = entry.upper()
+        matches = [c for c in catalog.codes if c == code or c.startswith(code + "-")]
+        rule = catalog.rule_for_code(matches[0]) if len(matches) == 1 else None
+    detector = rule.split("/", 1)[0] if rule and "/" in rule else None
+    return detector if detector in detectors else None
@@ -0,0 +1,340 @@
+"""
+Protocol parameter extraction and sanity checks.
+
+Collects the protocol parameters a codebase hardcodes (fees in bps, LTV
+ratios, liquidation bonuses, cooldowns and timelocks, oracle staleness
+bounds, max supply) from two places:
+
+- constants: Rust and Move `const NAME: T = ...;`, Solidity
+  `uint256 constant NAME = ...;` (and `immutable` with a literal value)
+- config accounts: literal values an Anchor handler writes into a field of
+  one of the program's `#[account]` types (`config.fee_bps = 30;`)
+
+Values are evaluated (underscores, integer suffixes, `10u64.pow(6)`,
+Solidity time and ether units, arithmetic over earlier constants) and
+scaled by the unit the name gives (``_bps``, ``_pct``, ``_wad``, ``_ms``,
+``_slots``, ``_days``, ...). Ratios without a unit in the name fall back to
+magnitude (above 1e15 is 1e18-scaled, above 100 is bps), and small integers
+that could be percent or bps are reported as unclear instead of guessed.
+
+Each value is checked against the sane range of its kind in the knowledge
+base (`extensions/knowledge/parameters/`); values outside it are findings.
+Every parameter, in range or not, goes in the run metadata, and from there
+into the report's "Protocol Parameters" appendix for the client to confirm.
+
+Built in (no external tool), so it is always available.
+"""
+
+import ast
+import operator
+import os
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from extensions.ir import load_programs
+from extensions.ir.rust_source import strip_comments
+from extensions.ir.workspace import is_skipped
+from extensions.knowledge.parameter_loader import ParameterLoader, ParameterRange
+
+CHECKER_VERSION = "1.0.0"
+SUFFIXES = (".rs", ".sol", ".move")
+
+# `pub const MAX_FEE_BPS: u16 = 1_000;` (Rust, Move)
+_CONST_RE = re.compile(r"\bconst\s+(?P<name>[A-Za-z_]\w*)\s*:\s*[^=;]+=\s*(?P<value>[^;]+);")
+# `uint256 public constant MAX_FEE_BPS = 1_000;`, `uint64 immutable COOLDOWN = 2 days;`
+_SOL_CONST_RE = re.compile(
+    r"\b(?:u?int\d*)\s+(?:(?:public|private|internal)\s+)*(?:constant|immutable)\s+"
+    r"(?:(?:public|private|internal)\s+)*(?P<name>[A-Za-z_]\w*)\s*=\s*(?P<value>[^;]+);"
+)
+# `ctx.accounts.config.fee_bps = 30;` (a plain assignment, not `+=` or `==`)
+_FIELD_WRITE_RE = re.compile(r"\b[A-Za-z_]\w*\s*\.\s*(?P<field>[A-Za-z_]\w*)\s*=(?!=)\s*(?P<value>[^;]+);")
+
+_RATIO_SCALES = [
+    (re.compile(r"(^|_)(bps|basis_points?)($|_)"), 10_000.0, "bps"),
+    (re.compile(r"(^|_)(pct|percent|percentage)($|_)"), 100.0, "percent"),
+    (re.compile(r"(^|_)ppm($|_)"), 1_000_000.0, "ppm"),
+    (re.compile(r"(^|_)wad($|_)"), 1e18, "1e18"),
+    (re.compile(r"(^|_)ray($|_)"), 1e27, "1e27"),
+]
+_DURATION_SCALES = [
+    (re.compile(r"(^|_)(ms|millis|milliseconds)($|_)"), 0.001, "milliseconds"),
+    (re.compile(r"(^|_)slots?($|_)"), 0.4, "slots"),
+    (re.compile(r"(^|_)(mins?|minutes?)($|_)"), 60.0, "minutes"),
+    (re.compile(r"(^|_)(hrs?|hours?)($|_)"), 3600.0, "hours"),
+    (re.compile(r"(^|_)days?($|_)"), 86400.0, "days"),
+    (re.compile(r"(^|_)epochs?($|_)"), None, "epochs"),
+]
+_SOL_UNITS = {"seconds": 1, "minutes": 60, "hours": 3600, "days": 86400, "weeks": 604800,
+              "wei": 1, "gwei": 10**9, "ether": 10**18}
+
+_OPS = {ast.Add: operator.add, ast.Sub: operator.sub, ast.Mult: operator.mul, ast.Div: operator.truediv,
+        ast.FloorDiv: operator.floordiv, ast.Mod: operator.mod, ast.Pow: operator.pow,
+        ast.LShift: operator.lshift, ast.RShift: operator.rshift}
+
+
+def snake_case(name: str) -> str:
+    """`MAX_FEE_BPS` -> `max_fee_bps`, `liquidationBonus` -> `liquidation_bonus`."""
+    return re.sub(r"(?<=[a-z0-9])(?=[A-Z])", "_", name).lower()
+
+
+def evaluate(expr: str, constants: dict[str, float] | None = None) -> float | None:
+    """Numeric value of a literal expression, or None when it is not one."""
+    text = " ".join(expr.split())
+    text = re.sub(r"\bas\s+[ui](?:8|16|32|64|128|size)\b", "", text)
+    text = re.sub(r"(\d(?:[\d_]*\d)?(?:\.\d+)?)_?(?:[ui](?:8|16|32|64|128|256|size)|f32|f64)\b", r"\1", text)
+    text = re.sub(r"(\d[\d_.]*(?:e\d+)?)\s+(" + "|".join(_SOL_UNITS) + r")\b",
+                  lambda m: f"({m.group(1)} * {_SOL_UNITS[m.group(2)]})", text)
+    text = re.sub(r"(\w+|\([^()]*\))\.pow\(([^()]*)\)", r"(\1 ** \2)", text)
+    try:
+        tree = ast.parse(text, mode="eval")
+    except SyntaxError:
+        return None
+
+    def walk(node: ast.AST) -> float:
+        if isinstance(node, ast.Expression):
+            return walk(node.body)
+        if isinstance(node, ast.Constant) and isinstance(node.value, (int, float)) and not isinstance(node.value, bool):
+            return node.value
+        if isinstance(node, ast.Name) and constants and node.id in constants:
+            return constants[node.id]
+        if isinstance(node, ast.UnaryOp) and isinstance(node.op, ast.USub):
+            return -walk(node.operand)
+        if isinstance(node, ast.BinOp) and type(node.op) in _OPS:
+            left, right = walk(node.left), walk(node.right)
+            if isinstance(node.op, ast.Pow) and abs(right) > 80:
+                raise ValueError("exponent too large")
+            return _OPS[type(node.op)](left, right)
+        raise ValueError("not a literal")
+
+    try:
+        value = walk(tree)
+    except (ValueError, ZeroDivisionError, OverflowError, TypeError):
+        return None
+    return value
+
+
+def normalize(kind: ParameterRange, name: str, value: float) -> tuple[float | None, str]:
+    """Value in the kind's unit (ratio as a fraction, duration in seconds) and the scale it was read in."""
+    if kind.unit == "ratio":
+        for pattern, scale, label in _RATIO_SCALES:
+            if pattern.search(name):
+                return value / scale, label
+        if value == 0 or (isinstance(value, float) and 0 < value <= 1):
+            return float(value), "fraction"
+        if value >= 1e15:
+            return value / 1e18, "1e18 (assumed)"
+        if 100 < value <= 10_000:
+            return value / 10_000, "bps (assumed)"
+        return None, "unclear"
+    if kind.unit == "duration":
+        for pattern, scale, label in _DURATION_SCALES:
+            if pattern.search(name):
+                return (value * scale if scale is not None else None), label
+        return float(value), "seconds"
+    return float(value), "units"
+
+
+def display(kind_unit: str, value: float | None) -> str:
+    """A normalized value for people: 5%, 2d 4h, 1,000,000."""
+    if value is None:
+        return "?"
+    if kind_unit == "ratio":
+        return f"{value * 100:.4g}%"
+    if kind_unit == "duration":
+        seconds = int(round(value))
+        if seconds == 0:
+            return "0s"
+        parts = []
+        for unit, size in (("d", 86400), ("h", 3600), ("m", 60), ("s", 1)):
+            if seconds >= size:
+                parts.append(f"{seconds // size}{unit}")
+                seconds %= size
+        return " ".join(parts[:2])
+    return f"{value:,.0f}" if float(value).is_integer() else f"{value:,}"
+
+
+def describe_range(kind: ParameterRange) -> str:
+    low = display(kind.unit, kind.min) if kind.min is not None else None
+    high = display(kind.unit, kind.max) if kind.max is not None else None
+    if low and high:
+        return f"{low} – {high}"
+    return f">= {low}" if low else f"<= {high}" if high else "any"
+
+
+@dataclass
+class ProtocolParameter:
+    """A hardcoded protocol parameter and how it compares to its sane range."""
+
+    name: str
+    kind: ParameterRange
+    raw: str
+    value: float | None  # As written (after evaluation)
+    normalized: float | None  # Fraction for ratios, seconds for durations
+    scale: str
+    source: str  # "constant" or "config"
+    path: str
+    line: int
+    account: str | None = None  # Config account type, for "config" parameters
+
+    @property
+    def status(self) -> str:
+        if self.normalized is None:
+            return "unclear"
+        return "ok" if self.kind.contains(self.normalized) else "out-of-range"
+
+    @property
+    def severity(self) -> str:
+        return self.kind.severity
+
+    @property
+    def label(self) -> str:
+        return f"{self.account}.{self.name}" if self.account else self.name
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "name": self.label, "kind": self.kind.id, "kind_name": self.kind.name, "unit": self.kind.unit,
+            "raw": self.raw, "value": self.value, "normalized": self.normalized, "scale": self.scale,
+            "display": display(self.kind.unit, self.normalized), "range": describe_range(self.kind),
+            "min": self.kind.min, "max": self.kind.max, "status": self.status, "source": self.source,
+            "file": self.path, "line": self.line,
+        }
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        shown = display(self.kind.unit, self.normalized)
+        return {
+            "title": f"{self.kind.name} `{self.label}` is outside the sane range ({shown})",
+            "description": (
+                f"`{self.label}` = `{self.raw}` reads as {shown} (as {self.scale}); "
+                f"the expected range is {describe_range(self.kind)}. {self.kind.description}"
+            ),
+            "vulnerability_type": "unsafe-protocol-parameter",
+            "severity": self.kind.severity,
+            "confidence": 0.4,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "protocol-params",
+                "source_files": [self.path],
+                "affected_lines": [self.line],
+                "parameter": self.label,
+                "parameter_kind": self.kind.id,
+                "value": self.normalized,
+                "scale": self.scale,
+                "precision": "syntactic",
+            },
+        }
+
+
+def _line(text: str, pos: int) -> int:
+    return text.count("\n", 0, pos) + 1
+
+
+def extract_constants(source: str, path: str, loader: ParameterLoader,
+                      constants: dict[str, float] | None = None) -> list[ProtocolParameter]:
+    """Parameters among the constants of one source file.
+
+    ``constants`` (name -> value) resolves references to constants declared
+    earlier, and is updated with every numeric constant of the file.
+    """
+    text = strip_comments(source)
+    pattern = _SOL_CONST_RE if path.endswith(".sol") else _CONST_RE
+    constants = {} if constants is None else constants
+    found = []
+    for m in pattern.finditer(text):
+        name, raw = m.group("name"), " ".join(m.group("value").split())
+        value = evaluate(raw, constants)
+        if value is None:
+            continue
+        constants[name] = value
+        snake = snake_case(name)
+        kind = loader.classify(snake)
+        if kind is None:
+            continue
+        normalized, scale = normalize(kind, snake, value)
+        found.append(ProtocolParameter(name, kind, raw, value, normalized, scale, "constant", path,
+                                       _line(text, m.start("name"))))
+    return found
+
+
+def _source_files(project_path: Path) -> list[Path]:
+    if project_path.is_file():
+        return [project_path] if project_path.suffix in SUFFIXES else []
+    return sorted(
+        p for p in project_path.rglob("*")
+        if p.suffix in SUFFIXES and p.is_file() and not is_skipped(p.relative_to(project_path))
+    )
+
+
+class ProtocolParameterChecker:
+    """Extracts hardcoded protocol parameters and checks them against sane ranges."""
+
+    def __init__(self, include_config: bool = True, parameters_dir: str | None = None):
+        """Initialize the checker.
+
+        Args:
+            include_config: Also read literal values written into Anchor config accounts
+            parameters_dir: Directory of parameter range YAML (default: the knowledge base's)
+        """
+        self.include_config = include_config
+        self.loader = ParameterLoader(Path(parameters_dir) if parameters_dir else None)
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{CHECKER_VERSION}"
+
+    def _config_parameters(self, project_path: Path, constants: dict[str, float]) -> list[ProtocolParameter]:
+        found = []
+        for program in load_programs(project_path):
+            if program.chain != "solana":
+                continue
+            owners = {f.name: t.name for t in program.account_types for f in t.fields}
+            root = Path(os.path.relpath(program.root, project_path))
+            for ix in program.instructions:
+                for m in _FIELD_WRITE_RE.finditer(ix.body):
+                    field_name = m.group("field")
+                    if field_name not in owners:
+                        continue
+                    raw = " ".join(m.group("value").split())
+                    value = evaluate(raw, constants)
+                    snake = snake_case(field_name)
+                    kind = self.loader.classify(snake) if value is not None else None
+                    if kind is None:
+                        continue
+                    normalized, scale = normalize(kind, snake, value)
+                    path = (root / ix.source_file).as_posix() if str(root) != "." else ix.source_file
+                    found.append(ProtocolParameter(field_name, kind, raw, value, normalized, scale, "config", path,
+                                                   ix.body_line + ix.body.count("\n", 0, m.start()),
+                                                   owners[field_name]))
+        return found
+
+    def run(self, project_path: Path) -> tuple[list[ProtocolParameter], dict]:
+        """Extract and check the parameters of every source file under ``project_path``.
+
+        Returns:
+            Tuple of (out-of-range parameters, metadata); metadata["parameters"] lists every parameter found
+        """
+        project_path = Path(project_path)
+        parameters: list[ProtocolParameter] = []
+        constants: dict[str, float] = {}
+        for file in _source_files(project_path):
+            try:
+                source = file.read_text(errors="replace")
+            except OSError:
+                continue
+            rel = file.name if project_path.is_file() else file.relative_to(project_path).as_posix()
+            parameters += extract_constants(source, rel, self.loader, constants)
+        if self.include_config and project_path.is_dir():
+            parameters += self._config_parameters(project_path, constants)
+
+        metadata = {
+            "tool": "protocol-params",
+            "version": CHECKER_VERSION,
+            "success": True,
+            "error": None,
+            "parameters": [p.to_dict() for p in parameters],
+        }
+        return [p for p in parameters if p.status == "out-of-range"], metadata
@@ -0,0 +1,246 @@
+"""
+Versioned rule ids, deprecations and migrations.
+
+Every detector hit has a rule id, ``<detector>/<vulnerability-type>``
+(``missing-signer/missing-signer-check``), recorded on the hit as
+``properties.rule_id``. The rule set as a whole is versioned: a release
+that renames, splits or merges rules bumps the version and lists the change
+in ``rule_catalog.yaml``:
+
+- rename: one id becomes another; ``ast_patterns/*`` -> ``ast-patterns/*``
+  renames every rule of a detector
+- split: one id becomes several, and whatever covered it covers each of them
+- merge: several ids become one
+
+Rules retired without a successor are listed under ``deprecated``.
+The built-in detectors' rules also have stable codes
+(``SOL-001-MISSING-SIGNER``), recorded as ``properties.rule_code``; a code
+follows its rule through renames and is never reused.
+Suppressions and baselines record the version they were written against
+and are migrated forward on load (see suppressions.py), so long-lived
+projects keep them across upgrades.
+"""
+
+import re
+from dataclasses import dataclass
+from functools import lru_cache
+from pathlib import Path
+from typing import Any
+
+import yaml
+
+from .confidence import source_tool
+
+CATALOG_FILE = Path(__file__).parent / "rule_catalog.yaml"
+MIGRATION_KINDS = ("rename", "split", "merge")
+# <chain or GEN>-<number>-<NAME>
+CODE_PATTERN = re.compile(r"^[A-Z]{2,5}-\d{3}-[A-Z0-9]+(?:-[A-Z0-9]+)*$")
+
+
+class RuleCatalogError(ValueError):
+    """A rule catalog that does not parse or describes impossible migrations."""
+    pass
+
+
+def rule_id(hypothesis: dict[str, Any], tool: str | None = None) -> str:
+    """The rule id of a detector hit (``properties.rule_id`` if it has one)."""
+    props = hypothesis.get("properties", {})
+    if props.get("rule_id"):
+        return props["rule_id"]
+    return f"{source_tool(hypothesis, tool) or 'unknown'}/{hypothesis.get('vulnerability_type') or 'unknown'}"
+
+
+def rule_code(hypothesis: dict[str, Any], tool: str | None = None, catalog: "RuleCatalog | None" = None) -> str | None:
+    """The stable code of a detector hit's rule (``properties.rule_code`` if it has one), if it has a code."""
+    props = hypothesis.get("properties", {})
+    if props.get("rule_code"):
+        return props["rule_code"]
+    return (catalog or _shipped_catalog()).code(rule_id(hypothesis, tool))
+
+
+@dataclass(frozen=True)
+class Migration:
+    """One rename, split or merge, made in rule set ``version``."""
+
+    version: int
+    kind: str
+    old: tuple[str, ...]
+    new: tuple[str, ...]
+    reason: str = ""
+
+    def apply(self, rule: str) -> list[str] | None:
+        """The ids ``rule`` becomes, or None if this migration leaves it alone."""
+        for old in self.old:
+            if old.endswith("/*"):
+                if rule.startswith(old[:-1]):
+                    return [self.new[0][:-1] + rule[len(old) - 1:]]
+            elif rule == old:
+                return list(self.new)
+        return None
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"version": self.version, "kind": self.kind, "from": list(self.old), "to": list(self.new),
+                "reason": self.reason}
+
+
+@dataclass(frozen=True)
+class Deprecation:
+    """A rule that no longer fires, and what replaced it (if anything)."""
+
+    rule: str
+    version: int
+    replaced_by: tuple[str, ...] = ()
+    reason: str = ""
+
+
+class RuleCatalog:
+    """The current rule set version with every migration that led to it."""
+
+    def __init__(self, version: int = 1, migrations: list[Migration] | None = None,
+                 deprecated: list[Deprecation] | None = None, codes: dict[str, str] | None = None):
+        self.version = version
+        self.migrations = sorted(migrations or [], key=lambda m: m.version)
+        self.deprecated = list(deprecated or [])
+        # Stable code -> rule id, or a detector's rule family (`<detector>/<prefix>*`)
+        self.codes = dict(codes or {})
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "RuleCatalog":
+        """Catalog from its YAML form.
+
+        Raises:
+            RuleCatalogError: If a migration has an unknown kind, the wrong
+                number of ids for its kind, or a version past the catalog's;
+                or a code is malformed or names a rule another code does
+        """
+        version = int(data.get("version", 1))
+        migrations = []
+        for entry in data.get("migrations") or []:
+            old, new = entry.get("from"), entry.get("to")
+            old = tuple([old] if isinstance(old, str) else old or [])
+            new = tuple([new] if isinstance(new, str) else new or [])
+            migration = Migration(int(entry.get("version", 0)), str(entry.get("kind", "")), old, new,
+                                  " ".join(str(entry.get("reason", "")).split()))
+            expected = {"rename": (1, 1), "split": (1, None), "merge": (None, 1)}.get(migration.kind)
+            if expected is None:
+                raise RuleCatalogError(f"unknown migration kind {migration.kind!r} "
+                                       f"(expected one of {', '.join(MIGRATION_KINDS)})")
+            if not old or not new or any(n is not None and n != len(ids) for n, ids in zip(expected, (old, new))):
+                raise RuleCatalogError(f"{migration.kind} of {list(old)} to {list(new)} has the wrong number of ids")
+            if any(i.endswith("/*") for i in old + new) and (
+                    migration.kind != "rename" or not (old[0].endswith("/*") and new[0].endswith("/*"))):
+                raise RuleCatalogError(f"wildcards only work renaming one detector to another: {list(old)}")
+            if not 1 < migration.version <= version:
+                raise RuleCatalogError(f"migration of {list(old)} has version {migration.version}, "
+                                       f"outside 2..{version}")
+            migrations.append(migration)
+        deprecated = [
+            Deprecation(str(entry["rule"]), int(entry.get("version", version)),
+                        tuple(entry.get("replaced_by") or ()), " ".join(str(entry.get("reason", "")).split()))
+            for entry in data.get("deprecated") or []
+        ]
+        codes = {str(code): str(rule) for code, rule in (data.get("codes") or {}).items()}
+        for code, rule in codes.items():
+            if not CODE_PATTERN.match(code):
+                raise RuleCatalogError(f"rule code {code!r} is not <CHAIN>-<NNN>-<NAME>")
+            if "/" not in rule or "*" in rule[:-1]:
+                raise RuleCatalogError(f"rule code {code} names {rule!r}, not a rule id or `<detector>/<prefix>*`")
+        numbers = ["-".join(code.split("-")[:2]) for code in codes]
+        reused = sorted({n for n in numbers if numbers.count(n) > 1})
+        if reused:
+            raise RuleCatalogError(f"rule code number {reused[0]} is used more than once")
+        rules = list(codes.values())
+        doubled = sorted({r for r in rules if rules.count(r) > 1})
+        if doubled:
+            raise RuleCatalogError(f"{doubled[0]} has more than one rule code")
+        return cls(version, migrations, deprecated, codes)
+
+    def migrate(self, rule: str, since: int) -> list[str]:
+        """The current ids of a rule as it was named in rule set ``since``."""
+        rules = [rule]
+        for migration in self.migrations:
+            if migration.version <= since:
+                continue
+            moved: list[str] = []
+            for r in rules:
+                for new in migration.apply(r) or [r]:
+                    if new not in moved:
+                        moved.append(new)
+            rules = moved
+        return rules
+
+    def merged_with(self, rule: str, since: int) -> list[str]:
+        """Other old ids merged into the same rule as ``rule`` after ``since``."""
+        current = set(self.migrate(rule, since))
+        siblings = []
+        for migration in self.migrations:
+            if migration.version > since and migration.kind == "merge":
+                for old in migration.old:
+                    if old != rule and set(self.migrate(old, migration.version - 1)) & current:
+                        siblings.append(old)
+        return siblings
+
+    def deprecation(self, rule: str) -> Deprecation | None:
+        """Why ``rule`` no longer fires, if it does not."""
+        for entry in self.deprecated:
+            if entry.rule == rule:
+                return entry
+        for migration in reversed(self.migrations):
+            new = migration.apply(rule)
+            if new is not None and new != [rule]:
+                return Deprecation(rule, migration.version, tuple(new), migration.reason)
+        return None
+
+    def retired(self, rule: str) -> bool:
+        """Whether ``rule`` is deprecated with nothing replacing it."""
+        entry = self.deprecation(rule)
+        return entry is not None and not entry.replaced_by
+
+    def code(self, rule: str) -> str | None:
+        """The stable code of ``rule``: the code naming it, else the longest rule family it is in."""
+        best: tuple[int, str] | None = None
+        for code, pattern in self.codes.items():
+            if pattern == rule:
+                return code
+            if pattern.endswith("*") and rule.startswith(pattern[:-1]) and (best is None or len(pattern) > best[0]):
+                best = (len(pattern), code)
+        return best[1] if best else None
+
+    def rule_for_code(self, code: str) -> str | None:
+        """The rule id (or family) ``code`` names, in any case."""
+        return self.codes.get(code.upper())
+
+    def changes(self, since: int) -> list[Migration]:
+        """Migrations made after rule set ``since``."""
+        return [m for m in self.migrations if m.version > since]
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "version": self.version,
+            "migrations": [m.to_dict() for m in self.migrations],
+            "deprecated": [{"rule": d.rule, "version": d.version, "replaced_by": list(d.replaced_by),
+                            "reason": d.reason} for d in self.deprecated],
+            "codes": dict(self.codes),
+        }
+
+
+def load_catalog(path: Path | None = None) -> RuleCatalog:
+    """Catalog from ``path`` (default: the shipped file).
+
+    Raises:
+        RuleCatalogError: If the file is unreadable or invalid; guessing here
+            would migrate suppressions wrongly
+    """
+    path = Path(path) if path else CATALOG_FILE
+    try:
+        data = yaml.safe_load(path.read_text()) or {}
+    except (OSError, yaml.YAMLError) as e:
+        raise RuleCatalogError(f"cannot read rule catalog {path}: {e}") from None
+    if not isinstance(data, dict):
+        raise RuleCatalogError(f"rule catalog {path} must be a mapping")
+    return RuleCatalog.from_dict(data)
+
+
+@lru_cache(maxsize=1)
+def _shipped_catalog() -> RuleCatalog:
+    return load_catalog()
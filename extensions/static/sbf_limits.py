@@ -0,0 +1,422 @@
+"""
+Stack and heap usage estimation against SBF limits.
+
+Solana programs run with a 4KB stack frame per function and a 32KB bump heap
+that never frees. Exceeding either fails at runtime (access violation or
+out-of-memory), often only for inputs larger than the ones tests use, and an
+allocation sized by the caller is a denial-of-service vector.
+
+Estimates, per scanned program:
+- stack: each function's locals (sized from the program's own struct and
+  const definitions) and by-value parameters; for Anchor, the
+  `try_accounts` frame of every `#[derive(Accounts)]` struct (unboxed
+  `Account<T>` fields live on the stack)
+- heap: constant-size allocations per function (`vec![x; N]`,
+  `Vec::with_capacity(N)`, `Box::new`), allocations sized by attacker
+  input (instruction data, account data, handler arguments), and heap
+  types deserialized from account data
+
+Estimates are conservative approximations (no padding, no inlining), meant
+to flag handlers worth measuring, not to replace `solana-program-test`.
+"""
+
+import os
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+from extensions.ir.rust_source import (
+    find_matching,
+    iter_functions,
+    iter_structs,
+    line_at,
+    parse_named_fields,
+    parse_params,
+    split_top_level,
+    statement_end,
+    strip_comments,
+)
+
+from .unsafe_rust import tainted_locals
+
+ESTIMATOR_VERSION = "1.0.0"
+
+STACK_FRAME_LIMIT = 4096
+HEAP_LIMIT = 32 * 1024
+# Fraction of a limit at which a warning (low severity) is raised
+WARN_RATIO = 0.75
+
+_SKIP_DIRS = {"target", ".git", "node_modules", "tests", "benches", "examples", ".anchor"}
+
+_PRIMITIVE_SIZES = {
+    "u8": 1, "i8": 1, "bool": 1,
+    "u16": 2, "i16": 2,
+    "u32": 4, "i32": 4, "f32": 4, "char": 4,
+    "u64": 8, "i64": 8, "f64": 8, "usize": 8, "isize": 8,
+    "u128": 16, "i128": 16,
+    "Pubkey": 32, "Hash": 32,
+}
+# Heap-backed or pointer-sized types: only the handle lives on the stack
+_HANDLE_SIZES = {"Vec": 24, "String": 24, "VecDeque": 32, "BTreeMap": 24, "HashMap": 48,
+                 "Box": 8, "Rc": 8, "Arc": 8, "RefMut": 16, "Ref": 16}
+# Anchor account wrappers that hold an AccountInfo by value
+_ACCOUNT_INFO_SIZE = 48
+_INFO_WRAPPERS = {"AccountInfo", "UncheckedAccount", "Signer", "SystemAccount", "Program", "Interface",
+                  "Sysvar", "AccountLoader"}
+_DATA_WRAPPERS = {"Account", "InterfaceAccount"}
+_UNKNOWN_SIZE = 8
+
+_CONST_RE = re.compile(r"\bconst\s+(?P<name>[A-Z_][A-Z0-9_]*)\s*:\s*\w+\s*=\s*(?P<value>[^;]+);")
+_LET_TYPED_RE = re.compile(r"\blet\s+(?:mut\s+)?(?P<name>[A-Za-z_]\w*)\s*:\s*(?P<ty>(?:\[[^\]]*\]|[^=;\[])+?)\s*=")
+_LET_RE = re.compile(r"\blet\s+(?:mut\s+)?(?P<name>[A-Za-z_]\w*)\s*=\s*")
+_ARRAY_LITERAL_RE = re.compile(r"\[\s*[^;\[\]]+;\s*(?P<len>[^\]]+)\]")
+_CTOR_RE = re.compile(r"(?P<ty>[A-Z]\w*)\s*(?:::\s*(?:try_from_slice|deserialize|try_deserialize|unpack|default|new|from)\b|\{)")
+_VEC_MACRO_RE = re.compile(r"\bvec!\s*\[")
+_WITH_CAPACITY_RE = re.compile(r"\b(?:Vec|String)\s*::\s*with_capacity\s*\(")
+_GROW_RE = re.compile(r"\.(?:resize|reserve|reserve_exact)\s*\(")
+_BOX_RE = re.compile(r"\bBox\s*::\s*new\s*\(")
+_DESERIALIZE_RE = re.compile(r"(?P<ty>[A-Z]\w*)\s*::\s*(?:try_from_slice|deserialize|try_deserialize)\s*\(")
+_ACCOUNT_DATA_RE = re.compile(r"\.(?:data\.borrow(?:_mut)?|try_borrow_(?:mut_)?data)\s*\(|\.data\b")
+
+
+@dataclass
+class SbfResourceFinding:
+    """A function or accounts struct likely to exceed an SBF limit."""
+
+    kind: str  # stack-frame, accounts-stack, heap-constant, heap-unbounded
+    file_path: str
+    line: int
+    function: str
+    estimate: int
+    limit: int
+    detail: str = ""
+    tainted: list[str] = field(default_factory=list)
+
+    @property
+    def severity(self) -> str:
+        if self.kind == "heap-unbounded":
+            return "medium"
+        return "high" if self.estimate > self.limit else "low"
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        resource = "heap" if self.kind.startswith("heap") else "stack"
+        if self.kind == "heap-unbounded":
+            title = f"Unbounded heap allocation in {self.function}"
+            description = (
+                f"`{self.detail}` in `{self.function}` allocates a caller-controlled amount "
+                f"({', '.join(self.tainted)}). The SBF heap is {self.limit // 1024}KB and never frees, so a large "
+                f"value aborts the transaction; behind a shared code path this is a denial-of-service vector."
+            )
+        else:
+            over = "exceeds" if self.estimate > self.limit else "is close to"
+            title = f"Estimated {resource} usage {over} SBF limit in {self.function}"
+            description = (
+                f"Estimated {resource} usage of `{self.function}` is ~{self.estimate} bytes, which {over} the "
+                f"{self.limit}-byte SBF {resource} limit. {self.detail}".strip()
+            )
+        return {
+            "title": title,
+            "description": description,
+            "vulnerability_type": f"sbf-{self.kind}",
+            "severity": self.severity,
+            "confidence": 0.5,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "sbf-limits",
+                "category": "resource-limits",
+                "source_files": [self.file_path],
+                "affected_lines": [self.line],
+                "function": self.function,
+                "estimate_bytes": self.estimate,
+                "limit_bytes": self.limit,
+            },
+        }
+
+
+class TypeSizer:
+    """Approximate in-memory sizes from the program's own struct and const definitions."""
+
+    def __init__(self, structs: dict[str, list[tuple[str, str]]] | None = None,
+                 consts: dict[str, int] | None = None):
+        self.structs = structs or {}
+        self.consts = consts or {}
+
+    def add_source(self, text: str) -> None:
+        """Register structs and integer consts from comment-stripped ``text``."""
+        for struct in iter_structs(text):
+            if struct.body:
+                self.structs[struct.name] = [(name, ty) for name, ty, _ in parse_named_fields(struct.body)]
+        for m in _CONST_RE.finditer(text):
+            value = self.eval_len(m.group("value"))
+            if value is not None:
+                self.consts[m.group("name")] = value
+
+    def eval_len(self, expr: str) -> int | None:
+        """Evaluate an array length / const expression (literals, consts, + - *)."""
+        expr = re.sub(r"(?<=\d)_(?=\d)|(?<=\d)(?:usize|u\d+)\b", "", expr.strip())
+        expr = re.sub(r"\bsize_of::<\s*([^>]+)>\(\)", lambda m: str(self.size(m.group(1))), expr)
+        # Sums of products of literals and consts; anything else is not a constant
+        total = 0
+        for sign, term in re.findall(r"([+-]?)\s*([^+-]+)", expr):
+            product = 1
+            for factor in term.split("*"):
+                factor = factor.strip()
+                if factor.isdigit():
+                    product *= int(factor)
+                elif factor in self.consts:
+                    product *= self.consts[factor]
+                else:
+                    return None
+            total += -product if sign == "-" else product
+        return total if expr else None
+
+    def size(self, ty: str, depth: int = 0) -> int:
+        """Approximate size in bytes of type expression ``ty``."""
+        ty = " ".join(ty.split()).strip()
+        ty = re.sub(r"^&\s*(?:'\w+\s+)?(?:mut\s+)?", "&", ty)
+        if not ty or depth > 8:
+            return _UNKNOWN_SIZE
+        if ty.startswith(("&", "*")):
+            return 16 if ty.lstrip("&*").startswith("[") or ty.startswith("&str") else 8
+        if ty.startswith("[") and ty.endswith("]"):
+            inner = split_top_level(ty[1:-1], ";")
+            if len(inner) == 2:
+                n = self.eval_len(inner[1])
+                return self.size(inner[0], depth + 1) * n if n is not None else _UNKNOWN_SIZE
+            return _UNKNOWN_SIZE
+        if ty.startswith("(") and ty.endswith(")"):
+            return sum(self.size(part, depth + 1) for part in split_top_level(ty[1:-1]))
+        m = re.match(r"(?:[\w:]+::)?(?P<base>\w+)\s*(?:<(?P<args>.*)>)?$", ty)
+        if not m:
+            return _UNKNOWN_SIZE
+        base, args = m.group("base"), split_top_level(m.group("args") or "")
+        args = [a for a in args if not a.startswith("'")]
+        if base in _PRIMITIVE_SIZES:
+            return _PRIMITIVE_SIZES[base]
+        if base in _HANDLE_SIZES:
+            return _HANDLE_SIZES[base]
+        if base == "Option":
+            return self.size(args[0], depth + 1) + 1 if args else _UNKNOWN_SIZE
+        if base in _DATA_WRAPPERS:
+            return _ACCOUNT_INFO_SIZE + (self.size(args[-1], depth + 1) if args else 0)
+        if base in _INFO_WRAPPERS:
+            return _ACCOUNT_INFO_SIZE
+        if base in self.structs:
+            return sum(self.size(field_ty, depth + 1) for _, field_ty in self.structs[base]) or 1
+        return _UNKNOWN_SIZE
+
+    def heap_fields(self, ty: str, depth: int = 0) -> list[str]:
+        """Fields of struct ``ty`` (recursively) whose contents live on the heap."""
+        fields = []
+        for name, field_ty in self.structs.get(ty, []):
+            base = re.match(r"(?:[\w:]+::)?(\w+)", field_ty)
+            if base and base.group(1) in ("Vec", "String", "VecDeque", "BTreeMap", "HashMap"):
+                fields.append(name)
+            elif base and base.group(1) in self.structs and depth < 4:
+                fields.extend(f"{name}.{sub}" for sub in self.heap_fields(base.group(1), depth + 1))
+        return fields
+
+
+class SbfResourceEstimator:
+    """Flags functions and accounts structs likely to exceed SBF stack/heap limits."""
+
+    def __init__(self, max_files: int = 2000, stack_limit: int = STACK_FRAME_LIMIT,
+                 heap_limit: int = HEAP_LIMIT):
+        """Initialize the estimator.
+
+        Args:
+            max_files: Maximum number of `.rs` files to scan
+            stack_limit: Per-function stack frame limit in bytes
+            heap_limit: Heap size in bytes (raise if the program requests a larger heap frame)
+        """
+        self.max_files = max_files
+        self.stack_limit = stack_limit
+        self.heap_limit = heap_limit
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{ESTIMATOR_VERSION}"
+
+    def run(self, project_path: Path) -> tuple[list[SbfResourceFinding], dict]:
+        """Estimate stack/heap usage for every Rust source under ``project_path``.
+
+        Returns:
+            Tuple of (findings, metadata); metadata["functions"] holds every estimate
+        """
+        project_path = Path(project_path)
+        metadata: dict[str, Any] = {
+            "tool": "sbf-limits",
+            "version": ESTIMATOR_VERSION,
+            "success": True,
+            "error": None,
+            "files_scanned": 0,
+            "limits": {"stack": self.stack_limit, "heap": self.heap_limit},
+            "functions": [],
+        }
+        sources: list[tuple[str, str]] = []
+        sizer = TypeSizer()
+        for path in self._rust_files(project_path):
+            try:
+                text = strip_comments(path.read_text(errors="replace"))
+            except OSError:
+                continue
+            sizer.add_source(text)
+            sources.append((Path(os.path.relpath(path, project_path)).as_posix(), text))
+        metadata["files_scanned"] = len(sources)
+
+        findings: list[SbfResourceFinding] = []
+        for rel, text in sources:
+            estimates, file_findings = self.estimate_source(text, rel, sizer)
+            metadata["functions"].extend(estimates)
+            findings.extend(file_findings)
+        return findings, metadata
+
+    def _rust_files(self, root: Path) -> list[Path]:
+        if root.is_file():
+            return [root] if root.suffix == ".rs" else []
+        files: list[Path] = []
+        for dirpath, dirnames, filenames in os.walk(root):
+            dirnames[:] = sorted(d for d in dirnames if d not in _SKIP_DIRS and not d.startswith("."))
+            for name in sorted(filenames):
+                if name.endswith(".rs"):
+                    files.append(Path(dirpath) / name)
+                    if len(files) >= self.max_files:
+                        return files
+        return files
+
+    def estimate_source(self, text: str, rel_path: str,
+                        sizer: TypeSizer) -> tuple[list[dict[str, Any]], list[SbfResourceFinding]]:
+        """Estimates and findings for one comment-stripped file."""
+        estimates: list[dict[str, Any]] = []
+        findings: list[SbfResourceFinding] = []
+
+        # Anchor deserializes every account of an instruction in one try_accounts frame
+        for struct in iter_structs(text):
+            if not any("Accounts" in attr for attr in struct.attributes) or not struct.body:
+                continue
+            fields = parse_named_fields(struct.body)
+            total = sum(sizer.size(ty) for _, ty, _ in fields)
+            estimates.append({"function": f"{struct.name}::try_accounts", "file": rel_path,
+                              "line": struct.line, "stack_bytes": total, "heap_bytes": 0})
+            if total > self.stack_limit * WARN_RATIO:
+                largest = sorted(((sizer.size(ty), name) for name, ty, _ in fields), reverse=True)[:3]
+                detail = "Largest accounts: " + ", ".join(f"`{name}` (~{size} B)" for size, name in largest) + \
+                         ". Wrapping large accounts in `Box<Account<...>>` (or zero-copy) moves them to the heap."
+                findings.append(SbfResourceFinding("accounts-stack", rel_path, struct.line,
+                                                   f"{struct.name}::try_accounts", total, self.stack_limit, detail))
+
+        for fn in iter_functions(text):
+            stack, locals_ = self._stack_frame(fn, sizer)
+            heap, unbounded = self._heap(fn, sizer)
+            line = line_at(text, fn.start)
+            estimates.append({"function": fn.name, "file": rel_path, "line": line,
+                              "stack_bytes": stack, "heap_bytes": heap,
+                              "unbounded_allocations": len(unbounded)})
+            if stack > self.stack_limit * WARN_RATIO:
+                largest = sorted(locals_, reverse=True)[:3]
+                detail = "Largest locals: " + ", ".join(f"`{name}` (~{size} B)" for size, name in largest) + "."
+                findings.append(SbfResourceFinding("stack-frame", rel_path, line, fn.name, stack,
+                                                   self.stack_limit, detail))
+            if heap > self.heap_limit * WARN_RATIO:
+                findings.append(SbfResourceFinding("heap-constant", rel_path, line, fn.name, heap, self.heap_limit,
+                                                   "The bump allocator never frees, so allocations add up."))
+            for offset, snippet, names in unbounded:
+                findings.append(SbfResourceFinding(
+                    "heap-unbounded", rel_path, line_at(text, fn.body_start + 1 + offset), fn.name,
+                    0, self.heap_limit, snippet, names,
+                ))
+        return estimates, findings
+
+    def _stack_frame(self, fn, sizer: TypeSizer) -> tuple[int, list[tuple[int, str]]]:
+        """(estimated frame bytes, [(size, local name)])."""
+        locals_: list[tuple[int, str]] = []
+        for name, ty in parse_params(fn.params):
+            if not ty.startswith(("&", "impl ")):
+                locals_.append((sizer.size(ty), name))
+        body = fn.body
+        typed = set()
+        for m in _LET_TYPED_RE.finditer(body):
+            typed.add(m.start())
+            locals_.append((sizer.size(m.group("ty")), m.group("name")))
+        for m in _LET_RE.finditer(body):
+            if m.start() in typed:
+                continue
+            size = self._expr_size(body[m.end():statement_end(body, m.end())], sizer)
+            if size:
+                locals_.append((size, m.group("name")))
+        return sum(size for size, _ in locals_), locals_
+
+    def _expr_size(self, rhs: str, sizer: TypeSizer) -> int:
+        rhs = rhs.strip()
+        if rhs.startswith("Box::new") or rhs.startswith("vec!") or rhs.startswith("&"):
+            return 8
+        m = _ARRAY_LITERAL_RE.match(rhs)
+        if m:
+            return (sizer.eval_len(m.group("len")) or 0) * _literal_elem_size(rhs)
+        m = _CTOR_RE.match(rhs)
+        if m and m.group("ty") in sizer.structs:
+            return sizer.size(m.group("ty"))
+        return 0
+
+    def _heap(self, fn, sizer: TypeSizer) -> tuple[int, list[tuple[int, str, list[str]]]]:
+        """(constant heap bytes, [(offset, snippet, tainted names)] for caller-sized allocations)."""
+        taint = tainted_locals(fn)
+        body = fn.body
+        constant = 0
+        unbounded: list[tuple[int, str, list[str]]] = []
+
+        def size_arg(offset: int, expr: str, elem: int = 1) -> None:
+            nonlocal constant
+            n = sizer.eval_len(expr)
+            if n is not None:
+                constant += n * elem
+                return
+            names = [name for name in re.findall(r"[A-Za-z_]\w*", expr) if name in taint]
+            if names and not _clamped(body[:offset], names):
+                snippet = " ".join(body[offset:statement_end(body, offset)].split())
+                unbounded.append((offset, snippet, list(dict.fromkeys(names))))
+
+        for m in _VEC_MACRO_RE.finditer(body):
+            close = find_matching(body, m.end() - 1)
+            parts = split_top_level(body[m.end():close], ";")
+            if len(parts) == 2:
+                size_arg(m.start(), parts[1], _literal_elem_size(parts[0]))
+        for m in list(_WITH_CAPACITY_RE.finditer(body)) + list(_GROW_RE.finditer(body)):
+            close = find_matching(body, m.end() - 1)
+            args = split_top_level(body[m.end():close])
+            if args:
+                size_arg(m.start(), args[0])
+        for m in _BOX_RE.finditer(body):
+            close = find_matching(body, m.end() - 1)
+            constant += self._expr_size(body[m.end():close], sizer)
+        # Account data deserialized into Vec/String fields: lengths come from the account
+        for m in _DESERIALIZE_RE.finditer(body):
+            close = find_matching(body, m.end() - 1)
+            arg = body[m.end():close]
+            names = [name for name in re.findall(r"[A-Za-z_]\w*", arg) if name in taint]
+            heap_fields = sizer.heap_fields(m.group("ty"))
+            if heap_fields and (_ACCOUNT_DATA_RE.search(arg) or names):
+                snippet = " ".join(body[m.start():close + 1].split())
+                unbounded.append((m.start(), snippet, [f"{m.group('ty')}.{f}" for f in heap_fields]))
+        return constant, unbounded
+
+
+def _literal_elem_size(expr: str) -> int:
+    m = re.search(r"\d+(u8|u16|u32|u64|u128|i8|i16|i32|i64|i128|usize)\b", expr)
+    return _PRIMITIVE_SIZES.get(m.group(1), 1) if m else (32 if "Pubkey" in expr else 1)
+
+
+def _clamped(before: str, names: list[str]) -> bool:
+    """Whether a tainted size is compared against a bound (or clamped) before allocating."""
+    for statement in re.split(r"[;{}]", before):
+        if not any(re.search(rf"\b{re.escape(n)}\b", statement) for n in names):
+            continue
+        if re.search(r"\.min\(|\.clamp\(", statement):
+            return True
+        if re.search(r"(?:<=?|>=?)", statement) and re.search(r"\b(?:if|require|assert|ensure)\w*!?", statement):
+            return True
+    return False
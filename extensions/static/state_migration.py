@@ -0,0 +1,377 @@
+"""
+Account schema changes between program versions.
+
+An upgrade replaces a program's code but not its accounts: every account
+written by the previous build keeps its old bytes, and the new build reads
+them with the new layout. Anchor deserializes `Account<T>` field by field
+after the 8-byte discriminator, so a layout change that no migration
+handles breaks state already on chain:
+
+- corrupting: a surviving field changed type or offset (a field inserted,
+  removed or reordered before it), so old bytes are read as other values
+- extended: fields appended at the end; old accounts are too short and
+  fail to deserialize until they are reallocated
+- renamed: the struct was renamed, which changes its discriminator, so old
+  accounts are rejected as the wrong type
+- truncated: trailing fields removed; old accounts still load, but keep
+  stale bytes a later version will read
+
+Layouts come from the `#[account]` structs of the current sources and of a
+``baseline``: a directory holding the previous version, or a git ref of the
+repository being scanned. A change counts as handled when the new version
+has a migration instruction for the type (named like `migrate`/`upgrade`,
+or reallocating an account of the type) or checks a version field of the
+struct in a handler. Run `sim migrate` to prove a finding on the built
+program with account fixtures in the old layout.
+"""
+
+import os
+import re
+import subprocess
+import tarfile
+import tempfile
+from contextlib import contextmanager
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Iterator
+
+from extensions.ir import load_programs
+from extensions.ir.model import AccountType, Program
+
+from .account_growth import _GROWABLE, _base, _borsh_bytes, _program_sizer, _state_type
+from .sbf_limits import TypeSizer
+
+ANALYZER_VERSION = "1.0.0"
+
+KINDS = ("corrupting", "extended", "renamed", "truncated")
+SEVERITY = {"corrupting": "high", "extended": "medium", "renamed": "medium", "truncated": "low"}
+
+_MIGRATION_NAME_RE = re.compile(r"migrat|upgrade|convert|realloc|resize", re.I)
+_VERSION_FIELD_RE = re.compile(r"^(?:schema_|layout_|account_|data_|state_)?version$")
+_RAW_WRAPPERS = ("AccountInfo", "UncheckedAccount", "AccountLoader")
+
+
+@dataclass
+class FieldChange:
+    """One field whose bytes the new layout reads differently."""
+
+    field: str
+    change: str  # added, removed, retyped, moved
+    old_type: str | None = None
+    new_type: str | None = None
+    # Byte offsets past the discriminator; None after a variable-length field
+    old_offset: int | None = None
+    new_offset: int | None = None
+
+    def describe(self) -> str:
+        if self.change == "added":
+            return f"`{self.field}` ({self.new_type}) added{_at(self.new_offset)}"
+        if self.change == "removed":
+            return f"`{self.field}` ({self.old_type}) removed{_at(self.old_offset)}"
+        if self.change == "retyped":
+            return f"`{self.field}` changed from {self.old_type} to {self.new_type}{_at(self.new_offset)}"
+        return f"`{self.field}` moved from {_offset(self.old_offset)} to {_offset(self.new_offset)}"
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"field": self.field, "change": self.change, "old_type": self.old_type, "new_type": self.new_type,
+                "old_offset": self.old_offset, "new_offset": self.new_offset}
+
+
+def _offset(offset: int | None) -> str:
+    return f"byte {offset}" if offset is not None else "a variable offset"
+
+
+def _at(offset: int | None) -> str:
+    return f" at byte {offset}" if offset is not None else ""
+
+
+@dataclass
+class LayoutChange:
+    """How an account type's layout differs from the baseline's."""
+
+    program: str
+    account_type: str
+    kind: str
+    changes: list[FieldChange] = field(default_factory=list)
+    old_name: str | None = None  # For renamed types
+    file: str = ""
+    line: int = 0
+    # Migration instructions or version checks that handle the change
+    handled_by: list[str] = field(default_factory=list)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "program": self.program,
+            "account_type": self.account_type,
+            "kind": self.kind,
+            "old_name": self.old_name,
+            "file": self.file,
+            "line": self.line,
+            "changes": [c.to_dict() for c in self.changes],
+            "handled_by": self.handled_by,
+        }
+
+
+@dataclass
+class StateMigrationFinding:
+    """An account layout change the new version does not migrate."""
+
+    change: LayoutChange
+    baseline: str
+
+    @property
+    def severity(self) -> str:
+        return SEVERITY[self.change.kind]
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        change = self.change
+        outcomes = {
+            "corrupting": "the upgraded program reads existing accounts with fields at the wrong bytes",
+            "extended": "existing accounts are too short for the new layout and fail to deserialize, locking "
+                        "whatever they hold until they are reallocated",
+            "renamed": "existing accounts carry the old discriminator and are rejected as the wrong account type",
+            "truncated": "existing accounts keep the removed fields' bytes, which a later version adding fields "
+                         "will read as its own",
+        }
+        subject = f"`{change.old_name}` was renamed to `{change.account_type}`" if change.kind == "renamed" else (
+            f"`{change.account_type}` changed since {self.baseline}: "
+            + "; ".join(c.describe() for c in change.changes))
+        description = (
+            f"{subject}. No migration instruction or version check handles it, so {outcomes[change.kind]}. Add an "
+            f"instruction that reads the old layout and rewrites each account (reallocating it when it grows), "
+            f"or a version field checked before use; `sim migrate` proves the effect on the built program."
+        )
+        return {
+            "title": f"`{change.account_type}` layout change without a migration ({change.kind})",
+            "description": description,
+            "vulnerability_type": "unmigrated-account-layout",
+            "severity": self.severity,
+            "confidence": 0.7 if change.kind == "corrupting" else 0.5,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "state-migration",
+                "category": "upgradeability",
+                "source_files": [change.file] if change.file else [],
+                "affected_lines": [change.line] if change.line else [],
+                "program": change.program,
+                "account_type": change.account_type,
+                "kind": change.kind,
+                "old_name": change.old_name,
+                "changes": [c.to_dict() for c in change.changes],
+                "baseline": self.baseline,
+                "precision": "semantic",
+            },
+        }
+
+
+def _type_text(ty: str) -> str:
+    return " ".join(ty.split())
+
+
+def _variable(ty: str, sizer: TypeSizer, depth: int = 0) -> bool:
+    """Whether the Borsh encoding of ``ty`` varies in length."""
+    base = _base(ty)
+    if base in _GROWABLE or base == "Option":
+        return True
+    return depth < 8 and any(_variable(f_ty, sizer, depth + 1) for _, f_ty in sizer.structs.get(base, []))
+
+
+def layout(at: AccountType, sizer: TypeSizer) -> list[tuple[str, str, int | None]]:
+    """(field, type, offset past the discriminator) of ``at``; offsets are None after a variable-length field."""
+    slots: list[tuple[str, str, int | None]] = []
+    offset: int | None = 0
+    zero_copy = at.properties.get("zero_copy", False)
+    for f in at.fields:
+        slots.append((f.name, _type_text(f.ty), offset))
+        if offset is None:
+            continue
+        if not zero_copy and _variable(f.ty, sizer):
+            offset = None
+        else:
+            offset += sizer.size(f.ty) if zero_copy else _borsh_bytes(f.ty, sizer)
+    return slots
+
+
+def compare_layouts(program: str, old: AccountType, new: AccountType, old_sizer: TypeSizer,
+                    new_sizer: TypeSizer) -> LayoutChange | None:
+    """The change from ``old`` to ``new``, or None when old accounts read the same."""
+    old_slots, new_slots = layout(old, old_sizer), layout(new, new_sizer)
+
+    def key(slots: list[tuple[str, str, int | None]], i: int) -> Any:
+        # Fields past a variable-length one line up when everything before them has the same types
+        return slots[i][2] if slots[i][2] is not None else tuple(ty for _, ty, _ in slots[:i])
+
+    old_index = {name: i for i, (name, _, _) in enumerate(old_slots)}
+    new_index = {name: i for i, (name, _, _) in enumerate(new_slots)}
+    # A field renamed in place reads the same bytes
+    added = [i for i, (name, _, _) in enumerate(new_slots) if name not in old_index]
+    removed = [j for j, (name, _, _) in enumerate(old_slots) if name not in new_index]
+    for i in list(added):
+        j = next((j for j in removed if old_slots[j][1] == new_slots[i][1] and key(old_slots, j) == key(new_slots, i)),
+                 None)
+        if j is not None:
+            added.remove(i)
+            removed.remove(j)
+    changes = []
+    for i, (name, ty, offset) in enumerate(new_slots):
+        if name not in old_index:
+            if i in added:
+                changes.append(FieldChange(name, "added", new_type=ty, new_offset=offset))
+            continue
+        j = old_index[name]
+        old_ty, old_offset = old_slots[j][1], old_slots[j][2]
+        if old_ty != ty:
+            changes.append(FieldChange(name, "retyped", old_ty, ty, old_offset, offset))
+        elif key(old_slots, j) != key(new_slots, i):
+            changes.append(FieldChange(name, "moved", old_ty, ty, old_offset, offset))
+    for j in removed:
+        name, ty, offset = old_slots[j]
+        changes.append(FieldChange(name, "removed", old_type=ty, old_offset=offset))
+    if not changes:
+        return None
+    kinds = {c.change for c in changes}
+    if kinds & {"retyped", "moved"}:
+        kind = "corrupting"
+    elif "added" in kinds:
+        # Additions only at the end; additions anywhere else move the fields after them
+        kind = "extended"
+    else:
+        kind = "truncated"
+    return LayoutChange(program, new.name, kind, changes, file=new.file, line=new.line)
+
+
+def migration_handlers(program: Program, type_name: str) -> list[str]:
+    """Instructions of ``program`` that migrate accounts of ``type_name``, and handlers checking its version."""
+    handlers = []
+    at = next((a for a in program.account_types if a.name == type_name), None)
+    version_fields = [f.name for f in at.fields if _VERSION_FIELD_RE.match(f.name)] if at else []
+    for ix in program.instructions:
+        typed = [a for a in ix.accounts if _state_type(a) == type_name]
+        raw = [a for a in ix.accounts if _base(a.ty) in _RAW_WRAPPERS] if re.search(
+            rf"\b{re.escape(type_name)}\b", ix.body) else []
+        if any(c.startswith("realloc") for a in typed for c in a.constraints):
+            handlers.append(f"{ix.name} (realloc)")
+        elif (typed or raw) and _MIGRATION_NAME_RE.search(ix.name):
+            handlers.append(ix.name)
+        for name in version_fields:
+            if re.search(rf"(?:\bif\b|\bmatch\b|\b(?:require|assert)\w*!)[^;{{]*\.\s*{name}\b", ix.body):
+                handlers.append(f"{ix.name} (checks {name})")
+    return list(dict.fromkeys(handlers))
+
+
+def _renamed(old: Program, new: Program, old_sizer: TypeSizer, new_sizer: TypeSizer) -> list[LayoutChange]:
+    """Types whose name changed but whose layout did not."""
+    old_types = {at.name: at for at in old.account_types}
+    new_types = {at.name: at for at in new.account_types}
+    gone = [at for name, at in old_types.items() if name not in new_types]
+    changes = []
+    for at in new.account_types:
+        if at.name in old_types:
+            continue
+        shape = [ty for _, ty, _ in layout(at, new_sizer)]
+        match = next((o for o in gone if [ty for _, ty, _ in layout(o, old_sizer)] == shape), None)
+        if match:
+            gone.remove(match)
+            changes.append(LayoutChange(new.name, at.name, "renamed", old_name=match.name, file=at.file,
+                                        line=at.line))
+    return changes
+
+
+def compare_programs(old: Program, new: Program) -> list[LayoutChange]:
+    """Layout changes of the account types ``new`` shares with (or renamed from) ``old``."""
+    old_sizer, new_sizer = _program_sizer(old), _program_sizer(new)
+    old_types = {at.name: at for at in old.account_types}
+    changes = []
+    for at in new.account_types:
+        if at.name in old_types:
+            change = compare_layouts(new.name, old_types[at.name], at, old_sizer, new_sizer)
+            if change:
+                changes.append(change)
+    changes += _renamed(old, new, old_sizer, new_sizer)
+    for change in changes:
+        change.handled_by = migration_handlers(new, change.account_type)
+    return changes
+
+
+@contextmanager
+def baseline_checkout(project_path: Path, baseline: str) -> Iterator[Path]:
+    """The baseline sources: a directory (relative to ``project_path`` or absolute), or a git ref of its repo."""
+    project_path = Path(project_path).resolve()
+    for candidate in (project_path / baseline, Path(baseline).expanduser()):
+        if candidate.is_dir():
+            yield candidate
+            return
+    try:
+        top = subprocess.run(["git", "-C", str(project_path), "rev-parse", "--show-toplevel"],
+                             capture_output=True, text=True, check=True).stdout.strip()
+        with tempfile.TemporaryDirectory(prefix="baskerville-baseline-") as tmp:
+            archive = Path(tmp) / "baseline.tar"
+            subprocess.run(["git", "-C", top, "archive", "--format=tar", "-o", str(archive), baseline],
+                           capture_output=True, text=True, check=True)
+            with tarfile.open(archive) as tar:
+                tar.extractall(Path(tmp) / "src", filter="data")
+            yield Path(tmp) / "src" / os.path.relpath(project_path, Path(top).resolve())
+    except (OSError, subprocess.CalledProcessError) as e:
+        detail = e.stderr.strip() if isinstance(e, subprocess.CalledProcessError) and e.stderr else str(e)
+        raise ValueError(f"baseline {baseline} is neither a directory nor a git ref ({detail})") from e
+
+
+class StateMigrationAnalyzer:
+    """Flags account layout changes since a baseline version that no migration handles."""
+
+    def __init__(self, baseline: str | None = None, include_handled: bool = False):
+        """Initialize the analyzer.
+
+        Args:
+            baseline: The previous version: a directory, or a git ref of the scanned repository. Without one
+                the analyzer has nothing to compare and reports nothing
+            include_handled: Also report changes a migration instruction or version check handles
+        """
+        self.baseline = baseline
+        self.include_handled = include_handled
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{ANALYZER_VERSION}"
+
+    def run(self, project_path: Path) -> tuple[list[StateMigrationFinding], dict]:
+        """Compare every program under ``project_path`` with its baseline version.
+
+        Returns:
+            Tuple of (findings, metadata)
+        """
+        metadata: dict[str, Any] = {
+            "tool": "state-migration",
+            "version": ANALYZER_VERSION,
+            "success": True,
+            "error": None,
+            "baseline": self.baseline,
+            "programs_compared": 0,
+            "changes": [],
+        }
+        if not self.baseline:
+            return [], metadata
+        project_path = Path(project_path)
+        try:
+            with baseline_checkout(project_path, self.baseline) as old_root:
+                old_programs = {p.name: p for p in load_programs(old_root) if p.framework == "anchor"}
+        except ValueError as e:
+            metadata.update(success=False, error=str(e))
+            return [], metadata
+
+        findings = []
+        for program in load_programs(project_path):
+            old = old_programs.get(program.name)
+            if program.framework != "anchor" or old is None:
+                continue
+            metadata["programs_compared"] += 1
+            root = Path(os.path.relpath(program.root, project_path))
+            for change in compare_programs(old, program):
+                if str(root) != "." and change.file:
+                    change.file = (root / change.file).as_posix()
+                metadata["changes"].append(change.to_dict())
+                if self.include_handled or not change.handled_by:
+                    findings.append(StateMigrationFinding(change, self.baseline))
+        return findings, metadata
@@ -0,0 +1,417 @@
+"""
+Suppressions and baselines for detector hits.
+
+A project's ``static_analysis/suppressions.json`` holds:
+
+- suppressions: a rule, optionally only under a path glob, that the team
+  decided not to see, with why and who decided
+- baseline: individual hits accepted as known (rule, file and title), so
+  later runs only show new ones
+
+The file records the rule set version it was written against. Loading it
+under a newer rule set migrates every rule id forward (see rules.py): a
+renamed rule keeps its entries, a split rule's entries are copied to each
+new rule, and a merge is reported because a suppression of one old rule
+now hides hits of the others too. The old file is kept as
+``suppressions.v<N>.json`` and entries for retired rules are reported
+rather than dropped.
+
+Two more live with the code rather than the project:
+
+- inline: a ``baskerville:allow(SOL-003)`` comment on a hit's line, or
+  among the comments and attributes directly above it, hides hits of that
+  rule there. It takes codes (``SOL-003`` or ``SOL-003-TYPE-COSPLAY``),
+  rule ids and detector names, comma-separated.
+- ``.baskerville-baseline.json`` at the workspace root: accepted findings
+  by rule id (the stable code where there is one), file and fingerprint
+  (see finding.py), so repeated `scan`s only report new ones. Fingerprints
+  count alike hits in line order, so they are matched against a whole
+  run's hits rather than one hit at a time
+"""
+
+import json
+import re
+from dataclasses import asdict, dataclass, field, replace
+from datetime import datetime
+from fnmatch import fnmatch
+from pathlib import Path
+from typing import Any
+
+from .confidence import source_tool
+from .finding import Finding, number_occurrences
+from .rules import RuleCatalog, load_catalog, rule_code, rule_id
+
+SUPPRESSIONS_FILE = "suppressions.json"
+BASELINE_FILE = ".baskerville-baseline.json"
+BASELINE_FORMAT = "baskerville-baseline"
+BASELINE_VERSION = 1
+
+_ALLOW = re.compile(r"baskerville:allow\(([^)]*)\)")
+_SHORT_CODE = re.compile(r"^[A-Z]{2,5}-\d{3}$")
+# Lines that may sit between an allow comment and the code it is about
+_COMMENT_PREFIXES = ("//", "/*", "*", "#")
+
+
+class SuppressionError(ValueError):
+    """A suppressions file that cannot be read or is newer than this rule set."""
+    pass
+
+
+def _files(hit: dict[str, Any]) -> list[str]:
+    return [str(f) for f in (hit.get("properties") or {}).get("source_files") or []]
+
+
+@dataclass
+class Suppression:
+    """Hide every hit of a rule (by id or stable code), or only those under ``path``."""
+
+    rule: str
+    path: str | None = None
+    reason: str = ""
+    created_by: str | None = None
+    created_at: str = field(default_factory=lambda: datetime.now().isoformat())
+    migrated_from: list[str] = field(default_factory=list)
+
+    def matches(self, hit: dict[str, Any]) -> bool:
+        if self.rule not in (rule_id(hit), rule_code(hit)):
+            return False
+        return self.path is None or any(fnmatch(f, self.path) for f in _files(hit))
+
+
+@dataclass
+class BaselineEntry:
+    """One known hit, matched on rule, first source file and title."""
+
+    rule: str
+    title: str
+    file: str | None = None
+    added_at: str = field(default_factory=lambda: datetime.now().isoformat())
+    migrated_from: list[str] = field(default_factory=list)
+
+    @classmethod
+    def of(cls, hit: dict[str, Any]) -> "BaselineEntry":
+        files = _files(hit)
+        return cls(rule=rule_id(hit), title=str(hit.get("title", "")), file=files[0] if files else None)
+
+    def key(self) -> tuple[str, str | None, str]:
+        return self.rule, self.file, self.title
+
+    def matches(self, hit: dict[str, Any]) -> bool:
+        return self.key() == BaselineEntry.of(hit).key()
+
+
+@dataclass
+class MigrationReport:
+    """What migrating a suppressions file to the current rule set changed."""
+
+    from_version: int
+    to_version: int
+    moved: list[tuple[str, list[str]]] = field(default_factory=list)
+    widened: list[tuple[str, list[str]]] = field(default_factory=list)
+    retired: list[str] = field(default_factory=list)
+    backup: Path | None = None
+
+    @property
+    def changed(self) -> bool:
+        return self.from_version != self.to_version
+
+    def lines(self) -> list[str]:
+        """Human-readable summary, one change per line."""
+        lines = [f"Migrated suppressions from rule set v{self.from_version} to v{self.to_version}"]
+        lines += [f"  {old} -> {', '.join(new)}" for old, new in self.moved]
+        lines += [f"  widened: suppressing {rule} now also hides {', '.join(others)} (merged)"
+                  for rule, others in self.widened]
+        lines += [f"  retired: {rule} no longer fires; its entries are kept but match nothing" for rule in self.retired]
+        if self.backup:
+            lines.append(f"  previous file kept as {self.backup}")
+        return lines
+
+
+class SuppressionStore:
+    """A project's suppressions and baseline, kept on the current rule set."""
+
+    def __init__(self, path: Path, catalog: RuleCatalog | None = None):
+        self.path = Path(path)
+        self.catalog = catalog or load_catalog()
+        self.rules_version = self.catalog.version
+        self.suppressions: list[Suppression] = []
+        self.baseline: list[BaselineEntry] = []
+
+    @classmethod
+    def for_project(cls, project_dir: Path, catalog: RuleCatalog | None = None) -> "SuppressionStore":
+        return cls(Path(project_dir) / "static_analysis" / SUPPRESSIONS_FILE, catalog)
+
+    def load(self, migrate: bool = True, write: bool = True) -> MigrationReport | None:
+        """Read the file, migrating it to the current rule set if it is older.
+
+        Args:
+            migrate: Migrate rule ids written against an older rule set
+            write: Save the migrated file (keeping the old one beside it)
+
+        Returns:
+            The migration report, or None if there was nothing to migrate
+
+        Raises:
+            SuppressionError: If the file is unreadable or was written by a
+                newer rule set, which this one cannot map back
+        """
+        if not self.path.exists():
+            return None
+        try:
+            data = json.loads(self.path.read_text())
+            self.suppressions = [Suppression(**entry) for entry in data.get("suppressions", [])]
+            self.baseline = [BaselineEntry(**entry) for entry in data.get("baseline", [])]
+        except (OSError, json.JSONDecodeError, TypeError, AttributeError) as e:
+            raise SuppressionError(f"cannot read {self.path}: {e}") from None
+        self.rules_version = int(data.get("rules_version", 1))
+        if self.rules_version > self.catalog.version:
+            raise SuppressionError(
+                f"{self.path} was written by rule set v{self.rules_version}, newer than this "
+                f"baskerville's v{self.catalog.version}; upgrade rather than lose its entries")
+        if not migrate or self.rules_version == self.catalog.version:
+            return None
+        original = self.path.read_text()
+        report = self.migrate()
+        if write:
+            report.backup = self.path.with_name(f"{self.path.stem}.v{report.from_version}{self.path.suffix}")
+            report.backup.write_text(original)
+            self.save()
+        return report
+
+    def migrate(self) -> MigrationReport:
+        """Move every entry's rule id to the current rule set (in memory)."""
+        since = self.rules_version
+        report = MigrationReport(since, self.catalog.version)
+        renamed: dict[str, list[str]] = {}
+
+        def current(rule: str) -> list[str]:
+            if rule not in renamed:
+                renamed[rule] = self.catalog.migrate(rule, since)
+                if renamed[rule] != [rule]:
+                    report.moved.append((rule, renamed[rule]))
+            return renamed[rule]
+
+        suppressions: dict[tuple[str, str | None], Suppression] = {}
+        for entry in self.suppressions:
+            for new in current(entry.rule):
+                key = (new, entry.path)
+                if key in suppressions:
+                    suppressions[key].migrated_from.append(entry.rule)
+                    continue
+                moved = Suppression(**{**asdict(entry), "rule": new})
+                if new != entry.rule:
+                    moved.migrated_from = [*entry.migrated_from, entry.rule]
+                suppressions[key] = moved
+        suppressed = {(s.rule, s.path) for s in self.suppressions}
+        for entry in self.suppressions:
+            others = [r for r in self.catalog.merged_with(entry.rule, since) if (r, entry.path) not in suppressed]
+            if others:
+                report.widened.append((entry.rule, others))
+
+        baseline: dict[tuple[str, str | None, str], BaselineEntry] = {}
+        for entry in self.baseline:
+            for new in current(entry.rule):
+                moved = BaselineEntry(**{**asdict(entry), "rule": new})
+                if new != entry.rule:
+                    moved.migrated_from = [*entry.migrated_from, entry.rule]
+                baseline.setdefault(moved.key(), moved)
+
+        self.suppressions = list(suppressions.values())
+        self.baseline = list(baseline.values())
+        report.retired = sorted({e.rule for e in [*self.suppressions, *self.baseline] if self.catalog.retired(e.rule)})
+        self.rules_version = self.catalog.version
+        return report
+
+    def save(self) -> Path:
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        self.path.write_text(json.dumps({
+            "rules_version": self.rules_version,
+            "suppressions": [asdict(s) for s in self.suppressions],
+            "baseline": [asdict(b) for b in self.baseline],
+        }, indent=2))
+        return self.path
+
+    def suppress(self, rule: str, path: str | None = None, reason: str = "",
+                 created_by: str | None = None) -> Suppression:
+        """Add (or replace) a suppression; an old rule id is stored as its current one(s)."""
+        added = None
+        for new in self.catalog.migrate(rule, 0):
+            self.suppressions = [s for s in self.suppressions if (s.rule, s.path) != (new, path)]
+            added = Suppression(new, path, reason, created_by)
+            self.suppressions.append(added)
+        return added
+
+    def add_baseline(self, hits: list[dict[str, Any]], since: int | None = None) -> int:
+        """Accept hits as known; returns how many were new to the baseline.
+
+        Args:
+            hits: Detector hits, e.g. a saved static_hypotheses.json
+            since: Rule set version the hits were produced under, if older
+        """
+        known = {b.key() for b in self.baseline}
+        added = 0
+        for hit in hits:
+            entry = BaselineEntry.of(hit)
+            rules = self.catalog.migrate(entry.rule, since) if since is not None else [entry.rule]
+            for rule in rules:
+                moved = BaselineEntry(rule, entry.title, entry.file)
+                if moved.key() not in known:
+                    known.add(moved.key())
+                    self.baseline.append(moved)
+                    added += 1
+        return added
+
+    def apply(self, hits: list[dict[str, Any]]) -> tuple[list[dict], list[dict], list[dict]]:
+        """Split hits into (kept, suppressed, baselined)."""
+        kept, suppressed, baselined = [], [], []
+        for hit in hits:
+            if any(s.matches(hit) for s in self.suppressions):
+                suppressed.append(hit)
+            elif any(b.matches(hit) for b in self.baseline):
+                baselined.append(hit)
+            else:
+                kept.append(hit)
+        return kept, suppressed, baselined
+
+
+def allows(token: str, hit: dict[str, Any]) -> bool:
+    """Whether an allow-comment entry (code, short code, rule id or detector) covers ``hit``."""
+    token = token.strip()
+    code = rule_code(hit)
+    if code and (token.upper() == code or (_SHORT_CODE.match(token.upper()) and code.startswith(token.upper() + "-"))):
+        return True
+    return token in (rule_id(hit), source_tool(hit))
+
+
+def allowed_at(lines: list[str], line: int) -> list[str]:
+    """Entries of the allow comments that apply to 1-based ``line``: on it, or in the comments right above it."""
+    if not 0 < line <= len(lines):
+        return []
+    tokens = []
+    for match in _ALLOW.finditer(lines[line - 1]):
+        tokens += match.group(1).split(",")
+    above = line - 2
+    while above >= 0 and lines[above].strip().startswith(_COMMENT_PREFIXES):
+        for match in _ALLOW.finditer(lines[above]):
+            tokens += match.group(1).split(",")
+        above -= 1
+    return [t.strip() for t in tokens if t.strip()]
+
+
+class InlineSuppressions:
+    """``baskerville:allow(...)`` comments in the scanned sources."""
+
+    def __init__(self, root: Path):
+        self.root = Path(root)
+        self._lines: dict[Path, list[str]] = {}
+
+    def _read(self, path: Path) -> list[str]:
+        if path not in self._lines:
+            try:
+                self._lines[path] = path.read_text(errors="replace").splitlines()
+            except OSError:
+                self._lines[path] = []
+        return self._lines[path]
+
+    def matches(self, hit: dict[str, Any]) -> bool:
+        """Whether an allow comment at the hit's location covers it."""
+        location = Finding.from_hypothesis(hit).location
+        if location is None or location.start_line is None:
+            return False
+        path = Path(location.path)
+        if not path.is_absolute():
+            path = (self.root if self.root.is_dir() else self.root.parent) / path
+        return any(allows(token, hit) for token in allowed_at(self._read(path), location.start_line))
+
+    def apply(self, hits: list[dict[str, Any]]) -> tuple[list[dict], list[dict]]:
+        """Split hits into (kept, suppressed)."""
+        kept, suppressed = [], []
+        for hit in hits:
+            (suppressed if self.matches(hit) else kept).append(hit)
+        return kept, suppressed
+
+
+@dataclass(frozen=True)
+class AcceptedFinding:
+    """One entry of a workspace baseline."""
+
+    rule_id: str
+    file: str | None
+    fingerprint: str
+    title: str = ""
+
+
+class WorkspaceBaseline:
+    """Accepted findings of a workspace, in ``.baskerville-baseline.json`` at its root."""
+
+    def __init__(self, workspace: Path):
+        self.workspace = Path(workspace)
+        self.path = self.workspace / BASELINE_FILE
+        self.entries: list[AcceptedFinding] = []
+
+    def _finding(self, hit: dict[str, Any]) -> Finding:
+        finding = Finding.from_hypothesis(hit)
+        # Paths relative to the workspace, so the file reads the same on every checkout
+        location = finding.location
+        if location is not None and Path(location.path).is_absolute():
+            path = Path(location.path)
+            if path.is_relative_to(self.workspace.resolve()):
+                finding.location = replace(location, path=path.relative_to(self.workspace.resolve()).as_posix())
+        return finding
+
+    def entries_for(self, hits: list[dict[str, Any]]) -> list[AcceptedFinding]:
+        """The entry of each hit; fingerprints number alike hits among ``hits`` (see finding.py)."""
+        findings = number_occurrences([self._finding(hit) for hit in hits])
+        return [AcceptedFinding(f.rule_id, f.location.path if f.location else None, f.fingerprint, f.title)
+                for f in findings]
+
+    def load(self) -> "WorkspaceBaseline":
+        """Read the file (nothing is accepted without one).
+
+        Raises:
+            SuppressionError: If the file is unreadable or not a baseline
+        """
+        if not self.path.exists():
+            return self
+        try:
+            data = json.loads(self.path.read_text())
+        except (OSError, ValueError) as e:
+            raise SuppressionError(f"cannot read {self.path}: {e}") from None
+        if not isinstance(data, dict) or data.get("format") != BASELINE_FORMAT:
+            raise SuppressionError(f"{self.path} is not a baskerville baseline")
+        if data.get("version", 0) > BASELINE_VERSION:
+            raise SuppressionError(f"{self.path} is baseline version {data['version']}, newer than supported "
+                                   f"({BASELINE_VERSION})")
+        self.entries = [AcceptedFinding(str(e["rule_id"]), e.get("file"), str(e["fingerprint"]), e.get("title", ""))
+                        for e in data.get("findings") or []]
+        return self
+
+    def save(self) -> Path:
+        entries = sorted(self.entries, key=lambda e: (e.file or "", e.rule_id, e.title))
+        self.path.write_text(json.dumps({
+            "format": BASELINE_FORMAT,
+            "version": BASELINE_VERSION,
+            "rules_version": load_catalog().version,
+            "findings": [asdict(e) for e in entries],
+        }, indent=2) + "\n")
+        return self.path
+
+    def accept(self, hits: list[dict[str, Any]]) -> int:
+        """Accept hits; returns how many were new to the baseline."""
+        known = set(self.entries)
+        added = [e for e in dict.fromkeys(self.entries_for(hits)) if e not in known]
+        self.entries += added
+        return len(added)
+
+    def apply(self, hits: list[dict[str, Any]]) -> tuple[list[dict], list[dict]]:
+        """Split hits into (new, baselined)."""
+        accepted = set(self.entries)
+        new, baselined = [], []
+        for hit, entry in zip(hits, self.entries_for(hits)):
+            (baselined if entry in accepted else new).append(hit)
+        return new, baselined
+
+    def stale(self, hits: list[dict[str, Any]]) -> list[AcceptedFinding]:
+        """Accepted findings ``hits`` no longer has (fixed, or their code moved)."""
+        current = set(self.entries_for(hits))
+        return [e for e in self.entries if e not in current]
@@ -0,0 +1,199 @@
+"""
+Type cosplay (missing discriminator check) detector for Solana programs.
+
+Every `#[account]` type of an Anchor program is owned by the same program,
+so an owner check cannot tell them apart: only the 8-byte discriminator
+Anchor writes at the start of the data says which type an account holds.
+`Account<'info, T>` and `T::try_deserialize` compare it; a handler that
+parses the data itself with Borsh (`T::try_from_slice(&data[8..])`,
+`T::deserialize`), `try_deserialize_unchecked`, `Account::try_from_unchecked`
+or a zero-copy cast of `data[8..]` skips it, and accepts any other account
+of the program whose layout parses as `T` (a user's `Profile` passed as the
+`Config` that names the admin).
+
+Walks every instruction of Anchor and native programs through the program
+IR and flags raw accounts whose data reaches such a deserializer (directly
+or through a local alias, found as the missing owner check finds them)
+while the handler compares no discriminator (`T::DISCRIMINATOR`, `data[..8]`, a
+`discriminant`/`account_type`/`Key::` field). Native programs have no Anchor
+discriminator, so there only reads that skip the first 8 bytes count. Each
+finding links to the `type_cosplay` PoC template.
+
+Built in (no external tool), so it is always available.
+"""
+
+import os
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from extensions.ir import load_programs
+from extensions.ir.model import Instruction, Program
+
+from .missing_owner import _DESERIALIZE_RE, _reads, _statement
+from .missing_signer import _RAW_WRAPPERS
+
+DETECTOR_VERSION = "1.0.0"
+TEMPLATE_ID = "type_cosplay"
+TEMPLATE_FILE = "extensions/knowledge/templates/solana/type_cosplay.rs"
+
+# Deserializers that never look at the discriminator; `try_deserialize` and `Pack::unpack` are left out
+_UNCHECKED_CALLS = {"try_from_slice", "deserialize", "try_deserialize_unchecked", "from_bytes", "from_bytes_mut",
+                    "try_from_bytes", "try_from_bytes_mut"}
+# `&data[8..]`, `&data[DISCRIMINATOR.len()..]`, `data.split_at(8)`
+_SKIP_RE = re.compile(r"\[\s*(?:8|(?:\w+\s*::\s*)*\w*DISCRIMINATOR\w*(?:\s*\.\s*len\s*\(\s*\))?)\s*\.\.|"
+                      r"split_at(?:_mut)?\s*\(\s*8\s*\)")
+_CHECK_TERMS_RE = re.compile(r"discriminat|DISCRIMINATOR|\baccount_type\b|\bAccountType::|\bKey::|"
+                             r"\[\s*(?:0\s*)?\.\.\s*8\s*\]")
+_COMPARISON_RE = re.compile(r"[!=]=|\brequire(?:_eq|_neq)?!|\bassert(?:_eq|_ne)?!|\bmatches!|\bstarts_with\s*\(")
+
+
+@dataclass
+class TypeCosplayFinding:
+    """Data of a raw account parsed as a type without checking its discriminator."""
+
+    program: str
+    instruction: str
+    account: str
+    account_type: str
+    path: str
+    line: int  # Where the data is deserialized
+    text: str
+    call: str  # The deserializer, or "cast" for a pointer cast
+    skipped: bool  # Whether the read starts past the 8 discriminator bytes
+    state_type: str | None = None
+    account_line: int = 0
+    program_id: str | None = None
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        ty = f"`{self.state_type}`" if self.state_type else "its state type"
+        how = ("skipping the 8-byte discriminator without comparing it" if self.skipped
+               else f"with `{self.call}`, which does not check the 8-byte discriminator")
+        context = {"PROGRAM_ID": self.program_id} if self.program_id else {}
+        program_id = self.program_id or "<program id>"
+        description = (
+            f"`{self.program}::{self.instruction}` parses the data of `{self.account}` ({self.account_type}) as {ty} "
+            f"{how} (`{self.text}`). Any other account of the program whose data parses as {ty} passes, owner "
+            f"checks included, so a caller hands in an account of another type (e.g. one they initialized "
+            f"themselves) as this one. Use `Account<'info, T>` or `T::try_deserialize`, or compare `data[..8]` "
+            f"with `T::DISCRIMINATOR` before parsing. PoC: `kb render {TEMPLATE_ID} -s PROGRAM_ID={program_id} "
+            f"-s VAULT_ACCOUNT=<pubkey>`."
+        )
+        return {
+            "title": f"Type cosplay: `{self.account}` parsed without a discriminator check in {self.instruction}",
+            "description": description,
+            "vulnerability_type": "type-cosplay",
+            "severity": "high",
+            "confidence": 0.6 if self.skipped else 0.5,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "type-cosplay",
+                "category": "account-validation",
+                "source_files": [self.path],
+                "affected_lines": [self.line],
+                "program": self.program,
+                "instruction": self.instruction,
+                "account": self.account,
+                "account_line": self.account_line,
+                "state_type": self.state_type,
+                "deserializer": self.call,
+                "skips_discriminator": self.skipped,
+                "poc_template": TEMPLATE_ID,
+                "poc_template_file": TEMPLATE_FILE,
+                "poc_context": context,
+                "precision": "syntactic",
+            },
+        }
+
+
+def _call(text: str) -> str | None:
+    """The discriminator-blind deserializer ``text`` uses, or None."""
+    if re.search(r"\btry_from_unchecked\s*\(", text):
+        return "try_from_unchecked"
+    m = _DESERIALIZE_RE.search(text)
+    if m:
+        return m.group("call") if m.group("call") in _UNCHECKED_CALLS else None
+    return "cast" if re.search(r"as\s*\*\s*(?:const|mut)\s+\w+", text) else None
+
+
+def discriminator_checked(ix: Instruction) -> bool:
+    """Whether the handler compares a discriminator (or type tag) anywhere."""
+    for m in _CHECK_TERMS_RE.finditer(ix.body):
+        start, end = _statement(ix.body, m.start())
+        if _COMPARISON_RE.search(ix.body[start:end]) or re.match(r"\s*(?:if|match)\b", ix.body[start:end]):
+            return True
+    return False
+
+
+def _skips(ix: Instruction, offset: int, text: str) -> bool:
+    """Whether the read at ``offset`` starts past the discriminator, directly or through a sliced local."""
+    if _SKIP_RE.search(text):
+        return True
+    # `let body = &data[8..];` then `T::try_from_slice(body)`
+    for m in re.finditer(r"let\s+(\w+)\s*(?::[^=]+)?=([^;]*);", ix.body[:offset]):
+        if _SKIP_RE.search(m.group(2)) and re.search(rf"\b{re.escape(m.group(1))}\b", text):
+            return True
+    return False
+
+
+def check_instruction(program: Program, ix: Instruction) -> list[TypeCosplayFinding]:
+    """Raw accounts of ``ix`` parsed without a discriminator check (paths relative to the program)."""
+    if discriminator_checked(ix):
+        return []
+    findings = []
+    for acc in ix.accounts:
+        if acc.wrapper not in _RAW_WRAPPERS:
+            continue
+        for offset, text, ty in sorted(_reads(ix, acc)):
+            call = _call(text)
+            if call is None:
+                continue
+            skipped = _skips(ix, offset, text)
+            if program.framework != "anchor" and not skipped:
+                continue
+            findings.append(TypeCosplayFinding(program.name, ix.name, acc.name, " ".join(acc.ty.split()),
+                                               ix.source_file, ix.body_line + ix.body.count("\n", 0, offset), text,
+                                               call, skipped, ty, acc.line, program.program_id))
+            break
+    return findings
+
+
+class TypeCosplayDetector:
+    """Flags raw account data parsed as a type without checking the account's discriminator."""
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{DETECTOR_VERSION}"
+
+    def run(self, project_path: Path) -> tuple[list[TypeCosplayFinding], dict]:
+        """Check every Anchor and native Solana program under ``project_path``.
+
+        Returns:
+            Tuple of (findings, metadata)
+        """
+        project_path = Path(project_path)
+        findings: list[TypeCosplayFinding] = []
+        checked = 0
+        for program in load_programs(project_path):
+            if program.chain != "solana" or program.framework not in ("anchor", "native"):
+                continue
+            root = Path(os.path.relpath(program.root, project_path))
+            for ix in program.instructions:
+                checked += 1
+                for finding in check_instruction(program, ix):
+                    if str(root) != ".":
+                        finding.path = (root / finding.path).as_posix()
+                    findings.append(finding)
+        metadata = {
+            "tool": "type-cosplay",
+            "version": DETECTOR_VERSION,
+            "success": True,
+            "error": None,
+            "instructions_checked": checked,
+            "template": TEMPLATE_ID,
+        }
+        return findings, metadata
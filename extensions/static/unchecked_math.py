@@ -0,0 +1,263 @@
+"""
+Unchecked balance arithmetic detector for Anchor programs.
+
+Rust release builds, which `anchor build` deploys, wrap on integer
+overflow unless the workspace's release profile sets
+`overflow-checks = true`. A handler that writes `vault.balance -= amount`
+without checking `amount` first wraps the balance to nearly `u64::MAX`
+instead of failing, and the attacker withdraws against it.
+
+Walks every Anchor instruction handler through the program IR and flags
+`+`, `-` and `*` (compound assignments and binary expressions) on
+balance-like fields of the instruction's accounts (`balance`, `amount`,
+`lamports`, `total_*`, `shares`, ...) and debits of lamports borrowed
+mutably (`**acc.try_borrow_mut_lamports()? -= x`). Subtractions the
+handler guards first (`require!(vault.balance >= amount)`, an `if`
+comparison on the same operands) and increments by a literal are left
+alone. Each finding recommends the matching `checked_*` call and links to
+the `unchecked_arithmetic` PoC template; with overflow checks enabled the
+operation panics instead of wrapping, so findings drop to low severity.
+
+Built in (no external tool), so it is always available.
+"""
+
+import os
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from extensions.ir import load_programs
+from extensions.ir.model import Instruction, Program
+
+DETECTOR_VERSION = "1.0.0"
+TEMPLATE_ID = "unchecked_arithmetic"
+TEMPLATE_FILE = "extensions/knowledge/templates/solana/unchecked_arithmetic.rs"
+
+# Field names that hold token or lamport amounts
+_BALANCE_NAME_RE = re.compile(
+    r"(?:^|_)(?:balances?|amounts?|lamports|supply|deposit(?:s|ed)?|withdrawn|shares|reserves?|liquidity|debt"
+    r"|collateral|staked?|rewards?|tokens|principal|borrowed|minted|locked|claimed|fees)(?:$|_)|^total_"
+)
+_ACCESS = r"(?:ctx\s*\.\s*accounts\s*\.\s*)?\b(?P<acc>[A-Za-z_]\w*)\s*\.\s*(?P<field>[A-Za-z_]\w*)"
+# `vault.balance -= amount`, `**vault.try_borrow_mut_lamports()? -= amount`
+_COMPOUND_RE = re.compile(_ACCESS + r"\s*(?P<op>[-+*])=\s*(?P<rhs>[^;]+)")
+_LAMPORTS_COMPOUND_RE = re.compile(
+    r"\*\*\s*(?:ctx\s*\.\s*accounts\s*\.\s*)?(?P<acc>[A-Za-z_]\w*)\s*(?:\.\s*to_account_info\s*\(\s*\))?"
+    r"\s*\.\s*(?:try_borrow_mut_lamports\s*\(\s*\)\s*\?|lamports\s*\.\s*borrow_mut\s*\(\s*\))"
+    r"\s*(?P<op>[-+*])=\s*(?P<rhs>[^;]+)"
+)
+# `vault.balance - amount`, `amount * pool.total_shares` (not `->`, `-=`, or a `checked_*` argument)
+_BINARY_LEFT_RE = re.compile(_ACCESS + r"(?:\s*\(\s*\))?\s*(?P<op>[-+*])(?![=>])\s*(?P<rhs>[A-Za-z_0-9(][^;,)]*)")
+_BINARY_RIGHT_RE = re.compile(r"(?P<lhs>[A-Za-z_0-9)]\w*)\s*(?P<op>[-+*])(?![=>])\s*" + _ACCESS)
+
+SUGGESTED = {"-": "checked_sub", "+": "checked_add", "*": "checked_mul"}
+_OVERFLOW_CHECKS_RE = re.compile(r"\[profile\.release\][^\[]*?overflow-checks\s*=\s*true", re.S)
+
+
+@dataclass
+class UncheckedArithmeticFinding:
+    """Wrapping `+`, `-` or `*` on a balance the instruction stores or moves."""
+
+    program: str
+    instruction: str
+    account: str
+    field: str  # "lamports" for borrowed lamports
+    op: str
+    path: str
+    line: int
+    text: str
+    compound: bool = True
+    overflow_checks: bool = False
+    program_id: str | None = None
+
+    @property
+    def target(self) -> str:
+        return f"{self.account}.{self.field}"
+
+    @property
+    def severity(self) -> str:
+        if self.overflow_checks:
+            return "low"
+        return "high" if self.op == "-" else "medium"
+
+    @property
+    def fix(self) -> str:
+        call = SUGGESTED[self.op]
+        if self.compound and self.field == "lamports":
+            return f"let lamports = {self.account}.lamports().{call}(..).ok_or(ErrorCode::MathOverflow)?;"
+        return f"{self.target} = {self.target}.{call}(..).ok_or(ErrorCode::MathOverflow)?;"
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        wraps = {"-": "underflows", "+": "overflows", "*": "overflows"}[self.op]
+        outcome = (
+            "The workspace enables `overflow-checks`, so it panics instead of wrapping; checked math turns the "
+            "panic into a proper error."
+            if self.overflow_checks else
+            "Release builds wrap instead of failing, so the stored value can jump to an attacker-favourable one."
+        )
+        program_id = self.program_id or "<program id>"
+        description = (
+            f"`{self.program}::{self.instruction}` computes `{self.text}`, which {wraps} `{self.target}` without a "
+            f"check. {outcome} Use `{SUGGESTED[self.op]}` and return an error on `None`. PoC: "
+            f"`kb render {TEMPLATE_ID} -s PROGRAM_ID={program_id} -s VAULT_ACCOUNT=<pubkey>`."
+        )
+        return {
+            "title": f"Unchecked `{self.op}` on `{self.target}` in {self.instruction}",
+            "description": description,
+            "vulnerability_type": "unchecked-balance-arithmetic",
+            "severity": self.severity,
+            "confidence": 0.3 if self.overflow_checks else 0.6,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "unchecked-math",
+                "category": "arithmetic",
+                "source_files": [self.path],
+                "affected_lines": [self.line],
+                "program": self.program,
+                "instruction": self.instruction,
+                "account": self.account,
+                "field": self.field,
+                "operator": self.op,
+                "overflow_checks": self.overflow_checks,
+                "suggested_fix": self.fix,
+                "poc_template": TEMPLATE_ID,
+                "poc_template_file": TEMPLATE_FILE,
+                "poc_context": {"PROGRAM_ID": self.program_id} if self.program_id else {},
+                "precision": "syntactic",
+            },
+        }
+
+
+def _operand(text: str) -> str:
+    """Last identifier of an operand (`ctx.accounts.vault.balance` -> `balance`, `amount` -> `amount`)."""
+    names = re.findall(r"[A-Za-z_]\w*", text.split("(")[0])
+    return names[-1] if names else ""
+
+
+def _guarded(body: str, pos: int, left: str, right: str) -> bool:
+    """Whether code before ``pos`` compares the operands of a subtraction."""
+    if not left or not right:
+        return False
+    before = body[:pos]
+    a, b = re.escape(left), re.escape(right)
+    return bool(re.search(rf"\b{a}\b[^;{{]*?(?:>=|>|<|<=)[^;{{]*?\b{b}\b|\b{b}\b[^;{{]*?(?:>=|>|<|<=)[^;{{]*?\b{a}\b",
+                          before))
+
+
+def _statement(body: str, pos: int) -> str:
+    start = max(body.rfind(";", 0, pos), body.rfind("{", 0, pos), body.rfind("}", 0, pos)) + 1
+    end = body.find(";", pos)
+    return " ".join(body[start:end if end >= 0 else len(body)].split())
+
+
+def _is_balance(ix: Instruction, acc: str, field: str) -> bool:
+    account = ix.account(acc)
+    return account is not None and not any(c.startswith("init") for c in account.constraints) and bool(
+        _BALANCE_NAME_RE.search(field.lower()))
+
+
+def check_instruction(program: Program, ix: Instruction,
+                      overflow_checks: bool = False) -> list[UncheckedArithmeticFinding]:
+    """Unchecked balance arithmetic in ``ix`` (paths relative to the program)."""
+    body = ix.body
+    findings: list[UncheckedArithmeticFinding] = []
+    seen: set[tuple[int, str]] = set()
+
+    def add(pos: int, acc: str, field: str, op: str, other: str, compound: bool) -> None:
+        line = ix.body_line + body.count("\n", 0, pos)
+        if (line, op) in seen:
+            return
+        if op == "-" and _guarded(body, pos, field, _operand(other)):
+            return
+        # `count += 1` style increments cannot realistically wrap a u64
+        if op == "+" and re.fullmatch(r"\d+(?:_?u(?:8|16|32|64|128))?", other.strip()):
+            return
+        seen.add((line, op))
+        findings.append(UncheckedArithmeticFinding(program.name, ix.name, acc, field, op, ix.source_file, line,
+                                                   _statement(body, pos), compound, overflow_checks,
+                                                   program.program_id))
+
+    for m in _LAMPORTS_COMPOUND_RE.finditer(body):
+        # Crediting lamports cannot realistically overflow; debits can take more than the account holds
+        if m.group("op") == "-" and ix.account(m.group("acc")) is not None:
+            add(m.start(), m.group("acc"), "lamports", m.group("op"), m.group("rhs"), True)
+    for m in _COMPOUND_RE.finditer(body):
+        if _is_balance(ix, m.group("acc"), m.group("field")):
+            add(m.start(), m.group("acc"), m.group("field"), m.group("op"), m.group("rhs"), True)
+    for m in _BINARY_LEFT_RE.finditer(body):
+        if _is_balance(ix, m.group("acc"), m.group("field")):
+            add(m.start(), m.group("acc"), m.group("field"), m.group("op"), m.group("rhs"), False)
+    for m in _BINARY_RIGHT_RE.finditer(body):
+        if m.group("lhs").isdigit() or not _is_balance(ix, m.group("acc"), m.group("field")):
+            continue
+        # `a - vault.balance` underflows on `a`; the guard to look for compares it with the field
+        add(m.start(), m.group("acc"), m.group("field"), m.group("op"), m.group("lhs"), False)
+    return sorted(findings, key=lambda f: f.line)
+
+
+def overflow_checks_enabled(root: Path, limit: Path) -> bool:
+    """Whether the nearest Cargo.toml release profile from ``root`` up to ``limit`` sets `overflow-checks`."""
+    current, limit = Path(root).resolve(), Path(limit).resolve()
+    while True:
+        manifest = current / "Cargo.toml"
+        if manifest.exists():
+            try:
+                if _OVERFLOW_CHECKS_RE.search(manifest.read_text(errors="replace")):
+                    return True
+            except OSError:
+                pass
+        if current == limit or current.parent == current:
+            return False
+        current = current.parent
+
+
+class UncheckedArithmeticDetector:
+    """Flags wrapping `+`, `-` and `*` on balance and lamport fields in Anchor handlers."""
+
+    def __init__(self, include_overflow_checked: bool = True):
+        """Initialize the detector.
+
+        Args:
+            include_overflow_checked: Also report (at low severity) programs built with overflow-checks
+        """
+        self.include_overflow_checked = include_overflow_checked
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{DETECTOR_VERSION}"
+
+    def run(self, project_path: Path) -> tuple[list[UncheckedArithmeticFinding], dict]:
+        """Check every Anchor program under ``project_path``.
+
+        Returns:
+            Tuple of (findings, metadata)
+        """
+        project_path = Path(project_path)
+        findings: list[UncheckedArithmeticFinding] = []
+        checked = 0
+        for program in load_programs(project_path):
+            if program.chain != "solana" or program.framework != "anchor":
+                continue
+            overflow_checks = overflow_checks_enabled(Path(program.root), project_path)
+            if overflow_checks and not self.include_overflow_checked:
+                continue
+            root = Path(os.path.relpath(program.root, project_path))
+            for ix in program.instructions:
+                checked += 1
+                for finding in check_instruction(program, ix, overflow_checks):
+                    if str(root) != ".":
+                        finding.path = (root / finding.path).as_posix()
+                    findings.append(finding)
+        metadata = {
+            "tool": "unchecked-math",
+            "version": DETECTOR_VERSION,
+            "success": True,
+            "error": None,
+            "instructions_checked": checked,
+            "template": TEMPLATE_ID,
+        }
+        return findings, metadata
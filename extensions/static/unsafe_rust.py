@@ -0,0 +1,438 @@
+"""
+Unsafe Rust auditor for on-chain programs.
+
+Inventories `unsafe` blocks/functions/impls and the memory-unsafe operations
+programs use for zero-copy parsing (raw pointer casts, `transmute`,
+`slice::from_raw_parts`, pointer arithmetic, unchecked indexing, `set_len`,
+`copy_nonoverlapping`), then follows attacker-controlled values through each
+function to see whether they reach a length, offset or reinterpreted buffer.
+
+Attacker-controlled sources:
+- instruction data parameters (`&[u8]`, `Vec<u8>`) and integer handler arguments
+- account data (`.data.borrow()`, `.try_borrow_data()`, ...) and `AccountInfo`s
+- values derived from those through `let` bindings and assignments
+
+Findings are reported as memory-safety issues (`memory-safety-*`), separate
+from the logic-level detectors; a bounds check on the tainted value before
+the sink lowers the severity instead of suppressing the finding.
+
+Built in (no external tool), so it is always available.
+"""
+
+import os
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+from extensions.ir.rust_source import (
+    find_matching,
+    iter_functions,
+    line_at,
+    parse_params,
+    split_top_level,
+    statement_end,
+    strip_comments,
+)
+
+AUDITOR_VERSION = "1.0.0"
+
+_SKIP_DIRS = {"target", ".git", "node_modules", "tests", "benches", "examples", ".anchor"}
+
+# Parameter types whose values the transaction sender controls
+_TAINTED_TYPE_RE = re.compile(
+    r"&\s*(?:mut\s+)?\[u8\]|Vec<u8>|AccountInfo|Context<|\b(?:u8|u16|u32|u64|u128|usize|i8|i16|i32|i64|i128|isize)\b"
+)
+# Expressions that read attacker-controlled bytes → source label
+_SOURCE_EXPRS = [
+    (re.compile(r"\.(?:data\.borrow(?:_mut)?|try_borrow_(?:mut_)?data)\s*\("), "account data"),
+    (re.compile(r"\b(?:instruction_data|ix_data)\b"), "instruction data"),
+    (re.compile(r"\bctx\.accounts\b"), "instruction accounts"),
+]
+_LET_RE = re.compile(r"\blet\s+(?P<pat>(?:mut\s+)?[A-Za-z_]\w*|\([^=]*?\))\s*(?::[^=]+)?=(?!=)")
+_ASSIGN_RE = re.compile(r"(?<![\w.])(?P<name>[A-Za-z_]\w*)\s*(?:[+\-*]?=)(?!=)")
+_IDENT_RE = re.compile(r"[A-Za-z_]\w*")
+_UNSAFE_RE = re.compile(r"\bunsafe\s+(?P<kind>fn|impl|\{)")
+_CFG_TEST_MOD_RE = re.compile(r"#\[cfg\(test\)\]\s*(?:pub\s+)?mod\s+\w+\s*\{")
+_BOUND_RE = re.compile(r"(?:<=?|>=?)|\.min\(|checked_|\.get\(|assert")
+
+# Free-function sinks: name → (finding kind, index of the length/offset/data argument)
+_CALL_SINKS: dict[str, tuple[str, int]] = {
+    "from_raw_parts": ("raw-slice-length", 1),
+    "from_raw_parts_mut": ("raw-slice-length", 1),
+    "copy_nonoverlapping": ("raw-copy-length", 2),
+    "copy": ("raw-copy-length", 2),
+    "transmute": ("transmute", 0),
+    "transmute_copy": ("transmute", 0),
+    "read_unaligned": ("raw-read", 0),
+    "write_unaligned": ("raw-read", 0),
+}
+# Method sinks: the first argument is the offset/index/length
+_METHOD_SINKS: dict[str, str] = {
+    "add": "pointer-offset",
+    "offset": "pointer-offset",
+    "sub": "pointer-offset",
+    "byte_add": "pointer-offset",
+    "get_unchecked": "unchecked-index",
+    "get_unchecked_mut": "unchecked-index",
+    "set_len": "set-len",
+}
+# Sinks that reinterpret a whole buffer, so only a size check guards them
+_SIZE_SINKS = {"transmute", "raw-read", "raw-pointer-cast"}
+_RAW_CAST_RE = re.compile(r"\bas\s+\*\s*(?:const|mut)\s+[\w:<>\[\]; ]+")
+
+_SINK_TITLES = {
+    "raw-slice-length": "attacker-controlled length in slice::from_raw_parts",
+    "raw-copy-length": "attacker-controlled count in raw pointer copy",
+    "transmute": "transmute of attacker-controlled bytes",
+    "raw-read": "unaligned raw read of attacker-controlled data",
+    "pointer-offset": "attacker-controlled pointer offset",
+    "unchecked-index": "attacker-controlled index in get_unchecked",
+    "set-len": "attacker-controlled length in Vec::set_len",
+    "raw-pointer-cast": "raw pointer cast of attacker-controlled data",
+}
+
+
+@dataclass
+class UnsafeSite:
+    """An `unsafe` region or memory-unsafe operation."""
+
+    kind: str  # unsafe_block, unsafe_fn, unsafe_impl, or a sink kind
+    file_path: str
+    line: int
+    function: str = ""
+    in_unsafe: bool = True
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "kind": self.kind,
+            "file": self.file_path,
+            "line": self.line,
+            "function": self.function,
+            "in_unsafe": self.in_unsafe,
+        }
+
+
+@dataclass
+class UnsafeRustFinding:
+    """A memory-unsafe operation reached by attacker-controlled data."""
+
+    kind: str
+    file_path: str
+    line: int
+    function: str
+    snippet: str
+    tainted: list[str] = field(default_factory=list)
+    sources: list[str] = field(default_factory=list)
+    guarded: bool = False
+    in_unsafe: bool = True
+
+    @property
+    def severity(self) -> str:
+        if not self.tainted:
+            return "info"
+        if self.guarded:
+            return "low"
+        # Out-of-bounds lengths/offsets are worse than reinterpreting in-bounds bytes
+        return "medium" if self.kind in _SIZE_SINKS else "high"
+
+    def to_hypothesis(self) -> dict[str, Any]:
+        """Convert to Hound hypothesis format."""
+        title = _SINK_TITLES.get(self.kind, self.kind)
+        if self.tainted:
+            flow = ", ".join(f"`{name}` (from {source})" for name, source in zip(self.tainted, self.sources))
+            description = f"In `{self.function}`, {flow} reaches `{self.snippet}`."
+            if self.guarded:
+                description += " A bounds check on the value precedes the operation; verify it covers every path."
+            else:
+                description += " No bounds check on the value was found before the operation, so a crafted " \
+                               "instruction or account can read or write outside the intended buffer."
+        else:
+            description = f"`{self.snippet}` in `{self.function}` (no attacker-controlled operand found)."
+        return {
+            "title": f"Unsafe Rust: {title}",
+            "description": description,
+            "vulnerability_type": f"memory-safety-{self.kind}",
+            "severity": self.severity,
+            "confidence": 0.4 if self.guarded or not self.tainted else 0.7,
+            "status": "proposed",
+            "node_refs": [],
+            "evidence": [],
+            "properties": {
+                "source_tool": "unsafe-rust",
+                "category": "memory-safety",
+                "source_files": [self.file_path],
+                "affected_lines": [self.line],
+                "function": self.function,
+                "tainted_values": self.tainted,
+                "taint_sources": self.sources,
+                "bounds_checked": self.guarded,
+                "in_unsafe": self.in_unsafe,
+                "precision": "dataflow" if self.tainted and not self.guarded else "syntactic",
+            },
+        }
+
+
+class UnsafeRustAuditor:
+    """Scans Rust program sources for unsafe code fed by attacker-controlled data."""
+
+    # Works file by file on the source text, so editors can run it on every change
+    latency = "hot"
+    source_suffixes = (".rs",)
+
+    def __init__(self, max_files: int = 2000, report_untainted: bool = False):
+        """Initialize the auditor.
+
+        Args:
+            max_files: Maximum number of `.rs` files to scan
+            report_untainted: Also emit info findings for sinks with no
+                attacker-controlled operand (the inventory always lists them)
+        """
+        self.max_files = max_files
+        self.report_untainted = report_untainted
+
+    def is_available(self) -> tuple[bool, str]:
+        """Always available (built in)."""
+        return True, f"builtin-{AUDITOR_VERSION}"
+
+    def run(self, project_path: Path) -> tuple[list[UnsafeRustFinding], dict]:
+        """Audit every Rust source under ``project_path``.
+
+        Returns:
+            Tuple of (findings, metadata); metadata["inventory"] lists every site
+        """
+        project_path = Path(project_path)
+        metadata: dict[str, Any] = {
+            "tool": "unsafe-rust",
+            "version": AUDITOR_VERSION,
+            "success": True,
+            "error": None,
+            "files_scanned": 0,
+            "inventory": [],
+            "counts": {},
+        }
+        findings: list[UnsafeRustFinding] = []
+        for path in self._rust_files(project_path):
+            try:
+                original = path.read_text(errors="replace")
+            except OSError:
+                continue
+            metadata["files_scanned"] += 1
+            rel = Path(os.path.relpath(path, project_path)).as_posix()
+            sites, file_findings = self.audit_source(original, rel)
+            metadata["inventory"].extend(site.to_dict() for site in sites)
+            findings.extend(f for f in file_findings if f.tainted or self.report_untainted)
+        for site in metadata["inventory"]:
+            metadata["counts"][site["kind"]] = metadata["counts"].get(site["kind"], 0) + 1
+        return findings, metadata
+
+    def _rust_files(self, root: Path) -> list[Path]:
+        if root.is_file():
+            return [root] if root.suffix == ".rs" else []
+        files: list[Path] = []
+        for dirpath, dirnames, filenames in os.walk(root):
+            dirnames[:] = sorted(d for d in dirnames if d not in _SKIP_DIRS and not d.startswith("."))
+            for name in sorted(filenames):
+                if name.endswith(".rs"):
+                    files.append(Path(dirpath) / name)
+                    if len(files) >= self.max_files:
+                        return files
+        return files
+
+    def check_source(self, text: str, rel_path: str) -> list[UnsafeRustFinding]:
+        """Findings for one file, filtered as ``run`` filters them."""
+        _, findings = self.audit_source(text, rel_path)
+        return [f for f in findings if f.tainted or self.report_untainted]
+
+    def audit_source(self, original: str, rel_path: str) -> tuple[list[UnsafeSite], list[UnsafeRustFinding]]:
+        """Inventory and taint-check one file; returns (sites, findings)."""
+        text = strip_comments(original)
+        excluded = _test_module_ranges(text)
+        unsafe_ranges = _unsafe_ranges(text)
+        fns = [fn for fn in iter_functions(text) if not _within(fn.start, excluded)]
+
+        sites: list[UnsafeSite] = []
+        for m in _UNSAFE_RE.finditer(text):
+            if _within(m.start(), excluded):
+                continue
+            kind = {"fn": "unsafe_fn", "impl": "unsafe_impl", "{": "unsafe_block"}[m.group("kind")]
+            name = _enclosing(fns, m.start())
+            if kind == "unsafe_fn":
+                fn_name = re.match(r"\s*([A-Za-z_]\w*)", text[m.end():])
+                name = fn_name.group(1) if fn_name else name
+            sites.append(UnsafeSite(kind, rel_path, line_at(text, m.start()), name))
+
+        findings: list[UnsafeRustFinding] = []
+        for fn in fns:
+            taint = tainted_locals(fn)
+            base = fn.body_start + 1
+            for offset, kind, operand, snippet in _sinks(fn.body):
+                pos = base + offset
+                # A nested fn's sinks are attributed to the innermost fn only
+                if _enclosing(fns, pos) != fn.name:
+                    continue
+                in_unsafe = _within(pos, unsafe_ranges)
+                # Everything but a pointer cast is an unsafe fn; outside unsafe it is a safe namesake
+                if not in_unsafe and kind != "raw-pointer-cast":
+                    continue
+                sites.append(UnsafeSite(kind, rel_path, line_at(text, pos), fn.name, in_unsafe))
+                names = list(dict.fromkeys(n for n in _IDENT_RE.findall(operand) if n in taint))
+                sources = [taint[n] for n in names]
+                if not names and _source_label(operand):
+                    names, sources = [" ".join(operand.split())], [_source_label(operand)]
+                findings.append(UnsafeRustFinding(
+                    kind=kind,
+                    file_path=rel_path,
+                    line=line_at(text, pos),
+                    function=fn.name,
+                    snippet=snippet,
+                    tainted=names,
+                    sources=sources,
+                    guarded=bool(names) and _bounds_checked(fn.body[:offset], names, kind in _SIZE_SINKS),
+                    in_unsafe=in_unsafe,
+                ))
+        sites.sort(key=lambda s: (s.line, s.kind))
+        return sites, findings
+
+
+def _within(pos: int, ranges: list[tuple[int, int]]) -> bool:
+    return any(start <= pos <= end for start, end in ranges)
+
+
+def _test_module_ranges(text: str) -> list[tuple[int, int]]:
+    ranges = []
+    for m in _CFG_TEST_MOD_RE.finditer(text):
+        ranges.append((m.start(), find_matching(text, m.end() - 1)))
+    return ranges
+
+
+def _unsafe_ranges(text: str) -> list[tuple[int, int]]:
+    """Offsets covered by `unsafe { ... }` blocks and `unsafe fn` bodies."""
+    ranges = []
+    for m in _UNSAFE_RE.finditer(text):
+        brace = m.end() - 1 if m.group("kind") == "{" else text.find("{", m.end())
+        if m.group("kind") == "impl" or brace == -1:
+            continue
+        ranges.append((brace, find_matching(text, brace)))
+    return ranges
+
+
+def _enclosing(fns, pos: int) -> str:
+    """Name of the innermost function whose body contains ``pos``."""
+    best = None
+    for fn in fns:
+        if fn.body_start <= pos <= fn.body_end and (best is None or fn.body_start > best.body_start):
+            best = fn
+    return best.name if best else ""
+
+
+def tainted_locals(fn) -> dict[str, str]:
+    """Attacker-controlled local names in ``fn`` → where the value comes from."""
+    taint: dict[str, str] = {}
+    for name, ty in parse_params(fn.params):
+        if _TAINTED_TYPE_RE.search(ty) and ty.strip() not in ("&Pubkey", "Pubkey"):
+            taint[name] = f"parameter `{name}`"
+    body = fn.body
+    for _ in range(4):
+        changed = False
+        for m in _LET_RE.finditer(body):
+            rhs = body[m.end():statement_end(body, m.end())]
+            origin = _origin(rhs, taint)
+            if origin is None:
+                continue
+            for name in _IDENT_RE.findall(m.group("pat")):
+                if name != "mut" and name not in taint:
+                    taint[name] = origin
+                    changed = True
+        for m in _ASSIGN_RE.finditer(body):
+            name = m.group("name")
+            if name in taint or name in ("let", "mut"):
+                continue
+            rhs = body[m.end():statement_end(body, m.end())]
+            origin = _origin(rhs, taint)
+            if origin is not None:
+                taint[name] = origin
+                changed = True
+        if not changed:
+            break
+    return taint
+
+
+def _origin(expr: str, taint: dict[str, str]) -> str | None:
+    label = _source_label(expr)
+    if label:
+        return label
+    for name in _IDENT_RE.findall(expr):
+        if name in taint:
+            return taint[name]
+    return None
+
+
+def _source_label(expr: str) -> str | None:
+    for pattern, label in _SOURCE_EXPRS:
+        if pattern.search(expr):
+            return label
+    return None
+
+
+def _sinks(body: str) -> list[tuple[int, str, str, str]]:
+    """(offset, kind, operand, snippet) for each memory-unsafe operation in ``body``."""
+    found = []
+    for m in re.finditer(r"(?<![\w])(?P<name>[A-Za-z_]\w*)\s*(?:::\s*<[^()]*>)?\s*\(", body):
+        name = m.group("name")
+        paren = m.end() - 1
+        close = find_matching(body, paren)
+        args = split_top_level(body[paren + 1:close])
+        is_method = m.start() > 0 and body[m.start() - 1] == "."
+        snippet = " ".join(body[m.start():close + 1].split())
+        if is_method and name in _METHOD_SINKS and args:
+            found.append((m.start(), _METHOD_SINKS[name], args[0], snippet))
+        elif not is_method and name in _CALL_SINKS:
+            kind, index = _CALL_SINKS[name]
+            if index < len(args):
+                found.append((m.start(), kind, args[index], snippet))
+    for m in _RAW_CAST_RE.finditer(body):
+        start = _expr_start(body, m.start())
+        operand = body[start:m.start()]
+        found.append((start, "raw-pointer-cast", operand, " ".join(body[start:m.end()].split())))
+    return sorted(found)
+
+
+def _expr_start(body: str, end: int) -> int:
+    """Start of the expression ending at ``end`` (back to the enclosing statement or argument)."""
+    depth = 0
+    i = end - 1
+    while i >= 0:
+        ch = body[i]
+        if ch in ")]":
+            depth += 1
+        elif ch in "([":
+            if depth == 0:
+                break
+            depth -= 1
+        elif ch in ";{},=" and depth == 0:
+            break
+        i -= 1
+    return i + 1
+
+
+def _bounds_checked(before: str, names: list[str], size_only: bool = False) -> bool:
+    """Whether a bounds check on a tainted value precedes the sink.
+
+    Offsets and lengths count as checked when compared against a `.len()`,
+    length constant or `size_of`, or clamped; a reinterpreted buffer
+    (``size_only``) only when its length is compared against a size.
+    """
+    for statement in re.split(r"[;{}]", before):
+        if not any(re.search(rf"\b{re.escape(n)}\b", statement) for n in names):
+            continue
+        sized = "LEN" in statement or "size_of" in statement
+        if size_only:
+            if sized and _BOUND_RE.search(statement):
+                return True
+            continue
+        if (sized or "len()" in statement) and _BOUND_RE.search(statement):
+            return True
+        if re.search(r"\.min\(|checked_(?:add|sub|mul)", statement):
+            return True
+    return False
@@ -69,6 +69,12 @@ def _invoke_click(cmd_func, params: dict):
     import click
     ctx = click.Context(cmd_func)
     ctx.params = params or {}
+    # The params skip click's parsing, so check choices here: a bad one is a usage error before the command runs
+    for param in cmd_func.params:
+        value = ctx.params.get(param.name)
+        if isinstance(param.type, click.Choice) and value is not None:
+            for item in value if param.multiple else (value,):
+                param.type.convert(item, param, ctx)
     try:
         cmd_func.invoke(ctx)
     except SystemExit as e:
@@ -202,20 +208,22 @@ def project_rm(
 @project_app.command("hypotheses")
 def project_hypotheses(
     name: str = typer.Argument(..., help="Project name"),
-    details: bool = typer.Option(False, "--details", "-d", help="Show full descriptions without abbreviation")
+    details: bool = typer.Option(False, "--details", "-d", help="Show full descriptions without abbreviation"),
+    min_confidence: str = typer.Option(None, "--min-confidence", help="Minimum confidence: high, medium, low or 0-1")
 ):
     """List all hypotheses for a project with confidence ratings."""
     from commands.project import hypotheses
-    _invoke_click(hypotheses, {'name': name, 'details': details})
+    _invoke_click(hypotheses, {'name': name, 'details': details, 'min_confidence': min_confidence})
 
 @project_app.command("ls-hypotheses")
 def project_ls_hypotheses(
     name: str = typer.Argument(..., help="Project name"),
-    details: bool = typer.Option(False, "--details", "-d", help="Show full descriptions without abbreviation")
+    details: bool = typer.Option(False, "--details", "-d", help="Show full descriptions without abbreviation"),
+    min_confidence: str = typer.Option(None, "--min-confidence", help="Minimum confidence: high, medium, low or 0-1")
 ):
     """Alias for 'project hypotheses' (lists hypotheses)."""
     from commands.project import hypotheses
-    _invoke_click(hypotheses, {'name': name, 'details': details})
+    _invoke_click(hypotheses, {'name': name, 'details': details, 'min_confidence': min_confidence})
 
 # Removed deprecated 'runs' subcommand. Use 'project sessions' instead.
 
@@ -285,10 +293,10 @@ def project_reset_hypotheses(
 def project_set_hypothesis_status(
     project_name: str = typer.Argument(..., help="Project name"),
     hypothesis_id: str = typer.Argument(..., help="Hypothesis ID (can be partial)"),
-    status: str = typer.Argument(..., help="New status: proposed, confirmed, or rejected"),
+    status: str = typer.Argument(..., help="New status: proposed, confirmed, rejected, or fixed"),
     force: bool = typer.Option(False, "--force", "-f", help="Force status change without confirmation")
 ):
-    """Set the status of a hypothesis to proposed, confirmed, or rejected."""
+    """Set the status of a hypothesis to proposed, confirmed, rejected, or fixed."""
     from commands.project import set_hypothesis_status
     _invoke_click(set_hypothesis_status, {
         'project_name': project_name,
@@ -1124,7 +1132,10 @@ def report(
     title: str | None = typer.Option(None, "--title", "-t", help="Custom report title"),
     auditors: str = typer.Option("Security Team", "--auditors", "-a", help="Comma-separated auditor names"),
     debug: bool = typer.Option(False, "--debug", help="Enable debug mode"),
-    all: bool = typer.Option(False, "--all", help="Include ALL hypotheses (not just confirmed) - WARNING: No QA performed, may contain false positives")
+    all: bool = typer.Option(False, "--all", help="Include ALL hypotheses (not just confirmed) - WARNING: No QA performed, may contain false positives"),
+    tag: list[str] = typer.Option(None, "--tag", help="Only report findings with this taxonomy tag (repeatable)"),
+    lang: str | None = typer.Option(None, "--lang", help="Report language (en, zh, es, ja; default en)"),
+    min_confidence: str | None = typer.Option(None, "--min-confidence", help="Minimum confidence: high, medium, low or 0-1")
 ):
     """Generate a professional security audit report."""
     import click
@@ -1143,7 +1154,10 @@ def report(
         'auditors': auditors,
         'debug': debug,
         'show_prompt': False,  # Add missing parameter
-        'include_all': all  # Pass the --all flag as include_all
+        'include_all': all,  # Pass the --all flag as include_all
+        'tags': tuple(tag) if tag else (),
+        'lang': lang,
+        'min_confidence': min_confidence
     }
     
     try:
@@ -1162,6 +1176,8 @@ def static_analysis(
     min_severity: str = typer.Option("low", "--min-severity", "-s", help="Minimum severity: high, medium, low, info"),
     no_dedup: bool = typer.Option(False, "--no-dedup", help="Disable deduplication across tools"),
     debug: bool = typer.Option(False, "--debug", help="Show debug information"),
+    min_confidence: str = typer.Option(None, "--min-confidence", help="Drop hits below: high, medium, low or 0-1"),
+    show_suppressed: bool = typer.Option(False, "--show-suppressed", help="Also show suppressed and baselined hits"),
 ):
     """Run static analysis (Slither/Aderyn) on a project."""
     import click
@@ -1179,6 +1195,8 @@ def static_analysis(
         'min_severity': min_severity,
         'no_dedup': no_dedup,
         'debug': debug,
+        'min_confidence': min_confidence,
+        'show_suppressed': show_suppressed,
     }
 
     try:
@@ -1193,6 +1211,7 @@ def static_analysis(
 def poc_make_prompt(
     project: str = typer.Argument(..., help="Project name"),
     hypothesis: str | None = typer.Option(None, "--hypothesis", "-h", help="Specific hypothesis ID to generate PoC for"),
+    scenario: str | None = typer.Option(None, "--scenario", help="Multi-step scenario file to compile into a harness"),
     debug: bool = typer.Option(False, "--debug", help="Enable debug mode")
 ):
     """Generate proof-of-concept prompts for confirmed vulnerabilities."""
@@ -1205,7 +1224,7 @@ def poc_make_prompt(
     config = load_config()
     
     # Run make-prompt command
-    make_prompt(project, hypothesis, config)
+    make_prompt(project, hypothesis, config, scenario)
 
 @poc_app.command("import")
 def poc_import(
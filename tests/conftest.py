@@ -4,12 +4,17 @@ Some environments have a global `llm` package installed that can shadow our
 local `hound/llm` package. To ensure tests import the in-repo implementation,
 we explicitly alias `llm` and its submodules to `hound.llm.*` in sys.modules
 before test collection.
+
+Also provides `anchor_workspace`, which writes Anchor workspaces of inline
+programs for the detector and scan tests.
 """
 
 import importlib
 import sys
 from pathlib import Path
 
+import pytest
+
 # Ensure current directory (package root) is at the front of sys.path
 ROOT = Path(__file__).resolve().parents[1]
 root_str = str(ROOT)
@@ -33,3 +38,40 @@ try:
 except Exception:
     # Fall back silently; tests will raise clearer errors later
     pass
+
+
+class AnchorWorkspace:
+    """Writes Anchor workspaces of inline programs (see the `anchor_workspace` fixture)."""
+
+    # Program id of the inline programs' `declare_id!`
+    PROGRAM_ID = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+
+    def __call__(self, root: Path, programs: dict[str, str], localnet: list[str] | dict[str, str] | None = None,
+                 manifest: str = "") -> Path:
+        """Write each program (crate name -> lib.rs source) under ``root``/programs and return ``root``.
+
+        ``localnet`` lists the programs Anchor.toml gives an id (PROGRAM_ID unless a dict maps
+        them to others); no Anchor.toml is written without it. ``manifest`` is appended to every
+        crate's Cargo.toml.
+        """
+        for name, source in programs.items():
+            crate = root / "programs" / name
+            (crate / "src").mkdir(parents=True, exist_ok=True)
+            (crate / "Cargo.toml").write_text(f'[package]\nname = "{name}"\nversion = "0.1.0"\n{manifest}')
+            (crate / "src" / "lib.rs").write_text(source)
+        if localnet:
+            ids = localnet if isinstance(localnet, dict) else dict.fromkeys(localnet, self.PROGRAM_ID)
+            (root / "Anchor.toml").write_text(
+                "[programs.localnet]\n" + "".join(f'{name} = "{program_id}"\n' for name, program_id in ids.items()))
+        return root
+
+    @staticmethod
+    def line(source: str, text: str) -> int:
+        """1-based line of the first ``text`` in ``source``."""
+        return source[:source.index(text)].count("\n") + 1
+
+
+@pytest.fixture
+def anchor_workspace() -> AnchorWorkspace:
+    """Writes Anchor workspaces: ``anchor_workspace(tmp_path, {"vault": SOURCE}, localnet=["vault"])``."""
+    return AnchorWorkspace()
@@ -0,0 +1,45 @@
+use cosmwasm_std::{Response, StdError, StdResult};
+use cw_storage_plus::Item;
+use sylvia::contract;
+#[cfg(not(feature = "library"))]
+use sylvia::entry_points;
+use sylvia::types::{ExecCtx, InstantiateCtx, QueryCtx};
+
+pub struct CounterContract {
+    pub(crate) count: Item<u32>,
+}
+
+#[cfg_attr(not(feature = "library"), entry_points)]
+#[contract]
+impl CounterContract {
+    pub const fn new() -> Self {
+        Self { count: Item::new("count") }
+    }
+
+    #[sv::msg(instantiate)]
+    pub fn instantiate(&self, ctx: InstantiateCtx, count: u32) -> StdResult<Response> {
+        self.count.save(ctx.deps.storage, &count)?;
+        Ok(Response::new())
+    }
+
+    #[sv::msg(exec)]
+    pub fn increment(&self, ctx: ExecCtx) -> StdResult<Response> {
+        self.count.update(ctx.deps.storage, |c| -> StdResult<u32> { Ok(c + 1) })?;
+        Ok(Response::new())
+    }
+
+    #[sv::msg(exec)]
+    pub fn reset(&self, ctx: ExecCtx, count: u32) -> StdResult<Response> {
+        self.count.save(ctx.deps.storage, &count)?;
+        Ok(Response::new())
+    }
+
+    #[sv::msg(query)]
+    pub fn count(&self, ctx: QueryCtx) -> StdResult<u32> {
+        self.count.load(ctx.deps.storage)
+    }
+
+    fn helper(&self) -> StdResult<()> {
+        Err(StdError::generic_err("unused"))
+    }
+}
@@ -0,0 +1,58 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use vault_types::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+
+use crate::error::ContractError;
+use crate::execute;
+use crate::state::{Config, CONFIG};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let owner = deps.api.addr_validate(&msg.owner)?;
+    CONFIG.save(deps.storage, &Config { owner, denom: msg.denom, hook: None })?;
+    Ok(Response::new().add_attribute("creator", info.sender))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Deposit {} => execute::deposit(deps, info),
+        ExecuteMsg::Withdraw { amount } => execute::withdraw(deps, env, info, amount),
+        ExecuteMsg::UpdateConfig { owner, hook } => execute::update_config(deps, info, owner, hook),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query::config(deps)?),
+        QueryMsg::Balance { address } => to_json_binary(&query::balance(deps, address)?),
+    }
+}
+
+mod query {
+    use super::*;
+    use cosmwasm_std::Uint128;
+    use vault_types::msg::ConfigResponse;
+
+    pub fn config(deps: Deps) -> StdResult<ConfigResponse> {
+        let config = CONFIG.load(deps.storage)?;
+        Ok(ConfigResponse { owner: config.owner, denom: config.denom })
+    }
+
+    pub fn balance(deps: Deps, address: String) -> StdResult<Uint128> {
+        let addr = deps.api.addr_validate(&address)?;
+        Ok(crate::state::BALANCES.may_load(deps.storage, &addr)?.unwrap_or_default())
+    }
+}
@@ -0,0 +1,48 @@
+use cosmwasm_std::{coins, to_json_binary, BankMsg, DepsMut, Env, MessageInfo, Response, Uint128, WasmMsg};
+use cw_utils::must_pay;
+
+use crate::error::ContractError;
+use crate::state::{BALANCES, CONFIG};
+
+pub fn deposit(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let amount = must_pay(&info, &config.denom).map_err(|_| ContractError::Unauthorized {})?;
+    BALANCES.update(deps.storage, &info.sender, |b| -> StdResult<_> { Ok(b.unwrap_or_default() + amount) })?;
+    Ok(Response::new())
+}
+
+pub fn withdraw(deps: DepsMut, _env: Env, info: MessageInfo, amount: Uint128) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    // Balance is never decremented before the transfer
+    let msg = BankMsg::Send {
+        to_address: info.sender.to_string(),
+        amount: coins(amount.u128(), config.denom),
+    };
+    let mut response = Response::new().add_message(msg);
+    if let Some(hook) = config.hook {
+        response = response.add_message(WasmMsg::Execute {
+            contract_addr: hook.to_string(),
+            msg: to_json_binary(&amount)?,
+            funds: vec![],
+        });
+    }
+    Ok(response)
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<String>,
+    hook: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(&owner)?;
+    }
+    config.hook = hook.map(|h| deps.api.addr_validate(&h)).transpose()?;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new())
+}
@@ -0,0 +1,4 @@
+pub mod contract;
+pub mod error;
+mod execute;
+pub mod state;
@@ -0,0 +1,11 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
+
+pub struct Config {
+    pub owner: Addr,
+    pub denom: String,
+    pub hook: Option<Addr>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const BALANCES: Map<&Addr, Uint128> = Map::new("balances");
@@ -0,0 +1,33 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Uint128};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub owner: String,
+    pub denom: String,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Deposit native funds sent with the message
+    Deposit {},
+    /// Withdraw `amount` back to the sender
+    Withdraw { amount: Uint128 },
+    /// Owner-only configuration update
+    UpdateConfig { owner: Option<String>, hook: Option<String> },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    #[returns(Uint128)]
+    Balance { address: String },
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub denom: String,
+}
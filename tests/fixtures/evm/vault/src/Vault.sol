@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {Ownable} from "./Ownable.sol";
+
+interface IOracle {
+    function price() external view returns (uint256);
+}
+
+contract Vault is Ownable {
+    uint256 public constant FEE_BPS = 30;
+    mapping(address => uint256) public balances;
+    IOracle public oracle;
+
+    constructor(IOracle oracle_) {
+        oracle = oracle_;
+    }
+
+    function deposit() external payable {
+        balances[msg.sender] += msg.value;
+    }
+
+    function withdraw(uint256 amount) external {
+        (bool ok, ) = msg.sender.call{value: amount}("");
+        require(ok, "transfer failed");
+        balances[msg.sender] -= amount;
+    }
+
+    function quote(uint256 amount) external view returns (uint256) {
+        return amount * oracle.price();
+    }
+
+    function transferOwnership(address newOwner) public override onlyOwner {
+        require(newOwner != address(0), "zero");
+        owner = newOwner;
+    }
+
+    function _fee(uint256 amount) internal pure returns (uint256) {
+        return amount * FEE_BPS / 10_000;
+    }
+
+    receive() external payable {}
+}
@@ -0,0 +1,58 @@
+# @version ^0.3.10
+
+interface IOracle:
+    def price() -> uint256: view
+
+owner: public(address)
+oracle: public(IOracle)
+balances: public(HashMap[address, uint256])
+FEE_BPS: constant(uint256) = 30
+
+
+@external
+def __init__(oracle: IOracle):
+    self.owner = msg.sender
+    self.oracle = oracle
+
+
+@external
+@payable
+def deposit():
+    self.balances[msg.sender] += msg.value
+
+
+@external
+def withdraw(amount: uint256):
+    raw_call(msg.sender, b"", value=amount)
+    self.balances[msg.sender] -= amount
+
+
+@external
+@nonreentrant("lock")
+def withdraw_safe(amount: uint256):
+    self.balances[msg.sender] -= amount
+    send(msg.sender, amount)
+
+
+@external
+@view
+def quote(amount: uint256) -> uint256:
+    return amount * self.oracle.price()
+
+
+@external
+def set_owner(new_owner: address):
+    assert msg.sender == self.owner, "not owner"
+    self.owner = new_owner
+
+
+@internal
+@pure
+def _fee(amount: uint256) -> uint256:
+    return amount * FEE_BPS / 10000
+
+
+@external
+@payable
+def __default__():
+    pass
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Feed111111111111111111111111111111111111111");
+
+#[program]
+pub mod oracle_feed {
+    use super::*;
+
+    pub fn publish(ctx: Context<Publish>, price: u64) -> Result<()> {
+        ctx.accounts.feed.price = price;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Publish<'info> {
+    #[account(mut, has_one = publisher)]
+    pub feed: Account<'info, Feed>,
+    pub publisher: Signer<'info>,
+}
+
+#[account]
+pub struct Feed {
+    pub publisher: Pubkey,
+    pub price: u64,
+}
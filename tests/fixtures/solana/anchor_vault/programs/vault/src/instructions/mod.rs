@@ -0,0 +1,3 @@
+pub mod withdraw;
+
+pub use withdraw::*;
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::Vault;
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: authority is compared against nothing (intentionally vulnerable)
+    pub authority: AccountInfo<'info>,
+    /// CHECK: recipient of the withdrawn lamports
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+    /// CHECK: program used for the payout CPI
+    pub payout_program: AccountInfo<'info>,
+}
+
+pub fn handler(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.balance -= amount;
+    let ix = Instruction {
+        program_id: ctx.accounts.payout_program.key(),
+        accounts: vec![],
+        data: vec![],
+    };
+    invoke(&ix, &[ctx.accounts.recipient.to_account_info()])?;
+    Ok(())
+}
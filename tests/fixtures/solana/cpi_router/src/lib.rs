@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+declare_id!("Rout111111111111111111111111111111111111111");
+
+pub const PLUGIN_ID: Pubkey = pubkey!("P1ug111111111111111111111111111111111111111");
+
+#[program]
+pub mod cpi_router {
+    use super::*;
+
+    pub fn route_swap(ctx: Context<RouteSwap>, amount: u64) -> Result<()> {
+        let bump = ctx.bumps.vault_authority;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"authority", &[bump]]];
+        // The swap program is whatever the caller passes in
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.swap_program.to_account_info(), cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, amount)
+    }
+
+    pub fn safe_transfer(ctx: Context<SafeTransfer>, amount: u64) -> Result<()> {
+        let bump = ctx.bumps.vault_authority;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"authority", &[bump]]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )
+    }
+
+    pub fn forward(ctx: Context<Forward>, data: Vec<u8>) -> Result<()> {
+        let bump = ctx.bumps.vault_authority;
+        let metas: Vec<AccountMeta> = ctx
+            .remaining_accounts
+            .iter()
+            .map(|a| AccountMeta::new(a.key(), a.is_signer))
+            .collect();
+        let ix = Instruction {
+            program_id: ctx.accounts.token_program.key(),
+            accounts: metas,
+            data: data.clone(),
+        };
+        invoke_signed(&ix, ctx.remaining_accounts, &[&[b"authority", &[bump]]])?;
+        Ok(())
+    }
+
+    pub fn call_plugin(ctx: Context<CallPlugin>) -> Result<()> {
+        require_keys_eq!(ctx.accounts.plugin_program.key(), PLUGIN_ID);
+        let bump = ctx.bumps.vault_authority;
+        let ix = Instruction {
+            program_id: ctx.accounts.plugin_program.key(),
+            accounts: vec![AccountMeta::new_readonly(ctx.accounts.vault_authority.key(), true)],
+            data: vec![0],
+        };
+        invoke_signed(
+            &ix,
+            &[ctx.accounts.vault_authority.to_account_info()],
+            &[&[b"authority", &[bump]]],
+        )?;
+        Ok(())
+    }
+
+    pub fn notify(ctx: Context<Notify>) -> Result<()> {
+        let ix = Instruction {
+            program_id: ctx.accounts.hook_program.key(),
+            accounts: vec![AccountMeta::new(ctx.accounts.user.key(), true)],
+            data: vec![1],
+        };
+        invoke(&ix, &[ctx.accounts.user.to_account_info()])?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RouteSwap<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+    /// CHECK: PDA signer for the vault
+    #[account(seeds = [b"authority"], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+    /// CHECK: any swap program
+    pub swap_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SafeTransfer<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+    /// CHECK: PDA signer for the vault
+    #[account(seeds = [b"authority"], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Forward<'info> {
+    /// CHECK: PDA signer for the vault
+    #[account(seeds = [b"authority"], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CallPlugin<'info> {
+    /// CHECK: PDA signer for the vault
+    #[account(seeds = [b"authority"], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+    /// CHECK: compared against PLUGIN_ID in the handler
+    pub plugin_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Notify<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: caller-chosen hook
+    pub hook_program: UncheckedAccount<'info>,
+}
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Guest11111111111111111111111111111111111111");
+
+pub const MAX_SPONSORS: usize = 16;
+
+#[program]
+pub mod guestbook {
+    use super::*;
+
+    pub fn init_board(ctx: Context<InitBoard>) -> Result<()> {
+        ctx.accounts.board.admin = ctx.accounts.admin.key();
+        Ok(())
+    }
+
+    /// Anyone can sign the shared board
+    pub fn sign_board(ctx: Context<SignBoard>, message: [u8; 32]) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+        board.entries.push(Entry { author: ctx.accounts.author.key(), message });
+        Ok(())
+    }
+
+    /// Registry grows by one name per call, paid by the caller
+    pub fn register(ctx: Context<Register>, name: [u8; 32]) -> Result<()> {
+        ctx.accounts.registry.names.push(name);
+        Ok(())
+    }
+
+    /// Capped list: still fillable by anyone
+    pub fn sponsor(ctx: Context<Sponsor>) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+        require!(board.sponsors.len() < MAX_SPONSORS, GuestbookError::Full);
+        board.sponsors.push(ctx.accounts.sponsor.key());
+        Ok(())
+    }
+
+    /// Per-user profile: the attacker only bloats their own account
+    pub fn set_bio(ctx: Context<SetBio>, bio: String) -> Result<()> {
+        ctx.accounts.profile.bio.push_str(&bio);
+        Ok(())
+    }
+
+    /// Only the admin may add moderators
+    pub fn add_moderator(ctx: Context<AddModerator>, moderator: Pubkey) -> Result<()> {
+        ctx.accounts.board.moderators.push(moderator);
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Board {
+    pub admin: Pubkey,
+    #[max_len(100)]
+    pub entries: Vec<Entry>,
+    #[max_len(16)]
+    pub sponsors: Vec<Pubkey>,
+    #[max_len(8)]
+    pub moderators: Vec<Pubkey>,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct Entry {
+    pub author: Pubkey,
+    pub message: [u8; 32],
+}
+
+#[account]
+pub struct Registry {
+    pub count: u64,
+    pub names: Vec<[u8; 32]>,
+}
+
+#[account]
+pub struct Profile {
+    pub owner: Pubkey,
+    pub bio: String,
+}
+
+#[derive(Accounts)]
+pub struct SignBoard<'info> {
+    #[account(mut, seeds = [b"board"], bump)]
+    pub board: Account<'info, Board>,
+    pub author: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Register<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry"],
+        bump,
+        realloc = 8 + 8 + 4 + (registry.names.len() + 1) * 32,
+        realloc::payer = payer,
+        realloc::zero = false,
+    )]
+    pub registry: Box<Account<'info, Registry>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Sponsor<'info> {
+    #[account(mut, seeds = [b"board"], bump)]
+    pub board: Account<'info, Board>,
+    pub sponsor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetBio<'info> {
+    #[account(mut, seeds = [b"profile", user.key().as_ref()], bump, realloc = 8 + 32 + 4 + 512, realloc::payer = user, realloc::zero = false)]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddModerator<'info> {
+    #[account(mut, has_one = admin)]
+    pub board: Account<'info, Board>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitBoard<'info> {
+    #[account(init, payer = admin, space = 8 + Board::INIT_SPACE, seeds = [b"board"], bump)]
+    pub board: Account<'info, Board>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[error_code]
+pub enum GuestbookError {
+    Full,
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use borsh::BorshDeserialize;
+
+pub mod state;
+use state::*;
+
+declare_id!("Book111111111111111111111111111111111111111");
+
+#[program]
+pub mod order_book {
+    use super::*;
+
+    pub fn place(ctx: Context<Place>, count: u32) -> Result<()> {
+        // Caller picks the buffer size
+        let mut scratch = vec![0u64; count as usize];
+        scratch[0] = ctx.accounts.market.seq;
+        Ok(())
+    }
+
+    pub fn settle(ctx: Context<Settle>, batch: u16) -> Result<()> {
+        require!(batch <= 32, ErrorCode::BatchTooLarge);
+        let mut fills = Vec::with_capacity(batch as usize);
+        fills.push(ctx.accounts.market.key());
+        Ok(())
+    }
+
+    pub fn replay(ctx: Context<Settle>) -> Result<()> {
+        let data = ctx.accounts.history.try_borrow_data()?;
+        let history = History::try_from_slice(&data[8..])?;
+        msg!("{}", history.fills.len());
+        Ok(())
+    }
+}
+
+pub fn snapshot(market: &Market) -> u64 {
+    let mut slab: [u8; 5000] = [0u8; 5000];
+    slab[0] = market.seq as u8;
+    let table = [0u64; 16];
+    slab[1] = table[0] as u8;
+    slab.len() as u64
+}
+
+pub fn prefill() -> usize {
+    let buf = vec![0u8; SLAB_LEN * 16];
+    buf.len()
+}
+
+#[derive(Accounts)]
+pub struct Place<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(mut)]
+    pub mirror: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Settle<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+    /// CHECK: history is parsed manually
+    pub history: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    BatchTooLarge,
+}
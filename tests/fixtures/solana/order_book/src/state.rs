@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_ORDERS: usize = 64;
+pub const SLAB_LEN: usize = MAX_ORDERS * 40;
+
+#[derive(Clone, Copy, AnchorSerialize, AnchorDeserialize)]
+pub struct Order {
+    pub owner: Pubkey,
+    pub price: u64,
+}
+
+#[account]
+pub struct Market {
+    pub authority: Pubkey,
+    pub bids: [Order; MAX_ORDERS],
+    pub asks: [Order; MAX_ORDERS],
+    pub seq: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct History {
+    pub market: Pubkey,
+    pub fills: Vec<Order>,
+    pub memo: String,
+}
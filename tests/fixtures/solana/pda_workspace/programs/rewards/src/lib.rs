@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Rewd111111111111111111111111111111111111111");
+
+#[program]
+pub mod rewards {
+    use super::*;
+
+    pub fn init_profile(ctx: Context<InitProfile>) -> Result<()> {
+        ctx.accounts.profile.owner = ctx.accounts.owner.key();
+        Ok(())
+    }
+
+    pub fn init_config(ctx: Context<InitConfig>) -> Result<()> {
+        ctx.accounts.config.is_admin = false;
+        Ok(())
+    }
+
+    pub fn create_pool(ctx: Context<CreatePool>, name: String) -> Result<()> {
+        ctx.accounts.pool.name = name;
+        Ok(())
+    }
+
+    pub fn stake(ctx: Context<Stake>) -> Result<()> {
+        ctx.accounts.stake.amount += 1;
+        Ok(())
+    }
+
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        let (expected_profile, _bump) =
+            Pubkey::find_program_address(&[b"user", ctx.accounts.owner.key().as_ref()], ctx.program_id);
+        require_keys_eq!(expected_profile, ctx.accounts.profile.key());
+        Ok(())
+    }
+}
+
+#[account]
+pub struct UserProfile {
+    pub owner: Pubkey,
+    pub balance: u64,
+}
+
+#[account]
+pub struct UserConfig {
+    pub owner: Pubkey,
+    pub is_admin: bool,
+}
+
+#[account]
+pub struct Pool {
+    pub mint: Pubkey,
+    pub name: String,
+}
+
+#[account]
+pub struct StakeAccount {
+    pub amount: u64,
+}
+
+#[account]
+pub struct StakeReceipt {
+    pub stake: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitProfile<'info> {
+    #[account(init, payer = owner, space = 8 + 40, seeds = [b"user", owner.key().as_ref()], bump)]
+    pub profile: Account<'info, UserProfile>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+    #[account(init, payer = owner, space = 8 + 33, seeds = [b"user", owner.key().as_ref()], bump)]
+    pub config: Account<'info, UserConfig>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct CreatePool<'info> {
+    #[account(init, payer = payer, space = 8 + 32 + 36, seeds = [b"pool", name.as_bytes()], bump)]
+    pub pool: Account<'info, Pool>,
+    /// CHECK: token vault, validated by seeds only
+    #[account(seeds = [b"poolvault", mint.key().as_ref()], bump)]
+    pub pool_vault: UncheckedAccount<'info>,
+    /// CHECK: any mint
+    pub mint: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut, seeds = [b"stake", owner.key().as_ref()], bump)]
+    pub stake: Account<'info, StakeAccount>,
+    #[account(seeds = [b"stake", owner.key().as_ref(), b"receipt"], bump)]
+    pub receipt: Account<'info, StakeReceipt>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    pub profile: Account<'info, UserProfile>,
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,6 @@
+use anchor_lang::prelude::*;
+
+#[constant]
+pub const POSITION_SEED: &[u8] = b"user";
+
+pub const METADATA_SEED: &str = "metadata";
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+pub mod constants;
+use constants::*;
+
+declare_id!("Stak111111111111111111111111111111111111111");
+
+#[program]
+pub mod staking {
+    use super::*;
+
+    pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+        ctx.accounts.position.owner = ctx.accounts.owner.key();
+        Ok(())
+    }
+
+    pub fn describe_position(ctx: Context<DescribePosition>, uri: String) -> Result<()> {
+        ctx.accounts.metadata.uri = uri;
+        Ok(())
+    }
+
+    pub fn boost(ctx: Context<Boost>) -> Result<()> {
+        let data = ctx.accounts.reward_profile.try_borrow_data()?;
+        msg!("{}", data.len());
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Position {
+    pub owner: Pubkey,
+}
+
+#[account]
+pub struct PositionMetadata {
+    pub uri: String,
+}
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(init, payer = owner, space = 8 + 32, seeds = [POSITION_SEED, owner.key().as_ref()], bump)]
+    pub position: Account<'info, Position>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DescribePosition<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 4 + 200,
+        seeds = [METADATA_SEED.as_bytes(), position.key().as_ref()],
+        bump
+    )]
+    pub metadata: Account<'info, PositionMetadata>,
+    #[account(has_one = owner)]
+    pub position: Account<'info, Position>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Boost<'info> {
+    /// CHECK: the owner's rewards profile
+    #[account(seeds = [b"user", owner.key().as_ref()], bump, seeds::program = rewards_program.key())]
+    pub reward_profile: UncheckedAccount<'info>,
+    pub owner: Signer<'info>,
+    pub rewards_program: Program<'info, Rewards>,
+}
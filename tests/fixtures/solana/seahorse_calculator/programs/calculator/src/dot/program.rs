@@ -0,0 +1,39 @@
+#![allow(unused_imports)]
+use crate::{id, seahorse_util::*};
+use anchor_lang::{prelude::*, solana_program};
+use std::{cell::RefCell, rc::Rc};
+
+#[account]
+#[derive(Debug)]
+pub struct Calculator {
+    pub owner: Pubkey,
+    pub display: i64,
+}
+
+pub fn init_calculator_handler<'info>(
+    mut owner: SeahorseSigner<'info, '_>,
+    mut calculator: Empty<Mutable<LoadedCalculator<'info, '_>>>,
+) -> () {
+    let mut calculator = calculator.account.clone();
+
+    assign!(calculator.borrow_mut().owner, owner.key());
+}
+
+pub fn do_operation_handler<'info>(
+    mut owner: SeahorseSigner<'info, '_>,
+    mut calculator: Mutable<LoadedCalculator<'info, '_>>,
+    mut op: Operation,
+    mut num: i64,
+) -> () {
+    if !(owner.key() == calculator.borrow().owner) {
+        panic!("This is not your calculator!");
+    }
+
+    if op == Operation::ADD {
+        assign!(calculator.borrow_mut().display, calculator.borrow().display + num);
+    } else {
+        if op == Operation::SUB {
+            assign!(calculator.borrow_mut().display, calculator.borrow().display - num);
+        }
+    }
+}
@@ -0,0 +1,44 @@
+#![allow(unused_imports)]
+pub mod dot;
+
+use anchor_lang::prelude::*;
+use dot::program::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+mod calculator {
+    use super::*;
+
+    #[derive(Accounts)]
+    pub struct InitCalculator<'info> {
+        #[account(mut)]
+        pub owner: Signer<'info>,
+        #[account(init, space = 8 + 40, payer = owner, seeds = ["Calculator".as_bytes().as_ref(), owner.key().as_ref()], bump)]
+        pub calculator: Box<Account<'info, dot::program::Calculator>>,
+        pub rent: Sysvar<'info, Rent>,
+        pub system_program: Program<'info, System>,
+    }
+
+    pub fn init_calculator(ctx: Context<InitCalculator>) -> Result<()> {
+        let owner = SeahorseSigner { account: &ctx.accounts.owner, programs: &HashMap::new() };
+        let calculator = Empty { account: dot::program::Calculator::load(&mut ctx.accounts.calculator, &HashMap::new()), bump: ctx.bumps.get("calculator").map(|bump| *bump) };
+        init_calculator_handler(owner.clone(), calculator.clone());
+        Ok(())
+    }
+
+    #[derive(Accounts)]
+    pub struct DoOperation<'info> {
+        #[account(mut)]
+        pub owner: Signer<'info>,
+        #[account(mut)]
+        pub calculator: Box<Account<'info, dot::program::Calculator>>,
+    }
+
+    pub fn do_operation(ctx: Context<DoOperation>, op: Operation, num: i64) -> Result<()> {
+        let owner = SeahorseSigner { account: &ctx.accounts.owner, programs: &HashMap::new() };
+        let calculator = dot::program::Calculator::load(&mut ctx.accounts.calculator, &HashMap::new());
+        do_operation_handler(owner.clone(), calculator.clone(), op, num);
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+# calculator
+# Built with Seahorse v0.2.7
+
+from seahorse.prelude import *
+
+declare_id('Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS')
+
+
+class Calculator(Account):
+    owner: Pubkey
+    display: i64
+
+
+class Operation(Enum):
+    ADD = 0
+    SUB = 1
+
+
+@instruction
+def init_calculator(owner: Signer, calculator: Empty[Calculator]):
+    calculator = calculator.init(payer=owner, seeds=['Calculator', owner])
+    calculator.owner = owner.key()
+
+
+@instruction
+def do_operation(
+    owner: Signer,
+    calculator: Calculator,
+    op: Operation,
+    num: i64,
+):
+    assert owner.key() == calculator.owner, 'This is not your calculator!'
+
+    if op == Operation.ADD:
+        calculator.display += num
+    elif op == Operation.SUB:
+        calculator.display -= num
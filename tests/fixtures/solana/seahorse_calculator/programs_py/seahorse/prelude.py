@@ -0,0 +1 @@
+# Seahorse prelude (stub)
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Keep111111111111111111111111111111111111111");
+
+pub const TREASURY_SIGNER: Pubkey = pubkey!("TSig111111111111111111111111111111111111111");
+
+#[program]
+pub mod keeper {
+    use super::*;
+
+    pub fn release(ctx: Context<Release>) -> Result<()> {
+        let amount = ctx.accounts.pot.lamports();
+        ctx.accounts.pot.sub_lamports(amount)?;
+        ctx.accounts.recipient.add_lamports(amount)?;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Pot {
+    pub total: u64,
+}
+
+#[derive(Accounts)]
+pub struct Release<'info> {
+    #[account(mut)]
+    pub pot: Account<'info, Pot>,
+    /// CHECK: paid out to
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+    #[account(address = TREASURY_SIGNER)]
+    pub treasury_signer: Signer<'info>,
+}
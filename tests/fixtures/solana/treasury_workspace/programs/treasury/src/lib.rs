@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use keeper::program::Keeper;
+
+declare_id!("Tres111111111111111111111111111111111111111");
+
+#[program]
+pub mod treasury {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        ctx.accounts.treasury.authority = ctx.accounts.admin.key();
+        Ok(())
+    }
+
+    pub fn set_authority(ctx: Context<SetAuthority>, new_authority: Pubkey) -> Result<()> {
+        // Missing: has_one = authority
+        ctx.accounts.treasury.authority = new_authority;
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.recipient.try_borrow_mut_lamports()? += amount;
+        Ok(())
+    }
+
+    pub fn sweep(ctx: Context<Sweep>) -> Result<()> {
+        let excess = ctx.accounts.treasury.to_account_info().lamports() - ctx.accounts.treasury.reserve;
+        ctx.accounts.treasury.sub_lamports(excess)?;
+        ctx.accounts.recipient.add_lamports(excess)?;
+        Ok(())
+    }
+
+    pub fn crank(ctx: Context<Crank>) -> Result<()> {
+        let bump = ctx.bumps.treasury_signer;
+        let seeds: &[&[&[u8]]] = &[&[b"signer", &[bump]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.keeper_program.to_account_info(),
+            keeper::cpi::accounts::Release {
+                pot: ctx.accounts.pot.to_account_info(),
+                recipient: ctx.accounts.recipient.to_account_info(),
+                treasury_signer: ctx.accounts.treasury_signer.to_account_info(),
+            },
+            seeds,
+        );
+        keeper::cpi::release(cpi_ctx)
+    }
+
+    pub fn pay_out(ctx: Context<PayOut>, amount: u64) -> Result<()> {
+        let bump = ctx.bumps.treasury_signer;
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.treasury_signer.to_account_info(),
+                },
+                &[&[b"signer", &[bump]]],
+            ),
+            amount,
+        )
+    }
+}
+
+#[account]
+pub struct Treasury {
+    pub authority: Pubkey,
+    pub reserve: u64,
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init, payer = admin, space = 8 + 40)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetAuthority<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, has_one = authority)]
+    pub treasury: Account<'info, Treasury>,
+    pub authority: Signer<'info>,
+    /// CHECK: any recipient the authority picks
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Sweep<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    /// CHECK: lamports go here
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Crank<'info> {
+    /// CHECK: the keeper's pot
+    #[account(mut)]
+    pub pot: UncheckedAccount<'info>,
+    /// CHECK: whoever cranks
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+    /// CHECK: PDA that signs for the treasury
+    #[account(seeds = [b"signer"], bump)]
+    pub treasury_signer: UncheckedAccount<'info>,
+    pub keeper_program: Program<'info, Keeper>,
+}
+
+#[derive(Accounts)]
+pub struct PayOut<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+    /// CHECK: PDA that signs for the treasury
+    #[account(seeds = [b"signer"], bump)]
+    pub treasury_signer: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
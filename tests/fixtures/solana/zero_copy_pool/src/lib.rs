@@ -0,0 +1,54 @@
+//! Native pool program that parses its state without copying.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+mod state;
+
+use state::PoolHeader;
+
+entrypoint!(process_instruction);
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let pool = next_account_info(accounts_iter)?;
+    let data = pool.try_borrow_data()?;
+
+    // Attacker-chosen record length, straight from instruction data
+    let len = u32::from_le_bytes(instruction_data[1..5].try_into().unwrap()) as usize;
+    let records = unsafe { std::slice::from_raw_parts(data.as_ptr().add(64), len) };
+
+    // Offset checked against the buffer before use
+    let offset = instruction_data[5] as usize;
+    if offset + 8 > data.len() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let word = unsafe { *data.as_ptr().add(offset) };
+
+    let header = unsafe { &*(data.as_ptr() as *const PoolHeader) };
+    let _ = (program_id, records, word, header);
+    Ok(())
+}
+
+fn checksum(seed: u64) -> u64 {
+    // Plain arithmetic, not pointer math
+    seed.wrapping_add(7)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn reads_back() {
+        let v = vec![0u8; 8];
+        let _ = unsafe { std::slice::from_raw_parts(v.as_ptr(), v.len()) };
+    }
+}
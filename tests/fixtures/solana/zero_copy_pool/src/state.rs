@@ -0,0 +1,16 @@
+#[repr(C)]
+pub struct PoolHeader {
+    pub authority: [u8; 32],
+    pub total: u64,
+}
+
+unsafe impl bytemuck::Zeroable for PoolHeader {}
+
+pub unsafe fn header_from(bytes: &[u8]) -> PoolHeader {
+    std::mem::transmute::<[u8; 40], PoolHeader>(bytes[..40].try_into().unwrap())
+}
+
+pub fn constant_table() -> &'static [u8] {
+    static TABLE: [u8; 4] = [1, 2, 3, 4];
+    unsafe { std::slice::from_raw_parts(TABLE.as_ptr(), 4) }
+}
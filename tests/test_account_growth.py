@@ -0,0 +1,68 @@
+"""
+Tests for the built-in account growth / storage-exhaustion analyzer.
+"""
+
+from pathlib import Path
+
+from extensions.ir import load_programs
+from extensions.static.account_growth import AccountGrowthAnalyzer
+from extensions.static.pipeline import StaticAnalysisPipeline
+
+FIXTURE = Path(__file__).parent / "fixtures" / "solana" / "guestbook"
+
+
+class TestAccountGrowthAnalyzer:
+    """Test growth-site detection, filtering and cost estimates."""
+
+    def setup_method(self):
+        self.findings, self.metadata = AccountGrowthAnalyzer().run(FIXTURE)
+        self.by_ix = {f.instruction: f for f in self.findings}
+
+    def test_max_len_recorded_in_ir(self):
+        board = load_programs(FIXTURE)[0].account_type("Board")
+        assert board.properties["max_len"] == {"entries": ["100"], "sponsors": ["16"], "moderators": ["8"]}
+
+    def test_growth_sites(self):
+        sites = {s["instruction"]: s for s in self.metadata["growth_sites"]}
+        assert set(sites) == {"sign_board", "register", "sponsor", "set_bio", "add_moderator"}
+        assert sites["set_bio"]["per_user"]
+        assert sites["add_moderator"]["privileged"]
+        assert sites["sponsor"]["checked"]
+        # Per-user and admin-only growth is not reported
+        assert set(self.by_ix) == {"sign_board", "register", "sponsor"}
+
+    def test_fixed_space_exhaustion(self):
+        finding = self.by_ix["sign_board"]
+        assert finding.kind == "capacity-exhaustion"
+        assert finding.line == 19
+        assert finding.element_bytes == 64
+        # 8 + INIT_SPACE (7212) minus the empty account (52 bytes)
+        assert finding.capacity_bytes == 7168
+        assert finding.appends_to_exhaust == 112
+        assert finding.cost_lamports == 112 * 5000
+        hyp = finding.to_hypothesis()
+        assert hyp["severity"] == "medium"
+        assert hyp["vulnerability_type"] == "storage-capacity-exhaustion"
+        assert hyp["properties"]["category"] == "denial-of-service"
+        assert "0.0006 SOL" in hyp["description"]
+
+    def test_checked_cap_is_low(self):
+        finding = self.by_ix["sponsor"]
+        assert finding.checked
+        assert finding.capacity_bytes == 16 * 32
+        assert finding.severity == "low"
+
+    def test_unbounded_realloc(self):
+        finding = self.by_ix["register"]
+        assert finding.kind == "unbounded-realloc"
+        assert finding.realloc_payer == "payer"
+        assert finding.payer_is_signer
+        assert finding.capacity_bytes == 32 * 1024 - 20
+        # The attacker pays rent for every byte plus one fee per append
+        assert finding.cost_lamports == finding.capacity_bytes * 6960 + finding.appends_to_exhaust * 5000
+        assert finding.severity == "medium"
+
+    def test_registered_for_solana(self):
+        pipeline = StaticAnalysisPipeline(chain_id="solana")
+        assert "account-growth" in pipeline.runners
+        assert pipeline.check_tools()["account-growth"][0]
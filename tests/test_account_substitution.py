@@ -0,0 +1,164 @@
+"""
+Tests for account substitution fuzzing: which lookalikes are tried for a
+transaction, how the program's acceptance is reported, and the CLI.
+"""
+
+import hashlib
+import json
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+import pytest
+import yaml
+from click.testing import CliRunner
+
+from commands.simulate import substitute
+from extensions.ir.anchor_config import b58encode
+from extensions.monitor.decode import anchor_discriminator
+from extensions.simulation import BackendError, ExecutionBackend, TransactionSpec, TxResult, load_substitution
+
+VAULT = Path(__file__).parent / "fixtures" / "solana" / "anchor_vault"
+PROGRAM_ID = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+TOKEN = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+
+
+def _key(name: str) -> bytes:
+    return hashlib.sha256(name.encode()).digest()
+
+
+class VaultBackend(ExecutionBackend):
+    """The fixture vault's deposit: checks the vault's owner and address and the depositor's token account
+    owner, but neither token account's mint nor that the two token accounts differ."""
+
+    def start(self):
+        self.keys, self.accounts = {}, {}
+
+    def keypair(self, name, lamports=0):
+        self.keys[name] = b58encode(_key(name))
+        return self.keys[name]
+
+    def set_account(self, pubkey, data, owner, lamports=0, executable=False):
+        self.accounts[self.keys.get(pubkey, pubkey)] = {"lamports": lamports, "data": bytes(data),
+                                                        "owner": self.keys.get(owner, owner),
+                                                        "executable": executable}
+
+    def get_account(self, pubkey):
+        account = self.accounts.get(self.keys.get(pubkey, pubkey))
+        return dict(account) if account else None
+
+    def send(self, instructions, payer, signers=()):
+        keys = [self.keys.get(a.pubkey, a.pubkey) for a in instructions[0].accounts]
+        signed = {self.keys.get(s, s) for s in (payer, *signers)}
+        vault, user = keys[:2]
+        accounts = [self.accounts.get(k) for k in keys]
+        if accounts[0]["owner"] != PROGRAM_ID:
+            return TxResult(False, "AccountOwnedByWrongProgram")
+        if vault != self.keys["vault"]:
+            return TxResult(False, "ConstraintSeeds")
+        if user not in signed:
+            return TxResult(False, "missing required signature")
+        if any(a["owner"] != TOKEN for a in accounts[2:4]):
+            return TxResult(False, "AccountOwnedByWrongProgram")
+        if b58encode(accounts[2]["data"][32:64]) != user:
+            return TxResult(False, "ConstraintTokenOwner")
+        return TxResult(True, logs=["Program log: Instruction: Deposit"], compute_units=4200)
+
+
+def _token_account(owner: str) -> str:
+    return (_key("mint") + _key(owner) + (10 ** 9).to_bytes(8, "little") + b"\x00" * 93).hex()
+
+
+CONFIG = {
+    "program": str(VAULT),
+    "keypairs": {"user": 10 ** 10, "vault": 0, "mint": 0, "user_token": 0, "vault_token": 0},
+    "accounts": [
+        {"pubkey": "vault", "owner": PROGRAM_ID, "data": "00" * 41, "lamports": 10 ** 6},
+        {"pubkey": "mint", "owner": TOKEN, "data": "00" * 44 + "0601" + "00" * 36, "lamports": 10 ** 6},
+        {"pubkey": "user_token", "owner": TOKEN, "data": _token_account("user"), "lamports": 10 ** 6},
+        {"pubkey": "vault_token", "owner": TOKEN, "data": _token_account("vault"), "lamports": 10 ** 6},
+        {"pubkey": TOKEN, "owner": "BPFLoader2111111111111111111111111111111111", "executable": True},
+    ],
+    "transaction": {
+        "program_id": PROGRAM_ID,
+        "instruction": "deposit",
+        "args": "e803000000000000",
+        "accounts": [
+            {"pubkey": "vault", "writable": True},
+            {"pubkey": "user", "signer": True},
+            {"pubkey": "user_token", "writable": True},
+            {"pubkey": "vault_token", "writable": True},
+            TOKEN,
+        ],
+    },
+}
+
+
+class TestSubstitution:
+    """Test mutant selection and findings against the fake vault."""
+
+    def test_transaction_spec(self):
+        tx = TransactionSpec.from_dict(CONFIG["transaction"], ["vault", "user"])
+        assert tx.data == anchor_discriminator("deposit") + bytes.fromhex("e803000000000000")
+        assert [a.name for a in tx.accounts] == ["vault", "user", "account_2", "account_3", "account_4"]
+        assert tx.payer == "user" and tx.signers == ["user"]
+        with pytest.raises(ValueError, match="payer"):
+            TransactionSpec.from_dict({"program_id": PROGRAM_ID, "accounts": ["vault"]})
+
+    def test_accepted_substitutions(self):
+        result = load_substitution(CONFIG, make_backend=VaultBackend).run()
+        tried = {s.label for s in result.substitutions}
+        assert tried == {
+            "wrong-owner:vault", "wrong-pda:vault", "wrong-signer:user",
+            "wrong-owner:user_token", "wrong-mint:user_token", "duplicate:user_token=vault_token",
+            "wrong-owner:vault_token", "wrong-mint:vault_token", "duplicate:vault_token=user_token",
+        }
+        assert {s.label for s in result.accepted()} == {
+            "wrong-mint:user_token", "wrong-mint:vault_token", "duplicate:vault_token=user_token",
+        }
+        rejected = {s.label: s.error for s in result.substitutions if not s.accepted}
+        assert rejected["wrong-pda:vault"] == "ConstraintSeeds"
+        assert rejected["wrong-signer:user"] == "ConstraintTokenOwner"
+
+        hyps = {h["title"]: h for h in (f.to_hypothesis() for f in result.findings())}
+        duplicate = hyps["deposit accepts `user_token` passed again as `vault_token`"]
+        assert duplicate["vulnerability_type"] == "duplicate-mutable-accounts" and duplicate["severity"] == "high"
+        mint = hyps["deposit accepts an account for an attacker mint as `user_token`"]
+        assert mint["properties"]["source_files"] == ["programs/vault/src/lib.rs"]
+        assert mint["properties"]["affected_lines"] == [54]
+        assert mint["properties"]["precision"] == "verified" and mint["properties"]["source_tool"] == "account-fuzz"
+
+    def test_skips_and_errors(self):
+        config = json.loads(json.dumps(CONFIG))
+        config["transaction"]["accounts"][1]["substitute"] = False
+        config["kinds"] = ["wrong-signer", "wrong-pda"]
+        result = load_substitution(config, make_backend=VaultBackend).run()
+        assert [s.label for s in result.substitutions] == ["wrong-pda:vault"]
+
+        config["transaction"]["accounts"][0]["pubkey"] = "user_token"
+        with pytest.raises(BackendError, match="legitimate transaction fails"):
+            load_substitution(config, make_backend=VaultBackend).run()
+        with pytest.raises(ValueError, match="unknown substitution kinds"):
+            load_substitution({**CONFIG, "kinds": ["wrong-bump"]}, make_backend=VaultBackend)
+
+    def test_cli(self, tmp_path):
+        path = tmp_path / "deposit.yaml"
+        path.write_text(yaml.safe_dump(CONFIG))
+        runner = CliRunner()
+        with patch("extensions.simulation.engine.backend_factory", return_value=VaultBackend):
+            result = runner.invoke(substitute, [str(path), "--json"])
+            assert result.exit_code == 0, result.output
+            data = json.loads(result.output)
+            assert data["summary"] == {"substitutions": 9, "accepted": 3}
+            assert data["manifest"]["command"]["name"] == "sim substitute"
+
+            with patch("commands.simulate.ProjectManager") as PM:
+                pm = MagicMock()
+                PM.return_value = pm
+                pm.get_project.return_value = {"path": str(tmp_path)}
+                result = runner.invoke(substitute, [str(path), "--kind", "wrong-mint", "--project", "p",
+                                                    "--import-hypotheses"])
+        assert result.exit_code == 0, result.output
+        assert "2 of 2 substitutions accepted" in result.output
+        assert (tmp_path / "simulations" / "deposit.json").exists()
+        store = json.loads((tmp_path / "hypotheses.json").read_text())["hypotheses"]
+        assert {h["vulnerability_type"] for h in store.values()} == {"missing-mint-check"}
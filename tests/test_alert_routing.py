@@ -0,0 +1,197 @@
+"""
+Tests for alert routing: channels, routes, deduplication windows, escalation and the test-alert command.
+"""
+
+import json
+
+import pytest
+import yaml
+from click.testing import CliRunner
+
+from commands.monitor import send_test_alert
+from extensions.monitor import Alert, AlertRouter, EmailChannel, Notification, make_channel
+
+
+def alert(rule_id="authority-withdraw-authority", severity="high", signature="sig1", instruction="withdraw",
+          kind="unexpected-authority", **details):
+    return Alert(rule_id=rule_id, kind=kind, severity=severity, title=f"{rule_id} fired", message="m",
+                 signature=signature, slot=1, program_id="Prog", instruction=instruction, finding="hyp_1",
+                 details=details)
+
+
+class Recorder:
+    """HTTP transport recording posts; answers with ``status``."""
+
+    def __init__(self, status=200):
+        self.status = status
+        self.posts = []
+
+    def __call__(self, url, body, headers, timeout):
+        self.posts.append((url, json.loads(body)))
+        return self.status, {}, b"ok"
+
+
+class FakeSMTP:
+    sent = []
+
+    def __init__(self, host, port, timeout=None):
+        self.host, self.port = host, port
+        self.calls = []
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, *exc):
+        return False
+
+    def starttls(self):
+        self.calls.append("starttls")
+
+    def login(self, user, password):
+        self.calls.append(("login", user, password))
+
+    def send_message(self, msg):
+        FakeSMTP.sent.append((self.calls, msg))
+
+
+ROUTING = """
+channels:
+  security: {type: slack, url: $SLACK_URL}
+  oncall: {type: pagerduty, routing_key: "${PD_KEY}"}
+  hook: {type: webhook, url: https://hooks.example.com/x}
+dedup_window: 600
+routes:
+  - {channels: [security], min_severity: low}
+  - {channels: [hook], rules: "authority-*", dedup_window: 60}
+escalation:
+  - {channels: [oncall], min_severity: critical}
+  - {channels: [oncall], min_severity: medium, repeats: 3, within: 300}
+"""
+
+
+def make_router(now, transport):
+    env = {"SLACK_URL": "https://hooks.slack.com/services/T/B/secret", "PD_KEY": "pd-key"}
+    router = AlertRouter.from_dict(yaml.safe_load(ROUTING), env=env, clock=lambda: now[0])
+    for channel in router.channels.values():
+        channel.transport = transport
+    return router
+
+
+class TestAlertRouting:
+    """Test channel payloads, route matching, suppression windows and escalation tiers."""
+
+    def test_channels(self):
+        transport = Recorder(202)
+        slack = make_channel("s", {"type": "slack", "url": "https://hooks.slack.com/services/T/B/secret"})
+        pager = make_channel("p", {"type": "pagerduty", "routing_key": "k"})
+        for channel in (slack, pager):
+            channel.transport = transport
+        note = Notification(alert(severity="critical"), "authority-withdraw-authority:withdraw", suppressed=4,
+                            escalation="critical severity")
+        slack.send(note)
+        pager.send(note)
+        (_, s), (pd_url, p) = transport.posts
+        assert s["text"].startswith(":rotating_light: [ESCALATED] [CRITICAL]")
+        assert "4 similar alerts suppressed" in s["blocks"][1]["text"]["text"]
+        assert pd_url == "https://events.pagerduty.com/v2/enqueue"
+        assert p["routing_key"] == "k" and p["dedup_key"] == "authority-withdraw-authority:withdraw"
+        assert p["payload"]["severity"] == "critical" and p["payload"]["custom_details"]["suppressed"] == 4
+
+        email = make_channel("e", {"type": "email", "to": "sec@example.com", "host": "smtp.example.com",
+                                   "username": "alerts", "password": "$SMTP_PASSWORD"}, env={"SMTP_PASSWORD": "pw"})
+        email.smtp_factory = FakeSMTP
+        email.send(Notification(alert(), "k"))
+        calls, msg = FakeSMTP.sent[-1]
+        assert calls == ["starttls", ("login", "alerts", "pw")]
+        assert msg["To"] == "sec@example.com" and msg["Subject"] == "[HIGH] authority-withdraw-authority fired"
+
+        for name, spec, env in (("a", {"type": "sms"}, {}), ("b", {"type": "slack"}, {}),
+                                ("c", {"type": "pagerduty", "routing_key": "$MISSING"}, {})):
+            with pytest.raises(ValueError):
+                make_channel(name, spec, env=env)
+
+    def test_routes_and_dedup(self):
+        now = [1000.0]
+        transport = Recorder()
+        router = make_router(now, transport)
+        assert [d.channel for d in router(alert())] == ["security", "hook"]
+        assert router(alert(signature="sig2")) == []
+        now[0] += 61
+        # Only the 60s route is out of its window; the third repeat within 300s also escalates
+        assert [d.channel for d in router(alert(signature="sig3"))] == ["hook", "oncall"]
+        assert transport.posts[-2][1]["suppressed"] == 1
+        assert [d.channel for d in router(alert(rule_id="large-outflow", kind="large-outflow",
+                                                instruction=None))] == ["security"]
+        assert router(alert(severity="info", rule_id="call-x", instruction="x")) == []
+        now[0] += 600
+        assert [d.channel for d in router(alert(signature="sig4"))] == ["security", "hook"]
+        assert "2 similar alerts suppressed" in transport.posts[-2][1]["blocks"][1]["text"]["text"]
+
+        by_payer = AlertRouter.from_dict({"channels": {"h": {"type": "webhook", "url": "http://h"}},
+                                          "routes": [{"channels": ["h"]}], "dedup_by": ["rule_id", "fee_payer"]},
+                                         clock=lambda: now[0])
+        by_payer.channels["h"].transport = Recorder()
+        assert len(by_payer(alert(fee_payer="A"))) == 1
+        assert len(by_payer(alert(fee_payer="B"))) == 1
+        assert by_payer(alert(fee_payer="A", signature="sig9")) == []
+
+    def test_escalation(self):
+        now = [0.0]
+        transport = Recorder()
+        router = make_router(now, transport)
+        (page,) = [d for d in router(alert(severity="critical")) if d.escalated]
+        assert page.channel == "oncall"
+        assert transport.posts[-1][1]["payload"]["summary"].startswith("[ESCALATED]")
+        assert not [d for d in router(alert(severity="critical", signature="s2")) if d.escalated]
+
+        medium = dict(rule_id="failure-burst", kind="failure-burst", severity="medium", instruction=None)
+        for i in range(2):
+            now[0] += 100
+            assert not [d for d in router(alert(signature=f"m{i}", **medium)) if d.escalated]
+        now[0] += 100
+        (page,) = [d for d in router(alert(signature="m2", **medium)) if d.escalated]
+        assert transport.posts[-1][1]["payload"]["custom_details"]["escalation"] == "medium alert repeated 3 times"
+
+        now[0] += 10_000
+        for i in range(3):
+            now[0] += 200
+            escalated = [d for d in router(alert(signature=f"n{i}", **medium)) if d.escalated]
+        # Repeats spread wider than `within` never escalate
+        assert escalated == []
+
+    def test_failures_and_config(self):
+        errors = []
+        router = AlertRouter.from_dict({"channels": {"h": {"type": "webhook", "url": "https://h.example/secret"}},
+                                        "routes": [{"channels": ["h"]}]}, on_error=errors.append)
+        router.channels["h"].transport = Recorder(500)
+        (d,) = router(alert())
+        assert not d.ok and "HTTP 500 from h.example" in d.error and "secret" not in d.error
+        assert errors == [d.error]
+
+        def unreachable(*args):
+            raise OSError("connection refused")
+
+        router.channels["h"].transport = unreachable
+        assert "unreachable" in router(alert(rule_id="other"))[0].error
+        assert isinstance(make_channel("e", {"type": "email", "to": ["a@b"]}), EmailChannel)
+        for bad in ({"channels": {}}, {"channels": {}, "routes": [{"channels": ["nope"]}]},
+                    {"channels": {}, "routes": [{"channels": [], "min_severity": "huge"}]}):
+            with pytest.raises(ValueError):
+                AlertRouter.from_dict(bad)
+
+    def test_cli(self, tmp_path, monkeypatch):
+        routing = tmp_path / "routing.yaml"
+        routing.write_text(yaml.safe_dump({"channels": {"h": {"type": "webhook", "url": "http://h"}},
+                                           "routes": [{"channels": ["h"], "min_severity": "high"}]}))
+        transport = Recorder()
+        monkeypatch.setattr("extensions.monitor.alerting.urllib_transport", transport)
+        runner = CliRunner()
+        result = runner.invoke(send_test_alert, [str(routing)])
+        assert result.exit_code == 0, result.output
+        assert transport.posts[0][1]["rule_id"] == "test-alert"
+        assert runner.invoke(send_test_alert, [str(routing), "--severity", "low"]).exit_code == 0
+        assert len(transport.posts) == 1
+        transport.status = 503
+        assert runner.invoke(send_test_alert, [str(routing)]).exit_code == 1
+        (tmp_path / "bad.yaml").write_text("routes: [{channels: [x]}]\n")
+        assert runner.invoke(send_test_alert, [str(tmp_path / "bad.yaml")]).exit_code == 1
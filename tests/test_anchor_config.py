@@ -0,0 +1,143 @@
+"""
+Tests for Anchor.toml ingestion and consistency checks.
+"""
+
+import json
+import shutil
+import sys
+import types
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+from click.testing import CliRunner
+
+from commands.ir import anchor_config
+from commands.poc import PoCContext, generate_poc_with_strategist, load_anchor_summary
+from extensions.ir import load_programs
+from extensions.ir.anchor_config import check_consistency, load_anchor_config, parse_anchor_config
+from extensions.simulation import LiteSvmBackend, StressCase
+from extensions.simulation.backend import load_account_dump
+from extensions.static.anchor_toml import AnchorTomlChecker
+from extensions.static.pipeline import StaticAnalysisPipeline
+
+WORKSPACE = Path(__file__).resolve().parent / "fixtures" / "solana" / "anchor_config"
+
+_B58 = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+
+def b58decode(address: str) -> bytes:
+    n = 0
+    for ch in address:
+        n = n * 58 + _B58.index(ch)
+    return n.to_bytes(32, "big")
+
+
+class TestAnchorConfig:
+    """Test parsing, consistency checks, the pipeline checker and consumers of the config."""
+
+    def setup_method(self):
+        self.config = load_anchor_config(WORKSPACE)
+
+    def test_parse(self):
+        config = self.config
+        assert config.root == WORKSPACE
+        assert config.cluster == "localnet"
+        assert config.clusters == ["localnet", "devnet"]
+        assert config.rpc_url() == "http://127.0.0.1:8999"
+        assert config.rpc_url("devnet") == "https://api.devnet.solana.com"
+        assert config.program_id("oracle-feed") == "Feed111111111111111111111111111111111111111"
+        assert config.program_id("escrow", "d") == "EscrDev1111111111111111111111111111111111111"
+        validator = config.validator
+        assert validator.url == "https://api.mainnet-beta.solana.com"
+        assert validator.startup_wait == 10000
+        assert validator.clones == ["H6ARHf6YXhGYeQfUzQNGk6rDNnLBQKrenN712K4AQJEG"]
+        assert config.account_files() == [WORKSPACE / "accounts" / "config.json"]
+        summary = config.summary()
+        assert summary["test_script"].startswith("yarn run ts-mocha")
+        assert summary["validator"]["genesis"][0]["program"] == "deps/mpl_token_metadata.so"
+        # Unknown clusters in [programs.*] and URL providers pass through
+        custom = parse_anchor_config('[provider]\ncluster = "http://10.0.0.2:8899"\n', WORKSPACE)
+        assert custom.rpc_url() == "http://10.0.0.2:8899" and custom.program_ids() == {}
+
+    def test_consistency(self):
+        issues = {(i.kind, i.program): i for i in check_consistency(self.config, load_programs(WORKSPACE))}
+        assert set(issues) == {
+            ("cluster-id-mismatch", "escrow"),
+            ("declare-id-mismatch", "escrow"),
+            ("invalid-address", "legacy_router"),
+            ("unknown-program", "legacy_router"),
+        }
+        declared = issues[("declare-id-mismatch", "escrow")]
+        assert declared.severity == "medium" and declared.line == 9
+        assert declared.details["declared"] == "EscrNew1111111111111111111111111111111111111"
+        assert issues[("cluster-id-mismatch", "escrow")].details["addresses"]["devnet"].startswith("EscrDev")
+        # oracle-feed (crate name) matches oracle_feed (Anchor.toml key)
+        assert not any(i.program == "oracle_feed" for i in issues.values())
+
+    def test_deploy_artifacts(self, tmp_path):
+        root = tmp_path / "ws"
+        shutil.copytree(WORKSPACE, root)
+        deploy = root / "target" / "deploy"
+        deploy.mkdir(parents=True)
+        (deploy / "escrow.so").write_bytes(b"\x7fELF")
+        (deploy / "oracle_feed-keypair.json").write_text(
+            json.dumps([1] * 32 + list(b58decode("Feed111111111111111111111111111111111111111"))))
+        (deploy / "escrow-keypair.json").write_text(json.dumps([1] * 32 + [2] * 32))
+        config = load_anchor_config(root)
+        issues = [i for i in check_consistency(config) if i.kind == "keypair-mismatch"]
+        assert [i.program for i in issues] == ["escrow"]
+        assert config.artifacts() == {
+            "Escr111111111111111111111111111111111111111": deploy / "escrow.so",
+            "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s": root / "deps" / "mpl_token_metadata.so",
+        }
+        backend = LiteSvmBackend.from_anchor(config)
+        assert backend.cwd == root and set(backend.programs) == set(config.artifacts())
+        assert backend.accounts == [root / "accounts" / "config.json"]
+        dump = load_account_dump(backend.accounts[0])
+        assert dump["data"] == b"\x01\x02\x03\x04" and dump["owner"].startswith("Escr")
+
+        with patch("extensions.simulation.math_stress.LiteSvmBackend") as Backend:
+            case = StressCase.from_dict({
+                "name": "fee", "reference": "amount / 100", "inputs": {"amount": 10},
+                "program": {"anchor": ".", "name": "escrow", "layout": ["u8:1", "u64:amount"]},
+            }, base=root)
+            calc = case.program()
+        assert calc.program_id == "Escr111111111111111111111111111111111111111"
+        assert Backend.from_anchor.call_args.args[0].root == root
+
+    def test_checker(self, tmp_path):
+        findings, metadata = AnchorTomlChecker().run(WORKSPACE)
+        assert metadata["success"] and metadata["issues"] == 4
+        hyp = next(f for f in findings if f.kind == "declare-id-mismatch").to_hypothesis()
+        assert hyp["title"] == "`declare_id!` of `escrow` disagrees with Anchor.toml"
+        assert hyp["vulnerability_type"] == "anchor-config-declare-id-mismatch"
+        assert hyp["properties"]["source_files"] == ["Anchor.toml"]
+        assert hyp["properties"]["affected_lines"] == [9]
+        assert len(AnchorTomlChecker(report_cluster_mismatch=False).run(WORKSPACE)[0]) == 3
+        assert "anchor-toml" in StaticAnalysisPipeline(chain_id="solana").runners
+        (tmp_path / "Anchor.toml").write_text("[programs.localnet\n")
+        assert AnchorTomlChecker().run(tmp_path)[1]["success"] is False
+
+    def test_poc_prompt(self):
+        summary = load_anchor_summary(str(WORKSPACE))
+        assert summary["program_ids"]["escrow"] == "Escr111111111111111111111111111111111111111"
+        assert load_anchor_summary(str(WORKSPACE / "programs")) is None
+        client = MagicMock()
+        client.return_value.raw.return_value = "prompt"
+        module = types.SimpleNamespace(UnifiedLLMClient=client)
+        context = PoCContext("p", {"title": "Offer can be cancelled by anyone"}, {}, {}, anchor_config=summary)
+        with patch.dict(sys.modules, {"llm.unified_client": module}):
+            assert generate_poc_with_strategist(context, {}) == "prompt"
+        prompt = client.return_value.raw.call_args.kwargs["user"]
+        assert "PROJECT CONFIGURATION (from Anchor.toml)" in prompt
+        assert "Program `escrow`: Escr111111111111111111111111111111111111111" in prompt
+        assert "localnet (http://127.0.0.1:8999)" in prompt
+
+    def test_cli(self):
+        result = CliRunner().invoke(anchor_config, [str(WORKSPACE), "--cluster", "devnet", "--json"])
+        assert result.exit_code == 0
+        data = json.loads(result.output)
+        assert data["program_ids"]["escrow"].startswith("EscrDev")
+        assert data["rpc_url"] == "https://api.devnet.solana.com"
+        assert len(data["issues"]) == 4
+        assert CliRunner().invoke(anchor_config, [str(WORKSPACE / "programs")]).exit_code == 1
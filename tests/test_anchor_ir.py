@@ -0,0 +1,79 @@
+"""
+Tests for the Anchor front-end and front-end selection.
+
+Uses the vulnerable vault fixture under tests/fixtures/solana/anchor_vault.
+"""
+
+from pathlib import Path
+
+from extensions.ir import AnchorFrontend, load_programs
+
+
+FIXTURES = Path(__file__).resolve().parent / "fixtures" / "solana"
+VAULT = FIXTURES / "anchor_vault"
+
+
+class TestAnchorFrontend:
+    """Test lowering of #[program] modules and Accounts structs."""
+
+    def setup_method(self):
+        programs = AnchorFrontend().load(VAULT)
+        assert len(programs) == 1
+        self.program = programs[0]
+
+    def test_program_metadata(self):
+        assert self.program.name == "vault"
+        assert self.program.framework == "anchor"
+        assert self.program.program_id == "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+        assert [ix.name for ix in self.program.instructions] == ["initialize", "deposit", "withdraw"]
+
+    def test_instruction_args_and_struct(self):
+        deposit = self.program.instruction("deposit")
+        assert deposit.accounts_struct == "Deposit"
+        assert [(a.name, a.ty) for a in deposit.args] == [("amount", "u64")]
+
+    def test_account_constraints(self):
+        init = self.program.instruction("initialize")
+        vault = init.account("vault")
+        assert vault.is_mut is True
+        assert vault.seeds == ['b"vault"', "authority.key().as_ref()"]
+        assert vault.inner_type == "Vault"
+        assert init.account("authority").is_signer is True
+        assert init.account("system_program").wrapper == "Program"
+
+    def test_delegated_handler_body(self):
+        withdraw = self.program.instruction("withdraw")
+        assert withdraw.source_file == "src/instructions/withdraw.rs"
+        assert "vault.balance -= amount" in withdraw.body
+        assert withdraw.account("authority").wrapper == "AccountInfo"
+        assert withdraw.account("authority").is_signer is False
+        assert "CHECK" in withdraw.account("authority").docs
+        assert withdraw.account("authority").file == "src/instructions/withdraw.rs"
+
+    def test_cpi_calls(self):
+        deposit = self.program.instruction("deposit")
+        assert [(c.kind, c.target) for c in deposit.calls] == [
+            ("cpi", "ctx.accounts.token_program.to_account_info()")
+        ]
+        withdraw = self.program.instruction("withdraw")
+        assert withdraw.calls[0].kind == "invoke"
+        assert withdraw.calls[0].target == "ctx.accounts.payout_program.key()"
+        lines = (VAULT / "programs/vault/src/instructions/withdraw.rs").read_text().split("\n")
+        assert "invoke(" in lines[withdraw.calls[0].line - 1]
+
+    def test_account_types(self):
+        vault = self.program.account_type("Vault")
+        assert vault is not None
+        assert [f.name for f in vault.fields] == ["authority", "balance", "bump"]
+
+
+class TestLoadPrograms:
+    """Test automatic front-end selection."""
+
+    def test_anchor_workspace(self):
+        programs = load_programs(VAULT)
+        assert [(p.name, p.framework) for p in programs] == [("vault", "anchor")]
+
+    def test_seahorse_not_loaded_twice(self):
+        programs = load_programs(FIXTURES / "seahorse_calculator")
+        assert [(p.name, p.framework) for p in programs] == [("calculator", "seahorse")]
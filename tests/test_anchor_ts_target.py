@@ -0,0 +1,86 @@
+"""
+Tests for the Anchor TypeScript render target: the sibling `.ts` bodies of
+Solana templates, TypeScript values from an IDL, and `kb render --target
+anchor-ts`.
+"""
+
+import json
+
+import pytest
+from click.testing import CliRunner
+
+from commands.knowledge import kb
+from extensions.knowledge import TemplateRegistry
+from extensions.knowledge.idl import parse_idl, template_context
+from extensions.knowledge.render import TemplateEngine
+from extensions.knowledge.template_loader import TemplateLoader
+
+PROGRAM = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+
+IDL = {
+    "address": PROGRAM,
+    "metadata": {"name": "vault"},
+    "instructions": [{"name": "withdrawAll", "accounts": [
+        {"name": "vault", "writable": True},
+        {"name": "authority", "signer": True},
+        {"name": "tokenProgram", "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"},
+        {"name": "systemProgram", "address": "11111111111111111111111111111111"}],
+        "args": [{"name": "amount", "type": "u64"}, {"name": "bump", "type": "u8"}]}],
+}
+
+
+class TestAnchorTsTarget:
+    """Test rendering Solana templates as Mocha tests for `anchor test`."""
+
+    def test_targets(self):
+        loader = TemplateLoader()
+        solana = loader.get_by_chain("solana")
+        assert solana and all("anchor-ts" in t.targets for t in solana)
+
+        template = loader.get("missing_signer")
+        ts = template.for_target("anchor-ts")
+        assert ts.harness.id == "anchor-ts" and ts.harness.test_path(ts.id) == "tests/missing_signer.ts"
+        # Same values as the Rust body, plus the workspace program's name (defaulted)
+        assert set(template.placeholders) < set(ts.placeholders) == set(template.placeholders) | {"PROGRAM_NAME"}
+        assert template.for_target("anchor") is template
+        assert template.metadata()["targets"] == ["anchor", "anchor-ts"]
+        with pytest.raises(ValueError, match="has no solana-program-test target"):
+            template.for_target("solana-program-test")
+        with pytest.raises(ValueError):
+            loader.get("flash_loan").for_target("anchor-ts")
+
+        found = TemplateRegistry(loader).query(harness="anchor-ts")
+        assert {t.id for t in found} == {t.id for t in solana}
+
+    def test_render_from_idl(self):
+        context = template_context(parse_idl(IDL), typescript=True)
+        assert context["ARG_FIELDS"] == "new BN(0),  // amount: u64\n0,  // bump: u8"
+        assert context["ACCOUNT_FIELDS"].split("\n") == [
+            "vault: vault.publicKey,  // mut",
+            "authority: attacker.publicKey,  // signer; Attacker, not the real one!",
+            'tokenProgram: new PublicKey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"),',
+            "systemProgram: SystemProgram.programId,"]
+
+        engine = TemplateEngine(TemplateLoader().get("missing_signer").for_target("anchor-ts"))
+        text = engine.render({**context, "VAULT_ACCOUNT": PROGRAM})
+        assert "const program = anchor.workspace.Vault as Program;" in text
+        assert "    //   .withdrawAll(\n    //     new BN(0),  // amount: u64\n    //     0,  // bump: u8\n" in text
+        assert "    //     systemProgram: SystemProgram.programId,\n    //   })" in text
+        assert "{{" not in text and not engine.harness.problems(text)
+
+    def test_cli(self, tmp_path):
+        runner = CliRunner()
+        idl = tmp_path / "vault.json"
+        idl.write_text(json.dumps(IDL))
+        result = runner.invoke(kb, ["render", "missing_signer", "--target", "anchor-ts", "--idl", str(idl),
+                                    "-s", f"VAULT_ACCOUNT={PROGRAM}", "-w", str(tmp_path)])
+        assert result.exit_code == 0, result.output
+        written = (tmp_path / "tests" / "missing_signer.ts").read_text()
+        assert 'import * as anchor from "@coral-xyz/anchor";' in written and "attacker.publicKey" in written
+        assert "Run with: anchor test" in result.output
+
+        result = runner.invoke(kb, ["render", "flash_loan", "--target", "anchor-ts"])
+        assert result.exit_code == 1
+        result = runner.invoke(kb, ["render", "missing_signer", "--target", "anchor-ts", "--program-test",
+                                    "--idl", str(idl)])
+        assert result.exit_code != 0 and "own target" in result.output
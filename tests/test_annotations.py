@@ -0,0 +1,93 @@
+"""
+Tests for auditor annotations: parsing `@baskerville` comments, ingesting
+them as an audit trail linked to findings, and the report section.
+"""
+
+import json
+from datetime import datetime
+from unittest.mock import patch
+
+from click.testing import CliRunner
+
+from commands.reporting import reporting
+from extensions.reporting.annotations import embed_annotations, ingest_annotations, parse_annotations
+
+
+SOURCE = """use anchor_lang::prelude::*;
+
+// @baskerville note: reviewed, invariant held by has_one = admin
+#[derive(Accounts)]
+pub struct SetFee<'info> {
+    pub admin: Signer<'info>,
+}
+
+pub fn set_fee(ctx: Context<SetFee>, fee: u64) -> Result<()> {
+    ctx.accounts.config.fee = fee; // @baskerville todo(alice): fee has no upper bound
+    *ctx.accounts.counter += 1;
+    Ok(())
+}
+"""
+
+
+def _project(tmp_path):
+    source = tmp_path / "src_tree"
+    (source / "programs" / "vault" / "src").mkdir(parents=True)
+    (source / "programs" / "vault" / "src" / "lib.rs").write_text(SOURCE)
+    project_dir = tmp_path / "project"
+    project_dir.mkdir()
+    (project_dir / "hypotheses.json").write_text(json.dumps({"version": "1.0", "hypotheses": {
+        "hyp_fee": {"title": "Unbounded fee", "severity": "medium",
+                    "properties": {"source_files": ["programs/vault/src/lib.rs"], "affected_lines": [11]}},
+        "hyp_other": {"title": "Elsewhere", "properties": {"source_files": ["programs/vault/src/lib.rs"],
+                                                           "affected_lines": [40]}},
+    }}))
+    return source, project_dir
+
+
+class TestAnnotations:
+    """Test ingesting annotations and linking them to findings."""
+
+    def test_parse(self):
+        notes = parse_annotations(SOURCE, "lib.rs")
+        assert [(n["kind"], n["line"], n["target_line"], n["author"]) for n in notes] == [
+            ("note", 3, 4, None), ("todo", 10, 10, "alice")]
+        assert notes[0]["text"] == "reviewed, invariant held by has_one = admin"
+        assert notes[0]["code"] == "#[derive(Accounts)]"
+        # A "#" is not a comment in Rust, but is in Vyper
+        assert parse_annotations("x: uint256  # @baskerville reviewed: ok\n", "a.vy")[0]["target_line"] == 1
+        assert parse_annotations("#[test] # @baskerville note: x\n", "a.rs") == []
+
+    def test_ingest(self, tmp_path):
+        source, project_dir = _project(tmp_path)
+        result = ingest_annotations(project_dir, source, now=datetime(2026, 10, 1))
+        assert result["added"] == 2
+        todo = next(a for a in result["annotations"] if a["kind"] == "todo")
+        assert todo["findings"] == ["hyp_fee"] and todo["file"] == "programs/vault/src/lib.rs"
+        store = json.loads((project_dir / "hypotheses.json").read_text())["hypotheses"]
+        assert store["hyp_fee"]["properties"]["annotations"] == [todo["id"]]
+        assert "annotations" not in store["hyp_other"]["properties"]
+
+        # Deleting a note keeps it in the trail
+        lib = source / "programs" / "vault" / "src" / "lib.rs"
+        lib.write_text(SOURCE.replace(" // @baskerville todo(alice): fee has no upper bound", ""))
+        result = ingest_annotations(project_dir, source, now=datetime(2026, 10, 2))
+        assert (result["added"], result["removed"]) == (0, 1)
+        removed = next(a for a in result["annotations"] if a["id"] == todo["id"])
+        assert removed["removed_at"].startswith("2026-10-02") and removed["first_seen"].startswith("2026-10-01")
+        store = json.loads((project_dir / "hypotheses.json").read_text())["hypotheses"]
+        assert store["hyp_fee"]["properties"]["annotations"] == []
+
+        report = embed_annotations("# Report\n\n## Findings\n\n...\n", result["annotations"], "markdown")
+        assert report.index("## Audit Trail") < report.index("## Findings")
+        assert "invariant held by has_one = admin" in report and "fee has no upper bound" not in report
+
+    def test_cli(self, tmp_path):
+        source, project_dir = _project(tmp_path)
+        runner = CliRunner()
+        with patch("commands.reporting.ProjectManager") as PM:
+            PM.return_value.get_project.return_value = {"path": str(project_dir), "source_path": str(source)}
+            result = runner.invoke(reporting, ["annotations", "demo", "--kind", "todo", "--json"])
+        assert result.exit_code == 0, result.output
+        notes = json.loads(result.output)
+        assert [n["text"] for n in notes] == ["fee has no upper bound"]
+        assert (project_dir / "annotations.json").exists()
@@ -0,0 +1,191 @@
+"""
+Tests for the arbitrary signed CPI detector.
+
+Verifies PDA-signed CPIs are flagged when the caller chooses the program,
+the signer seeds (instruction arguments, unchecked account keys, also
+through `let` bindings) or the instruction data, that pinned programs,
+validated seeds and unsigned CPIs are not, that findings link to the
+`arbitrary_cpi` PoC template, and that the pipeline merges them with the
+other CPI detectors' hits on one CPI.
+"""
+
+from pathlib import Path
+
+from extensions.knowledge.class_loader import ClassLoader
+from extensions.static import ArbitraryCpiDetector, StaticAnalysisPipeline
+from extensions.static.arbitrary_cpi import TEMPLATE_FILE
+
+
+ROOT = Path(__file__).resolve().parents[1]
+FIXTURES = ROOT / "tests" / "fixtures" / "solana"
+
+SOURCE = """use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod escrow {
+    use super::*;
+
+    pub fn release(ctx: Context<Release>, owner: Pubkey, bump: u8, amount: u64) -> Result<()> {
+        let seeds: &[&[&[u8]]] = &[&[b"escrow", owner.as_ref(), &[bump]]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.escrow_token.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.escrow.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, seeds);
+        token::transfer(cpi_ctx, amount)
+    }
+
+    pub fn relay(ctx: Context<Relay>, payload: Vec<u8>) -> Result<()> {
+        let bump = ctx.bumps.escrow;
+        let data = [&[7u8][..], &payload[..]].concat();
+        let ix = Instruction {
+            program_id: ctx.accounts.target.key(),
+            accounts: vec![AccountMeta::new(ctx.accounts.escrow.key(), true)],
+            data,
+        };
+        let seeds: &[&[&[u8]]] = &[&[b"escrow", ctx.accounts.owner.key.as_ref(), &[bump]]];
+        invoke_signed(&ix, &[ctx.accounts.escrow.to_account_info()], seeds)?;
+        Ok(())
+    }
+
+    pub fn settle(ctx: Context<Settle>, amount: u64) -> Result<()> {
+        let bump = ctx.bumps.escrow;
+        let seeds: &[&[&[u8]]] = &[&[b"escrow", ctx.accounts.owner.key.as_ref(), &[bump]]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.escrow_token.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.escrow.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, seeds);
+        token::transfer(cpi_ctx, amount)
+    }
+
+    pub fn ping(ctx: Context<Ping>, payload: Vec<u8>) -> Result<()> {
+        let ix = Instruction {
+            program_id: ctx.accounts.target.key(),
+            accounts: vec![],
+            data: payload,
+        };
+        invoke(&ix, &[])?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Release<'info> {
+    /// CHECK: signs through seeds
+    pub escrow: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub escrow_token: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Relay<'info> {
+    #[account(seeds = [b"escrow", owner.key().as_ref()], bump)]
+    pub escrow: SystemAccount<'info>,
+    /// CHECK: any key
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: the program to relay to
+    pub target: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Settle<'info> {
+    #[account(seeds = [b"escrow", owner.key().as_ref()], bump)]
+    pub escrow: SystemAccount<'info>,
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub escrow_token: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Ping<'info> {
+    /// CHECK: no signature is passed
+    pub target: UncheckedAccount<'info>,
+}
+"""
+
+
+class TestArbitraryCpi:
+    """Test the detector against an inline Anchor program and the fixtures."""
+
+    def test_findings(self, tmp_path, anchor_workspace):
+        findings, metadata = ArbitraryCpiDetector().run(anchor_workspace(tmp_path, {"escrow": SOURCE}))
+        assert metadata["signed_cpis"] == 3
+        # `owner` is pinned by the escrow's seeds in `relay` and signs in `settle`; `ping` signs nothing
+        flagged = [(f.site.instruction, f.reasons, f.controlled, f.severity, f.site.line) for f in findings]
+        assert flagged == [
+            ("release", ["caller-seeds"], {"caller-seeds": "owner"}, "high",
+             anchor_workspace.line(SOURCE, "let cpi_ctx")),
+            ("relay", ["caller-program", "caller-data"], {"caller-program": "target", "caller-data": "payload"},
+             "high", anchor_workspace.line(SOURCE, "invoke_signed(")),
+        ]
+        assert findings[0].path == "programs/escrow/src/lib.rs"
+
+    def test_bound_seeds(self, tmp_path, anchor_workspace):
+        # The seeds reach the CPI through two `let` bindings and a `&seeds[..]` reborrow
+        inline = SOURCE.replace("owner: Pubkey, bump: u8", "escrow_id: u64, bump: u8", 1).replace(
+            "&[&[b\"escrow\", owner.as_ref(), &[bump]]];",
+            "&[&[b\"escrow\", &escrow_id.to_le_bytes(), &[bump]]];", 1)
+        bound = inline.replace(
+            "let seeds: &[&[&[u8]]] = &[&[b\"escrow\", &escrow_id.to_le_bytes(), &[bump]]];",
+            "let seeds = &[b\"escrow\", &escrow_id.to_le_bytes(), &[bump]];\n        let signer = &[&seeds[..]];", 1,
+        ).replace("cpi_accounts, seeds);\n        token::transfer(cpi_ctx, amount)\n    }\n\n    pub fn relay",
+                  "cpi_accounts, signer);\n        token::transfer(cpi_ctx, amount)\n    }\n\n    pub fn relay", 1)
+        assert "let signer" in bound and "cpi_accounts, signer)" in bound
+        for index, source in enumerate((inline, bound)):
+            findings, _ = ArbitraryCpiDetector().run(anchor_workspace(tmp_path / str(index), {"escrow": source}))
+            assert [(f.site.instruction, f.controlled, f.severity) for f in findings][0] == (
+                "release", {"caller-seeds": "escrow_id"}, "high")
+
+    def test_fixtures(self):
+        findings, _ = ArbitraryCpiDetector().run(FIXTURES / "cpi_router")
+        assert [(f.site.instruction, f.path, f.site.line, f.reasons, f.severity) for f in findings] == [
+            ("route_swap", "src/lib.rs", 23, ["caller-program"], "high"),
+            ("forward", "src/lib.rs", 56, ["caller-data"], "medium"),
+        ]
+        # The keeper program is a `Program<'info, Keeper>`
+        assert ArbitraryCpiDetector().run(FIXTURES / "treasury_workspace")[0] == []
+
+    def test_hypothesis(self, tmp_path, anchor_workspace):
+        findings, _ = ArbitraryCpiDetector().run(anchor_workspace(tmp_path, {"escrow": SOURCE}))
+        hyp = findings[1].to_hypothesis()
+        assert hyp["title"] == "Arbitrary signed CPI in relay: the PDA signs a CPI into a program the caller chooses"
+        assert hyp["severity"] == "high" and hyp["confidence"] == 0.6
+        assert "`target` is not pinned" in hyp["description"] and "comes from `payload`" in hyp["description"]
+        props = hyp["properties"]
+        assert props["source_tool"] == "arbitrary-cpi" and props["pda_signers"] == ["escrow"]
+        assert props["poc_context"] == {"PROGRAM_ID": "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"}
+        assert (ROOT / props["poc_template_file"]).exists() and props["poc_template_file"] == TEMPLATE_FILE
+        vuln_class = ClassLoader().resolve(hyp["vulnerability_type"], "solana")
+        assert vuln_class.id == "untrusted-delegated-execution"
+        assert vuln_class.variant("solana").templates == ["arbitrary_cpi"]
+
+    def test_pipeline(self, tmp_path, anchor_workspace):
+        pipeline = StaticAnalysisPipeline(chain_id="solana")
+        assert "arbitrary-cpi" in pipeline.check_tools()
+        result = pipeline.run(anchor_workspace(tmp_path, {"escrow": SOURCE}), tools=["arbitrary-cpi"])
+        assert len(result.hypotheses) == 2
+        assert all(h["id"].startswith("static_arbitrary-cpi_") for h in result.hypotheses)
+
+    def test_merged(self):
+        # cpi-privileges, arbitrary-cpi and cpi-targets all flag route_swap's CPI at line 23
+        result = StaticAnalysisPipeline(chain_id="solana").run(
+            FIXTURES / "cpi_router", tools=["cpi-privileges", "arbitrary-cpi", "cpi-targets"])
+        hits = {min(h["properties"]["affected_lines"]): h for h in result.hypotheses}
+        assert sorted(hits) == [23, 56, 82] and {h["vulnerability_type"] for h in hits.values()} == {"arbitrary-cpi"}
+        assert sorted(hits[23]["properties"]["also_reported_by"]) == ["arbitrary-cpi", "cpi-targets"]
+        # cpi-privileges rates forwarding the user's signature low; a caller-chosen hook makes it high
+        assert hits[82]["severity"] == "high" and hits[82]["properties"]["also_reported_by"] == ["cpi-targets"]
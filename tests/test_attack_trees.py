@@ -0,0 +1,112 @@
+"""
+Tests for attack-tree synthesis.
+
+Verifies findings land under the goals they reach alone, that findings only
+dangerous together form chains rated above their weakest step, that goals
+leading to others become branches, and the Mermaid, report section and CLI
+outputs.
+"""
+
+import json
+from unittest.mock import patch
+
+from click.testing import CliRunner
+
+from commands.reporting import reporting
+from extensions.reporting.attack_trees import (
+    build_attack_trees,
+    embed_attack_trees,
+    load_findings,
+    render_attack_trees,
+    to_mermaid,
+)
+
+
+FINDINGS = {
+    "owner": {"title": "Missing owner check on config in withdraw", "severity": "high",
+              "vulnerability_type": "missing-owner-check", "status": "confirmed"},
+    "stale": {"title": "Oracle price used without staleness check", "severity": "medium",
+              "vulnerability_type": "stale-price", "status": "confirmed"},
+    "flash": {"title": "Pool balance can be borrowed with a flash loan", "severity": "low",
+              "vulnerability_type": "flash-loan", "status": "proposed"},
+    "reinit": {"title": "Config can be initialized twice", "severity": "high",
+               "vulnerability_type": "reinitialization", "status": "confirmed"},
+    "typo": {"title": "Typo in event name", "severity": "info", "status": "proposed"},
+    "logic": {"title": "Fee rounds the wrong way on refunds", "severity": "low", "tags": ["business logic"],
+              "status": "rejected"},
+}
+
+
+def _tree(data, goal):
+    return next(t for t in data["trees"] if t["goal"] == goal)
+
+
+class TestAttackTrees:
+    """Test tree composition and the exports."""
+
+    def test_build(self):
+        data = build_attack_trees(FINDINGS)
+        assert [t["goal"] for t in data["trees"]] == ["drain-funds", "take-over"]
+        drain = _tree(data, "drain-funds")
+        assert drain["severity"] == "high" and drain["chains"] == 1
+        assert [(b["kind"], b["severity"]) for b in drain["branches"]] == [
+            ("finding", "high"), ("goal", "high"), ("chain", "medium")]
+        chain = drain["branches"][2]
+        assert chain["title"] == "Price manipulation"
+        assert [s["findings"] for s in chain["steps"]] == [["stale"], ["flash"]]
+        assert drain["branches"][1]["title"] == "Take over privileged control, then withdraw as the authority"
+        assert [b["finding"] for b in _tree(data, "take-over")["branches"]] == ["owner", "reinit"]
+        assert "typo" not in data["findings"] and data["unplaced"] == ["logic"]
+
+    def test_chain_needs_every_step(self):
+        partial = {k: v for k, v in FINDINGS.items() if k != "flash"}
+        drain = _tree(build_attack_trees(partial), "drain-funds")
+        assert not drain["chains"]
+        # One finding covering the whole chain is a branch of its own
+        both = {"pm": {"title": "Spot price manipulation", "severity": "high", "tags": ["oracle", "flash-loan"]}}
+        drain = _tree(build_attack_trees(both), "drain-funds")
+        assert [(b["kind"], b["finding"]) for b in drain["branches"]] == [("finding", "pm")]
+
+    def test_mermaid(self):
+        data = build_attack_trees(FINDINGS)
+        diagram = to_mermaid(data)
+        assert diagram.startswith("flowchart TD\n")
+        assert 'g_drain_funds{{"Drain vault funds<br/>high"}}' in diagram
+        assert 'g_drain_funds -->|"Take over privileged control, then withdraw as the authority"| g_take_over' in diagram
+        assert '[/"AND: Price manipulation<br/>medium"\\]' in diagram
+        assert '-->|"Price can be moved or is stale"|' in diagram
+        # Each finding is drawn once
+        assert diagram.count('"Config can be initialized twice<br/>high"') == 1
+        assert "classDef high fill:" in diagram and "classDef critical" not in diagram
+        assert json.loads(render_attack_trees(data, "json"))["format"] == "baskerville-attack-trees"
+
+    def test_report_section(self):
+        data = build_attack_trees(FINDINGS)
+        report = embed_attack_trees("# Report\n\n## Findings\n\n...\n", data, "markdown")
+        assert report.index("## Attack Trees") < report.index("## Findings")
+        assert "- **MEDIUM** via Price manipulation (chain) — Price can be moved or is stale: Oracle price" in report
+        assert "```mermaid\nflowchart TD" in report
+        html = embed_attack_trees('<html><body><div class="footer"></div></body></html>', data, "html")
+        assert '<pre class="mermaid">flowchart TD' in html
+        assert embed_attack_trees("# Report\n", build_attack_trees({}), "markdown") == "# Report\n"
+
+    def test_load_and_cli(self, tmp_path):
+        project_dir = tmp_path / "project"
+        (project_dir / "static_analysis").mkdir(parents=True)
+        (project_dir / "hypotheses.json").write_text(json.dumps({"hypotheses": FINDINGS}))
+        (project_dir / "static_analysis" / "static_hypotheses.json").write_text(json.dumps([
+            {"id": "static_cpi_0", "title": "Unvalidated CPI target", "severity": "high",
+             "vulnerability_type": "arbitrary-cpi"}]))
+        assert sorted(load_findings(project_dir, confirmed_only=True)) == ["owner", "reinit", "stale"]
+        assert "logic" not in load_findings(project_dir) and "static_cpi_0" in load_findings(project_dir)
+
+        with patch("commands.reporting.ProjectManager") as PM:
+            PM.return_value.get_project.return_value = {"path": str(project_dir)}
+            out = CliRunner().invoke(reporting, ["attack-trees", "demo"])
+            assert out.exit_code == 0, out.output
+            diagram = (project_dir / "reports" / "attack_trees.mmd").read_text()
+            assert "Unvalidated CPI target" in diagram and "AND: Price manipulation" in diagram
+            out = CliRunner().invoke(reporting, ["attack-trees", "demo", "--confirmed-only", "--format", "json"])
+            assert out.exit_code == 0, out.output
+            data = json.loads((project_dir / "reports" / "attack_trees.json").read_text())
+            assert not _tree(data, "drain-funds")["chains"]
@@ -0,0 +1,239 @@
+"""
+Tests for mechanical fixes: missing signers retyped and bound to the state
+they guard with has_one (also several into one empty attribute), colliding
+PDA seeds given their own prefixes, fields a changed file no longer has,
+and `scan --fix` with --dry-run and --fix-patch.
+"""
+
+import json
+import shutil
+from pathlib import Path
+
+import pytest
+from click.testing import CliRunner
+
+from commands.scan import scan
+from extensions.static import MissingSignerDetector, PdaCollisionScanner, apply_fixes, plan_fixes, render_patch
+from extensions.static.autofix import Unfixable, _Workspace
+
+SIGNER_SOURCE = """use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn set_fee(ctx: Context<SetFee>, fee: u64) -> Result<()> {
+        ctx.accounts.config.fee = fee;
+        Ok(())
+    }
+
+    pub fn pause(ctx: Context<Pause>) -> Result<()> {
+        ctx.accounts.config.paused = true;
+        Ok(())
+    }
+
+    pub fn set_limit(ctx: Context<SetLimit>, limit: u64) -> Result<()> {
+        ctx.accounts.config.limit = limit;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetFee<'info> {
+    #[account(mut)]
+    pub config: Account<'info, Config>,
+    /// CHECK: the admin
+    pub admin: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    #[account(
+        mut, // writable
+    )]
+    pub config: Account<'info, Config>,
+    /// CHECK: the admin
+    pub admin: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetLimit<'info> {
+    pub config: Account<'info, Config>,
+    /// CHECK: the admin
+    pub admin: AccountInfo<'info>,
+}
+
+#[account]
+pub struct Config {
+    pub admin: Pubkey,
+    pub fee: u64,
+    pub limit: u64,
+    pub paused: bool,
+}
+"""
+
+PDA_SOURCE = """use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn create_profile(ctx: Context<CreateProfile>) -> Result<()> {
+        ctx.accounts.profile.owner = ctx.accounts.user.key();
+        Ok(())
+    }
+
+    pub fn create_vault(ctx: Context<CreateVault>) -> Result<()> {
+        ctx.accounts.vault.owner = ctx.accounts.user.key();
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateProfile<'info> {
+    #[account(init, payer = user, space = 8 + 32, seeds = [b"user", user.key().as_ref()], bump)]
+    pub profile: Account<'info, UserProfile>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateVault<'info> {
+    #[account(
+        init,
+        payer = user,
+        space = 8 + 32,
+        seeds = [b"user", user.key().as_ref()], // same prefix
+        bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+pub struct UserProfile {
+    pub owner: Pubkey,
+}
+
+#[account]
+pub struct Vault {
+    pub owner: Pubkey,
+}
+"""
+
+
+# The program's source, relative to the workspace
+LIB = Path("programs/vault/src/lib.rs")
+
+
+def _hits(scanner, root: Path) -> list[dict]:
+    findings, _ = scanner.run(root)
+    return [f.to_hypothesis() for f in findings]
+
+
+class TestAutofix:
+    """Test planning, rendering and applying mechanical fixes."""
+
+    def test_missing_signer(self, tmp_path, anchor_workspace):
+        lib = anchor_workspace(tmp_path, {"vault": SIGNER_SOURCE}, localnet=["vault"]) / LIB
+        hits = _hits(MissingSignerDetector(), tmp_path)
+        fixes, skipped = plan_fixes(hits, tmp_path)
+        assert len(fixes) == 3 and skipped == []
+        assert all(f.files == ["programs/vault/src/lib.rs"] and "bind it to `config`" in f.title for f in fixes)
+        patch = render_patch(fixes, tmp_path)
+        assert patch.startswith("--- a/programs/vault/src/lib.rs\n+++ b/programs/vault/src/lib.rs\n")
+        assert lib.read_text() == SIGNER_SOURCE
+
+        assert apply_fixes(fixes, tmp_path) == ["programs/vault/src/lib.rs"]
+        fixed = lib.read_text()
+        assert "#[account(mut, has_one = admin)]" in fixed
+        assert "        mut, // writable\n        has_one = admin,\n    )]" in fixed
+        assert "    #[account(has_one = admin)]\n    pub config: Account<'info, Config>,\n    /// CHECK" in fixed
+        assert "AccountInfo" not in fixed and "UncheckedAccount" not in fixed
+        assert fixed.count("pub admin: Signer<'info>,") == 3
+        assert _hits(MissingSignerDetector(), tmp_path) == []
+
+    def test_pda_collision(self, tmp_path, anchor_workspace):
+        lib = anchor_workspace(tmp_path, {"vault": PDA_SOURCE}, localnet=["vault"]) / LIB
+        hits = _hits(PdaCollisionScanner(), tmp_path)
+        fixes, skipped = plan_fixes(hits, tmp_path)
+        assert len(fixes) == 1 and skipped == [] and len(fixes[0].edits) == 2
+        assert "b\"user_profile\", b\"vault\"" in fixes[0].notes[0] and "migrate" in fixes[0].notes[0]
+        apply_fixes(fixes, tmp_path)
+        fixed = lib.read_text()
+        assert 'space = 8 + 32, seeds = [b"user_profile", user.key().as_ref()], bump)]' in fixed
+        assert '        seeds = [b"vault", user.key().as_ref()], // same prefix\n' in fixed
+        assert _hits(PdaCollisionScanner(), tmp_path) == []
+
+    def test_unfixable(self, tmp_path, anchor_workspace):
+        # No state the handler writes stores the admin, so there is nothing to bind it to
+        anchor_workspace(tmp_path, {"vault": SIGNER_SOURCE.replace("    pub admin: Pubkey,\n", "")}, localnet=["vault"])
+        fixes, skipped = plan_fixes(_hits(MissingSignerDetector(), tmp_path), tmp_path)
+        assert fixes == [] and len(skipped) == 3
+        assert "bind it to state by hand" in skipped[0]["reason"]
+
+    def test_empty_attribute(self, tmp_path, anchor_workspace):
+        # Both authorities are bound to the config through its empty #[account()]
+        source = SIGNER_SOURCE.replace(
+            "    #[account(mut)]\n    pub config: Account<'info, Config>,\n    /// CHECK: the admin\n"
+            "    pub admin: AccountInfo<'info>,\n",
+            "    #[account()]\n    pub config: Account<'info, Config>,\n    /// CHECK: the admin\n"
+            "    pub admin: AccountInfo<'info>,\n    /// CHECK: the operator\n    pub operator: AccountInfo<'info>,\n",
+        ).replace("    pub admin: Pubkey,\n", "    pub admin: Pubkey,\n    pub operator: Pubkey,\n")
+        lib = anchor_workspace(tmp_path, {"vault": source}, localnet=["vault"]) / LIB
+        hits = [h for h in _hits(MissingSignerDetector(), tmp_path) if h["properties"]["instruction"] == "set_fee"]
+        fixes, skipped = plan_fixes(hits, tmp_path)
+        assert len(fixes) == 2 and skipped == []
+        apply_fixes(fixes, tmp_path)
+        assert "    #[account(has_one = admin, has_one = operator)]\n    pub config" in lib.read_text()
+
+    def test_stale_line(self, tmp_path, anchor_workspace):
+        # The file lost lines after it was parsed: the field is not where the IR says
+        lib = anchor_workspace(tmp_path, {"vault": SIGNER_SOURCE}, localnet=["vault"]) / LIB
+        ws = _Workspace(tmp_path)
+        acc = ws.programs[0].instructions[-1].account("admin")
+        lib.write_text("\n".join(SIGNER_SOURCE.splitlines()[:10]))
+        with pytest.raises(Unfixable, match="has no line"):
+            ws.field("programs/vault/src/lib.rs", acc)
+
+    def test_cli(self, tmp_path, anchor_workspace):
+        lib = anchor_workspace(tmp_path, {"vault": SIGNER_SOURCE}, localnet=["vault"]) / LIB
+        runner = CliRunner()
+        args = [str(tmp_path), "--no-cache", "--tool", "missing-signer"]
+        result = runner.invoke(scan, args + ["--fix", "--dry-run"])
+        assert result.exit_code == 0, result.output
+        assert "+    pub admin: Signer<'info>," in result.output and lib.read_text() == SIGNER_SOURCE
+
+        patch_file = tmp_path / "fixes.patch"
+        result = runner.invoke(scan, args + ["--json", "--fix-patch", str(patch_file)])
+        assert result.exit_code == 0, result.output
+        data = json.loads(result.output)["fixes"]
+        assert len(data["fixes"]) == 3 and data["applied"] == [] and patch_file.read_text() == data["patch"]
+        assert lib.read_text() == SIGNER_SOURCE
+
+        result = runner.invoke(scan, args + ["--json", "--fix"])
+        assert json.loads(result.output)["fixes"]["applied"] == ["programs/vault/src/lib.rs"]
+        assert "Signer<'info>" in lib.read_text()
+        assert json.loads(runner.invoke(scan, args + ["--json"]).output)["hypotheses"] == []
+
+    def test_program_account(self, tmp_path):
+        # A program cannot sign: retyping call_plugin's plugin_program would make the instruction uncallable
+        shutil.copytree(Path(__file__).parent / "fixtures" / "solana" / "cpi_router", tmp_path / "cpi_router")
+        hit = {"id": "hyp_1", "title": "Missing signer check on `plugin_program` in call_plugin",
+               "properties": {"source_tool": "missing-signer", "program": "cpi_router", "instruction": "call_plugin",
+                              "account": "plugin_program", "gates": [{"kind": "key-check"}]}}
+        fixes, skipped = plan_fixes([hit], tmp_path)
+        assert fixes == [] and "cannot sign" in skipped[0]["reason"]
+        assert render_patch(fixes, tmp_path) == ""
+
+        result = CliRunner().invoke(scan, [str(tmp_path), "--no-cache", "--tool", "missing-signer", "--fix",
+                                           "--dry-run"])
+        assert result.exit_code == 0, result.output
+        assert "+++" not in result.output and "Signer<" not in result.output
@@ -0,0 +1,205 @@
+"""
+Tests for instruction argument boundary fuzzing: types from an IDL or the
+IR, the boundary values tried, how results are classified, and the CLI.
+"""
+
+import json
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+import pytest
+import yaml
+from click.testing import CliRunner
+
+from commands.simulate import boundaries
+from extensions.ir.tx_size import PACKET_DATA_SIZE, legacy_tx_size
+from extensions.monitor.decode import anchor_discriminator
+from extensions.simulation import ExecutionBackend, TxResult, load_boundaries, load_idl
+from extensions.simulation.boundary import classify, rust_type
+
+VAULT = Path(__file__).parent / "fixtures" / "solana" / "anchor_vault"
+PROGRAM_ID = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+U64_MAX = 2 ** 64 - 1
+
+# Legacy (pre-0.30) IDL: camelCase names, publicKey and string-named defined types
+IDL = {
+    "version": "0.1.0",
+    "name": "vault",
+    "instructions": [
+        {"name": "deposit", "accounts": [{"name": "vaultState", "isMut": True, "isSigner": False},
+                                         {"name": "user", "isMut": False, "isSigner": True}],
+         "args": [{"name": "amount", "type": "u64"}]},
+        {"name": "configure", "accounts": [{"name": "vaultState", "isMut": True, "isSigner": False},
+                                           {"name": "admin", "isMut": False, "isSigner": True}],
+         "args": [{"name": "name", "type": "string"}, {"name": "params", "type": {"defined": "Params"}}]},
+    ],
+    "types": [{"name": "Params", "type": {"kind": "struct", "fields": [
+        {"name": "feeBps", "type": "u16"}, {"name": "cap", "type": {"option": "u64"}}]}}],
+}
+
+
+def _panic(message):
+    return TxResult(False, "Program failed to complete",
+                    logs=[f"Program log: panicked at '{message}', programs/vault/src/lib.rs:30:9"])
+
+
+class VaultBackend(ExecutionBackend):
+    """A vault whose deposit adds with overflow checks, whose withdraw checks the balance, and whose
+    configure copies the name into 32 bytes and divides by the cap without checking either."""
+
+    def start(self):
+        self.balance, self.keys = 100, {}
+
+    def keypair(self, name, lamports=0):
+        return self.keys.setdefault(name, "1" * 12 + name[:1].upper() * 20)
+
+    def set_account(self, pubkey, data, owner, lamports=0, executable=False):
+        pass
+
+    def send(self, instructions, payer, signers=()):
+        data = instructions[0].data
+        tag, args = data[:8], data[8:]
+        if tag == anchor_discriminator("deposit"):
+            amount = int.from_bytes(args[:8], "little")
+            if amount == 0:
+                return TxResult(False, "custom program error: 0x1770", logs=["Program log: ZeroAmount"])
+            if self.balance + amount > U64_MAX:
+                return _panic("attempt to add with overflow")
+            self.balance += amount
+        elif tag == anchor_discriminator("withdraw"):
+            amount = int.from_bytes(args[:8], "little")
+            if amount > self.balance:
+                return TxResult(False, "custom program error: 0x1771", logs=["Program log: InsufficientFunds"])
+        elif tag == anchor_discriminator("configure"):
+            length = int.from_bytes(args[:4], "little")
+            if length > 32:
+                return _panic("range end index 33 out of range for slice of length 32")
+            params = args[4 + length:]
+            if params[2] and int.from_bytes(params[3:11], "little") == 0:
+                return _panic("attempt to divide by zero")
+        else:
+            return TxResult(False, "custom program error: 0x65")
+        return TxResult(True, compute_units=3100)
+
+
+def _instruction(name, **extra):
+    return {"program_id": PROGRAM_ID, "instruction": name,
+            "accounts": [{"pubkey": "vault", "writable": True}, {"pubkey": "user", "signer": True}], **extra}
+
+
+CONFIG = {
+    "keypairs": {"user": 10 ** 10},
+    "instructions": [
+        _instruction("deposit", args={"amount": 10}),
+        _instruction("configure", args={"name": "main", "params": {"feeBps": 30, "cap": 1000}},
+                     allow={"params.feeBps": ["zero"]}),
+    ],
+}
+
+
+def _outcomes(report):
+    return {(c.arg, c.label): c.outcome for c in report.cases}
+
+
+class TestBoundaryFuzz:
+    """Test types, boundary cases and classification against the fake vault."""
+
+    def test_types(self, tmp_path):
+        assert rust_type("Option<Vec<u8>>") == {"option": "bytes"}
+        assert rust_type("[Pubkey; 3]") == {"array": ["pubkey", 3]}
+        assert rust_type("Vec<DepositParams>") == {"vec": {"defined": {"name": "DepositParams"}}}
+        (tmp_path / "vault.json").write_text(json.dumps(IDL))
+        instructions, types = load_idl(tmp_path / "vault.json")
+        assert instructions["deposit"]["accounts"] == ["vault_state", "user"]
+        assert instructions["configure"]["args"][1].ty == {"defined": {"name": "Params"}}
+        assert types["Params"]["fields"][1]["type"] == {"option": "u64"}
+        assert instructions["deposit"]["discriminator"] == anchor_discriminator("deposit")
+
+        # An argument of a type the IDL does not define skips the instruction instead of failing the run
+        migrate = {"name": "migrate", "accounts": [], "args": [{"name": "plan", "type": {"defined": "Plan"}}]}
+        (tmp_path / "vault.json").write_text(json.dumps({**IDL, "instructions": [migrate]}))
+        config = {"idl": "vault.json", "instructions": [_instruction("migrate")]}
+        (report,) = load_boundaries(config, base=tmp_path, make_backend=VaultBackend).run().reports
+        assert report.error == "cannot encode argument type Plan"
+
+    def test_classification(self):
+        assert classify(TxResult(True), "zero") == "unexpected-success"
+        assert classify(TxResult(True), "zero", ["zero"]) == "accepted"
+        assert classify(TxResult(True), "max-realloc") == "accepted"
+        assert classify(_panic("attempt to multiply with overflow"), "max") == "overflow"
+        assert classify(_panic("attempt to divide by zero"), "zero") == "panic"
+        # Checked arithmetic returning an error is a rejection
+        assert classify(TxResult(False, "custom program error: 0x1770", ["Program log: MathOverflow"]), "max") \
+            == "rejected"
+
+    def test_idl_instructions(self, tmp_path):
+        (tmp_path / "vault.json").write_text(json.dumps(IDL))
+        result = load_boundaries({**CONFIG, "idl": "vault.json"}, base=tmp_path, make_backend=VaultBackend).run()
+        deposit, configure = result.reports
+        assert _outcomes(deposit) == {
+            ("amount", "zero"): "rejected", ("amount", "one"): "accepted", ("amount", "max"): "overflow",
+            ("amount", "max-realloc"): "accepted", ("amount", "max-account-size"): "accepted",
+        }
+        outcomes = _outcomes(configure)
+        assert outcomes[("name", "empty")] == "accepted" and outcomes[("name", "max-length")] == "panic"
+        assert outcomes[("params.feeBps", "zero")] == "accepted"
+        assert outcomes[("params.feeBps", "max")] == "unexpected-success"
+        assert outcomes[("params.cap", "none")] == "accepted" and outcomes[("params.cap", "zero")] == "panic"
+
+        # The longest name still fits in one transaction (keys: vault, user as payer, program)
+        longest = next(c.value for c in configure.cases if c.label == "max-length")
+        spec = configure.instruction
+        data = len(spec.encode({**spec.baseline(), "name": longest}, lambda _: b"\0" * 32))
+        assert 32 < len(longest) and legacy_tx_size(1, 3, 2, data) <= PACKET_DATA_SIZE
+
+        findings = {(f.instruction.name, f.arg, f.outcome): f for f in result.findings()}
+        assert set(findings) == {
+            ("deposit", "amount", "overflow"), ("configure", "name", "panic"),
+            ("configure", "params.feeBps", "unexpected-success"), ("configure", "params.cap", "panic"),
+            ("configure", "params.cap", "unexpected-success"),
+        }
+        overflow = findings[("deposit", "amount", "overflow")].to_hypothesis()
+        assert overflow["vulnerability_type"] == "integer-overflow" and overflow["severity"] == "medium"
+        assert "attempt to add with overflow" in overflow["description"]
+        assert overflow["properties"]["source_tool"] == "arg-fuzz"
+
+    def test_ir_instruction(self):
+        config = {"program": str(VAULT), "instructions": [_instruction("withdraw", args={"amount": 50})]}
+        result = load_boundaries(config, make_backend=VaultBackend).run()
+        (report,) = result.reports
+        assert [a.name for a in report.instruction.transaction.accounts][:2] == ["vault", "authority"]
+        assert _outcomes(report)[("amount", "zero")] == "unexpected-success"
+        (finding,) = result.findings()
+        hyp = finding.to_hypothesis()
+        assert hyp["severity"] == "low" and hyp["title"] == "withdraw accepts boundary values of `amount` (zero)"
+        assert hyp["properties"]["source_files"] == ["programs/vault/src/lib.rs"]
+        assert hyp["properties"]["affected_lines"] == [34]
+
+        with pytest.raises(ValueError, match="neither in the IDL"):
+            load_boundaries({"instructions": [_instruction("initialize")]}, make_backend=VaultBackend)
+        config["instructions"].append(_instruction("initialize"))
+        _, initialize = load_boundaries(config, make_backend=VaultBackend).run().reports
+        assert initialize.error.startswith("the legitimate transaction fails") and not initialize.cases
+
+    def test_cli(self, tmp_path):
+        (tmp_path / "vault.json").write_text(json.dumps(IDL))
+        path = tmp_path / "instructions.yaml"
+        path.write_text(yaml.safe_dump({**CONFIG, "idl": "vault.json"}))
+        runner = CliRunner()
+        with patch("extensions.simulation.engine.backend_factory", return_value=VaultBackend):
+            result = runner.invoke(boundaries, [str(path), "--json"])
+            assert result.exit_code == 0, result.output
+            data = json.loads(result.output)
+            assert data["summary"]["instructions"] == 2 and data["summary"]["findings"] == 5
+            assert data["manifest"]["command"]["name"] == "sim boundaries"
+
+            with patch("commands.simulate.ProjectManager") as PM:
+                pm = MagicMock()
+                PM.return_value = pm
+                pm.get_project.return_value = {"path": str(tmp_path)}
+                result = runner.invoke(boundaries, [str(path), "--instruction", "deposit", "--project", "p",
+                                                    "--import-hypotheses"])
+        assert result.exit_code == 0, result.output
+        assert "5 boundary cases over 1 instructions" in result.output
+        store = json.loads((tmp_path / "hypotheses.json").read_text())["hypotheses"]
+        assert [h["vulnerability_type"] for h in store.values()] == ["integer-overflow"]
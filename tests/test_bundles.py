@@ -0,0 +1,210 @@
+"""
+Tests for content-addressed knowledge bundles: deterministic builds, the
+verified local store, baskerville.toml pins, loading bundle content into
+the knowledge base, and recording pinned digests in reports.
+"""
+
+import io
+import json
+import tarfile
+
+import pytest
+from click.testing import CliRunner
+
+from extensions.knowledge import KnowledgeBase
+from extensions.knowledge.bundles import (
+    BundleError, BundleStore, build_bundle, load_pins, read_bundle, resolve_bundles,
+)
+from extensions.reporting.manifest import build_manifest
+from extensions.reporting.signing import ruleset_version
+
+TEMPLATE = """// Acme vault drain PoC
+use anchor_lang::prelude::*;
+
+#[tokio::test]
+async fn test_acme_drain() {
+    let vault = {{VAULT_ACCOUNT=vault}};
+}
+"""
+
+
+def _source(tmp_path, body=TEMPLATE):
+    source = tmp_path / "acme"
+    (source / "templates" / "solana").mkdir(parents=True, exist_ok=True)
+    (source / "templates" / "solana" / "acme_drain.rs").write_text(body)
+    (source / "notes.md").write_text("not bundled\n")
+    return source
+
+
+def _pin(workspace, name, digest, **extra):
+    lines = ["[[bundle]]", f'name = "{name}"', f'digest = "{digest}"']
+    lines += [f'{key} = "{value}"' for key, value in extra.items()]
+    (workspace / "baskerville.toml").write_text("\n".join(lines) + "\n")
+
+
+class TestBundles:
+    """Test bundle building, storage, pinning and use."""
+
+    def test_build_is_deterministic(self, tmp_path):
+        source = _source(tmp_path)
+        archive, digest = build_bundle(source, tmp_path / "a", "acme-solana", "1.0.0")
+        again, same = build_bundle(source, tmp_path / "b", "acme-solana", "1.0.0")
+        assert digest == same and archive.read_bytes() == again.read_bytes()
+        assert archive.name == f"acme-solana-{digest[7:19]}.tar.gz"
+        manifest, members = read_bundle(archive)
+        assert list(manifest["files"]) == ["templates/solana/acme_drain.rs"]
+        assert "notes.md" not in members
+
+        # The digest names the content and the version
+        assert build_bundle(source, tmp_path / "c", "acme-solana", "1.0.1")[1] != digest
+        _source(tmp_path, TEMPLATE + "// changed\n")
+        assert build_bundle(source, tmp_path / "d", "acme-solana", "1.0.0")[1] != digest
+        with pytest.raises(BundleError, match="has no checklists/"):
+            build_bundle(tmp_path / "a", tmp_path / "e", "empty", "1")
+
+    def test_store_detects_tampering(self, tmp_path):
+        archive, digest = build_bundle(_source(tmp_path), tmp_path / "dist", "acme-solana", "1.0.0")
+        store = BundleStore(tmp_path / "store")
+        added = store.add(archive)
+        assert (added.name, added.version, added.digest, added.signed) == ("acme-solana", "1.0.0", digest, False)
+        assert store.get(digest).files == added.files and [b.digest for b in store.list()] == [digest]
+        assert store.get("sha256:" + "0" * 64) is None
+        with pytest.raises(BundleError, match="not a sha256"):
+            store.get("acme-solana")
+
+        # Editing stored content stops the bundle loading
+        (added.root / "templates" / "solana" / "acme_drain.rs").write_text("// drained\n")
+        with pytest.raises(BundleError, match="checksum mismatch: templates/solana/acme_drain.rs"):
+            store.get(digest)
+        assert store.list() == []
+
+    def test_rejects_unsafe_and_altered_archives(self, tmp_path):
+        archive, _ = build_bundle(_source(tmp_path), tmp_path / "dist", "acme-solana", "1.0.0")
+        manifest, members = read_bundle(archive)
+
+        def repack(entries):
+            path = tmp_path / "bad.tar.gz"
+            with tarfile.open(path, "w:gz") as tar:
+                for name, data in entries.items():
+                    info = tarfile.TarInfo(name)
+                    info.size = len(data)
+                    tar.addfile(info, io.BytesIO(data))
+            return path
+
+        with pytest.raises(BundleError, match="unsafe member path: ../evil.rs"):
+            read_bundle(repack({**members, "../evil.rs": b""}))
+        altered = {**members, "templates/solana/acme_drain.rs": b"// other\n"}
+        with pytest.raises(BundleError, match="checksum mismatch"):
+            read_bundle(repack(altered))
+        with pytest.raises(BundleError, match="not in manifest: tips/extra.yaml"):
+            read_bundle(repack({**members, "tips/extra.yaml": b"tips: []\n"}))
+        # Re-digesting the files without updating the manifest digest is caught too
+        forged = {**manifest, "files": {"templates/solana/acme_drain.rs": "0" * 64}}
+        with pytest.raises(BundleError, match="manifest digest"):
+            read_bundle(repack({**altered, "bundle.json": json.dumps(forged).encode()}))
+
+    def test_pins(self, tmp_path):
+        archive, digest = build_bundle(_source(tmp_path), tmp_path / "dist", "acme-solana", "1.0.0")
+        store = BundleStore(tmp_path / "store")
+        workspace = tmp_path / "ws"
+        workspace.mkdir()
+        assert resolve_bundles(workspace, store) == []
+
+        _pin(workspace, "acme-solana", digest)
+        with pytest.raises(BundleError, match="is not in .*bundle add"):
+            resolve_bundles(workspace, store)
+        # A pinned archive path is added on first use
+        _pin(workspace, "acme-solana", digest, path=f"../dist/{archive.name}")
+        assert load_pins(workspace)[0].path == workspace / ".." / "dist" / archive.name
+        assert [b.digest for b in resolve_bundles(workspace, store)] == [digest]
+
+        _pin(workspace, "acme-evm", digest)
+        with pytest.raises(BundleError, match="is named acme-solana"):
+            resolve_bundles(workspace, store)
+        other, other_digest = build_bundle(_source(tmp_path, TEMPLATE + "//\n"), tmp_path / "v2", "acme-solana", "2")
+        _pin(workspace, "acme-solana", digest.replace(digest[-4:], "0000"), path=f"../v2/{other.name}")
+        with pytest.raises(BundleError, match=f"but .* is {other_digest}"):
+            resolve_bundles(workspace, store)
+        (workspace / "baskerville.toml").write_text("[[bundle]]\nname = \"acme-solana\"\n")
+        with pytest.raises(BundleError, match="needs a name and a digest"):
+            load_pins(workspace)
+
+    def test_knowledge_base_loads_bundles(self, tmp_path):
+        archive, digest = build_bundle(_source(tmp_path), tmp_path / "dist", "acme-solana", "1.0.0")
+        bundle = BundleStore(tmp_path / "store").add(archive)
+        kb = KnowledgeBase(bundles=[bundle])
+        assert kb.templates.get("acme_drain") is not None
+        assert KnowledgeBase().templates.get("acme_drain") is None
+        assert ruleset_version(bundles=[digest]) != ruleset_version()
+
+    def test_manifest_records_pins(self, tmp_path, monkeypatch):
+        monkeypatch.setenv("HOME", str(tmp_path))
+        _, digest = build_bundle(_source(tmp_path), tmp_path / "dist", "acme-solana", "1.0.0")
+        repo = tmp_path / "src"
+        repo.mkdir()
+        unpinned = build_manifest("reporting heatmap", {}, source=repo)
+        assert "bundles" not in unpinned
+
+        _pin(repo, "acme-solana", digest)
+        pinned = build_manifest("reporting heatmap", {}, source=repo)
+        assert pinned["bundles"] == [{"name": "acme-solana", "digest": digest}]
+        assert pinned["kb_version"] != unpinned["kb_version"]
+
+    def test_signed_bundles(self, tmp_path):
+        pytest.importorskip("cryptography")
+        from extensions.reporting import generate_keypair
+
+        keys = tmp_path / "keys"
+        generate_keypair(keys / "acme.pem", keys / "acme.pub.pem")
+        generate_keypair(keys / "other.pem", keys / "other.pub.pem")
+        source = _source(tmp_path)
+        archive, digest = build_bundle(source, tmp_path / "dist", "acme-solana", "1.0.0", key=keys / "acme.pem")
+        # Signing does not change what the bundle is
+        assert build_bundle(source, tmp_path / "plain", "acme-solana", "1.0.0")[1] == digest
+
+        store = BundleStore(tmp_path / "store")
+        with pytest.raises(BundleError, match="does not verify"):
+            store.add(archive, public_key=keys / "other.pub.pem")
+        assert store.list() == []
+        bundle = store.add(archive, public_key=keys / "acme.pub.pem")
+        assert bundle.signed and bundle.signer()
+
+        workspace = tmp_path / "ws"
+        workspace.mkdir()
+        _pin(workspace, "acme-solana", digest, key="../keys/other.pub.pem")
+        with pytest.raises(BundleError, match="does not verify"):
+            resolve_bundles(workspace, store)
+        _pin(workspace, "acme-solana", digest, key="../keys/acme.pub.pem")
+        assert resolve_bundles(workspace, store) == [bundle]
+
+    def test_cli(self, tmp_path, monkeypatch):
+        from commands.bundles import bundle
+        from commands.knowledge import kb
+
+        monkeypatch.setenv("HOME", str(tmp_path))
+        runner = CliRunner()
+        result = runner.invoke(bundle, ["build", str(_source(tmp_path)), "-n", "acme-solana", "-v", "1.0.0",
+                                        "-o", str(tmp_path / "dist")])
+        assert result.exit_code == 0, result.output
+        assert '[[bundle]]\nname = "acme-solana"\ndigest = "sha256:' in result.output
+        archive = next((tmp_path / "dist").iterdir())
+
+        workspace = tmp_path / "ws"
+        workspace.mkdir()
+        digest = read_bundle(archive)[0]["digest"]
+        _pin(workspace, "acme-solana", digest)
+        result = runner.invoke(bundle, ["verify", str(workspace)])
+        assert result.exit_code == 1 and "bundle add" in result.output
+
+        result = runner.invoke(bundle, ["add", str(archive)])
+        assert result.exit_code == 0 and "Added acme-solana 1.0.0" in result.output
+        result = runner.invoke(bundle, ["list", "--json"])
+        assert [b["digest"] for b in json.loads(result.output)] == [digest]
+        result = runner.invoke(bundle, ["verify", str(workspace)])
+        assert result.exit_code == 0 and digest in result.output
+
+        # kb render picks up the workspace's pinned templates
+        result = runner.invoke(kb, ["render", "acme_drain", "-w", str(workspace),
+                                    "-s", "VAULT_ACCOUNT=9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin"])
+        assert result.exit_code == 0, result.output
+        assert "Wrote" in result.output
@@ -0,0 +1,145 @@
+"""
+Tests for canary account planning and the canary-touched rule.
+
+Verifies findings pinned to an instruction get a canary in the state slot
+their exploit goes through (tripwire PDAs where the state is derived,
+decoy vaults otherwise) with the instruction creating it, that recorded
+addresses survive regeneration and become rules alerting on failed probes
+too, and the CLI and Geyser output.
+"""
+
+import json
+from pathlib import Path
+from unittest.mock import patch
+
+import pytest
+import yaml
+from click.testing import CliRunner
+
+from commands.monitor import canaries, rules
+from extensions.ir import load_programs
+from extensions.ir.anchor_config import b58encode
+from extensions.monitor import CanaryPlan, CanaryTouchedRule, RuleSet, decode_transaction, generate_geyser_plugin
+from extensions.monitor import anchor_discriminator, plan_canaries, rules_from_findings
+
+VAULT = Path(__file__).resolve().parent / "fixtures" / "solana" / "anchor_vault"
+PROGRAM_ID = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+ATTACKER = "Atk1111111111111111111111111111111111111111"
+VAULT_STATE = "Vau1t11111111111111111111111111111111111111"
+
+HYPOTHESES = {
+    "hyp_1": {"title": "Missing signer check on authority in withdraw", "severity": "critical",
+              "vulnerability_type": "access_control", "status": "confirmed"},
+    "hyp_2": {"title": "Token accounts may alias", "severity": "high",
+              "vulnerability_type": "duplicate-mutable-accounts", "properties": {"instruction": "deposit", "accounts": ["user_token", "vault_token"]}},
+    "hyp_3": {"title": "Vault drain in withdraw", "severity": "high", "vulnerability_type": "economic"},
+    "hyp_4": {"title": "Missing signer check in deposit", "severity": "high", "status": "rejected"},
+    "hyp_5": {"title": "Fee rounding", "severity": "low", "vulnerability_type": "arithmetic"},
+}
+
+
+def _program():
+    return load_programs(VAULT)[0]
+
+
+def _probe_tx(err=None):
+    """A `withdraw` of the canary vault signed by the attacker as its authority."""
+    keys = [ATTACKER, VAULT_STATE, PROGRAM_ID]
+    data = b58encode(anchor_discriminator("withdraw") + (5).to_bytes(8, "little"))
+    return {
+        "slot": 100,
+        "transaction": {
+            "signatures": ["probe"],
+            "message": {"header": {"numRequiredSignatures": 1, "numReadonlySignedAccounts": 0,
+                                   "numReadonlyUnsignedAccounts": 1},
+                        "accountKeys": keys,
+                        "instructions": [{"programIdIndex": 2, "data": data, "accounts": [1, 0, 0]}]},
+        },
+        "meta": {"err": err, "logMessages": []},
+    }
+
+
+class TestCanaries:
+    """Test planning, rules, the CLI and the Geyser plugin."""
+
+    def test_plan(self):
+        plan = plan_canaries(HYPOTHESES, _program())
+        assert plan.program == "vault" and plan.program_id == PROGRAM_ID
+        assert [(c.id, c.kind, c.account_type, c.findings, c.severity) for c in plan.canaries] == [
+            ("canary-withdraw-vault", "tripwire-pda", "Vault", ["hyp_1", "hyp_3"], "critical"),
+            ("canary-deposit-user-token", "decoy-vault", "TokenAccount", ["hyp_2"], "high")]
+        pda, decoy = plan.canaries
+        assert pda.setup.instruction == "initialize" and pda.setup.signers == ["authority"]
+        assert pda.setup.seeds == ['b"vault"', "authority.key().as_ref()"]
+        assert pda.steps()[0].startswith("Call `initialize` with a fresh canary key as `authority`")
+        assert decoy.setup.instruction is None and "spl-token create-account" in decoy.steps()[0]
+        assert "Fund it" in decoy.steps()[1]
+        assert plan_canaries(HYPOTHESES, _program(), min_severity="critical").canaries[0].findings == ["hyp_1"]
+
+    def test_rules(self):
+        plan = plan_canaries(HYPOTHESES, _program())
+        assert plan.rules() == [] and len(plan.pending()) == 2
+        plan.canaries[0].address = VAULT_STATE
+        loaded = CanaryPlan.from_dict(yaml.safe_load(yaml.safe_dump(plan.to_dict(), sort_keys=False)))
+        assert loaded.to_dict() == plan.to_dict()
+        fresh = plan_canaries(HYPOTHESES, _program())
+        fresh.merge(loaded)
+        assert fresh.canaries[0].address == VAULT_STATE and fresh.pending() == [fresh.canaries[1]]
+
+        (rule,) = fresh.rules()
+        assert isinstance(rule, CanaryTouchedRule) and rule.severity == "critical" and rule.finding == "hyp_1"
+        ruleset = rules_from_findings({}, _program())
+        ruleset.rules = [rule]
+        ruleset = RuleSet.from_dict(ruleset.to_dict())
+        # A failed probe alerts too
+        failed = _probe_tx(err={"InstructionError": [0, {"Custom": 2001}]})
+        (alert,) = ruleset.evaluate(decode_transaction(failed, PROGRAM_ID, ruleset.discriminators))
+        assert alert.kind == "canary-touched" and alert.instruction == "withdraw"
+        assert alert.details["canary"] == "canary-withdraw-vault" and alert.details["fee_payer"] == ATTACKER
+        assert alert.details["position"] == 0 and alert.details["success"] is False
+        for bad in ({"format": "other"}, {"format": "baskerville-canaries"},
+                    {**plan.to_dict(), "canaries": [{**plan.canaries[0].to_dict(), "kind": "honeypot"}]},
+                    {**plan.to_dict(), "canaries": [plan.canaries[0].to_dict()] * 2}):
+            with pytest.raises(ValueError):
+                CanaryPlan.from_dict(bad)
+
+    def test_cli(self, tmp_path):
+        (tmp_path / "hypotheses.json").write_text(json.dumps({"hypotheses": HYPOTHESES}))
+        runner = CliRunner()
+        with patch("commands.monitor.ProjectManager") as PM:
+            PM.return_value.get_project.return_value = {"path": str(tmp_path), "source_path": str(VAULT)}
+            result = runner.invoke(canaries, ["demo"])
+            assert result.exit_code == 0, result.output
+            assert "canary-withdraw-vault" in result.output and "Call `initialize`" in result.output
+            plan_file = tmp_path / "monitoring" / "vault.canaries.yaml"
+            data = yaml.safe_load(plan_file.read_text())
+            data["canaries"][0]["address"] = VAULT_STATE
+            plan_file.write_text(yaml.safe_dump(data, sort_keys=False))
+            # Regenerating keeps the recorded address
+            result = runner.invoke(canaries, ["demo", "--json"])
+            assert json.loads(result.output)["canaries"][0]["address"] == VAULT_STATE
+
+            result = runner.invoke(rules, ["demo", "--canaries", str(plan_file)])
+            assert result.exit_code == 0, result.output
+            assert "canary-deposit-user-token has no address yet" in result.output
+            (other := tmp_path / "other.yaml").write_text(yaml.safe_dump({**data, "program": "router"}))
+            assert runner.invoke(rules, ["demo", "--canaries", str(other)]).exit_code == 1
+        data = yaml.safe_load((tmp_path / "monitoring" / "vault.rules.yaml").read_text())
+        canary = next(r for r in data["rules"] if r["type"] == "canary-touched")
+        assert canary == {"id": "canary-withdraw-vault", "type": "canary-touched", "severity": "critical",
+                          "title": canary["title"], "finding": "hyp_1", "include_inner": True,
+                          "account": VAULT_STATE, "canary": "canary-withdraw-vault"}
+
+    def test_geyser(self):
+        plan = plan_canaries(HYPOTHESES, _program())
+        ruleset = rules_from_findings({}, _program())
+        ruleset.rules = [plan.canaries[0].rule()]
+        with pytest.raises(ValueError):
+            generate_geyser_plugin(ruleset)
+        plan.canaries[0].address = VAULT_STATE
+        ruleset.rules = plan.rules()
+        files = generate_geyser_plugin(ruleset)
+        assert (f'kind: RuleKind::CanaryTouched {{ account: "{VAULT_STATE}", canary: "canary-withdraw-vault" }}'
+                in files["src/rules.rs"])
+        assert 'RuleKind::CanaryTouched { .. } => "canary-touched"' in files["src/lib.rs"]
+
@@ -0,0 +1,99 @@
+"""
+Tests for finding confidence: levels and thresholds, calibration of detector
+hits by precision tier, the shipped calibration against the benchmark corpus,
+and the confidence filters.
+"""
+
+import json
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+import pytest
+from click.testing import CliRunner
+
+from commands.calibrate import calibrate
+from commands.project import hypotheses
+from extensions.static import StaticAnalysisPipeline
+from extensions.static.confidence import (
+    Calibration,
+    calibrate_hypothesis,
+    confidence_level,
+    load_calibration,
+    measure,
+    parse_confidence,
+)
+
+FIXTURES = Path(__file__).resolve().parent / "fixtures"
+
+
+class TestConfidence:
+    """Test levels, thresholds and per-hit calibration."""
+
+    def test_levels_and_thresholds(self):
+        assert [confidence_level(v) for v in (0.85, 0.8, 0.6, 0.2, None)] == ["high", "high", "medium", "low", "low"]
+        assert parse_confidence("Medium") == 0.5 and parse_confidence("70%") == 0.7 and parse_confidence("0.3") == 0.3
+        assert parse_confidence(None) is None
+        with pytest.raises(ValueError, match="invalid confidence"):
+            parse_confidence("sure")
+        with pytest.raises(ValueError, match="invalid confidence"):
+            parse_confidence("1.5")
+
+    def test_calibrate_hit(self):
+        calibration = Calibration(detectors={"cpi-privileges": {"dataflow": {"hits": 6, "true_positives": 6}}})
+        hit = {"confidence": 0.6, "properties": {"source_tool": "cpi-privileges"}}
+        calibrate_hypothesis(hit, calibration=calibration)
+        # (6 + 4 * 0.7) / (6 + 4)
+        assert hit["confidence"] == 0.88 and hit["properties"]["precision"] == "dataflow"
+        assert hit["properties"]["detector_confidence"] == 0.6
+        # Unmeasured tiers fall back to the prior; a hit may declare its own tier
+        guarded = {"id": "static_unsafe-rust_0123abcd", "properties": {"precision": "syntactic"}}
+        assert calibrate_hypothesis(guarded, calibration=calibration)["confidence"] == 0.35
+        llm = {"confidence": 0.9, "properties": {}}
+        assert calibrate_hypothesis(llm, calibration=calibration) == {"confidence": 0.9, "properties": {}}
+
+    def test_shipped_calibration_matches_corpus(self):
+        shipped = load_calibration()
+        measured, rows = measure(tools=set(shipped.detectors))
+        assert measured.to_dict() == shipped.to_dict()
+        # Unlabelled hits are the corpus's false positives
+        misses = {(r["tool"], r["type"]) for r in rows if not r["true_positive"]}
+        assert ("unsafe-rust", "memory-safety-pointer-offset") in misses
+        assert ("anchor-toml", "anchor-config-cluster-id-mismatch") in misses
+
+    def test_pipeline_hits_carry_precision(self):
+        result = StaticAnalysisPipeline(chain_id="solana").run(FIXTURES / "solana" / "zero_copy_pool")
+        by_type = {h["vulnerability_type"]: h for h in result.hypotheses
+                   if h["properties"]["source_tool"] == "unsafe-rust"}
+        tainted, guarded = by_type["memory-safety-raw-slice-length"], by_type["memory-safety-pointer-offset"]
+        assert tainted["properties"]["precision"] == "dataflow" and guarded["properties"]["precision"] == "syntactic"
+        assert tainted["confidence"] > guarded["confidence"]
+        assert confidence_level(guarded["confidence"]) == "low"
+
+
+class TestConfidenceCli:
+    """Test the calibrate command and the hypothesis filter."""
+
+    def test_calibrate(self, tmp_path):
+        out = tmp_path / "calibration.yaml"
+        result = CliRunner().invoke(calibrate, ["-o", str(out)])
+        assert result.exit_code == 0, result.output
+        assert "privilege-paths" in result.output
+        assert load_calibration(out).counts("privilege-paths", "dataflow") == {"hits": 6, "true_positives": 6}
+        data = json.loads(CliRunner().invoke(calibrate, ["--json"]).output)
+        assert data["calibration"]["corpus"] == "solana-fixtures-v1" and data["hits"]
+        assert CliRunner().invoke(calibrate, [str(tmp_path / "missing.yaml")]).exit_code == 1
+
+    def test_hypotheses_filter(self, tmp_path):
+        store = {"hypotheses": {
+            "h_high": {"title": "Sure thing", "confidence": 0.9, "properties": {"precision": "dataflow"}},
+            "h_low": {"title": "Long shot", "confidence": 0.3},
+        }}
+        (tmp_path / "hypotheses.json").write_text(json.dumps(store))
+        with patch("commands.project.ProjectManager") as PM:
+            pm = MagicMock()
+            PM.return_value = pm
+            pm.get_project.return_value = {"path": str(tmp_path)}
+            result = CliRunner().invoke(hypotheses, ["p", "--min-confidence", "high"])
+            assert result.exit_code == 0, result.output
+            assert "Sure thing" in result.output and "Long shot" not in result.output
+            assert CliRunner().invoke(hypotheses, ["p", "--min-confidence", "maybe"]).exit_code != 0
@@ -0,0 +1,104 @@
+"""
+Tests for the CosmWasm front-end.
+
+Uses the workspace fixture under tests/fixtures/cosmwasm/cw_vault: a plain
+contract whose messages live in a shared package, and a Sylvia contract.
+"""
+
+from pathlib import Path
+
+from extensions.ir import CosmWasmFrontend, load_programs
+from extensions.ir.cosmwasm import find_cosmos_msgs
+
+
+WORKSPACE = Path(__file__).resolve().parent / "fixtures" / "cosmwasm" / "cw_vault"
+
+
+class TestCosmWasmFrontend:
+    """Test message-enum-to-handler mapping and schema attachment."""
+
+    def setup_method(self):
+        programs = {p.name: p for p in CosmWasmFrontend().load(WORKSPACE)}
+        assert set(programs) == {"cw-vault", "counter"}
+        self.vault = programs["cw-vault"]
+        self.counter = programs["counter"]
+
+    def test_entry_points_and_names(self):
+        assert self.vault.chain == "cosmwasm"
+        assert set(self.vault.metadata["entry_points"]) == {"instantiate", "execute", "query"}
+        assert [ix.name for ix in self.vault.instructions] == [
+            "instantiate", "deposit", "withdraw", "update_config",
+            "query_config", "query_balance", "emergency_withdraw",
+        ]
+
+    def test_handlers_resolved_across_modules(self):
+        withdraw = self.vault.instruction("withdraw")
+        assert (withdraw.handler, withdraw.file) == ("withdraw", "src/execute.rs")
+        assert withdraw.discriminator == "withdraw"
+        assert [(a.name, a.ty) for a in withdraw.args] == [("amount", "Uint128")]
+        # Query handlers live in an inline `mod query` next to the entry point
+        balance = self.vault.instruction("query_balance")
+        assert (balance.handler, balance.file) == ("balance", "src/contract.rs")
+        assert balance.accounts == []
+
+    def test_message_from_workspace_package(self):
+        deposit = self.vault.instruction("deposit")
+        assert deposit.properties["message"] == "ExecuteMsg"
+        assert deposit.properties["message_file"] == "../../packages/vault-types/src/msg.rs"
+        init = self.vault.instruction("instantiate")
+        assert [a.name for a in init.args] == ["owner", "denom"]
+
+    def test_sender_and_funds(self):
+        update = self.vault.instruction("update_config")
+        assert update.account("sender").is_signer is True
+        assert update.properties.get("sender_checked") is True
+        assert "sender_checked" not in self.vault.instruction("withdraw").properties
+        assert self.vault.instruction("deposit").properties.get("reads_funds") is True
+
+    def test_outgoing_messages(self):
+        calls = self.vault.instruction("withdraw").calls
+        assert [(c.kind, c.target) for c in calls] == [
+            ("bank_send", "info.sender.to_string()"),
+            ("wasm_execute", "hook.to_string()"),
+        ]
+
+    def test_storage_layout(self):
+        storage = self.vault.account_type("storage")
+        assert [(f.name, f.ty) for f in storage.fields] == [("CONFIG", "Item<Config>"), ("BALANCES", "Map<&Addr, Uint128>")]
+        assert storage.properties["keys"] == {"CONFIG": "config", "BALANCES": "balances"}
+
+    def test_schema_attached_and_schema_only_variants(self):
+        assert self.vault.metadata["schema_files"] == ["schema/cw-vault.json"]
+        withdraw = self.vault.instruction("withdraw")
+        assert withdraw.properties["schema"]["required"] == ["amount"]
+        stale = self.vault.instruction("emergency_withdraw")
+        assert stale.properties["schema_only"] is True
+        assert [(a.name, a.ty) for a in stale.args] == [("recipient", "string")]
+
+    def test_sylvia_contract(self):
+        assert self.counter.framework == "sylvia"
+        assert [ix.name for ix in self.counter.instructions] == ["instantiate", "increment", "reset", "query_count"]
+        reset = self.counter.instruction("reset")
+        assert reset.properties["entry_point"] == "execute"
+        assert [a.name for a in reset.args] == ["count"]
+        assert self.counter.instruction("query_count").accounts == []
+
+
+class TestCosmosMsgs:
+    """Test outgoing message extraction on snippets."""
+
+    def test_ibc_and_smart_query(self):
+        body = '''
+    let price: Uint128 = deps.querier.query_wasm_smart(oracle, &OracleQuery::Price {})?;
+    let msg = IbcMsg::Transfer { channel_id: CHANNEL.to_string(), to_address: recipient, amount, timeout };
+'''
+        calls = find_cosmos_msgs(body, base_line=10)
+        assert [(c.kind, c.target, c.line) for c in calls] == [
+            ("wasm_query", "oracle", 11),
+            ("ibc_transfer", "recipient", 12),
+        ]
+
+
+def test_load_programs_selects_cosmwasm():
+    names = sorted(p.name for p in load_programs(WORKSPACE))
+    assert names == ["counter", "cw-vault"]
@@ -0,0 +1,53 @@
+"""
+Tests for the CosmWasm template pack: the `cosmwasm` namespace, the classes
+its templates hang off, and bech32 checks when rendering.
+"""
+
+from pathlib import Path
+
+from extensions.knowledge import Chain, ClassLoader, TemplateLoader, TemplateRegistry
+from extensions.knowledge.render import TemplateEngine
+
+
+KB_DIR = Path(__file__).resolve().parents[1] / "extensions" / "knowledge"
+ADMIN = "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu"
+
+
+class TestCosmwasmTemplates:
+    """Test the CosmWasm namespace, classes and rendering."""
+
+    def setup_method(self):
+        self.registry = TemplateRegistry(TemplateLoader(KB_DIR / "templates"))
+
+    def test_namespace(self):
+        assert Chain.parse("CosmWasm") is Chain.COSMWASM and Chain.COSMWASM.template_suffix == ".rs"
+        assert self.registry.by_chain()[Chain.COSMWASM] == [
+            "improper_reply", "missing_sender_validation", "unbounded_iteration"]
+        for template in self.registry.query(chain="cosmwasm"):
+            assert Path(template.path).parent.name == "cosmwasm" and "cosmwasm" in template.tags
+            assert template.severity and template.vuln_class and "cw_multi_test" in template.template
+
+    def test_classes(self):
+        classes = ClassLoader(KB_DIR / "classes")
+        for alias, class_id, template_id in [
+            ("missing-sender-validation", "unauthenticated-privileged-action", "missing_sender_validation"),
+            ("unbounded_iteration", "unbounded-resource-consumption", "unbounded_iteration"),
+            ("improper-reply-handling", "unchecked-call-result", "improper_reply"),
+        ]:
+            vuln_class = classes.resolve(alias, "cosmwasm")
+            assert vuln_class.id == class_id, alias
+            assert vuln_class.variant("cosmwasm").templates == [template_id]
+            assert [t.id for t in self.registry.query(vuln_class=alias, chain=Chain.COSMWASM)] == [template_id]
+        assert "info.sender" in classes.get("unauthenticated-privileged-action").fix_for("cosmwasm")
+
+    def test_render(self):
+        engine = TemplateEngine(self.registry.get("missing_sender_validation"))
+        context = {"contract_crate": "cw_vault", "privileged_msg": "UpdateConfig", "admin_addr": "0xabc"}
+        assert engine.validate(context)[1] == {"ADMIN_ADDR": "'0xabc' is not a bech32 address"}
+        text = engine.render({**context, "admin_addr": ADMIN})
+        assert text.startswith("// Generated by Baskerville") and f'Addr::unchecked("{ADMIN}")' in text
+        assert "ExecuteMsg::UpdateConfig" in text
+
+        reply = TemplateEngine(self.registry.get("improper_reply")).render(
+            {"contract_crate": "cw_vault", "trigger_msg": "Withdraw"})
+        assert "coins(1_000, \"uatom\")" in reply and "{{" not in reply
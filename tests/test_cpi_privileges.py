@@ -0,0 +1,123 @@
+"""
+Tests for the built-in CPI signer-privilege propagation analyzer.
+"""
+
+from pathlib import Path
+
+from extensions.static.cpi_privileges import CpiPrivilegeAnalyzer
+from extensions.static.pipeline import StaticAnalysisPipeline
+
+FIXTURE = Path(__file__).parent / "fixtures" / "solana" / "cpi_router"
+
+NATIVE_RELAY = '''
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+    pubkey::Pubkey,
+};
+
+entrypoint!(process_instruction);
+
+pub fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+    match instruction_data[0] {
+        0 => process_relay(program_id, accounts, &instruction_data[1..]),
+        1 => process_pay(program_id, accounts),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn process_relay(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let vault = next_account_info(iter)?;
+    let relay_authority = next_account_info(iter)?;
+    let target_program = next_account_info(iter)?;
+    let ix = Instruction {
+        program_id: *target_program.key,
+        accounts: vec![AccountMeta::new(*vault.key, false), AccountMeta::new_readonly(*relay_authority.key, true)],
+        data: data.to_vec(),
+    };
+    invoke_signed(&ix, &[vault.clone(), relay_authority.clone()], &[&[b"relay", &[255]]])
+}
+
+fn process_pay(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let vault = next_account_info(iter)?;
+    let relay_authority = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    if *token_program.key != spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let ix = spl_token::instruction::transfer(token_program.key, vault.key, vault.key, relay_authority.key, &[], 1)?;
+    invoke_signed(&ix, &[vault.clone(), relay_authority.clone()], &[&[b"relay", &[255]]])
+}
+'''
+
+
+class TestCpiPrivilegeAnalyzer:
+    """Test privilege tracking across CPI boundaries and the resulting findings."""
+
+    def setup_method(self):
+        self.findings, self.metadata = CpiPrivilegeAnalyzer().run(FIXTURE)
+        self.sites = {s["instruction"]: s for s in self.metadata["cpi_sites"]}
+        self.by_ix = {f.site.instruction: f for f in self.findings}
+
+    def test_sites_record_forwarded_privileges(self):
+        assert self.metadata["signed_cpis"] == 4
+        swap = self.sites["route_swap"]
+        assert swap["kind"] == "cpi_signed"
+        assert swap["target_account"] == "swap_program"
+        assert {a["name"]: a["origin"] for a in swap["forwarded"]} == {
+            "vault": "account", "destination": "account", "vault_authority": "pda",
+        }
+        assert all(a["writable"] for a in swap["forwarded"] if a["name"] != "vault_authority")
+
+    def test_target_validation(self):
+        assert self.sites["route_swap"]["target_check"] == "unchecked"
+        assert self.sites["safe_transfer"]["target_check"] == "program-type"
+        # require_keys_eq! against a constant id in the handler
+        assert self.sites["call_plugin"]["target_check"] == "key-check"
+        assert set(self.by_ix) == {"route_swap", "forward", "notify"}
+
+    def test_pda_signer_to_unvalidated_program(self):
+        finding = self.by_ix["route_swap"]
+        assert finding.kind == "pda-signer-to-unvalidated-program"
+        hyp = finding.to_hypothesis()
+        assert hyp["severity"] == "high"
+        assert hyp["vulnerability_type"] == "arbitrary-cpi"
+        assert hyp["properties"]["pda_signers"] == ["vault_authority"]
+        assert hyp["properties"]["source_files"] == ["src/lib.rs"]
+        assert hyp["properties"]["affected_lines"] == [23]
+
+    def test_remaining_accounts_and_caller_data(self):
+        finding = self.by_ix["forward"]
+        assert finding.site.reasons == ["pda-signer-with-remaining-accounts", "pda-signer-with-attacker-data"]
+        assert finding.site.data_source == "data"
+        assert finding.severity == "medium"
+
+    def test_forwarded_signer(self):
+        finding = self.by_ix["notify"]
+        assert finding.kind == "signer-to-unvalidated-program"
+        assert finding.severity == "low"
+        assert "notify" not in {f.site.instruction for f in CpiPrivilegeAnalyzer(report_signers=False).run(FIXTURE)[0]}
+
+    def test_native_program(self, tmp_path):
+        crate = tmp_path / "relay"
+        (crate / "src").mkdir(parents=True)
+        (crate / "Cargo.toml").write_text('[package]\nname = "relay"\nversion = "0.1.0"\n')
+        (crate / "src" / "lib.rs").write_text(NATIVE_RELAY)
+        findings, metadata = CpiPrivilegeAnalyzer().run(tmp_path)
+        relay, pay = metadata["cpi_sites"]
+        assert relay["target_account"] == "target_program"
+        assert relay["data_source"] == "data"
+        assert [a["name"] for a in relay["forwarded"] if a["origin"] == "pda"] == ["relay_authority"]
+        assert pay["target_check"] == "key-check"
+        assert len(findings) == 1
+        assert findings[0].to_hypothesis()["properties"]["source_files"] == ["relay/src/lib.rs"]
+
+    def test_registered_for_solana(self):
+        pipeline = StaticAnalysisPipeline(chain_id="solana")
+        assert "cpi-privileges" in pipeline.runners
+        assert pipeline.check_tools()["cpi-privileges"][0]
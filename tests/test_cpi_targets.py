@@ -0,0 +1,144 @@
+"""
+Tests for the unvalidated CPI target detector.
+"""
+
+from pathlib import Path
+
+from extensions.static import UnvalidatedCpiTargetDetector
+from extensions.static.cpi_targets import TEMPLATE_FILE
+from extensions.static.pipeline import StaticAnalysisPipeline
+
+ROOT = Path(__file__).resolve().parents[1]
+FIXTURE = Path(__file__).parent / "fixtures" / "solana" / "cpi_router"
+
+PINGER = '''use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke;
+
+declare_id!("Ping111111111111111111111111111111111111111");
+
+pub const ORACLE_ID: Pubkey = pubkey!("Orac111111111111111111111111111111111111111");
+
+#[program]
+pub mod pinger {
+    use super::*;
+
+    pub fn ping(ctx: Context<Ping>) -> Result<()> {
+        let ix = Instruction {
+            program_id: ctx.accounts.system_program.key(),
+            accounts: vec![],
+            data: vec![],
+        };
+        invoke(&ix, &[])?;
+        Ok(())
+    }
+
+    pub fn poke(ctx: Context<Poke>) -> Result<()> {
+        let ix = Instruction {
+            program_id: ctx.accounts.oracle_program.key(),
+            accounts: vec![],
+            data: vec![],
+        };
+        invoke(&ix, &[])?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Ping<'info> {
+    /// CHECK: meant to be the system program
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Poke<'info> {
+    /// CHECK: pinned by the constraint
+    #[account(constraint = oracle_program.key() == ORACLE_ID)]
+    pub oracle_program: UncheckedAccount<'info>,
+}
+'''
+
+NATIVE_RELAY = '''
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint,
+    entrypoint::ProgramResult,
+    program::invoke,
+    pubkey::Pubkey,
+};
+
+entrypoint!(process_instruction);
+
+pub fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let source = next_account_info(iter)?;
+    let destination = next_account_info(iter)?;
+    let owner = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let ix = spl_token::instruction::transfer(token_program.key, source.key, destination.key, owner.key, &[], 1)?;
+    invoke(&ix, &[source.clone(), destination.clone(), owner.clone()])
+}
+'''
+
+
+class TestUnvalidatedCpiTargets:
+    """Test unpinned CPI targets, their severity and suggested fixes."""
+
+    def setup_method(self):
+        self.findings, self.metadata = UnvalidatedCpiTargetDetector().run(FIXTURE)
+        self.by_ix = {f.site.instruction: f for f in self.findings}
+
+    def test_unpinned_targets(self):
+        # safe_transfer/forward use Program<'info, Token>; call_plugin compares the key in the handler
+        assert set(self.by_ix) == {"route_swap", "notify"}
+        swap = self.by_ix["route_swap"]
+        assert (swap.account, swap.account_type, swap.account_line) == ("swap_program", "UncheckedAccount<'info>", 97)
+        assert swap.site.line == 23 and swap.severity == "high"
+        # The forwarded `Transfer` accounts mark it as a token CPI
+        assert swap.suggested_fix.startswith("pub swap_program: Program<'info, Token>,")
+        assert "address = anchor_spl::token::ID" in swap.suggested_fix
+
+        notify = self.by_ix["notify"]
+        assert notify.severity == "high" and notify.expected_program is None
+        assert notify.suggested_fix == (
+            "pub hook_program: Program<'info, T>,  // or #[account(address = <expected program>::ID)]"
+        )
+
+    def test_hypothesis(self):
+        hyp = self.by_ix["route_swap"].to_hypothesis()
+        assert hyp["title"] == "Unvalidated CPI target `swap_program` in route_swap"
+        assert hyp["vulnerability_type"] == "arbitrary-cpi" and hyp["status"] == "proposed"
+        assert "the program's PDA signature" in hyp["description"] and "Fix: `pub swap_program" in hyp["description"]
+        props = hyp["properties"]
+        assert props["source_tool"] == "cpi-targets" and props["account"] == "swap_program"
+        assert props["source_files"] == ["src/lib.rs"] and props["affected_lines"] == [23, 97]
+        assert props["poc_template"] == "cpi_reentrancy" and (ROOT / TEMPLATE_FILE).exists()
+
+    def test_unsigned_cpi_and_constraint(self, tmp_path):
+        program = tmp_path / "programs" / "pinger"
+        (program / "src").mkdir(parents=True, exist_ok=True)
+        (program / "Cargo.toml").write_text('[package]\nname = "pinger"\nversion = "0.1.0"\n')
+        (program / "src" / "lib.rs").write_text(PINGER)
+        findings, metadata = UnvalidatedCpiTargetDetector().run(tmp_path)
+        assert metadata["instructions_checked"] == 2
+        (ping,) = findings
+        assert ping.site.instruction == "ping" and ping.severity == "medium"
+        assert ping.path == "programs/pinger/src/lib.rs"
+        assert ping.suggested_fix.startswith("pub system_program: Program<'info, System>,")
+        assert UnvalidatedCpiTargetDetector(min_severity="high").run(tmp_path)[0] == []
+
+    def test_native_program(self, tmp_path):
+        (tmp_path / "src").mkdir(parents=True, exist_ok=True)
+        (tmp_path / "Cargo.toml").write_text('[package]\nname = "relay"\nversion = "0.1.0"\n')
+        (tmp_path / "src" / "lib.rs").write_text(NATIVE_RELAY)
+        (finding,) = UnvalidatedCpiTargetDetector().run(tmp_path)[0]
+        assert finding.account == "token_program" and finding.site.line == 19
+        assert finding.suggested_fix == (
+            "if *token_program.key != spl_token::id() { return Err(ProgramError::IncorrectProgramId); }"
+        )
+
+    def test_registered_for_solana(self):
+        pipeline = StaticAnalysisPipeline(chain_id="solana", cpi_targets_config={"min_severity": "high"})
+        result = pipeline.run(FIXTURE, tools=["cpi-targets"])
+        assert {h["properties"]["instruction"] for h in result.hypotheses} == {"route_swap", "notify"}
+        assert all(h["id"].startswith("static_cpi-targets_") for h in result.hypotheses)
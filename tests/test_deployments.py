@@ -0,0 +1,255 @@
+"""
+Tests for deployment consistency: snapshots of Solana and EVM deployments,
+the divergences found between them, and the CLI.
+"""
+
+import base64
+import hashlib
+import json
+from unittest.mock import patch
+
+import pytest
+import yaml
+from click.testing import CliRunner
+
+from commands.deployments import check
+from extensions.deployments import (
+    EIP1967_ADMIN,
+    EIP1967_IMPLEMENTATION,
+    Authority,
+    ProgramState,
+    Snapshot,
+    abi_decode_word,
+    check_deployments,
+    executable_hash,
+    load_deployments,
+    take_snapshot,
+)
+from extensions.deployments.snapshot import UPGRADEABLE_LOADER
+from extensions.ir.anchor_config import b58encode
+from extensions.ir.keccak import function_selector
+
+SOURCE = """use anchor_lang::prelude::*;
+
+declare_id!("Vau1t11111111111111111111111111111111111111");
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn set_fee(ctx: Context<SetFee>, fee_bps: u16) -> Result<()> {
+        ctx.accounts.config.fee_bps = fee_bps;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetFee<'info> {
+    #[account(mut, has_one = admin)]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
+
+#[account]
+pub struct Config {
+    pub admin: Pubkey,
+    pub fee_bps: u16,
+    pub paused: bool,
+    pub mint: Pubkey,
+}
+"""
+
+
+def key(n):
+    return b58encode(bytes([n]) * 32)
+
+
+MAINNET, DEVNET = key(1), key(2)
+SQUADS = "SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf"
+OLD_ELF, NEW_ELF = b"\x7fELF old build", b"\x7fELF fixed build"
+
+
+@pytest.fixture
+def workspace(tmp_path, anchor_workspace):
+    # The vault deployed to mainnet and devnet, and a newer build of it in target/deploy
+    anchor_workspace(tmp_path, {"vault": SOURCE})
+    (tmp_path / "Anchor.toml").write_text(f'[programs.mainnet]\nvault = "{MAINNET}"\n\n'
+                                          f'[programs.devnet]\nvault = "{DEVNET}"\n\n'
+                                          '[provider]\ncluster = "mainnet"\nwallet = "id.json"\n')
+    (tmp_path / "target" / "deploy").mkdir(parents=True, exist_ok=True)
+    (tmp_path / "target" / "deploy" / "vault.so").write_bytes(NEW_ELF)
+    return tmp_path
+
+
+def _info(data, owner, executable=False):
+    return {"owner": owner, "executable": executable, "lamports": 1,
+            "data": [base64.b64encode(data).decode(), "base64"]}
+
+
+def _program(address, programdata, authority, elf, slot):
+    header = (3).to_bytes(4, "little") + slot.to_bytes(8, "little")
+    header += b"\x01" + bytes(authority) if authority else b"\x00" + bytes(32)
+    return {address: _info((2).to_bytes(4, "little") + programdata, UPGRADEABLE_LOADER, executable=True),
+            b58encode(programdata): _info(header + elf + bytes(64), UPGRADEABLE_LOADER)}
+
+
+def _config(admin, fee_bps, paused=False):
+    data = hashlib.sha256(b"account:Config").digest()[:8] + admin + fee_bps.to_bytes(2, "little")
+    return _info(data + bytes([paused]) + bytes(range(32)), "Vau1t11111111111111111111111111111111111111")
+
+
+class Solana:
+    """Accounts by address and block times by slot, as the RPC client would return them."""
+
+    def __init__(self, accounts, times=None):
+        self.accounts, self.times = accounts, times or {}
+
+    def get_multiple_accounts(self, addresses, encoding="base64", data_slice=None):
+        return [self.accounts.get(a) for a in addresses]
+
+    def call(self, method, params=None, cache=True):
+        assert method == "getBlockTime"
+        return self.times.get(params[0])
+
+
+class Evm:
+    """Code, storage and getter results of a few contracts."""
+
+    def __init__(self, code, storage=None, calls=None):
+        self.code, self.storage, self.calls = code, storage or {}, calls or {}
+
+    def call(self, method, params=None, cache=True):
+        if method == "eth_getCode":
+            return "0x" + self.code.get(params[0], b"").hex()
+        if method == "eth_getStorageAt":
+            return self.storage.get((params[0], params[1]), "0x" + "00" * 32)
+        assert method == "eth_call"
+        return self.calls[(params[0]["to"], params[0]["data"])]
+
+
+def _word(value):
+    return "0x" + (value if isinstance(value, bytes) else value.to_bytes(32, "big")).rjust(32, b"\x00").hex()
+
+
+WALLET, MULTISIG = bytes([7]) * 32, bytes([8]) * 32
+CLIENTS = {
+    "mainnet": Solana({**_program(MAINNET, bytes([11]) * 32, WALLET, OLD_ELF, 100),
+                       key(21): _config(WALLET, 30)}, {100: 1_700_000_000}),
+    "devnet": Solana({**_program(DEVNET, bytes([12]) * 32, MULTISIG, NEW_ELF, 500),
+                      key(22): _config(MULTISIG, 25), b58encode(MULTISIG): _info(b"", SQUADS)}, {500: 1_760_000_000}),
+}
+
+PROXY, IMPL, SAFE = "0x" + "aa" * 20, "0x" + "bb" * 20, "0x" + "cc" * 20
+BASE = Evm({PROXY: b"proxy", IMPL: b"implementation", SAFE: b"safe"},
+           {(PROXY, EIP1967_IMPLEMENTATION): _word(bytes.fromhex(IMPL[2:])),
+            (PROXY, EIP1967_ADMIN): _word(bytes.fromhex(SAFE[2:]))},
+           {(PROXY, "0x" + function_selector("feeBps()")): _word(30),
+            (PROXY, "0x" + function_selector("owner()")): _word(bytes.fromhex(SAFE[2:]))})
+
+CONFIG = {
+    "workspace": ".",
+    "accounts": {"config": {"type": "Config"}},
+    "deployments": {
+        "mainnet": {"accounts": {"config": key(21)}},
+        "devnet": {"accounts": {"config": key(22)}},
+        "base": {"chain": "evm", "provider": "https://base.example", "programs": {"vault": PROXY},
+                 "calls": {"config.fee_bps": {"contract": "vault", "call": "feeBps()", "type": "uint16"},
+                           "config.admin": {"contract": "vault", "call": "owner()", "type": "address"}}},
+    },
+}
+
+
+def _snapshots(workspace):
+    deployments = load_deployments(CONFIG, base=workspace)
+    clients = {**CLIENTS, "https://base.example": BASE}
+    return deployments, [take_snapshot(d, clients[d.provider_spec], deployments) for d in deployments.deployments]
+
+
+class TestDeployments:
+    """Test snapshots and checks against a vault on mainnet, devnet and an EVM chain."""
+
+    def test_load(self, workspace):
+        deployments = load_deployments(CONFIG, base=workspace)
+        mainnet, devnet, base = deployments.deployments
+        assert mainnet.programs == {"vault": MAINNET} and mainnet.production and mainnet.cluster == "mainnet"
+        assert devnet.programs == {"vault": DEVNET} and not devnet.production and not base.production
+        assert deployments.builds == {"vault": executable_hash(NEW_ELF)}
+        assert [f["name"] for f in deployments.types["Config"]["fields"]] == ["admin", "fee_bps", "paused", "mint"]
+        with pytest.raises(ValueError, match="no type for account vault_state"):
+            load_deployments({**CONFIG, "deployments": {**CONFIG["deployments"],
+                                                        "devnet": {"accounts": {"vault_state": key(3)}}}}, workspace)
+        with pytest.raises(ValueError, match="EVM deployments need a provider"):
+            load_deployments({"deployments": {"a": {"programs": {"v": MAINNET}}, "b": {"chain": "evm"}}})
+
+    def test_snapshots(self, workspace):
+        _, (mainnet, devnet, base) = _snapshots(workspace)
+        assert mainnet.programs["vault"] == ProgramState(MAINNET, hash=executable_hash(OLD_ELF), upgradeable=True,
+                                                         deployed_slot=100, deployed_at=1_700_000_000)
+        assert mainnet.params == {"config.fee_bps": 30, "config.paused": False}
+        assert mainnet.authorities == {"vault.upgrade_authority": Authority(b58encode(WALLET), "wallet"),
+                                       "config.admin": Authority(b58encode(WALLET), "wallet")}
+        assert devnet.authorities["config.admin"] == Authority(b58encode(MULTISIG), "program", SQUADS)
+        assert base.programs["vault"].implementation == IMPL and base.programs["vault"].hash == executable_hash(
+            b"implementation")
+        assert base.params == {"config.fee_bps": 30}
+        assert base.authorities == {"vault.upgrade_authority": Authority(SAFE, "program"),
+                                    "config.admin": Authority(SAFE, "program")}
+        assert not mainnet.errors and not base.errors
+
+        assert abi_decode_word((2 ** 256 - 1).to_bytes(32, "big"), "int256") == -1
+        with pytest.raises(ValueError, match="string"):
+            abi_decode_word(bytes(32), "string")
+
+    def test_checks(self, workspace):
+        deployments, snapshots = _snapshots(workspace)
+        findings = {(f.kind, f.subject): f for f in check_deployments(snapshots, deployments.builds)}
+        assert set(findings) == {("stale-deployment", "vault"), ("authority-divergence", "vault.upgrade_authority"),
+                                 ("authority-divergence", "config.admin"), ("config-divergence", "config.fee_bps")}
+        stale = findings["stale-deployment", "vault"]
+        assert stale.severity == "high" and stale.deployments == ["mainnet"] and "devnet" in stale.message
+        upgrade = findings["authority-divergence", "vault.upgrade_authority"]
+        assert upgrade.severity == "high" and upgrade.deployments == ["mainnet"]
+        # Only devnet charges 25 bps, and it is no production deployment
+        fee = findings["config-divergence", "config.fee_bps"]
+        assert fee.deployments == ["devnet"] and fee.severity == "info"
+        assert fee.to_hypothesis()["vulnerability_type"] == "deployment-config-divergence"
+
+        # Without the audited build, mainnet is behind the later devnet deploy
+        (version,) = [f for f in check_deployments(snapshots) if f.kind == "version-divergence"]
+        assert version.deployments == ["mainnet"] and "2023-11-14" in version.message
+
+        closed = Snapshot("mainnet", "solana", True, {"vault": ProgramState(MAINNET, found=False, error="closed")})
+        (missing,) = check_deployments([closed, snapshots[1]])
+        assert missing.kind == "missing-program" and missing.severity == "high"
+        immutable = Snapshot("devnet", "solana", programs={"vault": ProgramState(DEVNET, hash="a")},
+                             authorities={"vault.upgrade_authority": Authority(None, "none")})
+        wallet = Snapshot("mainnet", "solana", True, programs={"vault": ProgramState(MAINNET, hash="a")},
+                          authorities={"vault.upgrade_authority": Authority(MAINNET, "program", SQUADS)})
+        (upgradeable,) = check_deployments([wallet, immutable])
+        assert upgradeable.severity == "medium" and "immutable on devnet" in upgradeable.message
+
+    def test_cli(self, workspace):
+        config = workspace / "deployments.yaml"
+        config.write_text(yaml.safe_dump(CONFIG, sort_keys=False))
+        clients = {**CLIENTS, "https://base.example": BASE}
+        runner = CliRunner()
+        with patch("commands.deployments._client", side_effect=clients.get):
+            result = runner.invoke(check, [str(config), "--json"])
+            assert result.exit_code == 0, result.output
+            data = json.loads(result.output)
+            assert [s["deployment"] for s in data["snapshots"]] == ["mainnet", "devnet", "base"]
+            assert len(data["findings"]) == 4
+
+            with patch("commands.deployments.ProjectManager") as PM:
+                PM.return_value.get_project.return_value = {"path": str(workspace)}
+                result = runner.invoke(check, [str(config), "--deployment", "devnet", "--deployment", "base",
+                                               "--project", "p", "--import-hypotheses"])
+            assert result.exit_code == 0, result.output
+            store = json.loads((workspace / "hypotheses.json").read_text())["hypotheses"]
+            assert [h["vulnerability_type"] for h in store.values()] == ["deployment-config-divergence"]
+            assert (workspace / "deployments" / "deployments.json").exists()
+
+            assert runner.invoke(check, [str(config), "--deployment", "devnet"]).exit_code == 1
+        config.write_text(yaml.safe_dump({"deployments": {"mainnet": {}}}))
+        result = runner.invoke(check, [str(config)])
+        assert result.exit_code == 1 and "at least two deployments" in result.output
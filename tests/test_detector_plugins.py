@@ -0,0 +1,125 @@
+"""
+Tests for third-party detectors: registering a `Detector`, loading plugin
+modules, running them in the scan pipeline and reporting their hits.
+"""
+
+import json
+import sys
+
+import pytest
+from click.testing import CliRunner
+
+from commands.scan import scan
+from extensions.static import StaticAnalysisPipeline
+from extensions.static import plugins
+from extensions.static.plugins import Detector, PluginError, register_detector, unregister_detector
+
+PLUGIN = '''
+from extensions.static.plugins import Detector, register_detector
+
+
+@register_detector
+class StaleOracle(Detector):
+    name = "acme-oracle"
+    chains = ("solana",)
+    version = "1.2.0"
+
+    def run(self, project_path):
+        hits = []
+        for path in sorted(project_path.rglob("*.rs")):
+            for number, line in enumerate(path.read_text().splitlines(), 1):
+                if "get_price_unchecked" in line:
+                    hits.append(self.hit("Oracle price read without a staleness check", "...", "stale-oracle",
+                                         self.config.get("severity", "high"),
+                                         path.relative_to(project_path).as_posix(), number))
+        return hits, self.metadata(files_checked=1)
+
+
+@register_detector
+class Broken(Detector):
+    name = "acme-broken"
+
+    def run(self, project_path):
+        raise RuntimeError("license server unreachable")
+'''
+
+
+@pytest.fixture
+def plugin_module(tmp_path, monkeypatch):
+    (tmp_path / "acme_checks.py").write_text(PLUGIN)
+    monkeypatch.setattr(sys, "path", [str(tmp_path), *sys.path])
+    monkeypatch.setenv(plugins.PLUGINS_ENV, "acme_checks, acme_missing")
+    yield "acme_checks"
+    for name in ("acme-oracle", "acme-broken"):
+        unregister_detector(name)
+    plugins._loaded.clear()
+    plugins.LOAD_ERRORS.clear()
+    sys.modules.pop("acme_checks", None)
+
+
+# A program calling the unchecked price getter the plugin flags
+PRICE_SOURCE = ("use anchor_lang::prelude::*;\n\npub fn price(feed: &PriceFeed) -> i64 {\n"
+                "    feed.get_price_unchecked().price\n}\n")
+
+
+class TestDetectorPlugins:
+    """Test plugging third-party detectors into the scan pipeline."""
+
+    def test_register(self):
+        class Unnamed(Detector):
+            pass
+
+        class Shadow(Detector):
+            name = "missing-signer"
+
+        with pytest.raises(PluginError, match="kebab-case"):
+            register_detector(Unnamed)
+        with pytest.raises(PluginError, match="not a Detector"):
+            register_detector(object)
+        register_detector(Shadow)
+        try:
+            pipeline = StaticAnalysisPipeline(chain_id="solana")
+            # The built-in detector keeps its name
+            assert type(pipeline.runners["missing-signer"]).__name__ == "MissingSignerDetector"
+            assert "name taken" in pipeline.plugin_errors["missing-signer"]
+        finally:
+            unregister_detector("missing-signer")
+
+    def test_pipeline(self, tmp_path, anchor_workspace, plugin_module):
+        workspace = anchor_workspace(tmp_path / "ws", {"vault": PRICE_SOURCE}, localnet=["vault"])
+        pipeline = StaticAnalysisPipeline(chain_id="solana", plugin_configs={"acme-oracle": {"severity": "medium"}})
+        assert {"acme-oracle", "acme-broken"} <= set(pipeline.runners)
+        assert "ModuleNotFoundError" in pipeline.plugin_errors["acme_missing"]
+        # Chain-specific plugins only run on their chains
+        assert "acme-oracle" not in StaticAnalysisPipeline(chain_id="evm").runners
+        assert "acme-oracle" not in StaticAnalysisPipeline(chain_id="solana", plugins=False).runners
+
+        result = pipeline.run(workspace, tools=["acme-oracle", "acme-broken"])
+        [hit] = result.hypotheses
+        assert hit["severity"] == "medium" and hit["properties"]["rule_id"] == "acme-oracle/stale-oracle"
+        assert hit["properties"]["source_files"] == ["programs/vault/src/lib.rs"]
+        assert hit["properties"]["affected_lines"] == [4] and hit["id"].startswith("static_acme-oracle_")
+        assert result.metadata["plugins"] == ["acme-broken", "acme-oracle"]
+        # A plugin failing fails its detector, not the scan
+        assert result.metadata["tools"]["acme-broken"]["success"] is False
+        assert "license server" in result.metadata["tools"]["acme-broken"]["error"]
+
+    def test_cli(self, tmp_path, anchor_workspace, plugin_module):
+        workspace = anchor_workspace(tmp_path / "ws", {"vault": PRICE_SOURCE}, localnet=["vault"])
+        runner = CliRunner()
+        sarif = tmp_path / "hits.sarif"
+        result = runner.invoke(scan, [str(workspace), "--tool", "acme-oracle", "--json", "--sarif", str(sarif)])
+        assert result.exit_code == 0, result.output
+        report = json.loads(result.output)
+        assert [h["title"] for h in report["hypotheses"]] == ["Oracle price read without a staleness check"]
+        rules = json.loads(sarif.read_text())["runs"][0]["tool"]["driver"]["rules"]
+        assert [r["id"] for r in rules] == ["acme-oracle/stale-oracle"]
+
+        # Suppressed inline like any other detector
+        lib = workspace / "programs" / "vault" / "src" / "lib.rs"
+        lib.write_text(lib.read_text().replace("    feed.get", "    // baskerville:allow(acme-oracle)\n    feed.get"))
+        result = runner.invoke(scan, [str(workspace), "--tool", "acme-oracle", "--json"])
+        assert json.loads(result.output)["hypotheses"] == []
+
+        result = runner.invoke(scan, [str(workspace), "--tool", "acme-oracle", "--no-plugins"])
+        assert result.exit_code == 1 and "Unknown detector" in result.output
@@ -0,0 +1,161 @@
+"""
+Tests for diff-aware scanning: parsing changed lines out of git diffs,
+deciding which hits a change touches whatever the user's git config, and
+`scan --diff` on a workspace in a git repository.
+"""
+
+import json
+import subprocess
+from pathlib import Path
+
+import pytest
+from click.testing import CliRunner
+
+from commands.scan import scan
+from extensions.static import DiffError, DiffScope
+from extensions.static.diff_scope import parse_diff
+
+SOURCE = """use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod {name} {{
+    use super::*;
+
+    pub fn set_fee(ctx: Context<SetFee>, fee: u64) -> Result<()> {{
+        ctx.accounts.config.fee = fee;
+        Ok(())
+    }}
+}}
+
+#[derive(Accounts)]
+pub struct SetFee<'info> {{
+    #[account(mut, has_one = admin)]
+    pub config: Account<'info, Config>,
+    /// CHECK: config admin
+    pub admin: AccountInfo<'info>,
+}}
+"""
+
+DIFF = """diff --git a/programs/vault/src/lib.rs b/programs/vault/src/lib.rs
+--- a/programs/vault/src/lib.rs
++++ b/programs/vault/src/lib.rs
+@@ -10,0 +11,2 @@ pub mod vault {
++        msg!("fee");
++        msg!("set");
+@@ -20 +22 @@ pub struct SetFee<'info> {
+-    #[account(mut)]
++    #[account(mut, has_one = admin)]
+@@ -30,2 +31,0 @@
+-    old
+-    lines
+diff --git a/README.md b/README.md
+deleted file mode 100644
+--- a/README.md
++++ /dev/null
+@@ -1 +0,0 @@
+-gone
+"""
+
+
+def _hit(file: str, *lines: int, end_line: int | None = None) -> dict:
+    props = {"source_files": [file], "affected_lines": list(lines)}
+    if end_line is not None:
+        props["end_line"] = end_line
+    return {"title": "hit", "properties": props}
+
+
+def _git(root: Path, *args: str) -> str:
+    return subprocess.run(["git", "-C", str(root), *args], capture_output=True, text=True, check=True).stdout
+
+
+@pytest.fixture
+def workspace(tmp_path, anchor_workspace):
+    # A vault and a treasury program committed on `main`, checked out on a feature branch
+    programs = {name: SOURCE.format(name=name) for name in ("vault", "treasury")}
+    workspace = anchor_workspace(tmp_path / "ws", programs, localnet=["vault"])
+    _git(tmp_path, "init", "-q", "-b", "main")
+    _git(tmp_path, "config", "user.email", "dev@example.com")
+    _git(tmp_path, "config", "user.name", "dev")
+    _git(tmp_path, "add", "-A")
+    _git(tmp_path, "commit", "-q", "-m", "init")
+    _git(tmp_path, "checkout", "-q", "-b", "feature")
+    return workspace
+
+
+class TestDiffScan:
+    """Test scoping scans to the lines a change touches."""
+
+    def test_touches(self, tmp_path):
+        assert parse_diff(DIFF) == {"programs/vault/src/lib.rs": {11, 12, 22, 31, 32}}
+        scope = DiffScope("main", files={"programs/vault/src/lib.rs": {11, 12, 22}, "new.rs": None}, context=2)
+        assert scope.touches(_hit("programs/vault/src/lib.rs", 14))
+        assert not scope.touches(_hit("programs/vault/src/lib.rs", 15))
+        # A hit spanning a changed line, one in a new file, one without lines
+        assert scope.touches(_hit("programs/vault/src/lib.rs", 1, end_line=10))
+        assert scope.touches(_hit("new.rs:400", 400))
+        assert scope.touches({"properties": {"source_files": ["other.rs", "programs/vault/src/lib.rs"]}})
+        assert not scope.touches(_hit("other.rs", 11))
+        assert not scope.touches({"title": "no location"})
+
+        absolute = _hit(str(tmp_path / "programs" / "vault" / "src" / "lib.rs"), 22)
+        touched, rest = scope.filter([absolute, _hit("other.rs", 1)], tmp_path)
+        assert touched == [absolute] and len(rest) == 1
+
+    def test_from_git(self, tmp_path, workspace):
+        assert DiffScope.from_git(workspace, "main").files == {}
+
+        lib = workspace / "programs" / "vault" / "src" / "lib.rs"
+        lines = lib.read_text().splitlines()
+        lines[8] = "        ctx.accounts.config.fee = fee + 1;"
+        lib.write_text("\n".join(lines) + "\n")
+        _git(tmp_path, "commit", "-q", "-am", "change fee")
+        (workspace / "programs" / "vault" / "src" / "extra.rs").write_text("pub fn x() {}\n")
+        (tmp_path / "outside.rs").write_text("pub fn y() {}\n")
+        scope = DiffScope.from_git(workspace, "main", context=1)
+        assert scope.files == {"programs/vault/src/lib.rs": {9}, "programs/vault/src/extra.rs": None}
+        assert scope.base == _git(tmp_path, "rev-parse", "main").strip()
+        assert scope.to_dict()["files"] == ["programs/vault/src/extra.rs", "programs/vault/src/lib.rs"]
+
+        with pytest.raises(DiffError, match="merge-base"):
+            DiffScope.from_git(workspace, "no-such-ref")
+
+    def test_git_config(self, tmp_path, workspace):
+        # Settings that change what `git diff` prints must not change the scope
+        for key, value in (("diff.noprefix", "true"), ("diff.mnemonicPrefix", "true"), ("core.quotePath", "true"),
+                           ("color.diff", "always"), ("color.ui", "always")):
+            _git(tmp_path, "config", key, value)
+        lib = workspace / "programs" / "vault" / "src" / "lib.rs"
+        lib.write_text(lib.read_text().replace("fee = fee;", "fee = fee + 1;"))
+        (workspace / "programs" / "vault" / "src" / "tarifa_ñ.rs").write_text("pub fn x() {}\n")
+        scope = DiffScope.from_git(workspace, "main")
+        changed = lib.read_text().splitlines().index("        ctx.accounts.config.fee = fee + 1;") + 1
+        assert scope.files == {"programs/vault/src/lib.rs": {changed}, "programs/vault/src/tarifa_ñ.rs": None}
+
+    def test_cli(self, tmp_path, workspace):
+        runner = CliRunner()
+        args = [str(workspace), "--no-cache", "--json", "--tool", "missing-signer", "--diff", "main"]
+        result = runner.invoke(scan, args)
+        assert result.exit_code == 0, result.output
+        data = json.loads(result.output)
+        assert data["hypotheses"] == [] and data["diff"]["files"] == [] and data["tools"] == {}
+
+        # Only the program whose handler changed is reported, and its hits fail the check
+        lib = workspace / "programs" / "treasury" / "src" / "lib.rs"
+        lib.write_text(lib.read_text().replace("Ok(())", "msg!(\"fee set\");\n        Ok(())"))
+        result = runner.invoke(scan, args)
+        assert result.exit_code == 1, result.output
+        data = json.loads(result.output)
+        assert data["hypotheses"] and data["diff"]["outside"] >= 1
+        assert all("treasury" in str(h["properties"]["source_files"]) for h in data["hypotheses"])
+        assert {h["severity"] for h in data["hypotheses"]} == {"high"}
+
+        assert runner.invoke(scan, args + ["--fail-on", "never"]).exit_code == 0
+        assert runner.invoke(scan, args + ["--fail-on", "critical"]).exit_code == 0
+        assert runner.invoke(scan, args + ["--fail-on", "high"]).exit_code == 1
+        result = runner.invoke(scan, args[:2] + args[3:])
+        assert result.exit_code == 1 and "(--fail-on info)" in result.output
+
+        result = runner.invoke(scan, args[:-1] + ["no-such-ref"])
+        assert result.exit_code == 1 and "merge-base" in result.output
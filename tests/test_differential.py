@@ -0,0 +1,187 @@
+"""
+Tests for differential execution: verdicts per transaction, key and compute
+unit normalization between the two chains, and the CLI.
+"""
+
+import hashlib
+import json
+import os
+from unittest.mock import MagicMock, patch
+
+import pytest
+import yaml
+from click.testing import CliRunner
+
+from commands.simulate import diff
+from extensions.ir.anchor_config import b58encode
+from extensions.monitor.decode import anchor_discriminator, b58decode
+from extensions.simulation import ExecutionBackend, TxResult, load_differential
+
+PROGRAM_ID = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+
+
+class VaultBackend(ExecutionBackend):
+    """A vault storing its authority and balance, whose withdraw does not check the authority. Keys are
+    fresh on every start and compute units vary, as on a real chain."""
+
+    checks_authority = False
+
+    def start(self):
+        self.salt, self.keys, self.accounts = os.urandom(8), {}, {}
+
+    def keypair(self, name, lamports=0):
+        return self.keys.setdefault(name, b58encode(hashlib.sha256(self.salt + name.encode()).digest()))
+
+    def set_account(self, pubkey, data, owner, lamports=0, executable=False):
+        self.accounts[self.keys.get(pubkey, pubkey)] = {"lamports": lamports, "data": bytes(data), "owner": owner,
+                                                        "executable": executable}
+
+    def get_account(self, pubkey):
+        account = self.accounts.get(self.keys.get(pubkey, pubkey))
+        return dict(account) if account else None
+
+    def withdraw(self, vault, signer, amount):
+        if self.checks_authority and b58decode(signer) != vault["data"][:32]:
+            return TxResult(False, "custom program error: 0x7d1", logs=["Program log: ConstraintHasOne"])
+        return None
+
+    def send(self, instructions, payer, signers=()):
+        ix = instructions[0]
+        vault = self.accounts[self.keys[ix.accounts[0].pubkey]]
+        signer = self.keys[ix.accounts[1].pubkey]
+        tag, amount = ix.data[:8], int.from_bytes(ix.data[8:16], "little")
+        balance = int.from_bytes(vault["data"][32:40], "little")
+        if tag == anchor_discriminator("initialize"):
+            vault["data"] = b58decode(signer) + bytes(8)
+            return TxResult(True)
+        if tag == anchor_discriminator("withdraw"):
+            rejected = self.withdraw(vault, signer, amount)
+            if rejected:
+                return rejected
+            if amount > balance:
+                return TxResult(False, "custom program error: 0x1771", logs=["Program log: InsufficientFunds"])
+            balance -= amount
+        else:
+            balance += amount
+        vault["data"] = vault["data"][:32] + balance.to_bytes(8, "little")
+        units = int.from_bytes(os.urandom(2), "little")
+        return TxResult(True, logs=[f"Program log: {amount} by {signer}",
+                                    f"Program {PROGRAM_ID} consumed {units} of 200000 compute units"],
+                        compute_units=units)
+
+
+class PatchedBackend(VaultBackend):
+    checks_authority = True
+
+
+class OverreachingBackend(VaultBackend):
+    """A patch that rejects every withdraw, the authority's too."""
+
+    def withdraw(self, vault, signer, amount):
+        return TxResult(False, "custom program error: 0x7d1", logs=["Program log: ConstraintHasOne"])
+
+
+def _tx(name, instruction, signer, amount, **extra):
+    return {"name": name, "program_id": PROGRAM_ID, "instruction": instruction,
+            "args": amount.to_bytes(8, "little").hex(),
+            "accounts": [{"pubkey": "vault", "writable": True}, {"pubkey": signer, "signer": True}], **extra}
+
+
+CONFIG = {
+    "backend": {"accounts": ["fixtures/vault.json"]},
+    "original": {"programs": {PROGRAM_ID: "target/deploy/vault-v1.so"}},
+    "patched": {"programs": {PROGRAM_ID: "target/deploy/vault.so"}},
+    "keypairs": {"user": 10 ** 10, "attacker": 10 ** 10, "vault": 0},
+    "accounts": [{"pubkey": "vault", "owner": PROGRAM_ID, "data": "00" * 40, "lamports": 10 ** 6}],
+    "setup": [{"program_id": PROGRAM_ID, "instruction": "initialize",
+               "accounts": [{"pubkey": "vault", "writable": True}, {"pubkey": "user", "signer": True}]}],
+    "watch": ["vault"],
+    "transactions": [
+        _tx("deposit", "deposit", "user", 100),
+        _tx("withdraw", "withdraw", "user", 10),
+        _tx("drain", "withdraw", "attacker", 90, expect="rejected"),
+        _tx("deposit again", "deposit", "user", 100),
+    ],
+}
+
+
+def _run(original, patched, config=CONFIG):
+    return load_differential(config, builds={"original": original, "patched": patched}).run()
+
+
+def _verdicts(result):
+    return {tx.name: tx.verdict for tx in result.transactions}
+
+
+class TestDifferential:
+    """Test verdicts and normalization against the fake vault builds."""
+
+    def test_verdicts(self):
+        result = _run(VaultBackend, PatchedBackend)
+        assert _verdicts(result) == {"deposit": "unchanged", "withdraw": "unchanged", "drain": "intended",
+                                     "deposit again": "downstream"}
+        assert result.verified
+        deposit, _, drain, again = result.transactions
+        # The authority's key differs between the chains but normalizes to the same bytes
+        assert deposit.original.accounts["vault"]["data"] == deposit.patched.accounts["vault"]["data"]
+        assert deposit.original.logs[0] == "Program log: 100 by <user>" and not deposit.logs
+        assert drain.original.ok and drain.patched.error == "custom program error: 0x7d1"
+        (balance,) = again.accounts
+        assert balance.field == "data" and balance.ranges == [(32, 33)]
+
+        summary = result.to_dict()["summary"]
+        assert summary["transactions"] == 4 and summary["intended"] == 1 and summary["downstream"] == 1
+
+    def test_missing_and_unintended(self):
+        result = _run(VaultBackend, VaultBackend)
+        assert _verdicts(result)["drain"] == "missing" and not result.verified
+
+        result = _run(VaultBackend, OverreachingBackend)
+        assert _verdicts(result) == {"deposit": "unchanged", "withdraw": "unintended", "drain": "intended",
+                                     "deposit again": "downstream"}
+        withdraw = result.transactions[1]
+        assert "-Program log: 10 by <user>" in withdraw.logs and "+Program log: ConstraintHasOne" in withdraw.logs
+
+        # Without an earlier intended change, diverging state is unintended as well
+        config = {**CONFIG, "transactions": [dict(tx, expect=None) for tx in CONFIG["transactions"]]}
+        assert _verdicts(_run(VaultBackend, PatchedBackend, config))["deposit again"] == "unintended"
+
+    def test_config_errors(self):
+        with pytest.raises(ValueError, match="original and patched"):
+            load_differential({k: v for k, v in CONFIG.items() if k != "patched"})
+        duplicate = {**CONFIG, "transactions": [_tx("deposit", "deposit", "user", 1)] * 2}
+        with pytest.raises(ValueError, match="unique"):
+            _run(VaultBackend, PatchedBackend, duplicate)
+        unknown = {**CONFIG, "transactions": [_tx("drain", "withdraw", "attacker", 1, expect="reverted")]}
+        with pytest.raises(ValueError, match="unknown expectations reverted"):
+            _run(VaultBackend, PatchedBackend, unknown)
+
+    def test_cli(self, tmp_path):
+        path = tmp_path / "corpus.yaml"
+        path.write_text(yaml.safe_dump(CONFIG))
+        runner = CliRunner()
+
+        def factory(patched):
+            return lambda spec, base: patched if "vault.so" in str(spec["programs"]) else VaultBackend
+
+        with patch("extensions.simulation.engine.backend_factory", side_effect=factory(PatchedBackend)):
+            result = runner.invoke(diff, [str(path), "--json"])
+            assert result.exit_code == 0, result.output
+            data = json.loads(result.output)
+            assert data["verified"] and data["manifest"]["command"]["name"] == "sim diff"
+            assert [t["verdict"] for t in data["transactions"]] == ["unchanged", "unchanged", "intended",
+                                                                     "downstream"]
+
+            with patch("commands.simulate.ProjectManager") as PM:
+                pm = MagicMock()
+                PM.return_value = pm
+                pm.get_project.return_value = {"path": str(tmp_path)}
+                result = runner.invoke(diff, [str(path), "--project", "p"])
+            assert result.exit_code == 0, result.output
+            assert "Patch verified" in result.output
+            assert (tmp_path / "simulations" / "corpus.json").exists()
+
+        with patch("extensions.simulation.engine.backend_factory", side_effect=factory(OverreachingBackend)):
+            result = runner.invoke(diff, [str(path)])
+        assert result.exit_code == 1
+        assert "withdraw: unintended change" in result.output
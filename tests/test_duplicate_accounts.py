@@ -0,0 +1,215 @@
+"""
+Tests for the duplicate mutable account detector.
+
+Verifies pairs of mutable accounts of the same type are flagged when nothing
+keeps them apart, that key constraints, handler key checks (early error
+returns included), distinct seeds and `init` clear them while other
+constraints between the two and seeds that only differ in the account they
+take do not, that booked self-transfers and writes to both are high
+severity, and that findings link to the `duplicate_mutable_accounts` PoC
+template.
+"""
+
+from pathlib import Path
+
+from extensions.knowledge.class_loader import ClassLoader
+from extensions.static import DuplicateMutableAccountDetector, StaticAnalysisPipeline
+from extensions.static.duplicate_accounts import TEMPLATE_FILE
+
+
+ROOT = Path(__file__).resolve().parents[1]
+FIXTURES = ROOT / "tests" / "fixtures" / "solana"
+
+SOURCE = """use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod ledger {
+    use super::*;
+
+    pub fn swap(ctx: Context<Swap>, amount: u64) -> Result<()> {
+        let from = &mut ctx.accounts.from;
+        let to = &mut ctx.accounts.to;
+        let (a, b) = (from.balance - amount, to.balance + amount);
+        from.balance = a;
+        to.balance = b;
+        Ok(())
+    }
+
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.source.to_account_info(),
+            to: ctx.accounts.pool.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), amount)?;
+        ctx.accounts.position.staked += amount;
+        Ok(())
+    }
+
+    pub fn sweep(ctx: Context<Sweep>) -> Result<()> {
+        msg!("{} {}", ctx.accounts.left.amount, ctx.accounts.right.amount);
+        Ok(())
+    }
+
+    pub fn guarded(ctx: Context<Guarded>, amount: u64) -> Result<()> {
+        ctx.accounts.from.balance -= amount;
+        ctx.accounts.to.balance += amount;
+        Ok(())
+    }
+
+    pub fn checked(ctx: Context<Swap>, amount: u64) -> Result<()> {
+        require_keys_neq!(ctx.accounts.from.key(), ctx.accounts.to.key());
+        ctx.accounts.from.balance -= amount;
+        ctx.accounts.to.balance += amount;
+        Ok(())
+    }
+
+    pub fn open(ctx: Context<Open>) -> Result<()> {
+        ctx.accounts.fresh.balance = ctx.accounts.old.balance;
+        ctx.accounts.old.balance = 0;
+        Ok(())
+    }
+
+    pub fn seeded(ctx: Context<Seeded>) -> Result<()> {
+        ctx.accounts.bid.balance += ctx.accounts.ask.balance;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    pub from: Account<'info, Wallet>,
+    #[account(mut)]
+    pub to: Account<'info, Wallet>,
+}
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut)]
+    pub source: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub pool: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub position: Account<'info, Wallet>,
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Sweep<'info> {
+    #[account(mut)]
+    pub left: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub right: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct Guarded<'info> {
+    #[account(mut)]
+    pub from: Account<'info, Wallet>,
+    #[account(mut, constraint = from.key() != to.key() @ LedgerError::Duplicate)]
+    pub to: Account<'info, Wallet>,
+}
+
+#[derive(Accounts)]
+pub struct Open<'info> {
+    #[account(init, payer = user, space = 8 + 8)]
+    pub fresh: Account<'info, Wallet>,
+    #[account(mut)]
+    pub old: Account<'info, Wallet>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Seeded<'info> {
+    #[account(mut, seeds = [b"bid"], bump)]
+    pub bid: Account<'info, Wallet>,
+    #[account(mut, seeds = [b"ask"], bump)]
+    pub ask: Account<'info, Wallet>,
+}
+
+#[account]
+pub struct Wallet {
+    pub balance: u64,
+}
+"""
+
+
+class TestDuplicateAccounts:
+    """Test the detector against an inline Anchor program and the fixtures."""
+
+    def test_findings(self, tmp_path, anchor_workspace):
+        findings, metadata = DuplicateMutableAccountDetector().run(anchor_workspace(tmp_path, {"ledger": SOURCE}))
+        assert metadata["instructions_checked"] == 7
+        flagged = [(f.instruction, f.first, f.second, f.state_type, f.severity, f.line) for f in findings]
+        assert flagged == [
+            ("swap", "from", "to", "Wallet", "high", anchor_workspace.line(SOURCE, "pub to:")),
+            ("stake", "source", "pool", "TokenAccount", "high", anchor_workspace.line(SOURCE, "pub pool:")),
+            ("sweep", "left", "right", "TokenAccount", "medium", anchor_workspace.line(SOURCE, "pub right:")),
+        ]
+        assert findings[0].path == "programs/ledger/src/lib.rs"
+        assert findings[0].moves == "writes fields of both"
+        assert findings[1].moves == "books a transfer from `source` to the other"
+
+    def test_not_distinct(self, tmp_path, anchor_workspace):
+        # Equal mints and per-owner seeds still let the caller pass one account twice
+        source = SOURCE.replace(
+            "    #[account(mut, constraint = from.key() != to.key() @ LedgerError::Duplicate)]",
+            "    #[account(mut, constraint = from.mint == to.mint @ LedgerError::MintMismatch)]",
+        ).replace('seeds = [b"bid"], bump', 'seeds = [b"wallet", owner_a.key().as_ref()], bump').replace(
+            'seeds = [b"ask"], bump', 'seeds = [b"wallet", owner_b.key().as_ref()], bump').replace(
+            "    pub ask: Account<'info, Wallet>,\n",
+            "    pub ask: Account<'info, Wallet>,\n    pub owner_a: Signer<'info>,\n"
+            "    /// CHECK: seed only\n    pub owner_b: UncheckedAccount<'info>,\n")
+        findings, _ = DuplicateMutableAccountDetector().run(anchor_workspace(tmp_path, {"ledger": source}))
+        flagged = {(f.instruction, f.first, f.second) for f in findings}
+        assert {("guarded", "from", "to"), ("seeded", "bid", "ask")} <= flagged
+
+    def test_early_return(self, tmp_path, anchor_workspace):
+        # Returning an error when the keys are equal keeps the pair apart; only logging it does not
+        check = "require_keys_neq!(ctx.accounts.from.key(), ctx.accounts.to.key());"
+        guards = [
+            ("if ctx.accounts.from.key() == ctx.accounts.to.key() { return err!(LedgerError::Duplicate); }", False),
+            ("if ctx.accounts.to.key() == ctx.accounts.from.key() {\n"
+             "            return Err(LedgerError::Duplicate.into());\n        }", False),
+            ("if ctx.accounts.from.key() == ctx.accounts.to.key() { msg!(\"same account\"); }", True),
+        ]
+        for index, (guard, flagged) in enumerate(guards):
+            findings, _ = DuplicateMutableAccountDetector().run(
+                anchor_workspace(tmp_path / str(index), {"ledger": SOURCE.replace(check, guard, 1)}))
+            assert ("checked" in {f.instruction for f in findings}) == flagged
+
+    def test_fixtures(self):
+        findings, _ = DuplicateMutableAccountDetector().run(FIXTURES / "anchor_vault")
+        assert [(f.instruction, f.first, f.second, f.path, f.line, f.severity) for f in findings] == [
+            ("deposit", "user_token", "vault_token", "programs/vault/src/lib.rs", 56, "high")]
+        # Self-transfers nothing books are reported, at medium
+        findings, _ = DuplicateMutableAccountDetector().run(FIXTURES / "treasury_workspace")
+        assert [(f.instruction, f.severity) for f in findings] == [("pay_out", "medium")]
+
+    def test_hypothesis(self, tmp_path, anchor_workspace):
+        findings, _ = DuplicateMutableAccountDetector().run(anchor_workspace(tmp_path, {"ledger": SOURCE}))
+        hyp = findings[1].to_hypothesis()
+        assert hyp["title"] == "`source` and `pool` can be the same account in stake"
+        assert hyp["severity"] == "high" and hyp["confidence"] == 0.6
+        assert "`constraint = source.key() != pool.key()`" in hyp["description"]
+        assert findings[2].to_hypothesis()["confidence"] == 0.4
+        props = hyp["properties"]
+        assert props["source_tool"] == "duplicate-mutable" and props["accounts"] == ["source", "pool"]
+        assert (ROOT / props["poc_template_file"]).exists() and props["poc_template_file"] == TEMPLATE_FILE
+        vuln_class = ClassLoader().resolve(hyp["vulnerability_type"], "solana")
+        assert vuln_class.id == "aliased-mutable-accounts"
+        assert vuln_class.variant("solana").templates == ["duplicate_mutable_accounts"]
+
+    def test_pipeline(self, tmp_path, anchor_workspace):
+        pipeline = StaticAnalysisPipeline(chain_id="solana")
+        assert "duplicate-mutable" in pipeline.check_tools()
+        result = pipeline.run(anchor_workspace(tmp_path, {"ledger": SOURCE}), tools=["duplicate-mutable"])
+        assert len(result.hypotheses) == 3
+        assert all(h["id"].startswith("static_duplicate-mutable_") for h in result.hypotheses)
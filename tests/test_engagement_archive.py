@@ -0,0 +1,149 @@
+"""
+Tests for engagement archives: what an export carries, checksum and path
+verification on import, recreating the project and its bounty contests,
+and the export/import commands.
+"""
+
+import io
+import json
+import tarfile
+
+import pytest
+from click.testing import CliRunner
+
+import commands.archive as archive_cli
+from commands.project import ProjectManager
+from extensions.bounty import BountyStorage, Contest, Finding, Severity
+from extensions.reporting.archive import ArchiveError, export_engagement, import_engagement, read_archive
+
+
+def _engagement(tmp_path) -> tuple[ProjectManager, BountyStorage]:
+    source = tmp_path / "src" / "vault"
+    (source / "programs").mkdir(parents=True)
+    (source / "programs" / "lib.rs").write_text("pub fn withdraw() {}\n")
+    (source / "target").mkdir()
+    (source / "target" / "vault.so").write_bytes(b"\x7fELF")
+
+    manager = ProjectManager(tmp_path / "projects")
+    config = manager.create_project("vault", str(source), chain="solana")
+    project_dir = manager.projects_dir / config["name"]
+    (project_dir / "hypotheses.json").write_text(json.dumps({"hypotheses": {
+        "h1": {"title": "Missing signer", "status": "confirmed",
+               "status_history": [{"status": "confirmed", "at": "2026-09-02T10:00:00", "by": "alice"}]},
+        "h2": {"title": "Stale oracle", "status": "proposed"},
+    }}))
+    (project_dir / "poc" / "h1").mkdir(parents=True)
+    (project_dir / "poc" / "h1" / "metadata.json").write_text(json.dumps({"hypothesis_id": "h1"}))
+    (project_dir / "reports" / "audit.md").write_text("# Audit\n")
+    (project_dir / "project.json.lock").write_text("")
+
+    storage = BountyStorage(tmp_path / "bounty")
+    storage.save_contest(Contest(id="c1", platform="code4rena", name="Vault", url="https://example.com/c1",
+                                 project_name="vault", project_path=str(project_dir)))
+    storage.save_contest(Contest(id="c2", platform="sherlock", name="Other", url="https://example.com/c2"))
+    storage.save_finding(Finding(id="f1", contest_id="c1", title="Missing signer", description="Details.",
+                                 severity=Severity.HIGH, assignee="alice", reviewer="bob"))
+    return manager, storage
+
+
+class TestEngagementArchive:
+    """Test exporting, verifying and importing engagement archives."""
+
+    def test_export(self, tmp_path):
+        manager, storage = _engagement(tmp_path)
+        archive = tmp_path / "vault.tar.gz"
+        manifest = export_engagement(manager.get_project("vault"), archive, bounty_dir=storage.base_dir,
+                                     include_source=True)
+        assert manifest["format"] == "baskerville-engagement-archive" and manifest["contests"] == ["c1"]
+        assert manifest["sections"] == {"hypotheses": 2, "triaged": 1, "pocs": 1, "reports": 1, "sessions": 0,
+                                        "contests": 1, "bounty_findings": 1}
+        assert manifest["manifest"]["command"]["name"] == "export engagement"
+        assert "path" not in manifest["project"]
+        files = set(manifest["files"])
+        assert {"project/hypotheses.json", "project/poc/h1/metadata.json", "project/reports/audit.md",
+                "bounty/contests/c1.json", "bounty/findings/c1/f1.json", "source/programs/lib.rs"} <= files
+        # Lock files, build output and unlinked contests stay out
+        assert not any(f.endswith(".lock") or f.startswith("source/target") or "c2" in f for f in files)
+
+        read_manifest, contents = read_archive(archive)
+        assert read_manifest["files"] == manifest["files"] and set(contents) == files
+
+    def test_verification(self, tmp_path):
+        manager, storage = _engagement(tmp_path)
+        archive = tmp_path / "vault.tar.gz"
+        export_engagement(manager.get_project("vault"), archive, bounty_dir=storage.base_dir)
+
+        def rewrite(path, change):
+            with tarfile.open(archive) as tar:
+                members = [(m.name, tar.extractfile(m).read()) for m in tar.getmembers()]
+            with tarfile.open(path, "w:gz") as tar:
+                for name, data in change(members):
+                    info = tarfile.TarInfo(name)
+                    info.size = len(data)
+                    tar.addfile(info, io.BytesIO(data))
+            return path
+
+        tampered = rewrite(tmp_path / "tampered.tar.gz", lambda ms: [
+            (n, b'{"hypotheses": {}}' if n == "project/hypotheses.json" else d) for n, d in ms])
+        with pytest.raises(ArchiveError, match="checksum mismatch: project/hypotheses.json"):
+            read_archive(tampered)
+        escaping = rewrite(tmp_path / "escape.tar.gz", lambda ms: ms + [("project/../../evil", b"x")])
+        with pytest.raises(ArchiveError, match="Unsafe member path"):
+            read_archive(escaping)
+        dropped = rewrite(tmp_path / "dropped.tar.gz", lambda ms: [(n, d) for n, d in ms if "poc" not in n])
+        with pytest.raises(ArchiveError, match="missing: project/poc/h1/metadata.json"):
+            read_archive(dropped)
+        (tmp_path / "junk.tar.gz").write_bytes(b"not a tarball")
+        with pytest.raises(ArchiveError, match="Cannot read archive"):
+            read_archive(tmp_path / "junk.tar.gz")
+
+    def test_import(self, tmp_path):
+        manager, storage = _engagement(tmp_path)
+        archive = tmp_path / "vault.tar.gz"
+        export_engagement(manager.get_project("vault"), archive, bounty_dir=storage.base_dir, include_source=True)
+
+        other = ProjectManager(tmp_path / "elsewhere" / "projects")
+        other_storage = BountyStorage(tmp_path / "elsewhere" / "bounty")
+        result = import_engagement(archive, other, other_storage)
+        project = other.get_project("vault")
+        assert result["warnings"] == [] and project["source_path"] == str(other.projects_dir / "vault" / "source")
+        assert (other.projects_dir / "vault" / "source" / "programs" / "lib.rs").exists()
+        hypotheses = json.loads((other.projects_dir / "vault" / "hypotheses.json").read_text())["hypotheses"]
+        assert hypotheses["h1"]["status_history"][0]["by"] == "alice"
+        assert project["imported_from"]["archive"] == "vault.tar.gz"
+        assert other_storage.load_contest("c1").project_path == str(other.projects_dir / "vault")
+        assert other_storage.load_finding("c1", "f1").reviewer == "bob"
+
+        with pytest.raises(ArchiveError, match="Already exists: project 'vault', contest 'c1'"):
+            import_engagement(archive, other, other_storage)
+        renamed = import_engagement(archive, other, other_storage, name="vault-2026", force=True,
+                                    source_path=tmp_path / "missing")
+        assert other.get_project("vault-2026")["name"] == "vault-2026"
+        assert other_storage.load_contest("c1").project_name == "vault-2026"
+        assert "does not exist here" in renamed["warnings"][0]
+
+        # Re-exporting an imported project keeps its source in place
+        again = export_engagement(other.get_project("vault"), tmp_path / "again.tar.gz",
+                                  bounty_dir=other_storage.base_dir, include_source=True)
+        assert again["source_in_project"] == "source" and not again["includes_source"]
+        third = ProjectManager(tmp_path / "third")
+        assert import_engagement(tmp_path / "again.tar.gz", third, BountyStorage(tmp_path / "b3"))["source_path"] \
+            == str(third.projects_dir / "vault" / "source")
+
+    def test_cli(self, tmp_path, monkeypatch):
+        manager, storage = _engagement(tmp_path)
+        monkeypatch.setattr(archive_cli, "get_manager", lambda: manager)
+        monkeypatch.setattr(archive_cli, "get_bounty_storage", lambda: storage)
+        runner = CliRunner()
+        archive = tmp_path / "out" / "vault.tar.gz"
+        result = runner.invoke(archive_cli.export_cmd, ["vault", "-o", str(archive)])
+        assert result.exit_code == 0, result.output
+        assert "2 (1 triaged)" in result.output and archive.exists()
+        assert runner.invoke(archive_cli.export_cmd, ["nope"]).exit_code == 1
+
+        result = runner.invoke(archive_cli.import_cmd, [str(archive), "--verify-only"])
+        assert result.exit_code == 0 and "match their checksums" in result.output
+        assert runner.invoke(archive_cli.import_cmd, [str(archive)]).exit_code == 1
+        result = runner.invoke(archive_cli.import_cmd, [str(archive), "--name", "vault-copy", "--force"])
+        assert result.exit_code == 0, result.output
+        assert manager.get_project("vault-copy") is not None
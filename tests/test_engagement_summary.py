@@ -0,0 +1,143 @@
+"""
+Tests for the engagement summary export: scans, findings, PoCs and checklist
+coverage over a window, and the reporting engagement command.
+"""
+
+import json
+from datetime import datetime
+from unittest.mock import MagicMock, patch
+
+from click.testing import CliRunner
+
+from commands.reporting import engagement
+from commands.validator import _record_run
+from extensions.knowledge.checklist_loader import ChecklistItem
+from extensions.reporting.engagement import build_engagement_summary, render_methodology, status_events
+from extensions.static.pipeline import PipelineResult, StaticAnalysisPipeline
+
+
+def _hyp(title: str, severity: str, created: str, status: str = "proposed", history=None, **properties) -> dict:
+    hyp = {"title": title, "severity": severity, "status": status, "created_at": created,
+           "vulnerability_type": "arbitrary-cpi" if "CPI" in title else "logic", "properties": properties}
+    if history is not None:
+        hyp["status_history"] = [{"status": s, "at": at} for s, at in history]
+    return hyp
+
+
+def _item(item_id: str, category: str, tags: list[str]) -> ChecklistItem:
+    return ChecklistItem(id=item_id, category=category, subcategory="", question=f"{item_id}?", description="",
+                         remediation="", severity="high", tags=tags, references=[], source="custom",
+                         chain="solana")
+
+
+CHECKLIST = [
+    _item("SOL-CPI-01", "Solana CPI Security", ["cpi", "program-id"]),
+    _item("SOL-CPI-02", "Solana CPI Security", ["cpi", "signer-seeds"]),
+    _item("SOL-ORACLE-01", "Oracle", ["oracle", "stale-price"]),
+]
+
+
+def _project(tmp_path):
+    hypotheses = {
+        "h1": _hyp("Unvalidated CPI target", "high", "2026-09-02T10:00:00", "fixed",
+                   [("confirmed", "2026-09-05T09:00:00"), ("fixed", "2026-09-20T12:00:00")],
+                   checklist=["SOL-CPI-01"]),
+        # Opened before the window, confirmed in it
+        "h2": _hyp("Rounding in withdraw", "medium", "2026-08-20T10:00:00", "confirmed",
+                   [("confirmed", "2026-09-10T09:00:00")]),
+        "h3": _hyp("Stale oracle price", "low", "2026-09-12T10:00:00", "rejected",
+                   [("rejected", "2026-09-13T09:00:00")]),
+        "h4": _hyp("After the window", "high", "2026-10-05T10:00:00"),
+    }
+    (tmp_path / "hypotheses.json").write_text(json.dumps({"hypotheses": hypotheses}))
+    sessions = tmp_path / "sessions"
+    sessions.mkdir()
+    (sessions / "s1.json").write_text(json.dumps({
+        "session_id": "s1", "start_time": "2026-09-03T08:00:00", "end_time": "2026-09-03T12:00:00",
+        "status": "completed", "investigations": [{}, {}],
+        "coverage": {"nodes": {"visited": 30, "total": 40, "percent": 75.0}},
+    }))
+    (sessions / "s0.json").write_text(json.dumps({"session_id": "s0", "start_time": "2026-08-01T08:00:00"}))
+
+    pipeline = StaticAnalysisPipeline(chain_id="solana")
+    for run_time in ("2026-09-02T09:00:00", "2026-10-02T09:00:00"):
+        result = PipelineResult(metadata={"run_time": run_time, "chain_id": "solana",
+                                          "tools": {"missing-signer": {}, "soteria": {"available": False}}})
+        pipeline.save_results(result, tmp_path / "static_analysis")
+
+    for hid in ("h1", "h2"):
+        (tmp_path / "poc" / hid).mkdir(parents=True)
+        (tmp_path / "poc" / hid / "metadata.json").write_text(json.dumps(
+            {"hypothesis_id": hid, "title": hid, "files": [], "created_at": "2026-09-06T10:00:00"}))
+    _record_run(tmp_path, "h1", "anchor test", 1)
+    _record_run(tmp_path, "h1", "anchor test", 0)
+    _record_run(tmp_path, "h2", "anchor test", 101)
+    return tmp_path
+
+
+class TestEngagementSummary:
+    """Test the window aggregation, Markdown rendering and CLI."""
+
+    def test_summary(self, tmp_path):
+        project = _project(tmp_path)
+        summary = build_engagement_summary(project, datetime(2026, 9, 1), datetime.now(), checklist=CHECKLIST)
+        scans = summary["scans"]
+        assert scans["agent_sessions"] == 1 and scans["investigations"] == 2
+        assert scans["static_runs"] == 2 and scans["static_tools"] == ["missing-signer"]
+        assert summary["code_coverage"]["nodes"]["percent"] == 75.0
+
+        findings = summary["findings"]
+        assert (findings["opened"], findings["confirmed"], findings["fixed"], findings["rejected"]) == (3, 2, 1, 1)
+        assert findings["by_severity"]["high"] == {"opened": 2, "confirmed": 1, "fixed": 1}
+        assert list(findings["by_severity"]) == ["high", "medium", "low"]
+
+        # Runs are recorded now, so both PoCs are in the window; only h1 passed
+        pocs = summary["pocs"]
+        assert (pocs["total"], pocs["verified"], pocs["failing"]) == (2, 1, 1)
+
+        # The rejected oracle finding does not cover the oracle item
+        checklist = summary["checklist"]
+        assert checklist["covered"] == 2 and checklist["cited"] == 1 and checklist["uncovered"] == ["SOL-ORACLE-01"]
+        assert checklist["by_category"]["Solana CPI Security"] == {"total": 2, "covered": 2}
+
+    def test_window(self, tmp_path):
+        project = _project(tmp_path)
+        september = build_engagement_summary(project, datetime(2026, 9, 1), datetime(2026, 9, 30, 23, 59))
+        assert september["scans"]["static_runs"] == 1 and september["checklist"] is None
+        assert september["findings"]["opened"] == 2 and september["pocs"]["verified"] == 0
+        everything = build_engagement_summary(project)
+        assert everything["scans"]["agent_sessions"] == 2 and everything["findings"]["opened"] == 4
+
+        # A status set before history was kept dates from the hypothesis' creation
+        assert status_events({"status": "confirmed", "created_at": "2026-09-01T00:00:00"}) == [
+            ("confirmed", datetime(2026, 9, 1))
+        ]
+        assert status_events({"status": "proposed"}) == []
+
+    def test_markdown_and_cli(self, tmp_path):
+        project = _project(tmp_path)
+        text = render_methodology(build_engagement_summary(project, datetime(2026, 9, 1), checklist=CHECKLIST))
+        assert text.startswith("## Methodology") and "from 2026-09-01 to the present" in text
+        assert "| High | 2 | 1 | 1 |" in text and "1 of 2 proof(s) of concept passed" in text
+        assert "2 of 3 audit checklist item(s) (66.7%)" in text
+
+        with patch("commands.reporting.ProjectManager") as PM:
+            pm = MagicMock()
+            PM.return_value = pm
+            pm.get_project.return_value = {"path": str(project), "chain_id": "solana"}
+            runner = CliRunner()
+            result = runner.invoke(engagement, ["vault", "--since", "2026-09-01", "--until", "2026-09-30",
+                                                "--no-checklist"])
+            assert result.exit_code == 0, result.output
+            report = (project / "reports" / "engagement.md").read_text()
+            assert "to 2026-09-30" in report and "baskerville-manifest" in report
+
+            out = tmp_path / "engagement.json"
+            result = runner.invoke(engagement, ["vault", "--json", "-o", str(out), "--no-checklist"])
+            assert result.exit_code == 0, result.output
+            data = json.loads(out.read_text())
+            assert data["format"] == "baskerville-engagement" and data["manifest"]["command"]["name"] == (
+                "reporting engagement"
+            )
+            assert runner.invoke(engagement, ["vault", "--since", "2026-10-01", "--until", "2026-09-01"]).exit_code == 1
+            assert runner.invoke(engagement, ["vault", "--since", "soon"]).exit_code == 1
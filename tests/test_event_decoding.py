@@ -0,0 +1,244 @@
+"""
+Tests for program event schemas: extraction from `#[event]` structs and
+IDLs, decoding logged and self-CPI events in transactions, the Rust
+decoders of the Geyser plugin, and the CLI.
+"""
+
+import base64
+import json
+from unittest.mock import patch
+
+import pytest
+from click.testing import CliRunner
+
+from commands.monitor import events, replay
+from extensions.ir import AnchorFrontend
+from extensions.ir.anchor_config import b58encode
+from extensions.monitor import (
+    EVENT_IX_TAG,
+    EventDecoder,
+    InstructionCalledRule,
+    Monitor,
+    RuleSet,
+    anchor_discriminator,
+    decode_transaction,
+    event_discriminator,
+    generate_event_decoders,
+    generate_geyser_plugin,
+    rules_from_findings,
+    schemas_from_idl,
+    schemas_from_program,
+)
+
+PROGRAM_ID = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+USER = "Usr1111111111111111111111111111111111111111"
+TOKEN = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+
+SOURCE = """use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        emit!(Deposited { user: ctx.accounts.user.key(), amount });
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        emit_cpi!(crate::Withdrawn { vault: ctx.accounts.vault.key(), amount, fee: None, kind: Kind::Full });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    pub user: Signer<'info>,
+}
+
+#[account]
+pub struct Vault {
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct Deposited {
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct Withdrawn {
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub fee: Option<u64>,
+    pub kind: Kind,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum Kind {
+    Full,
+    Partial { remaining: u128 },
+}
+"""
+
+KIND = {"kind": "enum", "variants": [{"name": "Full"}, {"name": "Partial", "fields": [
+    {"name": "remaining", "type": "u128"}]}]}
+
+# Legacy IDLs carry event fields inline; 0.30+ ones a discriminator and a type of the same name
+LEGACY_IDL = {"version": "0.1.0", "name": "vault", "instructions": [], "types": [{"name": "Kind", "type": KIND}],
+              "events": [{"name": "Withdrawn", "fields": [
+                  {"name": "vault", "type": "publicKey", "index": False},
+                  {"name": "amount", "type": "u64", "index": False},
+                  {"name": "fee", "type": {"option": "u64"}, "index": False},
+                  {"name": "kind", "type": {"defined": "Kind"}, "index": False}]}]}
+IDL = {"address": PROGRAM_ID, "metadata": {"name": "vault", "spec": "0.1.0"}, "instructions": [],
+       "events": [{"name": "Withdrawn", "discriminator": list(event_discriminator("Withdrawn"))}],
+       "types": [{"name": "Kind", "type": KIND},
+                 {"name": "Withdrawn", "type": {"kind": "struct", "fields": [
+                     {"name": "vault", "type": "pubkey"}, {"name": "amount", "type": "u64"},
+                     {"name": "fee", "type": {"option": "u64"}},
+                     {"name": "kind", "type": {"defined": {"name": "Kind"}}}]}}]}
+
+VAULT = bytes(range(32))
+DEPOSITED = event_discriminator("Deposited") + bytes(32) + (5).to_bytes(8, "little")
+WITHDRAWN = (event_discriminator("Withdrawn") + VAULT + (7).to_bytes(8, "little") + b"\x01" + (1).to_bytes(8, "little")
+             + b"\x01" + (2 ** 100).to_bytes(16, "little"))
+
+
+def _program(tmp_path):
+    crate = tmp_path / "vault"
+    (crate / "src").mkdir(parents=True, exist_ok=True)
+    (crate / "Cargo.toml").write_text('[package]\nname = "vault"\nversion = "0.1.0"\n')
+    (crate / "src" / "lib.rs").write_text(SOURCE)
+    (program,) = AnchorFrontend().load(crate)
+    return program
+
+
+def _tx(signature="sig1", logs=(), inner=()):
+    keys = [USER, PROGRAM_ID, TOKEN]
+    return {
+        "slot": 100,
+        "transaction": {"signatures": [signature], "message": {
+            "header": {"numRequiredSignatures": 1, "numReadonlySignedAccounts": 0, "numReadonlyUnsignedAccounts": 2},
+            "accountKeys": keys,
+            "instructions": [{"programIdIndex": 1, "accounts": [0],
+                              "data": b58encode(anchor_discriminator("withdraw") + (7).to_bytes(8, "little"))}]}},
+        "meta": {"err": None, "logMessages": list(logs), "innerInstructions": [{"index": 0, "instructions": [
+            {"programIdIndex": program, "accounts": [], "data": b58encode(data)} for program, data in inner]}]},
+    }
+
+
+def _data(payload):
+    return f"Program data: {base64.b64encode(payload).decode()}"
+
+
+class TestEventDecoding:
+    """Test schemas, decoding and generated decoders against a small vault with two events."""
+
+    def test_schemas_from_sources(self, tmp_path):
+        program = _program(tmp_path)
+        assert [(e.name, e.emitted_by) for e in program.events] == [("Deposited", ["deposit"]),
+                                                                     ("Withdrawn", ["withdraw"])]
+        schemas, types = schemas_from_program(program)
+        withdrawn = schemas[1]
+        assert withdrawn.discriminator == event_discriminator("Withdrawn").hex() and withdrawn.line == 39
+        assert withdrawn.fields[2] == {"name": "fee", "type": {"option": "u64"}}
+        assert withdrawn.fields[3] == {"name": "kind", "type": {"defined": {"name": "Kind"}}}
+        assert "Vault" in types and "Kind" not in types
+
+        # Kind is no #[account] struct: decoding stops there and says why
+        decoded = EventDecoder(schemas, types).decode(WITHDRAWN)
+        assert decoded.name == "Withdrawn" and decoded.fields is None and "Kind" in decoded.error
+
+    def test_idl_schemas(self, tmp_path):
+        for name, idl in (("legacy.json", LEGACY_IDL), ("idl.json", IDL)):
+            (tmp_path / name).write_text(json.dumps(idl))
+            schemas, types = schemas_from_idl(tmp_path / name)
+            assert [e.name for e in schemas] == ["Withdrawn"]
+            decoded = EventDecoder(schemas, types).decode(WITHDRAWN, "cpi")
+            assert decoded.to_dict() == {"name": "Withdrawn", "source": "cpi", "fields": {
+                "vault": b58encode(VAULT), "amount": 7, "fee": 1, "kind": {"Partial": {"remaining": 2 ** 100}}}}
+        assert EventDecoder(schemas, types).decode(WITHDRAWN[:-3]).error.startswith("data ends at byte")
+        assert EventDecoder(schemas, types).decode(DEPOSITED) is None
+
+    def test_decode_transaction(self, tmp_path):
+        (tmp_path / "idl.json").write_text(json.dumps(IDL))
+        schemas, types = schemas_from_idl(tmp_path / "idl.json")
+        source_schemas, _ = schemas_from_program(_program(tmp_path))
+        decoder = EventDecoder(source_schemas[:1] + schemas, types)
+        logs = [f"Program {PROGRAM_ID} invoke [1]", _data(DEPOSITED),
+                f"Program {TOKEN} invoke [2]", _data(DEPOSITED), f"Program {TOKEN} success",
+                f"Program {PROGRAM_ID} consumed 5000 of 200000 compute units", f"Program {PROGRAM_ID} success",
+                _data(DEPOSITED)]
+        tx = _tx(logs=logs, inner=[(1, EVENT_IX_TAG + WITHDRAWN), (2, EVENT_IX_TAG + WITHDRAWN)])
+        event = decode_transaction(tx, PROGRAM_ID, {anchor_discriminator("withdraw").hex(): "withdraw"},
+                                   events=decoder)
+        # Only what the program itself logged, and its own self-CPIs
+        assert [(e.name, e.source) for e in event.events] == [("Deposited", "log"), ("Withdrawn", "cpi")]
+        assert event.events[0].fields == {"user": b58encode(bytes(32)), "amount": 5}
+        assert not decode_transaction(tx, PROGRAM_ID).events
+
+        ruleset = RuleSet(PROGRAM_ID, [InstructionCalledRule(id="w", instruction="withdraw")],
+                          discriminators={anchor_discriminator("withdraw").hex(): "withdraw"},
+                          events=list(decoder.events.values()), types=types)
+        seen = []
+        mon = Monitor(RuleSet.from_dict(json.loads(json.dumps(ruleset.to_dict()))), on_event=seen.append)
+        (alert,) = mon.process(tx)
+        assert [e["name"] for e in alert.details["events"]] == ["Deposited", "Withdrawn"]
+        assert seen[0].events[1].fields["kind"] == {"Partial": {"remaining": 2 ** 100}}
+
+    def test_rust_decoders(self, tmp_path):
+        (tmp_path / "idl.json").write_text(json.dumps(IDL))
+        schemas, types = schemas_from_idl(tmp_path / "idl.json")
+        rust = generate_event_decoders(schemas, types, "vault")
+        disc = ", ".join(f"0x{b:02x}" for b in event_discriminator("Withdrawn"))
+        assert f'(&[{disc}], "Withdrawn", read_event_withdrawn),' in rust
+        assert 'm.insert("fee".into(), if r.u8()? == 0 { Value::Null } else { Value::from(r.u64()?) });' in rust
+        assert 'm.insert("kind".into(), read_kind(r)?);' in rust and "fn read_kind(" in rust
+        assert 'Value::from(r.u128()?.to_string())' in rust
+        assert rust.index("fn read_kind(") < rust.index("fn read_event_withdrawn(")
+        with pytest.raises(ValueError, match="cannot decode type Kind"):
+            generate_event_decoders(schemas)
+
+        program = _program(tmp_path)
+        ruleset = rules_from_findings({}, program, events=(schemas, types))
+        files = generate_geyser_plugin(ruleset)
+        assert files["src/events.rs"] == generate_event_decoders(schemas, types, "vault")
+        assert "mod events;" in files["src/lib.rs"] and 'base64 = "0.22"' in files["Cargo.toml"]
+        # Without events the module is still there, with an empty table
+        assert "const EVENTS" in generate_geyser_plugin(RuleSet(PROGRAM_ID, rules=ruleset.rules))["src/events.rs"]
+
+    def test_cli(self, tmp_path):
+        _program(tmp_path)
+        (tmp_path / "idl.json").write_text(json.dumps(IDL))
+        runner = CliRunner()
+        with patch("commands.monitor.ProjectManager") as PM:
+            PM.return_value.get_project.return_value = {"path": str(tmp_path), "source_path": str(tmp_path / "vault")}
+            result = runner.invoke(events, ["demo", "--json"])
+            assert result.exit_code == 0, result.output
+            data = json.loads(result.output)
+            assert [e["name"] for e in data["events"]] == ["Deposited", "Withdrawn"]
+
+            result = runner.invoke(events, ["demo", "--idl", str(tmp_path / "idl.json"), "--json"])
+            assert result.exit_code == 0, result.output
+            (withdrawn,) = json.loads(result.output)["events"]
+            assert withdrawn["emitted_by"] == ["withdraw"] and withdrawn["file"] == "src/lib.rs"
+
+            ruleset = rules_from_findings({}, _program(tmp_path), events=schemas_from_idl(tmp_path / "idl.json"))
+        rules_file = tmp_path / "vault.rules.json"
+        rules_file.write_text(json.dumps(ruleset.to_dict()))
+        tx = _tx("x1", inner=[(1, EVENT_IX_TAG + WITHDRAWN)])
+
+        class Client:
+            def get_transaction(self, signature, commitment="finalized"):
+                return tx
+
+        with patch("commands.monitor._client", return_value=Client()):
+            result = runner.invoke(replay, [str(rules_file), "x1"])
+        assert result.exit_code == 0, result.output
+        assert "EVENT" in result.output and "Withdrawn" in result.output and str(2 ** 100) in result.output
@@ -0,0 +1,154 @@
+"""
+Tests for the low-level EVM front-ends: raw bytecode, Yul, and Huff.
+"""
+
+from pathlib import Path
+
+from extensions.ir import BytecodeFrontend, HuffFrontend, YulFrontend, load_programs
+from extensions.ir.evm_bytecode import OPCODES, disassemble, strip_metadata
+from extensions.ir.keccak import function_selector, keccak256
+
+
+FIXTURES = Path(__file__).resolve().parent / "fixtures" / "evm" / "lowlevel"
+_BY_NAME = {name: code for code, name in OPCODES.items()}
+
+
+def _assemble(program: list) -> bytes:
+    """Tiny two-pass assembler: ops are names, ("PUSH2", "label"), ("PUSH4", int), or "label:"."""
+    def size(item):
+        if isinstance(item, str):
+            return 0 if item.endswith(":") else 1
+        return 1 + int(item[0][4:])
+
+    labels, pc = {}, 0
+    for item in program:
+        if isinstance(item, str) and item.endswith(":"):
+            labels[item[:-1]] = pc
+        pc += size(item)
+    out = bytearray()
+    for item in program:
+        if isinstance(item, str):
+            if not item.endswith(":"):
+                out.append(_BY_NAME[item])
+            continue
+        name, value = item
+        value = labels[value] if isinstance(value, str) else value
+        width = int(name[4:])
+        out.append(_BY_NAME[name])
+        out += value.to_bytes(width, "big")
+    return bytes(out)
+
+
+# withdraw(uint256): SLOAD slot 1, CALL, then SSTORE slot 1 (state written after the call)
+# execute(address):  DELEGATECALL guarded by an owner SLOAD of slot 0
+WITHDRAW = int(function_selector("withdraw(uint256)"), 16)
+EXECUTE = int(function_selector("execute(address)"), 16)
+RUNTIME = _assemble([
+    ("PUSH1", 0x80), ("PUSH1", 0x40), "MSTORE",
+    ("PUSH1", 0), "CALLDATALOAD", ("PUSH1", 0xE0), "SHR",
+    "DUP1", ("PUSH4", WITHDRAW), "EQ", ("PUSH2", "withdraw"), "JUMPI",
+    "DUP1", ("PUSH4", EXECUTE), "EQ", ("PUSH2", "execute"), "JUMPI",
+    ("PUSH1", 0), "DUP1", "REVERT",
+    "withdraw:", "JUMPDEST",
+    ("PUSH1", 1), "SLOAD",
+    ("PUSH1", 0), "DUP1", "DUP1", "DUP1", ("PUSH1", 4), "CALLDATALOAD", "CALLER", "GAS", "CALL", "POP",
+    ("PUSH1", 0), ("PUSH1", 1), "SSTORE", "STOP",
+    "execute:", "JUMPDEST",
+    ("PUSH1", 0), "SLOAD", "CALLER", "EQ", ("PUSH2", "ok"), "JUMPI", ("PUSH1", 0), "DUP1", "REVERT",
+    "ok:", "JUMPDEST",
+    ("PUSH1", 0), "DUP1", "DUP1", "DUP1", ("PUSH1", 4), "CALLDATALOAD", "GAS", "DELEGATECALL", "STOP",
+])
+METADATA = bytes.fromhex("a264697066735822") + bytes(34) + bytes.fromhex("64736f6c6343000814") + (51).to_bytes(2, "big")
+
+
+class TestKeccak:
+    """Test the pure-Python Keccak-256 against known vectors."""
+
+    def test_vectors(self):
+        assert keccak256(b"").hex() == "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        assert function_selector("transfer(address,uint256)") == "a9059cbb"
+        assert function_selector("balanceOf(address)") == "70a08231"
+
+
+class TestBytecode:
+    """Test disassembly, dispatcher recovery, and effect ordering."""
+
+    def test_disassemble_push_immediates(self):
+        ops = disassemble(bytes.fromhex("6080604052"))
+        assert [(op.name, op.arg) for op in ops] == [("PUSH1", 0x80), ("PUSH1", 0x40), ("MSTORE", None)]
+
+    def test_strip_metadata(self):
+        code, stripped = strip_metadata(RUNTIME + METADATA)
+        assert stripped is True
+        assert code == RUNTIME
+
+    def test_selectors_and_effects(self, tmp_path):
+        binary = tmp_path / "Vault.bin"
+        binary.write_text("0x" + (RUNTIME + METADATA).hex())
+        program = BytecodeFrontend().load(binary)[0]
+
+        assert program.framework == "bytecode"
+        assert program.metadata["stripped_metadata"] is True
+        by_selector = {ix.discriminator: ix for ix in program.instructions}
+        assert set(by_selector) == {f"{WITHDRAW:08x}", f"{EXECUTE:08x}"}
+
+        withdraw = by_selector[f"{WITHDRAW:08x}"]
+        assert [e["op"] for e in withdraw.properties["effects"]] == ["sload", "call", "sstore"]
+        assert withdraw.properties["storage_writes"] == ["0x1"]
+        assert [c.kind for c in withdraw.calls] == ["call"]
+
+        execute = by_selector[f"{EXECUTE:08x}"]
+        assert [c.kind for c in execute.calls] == ["delegatecall"]
+        assert execute.properties["storage_reads"] == ["0x0"]
+        assert execute.properties["storage_writes"] == []
+
+    def test_artifact_json(self, tmp_path):
+        artifact = tmp_path / "Vault.json"
+        artifact.write_text('{"deployedBytecode": {"object": "0x%s"}}' % RUNTIME.hex())
+        program = BytecodeFrontend().load(artifact)[0]
+        assert len(program.instructions) == 2
+
+
+class TestYul:
+    """Test Yul dispatcher recovery and function inlining."""
+
+    def setup_method(self):
+        self.program = YulFrontend().load(FIXTURES / "Vault.yul")[0]
+
+    def test_cases_named_after_handlers(self):
+        assert self.program.name == "Vault"
+        names = [(ix.name, ix.discriminator) for ix in self.program.instructions]
+        assert names == [("withdraw", "2e1a7d4d"), ("execute", "1cff79cd"), ("balanceOf", "70a08231")]
+
+    def test_effects_follow_function_calls(self):
+        withdraw = self.program.instruction("withdraw")
+        assert [e["op"] for e in withdraw.properties["effects"]] == ["sload", "call", "sstore"]
+        assert withdraw.calls[0].target == "caller()"
+        source = (FIXTURES / "Vault.yul").read_text().split("\n")
+        assert "call(gas()" in source[withdraw.calls[0].line - 1]
+
+    def test_delegatecall_target(self):
+        execute = self.program.instruction("execute")
+        assert [(c.kind, c.target) for c in execute.calls] == [("delegatecall", "target")]
+
+
+class TestHuff:
+    """Test Huff jump-table recovery."""
+
+    def setup_method(self):
+        self.program = HuffFrontend().load(FIXTURES)[0]
+
+    def test_func_sig_and_literal_selectors(self):
+        names = [(ix.name, ix.handler, ix.discriminator) for ix in self.program.instructions]
+        assert names == [("withdraw", "WITHDRAW", "2e1a7d4d"), ("owner", "OWNER_OF", "8da5cb5b")]
+
+    def test_macro_effects(self):
+        withdraw = self.program.instruction("withdraw")
+        assert [e["op"] for e in withdraw.properties["effects"]] == ["call", "sstore"]
+        assert withdraw.properties["storage_writes"] == ["[OWNER]"]
+        assert self.program.instruction("owner").properties["storage_reads"] == ["[OWNER]"]
+
+
+def test_load_programs_picks_up_assembly_sources():
+    frameworks = sorted(p.framework for p in load_programs(FIXTURES))
+    assert frameworks == ["huff", "yul"]
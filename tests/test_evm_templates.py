@@ -0,0 +1,86 @@
+"""
+Tests for the EVM template namespace and chains as a registry dimension.
+
+Verifies the `Chain` type, loading of `templates/evm/` (and legacy
+top-level Solidity files), the classes the new Foundry templates hang off,
+and rendering of each new template.
+"""
+
+from pathlib import Path
+
+import pytest
+
+from extensions.knowledge import Chain, TemplateLoader, TemplateRegistry
+from extensions.knowledge.front_matter import FrontMatterError, normalize_front_matter
+from extensions.knowledge.render import TemplateEngine
+
+
+KB_DIR = Path(__file__).resolve().parents[1] / "extensions" / "knowledge"
+EVM_TEMPLATES = ["delegatecall_untrusted", "reentrancy", "signature_replay", "unchecked_return"]
+
+
+class TestEvmTemplates:
+    """Test the chain type, the EVM namespace and its templates."""
+
+    def setup_method(self):
+        self.registry = TemplateRegistry(TemplateLoader(KB_DIR / "templates"))
+
+    def test_chain(self):
+        assert Chain.parse(" Solana ") is Chain.SOLANA and Chain.parse(Chain.SUI) is Chain.SUI
+        assert Chain.EVM == "evm" and f"{Chain.EVM}" == "evm" and {"evm": 1}[Chain.EVM] == 1
+        assert (Chain.SOLANA.label, Chain.SOLANA.template_suffix) == ("Solana", ".rs")
+        with pytest.raises(ValueError, match="Unknown chain 'cosmos'"):
+            Chain.parse("cosmos")
+        with pytest.raises(FrontMatterError, match="Unknown chain"):
+            normalize_front_matter({"chain": "cosmos"})
+
+    def test_namespace(self):
+        by_chain = self.registry.by_chain()
+        assert list(by_chain) == [Chain.COSMWASM, Chain.EVM, Chain.SOLANA, Chain.SUI]
+        for template_id in EVM_TEMPLATES:
+            template = self.registry.get(template_id)
+            assert template.chain is Chain.EVM and template_id in by_chain[Chain.EVM]
+            assert Path(template.path).parent.name == "evm" and template.template.startswith("// SPDX")
+        reentrancy = self.registry.get("reentrancy")
+        assert reentrancy.vuln_class == "reentrant-state-inconsistency" and reentrancy.cwe == ["CWE-841"]
+        assert self.registry.index([reentrancy])[0]["chain"] == "evm"
+
+    def test_query(self):
+        ids = lambda templates: [t.id for t in templates]  # noqa: E731
+        assert ids(self.registry.query(chain=Chain.EVM, swc="SWC-112")) == ["delegatecall_untrusted"]
+        assert ids(self.registry.query(chain="EVM", cwe="252")) == ["unchecked_return"]
+        # Aliases name the class, whose Solana form is a PDA-signed CPI into a caller-chosen program (as
+        # `reentrancy` finds cpi_reentrancy)
+        assert ids(self.registry.query(vuln_class="delegatecall")) == ["delegatecall_untrusted", "arbitrary_cpi"]
+        assert ids(self.registry.query(vuln_class="signature-replay")) == ["signature_replay"]
+        assert ids(self.registry.query(chain=Chain.APTOS)) == []
+        with pytest.raises(ValueError, match="Unknown chain"):
+            self.registry.query(chain="cosmos")
+
+    def test_legacy_top_level(self, tmp_path):
+        (tmp_path / "evm").mkdir()
+        (tmp_path / "legacy.sol").write_text("contract T { uint x = {{AMOUNT}}; }\n")
+        (tmp_path / "evm" / "namespaced.sol").write_text("// ---\n// severity: low\n// ---\ncontract N {}\n")
+        (tmp_path / "evm" / "ignored.rs").write_text("fn main() {}\n")
+        loader = TemplateLoader(tmp_path)
+        assert loader.get("legacy").chain is Chain.EVM and loader.get("namespaced").severity == "low"
+        assert loader.get("ignored") is None and "evm" in loader.get("namespaced").tags
+
+    def test_render(self):
+        contexts = {
+            "delegatecall_untrusted": {"target_contract": "Wallet", "delegate_function": "execute"},
+            "unchecked_return": {"target_contract": "Vault", "deposit_function": "deposit",
+                                 "deposit_amount": "1000"},
+            "signature_replay": {"target_contract": "Airdrop", "claim_function": "claim", "claim_amount": "lots"},
+        }
+        for template_id, context in contexts.items():
+            engine = TemplateEngine(self.registry.get(template_id))
+            if template_id == "signature_replay":
+                # Amounts are checked as integers on EVM
+                assert engine.validate(context)[1] == {"CLAIM_AMOUNT": "'lots' is not an integer"}
+                context["claim_amount"] = "1 ether"
+            text = engine.render(context)
+            assert "{{" not in text and text.startswith("// Generated by Baskerville"), template_id
+        wallet = TemplateEngine(self.registry.get("delegatecall_untrusted")).render(
+            {"target_contract": "Wallet", "delegate_function": "execute", "owner_slot": "3"})
+        assert "uint256 slot = 3;" in wallet and "target.execute(" in wallet
@@ -0,0 +1,202 @@
+"""
+Tests for coverage-guided exploit search: register trace coverage, how
+validation branches steer the corpus, what the search reports, and the CLI.
+"""
+
+import hashlib
+import json
+import struct
+from unittest.mock import MagicMock, patch
+
+import yaml
+from click.testing import CliRunner
+
+from commands.simulate import search
+from extensions.ir.anchor_config import b58encode
+from extensions.monitor.decode import b58decode
+from extensions.simulation import (
+    CorpusEntry,
+    CoverageMap,
+    ExecutionBackend,
+    SearchInput,
+    TxResult,
+    load_search,
+    read_sbf_traces,
+)
+
+PROGRAM_ID = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+U64_MAX = 2 ** 64 - 1
+
+IDL = {
+    "version": "0.1.0",
+    "name": "vault",
+    "instructions": [{"name": "withdraw", "accounts": [{"name": "vault", "isMut": True, "isSigner": False},
+                                                       {"name": "authority", "isMut": False, "isSigner": True}],
+                      "args": [{"name": "amount", "type": "u64"}]}],
+}
+
+
+class VaultBackend(ExecutionBackend):
+    """A vault whose withdraw checks the vault's owner and address, the amount and the authority, except
+    on its legacy path for amounts of 2^63 and up, which skips the authority check. Branches are traced
+    as pcs: 0-6 the legitimate path, 40-41 the legacy path, 90 and up the rejections."""
+
+    def start(self):
+        self.keys, self.accounts = {}, {}
+
+    def keypair(self, name, lamports=0):
+        self.keys[name] = b58encode(hashlib.sha256(name.encode()).digest())
+        return self.keys[name]
+
+    def set_account(self, pubkey, data, owner, lamports=0, executable=False):
+        self.accounts[self.keys.get(pubkey, pubkey)] = {"lamports": lamports, "data": bytes(data),
+                                                        "owner": self.keys.get(owner, owner),
+                                                        "executable": executable}
+
+    def get_account(self, pubkey):
+        account = self.accounts.get(self.keys.get(pubkey, pubkey))
+        return dict(account) if account else None
+
+    def trace(self, pcs):
+        return list(dict.fromkeys((PROGRAM_ID, a, b) for a, b in zip(pcs, pcs[1:])))
+
+    def send(self, instructions, payer, signers=()):
+        ix = instructions[0]
+        keys = [self.keys.get(a.pubkey, a.pubkey) for a in ix.accounts]
+        signed = {self.keys.get(s, s) for s in (payer, *signers)}
+        amount = int.from_bytes(ix.data[8:16], "little")
+        vault = self.accounts.get(keys[0])
+        pcs = [0, 1]
+
+        def reject(pc, error):
+            return TxResult(False, error, [f"Program log: {error}"], 1200, self.trace(pcs + [pc]))
+
+        if not vault or vault["owner"] != PROGRAM_ID:
+            return reject(90, "AccountOwnedByWrongProgram")
+        pcs.append(2)
+        if keys[0] != self.keys["vault"]:
+            return reject(91, "ConstraintSeeds")
+        pcs.append(3)
+        if amount == 0:
+            return reject(92, "ZeroAmount")
+        if amount >= 2 ** 63:
+            pcs.append(40)
+            if amount == U64_MAX:
+                return reject(96, "InvalidAmount")
+            pcs.append(41)
+        else:
+            pcs.append(4)
+            if keys[1] not in signed or b58decode(keys[1]) != vault["data"][:32]:
+                return reject(93, "ConstraintHasOne")
+            pcs.append(5)
+            if amount > 10 ** 6:
+                return reject(94, "InsufficientFunds")
+            pcs.append(6)
+        return TxResult(True, logs=["Program log: Instruction: Withdraw"], compute_units=3000,
+                        coverage=self.trace(pcs))
+
+
+class UntracedBackend(VaultBackend):
+    def send(self, instructions, payer, signers=()):
+        result = super().send(instructions, payer, signers)
+        result.coverage = None
+        return result
+
+
+CONFIG = {
+    "idl": "vault.json",
+    "iterations": 300,
+    "seed": 2,
+    "keypairs": {"authority": 10 ** 10, "vault": 0},
+    "accounts": [{"pubkey": "vault", "owner": PROGRAM_ID, "data": hashlib.sha256(b"authority").hexdigest(),
+                  "lamports": 10 ** 6}],
+    "instructions": [{"program_id": PROGRAM_ID, "instruction": "withdraw", "args": {"amount": 1000},
+                      "accounts": [{"pubkey": "vault", "writable": True}, {"pubkey": "authority", "signer": True}]}],
+}
+
+
+def _search(tmp_path, backend=VaultBackend, **config):
+    (tmp_path / "vault.json").write_text(json.dumps(IDL))
+    return load_search({**CONFIG, **config}, base=tmp_path, make_backend=backend).run()
+
+
+class TestExploitSearch:
+    """Test coverage, the corpus and findings against the fake vault."""
+
+    def test_register_traces(self, tmp_path):
+        def regs(pcs):
+            return b"".join(struct.pack("<12Q", *([0] * 11), pc) for pc in pcs)
+
+        (tmp_path / "a.regs").write_bytes(regs([0, 1, 2, 7, 8, 2, 7]))
+        (tmp_path / "a.program_id").write_text("Cpi1111111111111111111111111111111111111111\n")
+        (tmp_path / "b.regs").write_bytes(regs([0, 5]) + b"\x01\x02")
+        edges = read_sbf_traces(tmp_path, PROGRAM_ID)
+        cpi = "Cpi1111111111111111111111111111111111111111"
+        assert {e for e in edges if e[0] == cpi} == {(cpi, 0, 1), (cpi, 1, 2), (cpi, 2, 7), (cpi, 7, 8),
+                                                      (cpi, 8, 2)}
+        assert (PROGRAM_ID, 0, 5) in edges and len(edges) == 6
+        assert not list(tmp_path.iterdir())
+
+    def test_validation_branches(self):
+        coverage = CoverageMap()
+        assert coverage.add([("p", 0, 1), ("p", 1, 2), ("p", 2, 3)], rejected=False) == 3
+        # Rejected where the legitimate path went on: the branch has both sides
+        assert coverage.add([("p", 0, 1), ("p", 1, 90)], rejected=True) == 1
+        assert coverage.sites == {("p", 1): {2, 90}} and not coverage.unexplored()
+        # Rejected off every accepted path: the branches past it have only the failing side
+        coverage.add([("p", 0, 1), ("p", 1, 2), ("p", 2, 40), ("p", 40, 96)], rejected=True)
+        assert coverage.unexplored() == {("p", 40)}
+
+        deep = CorpusEntry(SearchInput({}), "rejected", 1, {("p", 0), ("p", 1), ("p", 2), ("p", 40)})
+        shallow = CorpusEntry(SearchInput({}), "rejected", 1, {("p", 0), ("p", 1)})
+        assert coverage.energy(deep, coverage.unexplored()) == 2 + 8
+        assert coverage.energy(shallow, coverage.unexplored()) == 2
+        assert coverage.add([("p", 0, 1), ("p", 1, 2), ("p", 2, 40), ("p", 40, 41)], rejected=False) == 1
+        assert not coverage.unexplored() and coverage.energy(deep, set()) == 2
+
+    def test_finds_guarded_bypass(self, tmp_path):
+        (report,) = _search(tmp_path).reports
+        assert report.error is None and report.executions == 301
+        assert report.sites[(PROGRAM_ID, 40)] == {96, 41}
+        assert any("amount=max" in e.input.mutations and e.outcome == "rejected" for e in report.corpus)
+
+        (finding,) = report.findings
+        assert finding.kind == "bypass"
+        assert [s.label for s in finding.input.substitutions] == ["wrong-signer:authority"]
+        assert 2 ** 63 <= finding.input.values["amount"] < U64_MAX
+        hyp = finding.to_hypothesis()
+        assert hyp["vulnerability_type"] == "missing-signer-check" and hyp["severity"] == "critical"
+        assert hyp["title"].startswith("withdraw accepts an attacker signer as `authority` when amount")
+        assert hyp["properties"]["source_tool"] == "coverage-search"
+
+        # The same seed replays the same search
+        (again,) = _search(tmp_path).reports
+        assert again.findings[0].executions == finding.executions
+        assert again.findings[0].input.mutations == finding.input.mutations
+
+    def test_needs_coverage(self, tmp_path):
+        (report,) = _search(tmp_path, UntracedBackend).reports
+        assert report.error.startswith("the backend reports no coverage") and report.executions == 1
+
+    def test_cli(self, tmp_path):
+        (tmp_path / "vault.json").write_text(json.dumps(IDL))
+        path = tmp_path / "withdraw.yaml"
+        path.write_text(yaml.safe_dump(CONFIG))
+        runner = CliRunner()
+        with patch("extensions.simulation.engine.backend_factory", return_value=VaultBackend) as factory:
+            result = runner.invoke(search, [str(path), "--json", "--iterations", "50"])
+            assert result.exit_code == 0, result.output
+            assert factory.call_args[0][0]["trace"] is True
+            data = json.loads(result.output)
+            assert data["summary"]["executions"] == 51
+            assert data["manifest"]["command"]["name"] == "sim search"
+
+            with patch("commands.simulate.ProjectManager") as PM:
+                pm = MagicMock()
+                PM.return_value = pm
+                pm.get_project.return_value = {"path": str(tmp_path)}
+                result = runner.invoke(search, [str(path), "--project", "p", "--import-hypotheses"])
+        assert result.exit_code == 0, result.output
+        assert "301 executions over 1 instructions" in result.output
+        store = json.loads((tmp_path / "hypotheses.json").read_text())["hypotheses"]
+        assert [h["vulnerability_type"] for h in store.values()] == ["missing-signer-check"]
@@ -0,0 +1,128 @@
+"""
+Tests for runtime feature gate awareness: the knowledge base's gates and
+their status per cluster, the feature-gates checker, and the cluster's
+gates in `scan`, `kb features` and generated program tests.
+"""
+
+import base64
+import json
+
+from click.testing import CliRunner
+
+from commands.knowledge import kb
+from commands.scan import scan
+from extensions.knowledge import ClusterFeatures, FeatureLoader
+from extensions.knowledge.feature_loader import activation_slot, fetch_activation
+from extensions.knowledge.idl import parse_idl
+from extensions.knowledge.program_test import generate_program_test
+from extensions.knowledge.template_loader import TemplateLoader
+from extensions.static import FeatureGateChecker, StaticAnalysisPipeline
+from extensions.static.program_ids import is_pubkey
+
+SOURCE = """use anchor_lang::prelude::*;
+use solana_program::big_mod_exp::big_mod_exp;
+use solana_program::compute_units::sol_remaining_compute_units;
+
+#[program]
+pub mod rsa_gate {
+    use super::*;
+
+    pub fn verify(ctx: Context<Verify>, signature: Vec<u8>) -> Result<()> {
+        // poseidon::hashv(...) was the old scheme
+        let message = big_mod_exp(&signature, &[1, 0, 1], &ctx.accounts.key.modulus);
+        require!(sol_remaining_compute_units() > 10_000, ErrorCode::Budget);
+        let rent = Rent::get()?;
+        let (owed, _) = rent.due(ctx.accounts.key.to_account_info().lamports(), 64, 1.0);
+        Ok(())
+    }
+}
+"""
+
+
+def _feature_account(slot: int | None) -> dict:
+    data = bytes([1]) + slot.to_bytes(8, "little") if slot is not None else bytes([0])
+    return {"data": [base64.b64encode(data).decode(), "base64"], "owner": "Feature111111111111111111111111111111111111"}
+
+
+class FakeClient:
+    """Feature accounts of a cluster where every gate but big_mod_exp is active."""
+
+    def __init__(self, *args, **kwargs):
+        self.provider = type("P", (), {"cluster": "devnet"})()
+
+    def get_multiple_accounts(self, addresses, encoding="base64", data_slice=None):
+        big_mod_exp = FeatureLoader().get("big-mod-exp-syscall").feature
+        return [None if a == big_mod_exp else _feature_account(1000) for a in addresses]
+
+
+class TestFeatureGates:
+    """Test feature gate status per cluster and the checks that use it."""
+
+    def test_cluster_status(self):
+        gates = FeatureLoader().get_by_chain("solana")
+        assert len({g.id for g in gates}) == len(gates) and all(is_pubkey(g.feature) for g in gates)
+
+        mainnet = ClusterFeatures("mainnet-beta")
+        big_mod_exp = mainnet.loader.get("big-mod-exp-syscall")
+        assert mainnet.cluster == "mainnet" and not mainnet.is_active(big_mod_exp)
+        assert ClusterFeatures("localnet").is_active(big_mod_exp) and not ClusterFeatures("localnet").inactive()
+        assert mainnet.limits()["max_tx_account_locks"] == 64 and mainnet.limits()["max_cpi_account_infos"] == 128
+        assert mainnet.source.startswith("snapshot ")
+
+        # A gate removing behavior holds while inactive
+        rent = mainnet.loader.get("disable-rent-fees-collection")
+        assert mainnet.is_active(rent) and not mainnet.holds(rent)
+        assert [(g.id, m.group(0)) for g, m in mainnet.unmet(SOURCE)] == [
+            ("big-mod-exp-syscall", "big_mod_exp("), ("disable-rent-fees-collection", "rent.due(")]
+
+        assert activation_slot(_feature_account(42)) == 42 and activation_slot(_feature_account(None)) is None
+        active = fetch_activation(FakeClient(), gates)
+        live = ClusterFeatures("mainnet", active=active)
+        assert live.source == "rpc" and not live.is_active(big_mod_exp)
+        assert live.limits()["max_tx_account_locks"] == 128
+
+    def test_checker(self, tmp_path, anchor_workspace):
+        workspace = anchor_workspace(tmp_path, {"rsa_gate": SOURCE})
+        findings, metadata = FeatureGateChecker().run(workspace)
+        # The commented-out Poseidon call is not a dependency
+        assert [(f.gate.id, f.kind, f.severity) for f in findings] == [
+            ("big-mod-exp-syscall", "inactive", "medium"), ("disable-rent-fees-collection", "active", "low")]
+        assert {d["feature_gate"]: d["holds"] for d in metadata["dependencies"]} == {
+            "big-mod-exp-syscall": False, "remaining-compute-units": True, "disable-rent-fees-collection": False}
+        hyp = findings[0].to_hypothesis()
+        assert hyp["title"] == "Depends on big_mod_exp syscall, not active on mainnet"
+        assert hyp["properties"]["affected_lines"] == [11] and "big_mod_exp(&signature" in hyp["description"]
+
+        testnet, _ = FeatureGateChecker(cluster="testnet").run(workspace)
+        assert [f.gate.id for f in testnet] == ["disable-rent-fees-collection"]
+
+        pipeline = StaticAnalysisPipeline(chain_id="solana", feature_gates_config={"cluster": "devnet"})
+        result = pipeline.run(workspace, tools=["feature-gates"])
+        hyp = next(h for h in result.hypotheses if h["vulnerability_type"] == "feature-gate-inactive")
+        assert hyp["severity"] == "low" and hyp["properties"]["rule_code"] == "SOL-020-FEATURE-GATE"
+        assert result.metadata["tools"]["feature-gates"]["cluster"] == "devnet"
+
+    def test_cli(self, tmp_path, anchor_workspace, monkeypatch):
+        monkeypatch.setattr("extensions.rpc.RpcClient", FakeClient)
+        runner = CliRunner()
+        workspace = anchor_workspace(tmp_path / "ws", {"rsa_gate": SOURCE})
+        data = json.loads(runner.invoke(scan, [str(workspace), "--tool", "feature-gates", "--cluster", "testnet",
+                                               "--json"]).output)
+        assert [h["properties"]["feature_gate"] for h in data["hypotheses"]] == ["disable-rent-fees-collection"]
+
+        result = runner.invoke(kb, ["features", "--json"])
+        assert result.exit_code == 0, result.output
+        listed = json.loads(result.output)
+        assert listed["cluster"] == "mainnet" and listed["limits"]["max_tx_account_locks"] == 64
+        result = runner.invoke(kb, ["features", "--provider", "devnet"])
+        assert result.exit_code == 0, result.output
+        assert "Status on devnet from its feature accounts" in result.output
+        assert "max_tx_account_locks 128" in result.output
+
+        idl = {"address": "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS", "metadata": {"name": "vault"},
+               "instructions": [{"name": "withdraw", "accounts": [{"name": "authority"}], "args": []}]}
+        test = generate_program_test(TemplateLoader().get("missing_signer"), parse_idl(idl), cluster="mainnet")
+        big_mod_exp = FeatureLoader().get("big-mod-exp-syscall").feature
+        assert f'test.deactivate_feature(solana_sdk::pubkey!("{big_mod_exp}"));  // big-mod-exp-syscall' in test
+        assert "deactivate_feature" not in generate_program_test(TemplateLoader().get("missing_signer"),
+                                                                 parse_idl(idl), cluster="localnet")
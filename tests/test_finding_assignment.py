@@ -0,0 +1,98 @@
+"""
+Tests for finding assignment: assignee, reviewer and due date on bounty
+findings, the storage filters and workload stats, and the CLI commands.
+"""
+
+import json
+from datetime import date, timedelta
+
+import yaml
+from click.testing import CliRunner
+
+import commands.bounty as bounty_cli
+from extensions.bounty import BountyStorage, Contest, Finding, FindingFileWriter, FindingState, Severity
+
+
+def _finding(fid: str, severity: Severity = Severity.HIGH, **kwargs) -> Finding:
+    return Finding(id=fid, contest_id="c1", title=f"Finding {fid}", description="Details.", severity=severity,
+                   **kwargs)
+
+
+def _storage(tmp_path) -> BountyStorage:
+    storage = BountyStorage(tmp_path / "bounty")
+    storage.save_contest(Contest(id="c1", platform="code4rena", name="Vault", url="https://example.com/c1"))
+    yesterday = date.today() - timedelta(days=1)
+    storage.save_finding(_finding("f1", assignee="alice", reviewer="bob", due_date=yesterday))
+    storage.save_finding(_finding("f2", Severity.MEDIUM, assignee="Alice"))
+    storage.save_finding(_finding("f3", Severity.LOW, reviewer="bob"))
+    return storage
+
+
+class TestFindingAssignment:
+    """Test assignment fields, filters and commands."""
+
+    def test_model(self):
+        finding = _finding("f1")
+        finding.assign(" alice ", "bob", date(2026, 11, 1))
+        assert (finding.assignee, finding.reviewer, finding.due_date) == ("alice", "bob", date(2026, 11, 1))
+        finding.assign(reviewer="")
+        assert finding.assignee == "alice" and finding.reviewer == ""
+        restored = Finding.from_dict(json.loads(json.dumps(finding.to_dict())))
+        assert restored.assignee == "alice" and restored.due_date == date(2026, 11, 1)
+        # Findings stored before assignment existed load unassigned
+        legacy = {k: v for k, v in finding.to_dict().items() if k not in ("assignee", "reviewer", "due_date")}
+        assert Finding.from_dict(legacy).due_date is None
+
+        finding.due_date = date.today() - timedelta(days=1)
+        assert finding.is_overdue
+        finding.accept()
+        assert not finding.is_overdue
+
+    def test_storage_filters_and_stats(self, tmp_path):
+        storage = _storage(tmp_path)
+        ids = lambda findings: sorted(f.id for f in findings)  # noqa: E731
+        assert ids(storage.list_findings("c1", assignee="alice")) == ["f1", "f2"]
+        assert ids(storage.list_findings("c1", assignee="")) == ["f3"]
+        assert ids(storage.list_findings("c1", reviewer="bob", severity="low")) == ["f3"]
+        assert ids(storage.list_findings("c1", overdue=True)) == ["f1"]
+        stats = storage.contest_stats("c1")
+        assert stats["by_assignee"] == {"alice": 1, "Alice": 1}
+        assert stats["unassigned"] == 1 and stats["overdue"] == 1
+
+        writer = FindingFileWriter(tmp_path / "out")
+        (path,) = [p for p in writer.write(storage.list_findings("c1")) if "finding-f1" in p.name]
+        meta = yaml.safe_load(path.read_text().split("---\n", 2)[1])
+        assert meta["assignee"] == "alice" and meta["reviewer"] == "bob" and "due" in meta
+
+    def test_cli(self, tmp_path, monkeypatch):
+        storage = _storage(tmp_path)
+        monkeypatch.setattr(bounty_cli, "get_storage", lambda: storage)
+        runner = CliRunner()
+
+        result = runner.invoke(bounty_cli.assign, ["c1", "f3", "--to", "carol", "--due", "+2d"])
+        assert result.exit_code == 0, result.output
+        f3 = storage.load_finding("c1", "f3")
+        assert f3.assignee == "carol" and f3.reviewer == "bob" and f3.due_date == date.today() + timedelta(days=2)
+        assert runner.invoke(bounty_cli.assign, ["c1", "f3", "--due", "none"]).exit_code == 0
+        assert storage.load_finding("c1", "f3").due_date is None
+        assert runner.invoke(bounty_cli.assign, ["c1", "f3", "--due", "soon"]).exit_code == 1
+        assert runner.invoke(bounty_cli.assign, ["c1", "f3"]).exit_code == 1
+        assert runner.invoke(bounty_cli.assign, ["c1", "nope", "--to", "x"]).exit_code == 1
+
+        result = runner.invoke(bounty_cli.list_findings, ["c1", "--assignee", "ALICE", "--json"])
+        assert result.exit_code == 0, result.output
+        rows = json.loads(result.output)
+        assert [r["id"] for r in rows] == ["f1", "f2"] and rows[0]["reviewer"] == "bob"
+        result = runner.invoke(bounty_cli.list_findings, ["c1", "--overdue"])
+        assert result.exit_code == 0 and "f1" in result.output and "f2" not in result.output
+
+        for fid in ("f1", "f2"):
+            finding = storage.load_finding("c1", fid)
+            finding.accept()
+            storage.save_finding(finding)
+        out = tmp_path / "export"
+        result = runner.invoke(bounty_cli.export, ["c1", "-o", str(out), "--reviewer", "bob"])
+        assert result.exit_code == 0, result.output
+        assert [p.name for p in out.glob("*.md")] == ["high_f1.md"]
+        assert storage.load_finding("c1", "f1").state == FindingState.EXPORTED
+        assert storage.load_finding("c1", "f2").state == FindingState.ACCEPTED
@@ -0,0 +1,91 @@
+"""
+Tests for the file-per-finding Markdown export.
+"""
+
+from datetime import datetime
+
+import yaml
+
+from extensions.bounty import Finding, FindingFileWriter, Severity
+from extensions.bounty.finding_files import slugify
+
+
+def _finding(fid: str, title: str, severity: Severity, minute: int, **kwargs) -> Finding:
+    return Finding(
+        id=fid,
+        contest_id="c1",
+        title=title,
+        description=f"Description of {title}.",
+        severity=severity,
+        detected_at=datetime(2026, 1, 1, 12, minute),
+        **kwargs,
+    )
+
+
+def _front_matter(text: str) -> dict:
+    assert text.startswith("---\n")
+    return yaml.safe_load(text.split("---\n", 2)[1])
+
+
+class TestFindingFileWriter:
+    """Test file naming, front matter and PoC links."""
+
+    def setup_method(self):
+        self.findings = [
+            _finding("f2", "Unchecked oracle price", Severity.MEDIUM, 1),
+            _finding(
+                "f1", "Missing signer check on withdraw", Severity.HIGH, 2,
+                file_path="programs/vault/src/lib.rs", line_start=40, line_end=52,
+                chain="solana", proof_of_concept="```rust\n#[test]\nfn exploit() {}\n```",
+                recommendation="Require `authority: Signer<'info>`.",
+            ),
+            _finding("f3", "Stale | price feed", Severity.MEDIUM, 0),
+        ]
+
+    def test_stable_file_names(self, tmp_path):
+        paths = FindingFileWriter(tmp_path).write(self.findings)
+        assert [p.name for p in paths] == [
+            "H-01-missing-signer-check-on-withdraw.md",
+            "M-01-stale-price-feed.md",
+            "M-02-unchecked-oracle-price.md",
+        ]
+        # Re-exporting unchanged findings rewrites identical files
+        before = {p.name: p.read_text() for p in paths}
+        again = FindingFileWriter(tmp_path).write(list(reversed(self.findings)))
+        assert {p.name: p.read_text() for p in again} == before
+
+    def test_front_matter_and_sections(self, tmp_path):
+        path = FindingFileWriter(tmp_path).write(self.findings)[0]
+        text = path.read_text()
+        meta = _front_matter(text)
+        assert meta["id"] == "f1"
+        assert meta["severity"] == "high"
+        assert meta["location"] == "programs/vault/src/lib.rs#L40-L52"
+        assert meta["poc"] == "poc/H-01-missing-signer-check-on-withdraw.rs"
+        assert "# [H-01] Missing signer check on withdraw" in text
+        assert "## Recommendation" in text
+        assert "exported_at" not in meta
+
+    def test_inline_poc_written_without_fence(self, tmp_path):
+        FindingFileWriter(tmp_path).write(self.findings)
+        poc = tmp_path / "findings" / "poc" / "H-01-missing-signer-check-on-withdraw.rs"
+        assert poc.read_text() == "#[test]\nfn exploit() {}\n"
+
+    def test_imported_poc_linked(self, tmp_path):
+        project = tmp_path / "project"
+        (project / "poc" / "hyp-7").mkdir(parents=True)
+        finding = _finding("f9", "Reentrancy", Severity.HIGH, 0, hypothesis_id="hyp-7")
+        path = FindingFileWriter(tmp_path / "out", project_dir=project).write([finding])[0]
+        assert _front_matter(path.read_text())["poc"] == str(project / "poc" / "hyp-7")
+
+    def test_index(self, tmp_path):
+        FindingFileWriter(tmp_path).write(self.findings, contest_name="Vault Contest")
+        index = (tmp_path / "findings" / "README.md").read_text()
+        assert index.startswith("# Findings — Vault Contest")
+        assert "| H-01 | [Missing signer check on withdraw](H-01-missing-signer-check-on-withdraw.md) | high |" in index
+        assert "Stale \\| price feed" in index
+
+
+def test_slugify():
+    assert slugify("  Access-Control: owner can't be changed!  ") == "access-control-owner-can-t-be-changed"
+    assert slugify("???") == "finding"
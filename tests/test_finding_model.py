@@ -0,0 +1,126 @@
+"""
+Tests for the shared finding model: stable rule codes in the rule catalog,
+``Finding`` built from detector hits, fingerprints and run diffs, and
+suppressing and serializing findings by code.
+"""
+
+from pathlib import Path
+
+import pytest
+
+from extensions.reporting.formatters import build_findings_report
+from extensions.reporting.sarif import build_sarif
+from extensions.static import Finding, Span, StaticAnalysisPipeline, diff_findings
+from extensions.static.finding import number_occurrences
+from extensions.static.pipeline import _CHAIN_RUNNERS
+from extensions.static.rules import RuleCatalog, RuleCatalogError, load_catalog, rule_code
+from extensions.static.suppressions import SuppressionStore, WorkspaceBaseline
+
+FIXTURE = Path(__file__).parent / "fixtures" / "solana" / "cpi_router"
+
+
+def _hit(line: int = 12, title: str = "Missing signer check on `admin` in set_fee") -> dict:
+    return {"id": "static_missing-signer_0123abcd", "title": title, "severity": "HIGH", "confidence": 0.7,
+            "vulnerability_type": "missing-signer-check", "description": "Anyone can set the fee.",
+            "properties": {"source_tool": "missing-signer", "source_files": ["programs/vault/src/lib.rs",
+                                                                             "programs/vault/src/state.rs"],
+                           "affected_lines": [line, line + 4], "suggested_fix": "Signer<'info>",
+                           "poc_template": "missing_signer"}}
+
+
+class TestFindingModel:
+    """Test rule codes and the Finding type."""
+
+    def test_codes(self):
+        catalog = load_catalog()
+        assert catalog.code("missing-signer/missing-signer-check") == "SOL-001-MISSING-SIGNER"
+        # A family code covers a detector's parameterized rules; an exact code wins over it
+        assert catalog.code("sbf-limits/sbf-stack-frame") == "SOL-015-SBF-LIMITS"
+        assert catalog.code("pda-collisions/pda-seed-namespace-reuse") == "SOL-010-PDA-SEED-NAMESPACE"
+        assert catalog.code("pda-collisions/pda-seed-cross-type") == "SOL-009-PDA-SEED-COLLISION"
+        assert catalog.code("slither/reentrancy-eth") is None
+        assert catalog.rule_for_code("sol-001-missing-signer") == "missing-signer/missing-signer-check"
+        # Every code names a detector the pipeline runs
+        detectors = {name for runners in _CHAIN_RUNNERS.values() for name, _, _ in runners}
+        assert {rule.split("/")[0] for rule in catalog.codes.values()} <= detectors
+
+        with pytest.raises(RuleCatalogError, match="not <CHAIN>-<NNN>-<NAME>"):
+            RuleCatalog.from_dict({"codes": {"SOL-1-X": "a/b"}})
+        with pytest.raises(RuleCatalogError, match="SOL-001 is used more than once"):
+            RuleCatalog.from_dict({"codes": {"SOL-001-A": "a/b", "SOL-001-B": "a/c"}})
+        with pytest.raises(RuleCatalogError, match="a/b has more than one rule code"):
+            RuleCatalog.from_dict({"codes": {"SOL-001-A": "a/b", "SOL-002-A": "a/b"}})
+
+    def test_from_hypothesis(self):
+        finding = Finding.from_hypothesis(_hit())
+        assert (finding.rule_id, finding.rule) == ("SOL-001-MISSING-SIGNER", "missing-signer/missing-signer-check")
+        assert (finding.severity, finding.confidence, finding.detector) == ("high", 0.7, "missing-signer")
+        assert finding.location == Span("programs/vault/src/lib.rs", 12)
+        assert [str(s) for s in finding.related] == ["programs/vault/src/state.rs"]
+        assert (finding.remediation, finding.template) == ("Signer<'info>", "missing_signer")
+
+        # JSON and hypothesis round trips
+        assert Finding.from_dict(finding.to_dict()).to_dict() == finding.to_dict()
+        again = Finding.from_hypothesis(finding.to_hypothesis())
+        assert again.to_dict() == finding.to_dict()
+        assert finding.to_hypothesis()["properties"]["rule_code"] == "SOL-001-MISSING-SIGNER"
+
+        # Third-party hits go by their rule id
+        slither = Finding.from_hypothesis({"title": "Reentrancy", "vulnerability_type": "reentrancy-eth",
+                                           "severity": "informational", "properties": {"source_tool": "slither"}})
+        assert (slither.rule_id, slither.severity, slither.location) == ("slither/reentrancy-eth", "info", None)
+
+    def test_fingerprint_and_diff(self):
+        before = [Finding.from_hypothesis(_hit()), Finding.from_hypothesis(_hit(title="Fixed one"))]
+        # Lines moved, one fixed, one new
+        after = [Finding.from_hypothesis(_hit(line=40)), Finding.from_hypothesis(_hit(title="New one"))]
+        assert before[0].fingerprint == after[0].fingerprint
+        diff = diff_findings(before, after)
+        assert [f.title for f in diff.new] == ["New one"] and [f.title for f in diff.fixed] == ["Fixed one"]
+        assert diff.summary() == "1 new, 1 fixed, 1 unchanged"
+
+    def test_fingerprint_discriminators(self, tmp_path):
+        def math(line: int, field: str = "vault.total", op: str = "+") -> dict:
+            hit = _hit(line, "Unchecked arithmetic in deposit")
+            hit["properties"].update({"instruction": "deposit", "field": field, "operator": op})
+            return hit
+
+        # Same title, told apart by field and operator; repeats by their order
+        hits = [math(12), math(20, op="-"), math(30, field="vault.shares"), math(40), math(50)]
+        prints = [f.fingerprint for f in number_occurrences([Finding.from_hypothesis(h) for h in hits])]
+        assert len(set(prints)) == 5
+        shifted = number_occurrences([Finding.from_hypothesis(math(line + 7)) for line in (12, 40, 50)])
+        assert [f.fingerprint for f in shifted] == [prints[0], prints[3], prints[4]]
+        assert Finding.from_dict(shifted[2].to_dict()).fingerprint == prints[4]
+
+        # Removing the first repeat makes the second look like it
+        diff = diff_findings([Finding.from_hypothesis(h) for h in hits],
+                             [Finding.from_hypothesis(h) for h in hits if h["properties"]["affected_lines"][0] != 12])
+        assert diff.summary() == "0 new, 1 fixed, 4 unchanged"
+        baseline = WorkspaceBaseline(tmp_path)
+        assert baseline.accept(hits) == 5
+        assert baseline.apply([math(13), math(41), math(51)])[0] == []
+
+    def test_pipeline(self):
+        result = StaticAnalysisPipeline(chain_id="solana").run(FIXTURE, tools=["arbitrary-cpi"])
+        assert result.hypotheses
+        assert {h["properties"]["rule_code"] for h in result.hypotheses} == {"SOL-005-ARBITRARY-CPI-SIGNER"}
+        finding = result.findings[0]
+        assert finding.rule_id == "SOL-005-ARBITRARY-CPI-SIGNER" and finding.template == "arbitrary_cpi"
+        assert finding.location.path.endswith(".rs") and finding.location.start_line
+
+    def test_suppress_and_serialize(self, tmp_path):
+        store = SuppressionStore(tmp_path / "suppressions.json")
+        store.suppress("SOL-001-MISSING-SIGNER", reason="Admin is a multisig PDA")
+        other = {**_hit(), "vulnerability_type": "missing-owner-check",
+                 "properties": {"source_tool": "missing-owner", "source_files": ["programs/vault/src/lib.rs"]}}
+        kept, suppressed, _ = store.apply([_hit(), other])
+        assert (kept, suppressed) == ([other], [_hit()])
+        assert rule_code(other) == "SOL-002-MISSING-OWNER"
+
+        run = build_sarif([_hit()])["runs"][0]
+        assert run["tool"]["driver"]["rules"][0]["properties"]["code"] == "SOL-001-MISSING-SIGNER"
+        assert run["results"][0]["properties"]["code"] == "SOL-001-MISSING-SIGNER"
+        entry = build_findings_report([_hit()], "vault", "solana")["findings"][0]
+        assert entry["rule_id"] == "SOL-001-MISSING-SIGNER"
+        assert entry["fingerprint"] == Finding.from_hypothesis(_hit()).fingerprint
@@ -0,0 +1,215 @@
+"""
+Tests for fuzzing corpora: the store, searches resuming from it, replay
+against a fixed build, minimization, and the CLI.
+"""
+
+import hashlib
+import json
+from unittest.mock import MagicMock, patch
+
+import pytest
+import yaml
+from click.testing import CliRunner
+
+from commands.simulate import corpus_stats, minimize, replay, search
+from extensions.ir.anchor_config import b58encode
+from extensions.monitor.decode import b58decode
+from extensions.simulation import (
+    CorpusRecord,
+    CorpusStore,
+    ExecutionBackend,
+    TxResult,
+    load_search,
+    minimize_corpus,
+)
+from extensions.simulation import replay as replay_corpus
+
+PROGRAM_ID = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+
+IDL = {
+    "version": "0.1.0",
+    "name": "vault",
+    "instructions": [{"name": "withdraw", "accounts": [{"name": "vault", "isMut": True, "isSigner": False},
+                                                       {"name": "authority", "isMut": False, "isSigner": True}],
+                      "args": [{"name": "amount", "type": "u64"}]}],
+}
+
+
+class VaultBackend(ExecutionBackend):
+    """A vault whose withdraw skips the authority check on its legacy path (amounts of 2^63 and up). Branches
+    are traced as pcs: 0-6 the legitimate path, 40-41 the legacy path, 90 and up the rejections."""
+
+    legacy_checks_authority = False
+
+    def start(self):
+        self.keys, self.accounts = {}, {}
+
+    def keypair(self, name, lamports=0):
+        self.keys[name] = b58encode(hashlib.sha256(name.encode()).digest())
+        return self.keys[name]
+
+    def set_account(self, pubkey, data, owner, lamports=0, executable=False):
+        self.accounts[self.keys.get(pubkey, pubkey)] = {"lamports": lamports, "data": bytes(data),
+                                                        "owner": self.keys.get(owner, owner),
+                                                        "executable": executable}
+
+    def get_account(self, pubkey):
+        account = self.accounts.get(self.keys.get(pubkey, pubkey))
+        return dict(account) if account else None
+
+    def trace(self, pcs):
+        return list(dict.fromkeys((PROGRAM_ID, a, b) for a, b in zip(pcs, pcs[1:])))
+
+    def send(self, instructions, payer, signers=()):
+        ix = instructions[0]
+        keys = [self.keys.get(a.pubkey, a.pubkey) for a in ix.accounts]
+        signed = {self.keys.get(s, s) for s in (payer, *signers)}
+        amount = int.from_bytes(ix.data[8:16], "little")
+        vault = self.accounts.get(keys[0])
+        pcs = [0, 1]
+
+        def reject(pc, error):
+            return TxResult(False, error, [f"Program log: {error}"], 1200, self.trace(pcs + [pc]))
+
+        def authorized():
+            return keys[1] in signed and b58decode(keys[1]) == vault["data"][:32]
+
+        if not vault or vault["owner"] != PROGRAM_ID:
+            return reject(90, "AccountOwnedByWrongProgram")
+        pcs.append(2)
+        if keys[0] != self.keys["vault"]:
+            return reject(91, "ConstraintSeeds")
+        pcs.append(3)
+        if amount == 0:
+            return reject(92, "ZeroAmount")
+        if amount >= 2 ** 63:
+            pcs.append(40)
+            if amount == 2 ** 64 - 1:
+                return reject(96, "InvalidAmount")
+            if self.legacy_checks_authority and not authorized():
+                return reject(97, "ConstraintHasOne")
+            pcs.append(41)
+        else:
+            pcs.append(4)
+            if not authorized():
+                return reject(93, "ConstraintHasOne")
+            pcs.append(5)
+            if amount > 10 ** 6:
+                return reject(94, "InsufficientFunds")
+            pcs.append(6)
+        return TxResult(True, logs=["Program log: Instruction: Withdraw"], compute_units=3000,
+                        coverage=self.trace(pcs))
+
+
+class FixedBackend(VaultBackend):
+    legacy_checks_authority = True
+
+
+CONFIG = {
+    "idl": "vault.json",
+    "iterations": 300,
+    "seed": 2,
+    "keypairs": {"authority": 10 ** 10, "vault": 0},
+    "accounts": [{"pubkey": "vault", "owner": PROGRAM_ID, "data": hashlib.sha256(b"authority").hexdigest(),
+                  "lamports": 10 ** 6}],
+    "instructions": [{"program_id": PROGRAM_ID, "instruction": "withdraw", "args": {"amount": 1000},
+                      "accounts": [{"pubkey": "vault", "writable": True}, {"pubkey": "authority", "signer": True}]}],
+}
+
+
+def _search(tmp_path, store=None, backend=VaultBackend, **config):
+    (tmp_path / "vault.json").write_text(json.dumps(IDL))
+    return load_search({**CONFIG, **config}, base=tmp_path, make_backend=backend, corpus=store)
+
+
+def _record(**fields):
+    return CorpusRecord(**{"program_id": PROGRAM_ID, "instruction": "withdraw", "bucket": "queue",
+                           "values": {"amount": 5}, "outcome": "accepted", **fields})
+
+
+class TestFuzzCorpus:
+    """Test the store and the searches, replays and minimization around it, against the fake vault."""
+
+    def test_store(self, tmp_path):
+        store = CorpusStore(tmp_path)
+        record = _record(created_at="2026-01-01T00:00:00")
+        assert store.add(record) and not store.add(_record(created_at="2026-02-01T00:00:00", source="arg-fuzz"))
+        assert (tmp_path / PROGRAM_ID / "withdraw" / "queue" / f"{record.id}.json").exists()
+        assert store.add(_record(bucket="crashes", values={"amount": 6}, outcome="overflow",
+                                 created_at="2026-03-01T00:00:00"))
+        assert store.records() == [record, store.records(buckets=("crashes",))[0]]
+        assert store.stats() == {(PROGRAM_ID, "withdraw"): {"queue": 1, "crashes": 1, "witnesses": 0}}
+
+        (ix,) = _search(tmp_path).instructions
+        swapped = _record(substitutions=[{"kind": "wrong-signer", "slot": "owner", "other": None}])
+        with pytest.raises(ValueError, match="no account owner"):
+            swapped.to_input(ix)
+
+    def test_search_resumes(self, tmp_path):
+        store = CorpusStore(tmp_path / "corpus")
+        (report,) = _search(tmp_path, store).run().reports
+        assert report.replayed == 0 and report.saved == len(report.corpus) - 1 + len(report.findings)
+        (witness,) = store.records(buckets=("witnesses",))
+        assert witness.outcome == "bypass" and witness.substitutions[0]["slot"] == "authority"
+
+        # The next session starts from the stored coverage and finds the witness again without mutating
+        (resumed,) = _search(tmp_path, store, iterations=0).run().reports
+        assert resumed.replayed == report.saved and resumed.saved == 0
+        assert resumed.edges == report.edges and [f.kind for f in resumed.findings] == ["bypass"]
+
+    def test_replay_after_fix(self, tmp_path):
+        store = CorpusStore(tmp_path / "corpus")
+        _search(tmp_path, store).run()
+        (before,) = replay_corpus(_search(tmp_path), store, ("witnesses",))
+        assert before.reproduced and before.outcome == "bypass"
+        (after,) = replay_corpus(_search(tmp_path, backend=FixedBackend), store, ("witnesses",))
+        assert not after.reproduced and after.outcome == "rejected" and after.error == "ConstraintHasOne"
+
+    def test_minimize(self, tmp_path):
+        store = CorpusStore(tmp_path / "corpus")
+        _search(tmp_path, store).run()
+        queued = len(store.records(buckets=("queue",)))
+        # Takes no edge the legitimate input does not
+        store.add(_record(values={"amount": 2}, mutations=["amount=2"]))
+        (stats,) = minimize_corpus(_search(tmp_path), store).values()
+        assert stats == {"kept": queued, "removed": 1, "minimized": 1}
+
+        (witness,) = store.records(buckets=("witnesses",))
+        assert witness.minimized and witness.mutations[-1] == "wrong-signer:authority"
+        assert len(witness.mutations) == 2 and 2 ** 63 <= witness.values["amount"] < 2 ** 64 - 1
+        (again,) = replay_corpus(_search(tmp_path), store, ("witnesses",))
+        assert again.reproduced
+        # Minimized inputs are left alone, and the kept queue still takes every edge
+        assert minimize_corpus(_search(tmp_path), store)["withdraw"] == {"kept": stats["kept"], "removed": 0,
+                                                                         "minimized": 0}
+
+    def test_cli(self, tmp_path):
+        (tmp_path / "vault.json").write_text(json.dumps(IDL))
+        path = tmp_path / "withdraw.yaml"
+        path.write_text(yaml.safe_dump(CONFIG))
+        runner = CliRunner()
+        with patch("commands.simulate.ProjectManager") as PM:
+            pm = MagicMock()
+            PM.return_value = pm
+            pm.get_project.return_value = {"path": str(tmp_path)}
+            with patch("extensions.simulation.engine.backend_factory", return_value=VaultBackend):
+                result = runner.invoke(search, [str(path), "--project", "p"])
+                assert result.exit_code == 0, result.output
+                assert "Replayed 0 stored inputs" in result.output
+
+                result = runner.invoke(corpus_stats, ["--project", "p", "--json"])
+                assert result.exit_code == 0, result.output
+                (counts,) = json.loads(result.output)
+                assert counts["instruction"] == "withdraw" and counts["witnesses"] == 1
+
+                result = runner.invoke(minimize, [str(path), "--project", "p", "--json"])
+                assert result.exit_code == 0, result.output
+                assert json.loads(result.output)["withdraw"]["minimized"] == 1
+
+            with patch("extensions.simulation.engine.backend_factory", return_value=FixedBackend):
+                result = runner.invoke(replay, [str(path), "--project", "p"])
+            assert result.exit_code == 0, result.output
+            assert "0 of 1 stored inputs reproduce" in result.output
+
+        result = runner.invoke(replay, [str(path)])
+        assert result.exit_code == 1 and "--corpus" in result.output
@@ -0,0 +1,160 @@
+"""
+Tests for posting findings to GitHub: the context from GitHub Actions'
+environment, check runs with batched annotations, pull request reviews
+that only comment inside the diff and skip findings already posted, and
+`scan --github`.
+"""
+
+import json
+from pathlib import Path
+from unittest.mock import patch
+
+import pytest
+from click.testing import CliRunner
+
+from commands.scan import scan
+from extensions.knowledge.class_loader import ClassLoader
+from extensions.knowledge.template_loader import TemplateLoader
+from extensions.reporting import GitHubClient, GitHubContext, GitHubError, build_findings_report
+from extensions.reporting.github import comment_body
+
+SOURCE = """use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn set_fee(ctx: Context<SetFee>, fee: u64) -> Result<()> {
+        ctx.accounts.config.fee = fee;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetFee<'info> {
+    #[account(mut, has_one = admin)]
+    pub config: Account<'info, Config>,
+    /// CHECK: config admin
+    pub admin: AccountInfo<'info>,
+}
+"""
+
+CONTEXT = GitHubContext("acme/vault", "abc123", "secret", pr=3)
+
+
+def _hit(index: int, line: int, severity: str = "high") -> dict:
+    return {"id": f"hyp_{index}", "title": f"Admin {index} is not a signer", "severity": severity,
+            "vulnerability_type": "missing-signer-check",
+            "properties": {"source_tool": "missing-signer", "source_files": ["programs/vault/src/lib.rs"],
+                           "affected_lines": [line], "poc_template": "missing_signer"}}
+
+
+def _report(hits: list[dict]) -> dict:
+    return build_findings_report(hits, "vault", "solana", templates=TemplateLoader(), classes=ClassLoader())
+
+
+class FakeGitHub:
+    """HTTP transport recording requests; answers from ``responses`` by method and path."""
+
+    def __init__(self, responses=None, status=200):
+        self.responses = responses or {}
+        self.status = status
+        self.requests = []
+
+    def __call__(self, method, url, body, headers, timeout):
+        path = url.split("/repos/acme/vault/", 1)[1]
+        self.requests.append((method, path, json.loads(body) if body else None, headers))
+        if self.status != 200:
+            return self.status, {}, json.dumps({"message": "Resource not accessible by integration"}).encode()
+        return 200, {}, json.dumps(self.responses.get((method, path.split("?")[0]), {})).encode()
+
+
+class TestGitHubAnnotations:
+    """Test posting findings as check runs and pull request reviews."""
+
+    def test_context(self, tmp_path):
+        event = tmp_path / "event.json"
+        event.write_text(json.dumps({"pull_request": {"number": 12, "head": {"sha": "head456"}}}))
+        env = {"GITHUB_TOKEN": "secret", "GITHUB_REPOSITORY": "acme/vault", "GITHUB_SHA": "merge789",
+               "GITHUB_EVENT_PATH": str(event)}
+        context = GitHubContext.from_env(env)
+        assert (context.repo, context.sha, context.pr, context.api_url) == ("acme/vault", "head456", 12,
+                                                                          "https://api.github.com")
+        assert GitHubContext.from_env({**env, "GITHUB_EVENT_PATH": "", "GH_TOKEN": "x"}, pr=4).sha == "merge789"
+        with pytest.raises(GitHubError, match="GITHUB_TOKEN"):
+            GitHubContext.from_env({"GITHUB_REPOSITORY": "acme/vault"})
+        with pytest.raises(GitHubError, match="GITHUB_REPOSITORY"):
+            GitHubContext.from_env({"GITHUB_TOKEN": "secret", "GITHUB_SHA": "abc"})
+
+    def test_check_run(self):
+        fake = FakeGitHub({("POST", "check-runs"): {"id": 7, "html_url": "https://github.com/acme/vault/runs/7"}})
+        report = _report([_hit(i, 10 + i, "high" if i == 0 else "low") for i in range(55)])
+        posted = GitHubClient(CONTEXT, transport=fake).create_check_run(report, prefix="solana", fail_on="high")
+        assert posted == {"mode": "checks", "id": 7, "url": "https://github.com/acme/vault/runs/7",
+                          "conclusion": "failure", "annotations": 55}
+        (method, path, run, headers), (patch_method, patch_path, update, _) = fake.requests
+        assert (method, path, patch_method, patch_path) == ("POST", "check-runs", "PATCH", "check-runs/7")
+        assert headers["Authorization"] == "Bearer secret" and run["head_sha"] == "abc123"
+        first = run["output"]["annotations"][0]
+        assert len(run["output"]["annotations"]) == 50 and len(update["output"]["annotations"]) == 5
+        assert first["path"] == "solana/programs/vault/src/lib.rs" and first["start_line"] == 10
+        assert first["annotation_level"] == "failure" and "Suggested fix: Declare the authority" in first["message"]
+        assert "kb render missing_signer" in first["message"]
+
+        low = GitHubClient(CONTEXT, transport=FakeGitHub({("POST", "check-runs"): {"id": 8}}))
+        assert low.create_check_run(_report([_hit(1, 3, "low")]), fail_on="high")["conclusion"] == "neutral"
+        assert low.create_check_run(_report([_hit(1, 3, "low")]), fail_on="low")["conclusion"] == "failure"
+        assert low.create_check_run(report, fail_on="never")["conclusion"] == "neutral"
+        with pytest.raises(GitHubError, match="HTTP 403: Resource not accessible"):
+            GitHubClient(CONTEXT, transport=FakeGitHub(status=403)).create_check_run(report)
+
+    def test_review(self):
+        report = _report([_hit(0, 5), _hit(1, 40), _hit(2, 6)])
+        already = comment_body(report["findings"][2])
+        fake = FakeGitHub({
+            ("GET", "pulls/3/files"): [{"filename": "programs/vault/src/lib.rs", "patch": "@@ -1,8 +1,9 @@\n x"}],
+            ("GET", "pulls/3/comments"): [{"body": already}],
+            ("GET", "pulls/3/reviews"): [],
+            ("POST", "pulls/3/reviews"): {"html_url": "https://github.com/acme/vault/pull/3#review"},
+        })
+        posted = GitHubClient(CONTEXT, transport=fake).post(report, "review")
+        assert (posted["comments"], posted["listed"], posted["skipped"]) == (1, 1, 1)
+        review = fake.requests[-1][2]
+        assert review["commit_id"] == "abc123" and review["event"] == "COMMENT"
+        comment = review["comments"][0]
+        assert (comment["path"], comment["line"], comment["side"]) == ("programs/vault/src/lib.rs", 5, "RIGHT")
+        assert "**Suggested fix:**" in comment["body"] and "<!-- baskerville:" in comment["body"]
+        assert "programs/vault/src/lib.rs:40" in review["body"]
+
+        with pytest.raises(GitHubError, match="pull request unknown"):
+            GitHubClient(GitHubContext("acme/vault", "abc123", "secret"), transport=fake).post(report, "review")
+
+    def test_cli(self, tmp_path):
+        program = tmp_path / "programs" / "vault"
+        (program / "src").mkdir(parents=True)
+        (program / "Cargo.toml").write_text('[package]\nname = "vault"\nversion = "0.1.0"\n')
+        (program / "src" / "lib.rs").write_text(SOURCE)
+        (tmp_path / "Anchor.toml").write_text(
+            '[programs.localnet]\nvault = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"\n')
+        env = {"GITHUB_TOKEN": "secret", "GITHUB_REPOSITORY": "acme/vault", "GITHUB_SHA": "abc123",
+               "GITHUB_EVENT_PATH": ""}
+        fake = FakeGitHub({("POST", "check-runs"): {"id": 9, "html_url": "https://github.com/acme/vault/runs/9"}})
+        runner = CliRunner()
+        args = [str(tmp_path), "--no-cache", "--json", "--tool", "missing-signer", "--github", "checks"]
+        with patch("extensions.reporting.github.urllib_request", fake):
+            result = runner.invoke(scan, args, env=env)
+        assert result.exit_code == 0, result.output
+        data = json.loads(result.output)
+        # Outside a diff nothing fails the scan, so nothing fails its check run either
+        assert data["github"]["conclusion"] == "neutral" and data["github"]["annotations"] == len(data["hypotheses"])
+        annotations = fake.requests[0][2]["output"]["annotations"]
+        assert Path(annotations[0]["path"]).name == "lib.rs" and "Suggested fix" in annotations[0]["message"]
+
+        with patch("extensions.reporting.github.urllib_request", fake):
+            result = runner.invoke(scan, args + ["--fail-on", "high"], env=env)
+        assert result.exit_code == 1 and json.loads(result.output)["github"]["conclusion"] == "failure"
+
+        result = runner.invoke(scan, args, env={**env, "GITHUB_TOKEN": "", "GH_TOKEN": ""})
+        assert result.exit_code == 1 and "GITHUB_TOKEN" in result.output
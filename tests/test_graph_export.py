@@ -0,0 +1,108 @@
+"""
+Tests for the account/instruction/CPI graph export.
+
+Verifies findings are attached to the account slots, instructions and
+programs they point at, and that the treasury workspace renders as DOT,
+Mermaid and JSON from the `ir graph` command.
+"""
+
+import json
+from pathlib import Path
+
+from click.testing import CliRunner
+
+from commands.ir import graph
+from extensions.ir import load_programs
+from extensions.static.graph_export import annotate_findings, render_graph, to_dot, to_mermaid
+from extensions.static.privilege_paths import build_privilege_graph
+
+WORKSPACE = Path(__file__).parent / "fixtures" / "solana" / "treasury_workspace"
+TREASURY = "programs/treasury/src/lib.rs"
+
+
+def _line(text: str) -> int:
+    source = (WORKSPACE / TREASURY).read_text()
+    return source[:source.index(text)].count("\n") + 1
+
+
+def _finding(title: str, severity: str, lines: list[int], file: str = TREASURY, **extra) -> dict:
+    return {"id": title.lower().replace(" ", "_"), "title": title, "severity": severity, "confidence": 0.6,
+            "properties": {"source_files": [file], "affected_lines": lines}, **extra}
+
+
+class TestGraphExport:
+    """Test finding annotation and the DOT, Mermaid and JSON renderings."""
+
+    def setup_method(self):
+        self.programs = load_programs(WORKSPACE)
+        self.graph = build_privilege_graph(self.programs)
+
+    def test_annotate(self):
+        findings = [
+            _finding("Unchecked authority update", "high", [_line("ctx.accounts.treasury.authority = new_authority")]),
+            _finding("Unpinned recipient", "medium", [_line("pub recipient:")]),
+            _finding("Missing event", "low", [1]),
+            _finding("Path", "critical", [], file="", node_refs=["instruction:keeper.release", "nope"]),
+            _finding("Elsewhere", "high", [3], file="scripts/deploy.ts"),
+        ]
+        unplaced = annotate_findings(self.graph, self.programs, findings, WORKSPACE)
+        assert [f["title"] for f in unplaced] == ["Elsewhere"]
+
+        nodes = self.graph.nodes
+        set_authority = nodes["instruction:treasury.set_authority"].properties
+        assert [f["title"] for f in set_authority["findings"]] == ["Unchecked authority update"]
+        assert set_authority["max_severity"] == "high"
+        recipients = [n.id for n in nodes.values() if "Unpinned recipient" in str(n.properties.get("findings"))]
+        assert recipients and all(r.startswith("account:treasury.") and r.endswith(".recipient") for r in recipients)
+        assert nodes["program:treasury"].properties["max_severity"] == "low"
+        assert nodes["instruction:keeper.release"].properties["max_severity"] == "critical"
+        assert "findings" not in nodes["instruction:treasury.withdraw"].properties
+
+    def test_render(self):
+        annotate_findings(self.graph, self.programs,
+                          [_finding("Takeover", "high", [_line("ctx.accounts.treasury.authority = new_authority")])],
+                          WORKSPACE)
+        dot = to_dot(self.graph)
+        assert dot.startswith('digraph "programs" {') and dot.rstrip().endswith("}")
+        assert dot.count("subgraph cluster_") == 2
+        assert 'label="treasury\\nTres111111111111111111111111111111111111111";' in dot
+        assert ('"instruction:treasury.set_authority" [label="set_authority (unauthenticated)\\n1 finding (high)", '
+                'shape=box, style=filled, fillcolor="#f46036"];') in dot
+        assert '"instruction:treasury.crank" -> "instruction:keeper.release" [label="cpi (PDA-signed)"' in dot
+        assert "contains" not in dot
+
+        mermaid = to_mermaid(self.graph)
+        assert mermaid.startswith("flowchart LR\n")
+        assert mermaid.count("  subgraph ") == 2 and mermaid.count("\n  end\n") == 2
+        ids = {node_id: f"n{i}" for i, node_id in enumerate(self.graph.nodes)}
+        assert f'{ids["instruction:treasury.crank"]} ==>|"cpi (PDA-signed)"| {ids["instruction:keeper.release"]}' \
+            in mermaid
+        assert f"class {ids['instruction:treasury.set_authority']} high" in mermaid
+        assert "classDef high fill:#f46036" in mermaid and "classDef low" not in mermaid
+
+        data = json.loads(render_graph(self.graph, "json"))
+        assert data["stats"]["annotated_nodes"] == 1
+        assert {n["type"] for n in data["nodes"]} >= {"program", "instruction", "account", "state"}
+
+    def test_cli(self, tmp_path):
+        findings = tmp_path / "hypotheses.json"
+        findings.write_text(json.dumps({"hypotheses": {
+            "h1": _finding("Takeover", "high", [_line("ctx.accounts.treasury.authority = new_authority")]),
+            "h2": {**_finding("Hunch", "low", [_line("pub fn sweep")]), "confidence": 0.1},
+        }}))
+        runner = CliRunner()
+        result = runner.invoke(graph, [str(WORKSPACE), "--format", "mermaid", "--findings", str(findings),
+                                       "--min-confidence", "medium"])
+        assert result.exit_code == 0, result.output
+        assert result.output.startswith("flowchart LR") and "classDef high" in result.output
+        assert "classDef low" not in result.output
+
+        out = tmp_path / "graph.json"
+        result = runner.invoke(graph, [str(WORKSPACE), "--format", "json", "--findings", str(findings),
+                                       "--no-findings", "-o", str(out)])
+        assert result.exit_code == 0, result.output
+        assert json.loads(out.read_text())["stats"]["annotated_nodes"] == 0
+
+        result = runner.invoke(graph, [str(WORKSPACE), "-o", str(tmp_path / "graph.dot")])
+        assert result.exit_code == 0 and (tmp_path / "graph.dot").read_text().startswith("digraph")
+        assert runner.invoke(graph, [str(tmp_path / "empty")]).exit_code == 1
@@ -0,0 +1,74 @@
+"""
+Tests for the risk heat-map aggregation.
+"""
+
+from extensions.reporting import build_heatmap
+from extensions.reporting.heatmap import finding_files, finding_weight
+
+
+def _write(path, lines: int):
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text("\n".join(f"line {i};" for i in range(lines)) + "\n")
+
+
+class TestHeatMap:
+    """Test scoring, density and tree aggregation over a small source tree."""
+
+    def setup_method(self):
+        self.hypotheses = {
+            "h1": {"severity": "high", "status": "confirmed", "properties": {"source_files": ["src/Vault.sol"]}},
+            "h2": {"severity": "medium", "confidence": 0.5, "properties": {"source_files": ["src/Vault.sol", "src/oracle/Feed.sol"]}},
+            "h3": {"severity": "critical", "status": "rejected", "properties": {"source_files": ["src/oracle/Feed.sol"]}},
+            "h4": {"severity": "low", "confidence": 0.9},
+        }
+
+    def test_file_scores_and_heat(self, tmp_path):
+        _write(tmp_path / "src" / "Vault.sol", 100)
+        _write(tmp_path / "src" / "oracle" / "Feed.sol", 50)
+        _write(tmp_path / "src" / "Math.sol", 200)
+        data = build_heatmap(self.hypotheses, tmp_path)
+        files = {f["path"]: f for f in data["files"]}
+        # high confirmed (5.0) + half of medium at 0.5 confidence (0.5)
+        assert files["src/Vault.sol"]["score"] == 5.5
+        assert files["src/Vault.sol"]["density"] == 55.0
+        assert files["src/Vault.sol"]["heat"] == 1.0
+        # Rejected critical contributes nothing
+        assert files["src/oracle/Feed.sol"]["score"] == 0.5
+        assert files["src/Math.sol"]["heat"] == 0.0
+        assert data["files"][0]["path"] == "src/Vault.sol"
+        assert data["unlocated_findings"] == 1
+
+    def test_tree_aggregates_directories(self, tmp_path):
+        _write(tmp_path / "src" / "Vault.sol", 100)
+        _write(tmp_path / "src" / "oracle" / "Feed.sol", 50)
+        tree = build_heatmap(self.hypotheses, tmp_path)["tree"]
+        src = tree["children"][0]
+        assert src["name"] == "src"
+        assert src["loc"] == 150
+        assert src["score"] == 6.0
+        assert src["findings"] == 3
+        oracle = next(c for c in src["children"] if c["name"] == "oracle")
+        assert oracle["children"][0]["path"] == "src/oracle/Feed.sol"
+
+    def test_findings_only_without_root(self):
+        data = build_heatmap(self.hypotheses, None)
+        assert {f["path"] for f in data["files"]} == {"src/Vault.sol", "src/oracle/Feed.sol"}
+        # Without line counts heat falls back to raw score
+        assert all(f["density"] is None for f in data["files"])
+        assert data["files"][0]["heat"] == 1.0
+
+    def test_skips_dependency_dirs(self, tmp_path):
+        _write(tmp_path / "src" / "Vault.sol", 10)
+        _write(tmp_path / "lib" / "forge-std" / "Test.sol", 10)
+        data = build_heatmap({}, tmp_path)
+        assert [f["path"] for f in data["files"]] == ["src/Vault.sol"]
+
+
+def test_finding_helpers(tmp_path):
+    assert finding_weight({"severity": "high", "confidence": 0.4}) == 2.0
+    assert finding_weight({"severity": "unknown-level", "status": "confirmed"}) == 2.0
+    finding = {
+        "properties": {"source_files": [str(tmp_path / "src" / "A.sol"), "src/B.sol:12"]},
+        "supporting_evidence": [{"file": "/src/C.sol"}],
+    }
+    assert finding_files(finding, tmp_path) == ["src/A.sol", "src/B.sol", "src/C.sol"]
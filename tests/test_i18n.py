@@ -0,0 +1,146 @@
+"""
+Tests for localized output: message catalogs, knowledge-base overlays and
+report text in the selected language.
+"""
+
+import json
+import unittest.mock
+from pathlib import Path
+
+import pytest
+from click.testing import CliRunner
+
+from commands.knowledge import show_class
+from extensions.i18n import LOCALES, Translator, normalize_locale
+from extensions.knowledge import ChecklistLoader, ClassLoader, TipLoader
+
+
+KB_DIR = Path(__file__).resolve().parents[1] / "extensions" / "knowledge"
+
+
+class TestTranslator:
+    """Test locale selection and message lookup."""
+
+    def test_normalize_locale(self):
+        assert normalize_locale(None) == "en"
+        assert normalize_locale("zh-CN") == "zh"
+        assert normalize_locale("ja_JP") == "ja"
+        assert normalize_locale("ES") == "es"
+        with pytest.raises(ValueError, match="unsupported language"):
+            normalize_locale("klingon")
+
+    def test_catalogs_cover_english_keys(self):
+        english = Translator("en")
+        for code in LOCALES:
+            translator = Translator(code)
+            for key in english._messages["report"]:
+                assert translator._lookup(translator._messages, f"report.{key}"), (code, key)
+
+    def test_fallback(self, tmp_path):
+        (tmp_path / "en.yaml").write_text("report:\n  findings: Findings\n  issues: Issues\n")
+        (tmp_path / "ja.yaml").write_text("report:\n  findings: 検出事項\n")
+        translator = Translator("ja", tmp_path)
+        assert translator.t("report.findings") == "検出事項"
+        assert translator.t("report.issues") == "Issues"
+        assert translator.t("report.missing", default="Fallback") == "Fallback"
+        assert translator.t("report.missing") == "report.missing"
+        assert translator.language == "Japanese" and not translator.is_default
+
+
+class TestKnowledgeOverlays:
+    """Test translated descriptions and fix guidance."""
+
+    def test_class_overlay(self):
+        vuln_class = ClassLoader(KB_DIR / "classes", locale="zh").get("unauthenticated-privileged-action")
+        assert vuln_class.name == "未经认证的特权操作"
+        assert "Signer<'info>" in vuln_class.fix_for("solana")
+        assert vuln_class.variant("solana").name == "缺少签名者检查"
+        # Untranslated fields and aliases are kept
+        assert "missing-signer" in vuln_class.variant("solana").aliases
+        english = ClassLoader(KB_DIR / "classes").get("unauthenticated-privileged-action")
+        assert english.name == "Unauthenticated privileged action"
+
+    def test_every_class_translated(self):
+        english = ClassLoader(KB_DIR / "classes")
+        for code in ("zh", "es", "ja"):
+            localized = ClassLoader(KB_DIR / "classes", locale=code)
+            for vuln_class in english.list_all():
+                assert localized.get(vuln_class.id).fix != vuln_class.fix, (code, vuln_class.id)
+
+    def test_checklist_and_tip_overlays(self, tmp_path, monkeypatch):
+        monkeypatch.setattr(ChecklistLoader, "_load_cached_solodit", lambda self: [])
+        (tmp_path / "checklists" / "locales").mkdir(parents=True)
+        (tmp_path / "checklists" / "auth.yaml").write_text(
+            "category: Auth\nitems:\n  - id: AUTH-1\n    question: Is the caller checked?\n"
+            "    description: d\n    remediation: Check it\n")
+        (tmp_path / "checklists" / "locales" / "es.yaml").write_text(
+            "AUTH-1:\n  question: ¿Se verifica al llamante?\n")
+        items = ChecklistLoader(tmp_path, locale="es").get_all()
+        assert [(i.question, i.remediation) for i in items] == [("¿Se verifica al llamante?", "Check it")]
+
+        tips_dir = tmp_path / "tips"
+        (tips_dir / "locales").mkdir(parents=True)
+        (tips_dir / "core.yaml").write_text(
+            "tips:\n  - id: T-1\n    title: Check signers\n    category: Auth\n    tip: Always\n")
+        (tips_dir / "locales" / "ja.yaml").write_text("T-1:\n  title: 署名者を確認する\n")
+        tip = next(t for t in TipLoader(tips_dir, locale="ja").get_all() if t.id == "T-1")
+        assert (tip.title, tip.tip) == ("署名者を確認する", "Always")
+
+    def test_cli_lang(self, monkeypatch):
+        monkeypatch.setattr(ChecklistLoader, "_load_cached_solodit", lambda self: [])
+        runner = CliRunner()
+        result = runner.invoke(show_class, ["missing_signer", "--lang", "es"])
+        assert result.exit_code == 0, result.output
+        assert "Acción privilegiada" in result.output
+        result = runner.invoke(show_class, ["missing_signer", "--lang", "xx"])
+        assert result.exit_code != 0
+        assert "unsupported language" in result.output
+
+
+class TestLocalizedReport:
+    """Test report labels and boilerplate in the selected language."""
+
+    @staticmethod
+    def _findings(tmp_path, locale):
+        from analysis.report_generator import ReportGenerator
+
+        (tmp_path / "project.json").write_text(json.dumps({"chain_id": "solana"}))
+        (tmp_path / "hypotheses.json").write_text(json.dumps({"hypotheses": {
+            "h1": {"title": "Withdraw authority never signs", "vulnerability_type": "missing_signer",
+                   "status": "confirmed", "severity": "high", "description": "d"},
+        }}))
+        with unittest.mock.patch("analysis.report_generator.UnifiedLLMClient"):
+            rg = ReportGenerator(tmp_path, {}, locale=locale)
+        with unittest.mock.patch.object(rg, "_batch_generate_vulnerability_descriptions", return_value={}), \
+                unittest.mock.patch.object(rg, "_extract_code_for_finding", return_value=[]):
+            return rg, rg._get_confirmed_findings()
+
+    def test_markdown_japanese(self, tmp_path):
+        rg, findings = self._findings(tmp_path, "ja")
+        md = rg._format_findings_markdown(findings)
+        assert "### [高] Withdraw authority never signs" in md
+        assert "**脆弱性クラス:** 認証されていない特権操作" in md
+        assert "**推奨される対策:** " in md and "Signer<'info>" in md
+        assert "**影響範囲:**" in md
+        report = rg._generate_markdown_report(
+            application_name="App", project_name="app", project_source="src", report_date="2026-01-01",
+            auditors=["A"], executive_summary="", system_overview="", findings=findings)
+        assert report.startswith("# セキュリティ監査報告書")
+        assert "## 範囲と手法" in report and "Scope & Methodology" not in report
+        assert rg._format_findings_markdown([]) == "*本監査では確認済みの脆弱性は検出されませんでした。*"
+
+    def test_html_chinese(self, tmp_path):
+        rg, findings = self._findings(tmp_path, "zh")
+        html = rg._format_findings_html(findings)
+        assert "<strong>漏洞类别:</strong> 未经认证的特权操作" in html
+        assert "<strong>受影响组件:</strong>" in html
+        assert '<span class="severity-badge severity-high">高危</span>' in html
+        assert "Affected Components" not in html
+        assert "Write all prose in Simplified Chinese" in rg._language_rule()
+
+    def test_english_unchanged(self, tmp_path):
+        rg, findings = self._findings(tmp_path, None)
+        assert rg._language_rule() == ""
+        md = rg._format_findings_markdown(findings)
+        assert "### [HIGH] Withdraw authority never signs" in md
+        assert "**Affected:**" in md
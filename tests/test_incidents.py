@@ -0,0 +1,79 @@
+"""
+Tests for the real-world exploit database: loading and its links to classes
+and templates, ranking the incidents related to a finding, and citing them
+in findings reports and `kb incidents`.
+"""
+
+import json
+
+from click.testing import CliRunner
+
+from commands.knowledge import kb
+from extensions.knowledge import IncidentLoader, KnowledgeBase
+from extensions.knowledge.template_loader import TemplateLoader
+from extensions.reporting.formatters import build_findings_report, get_formatter
+
+OWNER = {"id": "hyp_owner", "title": "Vault data trusted without an owner check", "severity": "high",
+         "vulnerability_type": "missing-owner-check", "properties": {"poc_template": "missing_owner_check"}}
+
+
+class TestIncidents:
+    """Test loading incidents and relating them to findings."""
+
+    def test_links(self):
+        kb_instance = KnowledgeBase()
+        incidents = kb_instance.incidents.list_all()
+        assert len({i.id for i in incidents}) == len(incidents)
+        assert [i.date for i in incidents] == sorted((i.date for i in incidents), reverse=True)
+        wormhole = kb_instance.incidents.get("wormhole-2022")
+        assert wormhole.chain == "solana" and wormhole.loss() == "$326M" and wormhole.references
+        assert {i.chain for i in incidents} >= {"solana", "evm", "sui"}
+
+        # Every class, template and incident id a link names exists
+        for incident in incidents:
+            assert all(kb_instance.classes.get(c) for c in incident.classes), incident.id
+            assert all(kb_instance.templates.get(t) for t in incident.templates), incident.id
+        for template in kb_instance.templates.list_all():
+            assert all(kb_instance.incidents.get(i) for i in template.incidents), template.id
+        owner = kb_instance.templates.get("missing_owner_check")
+        assert owner.metadata()["incidents"][0] == "wormhole-2022"
+        assert [i.id for i in kb_instance.incidents.for_template(owner)][:1] == ["wormhole-2022"]
+        assert "cream-oracle-2021" in {i.id for i in kb_instance.incidents.for_template(
+            kb_instance.templates.get("flash_loan"))}
+
+    def test_for_finding(self, tmp_path):
+        kb_instance = KnowledgeBase()
+        related = kb_instance.related_incidents(OWNER, "solana")
+        # The template's incidents, largest loss first
+        assert [i.id for i in related] == ["wormhole-2022", "cashio-2022", "crema-2022"]
+        reentrancy = kb_instance.related_incidents({"vulnerability_type": "reentrancy"}, "evm", limit=None)
+        assert [i.id for i in reentrancy] == ["fei-rari-2022", "the-dao-2016", "cream-amp-2021"]
+        assert kb_instance.related_incidents({"vulnerability_type": "nothing-like-it"}) == []
+
+        # A class alone relates a finding; incidents on its chain come first
+        loader = IncidentLoader()
+        ranked = loader.for_finding({"vulnerability_type": "price-manipulation"}, "solana", "price-manipulation")
+        assert ranked[0].id == "mango-markets-2022" and len(ranked) == 3
+        assert loader.for_finding({}, vuln_class="unchecked-arithmetic", limit=None)[0].id == "cetus-2025"
+
+        (tmp_path / "broken.yaml").write_text("incidents:\n  - name: no id\n")
+        assert IncidentLoader(tmp_path).list_all() == []
+
+    def test_report_and_cli(self):
+        report = build_findings_report([OWNER], "vault", "solana", templates=TemplateLoader(),
+                                       incidents=IncidentLoader())
+        cited = report["findings"][0]["incidents"]
+        assert cited[0]["id"] == "wormhole-2022" and cited[0]["references"] and len(cited) == 3
+        markdown = get_formatter("markdown").format(report)
+        assert "**Related incidents:** [Wormhole bridge signature verification bypass](https://rekt.news/" in markdown
+        assert "(2022)" in markdown
+        assert build_findings_report([OWNER], "vault", "solana")["findings"][0]["incidents"] == []
+
+        runner = CliRunner()
+        result = runner.invoke(kb, ["incidents", "--chain", "sui", "--json"])
+        assert result.exit_code == 0, result.output
+        assert [i["id"] for i in json.loads(result.output)] == ["cetus-2025"]
+        result = runner.invoke(kb, ["incidents", "wormhole-2022"])
+        assert result.exit_code == 0 and "load_instruction_at" in result.output and "$326M" in result.output
+        result = runner.invoke(kb, ["incidents", "reentrancy", "--json"])
+        assert "the-dao-2016" in {i["id"] for i in json.loads(result.output)}
@@ -0,0 +1,108 @@
+"""
+Tests for on-chain instruction usage: sampling a program's transactions
+through the RPC client, exposure per instruction, and ranking hits by it.
+"""
+
+import json
+import shutil
+from pathlib import Path
+from unittest.mock import patch
+
+from click.testing import CliRunner
+
+from commands.scan import scan
+from extensions.ir import load_programs
+from extensions.ir.anchor_config import b58encode
+from extensions.monitor import anchor_discriminator
+from extensions.monitor.usage import UNKNOWN, collect_usage, weight_hits
+
+VAULT = Path(__file__).resolve().parent / "fixtures" / "solana" / "anchor_vault"
+PROGRAM_ID = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+ADMIN = "Adm1111111111111111111111111111111111111111"
+
+
+def _tx(signature: str, payer: str, name: str, slot: int, err=None) -> dict:
+    data = b58encode(anchor_discriminator(name)) if name != UNKNOWN else b58encode(b"\x00" * 8)
+    return {"slot": slot, "transaction": {"signatures": [signature], "message": {
+        "header": {"numRequiredSignatures": 1, "numReadonlySignedAccounts": 0, "numReadonlyUnsignedAccounts": 1},
+        "accountKeys": [payer, PROGRAM_ID],
+        "instructions": [{"programIdIndex": 1, "data": data, "accounts": [0]}]}},
+        "meta": {"err": err}}
+
+
+class FakeClient:
+    """Serves a fixed history: many deposits by many users, one withdraw by the admin."""
+
+    def __init__(self, *args, **kwargs):
+        txs = [_tx(f"dep{i}", f"User{i % 40}", "deposit", 1000 + i, err={"x": 1} if i % 10 == 0 else None)
+               for i in range(120)]
+        txs += [_tx("adm", ADMIN, "withdraw", 900), _tx("odd", ADMIN, UNKNOWN, 901)]
+        self.txs = {tx["transaction"]["signatures"][0]: tx for tx in sorted(txs, key=lambda t: -t["slot"])}
+        self.pages = []
+
+    def get_signatures_for_address(self, address, limit=1000, before=None, commitment="confirmed"):
+        assert address == PROGRAM_ID
+        names = list(self.txs)
+        start = names.index(before) + 1 if before else 0
+        self.pages.append(limit)
+        return [{"signature": s} for s in names[start:start + limit]]
+
+    def get_transaction(self, signature, commitment="finalized"):
+        return self.txs.get(signature)
+
+    def parallel(self, fn, items):
+        return [fn(item) for item in items]
+
+
+def _program():
+    return next(p for p in load_programs(VAULT) if p.name == "vault")
+
+
+class TestInstructionUsage:
+    """Test usage sampling and exposure-weighted ranking."""
+
+    def test_collect(self):
+        client = FakeClient()
+        usage = collect_usage(client, _program(), PROGRAM_ID, limit=100)
+        # The latest 100, newest first
+        assert usage.transactions == 100 and (usage.first_slot, usage.last_slot) == (1020, 1119)
+        deposit = usage.usage("deposit")
+        assert (deposit.calls, len(deposit.callers), deposit.failed) == (100, 40, 10)
+
+        usage = collect_usage(client, _program(), PROGRAM_ID, limit=500)
+        # A short page is the end of the history
+        assert client.pages == [100, 500] and usage.transactions == 122 and usage.instructions[UNKNOWN].calls == 1
+        assert usage.exposure("deposit") == 1.0 and usage.exposure("initialize") == 0.0
+        assert 0 < usage.exposure("withdraw") < 0.25
+        assert list(usage.to_dict()["instructions"])[0] == "deposit"
+
+    def test_weight_hits(self):
+        usage = collect_usage(FakeClient(), _program(), PROGRAM_ID, limit=500)
+        admin = {"severity": "medium", "properties": {"program": "vault", "instruction": "withdraw"}}
+        admin_high = {"severity": "high", "properties": {"program": "vault", "instruction": "withdraw"}}
+        hot = {"severity": "medium", "properties": {"program": "vault", "instruction": "deposit"}}
+        other = {"severity": "medium", "properties": {"program": "other", "instruction": "deposit"}}
+        # Among mediums the hot path ranks first and the admin path last; unsampled hits stay neutral.
+        # A high in the admin path still ranks above them.
+        ranked = weight_hits([admin, other, hot, admin_high], {"vault": usage})
+        assert ranked == [admin_high, hot, other, admin]
+        assert hot["properties"]["priority"] == 3.0 and other["properties"]["priority"] == 2.0
+        assert hot["properties"]["usage"]["calls"] == 120 and "usage" not in other["properties"]
+
+    def test_scan(self, tmp_path, monkeypatch):
+        monkeypatch.setenv("HOME", str(tmp_path))
+        workspace = tmp_path / "vault"
+        shutil.copytree(VAULT, workspace)
+        runner = CliRunner()
+        with patch("extensions.rpc.RpcClient", FakeClient):
+            result = runner.invoke(scan, [str(workspace), "--json", "--usage"])
+            assert result.exit_code == 0, result.output
+            data = json.loads(result.output)
+            assert data["usage"]["vault"]["instructions"]["deposit"]["calls"] == 120
+            top = data["hypotheses"][0]["properties"]
+            assert top["instruction"] == "deposit" and top["usage"]["exposure"] == 1.0
+
+            result = runner.invoke(scan, [str(workspace), "--usage"])
+            assert "Sampled 122 transactions of vault" in result.output and "120 by 40" in result.output
+        plain = json.loads(runner.invoke(scan, [str(workspace), "--json"]).output)
+        assert plain["usage"] is None and "priority" not in plain["hypotheses"][0]["properties"]
@@ -0,0 +1,247 @@
+"""
+Tests for the third-party integration checker.
+
+Verifies the integration requirements in the knowledge base load, and that
+a workspace integrating Pyth, Switchboard, SPL Governance, Wormhole and
+Jupiter is flagged where it skips their documented validation and passes
+where it follows it.
+"""
+
+import re
+from pathlib import Path
+
+from click.testing import CliRunner
+
+from commands.knowledge import kb
+from extensions.knowledge.integration_loader import IntegrationLoader
+from extensions.static import IntegrationChecker, StaticAnalysisPipeline
+
+
+KB_DIR = Path(__file__).resolve().parents[1] / "extensions" / "knowledge"
+
+UNSAFE = """use anchor_lang::prelude::*;
+use pyth_sdk_solana::state::SolanaPriceAccount;
+use switchboard_v2::AggregatorAccountData;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod lending {
+    use super::*;
+
+    pub fn borrow(ctx: Context<Borrow>, amount: u64) -> Result<()> {
+        let feed = SolanaPriceAccount::account_info_to_feed(&ctx.accounts.oracle)?;
+        let price = feed.get_price_unchecked();
+        let backup = AggregatorAccountData::new(&ctx.accounts.feed)?.get_result()?;
+        Ok(())
+    }
+
+    pub fn receive(ctx: Context<Receive>) -> Result<()> {
+        let vaa = PostedVaaData::try_from_slice(&ctx.accounts.posted_vaa.data.borrow())?;
+        let ix = load_instruction_at(0, &ctx.accounts.instructions)?;
+        Ok(())
+    }
+
+    pub fn vote(ctx: Context<Vote>) -> Result<()> {
+        let record = TokenOwnerRecordV2::try_from_slice(&ctx.accounts.voter_record.data.borrow())?;
+        let checked = get_token_owner_record_data(&ctx.accounts.governance_program.key(), &ctx.accounts.voter_record)?;
+        Ok(())
+    }
+
+    pub fn swap(ctx: Context<Swap>, data: Vec<u8>) -> Result<()> {
+        invoke(&Instruction { program_id: ctx.accounts.jupiter_program.key(), accounts: vec![], data }, &[])?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Borrow<'info> {
+    /// CHECK: Pyth price account
+    pub oracle: AccountInfo<'info>,
+    /// CHECK: Switchboard aggregator
+    pub feed: AccountInfo<'info>,
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Receive<'info> {
+    /// CHECK: Wormhole posted VAA
+    pub posted_vaa: UncheckedAccount<'info>,
+    /// CHECK: instructions sysvar
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Vote<'info> {
+    /// CHECK: token owner record
+    pub voter_record: AccountInfo<'info>,
+    /// CHECK: spl-governance
+    pub governance_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    /// CHECK: Jupiter
+    pub jupiter_program: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub output: Account<'info, TokenAccount>,
+}
+"""
+
+SAFE = """use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+declare_id!("9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin");
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn liquidate(ctx: Context<Liquidate>) -> Result<()> {
+        let price = ctx.accounts.price_update.get_price_no_older_than(&Clock::get()?, 60, &FEED_ID)?;
+        require!(price.conf * 50 < price.price as u64, VaultError::Confidence);
+        let value = scale(price.price, price.exponent);
+        let backup = ctx.accounts.feed.load()?;
+        backup.check_staleness(Clock::get()?.unix_timestamp, 300)?;
+        backup.check_confidence_interval(SwitchboardDecimal::from_f64(0.8))?;
+        Ok(())
+    }
+
+    pub fn redeem(ctx: Context<Redeem>) -> Result<()> {
+        let vaa = &ctx.accounts.posted;
+        require!(vaa.emitter_chain() == 2 && *vaa.emitter_address() == ctx.accounts.config.foreign_emitter,
+                 VaultError::Emitter);
+        Ok(())
+    }
+
+    pub fn vote(ctx: Context<Vote>) -> Result<()> {
+        let record = get_token_owner_record_data_for_realm_and_governing_mint(
+            &spl_governance::ID, &ctx.accounts.voter_record, &ctx.accounts.realm.key(), &ctx.accounts.mint.key())?;
+        Ok(())
+    }
+
+    pub fn swap(ctx: Context<Swap>, data: Vec<u8>, min_amount_out: u64) -> Result<()> {
+        let before = ctx.accounts.output.amount;
+        invoke(&Instruction { program_id: jupiter::ID, accounts: vec![], data }, &[])?;
+        ctx.accounts.output.reload()?;
+        require!(ctx.accounts.output.amount - before >= min_amount_out, VaultError::Slippage);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Liquidate<'info> {
+    pub price_update: Account<'info, PriceUpdateV2>,
+    #[account(address = vault.feed)]
+    pub feed: AccountLoader<'info, AggregatorAccountData>,
+    pub vault: Account<'info, Vault>,
+}
+
+#[derive(Accounts)]
+pub struct Redeem<'info> {
+    pub config: Account<'info, Config>,
+    #[account(seeds = [b"PostedVAA", &hash], bump, seeds::program = wormhole::program::ID)]
+    pub posted: Account<'info, wormhole::PostedVaa<Message>>,
+    #[account(init, payer = payer, seeds = [b"received", &vaa_hash], bump, space = 8)]
+    pub received: Account<'info, Received>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Vote<'info> {
+    /// CHECK: token owner record
+    pub voter_record: AccountInfo<'info>,
+    pub realm: Account<'info, Realm>,
+    pub mint: Account<'info, Mint>,
+    /// CHECK: pinned
+    #[account(address = spl_governance::ID)]
+    pub governance_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    /// CHECK: pinned
+    #[account(address = jupiter::ID)]
+    pub jupiter_program: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub output: Account<'info, TokenAccount>,
+}
+"""
+
+
+# Crate name -> source of the workspace the tests scan
+PROGRAMS = {"lending": UNSAFE, "vault": SAFE}
+
+
+class TestIntegrations:
+    """Test integration requirements and the checker."""
+
+    def test_knowledge(self, tmp_path):
+        loader = IntegrationLoader(KB_DIR / "integrations")
+        assert [i.id for i in loader.get_by_chain("solana")] == ["pyth", "switchboard", "spl-governance",
+                                                                 "wormhole", "jupiter"]
+        pyth = loader.get("pyth")
+        assert pyth.category == "oracle" and pyth.docs.startswith("https://")
+        pinned = pyth.requirement("PYTH-02")
+        assert pinned.require_account == [r"address\s*="] and "{account}" in pinned.require[0]
+        assert "\n" not in pinned.description
+        assert all(r.vulnerability_type and r.severity in ("high", "medium", "low")
+                   for i in loader.list_all() for r in i.requirements)
+
+        (tmp_path / "bad.yaml").write_text("integrations:\n  - id: broken\n    detect: ['(']\n")
+        assert IntegrationLoader(tmp_path).list_all() == []
+
+        result = CliRunner().invoke(kb, ["integrations", "wormhole"])
+        assert result.exit_code == 0, result.output
+        assert "WH-05" in result.output and "load_instruction_at" in result.output
+        assert CliRunner().invoke(kb, ["integrations", "chainlink"]).exit_code == 1
+
+    def test_deviations(self, tmp_path, anchor_workspace):
+        findings, metadata = IntegrationChecker().run(anchor_workspace(tmp_path, PROGRAMS))
+        assert metadata["integrations"]["pyth"] == ["lending::borrow", "vault::liquidate"]
+        assert metadata["integrations"]["jupiter"] == ["lending::swap", "vault::swap"]
+        assert {f.program for f in findings} == {"lending"}
+
+        got = {(f.instruction, f.requirement.id, f.account) for f in findings}
+        assert got == {
+            ("borrow", "PYTH-01", None), ("borrow", "PYTH-02", "oracle"), ("borrow", "PYTH-05", None),
+            ("borrow", "PYTH-06", None), ("borrow", "SB-01", "feed"), ("borrow", "SB-02", "feed"),
+            ("borrow", "SB-03", None), ("borrow", "SB-04", None),
+            ("receive", "WH-01", "posted_vaa"), ("receive", "WH-02", None), ("receive", "WH-03", None),
+            ("receive", "WH-04", None), ("receive", "WH-05", None),
+            ("vote", "GOV-01", None), ("vote", "GOV-02", None), ("vote", "GOV-04", "governance_program"),
+            ("swap", "JUP-01", "jupiter_program"), ("swap", "JUP-02", None), ("swap", "JUP-03", None),
+        }
+
+        by_id = {(f.instruction, f.requirement.id): f for f in findings}
+        stale = by_id[("borrow", "PYTH-01")]
+        assert stale.path == "programs/lending/src/lib.rs"
+        assert stale.line == anchor_workspace.line(UNSAFE, "let price = feed.get_price_unchecked")
+        assert stale.evidence == "get_price_unchecked("
+        assert by_id[("borrow", "PYTH-02")].line == anchor_workspace.line(UNSAFE, "pub oracle:")
+        assert by_id[("receive", "WH-05")].line == anchor_workspace.line(UNSAFE, "let ix = load_instruction_at")
+
+    def test_hypotheses(self, tmp_path, anchor_workspace):
+        checker = IntegrationChecker(integrations=["pyth"], min_severity="medium")
+        findings, _ = checker.run(anchor_workspace(tmp_path, PROGRAMS))
+        assert {f.requirement.id for f in findings} == {"PYTH-01", "PYTH-02"}
+        hyp = next(f for f in findings if f.account).to_hypothesis()
+        assert hyp["title"] == "Legacy price account is not pinned to the expected feed in borrow (`oracle`)"
+        assert hyp["vulnerability_type"] == "unpinned-oracle-account"
+        assert hyp["severity"] == "high" and hyp["status"] == "proposed"
+        assert re.search(r"`lending::borrow` integrates Pyth price feeds", hyp["description"])
+        assert "https://docs.pyth.network" in hyp["description"]
+        props = hyp["properties"]
+        assert props["source_tool"] == "integrations" and props["category"] == "oracle"
+        assert props["requirement"] == "PYTH-02" and props["account"] == "oracle"
+        assert props["source_files"] == ["programs/lending/src/lib.rs"]
+
+    def test_pipeline(self, tmp_path, anchor_workspace):
+        pipeline = StaticAnalysisPipeline(chain_id="solana", integrations_config={"integrations": ["wormhole"]})
+        assert "integrations" in pipeline.check_tools()
+        result = pipeline.run(anchor_workspace(tmp_path, PROGRAMS))
+        hyps = [h for h in result.hypotheses if h["properties"]["source_tool"] == "integrations"]
+        assert {h["properties"]["requirement"] for h in hyps} == {"WH-01", "WH-02", "WH-03", "WH-04", "WH-05"}
+        assert all(h["id"].startswith("static_integrations_") for h in hyps)
+        assert result.metadata["tools"]["integrations"]["requirements"]["wormhole"][0] == "WH-01"
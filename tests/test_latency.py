@@ -0,0 +1,83 @@
+"""
+Tests for detector latency classes, the hot path and background scheduling.
+"""
+
+import threading
+from pathlib import Path
+
+from extensions.static import BackgroundScheduler, HotPathRunner, StaticAnalysisPipeline, latency_of
+from extensions.static.latency import partition
+
+FIXTURE = Path(__file__).parent / "fixtures" / "solana" / "zero_copy_pool"
+LIB = FIXTURE / "src" / "lib.rs"
+
+
+class TestLatency:
+    """Test latency classification, hot-path checks and background runs."""
+
+    def setup_method(self):
+        self.pipeline = StaticAnalysisPipeline(chain_id="solana")
+
+    def test_partition(self):
+        groups = partition(self.pipeline)
+        assert groups["hot"] == ["unsafe-rust"]
+        assert {"missing-signer", "privilege-paths", "sbf-limits"} <= set(groups["warm"])
+        assert {"soteria", "cargo-audit"} <= set(groups["cold"])
+        assert latency_of("no-such-tool") == "cold"
+        assert sorted(sum(groups.values(), [])) == sorted(self.pipeline.runners)
+
+    def test_check_file(self):
+        hot = HotPathRunner(self.pipeline)
+        result = hot.check_file(LIB, root=FIXTURE)
+        assert result.path == "src/lib.rs" and list(result.timings_ms) == ["unsafe-rust"]
+        assert not result.deferred
+        # Same ids and confidence as a full pipeline run, so editor and batch hits line up
+        full = self.pipeline.run(FIXTURE, tools=["unsafe-rust"])
+        expected = {h["id"]: h["confidence"] for h in full.hypotheses
+                    if h["properties"]["source_files"] == ["src/lib.rs"]}
+        assert {h["id"]: h["confidence"] for h in result.hypotheses} == expected
+        assert any(h["vulnerability_type"] == "memory-safety-raw-slice-length" for h in result.hypotheses)
+
+        # Unsaved editor contents win over the file on disk
+        assert hot.check_file(LIB, text="pub fn noop() {}\n", root=FIXTURE).hypotheses == []
+        assert hot.check_file(FIXTURE / "Cargo.toml", root=FIXTURE).timings_ms == {}
+
+    def test_budget_defers_rule(self):
+        hot = HotPathRunner(self.pipeline, budget_ms=0.0)
+        first = hot.check_file(LIB, root=FIXTURE)
+        assert first.over_budget == ["unsafe-rust"] and first.hypotheses
+        second = hot.check_file(LIB, root=FIXTURE)
+        assert second.deferred == ["unsafe-rust"] and second.timings_ms == {} and second.hypotheses == []
+        assert hot.deferred("src/lib.rs") == {"unsafe-rust"} and hot.deferred("src/other.rs") == set()
+
+        # The background run picks the deferred rule up
+        scheduler = BackgroundScheduler(self.pipeline, hot=hot)
+        assert "unsafe-rust" in scheduler.tools() and "missing-signer" in scheduler.tools()
+        hot.reset()
+        assert "unsafe-rust" not in scheduler.tools()
+        scheduler.shutdown()
+
+    def test_schedule(self):
+        scheduler = BackgroundScheduler(self.pipeline)
+        done = []
+        result = scheduler.schedule(FIXTURE, callback=done.append).result(timeout=60)
+        assert "sbf-limits" in result.metadata["tools"] and "unsafe-rust" not in result.metadata["tools"]
+        assert done == [result]
+
+        # A run queued behind a busy worker is replaced by the next one
+        release = threading.Event()
+        runs = []
+
+        def run(path, tools=None):
+            runs.append(tools)
+            release.wait(10)
+            return tools
+
+        scheduler.pipeline.run = run
+        busy = scheduler.schedule(FIXTURE.parent / "guestbook", tools=["a"])
+        queued = scheduler.schedule(FIXTURE, tools=["b"])
+        latest = scheduler.schedule(FIXTURE, tools=["c"])
+        release.set()
+        assert busy.result(timeout=10) == ["a"] and latest.result(timeout=10) == ["c"]
+        assert queued.cancelled() and runs == [["a"], ["c"]]
+        scheduler.shutdown()
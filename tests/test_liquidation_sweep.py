@@ -0,0 +1,107 @@
+"""
+Tests for liquidation scenario sweeps and the economic-risk report section.
+"""
+
+import json
+import random
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+import yaml
+from click.testing import CliRunner
+
+from commands.simulate import liquidations
+from extensions.reporting.economic import embed_economic_risk, load_sweeps, sweep_chart_svg
+from extensions.simulation import CrashPath, LendingMarket, LiquidationSweep, SweepConfig, World, load_sweep
+
+CONFIG = Path(__file__).parent / "fixtures" / "simulation" / "liquidations.yaml"
+
+HTML = """<html><body><div class="container">
+        <div class="section">
+            <h2>System Overview</h2>
+        </div>
+        <div class="section">
+            <h2>Findings</h2>
+        </div>
+        <div class="footer"></div>
+</div></body></html>"""
+
+
+class TestLiquidationSweep:
+    """Test crash paths, scenario classification, findings, report embedding and the CLI."""
+
+    def setup_method(self):
+        self.result = load_sweep(yaml.safe_load(CONFIG.read_text())).run()
+        self.export = {**self.result.to_dict(), "name": "liquidations"}
+
+    def test_crash_path_and_venue_depth(self):
+        assert CrashPath(start=100.0, drop=0.5, at=2, over=2).path(6) == [100.0, 100.0, 75.0, 50.0, 50.0, 50.0]
+        deep = World(0, 100.0, 100.0, {}, 0.0, random.Random(0))
+        thin = World(0, 100.0, 100.0, {}, 0.0, random.Random(0), external_depth=10_000.0)
+        assert deep.sell_value(100.0) == 10_000.0
+        # Selling the venue's whole collateral side gets half its quote depth
+        assert thin.sell_value(100.0) == 5_000.0
+        assert thin.buy_amount(10_000.0) == 50.0
+
+    def test_classification(self):
+        status = {(s.size, s.latency, s.drop): s.status for s in self.result.scenarios}
+        assert len(status) == 16
+        assert status[(10, 0, 0.1)] == "healthy"
+        # Without latency small positions are liquidated in steps even through a 50% crash
+        assert status[(10, 0, 0.5)] == "liquidated"
+        # Oracle lag turns the same crash into bad debt
+        assert status[(10, 10, 0.3)] == "bad-debt"
+        # Price impact on large positions eats the bonus
+        assert status[(100000, 0, 0.2)] == "unprofitable"
+        worst = self.result.scenario(0.5, 10, 10)
+        assert worst.bad_debt_share > 0.25 and worst.liquidatable == 1
+
+    def test_findings(self):
+        findings = {f.kind: f for f in self.result.findings()}
+        assert set(findings) == {"bad-debt", "unprofitable-liquidation"}
+        assert findings["bad-debt"].severity == "high"
+        assert {"size": 10, "latency": 10, "drop": 0.3} in findings["bad-debt"].evidence["thresholds"]
+        assert [t["size"] for t in findings["unprofitable-liquidation"].evidence["thresholds"]] == [100000, 100000]
+        hyp = findings["bad-debt"].to_hypothesis()
+        assert hyp["vulnerability_type"] == "economic-bad-debt"
+        assert hyp["confidence"] == round(0.4 + 0.5 * 6 / 16, 2)
+        # A deep exit venue keeps large liquidations profitable
+        config = SweepConfig(drops=[0.2], latencies=[0], sizes=[100000], external_depth=None)
+        deep = LiquidationSweep(lambda: LendingMarket(liquidity=1e9), config).run()
+        assert deep.scenarios[0].status == "liquidated"
+        assert deep.findings() == []
+
+    def test_report_section(self, tmp_path):
+        chart = sweep_chart_svg(self.export, 10)
+        assert chart.startswith("<svg") and chart.count("<rect") == 8
+        html = embed_economic_risk(HTML, [self.export], "html")
+        assert html.index("<h2>Economic Risk</h2>") < html.index("<h2>Findings</h2>")
+        assert html.index("<h2>System Overview</h2>") < html.index("<h2>Economic Risk</h2>")
+        assert "bad debt 29.8%" in html and "<table>" in html
+        md = embed_economic_risk("# Report\n\n## Findings\n\nNone\n", [self.export], "markdown")
+        assert md.index("## Economic Risk") < md.index("## Findings")
+        assert "| 10 slots | healthy | liquidated | bad debt 2.1% | bad debt 29.8% |" in md
+        assert embed_economic_risk(HTML, [], "html") == HTML
+
+        (tmp_path / "simulations").mkdir()
+        (tmp_path / "simulations" / "liquidations.json").write_text(json.dumps(self.result.to_dict()))
+        (tmp_path / "simulations" / "lending.json").write_text(json.dumps({"tool": "simulation"}))
+        assert [s["name"] for s in load_sweeps(tmp_path)] == ["liquidations"]
+
+    def test_cli(self, tmp_path):
+        runner = CliRunner()
+        result = runner.invoke(liquidations, [str(CONFIG), "--json"])
+        assert result.exit_code == 0
+        data = json.loads(result.output)
+        assert data["tool"] == "liquidation-sweep"
+        assert data["manifest"]["command"]["name"] == "sim liquidations"
+
+        with patch("commands.simulate.ProjectManager") as PM:
+            pm = MagicMock()
+            PM.return_value = pm
+            pm.get_project.return_value = {"path": str(tmp_path)}
+            result = runner.invoke(liquidations, [str(CONFIG), "--project", "p", "--import-hypotheses"])
+        assert result.exit_code == 0
+        assert [s["name"] for s in load_sweeps(tmp_path)] == ["liquidations"]
+        store = json.loads((tmp_path / "hypotheses.json").read_text())["hypotheses"]
+        assert len(store) == 2
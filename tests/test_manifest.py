@@ -0,0 +1,119 @@
+"""
+Tests for the reproducibility manifest and the reproduce command.
+"""
+
+import json
+import subprocess
+
+from click.testing import CliRunner
+
+from commands.reproduce import reproduce
+from extensions.reporting import build_manifest, embed_manifest, extract_manifest
+from extensions.reporting.manifest import (
+    REDACTED,
+    compare_manifests,
+    config_hash,
+    redact_config,
+    restore_secrets,
+    write_sidecar,
+)
+
+
+class TestManifest:
+    """Test manifest contents, redaction, embedding and drift detection."""
+
+    def setup_method(self):
+        self.config = {
+            "openai": {"api_key_env": "OPENAI_API_KEY", "api_key": "sk-live-123"},
+            "models": {
+                "agent": {"provider": "openai", "model": "gpt-4.1"},
+                "reporting": {"model": "local-llm"},
+            },
+        }
+
+    def test_secrets_redacted_and_restored(self):
+        redacted = redact_config(self.config)
+        assert redacted["openai"]["api_key"] == REDACTED
+        assert redacted["openai"]["api_key_env"] == "OPENAI_API_KEY"
+        assert self.config["openai"]["api_key"] == "sk-live-123"
+        # The hash ignores secret values, so rotating a key keeps it stable
+        rotated = {**self.config, "openai": {**self.config["openai"], "api_key": "sk-other"}}
+        assert config_hash(rotated) == config_hash(self.config)
+
+        restored = restore_secrets(redacted, {"openai": {"api_key": "sk-now"}})
+        assert restored["openai"]["api_key"] == "sk-now"
+        assert "api_key" not in restore_secrets(redacted, {})["openai"]
+
+    def test_build_manifest(self, tmp_path):
+        repo = tmp_path / "src"
+        repo.mkdir()
+        (repo / "lib.rs").write_text("fn main() {}\n")
+        git = ["git", "-C", str(repo), "-c", "user.email=a@b", "-c", "user.name=a"]
+        subprocess.run(git + ["init", "-q"], check=True)
+        subprocess.run(git + ["add", "."], check=True)
+        subprocess.run(git + ["commit", "-qm", "init"], check=True)
+        head = subprocess.run(git + ["rev-parse", "HEAD"], capture_output=True, text=True).stdout.strip()
+
+        manifest = build_manifest(
+            "reporting heatmap", {"project_name": "vault"}, source=repo,
+            config=self.config, detectors={"slither": "0.10.0"},
+        )
+        assert manifest["target"]["commit"] == head
+        assert manifest["target"]["dirty"] is False
+        assert manifest["kb_version"].startswith("sha256:")
+        assert manifest["detectors"] == {"slither": "0.10.0"}
+        assert manifest["backends"]["models"] == {"agent": "openai/gpt-4.1", "reporting": "local-llm"}
+        assert manifest["config"]["openai"]["api_key"] == REDACTED
+        assert manifest["command"] == {"name": "reporting heatmap", "params": {"project_name": "vault"}}
+        # No timestamps: the same configuration yields the same manifest
+        assert build_manifest("reporting heatmap", {"project_name": "vault"}, source=repo,
+                              config=self.config, detectors={"slither": "0.10.0"}) == manifest
+
+    def test_embed_and_extract(self, tmp_path):
+        manifest = build_manifest("report", {"title": "</script><!-- -->"}, config=self.config)
+
+        html = embed_manifest("<html><body><h1>Report</h1></body></html>", manifest, "html")
+        assert html.index('id="baskerville-manifest"') < html.index("</body>")
+        # Re-embedding replaces rather than duplicates
+        html = embed_manifest(html, manifest, "html")
+        assert html.count('id="baskerville-manifest"') == 1
+        (tmp_path / "report.html").write_text(html)
+        assert extract_manifest(tmp_path / "report.html") == manifest
+
+        md = embed_manifest("# Findings\n", manifest, "markdown")
+        (tmp_path / "README.md").write_text(md)
+        assert extract_manifest(tmp_path / "README.md") == manifest
+
+        (tmp_path / "heatmap.json").write_text(json.dumps({"files": [], "manifest": manifest}))
+        assert extract_manifest(tmp_path / "heatmap.json") == manifest
+
+        (tmp_path / "inventory.csv").write_text("program,instruction\n")
+        write_sidecar(tmp_path / "inventory.csv", manifest)
+        assert extract_manifest(tmp_path / "inventory.csv") == manifest
+
+    def test_compare_manifests(self):
+        recorded = build_manifest("static", detectors={"slither": "0.10.0", "aderyn": "0.1.0"})
+        current = json.loads(json.dumps(recorded))
+        assert compare_manifests(recorded, current) == []
+
+        current["kb_version"] = "sha256:changed"
+        current["detectors"] = {"slither": "0.11.0"}
+        diffs = {name: (old, new) for name, old, new in compare_manifests(recorded, current)}
+        assert diffs["kb_version"] == (recorded["kb_version"], "sha256:changed")
+        assert diffs["detectors.slither"] == ("0.10.0", "0.11.0")
+        assert diffs["detectors.aderyn"] == ("0.1.0", None)
+
+    def test_reproduce_check(self, tmp_path):
+        manifest = build_manifest("reporting heatmap", {"project_name": "vault", "output": None})
+        output = tmp_path / "heatmap.json"
+        output.write_text(json.dumps({"files": [], "manifest": manifest}))
+        runner = CliRunner()
+        assert runner.invoke(reproduce, [str(output), "--check"]).exit_code == 0
+
+        manifest["kb_version"] = "sha256:0000000000000000"
+        output.write_text(json.dumps({"files": [], "manifest": manifest}))
+        result = runner.invoke(reproduce, [str(output), "--check"])
+        assert result.exit_code == 1
+
+        (tmp_path / "plain.html").write_text("<html></html>")
+        assert runner.invoke(reproduce, [str(tmp_path / "plain.html")]).exit_code == 1
@@ -0,0 +1,144 @@
+"""
+Tests for Monte Carlo stress testing of fee and reward math.
+"""
+
+import base64
+import json
+import random
+from pathlib import Path
+
+import pytest
+import yaml
+from click.testing import CliRunner
+
+from commands.simulate import math_stress
+from extensions.simulation import (
+    Distribution,
+    Evaluator,
+    ProgramCalculation,
+    StressCase,
+    TxResult,
+    evaluate_report,
+    load_cases,
+    run_case,
+)
+from extensions.simulation.math_stress import Overflow
+
+CASES = Path(__file__).parent / "fixtures" / "simulation" / "rewards.yaml"
+
+
+class FeeProgram:
+    """Backend standing in for a program computing a 0.3% fee via set_return_data."""
+
+    def __init__(self):
+        self.data = []
+
+    def start(self):
+        pass
+
+    def close(self):
+        pass
+
+    def keypair(self, name, lamports=0):
+        return name
+
+    def send(self, instructions, payer, signers=()):
+        data = instructions[0].data
+        self.data.append(data)
+        amount = int.from_bytes(data[1:9], "little")
+        if amount > 2 ** 63:
+            return TxResult(False, "Program failed: arithmetic overflow", ["Program log: overflow"])
+        fee = (amount // 10_000 * 30).to_bytes(8, "little")
+        return TxResult(True, None, ["Program Fee11 invoke [1]", f"Program return: Fee11 {base64.b64encode(fee).decode()}"])
+
+
+class TestMathStress:
+    """Test integer emulation, case runs, findings and the CLI."""
+
+    def setup_method(self):
+        self.cases = {c.name: c for c in load_cases(yaml.safe_load(CASES.read_text()))}
+
+    def test_evaluator(self):
+        ev = Evaluator("amount as u64 * fee_bps / 10_000")
+        assert ev.names == ["amount", "fee_bps"]
+        assert ev.integer({"amount": 9_999, "fee_bps": 1}) == 0
+        assert float(ev.exact({"amount": 9_999, "fee_bps": 1})) == 0.9999
+        assert ev.peak == 9_999
+        with pytest.raises(Overflow):
+            Evaluator("a * b").integer({"a": 2 ** 40, "b": 2 ** 40})
+        with pytest.raises(Overflow) as underflow:
+            Evaluator("a - b").integer({"a": 1, "b": 2})
+        assert underflow.value.kind == "underflow"
+        assert Evaluator("a - b", signed=True).integer({"a": 1, "b": 2}) == -1
+        assert Evaluator("-7 / 2", signed=True).integer({}) == -3
+        # Double-width intermediate
+        assert Evaluator("mul_div(a, b, c)").integer({"a": 2 ** 40, "b": 2 ** 40, "c": 2 ** 30}) == 2 ** 50
+        assert Evaluator("ceil_div(a, 3)").integer({"a": 10}) == 4
+
+    def test_distributions(self):
+        rng = random.Random(0)
+        amounts = [Distribution.parse({"loguniform": ["1e3", "1e9"]}).sample(rng) for _ in range(200)]
+        assert min(amounts) >= 1_000 and max(amounts) <= 10 ** 9
+        assert sum(a < 10 ** 6 for a in amounts) > 50
+        assert Distribution.parse({"choice": [5, 30]}).bounds == [5, 30]
+        assert Distribution.parse(7).sample(rng) == 7
+        with pytest.raises(ValueError):
+            Distribution.parse({"normal": [0, 1]})
+
+    def test_reward_drift(self):
+        report = run_case(self.cases["reward_per_token"])
+        assert report.width == 128
+        assert report.overflow["events"] == 0
+        assert report.overflow["headroom_bits"] > 60
+        # Truncation only ever loses: the accumulator ends below the exact value
+        assert report.drift["absolute"] > 0 and report.drift["mean"] > 0
+        assert report.drift["exact"] - report.drift["actual"] == pytest.approx(report.drift["absolute"])
+        assert report.precision["max_units"] < 1
+        assert report.precision["rounded_to_zero"] > 0
+        assert {f.kind for f in evaluate_report(report)} == {"drift", "rounds-to-zero"}
+
+    def test_divide_before_multiply(self):
+        report = run_case(self.cases["withdraw_fee"])
+        at = report.precision["max_units_at"]
+        assert report.precision["max_units"] >= 1
+        assert at["exact"] - at["actual"] == pytest.approx(report.precision["max_units"])
+        finding = next(f for f in evaluate_report(report) if f.kind == "precision")
+        hyp = finding.to_hypothesis()
+        assert hyp["vulnerability_type"] == "precision-loss"
+        assert hyp["properties"]["source_tool"] == "math-stress"
+        assert str(at["actual"]) in hyp["description"].replace(",", "")
+
+    def test_overflow(self):
+        report = run_case(self.cases["position_value"])
+        first = report.overflow["first"]
+        assert first["kind"] == "overflow"
+        assert first["inputs"]["amount"] * first["inputs"]["price"] > 2 ** 64 - 1
+        finding = evaluate_report(report)[0]
+        assert (finding.kind, finding.severity) == ("overflow", "high")
+        assert finding.to_hypothesis()["vulnerability_type"] == "arithmetic-overflow"
+
+    def test_program_calculation(self):
+        backend = FeeProgram()
+        case = StressCase(
+            "fee", {"amount": Distribution.parse({"loguniform": [1, 1e12]})}, reference="amount * 30 / 10_000",
+            into="fee", trials=5, steps=20,
+            program=lambda: ProgramCalculation(backend, "Fee11", ["u8:7", "u64:amount"]),
+        )
+        report = run_case(case)
+        assert backend.data[0][0] == 7 and len(backend.data[0]) == 9
+        assert report.width == 64
+        assert report.precision["max_units"] >= 1
+        assert "precision" in {f.kind for f in evaluate_report(report)}
+        calc = ProgramCalculation(backend, "Fee11", ["u8:7", "u64:amount"])
+        with pytest.raises(Overflow) as overflow:
+            calc.integer({"amount": 2 ** 64 - 1})
+        assert overflow.value.kind == "overflow"
+
+    def test_cli(self):
+        result = CliRunner().invoke(math_stress, [str(CASES), "--trials", "3", "--steps", "50", "--json"])
+        assert result.exit_code == 0
+        data = json.loads(result.output)
+        assert [r["case"] for r in data["reports"]] == ["reward_per_token", "withdraw_fee", "position_value"]
+        assert data["reports"][0]["trials"] == 3
+        assert data["manifest"]["command"]["name"] == "sim math"
+        assert {"case": "position_value", "kind": "overflow", "severity": "high"} in data["findings"]
@@ -0,0 +1,93 @@
+"""
+Tests for codebase metrics: handler complexity and LoC, attack-surface
+counts, the review order, the report section and the CLI.
+"""
+
+import json
+from pathlib import Path
+
+from click.testing import CliRunner
+
+from commands.ir import metrics
+from extensions.ir import load_programs
+from extensions.ir.metrics import build_metrics, count_loc, cyclomatic_complexity
+from extensions.reporting.metrics import embed_metrics, load_metrics
+
+FIXTURES = Path(__file__).resolve().parent / "fixtures"
+VAULT = FIXTURES / "solana" / "anchor_vault"
+ROUTER = FIXTURES / "solana" / "cpi_router"
+COSMWASM = FIXTURES / "cosmwasm" / "cw_vault"
+
+HANDLER = """{
+    // if this were a branch it would count
+    let msg = "if && while";
+    if amount == 0 || paused {
+        return err!(ErrorCode::Invalid);
+    }
+    match side {
+        Side::Bid => fill_bid(amount)?,
+        Side::Ask => fill_ask(amount)?,
+        Side::Cancel => {}
+    }
+    /* for i in 0..n { } */
+    for order in orders.iter() {
+        settle(order)?;
+    }
+    Ok(())
+}"""
+
+
+class TestMetrics:
+    """Test the per-instruction and per-program numbers."""
+
+    def test_complexity_and_loc(self):
+        # if, ||, two extra match arms, for; comments and strings ignored
+        assert cyclomatic_complexity(HANDLER) == 6
+        assert count_loc(HANDLER) == 15
+        assert cyclomatic_complexity("if x and not y:\n    pass\nelif z:\n    pass\n", "python") == 4
+
+    def test_attack_surface(self):
+        data = build_metrics(load_programs(VAULT) + load_programs(ROUTER))
+        programs = {p["name"]: p for p in data["programs"]}
+        vault = {i["instruction"]: i for i in programs["vault"]["instructions"]}
+        assert vault["initialize"]["privileged"] and not vault["withdraw"]["privileged"]
+        assert vault["withdraw"]["unchecked_accounts"] == ["authority", "recipient", "payout_program"]
+        assert vault["deposit"]["cpi_targets"] == ["token_program"]
+        router = {i["instruction"]: i for i in programs["cpi_router"]["instructions"]}
+        # PDA seeds and a require_keys_eq! in the handler both pin an account
+        assert router["forward"]["unchecked_accounts"] == [] and router["call_plugin"]["unchecked_accounts"] == []
+        assert router["route_swap"]["unchecked_accounts"] == ["swap_program"]
+        assert programs["cpi_router"]["summary"]["cpi_fan_out"] == 4
+        assert programs["vault"]["loc"] > programs["vault"]["summary"]["handler_loc"] > 0
+        assert data["totals"]["instructions"] == 8 and data["totals"]["unchecked_accounts"] == 5
+        # The open drain with three raw accounts comes first
+        assert data["review_order"][0]["instruction"] == "withdraw"
+        assert len(build_metrics(load_programs(ROUTER), top=2)["review_order"]) == 2
+
+    def test_sender_checked_is_privileged(self):
+        data = build_metrics(load_programs(COSMWASM))
+        vault = next(p for p in data["programs"] if p["name"] == "cw-vault")
+        assert next(i for i in vault["instructions"] if i["instruction"] == "update_config")["privileged"]
+
+
+class TestMetricsReport:
+    """Test the report section and the CLI."""
+
+    def test_section(self, tmp_path):
+        data = build_metrics(load_programs(VAULT))
+        md = embed_metrics("# Report\n\n## Findings\n\nNone\n", data, "markdown")
+        assert md.index("## Codebase Metrics") < md.index("## Findings")
+        assert "| vault | anchor |" in md and "| 1 | vault.withdraw |" in md
+        html = embed_metrics('<body><div class="section">\n<h2>Findings</h2></div></body>', data, "html")
+        assert html.index("<h2>Codebase Metrics</h2>") < html.index("<h2>Findings</h2>")
+        assert embed_metrics("x", None, "html") == "x"
+        assert load_metrics(tmp_path) is None
+
+    def test_cli(self, tmp_path):
+        out = tmp_path / "metrics.json"
+        result = CliRunner().invoke(metrics, [str(VAULT), "-o", str(out), "--top", "1"])
+        assert result.exit_code == 0, result.output
+        assert "Review first" in result.output and "vault.withdraw" in result.output
+        data = json.loads(out.read_text())
+        assert data["format"] == "baskerville-metrics" and data["manifest"]["command"]["name"] == "ir metrics"
+        assert load_metrics(tmp_path)["review_order"] == data["review_order"]
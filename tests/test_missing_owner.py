@@ -0,0 +1,181 @@
+"""
+Tests for the missing account owner check detector.
+
+Verifies raw accounts whose data an Anchor or native handler deserializes
+are flagged when nothing checks their owner, are left alone behind an
+`owner`/`address`/`seeds` constraint, a handler owner check or a key bound
+to state, that reads and owner checks through a local alias of the account
+count, and that findings link to the `missing_owner_check` PoC template.
+"""
+
+from pathlib import Path
+
+from extensions.knowledge.class_loader import ClassLoader
+from extensions.static import MissingOwnerDetector, StaticAnalysisPipeline
+from extensions.static.missing_owner import TEMPLATE_FILE
+
+
+ROOT = Path(__file__).resolve().parents[1]
+FIXTURES = ROOT / "tests" / "fixtures" / "solana"
+PROGRAM_ID = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+
+SOURCE = """use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let data = ctx.accounts.config.try_borrow_data()?;
+        let config = Config::try_deserialize(&mut &data[..])?;
+        require_keys_eq!(config.admin, ctx.accounts.admin.key());
+        Ok(())
+    }
+
+    pub fn price(ctx: Context<Price>) -> Result<()> {
+        let feed = PriceFeed::try_from_slice(&ctx.accounts.oracle.data.borrow())?;
+        let unchecked = Account::<Config>::try_from_unchecked(&ctx.accounts.config)?;
+        msg!("{} {}", feed.price, unchecked.fee);
+        Ok(())
+    }
+
+    pub fn checked(ctx: Context<Withdraw>) -> Result<()> {
+        require_keys_eq!(*ctx.accounts.config.owner, crate::ID, VaultError::InvalidOwner);
+        let config = Config::try_deserialize(&mut &ctx.accounts.config.data.borrow()[..])?;
+        msg!("{}", config.fee);
+        Ok(())
+    }
+
+    pub fn constrained(ctx: Context<Constrained>) -> Result<()> {
+        let a = Config::try_deserialize(&mut &ctx.accounts.owned.data.borrow()[..])?;
+        let b = Config::try_deserialize(&mut &ctx.accounts.pda.data.borrow()[..])?;
+        let c = Config::try_deserialize(&mut &ctx.accounts.pinned.data.borrow()[..])?;
+        msg!("{} {} {} {}", a.fee, b.fee, c.fee, ctx.accounts.unread.data_len());
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    /// CHECK: parsed in the handler
+    pub config: AccountInfo<'info>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Price<'info> {
+    /// CHECK: parsed in the handler
+    pub oracle: UncheckedAccount<'info>,
+    /// CHECK: parsed in the handler
+    pub config: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Constrained<'info> {
+    #[account(has_one = pinned)]
+    pub state: Account<'info, State>,
+    /// CHECK: owner constraint
+    #[account(owner = crate::ID)]
+    pub owned: AccountInfo<'info>,
+    /// CHECK: PDA
+    #[account(seeds = [b"config"], bump)]
+    pub pda: AccountInfo<'info>,
+    /// CHECK: bound to state
+    pub pinned: AccountInfo<'info>,
+    /// CHECK: never parsed
+    pub unread: AccountInfo<'info>,
+}
+"""
+
+
+ALIASED = """use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn read_config(ctx: Context<ReadConfig>) -> Result<()> {
+        let info = &ctx.accounts.cfg;
+        let data = info.try_borrow_data()?;
+        let config = Config::try_from_slice(&data[8..])?;
+        msg!("{}", config.fee);
+        Ok(())
+    }
+
+    pub fn read_checked(ctx: Context<ReadConfig>) -> Result<()> {
+        let info = ctx.accounts.cfg.to_account_info();
+        require_keys_eq!(*info.owner, crate::ID);
+        let data = info.try_borrow_data()?;
+        let config = Config::try_from_slice(&data[8..])?;
+        msg!("{}", config.fee);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ReadConfig<'info> {
+    /// CHECK: parsed by hand
+    pub cfg: AccountInfo<'info>,
+}
+
+#[account]
+pub struct Config {
+    pub fee: u64,
+}
+"""
+
+
+class TestMissingOwner:
+    """Test the detector against an inline Anchor program and the native fixtures."""
+
+    def test_findings(self, tmp_path, anchor_workspace):
+        findings, metadata = MissingOwnerDetector().run(anchor_workspace(tmp_path, {"vault": SOURCE}))
+        assert metadata["instructions_checked"] == 4
+        flagged = [(f.instruction, f.account, f.state_type, f.line) for f in findings]
+        assert flagged == [
+            ("withdraw", "config", "Config",
+             anchor_workspace.line(SOURCE, "let config = Config::try_deserialize(&mut &data")),
+            ("price", "oracle", "PriceFeed", anchor_workspace.line(SOURCE, "let feed")),
+            ("price", "config", "Config", anchor_workspace.line(SOURCE, "let unchecked")),
+        ]
+        withdraw = findings[0]
+        assert withdraw.path == "programs/vault/src/lib.rs" and withdraw.account_type == "AccountInfo<'info>"
+        assert withdraw.account_line == anchor_workspace.line(SOURCE, "pub config: AccountInfo")
+        assert withdraw.text == "let config = Config::try_deserialize(&mut &data[..])?"
+
+    def test_alias(self, tmp_path, anchor_workspace):
+        findings, _ = MissingOwnerDetector().run(anchor_workspace(tmp_path, {"vault": ALIASED}))
+        assert [(f.instruction, f.account, f.state_type) for f in findings] == [("read_config", "cfg", "Config")]
+        assert findings[0].text == "let config = Config::try_from_slice(&data[8..])?"
+        assert findings[0].line == ALIASED[:ALIASED.index("let config")].count("\n") + 1
+
+    def test_native(self):
+        findings, _ = MissingOwnerDetector().run(FIXTURES / "zero_copy_pool")
+        assert [(f.instruction, f.account, f.state_type, f.line) for f in findings] == [
+            ("process_instruction", "pool", "PoolHeader", 37)]
+        assert "as *const PoolHeader" in findings[0].text
+
+    def test_hypothesis(self, tmp_path, anchor_workspace):
+        findings, _ = MissingOwnerDetector().run(anchor_workspace(tmp_path, {"vault": SOURCE}))
+        hyp = findings[0].to_hypothesis()
+        assert hyp["title"] == "Missing owner check on `config` in withdraw"
+        assert hyp["severity"] == "high" and hyp["status"] == "proposed"
+        assert "parses the data of `config` (AccountInfo<'info>) as `Config`" in hyp["description"]
+        assert f"kb render missing_owner_check -s PROGRAM_ID={PROGRAM_ID}" in hyp["description"]
+        props = hyp["properties"]
+        assert props["source_tool"] == "missing-owner" and props["poc_context"] == {"PROGRAM_ID": PROGRAM_ID}
+        assert (ROOT / props["poc_template_file"]).exists() and props["poc_template_file"] == TEMPLATE_FILE
+        vuln_class = ClassLoader().resolve(hyp["vulnerability_type"], "solana")
+        assert vuln_class.id == "account-type-confusion"
+        assert vuln_class.variant("solana").templates == ["missing_owner_check", "type_cosplay"]
+
+    def test_pipeline(self, tmp_path, anchor_workspace):
+        pipeline = StaticAnalysisPipeline(chain_id="solana")
+        assert "missing-owner" in pipeline.check_tools()
+        result = pipeline.run(anchor_workspace(tmp_path, {"vault": SOURCE}), tools=["missing-owner"])
+        assert len(result.hypotheses) == 3
+        assert all(h["id"].startswith("static_missing-owner_") for h in result.hypotheses)
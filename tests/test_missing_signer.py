@@ -0,0 +1,208 @@
+"""
+Tests for the Anchor missing signer detector.
+
+Verifies raw authority accounts are flagged when the instruction mutates
+privileged state without requiring their signature, are left alone when
+typed `Signer`, constrained `signer` or checked with `is_signer`, that
+program accounts (CPI targets, keys checked against a fixed id) are not
+taken for authorities even when named like one, that two authorities of
+one instruction stay separate hits through the pipeline, and that findings
+link to the `missing_signer` PoC template.
+"""
+
+from pathlib import Path
+
+from extensions.knowledge.class_loader import ClassLoader
+from extensions.static import MissingSignerDetector, StaticAnalysisPipeline
+from extensions.static.missing_signer import TEMPLATE_FILE
+
+
+ROOT = Path(__file__).resolve().parents[1]
+PROGRAM_ID = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+
+SOURCE = """use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn set_fee(ctx: Context<SetFee>, fee: u64) -> Result<()> {
+        ctx.accounts.config.fee = fee;
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        require_keys_eq!(ctx.accounts.vault.authority, ctx.accounts.authority.key());
+        **ctx.accounts.vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.recipient.try_borrow_mut_lamports()? += amount;
+        Ok(())
+    }
+
+    pub fn rename(ctx: Context<Rename>, name: String) -> Result<()> {
+        ctx.accounts.config.name = name;
+        Ok(())
+    }
+
+    pub fn set_fee_checked(ctx: Context<SetFeeChecked>, fee: u64) -> Result<()> {
+        require!(ctx.accounts.admin.is_signer, VaultError::Unauthorized);
+        ctx.accounts.config.fee = fee;
+        Ok(())
+    }
+
+    pub fn set_fee_signed(ctx: Context<SetFeeSigned>, fee: u64) -> Result<()> {
+        ctx.accounts.config.fee += fee;
+        Ok(())
+    }
+
+    pub fn view(ctx: Context<SetFee>) -> Result<()> {
+        msg!("fee {}", ctx.accounts.config.fee);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetFee<'info> {
+    #[account(mut, has_one = admin)]
+    pub config: Account<'info, Config>,
+    /// CHECK: config admin
+    pub admin: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: compared in the handler
+    pub authority: UncheckedAccount<'info>,
+    /// CHECK: receives lamports
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Rename<'info> {
+    #[account(mut)]
+    pub config: Account<'info, Config>,
+    /// CHECK: never checked
+    pub owner: AccountInfo<'info>,
+    /// CHECK: PDA
+    #[account(seeds = [b"admin"], bump)]
+    pub admin_pda: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeChecked<'info> {
+    #[account(mut, has_one = admin)]
+    pub config: Account<'info, Config>,
+    /// CHECK: signature checked in the handler
+    pub admin: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeSigned<'info> {
+    #[account(mut, has_one = admin, constraint = config.owner == owner.key())]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    /// CHECK: signer constraint
+    #[account(signer)]
+    pub owner: AccountInfo<'info>,
+}
+"""
+
+
+class TestMissingSigner:
+    """Test the detector against an inline Anchor program."""
+
+    def test_findings(self, tmp_path, anchor_workspace):
+        findings, metadata = MissingSignerDetector().run(anchor_workspace(tmp_path, {"vault": SOURCE}))
+        assert metadata["instructions_checked"] == 6
+        by_ix = {f.instruction: f for f in findings}
+        assert set(by_ix) == {"set_fee", "withdraw", "rename"}
+
+        fee = by_ix["set_fee"]
+        assert (fee.account, fee.account_type) == ("admin", "AccountInfo<'info>")
+        assert fee.path == "programs/vault/src/lib.rs"
+        assert fee.line == anchor_workspace.line(SOURCE, "pub admin: AccountInfo")
+        assert [g.kind for g in fee.gates] == ["has_one"] and fee.severity == "high"
+        assert [(m.kind, m.line) for m in fee.mutations] == [
+            ("field-write", anchor_workspace.line(SOURCE, "ctx.accounts.config.fee = fee"))]
+
+        withdraw = by_ix["withdraw"]
+        assert withdraw.account == "authority" and withdraw.gates[0].kind == "key-check"
+        assert withdraw.gates[0].line == anchor_workspace.line(SOURCE, "require_keys_eq!")
+        assert "lamport-debit" in [m.kind for m in withdraw.mutations] and withdraw.severity == "critical"
+
+        # Only the name says `owner` is the authority; the seeded PDA is never a candidate
+        rename = by_ix["rename"]
+        assert rename.account == "owner" and not rename.bound
+
+        strict, _ = MissingSignerDetector(include_name_only=False).run(tmp_path)
+        assert {f.instruction for f in strict} == {"set_fee", "withdraw"}
+
+    def test_hypothesis(self, tmp_path, anchor_workspace):
+        findings, _ = MissingSignerDetector().run(anchor_workspace(tmp_path, {"vault": SOURCE}))
+        hyp = next(f for f in findings if f.instruction == "set_fee").to_hypothesis()
+        assert hyp["title"] == "Missing signer check on `admin` in set_fee"
+        assert hyp["severity"] == "high" and hyp["status"] == "proposed" and hyp["confidence"] == 0.7
+        assert "`vault::set_fee` treats `admin`" in hyp["description"]
+        assert f"kb render missing_signer -s PROGRAM_ID={PROGRAM_ID}" in hyp["description"]
+        props = hyp["properties"]
+        assert props["source_tool"] == "missing-signer" and props["precision"] == "semantic"
+        assert props["poc_template"] == "missing_signer" and props["poc_context"] == {"PROGRAM_ID": PROGRAM_ID}
+        assert (ROOT / props["poc_template_file"]).exists() and props["poc_template_file"] == TEMPLATE_FILE
+        # The state changes are listed, not located: each authority keeps its own line
+        assert props["affected_lines"] == [anchor_workspace.line(SOURCE, "pub admin: AccountInfo")]
+        assert [m["line"] for m in props["mutations"]] == [
+            anchor_workspace.line(SOURCE, "ctx.accounts.config.fee = fee")]
+
+        # The vulnerability type resolves to the class the template hangs off
+        vuln_class = ClassLoader().resolve(hyp["vulnerability_type"], "solana")
+        assert vuln_class.variant("solana").templates == ["missing_signer"]
+        name_only = next(f for f in findings if f.instruction == "rename").to_hypothesis()
+        assert name_only["confidence"] == 0.4 and name_only["properties"]["precision"] == "syntactic"
+
+    def test_pipeline(self, tmp_path, anchor_workspace):
+        pipeline = StaticAnalysisPipeline(chain_id="solana", missing_signer_config={"include_name_only": False})
+        assert "missing-signer" in pipeline.check_tools()
+        result = pipeline.run(anchor_workspace(tmp_path, {"vault": SOURCE}))
+        hyps = [h for h in result.hypotheses if h["properties"]["source_tool"] == "missing-signer"]
+        assert {h["properties"]["instruction"] for h in hyps} == {"set_fee", "withdraw"}
+        assert all(h["id"].startswith("static_missing-signer_") for h in hyps)
+
+    def test_program_accounts(self, tmp_path, anchor_workspace):
+        # call_plugin invokes `plugin_program` after checking it against PLUGIN_ID
+        findings, _ = MissingSignerDetector().run(ROOT / "tests" / "fixtures" / "solana" / "cpi_router")
+        assert findings == []
+
+        # A key compared against a fixed id is not bound to any stored authority
+        pinned = SOURCE.replace(
+            "require_keys_eq!(ctx.accounts.vault.authority, ctx.accounts.authority.key());",
+            "require_keys_eq!(ctx.accounts.oracle.key(), crate::ORACLE_ID);\n"
+            "        require!(ctx.accounts.oracle.key() == ID, VaultError::Unauthorized);",
+        ).replace("pub authority: UncheckedAccount<'info>,", "pub oracle: UncheckedAccount<'info>,")
+        findings, _ = MissingSignerDetector().run(anchor_workspace(tmp_path, {"vault": pinned}))
+        assert "withdraw" not in {f.instruction for f in findings}
+        assert {f.instruction for f in findings} == {"set_fee", "rename"}
+
+        # Named like an authority, but pinned to the governance program's id
+        pinned = SOURCE.replace(
+            "require_keys_eq!(ctx.accounts.vault.authority, ctx.accounts.authority.key());",
+            "require_keys_eq!(ctx.accounts.governance_program.key(), GOV_ID);",
+        ).replace("pub authority: UncheckedAccount<'info>,", "pub governance_program: UncheckedAccount<'info>,")
+        findings, _ = MissingSignerDetector().run(anchor_workspace(tmp_path / "gov", {"vault": pinned}))
+        assert "governance_program" not in {f.account for f in findings}
+        assert {f.instruction for f in findings} == {"set_fee", "rename"}
+
+    def test_two_authorities(self, tmp_path, anchor_workspace):
+        # Both raw authorities write the same state; neither hit may absorb the other
+        source = SOURCE.replace(
+            "    /// CHECK: config admin\n    pub admin: AccountInfo<'info>,\n}",
+            "    /// CHECK: config admin\n    pub admin: AccountInfo<'info>,\n"
+            "    /// CHECK: fee operator\n    pub operator: AccountInfo<'info>,\n}", 1)
+        assert source != SOURCE
+        result = StaticAnalysisPipeline(chain_id="solana").run(anchor_workspace(tmp_path, {"vault": source}),
+                                                               tools=["missing-signer"])
+        props = [h["properties"] for h in result.hypotheses]
+        assert {p["account"] for p in props if p["instruction"] == "set_fee"} == {"admin", "operator"}
@@ -0,0 +1,305 @@
+"""
+Tests for live monitoring: transaction decoding, rules, rules derived from findings, sources and the CLI.
+"""
+
+import json
+import time
+import urllib.error
+import urllib.request
+from pathlib import Path
+from unittest.mock import patch
+
+import pytest
+import yaml
+from click.testing import CliRunner
+
+from commands.monitor import geyser, replay, rules
+from extensions.ir import load_programs
+from extensions.ir.anchor_config import b58encode
+from extensions.monitor import (
+    FailureBurstRule,
+    InstructionCalledRule,
+    LargeOutflowRule,
+    Monitor,
+    Observed,
+    PollingSource,
+    RuleSet,
+    UnexpectedAuthorityRule,
+    WebhookSource,
+    anchor_discriminator,
+    decode_transaction,
+    generate_geyser_plugin,
+    rules_from_findings,
+)
+
+VAULT = Path(__file__).resolve().parent / "fixtures" / "solana" / "anchor_vault"
+PROGRAM_ID = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+USDC = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
+ATTACKER = "Atk1111111111111111111111111111111111111111"
+ADMIN = "Adm1111111111111111111111111111111111111111"
+VAULT_STATE = "Vau1t11111111111111111111111111111111111111"
+VAULT_TOKEN = "VtoK111111111111111111111111111111111111111"
+TOKEN = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+
+
+def withdraw_tx(signature="sig1", signer=ATTACKER, authority_signs=False, amount=0, slot=100, err=None):
+    """A v0 `withdraw` call: [vault, authority, recipient, ...], the vault token account loaded from a table."""
+    keys = [signer, VAULT_STATE] + ([] if authority_signs else [ADMIN]) + [PROGRAM_ID, TOKEN]
+    authority = 0 if authority_signs else 2
+    index = {k: i for i, k in enumerate(keys)}
+    loaded = len(keys)
+    data = b58encode(anchor_discriminator("withdraw") + (5).to_bytes(8, "little"))
+    balance = [{"accountIndex": loaded, "mint": USDC, "owner": VAULT_STATE,
+                "uiTokenAmount": {"amount": str(amount * 10 ** 6 + 1_000_000_000), "decimals": 6}}]
+    post = [{**balance[0], "uiTokenAmount": {"amount": "1000000000", "decimals": 6}}]
+    return {
+        "slot": slot,
+        "blockTime": 1_700_000_000,
+        "transaction": {
+            "signatures": [signature],
+            "message": {
+                "header": {"numRequiredSignatures": 1, "numReadonlySignedAccounts": 0,
+                           "numReadonlyUnsignedAccounts": len(keys) - 2},
+                "accountKeys": keys,
+                "instructions": [{"programIdIndex": index[PROGRAM_ID], "data": data,
+                                  "accounts": [index[VAULT_STATE], authority, 0, loaded]}],
+            },
+        },
+        "meta": {
+            "err": err,
+            "loadedAddresses": {"writable": [VAULT_TOKEN], "readonly": []},
+            "innerInstructions": [{"index": 0, "instructions": [
+                {"programIdIndex": index[TOKEN], "data": "3Bxs4h24hBtQy9rw", "accounts": [loaded, 0]}]}],
+            "preTokenBalances": balance,
+            "postTokenBalances": post,
+            "logMessages": ["Program log: Instruction: Withdraw"],
+        },
+    }
+
+
+def vault_program():
+    return next(p for p in load_programs(VAULT) if p.name == "vault")
+
+
+def vault_ruleset(*rule_list):
+    program = vault_program()
+    base = rules_from_findings({}, program)
+    return RuleSet(program_id=PROGRAM_ID, program="vault", discriminators=base.discriminators,
+                   accounts=base.accounts, rules=list(rule_list))
+
+
+class FakeClient:
+    """Stands in for the RPC client: transactions by signature and a fixed signature list."""
+
+    def __init__(self, txs=(), signatures=()):
+        self.txs = {tx["transaction"]["signatures"][0]: tx for tx in txs}
+        self.signatures = list(signatures)
+        self.fetched = []
+
+    def get_transaction(self, signature, commitment="finalized"):
+        self.fetched.append(signature)
+        return self.txs.get(signature)
+
+    def get_signatures_for_address(self, address, limit=1000, until=None, commitment=None):
+        sigs = [s["signature"] for s in self.signatures]
+        stop = sigs.index(until) if until in sigs else len(sigs)
+        return self.signatures[:stop][:limit]
+
+
+class TestMonitor:
+    """Test decoding, rule evaluation, rule derivation, sources and commands."""
+
+    def test_decode(self):
+        program = vault_program()
+        ruleset = rules_from_findings({}, program)
+        event = decode_transaction(withdraw_tx(amount=250), PROGRAM_ID, ruleset.discriminators)
+        assert event.signature == "sig1" and event.success and event.fee_payer == ATTACKER
+        (call,) = event.calls
+        assert call.name == "withdraw" and not call.inner and call.index == 0
+        assert call.accounts == [VAULT_STATE, ADMIN, ATTACKER, VAULT_TOKEN]
+        assert call.signer == [False, False, True, False]
+        assert call.writable == [True, False, True, True]
+        assert call.account("authority", ruleset.accounts["withdraw"]) == ADMIN
+        (delta,) = event.token_deltas
+        assert delta.account == VAULT_TOKEN and delta.mint == USDC and delta.ui_delta == -250
+        unknown = decode_transaction(withdraw_tx(), PROGRAM_ID)
+        assert unknown.calls[0].name is None
+        assert decode_transaction(withdraw_tx(), TOKEN).calls[0].inner
+
+    def test_rules(self):
+        authority = UnexpectedAuthorityRule(id="auth", severity="critical", instruction="withdraw",
+                                            account="authority")
+        ruleset = vault_ruleset(authority)
+        mon = Monitor(ruleset)
+        (alert,) = mon.process(withdraw_tx())
+        assert alert.rule_id == "auth" and alert.severity == "critical" and alert.instruction == "withdraw"
+        assert alert.details["account"] == ADMIN and alert.program_id == PROGRAM_ID
+        assert mon.process(withdraw_tx(signer=ADMIN, authority_signs=True)) == []
+        authority.expected = ["Other11111111111111111111111111111111111111"]
+        (alert,) = mon.process(withdraw_tx(signer=ADMIN, authority_signs=True))
+        assert "unexpected" in alert.message
+        assert mon.process(withdraw_tx(err={"InstructionError": [0, {"Custom": 6000}]})) == []
+
+        called = vault_ruleset(InstructionCalledRule(id="call", instruction="withdraw"),
+                               InstructionCalledRule(id="deposit", instruction="deposit"))
+        assert [a.rule_id for a in Monitor(called).process(withdraw_tx())] == ["call"]
+
+        outflow = LargeOutflowRule(id="out", min_samples=3)
+        mon = Monitor(vault_ruleset(outflow))
+        for amount in (10, 12, 9):
+            assert mon.process(withdraw_tx(amount=amount)) == []
+        (alert,) = mon.process(withdraw_tx(amount=5000))
+        assert alert.details["median"] == 10 and "500.0x" in alert.message
+        threshold = Monitor(vault_ruleset(LargeOutflowRule(id="cap", threshold=100, multiplier=None)))
+        assert len(threshold.process(withdraw_tx(amount=100))) == 1
+
+        burst = Monitor(vault_ruleset(FailureBurstRule(id="burst", count=3, window_slots=10)))
+        failed = {"InstructionError": [0, {"Custom": 6001}]}
+        assert burst.process(withdraw_tx(err=failed, slot=1)) == []
+        assert burst.process(withdraw_tx(err=failed, slot=20)) == []
+        assert burst.process(withdraw_tx(err=failed, slot=22)) == []
+        (alert,) = burst.process(withdraw_tx(err=failed, slot=25))
+        assert alert.details["fee_payer"] == ATTACKER and alert.details["instructions"] == ["withdraw"]
+        assert burst.process(withdraw_tx(err=failed, slot=26)) == []
+
+    def test_rules_from_findings(self):
+        program = vault_program()
+        hypotheses = {
+            "hyp_1": {"title": "Missing signer check on authority in withdraw", "severity": "critical",
+                      "vulnerability_type": "access_control", "status": "confirmed"},
+            "hyp_2": {"title": "Rounding in deposit share math", "severity": "medium",
+                      "vulnerability_type": "arithmetic"},
+            "hyp_3": {"title": "Vault drain through reward accrual", "severity": "high",
+                      "vulnerability_type": "economic"},
+            "hyp_4": {"title": "initialize can be front-run", "severity": "low", "vulnerability_type": "logic"},
+            "hyp_5": {"title": "Missing signer check in deposit", "severity": "high",
+                      "vulnerability_type": "access_control", "status": "rejected"},
+            "hyp_6": {"title": "Something about withdraw", "severity": "info", "vulnerability_type": "logic"},
+            "hyp_7": {"title": "Other program admin", "severity": "high", "vulnerability_type": "access_control",
+                      "properties": {"program": "router", "instruction": "withdraw"}},
+        }
+        ruleset = rules_from_findings(hypotheses, program)
+        assert ruleset.program_id == PROGRAM_ID and ruleset.program == "vault"
+        by_id = {r.id: r for r in ruleset.rules}
+        assert list(by_id) == ["authority-withdraw-authority", "large-outflow", "call-initialize", "failure-burst"]
+        assert by_id["authority-withdraw-authority"].finding == "hyp_1"
+        assert by_id["large-outflow"].severity == "high" and by_id["large-outflow"].finding == "hyp_3"
+        assert anchor_discriminator("withdraw").hex() in ruleset.discriminators
+
+        text = yaml.safe_dump(ruleset.to_dict(), sort_keys=False)
+        loaded = RuleSet.from_dict(yaml.safe_load(text))
+        assert loaded.to_dict() == ruleset.to_dict()
+        assert isinstance(loaded.rules[0], UnexpectedAuthorityRule)
+        for bad in ({"rules": []}, {"program_id": PROGRAM_ID, "rules": [{"id": "a", "type": "nope"}]},
+                    {"program_id": PROGRAM_ID, "rules": [{"id": "a", "type": "large-outflow"}] * 2},
+                    {"program_id": PROGRAM_ID, "rules": [{"id": "a", "type": "failure-burst", "severity": "huge"}]}):
+            with pytest.raises(ValueError):
+                RuleSet.from_dict(bad)
+        program.program_id = None
+        with pytest.raises(ValueError):
+            rules_from_findings({}, program)
+
+    def test_monitor_and_polling(self):
+        ruleset = vault_ruleset(UnexpectedAuthorityRule(id="auth", instruction="withdraw", account="authority"))
+        client = FakeClient([withdraw_tx("s1"), withdraw_tx("s3", signer=ADMIN, authority_signs=True)],
+                            [{"signature": s, "slot": 10 - i, "err": None} for i, s in enumerate(["s3", "s2", "s1"])])
+        seen = []
+        mon = Monitor(ruleset, client, [seen.append])
+        mon._fetch.delay = 0
+        mon._fetch.attempts = 2
+        source = PollingSource(client, PROGRAM_ID, interval=0, backfill=3)
+        assert mon.run(source, max_events=3) == 1
+        assert [a.signature for a in seen] == ["s1"]
+        assert mon.processed == 2 and mon.errors == 1
+        assert mon.handle(Observed("s1")) == [] and client.fetched.count("s1") == 1
+        with pytest.raises(ValueError):
+            Monitor(ruleset).handle(Observed("s9"))
+
+    def test_webhook(self):
+        source = WebhookSource(port=0, path="/hook", auth="Bearer t0ken")
+        host, port = source.address
+        url = f"http://{host}:{port}"
+
+        def post(path, payload, auth="Bearer t0ken"):
+            req = urllib.request.Request(url + path, data=json.dumps(payload).encode(),
+                                         headers={"Authorization": auth, "Content-Type": "application/json"})
+            try:
+                return urllib.request.urlopen(req, timeout=5).status
+            except urllib.error.HTTPError as e:
+                return e.code
+
+        try:
+            assert post("/hook", [withdraw_tx("w1")], auth="wrong") == 401
+            assert post("/other", [withdraw_tx("w1")]) == 404
+            assert post("/hook", [withdraw_tx("w1"), {"signature": "w2"}]) == 200
+            mon = Monitor(vault_ruleset(InstructionCalledRule(id="call", instruction="withdraw")),
+                          FakeClient([withdraw_tx("w2")]))
+            start = time.monotonic()
+            assert mon.run(source, max_events=2) == 2
+            assert time.monotonic() - start < 5
+        finally:
+            source.close()
+
+    def test_cli(self, tmp_path):
+        (tmp_path / "hypotheses.json").write_text(json.dumps({"hypotheses": {
+            "hyp_1": {"title": "Missing signer check on authority in withdraw", "severity": "critical",
+                      "vulnerability_type": "access_control"}}}))
+        runner = CliRunner()
+        with patch("commands.monitor.ProjectManager") as PM:
+            PM.return_value.get_project.return_value = {"path": str(tmp_path), "source_path": str(VAULT)}
+            result = runner.invoke(rules, ["demo"])
+            assert result.exit_code == 0, result.output
+            PM.return_value.get_project.return_value = None
+            assert runner.invoke(rules, ["missing"]).exit_code == 1
+        rules_file = tmp_path / "monitoring" / "vault.rules.yaml"
+        data = yaml.safe_load(rules_file.read_text())
+        assert data["program_id"] == PROGRAM_ID
+        assert [r["id"] for r in data["rules"]] == ["authority-withdraw-authority", "failure-burst"]
+
+        with patch("commands.monitor._client", return_value=FakeClient([withdraw_tx("x1")])):
+            result = runner.invoke(replay, [str(rules_file), "x1", "--json"])
+        assert result.exit_code == 0, result.output
+        (alert,) = json.loads(result.output)
+        assert alert["rule_id"] == "authority-withdraw-authority" and alert["finding"] == "hyp_1"
+        (tmp_path / "bad.yaml").write_text("rules: []\n")
+        assert runner.invoke(replay, [str(tmp_path / "bad.yaml"), "x1"]).exit_code == 1
+
+    def test_geyser_plugin(self, tmp_path):
+        ruleset = vault_ruleset(
+            UnexpectedAuthorityRule(id="auth", instruction="withdraw", account="authority", expected=[ADMIN]),
+            UnexpectedAuthorityRule(id="any-authority", account="authority"),
+            LargeOutflowRule(id="out", mint=USDC, threshold=1e6),
+            FailureBurstRule(id="burst", count=3))
+        files = generate_geyser_plugin(ruleset, ["auth", "any-authority", "out"], source="vault.rules.yaml")
+        assert sorted(files) == ["Cargo.toml", "README.md", "config.json", "src/events.rs", "src/lib.rs", "src/rules.rs"]
+        rules_rs = files["src/rules.rs"]
+        assert f'pub const PROGRAM_ID: &str = "{PROGRAM_ID}";' in rules_rs
+        disc = ", ".join(f"0x{b:02x}" for b in anchor_discriminator("withdraw"))
+        assert f'(&[{disc}], "withdraw"),' in rules_rs
+        assert 'AccountRef::Named(&[("withdraw", 1)])' in rules_rs
+        assert 'AccountRef::Named(&[("initialize", 1), ("withdraw", 1)])' in rules_rs
+        assert f'expected: &["{ADMIN}"]' in rules_rs and "threshold: Some(1000000.0_f64)" in rules_rs
+        assert '"burst"' not in rules_rs
+        assert 'name = "vault-baskerville-geyser"' in files["Cargo.toml"]
+        assert json.loads(files["config.json"])["libpath"] == "target/release/libvault_baskerville_geyser.so"
+        assert "fn notify_transaction" in files["src/lib.rs"]
+
+        for bad in (dict(rule_ids=["nope"]), dict(name="bad name")):
+            with pytest.raises(ValueError):
+                generate_geyser_plugin(ruleset, **bad)
+        with pytest.raises(ValueError):
+            generate_geyser_plugin(vault_ruleset(UnexpectedAuthorityRule(id="x", instruction="deposit",
+                                                                         account="authority")))
+        with pytest.raises(ValueError):
+            generate_geyser_plugin(vault_ruleset(LargeOutflowRule(id="x", threshold=float("inf"))))
+
+        rules_file = tmp_path / "vault.rules.yaml"
+        rules_file.write_text(yaml.safe_dump(ruleset.to_dict(), sort_keys=False))
+        runner = CliRunner()
+        result = runner.invoke(geyser, [str(rules_file), "--rule", "auth"])
+        assert result.exit_code == 0, result.output
+        crate = tmp_path / "vault-geyser"
+        assert '"auth"' in (crate / "src" / "rules.rs").read_text()
+        assert runner.invoke(geyser, [str(rules_file)]).exit_code == 1
+        assert runner.invoke(geyser, [str(rules_file), "--force", "--name", "vault-watch"]).exit_code == 0
+        assert 'name = "vault-watch"' in (crate / "Cargo.toml").read_text()
@@ -0,0 +1,185 @@
+"""
+Tests for compiled Move module ingestion and signer/ability analysis.
+
+Modules are serialized by a small builder below (the binary format is
+simple enough to write by hand), so no Move toolchain is needed.
+"""
+
+from pathlib import Path
+
+from extensions.ir import MoveBytecodeFrontend, load_programs
+from extensions.ir.move_bytecode import MoveBinaryError, deserialize_module
+
+
+def _uleb(n: int) -> bytes:
+    out = bytearray()
+    while True:
+        byte = n & 0x7F
+        n >>= 7
+        out.append(byte | (0x80 if n else 0))
+        if not n:
+            return bytes(out)
+
+
+def _vec(items: list[bytes]) -> bytes:
+    return _uleb(len(items)) + b"".join(items)
+
+
+# Signature tokens
+U64, ADDRESS, SIGNER = b"\x03", b"\x05", b"\x0c"
+
+
+def REF(t):
+    return b"\x06" + t
+
+
+def MUT(t):
+    return b"\x07" + t
+
+
+def STRUCT(i):
+    return b"\x08" + _uleb(i)
+
+
+# Opcodes
+MOVE_LOC, CALL, MUT_BORROW_GLOBAL, POP, RET = 0x0B, 0x11, 0x2A, 0x01, 0x02
+
+
+def _code(*ops) -> bytes:
+    out = bytearray()
+    for op in ops:
+        if isinstance(op, tuple):
+            out.append(op[0])
+            out += bytes([op[1]]) if op[0] == MOVE_LOC else _uleb(op[1])
+        else:
+            out.append(op)
+    return _uleb(len(ops)) + bytes(out)
+
+
+def _module(identifiers, addresses, module_handles, struct_handles, signatures, function_handles,
+            struct_defs, function_defs, version=6) -> bytes:
+    tables = [
+        (0x1, b"".join(_uleb(a) + _uleb(n) for a, n in module_handles)),
+        (0x2, b"".join(_uleb(m) + _uleb(n) + bytes([ab]) + _uleb(0) for m, n, ab in struct_handles)),
+        (0x3, b"".join(_uleb(m) + _uleb(n) + _uleb(p) + _uleb(r) + _uleb(0) for m, n, p, r in function_handles)),
+        (0x5, b"".join(_vec(sig) for sig in signatures)),
+        (0x7, b"".join(_uleb(len(s)) + s.encode() for s in identifiers)),
+        (0x8, b"".join(a.to_bytes(32, "big") for a in addresses)),
+        (0xA, b"".join(_uleb(h) + b"\x02" + _vec([_uleb(n) + t for n, t in fields]) for h, fields in struct_defs)),
+        (0xC, b"".join(
+            _uleb(h) + bytes([vis, flags]) + _vec([_uleb(a) for a in acquires]) + _uleb(0) + code
+            for h, vis, flags, acquires, code in function_defs
+        )),
+    ]
+    header, body = bytearray(), bytearray()
+    for kind, content in tables:
+        header += bytes([kind]) + _uleb(len(body)) + _uleb(len(content))
+        body += content
+    return b"\xa1\x1c\xeb\x0b" + version.to_bytes(4, "little") + _uleb(len(tables)) + header + body + _uleb(0)
+
+
+ENTRY = 0x4
+
+# module 0xcafe::vault { struct Vault has key { balance: u64 }
+#   public entry fun withdraw(s: &signer, amt: u64) acquires Vault   -- checks signer::address_of
+#   public entry fun drain(owner: address, amt: u64) acquires Vault  -- no signer at all
+#   fun helper() }
+APTOS_VAULT = _module(
+    identifiers=["vault", "Vault", "balance", "withdraw", "drain", "helper", "signer", "address_of"],
+    addresses=[0xCAFE, 0x1],
+    module_handles=[(0, 0), (1, 6)],
+    struct_handles=[(0, 1, 0x8 | 0x4)],
+    signatures=[[], [REF(SIGNER), U64], [ADDRESS, U64], [REF(SIGNER)], [ADDRESS]],
+    function_handles=[(0, 3, 1, 0), (0, 4, 2, 0), (0, 5, 0, 0), (1, 7, 3, 4)],
+    struct_defs=[(0, [(2, U64)])],
+    function_defs=[
+        (0, 1, ENTRY, [0], _code((MOVE_LOC, 0), (CALL, 3), (MUT_BORROW_GLOBAL, 0), POP, RET)),
+        (1, 1, ENTRY, [0], _code((MOVE_LOC, 0), (MUT_BORROW_GLOBAL, 0), POP, RET)),
+        (2, 0, 0, [], _code(RET)),
+    ],
+)
+
+# module 0xbeef::pool { struct Pool has key { reserve: u64 }
+#   public fun swap(pool: &mut Pool, amt: u64, ctx: &mut 0x2::tx_context::TxContext) }
+SUI_POOL = _module(
+    identifiers=["pool", "Pool", "reserve", "swap", "tx_context", "TxContext"],
+    addresses=[0xBEEF, 0x2],
+    module_handles=[(0, 0), (1, 4)],
+    struct_handles=[(0, 1, 0x8), (1, 5, 0x2)],
+    signatures=[[], [MUT(STRUCT(0)), U64, MUT(STRUCT(1))]],
+    function_handles=[(0, 3, 1, 0)],
+    struct_defs=[(0, [(2, U64)])],
+    function_defs=[(0, 1, 0, [], _code(RET))],
+)
+
+
+class TestDeserialize:
+    """Test the binary format reader."""
+
+    def test_module_tables(self):
+        module = deserialize_module(APTOS_VAULT)
+        assert module.id == "0xcafe::vault"
+        assert module.version == 6
+        assert module.dependencies == ["0x1::signer"]
+        assert [(s.name, s.abilities, s.fields) for s in module.structs] == [("Vault", ["store", "key"], [("balance", "u64")])]
+        withdraw = module.functions[0]
+        assert withdraw.params == ["&signer", "u64"]
+        assert withdraw.is_entry is True
+        assert withdraw.calls == ["0x1::signer::address_of"]
+        assert withdraw.global_writes == ["Vault"]
+
+    def test_bad_magic(self):
+        try:
+            deserialize_module(b"\x00" * 16)
+        except MoveBinaryError as e:
+            assert "magic" in str(e)
+        else:
+            raise AssertionError("expected MoveBinaryError")
+
+
+class TestLowering:
+    """Test signer/ability analysis in the lowered IR."""
+
+    def test_aptos_signer_accounts(self, tmp_path):
+        (tmp_path / "vault.mv").write_bytes(APTOS_VAULT)
+        program = MoveBytecodeFrontend().load(tmp_path / "vault.mv")[0]
+
+        assert (program.chain, program.framework, program.program_id) == ("aptos", "move-bytecode", "0xcafe")
+        assert [ix.name for ix in program.instructions] == ["withdraw", "drain"]  # private helper dropped
+
+        withdraw = program.instruction("withdraw")
+        assert [(a.name, a.is_signer) for a in withdraw.accounts] == [("arg0", True)]
+        assert withdraw.modifiers == ["entry"]
+        assert withdraw.properties["acquires"] == ["Vault"]
+
+        drain = program.instruction("drain")
+        assert drain.accounts == []
+        assert drain.properties["global_writes"] == ["Vault"]
+
+        vault = program.account_type("Vault")
+        assert vault.properties["abilities"] == ["store", "key"]
+
+    def test_sui_objects_and_tx_context(self, tmp_path):
+        modules = tmp_path / "build" / "pool" / "bytecode_modules"
+        modules.mkdir(parents=True)
+        (modules / "pool.mv").write_bytes(SUI_POOL)
+        (modules / "dependencies").mkdir()
+        (modules / "dependencies" / "ignored.mv").write_bytes(b"junk")
+
+        frontend = MoveBytecodeFrontend()
+        programs = frontend.load(tmp_path)
+        assert frontend.errors == []
+        swap = programs[0].instruction("swap")
+        assert programs[0].chain == "sui"
+        assert [(a.ty, a.is_mut, a.is_signer) for a in swap.accounts] == [("&mut Pool", True, False)]
+        assert swap.properties["tx_context"] == "arg2"
+
+    def test_corrupt_module_reported(self, tmp_path):
+        (tmp_path / "bad.mv").write_bytes(APTOS_VAULT[:40])
+        frontend = MoveBytecodeFrontend()
+        assert frontend.load(tmp_path) == []
+        assert frontend.errors[0].startswith("bad.mv")
+
+    def test_load_programs_accepts_modules(self, tmp_path):
+        (tmp_path / "vault.mv").write_bytes(APTOS_VAULT)
+        assert [p.framework for p in load_programs(Path(tmp_path))] == ["move-bytecode"]
@@ -0,0 +1,269 @@
+"""
+Tests for native Solana instruction-dispatch recovery.
+
+Covers tag-byte dispatch, Borsh enum dispatch, SPL-style `unpack` tag
+mapping, and per-handler account list reconstruction.
+"""
+
+from pathlib import Path
+
+from extensions.ir import NativeDispatchRecovery, recover_native_programs
+from extensions.ir.rust_source import find_matching, parse_match_arms, strip_comments
+
+
+TAG_BYTE_PROGRAM = '''
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint,
+    entrypoint::ProgramResult,
+    program::invoke,
+    pubkey::Pubkey,
+};
+
+solana_program::declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+entrypoint!(process_instruction);
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let (tag, rest) = instruction_data.split_first().ok_or(ProgramError::InvalidInstructionData)?;
+    match tag {
+        0 => process_initialize(program_id, accounts),
+        1 => process_withdraw(program_id, accounts, rest),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn process_initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let vault = next_account_info(iter)?;
+    let payer = next_account_info(iter)?;
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if vault.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+fn process_withdraw(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let vault = next_account_info(iter)?;
+    let authority = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    // No signer check on authority!
+    **vault.try_borrow_mut_lamports()? -= 1;
+    invoke(
+        &spl_token::instruction::transfer(token_program.key, vault.key, authority.key, authority.key, &[], 1)?,
+        &[vault.clone(), authority.clone()],
+    )?;
+    Ok(())
+}
+'''
+
+BORSH_PROGRAM = '''
+use borsh::{BorshDeserialize, BorshSerialize};
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub enum VaultInstruction {
+    /// Deposit lamports.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The vault account
+    /// 1. `[signer]` The depositor
+    Deposit { amount: u64 },
+    /// Withdraw lamports.
+    Withdraw(u64),
+    Close,
+}
+
+entrypoint!(process_instruction);
+
+pub fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    Processor::process(program_id, accounts, data)
+}
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+        let instruction = VaultInstruction::try_from_slice(data)?;
+        match instruction {
+            VaultInstruction::Deposit { amount } => Self::process_deposit(accounts, amount),
+            VaultInstruction::Withdraw(amount) => Self::process_withdraw(accounts, amount),
+            VaultInstruction::Close => {
+                let [vault, destination] = accounts else {
+                    return Err(ProgramError::NotEnoughAccountKeys);
+                };
+                **destination.lamports.borrow_mut() += vault.lamports();
+                Ok(())
+            }
+        }
+    }
+
+    fn process_deposit(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+        let vault = &accounts[0];
+        let depositor = &accounts[1];
+        Ok(())
+    }
+
+    fn process_withdraw(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+        let iter = &mut accounts.iter();
+        let vault = next_account_info(iter)?;
+        Ok(())
+    }
+}
+'''
+
+UNPACK_PROGRAM = '''
+pub enum TokenInstruction {
+    InitializeMint { decimals: u8 },
+    Transfer { amount: u64 },
+}
+
+impl TokenInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (&tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+        Ok(match tag {
+            7 => Self::Transfer { amount: unpack_u64(rest)? },
+            3 => Self::InitializeMint { decimals: rest[0] },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+}
+
+entrypoint!(process_instruction);
+
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
+    let instruction = TokenInstruction::unpack(input)?;
+    match instruction {
+        TokenInstruction::InitializeMint { decimals } => process_initialize_mint(accounts, decimals),
+        TokenInstruction::Transfer { amount } => process_transfer(accounts, amount),
+    }
+}
+
+fn process_initialize_mint(accounts: &[AccountInfo], decimals: u8) -> ProgramResult { Ok(()) }
+fn process_transfer(accounts: &[AccountInfo], amount: u64) -> ProgramResult { Ok(()) }
+'''
+
+
+def _write_crate(root: Path, name: str, source: str) -> Path:
+    crate = root / name
+    (crate / "src").mkdir(parents=True)
+    (crate / "Cargo.toml").write_text(f'[package]\nname = "{name}"\nversion = "0.1.0"\n')
+    (crate / "src" / "lib.rs").write_text(source)
+    return crate
+
+
+class TestRustSourceHelpers:
+    """Test the comment stripping and delimiter helpers."""
+
+    def test_strip_comments_preserves_layout(self):
+        src = 'let a = 1; // trailing\n/* block\n comment */ let b = b"//not";'
+        stripped = strip_comments(src)
+        assert len(stripped) == len(src)
+        assert stripped.count("\n") == src.count("\n")
+        assert "trailing" not in stripped
+        assert 'b"//not"' in stripped
+
+    def test_lifetimes_are_not_char_literals(self):
+        src = "fn f<'info>(a: Account<'info, X>) { let c = 'x'; /* gone */ }"
+        stripped = strip_comments(src)
+        assert "gone" not in stripped
+        assert "'info" in stripped
+
+    def test_find_matching_nested(self):
+        text = "{ a { b } (c) }"
+        assert find_matching(text, 0) == len(text) - 1
+
+    def test_parse_match_arms(self):
+        arms = parse_match_arms("0 => foo(a, b), 1 => { bar() } _ => Err(x),")
+        assert [a[0] for a in arms] == ["0", "1", "_"]
+        assert arms[0][1] == "foo(a, b)"
+
+
+class TestTagByteDispatch:
+    """Test `match tag { 0 => ... }` dispatch recovery."""
+
+    def test_recovers_handlers_and_discriminators(self, tmp_path):
+        crate = _write_crate(tmp_path, "vault", TAG_BYTE_PROGRAM)
+        programs = recover_native_programs(crate)
+
+        assert len(programs) == 1
+        program = programs[0]
+        assert program.framework == "native"
+        assert program.name == "vault"
+        assert program.program_id == "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+        assert program.metadata["dispatch"] == "tag_byte"
+
+        handlers = {ix.discriminator: ix.handler for ix in program.instructions}
+        assert handlers == {"0": "process_initialize", "1": "process_withdraw"}
+
+    def test_account_lists_and_checks(self, tmp_path):
+        crate = _write_crate(tmp_path, "vault", TAG_BYTE_PROGRAM)
+        program = recover_native_programs(crate)[0]
+
+        init = program.instruction("process_initialize")
+        assert [a.name for a in init.accounts] == ["vault", "payer"]
+        assert init.account("payer").is_signer is True
+        assert "owner" in init.account("vault").constraints
+
+        withdraw = program.instruction("process_withdraw")
+        assert [a.name for a in withdraw.accounts] == ["vault", "authority", "token_program"]
+        assert withdraw.account("authority").is_signer is False
+        assert withdraw.account("vault").is_mut is True
+        assert [a.name for a in withdraw.args] == ["data"]
+
+    def test_cpi_target_recovered(self, tmp_path):
+        crate = _write_crate(tmp_path, "vault", TAG_BYTE_PROGRAM)
+        withdraw = recover_native_programs(crate)[0].instruction("process_withdraw")
+
+        assert len(withdraw.calls) == 1
+        assert withdraw.calls[0].kind == "invoke"
+        assert withdraw.calls[0].target == "token_program.key"
+
+
+class TestEnumDispatch:
+    """Test Borsh enum and SPL-style unpack dispatch recovery."""
+
+    def test_borsh_enum_through_delegation(self, tmp_path):
+        crate = _write_crate(tmp_path, "borsh_vault", BORSH_PROGRAM)
+        program = recover_native_programs(crate)[0]
+
+        assert program.metadata["dispatch"] == "borsh_enum"
+        assert program.metadata["instruction_enum"] == "VaultInstruction"
+        names = [(ix.discriminator, ix.name) for ix in program.instructions]
+        assert names == [("0", "deposit"), ("1", "withdraw"), ("2", "close")]
+
+        deposit = program.instruction("deposit")
+        assert deposit.handler == "process_deposit"
+        assert [(a.name, a.ty) for a in deposit.args] == [("amount", "u64")]
+        assert [a.name for a in deposit.accounts] == ["vault", "depositor"]
+        # Doc convention marks vault writable, depositor as a declared (unchecked) signer
+        assert deposit.account("vault").is_mut is True
+        assert "declared_signer" in deposit.account("depositor").constraints
+        assert deposit.account("depositor").is_signer is False
+
+    def test_inline_arm_handler(self, tmp_path):
+        crate = _write_crate(tmp_path, "borsh_vault", BORSH_PROGRAM)
+        close = recover_native_programs(crate)[0].instruction("close")
+
+        assert close.properties.get("inline_handler") is True
+        assert [a.name for a in close.accounts] == ["vault", "destination"]
+        assert close.account("destination").is_mut is True
+
+    def test_unpack_tags_override_variant_order(self, tmp_path):
+        crate = _write_crate(tmp_path, "token", UNPACK_PROGRAM)
+        program = recover_native_programs(crate)[0]
+
+        assert program.metadata["dispatch"] == "enum"
+        tags = {ix.name: ix.discriminator for ix in program.instructions}
+        assert tags == {"initialize_mint": "3", "transfer": "7"}
+
+    def test_anchor_programs_are_skipped(self, tmp_path):
+        _write_crate(tmp_path, "anchor_prog", "#[program]\npub mod p {}\n")
+        assert NativeDispatchRecovery().find_program_roots(tmp_path) == []
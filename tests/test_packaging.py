@@ -0,0 +1,93 @@
+"""
+Tests for the distribution: a build from pyproject.toml carries every
+module and data file under the packages (whose top-level directories have
+no __init__.py), and the installed commands import nothing that is not a
+core dependency.
+"""
+
+import ast
+import subprocess
+import sys
+from pathlib import Path
+
+try:
+    import tomllib
+except ModuleNotFoundError:  # Python < 3.11
+    import tomli as tomllib
+
+ROOT = Path(__file__).resolve().parents[1]
+
+PACKAGES = ["llm", "ingest", "commands", "analysis", "visualization", "utils", "extensions"]
+
+# Import names that differ from the distribution that provides them
+DISTRIBUTIONS = {"yaml": "pyyaml", "dotenv": "python-dotenv"}
+
+
+def _build(root: Path) -> Path:
+    """Build the pure-Python part of the distribution into `root`, leaving the checkout untouched."""
+    result = subprocess.run(
+        [sys.executable, "-c", "import setuptools; setuptools.setup()", "--quiet",
+         "egg_info", "--egg-base", str(root), "build", "--build-base", str(root / "build"),
+         "build_py", "--build-lib", str(root / "lib")],
+        cwd=ROOT, capture_output=True, text=True, timeout=300,
+    )
+    assert result.returncode == 0, result.stderr
+    return root / "lib"
+
+
+def _module_imports(module: str, seen: set[str]) -> set[str]:
+    """Third-party names `module` and the repo modules it pulls in import at load time."""
+    if module in seen:
+        return set()
+    seen.add(module)
+    path = ROOT.joinpath(*module.split("."))
+    path = path.with_suffix(".py") if path.with_suffix(".py").exists() else path / "__init__.py"
+    if not path.exists():
+        return set()
+    package = module if path.name == "__init__.py" else module.rpartition(".")[0]
+    found = set()
+    # Imports under try/except or inside functions are optional or lazy
+    for node in ast.parse(path.read_text()).body:
+        if isinstance(node, ast.Import):
+            names = [alias.name for alias in node.names]
+        elif isinstance(node, ast.ImportFrom):
+            base = node.module or ""
+            if node.level:
+                parts = package.split(".")[: len(package.split(".")) - node.level + 1]
+                base = ".".join(parts + ([base] if base else []))
+            names = [base] + [f"{base}.{alias.name}" for alias in node.names]
+        else:
+            continue
+        for name in names:
+            top = name.split(".")[0]
+            if (ROOT / top).is_dir() or (ROOT / f"{top}.py").exists():
+                found |= _module_imports(name, seen)
+            elif top not in sys.stdlib_module_names:
+                found.add(top)
+    return found
+
+
+class TestPackaging:
+    """Test what a build ships and what the installed commands need."""
+
+    def test_build_contents(self, tmp_path):
+        lib = _build(tmp_path)
+        built = {p.relative_to(lib).as_posix() for p in lib.rglob("*") if p.is_file()}
+        expected = {p.relative_to(ROOT).as_posix() for package in PACKAGES for p in (ROOT / package).rglob("*")
+                    if p.is_file() and "__pycache__" not in p.parts}
+        assert expected - built == set()
+        for path in ("extensions/static/missing_signer.py", "extensions/static/rule_catalog.yaml",
+                     "extensions/knowledge/classes/core.yaml", "extensions/knowledge/templates/solana/missing_signer.rs",
+                     "commands/scan.py", "hound.py", "baskerville.py"):
+            assert path in built
+        assert not any(p.startswith(("tests/", "scripts/")) for p in built)
+        assert not (ROOT / "build").exists() and not list(ROOT.glob("*.egg-info"))
+
+    def test_commands_dependencies(self):
+        project = tomllib.loads((ROOT / "pyproject.toml").read_text())["project"]
+        core = {d.split(";")[0].split(">")[0].split("=")[0].strip().lower() for d in project["dependencies"]}
+        seen = set()
+        for script in project["scripts"].values():
+            imported = _module_imports(script.split(":")[0], seen)
+            assert {DISTRIBUTIONS.get(name, name) for name in imported} - core == set()
+        assert {"typer", "rich", "python-dotenv", "click"} <= core
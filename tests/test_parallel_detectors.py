@@ -0,0 +1,113 @@
+"""
+Tests for running detectors in parallel: the pipeline's ``jobs``, programs
+parsed once for every detector (and once across worker processes), and
+`scan --jobs`.
+"""
+
+import json
+import threading
+import time
+
+from click.testing import CliRunner
+
+from commands.scan import scan
+from extensions.ir import ProgramCache
+from extensions.static import StaticAnalysisPipeline
+from extensions.static.cache import ScanCache
+
+SOURCE = """use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod {name} {{
+    use super::*;
+
+    pub fn set_fee(ctx: Context<SetFee>, fee: u64) -> Result<()> {{
+        ctx.accounts.config.fee = fee;
+        Ok(())
+    }}
+}}
+
+#[derive(Accounts)]
+pub struct SetFee<'info> {{
+    #[account(mut, has_one = admin)]
+    pub config: Account<'info, Config>,
+    /// CHECK: config admin
+    pub admin: AccountInfo<'info>,
+}}
+"""
+
+TOOLS = ["missing-signer", "type-cosplay", "arbitrary-cpi", "duplicate-mutable", "cpi-targets"]
+
+
+def _programs(crates: int = 4) -> dict[str, str]:
+    return {f"vault{i}": SOURCE.format(name=f"vault{i}") for i in range(crates)}
+
+
+class TestParallelDetectors:
+    """Test running a chain's detectors at the same time."""
+
+    def test_same_results(self, tmp_path, anchor_workspace):
+        workspace = anchor_workspace(tmp_path / "ws", _programs(), localnet=["vault0"])
+        serial = StaticAnalysisPipeline(chain_id="solana", jobs=1).run(workspace, tools=TOOLS)
+        events = []
+        parallel = StaticAnalysisPipeline(chain_id="solana", jobs=4).run(workspace, tools=TOOLS,
+                                                                         progress=events.append)
+        assert serial.metadata["jobs"] == 1 and parallel.metadata["jobs"] == 4
+        assert parallel.hypotheses == serial.hypotheses and len(parallel.hypotheses) == 4
+        # Results keep the detectors' order whichever finishes first
+        assert list(parallel.metadata["tools"]) == list(serial.metadata["tools"])
+
+        completed = [e for e in events if e.event == "detector_completed"]
+        assert sorted(e.detector for e in completed) == sorted(TOOLS)
+        assert [e.detectors_completed for e in completed] == list(range(1, len(TOOLS) + 1))
+        assert events[-1].event == "finished" and events[-1].detectors_completed == len(TOOLS)
+
+    def test_parse_once(self, tmp_path, anchor_workspace):
+        workspace = anchor_workspace(tmp_path / "ws", _programs(1), localnet=["vault0"])
+        cache = ProgramCache()
+        calls = []
+        start = threading.Barrier(4)
+
+        def load():
+            calls.append(threading.get_ident())
+            time.sleep(0.05)
+            return ["lowered"]
+
+        def worker(results):
+            start.wait()
+            results.append(cache.get_or_load("anchor", workspace / "programs" / "vault0", load))
+
+        results = []
+        threads = [threading.Thread(target=worker, args=(results,)) for _ in range(4)]
+        for thread in threads:
+            thread.start()
+        for thread in threads:
+            thread.join()
+        assert len(calls) == 1 and results == [["lowered"]] * 4
+        assert (cache.misses, cache.hits) == (1, 3)
+
+    def test_worker_processes(self, tmp_path, anchor_workspace):
+        # Each crate is lowered once in the pool; what the workers lower is cached for the next scan
+        runs = {}
+        for jobs in (1, 4):
+            workspace = anchor_workspace(tmp_path / f"ws{jobs}", _programs(), localnet=["vault0"])
+            result = StaticAnalysisPipeline(chain_id="solana", jobs=jobs, cache=ScanCache(workspace)).run(
+                workspace, tools=TOOLS)
+            again = StaticAnalysisPipeline(chain_id="solana", jobs=jobs, cache=ScanCache(workspace)).run(
+                workspace, tools=["pda-collisions", "missing-owner"])
+            runs[jobs] = result.hypotheses, result.metadata["cache"]["programs_parsed"], again.metadata["cache"]
+        assert runs[4][:2] == runs[1][:2] and runs[4][1] > 0
+        assert runs[4][2]["programs_parsed"] == 0 and runs[4][2]["detectors_replayed"] == 0
+
+    def test_cli(self, tmp_path, anchor_workspace):
+        workspace = anchor_workspace(tmp_path / "ws", _programs(2), localnet=["vault0"])
+        runner = CliRunner()
+        result = runner.invoke(scan, [str(workspace), "--jobs", "2", "--no-cache", "--json"]
+                               + [arg for tool in TOOLS for arg in ("--tool", tool)])
+        assert result.exit_code == 0, result.output
+        assert len(json.loads(result.output)["hypotheses"]) == 2
+
+        result = runner.invoke(scan, [str(workspace), "--jobs", "0"])
+        assert result.exit_code != 0
@@ -0,0 +1,138 @@
+"""
+Tests for the workspace PDA derivation map and collision scanner.
+"""
+
+import json
+from pathlib import Path
+
+from click.testing import CliRunner
+
+from commands.ir import pda_map
+from extensions.ir import load_programs
+from extensions.ir.pda_map import (
+    PdaDerivation,
+    Seed,
+    build_pda_map,
+    find_conflicts,
+    find_namespace_reuse,
+    normalize_seed,
+    patterns_overlap,
+    seed_constants,
+)
+from extensions.static.pda_collisions import TEMPLATE_FILE, PdaCollisionScanner, type_seed
+from extensions.static.pipeline import StaticAnalysisPipeline
+
+ROOT = Path(__file__).resolve().parents[1]
+WORKSPACE = Path(__file__).resolve().parent / "fixtures" / "solana" / "pda_workspace"
+
+
+class TestPdaMap:
+    """Test derivation extraction, normalization and conflict detection."""
+
+    def setup_method(self):
+        self.derivations = build_pda_map(load_programs(WORKSPACE))
+        self.by_type = {d.account_type: d for d in self.derivations}
+        self.conflicts = {
+            (c.kind, frozenset((c.a.account_type, c.b.account_type))): c for c in find_conflicts(self.derivations)
+        }
+
+    def test_normalize_seed(self):
+        assert normalize_seed('b"vault"', None).value == "vault"
+        assert normalize_seed('"vault".as_bytes()', None).width == 5
+        assert normalize_seed("user.key.as_ref()", None).width == 32
+        assert normalize_seed("&[bump]", None).width == 1
+        assert normalize_seed("name.as_bytes()", None).kind == "bytes"
+
+    def test_patterns_overlap(self):
+        assert patterns_overlap(list("user") + ["?"] * 2, list("use") + ["?"] * 3)
+        assert not patterns_overlap(list("user") + ["?"] * 2, list("user") + ["?"] * 3)
+        assert patterns_overlap(list("pool") + ["*"], list("poolvault") + ["?"] * 32)
+        assert not patterns_overlap(list("pool") + ["*"], list("stake") + ["*"])
+
+    def test_derivations(self):
+        assert self.by_type["UserProfile"].schema == '"user" / <pubkey:owner>'
+        assert self.by_type["Pool"].schema == '"pool" / <bytes:name>'
+        # Body derivation: named after the variable, untyped
+        claim = self.by_type["expected_profile"]
+        assert claim.source == "find_program_address"
+        assert claim.line == 31
+        assert not claim.typed
+        # seeds::program points the derivation at the sibling program
+        assert self.by_type["reward_profile"].owner == "rewards"
+        assert self.by_type["reward_profile"].program == "staking"
+
+    def test_conflicts(self):
+        same_seeds = self.conflicts[("collision", frozenset({"UserProfile", "UserConfig"}))]
+        assert same_seeds.severity == "medium"
+        # Variable-length name can spell "vault" + the mint key
+        concat = self.conflicts[("collision", frozenset({"Pool", "pool_vault"}))]
+        assert concat.severity == "high"
+        assert ("prefix", frozenset({"StakeAccount", "StakeReceipt"})) in self.conflicts
+        assert ("cross-program", frozenset({"UserProfile", "Position"})) in self.conflicts
+        # Re-derivations of the same account are not conflicts
+        assert not any("expected_profile" in key[1] or "reward_profile" in key[1] for key in self.conflicts)
+
+    def test_scanner(self):
+        findings, metadata = PdaCollisionScanner(report_namespace=False).run(WORKSPACE)
+        assert {f.kind for f in findings} == {"collision", "cross-program"}
+        assert metadata["conflicts"] == {"collision": 2, "prefix": 1, "cross-program": 2}
+        hyp = next(f for f in findings if f.severity == "high").to_hypothesis()
+        assert hyp["vulnerability_type"] == "pda-seed-collision"
+        assert hyp["properties"]["source_files"] == ["programs/rewards/src/lib.rs"]
+        assert hyp["properties"]["affected_lines"] == [87, 90]
+        assert len(PdaCollisionScanner(report_prefix=True, report_namespace=False).run(WORKSPACE)[0]) == 5
+        assert "pda-collisions" in StaticAnalysisPipeline(chain_id="solana").runners
+
+    def test_template_fix(self):
+        findings = PdaCollisionScanner(report_namespace=False).run(WORKSPACE)[0]
+        pair = {"UserProfile", "UserConfig"}
+        finding = next(f for f in findings if {f.conflict.a.account_type, f.conflict.b.account_type} == pair)
+        assert type_seed("UserProfile") == "user_profile"
+        fix = finding.suggested_fix
+        assert fix["UserProfile"].startswith('seeds = [b"user_profile", ') and '"user"' not in fix["UserProfile"]
+        hyp = finding.to_hypothesis()
+        assert hyp["properties"]["poc_template"] == "pda_seed_collision" and (ROOT / TEMPLATE_FILE).exists()
+        assert "kb render pda_seed_collision" in hyp["description"]
+        # A variable-length `name` seed still spells "pool_vault" + mint behind any prefix
+        pool = next(f for f in findings if f.kind == "collision" and f is not finding)
+        assert pool.suggested_fix is None and pool.to_hypothesis()["properties"]["poc_template"] == "pda_seed_collision"
+        # Cross-program reuse needs `seeds::program`, not new seeds
+        cross = next(f for f in findings if f.kind == "cross-program")
+        assert cross.suggested_fix is None and "poc_template" not in cross.to_hypothesis()["properties"]
+
+    def test_seed_constants(self):
+        staking = next(p for p in load_programs(WORKSPACE) if p.name == "staking")
+        constants = seed_constants(staking)
+        assert constants["POSITION_SEED"].value == "user"
+        assert constants["POSITION_SEED"].file == "src/constants.rs"
+        assert normalize_seed("METADATA_SEED.as_bytes()", None, constants=constants).value == "metadata"
+        # Resolved constants take part in the byte-level comparison
+        assert self.by_type["Position"].schema == '"user" / <pubkey:owner>'
+
+    def test_namespace_reuse(self):
+        programs = load_programs(WORKSPACE)
+        constants = {p.name: seed_constants(p) for p in programs}
+        reuse = {r.value: r for r in find_namespace_reuse(self.derivations, constants)}
+        assert set(reuse) == {"user", "metadata"}
+        assert reuse["user"].kind == "workspace"
+        assert reuse["user"].programs == ["rewards", "staking"]
+        # The seeds::program reference into rewards is not a use of the namespace
+        assert "reward_profile" not in {d.account_type for d in reuse["user"].derivations}
+        assert reuse["metadata"].ecosystem == "Metaplex Token Metadata"
+        assert reuse["metadata"].severity == "low"
+        event = PdaDerivation("p", "p", "Log", "log", "src/lib.rs", 1, [Seed("const", 'b"__event_authority"', "__event_authority", 17)])
+        assert find_namespace_reuse([event])[0].severity == "medium"
+
+        findings, metadata = PdaCollisionScanner().run(WORKSPACE)
+        assert metadata["namespace_reuse"] == 2
+        hyp = next(f for f in findings if f.kind == "namespace" and f.reuse.value == "user").to_hypothesis()
+        assert hyp["vulnerability_type"] == "pda-seed-namespace-reuse"
+        assert "programs/staking/src/constants.rs" in hyp["properties"]["source_files"]
+
+    def test_cli_json(self):
+        result = CliRunner().invoke(pda_map, [str(WORKSPACE), "--json"])
+        assert result.exit_code == 0
+        data = json.loads(result.output)
+        assert len(data["derivations"]) == len(self.derivations)
+        assert {c["kind"] for c in data["conflicts"]} == {"collision", "prefix", "cross-program"}
+        assert {r["value"] for r in data["namespace_reuse"]} == {"user", "metadata"}
@@ -0,0 +1,59 @@
+"""
+Tests for the shared attacker harness: generated files, writing into an
+existing test directory and the CLI.
+"""
+
+import pytest
+from click.testing import CliRunner
+
+from commands.poc import load_harness_files
+from commands.pocs import harness
+from extensions.poc import generate_harness, write_harness
+from extensions.poc.harness import needs_fake_token
+
+
+class TestHarness:
+    """Test the generated helper files."""
+
+    def test_files(self):
+        assert list(generate_harness()) == ["attacker.ts"]
+        files = generate_harness(fake_token=True)
+        assert sorted(files) == ["attacker.ts", "fake-token/Cargo.toml", "fake-token/README.md",
+                                 "fake-token/src/lib.rs"]
+        library = files["attacker.ts"]
+        for helper in ("createAttacker", "airdrop", "ensureAta", "fundedTokenAccount", "deployFakeTokenProgram",
+                       "warpSeconds", "warpToEpoch"):
+            assert f"export async function {helper}(" in library
+        assert 'name = "fake-token"' in files["fake-token/Cargo.toml"]
+        assert "fake-token: signer" in files["fake-token/src/lib.rs"]
+
+    def test_write(self, tmp_path):
+        files = generate_harness(fake_token=True)
+        assert len(write_harness(files, tmp_path)) == 4
+        # Unchanged files are not rewritten; an edited copy is kept unless forced
+        assert write_harness(files, tmp_path) == []
+        (tmp_path / "attacker.ts").write_text("// edited\n")
+        with pytest.raises(FileExistsError, match="attacker.ts already exist"):
+            write_harness(files, tmp_path)
+        assert write_harness(files, tmp_path, force=True) == [tmp_path / "attacker.ts"]
+
+    def test_fake_token_classes(self):
+        assert needs_fake_token({"vulnerability_type": "cpi_reentrancy"})
+        assert needs_fake_token({"vulnerability_type": "arbitrary cpi"})
+        assert not needs_fake_token({"vulnerability_type": "missing_signer"})
+        assert not needs_fake_token({})
+        # Prompts only get helpers for Solana projects
+        assert load_harness_files([], {"vulnerability_type": "cpi_reentrancy"}) == {}
+
+
+class TestHarnessCli:
+    """Test the poc harness command."""
+
+    def test_write(self, tmp_path):
+        result = CliRunner().invoke(harness, [str(tmp_path), "--fake-token"])
+        assert result.exit_code == 0, result.output
+        assert (tmp_path / "fake-token" / "src" / "lib.rs").exists()
+        assert "up to date" in CliRunner().invoke(harness, [str(tmp_path)]).output
+        (tmp_path / "attacker.ts").write_text("// edited\n")
+        assert CliRunner().invoke(harness, [str(tmp_path)]).exit_code == 1
+        assert CliRunner().invoke(harness, [str(tmp_path), "--force"]).exit_code == 0
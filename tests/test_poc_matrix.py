@@ -0,0 +1,158 @@
+"""
+Tests for the PoC execution matrix: running packaged PoCs per backend,
+outcome and metric parsing, the run store, the matrix view and the CLI.
+"""
+
+import base64
+import json
+import shutil
+import subprocess
+from pathlib import Path
+
+import pytest
+from click.testing import CliRunner
+
+from commands.pocs import matrix, run
+from extensions.poc import PocRun, PocRunStore, parse_metrics, run_matrix, run_poc
+from extensions.poc.matrix import default_backends, matrix_rows
+
+WORKSPACE = Path(__file__).resolve().parent / "fixtures" / "solana" / "anchor_config"
+
+USDC = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
+TOKEN = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+
+
+def fetch(addresses):
+    info = {"lamports": 1_000_000, "owner": TOKEN, "executable": False, "rentEpoch": 0,
+            "data": [base64.b64encode(b"\x01" * 82).decode(), "base64"], "space": 82}
+    return [info if a == USDC else None for a in addresses]
+
+
+class FakeRunner:
+    """Records each run.sh invocation and answers with canned output."""
+
+    def __init__(self, *results):
+        self.results = list(results)
+        self.calls = []
+
+    def __call__(self, cmd, cwd, env, timeout):
+        self.calls.append({"cmd": cmd, "cwd": Path(cwd), "env": env,
+                           "run_sh": (Path(cwd) / "run.sh").read_text(),
+                           "fixtures": sorted(p.name for p in (Path(cwd) / "fixtures").rglob("*.json"))})
+        result = self.results.pop(0)
+        if isinstance(result, Exception):
+            raise result
+        return result
+
+
+@pytest.fixture
+def workspace(tmp_path):
+    source = tmp_path / "workspace"
+    shutil.copytree(WORKSPACE, source)
+    poc = tmp_path / "exploit.ts"
+    poc.write_text(f'const USDC = new PublicKey("{USDC}");\n')
+    return source, {"tests/exploit.ts": poc}
+
+
+class TestPocMatrix:
+    """Test runs, the store and the CLI."""
+
+    def test_run_poc(self, workspace):
+        source, pocs = workspace
+        runner = FakeRunner((0, "  exploit\n    ✓ drains\nProgram log: consumed 5123 of 200000 compute units\n"
+                                "Program log: consumed 812 of 200000 compute units\n  2 passing (3s)\n"),
+                            (1, "  1 passing (2s)\n  1 failing\n"))
+        local = run_poc(source, pocs, "hyp_1", "test-validator", runner=runner, fetch=fetch)
+        assert (local.status, local.tests_passed, local.tests_failed, local.compute_units) == ("passed", 2, 0, 5123)
+        assert local.command.startswith("npx ts-mocha") and local.exit_code == 0 and local.duration >= 0
+        call = runner.calls[0]
+        assert call["cmd"] == ["bash", "run.sh"] and int(call["env"]["RPC_PORT"]) >= 20_000
+        # A local run loads the workspace's own dump and snapshots nothing from the cluster
+        assert "--account Cfg1111111111111111111111111111111111111111 accounts/config.json" in call["run_sh"]
+        assert call["fixtures"] == ["manifest.json"]
+        assert not call["cwd"].exists()
+
+        fork = run_poc(source, pocs, "hyp_1", "fork", runner=runner, fetch=fetch)
+        assert (fork.status, fork.tests_passed, fork.tests_failed) == ("failed", 1, 1)
+        assert f"{USDC}.json" in runner.calls[1]["fixtures"]
+
+        skipped = run_poc(source, pocs, "hyp_1", "litesvm", runner=runner)
+        assert skipped.status == "skipped" and skipped.reason == "the PoC does not use LiteSVM"
+        assert run_poc(source, pocs, "hyp_1", "native", runner=FakeRunner((0, "ok\n"))).status == "passed"
+        timeout = run_poc(source, pocs, "hyp_1", "test-validator", timeout=5,
+                          runner=FakeRunner(subprocess.TimeoutExpired("bash", 5, output=b"building...\n")))
+        assert (timeout.status, timeout.reason, timeout.log) == ("error", "timed out after 5s", "building...")
+        broken = run_poc(source, pocs, "hyp_1", "test-validator", runner=FakeRunner((127, "anchor: not found\n")))
+        assert broken.status == "error" and "before any test ran" in broken.reason
+        with pytest.raises(ValueError):
+            run_poc(source, pocs, "hyp_1", "mainnet")
+
+        (source / "Anchor.toml").write_text((source / "Anchor.toml").read_text().replace(
+            'url = "https://api.mainnet-beta.solana.com"\n', ""))
+        assert "no cluster to fork" in run_poc(source, pocs, "hyp_1", "fork", runner=runner).reason
+        assert default_backends(source) == ["litesvm", "test-validator", "fork"]
+
+    def test_parse_metrics(self):
+        assert parse_metrics("test result: ok. 3 passed; 0 failed; 0 ignored\n"
+                             "test result: FAILED. 1 passed; 2 failed; 0 ignored\n") == {
+            "tests_passed": 4, "tests_failed": 2, "compute_units": None}
+        assert parse_metrics("Suite result: ok. 1 passed; 0 failed; 0 skipped")["tests_passed"] == 1
+        assert parse_metrics("Test result: OK. Total tests: 3; passed: 3; failed: 0")["tests_passed"] == 3
+        assert parse_metrics("error: could not compile") == {
+            "tests_passed": None, "tests_failed": None, "compute_units": None}
+
+    def test_store_and_matrix(self, workspace, tmp_path):
+        source, pocs = workspace
+        seen = []
+        runs = run_matrix(source, {"hyp_1": pocs, "hyp_2": pocs}, ["test-validator", "litesvm"],
+                          on_run=seen.append, runner=FakeRunner((0, "1 passing\n"), (1, "0 passing\n1 failing\n")))
+        assert [(r.hypothesis, r.backend, r.status) for r in runs] == [
+            ("hyp_1", "test-validator", "passed"), ("hyp_1", "litesvm", "skipped"),
+            ("hyp_2", "test-validator", "failed"), ("hyp_2", "litesvm", "skipped")]
+        assert seen == runs and len({r.batch for r in runs}) == 1
+
+        store = PocRunStore(tmp_path / "poc_runs.json")
+        store.record(runs)
+        store.record([PocRun("hyp_2", "test-validator", "passed", batch="later")])
+        assert len(store.runs()) == 5 and len(store.runs(hypothesis="hyp_2", backend="test-validator")) == 2
+        latest = store.latest()
+        assert latest[("hyp_2", "test-validator")].batch == "later"
+        backends, rows = matrix_rows(latest)
+        assert backends == ["litesvm", "test-validator"]
+        assert [(h, [r.status for r in cells]) for h, cells in rows] == [
+            ("hyp_1", ["skipped", "passed"]), ("hyp_2", ["skipped", "passed"])]
+        assert matrix_rows(latest, ["fork"])[1][0] == ("hyp_1", [None])
+
+    def test_cli(self, workspace, tmp_path, monkeypatch):
+        source, pocs = workspace
+        project_dir = tmp_path / "project"
+        for hypothesis in ("hyp_1", "hyp_2"):
+            poc_dir = project_dir / "poc" / hypothesis
+            poc_dir.mkdir(parents=True)
+            shutil.copy(pocs["tests/exploit.ts"], poc_dir / "exploit.ts")
+            (poc_dir / "metadata.json").write_text(json.dumps({"files": [
+                {"name": "exploit.ts", "original_path": str(source / "tests" / "exploit.ts")}]}))
+        (project_dir / "hypotheses.json").write_text(json.dumps({"hypotheses": {
+            "hyp_1": {"title": "Escrow drains", "severity": "high"}}}))
+
+        class FakeProjects:
+            def get_project(self, name):
+                return {"path": str(project_dir), "source_path": str(source)} if name == "escrow" else None
+
+        monkeypatch.setattr("commands.project.ProjectManager", FakeProjects)
+        monkeypatch.setattr("extensions.poc.matrix._run", FakeRunner((0, "1 passing\n"), (1, "boom\n")))
+        runner = CliRunner()
+        result = runner.invoke(run, ["escrow", "--all", "--backend", "test-validator", "--backend", "litesvm"])
+        assert result.exit_code == 0, result.output
+        assert "hyp_1 on test-validator" in result.output and "hyp_2 on litesvm" in result.output
+        assert "Verified on at least one backend: 1/2 hypotheses" in result.output
+
+        data = json.loads(runner.invoke(matrix, ["escrow", "--json"]).output)
+        assert data["backends"] == ["litesvm", "test-validator"]
+        assert data["hypotheses"][0]["title"] == "Escrow drains"
+        assert data["hypotheses"][1]["runs"]["test-validator"]["status"] == "error"
+        assert "Escrow drains" in runner.invoke(matrix, ["escrow"]).output
+
+        assert runner.invoke(run, ["escrow"]).exit_code == 1
+        assert runner.invoke(run, ["escrow", "hyp_1", "--all"]).exit_code == 1
+        assert "No imported PoC" in runner.invoke(run, ["escrow", "hyp_9"]).output
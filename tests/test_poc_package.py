@@ -0,0 +1,186 @@
+"""
+Tests for standalone PoC packages: workspace copy, lockfiles, account
+snapshots, run script, README and the CLI.
+"""
+
+import base64
+import json
+import shutil
+from pathlib import Path
+
+import pytest
+from click.testing import CliRunner
+
+from commands.pocs import package
+from extensions.ir.anchor_config import b58encode
+from extensions.poc import PackageError, package_poc
+from extensions.poc.package import detect_backend, poc_command, poc_destination
+from extensions.simulation.backend import load_account_dump
+
+WORKSPACE = Path(__file__).resolve().parent / "fixtures" / "solana" / "anchor_config"
+
+USDC = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
+JUPITER = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4"
+PYTH_SOL_USD = "H6ARHf6YXhGYeQfUzQNGk6rDNnLBQKrenN712K4AQJEG"
+TOKEN = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+LOADER = "BPFLoaderUpgradeab1e11111111111111111111111"
+PROGRAMDATA = b58encode(bytes(range(1, 33)))
+ELF = b"\x7fELF" + b"\x01" * 60
+
+
+def _info(data: bytes, owner: str, executable: bool = False) -> dict:
+    return {"lamports": 1_000_000, "owner": owner, "executable": executable, "rentEpoch": 0,
+            "data": [base64.b64encode(data).decode(), "base64"], "space": len(data)}
+
+
+CHAIN = {
+    USDC: _info(b"\x01" * 82, TOKEN),
+    PYTH_SOL_USD: _info(b"\x02" * 16, TOKEN),
+    JUPITER: _info((2).to_bytes(4, "little") + bytes(range(1, 33)), LOADER, executable=True),
+    PROGRAMDATA: _info((3).to_bytes(4, "little") + b"\0" * 41 + ELF, LOADER),
+}
+
+
+def fetch(addresses):
+    return [CHAIN.get(a) for a in addresses]
+
+
+@pytest.fixture
+def workspace(tmp_path):
+    source = tmp_path / "workspace"
+    shutil.copytree(WORKSPACE, source)
+    (source / "package.json").write_text('{"name": "escrow"}\n')
+    (source / "Cargo.toml").write_text('[workspace]\nmembers = ["programs/*"]\n')
+    (source / "Cargo.lock").write_text("version = 3\n")
+    (source / "README.md").write_text("# Escrow\n")
+    (source / "target" / "deploy").mkdir(parents=True)
+    (source / "target" / "deploy" / "escrow.so").write_bytes(b"stale build")
+    (source / "node_modules" / "x").mkdir(parents=True)
+    poc = tmp_path / "exploit.ts"
+    poc.write_text(f'const USDC = new PublicKey("{USDC}");\nconst JUP = new PublicKey("{JUPITER}");\n')
+    return source, poc
+
+
+class TestPackage:
+    """Test what goes into a package."""
+
+    def test_solana_validator_package(self, workspace, tmp_path):
+        source, poc = workspace
+        out = tmp_path / "pkg"
+        result = package_poc(source, {"tests/exploit.ts": poc}, out, "hyp_1",
+                             hypothesis={"title": "Escrow drains", "severity": "high", "description": "Anyone."},
+                             fetch=fetch, pin=False, manifest={"format": "baskerville-manifest"})
+        assert result.backend == "validator"
+        assert (out / "tests" / "exploit.ts").exists() and (out / "programs" / "escrow" / "Cargo.toml").exists()
+        assert not (out / "target").exists() and not (out / "node_modules").exists()
+        assert result.lockfiles == ["Cargo.lock"]
+        assert any("package.json" in w and "not pinned" in w for w in result.warnings)
+
+        # Cloned state is snapshotted, workspace programs are rebuilt
+        assert load_account_dump(out / result.accounts[USDC])["data"] == b"\x01" * 82
+        assert PYTH_SOL_USD in result.accounts  # Anchor.toml clone
+        assert result.accounts["Cfg1111111111111111111111111111111111111111"] == "accounts/config.json"
+        assert (out / result.programs[JUPITER]).read_bytes() == ELF
+        assert result.built_programs["Escr111111111111111111111111111111111111111"] == "target/deploy/escrow.so"
+        assert any("deps/mpl_token_metadata.so" in w for w in result.warnings)
+
+        script = (out / "run.sh").read_text()
+        assert "npm install\nanchor build\n" in script
+        assert f"--account {USDC} fixtures/accounts/{USDC}.json" in script
+        assert f"--bpf-program {JUPITER} fixtures/programs/{JUPITER}.so" in script
+        assert "--bpf-program Escr111111111111111111111111111111111111111 target/deploy/escrow.so" in script
+        assert script.rstrip().endswith("npx ts-mocha -t 1000000 tests/exploit.ts")
+        assert (out / "run.sh").stat().st_mode & 0o111
+
+        readme = (out / "README.md").read_text()
+        assert readme.startswith("# PoC: Escrow drains")
+        assert "./run.sh" in readme and "Anchor 0.30.1" in readme and "(high)" in readme
+        assert (out / "README.project.md").read_text() == "# Escrow\n"
+        meta = json.loads((out / "poc.json").read_text())
+        assert meta["hypothesis_id"] == "hyp_1" and meta["manifest"] == {"format": "baskerville-manifest"}
+        fixtures = json.loads((out / "fixtures" / "manifest.json").read_text())
+        assert fixtures["programs"][JUPITER] == f"fixtures/programs/{JUPITER}.so"
+
+    def test_refuses_unsafe_output(self, workspace, tmp_path):
+        source, poc = workspace
+        with pytest.raises(PackageError, match="inside the workspace"):
+            package_poc(source, {"poc/exploit.ts": poc}, source / "pkg", "hyp_1", fetch=fetch, pin=False)
+        (tmp_path / "taken").mkdir()
+        (tmp_path / "taken" / "file").write_text("x")
+        with pytest.raises(PackageError, match="already exists"):
+            package_poc(source, {"poc/exploit.ts": poc}, tmp_path / "taken", "hyp_1", fetch=fetch, pin=False)
+        result = package_poc(source, {"poc/exploit.ts": poc}, tmp_path / "taken", "hyp_1", backend="litesvm",
+                             fetch=fetch, pin=False, force=True)
+        assert not (tmp_path / "taken" / "file").exists()
+        assert 'export POC_FIXTURES="$PWD/fixtures"' in (tmp_path / "taken" / "run.sh").read_text()
+        assert "solana-test-validator" not in (tmp_path / "taken" / "run.sh").read_text()
+        assert result.backend == "litesvm"
+
+    def test_pin_generates_missing_lockfiles(self, tmp_path, monkeypatch):
+        from extensions.poc.package import pin_dependencies
+
+        monkeypatch.setattr(shutil, "which", lambda tool: f"/usr/bin/{tool}")
+        (tmp_path / "package.json").write_text("{}")
+        (tmp_path / "Move.toml").write_text("[package]\n")
+        calls = []
+
+        def run(cmd, cwd):
+            calls.append(cmd[0])
+            (cwd / "package-lock.json").write_text("{}")
+            return True
+
+        locks, warnings = pin_dependencies(tmp_path, run=run)
+        assert calls == ["npm"] and locks == ["package-lock.json"]
+        assert warnings[-1] == "no lockfile for Move.toml; dependency versions are not pinned"
+
+    def test_layout_and_commands(self, tmp_path):
+        assert poc_destination(tmp_path / "ws" / "test" / "Exploit.t.sol", tmp_path / "ws") == "test/Exploit.t.sol"
+        assert poc_destination(tmp_path / "elsewhere" / "x.ts", tmp_path / "ws") == "poc/x.ts"
+        (tmp_path / "move").mkdir()
+        (tmp_path / "move" / "Move.toml").write_text("")
+        (tmp_path / "crate" / "tests").mkdir(parents=True)
+        (tmp_path / "crate" / "Cargo.toml").write_text("")
+        assert poc_command(tmp_path, ["test/Exploit.t.sol"]) == "forge test --match-path test/Exploit.t.sol -vvv"
+        assert poc_command(tmp_path, ["move/tests/exploit.move"]) == "sui move test --path move"
+        assert poc_command(tmp_path, ["crate/tests/exploit.rs"]) == \
+            "cargo test --manifest-path crate/Cargo.toml --test exploit -- --nocapture"
+        with pytest.raises(PackageError, match="cannot tell how to run"):
+            poc_command(tmp_path, ["notes.txt"])
+        assert detect_backend(WORKSPACE) == "validator"
+        assert detect_backend(tmp_path / "move") == "native"
+
+
+class TestPackageCli:
+    """Test the poc package command."""
+
+    def test_foundry_package(self, tmp_path, monkeypatch):
+        source = tmp_path / "vault"
+        (source / "test").mkdir(parents=True)
+        (source / "foundry.toml").write_text("[profile.default]\n")
+        project_dir = tmp_path / "project"
+        poc_dir = project_dir / "poc" / "hyp_2"
+        poc_dir.mkdir(parents=True)
+        (poc_dir / "Exploit.t.sol").write_text("contract ExploitTest {}\n")
+        (poc_dir / "metadata.json").write_text(json.dumps({"files": [
+            {"name": "Exploit.t.sol", "original_path": str(source / "test" / "Exploit.t.sol")}]}))
+        (project_dir / "hypotheses.json").write_text(json.dumps({"hypotheses": {
+            "hyp_2": {"title": "Share inflation", "severity": "medium"}}}))
+
+        class FakeProjects:
+            def get_project(self, name):
+                return {"path": str(project_dir), "source_path": str(source)} if name == "vault" else None
+
+        monkeypatch.setattr("commands.project.ProjectManager", FakeProjects)
+        monkeypatch.setattr("utils.config_loader.load_config", lambda: {})
+        runner = CliRunner()
+        result = runner.invoke(package, ["vault", "hyp_2", "--archive"])
+        assert result.exit_code == 0, result.output
+        out = project_dir / "poc_packages" / "hyp_2"
+        assert (out / "test" / "Exploit.t.sol").exists()
+        assert "forge test --match-path test/Exploit.t.sol -vvv" in (out / "run.sh").read_text()
+        assert "Foundry (`forge`)" in (out / "README.md").read_text()
+        assert (project_dir / "poc_packages" / "hyp_2.tar.gz").exists()
+        assert json.loads((out / "poc.json").read_text())["manifest"]["command"]["name"] == "poc package"
+
+        assert "already exists" in runner.invoke(package, ["vault", "hyp_2"]).output
+        assert "not found" in runner.invoke(package, ["vault", "hyp_9"]).output
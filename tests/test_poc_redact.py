@@ -0,0 +1,149 @@
+"""
+Tests for disclosure-safe PoC copies: address placeholders across text,
+fixtures and binaries, amount removal and the CLI.
+"""
+
+import base64
+import json
+import shutil
+from pathlib import Path
+
+import pytest
+from click.testing import CliRunner
+
+from commands.pocs import redact
+from extensions.ir.anchor_config import b58encode
+from extensions.monitor.decode import b58decode
+from extensions.poc import PackageError, package_poc, redact_poc
+from extensions.poc.redact import REDACTED_AMOUNT, redact_amounts, redact_code_amounts
+from extensions.simulation.backend import load_account_dump
+
+WORKSPACE = Path(__file__).resolve().parent / "fixtures" / "solana" / "anchor_config"
+
+USDC = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
+JUPITER = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4"
+ESCROW = "Escr111111111111111111111111111111111111111"
+TOKEN = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+LOADER = "BPFLoaderUpgradeab1e11111111111111111111111"
+PROGRAMDATA = b58encode(bytes(range(1, 33)))
+AUTHORITY = b58encode(bytes(range(100, 132)))
+# The cloned program embeds the USDC mint, as a hardcoded `pubkey!` would
+ELF = b"\x7fELF" + b58decode(USDC) + b"\x01" * 28
+
+
+def _info(data: bytes, owner: str, executable: bool = False) -> dict:
+    return {"lamports": 1_000_000, "owner": owner, "executable": executable, "rentEpoch": 0,
+            "data": [base64.b64encode(data).decode(), "base64"], "space": len(data)}
+
+
+# Mint with a mint authority set
+MINT = (1).to_bytes(4, "little") + b58decode(AUTHORITY) + b"\0" * 46
+CHAIN = {
+    USDC: _info(MINT, TOKEN),
+    JUPITER: _info((2).to_bytes(4, "little") + bytes(range(1, 33)), LOADER, executable=True),
+    PROGRAMDATA: _info((3).to_bytes(4, "little") + b"\0" * 41 + ELF, LOADER),
+}
+
+EXPLOIT = f"""// Drains $4.2M from the escrow (1,250,000 USDC at today's price)
+const USDC = new PublicKey("{USDC}");
+const JUP = new PublicKey("{JUPITER}");
+const AMOUNT = 1_250_000_000_000;
+console.log("stolen 1250000 tokens");
+"""
+
+
+@pytest.fixture
+def package(tmp_path):
+    source = tmp_path / "workspace"
+    shutil.copytree(WORKSPACE, source)
+    poc = tmp_path / "exploit.ts"
+    poc.write_text(EXPLOIT)
+    out = tmp_path / "pkg"
+    package_poc(source, {"tests/exploit.ts": poc}, out, "hyp_1",
+                hypothesis={"title": "Escrow drains $4.2M", "description": "Drained 1,250,000 USDC."},
+                url="https://mainnet.helius-rpc.com", fetch=lambda addrs: [CHAIN.get(a) for a in addrs], pin=False)
+    return out
+
+
+class TestAmounts:
+    """Test which numbers count as drain amounts."""
+
+    def test_prose(self):
+        text, count = redact_amounts("Attacker drained 1,250,000 and took $4.2M (900 SOL) over 3 blocks.")
+        assert text == (f"Attacker drained {REDACTED_AMOUNT} and took {REDACTED_AMOUNT} "
+                        f"({REDACTED_AMOUNT}) over 3 blocks.")
+        assert count == 3
+
+    def test_code_numbers_kept(self):
+        text, count = redact_code_amounts(EXPLOIT, ".ts")
+        assert "const AMOUNT = 1_250_000_000_000;" in text
+        assert f"// Drains {REDACTED_AMOUNT} from the escrow ({REDACTED_AMOUNT} at today's price)" in text
+        assert f'console.log("stolen {REDACTED_AMOUNT}");' in text
+        assert count == 3
+        rust, _ = redact_code_amounts("fn f<'info>(x: u64) { let y = 5_000; } // 5 SOL lost\n", ".rs")
+        assert rust == f"fn f<'info>(x: u64) {{ let y = 5_000; }} // {REDACTED_AMOUNT} lost\n"
+
+
+class TestRedact:
+    """Test the redacted copy."""
+
+    def test_package_redaction(self, package, tmp_path):
+        out = tmp_path / "disclosure"
+        result = redact_poc(package, out, salt=b"fixed")
+        mapping = result.mapping
+        assert {USDC, JUPITER, ESCROW, AUTHORITY} <= set(mapping)
+        assert TOKEN in result.kept and TOKEN not in mapping
+        assert result.warnings == [], result.warnings
+        assert redact_poc(package, tmp_path / "again", salt=b"fixed").mapping == mapping
+        assert redact_poc(package, tmp_path / "other").mapping[USDC] != mapping[USDC]
+
+        # No live address is left anywhere, in text or as raw bytes
+        for f in out.rglob("*"):
+            if f.is_file():
+                data = f.read_bytes()
+                for address in mapping:
+                    assert address.encode() not in data and b58decode(address) not in data, (f, address)
+
+        # Sources, run script and fixtures agree on the placeholders
+        usdc, jupiter, escrow = mapping[USDC], mapping[JUPITER], mapping[ESCROW]
+        exploit = (out / "tests" / "exploit.ts").read_text()
+        assert f'new PublicKey("{usdc}")' in exploit and "1_250_000_000_000" in exploit
+        assert "$4.2M" not in exploit and "1,250,000" not in exploit
+        script = (out / "run.sh").read_text()
+        assert f"--account {usdc} fixtures/accounts/{usdc}.json" in script
+        assert f"--bpf-program {jupiter} fixtures/programs/{jupiter}.so" in script
+        assert f"--bpf-program {escrow} target/deploy/escrow.so" in script
+        assert f'escrow = "{escrow}"' in (out / "Anchor.toml").read_text()
+        dump = load_account_dump(out / "fixtures" / "accounts" / f"{usdc}.json")
+        assert dump["data"][4:36] == b58decode(mapping[AUTHORITY])
+        assert (out / "fixtures" / "programs" / f"{jupiter}.so").read_bytes()[4:36] == b58decode(usdc)
+        assert not (out / "fixtures" / "accounts" / f"{USDC}.json").exists()
+
+        meta = json.loads((out / "poc.json").read_text())
+        assert meta["redacted"] and meta["snapshot_url"] is None and meta["title"] == f"Escrow drains {REDACTED_AMOUNT}"
+        readme = (out / "README.md").read_text()
+        assert "helius" not in readme and "1,250,000" not in readme and "## Redaction" in readme
+        # The original is untouched
+        assert USDC in (package / "run.sh").read_text()
+
+    def test_keep_and_keypairs(self, package, tmp_path):
+        secret = list(range(32)) + list(b58decode(ESCROW).rjust(32, b"\0"))
+        (package / "escrow-keypair.json").write_text(json.dumps(secret))
+        result = redact_poc(package, tmp_path / "out", keep=[JUPITER])
+        assert ESCROW not in result.mapping and JUPITER not in result.mapping
+        assert f"--bpf-program {JUPITER} fixtures/programs/{JUPITER}.so" in (tmp_path / "out" / "run.sh").read_text()
+        with pytest.raises(PackageError, match="not a public key"):
+            redact_poc(package, tmp_path / "bad", keep=["nope"])
+        with pytest.raises(PackageError, match="already exists"):
+            redact_poc(package, tmp_path / "out")
+        with pytest.raises(PackageError, match="inside the PoC"):
+            redact_poc(package, package / "redacted")
+
+    def test_cli(self, package, tmp_path):
+        result = CliRunner().invoke(redact, [str(package)])
+        assert result.exit_code == 0, result.output
+        out = tmp_path / "pkg-redacted"
+        assert (out / "run.sh").exists()
+        mapping = json.loads((tmp_path / "pkg-redacted.redaction-map.json").read_text())
+        assert mapping["format"] == "baskerville-redaction-map" and USDC in mapping["addresses"]
+        assert not list(out.rglob("*.redaction-map.json"))
@@ -0,0 +1,140 @@
+"""
+Tests for multi-step exploit scenarios: parsing, checks against the
+workspace IR and the compiled Anchor test.
+"""
+
+import sys
+import types
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+import pytest
+from click.testing import CliRunner
+
+from commands.poc import PoCContext, generate_poc_with_strategist
+from commands.pocs import scenario
+from extensions.ir import load_programs
+from extensions.poc import ScenarioError, compile_scenario, load_scenario
+from extensions.poc.scenario import parse_scenario
+
+FIXTURES = Path(__file__).resolve().parent / "fixtures"
+VAULT = FIXTURES / "solana" / "anchor_vault"
+DRAIN = FIXTURES / "scenarios" / "vault_drain.yaml"
+
+
+@pytest.fixture(scope="module")
+def programs():
+    return load_programs(VAULT)
+
+
+def _scenario(steps, **extra):
+    return parse_scenario({"name": "s", "program": "vault", "actors": {"attacker": {}},
+                           "accounts": {"vault": {"pda": ["vault", {"key": "attacker"}], "program": "vault"}},
+                           "steps": steps, **extra})
+
+
+class TestParse:
+    """Test the scenario format."""
+
+    def test_load(self):
+        drain = load_scenario(DRAIN)
+        assert [s.name for s in drain.steps] == [
+            "victim opens the vault", "attacker withdraws as a stranger", "attacker tops up, then withdraws"]
+        assert drain.actors["victim"].lamports == 5_000_000_000
+        assert drain.accounts["vault"].pda == ["vault", {"key": "victim"}]
+        assert [c.transfer is not None for c in drain.steps[2].calls] == [True, False]
+        assert drain.steps[1].fails == "overflow"
+        assert [(c.account, c.kind, c.value) for c in drain.deltas] == [
+            ("vault", "lamports", 1000000), ("attacker", "lamports", "decrease")]
+
+    def test_malformed(self):
+        with pytest.raises(ScenarioError, match="needs at least one step"):
+            parse_scenario({"name": "s"})
+        with pytest.raises(ScenarioError, match=r"steps\[0\]\.instructions\[0\]: needs an instruction"):
+            parse_scenario({"name": "s", "steps": [{"instructions": [{"args": {}}]}]})
+        with pytest.raises(ScenarioError, match="exactly one of exists, lamports, token, field"):
+            _scenario([{"instruction": "withdraw"}], deltas=[{"account": "vault", "exists": True, "lamports": 1}])
+        with pytest.raises(ScenarioError, match="both an actor and an account"):
+            parse_scenario({"name": "s", "actors": {"x": {}}, "accounts": {"x": {"keypair": True}},
+                            "steps": [{"instruction": "i"}]})
+
+
+class TestCompile:
+    """Test the compiled test and the IR checks."""
+
+    def test_vault_drain(self, programs):
+        harness = compile_scenario(load_scenario(DRAIN), programs, anchor_version="0.30.1")
+        source = harness.source
+        assert harness.programs == ["vault"] and harness.transactions == 3 and harness.warnings == []
+        assert "const vaultProgram = anchor.workspace.Vault as Program;" in source
+        assert ('[vault, vaultBump] = PublicKey.findProgramAddressSync([Buffer.from("vault"), '
+                'victim.publicKey.toBuffer()], vaultProgram.programId);') in source
+        assert ("await vaultProgram.methods.initialize(vaultBump)"
+                ".accountsPartial({ vault: vault, authority: victim.publicKey }).instruction()") in source
+        assert "payoutProgram: new PublicKey(\"11111111111111111111111111111111\")" in source
+        assert 'await expectFailure(send(connection, instructions, [attacker]), "overflow");' in source
+        assert "vaultProgram.account.vault.fetch(vault)).authority), String(victim.publicKey)" in source
+        assert "SystemProgram.transfer({ fromPubkey: attacker.publicKey, toPubkey: vault, lamports: 1000000 })" \
+            in source
+        assert 'assert.equal(await lamports(connection, vault) - prior["lamports:vault"], 1000000n' in source
+        assert 'from "./attacker";' in source and "async function send(" not in source
+        # Steps run in the order given
+        assert source.index('it("1. victim opens') < source.index('it("2. attacker') < source.index('it("3. attacker')
+        assert compile_scenario(load_scenario(DRAIN), programs).source.count(".accounts({") == 3
+
+    def test_ir_mismatches(self, programs):
+        with pytest.raises(ScenarioError, match="vault has no instruction 'drain'"):
+            compile_scenario(_scenario([{"instruction": "drain"}]), programs)
+        with pytest.raises(ScenarioError, match="withdraw has no account 'owner'"):
+            compile_scenario(_scenario([{"instruction": "withdraw", "accounts": {"owner": "attacker"},
+                                         "args": {"amount": 1}}]), programs)
+        with pytest.raises(ScenarioError, match="withdraw needs argument 'amount'"):
+            compile_scenario(_scenario([{"instruction": "withdraw"}]), programs)
+        with pytest.raises(ScenarioError, match="'vault' cannot sign"):
+            compile_scenario(_scenario([{"instruction": "initialize", "accounts": {"authority": "vault"},
+                                         "args": {"bump": 1}}]), programs)
+        with pytest.raises(ScenarioError, match="unknown actor, account or program 'nobody'"):
+            compile_scenario(_scenario([{"instruction": "withdraw", "accounts": {"recipient": "nobody"},
+                                         "args": {"amount": 1}}]), programs)
+        with pytest.raises(ScenarioError, match="Vault has no field 'owner'"):
+            compile_scenario(_scenario(
+                [{"instruction": "initialize", "accounts": {"vault": "vault", "authority": "attacker"},
+                  "args": {"bump": {"bump": "vault"}}}],
+                deltas=[{"account": "vault", "field": "owner", "equals": "attacker"}]), programs)
+
+    def test_missing_accounts_and_signers(self, programs):
+        harness = compile_scenario(_scenario([{"instruction": "deposit", "accounts": {"user": "attacker"},
+                                               "args": {"amount": "1_000"}}]), programs)
+        # vault has seeds and token_program is a program; the token accounts cannot be derived
+        assert harness.warnings == ["steps[0].instructions[0]: deposit account 'user_token' is not given",
+                                    "steps[0].instructions[0]: deposit account 'vault_token' is not given"]
+        # `user` signs, so the attacker joins the signers without being listed
+        assert "await send(connection, instructions, [attacker]);" in harness.source
+        assert 'deposit(new anchor.BN("1000"))' in harness.source
+
+    def test_prompt_context(self, programs):
+        client = MagicMock()
+        client.return_value.raw.return_value = "prompt"
+        harness = compile_scenario(load_scenario(DRAIN), programs)
+        context = PoCContext("p", {"title": "Anyone can withdraw"}, {}, {}, scenario_harness=harness)
+        with patch.dict(sys.modules, {"llm.unified_client": types.SimpleNamespace(UnifiedLLMClient=client)}):
+            generate_poc_with_strategist(context, {})
+        prompt = client.return_value.raw.call_args.kwargs["user"]
+        assert "MULTI-STEP SCENARIO" in prompt and "`vault-drain`: 3 transactions" in prompt
+
+
+class TestScenarioCli:
+    """Test the poc scenario command."""
+
+    def test_compile(self, tmp_path):
+        out = tmp_path / "drain.ts"
+        result = CliRunner().invoke(scenario, [str(DRAIN), str(VAULT), "-o", str(out)])
+        assert result.exit_code == 0, result.output
+        assert "3 transaction(s)" in result.output
+        assert out.read_text().startswith("// Scenario `vault-drain`")
+        # The helpers the compiled test imports are written beside it
+        assert (tmp_path / "attacker.ts").exists()
+        bad = tmp_path / "bad.yaml"
+        bad.write_text("name: bad\nsteps:\n  - instruction: nope\n")
+        result = CliRunner().invoke(scenario, [str(bad), str(VAULT)])
+        assert result.exit_code == 1 and "no instruction 'nope'" in result.output
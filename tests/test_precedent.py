@@ -0,0 +1,187 @@
+"""
+Tests for published-audit precedent: report importers, the catalog, matching and severity calibration.
+"""
+
+import json
+from types import SimpleNamespace
+
+import pytest
+import yaml
+from click.testing import CliRunner
+
+from commands.precedent import import_report, list_reports, match
+from extensions.precedent import (
+    AuditReport,
+    PrecedentCatalog,
+    PrecedentMatcher,
+    load_report_file,
+    parse_markdown_report,
+    reports_from_solodit,
+)
+
+LENDING_REPORT = {
+    "firm": "OtterSec",
+    "protocol": "Lendy",
+    "chain": "solana",
+    "date": "2024-03-01",
+    "url": "https://example.com/lendy.pdf",
+    "findings": [
+        {"id": "H-01", "title": "Missing signer check on withdraw authority", "severity": "High",
+         "detectors": ["missing-signer"]},
+        {"id": "M-03", "title": "Stale oracle price accepted during liquidation", "severity": "M"},
+        {"id": "L-02", "title": "Rounding down of interest accrual", "severity": "low",
+         "classes": ["arithmetic"]},
+    ],
+}
+
+MARKDOWN = """# Vaulty Security Review
+
+## Table of contents
+- [H-01] Reentrancy in withdraw lets attacker drain vault
+
+## Findings
+
+### [H-01] Reentrancy in withdraw lets attacker drain vault
+
+The `withdraw` function transfers before updating balances.
+
+### [M-02]: Oracle price can be manipulated with a flash loan
+
+Spot price is read from the pool.
+
+## Low Risk
+
+#### L-1 Missing zero address check
+Constructor accepts zero.
+"""
+
+
+class TestImporters:
+    """Test report metadata, Markdown and Solodit importers."""
+
+    def test_metadata(self, tmp_path):
+        path = tmp_path / "lendy.yaml"
+        path.write_text(yaml.safe_dump(LENDING_REPORT))
+        report = load_report_file(path)
+        assert [f.severity for f in report.findings] == ["high", "medium", "low"]
+        assert report.findings[0].classes == ["access-control"]
+        assert report.findings[1].classes == ["oracle", "economic"]
+        assert report.findings[2].classes == ["arithmetic"]
+        assert report.key == "ottersec--lendy--2024-03-01"
+        assert AuditReport.from_dict(report.to_dict()) == report
+        with pytest.raises(ValueError, match="protocol"):
+            AuditReport.from_dict({"firm": "X", "findings": []})
+        with pytest.raises(ValueError, match="severity"):
+            AuditReport.from_dict({"firm": "X", "protocol": "Y", "findings": [{"id": "1", "title": "t"}]})
+
+    def test_markdown(self, tmp_path):
+        report = parse_markdown_report(MARKDOWN, firm="Pashov", protocol="Vaulty", chain="evm")
+        assert [(f.id, f.severity) for f in report.findings] == [("H-01", "high"), ("M-02", "medium"),
+                                                                 ("L-01", "low")]
+        assert report.findings[0].title == "Reentrancy in withdraw lets attacker drain vault"
+        assert "transfers before updating" in report.findings[0].description
+        assert "reentrancy" in report.findings[0].classes
+        path = tmp_path / "vaulty.md"
+        path.write_text(MARKDOWN)
+        with pytest.raises(ValueError, match="firm"):
+            load_report_file(path)
+        with pytest.raises(ValueError, match="no findings"):
+            parse_markdown_report("# Nothing here", firm="a", protocol="b")
+
+    def test_solodit(self):
+        hit = dict(content="Attacker can reenter.", quality_score=4, rarity_score=1, report_date="2023-11-02T00:00",
+                   source_link=None, finders=[], raw_data={}, url="https://solodit.example/x")
+        findings = [
+            SimpleNamespace(title="Reentrancy in claim", impact="HIGH", firm_name="Zellic", protocol_name="Farm",
+                            tags=["Reentrancy"], **hit),
+            SimpleNamespace(title="Unchecked return value", impact="MEDIUM", firm_name="Zellic",
+                            protocol_name="Farm", tags=[], **hit),
+            SimpleNamespace(title="Gas griefing", impact="GAS", firm_name="Spearbit", protocol_name="Pool",
+                            tags=["DOS"], **hit),
+        ]
+        reports = reports_from_solodit(findings, chain="evm")
+        assert [(r.firm, len(r.findings)) for r in reports] == [("Zellic", 2), ("Spearbit", 1)]
+        assert [f.id for f in reports[0].findings] == ["H-01", "M-01"]
+        assert reports[0].date == "2023-11-02"
+        assert reports[1].findings[0].severity == "info"
+
+
+class TestMatching:
+    """Test scoring, cross-chain weighting and severity calibration."""
+
+    def make_matcher(self, tmp_path, **kwargs):
+        catalog = PrecedentCatalog(tmp_path / "precedents")
+        catalog.add(AuditReport.from_dict(LENDING_REPORT))
+        catalog.add(parse_markdown_report(MARKDOWN, firm="Pashov", protocol="Vaulty", chain="evm"))
+        return PrecedentMatcher(catalog, **kwargs)
+
+    def test_match_and_summary(self, tmp_path):
+        matcher = self.make_matcher(tmp_path)
+        hyp = {"title": "Withdraw authority is not required to sign", "vulnerability_type": "missing_signer",
+               "severity": "critical"}
+        (best, *_) = matcher.match(hyp, chain="solana")
+        assert best.summary == "matches finding H-01 from OtterSec's audit of Lendy (high)"
+        assert any(r.startswith("class access-control") for r in best.reasons)
+        calibration = matcher.calibrate(matcher.match(hyp, chain="solana"))
+        assert calibration.suggested == "high" and calibration.differs_from("critical")
+
+        # A detector hit naming a known detector matches on that alone
+        hit = {"title": "x", "vulnerability_type": "missing-signer", "taxonomy": ["logic"]}
+        (m,) = matcher.match(hit, limit=1)
+        assert m.finding.id == "H-01" and "detector missing-signer" in m.reasons
+
+        assert matcher.match({"title": "Unused variable", "vulnerability_type": "naming"}) == []
+
+    def test_cross_chain(self, tmp_path):
+        matcher = self.make_matcher(tmp_path, min_score=0.0)
+        hyp = {"title": "Oracle price manipulation via flash loan", "vulnerability_type": "oracle"}
+        on_evm = {m.finding.id: m.score for m in matcher.match(hyp, chain="evm", limit=5)}
+        on_solana = {m.finding.id: m.score for m in matcher.match(hyp, chain="solana", limit=5)}
+        assert on_evm["M-02"] > on_solana["M-02"]
+        assert on_solana["M-03"] > on_evm["M-03"]
+        assert PrecedentMatcher.calibrate([]).describe() == "no precedent"
+
+
+class TestPrecedentCli:
+    """Test import, list and match commands against a project."""
+
+    def test_cli(self, tmp_path, monkeypatch):
+        monkeypatch.setenv("HOME", str(tmp_path))
+        project_dir = tmp_path / "proj"
+        project_dir.mkdir()
+        (project_dir / "hypotheses.json").write_text(json.dumps({"version": "1.0", "hypotheses": {
+            "h1": {"title": "Stale oracle price used in liquidation", "vulnerability_type": "oracle",
+                   "severity": "low"},
+            "h2": {"title": "Event emitted twice", "vulnerability_type": "events", "severity": "info"},
+        }}))
+
+        class FakeProjects:
+            def get_project(self, name):
+                return {"path": str(project_dir), "chain_id": "solana"} if name == "proj" else None
+
+        monkeypatch.setattr("commands.precedent.ProjectManager", FakeProjects)
+        runner = CliRunner()
+        report = tmp_path / "lendy.json"
+        report.write_text(json.dumps(LENDING_REPORT))
+        result = runner.invoke(import_report, [str(report)])
+        assert result.exit_code == 0, result.output
+        assert "Imported 3 findings" in result.output
+        assert "ottersec--lendy" in runner.invoke(list_reports, []).output
+        md = tmp_path / "vaulty.md"
+        md.write_text(MARKDOWN)
+        assert runner.invoke(import_report, [str(md)]).exit_code == 1
+        assert runner.invoke(import_report, [str(md), "--firm", "Pashov", "--protocol", "Vaulty"]).exit_code == 0
+
+        result = runner.invoke(match, ["proj"])
+        assert result.exit_code == 0, result.output
+        assert "matches finding M-03 from OtterSec's audit of Lendy" in result.output
+        assert "1/2 hypotheses have precedent" in result.output
+
+        result = runner.invoke(match, ["proj", "--json", "--annotate"])
+        assert result.exit_code == 0, result.output
+        data = json.loads(result.output)
+        assert data["h1"]["suggested_severity"] == "medium" and data["h2"]["matches"] == []
+        stored = json.loads((project_dir / "hypotheses.json").read_text())["hypotheses"]["h1"]["properties"]
+        assert stored["precedent_severity"] == "medium"
+        assert stored["precedents"][0]["finding_id"] == "M-03"
+        assert runner.invoke(match, ["missing"]).exit_code == 1
@@ -0,0 +1,84 @@
+"""
+Tests for the privilege graph and its canned escalation-path queries.
+"""
+
+import json
+from pathlib import Path
+
+from click.testing import CliRunner
+
+from commands.ir import privilege_paths
+from extensions.ir import load_programs
+from extensions.static.pipeline import StaticAnalysisPipeline
+from extensions.static.privilege_paths import PrivilegePathAnalyzer, build_privilege_graph, find_paths, run_query
+
+WORKSPACE = Path(__file__).parent / "fixtures" / "solana" / "treasury_workspace"
+
+
+class TestPrivilegePaths:
+    """Test graph construction, path search and multi-step findings."""
+
+    def setup_method(self):
+        self.graph = build_privilege_graph(load_programs(WORKSPACE))
+
+    def test_graph(self):
+        withdraw = self.graph.nodes["instruction:treasury.withdraw"].properties
+        assert withdraw["authenticated"] and withdraw["authorities"] == ["authority"]
+        # A payer alone does not authenticate
+        assert not self.graph.nodes["instruction:treasury.initialize"].properties["authenticated"]
+        gates = self.graph.in_edges("instruction:treasury.withdraw", "gates")
+        assert [(e.source_id, e.properties["field"]) for e in gates] == [("state:treasury.Treasury", "authority")]
+        cpi = self.graph.out_edges("instruction:treasury.crank", "cpi")[0]
+        assert cpi.target_id == "instruction:keeper.release"
+        assert cpi.properties["pda_signers"] == ["treasury_signer"]
+        data = self.graph.to_dict()
+        assert data["name"] == "PrivilegeGraph"
+        assert {"cpi", "gates", "lamport_transfer", "writes_field"} <= set(data["stats"]["edge_types"])
+
+    def test_lamport_drain(self):
+        paths = {p.entry: p for p in run_query(self.graph, "lamport-drain")}
+        assert set(paths) == {"instruction:treasury.sweep", "instruction:treasury.crank"}
+        # The authenticated withdraw is not an entry point
+        crank = paths["instruction:treasury.crank"]
+        assert [h.edge for h in crank.hops] == ["entry", "cpi", "uses", "lamport_transfer"]
+        assert "satisfies its authority `treasury_signer`" in crank.hops[1].note
+        assert (crank.hops[3].program, crank.hops[3].line) == ("keeper", 13)
+
+    def test_authority_takeover(self):
+        (path,) = run_query(self.graph, "authority-takeover")
+        assert path.entry == "instruction:treasury.set_authority"
+        assert path.hops[1].note == "writes `treasury.authority` (Treasury)"
+        assert path.hops[2].target == "instruction:treasury.withdraw"
+        # initialize writes the same field, but of an account it creates
+        assert not any(p.entry.endswith(".initialize") for p in run_query(self.graph, "authority-takeover"))
+
+    def test_pda_transfer(self):
+        (path,) = run_query(self.graph, "pda-transfer")
+        assert path.entry == "instruction:treasury.pay_out"
+        assert "signed by PDA treasury_signer" in path.hops[1].note
+
+    def test_find_paths(self):
+        paths = find_paths(
+            self.graph,
+            "instruction:treasury.crank",
+            lambda path, edge: edge.type == "cpi",
+            lambda path, edge: edge.type == "uses" and edge.properties["writable"],
+        )
+        assert {p[-1].target_id for p in paths} >= {"account:keeper.release.pot", "account:treasury.crank.pot"}
+
+    def test_findings(self):
+        findings, metadata = PrivilegePathAnalyzer().run(WORKSPACE)
+        assert metadata["paths"] == {"lamport-drain": 2, "authority-takeover": 1, "pda-transfer": 1}
+        hyp = next(f for f in findings if f.path.entry.endswith(".crank")).to_hypothesis()
+        assert hyp["vulnerability_type"] == "privilege-path-lamport-drain"
+        assert hyp["properties"]["source_files"] == ["programs/treasury/src/lib.rs", "programs/keeper/src/lib.rs"]
+        assert [s["step"] for s in hyp["properties"]["steps"]] == [1, 2, 3, 4]
+        assert "4. moves lamports out of `pot`" in hyp["description"]
+        assert len(PrivilegePathAnalyzer(queries=["pda-transfer"]).run(WORKSPACE)[0]) == 1
+        assert "privilege-paths" in StaticAnalysisPipeline(chain_id="solana").runners
+
+    def test_cli(self):
+        result = CliRunner().invoke(privilege_paths, [str(WORKSPACE), "-q", "authority-takeover", "--json"])
+        assert result.exit_code == 0
+        assert [p["query"] for p in json.loads(result.output)["paths"]] == ["authority-takeover"]
+        assert CliRunner().invoke(privilege_paths, [str(WORKSPACE), "-q", "nope"]).exit_code == 1
@@ -0,0 +1,110 @@
+"""
+Tests for the hardcoded program id checker.
+
+Verifies canonical deployments of known programs are inventoried, ids a few
+characters off a known id are flagged as lookalikes, other ids bound to a
+known program's name (constants, `address` constraints, key comparisons)
+are flagged as forks, and the project's own `declare_id!` ids are ignored.
+"""
+
+from extensions.knowledge import ProgramLoader
+from extensions.knowledge.class_loader import ClassLoader
+from extensions.static import ProgramIdChecker, StaticAnalysisPipeline
+from extensions.static.program_ids import edit_distance, is_pubkey, lookalike
+
+
+TOKEN = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+LOOKALIKE = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DB"
+METADATA_FORK = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU"
+OWN = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+
+SOURCE = f"""use anchor_lang::prelude::*;
+
+declare_id!("{OWN}");
+
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("{LOOKALIKE}");
+pub const MEMO: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+// pub const OLD: Pubkey = pubkey!("{METADATA_FORK}");
+
+#[program]
+pub mod minter {{
+    use super::*;
+
+    pub fn mint(ctx: Context<Mint>) -> Result<()> {{
+        require_keys_eq!(ctx.accounts.token_program.key(), pubkey!("{TOKEN}"));
+        Ok(())
+    }}
+}}
+
+#[derive(Accounts)]
+pub struct Mint<'info> {{
+    /// CHECK: Metaplex
+    #[account(address = pubkey!("{METADATA_FORK}"))]
+    pub metadata_program: UncheckedAccount<'info>,
+    /// CHECK: some vault
+    #[account(address = pubkey!("{OWN}"))]
+    pub vault: UncheckedAccount<'info>,
+}}
+"""
+
+
+class TestProgramIds:
+    """Test the known program fingerprints and the checker against an inline Anchor program."""
+
+    def test_knowledge_base(self):
+        loader = ProgramLoader()
+        assert loader.get("spl-token").program_id == TOKEN
+        assert loader.by_address("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo").id == "spl-memo"
+        assert all(is_pubkey(a) for p in loader.list_all() for a in p.addresses)
+        assert loader.classify("token_program_id").id == "spl-token"
+        assert loader.classify("token_2022_program").id == "spl-token-2022"
+        assert loader.classify("raydium_clmm_program").id == "raydium-clmm"
+        assert loader.classify("vault") is None
+
+    def test_lookalike(self):
+        programs = ProgramLoader().list_all()
+        assert edit_distance(LOOKALIKE, TOKEN) == 1
+        program, edits = lookalike(LOOKALIKE, programs)
+        assert program.id == "spl-token" and edits == 1
+        # Shares the vanity prefix but is otherwise unrelated
+        assert lookalike("Tokenkeg" + METADATA_FORK[8:], programs)[0].id == "spl-token"
+        assert lookalike(METADATA_FORK, programs) is None
+
+    def test_findings(self, tmp_path, anchor_workspace):
+        findings, metadata = ProgramIdChecker().run(anchor_workspace(tmp_path, {"minter": SOURCE}))
+        assert metadata["files_checked"] == 1
+        flagged = [(f.kind, f.program.id, f.name, f.line) for f in findings]
+        assert flagged == [
+            ("lookalike", "spl-token", None, anchor_workspace.line(SOURCE, "pub const TOKEN_PROGRAM_ID")),
+            ("fork", "metaplex-token-metadata", "metadata_program",
+             anchor_workspace.line(SOURCE, f'pubkey!("{METADATA_FORK}"))]')),
+        ]
+        assert findings[0].path == "programs/minter/src/lib.rs" and findings[0].edits == 1
+        assert findings[1].text.endswith("pub metadata_program:")
+        assert [(r["program"], r["line"]) for r in metadata["known_programs"]] == [
+            ("spl-memo", anchor_workspace.line(SOURCE, "pub const MEMO")),
+            ("spl-token", anchor_workspace.line(SOURCE, "require_keys_eq!"))]
+
+    def test_no_forks(self, tmp_path, anchor_workspace):
+        findings, _ = ProgramIdChecker(include_forks=False).run(anchor_workspace(tmp_path, {"minter": SOURCE}))
+        assert [f.kind for f in findings] == ["lookalike"]
+
+    def test_hypothesis(self, tmp_path, anchor_workspace):
+        findings, _ = ProgramIdChecker().run(anchor_workspace(tmp_path, {"minter": SOURCE}))
+        hyp = findings[0].to_hypothesis()
+        assert hyp["title"] == "Lookalike SPL Token program id `Tokenkeg…`"
+        assert hyp["severity"] == "high" and hyp["status"] == "proposed"
+        assert f"is not SPL Token (`{TOKEN}`)" in hyp["description"]
+        props = hyp["properties"]
+        assert props["source_tool"] == "program-ids" and props["canonical_address"] == TOKEN
+        assert findings[1].to_hypothesis()["severity"] == "medium"
+        for finding in findings:
+            vuln_class = ClassLoader().resolve(finding.to_hypothesis()["vulnerability_type"], "solana")
+            assert vuln_class.id == "untrusted-program-id"
+
+    def test_pipeline(self, tmp_path, anchor_workspace):
+        pipeline = StaticAnalysisPipeline(chain_id="solana")
+        assert "program-ids" in pipeline.check_tools()
+        result = pipeline.run(anchor_workspace(tmp_path, {"minter": SOURCE}), tools=["program-ids"])
+        assert len(result.hypotheses) == 2
+        assert all(h["id"].startswith("static_program-ids_") for h in result.hypotheses)
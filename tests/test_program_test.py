@@ -0,0 +1,111 @@
+"""
+Tests for generated solana-program-test exploits: account types and PDA
+seeds from the IDL, the generated BanksClient test, and `kb render
+--program-test`.
+"""
+
+import json
+
+import pytest
+from click.testing import CliRunner
+
+from extensions.knowledge.harness import HARNESSES
+from extensions.knowledge.idl import parse_idl
+from extensions.knowledge.program_test import generate_program_test
+from extensions.knowledge.template_loader import TemplateLoader
+
+PROGRAM = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+
+IDL = {
+    "address": PROGRAM,
+    "metadata": {"name": "vault", "version": "0.1.0", "spec": "0.1.0"},
+    "instructions": [
+        {"name": "withdraw", "discriminator": [183, 18, 70, 156, 148, 109, 161, 34], "accounts": [
+            {"name": "vault", "writable": True, "pda": {"seeds": [
+                {"kind": "const", "value": list(b"vault")}, {"kind": "account", "path": "authority"}]}},
+            {"name": "authority"},
+            {"name": "recipient", "writable": True},
+            {"name": "token_program", "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"},
+            {"name": "system_program", "address": "11111111111111111111111111111111"}],
+         "args": [{"name": "amount", "type": "u64"}]},
+        {"name": "close", "accounts": [
+            {"name": "ticket", "writable": True, "pda": {"seeds": [
+                {"kind": "const", "value": [0, 255]}, {"kind": "arg", "path": "id"},
+                {"kind": "account", "path": "vault.owner"}]}}],
+         "args": [{"name": "id", "type": "u32"}]},
+    ],
+    "accounts": [{"name": "Vault", "discriminator": [1, 2, 3, 4, 5, 6, 7, 8]}],
+    "types": [{"name": "Vault", "type": {"kind": "struct", "fields": [
+        {"name": "authority", "type": "pubkey"}, {"name": "balance", "type": "u64"}]}}],
+}
+
+
+class TestProgramTest:
+    """Test the solana-program-test exploit generator."""
+
+    def test_idl_state_and_seeds(self):
+        idl = parse_idl(IDL)
+        assert [(f.name, f.ty) for f in idl.account_types["Vault"]] == [("authority", "pubkey"), ("balance", "u64")]
+        assert idl.instruction("withdraw").accounts[0].seeds[1] == {"kind": "account", "path": "authority"}
+        # Legacy IDLs keep the layout on the account entry
+        legacy = parse_idl({"name": "vault", "instructions": [], "accounts": [
+            {"name": "Vault", "type": {"kind": "struct", "fields": [{"name": "isOpen", "type": "bool"}]}}]})
+        assert [f.name for f in legacy.account_types["Vault"]] == ["is_open"]
+
+    def test_generate(self):
+        text = generate_program_test(TemplateLoader().get("missing_signer"), parse_idl(IDL), "withdraw")
+        assert text.startswith("// Generated by Baskerville from the `missing_signer` PoC template and the vault IDL.")
+        # 1. setup, 2. funding and state, 3. malicious transaction, 4. assertion
+        assert 'let mut test = ProgramTest::new("vault", PROGRAM_ID, None);' in text
+        assert "use vault::{accounts, instruction, ID as PROGRAM_ID};" in text
+        assert "test.add_account(attacker.pubkey(), Account { lamports: 10 * LAMPORTS_PER_SOL" in text
+        assert ('let (vault, _) = Pubkey::find_program_address(&[b"vault", attacker.pubkey().as_ref()], '
+                "&PROGRAM_ID);") in text
+        assert ("    test.add_account(vault, program_account(&vault::Vault {\n"
+                "        authority: Pubkey::new_unique(),  // Pubkey\n"
+                "        balance: 0,  // u64\n    }));") in text
+        assert ("        accounts: accounts::Withdraw {\n"
+                "            vault,\n"
+                "            authority: attacker.pubkey(),  // Attacker, not the real one!\n"
+                "            recipient: recipient.pubkey(),\n"
+                '            token_program: solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"),\n'
+                "            system_program: system_program::ID,\n"
+                "        }\n        .to_account_metas(None),\n"
+                "        data: instruction::Withdraw { amount }.data(),") in text
+        assert "Some(&attacker.pubkey()), &[&attacker]" in text
+        assert 'assert!(result.is_ok(), "withdraw rejected the exploit (fixed?): {result:?}");' in text
+        assert "// Fix: Declare the authority as Signer<'info>" in text
+        assert "AccountSerialize" in text and "{{" not in text
+
+        # Arg and byte seeds; a seed on another account's field is left to the auditor
+        close = generate_program_test(TemplateLoader().get("missing_signer"), parse_idl(IDL), "close")
+        assert "// TODO: derive `ticket` from its seeds\n    let ticket = Pubkey::new_unique();" in close
+        assert "AccountSerialize" not in close and "rent::Rent" not in close
+        seeds = {**IDL, "instructions": [{**IDL["instructions"][1], "accounts": [
+            {"name": "ticket", "writable": True, "pda": {"seeds": IDL["instructions"][1]["accounts"][0]["pda"]
+                                                                  ["seeds"][:2]}}]}]}
+        assert "&[&[0,255], &id.to_le_bytes()]" in generate_program_test(TemplateLoader().get("missing_signer"),
+                                                                         parse_idl(seeds))
+
+        with pytest.raises(ValueError, match="program tests are for Solana"):
+            generate_program_test(TemplateLoader().get("reentrancy"), parse_idl(IDL), "withdraw")
+
+    def test_cli(self, tmp_path):
+        from commands.knowledge import kb
+
+        idl = tmp_path / "vault.json"
+        idl.write_text(json.dumps(IDL))
+        runner = CliRunner()
+        result = runner.invoke(kb, ["render", "missing_signer", "--idl", str(idl), "-i", "withdraw",
+                                    "--program-test", "-w", str(tmp_path)])
+        assert result.exit_code == 0, result.output
+        written = tmp_path / "tests" / "missing_signer.rs"
+        assert "async fn exploit_missing_signer()" in written.read_text()
+        assert "cargo test-sbf --test missing_signer" in result.output and "solana-program-test" in result.output
+        assert not HARNESSES["solana-program-test"].problems(written.read_text())
+
+        result = runner.invoke(kb, ["render", "missing_signer", "--program-test"])
+        assert result.exit_code != 0 and "needs --idl" in result.output
+        result = runner.invoke(kb, ["render", "missing_signer", "--idl", str(idl), "--program-test",
+                                    "-s", "INSTRUCTION=drain"])
+        assert result.exit_code != 0 and "take their values from the IDL" in result.output
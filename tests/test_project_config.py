@@ -0,0 +1,108 @@
+"""
+Tests for project configuration: the rules, ignored paths, template roots
+and output defaults of a workspace's baskerville.toml, and merging them
+with command-line flags.
+"""
+
+import json
+
+import pytest
+from click.testing import CliRunner
+
+from commands.scan import scan
+from extensions.static import ConfigError, ProjectConfig, StaticAnalysisPipeline
+
+SOURCE = """use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod {name} {{
+    use super::*;
+
+    pub fn set_fee(ctx: Context<SetFee>, fee: u64) -> Result<()> {{
+        ctx.accounts.config.fee = fee;
+        Ok(())
+    }}
+}}
+
+#[derive(Accounts)]
+pub struct SetFee<'info> {{
+    #[account(mut, has_one = admin)]
+    pub config: Account<'info, Config>,
+    /// CHECK: config admin
+    pub admin: AccountInfo<'info>,
+}}
+"""
+
+TOOLS = ["missing-signer", "type-cosplay", "arbitrary-cpi"]
+
+
+PROGRAMS = {name: SOURCE.format(name=name) for name in ("vault", "treasury")}
+
+
+class TestProjectConfig:
+    """Test reading baskerville.toml and applying it to scans."""
+
+    def test_load(self, tmp_path):
+        assert ProjectConfig.load(tmp_path) == ProjectConfig()
+        (tmp_path / "pocs").mkdir()
+        (tmp_path / "baskerville.toml").write_text(
+            '[[bundle]]\nname = "acme"\nversion = "1.0.0"\n'
+            '[rules]\ndisable = ["type-cosplay"]\n[rules.severity]\nSOL-001 = "Informational"\n'
+            '[templates]\ndirs = ["pocs"]\n[output]\nformat = "json"\n')
+        config = ProjectConfig.load(tmp_path)
+        assert config.disable == ["type-cosplay"] and config.severity == {"SOL-001": "info"}
+        assert config.template_dirs == [tmp_path / "pocs"]
+        assert config.default("format") == "json" and config.default("format", "table") == "table"
+        assert config.tools(TOOLS) == ["missing-signer", "arbitrary-cpi"]
+        # Codes, short codes and rule ids name their detector
+        assert ProjectConfig(enable=["SOL-001", "type-cosplay/type-cosplay"]).tools(TOOLS) == TOOLS[:2]
+        assert ProjectConfig(enable=["SOL-005-ARBITRARY-CPI-SIGNER"]).tools(TOOLS) == ["arbitrary-cpi"]
+
+        for text, error in [('[rules.severity]\nSOL-001 = "severe"\n', "expected one of"),
+                            ('[output]\nformat = "xml"\n', "format must be one of"),
+                            ('[rules]\ndisable = "SOL-001", 3\n', "cannot read"),
+                            ('[output]\nmin_confidence = "sure"\n', "min_confidence")]:
+            (tmp_path / "baskerville.toml").write_text(text)
+            with pytest.raises(ConfigError, match=error):
+                ProjectConfig.load(tmp_path)
+
+    def test_pipeline(self, tmp_path, anchor_workspace):
+        workspace = anchor_workspace(tmp_path / "ws", PROGRAMS, localnet=["vault"])
+        (workspace / "baskerville.toml").write_text('[rules]\ndisable = ["type-cosplay"]\n'
+                                                    '[rules.severity]\nSOL-001 = "low"\n'
+                                                    '[paths]\nignore = ["programs/treasury/**"]\n')
+        config = ProjectConfig.load(workspace)
+        result = StaticAnalysisPipeline(chain_id="solana", config=config).run(workspace)
+        assert "type-cosplay" not in result.metadata["tools"]
+        assert result.metadata["config"]["tools_disabled"] == ["type-cosplay"]
+        signer = [h for h in result.hypotheses if h["properties"]["source_tool"] == "missing-signer"]
+        assert len(signer) == 1 and "vault" in str(signer[0]["properties"]["source_files"])
+        assert signer[0]["severity"] == "low" and signer[0]["properties"]["detector_severity"] != "low"
+        assert result.metadata["config"]["filtered"] >= 1
+
+        # Detectors asked for by name run even when disabled
+        result = StaticAnalysisPipeline(chain_id="solana", config=config).run(workspace, tools=["type-cosplay"])
+        assert list(result.metadata["tools"]) == ["type-cosplay"]
+        assert result.metadata["config"]["tools_disabled"] == []
+
+    def test_cli_defaults(self, tmp_path, anchor_workspace):
+        workspace = anchor_workspace(tmp_path / "ws", PROGRAMS, localnet=["vault"])
+        (workspace / "baskerville.toml").write_text('[rules]\nenable = ["SOL-001"]\n'
+                                                    '[output]\nformat = "json"\nsarif = "out/scan.sarif"\n')
+        runner = CliRunner()
+        result = runner.invoke(scan, [str(workspace), "--no-cache"])
+        assert result.exit_code == 0, result.output
+        data = json.loads(result.output)
+        assert list(data["tools"]) == ["missing-signer"] and len(data["hypotheses"]) == 2
+        assert (workspace / "out" / "scan.sarif").is_file()
+
+        # Flags win over [output]
+        result = runner.invoke(scan, [str(workspace), "--no-cache", "--format", "table", "--min-confidence", "0.99"])
+        assert result.exit_code == 0, result.output
+        assert "No hits" in result.output and "Scanning" in result.output
+
+        (workspace / "baskerville.toml").write_text('[output]\nformat = "xml"\n')
+        result = runner.invoke(scan, [str(workspace), "--no-cache"])
+        assert result.exit_code == 1 and "format must be one of" in result.output
@@ -0,0 +1,119 @@
+"""
+Tests for protocol parameter extraction: reading constants and config
+account writes, scaling them by unit, checking them against the knowledge
+base's ranges, and the report appendix.
+"""
+
+import json
+from unittest.mock import patch
+
+from click.testing import CliRunner
+
+from commands.reporting import reporting
+from extensions.knowledge.parameter_loader import ParameterLoader
+from extensions.reporting.parameters import embed_parameters
+from extensions.static import ProtocolParameterChecker, StaticAnalysisPipeline
+from extensions.static.protocol_params import evaluate, extract_constants
+
+
+LENDING = """use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub const MAX_LTV_BPS: u16 = 9_800;
+pub const LIQUIDATION_BONUS: u64 = 5;
+pub const ONE_HOUR: i64 = 60 * 60;
+pub const WITHDRAW_COOLDOWN: i64 = ONE_HOUR * 24;
+const VAULT_SEED: &[u8] = b"vault";
+
+#[program]
+pub mod lending {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.protocol_fee_bps = 2_500;
+        config.max_price_age = 120;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Config {
+    pub protocol_fee_bps: u16,
+    pub max_price_age: u64,
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init, payer = admin, space = 8 + 10)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+"""
+
+TOKEN = """contract Token {
+    uint256 public constant MAX_SUPPLY = 0;
+    uint256 public constant TIMELOCK_DELAY = 2 days;
+    uint256 internal constant SLIPPAGE_WAD = 0.5e18;
+    address public constant FEE_RECIPIENT = address(0);
+}
+"""
+
+
+# Pins the Anchor version the parameters are checked against
+MANIFEST = '\n[dependencies]\nanchor-lang = "0.29.0"\n'
+
+
+class TestProtocolParams:
+    """Test extracting protocol parameters and checking their ranges."""
+
+    def test_extract(self):
+        params = {p.name: p for p in extract_constants(LENDING, "lib.rs", ParameterLoader())}
+        assert set(params) == {"MAX_LTV_BPS", "LIQUIDATION_BONUS", "WITHDRAW_COOLDOWN"}
+        assert params["MAX_LTV_BPS"].normalized == 0.98 and params["MAX_LTV_BPS"].status == "out-of-range"
+        # 5 could be percent or bps
+        assert params["LIQUIDATION_BONUS"].status == "unclear"
+        assert params["WITHDRAW_COOLDOWN"].normalized == 86400 and params["WITHDRAW_COOLDOWN"].status == "ok"
+
+        token = {p.name: p for p in extract_constants(TOKEN, "Token.sol", ParameterLoader())}
+        assert set(token) == {"MAX_SUPPLY", "TIMELOCK_DELAY", "SLIPPAGE_WAD"}
+        assert token["TIMELOCK_DELAY"].to_dict()["display"] == "2d"
+        assert (token["SLIPPAGE_WAD"].to_dict()["display"], token["SLIPPAGE_WAD"].status) == ("50%", "out-of-range")
+        assert token["MAX_SUPPLY"].status == "out-of-range"
+        assert evaluate("10u64.pow(6) * 3") == 3_000_000 and evaluate("fee_bps") is None
+
+    def test_run(self, tmp_path, anchor_workspace):
+        workspace = anchor_workspace(tmp_path, {"lending": LENDING}, manifest=MANIFEST)
+        findings, metadata = ProtocolParameterChecker().run(workspace)
+        by_name = {p["name"]: p for p in metadata["parameters"]}
+        fee = by_name["Config.protocol_fee_bps"]
+        assert (fee["source"], fee["display"], fee["status"]) == ("config", "25%", "out-of-range")
+        assert fee["file"] == "programs/lending/src/lib.rs" and fee["line"] == 17
+        assert by_name["Config.max_price_age"]["status"] == "ok"
+
+        hyp = next(f for f in findings if f.name == "protocol_fee_bps").to_hypothesis()
+        assert hyp["vulnerability_type"] == "unsafe-protocol-parameter" and hyp["severity"] == "medium"
+        assert hyp["title"] == "Fee `Config.protocol_fee_bps` is outside the sane range (25%)"
+        assert hyp["properties"]["affected_lines"] == [17]
+        assert {f.name for f in findings} == {"MAX_LTV_BPS", "protocol_fee_bps"}
+
+        report = embed_parameters("# Report\n\n## Findings\n\n...\n", metadata["parameters"], "markdown")
+        assert report.index("## Findings") < report.index("## Appendix: Protocol Parameters")
+        assert "| Config.protocol_fee_bps | Fee | 2_500 | 25% (bps) |" in report
+
+    def test_pipeline_and_cli(self, tmp_path, anchor_workspace):
+        workspace = anchor_workspace(tmp_path / "src", {"lending": LENDING}, manifest=MANIFEST)
+        result = StaticAnalysisPipeline(chain_id="solana").run(workspace, tools=["protocol-params"])
+        assert all(h["id"].startswith("static_protocol-params_") for h in result.hypotheses)
+        project_dir = tmp_path / "project"
+        (project_dir / "static_analysis").mkdir(parents=True)
+        (project_dir / "static_analysis" / "static_metadata.json").write_text(json.dumps(result.metadata))
+
+        with patch("commands.reporting.ProjectManager") as PM:
+            PM.return_value.get_project.return_value = {"path": str(project_dir)}
+            out = CliRunner().invoke(reporting, ["parameters", "demo", "--status", "unclear", "--json"])
+        assert out.exit_code == 0, out.output
+        assert [p["name"] for p in json.loads(out.output)] == ["LIQUIDATION_BONUS"]
@@ -0,0 +1,70 @@
+"""
+Tests for the findings report formatters: template and remediation
+enrichment, the JSON and Markdown output, and `reporting findings`.
+"""
+
+import json
+from unittest.mock import patch
+
+import pytest
+from click.testing import CliRunner
+
+from commands.reporting import reporting
+from extensions.knowledge.class_loader import ClassLoader
+from extensions.knowledge.template_loader import TemplateLoader
+from extensions.reporting.formatters import build_findings_report, get_formatter
+
+
+FINDINGS = {
+    "hyp_signer": {"title": "Admin is not a signer", "severity": "high", "confidence": 0.8,
+                   "vulnerability_type": "missing-signer-check", "description": "Anyone can set the fee.",
+                   "properties": {"source_files": ["programs/vault/src/lib.rs"], "affected_lines": [31],
+                                  "source_tool": "missing-signer", "poc_template": "missing_signer"}},
+    "hyp_note": {"title": "Event not emitted", "severity": "low", "confidence": 0.4,
+                 "properties": {"suggested_fix": "emit!(FeeSet { fee })"}},
+    "hyp_fp": {"title": "False positive", "severity": "critical", "status": "rejected"},
+}
+
+
+class TestReportFormatters:
+    """Test building and formatting findings reports."""
+
+    def test_enrichment(self):
+        report = build_findings_report({k: v for k, v in FINDINGS.items() if k != "hyp_fp"}, "vault", "solana",
+                                       templates=TemplateLoader(), classes=ClassLoader())
+        signer, note = report["findings"]
+        assert (signer["id"], note["id"]) == ("hyp_signer", "hyp_note")
+        assert signer["template"]["id"] == "missing_signer"
+        assert signer["remediation"] == TemplateLoader().get("missing_signer").remediation
+        assert "EXPLOIT" not in signer["template"]["snippet"] and signer["template"]["snippet"]
+        # No template or class, so the detector's suggestion
+        assert note["template"] is None and note["remediation"] == "emit!(FeeSet { fee })"
+        assert report["counts"]["high"] == 1 and report["counts"]["low"] == 1
+
+        data = json.loads(get_formatter("json").format(report))
+        assert data["format"] == "baskerville-findings" and len(data["findings"]) == 2
+
+        markdown = get_formatter("markdown").format(report)
+        assert markdown.index("### High") < markdown.index("### Low")
+        assert "#### H-1. Admin is not a signer" in markdown
+        assert "- **Location:** `programs/vault/src/lib.rs` (line 31)" in markdown
+        assert "```rust" in markdown and "**Remediation:**" in markdown
+        with pytest.raises(ValueError):
+            get_formatter("pdf")
+
+    def test_cli(self, tmp_path):
+        (tmp_path / "hypotheses.json").write_text(json.dumps({"version": "1.0", "hypotheses": FINDINGS}))
+        runner = CliRunner()
+        with patch("commands.reporting.ProjectManager") as PM:
+            PM.return_value.get_project.return_value = {"path": str(tmp_path), "chain_id": "solana"}
+            result = runner.invoke(reporting, ["findings", "demo", "--format", "json"])
+            assert result.exit_code == 0, result.output
+            ids = [f["id"] for f in json.loads((tmp_path / "reports" / "findings.json").read_text())["findings"]]
+            assert ids == ["hyp_signer", "hyp_note"]
+
+            result = runner.invoke(reporting, ["findings", "demo", "--all"])
+        assert result.exit_code == 0, result.output
+        markdown = (tmp_path / "reports" / "findings.md").read_text()
+        # Scored critical, the missing signer check ranks above the unscored critical
+        assert "#### C-1. Admin is not a signer" in markdown and "#### C-2. False positive" in markdown
+        assert "baskerville-manifest" in markdown
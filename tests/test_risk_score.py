@@ -0,0 +1,121 @@
+"""
+Tests for aggregate program risk scoring: the model, component scores,
+finding attribution, portfolio ranking and the CLI.
+"""
+
+import json
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+import pytest
+from click.testing import CliRunner
+
+from commands.reporting import risk
+from extensions.ir import load_programs
+from extensions.reporting.risk import RiskModel, attribute_findings, build_risk_report, load_risk_model, score_project
+
+FIXTURES = Path(__file__).resolve().parent / "fixtures"
+VAULT = FIXTURES / "solana" / "anchor_vault"
+ROUTER = FIXTURES / "solana" / "cpi_router"
+
+WITHDRAW = {"title": "Open withdraw", "severity": "high", "status": "confirmed",
+            "properties": {"source_files": ["programs/vault/src/instructions/withdraw.rs:12"]}}
+ADVISORY = {"title": "RUSTSEC-2024-0001: bad crate", "severity": "critical", "confidence": 0.9, "status": "proposed",
+            "properties": {"source_tool": "cargo-audit", "advisory_id": "RUSTSEC-2024-0001", "source_files": []}}
+
+
+class TestRiskModel:
+    """Test model loading and validation."""
+
+    def test_from_yaml(self, tmp_path):
+        path = tmp_path / "risk.yaml"
+        path.write_text("weights:\n  findings: 1\n  dependencies: 0\nseverity_weights:\n  high: 8\n")
+        model = load_risk_model(path)
+        assert model.weights["findings"] == 1 and model.weights["attack_surface"] == 0.25
+        assert model.severity_weights["high"] == 8 and model.severity_weights["critical"] == 10
+        assert model.grade(80) == "critical" and model.grade(30) == "medium" and model.grade(0) == "low"
+        assert load_risk_model(None).weights == RiskModel().weights
+
+    def test_rejects_bad_models(self, tmp_path):
+        with pytest.raises(ValueError, match="unknown risk model keys: weight"):
+            RiskModel.from_dict({"weight": {}})
+        with pytest.raises(ValueError, match="unknown risk components in weights: tvl"):
+            RiskModel.from_dict({"weights": {"tvl": 1}})
+        with pytest.raises(ValueError, match="must be numbers"):
+            RiskModel.from_dict({"scales": {"findings": "many"}})
+        with pytest.raises(ValueError, match="must not all be zero"):
+            RiskModel.from_dict({"weights": {c: 0 for c in RiskModel().weights}})
+        with pytest.raises(ValueError, match="cannot read risk model"):
+            load_risk_model(tmp_path / "missing.yaml")
+
+
+class TestRiskScore:
+    """Test the component scores and the ranking."""
+
+    def test_components(self):
+        programs = load_programs(VAULT)
+        rejected = {**WITHDRAW, "title": "Rejected", "status": "rejected"}
+        result = score_project(programs, [WITHDRAW, rejected, ADVISORY], VAULT, project="vault")
+        entry = result["programs"][0]
+        parts = entry["components"]
+        assert parts["findings"]["raw"] == 5.0 and parts["findings"]["by_severity"] == {"high": 1}
+        assert parts["dependencies"]["advisories"] == ["RUSTSEC-2024-0001"] and parts["dependencies"]["raw"] == 9.0
+        # initialize is the only privileged instruction, gated by one role
+        assert parts["centralization"]["dominant_role"] == "authority"
+        assert parts["centralization"]["score"] == pytest.approx(33.3)
+        assert parts["attack_surface"]["unchecked_accounts"] == 3
+        assert entry["score"] == pytest.approx(sum(p["contribution"] for p in parts.values()))
+        assert 0 < entry["score"] < 100 and entry["grade"] == RiskModel().grade(entry["score"])
+
+        clean = score_project(programs, [], VAULT)["programs"][0]
+        assert clean["components"]["findings"]["score"] == 0 and clean["score"] < entry["score"]
+
+    def test_attribution(self):
+        programs = load_programs(VAULT) + load_programs(ROUTER)
+        elsewhere = {"title": "Elsewhere", "severity": "low", "properties": {"source_files": ["app/src/index.ts"]}}
+        unlocated = {"title": "Unlocated", "severity": "low"}
+        owned, rest = attribute_findings(programs, [WITHDRAW, elsewhere, unlocated], FIXTURES / "solana")
+        assert owned == {"vault": [], "cpi_router": []} and len(rest) == 3
+        owned, rest = attribute_findings(programs, [WITHDRAW], VAULT)
+        assert owned["vault"] == [WITHDRAW] and rest == []
+        # Root-relative paths and a lone program both resolve
+        assert attribute_findings(load_programs(VAULT), [unlocated, elsewhere], VAULT)[0]["vault"] == [unlocated]
+        root_relative = {"title": "Lib", "properties": {"source_files": ["src/lib.rs"]}}
+        assert attribute_findings(programs, [root_relative], None)[0]["vault"] == [root_relative]
+
+    def test_ranking(self):
+        model = RiskModel.from_dict({"weights": {"findings": 1, "centralization": 0, "dependencies": 0,
+                                                 "attack_surface": 0}})
+        vault = score_project(load_programs(VAULT), [WITHDRAW], VAULT, model, project="a")
+        router = score_project(load_programs(ROUTER), [], ROUTER, model, project="b")
+        report = build_risk_report([router, vault], model)
+        assert report["format"] == "baskerville-risk" and report["model"]["weights"]["findings"] == 1
+        assert [p["program"] for p in report["programs"]] == ["vault", "cpi_router"]
+        assert report["programs"][0]["score"] == report["programs"][0]["components"]["findings"]["score"]
+        assert report["programs"][1]["score"] == 0
+        assert "programs" not in report["projects"][0]
+
+
+class TestRiskCli:
+    """Test the reporting risk command."""
+
+    def test_cli(self, tmp_path):
+        (tmp_path / "hypotheses.json").write_text(json.dumps({"hypotheses": {"h1": WITHDRAW}}))
+        (tmp_path / "static_analysis").mkdir()
+        (tmp_path / "static_analysis" / "static_hypotheses.json").write_text(json.dumps([WITHDRAW, ADVISORY]))
+        with patch("commands.reporting.ProjectManager") as PM:
+            pm = MagicMock()
+            PM.return_value = pm
+            pm.get_project.return_value = {"path": str(tmp_path), "source_path": str(VAULT)}
+            result = CliRunner().invoke(risk, ["vault"])
+            assert result.exit_code == 0, result.output
+            assert "Risk scores written" in result.output and "vault" in result.output
+            data = json.loads((tmp_path / "reports" / "risk.json").read_text())
+            assert data["manifest"]["command"]["name"] == "reporting risk"
+            # The static copy of the hypothesis is not counted twice
+            assert data["programs"][0]["components"]["findings"]["raw"] == 5.0
+            assert data["programs"][0]["components"]["dependencies"]["advisories"] == ["RUSTSEC-2024-0001"]
+
+            (tmp_path / "bad.yaml").write_text("weights: [1]\n")
+            result = CliRunner().invoke(risk, ["vault", "--model", str(tmp_path / "bad.yaml")])
+            assert result.exit_code == 1 and "must be a mapping" in result.output
@@ -0,0 +1,180 @@
+"""
+Tests for the shared RPC client: providers, retries, caching, batching and limits.
+"""
+
+import json
+import threading
+import time
+from unittest.mock import patch
+
+import pytest
+from click.testing import CliRunner
+
+from commands.rpc import call, provider
+from extensions.rpc import RateLimiter, RpcCache, RpcClient, RpcError, generic, resolve_provider
+
+USDC = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
+
+
+class FakeNode:
+    """Transport answering from a script of (status, headers, reply) steps, then from ``handler``."""
+
+    def __init__(self, script=(), handler=None):
+        self.script = list(script)
+        self.handler = handler or (lambda req: {"jsonrpc": "2.0", "id": req["id"], "result": req["method"]})
+        self.requests = []
+        self.in_flight = 0
+        self.max_in_flight = 0
+        self._lock = threading.Lock()
+
+    def __call__(self, url, body, headers, timeout):
+        payload = json.loads(body)
+        self.requests.append(payload)
+        with self._lock:
+            self.in_flight += 1
+            self.max_in_flight = max(self.max_in_flight, self.in_flight)
+        try:
+            if self.script:
+                step = self.script.pop(0)
+                if isinstance(step, Exception):
+                    raise step
+                status, resp_headers, reply = step
+                return status, resp_headers, json.dumps(reply).encode()
+            time.sleep(0.01)
+            if isinstance(payload, list):
+                return 200, {}, json.dumps([self.handler(r) for r in reversed(payload)]).encode()
+            return 200, {}, json.dumps(self.handler(payload)).encode()
+        finally:
+            with self._lock:
+                self.in_flight -= 1
+
+
+def make_client(node, **kwargs):
+    sleeps = []
+    client = RpcClient(generic("http://node", max_rps=0), transport=node, sleep=sleeps.append, **kwargs)
+    return client, sleeps
+
+
+class TestRpcClient:
+    """Test provider resolution, retry policy, cache policy, batching and rate/concurrency limits."""
+
+    def test_providers(self, monkeypatch):
+        monkeypatch.setenv("HELIUS_API_KEY", "secret")
+        monkeypatch.setenv("TRITON_RPC_URL", "https://acme.mainnet.rpcpool.com/tok3n")
+        monkeypatch.delenv("SOLANA_RPC_URL", raising=False)
+        helius = resolve_provider("devnet:helius")
+        assert helius.url == "https://devnet.helius-rpc.com/?api-key=secret"
+        assert helius.websocket_url.startswith("wss://devnet.helius-rpc.com/")
+        assert helius.to_dict()["url"] == "https://devnet.helius-rpc.com/?api-key=***"
+        assert resolve_provider("helius:mainnet").cluster == "mainnet"
+        assert resolve_provider("triton").to_dict()["url"] == "https://acme.mainnet.rpcpool.com/***"
+        assert resolve_provider().url == "https://api.mainnet-beta.solana.com"
+        assert resolve_provider("d").cluster == "devnet"
+        local = resolve_provider("http://127.0.0.1:8899")
+        assert local.cluster == "localnet" and local.websocket_url == "ws://127.0.0.1:8900"
+        assert local.max_rps == 1000.0
+        assert resolve_provider("mainnet", max_rps=2, max_concurrency=None).max_rps == 2
+        monkeypatch.setenv("SOLANA_RPC_URL", "devnet")
+        assert resolve_provider().cluster == "devnet"
+        for bad in ("helius:triton", "moon", "helius:testnet"):
+            with pytest.raises(ValueError):
+                resolve_provider(bad)
+        monkeypatch.delenv("HELIUS_API_KEY")
+        with pytest.raises(ValueError):
+            resolve_provider("helius")
+
+    def test_retries(self):
+        node = FakeNode([(429, {"Retry-After": "2"}, {}), (503, {}, {}), OSError("connection reset")])
+        client, sleeps = make_client(node)
+        assert client.call("getSlot") == "getSlot"
+        assert 1.9 < sleeps[0] <= 2.0 and 0.5 <= sleeps[1] <= 1.0 and 1.0 <= sleeps[2] <= 2.0
+        assert client.requests == 4 and client.retried == 3
+
+        behind = {"jsonrpc": "2.0", "id": 1, "error": {"code": -32005, "message": "Node is behind by 40 slots"}}
+        client, sleeps = make_client(FakeNode([(200, {}, behind)] * 3), retries=2)
+        with pytest.raises(RpcError) as excinfo:
+            client.call("getSlot")
+        assert excinfo.value.code == -32005 and len(sleeps) == 2
+
+        invalid = {"jsonrpc": "2.0", "id": 1, "error": {"code": -32602, "message": "Invalid param: WrongSize"}}
+        client, sleeps = make_client(FakeNode([(200, {}, invalid)]))
+        with pytest.raises(RpcError) as excinfo:
+            client.call("getAccountInfo", ["x"])
+        assert "WrongSize" in str(excinfo.value) and sleeps == []
+        client, _ = make_client(FakeNode([(401, {}, {})]))
+        with pytest.raises(RpcError) as excinfo:
+            client.call("getSlot")
+        assert excinfo.value.code == 401 and client.requests == 1
+
+    def test_cache_policy(self, tmp_path):
+        node = FakeNode()
+        client, _ = make_client(node, cache=RpcCache(tmp_path))
+        for _ in range(2):
+            client.get_transaction("sig")
+            client.get_transaction("sig", commitment="confirmed")
+            client.get_slot()
+            client.call("getAccountInfo", [USDC, {"encoding": "base64"}])
+        assert [r["method"] for r in node.requests].count("getTransaction") == 3
+        assert [r["method"] for r in node.requests].count("getSlot") == 2
+        assert [r["method"] for r in node.requests].count("getAccountInfo") == 1
+        client.call("getAccountInfo", [USDC, {"encoding": "base64"}], cache=False)
+        assert len(node.requests) == 7
+
+        # Persisted across processes, scoped by cluster
+        other, _ = make_client(FakeNode(), cache=RpcCache(tmp_path))
+        assert other.get_transaction("sig") == "getTransaction" and other.requests == 0
+        assert RpcCache(tmp_path).stats()["stored"] == {"getAccountInfo": 1, "getTransaction": 1}
+        devnet = RpcClient(generic("http://node", cluster="devnet"), transport=FakeNode(), cache=RpcCache(tmp_path))
+        devnet.get_transaction("sig")
+        assert devnet.requests == 1
+
+        cache = RpcCache(ttls={"getAccountInfo": 0.05})
+        cache.put("mainnet", "getAccountInfo", [USDC], {"value": 1})
+        assert cache.get("mainnet", "getAccountInfo", [USDC]) == (True, {"value": 1})
+        time.sleep(0.06)
+        assert cache.get("mainnet", "getAccountInfo", [USDC]) == (False, None)
+        cache.put("mainnet", "getAccountInfo", ["gone"], None)
+        assert cache.get("mainnet", "getAccountInfo", ["gone"]) == (False, None)
+
+    def test_batch(self):
+        node = FakeNode()
+        client, _ = make_client(node)
+        client.provider = generic("http://node", max_rps=0, max_batch=2)
+        client.call("getAccountInfo", [USDC, {"encoding": "base64"}])
+        calls = [("getAccountInfo", [USDC, {"encoding": "base64"}])] + [("getBalance", [str(i)]) for i in range(4)]
+        assert client.batch(calls) == ["getAccountInfo"] + ["getBalance"] * 4
+        assert [len(r) for r in node.requests[1:]] == [2, 2]
+        accounts = FakeNode(handler=lambda req: {"jsonrpc": "2.0", "id": req["id"],
+                                                 "result": {"value": [None] * len(req["params"][0])}})
+        client, _ = make_client(accounts)
+        infos = client.get_multiple_accounts([str(i) for i in range(250)], data_slice={"offset": 0, "length": 0})
+        assert len(infos) == 250
+        assert [len(r["params"][0]) for r in accounts.requests] == [100, 100, 50]
+
+    def test_limits(self):
+        now = [0.0]
+        sleeps = []
+        limiter = RateLimiter(2.0, sleep=sleeps.append, clock=lambda: now[0])
+        for _ in range(3):
+            limiter.acquire()
+        assert sleeps == [0.5, 1.0]
+        limiter.pause(5.0)
+        limiter.acquire()
+        assert sleeps[-1] == 5.0
+
+        node = FakeNode()
+        client = RpcClient(generic("http://node", max_rps=1000, max_concurrency=2), transport=node, cache=False)
+        assert client.parallel(lambda i: client.call("getBalance", [i]), range(8)) == ["getBalance"] * 8
+        assert node.max_in_flight <= 2
+
+    def test_cli(self, tmp_path, monkeypatch):
+        monkeypatch.setenv("HELIUS_API_KEY", "secret")
+        runner = CliRunner()
+        result = runner.invoke(provider, ["helius"])
+        assert result.exit_code == 0 and "secret" not in result.output
+        assert json.loads(result.output)["max_rps"] == 10.0
+        with patch("commands.rpc.default_cache_dir", return_value=tmp_path), \
+                patch("extensions.rpc.client.urllib_transport", FakeNode()):
+            result = runner.invoke(call, ["getBalance", f'["{USDC}"]', "--provider", "devnet"])
+            assert result.exit_code == 0 and json.loads(result.output) == "getBalance"
+            assert runner.invoke(call, ["getBalance", "[oops"]).exit_code == 1
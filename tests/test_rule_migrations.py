@@ -0,0 +1,133 @@
+"""
+Tests for versioned rule ids: the rule catalog's renames, splits and merges,
+and migrating a project's suppressions and baseline across them.
+"""
+
+import json
+from unittest.mock import patch
+
+import pytest
+from click.testing import CliRunner
+
+from commands.rules import rules
+from extensions.static.rules import RuleCatalog, RuleCatalogError, load_catalog, rule_id
+from extensions.static.suppressions import SuppressionError, SuppressionStore
+
+
+CATALOG = {
+    "version": 4,
+    "migrations": [
+        {"version": 2, "kind": "rename", "from": "old_tool/*", "to": "new-tool/*"},
+        {"version": 3, "kind": "split", "from": "new-tool/overflow", "to": ["new-tool/add-overflow",
+                                                                            "new-tool/mul-overflow"]},
+        {"version": 4, "kind": "merge", "from": ["lint/unused-var", "lint/unused-arg"], "to": "lint/unused"},
+    ],
+    "deprecated": [{"rule": "lint/magic-number", "version": 4, "reason": "Too noisy"}],
+}
+
+
+def _hit(rule: str, path: str = "src/lib.rs", title: str = "Hit") -> dict:
+    tool, vuln_type = rule.split("/")
+    return {"title": title, "vulnerability_type": vuln_type,
+            "properties": {"source_tool": tool, "source_files": [path], "rule_id": rule}}
+
+
+class TestRuleCatalog:
+    """Test rule id migration through the catalog."""
+
+    def test_migrate(self):
+        catalog = RuleCatalog.from_dict(CATALOG)
+        # Detector rename, then a split of one of its rules
+        assert catalog.migrate("old_tool/reentrancy", 1) == ["new-tool/reentrancy"]
+        assert catalog.migrate("old_tool/overflow", 1) == ["new-tool/add-overflow", "new-tool/mul-overflow"]
+        assert catalog.migrate("new-tool/overflow", 2) == ["new-tool/add-overflow", "new-tool/mul-overflow"]
+        assert catalog.migrate("old_tool/overflow", 4) == ["old_tool/overflow"]
+        assert catalog.merged_with("lint/unused-var", 3) == ["lint/unused-arg"]
+
+        assert catalog.deprecation("old_tool/x").replaced_by == ("new-tool/x",)
+        assert catalog.retired("lint/magic-number") and not catalog.retired("lint/unused")
+
+    def test_invalid(self):
+        with pytest.raises(RuleCatalogError, match="wrong number"):
+            RuleCatalog.from_dict({"version": 2, "migrations": [{"version": 2, "kind": "rename", "from": "a/b",
+                                                                 "to": ["c/d", "e/f"]}]})
+        with pytest.raises(RuleCatalogError, match="unknown migration kind"):
+            RuleCatalog.from_dict({"version": 2, "migrations": [{"version": 2, "kind": "move", "from": "a/b",
+                                                                 "to": "c/d"}]})
+        with pytest.raises(RuleCatalogError, match="outside"):
+            RuleCatalog.from_dict({"version": 2, "migrations": [{"version": 3, "kind": "rename", "from": "a/b",
+                                                                 "to": "c/d"}]})
+
+    def test_shipped_catalog(self):
+        catalog = load_catalog()
+        assert catalog.migrate("ast_patterns/ast-pattern-tx-origin", 1) == ["ast-patterns/ast-pattern-tx-origin"]
+        hit = {"id": "static_missing-signer_0123abcd", "vulnerability_type": "missing-signer-check"}
+        assert rule_id(hit) == "missing-signer/missing-signer-check"
+
+
+class TestSuppressionMigration:
+    """Test suppressions and baselines surviving rule changes."""
+
+    def test_migrate_on_load(self, tmp_path):
+        path = tmp_path / "static_analysis" / "suppressions.json"
+        path.parent.mkdir()
+        path.write_text(json.dumps({
+            "rules_version": 1,
+            "suppressions": [
+                {"rule": "old_tool/overflow", "path": "vendor/*", "reason": "Vendored"},
+                {"rule": "lint/unused-var", "reason": "Noise"},
+                {"rule": "lint/magic-number", "reason": "Noise"},
+            ],
+            "baseline": [{"rule": "old_tool/reentrancy", "title": "Known", "file": "src/lib.rs"}],
+        }))
+        store = SuppressionStore(path, RuleCatalog.from_dict(CATALOG))
+        report = store.load()
+
+        assert [s.rule for s in store.suppressions] == [
+            "new-tool/add-overflow", "new-tool/mul-overflow", "lint/unused", "lint/magic-number"]
+        assert store.suppressions[0].migrated_from == ["old_tool/overflow"]
+        assert report.widened == [("lint/unused-var", ["lint/unused-arg"])]
+        assert report.retired == ["lint/magic-number"]
+        assert report.backup == path.parent / "suppressions.v1.json"
+        assert json.loads(report.backup.read_text())["rules_version"] == 1
+        assert json.loads(path.read_text())["rules_version"] == 4
+
+        kept, suppressed, baselined = store.apply([
+            _hit("new-tool/mul-overflow", "vendor/math.rs"), _hit("new-tool/mul-overflow"),
+            _hit("lint/unused"), _hit("new-tool/reentrancy", title="Known"),
+        ])
+        assert len(kept) == 1 and kept[0]["properties"]["source_files"] == ["src/lib.rs"]
+        assert len(suppressed) == 2 and len(baselined) == 1
+
+        # Loading again finds nothing to migrate
+        assert SuppressionStore(path, RuleCatalog.from_dict(CATALOG)).load() is None
+
+    def test_newer_file(self, tmp_path):
+        path = tmp_path / "suppressions.json"
+        path.write_text(json.dumps({"rules_version": 9, "suppressions": [], "baseline": []}))
+        with pytest.raises(SuppressionError, match="newer"):
+            SuppressionStore(path, RuleCatalog.from_dict(CATALOG)).load()
+        assert json.loads(path.read_text())["rules_version"] == 9
+
+    def test_cli(self, tmp_path):
+        static_dir = tmp_path / "static_analysis"
+        static_dir.mkdir()
+        # Results saved before the ast_patterns rename carry no rules_version
+        (static_dir / "static_hypotheses.json").write_text(json.dumps([
+            {"title": "tx.origin", "vulnerability_type": "ast-pattern-tx-origin",
+             "properties": {"source_tool": "ast_patterns", "source_files": ["src/A.sol"]}}]))
+        runner = CliRunner()
+        with patch("commands.rules.ProjectManager") as PM:
+            PM.return_value.get_project.return_value = {"path": str(tmp_path)}
+            result = runner.invoke(rules, ["suppress", "demo", "ast_patterns/ast-pattern-delegatecall",
+                                           "--reason", "Proxy", "--by", "alice"])
+            assert result.exit_code == 0, result.output
+            assert "suppressing ast-patterns/ast-pattern-delegatecall instead" in result.output
+            result = runner.invoke(rules, ["baseline", "demo"])
+            assert result.exit_code == 0 and "Baselined 1 hits" in result.output
+            result = runner.invoke(rules, ["show", "demo", "--json"])
+            shown = json.loads(result.output)
+
+        assert [s["rule"] for s in shown["suppressions"]] == ["ast-patterns/ast-pattern-delegatecall"]
+        assert shown["baseline"][0]["rule"] == "ast-patterns/ast-pattern-tx-origin"
+        assert runner.invoke(rules, ["list", "--json"]).exit_code == 0
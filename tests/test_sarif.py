@@ -0,0 +1,91 @@
+"""
+Tests for the SARIF 2.1.0 export: rules, levels, locations relative to the
+scanned root, fix suggestions and the `scan --sarif` option.
+"""
+
+import json
+
+from click.testing import CliRunner
+
+from commands.scan import scan
+from extensions.knowledge.class_loader import ClassLoader
+from extensions.reporting.sarif import build_sarif
+
+
+SOURCE = """use anchor_lang::prelude::*;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn set_fee(ctx: Context<SetFee>, fee: u64) -> Result<()> {
+        ctx.accounts.config.fee = fee;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetFee<'info> {
+    #[account(mut, has_one = admin)]
+    pub config: Account<'info, Config>,
+    /// CHECK: config admin
+    pub admin: AccountInfo<'info>,
+}
+"""
+
+
+def _hits(root):
+    return [
+        {"id": "static_cpi-targets_0123abcd", "title": "Unvalidated CPI target", "severity": "high",
+         "vulnerability_type": "unvalidated-cpi-target", "confidence": 0.7, "description": "Caller picks the program.",
+         "properties": {"source_tool": "cpi-targets", "source_files": [str(root / "programs/vault/src/lib.rs")],
+                        "affected_lines": [42, 48], "suggested_fix": "Program<'info, Token>",
+                        "rule_id": "cpi-targets/unvalidated-cpi-target", "precision": "semantic"}},
+        {"id": "static_missing-signer_89abcdef", "title": "Missing signer check", "severity": "medium",
+         "vulnerability_type": "missing-signer-check",
+         "properties": {"source_tool": "missing-signer", "source_files": ["programs/vault/src/lib.rs"],
+                        "affected_lines": [10]}},
+    ]
+
+
+class TestSarif:
+    """Test serializing detector hits to SARIF."""
+
+    def test_build(self, tmp_path):
+        log = build_sarif(_hits(tmp_path), tmp_path, "solana",
+                          {"cpi-targets": {"version": "1.0"}, "soteria": {"available": False, "error": "not found"}},
+                          ClassLoader())
+        assert log["version"] == "2.1.0" and len(log["runs"]) == 1
+        run = log["runs"][0]
+        rules = {r["id"]: r for r in run["tool"]["driver"]["rules"]}
+        assert set(rules) == {"cpi-targets/unvalidated-cpi-target", "missing-signer/missing-signer-check"}
+        assert rules["cpi-targets/unvalidated-cpi-target"]["properties"]["security-severity"] == "8.0"
+        # No detector fix, so the vulnerability class's Solana guidance
+        assert "Signer" in rules["missing-signer/missing-signer-check"]["help"]["text"]
+
+        cpi, signer = run["results"]
+        assert (cpi["level"], signer["level"]) == ("error", "warning")
+        assert cpi["ruleIndex"] == 0 and signer["ruleIndex"] == 1
+        location = cpi["locations"][0]["physicalLocation"]
+        assert location["artifactLocation"] == {"uri": "programs/vault/src/lib.rs", "uriBaseId": "%SRCROOT%"}
+        assert location["region"] == {"startLine": 42}
+        assert cpi["properties"]["suggestedFix"] == "Program<'info, Token>"
+        assert cpi["partialFingerprints"] == {"baskervilleHitId/v1": "static_cpi-targets_0123abcd"}
+        assert run["originalUriBaseIds"]["SRCROOT"]["uri"] == tmp_path.resolve().as_uri() + "/"
+        assert run["invocations"][0]["toolExecutionNotifications"][0]["message"]["text"] == "soteria not run: not found"
+
+    def test_scan_sarif(self, tmp_path):
+        program = tmp_path / "vault" / "programs" / "vault"
+        (program / "src").mkdir(parents=True)
+        (program / "Cargo.toml").write_text('[package]\nname = "vault"\nversion = "0.1.0"\n')
+        (program / "src" / "lib.rs").write_text(SOURCE)
+        anchor_toml = '[programs.localnet]\nvault = "11111111111111111111111111111111"\n'
+        (tmp_path / "vault" / "Anchor.toml").write_text(anchor_toml)
+
+        out = tmp_path / "out.sarif"
+        result = CliRunner().invoke(scan, [str(tmp_path / "vault"), "--tool", "missing-signer", "--sarif", str(out)])
+        assert result.exit_code == 0, result.output
+        results = json.loads(out.read_text())["runs"][0]["results"]
+        assert [r["ruleId"] for r in results] == ["missing-signer/missing-signer-check"]
+        uri = results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"]
+        assert uri == "programs/vault/src/lib.rs"
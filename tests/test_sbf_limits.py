@@ -0,0 +1,74 @@
+"""
+Tests for the built-in SBF stack/heap usage estimator.
+"""
+
+from pathlib import Path
+
+from extensions.static.pipeline import StaticAnalysisPipeline
+from extensions.static.sbf_limits import SbfResourceEstimator, TypeSizer
+
+FIXTURE = Path(__file__).parent / "fixtures" / "solana" / "order_book"
+
+
+class TestSbfResourceEstimator:
+    """Test stack frame, accounts struct and heap allocation estimates."""
+
+    def setup_method(self):
+        self.findings, self.metadata = SbfResourceEstimator().run(FIXTURE)
+        self.by_function = {f.function: f for f in self.findings}
+
+    def test_type_sizes(self):
+        sizer = TypeSizer()
+        sizer.add_source((FIXTURE / "src" / "state.rs").read_text())
+        assert sizer.consts == {"MAX_ORDERS": 64, "SLAB_LEN": 2560}
+        assert sizer.size("Order") == 40
+        assert sizer.size("Market") == 32 + 2 * 64 * 40 + 8
+        assert sizer.size("Account<'info, Market>") == 48 + 5160
+        assert sizer.size("Box<Account<'info, Market>>") == 8
+        assert sizer.size("[u8; MAX_ORDERS * 2]") == 128
+        assert sizer.heap_fields("History") == ["fills", "memo"]
+
+    def test_unboxed_accounts_overflow_stack(self):
+        finding = self.by_function["Place::try_accounts"]
+        assert finding.kind == "accounts-stack"
+        assert finding.estimate > 4096
+        assert "Box<Account<...>>" in finding.detail
+        # Boxing moves the market account off the stack
+        assert "Settle::try_accounts" not in self.by_function
+
+    def test_large_local_array(self):
+        finding = self.by_function["snapshot"]
+        assert finding.kind == "stack-frame"
+        assert finding.estimate == 5000 + 128
+        hyp = finding.to_hypothesis()
+        assert hyp["severity"] == "high"
+        assert hyp["vulnerability_type"] == "sbf-stack-frame"
+        assert hyp["properties"]["category"] == "resource-limits"
+        assert hyp["properties"]["limit_bytes"] == 4096
+
+    def test_caller_sized_allocation(self):
+        finding = self.by_function["place"]
+        assert finding.kind == "heap-unbounded"
+        assert finding.tainted == ["count"]
+        assert finding.line == 15
+        assert finding.severity == "medium"
+        # A bounds check before allocating clears the finding
+        assert "settle" not in self.by_function
+
+    def test_heap_estimates(self):
+        assert self.by_function["prefill"].kind == "heap-constant"
+        assert self.by_function["prefill"].estimate == 2560 * 16
+        replay = self.by_function["replay"]
+        assert replay.kind == "heap-unbounded"
+        assert replay.tainted == ["History.fills", "History.memo"]
+
+    def test_metadata_lists_every_function(self):
+        assert self.metadata["files_scanned"] == 2
+        estimates = {e["function"]: e for e in self.metadata["functions"]}
+        assert estimates["settle"]["unbounded_allocations"] == 0
+        assert estimates["Settle::try_accounts"]["stack_bytes"] < 4096
+
+    def test_registered_for_solana(self):
+        pipeline = StaticAnalysisPipeline(chain_id="solana")
+        assert "sbf-limits" in pipeline.runners
+        assert pipeline.check_tools()["sbf-limits"][0]
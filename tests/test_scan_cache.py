@@ -0,0 +1,115 @@
+"""
+Tests for incremental scans: parsed programs and detector results reused
+from the workspace's .baskerville/cache while its files are unchanged.
+"""
+
+import json
+from pathlib import Path
+
+from click.testing import CliRunner
+
+from commands.scan import scan
+from extensions.ir import ProgramCache, load_programs, use_program_cache
+from extensions.static import ScanCache, StaticAnalysisPipeline
+
+SOURCE = """use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod {name} {{
+    use super::*;
+
+    pub fn set_fee(ctx: Context<SetFee>, fee: u64) -> Result<()> {{
+        ctx.accounts.config.fee = fee;
+        Ok(())
+    }}
+}}
+
+#[derive(Accounts)]
+pub struct SetFee<'info> {{
+    #[account(mut, has_one = admin)]
+    pub config: Account<'info, Config>,
+    /// CHECK: config admin
+    pub admin: AccountInfo<'info>,
+}}
+"""
+
+TOOLS = ["missing-signer", "type-cosplay", "arbitrary-cpi"]
+
+
+PROGRAMS = {name: SOURCE.format(name=name) for name in ("vault", "treasury")}
+
+
+def _scan(workspace: Path):
+    pipeline = StaticAnalysisPipeline(chain_id="solana", cache=ScanCache(workspace))
+    events = []
+    result = pipeline.run(workspace, tools=TOOLS, progress=events.append)
+    return result, [e for e in events if e.event == "detector_completed"]
+
+
+class TestScanCache:
+    """Test reusing programs and detector results between scans."""
+
+    def test_program_cache(self, tmp_path, anchor_workspace):
+        workspace = anchor_workspace(tmp_path / "ws", PROGRAMS, localnet=["vault"])
+        cache = ProgramCache(tmp_path / "programs")
+        with use_program_cache(cache):
+            first = load_programs(workspace)
+            # Crate discovery for each front-end, then each crate
+            assert cache.misses == 5 and cache.hits == 0
+            assert [p.name for p in load_programs(workspace)] == [p.name for p in first]
+            assert cache.hits == 5
+            # A copy: annotating one load does not change the next
+            first[0].name = "changed"
+            assert "changed" not in [p.name for p in load_programs(workspace)]
+
+        # On disk, for the next process
+        again = ProgramCache(tmp_path / "programs")
+        with use_program_cache(again):
+            load_programs(workspace)
+        assert again.hits == 5 and again.misses == 0
+
+    def test_replay(self, tmp_path, anchor_workspace):
+        workspace = anchor_workspace(tmp_path / "ws", PROGRAMS, localnet=["vault"])
+        first, events = _scan(workspace)
+        assert first.hypotheses and not any(e.cached for e in events)
+        assert first.metadata["cache"]["detectors_replayed"] == 0
+        assert (workspace / ".baskerville" / "cache" / ".gitignore").is_file()
+
+        second, events = _scan(workspace)
+        assert [e.detector for e in events if e.cached] == TOOLS
+        assert second.metadata["cache"]["detectors_replayed"] == len(TOOLS)
+        assert second.metadata["cache"]["programs_parsed"] == 0
+        assert second.hypotheses == first.hypotheses
+
+    def test_edit_one_crate(self, tmp_path, anchor_workspace):
+        workspace = anchor_workspace(tmp_path / "ws", PROGRAMS, localnet=["vault"])
+        _scan(workspace)
+        lib = workspace / "programs" / "treasury" / "src" / "lib.rs"
+        lib.write_text(lib.read_text().replace("/// CHECK: config admin\n    pub admin: AccountInfo",
+                                               "pub admin: Signer"))
+
+        result, events = _scan(workspace)
+        assert not any(e.cached for e in events)
+        cache = result.metadata["cache"]
+        # Only treasury and crate discovery (once per front-end) are redone, by the first detector
+        assert cache["programs_parsed"] == 4 and cache["programs_reused"] > 0
+        titles = [(h["title"], h["properties"]["source_files"]) for h in result.hypotheses
+                  if h["properties"]["source_tool"] == "missing-signer"]
+        assert len(titles) == 1 and "vault" in str(titles[0][1])
+
+    def test_cli(self, tmp_path, anchor_workspace):
+        workspace = anchor_workspace(tmp_path / "ws", PROGRAMS, localnet=["vault"])
+        runner = CliRunner()
+        args = [str(workspace), "--tool", "missing-signer", "--json"]
+        first = runner.invoke(scan, args)
+        assert first.exit_code == 0, first.output
+        second = runner.invoke(scan, args)
+        assert second.exit_code == 0, second.output
+        assert json.loads(second.output)["hypotheses"] == json.loads(first.output)["hypotheses"]
+
+        fresh = anchor_workspace(tmp_path / "fresh", PROGRAMS, localnet=["vault"])
+        result = runner.invoke(scan, [str(fresh), "--tool", "missing-signer", "--json", "--no-cache"])
+        assert result.exit_code == 0, result.output
+        assert json.loads(result.output)["hypotheses"] and not (fresh / ".baskerville").exists()
@@ -0,0 +1,80 @@
+"""
+Tests for the top-level `scan` command: chain detection, detector selection,
+hit listing and saving results for a workspace that is not a project, and
+choice options checked when the CLI wrapper invokes it.
+"""
+
+import json
+
+import click
+import pytest
+from click.testing import CliRunner
+
+from commands.scan import scan
+
+
+SOURCE = """use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn set_fee(ctx: Context<SetFee>, fee: u64) -> Result<()> {
+        ctx.accounts.config.fee = fee;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetFee<'info> {
+    #[account(mut, has_one = admin)]
+    pub config: Account<'info, Config>,
+    /// CHECK: config admin
+    pub admin: AccountInfo<'info>,
+}
+"""
+
+
+class TestScanCommand:
+    """Test scanning an Anchor workspace without a project."""
+
+    def test_scan(self, tmp_path, anchor_workspace):
+        workspace = anchor_workspace(tmp_path / "vault", {"vault": SOURCE}, localnet=["vault"])
+        runner = CliRunner()
+
+        result = runner.invoke(scan, [str(workspace), "--tool", "missing-signer", "--json"])
+        assert result.exit_code == 0, result.output
+        report = json.loads(result.output)
+        assert report["chain"] == "solana" and list(report["tools"]) == ["missing-signer"]
+        assert [h["title"] for h in report["hypotheses"]] == ["Missing signer check on `admin` in set_fee"]
+
+        out = tmp_path / "results"
+        result = runner.invoke(scan, [str(workspace), "--tool", "missing-signer", "-o", str(out)])
+        assert result.exit_code == 0, result.output
+        assert "Missing signer check" in result.output and "1 hits" in result.output
+        assert len(json.loads((out / "static_hypotheses.json").read_text())) == 1
+
+        # Dropped by the confidence floor
+        result = runner.invoke(scan, [str(workspace), "--tool", "missing-signer", "--min-confidence", "0.99"])
+        assert result.exit_code == 0 and "No hits" in result.output
+
+    def test_errors(self, tmp_path, anchor_workspace):
+        workspace = anchor_workspace(tmp_path / "vault", {"vault": SOURCE}, localnet=["vault"])
+        runner = CliRunner()
+        result = runner.invoke(scan, [str(workspace), "--tool", "slither"])
+        assert result.exit_code == 1 and "Unknown detector(s) for solana: slither" in result.output
+        result = runner.invoke(scan, [str(workspace), "--chain", "cosmwasm"])
+        assert result.exit_code == 1 and "No detectors for chain 'cosmwasm'" in result.output
+        assert runner.invoke(scan, [str(tmp_path / "missing")]).exit_code == 2
+
+    def test_wrapper_choices(self, tmp_path, anchor_workspace):
+        # The typer wrapper passes ready-made params, and a bad choice must still fail before the scan
+        pytest.importorskip("typer")
+        from hound import _invoke_click
+
+        workspace = anchor_workspace(tmp_path / "vault", {"vault": SOURCE}, localnet=["vault"])
+        for option, value in (("fail_on", "High"), ("output_format", "xml"), ("github_mode", "issues")):
+            with pytest.raises(click.BadParameter, match=f"'{value}' is not one of"):
+                _invoke_click(scan, {"path": str(workspace), "chain": None, "tools": (), option: value})
@@ -0,0 +1,141 @@
+"""
+Tests for scan progress: the pipeline's progress events, the event log API
+clients follow, and streaming them from the server to the CLI.
+"""
+
+import threading
+
+from click.testing import CliRunner
+
+import commands.server as server_cli
+from extensions.server import ApiClient, ApiError, ApiServer, ScanManager, TenantStore
+from extensions.static import ProgressEvent, ScanProgress, StaticAnalysisPipeline
+
+
+class _Finding:
+    def __init__(self, title: str):
+        self.title = title
+
+    def to_hypothesis(self) -> dict:
+        return {"title": self.title, "vulnerability_type": self.title, "severity": "high", "confidence": 0.5,
+                "properties": {"source_files": [f"{self.title}.sol"], "affected_lines": [1]}}
+
+
+class _Runner:
+    def __init__(self, findings: int, available: bool = True):
+        self.findings, self.available = findings, available
+
+    def is_available(self):
+        return (True, "1.0") if self.available else (False, "not installed")
+
+    def run(self, path):
+        return [_Finding(f"bug{i}") for i in range(self.findings)], {"success": True}
+
+
+class TestScanProgress:
+    """Test pipeline progress events and streaming them over the API."""
+
+    def test_pipeline_events(self, tmp_path):
+        (tmp_path / "src").mkdir()
+        (tmp_path / "src" / "Vault.sol").write_text("contract Vault {}\n")
+        (tmp_path / "src" / "Token.sol").write_text("contract Token {}\n")
+        (tmp_path / "node_modules").mkdir()
+        (tmp_path / "node_modules" / "Dep.sol").write_text("contract Dep {}\n")
+
+        pipeline = StaticAnalysisPipeline()
+        pipeline.runners = {"slither": _Runner(2), "aderyn": _Runner(0, available=False), "extra": _Runner(1)}
+        events: list[ProgressEvent] = []
+        result = pipeline.run(tmp_path, tools=["slither", "aderyn"], progress=events.append)
+
+        assert [(e.event, e.detector) for e in events] == [
+            ("started", None), ("files", None), ("detector_started", "slither"),
+            ("detector_completed", "slither"), ("detector_skipped", "aderyn"), ("finished", None)]
+        assert all(e.detectors_total == 2 for e in events)
+        assert events[1].files_parsed == 2
+        assert (events[3].detectors_completed, events[3].findings) == (1, 2)
+        assert events[-1].to_dict()["hypotheses"] == len(result.hypotheses) == 2
+        assert "error" not in events[-1].to_dict() and events[4].error == "not installed"
+
+        # No callback, no file walk, same result
+        assert len(pipeline.run(tmp_path, tools=["slither"]).hypotheses) == 2
+
+    def test_follow(self):
+        progress = ScanProgress()
+        progress.emit(ProgressEvent("started", detectors_total=1))
+        seen = []
+        reader = threading.Thread(target=lambda: seen.extend(e.event for e in progress.follow(1, timeout=5)))
+        reader.start()
+        progress.emit(ProgressEvent("detector_started", detector="slither"))
+        progress.emit(ProgressEvent("finished"))
+        reader.join(5)
+        assert seen == ["detector_started", "finished"] and progress.done
+        assert [e.seq for e in progress.events()] == [0, 1, 2]
+        assert list(progress.follow(3)) == [] and [e.event for e in progress.follow(2)] == ["finished"]
+
+    def test_stream(self, tmp_path):
+        store = TenantStore(tmp_path / "server")
+        store.add_tenant("acme")
+        store.add_tenant("globex")
+        source = store.storage_dir("acme") / "sources" / "vault"
+        source.mkdir(parents=True)
+        (source / "Vault.sol").write_text("contract Vault {}\n")
+        store.projects("acme").create_project("vault", str(source), chain="evm")
+        _, triager = store.create_key("acme", "triager")
+        _, viewer = store.create_key("acme", "viewer")
+        _, other = store.create_key("globex", "admin")
+
+        release = threading.Event()
+
+        def run(project, tools, progress):
+            progress(ProgressEvent("started", detectors_total=1))
+            progress(ProgressEvent("detector_started", detectors_total=1, files_parsed=1, detector=tools[0]))
+            release.wait(5)
+            if tools == ["boom"]:
+                raise RuntimeError("detector crashed")
+            progress(ProgressEvent("detector_completed", detectors_total=1, detectors_completed=1,
+                                   files_parsed=1, findings=3, detector=tools[0]))
+            progress(ProgressEvent("finished", detectors_total=1, detectors_completed=1, files_parsed=1,
+                                   findings=3, hypotheses=2))
+
+        api = ApiServer(store, port=0, scans=ScanManager(run))
+        api.start()
+        url = "http://%s:%d" % api.address
+        try:
+            client = ApiClient(url, triager, timeout=10)
+            scan = client.start_scan("vault", ["slither"])
+            assert scan["project"] == "vault" and scan["tools"] == ["slither"]
+
+            try:
+                ApiClient(url, viewer).start_scan("vault")
+                raise AssertionError("viewer started a scan")
+            except ApiError as e:
+                assert e.status == 403
+            try:
+                ApiClient(url, other).request("GET", f"/projects/vault/scans/{scan['id']}")
+                raise AssertionError("other tenant saw the scan")
+            except ApiError as e:
+                assert e.status == 404
+
+            stream = client.scan_events("vault", scan["id"])
+            assert next(stream)["event"] == "started"
+            assert next(stream)["detector"] == "slither"
+            assert client.request("GET", f"/projects/vault/scans/{scan['id']}")["status"] == "running"
+            release.set()
+            rest = list(stream)
+            assert [e["event"] for e in rest] == ["detector_completed", "finished"]
+            assert rest[-1]["findings"] == 3 and rest[-1]["seq"] == 3
+            assert client.request("GET", "/projects/vault/scans")[0]["status"] == "completed"
+            assert [e["event"] for e in client.scan_events("vault", scan["id"], since=3)] == ["finished"]
+
+            # The CLI renders the same stream and reports the outcome
+            runner = CliRunner()
+            done = runner.invoke(server_cli.scan, ["vault", "--url", url, "--key", triager, "--tool", "slither"])
+            assert done.exit_code == 0, done.output
+            assert "3 findings" in done.output and "2 unique hypotheses" in done.output
+            failed = runner.invoke(server_cli.scan, ["vault", "--url", url, "--key", triager, "--tool", "boom"])
+            assert failed.exit_code == 1 and "detector crashed" in failed.output
+            job = client.request("GET", "/projects/vault/scans")[-1]
+            assert job["status"] == "failed" and job["progress"]["error"] == "RuntimeError: detector crashed"
+            assert job["progress"]["files_parsed"] == 1
+        finally:
+            api.stop()
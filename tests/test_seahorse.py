@@ -0,0 +1,92 @@
+"""
+Tests for Seahorse ingestion and the generated-Rust → Python line map.
+"""
+
+from pathlib import Path
+
+from extensions.ir import SeahorseFrontend, SeahorseSourceMap
+
+
+PROJECT = Path(__file__).resolve().parent / "fixtures" / "solana" / "seahorse_calculator"
+PY_SOURCE = (PROJECT / "programs_py" / "calculator.py").read_text().split("\n")
+RUST_HANDLERS = "src/dot/program.rs"
+
+
+def _py_line_containing(text: str) -> int:
+    return next(i for i, line in enumerate(PY_SOURCE, 1) if text in line)
+
+
+def _rust_line_containing(text: str, rel: str = RUST_HANDLERS) -> int:
+    lines = (PROJECT / "programs" / "calculator" / rel).read_text().split("\n")
+    return next(i for i, line in enumerate(lines, 1) if text in line)
+
+
+class TestSeahorseFrontend:
+    """Test loading Seahorse projects through generated Anchor code."""
+
+    def setup_method(self):
+        programs = SeahorseFrontend().load(PROJECT)
+        assert len(programs) == 1
+        self.program = programs[0]
+        self.source_map = SeahorseSourceMap.from_program(self.program)
+
+    def test_detection(self, tmp_path):
+        assert SeahorseFrontend.is_seahorse_project(PROJECT)
+        assert not SeahorseFrontend.is_seahorse_project(tmp_path)
+
+    def test_program_loaded_from_generated_rust(self):
+        assert self.program.framework == "seahorse"
+        assert self.program.metadata["python_source"] == "programs_py/calculator.py"
+        names = [ix.name for ix in self.program.instructions]
+        assert names == ["init_calculator", "do_operation"]
+
+    def test_handlers_followed_into_dot_program(self):
+        ix = self.program.instruction("do_operation")
+        assert ix.source_file == RUST_HANDLERS
+        assert "panic!" in ix.body
+        assert ix.properties["python_line"] == _py_line_containing("def do_operation")
+
+    def test_statement_lines_map_back(self):
+        assert self.source_map.lookup(RUST_HANDLERS, _rust_line_containing("panic!")) == (
+            "programs_py/calculator.py",
+            _py_line_containing("assert owner.key()"),
+        )
+        sub_line = _rust_line_containing("display - num")
+        assert self.source_map.lookup(RUST_HANDLERS, sub_line)[1] == _py_line_containing("display -= num")
+
+    def test_account_fields_map_to_parameters(self):
+        ix = self.program.instruction("do_operation")
+        calc = ix.account("calculator")
+        mapped = self.source_map.lookup("src/lib.rs", calc.line)
+        assert mapped[1] == _py_line_containing("calculator: Calculator,")
+
+    def test_account_struct_maps_to_class(self):
+        line = _rust_line_containing("pub display: i64")
+        assert self.source_map.lookup(RUST_HANDLERS, line)[1] == _py_line_containing("display: i64")
+
+    def test_remap_hypothesis(self):
+        rust_line = _rust_line_containing("display + num")
+        hyp = {
+            "title": "Unchecked addition",
+            "properties": {
+                "source_files": [f"programs/calculator/{RUST_HANDLERS}"],
+                "affected_lines": [rust_line],
+            },
+        }
+        self.source_map.remap_hypothesis(hyp)
+        props = hyp["properties"]
+        assert props["source_files"] == ["programs_py/calculator.py"]
+        assert props["affected_lines"] == [_py_line_containing("display += num")]
+        assert props["generated_source"]["affected_lines"] == [rust_line]
+
+    def test_unrelated_hypothesis_unchanged(self):
+        hyp = {"properties": {"source_files": ["other/lib.rs"], "affected_lines": [3]}}
+        self.source_map.remap_hypothesis(hyp)
+        assert hyp["properties"]["source_files"] == ["other/lib.rs"]
+
+    def test_missing_generated_code(self, tmp_path):
+        (tmp_path / "programs_py").mkdir()
+        (tmp_path / "programs_py" / "demo.py").write_text("from seahorse.prelude import *\n")
+        programs = SeahorseFrontend().load(tmp_path)
+        assert programs[0].instructions == []
+        assert "seahorse build" in programs[0].metadata["error"]
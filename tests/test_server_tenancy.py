@@ -0,0 +1,135 @@
+"""
+Tests for multi-tenant server mode: tenants, hashed API keys, roles, and the
+JSON API's isolation of one tenant's projects from another's.
+"""
+
+import json
+import urllib.error
+import urllib.request
+
+import pytest
+from click.testing import CliRunner
+
+import commands.server as server_cli
+from extensions.server import ApiServer, Role, TenancyError, TenantStore
+
+
+def _store(tmp_path) -> TenantStore:
+    store = TenantStore(tmp_path / "server")
+    store.add_tenant("acme", "Acme Lending")
+    store.add_tenant("globex")
+    return store
+
+
+def _project(store: TenantStore, tenant: str, name: str, hypotheses: dict) -> None:
+    source = store.storage_dir(tenant) / "sources" / name
+    source.mkdir(parents=True)
+    (source / "lib.sol").write_text("contract Vault {}\n")
+    config = store.projects(tenant).create_project(name, str(source), chain="evm")
+    (store.projects_dir(tenant) / config["name"] / "hypotheses.json").write_text(
+        json.dumps({"hypotheses": hypotheses}))
+
+
+class TestServerTenancy:
+    """Test the tenant store, roles and the API."""
+
+    def test_store(self, tmp_path):
+        store = _store(tmp_path)
+        record, secret = store.create_key("acme", "Triager", "triage team")
+        assert record.role is Role.TRIAGER and secret.startswith(f"bsk_{record.id}_")
+        assert secret not in store.file.read_text()
+        tenant, key = store.authenticate(secret)
+        assert (tenant.id, key.id) == ("acme", record.id)
+        assert store.authenticate(secret + "x") is None and store.authenticate("nonsense") is None
+
+        assert Role.ADMIN.allows(Role.TRIAGER) and not Role.VIEWER.allows(Role.TRIAGER)
+        with pytest.raises(TenancyError, match="unknown role"):
+            store.create_key("acme", "owner")
+        with pytest.raises(TenancyError, match="invalid tenant id"):
+            store.add_tenant("../escape")
+        with pytest.raises(TenancyError, match="already exists"):
+            store.add_tenant("acme")
+
+        # Revoking only works within the key's own tenant
+        assert not store.revoke_key(record.id, "globex") and store.authenticate(secret)
+        assert store.revoke_key(record.id, "acme") and store.authenticate(secret) is None
+        _, other = store.create_key("globex", "viewer")
+        assert store.remove_tenant("globex") and store.authenticate(other) is None
+
+    def test_api_isolation(self, tmp_path):
+        store = _store(tmp_path)
+        _project(store, "acme", "vault", {"h1": {"title": "Reentrancy", "status": "proposed"}})
+        _project(store, "globex", "bridge", {"h9": {"title": "Replay", "status": "proposed"}})
+        keys = {role: store.create_key("acme", role)[1] for role in ("viewer", "triager", "admin")}
+        _, globex_admin = store.create_key("globex", "admin")
+
+        api = ApiServer(store, port=0)
+        api.start()
+        host, port = api.address
+
+        def call(method, path, key=None, body=None):
+            data = json.dumps(body).encode() if body is not None else None
+            request = urllib.request.Request(f"http://{host}:{port}{path}", data=data, method=method)
+            if key:
+                request.add_header("Authorization", f"Bearer {key}")
+            try:
+                with urllib.request.urlopen(request) as response:
+                    return response.status, json.loads(response.read())
+            except urllib.error.HTTPError as e:
+                return e.code, json.loads(e.read())
+
+        try:
+            assert call("GET", "/v1/projects")[0] == 401
+            status, me = call("GET", "/v1/whoami", keys["viewer"])
+            assert status == 200 and me["tenant"]["id"] == "acme" and me["role"] == "viewer"
+            assert [p["name"] for p in call("GET", "/v1/projects", keys["viewer"])[1]] == ["vault"]
+            assert "h1" in call("GET", "/v1/projects/vault/hypotheses", keys["viewer"])[1]
+
+            # Another tenant's project does not exist for this key, whatever its role
+            assert call("GET", "/v1/projects/bridge/hypotheses", keys["admin"])[0] == 404
+            assert call("GET", "/v1/projects/vault", globex_admin)[0] == 404
+            assert call("GET", "/v1/projects/..%2F..%2Fglobex", keys["admin"])[0] == 404
+
+            path = "/v1/projects/vault/hypotheses/h1/status"
+            assert call("POST", path, keys["viewer"], {"status": "confirmed"})[0] == 403
+            assert call("POST", path, keys["triager"], {"status": "maybe"})[0] == 400
+            status, hyp = call("POST", path, keys["triager"], {"status": "confirmed"})
+            assert status == 201 and hyp["status"] == "confirmed" and hyp["status_history"][0]["by"]
+
+            # Key management is admin-only and scoped to the tenant
+            assert call("POST", "/v1/keys", keys["triager"], {"role": "admin"})[0] == 403
+            status, created = call("POST", "/v1/keys", keys["admin"], {"role": "viewer", "label": "client"})
+            assert status == 201 and call("GET", "/v1/whoami", created["key"])[1]["tenant"]["id"] == "acme"
+            assert all(k["tenant"] == "acme" and "key_hash" not in k for k in call("GET", "/v1/keys", keys["admin"])[1])
+            globex_key = store.list_keys("globex")[0].id
+            assert call("DELETE", f"/v1/keys/{globex_key}", keys["admin"])[0] == 404
+            assert call("DELETE", f"/v1/keys/{created['id']}", keys["admin"])[0] == 200
+            assert call("GET", "/v1/whoami", created["key"])[0] == 401
+
+            # Sources must come from the tenant's own sources directory
+            escape = {"name": "stolen", "source": "../../globex/sources/bridge", "chain": "evm"}
+            assert call("POST", "/v1/projects", keys["admin"], escape)[0] == 400
+            (store.storage_dir("acme") / "sources" / "amm").mkdir()
+            status, config = call("POST", "/v1/projects", keys["admin"], {"name": "amm", "source": "amm",
+                                                                           "chain": "evm"})
+            assert status == 201 and config["name"] == "amm"
+            assert (store.projects_dir("acme") / "amm" / "project.json").exists()
+        finally:
+            api.stop()
+
+    def test_cli(self, tmp_path):
+        runner = CliRunner()
+        data_dir = str(tmp_path / "server")
+        assert runner.invoke(server_cli.tenant_add, ["acme", "--data-dir", data_dir]).exit_code == 0
+        assert runner.invoke(server_cli.tenant_add, ["Bad Id", "--data-dir", data_dir]).exit_code == 1
+        result = runner.invoke(server_cli.key_create, ["acme", "--role", "admin", "--data-dir", data_dir])
+        assert result.exit_code == 0, result.output
+        secret = next(line for line in result.output.splitlines() if line.startswith("bsk_"))
+        assert TenantStore(tmp_path / "server").authenticate(secret)[1].role is Role.ADMIN
+
+        result = runner.invoke(server_cli.keys, ["--json", "--data-dir", data_dir])
+        (key,) = json.loads(result.output)
+        assert key["tenant"] == "acme" and "key_hash" not in key
+        assert runner.invoke(server_cli.key_revoke, [key["id"], "--data-dir", data_dir]).exit_code == 0
+        assert runner.invoke(server_cli.key_revoke, ["nope", "--data-dir", data_dir]).exit_code == 1
+        assert runner.invoke(server_cli.key_create, ["nobody", "--data-dir", data_dir]).exit_code == 1
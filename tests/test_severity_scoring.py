@@ -0,0 +1,96 @@
+"""
+Tests for severity scoring: the rubric and its YAML overrides, scores from
+finding and class attributes, and ranking findings reports by score.
+"""
+
+import json
+from unittest.mock import patch
+
+import pytest
+from click.testing import CliRunner
+
+from commands.reporting import reporting
+from extensions.knowledge.class_loader import ClassLoader
+from extensions.reporting.formatters import build_findings_report, get_formatter
+from extensions.reporting.scoring import CLASS_VECTORS, SeverityRubric, load_severity_rubric
+
+FINDINGS = {
+    # Declared high by its template; anyone can call it in one transaction
+    "hyp_signer": {"title": "Admin is not a signer", "severity": "high", "confidence": 0.6,
+                   "vulnerability_type": "missing-signer-check"},
+    # Declared critical, but only the admin can reach it
+    "hyp_admin": {"title": "Admin can drain the vault", "severity": "critical", "confidence": 0.9,
+                  "vulnerability_type": "missing-signer-check",
+                  "properties": {"severity_vector": {"privileges": "privileged"}}},
+    "hyp_oracle": {"title": "Spot price used for collateral", "severity": "medium", "confidence": 0.9,
+                   "vulnerability_type": "price-manipulation"},
+    "hyp_note": {"title": "Event not emitted", "severity": "low", "confidence": 0.4},
+}
+
+
+class TestSeverityScoring:
+    """Test scoring findings and ranking reports by score."""
+
+    def test_rubric(self, tmp_path):
+        rubric = SeverityRubric()
+        assert {c.id for c in ClassLoader().list_all()} == set(CLASS_VECTORS)
+        signer = rubric.score(FINDINGS["hyp_signer"], "unauthenticated-privileged-action")
+        assert (signer.score, signer.severity, signer.basis) == (10.0, "critical", "vector")
+        assert signer.sources == {"impact": "class", "privileges": "class", "complexity": "class"}
+        admin = rubric.score(FINDINGS["hyp_admin"], "unauthenticated-privileged-action")
+        assert (admin.score, admin.severity, admin.sources["privileges"]) == (3.0, "low", "finding")
+        # Nothing known about it: its declared severity stands
+        note = rubric.score(FINDINGS["hyp_note"])
+        assert (note.severity, note.basis, note.score) == ("low", "reported", 0.1)
+
+        path = tmp_path / "rubric.yaml"
+        path.write_text("metrics:\n  complexity: {high: 0.6}\n  scope: {changed: 1.0, unchanged: 0.9}\n"
+                        "defaults: {scope: unchanged}\nclasses:\n  price-manipulation: {impact: frozen}\n"
+                        "bands: {critical: 9.5}\n")
+        custom = load_severity_rubric(path)
+        scored = custom.score(FINDINGS["hyp_oracle"], "price-manipulation")
+        assert scored.vector["scope"] == "unchanged" and scored.score == round(10 * 0.7 * 0.6 * 0.9, 1)
+        assert custom.band(9.4) == "high" and list(custom.bands)[0] == "critical"
+        for text, error in [("weights: {}", "unknown severity rubric keys"),
+                            ("classes:\n  share-inflation: {impact: everything}", "unknown impact level"),
+                            ("defaults: {reach: far}", "unknown attribute reach"),
+                            ("bands: {severe: 5}", "unknown severities"),
+                            ("metrics:\n  impact: {funds: lots}", "must be numbers")]:
+            path.write_text(text)
+            with pytest.raises(ValueError, match=error):
+                load_severity_rubric(path)
+
+    def test_report_ranking(self):
+        report = build_findings_report(FINDINGS, "vault", "solana", classes=ClassLoader(), rubric=SeverityRubric())
+        entries = {e["id"]: e for e in report["findings"]}
+        assert [e["id"] for e in report["findings"]] == ["hyp_signer", "hyp_oracle", "hyp_admin", "hyp_note"]
+        assert entries["hyp_signer"]["severity"] == "critical" and entries["hyp_signer"]["reported_severity"] == "high"
+        assert entries["hyp_oracle"]["score"]["score"] == 8.0 and entries["hyp_oracle"]["severity"] == "high"
+        assert report["counts"]["critical"] == 1 and report["counts"]["low"] == 2
+        markdown = get_formatter("markdown").format(report)
+        assert "- **Score:** 10.0 (impact funds, privileges none, complexity low)" in markdown
+
+        # Without a rubric findings keep their declared severities
+        plain = build_findings_report(FINDINGS, "vault", "solana", classes=ClassLoader())
+        assert plain["findings"][0]["id"] == "hyp_admin" and plain["findings"][0]["score"] is None
+
+    def test_cli(self, tmp_path):
+        (tmp_path / "hypotheses.json").write_text(json.dumps({"version": "1.0", "hypotheses": FINDINGS}))
+        (tmp_path / "rubric.yaml").write_text("classes:\n  price-manipulation: {complexity: low}\n")
+        runner = CliRunner()
+        with patch("commands.reporting.ProjectManager") as PM:
+            PM.return_value.get_project.return_value = {"path": str(tmp_path), "chain_id": "solana"}
+            out = tmp_path / "reports" / "findings.json"
+            result = runner.invoke(reporting, ["findings", "demo", "--format", "json", "--no-static",
+                                               "--rubric", str(tmp_path / "rubric.yaml")])
+            assert result.exit_code == 0, result.output
+            data = json.loads(out.read_text())
+            assert [f["severity"] for f in data["findings"][:2]] == ["critical", "critical"]
+
+            result = runner.invoke(reporting, ["findings", "demo", "--format", "json", "--reported-severity"])
+            assert result.exit_code == 0, result.output
+            assert json.loads(out.read_text())["findings"][0]["id"] == "hyp_admin"
+
+            (tmp_path / "bad.yaml").write_text("bands: {severe: 5}\n")
+            result = runner.invoke(reporting, ["findings", "demo", "--rubric", str(tmp_path / "bad.yaml")])
+            assert result.exit_code == 1 and "unknown severities" in result.output
@@ -0,0 +1,105 @@
+"""
+Tests for detached signing of report artifacts.
+"""
+
+import base64
+import json
+import subprocess
+
+import pytest
+
+pytest.importorskip("cryptography")
+
+from extensions.reporting import generate_keypair, sign_files, verify_envelope
+from extensions.reporting.signing import SigningError, read_statement, ruleset_version, source_commit
+
+
+class TestSigning:
+    """Test sign/verify round trips and tamper detection."""
+
+    def _keys(self, tmp_path, name="signing-key"):
+        private, public = tmp_path / "keys" / f"{name}.pem", tmp_path / "keys" / f"{name}.pub.pem"
+        generate_keypair(private, public)
+        return private, public
+
+    def _deliverable(self, tmp_path):
+        out = tmp_path / "delivery"
+        (out / "findings").mkdir(parents=True)
+        (out / "report.html").write_text("<h1>Report</h1>")
+        (out / "findings" / "H-01-missing-signer.md").write_text("# [H-01] Missing signer\n")
+        return [out / "report.html", out / "findings" / "H-01-missing-signer.md"]
+
+    def test_roundtrip_multiple_files(self, tmp_path):
+        private, public = self._keys(tmp_path)
+        files = self._deliverable(tmp_path)
+        envelope = sign_files(files, private, ruleset="sha256:test")
+        assert envelope.name == "SIGNATURES.sig.json"
+
+        result = verify_envelope(envelope, public)
+        assert result.valid, result.errors
+        assert result.verified_files == ["report.html", "findings/H-01-missing-signer.md"]
+        assert result.statement["predicate"]["ruleset_version"] == "sha256:test"
+
+    def test_single_file_default_name(self, tmp_path):
+        private, public = self._keys(tmp_path)
+        report = self._deliverable(tmp_path)[0]
+        envelope = sign_files([report], private)
+        assert envelope == report.with_name("report.html.sig.json")
+        assert verify_envelope(envelope, public).valid
+
+    def test_detects_modified_and_missing_files(self, tmp_path):
+        private, public = self._keys(tmp_path)
+        files = self._deliverable(tmp_path)
+        envelope = sign_files(files, private)
+
+        files[0].write_text("<h1>Report</h1><p>No issues found.</p>")
+        files[1].unlink()
+        result = verify_envelope(envelope, public)
+        assert not result.valid
+        assert "Modified file: report.html" in result.errors
+        assert "Missing file: findings/H-01-missing-signer.md" in result.errors
+
+    def test_rejects_wrong_key_and_edited_statement(self, tmp_path):
+        private, public = self._keys(tmp_path)
+        _, other_public = self._keys(tmp_path, "other")
+        envelope = sign_files(self._deliverable(tmp_path), private)
+        assert not verify_envelope(envelope, other_public).valid
+
+        # Editing the statement (e.g. the rule-set version) breaks the signature
+        data = json.loads(envelope.read_text())
+        statement = read_statement(envelope)
+        statement["predicate"]["ruleset_version"] = "sha256:forged"
+        data["payload"] = base64.b64encode(json.dumps(statement).encode()).decode()
+        envelope.write_text(json.dumps(data))
+        result = verify_envelope(envelope, public)
+        assert not result.valid
+        assert result.errors == ["No valid signature for this public key"]
+
+    def test_embeds_source_commit(self, tmp_path):
+        private, public = self._keys(tmp_path)
+        repo = tmp_path / "src"
+        repo.mkdir()
+        (repo / "lib.rs").write_text("fn main() {}\n")
+        git = ["git", "-C", str(repo), "-c", "user.email=a@b", "-c", "user.name=a"]
+        subprocess.run(git + ["init", "-q"], check=True)
+        subprocess.run(git + ["add", "."], check=True)
+        subprocess.run(git + ["commit", "-qm", "init"], check=True)
+        head = subprocess.run(git + ["rev-parse", "HEAD"], capture_output=True, text=True).stdout.strip()
+
+        envelope = sign_files(self._deliverable(tmp_path), private, source=repo)
+        predicate = verify_envelope(envelope, public).statement["predicate"]
+        assert predicate["source"] == {"commit": head, "dirty": False}
+        assert source_commit(tmp_path / "delivery") is None
+
+    def test_ruleset_version_tracks_content(self, tmp_path):
+        (tmp_path / "checklists").mkdir()
+        (tmp_path / "checklists" / "solana.yaml").write_text("items: []\n")
+        first = ruleset_version(tmp_path)
+        assert first.startswith("sha256:") and len(first) == len("sha256:") + 16
+        (tmp_path / "checklists" / "solana.yaml").write_text("items: [signer]\n")
+        assert ruleset_version(tmp_path) != first
+
+    def test_sign_requires_existing_files(self, tmp_path):
+        private, _ = self._keys(tmp_path)
+        with pytest.raises(SigningError):
+            sign_files([tmp_path / "missing.html"], private)
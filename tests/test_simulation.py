@@ -0,0 +1,232 @@
+"""
+Tests for the agent-based economic simulation.
+"""
+
+import json
+import random
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+import pytest
+import yaml
+from click.testing import CliRunner
+
+from commands.simulate import run as run_cmd
+from extensions.simulation import (
+    Action,
+    BackendError,
+    Clock,
+    EpochSchedule,
+    ExecutionBackend,
+    Instruction,
+    LendingMarket,
+    Liquidator,
+    LiteSvmBackend,
+    OracleModel,
+    PriceModel,
+    Simulation,
+    SimulationConfig,
+    SvmProtocol,
+    TxResult,
+    World,
+    load_simulation,
+)
+
+CONFIG = Path(__file__).parent / "fixtures" / "simulation" / "lending.yaml"
+
+
+class FakeBackend:
+    """Records what an SvmProtocol adapter sends."""
+
+    def __init__(self):
+        self.sent = []
+        self.slots = []
+        self.started = self.closed = False
+
+    def start(self):
+        self.started = True
+
+    def close(self):
+        self.closed = True
+
+    def warp_to_slot(self, slot):
+        self.slots.append(slot)
+
+    def send(self, instructions, payer, signers=()):
+        self.sent.append((instructions, payer, signers))
+        return TxResult(ok=len(self.sent) == 1, error=None if len(self.sent) == 1 else "custom program error: 0x1")
+
+
+class ClockBackend(ExecutionBackend):
+    """Holds a Clock sysvar, like LiteSVM's."""
+
+    def __init__(self):
+        self.clock = Clock()
+
+    def get_clock(self):
+        return Clock(**self.clock.to_dict())
+
+    def set_clock(self, clock):
+        self.clock = clock
+
+
+class Vault(SvmProtocol):
+    name = "vault"
+
+    def write_oracle(self, price):
+        self.price = price
+
+    def encode(self, action):
+        if action.kind != "open":
+            return None
+        return [Instruction("Vau1t11111111111111111111111111111111111111", data=b"\x01")]
+
+    def settle(self, action, result):
+        return {"collateral": -action.params["collateral"]}
+
+
+class TestSimulation:
+    """Test market models, agents, the engine and the CLI."""
+
+    def setup_method(self):
+        self.data = yaml.safe_load(CONFIG.read_text())
+
+    def test_oracle_model(self):
+        prices = [100.0, 100.2, 101.0, 103.0, 103.1, 103.2]
+        assert OracleModel().feed(prices) == prices
+        assert OracleModel(lag_slots=2).feed(prices) == [100.0, 100.0, 100.0, 100.2, 101.0, 103.0]
+        # Only moves of 1% or more, or every 4 slots
+        assert OracleModel(deviation=0.01, heartbeat=4).feed(prices) == [100.0, 100.0, 101.0, 103.0, 103.0, 103.0]
+        path = PriceModel(volatility=0.0, jump_probability=1.0, jump_size=-0.5).path(3, random.Random(0))
+        assert path == [100.0, 50.0, 25.0]
+
+    def test_lending_market(self):
+        market = LendingMarket({"ltv": 0.5, "liquidation_threshold": 0.6, "liquidation_bonus": 0.1})
+        market.set_price(0, 100.0)
+        assert not market.apply(Action("open", "alice", {"collateral": 10.0, "borrow": 600.0})).ok
+        opened = market.apply(Action("open", "alice", {"collateral": 10.0, "borrow": None}))
+        assert opened.deltas == {"collateral": -10.0, "quote": 500.0}
+        position = opened.data["position"]
+        assert not market.apply(Action("liquidate", "bob", {"position": position, "repay": 100.0})).ok
+        # Collateral worth less than the debt: full repayment allowed, the rest written off
+        market.set_price(1, 40.0)
+        result = market.apply(Action("liquidate", "bob", {"position": position, "repay": 500.0}))
+        assert result.deltas == {"quote": -500.0, "collateral": 10.0}
+        assert market.metrics(40.0)["bad_debt"] == 0.0
+        market.set_price(2, 100.0)
+        position = market.apply(Action("open", "alice", {"collateral": 1.0, "borrow": 50.0})).data["position"]
+        market.set_price(3, 20.0)
+        market.apply(Action("liquidate", "bob", {"position": position, "repay": 25.0}))
+        assert position not in market.positions
+        assert market.bad_debt == 25.0
+
+    def test_liquidator_declines_unprofitable(self):
+        market = LendingMarket({"liquidation_bonus": 0.0})
+        market.set_price(0, 100.0)
+        market.apply(Action("open", "alice", {"collateral": 10.0, "borrow": 750.0}))
+        market.set_price(1, 90.0)
+        liquidator = Liquidator(quote=10_000.0)
+        world = World(1, 90.0, 90.0, market.view(), 0.001, random.Random(0))
+        assert liquidator.act(world) == []
+        assert liquidator.declined == {1: (1, liquidator.declined[1][1])}
+        market.params["liquidation_bonus"] = 0.05
+        world.view = market.view()
+        assert [a.kind for a in liquidator.act(world)] == ["liquidate"]
+
+    def test_oracle_lag_attack(self):
+        config = SimulationConfig(
+            slots=60, price=PriceModel(volatility=0.0, jump_probability=1.0, jump_size=-0.01),
+            oracle=OracleModel(lag_slots=20),
+        )
+        agents = [{"type": "oracle-lag-attacker", "quote": 10_000.0, "min_edge": 0.02}]
+        result = Simulation(lambda: LendingMarket({"ltv": 0.9, "liquidation_threshold": 0.95}), agents, config).run()
+        run = result.runs[0]
+        assert run.agents["oracle-lag-attacker"]["actions"]["open"] > 0
+        assert run.extracted > 10_000.0
+        assert run.final["bad_debt"] > 0
+        kinds = {f.kind: f for f in result.findings()}
+        assert set(kinds) == {"oracle-lag-arbitrage", "bad-debt"}
+        assert kinds["oracle-lag-arbitrage"].severity == "high"
+
+    def test_config_simulation(self):
+        result = load_simulation(self.data).run()
+        assert [r.seed for r in result.runs] == [7, 8, 9]
+        assert result.params["liquidation_bonus"] == 0.02
+        findings = {f.kind: f for f in result.findings()}
+        assert set(findings) == {"oracle-lag-arbitrage", "bad-debt", "unprofitable-liquidation"}
+        assert findings["oracle-lag-arbitrage"].evidence["agents"] == ["arbitrageur"]
+        hyp = findings["bad-debt"].to_hypothesis()
+        assert hyp["vulnerability_type"] == "economic-bad-debt"
+        assert hyp["properties"]["source_tool"] == "simulation"
+        assert hyp["properties"]["simulation"]["runs"] == 3
+        # Seeded runs are reproducible
+        assert load_simulation(self.data).run().to_dict()["summary"] == result.to_dict()["summary"]
+
+    def test_svm_adapter(self):
+        backend = FakeBackend()
+        vault = Vault(backend, cap=10)
+        assert backend.started and vault.params == {"cap": 10}
+        vault.set_price(5, 1.5)
+        assert backend.slots == [5] and vault.price == 1.5
+        ok = vault.apply(Action("open", "alice", {"collateral": 2.0}))
+        assert ok.deltas == {"collateral": -2.0}
+        assert backend.sent[0][1:] == ("alice", ("alice",))
+        assert vault.apply(Action("open", "alice", {"collateral": 2.0})).error == "custom program error: 0x1"
+        assert not vault.apply(Action("swap", "alice", {})).ok
+        vault.close()
+        assert backend.closed
+        assert not LiteSvmBackend(node="no-such-node-binary").is_available()[0]
+        assert Instruction("p", data=b"\x01").to_dict()["data"] == "AQ=="
+
+    def test_cli(self, tmp_path):
+        runner = CliRunner()
+        result = runner.invoke(run_cmd, [str(CONFIG), "--runs", "1", "--json"])
+        assert result.exit_code == 0
+        data = json.loads(result.output)
+        assert data["config"]["runs"] == 1
+        assert data["manifest"]["command"]["name"] == "sim run"
+
+        with patch("commands.simulate.ProjectManager") as PM:
+            pm = MagicMock()
+            PM.return_value = pm
+            pm.get_project.return_value = {"path": str(tmp_path)}
+            result = runner.invoke(run_cmd, [str(CONFIG), "--project", "p", "--import-hypotheses"])
+        assert result.exit_code == 0
+        assert (tmp_path / "simulations" / "lending.json").exists()
+        store = json.loads((tmp_path / "hypotheses.json").read_text())["hypotheses"]
+        assert {h["created_by"] for h in store.values()} == {"simulation"}
+        assert len(store) == 3
+
+
+class TestClock:
+    """Test slot, epoch and time warps."""
+
+    def test_epoch_schedule(self):
+        schedule = EpochSchedule()
+        # Warmup epochs double from 32 slots up to the normal length
+        assert [schedule.epoch_of(slot) for slot in (0, 31, 32, 95, 96)] == [0, 0, 1, 1, 2]
+        assert schedule.first_slot(2) == 96 and schedule.first_slot(14) == schedule.first_normal_slot
+        assert schedule.epoch_of(schedule.first_normal_slot + 432_000) == 15
+        assert schedule.first_slot(20) == 524_256 + 6 * 432_000
+        flat = EpochSchedule(slots_per_epoch=100, leader_schedule_slot_offset=100, warmup=False, first_normal_epoch=0,
+                             first_normal_slot=0)
+        assert flat.epoch_of(250) == 2 and flat.leader_schedule_epoch(250) == 3
+
+    def test_warps(self):
+        backend = ClockBackend()
+        backend.set_unix_timestamp(1_700_000_000)
+        clock = backend.warp_to_slot(1_000)
+        assert (clock.slot, clock.unix_timestamp, clock.epoch) == (1_000, 1_700_000_400, 5)
+        assert clock.epoch_start_timestamp == 1_700_000_400
+        # A day past a vesting cliff: slots follow the time
+        clock = backend.advance_seconds(86_400)
+        assert clock.slot == 1_000 + 216_000 and clock.unix_timestamp == 1_700_086_800
+        clock = backend.advance_epochs(2)
+        assert clock.epoch == 14 and clock.slot == EpochSchedule().first_normal_slot
+        start = clock.epoch_start_timestamp
+        assert backend.advance(10, seconds=3_600).epoch_start_timestamp == start
+        # Stale oracle: time jumps, the slot stays
+        clock = backend.set_unix_timestamp(clock.unix_timestamp + 7_200)
+        assert backend.get_clock().slot == EpochSchedule().first_normal_slot + 10
+        with pytest.raises(BackendError, match="cannot warp back"):
+            backend.warp_to_slot(5)
@@ -0,0 +1,74 @@
+"""
+Tests for the slim library core: the front-ends, static detectors and
+knowledge base import and run with only the core dependencies installed,
+and the heavier subsystems stay behind their pyproject extras.
+"""
+
+import subprocess
+import sys
+import textwrap
+from pathlib import Path
+
+try:
+    import tomllib
+except ModuleNotFoundError:  # Python < 3.11
+    import tomli as tomllib
+
+ROOT = Path(__file__).resolve().parents[1]
+
+# Import names of the core dependencies (pyyaml, tomli)
+CORE_MODULES = {"yaml", "tomli"}
+
+SCRIPT = textwrap.dedent("""
+    import sys
+
+    class NotInstalled:
+        def find_spec(self, name, path=None, target=None):
+            top = name.split(".")[0]
+            if top not in sys.stdlib_module_names and top not in {core!r} | {{"extensions"}}:
+                raise ImportError(f"{{name}} is not a core dependency")
+
+    sys.meta_path.insert(0, NotInstalled())
+
+    from pathlib import Path
+
+    from extensions.knowledge import TemplateEngine, TemplateLoader
+    from extensions.knowledge.idl import parse_idl, template_context
+    from extensions.static import StaticAnalysisPipeline
+
+    result = StaticAnalysisPipeline(chain_id="solana").run(Path("tests/fixtures/solana/cpi_router"),
+                                                           tools=["arbitrary-cpi", "missing-signer"])
+    print("hypotheses", len(result.hypotheses))
+    idl = parse_idl({{"address": "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS",
+                      "instructions": [{{"name": "withdraw", "accounts": [{{"name": "vault"}}], "args": []}}]}})
+    context = {{**template_context(idl), "VAULT_ACCOUNT": "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin"}}
+    print("rendered", "accounts::Withdraw {{" in TemplateEngine(TemplateLoader().get("missing_signer")).render(context))
+    print("packages", sorted({{m.split(".")[1] for m in sys.modules if m.startswith("extensions.")}}))
+""").format(core=CORE_MODULES)
+
+
+class TestSlimCore:
+    """Test the core runs without the extras and the extras are declared."""
+
+    def test_core_without_extras(self):
+        result = subprocess.run([sys.executable, "-c", SCRIPT], cwd=ROOT, capture_output=True, text=True,
+                                env={"PYTHONPATH": str(ROOT), "PATH": ""}, timeout=300)
+        assert result.returncode == 0, result.stderr
+        lines = dict(line.split(" ", 1) for line in result.stdout.splitlines())
+        assert int(lines["hypotheses"]) > 0 and lines["rendered"] == "True"
+        # Execution backends, monitoring, RPC, server mode and LLM code are not loaded
+        assert lines["packages"] == "['i18n', 'ir', 'knowledge', 'static']"
+
+    def test_extras(self):
+        project = tomllib.loads((ROOT / "pyproject.toml").read_text())["project"]
+        # The library needs only PyYAML; the rest of the core is the installed commands' own dependencies
+        core = [d.split(">")[0] for d in project["dependencies"]]
+        assert core == ["pyyaml", "tomli", "typer", "click", "rich", "python-dotenv", "portalocker"]
+        extras = project["optional-dependencies"]
+        for extra in ("cli", "llm", "execution", "evm", "server", "net", "bounty", "signing"):
+            assert extra in extras
+        assert any(d.startswith("openai") for d in extras["llm"])
+        assert any(d.startswith("aiohttp") for d in extras["net"])
+        # `all` is the full CLI: every extra but the development tools
+        bundled = extras["all"][0].split("[", 1)[1].rstrip("]").split(",")
+        assert sorted(bundled) == sorted(set(extras) - {"all", "dev"})
@@ -0,0 +1,105 @@
+"""
+Tests for the Solidity front-end (solc standard-json AST → IR).
+
+Uses a pre-generated solc output for tests/fixtures/evm/vault so the
+tests don't need a solc binary.
+"""
+
+import json
+from pathlib import Path
+
+from extensions.ir import SolidityFrontend, load_programs
+
+
+PROJECT = Path(__file__).resolve().parent / "fixtures" / "evm" / "vault"
+SOLC_OUTPUT = PROJECT / "solc-output.json"
+
+
+class TestSolidityLowering:
+    """Test lowering contracts, inheritance, and calls."""
+
+    def setup_method(self):
+        self.frontend = SolidityFrontend()
+        programs = self.frontend.load(SOLC_OUTPUT)
+        assert self.frontend.errors == []
+        assert [p.name for p in programs] == ["Vault"]  # abstract bases and interfaces skipped
+        self.program = programs[0]
+
+    def test_program_metadata(self):
+        assert self.program.chain == "evm"
+        assert self.program.framework == "solidity"
+        assert Path(self.program.root) == PROJECT
+        assert self.program.metadata["linearization"] == ["Vault", "Ownable"]
+        assert self.program.source_files == ["src/Ownable.sol", "src/Vault.sol"]
+
+    def test_entry_points(self):
+        names = [ix.name for ix in self.program.instructions]
+        assert names == ["constructor", "deposit", "withdraw", "quote", "transferOwnership", "receive"]
+        withdraw = self.program.instruction("withdraw")
+        assert withdraw.discriminator == "2e1a7d4d"
+        assert withdraw.visibility == "external"
+        assert [(a.name, a.ty) for a in withdraw.args] == [("amount", "uint256")]
+        assert "balances[msg.sender] -= amount" in withdraw.body
+        assert self.program.instruction("_fee") is None
+
+    def test_override_wins_over_base(self):
+        transfer = self.program.instruction("transferOwnership")
+        assert transfer.file == "src/Vault.sol"
+        assert "inherited_from" not in transfer.properties
+        assert transfer.modifiers == ["onlyOwner"]
+        assert "address(0)" in transfer.body
+
+    def test_calls(self):
+        withdraw = self.program.instruction("withdraw")
+        assert [(c.kind, c.target) for c in withdraw.calls] == [("call", "msg.sender")]
+        source = (PROJECT / "src" / "Vault.sol").read_text().split("\n")
+        assert "msg.sender.call" in source[withdraw.calls[0].line - 1]
+        quote = self.program.instruction("quote")
+        assert [(c.kind, c.target) for c in quote.calls] == [("external", "oracle")]
+
+    def test_storage_layout_is_base_first(self):
+        storage = self.program.account_type("Vault")
+        assert [f.name for f in storage.fields] == ["owner", "balances", "oracle"]
+        assert storage.properties["constants"] == ["FEE_BPS"]
+
+
+class TestSolidityInputs:
+    """Test accepted input formats and discovery."""
+
+    def test_build_info_uses_embedded_sources(self, tmp_path):
+        output = json.loads(SOLC_OUTPUT.read_text())
+        sources = {p: {"content": (PROJECT / p).read_text()} for p in output["sources"]}
+        build_info = tmp_path / "build-info.json"
+        build_info.write_text(json.dumps({"input": {"language": "Solidity", "sources": sources}, "output": output}))
+
+        program = SolidityFrontend().load(build_info)[0]
+        assert program.instruction("withdraw").body_line == 23
+
+    def test_non_solc_json_reported(self, tmp_path):
+        bogus = tmp_path / "package.json"
+        bogus.write_text('{"name": "x"}')
+        frontend = SolidityFrontend()
+        assert frontend.load(bogus) == []
+        assert "standard-json" in frontend.errors[0]
+
+    def test_source_discovery(self, tmp_path):
+        (tmp_path / "foundry.toml").write_text('[profile.default]\nsrc = "contracts"\nremappings = ["@oz/=lib/oz/"]\n')
+        for rel in ("contracts/A.sol", "contracts/A.t.sol", "lib/oz/B.sol", "test/C.sol"):
+            (tmp_path / rel).parent.mkdir(parents=True, exist_ok=True)
+            (tmp_path / rel).write_text("contract X {}\n")
+        (tmp_path / "remappings.txt").write_text("forge-std/=lib/forge-std/src/\n")
+
+        frontend = SolidityFrontend()
+        assert frontend.source_files(tmp_path) == ["contracts/A.sol"]
+        assert frontend.remappings(tmp_path) == ["forge-std/=lib/forge-std/src/", "@oz/=lib/oz/"]
+
+    def test_missing_solc_is_not_fatal(self, tmp_path):
+        (tmp_path / "Token.sol").write_text("contract Token {}\n")
+        frontend = SolidityFrontend(solc=str(tmp_path / "no-such-solc"))
+        assert frontend.is_available()[0] is False
+        assert frontend.load(tmp_path) == []
+        assert frontend.errors
+
+    def test_load_programs_accepts_solc_output(self):
+        programs = load_programs(SOLC_OUTPUT)
+        assert [(p.name, p.framework) for p in programs] == [("Vault", "solidity")]
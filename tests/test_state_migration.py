@@ -0,0 +1,290 @@
+"""
+Tests for upgrade state migration: account layout changes against a
+baseline directory or git ref, migration handling, and the `sim migrate`
+proof on a fake upgraded build.
+"""
+
+import hashlib
+import json
+import os
+import subprocess
+from unittest.mock import patch
+
+import yaml
+from click.testing import CliRunner
+
+from commands.simulate import migrate
+from extensions.ir.anchor_config import b58encode
+from extensions.monitor.decode import anchor_discriminator
+from extensions.simulation import ExecutionBackend, TxResult, load_migration
+from extensions.simulation.migration import account_discriminator, read_account
+from extensions.static import StateMigrationAnalyzer, StaticAnalysisPipeline
+
+PROGRAM_ID = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+
+HEADER = f"""use anchor_lang::prelude::*;
+
+declare_id!("{PROGRAM_ID}");
+"""
+
+OLD = HEADER + """
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        ctx.accounts.vault.balance -= amount;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, has_one = authority)]
+    pub vault: Account<'info, Vault>,
+    pub position: Account<'info, Position>,
+    pub authority: Signer<'info>,
+}
+
+#[account]
+pub struct Vault {
+    pub authority: Pubkey,
+    pub balance: u64,
+    pub reserved: [u8; 8],
+}
+
+#[account]
+pub struct Config {
+    pub admin: Pubkey,
+}
+
+#[account]
+pub struct Position {
+    pub owner: Pubkey,
+    pub shares: u64,
+}
+
+#[account]
+pub struct Ticket {
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+"""
+
+NEW = HEADER + """
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        ctx.accounts.vault.balance -= amount;
+        Ok(())
+    }
+
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        ctx.accounts.config.paused = false;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, has_one = authority)]
+    pub vault: Account<'info, Vault>,
+    pub position: Account<'info, Position>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut, has_one = admin, realloc = 8 + 33, realloc::payer = admin, realloc::zero = false)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+pub struct Vault {
+    pub authority: Pubkey,
+    pub fee_bps: u16,
+    pub balance: u64,
+    pub reserved: [u8; 6],
+}
+
+#[account]
+pub struct Config {
+    pub admin: Pubkey,
+    pub paused: bool,
+}
+
+#[account]
+pub struct Position {
+    pub owner: Pubkey,
+    pub shares: u64,
+}
+
+#[account]
+pub struct Receipt {
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+"""
+
+
+def _write(root, source):
+    program = root / "programs" / "vault"
+    (program / "src").mkdir(parents=True, exist_ok=True)
+    (program / "Cargo.toml").write_text('[package]\nname = "vault"\nversion = "0.1.0"\n\n'
+                                        '[dependencies]\nanchor-lang = "0.29.0"\n')
+    (program / "src" / "lib.rs").write_text(source)
+    return root
+
+
+def _git(root, *args):
+    subprocess.run(["git", "-C", str(root), "-c", "user.name=t", "-c", "user.email=t@t", *args], check=True,
+                   capture_output=True)
+
+
+class UpgradedBackend(ExecutionBackend):
+    """The new build: Anchor-style loading of every program-owned account, and a migrate_config that reallocates
+    a Config into its new layout."""
+
+    types: dict = {}
+
+    def start(self):
+        self.salt, self.keys, self.accounts = os.urandom(8), {}, {}
+
+    def keypair(self, name, lamports=0):
+        return self.keys.setdefault(name, b58encode(hashlib.sha256(self.salt + name.encode()).digest()))
+
+    def set_account(self, pubkey, data, owner, lamports=0, executable=False):
+        self.accounts[self.keys.get(pubkey, pubkey)] = {"lamports": lamports, "data": bytes(data), "owner": owner,
+                                                        "executable": executable}
+
+    def get_account(self, pubkey):
+        account = self.accounts.get(self.keys.get(pubkey, pubkey))
+        return dict(account) if account else None
+
+    def send(self, instructions, payer, signers=()):
+        ix = instructions[0]
+        accounts = [self.accounts.get(self.keys.get(meta.pubkey, meta.pubkey)) for meta in ix.accounts]
+        if ix.data[:8] == anchor_discriminator("migrate_config"):
+            accounts[0]["data"] = accounts[0]["data"][:40] + b"\x00"
+            return TxResult(True)
+        for account in accounts:
+            if not account or account["owner"] != PROGRAM_ID:
+                continue
+            name = next((n for n in self.types if account["data"][:8] == account_discriminator(n)), None)
+            if name is None:
+                return TxResult(False, "custom program error: 0xbba",
+                                logs=["Program log: AnchorError. Error Code: AccountDiscriminatorMismatch."])
+            if read_account(name, account["data"], self.types)[1]:
+                return TxResult(False, "custom program error: 0xbbb",
+                                logs=["Program log: AnchorError. Error Code: AccountDidNotDeserialize."])
+        return TxResult(True)
+
+
+def _tx(name, accounts):
+    return {"name": name, "program_id": PROGRAM_ID, "instruction": name, "args": "",
+            "accounts": [{"pubkey": a, "writable": True} for a in accounts] + [{"pubkey": "user", "signer": True}]}
+
+
+def _config(tmp_path):
+    _write(tmp_path / "v1", OLD)
+    _write(tmp_path / "v2", NEW)
+    return {
+        "program_id": PROGRAM_ID,
+        "program": "v2",
+        "baseline": str(tmp_path / "v1"),
+        "keypairs": {"user": 10 ** 10, "vault": 0, "config": 0, "position": 0, "ticket": 0},
+        "fixtures": [
+            {"pubkey": "vault", "type": "Vault", "values": {"authority": "user", "balance": 1000, "reserved": [0] * 8}},
+            {"pubkey": "config", "type": "Config", "values": {"admin": "user"}},
+            {"pubkey": "position", "type": "Position", "values": {"owner": "user", "shares": 5}},
+            {"pubkey": "ticket", "type": "Ticket", "new_type": "Receipt", "values": {"owner": "user", "amount": 7}},
+        ],
+        "transactions": [_tx("migrate_config", ["config"]), _tx("withdraw", ["vault", "position"]),
+                         _tx("close_ticket", ["ticket"])],
+    }
+
+
+def _simulation(config, base):
+    sim = load_migration(config, base=base, make_backend=UpgradedBackend)
+    UpgradedBackend.types = sim.new_types
+    return sim
+
+
+class TestStateMigration:
+    """Test layout comparison, migration handling and the upgrade simulation."""
+
+    def test_layout_changes(self, tmp_path):
+        old, new = _write(tmp_path / "v1", OLD), _write(tmp_path / "v2", NEW)
+        findings, metadata = StateMigrationAnalyzer(baseline=str(old)).run(new)
+        assert metadata["programs_compared"] == 1
+        changes = {c["account_type"]: c for c in metadata["changes"]}
+        assert {name: c["kind"] for name, c in changes.items()} == {"Vault": "corrupting", "Config": "extended",
+                                                                   "Receipt": "renamed"}
+        assert [(c["field"], c["change"], c["old_offset"], c["new_offset"]) for c in changes["Vault"]["changes"]] == [
+            ("fee_bps", "added", None, 32), ("balance", "moved", 32, 34), ("reserved", "retyped", 40, 42)]
+        assert changes["Config"]["handled_by"] == ["migrate_config (realloc)"]
+
+        assert [f.change.account_type for f in findings] == ["Vault", "Receipt"]
+        hyp = findings[0].to_hypothesis()
+        assert hyp["severity"] == "high" and hyp["vulnerability_type"] == "unmigrated-account-layout"
+        assert "`balance` moved from byte 32 to byte 34" in hyp["description"]
+        assert hyp["properties"]["source_files"] == ["programs/vault/src/lib.rs"]
+        assert findings[1].to_hypothesis()["title"] == "`Receipt` layout change without a migration (renamed)"
+        assert len(StateMigrationAnalyzer(baseline=str(old), include_handled=True).run(new)[0]) == 3
+
+    def test_git_baseline_and_pipeline(self, tmp_path):
+        root = _write(tmp_path / "repo", OLD)
+        _git(root, "init", "-q")
+        _git(root, "add", "-A")
+        _git(root, "commit", "-qm", "v1")
+        _git(root, "tag", "v1")
+        _write(root, NEW)
+        findings, metadata = StateMigrationAnalyzer(baseline="v1").run(root / "programs" / "vault")
+        assert metadata["success"] and {f.change.account_type for f in findings} == {"Vault", "Receipt"}
+        _, metadata = StateMigrationAnalyzer(baseline="v9").run(root)
+        assert not metadata["success"] and "neither a directory nor a git ref" in metadata["error"]
+
+        pipeline = StaticAnalysisPipeline(chain_id="solana", state_migration_config={"baseline": "v1"})
+        assert pipeline.check_tools()["state-migration"][0]
+        result = pipeline.run(root, tools=["state-migration"])
+        assert len(result.hypotheses) == 2
+        assert StaticAnalysisPipeline(chain_id="solana").run(root, tools=["state-migration"]).hypotheses == []
+
+    def test_simulation(self, tmp_path):
+        result = _simulation(_config(tmp_path), tmp_path).run()
+        verdicts = {fx.fixture.pubkey: fx.verdict for fx in result.fixtures}
+        assert verdicts == {"vault": "corrupted", "config": "migrated", "position": "compatible",
+                            "ticket": "bricked"}
+        vault = result.fixtures[0]
+        assert vault.old["authority"] == "<user>" and vault.old["balance"] == 1000
+        assert vault.differences["balance"] == (1000, 0) and vault.result.ok
+
+        findings = {f.fixture.fixture.pubkey: f.to_hypothesis() for f in result.findings()}
+        assert set(findings) == {"vault", "ticket"}
+        assert findings["vault"]["severity"] == "high" and findings["vault"]["properties"]["precision"] == "verified"
+        assert "`balance` 1000 as 0" in findings["vault"]["description"]
+        assert findings["vault"]["properties"]["source_files"] == ["programs/vault/src/lib.rs"]
+        assert "0xbba" in findings["ticket"]["description"]
+        assert [c["account_type"] for c in result.to_dict()["layouts"]] == ["Vault", "Config", "Receipt"]
+
+    def test_cli(self, tmp_path):
+        config = _config(tmp_path)
+        path = tmp_path / "fixtures.yaml"
+        path.write_text(yaml.safe_dump(config))
+        UpgradedBackend.types = load_migration(config, base=tmp_path, make_backend=UpgradedBackend).new_types
+        with patch("extensions.simulation.engine.backend_factory", return_value=UpgradedBackend):
+            result = CliRunner().invoke(migrate, [str(path), "--json"])
+            assert result.exit_code == 0, result.output
+            data = json.loads(result.output)
+            assert data["summary"] == {"fixtures": 4, "bricked": 1, "corrupted": 1, "migrated": 1, "compatible": 1}
+            assert data["manifest"]["command"]["name"] == "sim migrate"
+
+            result = CliRunner().invoke(migrate, [str(path)])
+        assert result.exit_code == 0, result.output
+        assert "vault.balance: 1000 reads as 0" in result.output
+        assert "Upgrade misreads existing `Vault` accounts" in result.output
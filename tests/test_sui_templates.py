@@ -0,0 +1,82 @@
+"""
+Tests for the Sui template pack: test harness modelling, the Move-specific
+classes its templates hang off, and rendering `sui move test` modules into a
+package.
+"""
+
+from pathlib import Path
+
+import pytest
+from click.testing import CliRunner
+
+from extensions.knowledge import HARNESSES, Chain, ClassLoader, TemplateLoader, TemplateRegistry
+from extensions.knowledge.front_matter import FrontMatterError, normalize_front_matter
+from extensions.knowledge.harness import default_harness, parse_harness
+from extensions.knowledge.render import TemplateEngine
+
+
+KB_DIR = Path(__file__).resolve().parents[1] / "extensions" / "knowledge"
+SUI_TEMPLATES = ["capability_leak", "missing_capability_check", "privileged_public_transfer",
+                 "shared_object_race", "type_confusion"]
+
+
+class TestSuiTemplates:
+    """Test Move test harnesses, the Sui pack's classes and rendering."""
+
+    def setup_method(self):
+        self.registry = TemplateRegistry(TemplateLoader(KB_DIR / "templates"))
+
+    def test_harnesses(self):
+        assert default_harness("sui").id == "sui-move-test" and default_harness(Chain.SOLANA).id == "anchor"
+        sui, anchor = HARNESSES["sui-move-test"], HARNESSES["anchor"]
+        assert sui.test_path("shared_object_race") == "tests/shared_object_race.move"
+        assert anchor.test_path("missing_signer") == "tests/missing_signer.rs"
+        assert sui.run_command("shared_object_race") == "sui move test shared_object_race"
+        assert parse_harness("Sui Move Test") is sui
+        with pytest.raises(ValueError, match="does not run Solana tests"):
+            parse_harness("sui-move-test", "solana")
+        with pytest.raises(FrontMatterError, match="Unknown harness"):
+            normalize_front_matter({"harness": "hardhat"})
+
+        assert [t.id for t in self.registry.query(harness="sui-move-test")] == SUI_TEMPLATES
+        assert [t.id for t in self.registry.query(harness="anchor")] == [
+            "arbitrary_cpi", "cpi_reentrancy", "duplicate_mutable_accounts", "missing_owner_check",
+            "missing_signer", "pda_seed_collision", "type_cosplay", "unchecked_arithmetic"]
+        entry = next(e for e in self.registry.index() if e["id"] == "missing_capability_check")
+        assert entry["harness"] == "sui-move-test" and entry["test_path"] == "tests/missing_capability_check.move"
+
+    def test_pack(self):
+        assert self.registry.by_chain()[Chain.SUI] == SUI_TEMPLATES
+        for template in self.registry.query(chain="sui"):
+            assert f"module {{{{PACKAGE}}}}::{template.id}_tests" in template.template, template.id
+            assert template.harness.problems(template.template) == [], template.id
+            assert "test_scenario" in template.template and "PACKAGE" in template.context
+
+        classes = ClassLoader(KB_DIR / "classes")
+        for alias, class_id in [("missing-capability-check", "unauthenticated-privileged-action"),
+                                ("privileged-public-transfer", "unauthenticated-privileged-action"),
+                                ("shared-object-race", "shared-state-race")]:
+            assert classes.resolve(alias, "sui").id == class_id, alias
+        assert classes.get("unauthenticated-privileged-action").variant("sui").templates == [
+            "capability_leak", "missing_capability_check", "privileged_public_transfer"]
+        assert "without `store`" in classes.get("unauthenticated-privileged-action").fix_for("sui")
+
+    def test_render(self, tmp_path):
+        engine = TemplateEngine(self.registry.get("shared_object_race"))
+        assert engine.harness.id == "sui-move-test" and engine.required == ["PACKAGE"]
+        # Move literals take no Solidity units, and package names must be identifiers
+        assert engine.validate({"package": "vault", "deposit_amount": "1e18"})[1] == {
+            "DEPOSIT_AMOUNT": "'1e18' is not a Move integer literal"}
+        assert engine.validate({"package": "my-vault"})[1] == {"PACKAGE": "'my-vault' is not an identifier"}
+        assert engine.validate({"package": "vault", "deposit_amount": "5_000u64"}) == ([], {})
+        text = engine.render({"package": "vault", "module": "lending"})
+        assert "module vault::shared_object_race_tests" in text and "use vault::lending::{Self, Pool};" in text
+        assert "lending::deposit(&mut pool" in text and "{{" not in text
+
+        from commands.knowledge import render
+        runner = CliRunner()
+        result = runner.invoke(render, ["missing_capability_check", "-s", "PACKAGE=treasury", "-w", str(tmp_path)])
+        assert result.exit_code == 0, result.output
+        written = tmp_path / "tests" / "missing_capability_check.move"
+        assert "#[test_only]" in written.read_text()
+        assert "sui move test missing_capability_check" in result.output
@@ -0,0 +1,133 @@
+"""
+Tests for the controlled tag taxonomy: classification, KB and finding tagging,
+faceted filtering in the CLI, the review workflow and the HTML report.
+"""
+
+import pytest
+from click.testing import CliRunner
+
+from commands.knowledge import browse, checklist, list_tags
+from extensions.bounty import Finding, Severity
+from extensions.knowledge import ChecklistLoader, KnowledgeBase
+from extensions.knowledge.taxonomy import (
+    TAXONOMY,
+    classify,
+    facet_counts,
+    filter_by_tags,
+    hypothesis_tags,
+    normalize_tag,
+    resolve_tags,
+)
+
+
+@pytest.fixture
+def no_solodit(monkeypatch):
+    """Use only the bundled YAML checklists (no Solodit cache or fetch)."""
+    monkeypatch.setattr(ChecklistLoader, "_load_cached_solodit", lambda self: [])
+
+
+class TestTaxonomy:
+    """Test tag normalization, keyword classification and facet helpers."""
+
+    def test_normalize_and_resolve(self):
+        assert normalize_tag("Access Control") == "access-control"
+        assert normalize_tag("access_control") == "access-control"
+        assert normalize_tag("flash-loan") == "economic"
+        assert normalize_tag("CPI") == "cpi"
+        assert normalize_tag("something else") is None
+        assert resolve_tags(["oracle", "Chainlink", "oracle"]) == ["oracle"]
+        with pytest.raises(ValueError, match="unknown tag"):
+            resolve_tags(["nope"])
+
+    def test_classify(self):
+        assert classify(["flash-loan", "callback"]) == ["reentrancy", "economic"]
+        assert classify(texts=["Oracle & Price Feeds", "Is the deadline checked?"]) == ["oracle", "timing"]
+        # Description is only consulted when the short fields say nothing
+        assert classify(texts=["Weird bug"], fallback_texts=["the proxy can be upgraded by anyone"]) == [
+            "upgradeability"]
+        assert classify(texts=["Oracle"], fallback_texts=["the proxy"]) == ["oracle"]
+        assert classify(texts=["Weird bug"]) == ["logic"]
+        # Whole words only
+        assert "access-control" not in classify(texts=["Roleplay guidance"])
+
+    def test_filter_and_facets(self):
+        hyps = [
+            {"title": "Missing signer check on withdraw", "vulnerability_type": "access_control"},
+            {"title": "Stale price accepted", "vulnerability_type": "oracle"},
+            {"title": "Oracle manipulated with a flash loan", "vulnerability_type": "price_manipulation"},
+            {"title": "x", "taxonomy": ["cpi"]},
+        ]
+        assert hypothesis_tags(hyps[0]) == ["access-control"]
+        assert hypothesis_tags(hyps[3]) == ["cpi"]
+        assert [h["title"] for h in filter_by_tags(hyps, ["oracle"])] == [h["title"] for h in hyps[1:3]]
+        assert filter_by_tags(hyps, ["oracle", "economic"], match_all=True) == [hyps[2]]
+        assert filter_by_tags(hyps, []) == hyps
+        assert list(facet_counts(hyps).items())[0] == ("oracle", 2)
+
+
+class TestKnowledgeBaseTags:
+    """Test that every KB entry and finding carries controlled tags."""
+
+    def test_all_entries_tagged(self, no_solodit):
+        result = KnowledgeBase().browse()
+        entries = [*result.checklists, *result.tips, *result.templates]
+        assert entries
+        for entry in entries:
+            assert entry.taxonomy, entry.id
+            assert set(entry.taxonomy) <= set(TAXONOMY), entry.id
+        facets = result.facets()
+        for tag in ("access-control", "arithmetic", "cpi", "oracle", "economic", "upgradeability"):
+            assert facets.get(tag), tag
+
+    def test_query_and_browse(self, no_solodit):
+        kb = KnowledgeBase()
+        oracle = kb.query("price", tags=["oracle"])
+        assert oracle.checklists and all("oracle" in c.taxonomy for c in oracle.checklists)
+        solana_cpi = kb.browse(["cpi"], chain="solana")
+        assert solana_cpi.checklists and all(c.chain == "solana" for c in solana_cpi.checklists)
+        both = kb.browse(["cpi", "reentrancy"], match_all=True)
+        assert all({"cpi", "reentrancy"} <= set(e.taxonomy) for e in [*both.checklists, *both.tips])
+        assert kb.stats()["taxonomy"] == kb.browse().facets()
+
+    def test_finding_tags(self):
+        finding = Finding.from_hypothesis({"id": "h1", "title": "Reinitialization lets anyone reset admin",
+                                           "vulnerability_type": "initialization"}, "c1")
+        assert finding.tags == ["access-control", "initialization"]
+        restored = Finding.from_dict(finding.to_dict())
+        assert restored.tags == finding.tags
+        manual = Finding(id="f", contest_id="c", title="Custom", description="", severity=Severity.LOW,
+                         tags=["dos"])
+        assert Finding.from_dict(manual.to_dict()).tags == ["dos"]
+
+
+class TestFacetedViews:
+    """Test tag filters in the kb commands and the HTML report facet bar."""
+
+    def test_kb_cli(self, no_solodit):
+        runner = CliRunner()
+        result = runner.invoke(browse, ["--tag", "upgradeability"])
+        assert result.exit_code == 0, result.output
+        assert "Knowledge Base: upgradeability" in result.output
+        result = runner.invoke(checklist, ["--tag", "oracle", "--limit", "100"])
+        assert result.exit_code == 0, result.output
+        assert "ORC-01" in result.output and "REEN-01" not in result.output
+        assert runner.invoke(checklist, ["--tag", "bogus"]).exit_code == 2
+        result = runner.invoke(list_tags, [])
+        assert result.exit_code == 0 and "account-validation" in result.output
+
+    def test_report_facets(self):
+        from analysis.report_generator import ReportGenerator
+
+        generator = ReportGenerator.__new__(ReportGenerator)
+        generator.include_all = False
+        generator.pocs = {}
+        findings = [
+            {"id": "h1", "title": "A", "severity": "high", "description": "d", "affected_description": "x",
+             "tags": ["oracle", "economic"]},
+            {"id": "h2", "title": "B", "severity": "low", "description": "d", "affected_description": "x",
+             "tags": ["cpi"]},
+        ]
+        html = generator._format_findings_html(findings)
+        assert 'data-tags="oracle economic"' in html
+        assert 'data-tag="cpi"' in html and "CPI &amp; External Calls" in html
+        assert "**Tags:** Oracle, Economic" in generator._format_findings_markdown(findings)
@@ -0,0 +1,145 @@
+"""
+Tests for PoC template front matter and the template registry.
+
+Verifies YAML and TOML front matter parse out of commented template
+headers and are validated, that the shipped templates carry it, and that
+the registry and `kb templates` query templates by their metadata.
+"""
+
+import json
+from pathlib import Path
+
+import pytest
+from click.testing import CliRunner
+
+from extensions.knowledge.front_matter import FrontMatterError, normalize_front_matter, split_front_matter
+from extensions.knowledge.template_loader import TemplateLoader
+from extensions.knowledge.template_registry import TemplateRegistry
+
+
+KB_DIR = Path(__file__).resolve().parents[1] / "extensions" / "knowledge"
+
+YAML_TEMPLATE = """// ---
+// name: Unchecked Owner
+// class: account-type-confusion
+// severity: High
+// cwe: 345
+// context:
+//   program_id: Program under test
+//   FAKE_ACCOUNT: Account with the wrong owner
+// references: https://github.com/coral-xyz/sealevel-attacks
+// ---
+// PoC Template: Unchecked Owner
+use anchor_lang::prelude::*;
+
+fn exploit() { let p = {{PROGRAM_ID|pubkey}}; let a = {{FAKE_ACCOUNT|pubkey}}; }
+"""
+
+TOML_TEMPLATE = """// +++
+// name = "Closed Account Revival"
+// severity = "medium"
+// cwe = ["CWE-672"]
+// tags = ["close"]
+// +++
+use anchor_lang::prelude::*;
+
+fn revive() {}
+"""
+
+
+class TestTemplateRegistry:
+    """Test front matter parsing, template metadata and registry queries."""
+
+    def setup_method(self):
+        self.registry = TemplateRegistry(TemplateLoader(KB_DIR / "templates"))
+
+    def test_front_matter(self):
+        data, fmt, body = split_front_matter(YAML_TEMPLATE)
+        assert fmt == "yaml" and body.startswith("// PoC Template: Unchecked Owner")
+        meta = normalize_front_matter(data, ["PROGRAM_ID", "FAKE_ACCOUNT"])
+        assert meta["severity"] == "high" and meta["cwe"] == ["CWE-345"]
+        assert meta["context"] == {"PROGRAM_ID": "Program under test", "FAKE_ACCOUNT": "Account with the wrong owner"}
+        assert meta["references"] == ["https://github.com/coral-xyz/sealevel-attacks"]
+
+        data, fmt, body = split_front_matter(TOML_TEMPLATE)
+        assert fmt == "toml" and data["name"] == "Closed Account Revival" and body.startswith("use anchor_lang")
+        assert split_front_matter("use anchor_lang::prelude::*;\n") == ({}, None, "use anchor_lang::prelude::*;\n")
+
+        with pytest.raises(FrontMatterError, match="not used as placeholders: FAKE_ACCOUNT"):
+            normalize_front_matter(data | {"context": ["FAKE_ACCOUNT"]}, [])
+        with pytest.raises(FrontMatterError, match="severity must be one of"):
+            normalize_front_matter({"severity": "urgent"})
+        with pytest.raises(FrontMatterError, match="unknown front matter field"):
+            normalize_front_matter({"owner": "me"})
+        with pytest.raises(FrontMatterError, match="not closed"):
+            split_front_matter("// ---\n// name: x\nfn main() {}\n")
+
+    def test_loader(self, tmp_path, capsys):
+        solana = tmp_path / "solana"
+        solana.mkdir(parents=True, exist_ok=True)
+        (solana / "unchecked_owner.rs").write_text(YAML_TEMPLATE)
+        (solana / "closed_account.rs").write_text(TOML_TEMPLATE)
+        (solana / "broken.rs").write_text("// ---\n// severity: urgent\n// ---\nfn main() {}\n")
+        (solana / "stray_class.rs").write_text("// ---\n// class: no-such-class\n// ---\nfn main() {}\n")
+        loader = TemplateLoader(tmp_path)
+
+        owner = loader.get("unchecked_owner")
+        assert owner.name == "Unchecked Owner" and owner.chain == "solana"
+        assert owner.vuln_class == "account-type-confusion" and owner.severity == "high"
+        assert owner.placeholders == ["PROGRAM_ID", "FAKE_ACCOUNT"]
+        assert owner.template.startswith("// PoC Template") and "---" not in owner.template
+        assert owner.path == str(solana / "unchecked_owner.rs")
+        closed = loader.get("closed_account")
+        assert closed.cwe == ["CWE-672"] and "close" in closed.tags and closed.context == {}
+        assert loader.get("broken") is None
+        assert loader.get("stray_class").vuln_class is None
+        out = capsys.readouterr().out
+        assert "Failed to load Solana template" in out and "unknown vulnerability class no-such-class" in out
+
+    def test_shipped_templates(self):
+        for t in self.registry.list_all():
+            assert t.severity, t.id
+            assert t.vuln_class, t.id
+            assert set(t.context) <= set(t.placeholders), t.id
+        signer = self.registry.get("missing_signer")
+        assert signer.cwe == ["CWE-862"] and set(signer.context) == {
+            "PROGRAM_ID", "VAULT_ACCOUNT", "INSTRUCTION", "ARG_FIELDS", "ACCOUNT_FIELDS"}
+        assert signer.references[0].startswith("https://github.com/coral-xyz/sealevel-attacks")
+        assert not signer.template.startswith("// ---")
+        assert self.registry.get("reentrancy").swc == ["SWC-107"]
+
+    def test_query(self):
+        ids = lambda templates: [t.id for t in templates]  # noqa: E731
+        assert self.registry.chains() == ["cosmwasm", "evm", "solana", "sui"]
+        assert ids(self.registry.query(chain="sui", severity="medium")) == ["shared_object_race"]
+        assert ids(self.registry.query(vuln_class="unauthenticated-privileged-action")) == [
+            "missing_sender_validation", "access_control", "missing_signer", "capability_leak",
+            "missing_capability_check", "privileged_public_transfer"]
+        # Vulnerability types resolve onto their class
+        assert ids(self.registry.query(vuln_class="missing_signer", chain="solana")) == ["missing_signer"]
+        assert ids(self.registry.query(cwe="862")) == ["missing_sender_validation", "missing_signer",
+                                                      "missing_capability_check"]
+        assert ids(self.registry.query(swc="swc-128")) == ["dos_gas"]
+        assert "dos_gas" not in ids(self.registry.query(min_severity="high"))
+        assert self.registry.by_class()["price-manipulation"] == ["flash_loan", "oracle_manipulation"]
+        with pytest.raises(ValueError, match="Unknown severity"):
+            self.registry.query(severity="urgent")
+
+        entry = next(e for e in self.registry.index() if e["id"] == "missing_signer")
+        assert entry["class"] == "unauthenticated-privileged-action" and "template" not in entry
+
+    def test_cli(self):
+        from commands.knowledge import templates
+
+        runner = CliRunner()
+        result = runner.invoke(templates, ["--chain", "solana", "--json"])
+        assert result.exit_code == 0, result.output
+        data = json.loads(result.output)
+        assert [e["id"] for e in data] == ["arbitrary_cpi", "cpi_reentrancy", "duplicate_mutable_accounts",
+                                           "missing_owner_check", "missing_signer", "pda_seed_collision",
+                                           "type_cosplay", "unchecked_arithmetic"]
+        assert data[2]["context"]["VAULT_ACCOUNT"]
+
+        result = runner.invoke(templates, ["--class", "reentrancy"])
+        assert result.exit_code == 0 and "cpi_reentrancy" in result.output and "SWC-107" in result.output
+        assert runner.invoke(templates, ["--min-severity", "urgent"]).exit_code == 1
@@ -0,0 +1,121 @@
+"""
+Tests for PoC template rendering.
+
+Verifies placeholder parsing (defaults and filters), context validation
+against each placeholder's expected value format, rendering of the
+shipped Solana templates and the `kb render` command.
+"""
+
+from pathlib import Path
+
+import pytest
+from click.testing import CliRunner
+
+from extensions.knowledge.render import TemplateEngine, TemplateError, parse_placeholders
+from extensions.knowledge.template_loader import TemplateLoader
+
+
+KB_DIR = Path(__file__).resolve().parents[1] / "extensions" / "knowledge"
+PROGRAM = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+VAULT = "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin"
+
+TEMPLATE = """use anchor_lang::prelude::*;
+
+#[test]
+fn {{INSTRUCTION|snake}}_exploit() {
+    let program_id = {{PROGRAM_ID|pubkey}};
+    let vault = {{VAULT_ACCOUNT|pubkey}};
+    let ix = instruction::{{INSTRUCTION|camel}} { amount: {{WITHDRAW_AMOUNT=1_000_000}} };
+    let label = {{LABEL=drained|str}};
+}
+"""
+
+
+class TestTemplateRender:
+    """Test the template engine against inline and shipped templates."""
+
+    def setup_method(self):
+        self.loader = TemplateLoader(KB_DIR / "templates")
+
+    def test_parse_placeholders(self):
+        placeholders = parse_placeholders(TEMPLATE)
+        assert [p.name for p in placeholders] == ["INSTRUCTION", "PROGRAM_ID", "VAULT_ACCOUNT", "WITHDRAW_AMOUNT",
+                                                   "LABEL"]
+        by_name = {p.name: p for p in placeholders}
+        assert by_name["INSTRUCTION"].filters == ["snake", "camel"]
+        assert by_name["INSTRUCTION"].line == 4
+        assert by_name["WITHDRAW_AMOUNT"].default == "1_000_000"
+        assert by_name["LABEL"].default == "drained" and by_name["LABEL"].filters == ["str"]
+        assert TemplateEngine(TEMPLATE).required == ["INSTRUCTION", "PROGRAM_ID", "VAULT_ACCOUNT"]
+        with pytest.raises(TemplateError, match="unknown filter 'base64'"):
+            parse_placeholders("{{PROGRAM_ID|base64}}")
+
+    def test_render(self):
+        engine = TemplateEngine(TEMPLATE)
+        rendered = engine.render({"program_id": PROGRAM, "VAULT_ACCOUNT": VAULT, "instruction": "emergency_withdraw"})
+        assert "fn emergency_withdraw_exploit()" in rendered
+        assert f'let program_id = anchor_lang::solana_program::pubkey!("{PROGRAM}");' in rendered
+        assert "instruction::EmergencyWithdraw { amount: 1_000_000 }" in rendered
+        assert 'let label = "drained";' in rendered
+        assert "{{" not in rendered
+
+        overridden = engine.render({"PROGRAM_ID": PROGRAM, "VAULT_ACCOUNT": VAULT, "INSTRUCTION": "Withdraw",
+                                    "WITHDRAW_AMOUNT": "42", "LABEL": 'say "hi"'})
+        assert "fn withdraw_exploit()" in overridden and "amount: 42" in overridden
+        assert r'let label = "say \"hi\"";' in overridden
+
+    def test_validation(self):
+        engine = TemplateEngine(TEMPLATE)
+        missing, invalid = engine.validate({"PROGRAM_ID": "not-a-key", "INSTRUCTION": "withdraw all",
+                                            "WITHDRAW_AMOUNT": "lots"})
+        assert missing == ["VAULT_ACCOUNT"]
+        assert set(invalid) == {"PROGRAM_ID", "INSTRUCTION", "WITHDRAW_AMOUNT"}
+        assert "base58" in invalid["PROGRAM_ID"]
+
+        with pytest.raises(TemplateError) as excinfo:
+            engine.render({"PROGRAM_ID": PROGRAM[:-4], "INSTRUCTION": "withdraw"})
+        assert excinfo.value.missing == ["VAULT_ACCOUNT"]
+        assert list(excinfo.value.invalid) == ["PROGRAM_ID"]
+        assert "missing VAULT_ACCOUNT" in str(excinfo.value)
+
+        evm = TemplateEngine("{{POOL_ADDRESS}} {{ATTACK_AMOUNT}}", chain="evm")
+        assert evm.validate({"POOL_ADDRESS": "0x" + "ab" * 20, "ATTACK_AMOUNT": "10 ether"}) == ([], {})
+        assert list(evm.validate({"POOL_ADDRESS": PROGRAM, "ATTACK_AMOUNT": "1e18"})[1]) == ["POOL_ADDRESS"]
+
+    def test_shipped_template(self, tmp_path):
+        template = self.loader.get("missing_signer")
+        assert template.placeholders == ["PROGRAM_ID", "VAULT_ACCOUNT", "INSTRUCTION", "ARG_FIELDS", "ACCOUNT_FIELDS"]
+        engine = TemplateEngine(template)
+        assert engine.chain == "solana"
+
+        path = engine.write(tmp_path / "tests" / "missing_signer.rs", {"PROGRAM_ID": PROGRAM, "VAULT_ACCOUNT": VAULT})
+        text = path.read_text()
+        assert text.startswith("// Generated by Baskerville from the `missing_signer` PoC template")
+        assert f"let program = {PROGRAM};" in text and f"let vault = {VAULT};" in text
+        assert "{{" not in text
+        assert ("// let accounts = accounts::Withdraw {\n//         //     vault: vault.pubkey(),\n"
+                "//         //     authority: attacker.pubkey(),  // Attacker, not real owner!\n") in text
+
+        # Templates without placeholders render as they are
+        cpi = self.loader.get("cpi_reentrancy")
+        assert TemplateEngine(cpi).render({}, header=False) == cpi.template
+
+    def test_cli(self, tmp_path):
+        from commands.knowledge import kb
+
+        runner = CliRunner()
+        out = tmp_path / "exploit.rs"
+        result = runner.invoke(kb, ["render", "missing_signer", "-s", f"PROGRAM_ID={PROGRAM}",
+                                    "--set", f"vault_account={VAULT}", "-o", str(out)])
+        assert result.exit_code == 0, result.output
+        assert f"let vault = {VAULT};" in out.read_text()
+
+        result = runner.invoke(kb, ["render", "missing_signer", "-s", "PROGRAM_ID=abc"])
+        assert result.exit_code == 1
+        assert "missing VAULT_ACCOUNT" in result.output and "PROGRAM_ID" in result.output
+
+        result = runner.invoke(kb, ["render", "missing_signer", "-s", "PROGRAM_ID"])
+        assert result.exit_code != 0 and "KEY=VALUE" in result.output
+
+        result = runner.invoke(kb, ["render", "no_such_template"])
+        assert result.exit_code == 1 and "Unknown template" in result.output
@@ -0,0 +1,82 @@
+"""
+Tests for ranked full-text search of PoC templates: query parsing, BM25
+ranking across titles, descriptions, fixes and tags, snippets, and the
+`search` / `kb find` commands.
+"""
+
+import json
+
+import pytest
+from click.testing import CliRunner
+
+from commands.knowledge import kb
+from extensions.knowledge import KnowledgeBase, SearchQuery, TemplateIndex, TemplateLoader
+from extensions.knowledge.chains import Chain
+
+ORACLE = """// ---
+// name: Stale Oracle Price
+// description: Price read without checking its publish time
+// vulnerability_type: oracle-staleness
+// chain: solana
+// severity: high
+// remediation: Reject prices whose publish time is older than the maximum staleness.
+// tags: [oracle, pyth]
+// ---
+// PoC Template: Stale Oracle Price
+// Program: {{PROGRAM_ID}}
+"""
+
+
+class TestTemplateSearch:
+    """Test searching templates by free text and filters."""
+
+    def test_query(self):
+        query = SearchQuery.parse('Reentrancy CPIs "signer seeds" tag:CPI chain:Solana severity:high')
+        assert query.terms == ["reentrancy", "cpi", "signer", "seed"]
+        assert query.phrases == ["signer seed"]
+        assert query.tags == ["cpi"] and query.chain == Chain.SOLANA and query.severity == "high"
+        for text, error in [("tag:nope", "unknown tag"), ("chain:nope", "Unknown chain"),
+                            ("severity:huge", "unknown severity")]:
+            with pytest.raises(ValueError, match=error):
+                SearchQuery.parse(text)
+
+    def test_ranking(self):
+        kb = KnowledgeBase()
+        hits = kb.search("reentrancy cpi")
+        # Matching both words, in the title, beats matching one
+        assert hits[0].template.id == "cpi_reentrancy" and hits[0].matched == ["reentrancy", "cpi"]
+        assert hits[0].score > hits[1].score
+        assert kb.search("reentrancy cpi", chain="evm")[0].template.id == "reentrancy"
+
+        # Fix sections are indexed; snippets come from the text around the match
+        signer = kb.search("signer constraint", limit=1)[0]
+        assert signer.template.id == "missing_signer" and signer.field in ("title", "fix")
+        assert "signer" in signer.snippet.lower() and len(signer.snippet) <= 170
+        # Prefixes of four or more letters, and phrases
+        assert kb.search("reentr")[0].template.id in ("reentrancy", "cpi_reentrancy")
+        assert [h.template.id for h in kb.search('"signer seeds"')] == ["arbitrary_cpi"]
+        assert all(h.template.chain == Chain.SUI for h in kb.search("capability chain:sui"))
+        assert kb.search("zzzz qqqq") == []
+
+    def test_index_and_cli(self, tmp_path):
+        (tmp_path / "solana").mkdir()
+        (tmp_path / "solana" / "stale_oracle.rs").write_text(ORACLE)
+        loader = TemplateLoader(user_dirs=[tmp_path])
+        hits = TemplateIndex(loader.list_all(), loader.classes).search("stale oracle publish time")
+        assert hits[0].template.id == "stale_oracle" and hits[0].template.origin == "user"
+        assert hits[0].to_dict()["matched"] == ["stale", "oracle", "publish", "time"]
+        tagged = TemplateIndex(loader.list_all()).search("price", tags=["oracle"])
+        assert {h.template.id for h in tagged} >= {"stale_oracle"}
+        assert all("oracle" in h.template.taxonomy for h in tagged)
+
+        runner = CliRunner()
+        result = runner.invoke(kb, ["find", "stale oracle", "--template-dir", str(tmp_path), "--json", "-l", "2"])
+        assert result.exit_code == 0, result.output
+        data = json.loads(result.output)
+        assert data[0]["id"] == "stale_oracle" and len(data) <= 2 and "publish" in data[0]["snippet"]
+
+        result = runner.invoke(kb, ["find", "reentrancy cpi"])
+        assert result.exit_code == 0, result.output
+        assert "cpi_reentrancy" in result.output and "score" in result.output
+        result = runner.invoke(kb, ["find", "x", "-t", "nope"])
+        assert result.exit_code != 0
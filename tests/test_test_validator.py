@@ -0,0 +1,208 @@
+"""
+Tests for the managed test validator: clone planning, ports, lifecycle and the CLI.
+"""
+
+import json
+import os
+import shutil
+import signal
+import socket
+import sys
+import time
+from pathlib import Path
+from unittest.mock import patch
+
+import pytest
+from click.testing import CliRunner
+
+from commands.validator import plan, run
+from extensions.ir.anchor_config import load_anchor_config
+from extensions.poc import ClonePlan, TestValidator, ValidatorError, allocate_ports, extract_addresses, plan_clones
+
+WORKSPACE = Path(__file__).resolve().parent / "fixtures" / "solana" / "anchor_config"
+
+USDC = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
+PYTH_PROGRAM = "FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH"
+PYTH_SOL_USD = "H6ARHf6YXhGYeQfUzQNGk6rDNnLBQKrenN712K4AQJEG"
+JUPITER = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4"
+MISSING = "Missing111111111111111111111111111111111111"
+TOKEN = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+
+CHAIN = {
+    USDC: {"owner": TOKEN, "executable": False},
+    PYTH_SOL_USD: {"owner": PYTH_PROGRAM, "executable": False},
+    PYTH_PROGRAM: {"owner": "BPFLoaderUpgradeab1e11111111111111111111111", "executable": True},
+    JUPITER: {"owner": "BPFLoaderUpgradeab1e11111111111111111111111", "executable": True},
+}
+
+POC = f"""
+import {{ PublicKey }} from "@solana/web3.js";
+const USDC = new PublicKey("{USDC}");
+const JUP = new PublicKey('{JUPITER}');
+const ESCROW = new PublicKey("Escr111111111111111111111111111111111111111");
+const GONE = new PublicKey(`{MISSING}`);
+const TOKEN = new PublicKey("{TOKEN}");
+const label = "not-a-public-key";
+"""
+
+# Stands in for solana-test-validator: answers getHealth on --rpc-port, or fails on request
+FAKE_VALIDATOR = """\
+import json, os, sys
+from http.server import BaseHTTPRequestHandler, HTTPServer
+args = sys.argv[1:]
+print("fake validator", " ".join(args), flush=True)
+if os.environ.get("FAKE_VALIDATOR_FAIL"):
+    print("Error: failed to start validator: Address already in use", flush=True)
+    sys.exit(1)
+port = int(args[args.index("--rpc-port") + 1])
+os.makedirs(args[args.index("--ledger") + 1], exist_ok=True)
+
+class Handler(BaseHTTPRequestHandler):
+    def do_POST(self):
+        body = json.loads(self.rfile.read(int(self.headers["Content-Length"])))
+        reply = json.dumps({"jsonrpc": "2.0", "id": body["id"], "result": "ok"}).encode()
+        self.send_response(200)
+        self.send_header("Content-Length", str(len(reply)))
+        self.end_headers()
+        self.wfile.write(reply)
+
+    def log_message(self, *args):
+        pass
+
+HTTPServer(("127.0.0.1", port), Handler).serve_forever()
+"""
+
+
+def fake_fetch(calls):
+    def fetch(addresses):
+        calls.append(list(addresses))
+        return [CHAIN.get(a) for a in addresses]
+
+    return fetch
+
+
+class TestTestValidator:
+    """Test account extraction, clone planning, port allocation and the validator lifecycle."""
+
+    def setup_method(self):
+        self.anchor = load_anchor_config(WORKSPACE)
+
+    def fake_binary(self, tmp_path: Path) -> str:
+        script = tmp_path / "solana-test-validator"
+        script.write_text(f"#!{sys.executable}\n{FAKE_VALIDATOR}")
+        script.chmod(0o755)
+        return str(script)
+
+    def test_extract_addresses(self):
+        assert extract_addresses(POC) == [USDC, JUPITER, "Escr111111111111111111111111111111111111111", MISSING,
+                                          TOKEN]
+        assert extract_addresses('pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4")') == [JUPITER]
+
+    def test_plan_clones(self):
+        calls = []
+        clone_plan = plan_clones(extract_addresses(POC), anchor=self.anchor, fetch=fake_fetch(calls))
+        assert clone_plan.url == "https://api.mainnet-beta.solana.com"
+        assert clone_plan.accounts == [USDC, PYTH_SOL_USD]
+        # Jupiter directly, the Pyth program as owner of the cloned price account
+        assert clone_plan.upgradeable_programs == [JUPITER, PYTH_PROGRAM]
+        assert clone_plan.skipped == {
+            "Escr111111111111111111111111111111111111111": "loaded from the workspace",
+            MISSING: "not found on https://api.mainnet-beta.solana.com",
+            TOKEN: "built in (SPL Token)",
+        }
+        assert calls == [[USDC, JUPITER, MISSING, PYTH_SOL_USD], [PYTH_PROGRAM]]
+        args = clone_plan.args()
+        assert args[:2] == ["--url", "https://api.mainnet-beta.solana.com"]
+        assert ["--clone-upgradeable-program", PYTH_PROGRAM] == args[args.index(PYTH_PROGRAM) - 1:][:2]
+        assert args[-3:] == ["--account", "Cfg1111111111111111111111111111111111111111",
+                             str(WORKSPACE / "accounts" / "config.json")]
+
+        owners_only = plan_clones([PYTH_SOL_USD], url="http://x", fetch=fake_fetch([]), include_owners=False)
+        assert owners_only.accounts == [PYTH_SOL_USD] and owners_only.upgradeable_programs == []
+        with pytest.raises(ValidatorError):
+            plan_clones([USDC], fetch=fake_fetch([]))
+        with pytest.raises(ValidatorError):
+            ClonePlan(accounts=[USDC]).args()
+
+    def test_allocate_ports(self):
+        ports = allocate_ports(dynamic=10)
+        assert ports["faucet"] == ports["rpc"] + 2 and ports["dynamic_end"] - ports["dynamic_start"] == 9
+        for port in (ports["rpc"], ports["rpc"] + 1, ports["gossip"], ports["dynamic_end"]):
+            with socket.socket() as s:
+                s.bind(("127.0.0.1", port))
+        with socket.socket() as busy:
+            busy.bind(("127.0.0.1", 0))
+            taken = busy.getsockname()[1]
+            with patch("extensions.poc.validator.random.Random.randrange", return_value=taken):
+                with pytest.raises(ValidatorError):
+                    allocate_ports(dynamic=1, attempts=3)
+
+    def test_lifecycle(self, tmp_path):
+        binary = self.fake_binary(tmp_path)
+        node = TestValidator(ClonePlan(url="http://x", accounts=[USDC]), binary=binary, startup_timeout=20)
+        with node:
+            assert node.running
+            assert node.rpc_url == f"http://127.0.0.1:{node.ports['rpc']}"
+            assert node.ws_url.endswith(str(node.ports["rpc"] + 1))
+            command = node.command()
+            assert ["--clone", USDC] == command[command.index(USDC) - 1:][:2]
+            assert "--reset" in command and node.log_tail().startswith("fake validator")
+            pid, workdir = node._proc.pid, node.ledger.parent
+        assert not node.running and not workdir.exists()
+        with pytest.raises(ProcessLookupError):
+            os.kill(pid, 0)
+        node.stop()  # idempotent
+
+        # Teardown on an exception inside the block
+        with pytest.raises(RuntimeError):
+            with TestValidator(binary=binary, startup_timeout=20) as node:
+                pid = node._proc.pid
+                raise RuntimeError("PoC failed")
+        with pytest.raises(ProcessLookupError):
+            os.kill(pid, 0)
+        assert signal.getsignal(signal.SIGTERM) is signal.SIG_DFL
+
+    def test_startup_failure(self, tmp_path, monkeypatch):
+        binary = self.fake_binary(tmp_path)
+        monkeypatch.setenv("FAKE_VALIDATOR_FAIL", "1")
+        with patch("extensions.poc.validator.tempfile.mkdtemp", return_value=str(tmp_path / "work")):
+            (tmp_path / "work").mkdir()
+            with pytest.raises(ValidatorError) as excinfo:
+                TestValidator(binary=binary, startup_timeout=20).start()
+        assert "Address already in use" in str(excinfo.value)
+        assert not (tmp_path / "work").exists()
+        with pytest.raises(ValidatorError):
+            TestValidator(binary=str(tmp_path / "missing")).start()
+
+    def test_cli(self, tmp_path):
+        poc = tmp_path / "exploit.ts"
+        poc.write_text(POC)
+        runner = CliRunner()
+        with patch("extensions.poc.validator.fetch_accounts", return_value=fake_fetch([])):
+            result = runner.invoke(plan, [str(WORKSPACE), "--poc", str(poc), "--json"])
+            assert result.exit_code == 0
+            data = json.loads(result.output)
+            assert data["upgradeable_programs"] == [JUPITER, PYTH_PROGRAM]
+            assert data["args"][:2] == ["--url", "https://api.mainnet-beta.solana.com"]
+
+            root = tmp_path / "ws"
+            shutil.copytree(WORKSPACE, root)
+            out = tmp_path / "url.txt"
+            started = time.monotonic()
+            result = runner.invoke(run, [str(root), "--binary", self.fake_binary(tmp_path), "--exec",
+                                         f"{sys.executable} -c \"import os; open('{out}', 'w')"
+                                         f".write(os.environ['ANCHOR_PROVIDER_URL'])\""])
+        assert result.exit_code == 0, result.output
+        assert out.read_text().startswith("http://127.0.0.1:")
+        assert time.monotonic() - started < 30
+
+        project = tmp_path / "project"
+        with patch("commands.validator.resolve_source", return_value=(WORKSPACE, project)):
+            assert runner.invoke(plan, ["p", "--hypothesis", "hyp_1"]).exit_code == 1
+            (project / "poc" / "hyp_1").mkdir(parents=True)
+            (project / "poc" / "hyp_1" / "exploit.ts").write_text(f'const m = "{MISSING}";')
+            (project / "poc" / "hyp_1" / "metadata.json").write_text(json.dumps({"note": USDC}))
+            with patch("extensions.poc.validator.fetch_accounts", return_value=fake_fetch([])):
+                result = runner.invoke(plan, ["p", "--hypothesis", "hyp_1", "--json"])
+        data = json.loads(result.output)
+        assert MISSING in data["skipped"] and USDC not in data["accounts"]
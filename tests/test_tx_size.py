@@ -0,0 +1,99 @@
+"""
+Tests for transaction size and account-count feasibility.
+"""
+
+from pathlib import Path
+
+from click.testing import CliRunner
+
+from commands.ir import tx_size
+from commands.poc import load_alt_harnesses
+from extensions.ir import load_programs
+from extensions.ir.model import AccountField, Argument, Instruction, Program
+from extensions.ir.tx_size import (
+    analyze_transactions,
+    alt_harness,
+    borsh_min_size,
+    compact_u16_len,
+    instruction_footprint,
+    legacy_tx_size,
+)
+
+VAULT = Path(__file__).resolve().parent / "fixtures" / "solana" / "anchor_vault"
+
+
+def _program(n_accounts: int, args: list[Argument] | None = None) -> Program:
+    accounts = [AccountField("authority", "Signer<'info>", is_signer=True, is_mut=True)]
+    accounts += [AccountField(f"market_{i}", "AccountInfo<'info>", is_mut=True) for i in range(n_accounts - 1)]
+    ix = Instruction("rebalance", "rebalance", "src/lib.rs", 10, accounts=accounts, args=args or [])
+    return Program("amm", "solana", "anchor", "/tmp/amm", instructions=[ix])
+
+
+class TestTxSize:
+    """Test footprint computation and classification."""
+
+    def test_wire_sizes(self):
+        assert compact_u16_len(127) == 1
+        assert compact_u16_len(128) == 2
+        # One signature, signer + program id, no accounts, 8-byte discriminator
+        assert legacy_tx_size(1, 2, 0, 8) == 1 + 64 + 3 + 1 + 64 + 32 + 1 + 1 + 1 + 1 + 8
+        assert borsh_min_size("u64") == 8
+        assert borsh_min_size("Vec<Pubkey>") == 4
+        assert borsh_min_size("[u8; 32]") == 32
+        assert borsh_min_size("Option<u64>") == 1
+        assert borsh_min_size("SwapParams") is None
+
+    def test_vault_fits_legacy(self):
+        footprints = analyze_transactions(load_programs(VAULT))
+        assert {f.instruction for f in footprints} == {"initialize", "deposit", "withdraw"}
+        assert all(f.status == "ok" for f in footprints)
+        deposit = next(f for f in footprints if f.instruction == "deposit")
+        assert deposit.data_bytes == 8 + 8
+
+    def test_many_accounts_need_alt(self):
+        program = _program(40, [Argument("amounts", "Vec<u64>"), Argument("params", "SwapParams")])
+        footprint = instruction_footprint(program, program.instructions[0])
+        assert footprint.unique_keys == 41
+        assert footprint.legacy_bytes > 1232
+        assert footprint.v0_bytes <= 1232
+        assert footprint.status == "needs-alt"
+        assert footprint.lookup_tables == 1
+        assert not footprint.data_exact
+        assert footprint.unresolved_args == ["params"]
+
+    def test_account_lock_limit_is_infeasible(self):
+        program = _program(70)
+        assert instruction_footprint(program, program.instructions[0]).status == "infeasible"
+        # Where the 128-lock feature gate is active, it only needs a lookup table
+        assert instruction_footprint(program, program.instructions[0], max_account_locks=128).status == "needs-alt"
+
+    def test_optional_accounts_share_program_key(self):
+        program = _program(3)
+        ix = program.instructions[0]
+        ix.accounts.append(AccountField("referrer", "Option<AccountInfo<'info>>"))
+        footprint = instruction_footprint(program, ix)
+        assert footprint.accounts == 4
+        assert footprint.unique_keys == 4
+
+    def test_alt_harness_for_targeted_instruction(self):
+        program = _program(40)
+        harness = alt_harness(program, program.instructions[0])
+        assert "AddressLookupTableProgram.createLookupTable" in harness
+        assert "compileToV0Message" in harness
+        assert "export async function invokeRebalance" in harness
+        assert "//   - market_0" in harness
+        assert "//   - authority" not in harness
+
+        hypothesis = {"title": "Unchecked oracle in rebalance", "description": ""}
+        assert set(load_alt_harnesses([program], hypothesis)) == {"rebalance"}
+        assert load_alt_harnesses([program], {"title": "Rounding in deposit"}) == {}
+        assert load_alt_harnesses([_program(3)], hypothesis) == {}
+
+    def test_cli(self):
+        runner = CliRunner()
+        result = runner.invoke(tx_size, [str(VAULT), "--all"])
+        assert result.exit_code == 0
+        assert "0 of 3 instructions" in result.output
+        assert runner.invoke(tx_size, [str(VAULT), "--json"]).output.strip() == "[]"
+        # solana-test-validator has every feature gate, including 128 account locks
+        assert "128-account lock limit on localnet" in runner.invoke(tx_size, [str(VAULT), "--cluster", "l"]).output
@@ -0,0 +1,165 @@
+"""
+Tests for the type cosplay (missing discriminator check) detector.
+
+Verifies raw accounts an Anchor handler parses with Borsh past the 8-byte
+discriminator, with `try_deserialize_unchecked` or `try_from_unchecked` are
+flagged when the handler compares no discriminator, that `try_deserialize`
+and explicit discriminator checks are left alone, that native programs only
+count reads skipping the discriminator, that reads through a local alias of
+the account count, and that findings link to the `type_cosplay` PoC
+template.
+"""
+
+from pathlib import Path
+
+from extensions.knowledge.class_loader import ClassLoader
+from extensions.static import StaticAnalysisPipeline, TypeCosplayDetector
+from extensions.static.type_cosplay import TEMPLATE_FILE
+
+
+ROOT = Path(__file__).resolve().parents[1]
+FIXTURES = ROOT / "tests" / "fixtures" / "solana"
+
+SOURCE = """use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn withdraw_fees(ctx: Context<Fees>) -> Result<()> {
+        require_keys_eq!(*ctx.accounts.config.owner, crate::ID);
+        let data = ctx.accounts.config.try_borrow_data()?;
+        let config = Config::try_from_slice(&data[8..])?;
+        require_keys_eq!(config.admin, ctx.accounts.admin.key());
+        Ok(())
+    }
+
+    pub fn sliced(ctx: Context<Fees>) -> Result<()> {
+        let data = ctx.accounts.config.try_borrow_data()?;
+        let body = &data[8..];
+        let config = Config::try_from_slice(body)?;
+        msg!("{}", config.fee_bps);
+        Ok(())
+    }
+
+    pub fn unchecked(ctx: Context<Fees>) -> Result<()> {
+        let config = Config::try_deserialize_unchecked(&mut &ctx.accounts.config.data.borrow()[..])?;
+        let again = Account::<Config>::try_from_unchecked(&ctx.accounts.config)?;
+        msg!("{} {}", config.fee_bps, again.fee_bps);
+        Ok(())
+    }
+
+    pub fn checked(ctx: Context<Fees>) -> Result<()> {
+        let data = ctx.accounts.config.try_borrow_data()?;
+        require!(data[..8] == *Config::DISCRIMINATOR, VaultError::WrongAccountType);
+        let config = Config::try_from_slice(&data[8..])?;
+        msg!("{}", config.fee_bps);
+        Ok(())
+    }
+
+    pub fn anchor_checked(ctx: Context<Fees>) -> Result<()> {
+        let config = Config::try_deserialize(&mut &ctx.accounts.config.data.borrow()[..])?;
+        msg!("{}", config.fee_bps);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Fees<'info> {
+    /// CHECK: parsed in the handler
+    pub config: AccountInfo<'info>,
+    pub admin: Signer<'info>,
+}
+"""
+
+
+ALIASED = """use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn read_config(ctx: Context<ReadConfig>) -> Result<()> {
+        let info = &ctx.accounts.cfg;
+        let data = info.try_borrow_data()?;
+        let config = Config::try_from_slice(&data[8..])?;
+        msg!("{}", config.fee);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ReadConfig<'info> {
+    /// CHECK: parsed by hand
+    #[account(owner = crate::ID)]
+    pub cfg: AccountInfo<'info>,
+}
+
+#[account]
+pub struct Config {
+    pub fee: u64,
+}
+"""
+
+
+class TestTypeCosplay:
+    """Test the detector against an inline Anchor program and the fixtures."""
+
+    def test_findings(self, tmp_path, anchor_workspace):
+        findings, metadata = TypeCosplayDetector().run(anchor_workspace(tmp_path, {"vault": SOURCE}))
+        assert metadata["instructions_checked"] == 5
+        flagged = [(f.instruction, f.account, f.state_type, f.call, f.skipped, f.line) for f in findings]
+        assert flagged == [
+            ("withdraw_fees", "config", "Config", "try_from_slice", True,
+             anchor_workspace.line(SOURCE, "Config::try_from_slice(&data")),
+            ("sliced", "config", "Config", "try_from_slice", True,
+             anchor_workspace.line(SOURCE, "Config::try_from_slice(body)")),
+            ("unchecked", "config", "Config", "try_deserialize_unchecked", False,
+             anchor_workspace.line(SOURCE, "Config::try_deserialize_unchecked")),
+        ]
+        assert findings[0].path == "programs/vault/src/lib.rs"
+        assert findings[0].text == "let config = Config::try_from_slice(&data[8..])?"
+
+    def test_alias(self, tmp_path, anchor_workspace):
+        # The owner is checked, but any account of the program parses as `Config` through `info`
+        findings, _ = TypeCosplayDetector().run(anchor_workspace(tmp_path, {"vault": ALIASED}))
+        assert [(f.instruction, f.account, f.call, f.skipped) for f in findings] == [
+            ("read_config", "cfg", "try_from_slice", True)]
+        assert findings[0].text == "let config = Config::try_from_slice(&data[8..])?"
+
+        checked = ALIASED.replace("let config =", "require!(data[..8] == Config::DISCRIMINATOR, E::Type);\n"
+                                  "        let config =")
+        assert TypeCosplayDetector().run(anchor_workspace(tmp_path / "checked", {"vault": checked}))[0] == []
+
+    def test_fixtures(self):
+        findings, _ = TypeCosplayDetector().run(FIXTURES / "order_book")
+        assert [(f.instruction, f.account, f.state_type, f.line) for f in findings] == [
+            ("replay", "history", "History", 29)]
+        # Native reads from offset 0 have no Anchor discriminator to skip
+        findings, _ = TypeCosplayDetector().run(FIXTURES / "zero_copy_pool")
+        assert findings == []
+
+    def test_hypothesis(self, tmp_path, anchor_workspace):
+        findings, _ = TypeCosplayDetector().run(anchor_workspace(tmp_path, {"vault": SOURCE}))
+        hyp = findings[0].to_hypothesis()
+        assert hyp["title"] == "Type cosplay: `config` parsed without a discriminator check in withdraw_fees"
+        assert hyp["severity"] == "high" and hyp["confidence"] == 0.6
+        assert "skipping the 8-byte discriminator without comparing it" in hyp["description"]
+        assert "with `try_deserialize_unchecked`, which does not check" in findings[2].to_hypothesis()["description"]
+        props = hyp["properties"]
+        assert props["source_tool"] == "type-cosplay" and props["skips_discriminator"] is True
+        assert (ROOT / props["poc_template_file"]).exists() and props["poc_template_file"] == TEMPLATE_FILE
+        vuln_class = ClassLoader().resolve(hyp["vulnerability_type"], "solana")
+        assert vuln_class.id == "account-type-confusion"
+        assert "type_cosplay" in vuln_class.variant("solana").templates
+
+    def test_pipeline(self, tmp_path, anchor_workspace):
+        pipeline = StaticAnalysisPipeline(chain_id="solana")
+        assert "type-cosplay" in pipeline.check_tools()
+        result = pipeline.run(anchor_workspace(tmp_path, {"vault": SOURCE}), tools=["type-cosplay"])
+        assert len(result.hypotheses) == 3
+        assert all(h["id"].startswith("static_type-cosplay_") for h in result.hypotheses)
@@ -0,0 +1,116 @@
+"""
+Tests for the unchecked balance arithmetic detector.
+
+Verifies wrapping `+`, `-` and `*` on balance and lamport fields of an
+instruction's accounts are flagged with the matching `checked_*` fix, that
+guarded subtractions, checked calls and non-balance fields are left alone,
+that `overflow-checks` lowers the severity, and that findings link to the
+`unchecked_arithmetic` PoC template.
+"""
+
+from pathlib import Path
+
+from extensions.knowledge.class_loader import ClassLoader
+from extensions.static import StaticAnalysisPipeline, UncheckedArithmeticDetector
+from extensions.static.unchecked_math import TEMPLATE_FILE
+
+
+ROOT = Path(__file__).resolve().parents[1]
+
+SOURCE = """use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.balance -= amount;
+        **ctx.accounts.vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.owner.try_borrow_mut_lamports()? += amount;
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        ctx.accounts.vault.total_deposits = ctx.accounts.vault.total_deposits + amount;
+        ctx.accounts.vault.counter += 1;
+        Ok(())
+    }
+
+    pub fn safe_withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        require!(ctx.accounts.vault.balance >= amount, VaultError::InsufficientFunds);
+        ctx.accounts.vault.balance -= amount;
+        Ok(())
+    }
+
+    pub fn checked_withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_sub(amount).ok_or(VaultError::InsufficientFunds)?;
+        Ok(())
+    }
+
+    pub fn mint_shares(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let minted = amount * ctx.accounts.vault.total_shares;
+        ctx.accounts.vault.shares = minted;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, has_one = owner)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+"""
+
+
+class TestUncheckedMath:
+    """Test the detector against an inline Anchor program."""
+
+    def test_findings(self, tmp_path, anchor_workspace):
+        findings, metadata = UncheckedArithmeticDetector().run(anchor_workspace(tmp_path, {"vault": SOURCE}))
+        assert metadata["instructions_checked"] == 5
+        flagged = [(f.instruction, f.target, f.op, f.line) for f in findings]
+        assert flagged == [
+            ("withdraw", "vault.balance", "-", anchor_workspace.line(SOURCE, "vault.balance -= amount")),
+            ("withdraw", "vault.lamports", "-", anchor_workspace.line(SOURCE, "**ctx.accounts.vault")),
+            ("deposit", "vault.total_deposits", "+",
+             anchor_workspace.line(SOURCE, "ctx.accounts.vault.total_deposits =")),
+            ("mint_shares", "vault.total_shares", "*", anchor_workspace.line(SOURCE, "let minted")),
+        ]
+        withdraw = findings[0]
+        assert withdraw.path == "programs/vault/src/lib.rs" and withdraw.severity == "high"
+        assert withdraw.fix == "vault.balance = vault.balance.checked_sub(..).ok_or(ErrorCode::MathOverflow)?;"
+        assert findings[2].severity == "medium" and findings[2].text.endswith("total_deposits + amount")
+
+    def test_hypothesis(self, tmp_path, anchor_workspace):
+        findings, _ = UncheckedArithmeticDetector().run(anchor_workspace(tmp_path, {"vault": SOURCE}))
+        hyp = findings[0].to_hypothesis()
+        assert hyp["title"] == "Unchecked `-` on `vault.balance` in withdraw"
+        assert "Use `checked_sub`" in hyp["description"] and "kb render unchecked_arithmetic" in hyp["description"]
+        props = hyp["properties"]
+        assert props["source_tool"] == "unchecked-math" and props["poc_template"] == "unchecked_arithmetic"
+        assert (ROOT / props["poc_template_file"]).exists() and props["poc_template_file"] == TEMPLATE_FILE
+        vuln_class = ClassLoader().resolve(hyp["vulnerability_type"], "solana")
+        assert vuln_class.id == "unchecked-arithmetic"
+        assert vuln_class.variant("solana").templates == ["unchecked_arithmetic"]
+
+    def test_overflow_checks(self, tmp_path, anchor_workspace):
+        anchor_workspace(tmp_path, {"vault": SOURCE})
+        (tmp_path / "Cargo.toml").write_text('[workspace]\nmembers = ["programs/*"]\n\n'
+                                             '[profile.release]\noverflow-checks = true\n')
+        findings, _ = UncheckedArithmeticDetector().run(tmp_path)
+        assert findings and all(f.severity == "low" and f.overflow_checks for f in findings)
+        skipped, _ = UncheckedArithmeticDetector(include_overflow_checked=False).run(tmp_path)
+        assert skipped == []
+
+    def test_pipeline(self, tmp_path, anchor_workspace):
+        pipeline = StaticAnalysisPipeline(chain_id="solana")
+        assert "unchecked-math" in pipeline.check_tools()
+        result = pipeline.run(anchor_workspace(tmp_path, {"vault": SOURCE}), tools=["unchecked-math"])
+        assert len(result.hypotheses) == 4
+        assert all(h["id"].startswith("static_unchecked-math_") for h in result.hypotheses)
@@ -0,0 +1,83 @@
+"""
+Tests for the built-in unsafe Rust auditor.
+"""
+
+from pathlib import Path
+
+from extensions.static.pipeline import StaticAnalysisPipeline
+from extensions.static.unsafe_rust import UnsafeRustAuditor
+
+FIXTURE = Path(__file__).parent / "fixtures" / "solana" / "zero_copy_pool"
+
+
+class TestUnsafeRustAuditor:
+    """Test inventory, taint tracking and bounds-check detection."""
+
+    def setup_method(self):
+        self.findings, self.metadata = UnsafeRustAuditor().run(FIXTURE)
+        self.by_kind = {f.kind: f for f in self.findings}
+
+    def test_inventory(self):
+        counts = self.metadata["counts"]
+        assert self.metadata["files_scanned"] == 2
+        # The #[cfg(test)] module's unsafe block is not counted
+        assert counts["unsafe_block"] == 4
+        assert counts["unsafe_fn"] == 1
+        assert counts["unsafe_impl"] == 1
+        unsafe_fn = next(s for s in self.metadata["inventory"] if s["kind"] == "unsafe_fn")
+        assert unsafe_fn["function"] == "header_from"
+        # `seed.wrapping_add(7)` outside unsafe is not pointer arithmetic
+        assert not any(s["function"] == "checksum" for s in self.metadata["inventory"])
+
+    def test_unchecked_length_from_instruction_data(self):
+        finding = self.by_kind["raw-slice-length"]
+        assert finding.file_path == "src/lib.rs"
+        assert finding.tainted == ["len"]
+        assert finding.sources == ["instruction data"]
+        assert not finding.guarded
+        hyp = finding.to_hypothesis()
+        assert hyp["severity"] == "high"
+        assert hyp["vulnerability_type"] == "memory-safety-raw-slice-length"
+        assert hyp["properties"]["category"] == "memory-safety"
+        assert hyp["properties"]["affected_lines"] == [28]
+
+    def test_bounds_checked_offset_is_low(self):
+        finding = self.by_kind["pointer-offset"]
+        assert finding.tainted == ["offset"]
+        assert finding.guarded
+        assert finding.to_hypothesis()["severity"] == "low"
+
+    def test_reinterpreted_account_data(self):
+        cast = self.by_kind["raw-pointer-cast"]
+        assert cast.sources == ["account data"]
+        # The offset check does not cover the header's size
+        assert not cast.guarded
+        assert cast.severity == "medium"
+        transmute = self.by_kind["transmute"]
+        assert transmute.function == "header_from"
+        assert transmute.sources == ["parameter `bytes`"]
+
+    def test_untainted_sinks_only_on_request(self):
+        assert all(f.tainted for f in self.findings)
+        findings, _ = UnsafeRustAuditor(report_untainted=True).run(FIXTURE)
+        constant = [f for f in findings if f.function == "constant_table"]
+        assert len(constant) == 1
+        assert constant[0].severity == "info"
+
+    def test_size_check_guards_cast(self):
+        source = """
+pub fn load(data: &[u8]) -> &Header {
+    if data.len() < std::mem::size_of::<Header>() {
+        panic!("short");
+    }
+    unsafe { &*(data.as_ptr() as *const Header) }
+}
+"""
+        _, findings = UnsafeRustAuditor().audit_source(source, "src/lib.rs")
+        assert len(findings) == 1
+        assert findings[0].guarded
+
+    def test_registered_for_solana(self):
+        pipeline = StaticAnalysisPipeline(chain_id="solana")
+        assert "unsafe-rust" in pipeline.runners
+        assert pipeline.check_tools()["unsafe-rust"][0]
@@ -0,0 +1,105 @@
+"""
+Tests for the user's own template roots: loading them after the built-in
+templates, which template wins an id, and `--template-dir` / config /
+BASKERVILLE_TEMPLATE_DIRS.
+"""
+
+import json
+import os
+
+import pytest
+from click.testing import CliRunner
+
+from commands.knowledge import kb
+from extensions.knowledge import KnowledgeBase, TemplateLoader, TemplateRegistry, user_template_dirs
+from extensions.knowledge.template_loader import TEMPLATE_DIRS_ENV
+
+ORACLE = """// ---
+// name: Oracle Staleness
+// description: Price read without checking its publish time
+// vulnerability_type: oracle-staleness
+// chain: solana
+// severity: high
+// ---
+// PoC Template: Oracle Staleness (Acme)
+use anchor_lang::prelude::*;
+// Program: {{PROGRAM_ID}}
+"""
+
+SIGNER = """// ---
+// name: Missing Signer Check (Acme)
+// vulnerability_type: missing-signer
+// chain: solana
+// severity: critical
+// ---
+// Acme's {{NAME}} version: program {{PROGRAM_ID}}
+"""
+
+
+def _root(path, files):
+    (path / "solana").mkdir(parents=True)
+    for name, content in files.items():
+        (path / "solana" / name).write_text(content)
+    return path
+
+
+class TestUserTemplateDirs:
+    """Test merging private PoC template roots into the built-in registry."""
+
+    def test_merge(self, tmp_path):
+        first = _root(tmp_path / "acme", {"oracle_staleness.rs": ORACLE, "missing_signer.rs": SIGNER})
+        second = _root(tmp_path / "team", {"missing_signer.rs": SIGNER.replace("Acme", "Team")})
+        loader = TemplateLoader(user_dirs=[first, second])
+
+        oracle = loader.get("oracle_staleness")
+        assert oracle.origin == "user" and oracle.shadows == [] and oracle.severity == "high"
+        # Built-in, then each root in order: the last to define an id wins and records what it replaced
+        signer = loader.get("missing_signer")
+        assert signer.origin == "user" and "Team" in signer.name and signer.severity == "critical"
+        assert [p.rsplit(os.sep, 3)[-3] for p in signer.shadows] == ["templates", "acme"]
+        assert signer.metadata()["shadows"] == signer.shadows
+        # Untouched built-ins stay
+        assert loader.get("type_cosplay").origin == "builtin"
+
+        registry = TemplateRegistry(loader)
+        assert {t.id for t in registry.query(origin="user")} == {"oracle_staleness", "missing_signer"}
+        with pytest.raises(ValueError, match="Unknown origin"):
+            registry.query(origin="vendor")
+        assert KnowledgeBase(template_dirs=[first]).templates.get("oracle_staleness") is not None
+
+    def test_sources(self, tmp_path, monkeypatch):
+        config_dir = _root(tmp_path / "config", {})
+        env_dir = _root(tmp_path / "env", {})
+        cli_dir = _root(tmp_path / "cli", {})
+        monkeypatch.setenv(TEMPLATE_DIRS_ENV, os.pathsep.join([str(env_dir), str(tmp_path / "gone")]))
+        config = {"knowledge": {"template_dirs": [str(config_dir), str(cli_dir)]}}
+
+        # Lowest precedence first; a root listed twice keeps its last place; missing configured roots are skipped
+        assert user_template_dirs([cli_dir], config) == [config_dir.resolve(), env_dir.resolve(), cli_dir.resolve()]
+        with pytest.raises(ValueError, match="does not exist"):
+            user_template_dirs([tmp_path / "typo"])
+
+        monkeypatch.setenv("HOME", str(tmp_path))
+        assert user_template_dirs(config={"knowledge": {"template_dirs": "~/config"}})[0] == config_dir.resolve()
+
+    def test_cli(self, tmp_path, monkeypatch):
+        root = _root(tmp_path / "acme", {"oracle_staleness.rs": ORACLE, "missing_signer.rs": SIGNER})
+        monkeypatch.delenv(TEMPLATE_DIRS_ENV, raising=False)
+        runner = CliRunner()
+
+        result = runner.invoke(kb, ["templates", "--template-dir", str(root), "--origin", "user", "--json"])
+        assert result.exit_code == 0, result.output
+        listed = {t["id"]: t for t in json.loads(result.output)}
+        assert set(listed) == {"oracle_staleness", "missing_signer"} and listed["missing_signer"]["shadows"]
+        result = runner.invoke(kb, ["templates", "--template-dir", str(root), "-c", "solana"])
+        assert "user (overrides" in result.output
+
+        result = runner.invoke(kb, ["render", "oracle_staleness", "-s", "PROGRAM_ID=11111111111111111111111111111111"])
+        assert result.exit_code == 1 and "Unknown template" in result.output
+        monkeypatch.setenv(TEMPLATE_DIRS_ENV, str(root))
+        result = runner.invoke(kb, ["render", "oracle_staleness", "-s", "PROGRAM_ID=11111111111111111111111111111111"])
+        assert result.exit_code == 0, result.output
+        assert "// Program: 11111111111111111111111111111111" in result.output
+
+        result = runner.invoke(kb, ["templates", "--template-dir", str(tmp_path / "typo")])
+        assert result.exit_code != 0 and "does not exist" in result.output
@@ -0,0 +1,135 @@
+"""
+Tests for chain-agnostic vulnerability classes: loading, resolving
+vulnerability types, templates hanging off classes, KB context and reports.
+"""
+
+import json
+import unittest.mock
+from pathlib import Path
+
+import pytest
+from click.testing import CliRunner
+
+from commands.knowledge import list_classes, show_class
+from extensions.knowledge import ChecklistLoader, ClassLoader, KnowledgeBase, TemplateLoader
+
+
+KB_DIR = Path(__file__).resolve().parents[1] / "extensions" / "knowledge"
+
+
+@pytest.fixture
+def no_solodit(monkeypatch):
+    """Use only the bundled YAML checklists (no Solodit cache or fetch)."""
+    monkeypatch.setattr(ChecklistLoader, "_load_cached_solodit", lambda self: [])
+
+
+class TestClassLoader:
+    """Test class loading and vulnerability-type resolution."""
+
+    def setup_method(self):
+        self.loader = ClassLoader(KB_DIR / "classes")
+
+    def test_bundled_classes(self):
+        classes = self.loader.list_all()
+        assert len(classes) >= 8
+        for vuln_class in classes:
+            assert vuln_class.description and vuln_class.fix, vuln_class.id
+            assert vuln_class.variants, vuln_class.id
+
+    def test_resolve_across_chains(self):
+        for name, chain in [("missing_signer", "solana"), ("missing-modifier", "evm"),
+                            ("missing capability", "sui")]:
+            vuln_class = self.loader.resolve(name, chain)
+            assert vuln_class and vuln_class.id == "unauthenticated-privileged-action", name
+        assert self.loader.resolve("unauthenticated-privileged-action").name == "Unauthenticated privileged action"
+        assert self.loader.resolve("definitely-not-a-type") is None
+        assert self.loader.resolve("") is None
+
+    def test_chain_specific_guidance(self):
+        vuln_class = self.loader.get("unauthenticated-privileged-action")
+        assert "Signer<'info>" in vuln_class.fix_for("solana")
+        assert "onlyOwner" in vuln_class.fix_for("EVM")
+        assert vuln_class.fix_for("aptos") == vuln_class.fix
+        assert "On solana:" in vuln_class.describe("solana")
+        assert vuln_class.describe(None) == vuln_class.description
+
+    def test_invalid_file_is_skipped(self, tmp_path, capsys):
+        (tmp_path / "bad.yaml").write_text("classes:\n  - name: no id\n")
+        (tmp_path / "ok.yaml").write_text(
+            "classes:\n  - id: x\n    name: X\n    variants:\n      evm: {name: E, aliases: [Foo_Bar]}\n")
+        loader = ClassLoader(tmp_path)
+        assert [c.id for c in loader.list_all()] == ["x"]
+        assert loader.resolve("foo bar").id == "x"
+        assert "Failed to load" in capsys.readouterr().out
+
+
+class TestTemplatesHangOffClasses:
+    """Test that every chain's templates resolve to a class."""
+
+    def test_templates_have_classes(self):
+        loader = TemplateLoader(KB_DIR / "templates")
+        for template in loader.list_all():
+            assert template.vuln_class, template.id
+        signer = loader.get_by_class("unauthenticated-privileged-action")
+        assert {t.chain for t in signer} == {"cosmwasm", "evm", "solana", "sui"}
+        assert [t.id for t in loader.get_by_class("unauthenticated-privileged-action", "solana")] == ["missing_signer"]
+
+    def test_kb_reuses_templates_across_chains(self, no_solodit):
+        kb = KnowledgeBase(KB_DIR)
+        # No Solana template for this class, so EVM ones are offered
+        assert {t.id for t in kb.templates_for_class("share-inflation", "solana")} == {"inflation_attack"}
+        assert kb.get_poc_template("missing-modifier") is not None
+        result = kb.query("missing_signer", chain="solana")
+        assert [c.id for c in result.classes] == ["unauthenticated-privileged-action"]
+        assert "missing_signer" in [t.id for t in result.templates]
+        ctx = result.to_context()
+        assert "## Vulnerability Class: Unauthenticated privileged action" in ctx
+        assert "Missing onlyOwner / role modifier (evm)" in ctx
+        assert kb.stats()["classes"] == len(kb.classes.list_all())
+
+
+class TestClassCli:
+    """Test the classes and class commands."""
+
+    def test_list_and_show(self, no_solodit):
+        runner = CliRunner()
+        result = runner.invoke(list_classes, ["--chain", "solana"])
+        assert result.exit_code == 0, result.output
+        assert "unauthenticated-privileged-action" in result.output
+        assert "missing_signer" in result.output
+
+        result = runner.invoke(show_class, ["missing_signer", "--chain", "solana"])
+        assert result.exit_code == 0, result.output
+        assert "Unauthenticated privileged action" in result.output
+        for chain in ("solana", "evm", "sui"):
+            assert chain in result.output
+        assert "No vulnerability class" in runner.invoke(show_class, ["nope"]).output
+
+
+class TestReportClasses:
+    """Test findings carry their class and its chain-specific fix guidance."""
+
+    def test_markdown_recommendation(self, tmp_path):
+        from analysis.report_generator import ReportGenerator
+
+        (tmp_path / "project.json").write_text(json.dumps({"chain_id": "solana"}))
+        (tmp_path / "hypotheses.json").write_text(json.dumps({"hypotheses": {
+            "h1": {"title": "Withdraw authority never signs", "vulnerability_type": "missing_signer",
+                   "status": "confirmed", "severity": "high", "description": "d"},
+            "h2": {"title": "Odd event", "vulnerability_type": "events", "status": "confirmed",
+                   "severity": "low", "description": "d"},
+        }}))
+        with unittest.mock.patch("analysis.report_generator.UnifiedLLMClient"):
+            rg = ReportGenerator(tmp_path, {})
+        assert rg.chain == "solana"
+        with unittest.mock.patch.object(rg, "_batch_generate_vulnerability_descriptions", return_value={}), \
+                unittest.mock.patch.object(rg, "_extract_code_for_finding", return_value=[]):
+            findings = rg._get_confirmed_findings()
+        by_id = {f["id"]: f for f in findings}
+        assert by_id["h1"]["vuln_class"] == "unauthenticated-privileged-action"
+        assert by_id["h2"]["vuln_class"] is None
+        md = rg._format_findings_markdown(findings)
+        assert "**Class:** Unauthenticated privileged action" in md
+        assert "**Recommendation:** Declare the authority as Signer<'info>" in md
+        html = rg._format_findings_html(findings)
+        assert "<strong>Class:</strong> Unauthenticated privileged action" in html
@@ -0,0 +1,91 @@
+"""
+Tests for the Vyper front-end (`vyper -f ast` output → IR).
+
+Uses a saved AST for tests/fixtures/evm/vyper_vault/Vault.vy so the tests
+don't need a vyper binary.
+"""
+
+import json
+from pathlib import Path
+
+from extensions.ir import VyperFrontend
+
+
+PROJECT = Path(__file__).resolve().parent / "fixtures" / "evm" / "vyper_vault"
+AST_OUTPUT = PROJECT / "Vault.ast.json"
+
+
+class TestVyperLowering:
+    """Test lowering of functions, decorators, calls, and storage."""
+
+    def setup_method(self):
+        frontend = VyperFrontend()
+        programs = frontend.load(AST_OUTPUT)
+        assert frontend.errors == []
+        assert len(programs) == 1
+        self.program = programs[0]
+
+    def test_program_metadata(self):
+        assert self.program.name == "Vault"
+        assert self.program.chain == "evm"
+        assert self.program.framework == "vyper"
+        assert self.program.source_files == ["Vault.vy"]
+
+    def test_external_functions_only(self):
+        names = [ix.name for ix in self.program.instructions]
+        assert names == ["__init__", "deposit", "withdraw", "withdraw_safe", "quote", "set_owner", "__default__"]
+        assert self.program.instruction("__init__").properties["kind"] == "constructor"
+        assert self.program.instruction("__default__").properties["kind"] == "fallback"
+
+    def test_decorators_become_modifiers(self):
+        assert self.program.instruction("withdraw_safe").modifiers == ["nonreentrant"]
+        deposit = self.program.instruction("deposit")
+        assert deposit.properties["state_mutability"] == "payable"
+        assert deposit.visibility == "external"
+
+    def test_args_and_body(self):
+        set_owner = self.program.instruction("set_owner")
+        assert [(a.name, a.ty) for a in set_owner.args] == [("new_owner", "address")]
+        assert set_owner.body.startswith("assert msg.sender == self.owner")
+        assert set_owner.properties["signature"] == "set_owner(address)"
+
+    def test_calls(self):
+        withdraw = self.program.instruction("withdraw")
+        assert [(c.kind, c.target, c.line) for c in withdraw.calls] == [("call", "msg.sender", 26)]
+        safe = self.program.instruction("withdraw_safe")
+        assert [c.kind for c in safe.calls] == ["send"]
+        quote = self.program.instruction("quote")
+        assert [(c.kind, c.target) for c in quote.calls] == [("external", "self.oracle")]
+
+    def test_storage(self):
+        storage = self.program.account_type("Vault")
+        assert [(f.name, f.ty) for f in storage.fields] == [
+            ("owner", "address"),
+            ("oracle", "IOracle"),
+            ("balances", "HashMap[address, uint256]"),
+        ]
+        assert storage.properties["constants"] == ["FEE_BPS"]
+
+
+class TestVyperInputs:
+    """Test input handling."""
+
+    def test_method_identifiers_become_discriminators(self, tmp_path):
+        data = json.loads(AST_OUTPUT.read_text())
+        data["method_identifiers"] = {"withdraw(uint256)": "0x2e1a7d4d"}
+        (tmp_path / "Vault.vy").write_text((PROJECT / "Vault.vy").read_text())
+        saved = tmp_path / "Vault.ast.json"
+        saved.write_text(json.dumps(data))
+
+        program = VyperFrontend().load(saved)[0]
+        assert program.instruction("withdraw").discriminator == "2e1a7d4d"
+        assert program.instruction("deposit").discriminator is None
+
+    def test_source_discovery_and_missing_compiler(self, tmp_path):
+        for rel in ("contracts/Pool.vy", "tests/Mock.vy", "lib/dep/Dep.vy"):
+            (tmp_path / rel).parent.mkdir(parents=True, exist_ok=True)
+            (tmp_path / rel).write_text("# @version ^0.3.10\n")
+        frontend = VyperFrontend(vyper=str(tmp_path / "no-such-vyper"))
+        assert frontend.source_files(tmp_path) == ["contracts/Pool.vy"]
+        assert frontend.load(tmp_path) == []
+        assert len(frontend.errors) == 1
@@ -0,0 +1,117 @@
+"""
+Tests for suppressions that live with the code: `baskerville:allow(...)`
+comments honored by the pipeline, and the workspace's
+.baskerville-baseline.json of accepted findings that `scan` hides.
+"""
+
+import json
+
+from click.testing import CliRunner
+
+from commands.scan import scan
+from extensions.static import StaticAnalysisPipeline
+from extensions.static.suppressions import BASELINE_FILE, WorkspaceBaseline, allowed_at, allows
+
+SOURCE = """use anchor_lang::prelude::*;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn set_fee(ctx: Context<SetFee>, fee: u64) -> Result<()> {
+        ctx.accounts.config.fee = fee;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetFee<'info> {
+    #[account(mut, has_one = admin)]
+    pub config: Account<'info, Config>,
+    /// CHECK: config admin
+    pub admin: AccountInfo<'info>,
+}
+"""
+
+HIT = {"title": "Missing signer check", "vulnerability_type": "missing-signer-check",
+       "properties": {"source_tool": "missing-signer", "source_files": ["programs/vault/src/lib.rs"],
+                      "affected_lines": [18]}}
+
+
+# Anchor.toml's id for the vault
+LOCALNET = {"vault": "11111111111111111111111111111111"}
+
+
+class TestWorkspaceSuppressions:
+    """Test inline allow comments and the workspace baseline."""
+
+    def test_allow_comments(self):
+        # Short code, full code, rule id and detector name all cover the hit
+        for token in ("SOL-001", "sol-001-missing-signer", "missing-signer/missing-signer-check", "missing-signer"):
+            assert allows(token, HIT), token
+        assert not allows("SOL-002", HIT) and not allows("SOL-00", HIT)
+
+        lines = ["// baskerville:allow(SOL-009)", "fn f() {}", "/// CHECK: admin",
+                 "// baskerville:allow(SOL-002, SOL-001)", "#[account(mut)]",
+                 "pub admin: AccountInfo<'info>,  // baskerville:allow(missing-owner)"]
+        # Comments and attributes up to the previous line of code count; SOL-009 is fn f's
+        assert allowed_at(lines, 6) == ["missing-owner", "SOL-002", "SOL-001"]
+        assert allowed_at(lines, 2) == ["SOL-009"] and allowed_at(lines, 99) == []
+
+    def test_pipeline_honors_allow(self, tmp_path, anchor_workspace):
+        workspace = anchor_workspace(tmp_path / "vault", {"vault": SOURCE}, localnet=LOCALNET)
+        pipeline = StaticAnalysisPipeline(chain_id="solana")
+        assert len(pipeline.run(workspace, tools=["missing-signer"]).hypotheses) == 1
+
+        allowed = SOURCE.replace("    /// CHECK: config admin\n",
+                                 "    /// CHECK: config admin\n    // baskerville:allow(SOL-001) admin is a PDA\n")
+        anchor_workspace(tmp_path / "vault", {"vault": allowed}, localnet=LOCALNET)
+        result = pipeline.run(workspace, tools=["missing-signer"])
+        assert result.hypotheses == [] and len(result.suppressed) == 1
+        assert result.metadata["inline_suppressed"] == 1
+        shown = StaticAnalysisPipeline(chain_id="solana", inline_suppressions=False).run(workspace,
+                                                                                         tools=["missing-signer"])
+        assert len(shown.hypotheses) == 1
+
+    def test_baseline_file(self, tmp_path):
+        workspace = tmp_path / "ws"
+        workspace.mkdir()
+        absolute = {**HIT, "properties": {**HIT["properties"],
+                                          "source_files": [str(workspace / "programs/vault/src/lib.rs")]}}
+        baseline = WorkspaceBaseline(workspace)
+        assert baseline.accept([absolute, HIT]) == 1
+        baseline.save()
+
+        data = json.loads((workspace / BASELINE_FILE).read_text())
+        assert data["format"] == "baskerville-baseline"
+        (entry,) = data["findings"]
+        assert (entry["rule_id"], entry["file"]) == ("SOL-001-MISSING-SIGNER", "programs/vault/src/lib.rs")
+
+        loaded = WorkspaceBaseline(workspace).load()
+        moved = {**HIT, "properties": {**HIT["properties"], "affected_lines": [40]}}
+        other = {**HIT, "title": "Missing signer check on `owner`"}
+        new, baselined = loaded.apply([moved, other])
+        # Moving lines keeps the fingerprint; a different title is a new finding
+        assert (new, baselined) == ([other], [moved])
+        assert loaded.stale([other]) == loaded.entries
+
+    def test_scan(self, tmp_path, anchor_workspace):
+        workspace = anchor_workspace(tmp_path / "vault", {"vault": SOURCE}, localnet=LOCALNET)
+        runner = CliRunner()
+        args = [str(workspace), "--tool", "missing-signer", "--json"]
+        first = json.loads(runner.invoke(scan, args).output)
+        assert len(first["hypotheses"]) == 1 and first["baselined"] == 0
+
+        result = runner.invoke(scan, [str(workspace), "--tool", "missing-signer", "--update-baseline"])
+        assert result.exit_code == 0, result.output
+        assert "Baselined 1 hits" in result.output and "No hits" in result.output
+        assert (workspace / BASELINE_FILE).exists()
+
+        again = json.loads(runner.invoke(scan, args).output)
+        assert again["hypotheses"] == [] and again["baselined"] == 1
+        shown = json.loads(runner.invoke(scan, [*args, "--show-suppressed"]).output)
+        assert len(shown["hypotheses"]) == 1
+
+        (workspace / BASELINE_FILE).write_text("[]")
+        result = runner.invoke(scan, [str(workspace), "--tool", "missing-signer"])
+        assert result.exit_code == 0 and "Baseline not applied" in result.output